@@ -0,0 +1,423 @@
+//! Conversion defaults kept in a TOML file: `office2pdf.toml` in the working
+//! directory, or the file `--config` names.
+//!
+//! Keys are the long flag names without their dashes. Top-level keys apply
+//! to every input; `[profiles.<name>]` tables apply with `--profile <name>`,
+//! and `[files."<glob>"]` tables to the inputs matching the pattern, in the
+//! order the file lists them. Flags on the command line win over all of
+//! them:
+//!
+//! ```toml
+//! paper = "a4"
+//! font-path = ["fonts"]
+//!
+//! [profiles.archive]
+//! pdf-standard = "a-2b"
+//!
+//! [files."*.xlsx"]
+//! sheets = ["Summary"]
+//! gridlines = true
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use office2pdf::config::{
+    ConvertOptions, EffectFidelity, PaperSize, PdfStandard, SlideAspect, SlideRange, SlideScaleMode,
+};
+use serde::Deserialize;
+
+use crate::inputs::Pattern;
+
+/// The file looked for in the working directory.
+pub(crate) const CONFIG_FILE: &str = "office2pdf.toml";
+
+/// Conversion options by flag name, each layer of them overriding the one
+/// before. Settings left unset leave the layer below be.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct Settings {
+    pub(crate) sheets: Option<Vec<String>>,
+    pub(crate) ranges: Option<Vec<String>>,
+    pub(crate) include_hidden: Option<bool>,
+    pub(crate) gridlines: Option<bool>,
+    pub(crate) headings: Option<bool>,
+    pub(crate) ignore_frozen_panes: Option<bool>,
+    pub(crate) row_label_columns: Option<u32>,
+    pub(crate) label_column_pages: Option<bool>,
+    pub(crate) redact_hidden: Option<bool>,
+    pub(crate) blank_errors: Option<bool>,
+    pub(crate) annotate_dropdowns: Option<bool>,
+    pub(crate) locale: Option<String>,
+    pub(crate) slides: Option<String>,
+    pub(crate) sections: Option<Vec<String>>,
+    pub(crate) pdf_a: Option<bool>,
+    pub(crate) pdf_standard: Option<String>,
+    pub(crate) embed_source: Option<bool>,
+    pub(crate) linearize: Option<bool>,
+    pub(crate) compact: Option<bool>,
+    pub(crate) paper: Option<String>,
+    pub(crate) font_path: Option<Vec<PathBuf>>,
+    pub(crate) landscape: Option<bool>,
+    pub(crate) tagged: Option<bool>,
+    pub(crate) pdf_ua: Option<bool>,
+    pub(crate) streaming: Option<bool>,
+    pub(crate) streaming_chunk_size: Option<usize>,
+    pub(crate) effects: Option<String>,
+    pub(crate) slide_size: Option<String>,
+    pub(crate) slide_scale: Option<String>,
+    pub(crate) timeout: Option<u64>,
+    pub(crate) max_input_size: Option<u64>,
+}
+
+/// A configuration file's settings.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Config {
+    pub(crate) defaults: Settings,
+    pub(crate) profiles: BTreeMap<String, Settings>,
+    /// Glob patterns with the settings of the inputs matching them.
+    pub(crate) files: Vec<(Pattern, Settings)>,
+}
+
+impl Config {
+    /// The configuration `explicit` names, or else the one in the working
+    /// directory, if there is one.
+    pub(crate) fn discover(explicit: Option<&Path>) -> Result<Option<Config>> {
+        match explicit {
+            Some(path) => Config::load(path).map(Some),
+            None if Path::new(CONFIG_FILE).is_file() => {
+                Config::load(Path::new(CONFIG_FILE)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Read a configuration file. Its font directories are taken relative
+    /// to the file, not to the working directory.
+    pub(crate) fn load(path: &Path) -> Result<Config> {
+        let text: String =
+            std::fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+        let mut config: Config = Config::parse(&text).with_context(|| format!("in {:?}", path))?;
+        let dir: &Path = path.parent().unwrap_or(Path::new(""));
+        let layers = std::iter::once(&mut config.defaults)
+            .chain(config.profiles.values_mut())
+            .chain(config.files.iter_mut().map(|(_, settings)| settings));
+        for settings in layers {
+            for path in settings.font_path.iter_mut().flatten() {
+                *path = dir.join(&*path);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Parse a configuration, checking every setting in it.
+    pub(crate) fn parse(text: &str) -> Result<Config> {
+        let mut root: toml::Table = toml::from_str(text)?;
+        let mut config = Config::default();
+        if let Some(profiles) = root.remove("profiles") {
+            config.profiles = tables("profiles", profiles)?.into_iter().collect();
+        }
+        if let Some(files) = root.remove("files") {
+            for (pattern, settings) in tables("files", files)? {
+                config.files.push((Pattern::new(&pattern)?, settings));
+            }
+        }
+        // Split off before deserializing: the settings refuse keys they do
+        // not know, which would include the tables.
+        config.defaults = toml::Value::Table(root).try_into()?;
+        let layers = std::iter::once(&config.defaults)
+            .chain(config.profiles.values())
+            .chain(config.files.iter().map(|(_, settings)| settings));
+        for settings in layers {
+            apply(settings, &mut ConvertOptions::default())?;
+        }
+        Ok(config)
+    }
+
+    /// The settings for every input: the defaults, then the profile's.
+    pub(crate) fn base(&self, profile: Option<&str>) -> Result<Settings> {
+        let mut settings: Settings = self.defaults.clone();
+        if let Some(name) = profile {
+            let Some(overrides) = self.profiles.get(name) else {
+                anyhow::bail!("no profile {name:?} in the configuration");
+            };
+            layer(&mut settings, overrides);
+        }
+        Ok(settings)
+    }
+
+    /// The settings of the `[files]` patterns `input` matches, in order.
+    /// The path is matched as given, with `/` between its components.
+    pub(crate) fn overrides_for(&self, input: &Path) -> Settings {
+        let path: String = input.to_string_lossy().replace('\\', "/");
+        let path: &str = path.strip_prefix("./").unwrap_or(&path);
+        let mut settings = Settings::default();
+        for (pattern, overrides) in &self.files {
+            if pattern.matches(path) {
+                layer(&mut settings, overrides);
+            }
+        }
+        settings
+    }
+}
+
+/// The layers of settings a conversion goes by: the configuration's
+/// defaults and profile, the patterns an input matches, then the flags.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Layers {
+    pub(crate) config: Config,
+    /// The configuration's defaults with the profile over them.
+    pub(crate) base: Settings,
+    pub(crate) flags: Settings,
+}
+
+impl Layers {
+    /// The settings of every input.
+    pub(crate) fn settings(&self) -> Settings {
+        let mut settings: Settings = self.base.clone();
+        layer(&mut settings, &self.flags);
+        settings
+    }
+
+    /// The settings of `input`, when `[files]` patterns change them from
+    /// those of every input.
+    pub(crate) fn settings_for(&self, input: &Path) -> Option<Settings> {
+        let overrides: Settings = self.config.overrides_for(input);
+        if overrides == Settings::default() {
+            return None;
+        }
+        let mut settings: Settings = self.base.clone();
+        layer(&mut settings, &overrides);
+        layer(&mut settings, &self.flags);
+        Some(settings)
+    }
+}
+
+/// The tables under `[key]`, each a layer of settings, in the order
+/// written.
+fn tables(key: &str, value: toml::Value) -> Result<Vec<(String, Settings)>> {
+    let toml::Value::Table(entries) = value else {
+        anyhow::bail!("{key} must be a table");
+    };
+    entries
+        .into_iter()
+        .map(|(name, value)| {
+            let settings: Settings = value
+                .try_into()
+                .with_context(|| format!("in [{key}.{name:?}]"))?;
+            Ok((name, settings))
+        })
+        .collect()
+}
+
+/// Lay `overrides` over `settings`. Font directories add up rather than
+/// replace each other, and one way of choosing the PDF standard drops the
+/// other.
+pub(crate) fn layer(settings: &mut Settings, overrides: &Settings) {
+    // Listed in full so that a new setting cannot be left out.
+    let Settings {
+        sheets,
+        ranges,
+        include_hidden,
+        gridlines,
+        headings,
+        ignore_frozen_panes,
+        row_label_columns,
+        label_column_pages,
+        redact_hidden,
+        blank_errors,
+        annotate_dropdowns,
+        locale,
+        slides,
+        sections,
+        pdf_a,
+        pdf_standard,
+        embed_source,
+        linearize,
+        compact,
+        paper,
+        font_path,
+        landscape,
+        tagged,
+        pdf_ua,
+        streaming,
+        streaming_chunk_size,
+        effects,
+        slide_size,
+        slide_scale,
+        timeout,
+        max_input_size,
+    } = overrides;
+    if pdf_a.is_some() {
+        settings.pdf_standard = None;
+    }
+    if pdf_standard.is_some() {
+        settings.pdf_a = None;
+    }
+    match (&mut settings.font_path, font_path) {
+        (Some(paths), Some(more)) => paths.extend(more.iter().cloned()),
+        (paths, more) => over(paths, more),
+    }
+    over(&mut settings.sheets, sheets);
+    over(&mut settings.ranges, ranges);
+    over(&mut settings.include_hidden, include_hidden);
+    over(&mut settings.gridlines, gridlines);
+    over(&mut settings.headings, headings);
+    over(&mut settings.ignore_frozen_panes, ignore_frozen_panes);
+    over(&mut settings.row_label_columns, row_label_columns);
+    over(&mut settings.label_column_pages, label_column_pages);
+    over(&mut settings.redact_hidden, redact_hidden);
+    over(&mut settings.blank_errors, blank_errors);
+    over(&mut settings.annotate_dropdowns, annotate_dropdowns);
+    over(&mut settings.locale, locale);
+    over(&mut settings.slides, slides);
+    over(&mut settings.sections, sections);
+    over(&mut settings.pdf_a, pdf_a);
+    over(&mut settings.pdf_standard, pdf_standard);
+    over(&mut settings.embed_source, embed_source);
+    over(&mut settings.linearize, linearize);
+    over(&mut settings.compact, compact);
+    over(&mut settings.paper, paper);
+    over(&mut settings.landscape, landscape);
+    over(&mut settings.tagged, tagged);
+    over(&mut settings.pdf_ua, pdf_ua);
+    over(&mut settings.streaming, streaming);
+    over(&mut settings.streaming_chunk_size, streaming_chunk_size);
+    over(&mut settings.effects, effects);
+    over(&mut settings.slide_size, slide_size);
+    over(&mut settings.slide_scale, slide_scale);
+    over(&mut settings.timeout, timeout);
+    over(&mut settings.max_input_size, max_input_size);
+}
+
+/// Replace `setting` with `value`, if it is set.
+fn over<T: Clone>(setting: &mut Option<T>, value: &Option<T>) {
+    if value.is_some() {
+        setting.clone_from(value);
+    }
+}
+
+/// Set the options `settings` name.
+pub(crate) fn apply(settings: &Settings, options: &mut ConvertOptions) -> Result<()> {
+    let Settings {
+        sheets,
+        ranges,
+        include_hidden,
+        gridlines,
+        headings,
+        ignore_frozen_panes,
+        row_label_columns,
+        label_column_pages,
+        redact_hidden,
+        blank_errors,
+        annotate_dropdowns,
+        locale,
+        slides,
+        sections,
+        pdf_a,
+        pdf_standard,
+        embed_source,
+        linearize,
+        compact,
+        paper,
+        font_path,
+        landscape,
+        tagged,
+        pdf_ua,
+        streaming,
+        streaming_chunk_size,
+        effects,
+        slide_size,
+        slide_scale,
+        timeout,
+        max_input_size,
+    } = settings;
+    over(&mut options.sheet_names, sheets);
+    over(&mut options.xlsx_ranges, ranges);
+    set(&mut options.include_hidden, include_hidden);
+    set(&mut options.xlsx_gridlines, gridlines);
+    set(&mut options.xlsx_headings, headings);
+    set(&mut options.xlsx_ignore_frozen_panes, ignore_frozen_panes);
+    set(&mut options.xlsx_row_label_columns, row_label_columns);
+    set(&mut options.xlsx_label_column_pages, label_column_pages);
+    set(&mut options.xlsx_redact_hidden, redact_hidden);
+    set(&mut options.xlsx_blank_errors, blank_errors);
+    set(&mut options.xlsx_annotate_dropdowns, annotate_dropdowns);
+    over(&mut options.locale, locale);
+    if let Some(slides) = parsed("slides", slides, SlideRange::parse)? {
+        options.slide_range = Some(slides);
+    }
+    over(&mut options.slide_sections, sections);
+    if let Some(pdf_a) = pdf_a {
+        options.pdf_standard = pdf_a.then_some(PdfStandard::PdfA2b);
+    }
+    if let Some(standard) = parsed("pdf-standard", pdf_standard, PdfStandard::parse)? {
+        options.pdf_standard = Some(standard);
+    }
+    set(&mut options.embed_source, embed_source);
+    set(&mut options.linearize, linearize);
+    set(&mut options.compact, compact);
+    if let Some(paper) = parsed("paper", paper, PaperSize::parse)? {
+        options.paper_size = Some(paper);
+    }
+    if let Some(paths) = font_path {
+        options.font_paths.clone_from(paths);
+    }
+    over(&mut options.landscape, landscape);
+    set(&mut options.tagged, tagged);
+    set(&mut options.pdf_ua, pdf_ua);
+    set(&mut options.streaming, streaming);
+    over(&mut options.streaming_chunk_size, streaming_chunk_size);
+    if let Some(effects) = parsed("effects", effects, EffectFidelity::parse)? {
+        options.effects = effects;
+    }
+    if let Some(aspect) = parsed("slide-size", slide_size, SlideAspect::parse)? {
+        options.slide_scale_to = Some(aspect);
+    }
+    if let Some(mode) = parsed("slide-scale", slide_scale, SlideScaleMode::parse)? {
+        options.slide_scale_mode = mode;
+    }
+    if let Some(seconds) = positive("timeout", *timeout)? {
+        options.limits.timeout = Some(Duration::from_secs(seconds));
+    }
+    if let Some(megabytes) = positive("max-input-size", *max_input_size)? {
+        // The input and, for the ZIP packages the formats are, its
+        // decompressed parts: what a conversion has to read.
+        let bytes: u64 = megabytes.saturating_mul(1024 * 1024);
+        options.limits.max_input_bytes = Some(bytes);
+        options.limits.max_uncompressed_bytes = Some(bytes);
+    }
+    Ok(())
+}
+
+/// Set `option` to `value`, if it is set.
+fn set<T: Copy>(option: &mut T, value: &Option<T>) {
+    if let Some(value) = value {
+        *option = *value;
+    }
+}
+
+/// The setting `key` parsed, if it is set.
+fn parsed<T, E: std::fmt::Display>(
+    key: &str,
+    value: &Option<String>,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Result<Option<T>> {
+    value
+        .as_deref()
+        .map(|text| parse(text).map_err(|e| anyhow::anyhow!("invalid {key} value: {e}")))
+        .transpose()
+}
+
+fn positive(key: &str, value: Option<u64>) -> Result<Option<u64>> {
+    match value {
+        Some(0) => anyhow::bail!("invalid {key} value: expected a number above 0"),
+        value => Ok(value),
+    }
+}
+
+#[cfg(test)]
+#[path = "config_tests.rs"]
+mod tests;
@@ -0,0 +1,162 @@
+use super::*;
+
+use office2pdf::config::{PaperSize, PdfStandard};
+
+const CONFIG: &str = r#"
+# Defaults for every input
+paper = "a4"
+font-path = ["fonts"]   # relative to this file
+sheets = ["Summary", "Data"]
+
+[profiles.archive]
+pdf-standard = "a-2b"
+
+[profiles.print]
+pdf-a = false
+landscape = true
+
+[files."*.xlsx"]
+gridlines = true
+row-label-columns = 2
+
+[files."reports/**"]
+sheets = ["Totals"]
+"#;
+
+fn options(settings: &Settings) -> ConvertOptions {
+    let mut options = ConvertOptions::default();
+    apply(settings, &mut options).unwrap();
+    options
+}
+
+#[test]
+fn test_config_reads_quoted_keys_and_inline_tables_in_order() {
+    let config: Config = Config::parse(
+        "locale = 'de-DE'\nfiles.\"b/*.docx\" = { tagged = true }\n\
+         [files.\"a/*.docx\"]\nfont-path = [\"x\", \"y\", ] # comment\n",
+    )
+    .unwrap();
+    assert_eq!(config.defaults.locale.as_deref(), Some("de-DE"));
+    let patterns: Vec<&str> = config
+        .files
+        .iter()
+        .map(|(pattern, _)| pattern.as_str())
+        .collect();
+    assert_eq!(patterns, ["b/*.docx", "a/*.docx"]);
+    assert_eq!(config.files[0].1.tagged, Some(true));
+    assert_eq!(
+        config.files[1].1.font_path,
+        Some(vec![PathBuf::from("x"), PathBuf::from("y")])
+    );
+}
+
+#[test]
+fn test_config_reports_syntax_errors_by_line() {
+    let err = Config::parse("paper = \"a4\"\npaper = \"a5\"").unwrap_err();
+    assert!(format!("{err:#}").contains("line 2"), "{err:#}");
+    let err = Config::parse("tagged = true\n\nlocale = \"open").unwrap_err();
+    assert!(format!("{err:#}").contains("line 3"), "{err:#}");
+}
+
+#[test]
+fn test_config_layers_defaults_profiles_and_files() {
+    let config: Config = Config::parse(CONFIG).unwrap();
+    assert_eq!(config.profiles.len(), 2);
+    assert_eq!(config.files.len(), 2);
+
+    let base: Settings = config.base(None).unwrap();
+    let defaults: ConvertOptions = options(&base);
+    assert!(matches!(defaults.paper_size, Some(PaperSize::A4)));
+    assert_eq!(
+        defaults.sheet_names,
+        Some(vec!["Summary".to_string(), "Data".to_string()])
+    );
+    assert!(!defaults.xlsx_gridlines);
+
+    let archive: ConvertOptions = options(&config.base(Some("archive")).unwrap());
+    assert_eq!(archive.pdf_standard, Some(PdfStandard::PdfA2b));
+    assert!(config.base(Some("missing")).is_err());
+
+    let layers = Layers {
+        base: config.base(Some("archive")).unwrap(),
+        config,
+        flags: Settings {
+            pdf_a: Some(false),
+            ..Default::default()
+        },
+    };
+    // The flag drops the profile's PDF standard.
+    assert_eq!(options(&layers.settings()).pdf_standard, None);
+    assert!(layers.settings_for(Path::new("notes.docx")).is_none());
+
+    let report: Settings = layers
+        .settings_for(Path::new("./reports/q3/totals.xlsx"))
+        .unwrap();
+    let report: ConvertOptions = options(&report);
+    assert!(report.xlsx_gridlines);
+    assert_eq!(report.xlsx_row_label_columns, 2);
+    assert_eq!(report.sheet_names, Some(vec!["Totals".to_string()]));
+    assert_eq!(report.pdf_standard, None);
+}
+
+#[test]
+fn test_layer_adds_font_paths() {
+    let mut settings = Settings {
+        font_path: Some(vec![PathBuf::from("a")]),
+        ..Default::default()
+    };
+    layer(
+        &mut settings,
+        &Settings {
+            font_path: Some(vec![PathBuf::from("b")]),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        options(&settings).font_paths,
+        vec![PathBuf::from("a"), PathBuf::from("b")]
+    );
+}
+
+#[test]
+fn test_timeout_and_max_input_size_set_limits() {
+    let config: Config = Config::parse("timeout = 90\nmax-input-size = 512").unwrap();
+    let limits = options(&config.defaults).limits;
+    assert_eq!(limits.timeout, Some(std::time::Duration::from_secs(90)));
+    assert_eq!(limits.max_input_bytes, Some(512 * 1024 * 1024));
+    assert_eq!(limits.max_uncompressed_bytes, Some(512 * 1024 * 1024));
+    assert!(Config::parse("timeout = 0").is_err());
+}
+
+#[test]
+fn test_config_rejects_unknown_and_mistyped_settings() {
+    let err = Config::parse("papr = \"a4\"").unwrap_err();
+    assert!(
+        format!("{err:#}").contains("unknown field `papr`"),
+        "{err:#}"
+    );
+    let err = Config::parse("[files.\"*.docx\"]\ntagged = \"yes\"").unwrap_err();
+    assert!(format!("{err:#}").contains("expected a boolean"), "{err:#}");
+    let err = Config::parse("[profiles.print]\npaper = 4").unwrap_err();
+    assert!(format!("{err:#}").contains("print"), "{err:#}");
+    let err = Config::parse("paper = \"a9\"").unwrap_err();
+    assert!(format!("{err:#}").contains("invalid paper value"));
+    assert!(Config::parse("profiles = 1").is_err());
+}
+
+#[test]
+fn test_config_load_resolves_font_paths_against_its_directory() {
+    let dir = std::env::temp_dir().join("office2pdf_config_test_load");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(CONFIG_FILE);
+    std::fs::write(&path, CONFIG).unwrap();
+
+    let config: Config = Config::discover(Some(&path)).unwrap().unwrap();
+    assert_eq!(
+        options(&config.defaults).font_paths,
+        vec![dir.join("fonts")]
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,203 @@
+//! Skipping inputs that have not changed since they were last converted,
+//! for `--incremental`: a manifest in each output directory records what
+//! each PDF there was made from.
+//!
+//! An input counts as unchanged while its PDF exists and it has the size,
+//! modification time and options it had then. A new modification time with
+//! the same content, as a fresh checkout gives, is caught by a hash of the
+//! content before converting again.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::{Context, Result};
+use office2pdf::config::{ConvertOptions, SigningKey, StampContent};
+
+/// The manifest's file name, in the directory of the PDFs it describes.
+pub(crate) const MANIFEST_FILE: &str = ".office2pdf-manifest";
+
+const HEADER: &str = "# office2pdf manifest v1";
+
+/// What a PDF was made from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Entry {
+    pub(crate) size: u64,
+    /// Modification time in nanoseconds since the Unix epoch, 0 where the
+    /// file system keeps none.
+    pub(crate) modified: u128,
+    /// FNV-1a hash of the content.
+    pub(crate) content: u64,
+    /// Hash of the options converted with.
+    pub(crate) options: u64,
+    pub(crate) input: String,
+}
+
+/// The entries of one output directory, by PDF file name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Manifest {
+    pub(crate) entries: BTreeMap<String, Entry>,
+}
+
+impl Manifest {
+    /// The manifest in `dir`; empty when there is none or it cannot be
+    /// read, which only costs converting again.
+    pub(crate) fn load(dir: &Path) -> Manifest {
+        let Ok(text) = std::fs::read_to_string(dir.join(MANIFEST_FILE)) else {
+            return Manifest::default();
+        };
+        let mut lines = text.lines();
+        if lines.next() != Some(HEADER) {
+            return Manifest::default();
+        }
+        let entries = lines
+            .filter_map(|line| {
+                let mut fields = line.splitn(6, '\t');
+                let name: String = fields.next()?.to_string();
+                let entry = Entry {
+                    size: fields.next()?.parse().ok()?,
+                    modified: fields.next()?.parse().ok()?,
+                    content: u64::from_str_radix(fields.next()?, 16).ok()?,
+                    options: u64::from_str_radix(fields.next()?, 16).ok()?,
+                    input: fields.next()?.to_string(),
+                };
+                Some((name, entry))
+            })
+            .collect();
+        Manifest { entries }
+    }
+
+    /// Write the manifest into `dir`, one tab-separated line per PDF.
+    pub(crate) fn save(&self, dir: &Path) -> Result<()> {
+        let mut text: String = format!("{HEADER}\n");
+        for (name, entry) in &self.entries {
+            text.push_str(&format!(
+                "{name}\t{}\t{}\t{:016x}\t{:016x}\t{}\n",
+                entry.size, entry.modified, entry.content, entry.options, entry.input
+            ));
+        }
+        let path: PathBuf = dir.join(MANIFEST_FILE);
+        std::fs::write(&path, text).with_context(|| format!("writing {:?}", path))
+    }
+
+    /// Whether `output`'s entry says it was made from `input` as it is now,
+    /// with options hashing to `options`.
+    pub(crate) fn is_up_to_date(&self, input: &Path, output: &Path, options: u64) -> bool {
+        let Some(entry) = file_name(output).and_then(|name| self.entries.get(&name)) else {
+            return false;
+        };
+        if !output.is_file() || entry.input != input.to_string_lossy() || entry.options != options {
+            return false;
+        }
+        let Ok(metadata) = std::fs::metadata(input) else {
+            return false;
+        };
+        if metadata.len() != entry.size {
+            return false;
+        }
+        modified(&metadata) == entry.modified
+            || hash_file(input).is_ok_and(|content| content == entry.content)
+    }
+}
+
+/// The entry recording that `input` was converted with options hashing to
+/// `options`.
+pub(crate) fn entry_for(input: &Path, options: u64) -> Result<Entry> {
+    let metadata = std::fs::metadata(input).with_context(|| format!("reading {:?}", input))?;
+    Ok(Entry {
+        size: metadata.len(),
+        modified: modified(&metadata),
+        content: hash_file(input)?,
+        options,
+        input: input.to_string_lossy().into_owned(),
+    })
+}
+
+/// A hash of `options`, so that converting with other options counts as a
+/// change: their debug text, then the signing key, stamp image and font
+/// bytes, of which the debug text only shows the sizes, and the PDF
+/// passwords it leaves out. The hash reveals no more of a password than
+/// the encrypted PDF beside the manifest already does.
+pub(crate) fn hash_options(options: &ConvertOptions) -> u64 {
+    let mut hash: u64 = fnv1a(FNV_OFFSET, format!("{options:?}").as_bytes());
+    if let Some(signing) = &options.signing {
+        match &signing.key {
+            SigningKey::Pkcs12 { data, .. } => hash = fnv1a(hash, data),
+            SigningKey::Der {
+                private_key,
+                certificates,
+            } => {
+                hash = fnv1a(hash, private_key);
+                for certificate in certificates {
+                    hash = fnv1a(hash, certificate);
+                }
+            }
+        }
+    }
+    if let Some(stamp) = &options.stamp
+        && let StampContent::Image { data, .. } = &stamp.content
+    {
+        hash = fnv1a(hash, data);
+    }
+    if let Some(security) = &options.pdf_security {
+        hash = fnv1a(hash, security.user_password.as_bytes());
+        hash = fnv1a(hash, &[0]);
+        hash = fnv1a(hash, security.owner_password.as_bytes());
+    }
+    for font in &options.fonts.data {
+        hash = fnv1a(hash, font);
+    }
+    hash
+}
+
+/// The directory whose manifest records `output`.
+pub(crate) fn manifest_dir(output: &Path) -> PathBuf {
+    output.parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+/// The name a manifest knows `output` by.
+pub(crate) fn file_name(output: &Path) -> Option<String> {
+    output
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+fn modified(metadata: &std::fs::Metadata) -> u128 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |since| since.as_nanos())
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a, which unlike std's hashers gives the same hash from one build to
+/// the next, as a manifest kept across runs needs.
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+fn hash_file(path: &Path) -> Result<u64> {
+    let mut file = std::fs::File::open(path).with_context(|| format!("reading {:?}", path))?;
+    let mut buffer: Vec<u8> = vec![0; 64 * 1024];
+    let mut hash: u64 = FNV_OFFSET;
+    loop {
+        let read: usize = file
+            .read(&mut buffer)
+            .with_context(|| format!("reading {:?}", path))?;
+        if read == 0 {
+            return Ok(hash);
+        }
+        hash = fnv1a(hash, &buffer[..read]);
+    }
+}
+
+#[cfg(test)]
+#[path = "incremental_tests.rs"]
+mod tests;
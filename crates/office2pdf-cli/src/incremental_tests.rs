@@ -0,0 +1,143 @@
+use super::*;
+use office2pdf::config::{PdfSecurity, SigningConfig, Stamp};
+
+#[test]
+fn test_fnv1a_matches_reference_values() {
+    assert_eq!(fnv1a(FNV_OFFSET, b""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(fnv1a(FNV_OFFSET, b"a"), 0xaf63_dc4c_8601_ec8c);
+    assert_eq!(fnv1a(FNV_OFFSET, b"foobar"), 0x8594_4171_f739_67e8);
+}
+
+#[test]
+fn test_manifest_round_trips() {
+    let dir = std::env::temp_dir().join("office2pdf_incremental_test_round_trip");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut manifest = Manifest::default();
+    manifest.entries.insert(
+        "a b.pdf".to_string(),
+        Entry {
+            size: 12,
+            modified: 1_700_000_000_123_456_789,
+            content: 0xdead_beef,
+            options: 7,
+            input: "docs/a b.docx".to_string(),
+        },
+    );
+    manifest.save(&dir).unwrap();
+    assert_eq!(Manifest::load(&dir), manifest);
+
+    std::fs::write(dir.join(MANIFEST_FILE), "something else\n").unwrap();
+    assert_eq!(Manifest::load(&dir), Manifest::default());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_is_up_to_date_follows_input_output_and_options() {
+    let dir = std::env::temp_dir().join("office2pdf_incremental_test_up_to_date");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("report.docx");
+    let output = dir.join("report.pdf");
+    std::fs::write(&input, b"first version").unwrap();
+
+    let mut manifest = Manifest::default();
+    manifest
+        .entries
+        .insert("report.pdf".to_string(), entry_for(&input, 1).unwrap());
+    // No PDF yet.
+    assert!(!manifest.is_up_to_date(&input, &output, 1));
+
+    std::fs::write(&output, b"%PDF").unwrap();
+    assert!(manifest.is_up_to_date(&input, &output, 1));
+    assert!(!manifest.is_up_to_date(&input, &output, 2));
+    assert!(!manifest.is_up_to_date(&dir.join("other.docx"), &output, 1));
+
+    // Touched with the same content: still up to date, by the hash.
+    manifest.entries.get_mut("report.pdf").unwrap().modified += 1;
+    assert!(manifest.is_up_to_date(&input, &output, 1));
+
+    std::fs::write(&input, b"second version").unwrap();
+    assert!(!manifest.is_up_to_date(&input, &output, 1));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_hash_options_tells_options_apart() {
+    let landscape = ConvertOptions {
+        landscape: Some(true),
+        ..Default::default()
+    };
+    assert_eq!(
+        hash_options(&ConvertOptions::default()),
+        hash_options(&ConvertOptions::default())
+    );
+    assert_ne!(
+        hash_options(&ConvertOptions::default()),
+        hash_options(&landscape)
+    );
+}
+
+#[test]
+fn test_hash_options_reads_the_bytes_behind_sizes() {
+    // Swapping a stamp image for another of the same size leaves the
+    // options' debug text as it was, but the PDF changes.
+    let stamped = |data: Vec<u8>| ConvertOptions {
+        stamp: Some(Stamp::image(data, 100.0)),
+        ..Default::default()
+    };
+    assert_eq!(
+        hash_options(&stamped(vec![1, 2, 3])),
+        hash_options(&stamped(vec![1, 2, 3]))
+    );
+    assert_ne!(
+        hash_options(&stamped(vec![1, 2, 3])),
+        hash_options(&stamped(vec![3, 2, 1]))
+    );
+
+    let with_font = |data: Vec<u8>| {
+        let mut options = ConvertOptions::default();
+        options.fonts.data.push(data);
+        options
+    };
+    assert_ne!(
+        hash_options(&with_font(vec![0; 4])),
+        hash_options(&with_font(vec![1; 4]))
+    );
+
+    let signed = |private_key: Vec<u8>| ConvertOptions {
+        signing: Some(SigningConfig {
+            key: SigningKey::Der {
+                private_key,
+                certificates: vec![vec![9; 8]],
+            },
+            reason: None,
+            location: None,
+            contact_info: None,
+            appearance: None,
+        }),
+        ..Default::default()
+    };
+    assert_ne!(
+        hash_options(&signed(vec![5; 16])),
+        hash_options(&signed(vec![6; 16]))
+    );
+}
+
+#[test]
+fn test_hash_options_follows_pdf_passwords() {
+    let encrypted = |owner_password: &str| ConvertOptions {
+        pdf_security: Some(PdfSecurity {
+            owner_password: owner_password.to_string(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    assert_ne!(
+        hash_options(&encrypted("first")),
+        hash_options(&encrypted("second"))
+    );
+}
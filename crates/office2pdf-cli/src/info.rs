@@ -0,0 +1,92 @@
+//! `office2pdf info`: what a document holds, to pick `--sheets` and
+//! `--slides` values without opening it in Office.
+
+use std::fmt::Write;
+
+use office2pdf::error::{DocumentInfo, SheetInfo, SlideInfo};
+
+/// The summary `office2pdf info` prints for the document `name`: format and
+/// page estimate, metadata, sheets or slides, and embedded fonts.
+pub(crate) fn describe(name: &str, info: &DocumentInfo) -> String {
+    let mut out = String::new();
+    let pages: u32 = info.estimated_pages;
+    let _ = writeln!(
+        out,
+        "{name}: {}, about {pages} page{}",
+        info.format.extension().to_uppercase(),
+        if pages == 1 { "" } else { "s" }
+    );
+
+    let metadata = &info.metadata;
+    let keywords: Option<String> =
+        (!metadata.keywords.is_empty()).then(|| metadata.keywords.join(", "));
+    for (label, value) in [
+        ("Title", metadata.title.as_deref()),
+        ("Author", metadata.author.as_deref()),
+        ("Subject", metadata.subject.as_deref()),
+        ("Company", metadata.company.as_deref()),
+        ("Keywords", keywords.as_deref()),
+        ("Language", metadata.language.as_deref()),
+        ("Created", metadata.created.as_deref()),
+        ("Modified", metadata.modified.as_deref()),
+    ] {
+        if let Some(value) = value {
+            let _ = writeln!(out, "{:<10}{value}", format!("{label}:"));
+        }
+    }
+
+    if !info.sheets.is_empty() {
+        out.push_str("Sheets:\n");
+        write_sheets(&mut out, &info.sheets);
+    }
+    if !info.slides.is_empty() {
+        out.push_str("Slides:\n");
+        write_slides(&mut out, &info.slides);
+    }
+    if !info.embedded_fonts.is_empty() {
+        let _ = writeln!(out, "Embedded fonts: {}", info.embedded_fonts.join(", "));
+    }
+    out
+}
+
+/// One line per sheet, names padded so the extents line up.
+fn write_sheets(out: &mut String, sheets: &[SheetInfo]) {
+    let width: usize = sheets
+        .iter()
+        .map(|sheet| sheet.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for sheet in sheets {
+        let extent: String = if sheet.rows == 0 {
+            "empty".to_string()
+        } else {
+            format!("{} rows x {} columns", sheet.rows, sheet.columns)
+        };
+        let hidden: &str = if sheet.hidden { " (hidden)" } else { "" };
+        let _ = writeln!(out, "  {:<width$}  {extent}{hidden}", sheet.name);
+    }
+}
+
+/// One line per slide, numbered as `--slides` counts them, under the name
+/// of their section when the presentation has sections.
+fn write_slides(out: &mut String, slides: &[SlideInfo]) {
+    let has_sections: bool = slides.iter().any(|slide| slide.section.is_some());
+    let indent: &str = if has_sections { "    " } else { "  " };
+    let width: usize = slides
+        .last()
+        .map_or(1, |slide| slide.number.to_string().len());
+    let mut section: Option<&str> = None;
+    for (index, slide) in slides.iter().enumerate() {
+        if has_sections && (index == 0 || slide.section.as_deref() != section) {
+            section = slide.section.as_deref();
+            let _ = writeln!(out, "  {}", section.unwrap_or("(no section)"));
+        }
+        let title: &str = slide.title.as_deref().unwrap_or("(untitled)");
+        let hidden: &str = if slide.hidden { " (hidden)" } else { "" };
+        let _ = writeln!(out, "{indent}{:>width$}. {title}{hidden}", slide.number);
+    }
+}
+
+#[cfg(test)]
+#[path = "info_tests.rs"]
+mod tests;
@@ -0,0 +1,100 @@
+use super::*;
+
+use office2pdf::config::Format;
+use office2pdf::ir::Metadata;
+
+fn document_info(format: Format, estimated_pages: u32) -> DocumentInfo {
+    DocumentInfo {
+        format,
+        metadata: Metadata::default(),
+        estimated_pages,
+        sheets: Vec::new(),
+        slides: Vec::new(),
+        embedded_fonts: Vec::new(),
+    }
+}
+
+fn slide(number: u32, title: Option<&str>, section: Option<&str>, hidden: bool) -> SlideInfo {
+    SlideInfo {
+        number,
+        title: title.map(str::to_string),
+        section: section.map(str::to_string),
+        hidden,
+    }
+}
+
+#[test]
+fn test_describe_workbook_lists_sheets_and_metadata() {
+    let info = DocumentInfo {
+        metadata: Metadata {
+            title: Some("Budget".to_string()),
+            keywords: vec!["finance".to_string(), "2024".to_string()],
+            ..Metadata::default()
+        },
+        sheets: vec![
+            SheetInfo {
+                name: "Summary".to_string(),
+                rows: 40,
+                columns: 6,
+                hidden: false,
+            },
+            SheetInfo {
+                name: "Raw".to_string(),
+                rows: 0,
+                columns: 0,
+                hidden: true,
+            },
+        ],
+        ..document_info(Format::Xlsx, 3)
+    };
+
+    assert_eq!(
+        describe("budget.xlsx", &info),
+        "budget.xlsx: XLSX, about 3 pages\n\
+         Title:    Budget\n\
+         Keywords: finance, 2024\n\
+         Sheets:\n  \
+         Summary  40 rows x 6 columns\n  \
+         Raw      empty (hidden)\n"
+    );
+}
+
+#[test]
+fn test_describe_presentation_groups_slides_by_section() {
+    let info = DocumentInfo {
+        slides: (1..=10)
+            .map(|number| match number {
+                1 => slide(1, Some("Welcome"), Some("Intro"), false),
+                10 => slide(10, None, Some("Appendix"), true),
+                _ => slide(number, Some("Results"), Some("Body"), false),
+            })
+            .collect(),
+        embedded_fonts: vec!["Carlito".to_string()],
+        ..document_info(Format::Pptx, 9)
+    };
+
+    let text: String = describe("deck.pptx", &info);
+    assert!(
+        text.starts_with(
+            "deck.pptx: PPTX, about 9 pages\nSlides:\n  Intro\n     1. Welcome\n  Body\n     2. Results\n"
+        ),
+        "{text}"
+    );
+    assert!(
+        text.ends_with("  Appendix\n    10. (untitled) (hidden)\nEmbedded fonts: Carlito\n"),
+        "{text}"
+    );
+}
+
+#[test]
+fn test_describe_slides_without_sections_are_not_grouped() {
+    let info = DocumentInfo {
+        slides: vec![slide(1, Some("Only"), None, false)],
+        ..document_info(Format::Pptx, 1)
+    };
+
+    assert_eq!(
+        describe("one.pptx", &info),
+        "one.pptx: PPTX, about 1 page\nSlides:\n  1. Only\n"
+    );
+}
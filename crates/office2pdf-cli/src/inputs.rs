@@ -0,0 +1,183 @@
+//! Expanding the command line's inputs into the files to convert: named
+//! files as they are, directories walked for documents, with glob patterns
+//! choosing among the files found.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use globset::{Glob, GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
+use office2pdf::config::Format;
+use walkdir::WalkDir;
+
+/// A file to convert.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct InputFile {
+    pub(crate) path: PathBuf,
+    /// Where the file sits under the directory it was found in, for the
+    /// output directory to mirror; `None` for files named outright.
+    pub(crate) relative: Option<PathBuf>,
+}
+
+impl InputFile {
+    /// A file named on the command line.
+    pub(crate) fn named(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            relative: None,
+        }
+    }
+}
+
+/// Which files in directory inputs are converted.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InputFilter {
+    /// Walk subdirectories too, not only the directory's own files.
+    pub(crate) recursive: bool,
+    /// Walk into symbolic links to directories as well.
+    pub(crate) follow_symlinks: bool,
+    /// Glob patterns a file has to match one of; every DOCX, PPTX and XLSX
+    /// file when empty.
+    pub(crate) include: Vec<String>,
+    /// Glob patterns of files to leave out.
+    pub(crate) exclude: Vec<String>,
+}
+
+/// An [`InputFilter`]'s patterns, compiled once for the whole walk.
+struct CompiledFilter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl CompiledFilter {
+    fn new(filter: &InputFilter) -> Result<Self> {
+        let include: Option<GlobSet> = if filter.include.is_empty() {
+            None
+        } else {
+            Some(glob_set(&filter.include)?)
+        };
+        Ok(Self {
+            include,
+            exclude: glob_set(&filter.exclude)?,
+        })
+    }
+
+    /// Whether to convert the file at `relative` under a directory input,
+    /// `relative` using `/` between its components.
+    fn accepts(&self, relative: &str) -> bool {
+        let included: bool = match &self.include {
+            Some(include) => include.is_match(relative),
+            None => Path::new(relative)
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .and_then(Format::from_extension)
+                .is_some(),
+        };
+        included && !self.exclude.is_match(relative)
+    }
+}
+
+/// The files `inputs` stand for, in order: each directory's files, sorted
+/// by path, in place of the directory.
+///
+/// Symbolic links to files are converted like the files. Symbolic links
+/// to directories are skipped unless `filter.follow_symlinks` is set, and
+/// then a link back into a directory being walked is an error rather than
+/// a walk in circles.
+pub(crate) fn expand_inputs(inputs: &[PathBuf], filter: &InputFilter) -> Result<Vec<InputFile>> {
+    let compiled: CompiledFilter = CompiledFilter::new(filter)?;
+    let mut files: Vec<InputFile> = Vec::new();
+    for input in inputs {
+        if !input.is_dir() {
+            files.push(InputFile::named(input));
+            continue;
+        }
+        let mut found: Vec<PathBuf> = Vec::new();
+        let walk = WalkDir::new(input)
+            .min_depth(1)
+            .max_depth(if filter.recursive { usize::MAX } else { 1 })
+            .follow_links(filter.follow_symlinks);
+        for entry in walk {
+            let entry = entry.with_context(|| format!("reading directory {:?}", input))?;
+            // Follows a link the walk did not, to take in linked files.
+            if entry.path().is_file() {
+                found.push(entry.into_path());
+            }
+        }
+        found.sort();
+        for path in found {
+            let relative: PathBuf = path.strip_prefix(input).unwrap_or(&path).to_path_buf();
+            let components: Vec<String> = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            if compiled.accepts(&components.join("/")) {
+                files.push(InputFile {
+                    path,
+                    relative: Some(relative),
+                });
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// A glob pattern for paths with `/` between their components: `*` stands
+/// for any run of characters within a component, `?` for one such
+/// character and `**` for any number of whole components. A pattern
+/// without `/` is matched against the file name alone, so `*.docx` finds
+/// DOCX files at any depth.
+#[derive(Debug, Clone)]
+pub(crate) struct Pattern {
+    text: String,
+    matcher: GlobMatcher,
+}
+
+impl Pattern {
+    pub(crate) fn new(text: &str) -> Result<Self> {
+        Ok(Self {
+            text: text.to_string(),
+            matcher: glob(text)?.compile_matcher(),
+        })
+    }
+
+    /// The pattern as written.
+    pub(crate) fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub(crate) fn matches(&self, path: &str) -> bool {
+        self.matcher.is_match(path)
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+    }
+}
+
+/// `patterns` as one set, matching a path any of them matches.
+fn glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut set = GlobSetBuilder::new();
+    for pattern in patterns {
+        set.add(glob(pattern)?);
+    }
+    Ok(set.build()?)
+}
+
+/// `pattern` compiled as [`Pattern`] describes.
+fn glob(pattern: &str) -> Result<Glob> {
+    let anchored: String = if pattern.contains('/') {
+        pattern.to_string()
+    } else {
+        format!("**/{pattern}")
+    };
+    GlobBuilder::new(&anchored)
+        .literal_separator(true)
+        .build()
+        .with_context(|| format!("invalid glob pattern {pattern:?}"))
+}
+
+#[cfg(test)]
+#[path = "inputs_tests.rs"]
+mod tests;
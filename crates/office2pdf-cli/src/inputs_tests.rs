@@ -0,0 +1,140 @@
+use super::*;
+
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    Pattern::new(pattern).unwrap().matches(path)
+}
+
+#[test]
+fn test_glob_matches_stars_within_a_component() {
+    assert!(glob_matches("*.docx", "report.docx"));
+    assert!(glob_matches("*.docx", "team/notes/report.docx"));
+    assert!(!glob_matches("*.docx", "report.pptx"));
+    assert!(glob_matches("team/*.docx", "team/report.docx"));
+    assert!(!glob_matches("team/*.docx", "team/notes/report.docx"));
+    assert!(glob_matches("report-?.xlsx", "report-1.xlsx"));
+    assert!(!glob_matches("report-?.xlsx", "report-10.xlsx"));
+}
+
+#[test]
+fn test_glob_matches_double_star_across_components() {
+    assert!(glob_matches("**/*.docx", "report.docx"));
+    assert!(glob_matches("**/*.docx", "team/notes/report.docx"));
+    assert!(glob_matches("team/**/*.docx", "team/report.docx"));
+    assert!(glob_matches("team/**/*.docx", "team/a/b/report.docx"));
+    assert!(!glob_matches("team/**/*.docx", "other/report.docx"));
+    assert!(glob_matches("drafts/**", "drafts/a/b.docx"));
+    assert!(!glob_matches("drafts/**", "final/b.docx"));
+}
+
+#[test]
+fn test_glob_matches_without_backtracking_blowup() {
+    // Backtracking over the stars of this pattern would take 2^20 steps.
+    let pattern: String = "*a".repeat(20) + "b";
+    let path: String = "a".repeat(40);
+    assert!(!glob_matches(&pattern, &path));
+    assert!(Pattern::new("[unclosed").is_err());
+}
+
+#[test]
+fn test_expand_inputs_walks_directories_with_filters() {
+    let dir = std::env::temp_dir().join("office2pdf_inputs_test_walk");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("drafts")).unwrap();
+    std::fs::create_dir_all(dir.join("final")).unwrap();
+    for name in [
+        "a.docx",
+        "b.XLSX",
+        "notes.txt",
+        "drafts/c.docx",
+        "final/d.pptx",
+    ] {
+        std::fs::write(dir.join(name), b"").unwrap();
+    }
+    let relatives = |filter: &InputFilter| -> Vec<PathBuf> {
+        expand_inputs(std::slice::from_ref(&dir), filter)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.relative.unwrap())
+            .collect()
+    };
+
+    let shallow = InputFilter::default();
+    assert_eq!(
+        relatives(&shallow),
+        vec![PathBuf::from("a.docx"), PathBuf::from("b.XLSX")]
+    );
+
+    let recursive = InputFilter {
+        recursive: true,
+        exclude: vec!["drafts/**".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(
+        relatives(&recursive),
+        vec![
+            PathBuf::from("a.docx"),
+            PathBuf::from("b.XLSX"),
+            PathBuf::from("final/d.pptx"),
+        ]
+    );
+
+    let docx_only = InputFilter {
+        recursive: true,
+        include: vec!["**/*.docx".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(
+        relatives(&docx_only),
+        vec![PathBuf::from("a.docx"), PathBuf::from("drafts/c.docx")]
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_expand_inputs_keeps_named_files() {
+    let files: Vec<InputFile> =
+        expand_inputs(&[PathBuf::from("missing.docx")], &InputFilter::default()).unwrap();
+    assert_eq!(files, vec![InputFile::named("missing.docx")]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_expand_inputs_follows_directory_links_only_when_asked() {
+    let dir = std::env::temp_dir().join("office2pdf_inputs_test_links");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("tree")).unwrap();
+    std::fs::create_dir_all(dir.join("shared")).unwrap();
+    std::fs::write(dir.join("tree/a.docx"), b"").unwrap();
+    std::fs::write(dir.join("shared/b.docx"), b"").unwrap();
+    std::os::unix::fs::symlink(dir.join("shared"), dir.join("tree/shared")).unwrap();
+    let tree: PathBuf = dir.join("tree");
+    let relatives = |follow_symlinks: bool| -> Vec<PathBuf> {
+        let filter = InputFilter {
+            recursive: true,
+            follow_symlinks,
+            ..Default::default()
+        };
+        expand_inputs(std::slice::from_ref(&tree), &filter)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.relative.unwrap())
+            .collect()
+    };
+    assert_eq!(relatives(false), vec![PathBuf::from("a.docx")]);
+    assert_eq!(
+        relatives(true),
+        vec![PathBuf::from("a.docx"), PathBuf::from("shared/b.docx")]
+    );
+
+    // A link back up the tree is reported, not walked forever.
+    std::os::unix::fs::symlink(&tree, dir.join("shared/up")).unwrap();
+    let filter = InputFilter {
+        recursive: true,
+        follow_symlinks: true,
+        ..Default::default()
+    };
+    assert!(expand_inputs(std::slice::from_ref(&tree), &filter).is_err());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,310 @@
+//! Asynchronous conversions for the server: `POST /jobs` queues a document
+//! and answers at once with a job ID, a fixed pool of workers converts the
+//! queued documents in order, and `GET /jobs/{id}` and
+//! `GET /jobs/{id}/result` report on a job until its retention runs out.
+//!
+//! Only finished jobs expire; they are swept whenever the queue is used, so
+//! an idle server keeps them until the next request. Their PDFs are also
+//! capped in total size, past which the oldest results are dropped early.
+
+use std::collections::{HashMap, VecDeque};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use office2pdf::config::{ConvertOptions, Format};
+use office2pdf::error::ConvertMetrics;
+
+/// How the server runs jobs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct JobSettings {
+    /// Conversions that run at once.
+    pub(crate) workers: usize,
+    /// Jobs that may wait for a worker before new ones are turned away.
+    pub(crate) max_queued: usize,
+    /// How long a finished job and its PDF are kept.
+    pub(crate) retention: Duration,
+    /// Bytes of finished PDFs kept at most; the oldest go first past it.
+    pub(crate) max_retained_bytes: usize,
+}
+
+/// Where a job is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed(String),
+}
+
+impl JobStatus {
+    /// The name `GET /jobs/{id}` reports the status by.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Done => "done",
+            Self::Failed(_) => "failed",
+        }
+    }
+}
+
+/// A job as `GET /jobs/{id}` describes it.
+#[derive(Debug, Clone)]
+pub(crate) struct JobReport {
+    pub(crate) status: JobStatus,
+    pub(crate) format: Format,
+    pub(crate) metrics: Option<ConvertMetrics>,
+}
+
+/// What a worker made of a job's document: the PDF and its metrics, or why
+/// it could not be converted.
+pub(crate) type JobOutcome = Result<(Vec<u8>, Option<ConvertMetrics>), String>;
+
+/// Converts the document of a job.
+type Converter = dyn Fn(&[u8], Format, &ConvertOptions) -> JobOutcome + Send + Sync;
+
+/// Why `POST /jobs` was turned away.
+#[derive(Debug)]
+pub(crate) enum SubmitError {
+    /// `max_queued` jobs are already waiting.
+    QueueFull,
+    /// The OS random source, which job IDs come from, failed.
+    NoRandomness(getrandom::Error),
+}
+
+struct Job {
+    report: JobReport,
+    pdf: Option<Vec<u8>>,
+    /// When the job finished, which starts its retention.
+    finished: Option<Instant>,
+}
+
+/// A document waiting for a worker.
+struct Pending {
+    id: String,
+    data: Vec<u8>,
+    format: Format,
+    options: ConvertOptions,
+}
+
+#[derive(Default)]
+struct State {
+    jobs: HashMap<String, Job>,
+    pending: VecDeque<Pending>,
+    shutdown: bool,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    /// Signalled when a job is queued or the queue shuts down.
+    available: Condvar,
+    retention: Duration,
+    max_retained_bytes: usize,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        // A worker that panicked mid-update leaves nothing half-done that
+        // matters more than serving the other jobs.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The state, with the jobs whose retention ran out removed.
+    fn lock_swept(&self) -> MutexGuard<'_, State> {
+        let mut state = self.lock();
+        let retention: Duration = self.retention;
+        state
+            .jobs
+            .retain(|_, job| job.finished.is_none_or(|at| at.elapsed() < retention));
+        state
+    }
+}
+
+impl State {
+    /// Drop the oldest finished PDFs until those kept fit in `max_bytes`,
+    /// sparing job `newest`: a PDF larger than the cap on its own is still
+    /// kept until the next job finishes.
+    fn evict_results(&mut self, max_bytes: usize, newest: &str) {
+        let mut retained: usize = self.retained_bytes();
+        while retained > max_bytes {
+            let oldest: Option<String> = self
+                .jobs
+                .iter()
+                .filter(|(id, job)| job.pdf.is_some() && id.as_str() != newest)
+                .min_by_key(|(_, job)| job.finished)
+                .map(|(id, _)| id.clone());
+            let Some(oldest) = oldest else {
+                break;
+            };
+            if let Some(job) = self.jobs.remove(&oldest) {
+                retained -= job.pdf.map_or(0, |pdf| pdf.len());
+            }
+        }
+    }
+
+    fn retained_bytes(&self) -> usize {
+        self.jobs
+            .values()
+            .filter_map(|job| job.pdf.as_ref())
+            .map(Vec::len)
+            .sum()
+    }
+}
+
+/// The job queue and its workers, which stop when it is dropped.
+pub(crate) struct JobQueue {
+    shared: Arc<Shared>,
+    max_queued: usize,
+}
+
+impl JobQueue {
+    /// Start `settings.workers` workers converting queued jobs with
+    /// `convert`.
+    pub(crate) fn start(
+        settings: JobSettings,
+        convert: impl Fn(&[u8], Format, &ConvertOptions) -> JobOutcome + Send + Sync + 'static,
+    ) -> Self {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State::default()),
+            available: Condvar::new(),
+            retention: settings.retention,
+            max_retained_bytes: settings.max_retained_bytes,
+        });
+        let convert: Arc<Converter> = Arc::new(convert);
+        for _ in 0..settings.workers.max(1) {
+            let shared = Arc::clone(&shared);
+            let convert = Arc::clone(&convert);
+            std::thread::spawn(move || work(&shared, convert.as_ref()));
+        }
+        Self {
+            shared,
+            max_queued: settings.max_queued,
+        }
+    }
+
+    /// Queue a document, returning the ID of its job.
+    pub(crate) fn submit(
+        &self,
+        data: Vec<u8>,
+        format: Format,
+        options: ConvertOptions,
+    ) -> Result<String, SubmitError> {
+        let mut state = self.shared.lock_swept();
+        if state.pending.len() >= self.max_queued {
+            return Err(SubmitError::QueueFull);
+        }
+        let id: String = new_job_id().map_err(SubmitError::NoRandomness)?;
+        state.jobs.insert(
+            id.clone(),
+            Job {
+                report: JobReport {
+                    status: JobStatus::Queued,
+                    format,
+                    metrics: None,
+                },
+                pdf: None,
+                finished: None,
+            },
+        );
+        state.pending.push_back(Pending {
+            id: id.clone(),
+            data,
+            format,
+            options,
+        });
+        drop(state);
+        self.shared.available.notify_one();
+        Ok(id)
+    }
+
+    /// The job `id`, unless there is none or it expired.
+    pub(crate) fn report(&self, id: &str) -> Option<JobReport> {
+        let state = self.shared.lock_swept();
+        state.jobs.get(id).map(|job| job.report.clone())
+    }
+
+    /// The PDF of job `id`, or the status of a job without one.
+    pub(crate) fn result(&self, id: &str) -> Option<Result<Vec<u8>, JobStatus>> {
+        let state = self.shared.lock_swept();
+        let job = state.jobs.get(id)?;
+        Some(job.pdf.clone().ok_or_else(|| job.report.status.clone()))
+    }
+}
+
+impl Drop for JobQueue {
+    fn drop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.available.notify_all();
+    }
+}
+
+/// A worker: convert queued jobs one at a time until the queue shuts down.
+fn work(shared: &Shared, convert: &Converter) {
+    loop {
+        let pending: Pending = {
+            let mut state = shared.lock();
+            loop {
+                if state.shutdown {
+                    return;
+                }
+                if let Some(pending) = state.pending.pop_front() {
+                    break pending;
+                }
+                state = shared
+                    .available
+                    .wait(state)
+                    .unwrap_or_else(|e| e.into_inner());
+            }
+        };
+        set_running(shared, &pending.id);
+
+        // A panic must fail the job, not the worker: an unwound worker
+        // would leave the job `running` forever and the pool one short.
+        let outcome: JobOutcome = catch_unwind(AssertUnwindSafe(|| {
+            convert(&pending.data, pending.format, &pending.options)
+        }))
+        .unwrap_or_else(|panic| Err(format!("conversion panicked: {}", panic_message(&*panic))));
+
+        let mut state = shared.lock();
+        if let Some(job) = state.jobs.get_mut(&pending.id) {
+            match outcome {
+                Ok((pdf, metrics)) => {
+                    job.report.status = JobStatus::Done;
+                    job.report.metrics = metrics;
+                    job.pdf = Some(pdf);
+                }
+                Err(error) => job.report.status = JobStatus::Failed(error),
+            }
+            job.finished = Some(Instant::now());
+            state.evict_results(shared.max_retained_bytes, &pending.id);
+        }
+    }
+}
+
+/// The message a panic was raised with, when it has one.
+pub(crate) fn panic_message(panic: &(dyn std::any::Any + Send)) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+fn set_running(shared: &Shared, id: &str) {
+    if let Some(job) = shared.lock().jobs.get_mut(id) {
+        job.report.status = JobStatus::Running;
+    }
+}
+
+/// A job ID: 32 hex digits from the OS random source, so one client cannot
+/// fetch another's documents by guessing.
+fn new_job_id() -> Result<String, getrandom::Error> {
+    let mut bytes: [u8; 16] = [0; 16];
+    getrandom::fill(&mut bytes)?;
+    Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+#[cfg(test)]
+#[path = "jobs_tests.rs"]
+mod tests;
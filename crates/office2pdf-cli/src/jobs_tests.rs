@@ -0,0 +1,180 @@
+use super::*;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+fn settings(workers: usize, max_queued: usize, retention: Duration) -> JobSettings {
+    JobSettings {
+        workers,
+        max_queued,
+        retention,
+        max_retained_bytes: usize::MAX,
+    }
+}
+
+/// A stand-in for the library: "converts" by reversing the bytes, and fails
+/// on empty documents.
+fn reverse(data: &[u8], _format: Format, _options: &ConvertOptions) -> JobOutcome {
+    if data.is_empty() {
+        return Err("empty document".to_string());
+    }
+    Ok((data.iter().rev().copied().collect(), None))
+}
+
+/// Poll job `id` until its status passes `done`, failing after five seconds.
+fn wait_for(queue: &JobQueue, id: &str, done: impl Fn(&JobStatus) -> bool) -> JobReport {
+    let deadline: Instant = Instant::now() + Duration::from_secs(5);
+    loop {
+        let report: JobReport = queue.report(id).expect("job should exist");
+        if done(&report.status) {
+            return report;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "job stuck at {:?}",
+            report.status
+        );
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[test]
+fn test_submitted_job_converts_and_keeps_its_result() {
+    let queue = JobQueue::start(settings(2, 10, Duration::from_secs(60)), reverse);
+
+    let id: String = queue
+        .submit(b"abc".to_vec(), Format::Docx, ConvertOptions::default())
+        .unwrap();
+    let report: JobReport = wait_for(&queue, &id, |status| *status == JobStatus::Done);
+
+    assert_eq!(report.format, Format::Docx);
+    assert_eq!(queue.result(&id), Some(Ok(b"cba".to_vec())));
+    // Fetching the result does not consume it.
+    assert_eq!(queue.result(&id), Some(Ok(b"cba".to_vec())));
+}
+
+#[test]
+fn test_failed_job_reports_its_error() {
+    let queue = JobQueue::start(settings(1, 10, Duration::from_secs(60)), reverse);
+
+    let id: String = queue
+        .submit(Vec::new(), Format::Xlsx, ConvertOptions::default())
+        .unwrap();
+    let report: JobReport = wait_for(&queue, &id, |status| matches!(status, JobStatus::Failed(_)));
+
+    let failed = JobStatus::Failed("empty document".to_string());
+    assert_eq!(report.status, failed);
+    assert_eq!(queue.result(&id), Some(Err(failed)));
+}
+
+#[test]
+fn test_full_queue_turns_jobs_away_until_a_worker_frees_up() {
+    let release = Arc::new(AtomicBool::new(false));
+    let gate = Arc::clone(&release);
+    let queue = JobQueue::start(
+        settings(1, 1, Duration::from_secs(60)),
+        move |data: &[u8], format: Format, options: &ConvertOptions| {
+            while !gate.load(Ordering::SeqCst) {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            reverse(data, format, options)
+        },
+    );
+
+    let running: String = queue
+        .submit(b"1".to_vec(), Format::Pptx, ConvertOptions::default())
+        .unwrap();
+    wait_for(&queue, &running, |status| *status == JobStatus::Running);
+    let queued: String = queue
+        .submit(b"2".to_vec(), Format::Pptx, ConvertOptions::default())
+        .unwrap();
+    assert!(matches!(
+        queue.submit(b"3".to_vec(), Format::Pptx, ConvertOptions::default()),
+        Err(SubmitError::QueueFull)
+    ));
+    assert_eq!(queue.result(&queued), Some(Err(JobStatus::Queued)));
+
+    release.store(true, Ordering::SeqCst);
+    wait_for(&queue, &queued, |status| *status == JobStatus::Done);
+    assert!(
+        queue
+            .submit(b"3".to_vec(), Format::Pptx, ConvertOptions::default())
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_finished_jobs_expire_after_the_retention() {
+    let queue = JobQueue::start(settings(1, 10, Duration::from_millis(300)), reverse);
+
+    let id: String = queue
+        .submit(b"abc".to_vec(), Format::Docx, ConvertOptions::default())
+        .unwrap();
+    wait_for(&queue, &id, |status| *status == JobStatus::Done);
+    std::thread::sleep(Duration::from_millis(400));
+
+    assert!(queue.report(&id).is_none());
+    assert_eq!(queue.result(&id), None);
+}
+
+#[test]
+fn test_oldest_results_are_dropped_past_the_byte_cap() {
+    let queue = JobQueue::start(
+        JobSettings {
+            max_retained_bytes: 5,
+            ..settings(1, 10, Duration::from_secs(60))
+        },
+        reverse,
+    );
+
+    let first: String = queue
+        .submit(b"abc".to_vec(), Format::Docx, ConvertOptions::default())
+        .unwrap();
+    wait_for(&queue, &first, |status| *status == JobStatus::Done);
+    let second: String = queue
+        .submit(b"defg".to_vec(), Format::Docx, ConvertOptions::default())
+        .unwrap();
+    wait_for(&queue, &second, |status| *status == JobStatus::Done);
+
+    assert!(queue.report(&first).is_none());
+    assert_eq!(queue.result(&second), Some(Ok(b"gfed".to_vec())));
+}
+
+#[test]
+fn test_panicking_conversion_fails_the_job_and_keeps_the_worker() {
+    let queue = JobQueue::start(
+        settings(1, 10, Duration::from_secs(60)),
+        |data: &[u8], format: Format, options: &ConvertOptions| {
+            if data == b"boom" {
+                panic!("parser blew up");
+            }
+            reverse(data, format, options)
+        },
+    );
+
+    let panicked: String = queue
+        .submit(b"boom".to_vec(), Format::Docx, ConvertOptions::default())
+        .unwrap();
+    let report: JobReport = wait_for(&queue, &panicked, |status| {
+        matches!(status, JobStatus::Failed(_))
+    });
+    assert_eq!(
+        report.status,
+        JobStatus::Failed("conversion panicked: parser blew up".to_string())
+    );
+
+    // The only worker survived to convert the next job.
+    let next: String = queue
+        .submit(b"ok".to_vec(), Format::Docx, ConvertOptions::default())
+        .unwrap();
+    wait_for(&queue, &next, |status| *status == JobStatus::Done);
+}
+
+#[test]
+fn test_job_ids_are_distinct_hex() {
+    let first: String = new_job_id().unwrap();
+    let second: String = new_job_id().unwrap();
+
+    assert_eq!(first.len(), 32);
+    assert!(first.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_ne!(first, second);
+}
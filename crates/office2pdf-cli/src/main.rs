@@ -1,16 +1,43 @@
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::io::{Read, Write};
+use std::panic::{AssertUnwindSafe, catch_unwind};
 use std::path::{Path, PathBuf};
 use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, TryRecvError};
+use std::sync::{Arc, Mutex};
 
 use anyhow::{Context, Result};
 use clap::Parser;
-use office2pdf::config::{ConvertOptions, PaperSize, PdfStandard, SlideRange};
-use office2pdf::pdf_ops;
+use office2pdf::config::{
+    AssetKind, Bleed, ConvertOptions, Format, PdfSecurity, SignatureAppearance, SigningConfig,
+    SigningKey, SlideImageFormat, SlideImageOptions, SlideRange, Stamp, StampContent, StampPages,
+    StampPosition,
+};
+use office2pdf::error::{Asset, ConvertError, ConvertMetrics, ConvertResult, ConvertWarning};
+use office2pdf::{TypstOutput, pdf_ops};
 
+use config::{CONFIG_FILE, Config, Layers, Settings};
+use incremental::{Entry, Manifest, entry_for, file_name, hash_options, manifest_dir};
+use inputs::{InputFile, InputFilter, expand_inputs};
+use progress::{BatchProgress, FileProgress, Verbosity};
+use report::{FileReport, Status};
+use tolerant::{Fallback, convert_tolerant};
+
+mod config;
+mod incremental;
+mod info;
+mod inputs;
+#[cfg(feature = "server")]
+mod jobs;
 #[cfg(feature = "server")]
 mod metrics;
+mod progress;
+mod report;
 #[cfg(feature = "server")]
 mod server;
+mod tolerant;
 
 #[derive(clap::Subcommand)]
 enum Commands {
@@ -22,18 +49,240 @@ enum Commands {
         /// Output file path
         #[arg(short, long, default_value = "merged.pdf")]
         output: PathBuf,
+        /// Add a bookmark for each input, named from its title or file name,
+        /// with the input's own bookmarks under it
+        #[arg(long)]
+        bookmarks: bool,
+        /// Put a table of contents listing each input in front
+        #[arg(long)]
+        toc: bool,
     },
-    /// Split a PDF into parts by page ranges
+    /// Split a PDF into parts by page ranges, bookmarks, page count or size
     Split {
         /// Input PDF file
         input: PathBuf,
         /// Page ranges (e.g. "1-5,10-15")
-        #[arg(long, required = true, value_delimiter = ',')]
+        #[arg(long, value_delimiter = ',', group = "split_mode")]
         pages: Vec<String>,
+        /// Start a part at each top-level bookmark
+        #[arg(long, group = "split_mode")]
+        by_bookmark: bool,
+        /// Split into parts of this many pages
+        #[arg(long, value_name = "PAGES", group = "split_mode")]
+        every: Option<u32>,
+        /// Split into parts of at most this many megabytes
+        #[arg(long, value_name = "MB", group = "split_mode")]
+        max_size: Option<f64>,
+        /// File name of each part, without ".pdf": {stem}, {n}, {start},
+        /// {end} and {title} stand for the input's name, the part number,
+        /// its first and last page, and its bookmark title
+        #[arg(long, default_value = "{stem}_pages_{start}-{end}")]
+        name: String,
         /// Output directory for split files
         #[arg(long, default_value = ".")]
         outdir: PathBuf,
     },
+    /// Rotate pages of a PDF clockwise
+    Rotate {
+        /// Input PDF file
+        input: PathBuf,
+        /// Clockwise rotation: a multiple of 90, negative to turn counter-clockwise
+        #[arg(long, allow_hyphen_values = true)]
+        degrees: i32,
+        /// Page ranges to rotate (e.g. "1-3,5"); every page by default
+        #[arg(long, value_delimiter = ',')]
+        pages: Vec<String>,
+        /// Output file path
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Put the pages of a PDF in a new order
+    Reorder {
+        /// Input PDF file
+        input: PathBuf,
+        /// Every page number once, in the new order (e.g. "3,1,2")
+        #[arg(long, required = true, value_delimiter = ',')]
+        order: Vec<u32>,
+        /// Output file path
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Delete pages from a PDF
+    Delete {
+        /// Input PDF file
+        input: PathBuf,
+        /// Page ranges to delete (e.g. "2,5-7")
+        #[arg(long, required = true, value_delimiter = ',')]
+        pages: Vec<String>,
+        /// Output file path
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Draw the pages of one PDF over (or under) the pages of another
+    Overlay {
+        /// Input PDF file
+        input: PathBuf,
+        /// PDF to draw; its last page repeats on the remaining pages
+        overlay: PathBuf,
+        /// Pages to draw on: all, first, or a list such as "1,3-5"
+        #[arg(long, default_value = "all")]
+        pages: String,
+        /// Where the overlay sits: center, top-left, top, top-right,
+        /// bottom-left, bottom, bottom-right
+        #[arg(long, default_value = "center")]
+        position: String,
+        /// Size of the overlay relative to its own page size
+        #[arg(long, default_value_t = 1.0)]
+        scale: f64,
+        /// Draw the overlay under the page content, as a letterhead
+        #[arg(long)]
+        behind: bool,
+        /// Output file path
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Compare two PDFs page by page; exits with an error when they differ
+    Diff {
+        /// The PDF to compare against, such as the previous version's output
+        a: PathBuf,
+        /// The PDF to compare
+        b: PathBuf,
+        /// Also render the pages and compare them pixel by pixel
+        #[arg(long)]
+        pixels: bool,
+        /// Resolution pages are rendered at for --pixels
+        #[arg(long, default_value_t = 72.0, requires = "pixels")]
+        dpi: f32,
+        /// How far apart (0-255) pixel colors may be and still match
+        #[arg(long, default_value_t = 16, requires = "pixels")]
+        tolerance: u8,
+        /// Write a PNG marking the differing pixels of each page here
+        #[arg(long, requires = "pixels")]
+        outdir: Option<PathBuf>,
+    },
+    /// Stamp page numbers or Bates numbers onto a PDF
+    Number {
+        /// Input PDF file
+        input: PathBuf,
+        /// Stamp Bates numbers with this prefix (e.g. "ACME")
+        #[arg(long)]
+        bates: Option<String>,
+        /// Digits a Bates number is padded to
+        #[arg(long, default_value_t = 6)]
+        digits: usize,
+        /// Read "Page X of Y" rather than the number alone
+        #[arg(long, conflicts_with = "bates")]
+        of_total: bool,
+        /// Number of the first numbered page, to continue a set
+        #[arg(long, default_value_t = 1)]
+        start: u64,
+        /// Pages to number: all, first, or a list such as "2-10"
+        #[arg(long, default_value = "all")]
+        pages: String,
+        /// Number position: top-left, top, top-right, bottom-left, bottom,
+        /// bottom-right [default: bottom, or bottom-right for Bates numbers]
+        #[arg(long)]
+        position: Option<String>,
+        /// Output file path
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Encrypt a PDF with AES-256 behind a password
+    Encrypt {
+        /// Input PDF file
+        input: PathBuf,
+        /// Password needed to open the PDF; empty lets anyone open it with
+        /// only the --allow permissions
+        #[arg(long, default_value = "")]
+        user_password: String,
+        /// Password that opens the PDF with every permission
+        #[arg(long)]
+        owner_password: String,
+        /// What readers without the owner password may do: print, copy,
+        /// modify, annotate (e.g. "print,copy")
+        #[arg(long, value_delimiter = ',')]
+        allow: Vec<String>,
+        /// Output file path
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Remove the encryption of a PDF
+    Decrypt {
+        /// Input PDF file
+        input: PathBuf,
+        /// The user or owner password; empty for PDFs that only restrict
+        /// permissions
+        #[arg(long, default_value = "")]
+        password: String,
+        /// Output file path
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Digitally sign a PDF
+    #[command(group(clap::ArgGroup::new("signer").required(true).args(["p12", "key"])))]
+    Sign {
+        /// Input PDF file
+        input: PathBuf,
+        /// PKCS#12 (.p12/.pfx) file holding the key and certificate chain
+        #[arg(long, value_name = "FILE")]
+        p12: Option<PathBuf>,
+        /// Password of the --p12 file
+        #[arg(long, requires = "p12")]
+        password: Option<String>,
+        /// Unencrypted private key in PKCS#8 or PKCS#1 DER
+        #[arg(long, value_name = "FILE", requires = "cert")]
+        key: Option<PathBuf>,
+        /// DER certificate for --key: the signer's first, then its issuers
+        #[arg(long, value_name = "FILE", requires = "key")]
+        cert: Vec<PathBuf>,
+        /// Reason for signing, shown by PDF viewers
+        #[arg(long)]
+        reason: Option<String>,
+        /// Place where the document was signed, shown by PDF viewers
+        #[arg(long)]
+        location: Option<String>,
+        /// Draw the signature at PAGE:X,Y,WIDTH,HEIGHT (points from the bottom-left corner)
+        #[arg(long, value_name = "PAGE:X,Y,W,H")]
+        visible: Option<String>,
+        /// Output file path
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Render each page of a document as a PNG or SVG image, for previews
+    Rasterize {
+        /// Input file (.docx, .xlsx or .pptx)
+        input: PathBuf,
+        /// Image format: png or svg
+        #[arg(long, default_value = "png")]
+        format: String,
+        /// Resolution of PNG images, in dots per inch
+        #[arg(long, default_value_t = 144.0)]
+        dpi: f32,
+        /// Slides of a presentation to render (e.g. "2-5")
+        #[arg(long)]
+        slides: Option<String>,
+        /// Output directory for the images (default: the input's directory)
+        #[arg(long)]
+        outdir: Option<PathBuf>,
+    },
+    /// Save the images, media and fonts embedded in an Office file
+    Extract {
+        /// Input file (.docx, .xlsx or .pptx)
+        input: PathBuf,
+        /// Comma-separated kinds of files to save: images, media, fonts
+        #[arg(long, default_value = "images,fonts,media")]
+        what: String,
+        /// Output directory, with a folder per kind (default:
+        /// `<input stem>_assets` next to the input)
+        #[arg(long)]
+        outdir: Option<PathBuf>,
+    },
+    /// Show what a document holds: sheets, slides with their titles,
+    /// embedded fonts, metadata and about how many pages it converts to
+    Info {
+        /// Input file (.docx, .xlsx or .pptx)
+        input: PathBuf,
+    },
     #[cfg(feature = "server")]
     /// Start an HTTP server for document conversion
     Serve {
@@ -43,6 +292,21 @@ enum Commands {
         /// Port to listen on
         #[arg(long, default_value_t = 3000)]
         port: u16,
+        /// Requests answered at once
+        #[arg(long, default_value_t = 8)]
+        threads: usize,
+        /// Conversions `POST /jobs` runs at once (default: number of CPU cores)
+        #[arg(long, default_value_t = 0)]
+        workers: usize,
+        /// Jobs that may wait for a worker before `POST /jobs` answers 503
+        #[arg(long, default_value_t = 100)]
+        max_queued: usize,
+        /// Seconds a finished job and its PDF are kept for `GET /jobs/{id}`
+        #[arg(long, value_name = "SECS", default_value_t = 3600)]
+        job_ttl: u64,
+        /// Megabytes of finished PDFs kept; the oldest are dropped first past it
+        #[arg(long, value_name = "MB", default_value_t = 1024)]
+        max_retained_mb: usize,
     },
 }
 
@@ -58,30 +322,168 @@ struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
 
-    /// Input file paths (.docx, .xlsx, .pptx)
+    /// Input file paths (.docx, .xlsx, .pptx) or directories, or - to read
+    /// standard input
     #[arg(required = true)]
     inputs: Vec<PathBuf>,
 
-    /// Output PDF file path (only valid with a single input file)
+    /// Output PDF file path (only valid with a single input file), or - to
+    /// write standard output
     #[arg(short, long, conflicts_with = "outdir")]
     output: Option<PathBuf>,
 
-    /// Output directory for converted files
+    /// Format of standard input: docx, pptx or xlsx (detected from the
+    /// content when omitted)
+    #[arg(long)]
+    format: Option<String>,
+
+    /// Output directory for converted files; files found in directory
+    /// inputs keep their subdirectories under it
     #[arg(long)]
     outdir: Option<PathBuf>,
 
+    /// Convert the documents in subdirectories of directory inputs too
+    #[arg(short = 'r', long)]
+    recursive: bool,
+
+    /// Walk into symbolic links to directories found in directory inputs,
+    /// which are otherwise skipped
+    #[arg(long)]
+    follow_symlinks: bool,
+
+    /// Glob pattern choosing the files of directory inputs to convert, e.g.
+    /// "**/*.docx" (can be repeated; default: every .docx, .xlsx and .pptx)
+    #[arg(long)]
+    include: Vec<String>,
+
+    /// Glob pattern of files in directory inputs to leave out (can be repeated)
+    #[arg(long)]
+    exclude: Vec<String>,
+
+    /// Configuration file of conversion defaults (default: office2pdf.toml
+    /// in the working directory, if there is one)
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Configuration profile to convert with
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Ignore office2pdf.toml in the working directory
+    #[arg(long = "no-config", conflicts_with_all = ["config", "profile"])]
+    no_config: bool,
+
+    /// What to do when an output file already exists
+    #[arg(long = "if-exists", value_enum, default_value_t = ExistingOutput::Overwrite)]
+    if_exists: ExistingOutput,
+
+    /// Leave inputs whose output file exists unconverted (same as
+    /// --if-exists skip)
+    #[arg(long = "skip-existing", conflicts_with = "if_exists")]
+    skip_existing: bool,
+
+    /// Only convert inputs that changed since their last conversion, as
+    /// recorded in a .office2pdf-manifest file next to the outputs
+    #[arg(long, conflicts_with_all = ["output", "split_sections"])]
+    incremental: bool,
+
+    /// When a conversion fails, retry it without images, then without
+    /// charts, then with the text only, and tell which fallback it took
+    #[arg(long, conflicts_with = "split_sections")]
+    tolerant: bool,
+
+    /// Give up on a conversion that takes longer than SECS seconds. It runs
+    /// on in the background until the library next checks the clock, and
+    /// while 4 of them do, further conversions fail at once
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    timeout: Option<u64>,
+
+    /// Refuse inputs larger than MB megabytes, or packages whose parts
+    /// decompress to more. This bounds the input, not the memory a
+    /// conversion takes
+    #[arg(long, value_name = "MB", value_parser = clap::value_parser!(u64).range(1..))]
+    max_input_size: Option<u64>,
+
     /// XLSX sheet names to include (comma-separated, e.g. "Sheet1,Data")
     #[arg(long, value_delimiter = ',')]
     sheets: Option<Vec<String>>,
 
+    /// XLSX ranges or defined names to convert (comma-separated, e.g. "Sheet1!A1:F50,Dashboard")
+    #[arg(long, value_delimiter = ',')]
+    ranges: Option<Vec<String>>,
+
+    /// Include hidden XLSX sheets, rows, and columns
+    #[arg(long)]
+    include_hidden: bool,
+
+    /// Print light gridlines around XLSX cells, like Excel's "Print gridlines"
+    #[arg(long)]
+    gridlines: bool,
+
+    /// Print XLSX row numbers and column letters, like Excel's "Print headings"
+    #[arg(long)]
+    headings: bool,
+
+    /// Don't repeat frozen XLSX rows and columns on every page
+    #[arg(long = "ignore-frozen-panes")]
+    ignore_frozen_panes: bool,
+
+    /// Repeat the first N XLSX columns on every page of a sheet too wide for the paper
+    #[arg(long = "row-label-columns", default_value_t = 0)]
+    row_label_columns: u32,
+
+    /// Label each page of a wide XLSX sheet with the columns it shows
+    #[arg(long = "label-column-pages")]
+    label_column_pages: bool,
+
+    /// Print hidden cells of protected XLSX sheets blank
+    #[arg(long = "redact-hidden")]
+    redact_hidden: bool,
+
+    /// Print XLSX error values (e.g. "#DIV/0!") as blank cells
+    #[arg(long = "blank-errors")]
+    blank_errors: bool,
+
+    /// Mark XLSX dropdown cells and list their allowed values after each sheet
+    #[arg(long = "annotate-dropdowns")]
+    annotate_dropdowns: bool,
+
+    /// Locale for XLSX number separators and short dates (e.g. "de-DE")
+    #[arg(long)]
+    locale: Option<String>,
+
     /// PPTX slide range to include (e.g. "1-5" or "3")
     #[arg(long)]
     slides: Option<String>,
 
+    /// PPTX section names to include (comma-separated, e.g. "Intro,Results")
+    #[arg(long, value_delimiter = ',')]
+    sections: Option<Vec<String>>,
+
+    /// Write one PDF per PPTX section instead of a single PDF
+    #[arg(long = "split-sections", conflicts_with = "output")]
+    split_sections: bool,
+
     /// Produce PDF/A-2b compliant output for archival purposes
     #[arg(long = "pdf-a")]
     pdf_a: bool,
 
+    /// PDF standard to conform to: a-1b, a-2b, a-3b, or x-4
+    #[arg(long = "pdf-standard", conflicts_with = "pdf_a")]
+    pdf_standard: Option<String>,
+
+    /// Attach the input file to the PDF (with --pdf-standard, only a-3b allows it)
+    #[arg(long = "embed-source")]
+    embed_source: bool,
+
+    /// Linearize the PDF for fast web view: the first page shows before the rest downloads
+    #[arg(long, conflicts_with = "compact")]
+    linearize: bool,
+
+    /// Shrink the PDF: drop unused objects and pack the rest into compressed object streams
+    #[arg(long)]
+    compact: bool,
+
     /// Paper size for output (a4, letter, legal)
     #[arg(long)]
     paper: Option<String>,
@@ -110,21 +512,152 @@ struct Cli {
     #[arg(long, default_value = None)]
     streaming_chunk_size: Option<usize>,
 
-    /// Print per-stage timing metrics to stderr
+    /// Shape effect rendering: approximate (default) or off
+    #[arg(long)]
+    effects: Option<String>,
+
+    /// Rescale PPTX slides to a size preset: 4:3 or 16:9
+    #[arg(long)]
+    slide_size: Option<String>,
+
+    /// How --slide-size fits content: fit (default), maximize, or stretch
+    #[arg(long)]
+    slide_scale: Option<String>,
+
+    /// Draw CHAR for characters no available font has a glyph for
+    #[arg(long = "missing-glyph", value_name = "CHAR")]
+    missing_glyph: Option<char>,
+
+    /// Digitally sign the PDF with the key and certificate in a PKCS#12 (.p12/.pfx) file
+    #[arg(long, value_name = "FILE")]
+    sign: Option<PathBuf>,
+
+    /// Password of the --sign file
+    #[arg(long = "sign-password", value_name = "PASSWORD", requires = "sign")]
+    sign_password: Option<String>,
+
+    /// Reason for signing, shown by PDF viewers
+    #[arg(long = "sign-reason", requires = "sign")]
+    sign_reason: Option<String>,
+
+    /// Place where the document was signed, shown by PDF viewers
+    #[arg(long = "sign-location", requires = "sign")]
+    sign_location: Option<String>,
+
+    /// Draw the signature at PAGE:X,Y,WIDTH,HEIGHT (points from the bottom-left corner)
+    #[arg(long = "sign-visible", value_name = "PAGE:X,Y,W,H", requires = "sign")]
+    sign_visible: Option<String>,
+
+    /// Draw TEXT over every page, e.g. "DRAFT" or "DOC-{page}" for Bates numbers
+    #[arg(long, value_name = "TEXT", group = "stamp_mark")]
+    stamp: Option<String>,
+
+    /// Draw a PNG or JPEG image over every page instead of text
+    #[arg(long = "stamp-image", value_name = "FILE", group = "stamp_mark")]
+    stamp_image: Option<PathBuf>,
+
+    /// Font size of the stamp text, or width of the stamp image, in points
+    #[arg(long = "stamp-size", value_name = "PT", requires = "stamp_mark")]
+    stamp_size: Option<f64>,
+
+    /// Stamp position: center, top-left, top, top-right, bottom-left, bottom, bottom-right
+    #[arg(long = "stamp-position", requires = "stamp_mark")]
+    stamp_position: Option<String>,
+
+    /// Counter-clockwise stamp rotation in degrees
+    #[arg(
+        long = "stamp-rotation",
+        value_name = "DEGREES",
+        requires = "stamp_mark"
+    )]
+    stamp_rotation: Option<f64>,
+
+    /// Stamp opacity from 0 to 1
+    #[arg(long = "stamp-opacity", requires = "stamp_mark")]
+    stamp_opacity: Option<f64>,
+
+    /// Pages to stamp: all, first, or a list such as "1,3-5"
+    #[arg(long = "stamp-pages", requires = "stamp_mark")]
+    stamp_pages: Option<String>,
+
+    /// Extend every page by a bleed margin in points (9 is an eighth of an inch) for printing
+    #[arg(long, value_name = "PT")]
+    bleed: Option<f64>,
+
+    /// Draw crop marks at the corners of every page, outside the bleed
+    #[arg(long = "crop-marks")]
+    crop_marks: bool,
+
+    /// Print per-stage timing metrics to stderr (same as -vv)
     #[arg(long)]
     metrics: bool,
 
+    /// Print failures only: no progress bars, per-file lines or summary
+    #[arg(short, long, conflicts_with_all = ["verbose", "metrics"])]
+    quiet: bool,
+
+    /// Print each file's warnings (-v), and its timing metrics too (-vv)
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Print a JSON object per input on standard output instead of the
+    /// human-readable messages: status, output, page count, warnings with
+    /// their codes, and metrics
+    #[arg(long, conflicts_with = "split_sections")]
+    json: bool,
+
+    /// Also write the generated Typst markup and its images to DIR/<name>/
+    #[arg(long = "emit-typst", value_name = "DIR")]
+    emit_typst: Option<PathBuf>,
+
     /// Number of parallel conversion jobs (default: number of CPU cores)
     #[arg(short = 'j', long, default_value_t = 0)]
     jobs: usize,
 }
 
+/// What to do about an output file that already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+enum ExistingOutput {
+    /// Replace it.
+    #[default]
+    Overwrite,
+    /// Leave it, and the input unconverted.
+    Skip,
+    /// Fail the input.
+    Error,
+}
+
+/// How [`convert_batch`] runs, besides the conversion options.
+#[derive(Debug, Clone, Default)]
+struct BatchSettings<'a> {
+    outdir: Option<&'a Path>,
+    verbosity: Verbosity,
+    emit_typst: Option<&'a Path>,
+    /// Parallel conversions; 0 for one per CPU core.
+    jobs: usize,
+    if_exists: ExistingOutput,
+    /// Leave inputs unchanged since the manifest of their output directory
+    /// recorded them unconverted.
+    incremental: bool,
+    /// Retry failed conversions with less of the document.
+    tolerant: bool,
+    /// Report each input as a line of JSON on standard output.
+    json: bool,
+    /// Settings from the configuration file, for inputs its patterns match.
+    layers: Option<&'a Layers>,
+}
+
 /// Result of a batch conversion.
 struct BatchResult {
     /// Successfully converted files: (input, output) pairs.
     succeeded: Vec<(PathBuf, PathBuf)>,
+    /// Files left unconverted because their output exists: (input, output)
+    /// pairs.
+    skipped: Vec<(PathBuf, PathBuf)>,
     /// Failed files: (input, error message) pairs.
     failed: Vec<(PathBuf, String)>,
+    /// Files of `succeeded` converted only with a fallback of `--tolerant`.
+    degraded: Vec<(PathBuf, Fallback)>,
 }
 
 fn main() {
@@ -146,25 +679,250 @@ fn determine_output_path(input: &Path, output: Option<&Path>, outdir: Option<&Pa
     }
 }
 
-/// Convert a single file and write the PDF output.
+/// The output path for a file of a batch: one found in a directory input
+/// keeps its place under the directory in `outdir`.
+fn batch_output_path(input: &InputFile, outdir: Option<&Path>) -> PathBuf {
+    match (outdir, &input.relative) {
+        (Some(dir), Some(relative)) => dir.join(relative).with_extension("pdf"),
+        _ => determine_output_path(&input.path, None, outdir),
+    }
+}
+
+/// Write the Typst sources of a conversion to `dir/<input stem>/` as
+/// `main.typ` next to its images, so `typst compile main.typ` reproduces the
+/// PDF. Streaming conversions compile in parts, one `part-NN` directory each.
+fn write_typst_sources(input: &Path, dir: &Path, sources: &[TypstOutput]) -> Result<PathBuf> {
+    let root: PathBuf = dir.join(input.file_stem().unwrap_or_default());
+    for (index, source) in sources.iter().enumerate() {
+        let part_dir: PathBuf = if sources.len() == 1 {
+            root.clone()
+        } else {
+            root.join(format!("part-{:02}", index + 1))
+        };
+        std::fs::create_dir_all(&part_dir)
+            .with_context(|| format!("creating Typst output directory {:?}", part_dir))?;
+        let main_path: PathBuf = part_dir.join("main.typ");
+        std::fs::write(&main_path, &source.source)
+            .with_context(|| format!("writing {:?}", main_path))?;
+        for image in &source.images {
+            let image_path: PathBuf = part_dir.join(&image.path);
+            std::fs::write(&image_path, &image.data)
+                .with_context(|| format!("writing {:?}", image_path))?;
+        }
+    }
+    Ok(root)
+}
+
+/// The path standing for standard input as an input, or standard output as
+/// the output.
+const STDIO: &str = "-";
+
+fn is_stdio(path: &Path) -> bool {
+    path == Path::new(STDIO)
+}
+
+/// What converting an input reported besides its PDF.
+struct Conversion {
+    warnings: Vec<ConvertWarning>,
+    metrics: Option<ConvertMetrics>,
+    /// What `--tolerant` left out of the input to convert it.
+    fallback: Option<Fallback>,
+}
+
+/// Convert a single file and write the PDF output, and its Typst sources
+/// under `emit_typst` when given.
 fn convert_single(
     input: &Path,
     output: &Path,
     options: &ConvertOptions,
-    show_metrics: bool,
-) -> Result<()> {
-    let result = office2pdf::convert_with_options(input, options)
-        .with_context(|| format!("converting {:?}", input))?;
+    emit_typst: Option<&Path>,
+) -> Result<Conversion> {
+    let path: PathBuf = input.to_path_buf();
+    let result = convert_in_time(options, move |options| {
+        office2pdf::convert_with_options(&path, options)
+    })
+    .with_context(|| format!("converting {:?}", input))?;
+    finish_single(input, output, result, emit_typst)
+}
+
+/// Conversions given up on that are still running, and how many may be.
+struct Abandoned {
+    running: AtomicUsize,
+    max: usize,
+}
+
+/// Each conversion given up on holds a core and its memory until the
+/// library next checks the clock, so past a few of them a batch of
+/// pathological files would starve the rest; conversions then fail at once
+/// instead of piling more up.
+static ABANDONED: Abandoned = Abandoned {
+    running: AtomicUsize::new(0),
+    max: 4,
+};
+
+/// Run `convert`, on a thread of its own when `options` set a timeout so
+/// that it can be given up on: the library checks the clock only between
+/// pipeline stages, and a stage that runs away would hold up a whole
+/// batch. The thread given up on runs on to its next check, but nothing it
+/// produces is written.
+fn convert_in_time(
+    options: &ConvertOptions,
+    convert: impl FnOnce(&ConvertOptions) -> Result<ConvertResult, ConvertError> + Send + 'static,
+) -> Result<ConvertResult, ConvertError> {
+    convert_in_time_with(&ABANDONED, options, convert)
+}
 
-    let mut seen_warnings = HashSet::new();
-    for warning in &result.warnings {
-        let rendered = warning.to_string();
-        if seen_warnings.insert(rendered.clone()) {
-            eprintln!("Warning: {rendered}");
+fn convert_in_time_with(
+    abandoned: &'static Abandoned,
+    options: &ConvertOptions,
+    convert: impl FnOnce(&ConvertOptions) -> Result<ConvertResult, ConvertError> + Send + 'static,
+) -> Result<ConvertResult, ConvertError> {
+    let Some(timeout) = options.limits.timeout else {
+        return convert(options);
+    };
+    let running: usize = abandoned.running.load(Ordering::SeqCst);
+    if running >= abandoned.max {
+        return Err(ConvertError::LimitExceeded(format!(
+            "{running} conversions that timed out are still running"
+        )));
+    }
+    let options: ConvertOptions = options.clone();
+    let (sender, receiver) = mpsc::channel();
+    // Set once the conversion is given up on. The thread sends its result
+    // with the lock held, so the result is either sent before the timeout
+    // takes the lock or the thread sees it was given up on.
+    let given_up: Arc<Mutex<bool>> = Arc::new(Mutex::new(false));
+    let watched: Arc<Mutex<bool>> = Arc::clone(&given_up);
+    std::thread::Builder::new()
+        .name("office2pdf-convert".to_string())
+        .spawn(move || {
+            // Caught so that a panic after the timeout still gives the
+            // slot back; dropping the sender reports it otherwise.
+            let result = catch_unwind(AssertUnwindSafe(|| convert(&options)));
+            let given_up = watched.lock().unwrap_or_else(|e| e.into_inner());
+            if *given_up {
+                abandoned.running.fetch_sub(1, Ordering::SeqCst);
+            } else if let Ok(result) = result {
+                let _ = sender.send(result);
+            }
+        })?;
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => {
+            let mut given_up = given_up.lock().unwrap_or_else(|e| e.into_inner());
+            // The conversion may have finished since the timeout.
+            match receiver.try_recv() {
+                Ok(result) => return result,
+                Err(TryRecvError::Disconnected) => {
+                    return Err(ConvertError::Render("the conversion panicked".to_string()));
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+            *given_up = true;
+            abandoned.running.fetch_add(1, Ordering::SeqCst);
+            Err(ConvertError::LimitExceeded(format!(
+                "conversion took longer than {} ms",
+                timeout.as_millis()
+            )))
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            Err(ConvertError::Render("the conversion panicked".to_string()))
         }
     }
+}
+
+/// Convert a single file as [`convert_single`] does, with the fallbacks of
+/// `--tolerant` when `tolerant`.
+fn convert_file(
+    input: &Path,
+    output: &Path,
+    options: &ConvertOptions,
+    emit_typst: Option<&Path>,
+    tolerant: bool,
+) -> Result<Conversion> {
+    let (mut conversion, fallback) = convert_tolerant(tolerant, options, |options| {
+        convert_single(input, output, options, emit_typst)
+    })?;
+    conversion.fallback = fallback;
+    Ok(conversion)
+}
 
-    if show_metrics && let Some(ref m) = result.metrics {
+/// Convert standard input, of `format` or of the format its content
+/// shows, as [`convert_single`] converts a file, with the fallbacks of
+/// `--tolerant` when `tolerant`.
+fn convert_stdin(
+    format: Option<Format>,
+    output: &Path,
+    options: &ConvertOptions,
+    emit_typst: Option<&Path>,
+    tolerant: bool,
+) -> Result<Conversion> {
+    let mut data: Vec<u8> = Vec::new();
+    std::io::stdin()
+        .read_to_end(&mut data)
+        .context("reading standard input")?;
+    let data: Arc<Vec<u8>> = Arc::new(data);
+    let (mut conversion, fallback) = convert_tolerant(tolerant, options, |options| {
+        let data: Arc<Vec<u8>> = Arc::clone(&data);
+        let result = convert_in_time(options, move |options| {
+            convert_input_bytes(&data, format, options)
+        })
+        .context("converting standard input")?;
+        // Names the directory of the Typst sources.
+        finish_single(Path::new("stdin"), output, result, emit_typst)
+    })?;
+    conversion.fallback = fallback;
+    Ok(conversion)
+}
+
+fn convert_input_bytes(
+    data: &[u8],
+    format: Option<Format>,
+    options: &ConvertOptions,
+) -> Result<ConvertResult, ConvertError> {
+    match format {
+        Some(format) => office2pdf::convert_bytes(data, format, options),
+        None => office2pdf::convert_bytes_auto(data, options),
+    }
+}
+
+/// Write the PDF of converting `input` to `output` and the Typst sources
+/// under `emit_typst`.
+fn finish_single(
+    input: &Path,
+    output: &Path,
+    result: ConvertResult,
+    emit_typst: Option<&Path>,
+) -> Result<Conversion> {
+    if let Some(dir) = emit_typst {
+        let written: PathBuf = write_typst_sources(input, dir, &result.typst_sources)?;
+        eprintln!("Typst source: {:?}", written);
+    }
+
+    write_output(output, &result.pdf)?;
+    Ok(Conversion {
+        warnings: result.warnings,
+        metrics: result.metrics,
+        fallback: None,
+    })
+}
+
+/// Print the warnings of converting `input` from `-v` up, and its metrics
+/// from `-vv`, on standard error.
+fn print_diagnostics(input: &Path, conversion: &Conversion, verbosity: Verbosity) {
+    if verbosity >= Verbosity::Verbose {
+        let mut seen_warnings = HashSet::new();
+        for warning in &conversion.warnings {
+            let rendered = warning.to_string();
+            if seen_warnings.insert(rendered.clone()) {
+                eprintln!("Warning: {rendered}");
+            }
+        }
+    }
+
+    if verbosity >= Verbosity::Debug
+        && let Some(ref m) = conversion.metrics
+    {
         eprintln!("--- Metrics: {:?} ---", input);
         eprintln!("  Parse:   {:?}", m.parse_duration);
         eprintln!("  Codegen: {:?}", m.codegen_duration);
@@ -174,24 +932,342 @@ fn convert_single(
         eprintln!("  Output:  {} bytes", m.output_size_bytes);
         eprintln!("  Pages:   {}", m.page_count);
     }
+}
 
-    std::fs::write(output, result.pdf)
-        .with_context(|| format!("writing output to {:?}", output))?;
+/// Report a lone input as a line of JSON, passing its error on.
+fn report_json(input: &Path, output: &Path, conversion: Result<Conversion>) -> Result<()> {
+    match conversion {
+        Ok(conversion) => {
+            report_file(
+                true,
+                Verbosity::Normal,
+                input,
+                Some(output),
+                Status::Converted,
+                Some(&conversion),
+            );
+            Ok(())
+        }
+        Err(err) => {
+            let status = Status::Failed(format!("{err:#}"));
+            report_file(true, Verbosity::Normal, input, Some(output), status, None);
+            Err(err)
+        }
+    }
+}
 
-    Ok(())
+/// Tell how `input` went: as a line of JSON on standard output with
+/// `json`, or else in words, after its diagnostics; only failures when
+/// quiet.
+fn report_file(
+    json: bool,
+    verbosity: Verbosity,
+    input: &Path,
+    output: Option<&Path>,
+    status: Status,
+    conversion: Option<&Conversion>,
+) {
+    if json {
+        let report = FileReport {
+            input,
+            output,
+            status,
+            warnings: conversion.map_or(&[][..], |conversion| conversion.warnings.as_slice()),
+            metrics: conversion.and_then(|conversion| conversion.metrics.as_ref()),
+            fallback: conversion.and_then(|conversion| conversion.fallback),
+        };
+        println!("{}", report.to_json());
+        return;
+    }
+
+    if verbosity == Verbosity::Quiet && !matches!(status, Status::Failed(_)) {
+        return;
+    }
+    if let Some(conversion) = conversion {
+        print_diagnostics(input, conversion, verbosity);
+    }
+
+    let output: &Path = output.unwrap_or(Path::new(""));
+    let fallback: String = conversion
+        .map(|conversion| conversion_notes(conversion, verbosity))
+        .unwrap_or_default();
+    match status {
+        // Standard output carries the PDF itself.
+        Status::Converted if is_stdio(output) => {
+            eprintln!("Converted: {:?} -> standard output{fallback}", input)
+        }
+        Status::Converted => println!("Converted: {:?} -> {:?}{fallback}", input, output),
+        Status::Skipped => println!("Skipped: {:?} ({:?} exists)", input, output),
+        Status::Unchanged => println!("Unchanged: {:?} -> {:?}", input, output),
+        Status::Failed(error) => eprintln!("Failed: {:?}: {error}", input),
+    }
+}
+
+/// What a "Converted" line adds about `conversion`, such as
+/// " (without images; 2 warnings)": the fallback it took and, unless they
+/// were printed, how many warnings it had.
+fn conversion_notes(conversion: &Conversion, verbosity: Verbosity) -> String {
+    let mut notes: Vec<String> = Vec::new();
+    if let Some(fallback) = conversion.fallback {
+        notes.push(fallback.to_string());
+    }
+    let warnings: usize = conversion.warnings.len();
+    if verbosity < Verbosity::Verbose && warnings > 0 {
+        let plural: &str = if warnings == 1 { "" } else { "s" };
+        notes.push(format!("{warnings} warning{plural}"));
+    }
+    if notes.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", notes.join("; "))
+    }
+}
+
+/// Write `data` to the file `output`, or to standard output for `-`.
+fn write_output(output: &Path, data: &[u8]) -> Result<()> {
+    if is_stdio(output) {
+        let mut stdout = std::io::stdout().lock();
+        stdout
+            .write_all(data)
+            .and_then(|()| stdout.flush())
+            .context("writing to standard output")
+    } else {
+        std::fs::write(output, data).with_context(|| format!("writing output to {:?}", output))
+    }
+}
+
+/// File name for one section of a split presentation: `deck_section_02_Results.pdf`.
+/// Characters that are unsafe in file names are replaced with `_`.
+fn section_file_name(stem: &str, index: usize, name: &str) -> String {
+    let safe_name: String = name
+        .chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{stem}_section_{:02}_{safe_name}.pdf", index + 1)
+}
+
+/// Convert a PPTX file into one PDF per section, next to the input or in `outdir`.
+fn convert_sections(
+    input: &Path,
+    outdir: Option<&Path>,
+    options: &ConvertOptions,
+    verbosity: Verbosity,
+) -> Result<Vec<PathBuf>> {
+    let data = std::fs::read(input).with_context(|| format!("reading {:?}", input))?;
+    let result = office2pdf::convert_pptx_sections(&data, options)
+        .with_context(|| format!("converting {:?}", input))?;
+
+    if verbosity >= Verbosity::Verbose {
+        for warning in &result.warnings {
+            eprintln!("Warning: {warning}");
+        }
+    } else if verbosity == Verbosity::Normal && !result.warnings.is_empty() {
+        eprintln!(
+            "{:?}: {} warnings (-v shows them)",
+            input,
+            result.warnings.len()
+        );
+    }
+    if result.sections.is_empty() {
+        anyhow::bail!("{:?} has no sections to split", input);
+    }
+
+    let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+    let dir: &Path = outdir.unwrap_or_else(|| input.parent().unwrap_or(Path::new("")));
+    let mut written: Vec<PathBuf> = Vec::with_capacity(result.sections.len());
+    for (index, section) in result.sections.iter().enumerate() {
+        let out_path = dir.join(section_file_name(&stem, index, &section.name));
+        std::fs::write(&out_path, &section.pdf)
+            .with_context(|| format!("writing {:?}", out_path))?;
+        written.push(out_path);
+    }
+    Ok(written)
+}
+
+/// File name for one page rendered as an image: `report_page_03.png`.
+fn page_image_file_name(stem: &str, index: usize, format: SlideImageFormat) -> String {
+    format!("{stem}_page_{:02}.{}", index + 1, format.extension())
+}
+
+/// Render each page of `input` as an image, next to the input or in
+/// `outdir`.
+fn rasterize(
+    input: &Path,
+    outdir: Option<&Path>,
+    options: &SlideImageOptions,
+) -> Result<Vec<PathBuf>> {
+    let data = std::fs::read(input).with_context(|| format!("reading {:?}", input))?;
+    let format: Format = document_format(input, &data)?;
+    let images = office2pdf::render_to_images(&data, format, options)
+        .with_context(|| format!("rendering {:?}", input))?;
+
+    let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+    let dir: &Path = outdir.unwrap_or_else(|| input.parent().unwrap_or(Path::new("")));
+    if !dir.as_os_str().is_empty() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("creating output directory {:?}", dir))?;
+    }
+    let mut written: Vec<PathBuf> = Vec::with_capacity(images.len());
+    for image in &images {
+        let out_path = dir.join(page_image_file_name(&stem, image.page_index, image.format));
+        std::fs::write(&out_path, &image.data)
+            .with_context(|| format!("writing {:?}", out_path))?;
+        written.push(out_path);
+    }
+    Ok(written)
+}
+
+/// The format of the document `data` read from `input`: by content first,
+/// as conversion goes, so a misnamed file is still read.
+fn document_format(input: &Path, data: &[u8]) -> Result<Format> {
+    Format::detect(data)
+        .or_else(|| {
+            let ext = input.extension()?.to_str()?;
+            Format::from_extension(ext)
+        })
+        .ok_or_else(|| anyhow::anyhow!("{:?} is not a DOCX, PPTX or XLSX document", input))
+}
+
+/// Save the files of `kinds` embedded in `input`, each kind in its own
+/// folder of `outdir` (by default `<stem>_assets` next to the input).
+fn extract(input: &Path, outdir: Option<&Path>, kinds: &[AssetKind]) -> Result<Vec<PathBuf>> {
+    let data = std::fs::read(input).with_context(|| format!("reading {:?}", input))?;
+    let format: Format = document_format(input, &data)?;
+    let assets: Vec<Asset> = office2pdf::extract_assets(&data, format, kinds)
+        .with_context(|| format!("reading {:?}", input))?;
+
+    let dir: PathBuf = match outdir {
+        Some(outdir) => outdir.to_path_buf(),
+        None => {
+            let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+            input.with_file_name(format!("{stem}_assets"))
+        }
+    };
+    let mut written: Vec<PathBuf> = Vec::with_capacity(assets.len());
+    for asset in &assets {
+        let kind_dir: PathBuf = dir.join(asset.kind.name());
+        std::fs::create_dir_all(&kind_dir)
+            .with_context(|| format!("creating output directory {:?}", kind_dir))?;
+        let out_path: PathBuf = kind_dir.join(&asset.name);
+        std::fs::write(&out_path, &asset.data)
+            .with_context(|| format!("writing {:?}", out_path))?;
+        written.push(out_path);
+    }
+    Ok(written)
+}
+
+/// Parse a comma-separated list of asset kinds, such as "images,fonts".
+fn parse_asset_kinds(what: &str) -> Result<Vec<AssetKind>> {
+    let mut kinds: Vec<AssetKind> = Vec::new();
+    for name in what
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        let kind: AssetKind = AssetKind::parse(name).map_err(|e| anyhow::anyhow!("{e}"))?;
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+    if kinds.is_empty() {
+        anyhow::bail!("--what names no kind of file to extract");
+    }
+    Ok(kinds)
+}
+
+/// A signature by `key`, invisible unless `visible` places it
+/// (`PAGE:X,Y,W,H`).
+fn signing_config(
+    key: SigningKey,
+    reason: Option<String>,
+    location: Option<String>,
+    visible: Option<String>,
+) -> Result<SigningConfig> {
+    let appearance = visible
+        .map(|s| SignatureAppearance::parse(&s))
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    Ok(SigningConfig {
+        key,
+        reason,
+        location,
+        contact_info: None,
+        appearance,
+    })
+}
+
+/// The passwords and permissions of `office2pdf encrypt`.
+fn pdf_security(
+    user_password: String,
+    owner_password: String,
+    allow: &[String],
+) -> Result<PdfSecurity> {
+    let mut security = PdfSecurity {
+        user_password,
+        owner_password,
+        ..Default::default()
+    };
+    for permission in allow {
+        match permission.trim().to_ascii_lowercase().as_str() {
+            "print" => security.allow_print = true,
+            "copy" => security.allow_copy = true,
+            "modify" => security.allow_modify = true,
+            "annotate" => security.allow_annotate = true,
+            _ => anyhow::bail!(
+                "unknown permission: {permission}; expected one of: print, copy, modify, annotate"
+            ),
+        }
+    }
+    Ok(security)
+}
+
+/// Parse page range arguments such as "1-5" or "3".
+fn parse_page_ranges(pages: &[String]) -> Result<Vec<pdf_ops::PageRange>> {
+    pages
+        .iter()
+        .map(|s| {
+            pdf_ops::PageRange::parse(s)
+                .map_err(|e| anyhow::anyhow!("invalid page range '{s}': {e}"))
+        })
+        .collect()
 }
 
 /// Handle a CLI subcommand.
 fn handle_command(cmd: Commands) -> Result<()> {
     match cmd {
-        Commands::Merge { files, output } => {
+        Commands::Merge {
+            files,
+            output,
+            bookmarks,
+            toc,
+        } => {
             let inputs: Vec<Vec<u8>> = files
                 .iter()
                 .map(|f| std::fs::read(f).with_context(|| format!("reading {:?}", f)))
                 .collect::<Result<_>>()?;
 
             let refs: Vec<&[u8]> = inputs.iter().map(|v| v.as_slice()).collect();
-            let merged = pdf_ops::merge(&refs).map_err(|e| anyhow::anyhow!("{e}"))?;
+            let options = pdf_ops::MergeOptions {
+                bookmarks,
+                names: files
+                    .iter()
+                    .map(|f| {
+                        f.file_stem()
+                            .unwrap_or_default()
+                            .to_string_lossy()
+                            .into_owned()
+                    })
+                    .collect(),
+                table_of_contents: toc,
+            };
+            let merged =
+                pdf_ops::merge_with_options(&refs, &options).map_err(|e| anyhow::anyhow!("{e}"))?;
 
             std::fs::write(&output, merged)
                 .with_context(|| format!("writing output to {:?}", output))?;
@@ -202,77 +1278,483 @@ fn handle_command(cmd: Commands) -> Result<()> {
         Commands::Split {
             input,
             pages,
+            by_bookmark,
+            every,
+            max_size,
+            name,
             outdir,
         } => {
             let data = std::fs::read(&input).with_context(|| format!("reading {:?}", input))?;
 
-            let ranges: Vec<pdf_ops::PageRange> = pages
-                .iter()
-                .map(|s| {
-                    pdf_ops::PageRange::parse(s)
-                        .map_err(|e| anyhow::anyhow!("invalid page range '{s}': {e}"))
-                })
-                .collect::<Result<_>>()?;
-
-            let parts = pdf_ops::split(&data, &ranges).map_err(|e| anyhow::anyhow!("{e}"))?;
+            let parts: Vec<pdf_ops::SplitPart> = if !pages.is_empty() {
+                let ranges: Vec<pdf_ops::PageRange> = parse_page_ranges(&pages)?;
+                let parts = pdf_ops::split(&data, &ranges).map_err(|e| anyhow::anyhow!("{e}"))?;
+                ranges
+                    .into_iter()
+                    .zip(parts)
+                    .map(|(pages, pdf)| pdf_ops::SplitPart {
+                        pages,
+                        title: None,
+                        pdf,
+                    })
+                    .collect()
+            } else {
+                let by: pdf_ops::SplitBy = if by_bookmark {
+                    pdf_ops::SplitBy::Bookmarks
+                } else if let Some(count) = every {
+                    pdf_ops::SplitBy::PageCount(count)
+                } else if let Some(megabytes) = max_size {
+                    if !megabytes.is_finite() || megabytes <= 0.0 {
+                        anyhow::bail!("--max-size must be positive, got {megabytes}");
+                    }
+                    pdf_ops::SplitBy::MaxBytes((megabytes * 1024.0 * 1024.0) as u64)
+                } else {
+                    anyhow::bail!("give --pages, --by-bookmark, --every or --max-size");
+                };
+                pdf_ops::split_by(&data, &by).map_err(|e| anyhow::anyhow!("{e}"))?
+            };
 
             std::fs::create_dir_all(&outdir)
                 .with_context(|| format!("creating output directory {:?}", outdir))?;
 
             let stem = input.file_stem().unwrap_or_default().to_string_lossy();
 
-            for (i, (part, range)) in parts.iter().zip(ranges.iter()).enumerate() {
-                let filename = format!("{}_pages_{}-{}.pdf", stem, range.start, range.end);
+            for (i, part) in parts.iter().enumerate() {
+                let filename = part.file_name(&name, &stem, i + 1);
                 let out_path = outdir.join(&filename);
-                std::fs::write(&out_path, part)
+                std::fs::write(&out_path, &part.pdf)
                     .with_context(|| format!("writing {:?}", out_path))?;
                 println!(
                     "Split part {} (pages {}-{}) -> {:?}",
                     i + 1,
-                    range.start,
-                    range.end,
+                    part.pages.start,
+                    part.pages.end,
                     out_path
                 );
             }
             Ok(())
         }
+        Commands::Rotate {
+            input,
+            degrees,
+            pages,
+            output,
+        } => {
+            let data = std::fs::read(&input).with_context(|| format!("reading {:?}", input))?;
+            let mut ranges: Vec<pdf_ops::PageRange> = parse_page_ranges(&pages)?;
+            if ranges.is_empty() {
+                let total = pdf_ops::page_count(&data).map_err(|e| anyhow::anyhow!("{e}"))?;
+                ranges.push(pdf_ops::PageRange::new(1, total));
+            }
+            let rotated =
+                pdf_ops::rotate(&data, &ranges, degrees).map_err(|e| anyhow::anyhow!("{e}"))?;
+            std::fs::write(&output, rotated)
+                .with_context(|| format!("writing output to {:?}", output))?;
+            println!("Rotated {:?} by {degrees} degrees -> {:?}", input, output);
+            Ok(())
+        }
+        Commands::Reorder {
+            input,
+            order,
+            output,
+        } => {
+            let data = std::fs::read(&input).with_context(|| format!("reading {:?}", input))?;
+            let reordered = pdf_ops::reorder(&data, &order).map_err(|e| anyhow::anyhow!("{e}"))?;
+            std::fs::write(&output, reordered)
+                .with_context(|| format!("writing output to {:?}", output))?;
+            println!("Reordered {:?} -> {:?}", input, output);
+            Ok(())
+        }
+        Commands::Delete {
+            input,
+            pages,
+            output,
+        } => {
+            let data = std::fs::read(&input).with_context(|| format!("reading {:?}", input))?;
+            let ranges: Vec<pdf_ops::PageRange> = parse_page_ranges(&pages)?;
+            let trimmed = pdf_ops::delete(&data, &ranges).map_err(|e| anyhow::anyhow!("{e}"))?;
+            std::fs::write(&output, trimmed)
+                .with_context(|| format!("writing output to {:?}", output))?;
+            println!(
+                "Deleted pages {} of {:?} -> {:?}",
+                pages.join(","),
+                input,
+                output
+            );
+            Ok(())
+        }
+        Commands::Overlay {
+            input,
+            overlay,
+            pages,
+            position,
+            scale,
+            behind,
+            output,
+        } => {
+            let data = std::fs::read(&input).with_context(|| format!("reading {:?}", input))?;
+            let layer =
+                std::fs::read(&overlay).with_context(|| format!("reading {:?}", overlay))?;
+            let options = pdf_ops::OverlayOptions {
+                pages: StampPages::parse(&pages)
+                    .map_err(|e| anyhow::anyhow!("invalid --pages value: {e}"))?,
+                position: StampPosition::parse(&position)
+                    .map_err(|e| anyhow::anyhow!("invalid --position value: {e}"))?,
+                scale,
+                behind,
+            };
+            let combined =
+                pdf_ops::overlay(&data, &layer, &options).map_err(|e| anyhow::anyhow!("{e}"))?;
+            std::fs::write(&output, combined)
+                .with_context(|| format!("writing output to {:?}", output))?;
+            println!("Overlaid {:?} on {:?} -> {:?}", overlay, input, output);
+            Ok(())
+        }
+        Commands::Diff {
+            a,
+            b,
+            pixels,
+            dpi,
+            tolerance,
+            outdir,
+        } => {
+            let data_a = std::fs::read(&a).with_context(|| format!("reading {:?}", a))?;
+            let data_b = std::fs::read(&b).with_context(|| format!("reading {:?}", b))?;
+            let options = pdf_ops::DiffOptions {
+                pixels,
+                dpi,
+                tolerance,
+            };
+            let report =
+                pdf_ops::diff(&data_a, &data_b, &options).map_err(|e| anyhow::anyhow!("{e}"))?;
+            if let Some(outdir) = &outdir {
+                std::fs::create_dir_all(outdir)
+                    .with_context(|| format!("creating output directory {:?}", outdir))?;
+            }
+            if report.pages_a != report.pages_b {
+                println!(
+                    "Page count: {} in {:?}, {} in {:?}",
+                    report.pages_a, a, report.pages_b, b
+                );
+            }
+            for page in &report.pages {
+                println!("Page {}:", page.page);
+                for change in &page.text {
+                    match change {
+                        pdf_ops::TextChange::Removed(line) => println!("  - {line}"),
+                        pdf_ops::TextChange::Added(line) => println!("  + {line}"),
+                    }
+                }
+                if let Some(pixels) = page.pixels.as_ref().filter(|pixels| pixels.differing > 0) {
+                    println!(
+                        "  {} of {} pixels differ ({:.2}%)",
+                        pixels.differing,
+                        pixels.total,
+                        pixels.differing as f64 * 100.0 / pixels.total as f64
+                    );
+                    if let Some(outdir) = &outdir {
+                        let out_path = outdir.join(format!("page-{}.png", page.page));
+                        std::fs::write(&out_path, &pixels.image)
+                            .with_context(|| format!("writing {:?}", out_path))?;
+                        println!("  -> {:?}", out_path);
+                    }
+                }
+            }
+            if !report.is_identical() {
+                anyhow::bail!("{:?} and {:?} differ", a, b);
+            }
+            println!("{:?} and {:?} match", a, b);
+            Ok(())
+        }
+        Commands::Rasterize {
+            input,
+            format,
+            dpi,
+            slides,
+            outdir,
+        } => {
+            let options = SlideImageOptions {
+                format: SlideImageFormat::parse(&format).map_err(|e| anyhow::anyhow!("{e}"))?,
+                dpi,
+                convert: ConvertOptions {
+                    slide_range: slides
+                        .map(|slides| SlideRange::parse(&slides))
+                        .transpose()
+                        .map_err(|e| anyhow::anyhow!("invalid --slides value: {e}"))?,
+                    ..Default::default()
+                },
+            };
+            let written: Vec<PathBuf> = rasterize(&input, outdir.as_deref(), &options)?;
+            if written.is_empty() {
+                anyhow::bail!("{:?} has no pages to render", input);
+            }
+            for (index, path) in written.iter().enumerate() {
+                println!("Rendered page {} of {:?} -> {:?}", index + 1, input, path);
+            }
+            Ok(())
+        }
+        Commands::Extract {
+            input,
+            what,
+            outdir,
+        } => {
+            let kinds: Vec<AssetKind> = parse_asset_kinds(&what)?;
+            let written: Vec<PathBuf> = extract(&input, outdir.as_deref(), &kinds)?;
+            if written.is_empty() {
+                println!("No embedded {what} in {:?}", input);
+            }
+            for path in &written {
+                println!("Extracted {:?}", path);
+            }
+            Ok(())
+        }
+        Commands::Info { input } => {
+            let data = std::fs::read(&input).with_context(|| format!("reading {:?}", input))?;
+            let format: Format = document_format(&input, &data)?;
+            let summary = office2pdf::document_info(&data, format, &ConvertOptions::default())
+                .with_context(|| format!("reading {:?}", input))?;
+            print!("{}", info::describe(&input.display().to_string(), &summary));
+            Ok(())
+        }
+        Commands::Encrypt {
+            input,
+            user_password,
+            owner_password,
+            allow,
+            output,
+        } => {
+            let data = std::fs::read(&input).with_context(|| format!("reading {:?}", input))?;
+            let security = pdf_security(user_password, owner_password, &allow)?;
+            let encrypted =
+                pdf_ops::encrypt(&data, &security).map_err(|e| anyhow::anyhow!("{e}"))?;
+            std::fs::write(&output, encrypted)
+                .with_context(|| format!("writing output to {:?}", output))?;
+            println!("Encrypted {:?} -> {:?}", input, output);
+            Ok(())
+        }
+        Commands::Decrypt {
+            input,
+            password,
+            output,
+        } => {
+            let data = std::fs::read(&input).with_context(|| format!("reading {:?}", input))?;
+            let decrypted =
+                pdf_ops::decrypt(&data, &password).map_err(|e| anyhow::anyhow!("{e}"))?;
+            std::fs::write(&output, decrypted)
+                .with_context(|| format!("writing output to {:?}", output))?;
+            println!("Decrypted {:?} -> {:?}", input, output);
+            Ok(())
+        }
+        Commands::Sign {
+            input,
+            p12,
+            password,
+            key,
+            cert,
+            reason,
+            location,
+            visible,
+            output,
+        } => {
+            let data = std::fs::read(&input).with_context(|| format!("reading {:?}", input))?;
+            let read = |path: &PathBuf| -> Result<Vec<u8>> {
+                std::fs::read(path).with_context(|| format!("reading {:?}", path))
+            };
+            let key = match (p12, key) {
+                (Some(p12), _) => SigningKey::Pkcs12 {
+                    data: read(&p12)?,
+                    password: password.unwrap_or_default(),
+                },
+                (None, Some(key)) => SigningKey::Der {
+                    private_key: read(&key)?,
+                    certificates: cert.iter().map(read).collect::<Result<_>>()?,
+                },
+                (None, None) => anyhow::bail!("--p12 or --key is required"),
+            };
+            let config = signing_config(key, reason, location, visible)
+                .context("invalid --visible value")?;
+            let signed = pdf_ops::sign(&data, &config).map_err(|e| anyhow::anyhow!("{e}"))?;
+            std::fs::write(&output, signed)
+                .with_context(|| format!("writing output to {:?}", output))?;
+            println!("Signed {:?} -> {:?}", input, output);
+            Ok(())
+        }
+        Commands::Number {
+            input,
+            bates,
+            digits,
+            of_total,
+            start,
+            pages,
+            position,
+            output,
+        } => {
+            let data = std::fs::read(&input).with_context(|| format!("reading {:?}", input))?;
+            let mut options: pdf_ops::NumberingOptions = match bates {
+                Some(prefix) => pdf_ops::NumberingOptions::bates(prefix, digits),
+                None if of_total => pdf_ops::NumberingOptions {
+                    style: pdf_ops::NumberingStyle::PageOfTotal,
+                    ..pdf_ops::NumberingOptions::default()
+                },
+                None => pdf_ops::NumberingOptions::default(),
+            };
+            options.start = start;
+            options.pages = StampPages::parse(&pages)
+                .map_err(|e| anyhow::anyhow!("invalid --pages value: {e}"))?;
+            if let Some(position) = position {
+                options.position = StampPosition::parse(&position)
+                    .map_err(|e| anyhow::anyhow!("invalid --position value: {e}"))?;
+            }
+            let numbered =
+                pdf_ops::stamp_page_numbers(&data, &options).map_err(|e| anyhow::anyhow!("{e}"))?;
+            std::fs::write(&output, numbered)
+                .with_context(|| format!("writing output to {:?}", output))?;
+            println!("Numbered {:?} -> {:?}", input, output);
+            Ok(())
+        }
         #[cfg(feature = "server")]
-        Commands::Serve { host, port } => server::start_server(&host, port),
+        Commands::Serve {
+            host,
+            port,
+            threads,
+            workers,
+            max_queued,
+            job_ttl,
+            max_retained_mb,
+        } => {
+            let workers: usize = if workers == 0 {
+                std::thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(1)
+            } else {
+                workers
+            };
+            let settings = jobs::JobSettings {
+                workers,
+                max_queued,
+                retention: std::time::Duration::from_secs(job_ttl),
+                max_retained_bytes: max_retained_mb.saturating_mul(1024 * 1024),
+            };
+            server::start_server(&host, port, threads, settings)
+        }
     }
 }
 
 /// Convert multiple files independently, collecting results.
 ///
-/// When `jobs > 1` and there are multiple inputs, files are converted in
-/// parallel using a rayon thread pool. `jobs == 0` means "use all available
-/// CPU cores" (rayon's default).
+/// When `settings.jobs > 1` and there are multiple inputs, files are
+/// converted in parallel using a rayon thread pool. `jobs == 0` means "use
+/// all available CPU cores" (rayon's default).
 fn convert_batch(
-    inputs: &[PathBuf],
-    outdir: Option<&Path>,
+    inputs: &[InputFile],
     options: &ConvertOptions,
-    show_metrics: bool,
-    jobs: usize,
+    settings: &BatchSettings,
 ) -> BatchResult {
-    let convert_one = |input: &PathBuf| -> Result<(PathBuf, PathBuf), (PathBuf, String)> {
-        let output_path = determine_output_path(input, None, outdir);
-        match convert_single(input, &output_path, options, show_metrics) {
-            Ok(()) => {
-                println!("Converted: {:?} -> {:?}", input, output_path);
-                Ok((input.clone(), output_path))
+    // Read before converting and written after, so that parallel
+    // conversions need not share them.
+    let mut manifests: BTreeMap<PathBuf, Manifest> = BTreeMap::new();
+    if settings.incremental {
+        for input in inputs {
+            let dir: PathBuf = manifest_dir(&batch_output_path(input, settings.outdir));
+            if !manifests.contains_key(&dir) {
+                let manifest: Manifest = Manifest::load(&dir);
+                manifests.insert(dir, manifest);
             }
+        }
+    }
+
+    let progress = BatchProgress::new(inputs.len(), settings.verbosity);
+    let convert_one = |input: &InputFile| -> Result<Converted, (PathBuf, String)> {
+        let output_path = batch_output_path(input, settings.outdir);
+        let input = &input.path;
+        // Every input is reported once, which counts it as done.
+        let report = |status: Status, conversion: Option<&Conversion>| {
+            let output: &Path = &output_path;
+            progress.suspend(|| {
+                report_file(
+                    settings.json,
+                    settings.verbosity,
+                    input,
+                    Some(output),
+                    status,
+                    conversion,
+                )
+            });
+            progress.file_done();
+        };
+        let options = match options_for(input, options, settings.layers) {
+            Ok(options) => options,
             Err(err) => {
-                eprintln!("Failed: {:?}: {err:#}", input);
+                report(Status::Failed(format!("{err:#}")), None);
+                return Err((input.clone(), format!("{err:#}")));
+            }
+        };
+        let options_hash: Option<u64> = settings.incremental.then(|| hash_options(&options));
+        if let Some(hash) = options_hash
+            && manifests
+                .get(&manifest_dir(&output_path))
+                .is_some_and(|manifest| manifest.is_up_to_date(input, &output_path, hash))
+        {
+            report(Status::Unchanged, None);
+            return Ok(Converted::Skipped(input.clone(), output_path.clone()));
+        }
+        if output_path.exists() {
+            match settings.if_exists {
+                ExistingOutput::Overwrite => {}
+                ExistingOutput::Skip => {
+                    report(Status::Skipped, None);
+                    return Ok(Converted::Skipped(input.clone(), output_path.clone()));
+                }
+                ExistingOutput::Error => {
+                    let message = format!("output {:?} already exists", output_path);
+                    report(Status::Failed(message.clone()), None);
+                    return Err((input.clone(), message));
+                }
+            }
+        }
+        // Set after hashing: the callback is no setting of the conversion.
+        let file_progress: FileProgress = progress.start_file(input);
+        let options = ConvertOptions {
+            progress: Some(file_progress.callback()),
+            ..options.into_owned()
+        };
+        let result = create_parent_dir(&output_path).and_then(|()| {
+            convert_file(
+                input,
+                &output_path,
+                &options,
+                settings.emit_typst,
+                settings.tolerant,
+            )
+        });
+        drop(file_progress);
+        match result {
+            Ok(conversion) => {
+                report(Status::Converted, Some(&conversion));
+                // An input that cannot be read back is only converted again
+                // next time.
+                let entry: Option<Entry> =
+                    options_hash.and_then(|hash| entry_for(input, hash).ok());
+                Ok(Converted::Written {
+                    input: input.clone(),
+                    output: output_path.clone(),
+                    entry,
+                    fallback: conversion.fallback,
+                })
+            }
+            Err(err) => {
+                report(Status::Failed(format!("{err:#}")), None);
                 Err((input.clone(), format!("{err:#}")))
             }
         }
     };
 
-    let effective_jobs = if jobs == 0 {
+    let effective_jobs = if settings.jobs == 0 {
         std::thread::available_parallelism()
             .map(|n| n.get())
             .unwrap_or(1)
     } else {
-        jobs
+        settings.jobs
     };
 
     let results: Vec<_> = if effective_jobs > 1 && inputs.len() > 1 {
@@ -285,25 +1767,132 @@ fn convert_batch(
     } else {
         inputs.iter().map(convert_one).collect()
     };
+    progress.finish();
 
     let mut batch = BatchResult {
         succeeded: Vec::new(),
+        skipped: Vec::new(),
         failed: Vec::new(),
+        degraded: Vec::new(),
     };
+    let mut updated: BTreeSet<PathBuf> = BTreeSet::new();
     for r in results {
         match r {
-            Ok(pair) => batch.succeeded.push(pair),
+            Ok(Converted::Written {
+                input,
+                output,
+                entry,
+                fallback,
+            }) => {
+                if let (Some(entry), Some(name)) = (entry, file_name(&output)) {
+                    let dir: PathBuf = manifest_dir(&output);
+                    manifests
+                        .entry(dir.clone())
+                        .or_default()
+                        .entries
+                        .insert(name, entry);
+                    updated.insert(dir);
+                }
+                if let Some(fallback) = fallback {
+                    batch.degraded.push((input.clone(), fallback));
+                }
+                batch.succeeded.push((input, output));
+            }
+            Ok(Converted::Skipped(input, output)) => batch.skipped.push((input, output)),
             Err(pair) => batch.failed.push(pair),
         }
     }
+    for dir in updated {
+        if let Err(err) = manifests[&dir].save(&dir) {
+            eprintln!("Warning: {err:#}");
+        }
+    }
     batch
 }
 
+/// How a file of a batch that did not fail went.
+enum Converted {
+    Written {
+        input: PathBuf,
+        output: PathBuf,
+        /// The manifest entry of an incremental batch.
+        entry: Option<Entry>,
+        fallback: Option<Fallback>,
+    },
+    Skipped(PathBuf, PathBuf),
+}
+
+/// The options for `input`: `options`, changed where the configuration's
+/// `[files]` patterns match the input.
+fn options_for<'a>(
+    input: &Path,
+    options: &'a ConvertOptions,
+    layers: Option<&Layers>,
+) -> Result<Cow<'a, ConvertOptions>> {
+    match layers.and_then(|layers| layers.settings_for(input)) {
+        Some(settings) => {
+            let mut options: ConvertOptions = options.clone();
+            config::apply(&settings, &mut options)
+                .with_context(|| format!("configuring {:?}", input))?;
+            Ok(Cow::Owned(options))
+        }
+        None => Ok(Cow::Borrowed(options)),
+    }
+}
+
+/// Create the directory `path` goes in, for outputs mirroring a source tree.
+fn create_parent_dir(path: &Path) -> Result<()> {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => std::fs::create_dir_all(dir)
+            .with_context(|| format!("creating output directory {:?}", dir)),
+        _ => Ok(()),
+    }
+}
+
+/// The conversion options given as flags, as the layer of settings over
+/// the configuration file's. Flags left off leave the configuration be.
+fn flag_settings(cli: &Cli) -> Settings {
+    let switch = |on: bool| on.then_some(true);
+    Settings {
+        sheets: cli.sheets.clone(),
+        ranges: cli.ranges.clone(),
+        include_hidden: switch(cli.include_hidden),
+        gridlines: switch(cli.gridlines),
+        headings: switch(cli.headings),
+        ignore_frozen_panes: switch(cli.ignore_frozen_panes),
+        row_label_columns: (cli.row_label_columns > 0).then_some(cli.row_label_columns),
+        label_column_pages: switch(cli.label_column_pages),
+        redact_hidden: switch(cli.redact_hidden),
+        blank_errors: switch(cli.blank_errors),
+        annotate_dropdowns: switch(cli.annotate_dropdowns),
+        locale: cli.locale.clone(),
+        slides: cli.slides.clone(),
+        sections: cli.sections.clone(),
+        pdf_a: switch(cli.pdf_a),
+        pdf_standard: cli.pdf_standard.clone(),
+        embed_source: switch(cli.embed_source),
+        linearize: switch(cli.linearize),
+        compact: switch(cli.compact),
+        paper: cli.paper.clone(),
+        font_path: (!cli.font_path.is_empty()).then(|| cli.font_path.clone()),
+        landscape: switch(cli.landscape),
+        tagged: switch(cli.tagged),
+        pdf_ua: switch(cli.pdf_ua),
+        streaming: switch(cli.streaming),
+        streaming_chunk_size: cli.streaming_chunk_size,
+        effects: cli.effects.clone(),
+        slide_size: cli.slide_size.clone(),
+        slide_scale: cli.slide_scale.clone(),
+        timeout: cli.timeout,
+        max_input_size: cli.max_input_size,
+    }
+}
+
 fn run() -> Result<()> {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
 
     // Handle subcommands
-    if let Some(cmd) = cli.command {
+    if let Some(cmd) = cli.command.take() {
         return handle_command(cmd);
     }
 
@@ -312,38 +1901,153 @@ fn run() -> Result<()> {
         anyhow::bail!("--output cannot be used with multiple input files; use --outdir instead");
     }
 
-    let slide_range = cli
-        .slides
-        .map(|s| SlideRange::parse(&s))
-        .transpose()
-        .map_err(|e| anyhow::anyhow!("invalid --slides value: {e}"))?;
-
-    let pdf_standard = if cli.pdf_a {
-        Some(PdfStandard::PdfA2b)
-    } else {
+    let config: Option<Config> = if cli.no_config {
         None
+    } else {
+        Config::discover(cli.config.as_deref())?
+    };
+    if cli.profile.is_some() && config.is_none() {
+        anyhow::bail!("--profile needs a configuration file: {CONFIG_FILE} or --config");
+    }
+    let flags: Settings = flag_settings(&cli);
+    let if_exists: ExistingOutput = if cli.skip_existing {
+        ExistingOutput::Skip
+    } else {
+        cli.if_exists
     };
 
-    let paper_size = cli
-        .paper
-        .map(|s| PaperSize::parse(&s))
-        .transpose()
-        .map_err(|e| anyhow::anyhow!("invalid --paper value: {e}"))?;
-
-    let landscape = if cli.landscape { Some(true) } else { None };
-
-    let options = ConvertOptions {
-        sheet_names: cli.sheets,
-        slide_range,
-        pdf_standard,
-        paper_size,
-        font_paths: cli.font_path,
-        landscape,
-        tagged: cli.tagged,
-        pdf_ua: cli.pdf_ua,
-        streaming: cli.streaming,
-        streaming_chunk_size: cli.streaming_chunk_size,
+    let signing = cli
+        .sign
+        .map(|path| -> Result<SigningConfig> {
+            let data = std::fs::read(&path).with_context(|| format!("reading {:?}", path))?;
+            let key = SigningKey::Pkcs12 {
+                data,
+                password: cli.sign_password.unwrap_or_default(),
+            };
+            signing_config(key, cli.sign_reason, cli.sign_location, cli.sign_visible)
+                .context("invalid --sign-visible value")
+        })
+        .transpose()?;
+
+    let stamp = match (cli.stamp, cli.stamp_image) {
+        (Some(text), _) => Some(Stamp::text(text)),
+        (None, Some(path)) => {
+            let data = std::fs::read(&path).with_context(|| format!("reading {:?}", path))?;
+            Some(Stamp::image(data, 144.0))
+        }
+        (None, None) => None,
+    };
+    let stamp = stamp
+        .map(|mut stamp| -> Result<Stamp> {
+            if let Some(size) = cli.stamp_size {
+                match &mut stamp.content {
+                    StampContent::Text { font_size, .. } => *font_size = size,
+                    StampContent::Image { width, .. } => *width = size,
+                }
+            }
+            if let Some(position) = cli.stamp_position {
+                stamp.position = StampPosition::parse(&position)
+                    .map_err(|e| anyhow::anyhow!("invalid --stamp-position value: {e}"))?;
+            }
+            if let Some(pages) = cli.stamp_pages {
+                stamp.pages = StampPages::parse(&pages)
+                    .map_err(|e| anyhow::anyhow!("invalid --stamp-pages value: {e}"))?;
+            }
+            stamp.rotation = cli.stamp_rotation.unwrap_or(stamp.rotation);
+            stamp.opacity = cli.stamp_opacity.unwrap_or(stamp.opacity);
+            Ok(stamp)
+        })
+        .transpose()?;
+
+    let bleed = (cli.bleed.is_some() || cli.crop_marks).then(|| Bleed {
+        margin: cli.bleed.unwrap_or(0.0),
+        crop_marks: cli.crop_marks,
+    });
+
+    let layers = Layers {
+        base: match &config {
+            Some(config) => config.base(cli.profile.as_deref())?,
+            None => Settings::default(),
+        },
+        config: config.unwrap_or_default(),
+        flags,
+    };
+    let mut options = ConvertOptions {
+        emit_typst_source: cli.emit_typst.is_some(),
+        missing_glyph_replacement: cli.missing_glyph,
+        signing,
+        stamp,
+        bleed,
+        ..Default::default()
+    };
+    config::apply(&layers.settings(), &mut options)?;
+
+    let format: Option<Format> = cli
+        .format
+        .map(|s| {
+            Format::from_extension(&s).ok_or_else(|| {
+                anyhow::anyhow!("invalid --format value: {s}; expected docx, pptx or xlsx")
+            })
+        })
+        .transpose()?;
+    let verbosity: Verbosity = Verbosity::from_flags(cli.quiet, cli.verbose, cli.metrics);
+
+    if cli.inputs.iter().any(|input| is_stdio(input)) {
+        if cli.inputs.len() > 1 {
+            anyhow::bail!("standard input (-) cannot be combined with other inputs");
+        }
+        if cli.split_sections || cli.outdir.is_some() {
+            anyhow::bail!(
+                "--split-sections and --outdir name outputs after the input file; \
+                 use --output with standard input"
+            );
+        }
+        // A pipeline reads the PDF from standard output unless told otherwise.
+        let output: PathBuf = cli.output.unwrap_or_else(|| PathBuf::from(STDIO));
+        if cli.json && is_stdio(&output) {
+            anyhow::bail!("--json prints results on standard output; write the PDF with --output");
+        }
+        let conversion = convert_stdin(
+            format,
+            &output,
+            &options,
+            cli.emit_typst.as_deref(),
+            cli.tolerant,
+        );
+        if cli.json {
+            return report_json(Path::new(STDIO), &output, conversion);
+        }
+        let conversion: Conversion = conversion?;
+        if verbosity == Verbosity::Quiet {
+            return Ok(());
+        }
+        print_diagnostics(Path::new("standard input"), &conversion, verbosity);
+        let notes: String = conversion_notes(&conversion, verbosity);
+        if !is_stdio(&output) {
+            println!("Converted: standard input -> {:?}{notes}", output);
+        } else if !notes.is_empty() {
+            // Not worth a line otherwise, with the PDF on standard output.
+            eprintln!("Converted: standard input -> standard output{notes}");
+        }
+        return Ok(());
+    }
+    if format.is_some() {
+        anyhow::bail!("--format applies to standard input (-); files go by their extension");
+    }
+
+    let filter = InputFilter {
+        recursive: cli.recursive,
+        follow_symlinks: cli.follow_symlinks,
+        include: cli.include,
+        exclude: cli.exclude,
     };
+    let files: Vec<InputFile> = expand_inputs(&cli.inputs, &filter)?;
+    if cli.output.is_some() && files.iter().any(|file| file.relative.is_some()) {
+        anyhow::bail!("--output cannot be used with a directory input; use --outdir instead");
+    }
+    if files.is_empty() {
+        anyhow::bail!("no files to convert in {:?}", cli.inputs);
+    }
 
     // Create outdir if specified and doesn't exist
     if let Some(ref outdir) = cli.outdir {
@@ -351,34 +2055,94 @@ fn run() -> Result<()> {
             .with_context(|| format!("creating output directory {:?}", outdir))?;
     }
 
-    let show_metrics = cli.metrics;
+    if cli.split_sections {
+        for file in &files {
+            let options = options_for(&file.path, &options, Some(&layers))?;
+            for out_path in
+                convert_sections(&file.path, cli.outdir.as_deref(), &options, verbosity)?
+            {
+                if verbosity > Verbosity::Quiet {
+                    println!("Converted: {:?} -> {:?}", file.path, out_path);
+                }
+            }
+        }
+        return Ok(());
+    }
 
     // Single file with explicit --output
     if let Some(output) = cli.output {
-        let input = &cli.inputs[0];
-        convert_single(input, &output, &options, show_metrics)?;
-        println!("Converted: {:?} -> {:?}", input, output);
+        let input = &files[0].path;
+        if cli.json && is_stdio(&output) {
+            anyhow::bail!("--json prints results on standard output; write the PDF to a file");
+        }
+        if if_exists != ExistingOutput::Overwrite && !is_stdio(&output) && output.exists() {
+            if if_exists == ExistingOutput::Skip {
+                report_file(
+                    cli.json,
+                    verbosity,
+                    input,
+                    Some(&output),
+                    Status::Skipped,
+                    None,
+                );
+                return Ok(());
+            }
+            anyhow::bail!("output {:?} already exists", output);
+        }
+        let conversion = options_for(input, &options, Some(&layers)).and_then(|options| {
+            convert_file(
+                input,
+                &output,
+                &options,
+                cli.emit_typst.as_deref(),
+                cli.tolerant,
+            )
+        });
+        if cli.json {
+            return report_json(input, &output, conversion);
+        }
+        let conversion: Conversion = conversion?;
+        report_file(
+            false,
+            verbosity,
+            input,
+            Some(&output),
+            Status::Converted,
+            Some(&conversion),
+        );
         return Ok(());
     }
 
     // Batch conversion (works for 1 or many files)
-    let result = convert_batch(
-        &cli.inputs,
-        cli.outdir.as_deref(),
-        &options,
-        show_metrics,
-        cli.jobs,
-    );
+    let settings = BatchSettings {
+        outdir: cli.outdir.as_deref(),
+        verbosity,
+        emit_typst: cli.emit_typst.as_deref(),
+        jobs: cli.jobs,
+        if_exists,
+        incremental: cli.incremental,
+        tolerant: cli.tolerant,
+        json: cli.json,
+        layers: Some(&layers),
+    };
+    let result = convert_batch(&files, &options, &settings);
 
     // Print summary when there are multiple files
-    let total = result.succeeded.len() + result.failed.len();
-    if total > 1 {
+    let total = result.succeeded.len() + result.skipped.len() + result.failed.len();
+    if total > 1 && !cli.json && verbosity > Verbosity::Quiet {
         println!(
-            "\nSummary: {} succeeded, {} failed (out of {} files)",
+            "\nSummary: {} succeeded, {} skipped, {} failed (out of {} files)",
             result.succeeded.len(),
+            result.skipped.len(),
             result.failed.len(),
             total
         );
+        if !result.degraded.is_empty() {
+            println!("Converted with fallbacks:");
+            for (path, fallback) in &result.degraded {
+                println!("  {:?}: {fallback}", path);
+            }
+        }
         if !result.failed.is_empty() {
             println!("Failed files:");
             for (path, err) in &result.failed {
@@ -10,6 +10,84 @@ fn make_test_docx() -> Vec<u8> {
     buf.into_inner()
 }
 
+#[test]
+fn test_section_file_name_numbers_and_sanitizes() {
+    assert_eq!(
+        section_file_name("deck", 1, "Q3 Results/Draft"),
+        "deck_section_02_Q3_Results_Draft.pdf"
+    );
+}
+
+#[test]
+fn test_rasterize_writes_one_image_per_page() {
+    let dir = std::env::temp_dir().join("office2pdf_rasterize_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("report.docx");
+    std::fs::write(&input, make_test_docx()).unwrap();
+
+    let outdir = dir.join("thumbs");
+    let written: Vec<PathBuf> =
+        rasterize(&input, Some(&outdir), &SlideImageOptions::default()).unwrap();
+    assert_eq!(written, vec![outdir.join("report_page_01.png")]);
+    assert!(std::fs::read(&written[0]).unwrap().starts_with(b"\x89PNG"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_parse_asset_kinds() {
+    assert_eq!(
+        parse_asset_kinds("images, fonts,images").unwrap(),
+        [AssetKind::Image, AssetKind::Font]
+    );
+    assert!(parse_asset_kinds("images,charts").is_err());
+    assert!(parse_asset_kinds(",").is_err());
+}
+
+#[test]
+fn test_extract_saves_embedded_images_by_kind() {
+    let dir = std::env::temp_dir().join("office2pdf_extract_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    // A 1x1 BMP, which docx-rs stores in the package's media folder.
+    let mut bmp: Vec<u8> = b"BM".to_vec();
+    for value in [58u32, 0, 54, 40, 1, 1] {
+        bmp.extend_from_slice(&value.to_le_bytes());
+    }
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&24u16.to_le_bytes());
+    for value in [0u32, 4, 0, 0, 0, 0] {
+        bmp.extend_from_slice(&value.to_le_bytes());
+    }
+    bmp.extend_from_slice(&[0x00, 0x00, 0xFF, 0x00]);
+    let docx = docx_rs::Docx::new().add_paragraph(
+        docx_rs::Paragraph::new().add_run(docx_rs::Run::new().add_image(docx_rs::Pic::new(&bmp))),
+    );
+    let mut buf = Cursor::new(Vec::new());
+    docx.build().pack(&mut buf).unwrap();
+    let input = dir.join("report.docx");
+    std::fs::write(&input, buf.into_inner()).unwrap();
+
+    let written: Vec<PathBuf> = extract(&input, None, &AssetKind::ALL).unwrap();
+    assert_eq!(written.len(), 1);
+    assert_eq!(
+        written[0].parent().unwrap(),
+        dir.join("report_assets").join("images")
+    );
+
+    let outdir = dir.join("assets");
+    let written: Vec<PathBuf> = extract(&input, Some(&outdir), &[AssetKind::Font]).unwrap();
+    assert!(written.is_empty());
+    assert!(!outdir.exists(), "nothing to save creates no folders");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+fn named(paths: &[PathBuf]) -> Vec<InputFile> {
+    paths.iter().map(InputFile::named).collect()
+}
+
 // --- Unit tests for determine_output_path ---
 
 #[test]
@@ -59,7 +137,11 @@ fn test_batch_convert_multiple_files() {
 
     let inputs = vec![file1, file2];
     let options = ConvertOptions::default();
-    let result = convert_batch(&inputs, None, &options, false, 1);
+    let settings = BatchSettings {
+        jobs: 1,
+        ..Default::default()
+    };
+    let result = convert_batch(&named(&inputs), &options, &settings);
 
     assert_eq!(result.succeeded.len(), 2);
     assert_eq!(result.failed.len(), 0);
@@ -69,6 +151,94 @@ fn test_batch_convert_multiple_files() {
     let _ = std::fs::remove_dir_all(&dir);
 }
 
+#[test]
+fn test_batch_convert_directory_mirrors_tree_and_skips_existing() {
+    let dir = std::env::temp_dir().join("office2pdf_batch_test_tree");
+    let src = dir.join("docs");
+    let outdir = dir.join("out");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(src.join("team/notes")).unwrap();
+
+    let docx_data = make_test_docx();
+    std::fs::write(src.join("top.docx"), &docx_data).unwrap();
+    std::fs::write(src.join("team/notes/deep.docx"), &docx_data).unwrap();
+    std::fs::write(src.join("team/readme.txt"), b"not a document").unwrap();
+
+    let filter = InputFilter {
+        recursive: true,
+        ..Default::default()
+    };
+    let files: Vec<InputFile> = expand_inputs(std::slice::from_ref(&src), &filter).unwrap();
+    assert_eq!(files.len(), 2);
+
+    let options = ConvertOptions::default();
+    let mut settings = BatchSettings {
+        outdir: Some(&outdir),
+        jobs: 1,
+        ..Default::default()
+    };
+    let result = convert_batch(&files, &options, &settings);
+    assert_eq!(result.succeeded.len(), 2);
+    assert!(outdir.join("top.pdf").exists());
+    assert!(outdir.join("team/notes/deep.pdf").exists());
+
+    settings.if_exists = ExistingOutput::Skip;
+    let result = convert_batch(&files, &options, &settings);
+    assert_eq!(result.succeeded.len(), 0);
+    assert_eq!(result.skipped.len(), 2);
+
+    settings.if_exists = ExistingOutput::Error;
+    let result = convert_batch(&files, &options, &settings);
+    assert_eq!(result.failed.len(), 2);
+    assert!(result.failed[0].1.contains("already exists"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_batch_convert_incremental_skips_unchanged_inputs() {
+    let dir = std::env::temp_dir().join("office2pdf_batch_test_incremental");
+    let outdir = dir.join("out");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let docx_data = make_test_docx();
+    let first = dir.join("first.docx");
+    let second = dir.join("second.docx");
+    std::fs::write(&first, &docx_data).unwrap();
+    std::fs::write(&second, &docx_data).unwrap();
+    let files: Vec<InputFile> = named(&[first.clone(), second]);
+
+    let options = ConvertOptions::default();
+    let settings = BatchSettings {
+        outdir: Some(&outdir),
+        jobs: 1,
+        incremental: true,
+        ..Default::default()
+    };
+    let result = convert_batch(&files, &options, &settings);
+    assert_eq!(result.succeeded.len(), 2);
+    assert!(outdir.join(incremental::MANIFEST_FILE).exists());
+
+    let result = convert_batch(&files, &options, &settings);
+    assert_eq!(result.succeeded.len(), 0);
+    assert_eq!(result.skipped.len(), 2);
+
+    // A changed input and changed options are converted again.
+    std::fs::write(&first, b"no longer a document").unwrap();
+    let result = convert_batch(&files, &options, &settings);
+    assert_eq!(result.failed.len(), 1);
+    assert_eq!(result.skipped.len(), 1);
+    let landscape = ConvertOptions {
+        landscape: Some(true),
+        ..Default::default()
+    };
+    let result = convert_batch(&files[1..], &landscape, &settings);
+    assert_eq!(result.succeeded.len(), 1);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 #[test]
 fn test_batch_convert_partial_failure() {
     let dir = std::env::temp_dir().join("office2pdf_batch_test_fail");
@@ -83,7 +253,11 @@ fn test_batch_convert_partial_failure() {
 
     let inputs = vec![file1, file2.clone()];
     let options = ConvertOptions::default();
-    let result = convert_batch(&inputs, None, &options, false, 1);
+    let settings = BatchSettings {
+        jobs: 1,
+        ..Default::default()
+    };
+    let result = convert_batch(&named(&inputs), &options, &settings);
 
     assert_eq!(result.succeeded.len(), 1);
     assert_eq!(result.failed.len(), 1);
@@ -109,7 +283,12 @@ fn test_batch_convert_with_outdir() {
 
     let inputs = vec![file1, file2];
     let options = ConvertOptions::default();
-    let result = convert_batch(&inputs, Some(&outdir), &options, false, 1);
+    let settings = BatchSettings {
+        outdir: Some(&outdir),
+        jobs: 1,
+        ..Default::default()
+    };
+    let result = convert_batch(&named(&inputs), &options, &settings);
 
     assert_eq!(result.succeeded.len(), 2);
     assert_eq!(result.failed.len(), 0);
@@ -140,7 +319,11 @@ fn test_batch_convert_parallel_jobs_2() {
         .collect();
 
     let options = ConvertOptions::default();
-    let result = convert_batch(&inputs, None, &options, false, 2);
+    let settings = BatchSettings {
+        jobs: 2,
+        ..Default::default()
+    };
+    let result = convert_batch(&named(&inputs), &options, &settings);
 
     assert_eq!(result.succeeded.len(), 4);
     assert_eq!(result.failed.len(), 0);
@@ -168,7 +351,11 @@ fn test_batch_convert_parallel_partial_failure() {
 
     let inputs = vec![good, bad.clone()];
     let options = ConvertOptions::default();
-    let result = convert_batch(&inputs, None, &options, false, 2);
+    let settings = BatchSettings {
+        jobs: 2,
+        ..Default::default()
+    };
+    let result = convert_batch(&named(&inputs), &options, &settings);
 
     assert_eq!(result.succeeded.len(), 1);
     assert_eq!(result.failed.len(), 1);
@@ -196,7 +383,12 @@ fn test_batch_convert_parallel_with_outdir() {
         .collect();
 
     let options = ConvertOptions::default();
-    let result = convert_batch(&inputs, Some(&outdir), &options, false, 2);
+    let settings = BatchSettings {
+        outdir: Some(&outdir),
+        jobs: 2,
+        ..Default::default()
+    };
+    let result = convert_batch(&named(&inputs), &options, &settings);
 
     assert_eq!(result.succeeded.len(), 3);
     assert_eq!(result.failed.len(), 0);
@@ -222,7 +414,11 @@ fn test_batch_convert_single_file_with_jobs() {
 
     let inputs = vec![input];
     let options = ConvertOptions::default();
-    let result = convert_batch(&inputs, None, &options, false, 4);
+    let settings = BatchSettings {
+        jobs: 4,
+        ..Default::default()
+    };
+    let result = convert_batch(&named(&inputs), &options, &settings);
 
     assert_eq!(result.succeeded.len(), 1);
     assert_eq!(result.failed.len(), 0);
@@ -248,7 +444,11 @@ fn test_batch_convert_sequential_jobs_1() {
         .collect();
 
     let options = ConvertOptions::default();
-    let result = convert_batch(&inputs, None, &options, false, 1);
+    let settings = BatchSettings {
+        jobs: 1,
+        ..Default::default()
+    };
+    let result = convert_batch(&named(&inputs), &options, &settings);
 
     assert_eq!(result.succeeded.len(), 3);
     assert_eq!(result.failed.len(), 0);
@@ -268,9 +468,300 @@ fn test_convert_single_with_metrics() {
     std::fs::write(&input, &docx_data).unwrap();
 
     let options = ConvertOptions::default();
-    // Should succeed with metrics=true (metrics printed to stderr)
-    convert_single(&input, &output, &options, true).unwrap();
+    let conversion: Conversion = convert_single(&input, &output, &options, None).unwrap();
+    assert!(output.exists());
+    let metrics = conversion.metrics.expect("conversions report metrics");
+    assert_eq!(metrics.page_count, 1);
+    assert!(metrics.output_size_bytes > 0);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_convert_single_emits_typst_source() {
+    let dir = std::env::temp_dir().join("office2pdf_emit_typst_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let input = dir.join("report.docx");
+    let output = dir.join("report.pdf");
+    std::fs::write(&input, make_test_docx()).unwrap();
+    let typst_dir = dir.join("typst");
+
+    let options = ConvertOptions {
+        emit_typst_source: true,
+        ..Default::default()
+    };
+    convert_single(&input, &output, &options, Some(&typst_dir)).unwrap();
     assert!(output.exists());
+    let source = std::fs::read_to_string(typst_dir.join("report").join("main.typ")).unwrap();
+    assert!(source.contains("Hello batch"), "{source}");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_convert_input_bytes_with_and_without_format() {
+    let docx_data = make_test_docx();
+    let options = ConvertOptions::default();
+    // Standard input has no extension: the content shows the format.
+    assert!(
+        !convert_input_bytes(&docx_data, None, &options)
+            .unwrap()
+            .pdf
+            .is_empty()
+    );
+    assert!(convert_input_bytes(&docx_data, Some(Format::Docx), &options).is_ok());
+    assert!(convert_input_bytes(b"not a document", None, &options).is_err());
+}
+
+#[test]
+fn test_cli_accepts_directory_filters() {
+    let cli = Cli::try_parse_from([
+        "office2pdf",
+        "docs/",
+        "--recursive",
+        "--include",
+        "**/*.docx",
+        "--exclude",
+        "drafts/**",
+        "--outdir",
+        "out/",
+        "--if-exists",
+        "skip",
+    ])
+    .unwrap();
+    assert!(cli.recursive);
+    assert_eq!(cli.include, vec!["**/*.docx"]);
+    assert_eq!(cli.exclude, vec!["drafts/**"]);
+    assert_eq!(cli.if_exists, ExistingOutput::Skip);
+    assert!(Cli::try_parse_from(["office2pdf", "a.docx", "--if-exists", "maybe"]).is_err());
+}
+
+#[test]
+fn test_cli_json_excludes_split_sections() {
+    let cli = Cli::try_parse_from(["office2pdf", "a.docx", "b.docx", "--json"]).unwrap();
+    assert!(cli.json);
+    assert!(
+        Cli::try_parse_from(["office2pdf", "deck.pptx", "--json", "--split-sections"]).is_err()
+    );
+}
+
+#[test]
+fn test_pdf_security_grants_listed_permissions() {
+    let allow: Vec<String> = vec!["print".to_string(), " Copy".to_string()];
+    let security = pdf_security(String::new(), "owner".to_string(), &allow).unwrap();
+    assert!(security.allow_print && security.allow_copy);
+    assert!(!security.allow_modify && !security.allow_annotate);
+    assert!(pdf_security(String::new(), "owner".to_string(), &["fax".to_string()]).is_err());
+}
+
+#[test]
+fn test_cli_sign_needs_a_p12_file_or_a_key_and_certificate() {
+    let parse = |args: &[&str]| -> bool {
+        let mut argv: Vec<&str> = vec!["office2pdf", "sign", "in.pdf", "-o", "out.pdf"];
+        argv.extend_from_slice(args);
+        Cli::try_parse_from(argv).is_ok()
+    };
+    assert!(parse(&["--p12", "signer.p12", "--password", "secret"]));
+    assert!(parse(&[
+        "--key",
+        "signer.key",
+        "--cert",
+        "signer.crt",
+        "--cert",
+        "ca.crt"
+    ]));
+    assert!(!parse(&[]));
+    assert!(!parse(&["--key", "signer.key"]));
+    assert!(!parse(&[
+        "--p12",
+        "signer.p12",
+        "--key",
+        "signer.key",
+        "--cert",
+        "signer.crt"
+    ]));
+}
+
+#[test]
+fn test_cli_quiet_excludes_verbose_and_metrics() {
+    let cli = Cli::try_parse_from(["office2pdf", "a.docx", "-vv"]).unwrap();
+    assert_eq!(cli.verbose, 2);
+    assert!(
+        Cli::try_parse_from(["office2pdf", "a.docx", "-q"])
+            .unwrap()
+            .quiet
+    );
+    assert!(Cli::try_parse_from(["office2pdf", "a.docx", "-q", "-v"]).is_err());
+    assert!(Cli::try_parse_from(["office2pdf", "a.docx", "--quiet", "--metrics"]).is_err());
+}
+
+#[test]
+fn test_conversion_notes_count_warnings_until_they_are_printed() {
+    let warning = || ConvertWarning::UnsupportedElement {
+        format: "DOCX".to_string(),
+        element: "ink".to_string(),
+        location: None,
+    };
+    let mut conversion = Conversion {
+        warnings: vec![],
+        metrics: None,
+        fallback: None,
+    };
+    assert_eq!(conversion_notes(&conversion, Verbosity::Normal), "");
+
+    conversion.warnings = vec![warning(), warning()];
+    assert_eq!(
+        conversion_notes(&conversion, Verbosity::Normal),
+        " (2 warnings)"
+    );
+    assert_eq!(conversion_notes(&conversion, Verbosity::Verbose), "");
+
+    conversion.fallback = Some(Fallback::NoImages);
+    conversion.warnings.pop();
+    assert_eq!(
+        conversion_notes(&conversion, Verbosity::Normal),
+        " (without images; 1 warning)"
+    );
+}
+
+#[test]
+fn test_cli_tolerant_excludes_split_sections() {
+    let cli = Cli::try_parse_from(["office2pdf", "a.docx", "--tolerant"]).unwrap();
+    assert!(cli.tolerant);
+    assert!(
+        Cli::try_parse_from(["office2pdf", "deck.pptx", "--tolerant", "--split-sections"]).is_err()
+    );
+}
+
+#[test]
+fn test_batch_convert_tolerant_keeps_documents_that_convert() {
+    let dir = std::env::temp_dir().join("office2pdf_batch_test_tolerant");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("fine.docx");
+    std::fs::write(&input, make_test_docx()).unwrap();
+
+    let options = ConvertOptions::default();
+    let settings = BatchSettings {
+        jobs: 1,
+        tolerant: true,
+        ..Default::default()
+    };
+    let result = convert_batch(&named(&[input]), &options, &settings);
+    assert_eq!(result.succeeded.len(), 1);
+    assert!(result.degraded.is_empty());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_convert_in_time_gives_up_after_the_timeout() {
+    let mut options = ConvertOptions::default();
+    options.limits.timeout = Some(std::time::Duration::from_millis(50));
+
+    let slow = convert_in_time(&options, |_| {
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        Err(ConvertError::Parse("too late".to_string()))
+    });
+    assert!(matches!(slow, Err(ConvertError::LimitExceeded(_))));
+
+    // Unwinding a panic with a backtrace can take longer than the 50 ms.
+    options.limits.timeout = Some(std::time::Duration::from_secs(10));
+    let quick = convert_in_time(&options, |_| Err(ConvertError::Parse("bad".to_string())));
+    assert!(matches!(quick, Err(ConvertError::Parse(_))));
+
+    let panicked = convert_in_time(&options, |_| panic!("broken"));
+    assert!(matches!(panicked, Err(ConvertError::Render(_))));
+}
+
+#[test]
+fn test_convert_in_time_caps_the_conversions_given_up_on() {
+    static ABANDONED_HERE: Abandoned = Abandoned {
+        running: AtomicUsize::new(0),
+        max: 1,
+    };
+    let mut options = ConvertOptions::default();
+    options.limits.timeout = Some(std::time::Duration::from_millis(20));
+
+    let (release, gate) = mpsc::channel::<()>();
+    let stuck = convert_in_time_with(&ABANDONED_HERE, &options, move |_| {
+        let _ = gate.recv();
+        Err(ConvertError::Parse("too late".to_string()))
+    });
+    assert!(matches!(stuck, Err(ConvertError::LimitExceeded(_))));
+    let refused = convert_in_time_with(&ABANDONED_HERE, &options, |_| {
+        panic!("must not start while the cap is reached")
+    });
+    assert!(
+        matches!(&refused, Err(ConvertError::LimitExceeded(message)) if message.contains("still running")),
+        "{refused:?}"
+    );
+
+    // Once the stuck conversion ends, its slot is free again.
+    drop(release);
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while ABANDONED_HERE.running.load(Ordering::SeqCst) > 0 {
+        assert!(std::time::Instant::now() < deadline, "slot never freed");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    options.limits.timeout = Some(std::time::Duration::from_secs(10));
+    let quick = convert_in_time_with(&ABANDONED_HERE, &options, |_| {
+        Err(ConvertError::Parse("bad".to_string()))
+    });
+    assert!(matches!(quick, Err(ConvertError::Parse(_))));
+}
+
+#[test]
+fn test_flag_settings_hold_only_the_flags_given() {
+    let cli = Cli::try_parse_from([
+        "office2pdf",
+        "report.xlsx",
+        "--profile",
+        "print",
+        "--paper",
+        "letter",
+        "--gridlines",
+        "--sheets",
+        "A,B",
+    ])
+    .unwrap();
+    assert_eq!(cli.profile.as_deref(), Some("print"));
+    let flags: Settings = flag_settings(&cli);
+    assert_eq!(
+        flags,
+        Settings {
+            gridlines: Some(true),
+            paper: Some("letter".to_string()),
+            sheets: Some(vec!["A".to_string(), "B".to_string()]),
+            ..Default::default()
+        }
+    );
+    assert!(
+        Cli::try_parse_from(["office2pdf", "a.docx", "--no-config", "--profile", "x"]).is_err()
+    );
+}
+
+#[test]
+fn test_cli_accepts_stdin_and_stdout() {
+    let cli = Cli::try_parse_from(["office2pdf", "-", "--format", "docx", "-o", "-"]).unwrap();
+    assert_eq!(cli.inputs, vec![PathBuf::from("-")]);
+    assert!(is_stdio(cli.inputs[0].as_path()));
+    assert!(cli.output.as_deref().is_some_and(is_stdio));
+    assert_eq!(cli.format.as_deref(), Some("docx"));
+    assert!(!is_stdio(Path::new("report.pdf")));
+}
+
+#[test]
+fn test_write_output_to_file() {
+    let dir = std::env::temp_dir().join("office2pdf_write_output_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let output = dir.join("out.pdf");
+    write_output(&output, b"%PDF-1.7").unwrap();
+    assert_eq!(std::fs::read(&output).unwrap(), b"%PDF-1.7");
 
     let _ = std::fs::remove_dir_all(&dir);
 }
@@ -335,6 +826,8 @@ fn test_cli_merge_command() {
     let cmd = Commands::Merge {
         files: vec![file1, file2],
         output: output.clone(),
+        bookmarks: false,
+        toc: false,
     };
     handle_command(cmd).unwrap();
 
@@ -345,6 +838,36 @@ fn test_cli_merge_command() {
     let _ = std::fs::remove_dir_all(&dir);
 }
 
+#[test]
+fn test_cli_merge_command_with_bookmarks_and_toc() {
+    let dir = std::env::temp_dir().join("office2pdf_cli_merge_toc_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let file1 = dir.join("intro.pdf");
+    let file2 = dir.join("appendix.pdf");
+    std::fs::write(&file1, make_test_pdf(1)).unwrap();
+    std::fs::write(&file2, make_test_pdf(2)).unwrap();
+    let output = dir.join("merged.pdf");
+
+    handle_command(Commands::Merge {
+        files: vec![file1, file2],
+        output: output.clone(),
+        bookmarks: true,
+        toc: true,
+    })
+    .unwrap();
+    let doc = lopdf::Document::load(&output).unwrap();
+    assert_eq!(doc.get_pages().len(), 4);
+    let contents = doc.get_page_content(doc.get_pages()[&1]).unwrap();
+    let contents = String::from_utf8_lossy(&contents);
+    assert!(contents.contains("(intro) Tj"));
+    assert!(contents.contains("(appendix) Tj"));
+    assert!(doc.catalog().unwrap().has(b"Outlines"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 #[test]
 fn test_cli_split_command() {
     let dir = std::env::temp_dir().join("office2pdf_cli_split_test");
@@ -359,6 +882,10 @@ fn test_cli_split_command() {
     let cmd = Commands::Split {
         input: input.clone(),
         pages: vec!["1-2".to_string(), "3-4".to_string()],
+        by_bookmark: false,
+        every: None,
+        max_size: None,
+        name: "{stem}_pages_{start}-{end}".to_string(),
         outdir: outdir.clone(),
     };
     handle_command(cmd).unwrap();
@@ -373,3 +900,176 @@ fn test_cli_split_command() {
 
     let _ = std::fs::remove_dir_all(&dir);
 }
+
+#[test]
+fn test_cli_split_command_every_with_name_template() {
+    let dir = std::env::temp_dir().join("office2pdf_cli_split_every_test");
+    let outdir = dir.join("splits");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let input = dir.join("doc.pdf");
+    std::fs::write(&input, make_test_pdf(5)).unwrap();
+
+    handle_command(Commands::Split {
+        input,
+        pages: Vec::new(),
+        by_bookmark: false,
+        every: Some(2),
+        max_size: None,
+        name: "{stem}-{n}".to_string(),
+        outdir: outdir.clone(),
+    })
+    .unwrap();
+    let counts: Vec<u32> = ["doc-1.pdf", "doc-2.pdf", "doc-3.pdf"]
+        .iter()
+        .map(|name| pdf_ops::page_count(&std::fs::read(outdir.join(name)).unwrap()).unwrap())
+        .collect();
+    assert_eq!(counts, vec![2, 2, 1]);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_cli_rotate_reorder_delete_commands() {
+    let dir = std::env::temp_dir().join("office2pdf_cli_page_ops_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let input = dir.join("doc.pdf");
+    std::fs::write(&input, make_test_pdf(3)).unwrap();
+
+    let rotated = dir.join("rotated.pdf");
+    handle_command(Commands::Rotate {
+        input: input.clone(),
+        degrees: 90,
+        pages: Vec::new(),
+        output: rotated.clone(),
+    })
+    .unwrap();
+    let doc = lopdf::Document::load(&rotated).unwrap();
+    for page_id in doc.get_pages().into_values() {
+        let page = doc.get_dictionary(page_id).unwrap();
+        assert_eq!(page.get(b"Rotate").unwrap().as_i64().unwrap(), 90);
+    }
+
+    let reordered = dir.join("reordered.pdf");
+    handle_command(Commands::Reorder {
+        input: rotated,
+        order: vec![3, 2, 1],
+        output: reordered.clone(),
+    })
+    .unwrap();
+    let doc = lopdf::Document::load(&reordered).unwrap();
+    let first = doc.get_page_content(doc.get_pages()[&1]).unwrap();
+    assert!(String::from_utf8_lossy(&first).contains("(Page 3)"));
+
+    let trimmed = dir.join("trimmed.pdf");
+    handle_command(Commands::Delete {
+        input: reordered,
+        pages: vec!["1".to_string(), "3".to_string()],
+        output: trimmed.clone(),
+    })
+    .unwrap();
+    assert_eq!(
+        pdf_ops::page_count(&std::fs::read(&trimmed).unwrap()).unwrap(),
+        1
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_cli_overlay_command() {
+    let dir = std::env::temp_dir().join("office2pdf_cli_overlay_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let input = dir.join("letter.pdf");
+    std::fs::write(&input, make_test_pdf(2)).unwrap();
+    let letterhead = dir.join("letterhead.pdf");
+    std::fs::write(&letterhead, make_test_pdf(1)).unwrap();
+
+    let output = dir.join("out.pdf");
+    handle_command(Commands::Overlay {
+        input,
+        overlay: letterhead,
+        pages: "all".to_string(),
+        position: "center".to_string(),
+        scale: 1.0,
+        behind: true,
+        output: output.clone(),
+    })
+    .unwrap();
+    let doc = lopdf::Document::load(&output).unwrap();
+    assert_eq!(doc.get_pages().len(), 2);
+    for page_id in doc.get_pages().into_values() {
+        let content = doc.get_page_content(page_id).unwrap();
+        assert!(String::from_utf8_lossy(&content).starts_with("/Artifact BMC"));
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_cli_diff_command() {
+    let dir = std::env::temp_dir().join("office2pdf_cli_diff_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let two = dir.join("two.pdf");
+    std::fs::write(&two, make_test_pdf(2)).unwrap();
+    let three = dir.join("three.pdf");
+    std::fs::write(&three, make_test_pdf(3)).unwrap();
+
+    let diff = |a: &Path, b: &Path| {
+        handle_command(Commands::Diff {
+            a: a.to_path_buf(),
+            b: b.to_path_buf(),
+            pixels: false,
+            dpi: 72.0,
+            tolerance: 16,
+            outdir: None,
+        })
+    };
+    diff(&two, &two).unwrap();
+    let err = diff(&two, &three).unwrap_err();
+    assert!(err.to_string().contains("differ"), "{err}");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_cli_number_command_bates() {
+    let dir = std::env::temp_dir().join("office2pdf_cli_number_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let input = dir.join("exhibit.pdf");
+    std::fs::write(&input, make_test_pdf(2)).unwrap();
+
+    let output = dir.join("out.pdf");
+    handle_command(Commands::Number {
+        input,
+        bates: Some("ACME".to_string()),
+        digits: 4,
+        of_total: false,
+        start: 11,
+        pages: "all".to_string(),
+        position: None,
+        output: output.clone(),
+    })
+    .unwrap();
+    let doc = lopdf::Document::load(&output).unwrap();
+    let contents: Vec<String> = doc
+        .get_pages()
+        .into_values()
+        .map(|page_id| {
+            String::from_utf8_lossy(&doc.get_page_content(page_id).unwrap()).into_owned()
+        })
+        .collect();
+    assert!(contents[0].contains("(ACME0011) Tj"), "{}", contents[0]);
+    assert!(contents[1].contains("(ACME0012) Tj"), "{}", contents[1]);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
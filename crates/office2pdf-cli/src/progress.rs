@@ -0,0 +1,134 @@
+//! What the CLI says while it converts: how much (`--quiet`, `-v`, `-vv`)
+//! and, on a terminal, progress bars for the batch and for the stage each
+//! file being converted has reached.
+//!
+//! The bars are drawn on standard error and only when it is a terminal, so
+//! logs and pipes get the plain per-file lines alone.
+
+use std::path::Path;
+use std::time::Duration;
+
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use office2pdf::config::{Progress, ProgressCallback, ProgressStage};
+
+/// How much the CLI prints besides errors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Verbosity {
+    /// Failures only.
+    Quiet,
+    /// A line per file, noting how many warnings it had, and a summary.
+    #[default]
+    Normal,
+    /// The warnings themselves too.
+    Verbose,
+    /// Per-stage timing metrics too.
+    Debug,
+}
+
+impl Verbosity {
+    /// The level of `--quiet`, the number of `-v`s and `--metrics`, which
+    /// is `-vv` by its old name.
+    pub(crate) fn from_flags(quiet: bool, verbose: u8, metrics: bool) -> Self {
+        match verbose {
+            _ if quiet => Verbosity::Quiet,
+            _ if metrics => Verbosity::Debug,
+            0 => Verbosity::Normal,
+            1 => Verbosity::Verbose,
+            _ => Verbosity::Debug,
+        }
+    }
+}
+
+/// The bars of a batch: how many files are done, and above it one line per
+/// file being converted, saying what it is at.
+pub(crate) struct BatchProgress {
+    bars: MultiProgress,
+    overall: ProgressBar,
+}
+
+impl BatchProgress {
+    pub(crate) fn new(files: usize, verbosity: Verbosity) -> Self {
+        let target: ProgressDrawTarget = if verbosity == Verbosity::Quiet {
+            ProgressDrawTarget::hidden()
+        } else {
+            ProgressDrawTarget::stderr()
+        };
+        let bars = MultiProgress::with_draw_target(target);
+        let overall: ProgressBar = bars.add(ProgressBar::new(files as u64));
+        overall.set_style(
+            ProgressStyle::with_template("[{bar:30}] {pos}/{len} files, {elapsed} ({eta} left)")
+                .expect("valid progress template")
+                .progress_chars("=> "),
+        );
+        Self { bars, overall }
+    }
+
+    /// Show `input` as being converted until the returned handle drops.
+    pub(crate) fn start_file(&self, input: &Path) -> FileProgress {
+        let bar: ProgressBar = self
+            .bars
+            .insert_before(&self.overall, ProgressBar::new_spinner());
+        bar.set_style(
+            ProgressStyle::with_template("{spinner} {prefix}: {msg}")
+                .expect("valid progress template"),
+        );
+        let name = input.file_name().unwrap_or(input.as_os_str());
+        bar.set_prefix(name.to_string_lossy().into_owned());
+        bar.set_message("starting");
+        bar.enable_steady_tick(Duration::from_millis(100));
+        FileProgress { bar }
+    }
+
+    /// Run `print` with the bars cleared, so its lines don't tear them.
+    pub(crate) fn suspend<R>(&self, print: impl FnOnce() -> R) -> R {
+        self.bars.suspend(print)
+    }
+
+    /// Count one more file as done, however it went.
+    pub(crate) fn file_done(&self) {
+        self.overall.inc(1);
+    }
+
+    /// Take the bars off the terminal, before the summary.
+    pub(crate) fn finish(&self) {
+        self.overall.finish_and_clear();
+    }
+}
+
+/// The line of a file being converted; cleared when dropped.
+pub(crate) struct FileProgress {
+    bar: ProgressBar,
+}
+
+impl FileProgress {
+    /// The callback moving this line along with the conversion.
+    pub(crate) fn callback(&self) -> ProgressCallback {
+        let bar: ProgressBar = self.bar.clone();
+        ProgressCallback::new(move |progress: Progress| bar.set_message(stage_message(progress)))
+    }
+}
+
+impl Drop for FileProgress {
+    fn drop(&mut self) {
+        self.bar.finish_and_clear();
+    }
+}
+
+/// What a file's line says at `progress`, such as "compiling 3/12".
+fn stage_message(progress: Progress) -> String {
+    let stage: &str = match progress.stage {
+        ProgressStage::Parse => "parsing",
+        ProgressStage::Codegen => "laying out",
+        ProgressStage::Compile => "compiling",
+        ProgressStage::Merge => "merging",
+    };
+    if progress.total > 1 {
+        format!("{stage} {}/{}", progress.current, progress.total)
+    } else {
+        stage.to_string()
+    }
+}
+
+#[cfg(test)]
+#[path = "progress_tests.rs"]
+mod tests;
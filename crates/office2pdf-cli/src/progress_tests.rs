@@ -0,0 +1,59 @@
+use super::*;
+
+#[test]
+fn test_verbosity_from_flags() {
+    assert_eq!(Verbosity::from_flags(false, 0, false), Verbosity::Normal);
+    assert_eq!(Verbosity::from_flags(true, 0, false), Verbosity::Quiet);
+    assert_eq!(Verbosity::from_flags(false, 1, false), Verbosity::Verbose);
+    assert_eq!(Verbosity::from_flags(false, 3, false), Verbosity::Debug);
+    assert_eq!(Verbosity::from_flags(false, 0, true), Verbosity::Debug);
+    assert!(Verbosity::Quiet < Verbosity::Normal && Verbosity::Verbose < Verbosity::Debug);
+}
+
+#[test]
+fn test_stage_message_counts_units_of_long_stages() {
+    let progress = |stage: ProgressStage, current: u32, total: u32| Progress {
+        stage,
+        current,
+        total,
+        elapsed: Duration::ZERO,
+    };
+    assert_eq!(
+        stage_message(progress(ProgressStage::Parse, 0, 1)),
+        "parsing"
+    );
+    assert_eq!(
+        stage_message(progress(ProgressStage::Compile, 3, 12)),
+        "compiling 3/12"
+    );
+}
+
+#[test]
+fn test_file_progress_follows_the_conversion() {
+    let docx = docx_rs::Docx::new()
+        .add_paragraph(docx_rs::Paragraph::new().add_run(docx_rs::Run::new().add_text("Progress")));
+    let mut buf = std::io::Cursor::new(Vec::new());
+    docx.build().pack(&mut buf).unwrap();
+
+    // Hidden, as in tests and pipes: the bars still keep their state.
+    let progress = BatchProgress::new(1, Verbosity::Quiet);
+    let file: FileProgress = progress.start_file(Path::new("docs/report.docx"));
+    assert_eq!(file.bar.message(), "starting");
+    let options = office2pdf::config::ConvertOptions {
+        progress: Some(file.callback()),
+        ..Default::default()
+    };
+    office2pdf::convert_bytes(
+        &buf.into_inner(),
+        office2pdf::config::Format::Docx,
+        &options,
+    )
+    .unwrap();
+    assert!(file.bar.message().starts_with("compiling"));
+    assert_eq!(file.bar.prefix(), "report.docx");
+
+    drop(file);
+    progress.file_done();
+    assert_eq!(progress.overall.position(), 1);
+    progress.finish();
+}
@@ -0,0 +1,148 @@
+//! Machine-readable results: with `--json`, one JSON object per input on a
+//! line of its own on standard output, as each input finishes.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use office2pdf::error::{ConvertMetrics, ConvertWarning};
+
+use crate::tolerant::Fallback;
+
+/// What became of an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Status {
+    Converted,
+    /// Left unconverted because its output exists.
+    Skipped,
+    /// Left unconverted because it has not changed since its output was
+    /// made.
+    Unchanged,
+    Failed(String),
+}
+
+/// The result of one input, as `--json` prints it.
+#[derive(Debug)]
+pub(crate) struct FileReport<'a> {
+    pub(crate) input: &'a Path,
+    pub(crate) output: Option<&'a Path>,
+    pub(crate) status: Status,
+    pub(crate) warnings: &'a [ConvertWarning],
+    pub(crate) metrics: Option<&'a ConvertMetrics>,
+    /// How much of the input `--tolerant` left out to convert it.
+    pub(crate) fallback: Option<Fallback>,
+}
+
+impl FileReport<'_> {
+    /// The report as a single line of JSON:
+    ///
+    /// ```json
+    /// {"input":"a.docx","status":"converted","output":"a.pdf",
+    ///  "fallback":"no-images","pages":2,
+    ///  "warnings":[{"code":"fallback_used","severity":"info","format":"DOCX",
+    ///  "location":"paragraph 3","message":"..."}],
+    ///  "metrics":{"parse_ms":1.2,"codegen_ms":0.3,"compile_ms":40.1,
+    ///  "total_ms":41.6,"input_bytes":9000,"output_bytes":12000}}
+    /// ```
+    ///
+    /// `fallback` is only there for an input converted with one, and
+    /// `error` takes the place of the page count and metrics of a failed
+    /// input; a warning raised more than once is listed once.
+    pub(crate) fn to_json(&self) -> String {
+        let mut json = String::from("{");
+        let _ = write!(json, "\"input\":{}", json_string(&path_text(self.input)));
+        let status: &str = match self.status {
+            Status::Converted => "converted",
+            Status::Skipped => "skipped",
+            Status::Unchanged => "unchanged",
+            Status::Failed(_) => "failed",
+        };
+        let _ = write!(json, ",\"status\":\"{status}\"");
+        if let Some(output) = self.output {
+            let _ = write!(json, ",\"output\":{}", json_string(&path_text(output)));
+        }
+        if let Some(fallback) = self.fallback {
+            let _ = write!(json, ",\"fallback\":\"{}\"", fallback.name());
+        }
+        if let Status::Failed(error) = &self.status {
+            let _ = write!(json, ",\"error\":{}", json_string(error));
+        }
+        if let Some(metrics) = self.metrics {
+            let _ = write!(json, ",\"pages\":{}", metrics.page_count);
+        }
+
+        json.push_str(",\"warnings\":[");
+        let mut seen: Vec<&ConvertWarning> = Vec::new();
+        for warning in self.warnings {
+            if seen.contains(&warning) {
+                continue;
+            }
+            if !seen.is_empty() {
+                json.push(',');
+            }
+            let _ = write!(
+                json,
+                "{{\"code\":\"{}\",\"severity\":\"{}\",\"format\":{}",
+                warning.code(),
+                warning.severity(),
+                json_string(warning.format())
+            );
+            if let Some(location) = warning.location() {
+                let _ = write!(json, ",\"location\":{}", json_string(&location.to_string()));
+            }
+            let _ = write!(json, ",\"message\":{}}}", json_string(&warning.to_string()));
+            seen.push(warning);
+        }
+        json.push(']');
+
+        if let Some(metrics) = self.metrics {
+            let _ = write!(json, ",\"metrics\":{}", metrics_json(metrics));
+        }
+        json.push('}');
+        json
+    }
+}
+
+/// Stage timings in milliseconds and sizes in bytes, as a JSON object; the
+/// server reports job metrics in the same shape.
+pub(crate) fn metrics_json(metrics: &ConvertMetrics) -> String {
+    let millis = |duration: std::time::Duration| duration.as_secs_f64() * 1000.0;
+    format!(
+        "{{\"parse_ms\":{:.3},\"codegen_ms\":{:.3},\"compile_ms\":{:.3},\
+         \"total_ms\":{:.3},\"input_bytes\":{},\"output_bytes\":{}}}",
+        millis(metrics.parse_duration),
+        millis(metrics.codegen_duration),
+        millis(metrics.compile_duration),
+        millis(metrics.total_duration),
+        metrics.input_size_bytes,
+        metrics.output_size_bytes
+    )
+}
+
+fn path_text(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+/// `text` as a JSON string literal.
+pub(crate) fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+#[path = "report_tests.rs"]
+mod tests;
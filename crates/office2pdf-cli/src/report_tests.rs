@@ -0,0 +1,81 @@
+use super::*;
+
+use std::time::Duration;
+
+use office2pdf::error::WarningLocation;
+
+fn fallback(location: Option<WarningLocation>) -> ConvertWarning {
+    ConvertWarning::FallbackUsed {
+        format: "DOCX".to_string(),
+        from: "SmartArt".to_string(),
+        to: "image".to_string(),
+        location,
+    }
+}
+
+#[test]
+fn test_file_report_json_for_a_converted_input() {
+    let warnings: Vec<ConvertWarning> = vec![
+        fallback(Some(WarningLocation::Paragraph(3))),
+        fallback(Some(WarningLocation::Paragraph(3))),
+        fallback(None),
+    ];
+    let metrics = ConvertMetrics {
+        parse_duration: Duration::from_micros(1500),
+        codegen_duration: Duration::from_millis(2),
+        compile_duration: Duration::from_millis(30),
+        total_duration: Duration::from_micros(33500),
+        input_size_bytes: 1024,
+        output_size_bytes: 2048,
+        page_count: 5,
+    };
+    let report = FileReport {
+        input: Path::new("in/a.docx"),
+        output: Some(Path::new("out/a.pdf")),
+        status: Status::Converted,
+        warnings: &warnings,
+        metrics: Some(&metrics),
+        fallback: Some(Fallback::NoImages),
+    };
+    let expected_warning = |location: &str| {
+        format!(
+            "{{\"code\":\"fallback_used\",\"severity\":\"info\",\"format\":\"DOCX\"{location},\
+             \"message\":{}}}",
+            json_string(
+                &fallback(match location {
+                    "" => None,
+                    _ => Some(WarningLocation::Paragraph(3)),
+                })
+                .to_string()
+            )
+        )
+    };
+    assert_eq!(
+        report.to_json(),
+        format!(
+            "{{\"input\":\"in/a.docx\",\"status\":\"converted\",\"output\":\"out/a.pdf\",\
+             \"fallback\":\"no-images\",\"pages\":5,\"warnings\":[{},{}],\"metrics\":{{\"parse_ms\":1.500,\
+             \"codegen_ms\":2.000,\"compile_ms\":30.000,\"total_ms\":33.500,\
+             \"input_bytes\":1024,\"output_bytes\":2048}}}}",
+            expected_warning(",\"location\":\"paragraph 3\""),
+            expected_warning("")
+        )
+    );
+}
+
+#[test]
+fn test_file_report_json_for_a_failed_input() {
+    let report = FileReport {
+        input: Path::new("bad \"one\".docx"),
+        output: None,
+        status: Status::Failed("line 1\n\tbroken \\ here\u{1}".to_string()),
+        warnings: &[],
+        metrics: None,
+        fallback: None,
+    };
+    assert_eq!(
+        report.to_json(),
+        "{\"input\":\"bad \\\"one\\\".docx\",\"status\":\"failed\",\
+         \"error\":\"line 1\\n\\tbroken \\\\ here\\u0001\",\"warnings\":[]}"
+    );
+}
@@ -3,32 +3,49 @@
 //! Provides a REST API for document conversion via `office2pdf serve`.
 
 use std::collections::HashMap;
+use std::panic::{AssertUnwindSafe, catch_unwind};
 use std::sync::Arc;
 
 use anyhow::Result;
 use office2pdf::config::{ConvertOptions, Format, PaperSize};
 
+use crate::jobs::{self, JobQueue, JobSettings, JobStatus, SubmitError};
 use crate::metrics::{self, MetricsStore};
+use crate::report::{json_string, metrics_json};
 
-/// Start the HTTP server on the given host and port.
-pub fn start_server(host: &str, port: u16) -> Result<()> {
+/// Start the HTTP server on the given host and port, answering up to
+/// `threads` requests at once and running `/jobs` conversions as `jobs`
+/// says.
+pub fn start_server(host: &str, port: u16, threads: usize, jobs: JobSettings) -> Result<()> {
     let addr = format!("{host}:{port}");
     let server = tiny_http::Server::http(&addr)
         .map_err(|e| anyhow::anyhow!("failed to bind to {addr}: {e}"))?;
 
     let metrics = Arc::new(MetricsStore::new());
+    let queue = start_job_queue(jobs, Arc::clone(&metrics));
 
     eprintln!("office2pdf server listening on http://{addr}");
     eprintln!("Endpoints:");
-    eprintln!("  POST /convert  - Convert a document to PDF");
-    eprintln!("  GET  /health   - Health check");
-    eprintln!("  GET  /formats  - List supported formats");
-    eprintln!("  GET  /metrics  - Prometheus metrics");
-
-    for mut request in server.incoming_requests() {
-        let response = dispatch(&mut request, &metrics);
-        let _ = request.respond(response);
-    }
+    eprintln!("  POST /convert           - Convert a document to PDF");
+    eprintln!("  POST /jobs              - Queue a document for conversion");
+    eprintln!("  GET  /jobs/{{id}}         - Status of a queued conversion");
+    eprintln!("  GET  /jobs/{{id}}/result  - PDF of a finished conversion");
+    eprintln!("  GET  /health            - Health check");
+    eprintln!("  GET  /formats           - List supported formats");
+    eprintln!("  GET  /metrics           - Prometheus metrics");
+
+    // Every thread takes the next request, so polling a job is not stuck
+    // behind a synchronous `/convert` or a large upload.
+    std::thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| {
+                for mut request in server.incoming_requests() {
+                    let response = dispatch(&mut request, &metrics, &queue);
+                    let _ = request.respond(response);
+                }
+            });
+        }
+    });
 
     Ok(())
 }
@@ -54,7 +71,25 @@ fn json_response(status: i32, body: &str) -> Response {
         .with_status_code(status)
 }
 
-fn dispatch(request: &mut tiny_http::Request, metrics: &MetricsStore) -> Response {
+/// A job queue whose workers convert with the library and count their
+/// conversions in `metrics`, as `POST /convert` does.
+fn start_job_queue(settings: JobSettings, metrics: Arc<MetricsStore>) -> JobQueue {
+    JobQueue::start(settings, move |data, format, options| {
+        metrics.start_conversion();
+        let result = convert_document(data, format, options);
+        metrics.end_conversion();
+        record_conversion(&metrics, &result);
+        result
+            .map(|outcome| (outcome.pdf, outcome.metrics))
+            .map_err(|failure| failure.message)
+    })
+}
+
+fn dispatch(
+    request: &mut tiny_http::Request,
+    metrics: &MetricsStore,
+    queue: &JobQueue,
+) -> Response {
     let url = request.url().to_string();
     let path = url.split('?').next().unwrap_or(&url).to_string();
     let is_get = *request.method() == tiny_http::Method::Get;
@@ -68,6 +103,13 @@ fn dispatch(request: &mut tiny_http::Request, metrics: &MetricsStore) -> Respons
         handle_metrics(metrics)
     } else if is_post && path == "/convert" {
         handle_convert(request, &url, metrics)
+    } else if is_post && path == "/jobs" {
+        handle_submit_job(request, &url, metrics, queue)
+    } else if let Some(job) = path.strip_prefix("/jobs/").filter(|_| is_get) {
+        match job.strip_suffix("/result") {
+            Some(id) => handle_job_result(queue, id),
+            None => handle_job_status(queue, job),
+        }
     } else {
         json_response(404, r#"{"error":"not found"}"#)
     }
@@ -91,9 +133,31 @@ fn handle_metrics(metrics: &MetricsStore) -> Response {
 
 fn handle_convert(request: &mut tiny_http::Request, url: &str, metrics: &MetricsStore) -> Response {
     metrics.start_conversion();
-    let result = handle_convert_inner(request, url);
+    let result = read_submission(request, url).and_then(|submission| {
+        convert_document(&submission.data, submission.format, &submission.options)
+    });
     metrics.end_conversion();
+    record_conversion(metrics, &result);
 
+    match result {
+        Ok(outcome) => pdf_response(outcome.pdf),
+        Err(failure) => json_response(
+            400,
+            &format!(r#"{{"error":{}}}"#, json_string(&failure.message)),
+        ),
+    }
+}
+
+fn pdf_response(pdf: Vec<u8>) -> Response {
+    tiny_http::Response::from_data(pdf)
+        .with_header(pdf_header())
+        .with_status_code(200)
+}
+
+fn record_conversion(
+    metrics: &MetricsStore,
+    result: &std::result::Result<ConvertOutcome, ConvertFailure>,
+) {
     match result {
         Ok(outcome) => {
             let format_label = metrics::format_to_label(outcome.format);
@@ -108,15 +172,91 @@ fn handle_convert(request: &mut tiny_http::Request, url: &str, metrics: &Metrics
             } else {
                 metrics.record_success(format_label, 0.0, 0, 0, 0);
             }
-            tiny_http::Response::from_data(outcome.pdf)
-                .with_header(pdf_header())
-                .with_status_code(200)
         }
+        Err(failure) => metrics.record_failure(&failure.format_label, &failure.error_type),
+    }
+}
+
+/// `POST /jobs`: queue the document and answer `202 Accepted` with the ID
+/// to poll, without waiting for the conversion.
+fn handle_submit_job(
+    request: &mut tiny_http::Request,
+    url: &str,
+    metrics: &MetricsStore,
+    queue: &JobQueue,
+) -> Response {
+    let submission = match read_submission(request, url) {
+        Ok(submission) => submission,
         Err(failure) => {
+            // Counted like a `/convert` request that fails the same way.
             metrics.record_failure(&failure.format_label, &failure.error_type);
-            let msg = failure.message.replace('"', "\\\"");
-            json_response(400, &format!(r#"{{"error":"{msg}"}}"#))
+            return json_response(
+                400,
+                &format!(r#"{{"error":{}}}"#, json_string(&failure.message)),
+            );
+        }
+    };
+    match queue.submit(submission.data, submission.format, submission.options) {
+        Ok(id) => {
+            let location =
+                tiny_http::Header::from_bytes("Location", format!("/jobs/{id}")).unwrap();
+            json_response(202, &format!(r#"{{"id":"{id}","status":"queued"}}"#))
+                .with_header(location)
         }
+        Err(SubmitError::QueueFull) => json_response(503, r#"{"error":"job queue is full"}"#),
+        Err(SubmitError::NoRandomness(e)) => json_response(
+            500,
+            &format!(
+                r#"{{"error":{}}}"#,
+                json_string(&format!("cannot make a job ID: {e}"))
+            ),
+        ),
+    }
+}
+
+/// `GET /jobs/{id}`: the status of a job, with its page count and metrics
+/// once done or its error once failed.
+fn handle_job_status(queue: &JobQueue, id: &str) -> Response {
+    let Some(report) = queue.report(id) else {
+        return json_response(404, r#"{"error":"job not found"}"#);
+    };
+    let mut body = format!(
+        r#"{{"id":{},"status":"{}","format":"{}""#,
+        json_string(id),
+        report.status.name(),
+        metrics::format_to_label(report.format)
+    );
+    if let Some(ref m) = report.metrics {
+        body.push_str(&format!(
+            r#","pages":{},"metrics":{}"#,
+            m.page_count,
+            metrics_json(m)
+        ));
+    }
+    if let JobStatus::Failed(ref error) = report.status {
+        body.push_str(&format!(r#","error":{}"#, json_string(error)));
+    }
+    body.push('}');
+    json_response(200, &body)
+}
+
+/// `GET /jobs/{id}/result`: the PDF of a done job; `409 Conflict` while
+/// it is still queued or running, or when it failed.
+fn handle_job_result(queue: &JobQueue, id: &str) -> Response {
+    match queue.result(id) {
+        None => json_response(404, r#"{"error":"job not found"}"#),
+        Some(Ok(pdf)) => pdf_response(pdf),
+        Some(Err(JobStatus::Failed(error))) => json_response(
+            409,
+            &format!(r#"{{"status":"failed","error":{}}}"#, json_string(&error)),
+        ),
+        Some(Err(status)) => json_response(
+            409,
+            &format!(
+                r#"{{"status":"{}","error":"job has not finished"}}"#,
+                status.name()
+            ),
+        ),
     }
 }
 
@@ -132,10 +272,18 @@ struct ConvertFailure {
     error_type: String,
 }
 
-fn handle_convert_inner(
+/// A document posted to `/convert` or `/jobs`, with the options its query
+/// string asks for.
+struct Submission {
+    data: Vec<u8>,
+    format: Format,
+    options: ConvertOptions,
+}
+
+fn read_submission(
     request: &mut tiny_http::Request,
     url: &str,
-) -> std::result::Result<ConvertOutcome, ConvertFailure> {
+) -> std::result::Result<Submission, ConvertFailure> {
     // Read body
     let mut body = Vec::new();
     request
@@ -178,21 +326,24 @@ fn handle_convert_inner(
             error_type: "unsupported_format".to_string(),
         })?
     } else {
-        detect_format_from_filename(&file.filename).ok_or_else(|| ConvertFailure {
-            message: format!("cannot detect format from filename: {}", file.filename),
-            format_label: "unknown".to_string(),
-            error_type: "unsupported_format".to_string(),
-        })?
+        detect_format_from_filename(&file.filename)
+            .or_else(|| Format::detect(&file.data))
+            .ok_or_else(|| ConvertFailure {
+                message: format!(
+                    "cannot detect format from filename or contents: {}",
+                    file.filename
+                ),
+                format_label: "unknown".to_string(),
+                error_type: "unsupported_format".to_string(),
+            })?
     };
 
-    let format_label = metrics::format_to_label(format).to_string();
-
     // Build options
     let mut options = ConvertOptions::default();
     if let Some(paper) = query.get("paper") {
         options.paper_size = Some(PaperSize::parse(paper).map_err(|e| ConvertFailure {
             message: e.to_string(),
-            format_label: format_label.clone(),
+            format_label: metrics::format_to_label(format).to_string(),
             error_type: "invalid_request".to_string(),
         })?);
     }
@@ -202,13 +353,35 @@ fn handle_convert_inner(
         options.landscape = Some(true);
     }
 
-    // Convert
-    let result =
-        office2pdf::convert_bytes(&file.data, format, &options).map_err(|e| ConvertFailure {
-            message: format!("conversion failed: {e}"),
-            format_label,
-            error_type: "conversion".to_string(),
-        })?;
+    Ok(Submission {
+        data: file.data,
+        format,
+        options,
+    })
+}
+
+fn convert_document(
+    data: &[u8],
+    format: Format,
+    options: &ConvertOptions,
+) -> std::result::Result<ConvertOutcome, ConvertFailure> {
+    let format_label: String = metrics::format_to_label(format).to_string();
+    // The library catches parser panics but not every other one; without
+    // this a panic would kill the request thread or job worker and skip
+    // `end_conversion`, leaving the active gauge high for good.
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        office2pdf::convert_bytes(data, format, options)
+    }))
+    .map_err(|panic| ConvertFailure {
+        message: format!("conversion panicked: {}", jobs::panic_message(&*panic)),
+        format_label: format_label.clone(),
+        error_type: "panic".to_string(),
+    })?
+    .map_err(|e| ConvertFailure {
+        message: format!("conversion failed: {e}"),
+        format_label,
+        error_type: "conversion".to_string(),
+    })?;
 
     Ok(ConvertOutcome {
         pdf: result.pdf,
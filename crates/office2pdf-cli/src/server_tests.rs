@@ -116,11 +116,12 @@ fn start_test_server(n: usize) -> (std::thread::JoinHandle<()>, u16, Arc<Metrics
 
     let metrics = Arc::new(MetricsStore::new());
     let metrics_clone = Arc::clone(&metrics);
+    let queue = start_job_queue(test_job_settings(), Arc::clone(&metrics));
 
     let handle = std::thread::spawn(move || {
         for _ in 0..n {
             if let Ok(mut request) = server.recv() {
-                let response = dispatch(&mut request, &metrics_clone);
+                let response = dispatch(&mut request, &metrics_clone, &queue);
                 let _ = request.respond(response);
             }
         }
@@ -129,6 +130,82 @@ fn start_test_server(n: usize) -> (std::thread::JoinHandle<()>, u16, Arc<Metrics
     (handle, port, metrics)
 }
 
+fn test_job_settings() -> JobSettings {
+    JobSettings {
+        workers: 1,
+        max_queued: 10,
+        retention: std::time::Duration::from_secs(60),
+        max_retained_bytes: usize::MAX,
+    }
+}
+
+/// Start a server on an ephemeral port that keeps serving until the test
+/// process exits, for tests that poll a job an unknown number of times.
+fn start_job_test_server() -> String {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let port = match server.server_addr() {
+        tiny_http::ListenAddr::IP(addr) => addr.port(),
+        _ => panic!("expected IP address"),
+    };
+
+    let metrics = Arc::new(MetricsStore::new());
+    let queue = start_job_queue(test_job_settings(), Arc::clone(&metrics));
+    std::thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let response = dispatch(&mut request, &metrics, &queue);
+            let _ = request.respond(response);
+        }
+    });
+
+    format!("127.0.0.1:{port}")
+}
+
+/// Submit `data` as `filename` to `POST /jobs`, returning the job ID.
+fn submit_job(addr: &str, data: &[u8], filename: &str) -> String {
+    let boundary = "JobBoundary";
+    let multipart_body = build_multipart_body(data, filename, boundary);
+    let content_type = format!("multipart/form-data; boundary={boundary}");
+    let resp = send_request(
+        addr,
+        "POST",
+        "/jobs",
+        &[("Content-Type", &content_type)],
+        &multipart_body,
+    );
+    assert_eq!(resp.status_code, 202, "{}", resp.body_str());
+
+    let body = resp.body_str();
+    let id = body
+        .split("\"id\":\"")
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .expect("response should carry the job ID")
+        .to_string();
+    assert_eq!(
+        resp.headers.get("location").map(|s| s.as_str()),
+        Some(format!("/jobs/{id}").as_str())
+    );
+    id
+}
+
+/// Poll `GET /jobs/{id}` until the job is no longer queued or running.
+fn wait_for_job(addr: &str, id: &str) -> String {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(60);
+    loop {
+        let resp = send_request(addr, "GET", &format!("/jobs/{id}"), &[], &[]);
+        assert_eq!(resp.status_code, 200);
+        let body = resp.body_str();
+        if !body.contains("\"status\":\"queued\"") && !body.contains("\"status\":\"running\"") {
+            return body;
+        }
+        assert!(
+            std::time::Instant::now() < deadline,
+            "job never finished: {body}"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+}
+
 struct HttpResponse {
     status_code: u16,
     #[allow(dead_code)]
@@ -323,6 +400,34 @@ fn test_convert_invalid_format_error() {
     handle.join().unwrap();
 }
 
+#[test]
+fn test_convert_error_body_escapes_the_message() {
+    let (handle, port, _metrics) = start_test_server(1);
+    let addr = format!("127.0.0.1:{port}");
+
+    // The message quotes the file name, whose backslash must be escaped
+    // for the body to stay valid JSON.
+    let boundary = "TestBoundary24680";
+    let multipart_body = build_multipart_body(b"not a document", "a\\q.txt", boundary);
+    let content_type = format!("multipart/form-data; boundary={boundary}");
+
+    let resp = send_request(
+        &addr,
+        "POST",
+        "/convert",
+        &[("Content-Type", &content_type)],
+        &multipart_body,
+    );
+
+    assert_eq!(resp.status_code, 400);
+    assert_eq!(
+        resp.body_str(),
+        r#"{"error":"cannot detect format from filename or contents: a\\q.txt"}"#
+    );
+
+    handle.join().unwrap();
+}
+
 #[test]
 fn test_convert_with_format_override() {
     let (handle, port, _metrics) = start_test_server(1);
@@ -501,3 +606,63 @@ fn test_metrics_multiple_conversions() {
 
     handle.join().unwrap();
 }
+
+#[test]
+fn test_job_converts_docx_and_serves_the_result() {
+    let addr = start_job_test_server();
+
+    let id = submit_job(&addr, &make_test_docx(), "test.docx");
+    let status = wait_for_job(&addr, &id);
+    assert!(status.contains("\"status\":\"done\""), "{status}");
+    assert!(status.contains("\"format\":\"docx\""), "{status}");
+    assert!(status.contains("\"pages\":1"), "{status}");
+    assert!(status.contains("\"metrics\":{\"parse_ms\":"), "{status}");
+
+    let resp = send_request(&addr, "GET", &format!("/jobs/{id}/result"), &[], &[]);
+    assert_eq!(resp.status_code, 200);
+    assert!(resp.content_type().unwrap().contains("application/pdf"));
+    assert!(resp.body.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_failed_job_reports_error_and_has_no_result() {
+    let addr = start_job_test_server();
+
+    let id = submit_job(&addr, b"not a document", "broken.docx");
+    let status = wait_for_job(&addr, &id);
+    assert!(status.contains("\"status\":\"failed\""), "{status}");
+    assert!(status.contains("\"error\":\"conversion failed"), "{status}");
+
+    let resp = send_request(&addr, "GET", &format!("/jobs/{id}/result"), &[], &[]);
+    assert_eq!(resp.status_code, 409);
+    assert!(resp.body_str().contains("\"status\":\"failed\""));
+}
+
+#[test]
+fn test_unknown_job_returns_404() {
+    let addr = start_job_test_server();
+
+    let status = send_request(&addr, "GET", "/jobs/0123456789abcdef", &[], &[]);
+    assert_eq!(status.status_code, 404);
+    let result = send_request(&addr, "GET", "/jobs/0123456789abcdef/result", &[], &[]);
+    assert_eq!(result.status_code, 404);
+}
+
+#[test]
+fn test_job_with_undetectable_format_is_rejected() {
+    let addr = start_job_test_server();
+
+    let boundary = "JobBoundary";
+    let multipart_body = build_multipart_body(b"not a document", "test.txt", boundary);
+    let content_type = format!("multipart/form-data; boundary={boundary}");
+    let resp = send_request(
+        &addr,
+        "POST",
+        "/jobs",
+        &[("Content-Type", &content_type)],
+        &multipart_body,
+    );
+
+    assert_eq!(resp.status_code, 400);
+    assert!(resp.body_str().contains("\"error\""));
+}
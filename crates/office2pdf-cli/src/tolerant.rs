@@ -0,0 +1,235 @@
+//! Retrying failed conversions with less of the document, for
+//! `--tolerant`: a PDF without the pictures, charts or drawing that broke
+//! rendering beats no PDF at all.
+//!
+//! Each fallback drops more than the one before: the images, then the charts
+//! too, then everything but the text.
+
+use std::fmt;
+
+use anyhow::Result;
+use office2pdf::config::{ConvertOptions, DocumentTransform};
+use office2pdf::error::ConvertError;
+use office2pdf::ir::{
+    Block, Document, FixedElementKind, HFInline, HeaderFooter, Insets, Page, Paragraph,
+    ParagraphStyle, Run, SmartArt, Table, TextBoxData, TextBoxVerticalAlign, TextStyle,
+};
+
+/// A degraded way to convert a document, in the order they are tried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Fallback {
+    NoImages,
+    NoCharts,
+    TextOnly,
+}
+
+impl Fallback {
+    pub(crate) const ALL: [Fallback; 3] =
+        [Fallback::NoImages, Fallback::NoCharts, Fallback::TextOnly];
+
+    /// The name `--json` reports the fallback by.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Fallback::NoImages => "no-images",
+            Fallback::NoCharts => "no-charts",
+            Fallback::TextOnly => "text-only",
+        }
+    }
+
+    /// `options` with the document degraded before rendering. The CLI sets
+    /// no transform of its own, so there is none to keep.
+    pub(crate) fn options(self, options: &ConvertOptions) -> ConvertOptions {
+        ConvertOptions {
+            transform: Some(DocumentTransform::new(move |doc: &mut Document| {
+                degrade(doc, self)
+            })),
+            ..options.clone()
+        }
+    }
+}
+
+impl fmt::Display for Fallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Fallback::NoImages => "without images",
+            Fallback::NoCharts => "without images and charts",
+            Fallback::TextOnly => "text only",
+        })
+    }
+}
+
+/// Convert with `convert`, and with `tolerant`, when that fails in a way a
+/// simpler document might not, again with each fallback in turn. The first
+/// error is the one returned when every fallback fails too, as it tells
+/// what is wrong with the document.
+pub(crate) fn convert_tolerant<T>(
+    tolerant: bool,
+    options: &ConvertOptions,
+    convert: impl Fn(&ConvertOptions) -> Result<T>,
+) -> Result<(T, Option<Fallback>)> {
+    let err = match convert(options) {
+        Ok(converted) => return Ok((converted, None)),
+        Err(err) if tolerant && is_retryable(&err) => err,
+        Err(err) => return Err(err),
+    };
+    for fallback in Fallback::ALL {
+        if let Ok(converted) = convert(&fallback.options(options)) {
+            return Ok((converted, Some(fallback)));
+        }
+    }
+    Err(err)
+}
+
+/// Whether `err` may come of the content a fallback drops: rendering
+/// failures, and limits that big pictures can exceed. Unreadable, encrypted
+/// or unsupported input fails the same way however much is dropped.
+fn is_retryable(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<ConvertError>(),
+            Some(ConvertError::Render(_) | ConvertError::LimitExceeded(_))
+        )
+    })
+}
+
+/// Drop what `fallback` leaves out of `doc`.
+pub(crate) fn degrade(doc: &mut Document, fallback: Fallback) {
+    let text_only: bool = fallback == Fallback::TextOnly;
+    for page in &mut doc.pages {
+        match page {
+            Page::Flow(page) => {
+                degrade_blocks(&mut page.content, fallback);
+                for header_footer in [&mut page.header, &mut page.footer].into_iter().flatten() {
+                    degrade_header_footer(header_footer);
+                }
+            }
+            Page::Fixed(page) => {
+                page.background_image = None;
+                if text_only {
+                    page.background_color = None;
+                    page.background_gradient = None;
+                }
+                page.elements.retain_mut(|element| match &mut element.kind {
+                    FixedElementKind::Image(_) => false,
+                    FixedElementKind::Chart(_) => fallback < Fallback::NoCharts,
+                    FixedElementKind::Shape(_) => !text_only,
+                    FixedElementKind::SmartArt(smart_art) => {
+                        if text_only {
+                            element.kind = FixedElementKind::TextBox(smart_art_text(smart_art));
+                        }
+                        true
+                    }
+                    FixedElementKind::TextBox(text_box) => {
+                        degrade_blocks(&mut text_box.content, fallback);
+                        if text_only {
+                            text_box.fill = None;
+                            text_box.opacity = None;
+                            text_box.stroke = None;
+                            text_box.shape_kind = None;
+                        }
+                        true
+                    }
+                    FixedElementKind::Table(table) => {
+                        degrade_table(table, fallback);
+                        true
+                    }
+                });
+            }
+            Page::Sheet(page) => {
+                page.images.clear();
+                if fallback >= Fallback::NoCharts {
+                    page.charts.clear();
+                }
+                degrade_table(&mut page.table, fallback);
+                for header_footer in [&mut page.header, &mut page.footer].into_iter().flatten() {
+                    degrade_header_footer(header_footer);
+                }
+            }
+        }
+    }
+}
+
+fn degrade_blocks(blocks: &mut Vec<Block>, fallback: Fallback) {
+    let text_only: bool = fallback == Fallback::TextOnly;
+    blocks.retain_mut(|block| match block {
+        Block::Image(_) | Block::InlineImages(_) | Block::FloatingImage(_) => false,
+        Block::Chart(_) => fallback < Fallback::NoCharts,
+        Block::FloatingShape(_) => !text_only,
+        Block::MathEquation(equation) => {
+            // The notation itself still reads as text.
+            if text_only {
+                *block = Block::Paragraph(text_paragraph(equation.content.clone()));
+            }
+            true
+        }
+        Block::FloatingTextBox(text_box) => {
+            degrade_blocks(&mut text_box.content, fallback);
+            true
+        }
+        Block::Table(table) => {
+            degrade_table(table, fallback);
+            true
+        }
+        Block::Paragraph(_) | Block::List(_) | Block::PageBreak | Block::ColumnBreak => true,
+    });
+}
+
+fn degrade_table(table: &mut Table, fallback: Fallback) {
+    for cell in table.rows.iter_mut().flat_map(|row| row.cells.iter_mut()) {
+        degrade_blocks(&mut cell.content, fallback);
+        if fallback == Fallback::TextOnly {
+            cell.data_bar = None;
+        }
+    }
+}
+
+fn degrade_header_footer(header_footer: &mut HeaderFooter) {
+    for paragraph in &mut header_footer.paragraphs {
+        paragraph
+            .elements
+            .retain(|element| !matches!(element, HFInline::Image(_)));
+    }
+}
+
+/// A plain text box holding the nodes of `smart_art`, one paragraph each,
+/// indented by depth.
+fn smart_art_text(smart_art: &SmartArt) -> TextBoxData {
+    TextBoxData {
+        content: smart_art
+            .items
+            .iter()
+            .map(|node| {
+                Block::Paragraph(text_paragraph(format!(
+                    "{}{}",
+                    "    ".repeat(node.depth),
+                    node.text
+                )))
+            })
+            .collect(),
+        padding: Insets::default(),
+        vertical_align: TextBoxVerticalAlign::Top,
+        fill: None,
+        opacity: None,
+        stroke: None,
+        shape_kind: None,
+        no_wrap: false,
+        auto_fit: false,
+        text_rotation_deg: None,
+    }
+}
+
+fn text_paragraph(text: String) -> Paragraph {
+    Paragraph {
+        style: ParagraphStyle::default(),
+        runs: vec![Run {
+            text,
+            style: TextStyle::default(),
+            href: None,
+            footnote: None,
+        }],
+    }
+}
+
+#[cfg(test)]
+#[path = "tolerant_tests.rs"]
+mod tests;
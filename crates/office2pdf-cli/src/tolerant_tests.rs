@@ -0,0 +1,132 @@
+use super::*;
+
+use std::cell::RefCell;
+
+use office2pdf::ir::{
+    Chart, ChartGrouping, ChartType, FlowPage, ImageData, ImageFormat, Margins, MathEquation,
+    Metadata, PageSize, StyleSheet,
+};
+
+fn image() -> ImageData {
+    ImageData {
+        data: vec![],
+        format: ImageFormat::Png,
+        width: Some(20.0),
+        height: Some(10.0),
+        crop: None,
+        stroke: None,
+        alignment: None,
+        clip_shape: None,
+        shadow: None,
+    }
+}
+
+fn flow_document() -> Document {
+    let chart = Chart {
+        chart_type: ChartType::Bar,
+        title: None,
+        categories: vec![],
+        series: vec![],
+        grouping: ChartGrouping::default(),
+    };
+    let page = FlowPage {
+        size: PageSize::default(),
+        margins: Margins::default(),
+        content: vec![
+            Block::Paragraph(text_paragraph("Intro".to_string())),
+            Block::Image(image()),
+            Block::Chart(chart),
+            Block::MathEquation(MathEquation {
+                content: "x^2".to_string(),
+                display: true,
+            }),
+        ],
+        header: None,
+        footer: None,
+        columns: None,
+        line_grid_pitch: None,
+    };
+    Document {
+        metadata: Metadata::default(),
+        pages: vec![Page::Flow(page)],
+        styles: StyleSheet::default(),
+    }
+}
+
+/// The kinds of the blocks of the first page, as their debug names.
+fn block_kinds(doc: &Document) -> Vec<String> {
+    let Page::Flow(page) = &doc.pages[0] else {
+        panic!("expected a flow page");
+    };
+    page.content
+        .iter()
+        .map(|block| {
+            let name: String = format!("{block:?}");
+            name[..name.find('(').unwrap_or(name.len())].to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn test_fallbacks_drop_more_each_time() {
+    let mut doc = flow_document();
+    degrade(&mut doc, Fallback::NoImages);
+    assert_eq!(block_kinds(&doc), ["Paragraph", "Chart", "MathEquation"]);
+
+    let mut doc = flow_document();
+    degrade(&mut doc, Fallback::NoCharts);
+    assert_eq!(block_kinds(&doc), ["Paragraph", "MathEquation"]);
+
+    let mut doc = flow_document();
+    degrade(&mut doc, Fallback::TextOnly);
+    assert_eq!(block_kinds(&doc), ["Paragraph", "Paragraph"]);
+    assert!(doc.to_plain_text().contains("x^2"));
+}
+
+#[test]
+fn test_convert_tolerant_retries_render_failures_only() {
+    let tried: RefCell<Vec<bool>> = RefCell::new(Vec::new());
+    let options = ConvertOptions::default();
+
+    // Fails until the document is degraded twice.
+    let result = convert_tolerant(true, &options, |options: &ConvertOptions| {
+        tried.borrow_mut().push(options.transform.is_some());
+        if tried.borrow().len() < 3 {
+            Err(
+                anyhow::Error::new(ConvertError::Render("bad image".to_string()))
+                    .context("converting \"a.docx\""),
+            )
+        } else {
+            Ok("pdf")
+        }
+    })
+    .unwrap();
+    assert_eq!(result, ("pdf", Some(Fallback::NoCharts)));
+    assert_eq!(*tried.borrow(), [false, true, true]);
+
+    // Without --tolerant, and for errors no fallback can help, one try.
+    for (tolerant, render) in [(false, true), (true, false)] {
+        tried.borrow_mut().clear();
+        let result = convert_tolerant(tolerant, &options, |_: &ConvertOptions| -> Result<()> {
+            tried.borrow_mut().push(true);
+            Err(if render {
+                ConvertError::Render("bad image".to_string())
+            } else {
+                ConvertError::Parse("not a zip".to_string())
+            }
+            .into())
+        });
+        assert!(result.is_err());
+        assert_eq!(tried.borrow().len(), 1);
+    }
+
+    // Every fallback failing returns the first error.
+    let attempts: RefCell<usize> = RefCell::new(0);
+    let err = convert_tolerant(true, &options, |_: &ConvertOptions| -> Result<()> {
+        *attempts.borrow_mut() += 1;
+        Err(ConvertError::Render(format!("attempt {}", attempts.borrow())).into())
+    })
+    .unwrap_err();
+    assert_eq!(*attempts.borrow(), 1 + Fallback::ALL.len());
+    assert!(err.to_string().contains("attempt 1"));
+}
@@ -1,3 +1,9 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::error::WarningSeverity;
+use crate::ir::{Color, Document, Metadata};
+
 /// Supported input document formats.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
@@ -17,6 +23,37 @@ impl Format {
             _ => None,
         }
     }
+
+    /// Detect the format from the file's contents: the content type of the
+    /// main part of an OOXML package, so a misnamed file is still read
+    /// correctly. Returns `None` for anything else, including encrypted
+    /// files, whose package is only readable once decrypted, and legacy
+    /// binary `.doc`, `.xls` and `.ppt` files;
+    /// [`convert_bytes_auto`](crate::convert_bytes_auto) decrypts the first
+    /// and names the second in its error.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        crate::parser::sniff::detect_package(data)
+    }
+
+    /// The file extension, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Docx => "docx",
+            Self::Pptx => "pptx",
+            Self::Xlsx => "xlsx",
+        }
+    }
+
+    /// The media type of the format's documents.
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Docx => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            Self::Pptx => {
+                "application/vnd.openxmlformats-officedocument.presentationml.presentation"
+            }
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        }
+    }
 }
 
 /// A range of slide numbers (1-indexed) for PPTX conversion.
@@ -75,8 +112,671 @@ impl SlideRange {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub enum PdfStandard {
+    /// PDF/A-1b, the PDF 1.4 archival level older validators and archives
+    /// ask for. It forbids transparency, so documents using opacity or
+    /// shape effects fail to export.
+    PdfA1b,
     /// PDF/A-2b for archival purposes.
     PdfA2b,
+    /// PDF/A-3b: PDF/A-2b that also allows files of any type to be
+    /// attached, such as the source document.
+    PdfA3b,
+    /// PDF/X-4 for print exchange. The PDF also conforms to PDF/A-2b, whose
+    /// sRGB ICC profile doubles as the PDF/X output intent, matching the
+    /// colors the document is drawn in. Validators also expect a document
+    /// title, which comes from the source's metadata. Requires the
+    /// `pdf-ops` feature.
+    PdfX4,
+}
+
+impl PdfStandard {
+    /// Parse a standard name (case-insensitive): "a-1b", "a-2b", "a-3b",
+    /// "x-4", with or without a `pdf/` or `pdf` prefix.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let lower: String = s.to_ascii_lowercase();
+        let name: &str = lower
+            .strip_prefix("pdf/")
+            .or_else(|| lower.strip_prefix("pdf"))
+            .unwrap_or(&lower);
+        match name {
+            "a-1b" | "a1b" => Ok(Self::PdfA1b),
+            "a-2b" | "a2b" => Ok(Self::PdfA2b),
+            "a-3b" | "a3b" => Ok(Self::PdfA3b),
+            "x-4" | "x4" => Ok(Self::PdfX4),
+            _ => Err(format!(
+                "unknown PDF standard: {s}; expected one of: a-1b, a-2b, a-3b, x-4"
+            )),
+        }
+    }
+}
+
+/// Password protection of the output PDF; see
+/// [`ConvertOptions::pdf_security`]. The default allows nothing a viewer
+/// can restrict, so set the permissions to grant.
+#[derive(Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct PdfSecurity {
+    /// Password needed to open the PDF. Empty lets anyone open it, with
+    /// only the permissions below.
+    pub user_password: String,
+    /// Password that opens the PDF with every permission. Must not be
+    /// empty, or anyone could lift the restrictions.
+    pub owner_password: String,
+    /// Allow printing, at full quality.
+    pub allow_print: bool,
+    /// Allow copying text and images. Extraction for accessibility tools
+    /// is always allowed.
+    pub allow_copy: bool,
+    /// Allow editing the content and inserting, rotating or deleting pages.
+    pub allow_modify: bool,
+    /// Allow adding comments and filling in form fields.
+    pub allow_annotate: bool,
+}
+
+impl std::fmt::Debug for PdfSecurity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Passwords stay out of logs; whether anyone can open the PDF is
+        // what matters when reading one.
+        f.debug_struct("PdfSecurity")
+            .field("has_user_password", &!self.user_password.is_empty())
+            .field("allow_print", &self.allow_print)
+            .field("allow_copy", &self.allow_copy)
+            .field("allow_modify", &self.allow_modify)
+            .field("allow_annotate", &self.allow_annotate)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Changes to the document metadata written to the PDF, over what the
+/// source file's properties hold; see [`ConvertOptions::metadata_override`].
+/// For each field, `None` keeps the source's value, `Some(None)` clears it
+/// and `Some(Some(value))` replaces it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MetadataOverride {
+    /// Document title.
+    pub title: Option<Option<String>>,
+    /// Author.
+    pub author: Option<Option<String>>,
+    /// Subject, the PDF's `Subject` entry.
+    pub subject: Option<Option<String>>,
+    /// Description, written as the subject when there is none.
+    pub description: Option<Option<String>>,
+    /// Keywords; `Some(vec![])` clears them.
+    pub keywords: Option<Vec<String>>,
+    /// Company.
+    pub company: Option<Option<String>>,
+    /// Custom properties by name: `Some(value)` sets one, `None` removes it.
+    pub custom_properties: BTreeMap<String, Option<String>>,
+    /// Drop all of the source's custom properties before applying
+    /// `custom_properties`.
+    pub clear_custom_properties: bool,
+}
+
+impl MetadataOverride {
+    /// Apply the override to `metadata`.
+    pub(crate) fn apply(&self, metadata: &mut Metadata) {
+        let fields = [
+            (&self.title, &mut metadata.title),
+            (&self.author, &mut metadata.author),
+            (&self.subject, &mut metadata.subject),
+            (&self.description, &mut metadata.description),
+            (&self.company, &mut metadata.company),
+        ];
+        for (replacement, field) in fields {
+            if let Some(value) = replacement {
+                field.clone_from(value);
+            }
+        }
+        if let Some(keywords) = &self.keywords {
+            metadata.keywords.clone_from(keywords);
+        }
+        if self.clear_custom_properties {
+            metadata.custom_properties.clear();
+        }
+        for (name, value) in &self.custom_properties {
+            let existing: Option<usize> = metadata
+                .custom_properties
+                .iter()
+                .position(|(existing, _)| existing == name);
+            match (existing, value) {
+                (Some(index), Some(value)) => metadata.custom_properties[index].1.clone_from(value),
+                (Some(index), None) => {
+                    metadata.custom_properties.remove(index);
+                }
+                (None, Some(value)) => metadata
+                    .custom_properties
+                    .push((name.clone(), value.clone())),
+                (None, None) => {}
+            }
+        }
+    }
+}
+
+/// A digital signature applied to the output PDF; see
+/// [`ConvertOptions::signing`] and `pdf_ops::sign`. The signature is
+/// PAdES baseline B-B: a detached CAdES signature (RSA with SHA-256)
+/// carrying the signer's certificate chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SigningConfig {
+    /// The signer's private key and certificate.
+    pub key: SigningKey,
+    /// Why the document was signed, shown by PDF viewers.
+    pub reason: Option<String>,
+    /// Where the document was signed, shown by PDF viewers.
+    pub location: Option<String>,
+    /// How to reach the signer, shown by PDF viewers.
+    pub contact_info: Option<String>,
+    /// Where to draw the signature on the page. If `None`, the signature
+    /// is invisible: it shows only in the viewer's signature panel.
+    pub appearance: Option<SignatureAppearance>,
+}
+
+/// The signer's RSA key and certificate chain; see [`SigningConfig::key`].
+#[derive(Clone, PartialEq)]
+pub enum SigningKey {
+    /// A PKCS#12 file (`.p12` or `.pfx`) holding the key and its
+    /// certificate chain, and the password it is protected with.
+    Pkcs12 { data: Vec<u8>, password: String },
+    /// An unencrypted private key in PKCS#8 or PKCS#1 DER, and DER
+    /// certificates, the signer's first, then its issuers.
+    Der {
+        private_key: Vec<u8>,
+        certificates: Vec<Vec<u8>>,
+    },
+}
+
+impl std::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Key material and passwords stay out of logs.
+        match self {
+            Self::Pkcs12 { data, .. } => f
+                .debug_struct("Pkcs12")
+                .field("data_size", &data.len())
+                .finish_non_exhaustive(),
+            Self::Der { certificates, .. } => f
+                .debug_struct("Der")
+                .field("certificate_count", &certificates.len())
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+/// A visible signature box; see [`SigningConfig::appearance`]. It shows the
+/// signer's name, the signing time and the reason, in Helvetica, which is
+/// not embedded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignatureAppearance {
+    /// Page the box is drawn on, 1-indexed.
+    pub page: u32,
+    /// Left edge, in points from the left of the page.
+    pub x: f64,
+    /// Bottom edge, in points from the bottom of the page.
+    pub y: f64,
+    /// Width in points.
+    pub width: f64,
+    /// Height in points.
+    pub height: f64,
+}
+
+impl SignatureAppearance {
+    /// Parse `PAGE:X,Y,WIDTH,HEIGHT`, such as `1:36,36,200,50`, in points
+    /// from the bottom-left corner of the page.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (page, rect) = s
+            .split_once(':')
+            .ok_or_else(|| format!("expected PAGE:X,Y,WIDTH,HEIGHT, got {s}"))?;
+        let page: u32 = page
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid page number: {page}"))?;
+        if page == 0 {
+            return Err("page number must be >= 1".to_string());
+        }
+        let numbers: Vec<f64> = rect
+            .split(',')
+            .map(|n| {
+                n.trim()
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid number: {n}"))
+            })
+            .collect::<Result<_, _>>()?;
+        let [x, y, width, height] = numbers[..] else {
+            return Err(format!("expected X,Y,WIDTH,HEIGHT, got {rect}"));
+        };
+        Ok(Self {
+            page,
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+}
+
+/// Text or an image drawn over the pages of the output PDF, such as
+/// "DRAFT", a Bates number or a confidentiality notice; see
+/// [`ConvertOptions::stamp`] and `pdf_ops::stamp`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stamp {
+    /// What to draw.
+    pub content: StampContent,
+    /// Where on the page to draw it.
+    pub position: StampPosition,
+    /// Distance from the page edges for every position but
+    /// [`StampPosition::Center`], in points.
+    pub margin: f64,
+    /// Counter-clockwise rotation around the stamp's center, in degrees.
+    pub rotation: f64,
+    /// From 0 (invisible) to 1 (opaque).
+    pub opacity: f64,
+    /// The pages to stamp.
+    pub pages: StampPages,
+}
+
+impl Stamp {
+    /// `text` in 48 pt gray Helvetica, half transparent, centered on every
+    /// page.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            content: StampContent::Text {
+                text: text.into(),
+                font_size: 48.0,
+                color: Color::new(128, 128, 128),
+            },
+            position: StampPosition::Center,
+            margin: 36.0,
+            rotation: 0.0,
+            opacity: 0.5,
+            pages: StampPages::All,
+        }
+    }
+
+    /// A PNG or JPEG image `width` points wide, opaque, centered on every
+    /// page.
+    pub fn image(data: Vec<u8>, width: f64) -> Self {
+        Self {
+            content: StampContent::Image { data, width },
+            opacity: 1.0,
+            ..Self::text(String::new())
+        }
+    }
+}
+
+/// What a [`Stamp`] draws.
+#[derive(Clone, PartialEq)]
+pub enum StampContent {
+    /// A line of Helvetica, which is not embedded. `{page}` and `{pages}`
+    /// are replaced by the page number and the page count, for Bates
+    /// numbering. Characters outside Latin-1 are drawn as `?`.
+    Text {
+        text: String,
+        font_size: f64,
+        color: Color,
+    },
+    /// A PNG or JPEG image `width` points wide, as tall as its aspect ratio
+    /// makes it.
+    Image { data: Vec<u8>, width: f64 },
+}
+
+impl std::fmt::Debug for StampContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text {
+                text,
+                font_size,
+                color,
+            } => f
+                .debug_struct("Text")
+                .field("text", text)
+                .field("font_size", font_size)
+                .field("color", color)
+                .finish(),
+            // Image bytes run to megabytes; their size says enough.
+            Self::Image { data, width } => f
+                .debug_struct("Image")
+                .field("data_size", &data.len())
+                .field("width", width)
+                .finish(),
+        }
+    }
+}
+
+/// Where a [`Stamp`] sits on the page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StampPosition {
+    #[default]
+    Center,
+    TopLeft,
+    TopCenter,
+    TopRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl StampPosition {
+    /// Parse a stamp position string (case-insensitive): "center",
+    /// "top-left", "top", "top-right", "bottom-left", "bottom",
+    /// "bottom-right".
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "center" | "middle" => Ok(Self::Center),
+            "top-left" => Ok(Self::TopLeft),
+            "top" | "top-center" => Ok(Self::TopCenter),
+            "top-right" => Ok(Self::TopRight),
+            "bottom-left" => Ok(Self::BottomLeft),
+            "bottom" | "bottom-center" => Ok(Self::BottomCenter),
+            "bottom-right" => Ok(Self::BottomRight),
+            _ => Err(format!(
+                "unknown stamp position: {s}; expected one of: center, top-left, top, \
+                 top-right, bottom-left, bottom, bottom-right"
+            )),
+        }
+    }
+}
+
+/// The pages a [`Stamp`] is drawn on, numbered from 1 in the output PDF.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum StampPages {
+    #[default]
+    All,
+    First,
+    /// Inclusive `(start, end)` page ranges.
+    Ranges(Vec<(u32, u32)>),
+}
+
+impl StampPages {
+    /// Parse a stamp page selection (case-insensitive): "all", "first", or
+    /// comma-separated pages and ranges such as "1,3-5".
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "all" => return Ok(Self::All),
+            "first" => return Ok(Self::First),
+            _ => {}
+        }
+        let ranges: Vec<(u32, u32)> = s
+            .split(',')
+            .map(|part| {
+                let part: &str = part.trim();
+                let (start, end) = part.split_once('-').unwrap_or((part, part));
+                let number = |n: &str| {
+                    n.trim()
+                        .parse::<u32>()
+                        .map_err(|_| format!("invalid page number: {n}"))
+                };
+                Ok((number(start)?, number(end)?))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self::Ranges(ranges))
+    }
+
+    /// Whether page `number` is stamped.
+    pub fn contains(&self, number: u32) -> bool {
+        match self {
+            Self::All => true,
+            Self::First => number == 1,
+            Self::Ranges(ranges) => ranges
+                .iter()
+                .any(|&(start, end)| (start..=end).contains(&number)),
+        }
+    }
+}
+
+/// Bleed and crop marks for commercial printing; see [`ConvertOptions::bleed`]
+/// and `pdf_ops::bleed`. The page as converted becomes the trim box, what
+/// the printer cuts to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Bleed {
+    /// How far the page extends past the trim box on every side, in points:
+    /// 9 pt is the customary eighth of an inch, 8.5 pt about 3 mm. Content
+    /// the source places past the page edge, such as a PPTX picture hanging
+    /// off the slide, shows in the bleed; page backgrounds do not reach it.
+    pub margin: f64,
+    /// Draw crop marks at the corners of the trim box, outside the bleed.
+    pub crop_marks: bool,
+}
+
+/// Recompression of raster images; see
+/// [`ConvertOptions::image_optimization`]. An image is only replaced when
+/// the result is smaller.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ImageOptimization {
+    /// Downsample images holding more pixels per inch than this at the
+    /// size they are placed. `None` keeps every pixel.
+    pub max_dpi: Option<f32>,
+    /// JPEG quality, 1 to 100, of re-encoded images; setting it also
+    /// re-encodes JPEGs that are not downsampled. `None` uses 85 for
+    /// images that are re-encoded anyway.
+    pub jpeg_quality: Option<u8>,
+    /// Store opaque PNG, BMP and TIFF photographs as JPEG. Images with
+    /// transparency or few colours, like screenshots and charts, stay
+    /// lossless.
+    pub png_to_jpeg: bool,
+}
+
+impl Default for ImageOptimization {
+    /// 150 dpi, quality 85 and JPEG photos: screen and office-printer
+    /// quality.
+    fn default() -> Self {
+        Self {
+            max_dpi: Some(150.0),
+            jpeg_quality: None,
+            png_to_jpeg: true,
+        }
+    }
+}
+
+/// How characters that render as stray glyphs are handled between parsing
+/// and rendering; see [`ConvertOptions::text_sanitization`]. Removed
+/// characters are reported as
+/// [`ConvertWarning::ContentOmitted`](crate::error::ConvertWarning::ContentOmitted),
+/// one per character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub enum TextSanitization {
+    /// Leave text as parsed.
+    Off,
+    /// Remove control characters other than tabs and line breaks (form
+    /// feeds, field separators and the like), noncharacters and object
+    /// replacement characters, which no font has a glyph for. Soft hyphens, zero-width spaces, bidirectional marks
+    /// and non-breaking spaces are kept: Typst lays them out correctly.
+    #[default]
+    Normalize,
+    /// Also remove soft hyphens, zero-width spaces, bidirectional marks
+    /// and other invisible formatting characters, so the PDF's text layer
+    /// holds only what is seen. Non-breaking spaces and the zero-width
+    /// joiners some scripts need are kept.
+    Strip,
+}
+
+/// How shape effects without a Typst primitive (glow, reflection, soft
+/// edges, 3-D rotation and extrusion) are reproduced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub enum EffectFidelity {
+    /// Approximate effects with layered translucent copies of the shape.
+    #[default]
+    Approximate,
+    /// Omit glow, reflection, soft-edge, and 3-D effects.
+    Off,
+}
+
+impl EffectFidelity {
+    /// Parse an effect fidelity string (case-insensitive): "approximate", "off".
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "approximate" => Ok(Self::Approximate),
+            "off" | "none" => Ok(Self::Off),
+            _ => Err(format!(
+                "unknown effect fidelity: {s}; expected one of: approximate, off"
+            )),
+        }
+    }
+}
+
+/// Slide size preset a PPTX deck can be rescaled to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub enum SlideAspect {
+    /// 4:3 on-screen show: 720pt × 540pt (10in × 7.5in).
+    Standard4x3,
+    /// 16:9 widescreen: 960pt × 540pt (13.333in × 7.5in).
+    Widescreen16x9,
+}
+
+impl SlideAspect {
+    /// Returns (width, height) in points.
+    pub fn dimensions(&self) -> (f64, f64) {
+        match self {
+            Self::Standard4x3 => (720.0, 540.0),
+            Self::Widescreen16x9 => (960.0, 540.0),
+        }
+    }
+
+    /// Parse a slide aspect string (case-insensitive): "4:3", "16:9",
+    /// "standard", "widescreen".
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "4:3" | "standard" => Ok(Self::Standard4x3),
+            "16:9" | "widescreen" => Ok(Self::Widescreen16x9),
+            _ => Err(format!(
+                "unknown slide aspect: {s}; expected one of: 4:3, 16:9"
+            )),
+        }
+    }
+}
+
+/// How slide content is fitted when [`ConvertOptions::slide_scale_to`]
+/// changes the slide aspect ratio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub enum SlideScaleMode {
+    /// Scale content uniformly until it fits and center it, leaving empty
+    /// bands on two sides (PowerPoint's "Ensure Fit").
+    #[default]
+    Fit,
+    /// Scale content uniformly until it fills the slide and center it,
+    /// cropping what overflows (PowerPoint's "Maximize").
+    Maximize,
+    /// Scale positions and sizes on each axis independently so content
+    /// spans the whole slide; text and strokes follow the smaller factor.
+    Stretch,
+}
+
+impl SlideScaleMode {
+    /// Parse a slide scale mode string (case-insensitive): "fit",
+    /// "maximize", "stretch".
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "fit" | "letterbox" => Ok(Self::Fit),
+            "maximize" | "fill" => Ok(Self::Maximize),
+            "stretch" => Ok(Self::Stretch),
+            _ => Err(format!(
+                "unknown slide scale mode: {s}; expected one of: fit, maximize, stretch"
+            )),
+        }
+    }
+}
+
+/// Image encoding for [`render_to_images`](crate::render_to_images) and
+/// [`render_slides_to_images`](crate::render_slides_to_images).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub enum SlideImageFormat {
+    /// Rasterized PNG at [`SlideImageOptions::dpi`].
+    #[default]
+    Png,
+    /// Resolution-independent SVG; text is embedded as glyph outlines.
+    Svg,
+}
+
+impl SlideImageFormat {
+    /// Parse a slide image format string (case-insensitive): "png", "svg".
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "png" => Ok(Self::Png),
+            "svg" => Ok(Self::Svg),
+            _ => Err(format!(
+                "unknown slide image format: {s}; expected one of: png, svg"
+            )),
+        }
+    }
+
+    /// File extension for images in this format, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+/// Options for rendering slides, or the pages of any document, to images
+/// instead of PDF.
+#[derive(Debug, Clone)]
+pub struct SlideImageOptions {
+    /// Output encoding.
+    pub format: SlideImageFormat,
+    /// Raster resolution in dots per inch. Ignored for SVG.
+    pub dpi: f32,
+    /// Parse and layout options shared with PDF conversion (slide range,
+    /// slide size, font paths, ...). PDF-only settings are ignored.
+    pub convert: ConvertOptions,
+}
+
+impl Default for SlideImageOptions {
+    fn default() -> Self {
+        Self {
+            format: SlideImageFormat::Png,
+            // 2x the 72pt-per-inch slide grid: sharp thumbnails at a
+            // moderate file size.
+            dpi: 144.0,
+            convert: ConvertOptions::default(),
+        }
+    }
+}
+
+/// A kind of file embedded in an Office package, for
+/// [`extract_assets`](crate::extract_assets).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub enum AssetKind {
+    /// Pictures in the package's media folder (PNG, JPEG, EMF, SVG, ...).
+    Image,
+    /// Other media and embedded objects: audio, video, OLE packages.
+    Media,
+    /// Embedded fonts, deobfuscated (DOCX and PPTX only).
+    Font,
+}
+
+impl AssetKind {
+    /// Every kind, in the order they are listed.
+    pub const ALL: [AssetKind; 3] = [AssetKind::Image, AssetKind::Media, AssetKind::Font];
+
+    /// Parse an asset kind (case-insensitive, singular or plural): "images",
+    /// "media", "fonts".
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "image" | "images" => Ok(Self::Image),
+            "media" => Ok(Self::Media),
+            "font" | "fonts" => Ok(Self::Font),
+            _ => Err(format!(
+                "unknown asset kind: {s}; expected one of: images, media, fonts"
+            )),
+        }
+    }
+
+    /// The plural name, as used for the folder the CLI extracts into.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Image => "images",
+            Self::Media => "media",
+            Self::Font => "fonts",
+        }
+    }
 }
 
 /// Paper size for output PDF.
@@ -119,14 +819,62 @@ impl PaperSize {
 }
 
 /// Options controlling the conversion process.
-#[derive(Debug, Clone, Default)]
+#[derive(Clone, Default)]
 #[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct ConvertOptions {
     /// Filter XLSX sheets by name. Only sheets whose names are in this list
     /// will be included. If `None`, all sheets are included.
     pub sheet_names: Option<Vec<String>>,
+    /// Convert only these XLSX ranges, in the order given: `Sheet1!A1:F50`,
+    /// `'My Sheet'!B2:D9`, or the name of a workbook defined name. Each range
+    /// prints like its sheet with the range as the print area. If `None`,
+    /// whole sheets are converted.
+    pub xlsx_ranges: Option<Vec<String>>,
+    /// Render hidden XLSX sheets, rows, and columns. By default they are
+    /// left out, as Excel does when printing, and a warning lists what was
+    /// omitted.
+    pub include_hidden: bool,
+    /// Print light gridlines around XLSX cells on every sheet, as Excel's
+    /// "Print gridlines" does. Sheets whose page setup asks for gridlines
+    /// print them either way.
+    pub xlsx_gridlines: bool,
+    /// Print Excel's row numbers and column letters around every XLSX
+    /// sheet. Sheets whose page setup asks for headings print them either
+    /// way.
+    pub xlsx_headings: bool,
+    /// Don't repeat an XLSX sheet's frozen rows and columns on every printed
+    /// page. By default frozen panes act as print titles for sheets that
+    /// set none of their own.
+    pub xlsx_ignore_frozen_panes: bool,
+    /// Repeat this many leading columns on every page an XLSX sheet too wide
+    /// for the paper overflows onto, for sheets without print-title or
+    /// frozen columns of their own. `0` repeats nothing, as Excel does.
+    pub xlsx_row_label_columns: u32,
+    /// Label every page of an XLSX sheet split across pages by width with
+    /// the columns it shows ("Columns H–P") in the page header.
+    pub xlsx_label_column_pages: bool,
+    /// Print the cells of protected XLSX sheets whose format marks them
+    /// hidden as blank, so neither their formulas nor their values are
+    /// shared. A warning counts the redacted cells of each sheet.
+    pub xlsx_redact_hidden: bool,
+    /// Print XLSX error values such as `#DIV/0!` blank on every sheet, as
+    /// Excel's "Cell errors as: <blank>" page setup does. Sheets set to
+    /// print errors as `--` or `#N/A` do so when this is off.
+    pub xlsx_blank_errors: bool,
+    /// Mark XLSX cells that offer a dropdown list (list data validation)
+    /// with a small ▼, and follow each sheet with a page listing the values
+    /// its dropdowns allow.
+    pub xlsx_annotate_dropdowns: bool,
+    /// Locale (a BCP 47 tag such as `de-DE`) whose decimal and thousands
+    /// separators and short date XLSX numbers are shown with. If `None`,
+    /// numbers look as they do in en-US Excel.
+    pub locale: Option<String>,
     /// Filter PPTX slides by range (1-indexed). If `None`, all slides are included.
     pub slide_range: Option<SlideRange>,
+    /// Filter PPTX slides by section name (`<p14:section>`). Only slides in
+    /// the listed sections are included; combined with `slide_range`, a slide
+    /// must satisfy both. If `None`, sections are ignored.
+    pub slide_sections: Option<Vec<String>>,
     /// PDF standard to enforce. If `None`, produces a standard PDF 1.7.
     pub pdf_standard: Option<PdfStandard>,
     /// Override paper size for the output PDF. If `None`, uses the source document's size.
@@ -134,16 +882,42 @@ pub struct ConvertOptions {
     /// Additional font directories to search for fonts.
     #[cfg_attr(feature = "typescript", ts(type = "Array<string>"))]
     pub font_paths: Vec<std::path::PathBuf>,
+    /// Font substitutions, per-script fallbacks, fonts supplied in memory
+    /// and whether installed system fonts are used.
+    pub fonts: FontConfig,
     /// Force landscape orientation. If `Some(true)`, swaps width/height so width > height.
     /// If `Some(false)`, forces portrait. If `None`, uses source document orientation.
     pub landscape: Option<bool>,
     /// Enable tagged PDF output with document structure tags (H1-H6, P, Table, Figure).
     /// When `true`, the output PDF includes accessibility tags that map document
-    /// structure for screen readers and assistive technologies.
+    /// structure for screen readers and assistive technologies. Page headers
+    /// and footers, slide backgrounds, and bare slide shapes are marked as
+    /// artifacts, keeping them out of the reading order.
     pub tagged: bool,
     /// Enable PDF/UA (Universal Accessibility) compliance. Implies `tagged: true`.
     /// Combines tagged PDF with the PDF/UA-1 standard for full accessibility compliance.
     pub pdf_ua: bool,
+    /// Make the PDF depend on the input alone, for content-addressed storage
+    /// and caching: the date and time XLSX headers show, the signing time
+    /// and, unless the document has its own date, the creation timestamp
+    /// PDF/A and PDF/UA require are pinned to the Unix epoch, and the
+    /// document ID is a hash of the content. The same bytes converted
+    /// with the same options and fonts then give a byte-identical PDF.
+    /// Cannot be combined with [`pdf_security`](Self::pdf_security), whose
+    /// encryption key must be random.
+    pub deterministic: bool,
+    /// Character drawn in place of characters no available font has a
+    /// glyph for, which otherwise show as the font's missing-glyph box.
+    /// Either way each such character is reported once as a
+    /// [`ConvertWarning::MissingGlyph`](crate::error::ConvertWarning::MissingGlyph).
+    pub missing_glyph_replacement: Option<char>,
+    /// Downsample and recompress raster images before they are embedded.
+    /// Requires the `image-opt` feature. `None` embeds images as found.
+    pub image_optimization: Option<ImageOptimization>,
+    /// Return the generated Typst markup and the images it references in
+    /// [`ConvertResult::typst_sources`](crate::error::ConvertResult::typst_sources),
+    /// to see why a page is laid out as it is or to attach to a bug report.
+    pub emit_typst_source: bool,
     /// Enable streaming mode for large file processing.
     /// In streaming mode, XLSX files are processed in chunks of rows to bound memory usage.
     /// Each chunk is compiled independently and the resulting PDFs are merged.
@@ -152,8 +926,378 @@ pub struct ConvertOptions {
     /// Chunk size (in rows) for streaming mode. Defaults to 1000 if `None`.
     /// Only used when `streaming` is `true`.
     pub streaming_chunk_size: Option<usize>,
+    /// Compile a deck's slides in batches on the rayon thread pool and
+    /// merge the PDFs, instead of as one Typst document on one thread.
+    /// Requires the `rayon` feature. Documents with flowing or sheet pages,
+    /// decks too small to split, and PDF/A or tagged output, whose
+    /// document-wide structure a merge would lose, compile sequentially.
+    pub parallel_compile: bool,
+    /// How glow, reflection, and soft-edge shape effects are rendered.
+    /// Exact reproduction is impossible in Typst, so the default approximates
+    /// them with layered translucent shapes; `Off` omits them.
+    pub effects: EffectFidelity,
+    /// Rescale every PPTX slide to this size preset. Element positions and
+    /// sizes are mapped onto the new slide according to `slide_scale_mode`.
+    /// If `None`, slides keep the deck's own size.
+    pub slide_scale_to: Option<SlideAspect>,
+    /// How content is fitted when `slide_scale_to` changes the aspect ratio.
+    pub slide_scale_mode: SlideScaleMode,
+    /// How control and invisible formatting characters in the text are
+    /// handled, after `transform`. Defaults to
+    /// [`TextSanitization::Normalize`].
+    pub text_sanitization: TextSanitization,
+    /// Hook run on every parsed document before it is rendered, to redact
+    /// text, add a cover page, rewrite fonts and the like. If `None`, the
+    /// document renders as parsed.
+    #[cfg_attr(feature = "typescript", ts(skip))]
+    pub transform: Option<DocumentTransform>,
+    /// Callback told how far the conversion has got, for progress bars and
+    /// server status endpoints. If `None`, nothing is reported.
+    #[cfg_attr(feature = "typescript", ts(skip))]
+    pub progress: Option<ProgressCallback>,
+    /// Resource limits for converting untrusted input. Unset limits are not
+    /// enforced.
+    pub limits: Limits,
+    /// Fail the conversion with
+    /// [`ConvertError::WarningThreshold`](crate::error::ConvertError::WarningThreshold)
+    /// on the first warning of this severity or worse, for pipelines that
+    /// would rather reject a document than publish a degraded PDF. If
+    /// `None`, warnings never fail a conversion.
+    pub fail_on_warning_severity: Option<WarningSeverity>,
+    /// In [`convert_many`](crate::convert_many), add a top-level bookmark
+    /// for each input document, named by its title.
+    pub document_bookmarks: bool,
+    /// In [`convert_many`](crate::convert_many), open the PDF with a table
+    /// of contents listing each input document and the page it starts on.
+    pub table_of_contents: bool,
+    /// Password for an encrypted DOCX, XLSX or PPTX. Files saved with
+    /// "Encrypt with Password" are decrypted before parsing; without a
+    /// password they fail with
+    /// [`ConvertError::UnsupportedEncryption`](crate::error::ConvertError::UnsupportedEncryption),
+    /// and with the wrong one with
+    /// [`ConvertError::IncorrectPassword`](crate::error::ConvertError::IncorrectPassword).
+    /// Ignored for files that are not encrypted.
+    pub input_password: Option<String>,
+    /// Encrypt the PDF with AES-256 behind a user and an owner password and
+    /// restrict what viewers allow without the owner password. Requires the
+    /// `pdf-ops` feature; PDF/A and PDF/X forbid encryption. If `None`, the
+    /// PDF is not encrypted.
+    pub pdf_security: Option<PdfSecurity>,
+    /// Digitally sign the PDF, once everything else is done. Requires the
+    /// `sign` feature; cannot be combined with `pdf_security`. If `None`,
+    /// the PDF is not signed.
+    #[cfg_attr(feature = "typescript", ts(skip))]
+    pub signing: Option<SigningConfig>,
+    /// Set or clear document metadata (title, author, subject, keywords,
+    /// company, custom properties) before it is written to the PDF's
+    /// document information and XMP metadata. Company and custom properties
+    /// are only written with the `pdf-ops` feature. If `None`, the source
+    /// file's properties are written as they are.
+    pub metadata_override: Option<MetadataOverride>,
+    /// Attach the input file to the PDF as an embedded file with the
+    /// `Source` relationship, so the editable original travels with it.
+    /// Requires the `pdf-ops` feature; of the PDF standards only PDF/A-3b
+    /// allows it. In [`convert_many`](crate::convert_many) and
+    /// [`convert_xlsx_many`](crate::convert_xlsx_many) every input is
+    /// attached to the merged PDF.
+    pub embed_source: bool,
+    /// Draw text or an image, such as "DRAFT" or a confidentiality notice,
+    /// over the pages of the PDF. Requires the `pdf-ops` feature; text
+    /// stamps use Helvetica, which is not embedded, so they cannot be
+    /// combined with PDF/A or PDF/X. If `None`, nothing is drawn.
+    #[cfg_attr(feature = "typescript", ts(skip))]
+    pub stamp: Option<Stamp>,
+    /// Linearize the PDF ("fast web view"), so a viewer can show the first
+    /// page before the rest of the file has downloaded. Requires the
+    /// `pdf-ops` feature; cannot be combined with `compact`, `pdf_security`
+    /// or `signing`, which would undo it.
+    pub linearize: bool,
+    /// Shrink the PDF: drop unused objects, compress streams and pack
+    /// objects into compressed object streams. Requires the `pdf-ops`
+    /// feature; cannot be combined with `linearize`, `pdf_security` or
+    /// `signing`, nor with PDF/A-1b, which forbids object streams.
+    pub compact: bool,
+    /// Extend the pages by a bleed margin and set their trim and bleed
+    /// boxes, optionally with crop marks, for commercial printing. Requires
+    /// the `pdf-ops` feature. If `None`, pages keep their size.
+    pub bleed: Option<Bleed>,
+}
+
+impl std::fmt::Debug for ConvertOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Listed without `..`, so a new field cannot be left out. The
+        // input password stays out of logs.
+        let Self {
+            sheet_names,
+            xlsx_ranges,
+            include_hidden,
+            xlsx_gridlines,
+            xlsx_headings,
+            xlsx_ignore_frozen_panes,
+            xlsx_row_label_columns,
+            xlsx_label_column_pages,
+            xlsx_redact_hidden,
+            xlsx_blank_errors,
+            xlsx_annotate_dropdowns,
+            locale,
+            slide_range,
+            slide_sections,
+            pdf_standard,
+            paper_size,
+            font_paths,
+            fonts,
+            landscape,
+            tagged,
+            pdf_ua,
+            deterministic,
+            missing_glyph_replacement,
+            image_optimization,
+            emit_typst_source,
+            streaming,
+            streaming_chunk_size,
+            parallel_compile,
+            effects,
+            slide_scale_to,
+            slide_scale_mode,
+            text_sanitization,
+            transform,
+            progress,
+            limits,
+            fail_on_warning_severity,
+            document_bookmarks,
+            table_of_contents,
+            input_password,
+            pdf_security,
+            signing,
+            metadata_override,
+            embed_source,
+            stamp,
+            linearize,
+            compact,
+            bleed,
+        } = self;
+        f.debug_struct("ConvertOptions")
+            .field("sheet_names", sheet_names)
+            .field("xlsx_ranges", xlsx_ranges)
+            .field("include_hidden", include_hidden)
+            .field("xlsx_gridlines", xlsx_gridlines)
+            .field("xlsx_headings", xlsx_headings)
+            .field("xlsx_ignore_frozen_panes", xlsx_ignore_frozen_panes)
+            .field("xlsx_row_label_columns", xlsx_row_label_columns)
+            .field("xlsx_label_column_pages", xlsx_label_column_pages)
+            .field("xlsx_redact_hidden", xlsx_redact_hidden)
+            .field("xlsx_blank_errors", xlsx_blank_errors)
+            .field("xlsx_annotate_dropdowns", xlsx_annotate_dropdowns)
+            .field("locale", locale)
+            .field("slide_range", slide_range)
+            .field("slide_sections", slide_sections)
+            .field("pdf_standard", pdf_standard)
+            .field("paper_size", paper_size)
+            .field("font_paths", font_paths)
+            .field("fonts", fonts)
+            .field("landscape", landscape)
+            .field("tagged", tagged)
+            .field("pdf_ua", pdf_ua)
+            .field("deterministic", deterministic)
+            .field("missing_glyph_replacement", missing_glyph_replacement)
+            .field("image_optimization", image_optimization)
+            .field("emit_typst_source", emit_typst_source)
+            .field("streaming", streaming)
+            .field("streaming_chunk_size", streaming_chunk_size)
+            .field("parallel_compile", parallel_compile)
+            .field("effects", effects)
+            .field("slide_scale_to", slide_scale_to)
+            .field("slide_scale_mode", slide_scale_mode)
+            .field("text_sanitization", text_sanitization)
+            .field("transform", transform)
+            .field("progress", progress)
+            .field("limits", limits)
+            .field("fail_on_warning_severity", fail_on_warning_severity)
+            .field("document_bookmarks", document_bookmarks)
+            .field("table_of_contents", table_of_contents)
+            .field("has_input_password", &input_password.is_some())
+            .field("pdf_security", pdf_security)
+            .field("signing", signing)
+            .field("metadata_override", metadata_override)
+            .field("embed_source", embed_source)
+            .field("stamp", stamp)
+            .field("linearize", linearize)
+            .field("compact", compact)
+            .field("bleed", bleed)
+            .finish()
+    }
+}
+
+/// Resource limits enforced while converting; see [`ConvertOptions::limits`].
+/// Exceeding one fails the conversion with
+/// [`ConvertError::LimitExceeded`](crate::error::ConvertError::LimitExceeded).
+///
+/// There is no memory ceiling: these bound what a document may contain, not
+/// what converting it allocates. To cap memory, run conversions in a child
+/// process under an OS limit such as `setrlimit` or a cgroup.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct Limits {
+    /// Largest accepted input file, in bytes.
+    pub max_input_bytes: Option<u64>,
+    /// Largest total size of the input's ZIP entries once decompressed, in
+    /// bytes. The entries are inflated and counted before parsing, without
+    /// trusting the sizes the archive declares, so a zip bomb fails after
+    /// at most this many bytes of decompression and before any parser holds
+    /// it in memory.
+    pub max_uncompressed_bytes: Option<u64>,
+    /// Most pages the document may have: slides, sheets and sections before
+    /// rendering and, with the `pdf-ops` feature, pages of the output PDF.
+    pub max_pages: Option<u32>,
+    /// Most images the document may contain.
+    pub max_images: Option<u32>,
+    /// Longest the conversion may take. Checked between pipeline stages, so
+    /// a Typst compile already running finishes before the conversion
+    /// fails.
+    #[cfg_attr(feature = "typescript", ts(type = "number | null"))]
+    pub timeout: Option<std::time::Duration>,
+}
+
+/// Font configuration; see [`ConvertOptions::fonts`]. The default uses the
+/// built-in substitution table and every installed font.
+#[derive(Clone, Default, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct FontConfig {
+    /// Families to render in place of a requested family, in order of
+    /// preference, such as `"Calibri"` to `["Carlito"]`. They are used
+    /// even when the requested family is installed, so output does not
+    /// depend on the machine; the requested family and the built-in
+    /// substitutes follow them in case none is available. Keys match
+    /// family names ignoring ASCII case.
+    pub substitutions: BTreeMap<String, Vec<String>>,
+    /// Families tried, per script, for characters no requested or
+    /// substitute font covers.
+    pub fallbacks: FontFallbacks,
+    /// Font files (TTF, OTF or TTC) supplied in memory, usable like
+    /// installed fonts. On WASM these are the only fonts besides the ones
+    /// bundled with Typst.
+    #[cfg_attr(feature = "typescript", ts(type = "Array<Uint8Array>"))]
+    pub data: Vec<Vec<u8>>,
+    /// Do not discover installed system fonts, including Office's bundled
+    /// fonts on macOS: only `font_paths`, `data` and the fonts bundled with
+    /// Typst are used, so output is the same on every machine.
+    pub disable_system_fonts: bool,
+}
+
+impl std::fmt::Debug for FontConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Font files run to megabytes; their sizes say enough.
+        let data_sizes: Vec<usize> = self.data.iter().map(Vec::len).collect();
+        f.debug_struct("FontConfig")
+            .field("substitutions", &self.substitutions)
+            .field("fallbacks", &self.fallbacks)
+            .field("data_sizes", &data_sizes)
+            .field("disable_system_fonts", &self.disable_system_fonts)
+            .finish()
+    }
+}
+
+/// Ordered fallback families per script; see [`FontConfig::fallbacks`].
+/// CJK and Arabic families are only used for their own scripts; Latin ones
+/// for everything else.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct FontFallbacks {
+    pub latin: Vec<String>,
+    /// For Han, kana, Hangul and Bopomofo characters.
+    pub cjk: Vec<String>,
+    pub arabic: Vec<String>,
+}
+
+impl FontFallbacks {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.latin.is_empty() && self.cjk.is_empty() && self.arabic.is_empty()
+    }
+}
+
+/// A function that edits the parsed [`Document`](crate::ir::Document)
+/// between parsing and rendering; see [`ConvertOptions::transform`].
+///
+/// It runs once per document the pipeline renders: once per conversion,
+/// but once per chunk in streaming XLSX conversion and once per sheet in
+/// [`convert_xlsx_many`](crate::convert_xlsx_many). PPTX sections are split
+/// by slide position after the transform has run, so a transform that adds
+/// or removes slides shifts them.
+#[derive(Clone)]
+pub struct DocumentTransform(Arc<dyn Fn(&mut Document) + Send + Sync>);
+
+impl DocumentTransform {
+    /// Wrap a closure as a transform.
+    pub fn new(transform: impl Fn(&mut Document) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(transform))
+    }
+
+    pub(crate) fn apply(&self, doc: &mut Document) {
+        (self.0)(doc);
+    }
+}
+
+impl std::fmt::Debug for DocumentTransform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DocumentTransform(..)")
+    }
+}
+
+/// A stage of the conversion pipeline; see [`Progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressStage {
+    /// Reading the source document into the IR.
+    Parse,
+    /// Generating Typst markup from the IR.
+    Codegen,
+    /// Compiling Typst markup to PDF. Streaming XLSX conversion generates
+    /// and compiles each chunk in one step and reports it here.
+    Compile,
+    /// Merging separately compiled PDFs into one.
+    Merge,
+}
+
+/// One report to [`ConvertOptions::progress`]: `current` of `total` units
+/// of `stage` are done. A stage is reported when it starts, with `current`
+/// at 0, and again as units finish until `current` equals `total`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    pub stage: ProgressStage,
+    /// Units done: pages (slides, sheets, sections) for codegen and
+    /// compilation, chunks when streaming, 1 for parsing and merging.
+    pub current: u32,
+    pub total: u32,
+    /// Time since the conversion started.
+    pub elapsed: std::time::Duration,
+}
+
+/// A function receiving [`Progress`] reports; see
+/// [`ConvertOptions::progress`]. It is called on the converting thread, so
+/// it should return quickly.
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<dyn Fn(Progress) + Send + Sync>);
+
+impl ProgressCallback {
+    /// Wrap a closure as a progress callback.
+    pub fn new(callback: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    pub(crate) fn report(&self, progress: Progress) {
+        (self.0)(progress);
+    }
 }
 
+impl std::fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressCallback(..)")
+    }
+}
+
+#[path = "config_builder.rs"]
+mod builder;
+pub use builder::ConvertOptionsBuilder;
+
 #[cfg(test)]
 #[path = "config_tests.rs"]
 mod tests;
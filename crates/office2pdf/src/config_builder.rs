@@ -0,0 +1,605 @@
+//! [`ConvertOptionsBuilder`] and [`ConvertOptions::validate`]: options
+//! assembled with chained setters and checked for combinations that would
+//! otherwise only fail, or silently do nothing, once a conversion runs.
+
+use std::path::PathBuf;
+
+use super::{
+    Bleed, ConvertOptions, DocumentTransform, EffectFidelity, FontConfig, ImageOptimization,
+    Limits, MetadataOverride, PaperSize, PdfSecurity, PdfStandard, Progress, ProgressCallback,
+    SigningConfig, SlideAspect, SlideRange, SlideScaleMode, Stamp, StampContent, StampPages,
+};
+use crate::error::{ConvertError, WarningSeverity};
+use crate::ir::Document;
+
+impl ConvertOptions {
+    /// Start building options from the defaults.
+    pub fn builder() -> ConvertOptionsBuilder {
+        ConvertOptionsBuilder::default()
+    }
+
+    /// Check for settings that contradict each other or cannot take
+    /// effect, returning [`ConvertError::InvalidOptions`] for the first one
+    /// found. [`ConvertOptionsBuilder::build`] calls this; options written as
+    /// a struct literal are only checked when the caller asks.
+    pub fn validate(&self) -> Result<(), ConvertError> {
+        if self.pdf_ua && !self.tagged {
+            return Err(invalid("pdf_ua requires tagged output; set tagged as well"));
+        }
+        // PDF/UA-1 builds on PDF 1.7, which PDF/A-1's PDF 1.4 base predates.
+        if self.pdf_ua && self.pdf_standard == Some(PdfStandard::PdfA1b) {
+            return Err(invalid(
+                "pdf_ua cannot be combined with PDF/A-1b; use PDF/A-2b",
+            ));
+        }
+        if self.pdf_standard == Some(PdfStandard::PdfX4) && !cfg!(feature = "pdf-ops") {
+            return Err(invalid(
+                "PDF/X-4 requires the pdf-ops feature to write its output intent",
+            ));
+        }
+        if let Some(security) = &self.pdf_security {
+            if !cfg!(feature = "pdf-ops") {
+                return Err(invalid("pdf_security requires the pdf-ops feature"));
+            }
+            if security.owner_password.is_empty() {
+                return Err(invalid(
+                    "pdf_security needs an owner password, or anyone could lift its restrictions",
+                ));
+            }
+            if self.pdf_standard.is_some() {
+                return Err(invalid(
+                    "PDF/A and PDF/X forbid encryption; drop pdf_standard or pdf_security",
+                ));
+            }
+            // The file key and every AES IV are drawn at random; pinning
+            // them would let anyone who knows the constant read the PDF.
+            if self.deterministic {
+                return Err(invalid(
+                    "deterministic output cannot be encrypted; drop deterministic or pdf_security",
+                ));
+            }
+        }
+        if let Some(signing) = &self.signing {
+            if !cfg!(feature = "sign") {
+                return Err(invalid("signing requires the sign feature"));
+            }
+            // A signature covers the bytes as written; encrypting them
+            // afterwards would break it.
+            if self.pdf_security.is_some() {
+                return Err(invalid("signing cannot be combined with pdf_security"));
+            }
+            if let Some(appearance) = &signing.appearance {
+                let finite: bool = [
+                    appearance.x,
+                    appearance.y,
+                    appearance.width,
+                    appearance.height,
+                ]
+                .iter()
+                .all(|value| value.is_finite());
+                if !finite || appearance.width <= 0.0 || appearance.height <= 0.0 {
+                    return Err(invalid(
+                        "the signature appearance needs a finite position and a positive size",
+                    ));
+                }
+                if appearance.page == 0 {
+                    return Err(invalid("the signature appearance page is 1-indexed"));
+                }
+                // Its text is set in Helvetica, which is not embedded.
+                if self.pdf_standard.is_some() {
+                    return Err(invalid(
+                        "a visible signature cannot be combined with PDF/A or PDF/X; sign invisibly",
+                    ));
+                }
+            }
+        }
+        if self.embed_source {
+            if !cfg!(feature = "pdf-ops") {
+                return Err(invalid("embed_source requires the pdf-ops feature"));
+            }
+            // PDF/A-1 forbids embedded files, PDF/A-2 allows only PDF/A
+            // ones, and PDF/X-4 builds on PDF/A-2b here.
+            if matches!(
+                self.pdf_standard,
+                Some(PdfStandard::PdfA1b | PdfStandard::PdfA2b | PdfStandard::PdfX4)
+            ) {
+                return Err(invalid(
+                    "embed_source needs PDF/A-3b among the PDF standards; PDF/A-1, PDF/A-2 and PDF/X-4 cannot carry the original",
+                ));
+            }
+        }
+        if let Some(stamp) = &self.stamp {
+            if !cfg!(feature = "pdf-ops") {
+                return Err(invalid("stamp requires the pdf-ops feature"));
+            }
+            stamp.check()?;
+            match &stamp.content {
+                // Its text is set in Helvetica, which is not embedded.
+                StampContent::Text { .. } if self.pdf_standard.is_some() => {
+                    return Err(invalid(
+                        "a text stamp cannot be combined with PDF/A or PDF/X; stamp an image",
+                    ));
+                }
+                // An image's alpha channel becomes a soft mask.
+                StampContent::Image { .. } if self.pdf_standard == Some(PdfStandard::PdfA1b) => {
+                    return Err(invalid(
+                        "PDF/A-1 forbids the transparency an image stamp may carry",
+                    ));
+                }
+                _ => {}
+            }
+            if stamp.opacity < 1.0 && self.pdf_standard == Some(PdfStandard::PdfA1b) {
+                return Err(invalid(
+                    "PDF/A-1 forbids transparency; set the stamp opacity to 1",
+                ));
+            }
+        }
+        if let Some(bleed) = &self.bleed {
+            if !cfg!(feature = "pdf-ops") {
+                return Err(invalid("bleed requires the pdf-ops feature"));
+            }
+            bleed.check()?;
+        }
+        if self.linearize || self.compact {
+            if !cfg!(feature = "pdf-ops") {
+                return Err(invalid("linearize and compact require the pdf-ops feature"));
+            }
+            // A linearized file keeps its first page out of object streams.
+            if self.linearize && self.compact {
+                return Err(invalid("linearize cannot be combined with compact"));
+            }
+            // Encrypting rewrites the file and signing appends to it, which
+            // undoes a linearized layout; compacting after them would break
+            // both.
+            if self.pdf_security.is_some() || self.signing.is_some() {
+                return Err(invalid(
+                    "linearize and compact cannot be combined with pdf_security or signing",
+                ));
+            }
+            if self.compact && self.pdf_standard == Some(PdfStandard::PdfA1b) {
+                return Err(invalid("PDF/A-1 forbids the object streams compact writes"));
+            }
+        }
+        if self.streaming && !cfg!(feature = "pdf-ops") {
+            return Err(invalid(
+                "streaming requires the pdf-ops feature to merge its chunks",
+            ));
+        }
+        // Chunks are compiled apart and merged page by page, which drops the
+        // document-level structure tree and PDF/A metadata.
+        if self.streaming && (self.pdf_standard.is_some() || self.tagged) {
+            return Err(invalid(
+                "streaming cannot produce PDF/A or tagged output; turn one of them off",
+            ));
+        }
+        match self.streaming_chunk_size {
+            Some(0) => return Err(invalid("streaming_chunk_size must be at least 1")),
+            Some(_) if !self.streaming => {
+                return Err(invalid(
+                    "streaming_chunk_size has no effect without streaming",
+                ));
+            }
+            _ => {}
+        }
+        if self.parallel_compile && !cfg!(feature = "rayon") {
+            return Err(invalid("parallel_compile requires the rayon feature"));
+        }
+        if let Some(images) = &self.image_optimization {
+            if !cfg!(feature = "image-opt") {
+                return Err(invalid("image_optimization requires the image-opt feature"));
+            }
+            if let Some(max_dpi) = images.max_dpi
+                && !(max_dpi.is_finite() && max_dpi > 0.0)
+            {
+                return Err(invalid(format!(
+                    "image_optimization.max_dpi {max_dpi} must be positive"
+                )));
+            }
+            if let Some(quality) = images.jpeg_quality
+                && !(1..=100).contains(&quality)
+            {
+                return Err(invalid(format!(
+                    "image_optimization.jpeg_quality {quality} must be 1 to 100"
+                )));
+            }
+        }
+        for (name, list) in [
+            ("sheet_names", &self.sheet_names),
+            ("xlsx_ranges", &self.xlsx_ranges),
+            ("slide_sections", &self.slide_sections),
+        ] {
+            if list.as_ref().is_some_and(Vec::is_empty) {
+                return Err(invalid(format!(
+                    "{name} is empty, so nothing would be converted; use None for all"
+                )));
+            }
+        }
+        if let Some(range) = &self.slide_range
+            && (range.start == 0 || range.start > range.end)
+        {
+            return Err(invalid(format!(
+                "slide_range {}-{} is empty; slides are numbered from 1",
+                range.start, range.end
+            )));
+        }
+        if let Some(PaperSize::Custom { width, height }) = self.paper_size
+            && !(width.is_finite() && height.is_finite() && width > 0.0 && height > 0.0)
+        {
+            return Err(invalid(format!(
+                "paper_size {width}x{height} pt must be positive"
+            )));
+        }
+        if let Some((family, _)) = self
+            .fonts
+            .substitutions
+            .iter()
+            .find(|(_, substitutes)| substitutes.is_empty())
+        {
+            return Err(invalid(format!(
+                "fonts.substitutions maps \"{family}\" to no families"
+            )));
+        }
+        if self.slide_scale_to.is_none() && self.slide_scale_mode != SlideScaleMode::default() {
+            return Err(invalid(
+                "slide_scale_mode has no effect without slide_scale_to",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Bleed {
+    /// Check that the bleed changes the pages: a finite margin of 0 or more
+    /// points, with crop marks if it is 0.
+    pub(crate) fn check(&self) -> Result<(), ConvertError> {
+        if !self.margin.is_finite() || self.margin < 0.0 {
+            return Err(invalid(format!(
+                "the bleed margin must be 0 or more points, got {}",
+                self.margin
+            )));
+        }
+        if self.margin == 0.0 && !self.crop_marks {
+            return Err(invalid(
+                "a bleed of 0 without crop marks leaves the pages as they are",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Stamp {
+    /// Check that the stamp can be drawn: a visible size, an opacity from 0
+    /// to 1 and page ranges that count from 1.
+    pub(crate) fn check(&self) -> Result<(), ConvertError> {
+        let size: f64 = match &self.content {
+            StampContent::Text { font_size, .. } => *font_size,
+            StampContent::Image { width, .. } => *width,
+        };
+        if !(size.is_finite() && size > 0.0) {
+            return Err(invalid(format!(
+                "the stamp size must be a positive number of points, got {size}"
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.opacity) {
+            return Err(invalid(format!(
+                "the stamp opacity must be between 0 and 1, got {}",
+                self.opacity
+            )));
+        }
+        if !(self.margin.is_finite() && self.rotation.is_finite()) {
+            return Err(invalid("the stamp margin and rotation must be finite"));
+        }
+        if let StampPages::Ranges(ranges) = &self.pages
+            && let Some((start, end)) = ranges
+                .iter()
+                .find(|&&(start, end)| start == 0 || start > end)
+        {
+            return Err(invalid(format!(
+                "invalid stamp page range {start}-{end}; pages count from 1"
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn invalid(message: impl Into<String>) -> ConvertError {
+    ConvertError::InvalidOptions(message.into())
+}
+
+/// Builds [`ConvertOptions`] with chained setters; see
+/// [`ConvertOptions::builder`]. Each setter sets the field of the same
+/// name; `Option` fields take the value to wrap in `Some`.
+#[derive(Debug, Clone, Default)]
+pub struct ConvertOptionsBuilder {
+    options: ConvertOptions,
+}
+
+impl ConvertOptionsBuilder {
+    /// Validate and return the options.
+    pub fn build(self) -> Result<ConvertOptions, ConvertError> {
+        self.options.validate()?;
+        Ok(self.options)
+    }
+
+    /// See [`ConvertOptions::sheet_names`].
+    pub fn sheet_names(mut self, names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.options.sheet_names = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// See [`ConvertOptions::xlsx_ranges`].
+    pub fn xlsx_ranges(mut self, ranges: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.options.xlsx_ranges = Some(ranges.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// See [`ConvertOptions::include_hidden`].
+    pub fn include_hidden(mut self, include_hidden: bool) -> Self {
+        self.options.include_hidden = include_hidden;
+        self
+    }
+
+    /// See [`ConvertOptions::xlsx_gridlines`].
+    pub fn xlsx_gridlines(mut self, gridlines: bool) -> Self {
+        self.options.xlsx_gridlines = gridlines;
+        self
+    }
+
+    /// See [`ConvertOptions::xlsx_headings`].
+    pub fn xlsx_headings(mut self, headings: bool) -> Self {
+        self.options.xlsx_headings = headings;
+        self
+    }
+
+    /// See [`ConvertOptions::xlsx_ignore_frozen_panes`].
+    pub fn xlsx_ignore_frozen_panes(mut self, ignore: bool) -> Self {
+        self.options.xlsx_ignore_frozen_panes = ignore;
+        self
+    }
+
+    /// See [`ConvertOptions::xlsx_row_label_columns`].
+    pub fn xlsx_row_label_columns(mut self, columns: u32) -> Self {
+        self.options.xlsx_row_label_columns = columns;
+        self
+    }
+
+    /// See [`ConvertOptions::xlsx_label_column_pages`].
+    pub fn xlsx_label_column_pages(mut self, label: bool) -> Self {
+        self.options.xlsx_label_column_pages = label;
+        self
+    }
+
+    /// See [`ConvertOptions::xlsx_redact_hidden`].
+    pub fn xlsx_redact_hidden(mut self, redact: bool) -> Self {
+        self.options.xlsx_redact_hidden = redact;
+        self
+    }
+
+    /// See [`ConvertOptions::xlsx_blank_errors`].
+    pub fn xlsx_blank_errors(mut self, blank: bool) -> Self {
+        self.options.xlsx_blank_errors = blank;
+        self
+    }
+
+    /// See [`ConvertOptions::xlsx_annotate_dropdowns`].
+    pub fn xlsx_annotate_dropdowns(mut self, annotate: bool) -> Self {
+        self.options.xlsx_annotate_dropdowns = annotate;
+        self
+    }
+
+    /// See [`ConvertOptions::locale`].
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.options.locale = Some(locale.into());
+        self
+    }
+
+    /// See [`ConvertOptions::slide_range`].
+    pub fn slide_range(mut self, range: SlideRange) -> Self {
+        self.options.slide_range = Some(range);
+        self
+    }
+
+    /// See [`ConvertOptions::slide_sections`].
+    pub fn slide_sections(mut self, sections: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.options.slide_sections = Some(sections.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// See [`ConvertOptions::pdf_standard`].
+    pub fn pdf_standard(mut self, standard: PdfStandard) -> Self {
+        self.options.pdf_standard = Some(standard);
+        self
+    }
+
+    /// See [`ConvertOptions::paper_size`].
+    pub fn paper_size(mut self, paper_size: PaperSize) -> Self {
+        self.options.paper_size = Some(paper_size);
+        self
+    }
+
+    /// Add a directory to [`ConvertOptions::font_paths`].
+    pub fn font_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.options.font_paths.push(path.into());
+        self
+    }
+
+    /// See [`ConvertOptions::missing_glyph_replacement`].
+    pub fn missing_glyph_replacement(mut self, replacement: char) -> Self {
+        self.options.missing_glyph_replacement = Some(replacement);
+        self
+    }
+
+    /// See [`ConvertOptions::image_optimization`].
+    pub fn image_optimization(mut self, image_optimization: ImageOptimization) -> Self {
+        self.options.image_optimization = Some(image_optimization);
+        self
+    }
+
+    /// See [`ConvertOptions::emit_typst_source`].
+    pub fn emit_typst_source(mut self, emit_typst_source: bool) -> Self {
+        self.options.emit_typst_source = emit_typst_source;
+        self
+    }
+
+    /// See [`ConvertOptions::fonts`].
+    pub fn fonts(mut self, fonts: FontConfig) -> Self {
+        self.options.fonts = fonts;
+        self
+    }
+
+    /// See [`ConvertOptions::landscape`].
+    pub fn landscape(mut self, landscape: bool) -> Self {
+        self.options.landscape = Some(landscape);
+        self
+    }
+
+    /// See [`ConvertOptions::tagged`].
+    pub fn tagged(mut self, tagged: bool) -> Self {
+        self.options.tagged = tagged;
+        self
+    }
+
+    /// See [`ConvertOptions::pdf_ua`]. Turning it on turns on `tagged`
+    /// too, which PDF/UA requires.
+    pub fn pdf_ua(mut self, pdf_ua: bool) -> Self {
+        self.options.pdf_ua = pdf_ua;
+        self.options.tagged |= pdf_ua;
+        self
+    }
+
+    /// See [`ConvertOptions::deterministic`].
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.options.deterministic = deterministic;
+        self
+    }
+
+    /// See [`ConvertOptions::streaming`].
+    pub fn streaming(mut self, streaming: bool) -> Self {
+        self.options.streaming = streaming;
+        self
+    }
+
+    /// See [`ConvertOptions::streaming_chunk_size`].
+    pub fn streaming_chunk_size(mut self, rows: usize) -> Self {
+        self.options.streaming_chunk_size = Some(rows);
+        self
+    }
+
+    /// See [`ConvertOptions::parallel_compile`].
+    pub fn parallel_compile(mut self, parallel_compile: bool) -> Self {
+        self.options.parallel_compile = parallel_compile;
+        self
+    }
+
+    /// See [`ConvertOptions::effects`].
+    pub fn effects(mut self, effects: EffectFidelity) -> Self {
+        self.options.effects = effects;
+        self
+    }
+
+    /// See [`ConvertOptions::slide_scale_to`].
+    pub fn slide_scale_to(mut self, aspect: SlideAspect) -> Self {
+        self.options.slide_scale_to = Some(aspect);
+        self
+    }
+
+    /// See [`ConvertOptions::slide_scale_mode`].
+    pub fn slide_scale_mode(mut self, mode: SlideScaleMode) -> Self {
+        self.options.slide_scale_mode = mode;
+        self
+    }
+
+    /// See [`ConvertOptions::transform`].
+    pub fn transform(mut self, transform: impl Fn(&mut Document) + Send + Sync + 'static) -> Self {
+        self.options.transform = Some(DocumentTransform::new(transform));
+        self
+    }
+
+    /// See [`ConvertOptions::progress`].
+    pub fn progress(mut self, callback: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        self.options.progress = Some(ProgressCallback::new(callback));
+        self
+    }
+
+    /// See [`ConvertOptions::limits`].
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.options.limits = limits;
+        self
+    }
+
+    /// See [`ConvertOptions::fail_on_warning_severity`].
+    pub fn fail_on_warning_severity(mut self, severity: WarningSeverity) -> Self {
+        self.options.fail_on_warning_severity = Some(severity);
+        self
+    }
+
+    /// See [`ConvertOptions::document_bookmarks`].
+    pub fn document_bookmarks(mut self, bookmarks: bool) -> Self {
+        self.options.document_bookmarks = bookmarks;
+        self
+    }
+
+    /// See [`ConvertOptions::table_of_contents`].
+    pub fn table_of_contents(mut self, table_of_contents: bool) -> Self {
+        self.options.table_of_contents = table_of_contents;
+        self
+    }
+
+    /// See [`ConvertOptions::input_password`].
+    pub fn input_password(mut self, password: impl Into<String>) -> Self {
+        self.options.input_password = Some(password.into());
+        self
+    }
+
+    /// See [`ConvertOptions::pdf_security`].
+    pub fn pdf_security(mut self, security: PdfSecurity) -> Self {
+        self.options.pdf_security = Some(security);
+        self
+    }
+
+    /// See [`ConvertOptions::signing`].
+    pub fn signing(mut self, signing: SigningConfig) -> Self {
+        self.options.signing = Some(signing);
+        self
+    }
+
+    /// See [`ConvertOptions::embed_source`].
+    pub fn embed_source(mut self, embed: bool) -> Self {
+        self.options.embed_source = embed;
+        self
+    }
+
+    /// See [`ConvertOptions::stamp`].
+    pub fn stamp(mut self, stamp: Stamp) -> Self {
+        self.options.stamp = Some(stamp);
+        self
+    }
+
+    /// See [`ConvertOptions::linearize`].
+    pub fn linearize(mut self, linearize: bool) -> Self {
+        self.options.linearize = linearize;
+        self
+    }
+
+    /// See [`ConvertOptions::compact`].
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.options.compact = compact;
+        self
+    }
+
+    /// See [`ConvertOptions::bleed`].
+    pub fn bleed(mut self, bleed: Bleed) -> Self {
+        self.options.bleed = Some(bleed);
+        self
+    }
+
+    /// See [`ConvertOptions::metadata_override`].
+    pub fn metadata_override(mut self, metadata: MetadataOverride) -> Self {
+        self.options.metadata_override = Some(metadata);
+        self
+    }
+}
+
+#[cfg(test)]
+#[path = "config_builder_tests.rs"]
+mod tests;
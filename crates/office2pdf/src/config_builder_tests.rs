@@ -0,0 +1,452 @@
+use super::*;
+use crate::config::{Bleed, SignatureAppearance, SigningKey, Stamp, StampPages};
+
+#[test]
+fn test_builder_sets_fields() {
+    let options: ConvertOptions = ConvertOptions::builder()
+        .sheet_names(["Summary", "Q1"])
+        .paper_size(PaperSize::A4)
+        .landscape(true)
+        .locale("de-DE")
+        .font_path("/fonts")
+        .build()
+        .unwrap();
+    assert_eq!(
+        options.sheet_names,
+        Some(vec!["Summary".to_string(), "Q1".to_string()])
+    );
+    assert_eq!(options.paper_size, Some(PaperSize::A4));
+    assert_eq!(options.landscape, Some(true));
+    assert_eq!(options.locale.as_deref(), Some("de-DE"));
+    assert_eq!(options.font_paths, vec![PathBuf::from("/fonts")]);
+}
+
+#[test]
+fn test_builder_pdf_ua_turns_on_tagged() {
+    let options: ConvertOptions = ConvertOptions::builder().pdf_ua(true).build().unwrap();
+    assert!(options.pdf_ua);
+    assert!(options.tagged);
+}
+
+#[test]
+fn test_validate_accepts_defaults() {
+    assert!(ConvertOptions::default().validate().is_ok());
+}
+
+fn rejection(options: ConvertOptions) -> String {
+    match options.validate() {
+        Err(ConvertError::InvalidOptions(message)) => message,
+        other => panic!("expected InvalidOptions, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_validate_rejects_pdf_ua_without_tagged() {
+    let message: String = rejection(ConvertOptions {
+        pdf_ua: true,
+        ..Default::default()
+    });
+    assert!(message.contains("tagged"), "{message}");
+}
+
+#[test]
+fn test_validate_rejects_pdf_ua_with_pdf_a_1b() {
+    let message: String = rejection(ConvertOptions {
+        tagged: true,
+        pdf_ua: true,
+        pdf_standard: Some(PdfStandard::PdfA1b),
+        ..Default::default()
+    });
+    assert!(message.contains("PDF/A-1b"), "{message}");
+
+    assert!(
+        ConvertOptions::builder()
+            .pdf_ua(true)
+            .pdf_standard(PdfStandard::PdfA3b)
+            .build()
+            .is_ok()
+    );
+}
+
+#[cfg(not(feature = "pdf-ops"))]
+#[test]
+fn test_validate_rejects_pdf_x_4_without_pdf_ops() {
+    let message: String = rejection(ConvertOptions {
+        pdf_standard: Some(PdfStandard::PdfX4),
+        ..Default::default()
+    });
+    assert!(message.contains("pdf-ops"), "{message}");
+}
+
+#[test]
+fn test_validate_rejects_pdf_security_that_cannot_hold() {
+    let security: PdfSecurity = PdfSecurity {
+        owner_password: "owner".to_string(),
+        ..Default::default()
+    };
+    let message: String = rejection(ConvertOptions {
+        pdf_security: Some(PdfSecurity {
+            owner_password: String::new(),
+            ..security.clone()
+        }),
+        ..Default::default()
+    });
+    let expected: &str = if cfg!(feature = "pdf-ops") {
+        "owner password"
+    } else {
+        "pdf-ops"
+    };
+    assert!(message.contains(expected), "{message}");
+
+    assert!(
+        ConvertOptions::builder()
+            .pdf_security(security.clone())
+            .pdf_standard(PdfStandard::PdfA2b)
+            .build()
+            .is_err()
+    );
+    assert_eq!(
+        ConvertOptions::builder()
+            .pdf_security(security.clone())
+            .build()
+            .is_ok(),
+        cfg!(feature = "pdf-ops")
+    );
+    if cfg!(feature = "pdf-ops") {
+        let message: String = rejection(ConvertOptions {
+            pdf_security: Some(security),
+            deterministic: true,
+            ..Default::default()
+        });
+        assert!(message.contains("deterministic"), "{message}");
+    }
+}
+
+#[test]
+fn test_validate_rejects_signing_that_cannot_hold() {
+    let signing: SigningConfig = SigningConfig {
+        key: SigningKey::Der {
+            private_key: Vec::new(),
+            certificates: Vec::new(),
+        },
+        reason: None,
+        location: None,
+        contact_info: None,
+        appearance: Some(SignatureAppearance {
+            page: 1,
+            x: 36.0,
+            y: 36.0,
+            width: 0.0,
+            height: 50.0,
+        }),
+    };
+    let message: String = rejection(ConvertOptions {
+        signing: Some(signing.clone()),
+        ..Default::default()
+    });
+    let expected: &str = if cfg!(feature = "sign") {
+        "positive size"
+    } else {
+        "sign feature"
+    };
+    assert!(message.contains(expected), "{message}");
+
+    if cfg!(feature = "sign") {
+        let visible: SigningConfig = SigningConfig {
+            appearance: Some(SignatureAppearance {
+                width: 200.0,
+                ..signing.appearance.unwrap()
+            }),
+            ..signing.clone()
+        };
+        let message: String = rejection(ConvertOptions {
+            signing: Some(visible),
+            pdf_standard: Some(PdfStandard::PdfA2b),
+            ..Default::default()
+        });
+        assert!(message.contains("PDF/A"), "{message}");
+
+        let message: String = rejection(ConvertOptions {
+            signing: Some(SigningConfig {
+                appearance: None,
+                ..signing
+            }),
+            pdf_security: Some(PdfSecurity {
+                owner_password: "owner".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        assert!(message.contains("pdf_security"), "{message}");
+    }
+}
+
+#[test]
+fn test_validate_embed_source_needs_pdf_a_3_among_standards() {
+    if !cfg!(feature = "pdf-ops") {
+        let message: String = rejection(ConvertOptions {
+            embed_source: true,
+            ..Default::default()
+        });
+        assert!(message.contains("pdf-ops"), "{message}");
+        return;
+    }
+    for standard in [PdfStandard::PdfA1b, PdfStandard::PdfA2b, PdfStandard::PdfX4] {
+        let message: String = rejection(ConvertOptions {
+            embed_source: true,
+            pdf_standard: Some(standard),
+            ..Default::default()
+        });
+        assert!(message.contains("PDF/A-3b"), "{message}");
+    }
+    assert!(
+        ConvertOptions::builder()
+            .embed_source(true)
+            .pdf_standard(PdfStandard::PdfA3b)
+            .build()
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_validate_rejects_unusable_stamps() {
+    if !cfg!(feature = "pdf-ops") {
+        let message: String = rejection(ConvertOptions {
+            stamp: Some(Stamp::text("DRAFT")),
+            ..Default::default()
+        });
+        assert!(message.contains("pdf-ops"), "{message}");
+        return;
+    }
+    let mut stamp: Stamp = Stamp::text("DRAFT");
+    stamp.opacity = 1.5;
+    let message: String = rejection(ConvertOptions {
+        stamp: Some(stamp),
+        ..Default::default()
+    });
+    assert!(message.contains("opacity"), "{message}");
+
+    let message: String = rejection(ConvertOptions {
+        stamp: Some(Stamp::image(Vec::new(), 0.0)),
+        ..Default::default()
+    });
+    assert!(message.contains("size"), "{message}");
+
+    let mut stamp: Stamp = Stamp::text("DRAFT");
+    stamp.pages = StampPages::Ranges(vec![(0, 2)]);
+    let message: String = rejection(ConvertOptions {
+        stamp: Some(stamp),
+        ..Default::default()
+    });
+    assert!(message.contains("page range"), "{message}");
+}
+
+#[test]
+fn test_validate_stamp_against_pdf_standards() {
+    if !cfg!(feature = "pdf-ops") {
+        return;
+    }
+    let message: String = rejection(ConvertOptions {
+        stamp: Some(Stamp::text("DRAFT")),
+        pdf_standard: Some(PdfStandard::PdfA2b),
+        ..Default::default()
+    });
+    assert!(message.contains("text stamp"), "{message}");
+    let message: String = rejection(ConvertOptions {
+        stamp: Some(Stamp::image(vec![0], 72.0)),
+        pdf_standard: Some(PdfStandard::PdfA1b),
+        ..Default::default()
+    });
+    assert!(message.contains("PDF/A-1"), "{message}");
+    assert!(
+        ConvertOptions::builder()
+            .stamp(Stamp::image(vec![0], 72.0))
+            .pdf_standard(PdfStandard::PdfA2b)
+            .build()
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_validate_rejects_streaming_settings_that_cannot_apply() {
+    let message: String = rejection(ConvertOptions {
+        streaming_chunk_size: Some(500),
+        ..Default::default()
+    });
+    assert!(message.contains("streaming_chunk_size"), "{message}");
+
+    assert!(
+        ConvertOptions::builder()
+            .streaming(true)
+            .streaming_chunk_size(0)
+            .build()
+            .is_err()
+    );
+    assert!(
+        ConvertOptions::builder()
+            .streaming(true)
+            .pdf_standard(PdfStandard::PdfA2b)
+            .build()
+            .is_err()
+    );
+}
+
+#[cfg(not(feature = "pdf-ops"))]
+#[test]
+fn test_validate_rejects_streaming_without_pdf_ops() {
+    let message: String = rejection(ConvertOptions {
+        streaming: true,
+        ..Default::default()
+    });
+    assert!(message.contains("pdf-ops"), "{message}");
+}
+
+#[cfg(not(feature = "rayon"))]
+#[test]
+fn test_validate_rejects_parallel_compile_without_rayon() {
+    let message: String = rejection(ConvertOptions {
+        parallel_compile: true,
+        ..Default::default()
+    });
+    assert!(message.contains("rayon"), "{message}");
+}
+
+#[test]
+fn test_validate_rejects_empty_selections() {
+    let message: String = rejection(ConvertOptions {
+        slide_sections: Some(Vec::new()),
+        ..Default::default()
+    });
+    assert!(message.contains("slide_sections"), "{message}");
+    assert!(
+        ConvertOptions::builder()
+            .slide_range(SlideRange::new(5, 2))
+            .build()
+            .is_err()
+    );
+}
+
+#[test]
+fn test_validate_rejects_non_positive_paper_size() {
+    assert!(
+        ConvertOptions::builder()
+            .paper_size(PaperSize::Custom {
+                width: 0.0,
+                height: 792.0,
+            })
+            .build()
+            .is_err()
+    );
+}
+
+#[test]
+fn test_validate_rejects_substitution_without_families() {
+    let fonts = FontConfig {
+        substitutions: [("Calibri".to_string(), Vec::new())].into(),
+        ..FontConfig::default()
+    };
+    assert!(ConvertOptions::builder().fonts(fonts).build().is_err());
+}
+
+#[cfg(not(feature = "image-opt"))]
+#[test]
+fn test_validate_rejects_image_optimization_without_feature() {
+    let message: String = rejection(ConvertOptions {
+        image_optimization: Some(ImageOptimization::default()),
+        ..Default::default()
+    });
+    assert!(message.contains("image-opt"), "{message}");
+}
+
+#[cfg(feature = "image-opt")]
+#[test]
+fn test_validate_rejects_out_of_range_jpeg_quality() {
+    let message: String = rejection(ConvertOptions {
+        image_optimization: Some(ImageOptimization {
+            jpeg_quality: Some(0),
+            ..ImageOptimization::default()
+        }),
+        ..Default::default()
+    });
+    assert!(message.contains("jpeg_quality"), "{message}");
+}
+
+#[test]
+fn test_validate_linearize_and_compact_combinations() {
+    if !cfg!(feature = "pdf-ops") {
+        let message: String = rejection(ConvertOptions {
+            linearize: true,
+            ..Default::default()
+        });
+        assert!(message.contains("pdf-ops"), "{message}");
+        return;
+    }
+    let message: String = rejection(ConvertOptions {
+        linearize: true,
+        compact: true,
+        ..Default::default()
+    });
+    assert!(message.contains("linearize cannot"), "{message}");
+    let message: String = rejection(ConvertOptions {
+        compact: true,
+        pdf_security: Some(PdfSecurity {
+            owner_password: "owner".to_string(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+    assert!(message.contains("pdf_security"), "{message}");
+    let message: String = rejection(ConvertOptions {
+        compact: true,
+        pdf_standard: Some(PdfStandard::PdfA1b),
+        ..Default::default()
+    });
+    assert!(message.contains("PDF/A-1"), "{message}");
+    assert!(
+        ConvertOptions::builder()
+            .linearize(true)
+            .pdf_standard(PdfStandard::PdfA1b)
+            .build()
+            .is_ok()
+    );
+    assert!(
+        ConvertOptions::builder()
+            .compact(true)
+            .pdf_standard(PdfStandard::PdfA2b)
+            .build()
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_validate_rejects_unusable_bleed() {
+    let bleed: Bleed = Bleed {
+        margin: 9.0,
+        crop_marks: true,
+    };
+    if !cfg!(feature = "pdf-ops") {
+        let message: String = rejection(ConvertOptions {
+            bleed: Some(bleed),
+            ..Default::default()
+        });
+        assert!(message.contains("pdf-ops"), "{message}");
+        return;
+    }
+    let message: String = rejection(ConvertOptions {
+        bleed: Some(Bleed {
+            margin: -1.0,
+            ..bleed
+        }),
+        ..Default::default()
+    });
+    assert!(message.contains("bleed margin"), "{message}");
+    let message: String = rejection(ConvertOptions {
+        bleed: Some(Bleed {
+            margin: 0.0,
+            crop_marks: false,
+        }),
+        ..Default::default()
+    });
+    assert!(message.contains("crop marks"), "{message}");
+    assert!(ConvertOptions::builder().bleed(bleed).build().is_ok());
+}
@@ -72,6 +72,33 @@ fn test_pdf_standard_enum_exists() {
     assert_eq!(format!("{std:?}"), "PdfA2b");
 }
 
+#[test]
+fn test_pdf_security_debug_hides_passwords() {
+    let security = PdfSecurity {
+        user_password: "open-sesame".to_string(),
+        owner_password: "master-key".to_string(),
+        allow_print: true,
+        ..Default::default()
+    };
+    let debug: String = format!("{security:?}");
+    assert!(!debug.contains("open-sesame"), "{debug}");
+    assert!(!debug.contains("master-key"), "{debug}");
+    assert!(debug.contains("allow_print: true"), "{debug}");
+}
+
+#[test]
+fn test_convert_options_debug_hides_input_password() {
+    let options = ConvertOptions {
+        input_password: Some("open-sesame".to_string()),
+        landscape: Some(true),
+        ..Default::default()
+    };
+    let debug: String = format!("{options:?}");
+    assert!(!debug.contains("open-sesame"), "{debug}");
+    assert!(debug.contains("has_input_password: true"), "{debug}");
+    assert!(debug.contains("landscape: Some(true)"), "{debug}");
+}
+
 #[test]
 fn test_convert_options_pdf_standard_default_none() {
     let opts = ConvertOptions::default();
@@ -87,6 +114,118 @@ fn test_convert_options_with_pdf_standard() {
     assert_eq!(opts.pdf_standard, Some(PdfStandard::PdfA2b));
 }
 
+#[test]
+fn test_pdf_standard_parse() {
+    assert_eq!(PdfStandard::parse("a-1b").unwrap(), PdfStandard::PdfA1b);
+    assert_eq!(PdfStandard::parse("PDF/A-2b").unwrap(), PdfStandard::PdfA2b);
+    assert_eq!(PdfStandard::parse("pdfa3b").unwrap(), PdfStandard::PdfA3b);
+    assert_eq!(PdfStandard::parse("X-4").unwrap(), PdfStandard::PdfX4);
+    assert!(PdfStandard::parse("a-4").is_err());
+}
+
+#[test]
+fn test_stamp_position_parse() {
+    assert_eq!(
+        StampPosition::parse("center").unwrap(),
+        StampPosition::Center
+    );
+    assert_eq!(
+        StampPosition::parse("Top").unwrap(),
+        StampPosition::TopCenter
+    );
+    assert_eq!(
+        StampPosition::parse("bottom-right").unwrap(),
+        StampPosition::BottomRight
+    );
+    assert!(StampPosition::parse("left").is_err());
+}
+
+#[test]
+fn test_stamp_pages_contains() {
+    assert!(StampPages::All.contains(7));
+    assert!(StampPages::First.contains(1));
+    assert!(!StampPages::First.contains(2));
+    let ranges: StampPages = StampPages::Ranges(vec![(2, 3), (5, 5)]);
+    let stamped: Vec<u32> = (1..=6).filter(|&number| ranges.contains(number)).collect();
+    assert_eq!(stamped, vec![2, 3, 5]);
+}
+
+#[test]
+fn test_stamp_pages_parse() {
+    assert_eq!(StampPages::parse("First").unwrap(), StampPages::First);
+    assert_eq!(StampPages::parse("all").unwrap(), StampPages::All);
+    assert_eq!(
+        StampPages::parse("1, 3-5").unwrap(),
+        StampPages::Ranges(vec![(1, 1), (3, 5)])
+    );
+    assert!(StampPages::parse("2-x").is_err());
+}
+
+#[test]
+fn test_signature_appearance_parse() {
+    assert_eq!(
+        SignatureAppearance::parse("2:36, 40.5,200,50").unwrap(),
+        SignatureAppearance {
+            page: 2,
+            x: 36.0,
+            y: 40.5,
+            width: 200.0,
+            height: 50.0,
+        }
+    );
+    assert!(SignatureAppearance::parse("36,40,200,50").is_err());
+    assert!(SignatureAppearance::parse("0:36,40,200,50").is_err());
+    assert!(SignatureAppearance::parse("1:36,40,200").is_err());
+}
+
+#[test]
+fn test_metadata_override_sets_clears_and_keeps_fields() {
+    let mut metadata = Metadata {
+        title: Some("Draft".to_string()),
+        author: Some("Jane".to_string()),
+        subject: Some("Budget".to_string()),
+        keywords: vec!["q1".to_string()],
+        custom_properties: vec![
+            ("Client".to_string(), "Globex".to_string()),
+            ("Status".to_string(), "Draft".to_string()),
+        ],
+        ..Metadata::default()
+    };
+    let override_ = MetadataOverride {
+        title: Some(Some("Final".to_string())),
+        author: Some(None),
+        company: Some(Some("Acme".to_string())),
+        custom_properties: BTreeMap::from([
+            ("Status".to_string(), Some("Approved".to_string())),
+            ("Client".to_string(), None),
+            ("Owner".to_string(), Some("Finance".to_string())),
+        ]),
+        ..MetadataOverride::default()
+    };
+    override_.apply(&mut metadata);
+    assert_eq!(metadata.title.as_deref(), Some("Final"));
+    assert_eq!(metadata.author, None);
+    assert_eq!(metadata.subject.as_deref(), Some("Budget"));
+    assert_eq!(metadata.keywords, vec!["q1"]);
+    assert_eq!(metadata.company.as_deref(), Some("Acme"));
+    assert_eq!(
+        metadata.custom_properties,
+        vec![
+            ("Status".to_string(), "Approved".to_string()),
+            ("Owner".to_string(), "Finance".to_string()),
+        ]
+    );
+
+    MetadataOverride {
+        keywords: Some(Vec::new()),
+        clear_custom_properties: true,
+        ..MetadataOverride::default()
+    }
+    .apply(&mut metadata);
+    assert!(metadata.keywords.is_empty());
+    assert!(metadata.custom_properties.is_empty());
+}
+
 // --- PaperSize tests ---
 
 #[test]
@@ -238,3 +377,113 @@ fn test_convert_options_with_streaming_chunk_size() {
     assert!(opts.streaming);
     assert_eq!(opts.streaming_chunk_size, Some(500));
 }
+
+#[test]
+fn test_effect_fidelity_parse() {
+    assert_eq!(
+        EffectFidelity::parse("approximate").unwrap(),
+        EffectFidelity::Approximate
+    );
+    assert_eq!(EffectFidelity::parse("OFF").unwrap(), EffectFidelity::Off);
+    assert_eq!(EffectFidelity::parse("none").unwrap(), EffectFidelity::Off);
+    assert!(EffectFidelity::parse("exact").is_err());
+}
+
+#[test]
+fn test_convert_options_effects_default_approximate() {
+    let opts = ConvertOptions::default();
+    assert_eq!(opts.effects, EffectFidelity::Approximate);
+}
+
+#[test]
+fn test_slide_aspect_parse_and_dimensions() {
+    assert_eq!(SlideAspect::parse("4:3").unwrap(), SlideAspect::Standard4x3);
+    assert_eq!(
+        SlideAspect::parse("Widescreen").unwrap(),
+        SlideAspect::Widescreen16x9
+    );
+    assert!(SlideAspect::parse("16:10").is_err());
+    assert_eq!(SlideAspect::Standard4x3.dimensions(), (720.0, 540.0));
+    assert_eq!(SlideAspect::Widescreen16x9.dimensions(), (960.0, 540.0));
+}
+
+#[test]
+fn test_slide_scale_mode_parse() {
+    assert_eq!(SlideScaleMode::parse("fit").unwrap(), SlideScaleMode::Fit);
+    assert_eq!(
+        SlideScaleMode::parse("MAXIMIZE").unwrap(),
+        SlideScaleMode::Maximize
+    );
+    assert_eq!(
+        SlideScaleMode::parse("stretch").unwrap(),
+        SlideScaleMode::Stretch
+    );
+    assert!(SlideScaleMode::parse("crop").is_err());
+    assert_eq!(SlideScaleMode::default(), SlideScaleMode::Fit);
+}
+
+#[test]
+fn test_slide_image_format_parse() {
+    assert_eq!(
+        SlideImageFormat::parse("PNG").unwrap(),
+        SlideImageFormat::Png
+    );
+    assert_eq!(
+        SlideImageFormat::parse("svg").unwrap(),
+        SlideImageFormat::Svg
+    );
+    assert!(SlideImageFormat::parse("jpeg").is_err());
+    assert_eq!(SlideImageFormat::Svg.extension(), "svg");
+    assert_eq!(SlideImageOptions::default().dpi, 144.0);
+}
+
+#[test]
+fn test_asset_kind_parse() {
+    assert_eq!(AssetKind::parse("Images").unwrap(), AssetKind::Image);
+    assert_eq!(AssetKind::parse("font").unwrap(), AssetKind::Font);
+    assert_eq!(AssetKind::parse("media").unwrap(), AssetKind::Media);
+    assert!(AssetKind::parse("charts").is_err());
+    assert_eq!(AssetKind::Font.name(), "fonts");
+}
+
+#[test]
+fn test_format_detect_reads_package_content_types() {
+    use crate::test_support::{build_test_docx, build_test_pptx, build_test_xlsx};
+
+    assert_eq!(Format::detect(&build_test_docx()), Some(Format::Docx));
+    assert_eq!(Format::detect(&build_test_xlsx()), Some(Format::Xlsx));
+    assert_eq!(Format::detect(&build_test_pptx()), Some(Format::Pptx));
+}
+
+#[test]
+fn test_format_detect_recognizes_templates_and_falls_back_to_part_names() {
+    use std::io::{Cursor, Write};
+
+    let package = |entries: &[(&str, &str)]| -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in entries {
+            zip.start_file(*name, zip::write::FileOptions::default())
+                .unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    };
+    let template: Vec<u8> = package(&[(
+        "[Content_Types].xml",
+        r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Override PartName="/ppt/presentation.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.template.main+xml"/></Types>"#,
+    )]);
+    assert_eq!(Format::detect(&template), Some(Format::Pptx));
+
+    let without_content_types: Vec<u8> = package(&[("xl/workbook.xml", "<workbook/>")]);
+    assert_eq!(Format::detect(&without_content_types), Some(Format::Xlsx));
+
+    assert_eq!(Format::detect(&package(&[("readme.txt", "hi")])), None);
+    assert_eq!(Format::detect(b"%PDF-1.7"), None);
+}
+
+#[test]
+fn test_format_detect_leaves_ole2_files_undetected() {
+    let mut ole2: Vec<u8> = vec![0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+    ole2.resize(512, 0);
+    assert_eq!(Format::detect(&ole2), None);
+}
@@ -29,6 +29,8 @@ fn test_pdf_standard_ts_declaration() {
     let decl = PdfStandard::decl(&cfg());
     assert!(decl.contains("PdfStandard"), "PdfStandard TS decl: {decl}");
     assert!(decl.contains("PdfA2b"), "should contain PdfA2b variant");
+    assert!(decl.contains("PdfA3b"), "should contain PdfA3b variant");
+    assert!(decl.contains("PdfX4"), "should contain PdfX4 variant");
 }
 
 #[test]
@@ -44,6 +44,14 @@ pub const HEADING_FONT_SIZES: [f64; 6] = [24.0, 20.0, 16.0, 14.0, 12.0, 11.0];
 /// Typst's default body text size when generated markup does not set one.
 pub const TYPST_DEFAULT_FONT_SIZE_PT: f64 = 11.0;
 
+/// PowerPoint's text size when neither the run nor any inherited list style
+/// sets `sz`.
+pub const PPTX_DEFAULT_FONT_SIZE_PT: f64 = 18.0;
+
+/// Single line height as a multiple of font size, used to resolve
+/// percent-of-line spacing (`a:spcPct` inside `a:spcBef`/`a:spcAft`).
+pub const SINGLE_LINE_HEIGHT_FACTOR: f64 = 1.2;
+
 // ---------------------------------------------------------------------------
 // Streaming
 // ---------------------------------------------------------------------------
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::render::typst_gen::TypstOutput;
+
 /// Errors that can occur during document conversion.
 #[derive(Debug, Error)]
 pub enum ConvertError {
@@ -17,12 +19,29 @@ pub enum ConvertError {
 
     #[error("file is encrypted/password-protected and cannot be converted")]
     UnsupportedEncryption,
+
+    #[error("the password does not open this encrypted file")]
+    IncorrectPassword,
+
+    #[error("conversion was cancelled")]
+    Cancelled,
+
+    #[error("limit exceeded: {0}")]
+    LimitExceeded(String),
+
+    #[error("invalid options: {0}")]
+    InvalidOptions(String),
+
+    #[error("warning at or above the failure severity: {0}")]
+    WarningThreshold(Box<ConvertWarning>),
 }
 
 /// A non-fatal warning emitted when an element cannot be fully processed.
 ///
 /// Warnings are structured so that callers can programmatically inspect
-/// what was degraded during conversion.
+/// what was degraded during conversion: [`code`](Self::code) is stable
+/// across releases, [`severity`](Self::severity) ranks it, and `location`
+/// says where in the document it happened when the parser knows.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub enum ConvertWarning {
@@ -32,6 +51,8 @@ pub enum ConvertWarning {
         format: String,
         /// Name or description of the unsupported element.
         element: String,
+        /// Where the element is in the document.
+        location: Option<WarningLocation>,
     },
     /// An element was partially rendered (some features degraded).
     PartialElement {
@@ -41,6 +62,8 @@ pub enum ConvertWarning {
         element: String,
         /// Detail about what was degraded.
         detail: String,
+        /// Where the element is in the document.
+        location: Option<WarningLocation>,
     },
     /// A fallback representation was used instead of full rendering.
     FallbackUsed {
@@ -50,6 +73,8 @@ pub enum ConvertWarning {
         from: String,
         /// Fallback representation used.
         to: String,
+        /// Where the element is in the document.
+        location: Option<WarningLocation>,
     },
     /// An element was skipped during parsing.
     ParseSkipped {
@@ -57,7 +82,95 @@ pub enum ConvertWarning {
         format: String,
         /// Reason the element was skipped.
         reason: String,
+        /// Where the element is in the document.
+        location: Option<WarningLocation>,
     },
+    /// Content was left out on purpose, as the options asked (e.g. hidden
+    /// sheets, rows and columns without `include_hidden`).
+    ContentOmitted {
+        /// Document format (e.g. "DOCX", "PPTX", "XLSX").
+        format: String,
+        /// What was left out.
+        content: String,
+        /// Where the content is in the document.
+        location: Option<WarningLocation>,
+    },
+    /// The laid-out text of a slide text box is taller than the box, so it
+    /// spills past the box's bounds in the output.
+    ContentOverflow {
+        /// Document format (e.g. "PPTX").
+        format: String,
+        /// The slide's page in the output, numbered from 1. Hidden slides
+        /// are not counted.
+        slide: u32,
+        /// Description of the text box, by the start of its text.
+        element: String,
+        /// The slide in the source presentation, which differs from `slide`
+        /// when hidden or unselected slides come before it.
+        location: Option<WarningLocation>,
+    },
+    /// No available font has a glyph for a character, so it shows as the
+    /// font's missing-glyph box or as
+    /// [`ConvertOptions::missing_glyph_replacement`](crate::config::ConvertOptions::missing_glyph_replacement).
+    /// Reported once per character.
+    MissingGlyph {
+        /// Document format (e.g. "DOCX", "PPTX", "XLSX").
+        format: String,
+        /// The character without a glyph.
+        character: char,
+        /// Output page of its first occurrence, numbered from 1.
+        page: u32,
+        /// The run of text it first occurs in, shortened.
+        text: String,
+        /// What it was drawn as instead, if anything.
+        replacement: Option<char>,
+    },
+}
+
+/// How much a [`ConvertWarning`] matters, from least to most; see
+/// [`ConvertOptions::fail_on_warning_severity`](crate::config::ConvertOptions::fail_on_warning_severity).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub enum WarningSeverity {
+    /// Expected behavior worth knowing about: an approximation or a
+    /// requested omission.
+    Info,
+    /// Part of the document is missing or degraded in the output.
+    Warning,
+    /// The parser failed on part of the document and carried on without it.
+    RecoveredError,
+}
+
+impl std::fmt::Display for WarningSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::RecoveredError => "error",
+        })
+    }
+}
+
+/// Where in the source document a [`ConvertWarning`] arose.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub enum WarningLocation {
+    /// A PPTX slide, numbered from 1.
+    Slide(u32),
+    /// An XLSX worksheet, by name.
+    Sheet(String),
+    /// A DOCX body element (paragraph, table, ...), indexed from 0.
+    Paragraph(u32),
+}
+
+impl std::fmt::Display for WarningLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Slide(number) => write!(f, "slide {number}"),
+            Self::Sheet(name) => write!(f, "sheet '{name}'"),
+            Self::Paragraph(index) => write!(f, "paragraph {index}"),
+        }
+    }
 }
 
 impl ConvertWarning {
@@ -67,30 +180,129 @@ impl ConvertWarning {
             Self::UnsupportedElement { format, .. }
             | Self::PartialElement { format, .. }
             | Self::FallbackUsed { format, .. }
-            | Self::ParseSkipped { format, .. } => format,
+            | Self::ParseSkipped { format, .. }
+            | Self::ContentOmitted { format, .. }
+            | Self::ContentOverflow { format, .. }
+            | Self::MissingGlyph { format, .. } => format,
+        }
+    }
+
+    /// A machine-readable code for the kind of warning. Codes are stable
+    /// across releases, unlike the [`Display`](std::fmt::Display) text.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnsupportedElement { .. } => "unsupported_element",
+            Self::PartialElement { .. } => "partial_element",
+            Self::FallbackUsed { .. } => "fallback_used",
+            Self::ParseSkipped { .. } => "parse_skipped",
+            Self::ContentOmitted { .. } => "content_omitted",
+            Self::ContentOverflow { .. } => "content_overflow",
+            Self::MissingGlyph { .. } => "missing_glyph",
+        }
+    }
+
+    /// How much the warning matters.
+    pub fn severity(&self) -> WarningSeverity {
+        match self {
+            Self::FallbackUsed { .. } | Self::ContentOmitted { .. } => WarningSeverity::Info,
+            Self::UnsupportedElement { .. }
+            | Self::PartialElement { .. }
+            | Self::ContentOverflow { .. }
+            | Self::MissingGlyph { .. } => WarningSeverity::Warning,
+            Self::ParseSkipped { .. } => WarningSeverity::RecoveredError,
+        }
+    }
+
+    /// Where in the document the warning arose, when known.
+    pub fn location(&self) -> Option<&WarningLocation> {
+        match self {
+            Self::UnsupportedElement { location, .. }
+            | Self::PartialElement { location, .. }
+            | Self::FallbackUsed { location, .. }
+            | Self::ParseSkipped { location, .. }
+            | Self::ContentOmitted { location, .. }
+            | Self::ContentOverflow { location, .. } => location.as_ref(),
+            // Found after layout, by output page rather than by slide,
+            // sheet or paragraph of the source.
+            Self::MissingGlyph { .. } => None,
+        }
+    }
+
+    /// Sets the location unless the warning already has one: slide
+    /// warnings are raised deep in the slide parser, which only knows the
+    /// slide by its label.
+    pub(crate) fn or_location(mut self, at: WarningLocation) -> Self {
+        match &mut self {
+            Self::UnsupportedElement { location, .. }
+            | Self::PartialElement { location, .. }
+            | Self::FallbackUsed { location, .. }
+            | Self::ParseSkipped { location, .. }
+            | Self::ContentOmitted { location, .. }
+            | Self::ContentOverflow { location, .. } => {
+                location.get_or_insert(at);
+            }
+            Self::MissingGlyph { .. } => {}
         }
+        self
     }
 }
 
 impl std::fmt::Display for ConvertWarning {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The messages already name the slide or sheet for a reader;
+        // `location` is the same information for code.
         match self {
-            Self::UnsupportedElement { format, element } => {
+            Self::UnsupportedElement {
+                format, element, ..
+            } => {
                 write!(f, "[{format}] unsupported element: {element}")
             }
             Self::PartialElement {
                 format,
                 element,
                 detail,
+                ..
             } => {
                 write!(f, "[{format}] partial rendering of {element}: {detail}")
             }
-            Self::FallbackUsed { format, from, to } => {
+            Self::FallbackUsed {
+                format, from, to, ..
+            } => {
                 write!(f, "[{format}] fallback: {from} rendered as {to}")
             }
-            Self::ParseSkipped { format, reason } => {
+            Self::ParseSkipped { format, reason, .. } => {
                 write!(f, "[{format}] skipped: {reason}")
             }
+            Self::ContentOmitted {
+                format, content, ..
+            } => {
+                write!(f, "[{format}] omitted: {content}")
+            }
+            Self::ContentOverflow {
+                format,
+                slide,
+                element,
+                ..
+            } => {
+                write!(f, "[{format}] content overflows {element} on slide {slide}")
+            }
+            Self::MissingGlyph {
+                format,
+                character,
+                page,
+                text,
+                replacement,
+            } => {
+                write!(
+                    f,
+                    "[{format}] no font has a glyph for '{character}' (U+{:04X}), first on page {page} in \"{text}\"",
+                    u32::from(*character)
+                )?;
+                match replacement {
+                    Some(replacement) => write!(f, "; drawn as '{replacement}'"),
+                    None => Ok(()),
+                }
+            }
         }
     }
 }
@@ -128,6 +340,119 @@ pub struct ConvertResult {
     pub warnings: Vec<ConvertWarning>,
     /// Per-stage timing metrics, populated when instrumentation is enabled.
     pub metrics: Option<ConvertMetrics>,
+    /// The Typst markup compiled into the PDF and its images, when
+    /// [`ConvertOptions::emit_typst_source`](crate::config::ConvertOptions::emit_typst_source)
+    /// is set: one per compiled part, so streaming conversions have one per
+    /// chunk. Empty otherwise.
+    pub typst_sources: Vec<TypstOutput>,
+}
+
+/// Text of a document extracted by [`extract_text`](crate::extract_text)
+/// instead of converting it to PDF.
+#[derive(Debug)]
+pub struct ExtractedText {
+    /// The text with paragraphs separated by blank lines and table cells by
+    /// tabs.
+    pub text: String,
+    /// The same content as GitHub-flavored Markdown.
+    pub markdown: String,
+    /// Warnings collected while parsing (non-fatal issues).
+    pub warnings: Vec<ConvertWarning>,
+}
+
+/// One presentation section rendered as its own PDF.
+#[derive(Debug)]
+pub struct SectionPdf {
+    /// Section name from the presentation (`<p14:section name>`).
+    pub name: String,
+    /// The section's PDF bytes.
+    pub pdf: Vec<u8>,
+}
+
+/// Result of converting a presentation into one PDF per section.
+#[derive(Debug)]
+pub struct SectionConvertResult {
+    /// Sections in presentation order; sections without visible slides are omitted.
+    pub sections: Vec<SectionPdf>,
+    /// Warnings collected during conversion (non-fatal issues).
+    pub warnings: Vec<ConvertWarning>,
+}
+
+/// One page rendered as an image by
+/// [`render_to_images`](crate::render_to_images) or
+/// [`render_slides_to_images`](crate::render_slides_to_images).
+#[derive(Debug)]
+pub struct ImageOutput {
+    /// Zero-based position of the page among the rendered pages: the
+    /// slides of a presentation, the laid-out pages of other documents.
+    pub page_index: usize,
+    /// Encoding of `data`.
+    pub format: crate::config::SlideImageFormat,
+    /// Encoded PNG bytes or UTF-8 SVG markup.
+    pub data: Vec<u8>,
+    /// Image width: pixels for PNG, points for SVG.
+    pub width: u32,
+    /// Image height: pixels for PNG, points for SVG.
+    pub height: u32,
+}
+
+/// A file embedded in an Office package, returned by
+/// [`extract_assets`](crate::extract_assets).
+#[derive(Debug)]
+pub struct Asset {
+    /// What kind of file this is.
+    pub kind: crate::config::AssetKind,
+    /// File name: the part's name in the package (`image1.png`), or for
+    /// fonts `Typeface-style.ext`.
+    pub name: String,
+    /// The file's bytes, as stored; fonts are deobfuscated.
+    pub data: Vec<u8>,
+}
+
+/// A summary of a document from [`document_info`](crate::document_info):
+/// what it contains and about how long its PDF will be, without converting
+/// it.
+#[derive(Debug, Clone)]
+pub struct DocumentInfo {
+    pub format: crate::config::Format,
+    pub metadata: crate::ir::Metadata,
+    /// About how many pages the PDF will have. For DOCX on its own paper
+    /// this is the count Word recorded when it last saved the file, when
+    /// there is one; otherwise it is estimated from the amount of content,
+    /// without layout.
+    pub estimated_pages: u32,
+    /// The worksheets of a workbook in order, hidden ones included.
+    pub sheets: Vec<SheetInfo>,
+    /// The slides of a presentation in order, hidden ones included.
+    pub slides: Vec<SlideInfo>,
+    /// Typefaces embedded in the document, in declaration order.
+    pub embedded_fonts: Vec<String>,
+}
+
+/// A worksheet listed by [`DocumentInfo::sheets`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetInfo {
+    pub name: String,
+    /// The last row holding a cell (1-based); 0 for an empty sheet.
+    pub rows: u32,
+    /// The last column holding a cell (1-based); 0 for an empty sheet.
+    pub columns: u32,
+    /// Whether Excel hides the sheet, which conversion then leaves out
+    /// unless `include_hidden` is set.
+    pub hidden: bool,
+}
+
+/// A slide listed by [`DocumentInfo::slides`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlideInfo {
+    /// The slide number (1-based), as `slide_range` counts slides.
+    pub number: u32,
+    /// The text of the slide's title placeholder, if it has one.
+    pub title: Option<String>,
+    /// The presentation section the slide belongs to.
+    pub section: Option<String>,
+    /// Whether the slide is hidden, which leaves it out of the PDF.
+    pub hidden: bool,
 }
 
 #[cfg(test)]
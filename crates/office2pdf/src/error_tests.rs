@@ -5,6 +5,7 @@ fn test_unsupported_element_display() {
     let w = ConvertWarning::UnsupportedElement {
         format: "DOCX".to_string(),
         element: "OLE object".to_string(),
+        location: None,
     };
     assert_eq!(w.to_string(), "[DOCX] unsupported element: OLE object");
 }
@@ -15,6 +16,7 @@ fn test_partial_element_display() {
         format: "PPTX".to_string(),
         element: "scheme color".to_string(),
         detail: "tint modifier ignored".to_string(),
+        location: None,
     };
     assert_eq!(
         w.to_string(),
@@ -28,6 +30,7 @@ fn test_fallback_used_display() {
         format: "DOCX".to_string(),
         from: "chart".to_string(),
         to: "data table".to_string(),
+        location: None,
     };
     assert_eq!(
         w.to_string(),
@@ -40,6 +43,7 @@ fn test_parse_skipped_display() {
     let w = ConvertWarning::ParseSkipped {
         format: "PPTX".to_string(),
         reason: "slide 3 failed to parse: missing XML".to_string(),
+        location: None,
     };
     assert_eq!(
         w.to_string(),
@@ -53,6 +57,7 @@ fn test_warning_format_accessor() {
         format: "XLSX".to_string(),
         from: "chart".to_string(),
         to: "data table".to_string(),
+        location: None,
     };
     assert_eq!(w.format(), "XLSX");
 }
@@ -62,6 +67,7 @@ fn test_warning_clone_and_eq() {
     let w = ConvertWarning::ParseSkipped {
         format: "DOCX".to_string(),
         reason: "element panicked".to_string(),
+        location: None,
     };
     let w2 = w.clone();
     assert_eq!(w, w2);
@@ -74,8 +80,10 @@ fn test_convert_result_fields() {
         warnings: vec![ConvertWarning::UnsupportedElement {
             format: "DOCX".to_string(),
             element: "Image".to_string(),
+            location: None,
         }],
         metrics: None,
+        typst_sources: Vec::new(),
     };
     assert_eq!(result.pdf, vec![0x25, 0x50, 0x44, 0x46]);
     assert_eq!(result.warnings.len(), 1);
@@ -88,6 +96,7 @@ fn test_convert_result_empty_warnings() {
         pdf: vec![1, 2, 3],
         warnings: vec![],
         metrics: None,
+        typst_sources: Vec::new(),
     };
     assert!(result.warnings.is_empty());
 }
@@ -145,6 +154,7 @@ fn test_convert_result_with_metrics() {
             output_size_bytes: 200,
             page_count: 1,
         }),
+        typst_sources: Vec::new(),
     };
     assert!(result.metrics.is_some());
     let m = result.metrics.unwrap();
@@ -184,20 +194,24 @@ fn test_all_variants_carry_format() {
         ConvertWarning::UnsupportedElement {
             format: "DOCX".to_string(),
             element: "x".to_string(),
+            location: None,
         },
         ConvertWarning::PartialElement {
             format: "PPTX".to_string(),
             element: "x".to_string(),
             detail: "y".to_string(),
+            location: None,
         },
         ConvertWarning::FallbackUsed {
             format: "XLSX".to_string(),
             from: "x".to_string(),
             to: "y".to_string(),
+            location: None,
         },
         ConvertWarning::ParseSkipped {
             format: "DOCX".to_string(),
             reason: "x".to_string(),
+            location: None,
         },
     ];
     let expected_formats = ["DOCX", "PPTX", "XLSX", "DOCX"];
@@ -205,3 +219,156 @@ fn test_all_variants_carry_format() {
         assert_eq!(w.format(), *expected);
     }
 }
+
+#[test]
+fn test_content_omitted_display() {
+    let w = ConvertWarning::ContentOmitted {
+        format: "XLSX".to_string(),
+        content: "hidden sheet 'Secret'".to_string(),
+        location: Some(WarningLocation::Sheet("Secret".to_string())),
+    };
+    assert_eq!(w.to_string(), "[XLSX] omitted: hidden sheet 'Secret'");
+}
+
+#[test]
+fn test_content_overflow_display() {
+    let w = ConvertWarning::ContentOverflow {
+        format: "PPTX".to_string(),
+        slide: 4,
+        element: "text box \"Agenda\"".to_string(),
+        location: Some(WarningLocation::Slide(6)),
+    };
+    assert_eq!(
+        w.to_string(),
+        "[PPTX] content overflows text box \"Agenda\" on slide 4"
+    );
+    assert_eq!(w.location(), Some(&WarningLocation::Slide(6)));
+}
+
+#[test]
+fn test_missing_glyph_display() {
+    let mut w = ConvertWarning::MissingGlyph {
+        format: "DOCX".to_string(),
+        character: '\u{1F6F8}',
+        page: 2,
+        text: "Launch \u{1F6F8} today".to_string(),
+        replacement: None,
+    };
+    assert_eq!(
+        w.to_string(),
+        "[DOCX] no font has a glyph for '\u{1F6F8}' (U+1F6F8), first on page 2 in \"Launch \u{1F6F8} today\""
+    );
+    if let ConvertWarning::MissingGlyph { replacement, .. } = &mut w {
+        *replacement = Some('?');
+    }
+    assert!(w.to_string().ends_with("; drawn as '?'"));
+}
+
+#[test]
+fn test_warning_codes_and_severities() {
+    let cases = [
+        (
+            ConvertWarning::UnsupportedElement {
+                format: "DOCX".to_string(),
+                element: "x".to_string(),
+                location: None,
+            },
+            "unsupported_element",
+            WarningSeverity::Warning,
+        ),
+        (
+            ConvertWarning::PartialElement {
+                format: "PPTX".to_string(),
+                element: "x".to_string(),
+                detail: "y".to_string(),
+                location: None,
+            },
+            "partial_element",
+            WarningSeverity::Warning,
+        ),
+        (
+            ConvertWarning::FallbackUsed {
+                format: "XLSX".to_string(),
+                from: "x".to_string(),
+                to: "y".to_string(),
+                location: None,
+            },
+            "fallback_used",
+            WarningSeverity::Info,
+        ),
+        (
+            ConvertWarning::ParseSkipped {
+                format: "DOCX".to_string(),
+                reason: "x".to_string(),
+                location: None,
+            },
+            "parse_skipped",
+            WarningSeverity::RecoveredError,
+        ),
+        (
+            ConvertWarning::ContentOmitted {
+                format: "XLSX".to_string(),
+                content: "x".to_string(),
+                location: None,
+            },
+            "content_omitted",
+            WarningSeverity::Info,
+        ),
+        (
+            ConvertWarning::ContentOverflow {
+                format: "PPTX".to_string(),
+                slide: 1,
+                element: "x".to_string(),
+                location: None,
+            },
+            "content_overflow",
+            WarningSeverity::Warning,
+        ),
+        (
+            ConvertWarning::MissingGlyph {
+                format: "DOCX".to_string(),
+                character: 'x',
+                page: 1,
+                text: "x".to_string(),
+                replacement: None,
+            },
+            "missing_glyph",
+            WarningSeverity::Warning,
+        ),
+    ];
+    for (warning, code, severity) in cases {
+        assert_eq!(warning.code(), code);
+        assert_eq!(warning.severity(), severity);
+    }
+}
+
+#[test]
+fn test_warning_severity_is_ordered() {
+    assert!(WarningSeverity::Info < WarningSeverity::Warning);
+    assert!(WarningSeverity::Warning < WarningSeverity::RecoveredError);
+}
+
+#[test]
+fn test_warning_location_display() {
+    assert_eq!(WarningLocation::Slide(3).to_string(), "slide 3");
+    assert_eq!(
+        WarningLocation::Sheet("Q1".to_string()).to_string(),
+        "sheet 'Q1'"
+    );
+    assert_eq!(WarningLocation::Paragraph(12).to_string(), "paragraph 12");
+}
+
+#[test]
+fn test_or_location_keeps_existing_location() {
+    let w = ConvertWarning::ParseSkipped {
+        format: "PPTX".to_string(),
+        reason: "x".to_string(),
+        location: None,
+    }
+    .or_location(WarningLocation::Slide(2));
+    assert_eq!(w.location(), Some(&WarningLocation::Slide(2)));
+    assert_eq!(
+        w.or_location(WarningLocation::Slide(5)).location(),
+        Some(&WarningLocation::Slide(2))
+    );
+}
@@ -0,0 +1,240 @@
+//! Round-trip text fidelity checks between a source document and its PDF.
+//!
+//! The source is parsed into the IR and split into text segments; the PDF's
+//! text layer is extracted with [`pdf_ops::extract_text`](crate::pdf_ops::extract_text).
+//! Both sides are normalized (NFKC, typographic quotes folded, whitespace
+//! dropped) because PDF text extraction does not preserve line breaks or
+//! inter-word spacing reliably.
+
+use std::collections::BTreeMap;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::config::{ConvertOptions, Format};
+use crate::error::ConvertError;
+
+/// Shortest run of characters that counts as recovered from the PDF. Shorter
+/// matches are too likely to be coincidental.
+const MIN_RECOVERED_RUN_CHARS: usize = 4;
+
+/// Segments with at least this fraction of their characters recovered are
+/// reported as garbled rather than missing.
+const GARBLED_MIN_RECOVERED_FRACTION: f64 = 0.5;
+
+/// Differences between the text of a source document and its PDF.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FidelityDiff {
+    /// Number of non-blank text segments in the source document.
+    pub source_segments: usize,
+    /// Source segments with no meaningful trace in the PDF.
+    pub missing: Vec<String>,
+    /// Source segments only partially recoverable from the PDF.
+    pub garbled: Vec<GarbledSegment>,
+    /// Fraction of source characters recovered from the PDF, 0.0 to 1.0.
+    pub coverage: f64,
+}
+
+impl FidelityDiff {
+    /// True when every source segment was found intact in the PDF.
+    pub fn is_faithful(&self) -> bool {
+        self.missing.is_empty() && self.garbled.is_empty()
+    }
+}
+
+/// A source segment whose text only partially survives in the PDF.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GarbledSegment {
+    /// The segment text as it appears in the source document.
+    pub source: String,
+    /// Fraction of the segment's characters recovered from the PDF.
+    pub recovered: f64,
+}
+
+pub(crate) fn verify(
+    source: &[u8],
+    format: Format,
+    pdf: &[u8],
+    options: &ConvertOptions,
+) -> Result<FidelityDiff, ConvertError> {
+    let (doc, _warnings) = crate::pipeline::parse_document(source, format, options)?;
+    let pdf_text: String = crate::pdf_ops::extract_text(pdf)?
+        .into_iter()
+        .map(|page| page.text)
+        .collect();
+    Ok(diff_segments(&doc.text_segments(), &pdf_text))
+}
+
+/// Compare source segments against the PDF's extracted text.
+pub(crate) fn diff_segments(segments: &[String], pdf_text: &str) -> FidelityDiff {
+    let haystack: SubstringIndex = SubstringIndex::new(&normalize_text(pdf_text));
+    let mut diff = FidelityDiff {
+        source_segments: 0,
+        missing: Vec::new(),
+        garbled: Vec::new(),
+        coverage: 1.0,
+    };
+    let mut total_chars: usize = 0;
+    let mut recovered_chars: usize = 0;
+
+    for segment in segments {
+        let needle: Vec<char> = normalize_text(segment).chars().collect();
+        if needle.is_empty() {
+            continue;
+        }
+        diff.source_segments += 1;
+        total_chars += needle.len();
+
+        let recovered: usize = recovered_char_count(&needle, &haystack);
+        recovered_chars += recovered;
+        if recovered == needle.len() {
+            continue;
+        }
+        let fraction: f64 = recovered as f64 / needle.len() as f64;
+        if fraction >= GARBLED_MIN_RECOVERED_FRACTION {
+            diff.garbled.push(GarbledSegment {
+                source: segment.clone(),
+                recovered: fraction,
+            });
+        } else {
+            diff.missing.push(segment.clone());
+        }
+    }
+
+    if total_chars > 0 {
+        diff.coverage = recovered_chars as f64 / total_chars as f64;
+    }
+    diff
+}
+
+/// Count the characters of `needle` covered by greedy longest runs that occur
+/// in `haystack`. A paragraph broken by a page footer or a column wrap is
+/// still fully recovered as long as each piece appears somewhere.
+fn recovered_char_count(needle: &[char], haystack: &SubstringIndex) -> usize {
+    if haystack.longest_prefix(needle) == needle.len() {
+        return needle.len();
+    }
+    if needle.len() < MIN_RECOVERED_RUN_CHARS {
+        return 0;
+    }
+
+    let mut recovered: usize = 0;
+    let mut start: usize = 0;
+    while start < needle.len() {
+        let run_len: usize = haystack.longest_prefix(&needle[start..]);
+        if run_len >= MIN_RECOVERED_RUN_CHARS {
+            recovered += run_len;
+            start += run_len;
+        } else {
+            start += 1;
+        }
+    }
+    recovered
+}
+
+/// Suffix automaton of the PDF text: every substring of the text is a path
+/// of transitions from the first state. It is built once, in time linear in
+/// the text, and finding the longest prefix of a segment that occurs in the
+/// text is then a single walk. Searching the text afresh for every prefix
+/// was quadratic in the length of the PDF's text.
+struct SubstringIndex {
+    states: Vec<IndexState>,
+}
+
+struct IndexState {
+    /// Length of the longest substring ending in this state.
+    len: usize,
+    /// The state of the longest suffix that ends elsewhere too.
+    link: Option<usize>,
+    next: BTreeMap<char, usize>,
+}
+
+impl SubstringIndex {
+    fn new(text: &str) -> Self {
+        let mut states: Vec<IndexState> = vec![IndexState {
+            len: 0,
+            link: None,
+            next: BTreeMap::new(),
+        }];
+        let mut last: usize = 0;
+        for ch in text.chars() {
+            let current: usize = states.len();
+            states.push(IndexState {
+                len: states[last].len + 1,
+                link: None,
+                next: BTreeMap::new(),
+            });
+            let mut state: Option<usize> = Some(last);
+            while let Some(from) = state {
+                if states[from].next.contains_key(&ch) {
+                    break;
+                }
+                states[from].next.insert(ch, current);
+                state = states[from].link;
+            }
+            states[current].link = Some(match state {
+                None => 0,
+                Some(from) => {
+                    let to: usize = states[from].next[&ch];
+                    if states[from].len + 1 == states[to].len {
+                        to
+                    } else {
+                        let clone: usize = states.len();
+                        states.push(IndexState {
+                            len: states[from].len + 1,
+                            link: states[to].link,
+                            next: states[to].next.clone(),
+                        });
+                        let mut state: Option<usize> = Some(from);
+                        while let Some(from) = state {
+                            if states[from].next.get(&ch) != Some(&to) {
+                                break;
+                            }
+                            states[from].next.insert(ch, clone);
+                            state = states[from].link;
+                        }
+                        states[to].link = Some(clone);
+                        clone
+                    }
+                }
+            });
+            last = current;
+        }
+        Self { states }
+    }
+
+    /// Length of the longest prefix of `chars` that occurs in the text.
+    fn longest_prefix(&self, chars: &[char]) -> usize {
+        let mut state: usize = 0;
+        for (index, ch) in chars.iter().enumerate() {
+            match self.states[state].next.get(ch) {
+                Some(&next) => state = next,
+                None => return index,
+            }
+        }
+        chars.len()
+    }
+}
+
+fn normalize_text(text: &str) -> String {
+    text.nfkc()
+        .filter(|ch| !ch.is_whitespace() && !is_invisible_format_char(*ch))
+        .map(|ch| match ch {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => '\'',
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => '"',
+            '\u{2010}' | '\u{2011}' => '-',
+            other => other,
+        })
+        .collect()
+}
+
+/// Soft hyphens and zero-width characters never reach the text layer.
+fn is_invisible_format_char(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{00AD}' | '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}'
+    )
+}
+
+#[cfg(test)]
+#[path = "fidelity_tests.rs"]
+mod tests;
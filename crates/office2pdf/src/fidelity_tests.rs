@@ -0,0 +1,104 @@
+use super::*;
+
+fn segments(texts: &[&str]) -> Vec<String> {
+    texts.iter().map(|text| text.to_string()).collect()
+}
+
+#[test]
+fn test_diff_segments_ignores_whitespace_and_quote_style() {
+    let diff = diff_segments(
+        &segments(&["Quarterly \"revenue\" grew", "Don't panic"]),
+        "Quarterly “revenue”\ngrew Don’t  panic",
+    );
+    assert!(diff.is_faithful(), "{diff:?}");
+    assert_eq!(diff.source_segments, 2);
+    assert_eq!(diff.coverage, 1.0);
+}
+
+#[test]
+fn test_diff_segments_recovers_paragraph_split_by_footer() {
+    let diff = diff_segments(
+        &segments(&["The committee approved the annual budget"]),
+        "The committee approved Page 1 the annual budget",
+    );
+    assert!(diff.is_faithful(), "{diff:?}");
+}
+
+#[test]
+fn test_diff_segments_reports_missing_segment() {
+    let diff = diff_segments(
+        &segments(&["Present paragraph", "Dropped paragraph text"]),
+        "Present paragraph",
+    );
+    assert_eq!(diff.missing, vec!["Dropped paragraph text".to_string()]);
+    assert!(diff.garbled.is_empty());
+    assert!(diff.coverage < 1.0);
+}
+
+#[test]
+fn test_diff_segments_reports_garbled_segment() {
+    let diff = diff_segments(
+        &segments(&["Revenue summary for Q3"]),
+        "Revenue summ\u{FFFD}\u{FFFD}y for Q3",
+    );
+    assert!(diff.missing.is_empty());
+    assert_eq!(diff.garbled.len(), 1);
+    assert_eq!(diff.garbled[0].source, "Revenue summary for Q3");
+    assert!(diff.garbled[0].recovered >= 0.5 && diff.garbled[0].recovered < 1.0);
+}
+
+#[test]
+fn test_diff_segments_scales_to_long_text() {
+    // Letters from a linear congruential generator, so runs repeat rarely.
+    let mut seed: u32 = 1;
+    let pdf_text: String = (0..200_000)
+        .map(|_| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            char::from(b'a' + (seed >> 16) as u8 % 26)
+        })
+        .collect();
+    // Half the segment is in the PDF; the rest is digits, which are not.
+    let segment: String = format!("{}{}", &pdf_text[50_000..70_000], "7".repeat(20_000));
+    let diff = diff_segments(&[segment], &pdf_text);
+    assert_eq!(diff.garbled.len(), 1, "{diff:?}");
+    assert_eq!(diff.garbled[0].recovered, 0.5);
+}
+
+#[test]
+fn test_substring_index_finds_longest_prefix() {
+    let index = SubstringIndex::new("abcbcdabd");
+    let chars = |text: &str| -> Vec<char> { text.chars().collect() };
+    assert_eq!(index.longest_prefix(&chars("bcda")), 4);
+    assert_eq!(index.longest_prefix(&chars("bcdx")), 3);
+    assert_eq!(index.longest_prefix(&chars("dabd")), 4);
+    assert_eq!(index.longest_prefix(&chars("abda")), 3);
+    assert_eq!(index.longest_prefix(&chars("x")), 0);
+    assert_eq!(index.longest_prefix(&[]), 0);
+}
+
+#[test]
+fn test_diff_segments_empty_source_is_faithful() {
+    let diff = diff_segments(&[], "anything");
+    assert!(diff.is_faithful());
+    assert_eq!(diff.source_segments, 0);
+    assert_eq!(diff.coverage, 1.0);
+}
+
+#[test]
+fn test_verify_round_trips_converted_docx() {
+    let docx = crate::test_support::build_docx_with_title("Fidelity");
+    let result = crate::convert_bytes(&docx, Format::Docx, &ConvertOptions::default()).unwrap();
+    let diff = crate::verify_fidelity(&docx, Format::Docx, &result.pdf).unwrap();
+    assert!(diff.is_faithful(), "{diff:?}");
+    assert_eq!(diff.source_segments, 1);
+}
+
+#[test]
+fn test_verify_flags_text_absent_from_pdf() {
+    let docx = crate::test_support::build_docx_with_title("Fidelity");
+    let unrelated = crate::test_support::make_simple_document("Unrelated content");
+    let pdf = crate::render_document(&unrelated).unwrap();
+    let diff = crate::verify_fidelity(&docx, Format::Docx, &pdf).unwrap();
+    assert_eq!(diff.missing, vec!["Hello".to_string()]);
+    assert_eq!(diff.coverage, 0.0);
+}
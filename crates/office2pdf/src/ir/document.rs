@@ -3,14 +3,18 @@ use super::style::StyleSheet;
 
 /// Top-level document model produced by parsers and consumed by the renderer.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Document {
     pub metadata: Metadata,
     pub pages: Vec<Page>,
     pub styles: StyleSheet,
 }
 
-/// Document metadata extracted from OOXML `docProps/core.xml` (Dublin Core).
+/// Document metadata extracted from OOXML `docProps/core.xml` (Dublin Core),
+/// `docProps/app.xml` and `docProps/custom.xml`.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Metadata {
     pub title: Option<String>,
     pub author: Option<String>,
@@ -18,10 +22,21 @@ pub struct Metadata {
     pub description: Option<String>,
     pub created: Option<String>,
     pub modified: Option<String>,
+    /// Primary language as a BCP 47 tag such as `en-US`, written to the PDF
+    /// catalog for assistive technology.
+    pub language: Option<String>,
+    /// Keywords from `cp:keywords`, split at commas and semicolons.
+    pub keywords: Vec<String>,
+    /// Company from the extended properties in `docProps/app.xml`.
+    pub company: Option<String>,
+    /// User-defined properties from `docProps/custom.xml` as `(name, value)`
+    /// pairs in file order, values as written (`true`, `2024-01-31T00:00:00Z`).
+    pub custom_properties: Vec<(String, String)>,
 }
 
 /// A page in the document — variant depends on source format.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Page {
     /// DOCX: flowing text pages.
     Flow(FlowPage),
@@ -33,6 +48,7 @@ pub enum Page {
 
 /// Page dimensions.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PageSize {
     /// Width in points (1 pt = 1/72 inch).
     pub width: f64,
@@ -51,6 +67,7 @@ impl Default for PageSize {
 
 /// Page margins in points.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Margins {
     pub top: f64,
     pub bottom: f64,
@@ -71,6 +88,7 @@ impl Default for Margins {
 
 /// Column layout configuration for multi-column sections.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColumnLayout {
     /// Number of columns (must be >= 2 for multi-column layout).
     pub num_columns: u32,
@@ -82,6 +100,7 @@ pub struct ColumnLayout {
 
 /// A flowing-content page (DOCX).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FlowPage {
     pub size: PageSize,
     pub margins: Margins,
@@ -97,6 +116,7 @@ pub struct FlowPage {
 
 /// A fixed-layout page (PPTX slides).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FixedPage {
     pub size: PageSize,
     pub elements: Vec<FixedElement>,
@@ -104,10 +124,28 @@ pub struct FixedPage {
     pub background_color: Option<super::style::Color>,
     /// Optional gradient background (takes precedence over `background_color` when present).
     pub background_gradient: Option<super::elements::GradientFill>,
+    /// Optional picture background, drawn over the background fill and
+    /// behind every element.
+    pub background_image: Option<BackgroundImage>,
+    /// The PPTX slide this page was parsed from, numbered from 1 as
+    /// `slide_range` counts them, so layout warnings can name the slide
+    /// even when hidden or unselected slides shift the page numbers.
+    pub source_slide: Option<u32>,
+}
+
+/// Picture fill of a fixed page background (PPTX `<p:bgPr><a:blipFill>`).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BackgroundImage {
+    pub image: super::elements::ImageData,
+    /// Size of one tile in points when the picture repeats (`<a:tile>`);
+    /// `None` stretches the picture over the whole page.
+    pub tile_size: Option<(f64, f64)>,
 }
 
 /// An element with fixed position on a page.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FixedElement {
     /// X position in points from left edge.
     pub x: f64,
@@ -119,10 +157,14 @@ pub struct FixedElement {
     pub height: f64,
     /// The content of this element.
     pub kind: FixedElementKind,
+    /// Click target covering the element's frame: an external URL, or a
+    /// [`page_link`](crate::ir::page_link) to another page of the document.
+    pub href: Option<String>,
 }
 
 /// Types of fixed-position elements.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FixedElementKind {
     TextBox(super::elements::TextBoxData),
     Image(super::elements::ImageData),
@@ -134,6 +176,7 @@ pub enum FixedElementKind {
 
 /// A spreadsheet sheet page (XLSX sheets).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SheetPage {
     pub name: String,
     pub size: PageSize,
@@ -152,6 +195,7 @@ pub struct SheetPage {
 
 /// A worksheet text box anchored to a sheet row.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SheetTextBox {
     /// 1-indexed row number after which the box is rendered (like charts).
     pub anchor_row: u32,
@@ -170,11 +214,15 @@ pub struct SheetTextBox {
 
 /// A worksheet drawing image anchored to a sheet row.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SheetImage {
-    /// 1-indexed row number after which the image is rendered (like charts).
+    /// 1-indexed row number after which the image is rendered (like charts);
+    /// 0 renders it above the first row.
     pub anchor_row: u32,
     /// Horizontal offset of the anchor from the sheet's left edge, points.
     pub x_offset_pt: f64,
+    /// Vertical offset of the image's top below its anchor row, points.
+    pub y_offset_pt: f64,
     pub image: super::elements::ImageData,
 }
 
@@ -4,6 +4,7 @@ use super::style::{Alignment, Color, ParagraphStyle, TabLeader, TextStyle};
 
 /// Header or footer content for flow pages.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HeaderFooter {
     pub paragraphs: Vec<HeaderFooterParagraph>,
     /// Distance in points from the page edge, as specified by the section page margins.
@@ -12,6 +13,7 @@ pub struct HeaderFooter {
 
 /// A paragraph within a header or footer.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HeaderFooterParagraph {
     pub style: ParagraphStyle,
     pub elements: Vec<HFInline>,
@@ -21,6 +23,7 @@ pub struct HeaderFooterParagraph {
 
 /// Page- or margin-relative positioning for a header/footer paragraph frame.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HeaderFooterFrame {
     pub x: Option<f64>,
     pub y: Option<f64>,
@@ -31,6 +34,7 @@ pub struct HeaderFooterFrame {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FrameAnchor {
     Page,
     Margin,
@@ -40,6 +44,7 @@ pub enum FrameAnchor {
 
 /// A position-relative tab (`w:ptab`) inside header/footer content.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PositionedTab {
     pub alignment: PositionedTabAlignment,
     pub relative_to: PositionedTabRelativeTo,
@@ -47,6 +52,7 @@ pub struct PositionedTab {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PositionedTabAlignment {
     Center,
     #[default]
@@ -55,6 +61,7 @@ pub enum PositionedTabAlignment {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PositionedTabRelativeTo {
     Indent,
     #[default]
@@ -63,6 +70,7 @@ pub enum PositionedTabRelativeTo {
 
 /// An inline element within a header or footer paragraph.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HFInline {
     /// A text run with styling.
     Run(Run),
@@ -74,10 +82,25 @@ pub enum HFInline {
     TotalPages,
     /// Alignment tab positioned relative to the paragraph indent or page margin.
     PositionedTab(PositionedTab),
+    /// Date or time of rendering field.
+    DateTime(DateTimeField),
+}
+
+/// A field showing when the document is rendered, such as the `&D` and `&T`
+/// codes of an XLSX header. It is filled in while rendering rather than
+/// parsing, so the renderer's clock decides it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DateTimeField {
+    /// Excel number format of date and time codes, such as `m/d/yyyy` or
+    /// `h:mm AM/PM`.
+    pub format: String,
+    pub style: TextStyle,
 }
 
 /// Block-level content elements.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Block {
     Paragraph(Paragraph),
     Table(Table),
@@ -96,8 +119,10 @@ pub enum Block {
 
 /// A chart extracted from an embedded chart object.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Chart {
-    /// The type of chart (bar, line, pie, etc.).
+    /// The type of chart (bar, line, pie, etc.). For combo charts this is the
+    /// type of the first plot; series from other plots carry their own type.
     pub chart_type: ChartType,
     /// Optional chart title.
     pub title: Option<String>,
@@ -105,31 +130,59 @@ pub struct Chart {
     pub categories: Vec<String>,
     /// Data series.
     pub series: Vec<ChartSeries>,
+    /// How bar, column, line, and area series share the value axis.
+    pub grouping: ChartGrouping,
 }
 
 /// The type of chart.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ChartType {
     Bar,
     Column,
     Line,
     Pie,
+    Doughnut,
     Area,
     Scatter,
+    Bubble,
+    Radar,
     Other(String),
 }
 
+/// Series arrangement of a bar, column, line, or area chart (`<c:grouping>`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChartGrouping {
+    /// Series side by side (bars) or overlapping (lines, areas).
+    #[default]
+    Clustered,
+    /// Series stacked on top of each other.
+    Stacked,
+    /// Series stacked and scaled so each category totals 100%.
+    PercentStacked,
+}
+
 /// A data series within a chart.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChartSeries {
     /// Optional series name.
     pub name: Option<String>,
     /// Data values for this series.
     pub values: Vec<f64>,
+    /// Bubble sizes of a bubble chart series, parallel to `values`.
+    pub bubble_sizes: Vec<f64>,
+    /// Plot type of this series in a combo chart, when it differs from the
+    /// chart's `chart_type`.
+    pub chart_type: Option<ChartType>,
+    /// Whether the series is plotted against the secondary value axis.
+    pub secondary_axis: bool,
 }
 
 /// A math equation (from OMML or similar).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathEquation {
     /// Typst math notation content (without surrounding `$` delimiters).
     pub content: String,
@@ -139,6 +192,7 @@ pub struct MathEquation {
 
 /// How text wraps around a floating image.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WrapMode {
     /// Text wraps around the image on both sides (square bounding box).
     Square,
@@ -156,6 +210,7 @@ pub enum WrapMode {
 
 /// A floating image with positioning and text wrap mode.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FloatingImage {
     pub image: ImageData,
     pub wrap_mode: WrapMode,
@@ -167,6 +222,7 @@ pub struct FloatingImage {
 
 /// A floating text box with positioning, size, and text wrap mode.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FloatingTextBox {
     pub content: Vec<Block>,
     pub wrap_mode: WrapMode,
@@ -185,6 +241,7 @@ pub struct FloatingTextBox {
 /// that carry geometry but no text box — these have no docx-rs representation
 /// and would otherwise be dropped (issue #176).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FloatingShape {
     pub shape: Shape,
     /// On-page bounding-box width in points (from `wp:extent`).
@@ -200,6 +257,7 @@ pub struct FloatingShape {
 
 /// Vertical alignment for fixed text box content.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextBoxVerticalAlign {
     #[default]
     Top,
@@ -209,6 +267,7 @@ pub enum TextBoxVerticalAlign {
 
 /// A fixed-position text box with content padding and vertical alignment.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextBoxData {
     pub content: Vec<Block>,
     pub padding: Insets,
@@ -234,6 +293,7 @@ pub struct TextBoxData {
 
 /// The kind of list: ordered (numbered) or unordered (bulleted).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ListKind {
     Ordered,
     Unordered,
@@ -241,6 +301,7 @@ pub enum ListKind {
 
 /// Numbering configuration for a specific list level.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListLevelStyle {
     pub kind: ListKind,
     /// Optional Typst numbering pattern derived from Word's lvlText/numFmt.
@@ -255,6 +316,7 @@ pub struct ListLevelStyle {
 
 /// A list block containing items at various indent levels.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct List {
     pub kind: ListKind,
     pub items: Vec<ListItem>,
@@ -264,6 +326,7 @@ pub struct List {
 
 /// A single list item with content and indent level.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListItem {
     pub content: Vec<Paragraph>,
     pub level: u32,
@@ -273,6 +336,7 @@ pub struct ListItem {
 
 /// A paragraph consisting of styled text runs.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Paragraph {
     pub style: ParagraphStyle,
     pub runs: Vec<Run>,
@@ -280,18 +344,53 @@ pub struct Paragraph {
 
 /// A run of text with uniform formatting.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Run {
     pub text: String,
     pub style: TextStyle,
-    /// Optional hyperlink URL. When present, the run is rendered as a clickable link.
+    /// Optional hyperlink URL, or a [`page_link`] or [`sheet_link`] to
+    /// another page of the document. When present, the run is rendered as a
+    /// clickable link.
     pub href: Option<String>,
     /// Optional footnote/endnote content. When present, a footnote marker is emitted and
     /// the content is rendered at the bottom of the page.
     pub footnote: Option<String>,
 }
 
+/// Prefix of in-document page links; see [`page_link`].
+const PAGE_LINK_PREFIX: &str = "#page=";
+
+/// Href that jumps to the 1-based `page` of the same document, written in the
+/// PDF open-parameter form (`#page=N`).
+pub fn page_link(page: usize) -> String {
+    format!("{PAGE_LINK_PREFIX}{page}")
+}
+
+/// Target page of an href built by [`page_link`], or `None` for URLs.
+pub fn page_link_target(href: &str) -> Option<usize> {
+    href.strip_prefix(PAGE_LINK_PREFIX)?
+        .parse::<usize>()
+        .ok()
+        .filter(|page| *page > 0)
+}
+
+/// Prefix of in-document sheet links; see [`sheet_link`].
+const SHEET_LINK_PREFIX: &str = "#sheet=";
+
+/// Href that jumps to the first page of the named worksheet in the same
+/// document.
+pub fn sheet_link(sheet_name: &str) -> String {
+    format!("{SHEET_LINK_PREFIX}{sheet_name}")
+}
+
+/// Target sheet name of an href built by [`sheet_link`], or `None` for URLs.
+pub fn sheet_link_target(href: &str) -> Option<&str> {
+    href.strip_prefix(SHEET_LINK_PREFIX)
+}
+
 /// A table.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table {
     pub rows: Vec<TableRow>,
     pub column_widths: Vec<f64>,
@@ -312,6 +411,7 @@ pub struct Table {
 
 /// A table row.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableRow {
     pub cells: Vec<TableCell>,
     pub height: Option<f64>,
@@ -319,6 +419,7 @@ pub struct TableRow {
 
 /// A data bar rendering within a cell (conditional formatting).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DataBarInfo {
     /// Bar color.
     pub color: Color,
@@ -328,14 +429,26 @@ pub struct DataBarInfo {
 
 /// Vertical alignment within a table cell.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CellVerticalAlign {
     Top,
     Center,
     Bottom,
 }
 
+/// Orientation of text inside a table cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellTextRotation {
+    /// Rotated by the given degrees, counterclockwise positive (-90 to 90).
+    Angle(f64),
+    /// Upright letters stacked top to bottom.
+    Stacked,
+}
+
 /// Insets/padding in points.
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Insets {
     pub top: f64,
     pub right: f64,
@@ -345,6 +458,7 @@ pub struct Insets {
 
 /// A table cell.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableCell {
     pub content: Vec<Block>,
     pub col_span: u32,
@@ -365,6 +479,13 @@ pub struct TableCell {
     pub vertical_align: Option<CellVerticalAlign>,
     /// Optional cell padding override in points.
     pub padding: Option<Insets>,
+    /// Keep the content on one line and clip it at the cell edge instead of
+    /// wrapping (Excel text without `wrapText` that has nowhere to spill).
+    pub no_wrap: bool,
+    /// Scale the content down until it fits the cell width on one line.
+    pub shrink_to_fit: bool,
+    /// Text orientation of the cell content.
+    pub text_rotation: Option<CellTextRotation>,
 }
 
 impl Default for TableCell {
@@ -381,12 +502,16 @@ impl Default for TableCell {
             spill_width: None,
             vertical_align: None,
             padding: None,
+            no_wrap: false,
+            shrink_to_fit: false,
+            text_rotation: None,
         }
     }
 }
 
 /// Cell border specification.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CellBorder {
     pub top: Option<BorderSide>,
     pub bottom: Option<BorderSide>,
@@ -396,6 +521,7 @@ pub struct CellBorder {
 
 /// Border line style (dash pattern).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BorderLineStyle {
     #[default]
     Solid,
@@ -409,6 +535,7 @@ pub enum BorderLineStyle {
 
 /// A single border side.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BorderSide {
     pub width: f64,
     pub color: Color,
@@ -417,6 +544,7 @@ pub struct BorderSide {
 
 /// Fractions of the source image cropped away from each edge.
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageCrop {
     pub left: f64,
     pub top: f64,
@@ -432,6 +560,7 @@ impl ImageCrop {
 
 /// Image data.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageData {
     pub data: Vec<u8>,
     pub format: ImageFormat,
@@ -451,6 +580,7 @@ pub struct ImageData {
 
 /// Supported picture clip geometries (PowerPoint "crop to shape").
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImageClipShape {
     /// Rounded rectangle with the corner radius as a fraction of the
     /// shorter side (PowerPoint's roundRect `adj`, default 1/6 ≈ 0.1667).
@@ -460,6 +590,7 @@ pub enum ImageClipShape {
 
 /// Supported image formats.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImageFormat {
     Png,
     Jpeg,
@@ -485,27 +616,54 @@ impl ImageFormat {
 
 /// A node in a SmartArt diagram with hierarchy depth.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SmartArtNode {
     /// The text content of this node.
     pub text: String,
     /// Depth in the hierarchy (0 = top-level node).
     pub depth: usize,
+    /// Index of the parent node in [`SmartArt::items`], `None` for top-level
+    /// nodes.
+    pub parent: Option<usize>,
 }
 
 /// SmartArt diagram content extracted from a presentation.
 ///
 /// Contains nodes extracted from the SmartArt data model with hierarchy
-/// information derived from the connection list.
-/// Rendered as an indented tree or numbered steps since full SmartArt
-/// layout engines are not feasible in a pure-Rust converter.
+/// information derived from the connection list, plus the layout family
+/// from the layout definition. Rendered as an approximate geometric layout
+/// per family since full SmartArt layout engines are not feasible in a
+/// pure-Rust converter.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SmartArt {
     /// Nodes extracted from SmartArt data points with hierarchy depth.
     pub items: Vec<SmartArtNode>,
+    /// Layout algorithm family used to arrange the nodes.
+    pub layout: SmartArtLayout,
+}
+
+/// SmartArt layout algorithm family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SmartArtLayout {
+    /// Lists, processes, and unrecognized layouts: an indented tree or
+    /// numbered steps.
+    #[default]
+    List,
+    /// Nodes around a circle (cycle and radial layouts).
+    Cycle,
+    /// Org charts and hierarchies: a tree of boxes with connectors.
+    Hierarchy,
+    /// Stacked bands of a triangle.
+    Pyramid,
+    /// Quadrants of a grid.
+    Matrix,
 }
 
 /// A single stop in a gradient fill.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GradientStop {
     /// Position along the gradient axis, from 0.0 (start) to 1.0 (end).
     pub position: f64,
@@ -515,6 +673,7 @@ pub struct GradientStop {
 
 /// A linear gradient fill.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GradientFill {
     /// Gradient color stops, ordered by position.
     pub stops: Vec<GradientStop>,
@@ -523,7 +682,8 @@ pub struct GradientFill {
 }
 
 /// An outer shadow effect on a shape.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Shadow {
     /// Blur radius in points.
     pub blur_radius: f64,
@@ -535,10 +695,107 @@ pub struct Shadow {
     pub color: Color,
     /// Opacity from 0.0 (fully transparent) to 1.0 (fully opaque).
     pub opacity: f64,
+    /// Horizontal scale of the shadow silhouette (1.0 = same size as the
+    /// shape; `sx`, or the preset's perspective).
+    pub scale_x: f64,
+    /// Vertical scale of the shadow silhouette; negative values fold the
+    /// shadow forward below the shape's bottom edge (`sy`).
+    pub scale_y: f64,
+    /// Horizontal skew in degrees; positive values lean the top to the left
+    /// (`kx`).
+    pub skew_x: f64,
+    /// Vertical skew in degrees (`ky`).
+    pub skew_y: f64,
+}
+
+impl Shadow {
+    /// True when the silhouette is scaled or skewed (perspective shadows),
+    /// i.e. not just an offset copy of the shape.
+    pub fn has_perspective(&self) -> bool {
+        self.scale_x != 1.0 || self.scale_y != 1.0 || self.skew_x != 0.0 || self.skew_y != 0.0
+    }
+}
+
+/// A soft glow halo around a shape silhouette (`a:effectLst/a:glow`).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Glow {
+    /// Glow radius in points, measured outward from the shape edge.
+    pub radius: f64,
+    /// Glow color.
+    pub color: Color,
+    /// Opacity at the shape edge from 0.0 (fully transparent) to 1.0 (fully opaque).
+    pub opacity: f64,
+}
+
+/// A mirrored copy of the shape below its bottom edge (`a:effectLst/a:reflection`).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reflection {
+    /// Gap between the shape's bottom edge and the reflection in points.
+    pub distance: f64,
+    /// Opacity of the reflection next to the shape (`stA`).
+    pub start_opacity: f64,
+    /// Opacity of the reflection at its far end (`endA`).
+    pub end_opacity: f64,
+    /// Fraction of the shape height that is mirrored, from 0.0 to 1.0 (`endPos`).
+    pub end_position: f64,
+}
+
+/// Shape effects that have no Typst primitive and are approximated in
+/// codegen (see `ConvertOptions::effects`).
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShapeEffects {
+    pub glow: Option<Glow>,
+    pub reflection: Option<Reflection>,
+    /// Soft-edge (feather) radius in points (`a:softEdge rad`).
+    pub soft_edge_radius: Option<f64>,
+    /// Camera rotation of the 3-D scene (`a:scene3d/a:camera`).
+    pub rotation_3d: Option<Rotation3d>,
+    /// Extruded depth of the 3-D shape (`a:sp3d`).
+    pub extrusion: Option<Extrusion>,
+}
+
+impl ShapeEffects {
+    /// True when no effect is present.
+    pub fn is_empty(&self) -> bool {
+        self.glow.is_none()
+            && self.reflection.is_none()
+            && self.soft_edge_radius.is_none()
+            && self.rotation_3d.is_none()
+            && self.extrusion.is_none()
+    }
+}
+
+/// Rotation of a shape in a 3-D scene, in degrees. Rendered as the
+/// orthographic projection of the rotated shape plane (an affine scale and
+/// skew), without perspective foreshortening.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rotation3d {
+    /// Tilt about the horizontal axis (`rot lat`).
+    pub latitude: f64,
+    /// Turn about the vertical axis (`rot lon`).
+    pub longitude: f64,
+    /// Spin in the shape plane (`rot rev`).
+    pub revolution: f64,
+}
+
+/// Extrusion of a 3-D shape (`a:sp3d extrusionH`), visible as side faces
+/// once the scene is rotated.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Extrusion {
+    /// Extrusion depth in points.
+    pub depth: f64,
+    /// Side face color (`a:extrusionClr`); `None` darkens the shape fill.
+    pub color: Option<Color>,
 }
 
 /// Basic geometric shape.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Shape {
     pub kind: ShapeKind,
     pub fill: Option<Color>,
@@ -551,10 +808,13 @@ pub struct Shape {
     pub opacity: Option<f64>,
     /// Outer shadow effect.
     pub shadow: Option<Shadow>,
+    /// Glow, reflection, and soft-edge effects.
+    pub effects: ShapeEffects,
 }
 
 /// Shape types.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShapeKind {
     Rectangle,
     Ellipse,
@@ -573,6 +833,11 @@ pub enum ShapeKind {
         head_end: ArrowHead,
         tail_end: ArrowHead,
     },
+    /// Freehand pen stroke (ink annotation) in points, relative to the
+    /// element's top-left. Drawn as one path with round caps and joins.
+    InkStroke {
+        points: Vec<(f64, f64)>,
+    },
     /// Rectangle with rounded corners. `radius_fraction` is relative to `min(width, height)`.
     RoundedRectangle {
         radius_fraction: f64,
@@ -585,6 +850,7 @@ pub enum ShapeKind {
 
 /// Arrowhead decoration on a line endpoint.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ArrowHead {
     #[default]
     None,
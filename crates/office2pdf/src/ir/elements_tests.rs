@@ -230,3 +230,18 @@ fn test_header_footer_with_page_number() {
     assert_eq!(hf.paragraphs[0].elements.len(), 2);
     assert!(matches!(hf.paragraphs[0].elements[1], HFInline::PageNumber));
 }
+
+#[test]
+fn test_page_link_round_trips_target_page() {
+    assert_eq!(page_link(3), "#page=3");
+    assert_eq!(page_link_target(&page_link(3)), Some(3));
+    assert_eq!(page_link_target("#page=0"), None);
+    assert_eq!(page_link_target("https://example.com/#page=2"), None);
+}
+
+#[test]
+fn test_sheet_link_round_trips_sheet_name() {
+    assert_eq!(sheet_link("Q1 Sales"), "#sheet=Q1 Sales");
+    assert_eq!(sheet_link_target(&sheet_link("Q1 Sales")), Some("Q1 Sales"));
+    assert_eq!(sheet_link_target("https://example.com/#sheet=Q1"), None);
+}
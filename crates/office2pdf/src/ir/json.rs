@@ -0,0 +1,72 @@
+//! JSON persistence of the IR (`serde` feature). Parse once, store or edit
+//! the [`Document`], and render it later or elsewhere, for example parse
+//! natively and render in WASM. Other serde formats such as CBOR work on
+//! [`Document`] directly; the JSON helpers add a version envelope so a
+//! document saved by an incompatible release is rejected instead of
+//! misread.
+
+use serde::{Deserialize, Serialize};
+
+use super::document::Document;
+use crate::error::ConvertError;
+
+/// Version of the serialized IR. Bumped whenever a change to the IR types
+/// would make older JSON deserialize into something different.
+pub const IR_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct EnvelopeRef<'a> {
+    version: u32,
+    document: &'a Document,
+}
+
+/// Only the version is read first, so a document from another release is
+/// reported as such rather than as whatever field no longer matches.
+#[derive(Deserialize)]
+struct VersionProbe {
+    version: u32,
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    document: Document,
+}
+
+impl Document {
+    /// Serialize the document as JSON, tagged with [`IR_VERSION`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConvertError::Render`] if serialization fails.
+    pub fn to_json(&self) -> Result<String, ConvertError> {
+        serde_json::to_string(&EnvelopeRef {
+            version: IR_VERSION,
+            document: self,
+        })
+        .map_err(|e| ConvertError::Render(format!("failed to serialize IR: {e}")))
+    }
+
+    /// Read a document written by [`Document::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConvertError::Parse`] if the JSON is malformed or was
+    /// written for another [`IR_VERSION`].
+    pub fn from_json(json: &str) -> Result<Self, ConvertError> {
+        let probe: VersionProbe = serde_json::from_str(json)
+            .map_err(|e| ConvertError::Parse(format!("invalid IR JSON: {e}")))?;
+        if probe.version != IR_VERSION {
+            return Err(ConvertError::Parse(format!(
+                "IR JSON version {} is not supported (expected {IR_VERSION})",
+                probe.version
+            )));
+        }
+        let envelope: Envelope = serde_json::from_str(json)
+            .map_err(|e| ConvertError::Parse(format!("invalid IR JSON: {e}")))?;
+        Ok(envelope.document)
+    }
+}
+
+#[cfg(test)]
+#[path = "json_tests.rs"]
+mod tests;
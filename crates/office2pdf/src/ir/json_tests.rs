@@ -0,0 +1,67 @@
+use super::*;
+use crate::ir::{
+    Block, FlowPage, Margins, Metadata, Page, PageSize, Paragraph, ParagraphStyle, Run, StyleSheet,
+    TextStyle,
+};
+
+fn sample_document() -> Document {
+    Document {
+        metadata: Metadata {
+            title: Some("Report".to_string()),
+            ..Metadata::default()
+        },
+        pages: vec![Page::Flow(FlowPage {
+            size: PageSize::default(),
+            margins: Margins::default(),
+            content: vec![Block::Paragraph(Paragraph {
+                style: ParagraphStyle::default(),
+                runs: vec![Run {
+                    text: "Hello".to_string(),
+                    style: TextStyle {
+                        bold: Some(true),
+                        ..TextStyle::default()
+                    },
+                    href: None,
+                    footnote: None,
+                }],
+            })],
+            header: None,
+            footer: None,
+            columns: None,
+            line_grid_pitch: None,
+        })],
+        styles: StyleSheet::default(),
+    }
+}
+
+#[test]
+fn test_json_round_trip() {
+    let json: String = sample_document().to_json().unwrap();
+    assert!(json.starts_with(&format!("{{\"version\":{IR_VERSION},")));
+
+    let restored: Document = Document::from_json(&json).unwrap();
+    assert_eq!(restored.metadata.title.as_deref(), Some("Report"));
+    let [Page::Flow(page)] = restored.pages.as_slice() else {
+        panic!("expected one flow page");
+    };
+    let [Block::Paragraph(paragraph)] = page.content.as_slice() else {
+        panic!("expected one paragraph");
+    };
+    assert_eq!(paragraph.runs[0].text, "Hello");
+    assert_eq!(paragraph.runs[0].style.bold, Some(true));
+    assert_eq!(restored.to_json().unwrap(), json);
+}
+
+#[test]
+fn test_from_json_rejects_other_versions() {
+    let json: String = sample_document().to_json().unwrap().replacen(
+        &format!("\"version\":{IR_VERSION}"),
+        &format!("\"version\":{}", IR_VERSION + 1),
+        1,
+    );
+    let error: String = Document::from_json(&json).unwrap_err().to_string();
+    assert!(error.contains("version"), "{error}");
+
+    assert!(Document::from_json("{\"document\":{}}").is_err());
+    assert!(Document::from_json("not json").is_err());
+}
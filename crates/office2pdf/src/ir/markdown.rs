@@ -0,0 +1,430 @@
+//! Plain text and Markdown renderings of the IR, for search indexes and
+//! LLM input where a PDF is not needed. Both walk the document in reading
+//! order like [`Document::text_segments`]; headers and footers are left out
+//! because they repeat on every page and carry no content of their own.
+
+use super::document::{Document, FixedElementKind, Page};
+use super::elements::{
+    Block, List, ListKind, Paragraph, Run, SmartArt, Table, page_link_target, sheet_link_target,
+};
+
+impl Document {
+    /// The document's text with paragraphs separated by blank lines, list
+    /// items and table rows on lines of their own, and table cells separated
+    /// by tabs. Footnotes follow the text, numbered as they are referenced.
+    pub fn to_plain_text(&self) -> String {
+        TextWriter::new(false).write(self)
+    }
+
+    /// The document as GitHub-flavored Markdown: headings, emphasis, lists,
+    /// tables, links and footnotes. Each sheet opens with its name as a
+    /// heading and slides are separated by thematic breaks. Images, shapes
+    /// and equations are omitted.
+    pub fn to_markdown(&self) -> String {
+        TextWriter::new(true).write(self)
+    }
+}
+
+struct TextWriter {
+    markdown: bool,
+    /// Finished blocks, joined with blank lines at the end.
+    blocks: Vec<String>,
+    footnotes: Vec<String>,
+}
+
+impl TextWriter {
+    fn new(markdown: bool) -> Self {
+        Self {
+            markdown,
+            blocks: Vec::new(),
+            footnotes: Vec::new(),
+        }
+    }
+
+    fn write(mut self, doc: &Document) -> String {
+        let mut previous_was_slide = false;
+        for page in &doc.pages {
+            match page {
+                Page::Flow(flow) => self.push_blocks(&flow.content),
+                Page::Fixed(fixed) => {
+                    if previous_was_slide && self.markdown {
+                        self.blocks.push("---".to_string());
+                    }
+                    for element in &fixed.elements {
+                        match &element.kind {
+                            FixedElementKind::TextBox(text_box) => {
+                                self.push_blocks(&text_box.content);
+                            }
+                            FixedElementKind::Table(table) => self.push_table(table),
+                            FixedElementKind::SmartArt(smartart) => self.push_smartart(smartart),
+                            FixedElementKind::Chart(chart) => {
+                                if let Some(title) = &chart.title {
+                                    self.push_text(title);
+                                }
+                            }
+                            FixedElementKind::Image(_) | FixedElementKind::Shape(_) => {}
+                        }
+                    }
+                }
+                Page::Sheet(sheet) => {
+                    let name: String = if self.markdown {
+                        format!("## {}", escape(&sheet.name, false))
+                    } else {
+                        sheet.name.clone()
+                    };
+                    self.blocks.push(name);
+                    self.push_table(&sheet.table);
+                    for text_box in &sheet.text_boxes {
+                        for paragraph in &text_box.paragraphs {
+                            self.push_paragraph(paragraph);
+                        }
+                    }
+                    for (_, chart) in &sheet.charts {
+                        if let Some(title) = &chart.title {
+                            self.push_text(title);
+                        }
+                    }
+                }
+            }
+            previous_was_slide = matches!(page, Page::Fixed(_));
+        }
+
+        let footnotes: Vec<String> = std::mem::take(&mut self.footnotes);
+        for (index, footnote) in footnotes.iter().enumerate() {
+            let number: usize = index + 1;
+            self.blocks.push(if self.markdown {
+                format!("[^{number}]: {}", escape(footnote, false))
+            } else {
+                format!("[{number}] {footnote}")
+            });
+        }
+
+        let mut out: String = self.blocks.join("\n\n");
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if !text.trim().is_empty() {
+            self.blocks.push(if self.markdown {
+                escape(text, false)
+            } else {
+                text.to_string()
+            });
+        }
+    }
+
+    fn push_blocks(&mut self, blocks: &[Block]) {
+        for block in blocks {
+            match block {
+                Block::Paragraph(paragraph) => self.push_paragraph(paragraph),
+                Block::Table(table) => self.push_table(table),
+                Block::List(list) => self.push_list(list),
+                Block::FloatingTextBox(text_box) => self.push_blocks(&text_box.content),
+                Block::Chart(chart) => {
+                    if let Some(title) = &chart.title {
+                        self.push_text(title);
+                    }
+                }
+                Block::Image(_)
+                | Block::InlineImages(_)
+                | Block::FloatingImage(_)
+                | Block::FloatingShape(_)
+                | Block::MathEquation(_)
+                | Block::PageBreak
+                | Block::ColumnBreak => {}
+            }
+        }
+    }
+
+    fn push_paragraph(&mut self, paragraph: &Paragraph) {
+        let text: String = self.inline(&paragraph.runs, false);
+        if text.trim().is_empty() {
+            return;
+        }
+        match paragraph.style.heading_level {
+            Some(level) if self.markdown => {
+                let hashes: String = "#".repeat(usize::from(level.clamp(1, 6)));
+                self.blocks.push(format!("{hashes} {text}"));
+            }
+            _ => self.blocks.push(text),
+        }
+    }
+
+    fn push_list(&mut self, list: &List) {
+        // Ordered numbering restarts whenever a shallower item ends a level.
+        let mut counters: Vec<u32> = Vec::new();
+        let mut lines: Vec<String> = Vec::new();
+        for item in &list.items {
+            let depth: usize = item.level as usize;
+            counters.truncate(depth + 1);
+            counters.resize(depth + 1, 0);
+            counters[depth] = match item.start_at {
+                Some(start) => start,
+                None => counters[depth] + 1,
+            };
+            let kind: &ListKind = list
+                .level_styles
+                .get(&item.level)
+                .map_or(&list.kind, |style| &style.kind);
+            let marker: String = match kind {
+                ListKind::Ordered => format!("{}.", counters[depth]),
+                ListKind::Unordered if self.markdown => "-".to_string(),
+                ListKind::Unordered => "•".to_string(),
+            };
+            let text: String = item
+                .content
+                .iter()
+                .map(|paragraph| self.inline(&paragraph.runs, false))
+                .filter(|text| !text.trim().is_empty())
+                .collect::<Vec<String>>()
+                .join(" ");
+            // Four spaces nest under any marker up to "99.", as CommonMark
+            // requires a child to start past its parent's marker.
+            let indent: String = (if self.markdown { "    " } else { "  " }).repeat(depth);
+            lines.push(format!("{indent}{marker} {text}").trim_end().to_string());
+        }
+        if !lines.is_empty() {
+            self.blocks.push(lines.join("\n"));
+        }
+    }
+
+    fn push_smartart(&mut self, smartart: &SmartArt) {
+        let lines: Vec<String> = smartart
+            .items
+            .iter()
+            .filter(|node| !node.text.trim().is_empty())
+            .map(|node| {
+                let indent: String = "  ".repeat(node.depth);
+                let text: String = if self.markdown {
+                    escape(&node.text, false)
+                } else {
+                    node.text.clone()
+                };
+                let marker: &str = if self.markdown { "-" } else { "•" };
+                format!("{indent}{marker} {text}")
+            })
+            .collect();
+        if !lines.is_empty() {
+            self.blocks.push(lines.join("\n"));
+        }
+    }
+
+    fn push_table(&mut self, table: &Table) {
+        let grid: Vec<Vec<String>> = self.table_grid(table);
+        let columns: usize = grid.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 || grid.iter().flatten().all(|cell| cell.is_empty()) {
+            return;
+        }
+        let mut lines: Vec<String> = Vec::with_capacity(grid.len() + 1);
+        for (index, row) in grid.iter().enumerate() {
+            let mut cells: Vec<&str> = row.iter().map(String::as_str).collect();
+            cells.resize(columns, "");
+            if self.markdown {
+                lines.push(format!("| {} |", cells.join(" | ")));
+                // GFM tables need a header row; the first row serves as one
+                // even when the source marks none.
+                if index == 0 {
+                    lines.push(format!("|{}", " --- |".repeat(columns)));
+                }
+            } else {
+                lines.push(cells.join("\t").trim_end().to_string());
+            }
+        }
+        self.blocks.push(lines.join("\n"));
+    }
+
+    /// The table's cell texts laid out on its grid: a cell spanning several
+    /// columns or rows is written once and the positions it covers stay
+    /// empty, so columns line up.
+    fn table_grid(&mut self, table: &Table) -> Vec<Vec<String>> {
+        let mut grid: Vec<Vec<String>> = Vec::with_capacity(table.rows.len());
+        // Rows still covered by a row-spanning cell, per column.
+        let mut covered: Vec<u32> = Vec::new();
+        for row in &table.rows {
+            let mut texts: Vec<String> = Vec::new();
+            let mut cells = row.cells.iter();
+            let mut col: usize = 0;
+            loop {
+                if covered.get(col).is_some_and(|rows| *rows > 0) {
+                    covered[col] -= 1;
+                    texts.push(String::new());
+                    col += 1;
+                    continue;
+                }
+                let Some(cell) = cells.next() else {
+                    break;
+                };
+                let span: usize = cell.col_span.max(1) as usize;
+                texts.push(self.cell_text(&cell.content));
+                texts.resize(texts.len() + span - 1, String::new());
+                if covered.len() < col + span {
+                    covered.resize(col + span, 0);
+                }
+                for rows in &mut covered[col..col + span] {
+                    *rows = cell.row_span.saturating_sub(1);
+                }
+                col += span;
+            }
+            // Covered columns past the row's last cell still take a row.
+            for rows in covered.iter_mut().skip(col) {
+                *rows = rows.saturating_sub(1);
+            }
+            grid.push(texts);
+        }
+        grid
+    }
+
+    /// A cell's content on one line: GFM table cells cannot hold blocks.
+    fn cell_text(&mut self, blocks: &[Block]) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        self.collect_cell_text(blocks, &mut parts);
+        parts.join(if self.markdown { "<br>" } else { " " })
+    }
+
+    fn collect_cell_text(&mut self, blocks: &[Block], parts: &mut Vec<String>) {
+        for block in blocks {
+            match block {
+                Block::Paragraph(paragraph) => parts.push(self.inline(&paragraph.runs, true)),
+                Block::List(list) => {
+                    for item in &list.items {
+                        for paragraph in &item.content {
+                            parts.push(self.inline(&paragraph.runs, true));
+                        }
+                    }
+                }
+                Block::Table(table) => {
+                    for row in &table.rows {
+                        for cell in &row.cells {
+                            self.collect_cell_text(&cell.content, parts);
+                        }
+                    }
+                }
+                Block::FloatingTextBox(text_box) => {
+                    self.collect_cell_text(&text_box.content, parts);
+                }
+                _ => {}
+            }
+        }
+        parts.retain(|part| !part.trim().is_empty());
+    }
+
+    /// One paragraph's runs as a line of text. Adjacent runs with the same
+    /// emphasis and link are merged so Markdown markers don't pile up.
+    fn inline(&mut self, runs: &[Run], in_table: bool) -> String {
+        let mut out: String = String::new();
+        let mut index: usize = 0;
+        while index < runs.len() {
+            let key: RunKey = RunKey::of(&runs[index]);
+            let mut text: String = String::new();
+            let mut footnote_refs: String = String::new();
+            while index < runs.len() && RunKey::of(&runs[index]) == key {
+                let run: &Run = &runs[index];
+                if run.style.all_caps == Some(true) {
+                    text.push_str(&run.text.to_uppercase());
+                } else {
+                    text.push_str(&run.text);
+                }
+                if let Some(footnote) = &run.footnote {
+                    self.footnotes.push(footnote.clone());
+                    let number: usize = self.footnotes.len();
+                    footnote_refs.push_str(&if self.markdown {
+                        format!("[^{number}]")
+                    } else {
+                        format!("[{number}]")
+                    });
+                }
+                index += 1;
+            }
+            if self.markdown {
+                out.push_str(&key.wrap(&escape(&text, in_table)));
+            } else {
+                out.push_str(&text.replace('\n', if in_table { " " } else { "\n" }));
+            }
+            out.push_str(&footnote_refs);
+        }
+        out
+    }
+}
+
+/// The formatting of a run that Markdown can express.
+#[derive(PartialEq)]
+struct RunKey<'a> {
+    bold: bool,
+    italic: bool,
+    strikethrough: bool,
+    /// External link target; in-document page and sheet links have no
+    /// meaning outside the PDF.
+    href: Option<&'a str>,
+}
+
+impl<'a> RunKey<'a> {
+    fn of(run: &'a Run) -> Self {
+        Self {
+            bold: run.style.bold == Some(true),
+            italic: run.style.italic == Some(true),
+            strikethrough: run.style.strikethrough == Some(true),
+            href: run.href.as_deref().filter(|href| {
+                page_link_target(href).is_none() && sheet_link_target(href).is_none()
+            }),
+        }
+    }
+
+    /// Wrap escaped text in this key's markers. Markers go around the text
+    /// without its surrounding whitespace, as CommonMark requires.
+    fn wrap(&self, text: &str) -> String {
+        let trimmed: &str = text.trim();
+        if trimmed.is_empty() {
+            return text.to_string();
+        }
+        let start: usize = text.len() - text.trim_start().len();
+        let (leading, rest) = text.split_at(start);
+        let trailing: &str = &rest[trimmed.len()..];
+
+        let mut inner: String = trimmed.to_string();
+        if self.strikethrough {
+            inner = format!("~~{inner}~~");
+        }
+        if self.italic {
+            inner = format!("_{inner}_");
+        }
+        if self.bold {
+            inner = format!("**{inner}**");
+        }
+        if let Some(href) = self.href {
+            inner = format!("[{inner}](<{}>)", href.replace('>', "%3E"));
+        }
+        format!("{leading}{inner}{trailing}")
+    }
+}
+
+/// Escape the characters Markdown would read as markup. Line breaks become
+/// hard breaks, or `<br>` inside a table cell.
+fn escape(text: &str, in_table: bool) -> String {
+    let mut out: String = String::with_capacity(text.len());
+    let mut line_start: bool = true;
+    for ch in text.chars() {
+        match ch {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '~' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '|' if in_table => out.push_str("\\|"),
+            '#' | '-' | '+' if line_start => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '\n' if in_table => out.push_str("<br>"),
+            '\n' => out.push_str("\\\n"),
+            _ => out.push(ch),
+        }
+        line_start = ch == '\n' || (line_start && ch == ' ');
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "markdown_tests.rs"]
+mod tests;
@@ -0,0 +1,242 @@
+use crate::ir::*;
+
+fn run(text: &str) -> Run {
+    Run {
+        text: text.to_string(),
+        style: TextStyle::default(),
+        href: None,
+        footnote: None,
+    }
+}
+
+fn styled_run(text: &str, style: TextStyle) -> Run {
+    Run { style, ..run(text) }
+}
+
+fn paragraph(runs: Vec<Run>) -> Paragraph {
+    Paragraph {
+        style: ParagraphStyle::default(),
+        runs,
+    }
+}
+
+fn cell(text: &str) -> TableCell {
+    TableCell {
+        content: vec![Block::Paragraph(paragraph(vec![run(text)]))],
+        ..TableCell::default()
+    }
+}
+
+fn flow_document(content: Vec<Block>) -> Document {
+    Document {
+        metadata: Metadata::default(),
+        pages: vec![Page::Flow(FlowPage {
+            size: PageSize::default(),
+            margins: Margins::default(),
+            content,
+            header: None,
+            footer: None,
+            columns: None,
+            line_grid_pitch: None,
+        })],
+        styles: StyleSheet::default(),
+    }
+}
+
+fn sample_document() -> Document {
+    let heading = Paragraph {
+        style: ParagraphStyle {
+            heading_level: Some(2),
+            ..ParagraphStyle::default()
+        },
+        runs: vec![run("Results")],
+    };
+    let mut link = run("site");
+    link.href = Some("https://example.com".to_string());
+    let mut internal_link = run("page 3");
+    internal_link.href = Some(page_link(3));
+    let mut claim = run("Claim");
+    claim.footnote = Some("Source note".to_string());
+    let body = paragraph(vec![
+        run("See the "),
+        styled_run(
+            "full ",
+            TextStyle {
+                bold: Some(true),
+                ..TextStyle::default()
+            },
+        ),
+        styled_run(
+            "report",
+            TextStyle {
+                bold: Some(true),
+                ..TextStyle::default()
+            },
+        ),
+        run(" on our "),
+        link,
+        run(", "),
+        internal_link,
+        run(" and 2*3. "),
+        claim,
+    ]);
+    let list = List {
+        kind: ListKind::Ordered,
+        items: vec![
+            ListItem {
+                content: vec![paragraph(vec![run("First")])],
+                level: 0,
+                start_at: None,
+            },
+            ListItem {
+                content: vec![paragraph(vec![run("Nested")])],
+                level: 1,
+                start_at: None,
+            },
+            ListItem {
+                content: vec![paragraph(vec![run("Second")])],
+                level: 0,
+                start_at: None,
+            },
+        ],
+        level_styles: std::collections::BTreeMap::from([(
+            1,
+            ListLevelStyle {
+                kind: ListKind::Unordered,
+                numbering_pattern: None,
+                full_numbering: false,
+                marker_text: None,
+                marker_style: None,
+            },
+        )]),
+    };
+    let table = Table {
+        rows: vec![
+            TableRow {
+                cells: vec![cell("Name"), cell("Qty")],
+                height: None,
+            },
+            TableRow {
+                cells: vec![cell("a|b"), cell("3")],
+                height: None,
+            },
+        ],
+        ..Table::default()
+    };
+    flow_document(vec![
+        Block::Paragraph(heading),
+        Block::Paragraph(body),
+        Block::List(list),
+        Block::Table(table),
+    ])
+}
+
+#[test]
+fn test_to_markdown_renders_structure() {
+    assert_eq!(
+        sample_document().to_markdown(),
+        "## Results\n\n\
+         See the **full report** on our [site](<https://example.com>), page 3 and 2\\*3. Claim[^1]\n\n\
+         1. First\n    - Nested\n2. Second\n\n\
+         | Name | Qty |\n| --- | --- |\n| a\\|b | 3 |\n\n\
+         [^1]: Source note\n"
+    );
+}
+
+#[test]
+fn test_to_plain_text_drops_markup() {
+    assert_eq!(
+        sample_document().to_plain_text(),
+        "Results\n\n\
+         See the full report on our site, page 3 and 2*3. Claim[1]\n\n\
+         1. First\n  • Nested\n2. Second\n\n\
+         Name\tQty\na|b\t3\n\n\
+         [1] Source note\n"
+    );
+}
+
+#[test]
+fn test_table_spans_keep_columns_aligned() {
+    let table = Table {
+        rows: vec![
+            TableRow {
+                cells: vec![
+                    TableCell {
+                        row_span: 2,
+                        ..cell("Tall")
+                    },
+                    TableCell {
+                        col_span: 2,
+                        ..cell("Wide")
+                    },
+                ],
+                height: None,
+            },
+            TableRow {
+                cells: vec![cell("B2"), cell("C2")],
+                height: None,
+            },
+        ],
+        ..Table::default()
+    };
+    let doc = flow_document(vec![Block::Table(table)]);
+    assert_eq!(doc.to_plain_text(), "Tall\tWide\n\tB2\tC2\n");
+}
+
+#[test]
+fn test_sheets_are_headed_and_slides_separated() {
+    let sheet = Page::Sheet(SheetPage {
+        name: "Q1".to_string(),
+        size: PageSize::default(),
+        margins: Margins::default(),
+        table: Table {
+            rows: vec![TableRow {
+                cells: vec![cell("Total"), cell("42")],
+                height: None,
+            }],
+            ..Table::default()
+        },
+        header: None,
+        footer: None,
+        charts: Vec::new(),
+        images: Vec::new(),
+        text_boxes: Vec::new(),
+    });
+    let slide = |text: &str| {
+        Page::Fixed(FixedPage {
+            size: PageSize::default(),
+            elements: vec![FixedElement {
+                x: 0.0,
+                y: 0.0,
+                width: 100.0,
+                height: 20.0,
+                kind: FixedElementKind::TextBox(TextBoxData {
+                    content: vec![Block::Paragraph(paragraph(vec![run(text)]))],
+                    padding: Insets::default(),
+                    vertical_align: TextBoxVerticalAlign::Top,
+                    fill: None,
+                    opacity: None,
+                    stroke: None,
+                    shape_kind: None,
+                    no_wrap: false,
+                    auto_fit: false,
+                    text_rotation_deg: None,
+                }),
+                href: None,
+            }],
+            background_color: None,
+            background_gradient: None,
+            background_image: None,
+            source_slide: None,
+        })
+    };
+    let doc = Document {
+        metadata: Metadata::default(),
+        pages: vec![sheet, slide("One"), slide("Two")],
+        styles: StyleSheet::default(),
+    };
+    assert_eq!(
+        doc.to_markdown(),
+        "## Q1\n\n| Total | 42 |\n| --- | --- |\n\nOne\n\n---\n\nTwo\n"
+    );
+}
@@ -1,9 +1,15 @@
 mod document;
 mod elements;
+#[cfg(feature = "serde")]
+mod json;
+mod markdown;
 mod style;
+mod text;
 
 pub use document::*;
 pub use elements::*;
+#[cfg(feature = "serde")]
+pub use json::IR_VERSION;
 pub use style::*;
 
 /// Common interface for elements with position and dimensions.
@@ -15,7 +15,9 @@ fn fixed_element_positioned_returns_direct_fields() {
             rotation_deg: None,
             opacity: None,
             shadow: None,
+            effects: ShapeEffects::default(),
         }),
+        href: None,
     };
     assert!((elem.x() - 10.5).abs() < f64::EPSILON);
     assert!((elem.y() - 20.0).abs() < f64::EPSILON);
@@ -104,7 +106,9 @@ fn positioned_trait_works_through_dyn_dispatch() {
             rotation_deg: None,
             opacity: None,
             shadow: None,
+            effects: ShapeEffects::default(),
         }),
+        href: None,
     };
     let ftb = FloatingTextBox {
         content: vec![],
@@ -138,7 +142,9 @@ fn fixed_element_positioned_with_zero_dimensions() {
             rotation_deg: None,
             opacity: None,
             shadow: None,
+            effects: ShapeEffects::default(),
         }),
+        href: None,
     };
     assert!(elem.x().abs() < f64::EPSILON);
     assert!(elem.y().abs() < f64::EPSILON);
@@ -1,5 +1,6 @@
 /// Collection of named styles in the document.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StyleSheet {
     pub styles: Vec<NamedStyle>,
     /// Document default tab stop interval in points (`w:defaultTabStop`
@@ -10,6 +11,7 @@ pub struct StyleSheet {
 
 /// A named style that can be referenced by paragraphs/runs.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NamedStyle {
     pub id: String,
     pub name: String,
@@ -19,6 +21,7 @@ pub struct NamedStyle {
 
 /// Paragraph-level formatting.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParagraphStyle {
     pub alignment: Option<Alignment>,
     pub indent_left: Option<f64>,
@@ -51,6 +54,7 @@ pub struct ParagraphStyle {
 
 /// A custom tab stop definition.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TabStop {
     /// Position in points from the left margin.
     pub position: f64,
@@ -62,6 +66,7 @@ pub struct TabStop {
 
 /// Tab stop alignment type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TabAlignment {
     #[default]
     Left,
@@ -72,6 +77,7 @@ pub enum TabAlignment {
 
 /// Leader character for a tab stop.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TabLeader {
     #[default]
     None,
@@ -82,6 +88,7 @@ pub enum TabLeader {
 
 /// Text direction for bidirectional (BiDi) rendering.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextDirection {
     /// Left-to-right (default for Latin, CJK scripts).
     Ltr,
@@ -91,6 +98,7 @@ pub enum TextDirection {
 
 /// Text alignment.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Alignment {
     Left,
     Center,
@@ -100,6 +108,7 @@ pub enum Alignment {
 
 /// Line spacing specification.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineSpacing {
     /// Multiplier (e.g. 1.0 = single, 1.5, 2.0 = double).
     Proportional(f64),
@@ -109,6 +118,7 @@ pub enum LineSpacing {
 
 /// Font-relative line box metrics.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineBox {
     /// Distance above the baseline, in em units.
     pub ascent_em: f64,
@@ -118,6 +128,7 @@ pub struct LineBox {
 
 /// Vertical alignment for superscript/subscript text.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VerticalTextAlign {
     Superscript,
     Subscript,
@@ -125,6 +136,7 @@ pub enum VerticalTextAlign {
 
 /// Character-level formatting.
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextStyle {
     pub font_family: Option<String>,
     pub font_size: Option<f64>,
@@ -143,6 +155,22 @@ pub struct TextStyle {
     pub small_caps: Option<bool>,
     /// Character spacing (letter spacing / tracking) in points.
     pub letter_spacing: Option<f64>,
+    /// Stroke around the glyph outlines (PPTX `<a:rPr><a:ln>`).
+    pub outline: Option<TextOutline>,
+    /// Drop shadow behind the glyphs (PPTX `<a:rPr><a:effectLst><a:outerShdw>`).
+    pub shadow: Option<super::elements::Shadow>,
+    /// BCP 47 tag of a run written in another language than the document's
+    /// [`Metadata::language`](super::Metadata::language) (PPTX `<a:rPr lang>`).
+    pub language: Option<String>,
+}
+
+/// Outline stroke drawn around text glyphs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextOutline {
+    pub color: Color,
+    /// Stroke width in points.
+    pub width: f64,
 }
 
 impl TextStyle {
@@ -186,6 +214,15 @@ impl TextStyle {
         if other.letter_spacing.is_some() {
             self.letter_spacing = other.letter_spacing;
         }
+        if other.outline.is_some() {
+            self.outline = other.outline;
+        }
+        if other.shadow.is_some() {
+            self.shadow = other.shadow.clone();
+        }
+        if other.language.is_some() {
+            self.language = other.language.clone();
+        }
     }
 }
 
@@ -238,6 +275,7 @@ impl ParagraphStyle {
 
 /// RGB color.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -75,6 +75,9 @@ fn text_style_merge_from_all_none_source_preserves_target() {
         all_caps: Some(true),
         small_caps: Some(false),
         letter_spacing: Some(1.5),
+        outline: None,
+        shadow: None,
+        language: None,
     };
     let original: TextStyle = target.clone();
     let source = TextStyle::default();
@@ -99,6 +102,9 @@ fn text_style_merge_from_all_some_source_overwrites_target() {
         all_caps: Some(true),
         small_caps: Some(true),
         letter_spacing: Some(1.5),
+        outline: None,
+        shadow: None,
+        language: Some("en-US".to_string()),
     };
     let source = TextStyle {
         font_family: Some("Times".to_string()),
@@ -113,6 +119,9 @@ fn text_style_merge_from_all_some_source_overwrites_target() {
         all_caps: Some(false),
         small_caps: Some(false),
         letter_spacing: Some(3.0),
+        outline: None,
+        shadow: None,
+        language: Some("ko-KR".to_string()),
     };
 
     target.merge_from(&source);
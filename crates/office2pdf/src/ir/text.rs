@@ -0,0 +1,137 @@
+use super::document::{Document, FixedElementKind, Page};
+use super::elements::{Block, HFInline, HeaderFooter, Paragraph, Table};
+
+impl Document {
+    /// Visible text of the document, one segment per paragraph, list item,
+    /// table cell paragraph, diagram node, or chart title, in reading order.
+    ///
+    /// Footnote bodies become their own segments; header and footer
+    /// paragraphs are split at page-number fields, and whitespace-only
+    /// segments are skipped.
+    pub fn text_segments(&self) -> Vec<String> {
+        let mut segments: Vec<String> = Vec::new();
+        for page in &self.pages {
+            match page {
+                Page::Flow(flow) => {
+                    push_header_footer(&mut segments, flow.header.as_ref());
+                    push_blocks(&mut segments, &flow.content);
+                    push_header_footer(&mut segments, flow.footer.as_ref());
+                }
+                Page::Fixed(fixed) => {
+                    for element in &fixed.elements {
+                        match &element.kind {
+                            FixedElementKind::TextBox(text_box) => {
+                                push_blocks(&mut segments, &text_box.content);
+                            }
+                            FixedElementKind::Table(table) => push_table(&mut segments, table),
+                            FixedElementKind::SmartArt(smartart) => {
+                                for node in &smartart.items {
+                                    push_segment(&mut segments, node.text.clone());
+                                }
+                            }
+                            FixedElementKind::Chart(chart) => {
+                                if let Some(title) = &chart.title {
+                                    push_segment(&mut segments, title.clone());
+                                }
+                            }
+                            FixedElementKind::Image(_) | FixedElementKind::Shape(_) => {}
+                        }
+                    }
+                }
+                Page::Sheet(sheet) => {
+                    push_header_footer(&mut segments, sheet.header.as_ref());
+                    push_table(&mut segments, &sheet.table);
+                    for text_box in &sheet.text_boxes {
+                        for paragraph in &text_box.paragraphs {
+                            push_paragraph(&mut segments, paragraph);
+                        }
+                    }
+                    for (_, chart) in &sheet.charts {
+                        if let Some(title) = &chart.title {
+                            push_segment(&mut segments, title.clone());
+                        }
+                    }
+                    push_header_footer(&mut segments, sheet.footer.as_ref());
+                }
+            }
+        }
+        segments
+    }
+}
+
+fn push_segment(segments: &mut Vec<String>, text: String) {
+    if !text.trim().is_empty() {
+        segments.push(text);
+    }
+}
+
+fn push_paragraph(segments: &mut Vec<String>, paragraph: &Paragraph) {
+    let text: String = paragraph.runs.iter().map(|run| run.text.as_str()).collect();
+    push_segment(segments, text);
+    for footnote in paragraph
+        .runs
+        .iter()
+        .filter_map(|run| run.footnote.as_ref())
+    {
+        push_segment(segments, footnote.clone());
+    }
+}
+
+fn push_table(segments: &mut Vec<String>, table: &Table) {
+    for row in &table.rows {
+        for cell in &row.cells {
+            push_blocks(segments, &cell.content);
+        }
+    }
+}
+
+fn push_blocks(segments: &mut Vec<String>, blocks: &[Block]) {
+    for block in blocks {
+        match block {
+            Block::Paragraph(paragraph) => push_paragraph(segments, paragraph),
+            Block::Table(table) => push_table(segments, table),
+            Block::List(list) => {
+                for item in &list.items {
+                    for paragraph in &item.content {
+                        push_paragraph(segments, paragraph);
+                    }
+                }
+            }
+            Block::FloatingTextBox(text_box) => push_blocks(segments, &text_box.content),
+            Block::Chart(chart) => {
+                if let Some(title) = &chart.title {
+                    push_segment(segments, title.clone());
+                }
+            }
+            Block::Image(_)
+            | Block::InlineImages(_)
+            | Block::FloatingImage(_)
+            | Block::FloatingShape(_)
+            | Block::MathEquation(_)
+            | Block::PageBreak
+            | Block::ColumnBreak => {}
+        }
+    }
+}
+
+fn push_header_footer(segments: &mut Vec<String>, header_footer: Option<&HeaderFooter>) {
+    let Some(header_footer) = header_footer else {
+        return;
+    };
+    // Fields split the paragraph: "Page 3 of 9" yields "Page " and " of "
+    // because the field values only exist once the PDF is laid out.
+    for paragraph in &header_footer.paragraphs {
+        let mut text: String = String::new();
+        for element in &paragraph.elements {
+            match element {
+                HFInline::Run(run) => text.push_str(&run.text),
+                _ => push_segment(segments, std::mem::take(&mut text)),
+            }
+        }
+        push_segment(segments, text);
+    }
+}
+
+#[cfg(test)]
+#[path = "text_tests.rs"]
+mod tests;
@@ -0,0 +1,103 @@
+use crate::ir::*;
+
+fn run(text: &str) -> Run {
+    Run {
+        text: text.to_string(),
+        style: TextStyle::default(),
+        href: None,
+        footnote: None,
+    }
+}
+
+fn paragraph(runs: Vec<Run>) -> Paragraph {
+    Paragraph {
+        style: ParagraphStyle::default(),
+        runs,
+    }
+}
+
+fn flow_document(content: Vec<Block>, footer: Option<HeaderFooter>) -> Document {
+    Document {
+        metadata: Metadata::default(),
+        pages: vec![Page::Flow(FlowPage {
+            size: PageSize::default(),
+            margins: Margins::default(),
+            content,
+            header: None,
+            footer,
+            columns: None,
+            line_grid_pitch: None,
+        })],
+        styles: StyleSheet::default(),
+    }
+}
+
+#[test]
+fn test_text_segments_joins_runs_and_skips_blank_paragraphs() {
+    let doc = flow_document(
+        vec![
+            Block::Paragraph(paragraph(vec![run("Hello, "), run("world")])),
+            Block::Paragraph(paragraph(vec![run("   ")])),
+            Block::PageBreak,
+            Block::Paragraph(paragraph(vec![run("Second page")])),
+        ],
+        None,
+    );
+    assert_eq!(doc.text_segments(), vec!["Hello, world", "Second page"]);
+}
+
+#[test]
+fn test_text_segments_walks_tables_lists_and_footnotes() {
+    let mut footnoted = run("Claim");
+    footnoted.footnote = Some("Source note".to_string());
+    let table = Table {
+        rows: vec![TableRow {
+            cells: vec![
+                TableCell {
+                    content: vec![Block::Paragraph(paragraph(vec![run("A1")]))],
+                    ..TableCell::default()
+                },
+                TableCell {
+                    content: vec![Block::Paragraph(paragraph(vec![run("B1")]))],
+                    ..TableCell::default()
+                },
+            ],
+            height: None,
+        }],
+        ..Table::default()
+    };
+    let list = List {
+        kind: ListKind::Unordered,
+        items: vec![ListItem {
+            content: vec![paragraph(vec![footnoted])],
+            level: 0,
+            start_at: None,
+        }],
+        level_styles: Default::default(),
+    };
+    let doc = flow_document(vec![Block::Table(table), Block::List(list)], None);
+    assert_eq!(
+        doc.text_segments(),
+        vec!["A1", "B1", "Claim", "Source note"]
+    );
+}
+
+#[test]
+fn test_text_segments_splits_footer_at_page_fields() {
+    let footer = HeaderFooter {
+        paragraphs: vec![HeaderFooterParagraph {
+            style: ParagraphStyle::default(),
+            elements: vec![
+                HFInline::Run(run("Page ")),
+                HFInline::PageNumber,
+                HFInline::Run(run(" of ")),
+                HFInline::TotalPages,
+            ],
+            border: None,
+            frame: None,
+        }],
+        distance_from_edge: None,
+    };
+    let doc = flow_document(vec![], Some(footer));
+    assert_eq!(doc.text_segments(), vec!["Page ", " of "]);
+}
@@ -40,6 +40,8 @@
 pub mod config;
 pub(crate) mod defaults;
 pub mod error;
+#[cfg(feature = "pdf-ops")]
+pub mod fidelity;
 pub mod ir;
 pub(crate) mod parser;
 #[cfg(feature = "pdf-ops")]
@@ -61,13 +63,38 @@ pub mod internal {
     pub use crate::render::typst_gen::{TypstOutput, generate_typst};
 }
 
+pub use converter::Converter;
+pub use render::typst_gen::{ImageAsset, TypstOutput};
+
+#[cfg(feature = "tokio")]
+pub use tokio_util::sync::CancellationToken;
+
 use config::{ConvertOptions, Format};
 use error::{ConvertError, ConvertResult};
+#[cfg(feature = "tokio")]
+#[path = "lib_async.rs"]
+mod async_convert;
+#[cfg(feature = "pdf-ops")]
+#[path = "lib_consolidate.rs"]
+mod consolidate;
+#[path = "lib_converter.rs"]
+mod converter;
+#[path = "lib_info.rs"]
+mod info;
+#[path = "lib_limits.rs"]
+mod limits;
+#[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
+#[path = "lib_parallel.rs"]
+mod parallel;
 #[path = "lib_pipeline.rs"]
 mod pipeline;
+#[path = "lib_sanitize.rs"]
+mod sanitize;
 #[cfg(test)]
 #[path = "lib_test_support.rs"]
 pub(crate) mod test_support;
+#[path = "lib_trace.rs"]
+mod trace;
 
 #[cfg(test)]
 fn is_ole2(data: &[u8]) -> bool {
@@ -82,7 +109,9 @@ fn should_resolve_font_context(doc: &ir::Document, options: &ConvertOptions) ->
 
 /// Convert a file at the given path to PDF bytes with warnings.
 ///
-/// Detects the format from the file extension (`.docx`, `.pptx`, `.xlsx`).
+/// Accepts files with a `.docx`, `.pptx` or `.xlsx` extension. The format
+/// comes from the contents when [`Format::detect`] recognizes them, so a
+/// workbook saved as `.docx` still converts as a workbook.
 ///
 /// This function is not available on `wasm32` targets because it reads from the
 /// filesystem. Use [`convert_bytes`] for in-memory conversion on WASM.
@@ -135,6 +164,292 @@ pub fn convert_bytes(
     pipeline::convert_bytes(data, format, options)
 }
 
+/// Convert raw bytes of an unknown format to PDF bytes with warnings.
+///
+/// The format is detected with [`Format::detect`]; an encrypted file is
+/// decrypted with [`ConvertOptions::input_password`] first, since its
+/// format is only visible inside the package.
+///
+/// # Errors
+///
+/// Returns [`ConvertError::UnsupportedFormat`] if the bytes are not a
+/// DOCX, PPTX or XLSX package, naming the legacy format for `.doc`, `.xls`
+/// and `.ppt` files, and otherwise fails as [`convert_bytes`] does.
+pub fn convert_bytes_auto(
+    data: &[u8],
+    options: &ConvertOptions,
+) -> Result<ConvertResult, ConvertError> {
+    pipeline::convert_bytes_auto(data, options)
+}
+
+/// Parse raw bytes of a known format into the IR without rendering.
+///
+/// Use this to inspect or transform the [`ir::Document`] before handing it to
+/// [`generate_typst`] or [`render_document`]. The warnings are those
+/// [`convert_bytes`] would report for parsing; font fallback warnings only
+/// arise when rendering.
+///
+/// # Errors
+///
+/// Returns [`ConvertError::UnsupportedEncryption`] for encrypted files
+/// without [`ConvertOptions::input_password`] and
+/// [`ConvertError::Parse`] if the document cannot be parsed.
+pub fn parse_bytes(
+    data: &[u8],
+    format: Format,
+    options: &ConvertOptions,
+) -> Result<(ir::Document, Vec<error::ConvertWarning>), ConvertError> {
+    pipeline::parse_bytes(data, format, options)
+}
+
+/// Extract the text of a document as plain text and Markdown, without
+/// rendering a PDF.
+///
+/// Runs the same parser as [`convert_bytes`], so options such as
+/// `sheet_names`, `slide_range` and `transform` apply, but skips Typst
+/// compilation entirely. The Markdown keeps headings, emphasis, lists,
+/// tables, links and footnotes; see [`ir::Document::to_markdown`].
+///
+/// # Errors
+///
+/// Returns [`ConvertError::UnsupportedEncryption`] for encrypted files
+/// without [`ConvertOptions::input_password`] and
+/// [`ConvertError::Parse`] if the document cannot be parsed.
+pub fn extract_text(
+    data: &[u8],
+    format: Format,
+    options: &ConvertOptions,
+) -> Result<error::ExtractedText, ConvertError> {
+    pipeline::extract_text(data, format, options)
+}
+
+/// Generate the Typst source for an IR document without compiling it.
+///
+/// The output is what [`convert_bytes`] compiles: page size, landscape and
+/// other layout options apply, and requested font families are substituted
+/// from the fonts available. Compile it with your own Typst world, making
+/// the [`TypstOutput::images`] available at their paths and the fonts in
+/// `options.font_paths` discoverable.
+///
+/// # Errors
+///
+/// Returns [`ConvertError::Render`] if code generation fails.
+pub fn generate_typst(
+    doc: &ir::Document,
+    options: &ConvertOptions,
+) -> Result<TypstOutput, ConvertError> {
+    pipeline::generate_typst(doc, options)
+}
+
+/// Async [`convert_bytes`] that can be cancelled.
+///
+/// The conversion runs on Tokio's blocking pool. Cancelling `cancel`
+/// resolves the future with [`ConvertError::Cancelled`] right away. The
+/// background work, also when the future is dropped, stops at its next
+/// stage boundary (after parsing, before Typst compilation, before the
+/// next chunk when streaming, or after compilation), so an abandoned
+/// request never starts another compile.
+///
+/// A Typst compile cannot be interrupted: one already running keeps its
+/// blocking-pool thread busy until it returns, however long that takes.
+/// Streaming XLSX conversion compiles chunk by chunk and so notices
+/// cancellation sooner.
+///
+/// Requires the `tokio` feature and a Tokio runtime.
+///
+/// # Errors
+///
+/// Returns [`ConvertError::Cancelled`] if `cancel` fires before the
+/// conversion completes, or the errors of [`convert_bytes`].
+#[cfg(feature = "tokio")]
+pub async fn convert_bytes_async(
+    data: Vec<u8>,
+    format: Format,
+    options: ConvertOptions,
+    cancel: CancellationToken,
+) -> Result<ConvertResult, ConvertError> {
+    async_convert::convert_bytes_async(data, format, options, cancel).await
+}
+
+/// Convert a PPTX presentation into one PDF per section (`<p14:section>`).
+///
+/// `options.slide_sections` and `options.slide_range` still apply, so a
+/// subset of sections can be exported. Presentations without sections
+/// yield no output.
+///
+/// Requires the `pdf-ops` feature.
+///
+/// # Errors
+///
+/// Returns [`ConvertError`] on parse or render failure.
+#[cfg(feature = "pdf-ops")]
+pub fn convert_pptx_sections(
+    data: &[u8],
+    options: &ConvertOptions,
+) -> Result<error::SectionConvertResult, ConvertError> {
+    pipeline::convert_pptx_sections(data, options)
+}
+
+/// Convert several XLSX workbooks into one PDF that opens with an index
+/// page listing each workbook and its sheets with the page they start on.
+///
+/// Workbooks are listed by their document title, or "Workbook 1", "Workbook
+/// 2", ... when they have none. `options` applies to every workbook. Each
+/// sheet is laid out on its own, so links from one sheet to another are
+/// dropped.
+///
+/// Requires the `pdf-ops` feature.
+///
+/// # Errors
+///
+/// Returns [`ConvertError`] on parse or render failure of any workbook.
+#[cfg(feature = "pdf-ops")]
+pub fn convert_xlsx_many(
+    workbooks: &[&[u8]],
+    options: &ConvertOptions,
+) -> Result<ConvertResult, ConvertError> {
+    consolidate::convert_xlsx_many(workbooks, options)
+}
+
+/// Convert several documents of any supported format and merge them, in
+/// order, into one PDF.
+///
+/// `options` applies to every document. With `options.document_bookmarks`
+/// each document gets a top-level bookmark, and with
+/// `options.table_of_contents` the PDF opens with a page listing each
+/// document and the page it starts on. Documents are named by their title,
+/// or "Document 1", "Document 2", ... when they have none. Warnings of all
+/// documents are returned together.
+///
+/// Requires the `pdf-ops` feature.
+///
+/// # Errors
+///
+/// Returns [`ConvertError`] if `inputs` is empty or any document fails to
+/// convert.
+#[cfg(feature = "pdf-ops")]
+pub fn convert_many(
+    inputs: &[(Vec<u8>, Format)],
+    options: &ConvertOptions,
+) -> Result<ConvertResult, ConvertError> {
+    consolidate::convert_many(inputs, options)
+}
+
+/// Render each slide of a PPTX presentation to a PNG or SVG image.
+///
+/// Runs the same parse and layout pipeline as [`convert_bytes`] but encodes
+/// every page as an image instead of a PDF, which suits thumbnails and web
+/// previews. `options.convert` applies as usual, so `slide_range` selects
+/// which slides are rendered.
+///
+/// # Errors
+///
+/// Returns [`ConvertError`] on parse or render failure, including a
+/// non-positive `options.dpi` for PNG output.
+pub fn render_slides_to_images(
+    data: &[u8],
+    options: &config::SlideImageOptions,
+) -> Result<Vec<error::ImageOutput>, ConvertError> {
+    pipeline::render_to_images(data, Format::Pptx, options)
+}
+
+/// Render each page of a document of any format to a PNG or SVG image.
+///
+/// Like [`render_slides_to_images`], but a DOCX or XLSX document comes back
+/// as the pages its PDF would have, for previews of every kind of input.
+///
+/// # Errors
+///
+/// Returns [`ConvertError`] on parse or render failure, including a
+/// non-positive `options.dpi` for PNG output.
+pub fn render_to_images(
+    data: &[u8],
+    format: Format,
+    options: &config::SlideImageOptions,
+) -> Result<Vec<error::ImageOutput>, ConvertError> {
+    pipeline::render_to_images(data, format, options)
+}
+
+/// List the files embedded in a DOCX, PPTX or XLSX package: pictures, other
+/// media and embedded objects, and embedded fonts.
+///
+/// Only the `kinds` asked for are returned. Pictures and media come as
+/// stored in the package; fonts are deobfuscated, ready to install.
+///
+/// # Errors
+///
+/// Returns [`ConvertError::Parse`] if `data` is not a readable package and
+/// [`ConvertError::UnsupportedEncryption`] if it is encrypted.
+pub fn extract_assets(
+    data: &[u8],
+    format: Format,
+    kinds: &[config::AssetKind],
+) -> Result<Vec<error::Asset>, ConvertError> {
+    let data: std::borrow::Cow<[u8]> =
+        pipeline::decrypted_input(data, &config::ConvertOptions::default())?;
+    parser::assets::read_assets(&data, format, kinds)
+}
+
+/// Summarize a document without converting it: its metadata, the sheets of
+/// a workbook with their extent, the slides of a presentation with their
+/// titles, the embedded fonts, and about how many pages its PDF will have.
+///
+/// Use it to pick `sheet_names` or `slide_range` before converting. The
+/// sheet and slide lists cover the whole document, hidden ones included;
+/// `options` only shape the page estimate, as they would the conversion,
+/// and supply [`ConvertOptions::input_password`] for encrypted files.
+///
+/// # Errors
+///
+/// Returns [`ConvertError::UnsupportedEncryption`] for encrypted files
+/// without a password and [`ConvertError::Parse`] if the document cannot be
+/// parsed.
+pub fn document_info(
+    data: &[u8],
+    format: Format,
+    options: &ConvertOptions,
+) -> Result<error::DocumentInfo, ConvertError> {
+    info::document_info(data, format, options)
+}
+
+/// Compare the text of a source document with the text layer of its PDF.
+///
+/// Parses `source` with default options and reports source text segments
+/// that are missing from, or only partially recoverable in, `pdf`. Use it as
+/// an automated QA gate before archiving converted documents.
+///
+/// Requires the `pdf-ops` feature.
+///
+/// # Errors
+///
+/// Returns [`ConvertError::Parse`] if the source or the PDF cannot be read.
+#[cfg(feature = "pdf-ops")]
+pub fn verify_fidelity(
+    source: &[u8],
+    format: Format,
+    pdf: &[u8],
+) -> Result<fidelity::FidelityDiff, ConvertError> {
+    fidelity::verify(source, format, pdf, &ConvertOptions::default())
+}
+
+/// Like [`verify_fidelity`], but parses the source with the same options used
+/// for conversion, so sheet and slide filters are respected.
+///
+/// Requires the `pdf-ops` feature.
+///
+/// # Errors
+///
+/// Returns [`ConvertError::Parse`] if the source or the PDF cannot be read.
+#[cfg(feature = "pdf-ops")]
+pub fn verify_fidelity_with_options(
+    source: &[u8],
+    format: Format,
+    pdf: &[u8],
+    options: &ConvertOptions,
+) -> Result<fidelity::FidelityDiff, ConvertError> {
+    fidelity::verify(source, format, pdf, options)
+}
+
 /// Render an IR Document to PDF bytes.
 ///
 ///// Render an IR [`Document`](ir::Document) directly to PDF bytes.
@@ -172,3 +487,35 @@ mod ts_integration_tests;
 #[cfg(all(test, feature = "pdf-ops"))]
 #[path = "lib_streaming_tests.rs"]
 mod streaming_tests;
+
+#[cfg(all(test, feature = "pdf-ops"))]
+#[path = "lib_sections_tests.rs"]
+mod sections_tests;
+
+#[cfg(all(test, feature = "pdf-ops"))]
+#[path = "lib_consolidate_tests.rs"]
+mod consolidate_tests;
+
+#[cfg(test)]
+#[path = "lib_converter_tests.rs"]
+mod converter_tests;
+
+#[cfg(test)]
+#[path = "lib_limits_tests.rs"]
+mod limits_tests;
+
+#[cfg(all(test, feature = "rayon"))]
+#[path = "lib_parallel_tests.rs"]
+mod parallel_tests;
+
+#[cfg(test)]
+#[path = "lib_sanitize_tests.rs"]
+mod sanitize_tests;
+
+#[cfg(all(test, feature = "tokio"))]
+#[path = "lib_async_tests.rs"]
+mod async_tests;
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+#[path = "lib_slide_images_tests.rs"]
+mod slide_images_tests;
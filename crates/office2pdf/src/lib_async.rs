@@ -0,0 +1,52 @@
+//! Async conversion for servers (`tokio` feature). The conversion runs on
+//! Tokio's blocking pool so it never stalls the async workers, and a
+//! [`CancellationToken`] stops waiting for it at once and stops the work
+//! itself at the next stage boundary.
+//!
+//! The token is not checked inside a stage. Typst offers no way to
+//! interrupt a compile, and a `World` cannot abort one either: failing a
+//! file or font lookup only turns into a diagnostic. So a cancelled
+//! conversion keeps its blocking thread until the compile it is in
+//! returns, which for a large document is most of the conversion.
+
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{ConvertOptions, Format};
+use crate::error::{ConvertError, ConvertResult};
+
+use super::pipeline::convert_bytes_with_checkpoint;
+
+pub(super) async fn convert_bytes_async(
+    data: Vec<u8>,
+    format: Format,
+    options: ConvertOptions,
+    cancel: CancellationToken,
+) -> Result<ConvertResult, ConvertError> {
+    if cancel.is_cancelled() {
+        return Err(ConvertError::Cancelled);
+    }
+    // A child token cancelled when this future is dropped, so an abandoned
+    // request stops its background work too.
+    let task_cancel: CancellationToken = cancel.child_token();
+    let _drop_guard = task_cancel.clone().drop_guard();
+    let task = tokio::task::spawn_blocking(move || {
+        convert_bytes_with_checkpoint(&data, format, &options, &|| {
+            if task_cancel.is_cancelled() {
+                Err(ConvertError::Cancelled)
+            } else {
+                Ok(())
+            }
+        })
+    });
+    tokio::select! {
+        biased;
+        () = cancel.cancelled() => Err(ConvertError::Cancelled),
+        joined = task => match joined {
+            Ok(result) => result,
+            // A panic escaping the parser's own guard, e.g. in Typst.
+            Err(join_error) => Err(ConvertError::Render(format!(
+                "conversion task failed: {join_error}"
+            ))),
+        },
+    }
+}
@@ -0,0 +1,62 @@
+use super::test_support::build_test_docx;
+use super::*;
+
+#[tokio::test]
+async fn test_convert_bytes_async_produces_pdf() {
+    let result = convert_bytes_async(
+        build_test_docx(),
+        Format::Docx,
+        ConvertOptions::default(),
+        CancellationToken::new(),
+    )
+    .await
+    .unwrap();
+    assert!(result.pdf.starts_with(b"%PDF"));
+}
+
+#[tokio::test]
+async fn test_convert_bytes_async_honours_cancelled_token() {
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let result = convert_bytes_async(
+        build_test_docx(),
+        Format::Docx,
+        ConvertOptions::default(),
+        cancel,
+    )
+    .await;
+    assert!(matches!(result, Err(ConvertError::Cancelled)));
+}
+
+#[test]
+fn test_failing_checkpoint_stops_the_conversion() {
+    let calls = std::sync::atomic::AtomicUsize::new(0);
+    let result = pipeline::convert_bytes_with_checkpoint(
+        &build_test_docx(),
+        Format::Docx,
+        &ConvertOptions::default(),
+        &|| {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(ConvertError::Cancelled)
+        },
+    );
+    assert!(matches!(result, Err(ConvertError::Cancelled)));
+    assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_checkpoint_runs_at_every_stage_boundary() {
+    let calls = std::sync::atomic::AtomicUsize::new(0);
+    let result = pipeline::convert_bytes_with_checkpoint(
+        &build_test_docx(),
+        Format::Docx,
+        &ConvertOptions::default(),
+        &|| {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        },
+    );
+    assert!(result.is_ok());
+    // After parsing, before compiling and after compiling.
+    assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+}
@@ -0,0 +1,283 @@
+//! Consolidation of several documents into one PDF: [`convert_many`] for
+//! any mix of formats, with optional bookmarks and a table of contents, and
+//! [`convert_xlsx_many`] for workbooks, which opens with an index of every
+//! workbook and sheet and the page it starts on.
+
+use std::borrow::Cow;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use crate::config::{ConvertOptions, Format};
+use crate::error::{ConvertError, ConvertResult, ConvertWarning};
+use crate::ir::{
+    Alignment, Block, Document, FlowPage, Margins, Metadata, Page, PageSize, Paragraph,
+    ParagraphStyle, Run, StyleSheet, TabAlignment, TabLeader, TabStop, TextStyle,
+};
+use crate::render::typst_gen::TypstOutput;
+use crate::{parser, pdf_ops};
+
+use super::limits;
+use super::pipeline::{
+    build_convert_result, check_warning_severity, convert_bytes, decrypted_input, finish_pdf,
+    prepare_document, render_document_with_options,
+};
+
+/// Indent of sheet entries under their workbook, in points.
+const SHEET_INDENT_PT: f64 = 18.0;
+
+/// One document's line in the index: its page count and, for workbooks,
+/// the name and page count of each sheet.
+#[derive(Debug, Clone, PartialEq)]
+struct IndexedDocument {
+    title: String,
+    page_count: u32,
+    sections: Vec<(String, u32)>,
+}
+
+pub(super) fn convert_many(
+    inputs: &[(Vec<u8>, Format)],
+    options: &ConvertOptions,
+) -> Result<ConvertResult, ConvertError> {
+    if inputs.is_empty() {
+        return Err(ConvertError::Parse(
+            "no input documents to convert".to_string(),
+        ));
+    }
+    let mut warnings: Vec<ConvertWarning> = Vec::new();
+    let mut indexed: Vec<IndexedDocument> = Vec::with_capacity(inputs.len());
+    let mut pdfs: Vec<Vec<u8>> = Vec::with_capacity(inputs.len() + 1);
+    let mut typst_sources: Vec<TypstOutput> = Vec::new();
+    // Only the merged PDF is stamped, bled, encrypted, signed, linearized,
+    // compacted or carries the sources; the parts must stay readable and may
+    // change, and `{pages}` counts the merged pages.
+    let part_options: ConvertOptions = ConvertOptions {
+        stamp: None,
+        bleed: None,
+        pdf_security: None,
+        signing: None,
+        embed_source: false,
+        linearize: false,
+        compact: false,
+        ..options.clone()
+    };
+    for (index, (data, format)) in inputs.iter().enumerate() {
+        // Decrypted here so the title can be read from the package too.
+        let decrypted: Cow<[u8]> = decrypted_input(data, options)?;
+        let result: ConvertResult = convert_bytes(&decrypted, *format, &part_options)?;
+        warnings.extend(result.warnings);
+        typst_sources.extend(result.typst_sources);
+        indexed.push(IndexedDocument {
+            title: document_title(&decrypted).unwrap_or_else(|| format!("Document {}", index + 1)),
+            page_count: pdf_ops::page_count(&result.pdf)?,
+            sections: Vec::new(),
+        });
+        pdfs.push(result.pdf);
+    }
+
+    let mut index_pages: u32 = 0;
+    if options.table_of_contents {
+        let (index_pdf, pages) = render_index(&indexed, options)?;
+        pdfs.insert(0, index_pdf);
+        index_pages = pages;
+    }
+    let parts: Vec<&[u8]> = pdfs.iter().map(Vec::as_slice).collect();
+    let mut pdf: Vec<u8> = pdf_ops::merge(&parts)?;
+    if options.document_bookmarks {
+        let mut next_page: u32 = index_pages + 1;
+        let mut bookmarks: Vec<(String, u32)> = Vec::with_capacity(indexed.len());
+        for document in &indexed {
+            // An empty document has no page to point at.
+            if document.page_count > 0 {
+                bookmarks.push((document.title.clone(), next_page));
+            }
+            next_page += document.page_count;
+        }
+        pdf = pdf_ops::set_outline(&pdf, &bookmarks)?;
+    }
+    let sources: Vec<(&[u8], Format)> = inputs
+        .iter()
+        .map(|(data, format)| (data.as_slice(), *format))
+        .collect();
+    let pdf: Vec<u8> = finish_pdf(pdf, &sources, options)?;
+    Ok(build_convert_result(pdf, warnings, None, typst_sources))
+}
+
+/// The title in an OOXML package's core properties, if it has one.
+fn document_title(data: &[u8]) -> Option<String> {
+    let mut archive = parser::open_zip(data).ok()?;
+    parser::metadata::extract_metadata_from_zip(&mut archive)
+        .title
+        .filter(|title| !title.trim().is_empty())
+}
+
+pub(super) fn convert_xlsx_many(
+    workbooks: &[&[u8]],
+    options: &ConvertOptions,
+) -> Result<ConvertResult, ConvertError> {
+    let start: Instant = Instant::now();
+    let mut warnings: Vec<ConvertWarning> = Vec::new();
+    let mut indexed: Vec<IndexedDocument> = Vec::with_capacity(workbooks.len());
+    let mut sheet_pdfs: Vec<Vec<u8>> = Vec::new();
+    for (index, data) in workbooks.iter().enumerate() {
+        let decrypted: Cow<[u8]> = decrypted_input(data, options)?;
+        let data: &[u8] = &decrypted;
+        limits::check_input(data, &options.limits)?;
+        let parse_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            parser::xlsx::XlsxParser.parse_by_sheet(data, options)
+        }));
+        let (mut sheet_docs, sheet_warnings) = match parse_result {
+            Ok(result) => result?,
+            Err(panic_info) => {
+                return Err(ConvertError::Parse(format!(
+                    "upstream parser panicked: {}",
+                    parser::extract_panic_message(&panic_info)
+                )));
+            }
+        };
+        check_warning_severity(&sheet_warnings, options)?;
+        warnings.extend(sheet_warnings);
+        for sheet_doc in &mut sheet_docs {
+            warnings.extend(prepare_document(sheet_doc, Format::Xlsx, options));
+        }
+        limits::check_documents(&sheet_docs, &options.limits)?;
+
+        let title: String = sheet_docs
+            .first()
+            .and_then(|doc| doc.metadata.title.clone())
+            .filter(|title| !title.trim().is_empty())
+            .unwrap_or_else(|| format!("Workbook {}", index + 1));
+        let mut sheets: Vec<(String, u32)> = Vec::with_capacity(sheet_docs.len());
+        for sheet_doc in &sheet_docs {
+            let Some(Page::Sheet(sheet_page)) = sheet_doc.pages.first() else {
+                continue;
+            };
+            limits::check_deadline(start, &options.limits)?;
+            // Each sheet is compiled on its own: its PDF page count is what
+            // the index needs, and Typst only knows it after layout.
+            let pdf: Vec<u8> = render_document_with_options(sheet_doc, options)?;
+            sheets.push((sheet_page.name.clone(), pdf_ops::page_count(&pdf)?));
+            sheet_pdfs.push(pdf);
+        }
+        indexed.push(IndexedDocument {
+            title,
+            page_count: sheets.iter().map(|(_, pages)| pages).sum(),
+            sections: sheets,
+        });
+    }
+
+    let (index_pdf, _) = render_index(&indexed, options)?;
+    let mut parts: Vec<&[u8]> = Vec::with_capacity(sheet_pdfs.len() + 1);
+    parts.push(&index_pdf);
+    parts.extend(sheet_pdfs.iter().map(Vec::as_slice));
+    let sources: Vec<(&[u8], Format)> =
+        workbooks.iter().map(|data| (*data, Format::Xlsx)).collect();
+    let pdf: Vec<u8> = finish_pdf(pdf_ops::merge(&parts)?, &sources, options)?;
+    Ok(build_convert_result(pdf, warnings, None, Vec::new()))
+}
+
+/// Render the index and return it with its page count.
+fn render_index(
+    documents: &[IndexedDocument],
+    options: &ConvertOptions,
+) -> Result<(Vec<u8>, u32), ConvertError> {
+    // The index's own length shifts every page number after it; a second
+    // pass settles it when the index runs past one page.
+    let mut index_pages: u32 = 1;
+    let mut index_pdf: Vec<u8> =
+        render_document_with_options(&index_document(documents, index_pages), options)?;
+    let rendered_pages: u32 = pdf_ops::page_count(&index_pdf)?;
+    if rendered_pages != index_pages {
+        index_pages = rendered_pages;
+        index_pdf = render_document_with_options(&index_document(documents, index_pages), options)?;
+    }
+    Ok((index_pdf, index_pages))
+}
+
+/// The index: each document, then its sheets, with the page each starts on
+/// after `index_pages` pages of index.
+fn index_document(documents: &[IndexedDocument], index_pages: u32) -> Document {
+    let size = PageSize::default();
+    let margins = Margins::default();
+    let right_edge: f64 = size.width - margins.left - margins.right;
+
+    let mut content: Vec<Block> = vec![index_line(
+        "Contents".to_string(),
+        TextStyle {
+            bold: Some(true),
+            font_size: Some(16.0),
+            ..TextStyle::default()
+        },
+        ParagraphStyle {
+            space_after: Some(12.0),
+            ..ParagraphStyle::default()
+        },
+    )];
+    let mut next_page: u32 = index_pages + 1;
+    for document in documents {
+        let entry_style = |indent: f64, space_before: f64| ParagraphStyle {
+            indent_left: Some(indent),
+            space_before: Some(space_before),
+            tab_stops: Some(vec![TabStop {
+                position: right_edge - indent,
+                alignment: TabAlignment::Right,
+                leader: TabLeader::Dot,
+            }]),
+            ..ParagraphStyle::default()
+        };
+        // A document without printed pages is listed without a page.
+        let document_line: String = if document.page_count == 0 {
+            document.title.clone()
+        } else {
+            format!("{}\t{next_page}", document.title)
+        };
+        content.push(index_line(
+            document_line,
+            TextStyle {
+                bold: Some(true),
+                ..TextStyle::default()
+            },
+            entry_style(0.0, 8.0),
+        ));
+        let mut section_page: u32 = next_page;
+        for (sheet_name, page_count) in &document.sections {
+            content.push(index_line(
+                format!("{sheet_name}\t{section_page}"),
+                TextStyle::default(),
+                entry_style(SHEET_INDENT_PT, 2.0),
+            ));
+            section_page += page_count;
+        }
+        next_page += document.page_count;
+    }
+
+    Document {
+        metadata: Metadata::default(),
+        pages: vec![Page::Flow(FlowPage {
+            size,
+            margins,
+            content,
+            header: None,
+            footer: None,
+            columns: None,
+            line_grid_pitch: None,
+        })],
+        styles: StyleSheet::default(),
+    }
+}
+
+fn index_line(text: String, style: TextStyle, paragraph_style: ParagraphStyle) -> Block {
+    Block::Paragraph(Paragraph {
+        style: ParagraphStyle {
+            alignment: Some(Alignment::Left),
+            ..paragraph_style
+        },
+        runs: vec![Run {
+            text,
+            style,
+            href: None,
+            footnote: None,
+        }],
+    })
+}
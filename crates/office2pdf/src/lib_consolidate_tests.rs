@@ -0,0 +1,113 @@
+use std::io::Cursor;
+
+use super::*;
+
+/// A workbook with one short sheet per name, titled `title` when given.
+fn build_workbook(title: Option<&str>, sheet_names: &[&str]) -> Vec<u8> {
+    let mut book = umya_spreadsheet::new_file_empty_worksheet();
+    for name in sheet_names {
+        let sheet = book.new_sheet(*name).unwrap();
+        sheet.get_cell_mut("A1").set_value(format!("{name} data"));
+    }
+    if let Some(title) = title {
+        book.get_properties_mut().set_title(title);
+    }
+    let mut cursor = Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    cursor.into_inner()
+}
+
+#[test]
+fn test_convert_xlsx_many_opens_with_an_index() {
+    let first: Vec<u8> = build_workbook(Some("Budget"), &["Income", "Costs"]);
+    let second: Vec<u8> = build_workbook(None, &["Notes"]);
+    let result = convert_xlsx_many(&[&first, &second], &config::ConvertOptions::default()).unwrap();
+
+    // The index page, then one page per sheet.
+    assert_eq!(pdf_ops::page_count(&result.pdf).unwrap(), 4);
+    let pages: Vec<String> = pdf_ops::extract_text(&result.pdf)
+        .unwrap()
+        .into_iter()
+        .map(|page| page.text)
+        .collect();
+    let index: &str = &pages[0];
+    for entry in [
+        "Contents",
+        "Budget",
+        "Income",
+        "Costs",
+        "Workbook 2",
+        "Notes",
+    ] {
+        assert!(index.contains(entry), "missing {entry}: {index}");
+    }
+    // Sheets start on pages 2, 3 and 4, after the one index page.
+    for page in ["2", "3", "4"] {
+        assert!(index.contains(page), "missing page {page}: {index}");
+    }
+    assert!(pages[1].contains("Income data"), "{}", pages[1]);
+    assert!(pages[3].contains("Notes data"), "{}", pages[3]);
+}
+
+#[test]
+fn test_convert_xlsx_many_rejects_encrypted_workbooks() {
+    let encrypted: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+    let result = convert_xlsx_many(&[&encrypted], &config::ConvertOptions::default());
+    assert!(matches!(result, Err(ConvertError::UnsupportedEncryption)));
+}
+
+#[test]
+fn test_convert_many_merges_with_contents_and_bookmarks() {
+    let inputs: Vec<(Vec<u8>, config::Format)> = vec![
+        (
+            super::test_support::build_docx_with_title("Annual report"),
+            config::Format::Docx,
+        ),
+        (build_workbook(None, &["Figures"]), config::Format::Xlsx),
+    ];
+    let options = config::ConvertOptions {
+        document_bookmarks: true,
+        table_of_contents: true,
+        ..Default::default()
+    };
+    let result = convert_many(&inputs, &options).unwrap();
+
+    // The contents page, then one page per document.
+    assert_eq!(pdf_ops::page_count(&result.pdf).unwrap(), 3);
+    let pages: Vec<String> = pdf_ops::extract_text(&result.pdf)
+        .unwrap()
+        .into_iter()
+        .map(|page| page.text)
+        .collect();
+    for entry in ["Contents", "Annual report", "Document 2", "2", "3"] {
+        assert!(pages[0].contains(entry), "missing {entry}: {}", pages[0]);
+    }
+    assert!(pages[2].contains("Figures data"), "{}", pages[2]);
+
+    let doc = lopdf::Document::load_mem(&result.pdf).unwrap();
+    let outlines = doc
+        .get_dictionary(
+            doc.catalog()
+                .unwrap()
+                .get(b"Outlines")
+                .unwrap()
+                .as_reference()
+                .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(outlines.get(b"Count").unwrap().as_i64().unwrap(), 2);
+}
+
+#[test]
+fn test_convert_many_without_extras_only_concatenates() {
+    let inputs: Vec<(Vec<u8>, config::Format)> = vec![
+        (build_workbook(None, &["One"]), config::Format::Xlsx),
+        (build_workbook(None, &["Two"]), config::Format::Xlsx),
+    ];
+    let result = convert_many(&inputs, &config::ConvertOptions::default()).unwrap();
+    assert_eq!(pdf_ops::page_count(&result.pdf).unwrap(), 2);
+    let doc = lopdf::Document::load_mem(&result.pdf).unwrap();
+    assert!(doc.catalog().unwrap().get(b"Outlines").is_err());
+
+    assert!(convert_many(&[], &config::ConvertOptions::default()).is_err());
+}
@@ -0,0 +1,105 @@
+//! [`Converter`]: options held together with the compilation state worth
+//! keeping between conversions, for servers converting many documents the
+//! same way.
+
+use std::sync::Arc;
+
+use crate::config::{ConvertOptions, Format};
+use crate::error::{ConvertError, ConvertResult};
+use crate::render::compile_cache::{CompileCache, with_compile_cache};
+
+use super::pipeline;
+
+/// A reusable conversion handle. Each conversion through it reuses what
+/// earlier ones built: Typst's standard library, the faces of
+/// [`FontConfig::data`](crate::config::FontConfig::data), the font families
+/// resolved for [`ConvertOptions::font_paths`], and image data shared by
+/// content hash. Installed fonts are discovered once per process either way.
+///
+/// Clones share the cache, so one converter can serve a thread pool.
+///
+/// ```no_run
+/// use office2pdf::Converter;
+/// use office2pdf::config::{ConvertOptions, Format};
+///
+/// let converter = Converter::new(ConvertOptions::default());
+/// for path in ["a.docx", "b.docx"] {
+///     let data = std::fs::read(path).unwrap();
+///     let result = converter.convert_bytes(&data, Format::Docx).unwrap();
+///     std::fs::write(format!("{path}.pdf"), &result.pdf).unwrap();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Converter {
+    options: ConvertOptions,
+    cache: Arc<CompileCache>,
+}
+
+impl Converter {
+    /// A converter that converts with `options`.
+    pub fn new(options: ConvertOptions) -> Self {
+        Self {
+            options,
+            cache: Arc::new(CompileCache::new()),
+        }
+    }
+
+    /// The options every conversion uses.
+    pub fn options(&self) -> &ConvertOptions {
+        &self.options
+    }
+
+    /// Convert the file at `path`, as [`convert_with_options`](crate::convert_with_options)
+    /// does.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConvertError`] on unsupported format, I/O, parse, or render failure.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn convert(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<ConvertResult, ConvertError> {
+        with_compile_cache(Some(&self.cache), || {
+            pipeline::convert_with_options(path, &self.options)
+        })
+    }
+
+    /// Convert `data` of a known format, as [`convert_bytes`](crate::convert_bytes)
+    /// does.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConvertError`] on parse or render failure.
+    pub fn convert_bytes(
+        &self,
+        data: &[u8],
+        format: Format,
+    ) -> Result<ConvertResult, ConvertError> {
+        with_compile_cache(Some(&self.cache), || {
+            pipeline::convert_bytes(data, format, &self.options)
+        })
+    }
+
+    /// Convert `data` of an unknown format, as
+    /// [`convert_bytes_auto`](crate::convert_bytes_auto) does.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConvertError::UnsupportedFormat`] if the bytes are not a
+    /// DOCX, PPTX or XLSX package, and otherwise fails as
+    /// [`Converter::convert_bytes`] does.
+    pub fn convert_bytes_auto(&self, data: &[u8]) -> Result<ConvertResult, ConvertError> {
+        with_compile_cache(Some(&self.cache), || {
+            pipeline::convert_bytes_auto(data, &self.options)
+        })
+    }
+}
+
+impl std::fmt::Debug for Converter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Converter")
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,35 @@
+#![cfg(not(target_arch = "wasm32"))] // native-only unit tests (system fonts)
+use super::Converter;
+use super::config::{ConvertOptions, Format};
+use super::test_support::{build_test_docx, build_test_pptx};
+
+#[test]
+fn test_converter_matches_one_off_conversion() {
+    let options = ConvertOptions {
+        deterministic: true,
+        ..Default::default()
+    };
+    let converter = Converter::new(options.clone());
+    let data: Vec<u8> = build_test_docx();
+    let expected = super::convert_bytes(&data, Format::Docx, &options).unwrap();
+    for _ in 0..2 {
+        let result = converter.convert_bytes(&data, Format::Docx).unwrap();
+        assert!(result.pdf == expected.pdf, "cached conversion differs");
+    }
+}
+
+#[test]
+fn test_converter_is_shared_between_threads() {
+    let converter = Converter::new(ConvertOptions::default());
+    let data: Vec<u8> = build_test_pptx();
+    std::thread::scope(|scope| {
+        for _ in 0..2 {
+            let converter: Converter = converter.clone();
+            let data: &[u8] = &data;
+            scope.spawn(move || {
+                let result = converter.convert_bytes_auto(data).unwrap();
+                assert!(result.pdf.starts_with(b"%PDF"));
+            });
+        }
+    });
+}
@@ -0,0 +1,185 @@
+//! Document summaries for [`document_info`](crate::document_info): sheets,
+//! slides, embedded fonts, metadata and an estimate of the page count, read
+//! without laying out or compiling the document.
+
+use std::borrow::Cow;
+
+use crate::config::{ConvertOptions, Format};
+use crate::error::{ConvertError, DocumentInfo, SheetInfo, SlideInfo};
+use crate::ir::{Block, Document, FlowPage, Page, Paragraph, SheetPage, Table};
+use crate::parser;
+
+use super::pipeline::{decrypted_input, parse_bytes};
+
+/// Type size assumed for text without an explicit size, in points.
+const DEFAULT_FONT_SIZE_PT: f64 = 11.0;
+
+/// Average glyph width as a share of the type size; close enough for
+/// proportional Latin text to count lines.
+const AVERAGE_GLYPH_WIDTH_EM: f64 = 0.5;
+
+/// Line height as a share of the type size.
+const LINE_HEIGHT_EM: f64 = 1.2;
+
+/// Height of a worksheet row without an explicit height: Excel's default
+/// for 11pt Calibri.
+const DEFAULT_ROW_HEIGHT_PT: f64 = 15.0;
+
+/// Height taken by a chart in flowing text without a size of its own.
+const CHART_HEIGHT_PT: f64 = 216.0;
+
+pub(super) fn document_info(
+    data: &[u8],
+    format: Format,
+    options: &ConvertOptions,
+) -> Result<DocumentInfo, ConvertError> {
+    let data: Cow<[u8]> = decrypted_input(data, options)?;
+    let (doc, _warnings) = parse_bytes(&data, format, options)?;
+
+    let sheets: Vec<SheetInfo> = match format {
+        Format::Xlsx => parser::xlsx::read_sheet_outline(&data)?,
+        _ => Vec::new(),
+    };
+    let slides: Vec<SlideInfo> = match format {
+        Format::Pptx => parser::pptx::read_slide_outline(&data)?,
+        _ => Vec::new(),
+    };
+    // Word counts pages when it saves; that beats any estimate made
+    // without layout, unless the paper changes.
+    let recorded_pages: Option<u32> = match format {
+        Format::Docx if options.paper_size.is_none() && options.landscape.is_none() => {
+            parser::open_zip(&data)
+                .ok()
+                .and_then(|mut archive| parser::metadata::read_app_pages(&mut archive))
+        }
+        _ => None,
+    };
+    Ok(DocumentInfo {
+        format,
+        estimated_pages: recorded_pages.unwrap_or_else(|| estimate_pages(&doc)),
+        metadata: doc.metadata,
+        sheets,
+        slides,
+        embedded_fonts: parser::embedded_fonts::embedded_typefaces(&data, format),
+    })
+}
+
+/// Estimate the page count of a parsed document: one page per slide, and
+/// for sheets and flowing text the height of their content over the
+/// printable height of their pages.
+pub(super) fn estimate_pages(doc: &Document) -> u32 {
+    doc.pages
+        .iter()
+        .map(|page| match page {
+            Page::Fixed(_) => 1,
+            Page::Sheet(sheet) => sheet_pages(sheet),
+            Page::Flow(flow) => flow_pages(flow),
+        })
+        .sum()
+}
+
+fn sheet_pages(sheet: &SheetPage) -> u32 {
+    let printable: f64 = sheet.size.height - sheet.margins.top - sheet.margins.bottom;
+    let content: f64 = sheet
+        .table
+        .rows
+        .iter()
+        .map(|row| row.height.unwrap_or(DEFAULT_ROW_HEIGHT_PT))
+        .sum();
+    pages_for(content, printable)
+}
+
+fn flow_pages(flow: &FlowPage) -> u32 {
+    let width: f64 = flow.size.width - flow.margins.left - flow.margins.right;
+    let printable: f64 = flow.size.height - flow.margins.top - flow.margins.bottom;
+    // Explicit page breaks start a new page however full the last one was.
+    flow.content
+        .split(|block| matches!(block, Block::PageBreak))
+        .map(|blocks| pages_for(blocks_height(blocks, width), printable))
+        .sum()
+}
+
+/// Pages needed for `content` points of height, at least one.
+fn pages_for(content: f64, printable: f64) -> u32 {
+    if printable <= 0.0 {
+        return 1;
+    }
+    ((content / printable).ceil() as u32).max(1)
+}
+
+fn blocks_height(blocks: &[Block], width: f64) -> f64 {
+    blocks.iter().map(|block| block_height(block, width)).sum()
+}
+
+fn block_height(block: &Block, width: f64) -> f64 {
+    match block {
+        Block::Paragraph(paragraph) => paragraph_height(paragraph, width),
+        Block::List(list) => list
+            .items
+            .iter()
+            .flat_map(|item| &item.content)
+            .map(|paragraph| paragraph_height(paragraph, width))
+            .sum(),
+        Block::Table(table) => table_height(table, width),
+        Block::Image(image) => image.height.unwrap_or(0.0),
+        Block::InlineImages(images) => images
+            .iter()
+            .filter_map(|image| image.height)
+            .fold(0.0, f64::max),
+        Block::MathEquation(_) => DEFAULT_FONT_SIZE_PT * LINE_HEIGHT_EM,
+        Block::Chart(_) => CHART_HEIGHT_PT,
+        // Floating objects sit beside or over the text rather than taking
+        // lines of their own.
+        Block::FloatingImage(_)
+        | Block::FloatingTextBox(_)
+        | Block::FloatingShape(_)
+        | Block::PageBreak
+        | Block::ColumnBreak => 0.0,
+    }
+}
+
+fn paragraph_height(paragraph: &Paragraph, width: f64) -> f64 {
+    let font_size: f64 = paragraph
+        .runs
+        .iter()
+        .find_map(|run| run.style.font_size)
+        .unwrap_or(DEFAULT_FONT_SIZE_PT);
+    let chars: usize = paragraph
+        .runs
+        .iter()
+        .map(|run| run.text.chars().count())
+        .sum();
+    let chars_per_line: f64 = (width / (font_size * AVERAGE_GLYPH_WIDTH_EM)).max(1.0);
+    let lines: f64 = (chars as f64 / chars_per_line).ceil().max(1.0);
+    paragraph.style.space_before.unwrap_or(0.0)
+        + lines * font_size * LINE_HEIGHT_EM
+        + paragraph.style.space_after.unwrap_or(0.0)
+}
+
+fn table_height(table: &Table, width: f64) -> f64 {
+    table
+        .rows
+        .iter()
+        .map(|row| {
+            let fallback_width: f64 = width / row.cells.len().max(1) as f64;
+            let content: f64 = row
+                .cells
+                .iter()
+                .enumerate()
+                .map(|(index, cell)| {
+                    let cell_width: f64 = table
+                        .column_widths
+                        .get(index)
+                        .copied()
+                        .unwrap_or(fallback_width);
+                    blocks_height(&cell.content, cell_width)
+                })
+                .fold(0.0, f64::max);
+            content.max(row.height.unwrap_or(0.0))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+#[path = "lib_info_tests.rs"]
+mod tests;
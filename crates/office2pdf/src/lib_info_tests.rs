@@ -0,0 +1,74 @@
+use super::*;
+use crate::ir::{Margins, PageSize};
+use crate::test_support::{build_test_pptx, build_test_xlsx, make_simple_document};
+
+/// A flow page of `text`, with `breaks` page breaks each followed by a
+/// short paragraph.
+fn flow_document(text: &str, breaks: usize) -> Document {
+    let mut doc: Document = make_simple_document(text);
+    if let Page::Flow(flow) = &mut doc.pages[0] {
+        let paragraph: Block = flow.content[0].clone();
+        for _ in 0..breaks {
+            flow.content.push(Block::PageBreak);
+            flow.content.push(paragraph.clone());
+        }
+        flow.size = PageSize {
+            width: 612.0,
+            height: 792.0,
+        };
+        flow.margins = Margins {
+            top: 72.0,
+            bottom: 72.0,
+            left: 72.0,
+            right: 72.0,
+        };
+    }
+    doc
+}
+
+#[test]
+fn test_estimate_pages_counts_text_height_and_page_breaks() {
+    assert_eq!(estimate_pages(&flow_document("Short", 0)), 1);
+    assert_eq!(estimate_pages(&flow_document("Short", 2)), 3);
+    // 85 characters to a 468pt line of 11pt text, 49 lines to a 648pt
+    // page: 10,000 characters take 118 lines, a little over two pages.
+    assert_eq!(estimate_pages(&flow_document(&"x".repeat(10_000), 0)), 3);
+}
+
+#[test]
+fn test_document_info_lists_sheets_of_a_workbook() {
+    let info: DocumentInfo =
+        document_info(&build_test_xlsx(), Format::Xlsx, &ConvertOptions::default()).unwrap();
+
+    assert_eq!(info.format, Format::Xlsx);
+    assert_eq!(
+        info.sheets,
+        vec![SheetInfo {
+            name: "Sheet1".to_string(),
+            rows: 2,
+            columns: 2,
+            hidden: false,
+        }]
+    );
+    assert!(info.slides.is_empty());
+    assert_eq!(info.estimated_pages, 1);
+}
+
+#[test]
+fn test_document_info_lists_slides_of_a_presentation() {
+    let info: DocumentInfo =
+        document_info(&build_test_pptx(), Format::Pptx, &ConvertOptions::default()).unwrap();
+
+    assert_eq!(
+        info.slides,
+        vec![SlideInfo {
+            number: 1,
+            title: None,
+            section: None,
+            hidden: false,
+        }]
+    );
+    assert!(info.sheets.is_empty());
+    assert!(info.embedded_fonts.is_empty());
+    assert_eq!(info.estimated_pages, 1);
+}
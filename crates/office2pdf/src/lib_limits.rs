@@ -0,0 +1,190 @@
+//! Enforcement of [`Limits`]: the input is checked before parsing, the
+//! parsed document before rendering, and the clock at every pipeline
+//! checkpoint.
+
+use std::io::{ErrorKind, Read};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use crate::config::Limits;
+use crate::error::ConvertError;
+use crate::ir::{Block, Document, FixedElementKind, HFInline, HeaderFooter, Page};
+
+/// Check the input's size and, for ZIP containers, the size of its entries
+/// once decompressed.
+pub(super) fn check_input(data: &[u8], limits: &Limits) -> Result<(), ConvertError> {
+    if let Some(max) = limits.max_input_bytes
+        && data.len() as u64 > max
+    {
+        return Err(exceeded(format!(
+            "input is {} bytes, more than the {max} allowed",
+            data.len()
+        )));
+    }
+    let Some(max) = limits.max_uncompressed_bytes else {
+        return Ok(());
+    };
+    // Not a ZIP: the parser reports that itself.
+    let Ok(mut archive) = crate::parser::open_zip(data) else {
+        return Ok(());
+    };
+    // The entries are inflated and counted rather than trusting the sizes
+    // their headers declare, which a zip bomb understates. The parsers
+    // cannot be capped instead: docx-rs and umya-spreadsheet open the
+    // package themselves. All entries share one budget of `max + 1` bytes
+    // and reading stops when it runs out, so a bomb costs at most that much
+    // inflation, none of it kept, however its bytes are spread over entries.
+    let mut remaining: u64 = max.saturating_add(1);
+    for index in 0..archive.len() {
+        // Entries no parser can read either, such as encrypted ones, fail
+        // there.
+        let Ok(entry) = archive.by_index(index) else {
+            continue;
+        };
+        remaining -= inflated_size(entry, remaining);
+        if remaining == 0 {
+            return Err(exceeded(format!(
+                "input decompresses to more than the {max} bytes allowed"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The bytes `entry` inflates to, counting no further than `cap`. A corrupt
+/// stream counts up to where it breaks, as far as a parser would get.
+fn inflated_size(entry: impl Read, cap: u64) -> u64 {
+    let mut reader = entry.take(cap);
+    let mut buffer: [u8; 8192] = [0; 8192];
+    let mut total: u64 = 0;
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return total,
+            Ok(read) => total += read as u64,
+            Err(error) if error.kind() == ErrorKind::Interrupted => {}
+            Err(_) => return total,
+        }
+    }
+}
+
+/// Check the page and image counts of the documents one conversion
+/// renders; streaming conversion renders several.
+pub(super) fn check_documents<'a>(
+    docs: impl IntoIterator<Item = &'a Document>,
+    limits: &Limits,
+) -> Result<(), ConvertError> {
+    if limits.max_pages.is_none() && limits.max_images.is_none() {
+        return Ok(());
+    }
+    let (mut pages, mut images): (u64, u64) = (0, 0);
+    for doc in docs {
+        pages += doc.pages.len() as u64;
+        images += doc.pages.iter().map(page_image_count).sum::<u64>();
+    }
+    check_page_count(pages, limits)?;
+    if let Some(max) = limits.max_images
+        && images > u64::from(max)
+    {
+        return Err(exceeded(format!(
+            "document has {images} images, more than the {max} allowed"
+        )));
+    }
+    Ok(())
+}
+
+/// Check a page count against `max_pages`.
+pub(super) fn check_page_count(pages: u64, limits: &Limits) -> Result<(), ConvertError> {
+    match limits.max_pages {
+        Some(max) if pages > u64::from(max) => Err(exceeded(format!(
+            "document has {pages} pages, more than the {max} allowed"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Fail once the conversion started at `start` has run past `timeout`.
+pub(super) fn check_deadline(start: Instant, limits: &Limits) -> Result<(), ConvertError> {
+    match limits.timeout {
+        Some(timeout) if start.elapsed() > timeout => Err(exceeded(format!(
+            "conversion took longer than {} ms",
+            timeout.as_millis()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn exceeded(message: String) -> ConvertError {
+    ConvertError::LimitExceeded(message)
+}
+
+fn page_image_count(page: &Page) -> u64 {
+    match page {
+        Page::Flow(flow) => {
+            blocks_image_count(&flow.content)
+                + header_footer_image_count(flow.header.as_ref())
+                + header_footer_image_count(flow.footer.as_ref())
+        }
+        Page::Fixed(fixed) => {
+            let elements: u64 = fixed
+                .elements
+                .iter()
+                .map(|element| match &element.kind {
+                    FixedElementKind::Image(_) => 1,
+                    FixedElementKind::TextBox(text_box) => blocks_image_count(&text_box.content),
+                    FixedElementKind::Table(table) => table
+                        .rows
+                        .iter()
+                        .flat_map(|row| &row.cells)
+                        .map(|cell| blocks_image_count(&cell.content))
+                        .sum(),
+                    FixedElementKind::Shape(_)
+                    | FixedElementKind::SmartArt(_)
+                    | FixedElementKind::Chart(_) => 0,
+                })
+                .sum();
+            elements + u64::from(fixed.background_image.is_some())
+        }
+        Page::Sheet(sheet) => {
+            sheet.images.len() as u64
+                + header_footer_image_count(sheet.header.as_ref())
+                + header_footer_image_count(sheet.footer.as_ref())
+        }
+    }
+}
+
+fn blocks_image_count(blocks: &[Block]) -> u64 {
+    blocks
+        .iter()
+        .map(|block| match block {
+            Block::Image(_) | Block::FloatingImage(_) => 1,
+            Block::InlineImages(images) => images.len() as u64,
+            Block::FloatingTextBox(text_box) => blocks_image_count(&text_box.content),
+            Block::Table(table) => table
+                .rows
+                .iter()
+                .flat_map(|row| &row.cells)
+                .map(|cell| blocks_image_count(&cell.content))
+                .sum(),
+            Block::Paragraph(_)
+            | Block::FloatingShape(_)
+            | Block::List(_)
+            | Block::MathEquation(_)
+            | Block::Chart(_)
+            | Block::PageBreak
+            | Block::ColumnBreak => 0,
+        })
+        .sum()
+}
+
+fn header_footer_image_count(header_footer: Option<&HeaderFooter>) -> u64 {
+    header_footer.map_or(0, |header_footer| {
+        header_footer
+            .paragraphs
+            .iter()
+            .flat_map(|paragraph| &paragraph.elements)
+            .filter(|element| matches!(element, HFInline::Image(_)))
+            .count() as u64
+    })
+}
@@ -0,0 +1,147 @@
+use super::test_support::{build_test_docx, make_simple_document, make_test_png};
+use super::*;
+use crate::config::Limits;
+use crate::ir::{Block, ImageData, ImageFormat, Page};
+
+fn convert_with_limits(limits: Limits) -> Result<ConvertResult, ConvertError> {
+    let options = ConvertOptions {
+        limits,
+        ..ConvertOptions::default()
+    };
+    convert_bytes(&build_test_docx(), Format::Docx, &options)
+}
+
+#[test]
+fn test_default_limits_allow_conversion() {
+    assert!(convert_with_limits(Limits::default()).is_ok());
+}
+
+#[test]
+fn test_input_size_limits() {
+    let result = convert_with_limits(Limits {
+        max_input_bytes: Some(64),
+        ..Limits::default()
+    });
+    assert!(matches!(result, Err(ConvertError::LimitExceeded(_))));
+
+    // The document XML alone decompresses to more than this.
+    let result = convert_with_limits(Limits {
+        max_uncompressed_bytes: Some(256),
+        ..Limits::default()
+    });
+    assert!(matches!(result, Err(ConvertError::LimitExceeded(_))));
+}
+
+#[test]
+fn test_page_limit() {
+    let result = convert_with_limits(Limits {
+        max_pages: Some(0),
+        ..Limits::default()
+    });
+    let Err(ConvertError::LimitExceeded(message)) = result else {
+        panic!("expected a page limit error");
+    };
+    assert!(message.contains("pages"), "{message}");
+}
+
+#[test]
+fn test_image_limit_counts_images_in_the_document() {
+    let mut doc = make_simple_document("With pictures");
+    let image = ImageData {
+        data: make_test_png(),
+        format: ImageFormat::Png,
+        width: Some(10.0),
+        height: Some(10.0),
+        crop: None,
+        stroke: None,
+        alignment: None,
+        clip_shape: None,
+        shadow: None,
+    };
+    let Page::Flow(page) = &mut doc.pages[0] else {
+        panic!("expected a flow page");
+    };
+    page.content.push(Block::Image(image.clone()));
+    page.content
+        .push(Block::InlineImages(vec![image.clone(), image]));
+
+    let limits = |max_images: u32| Limits {
+        max_images: Some(max_images),
+        ..Limits::default()
+    };
+    assert!(limits::check_documents([&doc], &limits(3)).is_ok());
+    assert!(matches!(
+        limits::check_documents([&doc], &limits(2)),
+        Err(ConvertError::LimitExceeded(_))
+    ));
+}
+
+#[test]
+fn test_timeout_fails_at_the_next_checkpoint() {
+    let result = convert_with_limits(Limits {
+        timeout: Some(std::time::Duration::ZERO),
+        ..Limits::default()
+    });
+    assert!(matches!(result, Err(ConvertError::LimitExceeded(_))));
+}
+
+/// A ZIP of one deflated entry of `size` zeros whose headers declare it
+/// empty, as a zip bomb would to slip past a check of the declared sizes.
+fn understated_zip(size: usize) -> Vec<u8> {
+    use std::io::Write;
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file("word/document.xml", options).unwrap();
+    zip.write_all(&vec![0; size]).unwrap();
+    let mut bytes: Vec<u8> = zip.finish().unwrap().into_inner();
+    // The uncompressed size sits 22 bytes into the local header and 24
+    // into the central directory entry.
+    for (signature, offset) in [(b"PK\x03\x04", 22), (b"PK\x01\x02", 24)] {
+        let start: usize = bytes
+            .windows(4)
+            .position(|window| window == signature)
+            .unwrap();
+        bytes[start + offset..start + offset + 4].copy_from_slice(&[0; 4]);
+    }
+    bytes
+}
+
+#[test]
+fn test_uncompressed_limit_counts_inflated_bytes_not_declared_sizes() {
+    let bomb: Vec<u8> = understated_zip(1 << 20);
+    let limits = |max: u64| Limits {
+        max_uncompressed_bytes: Some(max),
+        ..Limits::default()
+    };
+    assert!(matches!(
+        limits::check_input(&bomb, &limits(1 << 16)),
+        Err(ConvertError::LimitExceeded(_))
+    ));
+    assert!(limits::check_input(&bomb, &limits(1 << 20)).is_ok());
+}
+
+#[test]
+fn test_uncompressed_limit_is_shared_by_all_entries() {
+    use std::io::Write;
+    // Four entries of 64 KiB each: every one fits the limit, together they
+    // do not.
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for index in 0..4 {
+        zip.start_file(format!("word/part{index}.xml"), options)
+            .unwrap();
+        zip.write_all(&[0; 1 << 16]).unwrap();
+    }
+    let data: Vec<u8> = zip.finish().unwrap().into_inner();
+    let limits = |max: u64| Limits {
+        max_uncompressed_bytes: Some(max),
+        ..Limits::default()
+    };
+    assert!(matches!(
+        limits::check_input(&data, &limits(3 << 16)),
+        Err(ConvertError::LimitExceeded(_))
+    ));
+    assert!(limits::check_input(&data, &limits(4 << 16)).is_ok());
+}
@@ -0,0 +1,206 @@
+//! Parallel compilation of slide decks, with the `rayon` feature: fixed
+//! pages never flow into each other, so a deck splits into batches of
+//! slides that are generated and compiled on the rayon thread pool and
+//! merged page by page afterwards.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::config::ConvertOptions;
+use crate::error::ConvertError;
+use crate::ir::{
+    Block, Document, FixedElement, FixedElementKind, Page, Paragraph, Table, page_link_target,
+};
+use crate::render::compile_cache::{self, CompileCache};
+use crate::render::font_context::FontSearchContext;
+use crate::render::pdf::{CompiledPdf, FontSources, MissingGlyph, PdfExport, TextOverflow};
+use crate::render::typst_gen::{PageNumbering, TypstOutput};
+use crate::{pdf_ops, render};
+
+/// Fewest slides in a batch. Every batch builds its own Typst world and
+/// loads its fonts, which a handful of slides does not pay back.
+const MIN_BATCH_PAGES: usize = 4;
+
+/// A deck compiled in batches.
+pub(super) struct ParallelOutput {
+    pub(super) pdf: Vec<u8>,
+    /// Codegen and compile time summed over the batches, so more than the
+    /// wall-clock time when they overlap.
+    pub(super) codegen_duration: Duration,
+    pub(super) compile_duration: Duration,
+    /// The batches' Typst sources, when `emit_typst_source` is set.
+    pub(super) typst_sources: Vec<TypstOutput>,
+    /// Overflowing text boxes of every batch. Codegen numbers their slides
+    /// from the batch offset, so they need no renumbering.
+    pub(super) overflows: Vec<TextOverflow>,
+    /// Characters without a glyph, numbered by page of the merged PDF.
+    pub(super) missing_glyphs: Vec<MissingGlyph>,
+}
+
+/// Whether `doc` is compiled in parallel: `parallel_compile` is set, every
+/// page is a slide, there are enough of them for two batches, and the
+/// output has no document-wide structure that merging would drop. Links
+/// between slides would point into the wrong batch.
+pub(super) fn applies(doc: &Document, options: &ConvertOptions) -> bool {
+    options.parallel_compile
+        && options.pdf_standard.is_none()
+        && !options.tagged
+        && !options.pdf_ua
+        && batch_size(doc.pages.len()).is_some()
+        && doc.pages.iter().all(|page| match page {
+            Page::Fixed(fixed) => !fixed.elements.iter().any(links_to_page),
+            Page::Flow(_) | Page::Sheet(_) => false,
+        })
+}
+
+fn links_to_page(element: &FixedElement) -> bool {
+    is_page_link(element.href.as_deref())
+        || match &element.kind {
+            FixedElementKind::TextBox(text_box) => blocks_link_to_page(&text_box.content),
+            FixedElementKind::Table(table) => table_links_to_page(table),
+            _ => false,
+        }
+}
+
+fn blocks_link_to_page(blocks: &[Block]) -> bool {
+    blocks.iter().any(|block| match block {
+        Block::Paragraph(paragraph) => paragraph_links_to_page(paragraph),
+        Block::Table(table) => table_links_to_page(table),
+        Block::List(list) => list
+            .items
+            .iter()
+            .flat_map(|item| &item.content)
+            .any(paragraph_links_to_page),
+        Block::FloatingTextBox(text_box) => blocks_link_to_page(&text_box.content),
+        _ => false,
+    })
+}
+
+fn table_links_to_page(table: &Table) -> bool {
+    table
+        .rows
+        .iter()
+        .flat_map(|row| &row.cells)
+        .any(|cell| blocks_link_to_page(&cell.content))
+}
+
+fn paragraph_links_to_page(paragraph: &Paragraph) -> bool {
+    paragraph
+        .runs
+        .iter()
+        .any(|run| is_page_link(run.href.as_deref()))
+}
+
+fn is_page_link(href: Option<&str>) -> bool {
+    href.and_then(page_link_target).is_some()
+}
+
+/// Slides per batch for a deck of `page_count`, or `None` when it is too
+/// small to split. Batches are about even, one per pool thread.
+fn batch_size(page_count: usize) -> Option<usize> {
+    let threads: usize = rayon::current_num_threads();
+    let size: usize = page_count.div_ceil(threads).max(MIN_BATCH_PAGES);
+    (threads > 1 && page_count > size).then_some(size)
+}
+
+/// Generate and compile `doc` in batches and merge them into one PDF.
+/// Callers check [`applies`] first.
+pub(super) fn compile(
+    doc: Document,
+    options: &ConvertOptions,
+    font_context: Option<&FontSearchContext>,
+) -> Result<ParallelOutput, ConvertError> {
+    let total_pages: u32 = doc.pages.len() as u32;
+    let size: usize = batch_size(doc.pages.len()).unwrap_or(doc.pages.len());
+    let Document {
+        metadata,
+        pages,
+        styles,
+    } = doc;
+    let mut batches: Vec<(u32, Document)> = Vec::new();
+    let mut pages = pages.into_iter().peekable();
+    let mut offset: u32 = 0;
+    while pages.peek().is_some() {
+        let batch: Vec<Page> = pages.by_ref().take(size).collect();
+        let batch_pages: u32 = batch.len() as u32;
+        batches.push((
+            offset,
+            Document {
+                metadata: metadata.clone(),
+                pages: batch,
+                styles: styles.clone(),
+            },
+        ));
+        offset += batch_pages;
+    }
+
+    let search_paths = font_context
+        .map(|context| context.search_paths())
+        .unwrap_or(&[]);
+    let export: PdfExport = PdfExport::from_options(options);
+    // A `Converter`'s cache is active on the calling thread only.
+    let cache: Option<Arc<CompileCache>> = compile_cache::active();
+    let compiled: Vec<(CompiledPdf, TypstOutput, Duration, Duration)> = batches
+        .into_par_iter()
+        .map(|(offset, batch)| {
+            let codegen_start: Instant = Instant::now();
+            // Slide numbers are resolved when parsing; the numbering only
+            // matters to header and footer fields.
+            let output: TypstOutput = render::typst_gen::generate_typst_part(
+                &batch,
+                options,
+                font_context,
+                PageNumbering {
+                    offset,
+                    total: Some(total_pages),
+                },
+            )?;
+            let codegen_duration: Duration = codegen_start.elapsed();
+            let compile_start: Instant = Instant::now();
+            let batch_pdf: CompiledPdf = compile_cache::with_compile_cache(cache.as_ref(), || {
+                render::pdf::compile_to_pdf_with(
+                    &output.source,
+                    &output.images,
+                    &FontSources::new(search_paths, &options.fonts),
+                    &export,
+                )
+            })?;
+            Ok((batch_pdf, output, codegen_duration, compile_start.elapsed()))
+        })
+        .collect::<Result<_, ConvertError>>()?;
+
+    let mut parallel = ParallelOutput {
+        pdf: Vec::new(),
+        codegen_duration: Duration::ZERO,
+        compile_duration: Duration::ZERO,
+        typst_sources: Vec::new(),
+        overflows: Vec::new(),
+        missing_glyphs: Vec::new(),
+    };
+    let mut pdfs: Vec<Vec<u8>> = Vec::with_capacity(compiled.len());
+    let mut pages_before: u32 = 0;
+    for (batch_pdf, output, codegen_duration, compile_duration) in compiled {
+        render::pdf::extend_missing_glyphs(
+            &mut parallel.missing_glyphs,
+            batch_pdf.missing_glyphs,
+            pages_before,
+        );
+        pages_before += batch_pdf.page_count;
+        pdfs.push(batch_pdf.pdf);
+        parallel.overflows.extend(batch_pdf.overflows);
+        parallel.codegen_duration += codegen_duration;
+        parallel.compile_duration += compile_duration;
+        if options.emit_typst_source {
+            parallel.typst_sources.push(output);
+        }
+    }
+    let parts: Vec<&[u8]> = pdfs.iter().map(Vec::as_slice).collect();
+    let merged: Vec<u8> = pdf_ops::merge(&parts)?;
+    // Every batch carries the document's title and author; the merge keeps
+    // none of them.
+    let merged: Vec<u8> = pdf_ops::copy_info(&merged, &pdfs[0])?;
+    parallel.pdf = pdf_ops::set_document_properties(&merged, &metadata)?;
+    Ok(parallel)
+}
@@ -0,0 +1,118 @@
+use super::config::ConvertOptions;
+use super::ir::*;
+use super::parallel::{applies, compile};
+use super::test_support::make_simple_document;
+
+fn slide(text: &str, href: Option<String>) -> Page {
+    Page::Fixed(FixedPage {
+        size: PageSize::default(),
+        elements: vec![FixedElement {
+            x: 36.0,
+            y: 36.0,
+            width: 300.0,
+            height: 40.0,
+            kind: FixedElementKind::TextBox(TextBoxData {
+                content: vec![Block::Paragraph(Paragraph {
+                    style: ParagraphStyle::default(),
+                    runs: vec![Run {
+                        text: text.to_string(),
+                        style: TextStyle::default(),
+                        href,
+                        footnote: None,
+                    }],
+                })],
+                padding: Insets::default(),
+                vertical_align: TextBoxVerticalAlign::Top,
+                fill: None,
+                opacity: None,
+                stroke: None,
+                shape_kind: None,
+                no_wrap: false,
+                auto_fit: false,
+                text_rotation_deg: None,
+            }),
+            href: None,
+        }],
+        background_color: None,
+        background_gradient: None,
+        background_image: None,
+        source_slide: None,
+    })
+}
+
+fn deck(slides: usize) -> Document {
+    Document {
+        metadata: Metadata {
+            title: Some("Deck".to_string()),
+            ..Metadata::default()
+        },
+        pages: (1..=slides)
+            .map(|n| slide(&format!("Slide {n}"), None))
+            .collect(),
+        styles: StyleSheet::default(),
+    }
+}
+
+/// Four threads whatever the machine, so decks split the same everywhere.
+fn on_four_threads<T: Send>(run: impl FnOnce() -> T + Send) -> T {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap()
+        .install(run)
+}
+
+fn parallel_options() -> ConvertOptions {
+    ConvertOptions {
+        parallel_compile: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_parallel_applies_only_to_large_enough_decks() {
+    on_four_threads(|| {
+        assert!(applies(&deck(16), &parallel_options()));
+        assert!(!applies(&deck(16), &ConvertOptions::default()));
+        // One batch's worth of slides is not worth splitting.
+        assert!(!applies(&deck(4), &parallel_options()));
+        assert!(!applies(&make_simple_document("Flow"), &parallel_options()));
+        let tagged = ConvertOptions {
+            tagged: true,
+            ..parallel_options()
+        };
+        assert!(!applies(&deck(16), &tagged));
+    });
+}
+
+#[test]
+fn test_parallel_skips_decks_with_slide_links() {
+    let mut linked: Document = deck(16);
+    linked.pages[0] = slide("Contents", Some(page_link(12)));
+    on_four_threads(|| assert!(!applies(&linked, &parallel_options())));
+}
+
+#[test]
+fn test_parallel_compile_keeps_slide_order_and_metadata() {
+    let options = ConvertOptions {
+        emit_typst_source: true,
+        ..parallel_options()
+    };
+    let output = on_four_threads(|| compile(deck(16), &options, None)).unwrap();
+    assert_eq!(output.typst_sources.len(), 4);
+    assert_eq!(crate::pdf_ops::page_count(&output.pdf).unwrap(), 16);
+
+    let pages: Vec<String> = crate::pdf_ops::extract_text(&output.pdf)
+        .unwrap()
+        .into_iter()
+        .map(|page| page.text)
+        .collect();
+    for (index, text) in pages.iter().enumerate() {
+        assert!(text.contains(&format!("Slide {}", index + 1)), "{text}");
+    }
+    let pdf = lopdf::Document::load_mem(&output.pdf).unwrap();
+    let info = pdf
+        .get_dictionary(pdf.trailer.get(b"Info").unwrap().as_reference().unwrap())
+        .unwrap();
+    assert_eq!(info.get(b"Title").unwrap().as_str().unwrap(), b"Deck");
+}
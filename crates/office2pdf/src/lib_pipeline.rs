@@ -1,14 +1,26 @@
+use std::borrow::Cow;
 use std::collections::HashSet;
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::Instant;
 #[cfg(target_arch = "wasm32")]
 use web_time::Instant;
 
-use crate::config::{ConvertOptions, Format};
-use crate::error::{ConvertError, ConvertMetrics, ConvertResult, ConvertWarning};
+use crate::config::{ConvertOptions, Format, Progress, ProgressStage, SlideImageOptions};
+use crate::error::{
+    ConvertError, ConvertMetrics, ConvertResult, ConvertWarning, ExtractedText, ImageOutput,
+    WarningLocation,
+};
+#[cfg(feature = "pdf-ops")]
+use crate::error::{SectionConvertResult, SectionPdf};
 use crate::parser::Parser;
+use crate::render::pdf::{CompiledPdf, FontSources, MissingGlyph, PdfExport, TextOverflow};
+use crate::render::typst_gen::TypstOutput;
 use crate::{ir, parser, render};
 
+#[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
+use super::parallel;
+use super::{limits, sanitize, trace};
+
 fn format_label(format: Format) -> &'static str {
     match format {
         Format::Docx => "DOCX",
@@ -22,28 +34,71 @@ fn dedup_warnings(warnings: &mut Vec<ConvertWarning>) {
     warnings.retain(|warning| seen.insert(warning.to_string()));
 }
 
+/// Fail with [`ConvertError::WarningThreshold`] on the first warning at or
+/// above `options.fail_on_warning_severity`. Called as soon as warnings are
+/// collected, so a rejected document is never rendered.
+pub(super) fn check_warning_severity(
+    warnings: &[ConvertWarning],
+    options: &ConvertOptions,
+) -> Result<(), ConvertError> {
+    let Some(threshold) = options.fail_on_warning_severity else {
+        return Ok(());
+    };
+    match warnings
+        .iter()
+        .find(|warning| warning.severity() >= threshold)
+    {
+        Some(warning) => Err(ConvertError::WarningThreshold(Box::new(warning.clone()))),
+        None => Ok(()),
+    }
+}
+
+/// Warnings for what only shows once the document is laid out: slide text
+/// boxes whose text came out taller than the box, and characters no font
+/// has a glyph for.
+fn layout_warnings(
+    format: Format,
+    overflows: Vec<TextOverflow>,
+    missing_glyphs: Vec<MissingGlyph>,
+    options: &ConvertOptions,
+) -> impl Iterator<Item = ConvertWarning> {
+    let replacement: Option<char> = options.missing_glyph_replacement;
+    let overflow_warnings =
+        overflows
+            .into_iter()
+            .map(move |overflow| ConvertWarning::ContentOverflow {
+                format: format_label(format).to_string(),
+                slide: overflow.slide,
+                element: overflow.element,
+                location: overflow.source_slide.map(WarningLocation::Slide),
+            });
+    let glyph_warnings =
+        missing_glyphs
+            .into_iter()
+            .map(move |glyph| ConvertWarning::MissingGlyph {
+                format: format_label(format).to_string(),
+                character: glyph.character,
+                page: glyph.page,
+                text: glyph.text,
+                replacement,
+            });
+    overflow_warnings.chain(glyph_warnings)
+}
+
 /// Build a `ConvertResult`, deduplicating warnings automatically so callers
 /// don't need to remember to call `dedup_warnings` before every return site.
-fn build_convert_result(
+pub(super) fn build_convert_result(
     pdf: Vec<u8>,
     mut warnings: Vec<ConvertWarning>,
     metrics: Option<ConvertMetrics>,
+    typst_sources: Vec<TypstOutput>,
 ) -> ConvertResult {
     dedup_warnings(&mut warnings);
     ConvertResult {
         pdf,
         warnings,
         metrics,
-    }
-}
-
-fn extract_panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
-    if let Some(s) = payload.downcast_ref::<String>() {
-        s.clone()
-    } else if let Some(s) = payload.downcast_ref::<&str>() {
-        (*s).to_string()
-    } else {
-        "unknown panic".to_string()
+        typst_sources,
     }
 }
 
@@ -53,6 +108,22 @@ pub(super) fn is_ole2(data: &[u8]) -> bool {
     data.len() >= OLE2_MAGIC.len() && data[..OLE2_MAGIC.len()] == OLE2_MAGIC
 }
 
+/// The OOXML package to parse: `data` itself, or for an encrypted file
+/// (an OLE2 container) the package decrypted with
+/// [`ConvertOptions::input_password`].
+pub(super) fn decrypted_input<'a>(
+    data: &'a [u8],
+    options: &ConvertOptions,
+) -> Result<Cow<'a, [u8]>, ConvertError> {
+    if !is_ole2(data) {
+        return Ok(Cow::Borrowed(data));
+    }
+    match &options.input_password {
+        Some(password) => Ok(Cow::Owned(parser::encryption::decrypt(data, password)?)),
+        None => Err(ConvertError::UnsupportedEncryption),
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub(super) fn should_resolve_font_context(
     doc: &ir::Document,
@@ -61,6 +132,8 @@ pub(super) fn should_resolve_font_context(
 ) -> bool {
     has_embedded_fonts
         || !options.font_paths.is_empty()
+        || !options.fonts.data.is_empty()
+        || options.fonts.disable_system_fonts
         || render::font_subst::document_requests_font_families(doc)
 }
 
@@ -74,17 +147,142 @@ fn resolve_font_context_with_embedded(
     if !should_resolve_font_context(doc, options, has_embedded) {
         return None;
     }
+    let Some(dir) = embedded_font_dir.filter(|dir| !dir.is_empty()) else {
+        return Some(options_font_context(options));
+    };
     let mut all_paths: Vec<std::path::PathBuf> = options.font_paths.clone();
-    if let Some(dir) = embedded_font_dir
-        && !dir.is_empty()
-    {
-        all_paths.push(dir.path().to_path_buf());
-    }
+    all_paths.push(dir.path().to_path_buf());
     Some(render::font_context::resolve_font_search_context(
         &all_paths,
+        &options.fonts,
     ))
 }
 
+/// The font context of `options`' own font paths, resolved once per
+/// `Converter` when one is converting.
+#[cfg(not(target_arch = "wasm32"))]
+fn options_font_context(options: &ConvertOptions) -> render::font_context::FontSearchContext {
+    let resolve =
+        || render::font_context::resolve_font_search_context(&options.font_paths, &options.fonts);
+    match render::compile_cache::active() {
+        Some(cache) => cache.font_context(resolve),
+        None => resolve(),
+    }
+}
+
+/// Parse `data` into the IR with the parser for `format`, turning parser
+/// panics into [`ConvertError::Parse`], then apply `options.transform`.
+pub(super) fn parse_document(
+    data: &[u8],
+    format: Format,
+    options: &ConvertOptions,
+) -> Result<(ir::Document, Vec<ConvertWarning>), ConvertError> {
+    let _span = trace::stage(ProgressStage::Parse);
+    limits::check_input(data, &options.limits)?;
+    let parser: Box<dyn Parser> = match format {
+        Format::Docx => Box::new(parser::docx::DocxParser),
+        Format::Pptx => Box::new(parser::pptx::PptxParser),
+        Format::Xlsx => Box::new(parser::xlsx::XlsxParser),
+    };
+
+    let parse_result =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| parser.parse(data, options)));
+    let (mut doc, mut warnings) = match parse_result {
+        Ok(result) => result?,
+        Err(panic_info) => {
+            return Err(ConvertError::Parse(format!(
+                "upstream parser panicked: {}",
+                parser::extract_panic_message(&panic_info)
+            )));
+        }
+    };
+    check_warning_severity(&warnings, options)?;
+    warnings.extend(prepare_document(&mut doc, format, options));
+    limits::check_documents([&doc], &options.limits)?;
+    Ok((doc, warnings))
+}
+
+/// Tell `options.progress`, if set, that `current` of `total` units of
+/// `stage` are done.
+pub(super) fn report_progress(
+    options: &ConvertOptions,
+    stage: ProgressStage,
+    current: u32,
+    total: u32,
+    start: Instant,
+) {
+    if let Some(progress) = &options.progress {
+        progress.report(Progress {
+            stage,
+            current,
+            total,
+            elapsed: start.elapsed(),
+        });
+    }
+}
+
+/// Run `options.transform`, if any, on a freshly parsed document, apply
+/// `options.metadata_override`, then sanitize its text per
+/// `options.text_sanitization`.
+pub(super) fn prepare_document(
+    doc: &mut ir::Document,
+    format: Format,
+    options: &ConvertOptions,
+) -> Vec<ConvertWarning> {
+    if let Some(transform) = &options.transform {
+        transform.apply(doc);
+    }
+    if let Some(metadata) = &options.metadata_override {
+        metadata.apply(&mut doc.metadata);
+    }
+    sanitize::sanitize_document(doc, options.text_sanitization, format_label(format))
+}
+
+pub(super) fn parse_bytes(
+    data: &[u8],
+    format: Format,
+    options: &ConvertOptions,
+) -> Result<(ir::Document, Vec<ConvertWarning>), ConvertError> {
+    let decrypted: Cow<[u8]> = decrypted_input(data, options)?;
+    let data: &[u8] = &decrypted;
+    let (doc, mut warnings) = parse_document(data, format, options)?;
+    dedup_warnings(&mut warnings);
+    Ok((doc, warnings))
+}
+
+pub(super) fn extract_text(
+    data: &[u8],
+    format: Format,
+    options: &ConvertOptions,
+) -> Result<ExtractedText, ConvertError> {
+    let (doc, warnings) = parse_bytes(data, format, options)?;
+    Ok(ExtractedText {
+        text: doc.to_plain_text(),
+        markdown: doc.to_markdown(),
+        warnings,
+    })
+}
+
+/// Typst codegen with the font family substitution [`convert_bytes`] applies.
+pub(super) fn generate_typst(
+    doc: &ir::Document,
+    options: &ConvertOptions,
+) -> Result<render::typst_gen::TypstOutput, ConvertError> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let font_context = resolve_font_context_with_embedded(doc, options, None);
+        render::typst_gen::generate_typst_with_options_and_font_context(
+            doc,
+            options,
+            font_context.as_ref(),
+        )
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        render::typst_gen::generate_typst_with_options(doc, options)
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub(super) fn convert(path: impl AsRef<std::path::Path>) -> Result<ConvertResult, ConvertError> {
     convert_with_options(path, &ConvertOptions::default())
@@ -105,6 +303,9 @@ pub(super) fn convert_with_options(
         .ok_or_else(|| ConvertError::UnsupportedFormat(ext.to_string()))?;
 
     let data = std::fs::read(path)?;
+    // The extension decides which files are accepted; the contents, when
+    // recognizable, decide how they are parsed.
+    let format: Format = Format::detect(&data).unwrap_or(format);
     convert_bytes(&data, format, options)
 }
 
@@ -113,16 +314,204 @@ pub(super) fn convert_bytes(
     format: Format,
     options: &ConvertOptions,
 ) -> Result<ConvertResult, ConvertError> {
-    if is_ole2(data) {
-        return Err(ConvertError::UnsupportedEncryption);
+    convert_bytes_with_checkpoint(data, format, options, &|| Ok(()))
+}
+
+pub(super) fn convert_bytes_auto(
+    data: &[u8],
+    options: &ConvertOptions,
+) -> Result<ConvertResult, ConvertError> {
+    if is_ole2(data)
+        && let Some(legacy) = parser::sniff::legacy_format(data)
+    {
+        return Err(ConvertError::UnsupportedFormat(format!(
+            "{legacy}; save it as DOCX, PPTX or XLSX"
+        )));
     }
+    let decrypted: Cow<[u8]> = decrypted_input(data, options)?;
+    let format: Format = Format::detect(&decrypted).ok_or_else(|| {
+        ConvertError::UnsupportedFormat("not a DOCX, PPTX or XLSX document".to_string())
+    })?;
+    convert_bytes(&decrypted, format, options)
+}
+
+/// [`convert_bytes`] calling `checkpoint` between stages: after parsing,
+/// before Typst compilation, when streaming before each chunk, and after
+/// compilation before the passes over the finished PDF. An error from it
+/// abandons the conversion there, which is how cancellation stops work a
+/// Typst compile cannot be interrupted in the middle of.
+pub(super) fn convert_bytes_with_checkpoint(
+    data: &[u8],
+    format: Format,
+    options: &ConvertOptions,
+    checkpoint: &dyn Fn() -> Result<(), ConvertError>,
+) -> Result<ConvertResult, ConvertError> {
+    trace::conversion(format, data.len(), || {
+        let mut result: ConvertResult = convert_bytes_stages(data, format, options, checkpoint)?;
+        checkpoint()?;
+        result.pdf = finish_pdf(result.pdf, &[(data, format)], options)?;
+        if let Some(metrics) = result.metrics.as_mut() {
+            metrics.output_size_bytes = result.pdf.len() as u64;
+        }
+        Ok(result)
+    })
+}
 
+/// The last passes over a converted `pdf`, converted from `sources`: the
+/// stamp, placed on the page as converted, the bleed, the attached sources,
+/// linearization or compaction, then encryption and signing, which must
+/// cover everything before them.
+pub(super) fn finish_pdf(
+    pdf: Vec<u8>,
+    sources: &[(&[u8], Format)],
+    options: &ConvertOptions,
+) -> Result<Vec<u8>, ConvertError> {
+    let pdf: Vec<u8> = stamp_pdf(pdf, options)?;
+    let pdf: Vec<u8> = bleed_pdf(pdf, options)?;
+    let pdf: Vec<u8> = embed_sources(pdf, sources, options)?;
+    let pdf: Vec<u8> = optimize_pdf(pdf, options)?;
+    secure_pdf(pdf, options)
+}
+
+/// `pdf` with [`ConvertOptions::stamp`] drawn over its pages.
+fn stamp_pdf(pdf: Vec<u8>, options: &ConvertOptions) -> Result<Vec<u8>, ConvertError> {
+    let Some(stamp) = &options.stamp else {
+        return Ok(pdf);
+    };
+    // Refused rather than left out, for options that skipped `validate`.
+    if !cfg!(feature = "pdf-ops") {
+        return Err(ConvertError::InvalidOptions(
+            "stamp requires the pdf-ops feature".to_string(),
+        ));
+    }
     #[cfg(feature = "pdf-ops")]
-    if options.streaming && format == Format::Xlsx {
-        return convert_bytes_streaming_xlsx(data, options);
+    let pdf: Vec<u8> = crate::pdf_ops::stamp(&pdf, stamp)?;
+    #[cfg(not(feature = "pdf-ops"))]
+    let _ = stamp;
+    Ok(pdf)
+}
+
+/// `pdf` with the bleed and crop marks of [`ConvertOptions::bleed`].
+fn bleed_pdf(pdf: Vec<u8>, options: &ConvertOptions) -> Result<Vec<u8>, ConvertError> {
+    let Some(bleed) = &options.bleed else {
+        return Ok(pdf);
+    };
+    // Refused rather than left out, for options that skipped `validate`.
+    if !cfg!(feature = "pdf-ops") {
+        return Err(ConvertError::InvalidOptions(
+            "bleed requires the pdf-ops feature".to_string(),
+        ));
     }
+    #[cfg(feature = "pdf-ops")]
+    let pdf: Vec<u8> = crate::pdf_ops::bleed(&pdf, bleed)?;
+    #[cfg(not(feature = "pdf-ops"))]
+    let _ = bleed;
+    Ok(pdf)
+}
+
+/// `pdf` with `sources` attached when [`ConvertOptions::embed_source`] asks.
+fn embed_sources(
+    pdf: Vec<u8>,
+    sources: &[(&[u8], Format)],
+    options: &ConvertOptions,
+) -> Result<Vec<u8>, ConvertError> {
+    if !options.embed_source {
+        return Ok(pdf);
+    }
+    // Refused rather than left out, for options that skipped `validate`.
+    if !cfg!(feature = "pdf-ops") {
+        return Err(ConvertError::InvalidOptions(
+            "embed_source requires the pdf-ops feature".to_string(),
+        ));
+    }
+    #[cfg(feature = "pdf-ops")]
+    let pdf: Vec<u8> = crate::pdf_ops::attach_sources(&pdf, sources)?;
+    #[cfg(not(feature = "pdf-ops"))]
+    let _ = sources;
+    Ok(pdf)
+}
+
+/// `pdf` linearized or compacted as [`ConvertOptions::linearize`] and
+/// [`ConvertOptions::compact`] ask.
+fn optimize_pdf(pdf: Vec<u8>, options: &ConvertOptions) -> Result<Vec<u8>, ConvertError> {
+    if !options.linearize && !options.compact {
+        return Ok(pdf);
+    }
+    // Refused rather than left out, for options that skipped `validate`.
+    if !cfg!(feature = "pdf-ops") {
+        return Err(ConvertError::InvalidOptions(
+            "linearize and compact require the pdf-ops feature".to_string(),
+        ));
+    }
+    #[cfg(feature = "pdf-ops")]
+    let pdf: Vec<u8> = if options.linearize {
+        crate::pdf_ops::linearize(&pdf)?
+    } else {
+        crate::pdf_ops::compact(&pdf)?
+    };
+    Ok(pdf)
+}
+
+/// `pdf` encrypted or signed as [`ConvertOptions::pdf_security`] and
+/// [`ConvertOptions::signing`] ask. Applied to finished output only:
+/// merging, splitting and page counting need to read the PDF without a
+/// password, and any later change would break the signature.
+fn secure_pdf(pdf: Vec<u8>, options: &ConvertOptions) -> Result<Vec<u8>, ConvertError> {
+    // Refused rather than written in the clear or unsigned, for options
+    // that skipped `validate`.
+    if options.pdf_security.is_some() && !cfg!(feature = "pdf-ops") {
+        return Err(ConvertError::InvalidOptions(
+            "pdf_security requires the pdf-ops feature".to_string(),
+        ));
+    }
+    if options.signing.is_some() && !cfg!(feature = "sign") {
+        return Err(ConvertError::InvalidOptions(
+            "signing requires the sign feature".to_string(),
+        ));
+    }
+    if options.pdf_security.is_some() && options.deterministic {
+        return Err(ConvertError::InvalidOptions(
+            "deterministic output cannot be encrypted".to_string(),
+        ));
+    }
+    #[cfg(feature = "pdf-ops")]
+    let pdf: Vec<u8> = match &options.pdf_security {
+        Some(security) => crate::pdf_ops::encrypt(&pdf, security)?,
+        None => pdf,
+    };
+    #[cfg(feature = "sign")]
+    let pdf: Vec<u8> = match &options.signing {
+        Some(signing) if options.deterministic => {
+            crate::pdf_ops::sign_at(&pdf, signing, &crate::render::pdf::epoch_datetime()?)?
+        }
+        Some(signing) => crate::pdf_ops::sign(&pdf, signing)?,
+        None => pdf,
+    };
+    Ok(pdf)
+}
+
+fn convert_bytes_stages(
+    data: &[u8],
+    format: Format,
+    options: &ConvertOptions,
+    checkpoint: &dyn Fn() -> Result<(), ConvertError>,
+) -> Result<ConvertResult, ConvertError> {
+    let decrypted: Cow<[u8]> = decrypted_input(data, options)?;
+    let data: &[u8] = &decrypted;
+    // Before embedded fonts are inflated, not only before parsing.
+    limits::check_input(data, &options.limits)?;
 
     let total_start: Instant = Instant::now();
+    let checkpoint = || -> Result<(), ConvertError> {
+        limits::check_deadline(total_start, &options.limits)?;
+        checkpoint()
+    };
+
+    #[cfg(feature = "pdf-ops")]
+    if options.streaming && format == Format::Xlsx {
+        return convert_bytes_streaming_xlsx(data, options, &checkpoint);
+    }
+
     let input_size_bytes = data.len() as u64;
 
     // Extract embedded fonts before parsing (PPTX/DOCX only).
@@ -131,26 +520,13 @@ pub(super) fn convert_bytes(
     #[cfg(not(target_arch = "wasm32"))]
     let embedded_font_dir = parser::embedded_fonts::extract_embedded_fonts(data, format);
 
-    let parser: Box<dyn Parser> = match format {
-        Format::Docx => Box::new(parser::docx::DocxParser),
-        Format::Pptx => Box::new(parser::pptx::PptxParser),
-        Format::Xlsx => Box::new(parser::xlsx::XlsxParser),
-    };
-
     let parse_start: Instant = Instant::now();
-    let parse_result =
-        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| parser.parse(data, options)));
-    let (doc, mut warnings) = match parse_result {
-        Ok(result) => result?,
-        Err(panic_info) => {
-            return Err(ConvertError::Parse(format!(
-                "upstream parser panicked: {}",
-                extract_panic_message(&panic_info)
-            )));
-        }
-    };
+    report_progress(options, ProgressStage::Parse, 0, 1, total_start);
+    let (doc, mut warnings) = parse_document(data, format, options)?;
     let parse_duration = parse_start.elapsed();
     let page_count = doc.pages.len() as u32;
+    report_progress(options, ProgressStage::Parse, 1, 1, total_start);
+    checkpoint()?;
 
     #[cfg(not(target_arch = "wasm32"))]
     let font_context =
@@ -159,13 +535,18 @@ pub(super) fn convert_bytes(
     #[cfg(not(target_arch = "wasm32"))]
     if let Some(font_context) = font_context.as_ref() {
         warnings.extend(
-            render::font_subst::detect_missing_font_fallbacks_with_context(&doc, font_context)
-                .into_iter()
-                .map(|(from, to)| ConvertWarning::FallbackUsed {
-                    format: format_label(format).to_string(),
-                    from,
-                    to,
-                }),
+            render::font_subst::detect_missing_font_fallbacks_with_context(
+                &doc,
+                font_context,
+                &options.fonts,
+            )
+            .into_iter()
+            .map(|(from, to)| ConvertWarning::FallbackUsed {
+                format: format_label(format).to_string(),
+                from,
+                to,
+                location: None,
+            }),
         );
     }
 
@@ -177,10 +558,52 @@ pub(super) fn convert_bytes(
                 format: format_label(format).to_string(),
                 from,
                 to,
+                location: None,
             }),
     );
+    // Font fallbacks are only known once fonts are resolved, after parsing.
+    check_warning_severity(&warnings, options)?;
+
+    #[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
+    if parallel::applies(&doc, options) {
+        let compile_span = trace::stage(ProgressStage::Compile);
+        report_progress(options, ProgressStage::Compile, 0, page_count, total_start);
+        let output = parallel::compile(doc, options, font_context.as_ref())?;
+        drop(compile_span);
+        warnings.extend(layout_warnings(
+            format,
+            output.overflows,
+            output.missing_glyphs,
+            options,
+        ));
+        check_warning_severity(&warnings, options)?;
+        report_progress(
+            options,
+            ProgressStage::Compile,
+            page_count,
+            page_count,
+            total_start,
+        );
+        let output_size_bytes = output.pdf.len() as u64;
+        return Ok(build_convert_result(
+            output.pdf,
+            warnings,
+            Some(ConvertMetrics {
+                parse_duration,
+                codegen_duration: output.codegen_duration,
+                compile_duration: output.compile_duration,
+                total_duration: total_start.elapsed(),
+                input_size_bytes,
+                output_size_bytes,
+                page_count,
+            }),
+            output.typst_sources,
+        ));
+    }
 
     let codegen_start: Instant = Instant::now();
+    let codegen_span = trace::stage(ProgressStage::Codegen);
+    report_progress(options, ProgressStage::Codegen, 0, page_count, total_start);
     #[cfg(not(target_arch = "wasm32"))]
     let output = render::typst_gen::generate_typst_with_options_and_font_context(
         &doc,
@@ -190,33 +613,75 @@ pub(super) fn convert_bytes(
     #[cfg(target_arch = "wasm32")]
     let output = render::typst_gen::generate_typst_with_options(&doc, options)?;
     let codegen_duration = codegen_start.elapsed();
+    drop(codegen_span);
+    report_progress(
+        options,
+        ProgressStage::Codegen,
+        page_count,
+        page_count,
+        total_start,
+    );
+    checkpoint()?;
 
     let compile_start: Instant = Instant::now();
+    let compile_span = trace::stage(ProgressStage::Compile);
+    report_progress(options, ProgressStage::Compile, 0, page_count, total_start);
     #[cfg(not(target_arch = "wasm32"))]
-    let pdf = render::pdf::compile_to_pdf(
+    let compiled = render::pdf::compile_to_pdf_with(
         &output.source,
         &output.images,
-        options.pdf_standard,
-        font_context
-            .as_ref()
-            .map(|context| context.search_paths())
-            .unwrap_or(&[]),
-        options.tagged,
-        options.pdf_ua,
+        &FontSources::new(
+            font_context
+                .as_ref()
+                .map(|context| context.search_paths())
+                .unwrap_or(&[]),
+            &options.fonts,
+        ),
+        &PdfExport::from_options(options),
     )?;
     #[cfg(target_arch = "wasm32")]
-    let pdf = render::pdf::compile_to_pdf(
+    let compiled = render::pdf::compile_to_pdf_with(
         &output.source,
         &output.images,
-        options.pdf_standard,
-        &options.font_paths,
-        options.tagged,
-        options.pdf_ua,
+        &FontSources::new(&options.font_paths, &options.fonts),
+        &PdfExport::from_options(options),
     )?;
+    #[cfg(feature = "pdf-ops")]
+    let pdf: Vec<u8> = crate::pdf_ops::set_document_properties(&compiled.pdf, &doc.metadata)?;
+    #[cfg(not(feature = "pdf-ops"))]
+    let pdf: Vec<u8> = compiled.pdf;
+    warnings.extend(layout_warnings(
+        format,
+        compiled.overflows,
+        compiled.missing_glyphs,
+        options,
+    ));
+    check_warning_severity(&warnings, options)?;
     let compile_duration = compile_start.elapsed();
+    drop(compile_span);
+    report_progress(
+        options,
+        ProgressStage::Compile,
+        page_count,
+        page_count,
+        total_start,
+    );
+    // Flow documents only know their page count once laid out.
+    #[cfg(feature = "pdf-ops")]
+    if options.limits.max_pages.is_some() {
+        limits::check_page_count(
+            u64::from(crate::pdf_ops::page_count(&pdf)?),
+            &options.limits,
+        )?;
+    }
 
     let total_duration = total_start.elapsed();
     let output_size_bytes = pdf.len() as u64;
+    let typst_sources: Vec<TypstOutput> = if options.emit_typst_source {
+        vec![output]
+    } else {
+        Vec::new()
+    };
 
     Ok(build_convert_result(
         pdf,
@@ -230,13 +695,42 @@ pub(super) fn convert_bytes(
             output_size_bytes,
             page_count,
         }),
+        typst_sources,
     ))
 }
 
+/// Header and footer fields of a document's sheet pages.
+#[cfg(feature = "pdf-ops")]
+fn sheet_hf_fields(doc: &ir::Document) -> impl Iterator<Item = &ir::HFInline> {
+    doc.pages
+        .iter()
+        .filter_map(|page| match page {
+            ir::Page::Sheet(sheet_page) => Some(sheet_page),
+            _ => None,
+        })
+        .flat_map(|sheet_page| sheet_page.header.iter().chain(&sheet_page.footer))
+        .flat_map(|header_footer| &header_footer.paragraphs)
+        .flat_map(|paragraph| &paragraph.elements)
+}
+
+/// Whether a document prints page numbers or the page count.
+#[cfg(feature = "pdf-ops")]
+fn uses_page_fields(doc: &ir::Document) -> bool {
+    sheet_hf_fields(doc)
+        .any(|field| matches!(field, ir::HFInline::PageNumber | ir::HFInline::TotalPages))
+}
+
+/// Whether a document prints the page count.
+#[cfg(feature = "pdf-ops")]
+fn uses_total_pages(doc: &ir::Document) -> bool {
+    sheet_hf_fields(doc).any(|field| matches!(field, ir::HFInline::TotalPages))
+}
+
 #[cfg(feature = "pdf-ops")]
 fn convert_bytes_streaming_xlsx(
     data: &[u8],
     options: &ConvertOptions,
+    checkpoint: &dyn Fn() -> Result<(), ConvertError>,
 ) -> Result<ConvertResult, ConvertError> {
     let total_start: Instant = Instant::now();
     let input_size_bytes = data.len() as u64;
@@ -247,19 +741,29 @@ fn convert_bytes_streaming_xlsx(
     let xlsx_parser = parser::xlsx::XlsxParser;
 
     let parse_start: Instant = Instant::now();
+    let parse_span = trace::stage(ProgressStage::Parse);
+    report_progress(options, ProgressStage::Parse, 0, 1, total_start);
+    limits::check_input(data, &options.limits)?;
     let parse_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
         xlsx_parser.parse_streaming(data, options, chunk_size)
     }));
-    let (chunk_docs, warnings) = match parse_result {
+    let (mut chunk_docs, mut warnings) = match parse_result {
         Ok(result) => result?,
         Err(panic_info) => {
             return Err(ConvertError::Parse(format!(
                 "upstream parser panicked: {}",
-                extract_panic_message(&panic_info)
+                parser::extract_panic_message(&panic_info)
             )));
         }
     };
+    check_warning_severity(&warnings, options)?;
+    for chunk_doc in &mut chunk_docs {
+        warnings.extend(prepare_document(chunk_doc, Format::Xlsx, options));
+    }
+    limits::check_documents(&chunk_docs, &options.limits)?;
     let parse_duration = parse_start.elapsed();
+    drop(parse_span);
+    report_progress(options, ProgressStage::Parse, 1, 1, total_start);
 
     if chunk_docs.is_empty() {
         let empty_doc = ir::Document {
@@ -305,68 +809,153 @@ fn convert_bytes_streaming_xlsx(
                 output_size_bytes: 0,
                 page_count: 0,
             }),
+            if options.emit_typst_source {
+                vec![output]
+            } else {
+                Vec::new()
+            },
         ));
     }
 
     let mut all_pdfs: Vec<Vec<u8>> = Vec::with_capacity(chunk_docs.len());
+    let mut typst_sources: Vec<TypstOutput> = Vec::new();
     let mut codegen_duration_total = std::time::Duration::ZERO;
     let mut compile_duration_total = std::time::Duration::ZERO;
     let mut total_page_count: u32 = 0;
+    let mut missing_glyphs: Vec<MissingGlyph> = Vec::new();
+    let mut pages_before: u32 = 0;
 
     #[cfg(not(target_arch = "wasm32"))]
     let font_context = if options.font_paths.is_empty()
+        && options.fonts.data.is_empty()
+        && !options.fonts.disable_system_fonts
         && !chunk_docs
             .iter()
             .any(render::font_subst::document_requests_font_families)
     {
         None
     } else {
-        Some(render::font_context::resolve_font_search_context(
-            &options.font_paths,
-        ))
+        Some(options_font_context(options))
     };
 
-    for chunk_doc in chunk_docs {
-        total_page_count += chunk_doc.pages.len() as u32;
-
+    let mut render_chunk = |chunk_doc: &ir::Document,
+                            page_numbering: render::typst_gen::PageNumbering|
+     -> Result<(CompiledPdf, TypstOutput), ConvertError> {
+        checkpoint()?;
         let codegen_start: Instant = Instant::now();
+        let codegen_span = trace::stage(ProgressStage::Codegen);
         #[cfg(not(target_arch = "wasm32"))]
-        let output = render::typst_gen::generate_typst_with_options_and_font_context(
-            &chunk_doc,
+        let output = render::typst_gen::generate_typst_part(
+            chunk_doc,
             options,
             font_context.as_ref(),
+            page_numbering,
         )?;
         #[cfg(target_arch = "wasm32")]
-        let output = render::typst_gen::generate_typst_with_options(&chunk_doc, options)?;
+        let output =
+            render::typst_gen::generate_typst_part(chunk_doc, options, None, page_numbering)?;
         codegen_duration_total += codegen_start.elapsed();
+        drop(codegen_span);
 
         let compile_start: Instant = Instant::now();
+        let _compile_span = trace::stage(ProgressStage::Compile);
         #[cfg(not(target_arch = "wasm32"))]
-        let pdf = render::pdf::compile_to_pdf(
+        let compiled = render::pdf::compile_to_pdf_with(
             &output.source,
             &output.images,
-            options.pdf_standard,
-            font_context
-                .as_ref()
-                .map(|context| context.search_paths())
-                .unwrap_or(&[]),
-            options.tagged,
-            options.pdf_ua,
+            &FontSources::new(
+                font_context
+                    .as_ref()
+                    .map(|context| context.search_paths())
+                    .unwrap_or(&[]),
+                &options.fonts,
+            ),
+            &PdfExport::from_options(options),
         )?;
         #[cfg(target_arch = "wasm32")]
-        let pdf = render::pdf::compile_to_pdf(
+        let compiled = render::pdf::compile_to_pdf_with(
             &output.source,
             &output.images,
-            options.pdf_standard,
-            &options.font_paths,
-            options.tagged,
-            options.pdf_ua,
+            &FontSources::new(&options.font_paths, &options.fonts),
+            &PdfExport::from_options(options),
         )?;
         compile_duration_total += compile_start.elapsed();
+        Ok((compiled, output))
+    };
 
-        all_pdfs.push(pdf);
+    // Chunks compile separately, so header/footer page numbers would restart
+    // in every chunk. Each chunk continues from the pages before it; the
+    // page count is only known once all are compiled, so chunks that print
+    // it are rendered again with the total.
+    let numbered: bool = chunk_docs.len() > 1 && chunk_docs.iter().any(uses_page_fields);
+    let mut page_offsets: Vec<u32> = Vec::with_capacity(chunk_docs.len());
+    let mut page_offset: u32 = 0;
+    let chunk_count: u32 = chunk_docs.len() as u32;
+    for (index, chunk_doc) in chunk_docs.iter().enumerate() {
+        report_progress(
+            options,
+            ProgressStage::Compile,
+            index as u32,
+            chunk_count,
+            total_start,
+        );
+        total_page_count += chunk_doc.pages.len() as u32;
+        page_offsets.push(page_offset);
+        let (compiled, output) = render_chunk(
+            chunk_doc,
+            render::typst_gen::PageNumbering {
+                offset: page_offset,
+                total: None,
+            },
+        )?;
+        render::pdf::extend_missing_glyphs(
+            &mut missing_glyphs,
+            compiled.missing_glyphs,
+            pages_before,
+        );
+        pages_before += compiled.page_count;
+        if numbered {
+            page_offset += compiled.page_count;
+        }
+        all_pdfs.push(compiled.pdf);
+        if options.emit_typst_source {
+            typst_sources.push(output);
+        }
+    }
+    report_progress(
+        options,
+        ProgressStage::Compile,
+        chunk_count,
+        chunk_count,
+        total_start,
+    );
+    if numbered {
+        for (index, chunk_doc) in chunk_docs.iter().enumerate() {
+            if uses_total_pages(chunk_doc) {
+                let (compiled, output) = render_chunk(
+                    chunk_doc,
+                    render::typst_gen::PageNumbering {
+                        offset: page_offsets[index],
+                        total: Some(page_offset),
+                    },
+                )?;
+                all_pdfs[index] = compiled.pdf;
+                if options.emit_typst_source {
+                    typst_sources[index] = output;
+                }
+            }
+        }
     }
 
+    // The re-rendered chunks only differ in their page numbers.
+    warnings.extend(layout_warnings(
+        Format::Xlsx,
+        Vec::new(),
+        missing_glyphs,
+        options,
+    ));
+    check_warning_severity(&warnings, options)?;
+
     let final_pdf = if all_pdfs.len() == 1 {
         // Safety: len() == 1 guarantees at least one element
         all_pdfs
@@ -374,9 +963,13 @@ fn convert_bytes_streaming_xlsx(
             .next()
             .expect("all_pdfs is non-empty (len == 1)")
     } else {
+        report_progress(options, ProgressStage::Merge, 0, 1, total_start);
+        let _merge_span = trace::stage(ProgressStage::Merge);
         let refs: Vec<&[u8]> = all_pdfs.iter().map(|p| p.as_slice()).collect();
-        crate::pdf_ops::merge(&refs)
-            .map_err(|e| ConvertError::Render(format!("PDF merge failed: {e}")))?
+        let merged: Vec<u8> = crate::pdf_ops::merge(&refs)
+            .map_err(|e| ConvertError::Render(format!("PDF merge failed: {e}")))?;
+        report_progress(options, ProgressStage::Merge, 1, 1, total_start);
+        merged
     };
 
     let total_duration = total_start.elapsed();
@@ -394,9 +987,225 @@ fn convert_bytes_streaming_xlsx(
             output_size_bytes,
             page_count: total_page_count,
         }),
+        typst_sources,
     ))
 }
 
+/// Convert a PPTX presentation and split the PDF at section boundaries.
+///
+/// The deck is compiled once and then split, so fonts and images shared
+/// between sections are only processed a single time.
+#[cfg(feature = "pdf-ops")]
+pub(super) fn convert_pptx_sections(
+    data: &[u8],
+    options: &ConvertOptions,
+) -> Result<SectionConvertResult, ConvertError> {
+    let source: &[u8] = data;
+    let decrypted: Cow<[u8]> = decrypted_input(data, options)?;
+    let data: &[u8] = &decrypted;
+    limits::check_input(data, &options.limits)?;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let embedded_font_dir = parser::embedded_fonts::extract_embedded_fonts(data, Format::Pptx);
+
+    let parse_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        parser::pptx::PptxParser.parse_with_sections(data, options)
+    }));
+    let (mut doc, mut warnings, sections) = match parse_result {
+        Ok(result) => result?,
+        Err(panic_info) => {
+            return Err(ConvertError::Parse(format!(
+                "upstream parser panicked: {}",
+                parser::extract_panic_message(&panic_info)
+            )));
+        }
+    };
+    check_warning_severity(&warnings, options)?;
+    warnings.extend(prepare_document(&mut doc, Format::Pptx, options));
+    limits::check_documents([&doc], &options.limits)?;
+    dedup_warnings(&mut warnings);
+    if sections.is_empty() {
+        return Ok(SectionConvertResult {
+            sections: Vec::new(),
+            warnings,
+        });
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let compiled = {
+        let font_context =
+            resolve_font_context_with_embedded(&doc, options, embedded_font_dir.as_ref());
+        let output = render::typst_gen::generate_typst_with_options_and_font_context(
+            &doc,
+            options,
+            font_context.as_ref(),
+        )?;
+        render::pdf::compile_to_pdf_with(
+            &output.source,
+            &output.images,
+            &FontSources::new(
+                font_context
+                    .as_ref()
+                    .map(|context| context.search_paths())
+                    .unwrap_or(&[]),
+                &options.fonts,
+            ),
+            &PdfExport::from_options(options),
+        )?
+    };
+    #[cfg(target_arch = "wasm32")]
+    let compiled = {
+        let output = render::typst_gen::generate_typst_with_options(&doc, options)?;
+        render::pdf::compile_to_pdf_with(
+            &output.source,
+            &output.images,
+            &FontSources::new(&options.font_paths, &options.fonts),
+            &PdfExport::from_options(options),
+        )?
+    };
+    let pdf: Vec<u8> = compiled.pdf;
+    warnings.extend(layout_warnings(
+        Format::Pptx,
+        compiled.overflows,
+        compiled.missing_glyphs,
+        options,
+    ));
+    check_warning_severity(&warnings, options)?;
+
+    let mut ranges: Vec<crate::pdf_ops::PageRange> = Vec::with_capacity(sections.len());
+    let mut next_page: u32 = 1;
+    for section in &sections {
+        let page_count: u32 = section.page_count as u32;
+        ranges.push(crate::pdf_ops::PageRange::new(
+            next_page,
+            next_page + page_count - 1,
+        ));
+        next_page += page_count;
+    }
+    // Every slide is one fixed page; anything else means the page spans
+    // above no longer line up with the PDF.
+    let pdf_page_count: u32 = crate::pdf_ops::page_count(&pdf)?;
+    if pdf_page_count != next_page - 1 {
+        return Err(ConvertError::Render(format!(
+            "expected {} pages for section split, PDF has {pdf_page_count}",
+            next_page - 1
+        )));
+    }
+    let parts: Vec<Vec<u8>> = crate::pdf_ops::split(&pdf, &ranges)?;
+
+    Ok(SectionConvertResult {
+        sections: sections
+            .into_iter()
+            .zip(parts)
+            .map(|(section, pdf)| {
+                Ok(SectionPdf {
+                    name: section.name,
+                    pdf: finish_pdf(pdf, &[(source, Format::Pptx)], options)?,
+                })
+            })
+            .collect::<Result<Vec<SectionPdf>, ConvertError>>()?,
+        warnings,
+    })
+}
+
+/// Parse a document and encode each laid-out page as an image, reusing the
+/// PDF pipeline up to Typst layout.
+pub(super) fn render_to_images(
+    data: &[u8],
+    format: Format,
+    options: &SlideImageOptions,
+) -> Result<Vec<ImageOutput>, ConvertError> {
+    let decrypted: Cow<[u8]> = decrypted_input(data, &options.convert)?;
+    let data: &[u8] = &decrypted;
+    let convert_options: &ConvertOptions = &options.convert;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let embedded_font_dir = parser::embedded_fonts::extract_embedded_fonts(data, format);
+    let (doc, _warnings) = parse_document(data, format, convert_options)?;
+    // Typst always lays out at least one page; an empty selection must not
+    // come back as a blank image.
+    if doc.pages.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let font_context =
+            resolve_font_context_with_embedded(&doc, convert_options, embedded_font_dir.as_ref());
+        let output = render::typst_gen::generate_typst_with_options_and_font_context(
+            &doc,
+            convert_options,
+            font_context.as_ref(),
+        )?;
+        render::pdf::compile_to_images(
+            &output.source,
+            &output.images,
+            &FontSources::new(
+                font_context
+                    .as_ref()
+                    .map(|context| context.search_paths())
+                    .unwrap_or(&[]),
+                &convert_options.fonts,
+            ),
+            options.format,
+            options.dpi,
+        )
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        let output = render::typst_gen::generate_typst_with_options(&doc, convert_options)?;
+        render::pdf::compile_to_images(
+            &output.source,
+            &output.images,
+            &FontSources::new(&convert_options.font_paths, &convert_options.fonts),
+            options.format,
+            options.dpi,
+        )
+    }
+}
+
+/// Render a parsed document to PDF with `options`, resolving fonts the way
+/// [`convert_bytes`] does.
+#[cfg(feature = "pdf-ops")]
+pub(super) fn render_document_with_options(
+    doc: &ir::Document,
+    options: &ConvertOptions,
+) -> Result<Vec<u8>, ConvertError> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let font_context = resolve_font_context_with_embedded(doc, options, None);
+        let output = render::typst_gen::generate_typst_with_options_and_font_context(
+            doc,
+            options,
+            font_context.as_ref(),
+        )?;
+        render::pdf::compile_to_pdf_with(
+            &output.source,
+            &output.images,
+            &FontSources::new(
+                font_context
+                    .as_ref()
+                    .map(|context| context.search_paths())
+                    .unwrap_or(&[]),
+                &options.fonts,
+            ),
+            &PdfExport::from_options(options),
+        )
+        .map(|compiled| compiled.pdf)
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        let output = render::typst_gen::generate_typst_with_options(doc, options)?;
+        render::pdf::compile_to_pdf_with(
+            &output.source,
+            &output.images,
+            &FontSources::new(&options.font_paths, &options.fonts),
+            &PdfExport::from_options(options),
+        )
+        .map(|compiled| compiled.pdf)
+    }
+}
+
 pub(super) fn render_document(doc: &ir::Document) -> Result<Vec<u8>, ConvertError> {
     #[cfg(not(target_arch = "wasm32"))]
     {
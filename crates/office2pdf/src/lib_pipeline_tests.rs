@@ -1,8 +1,10 @@
 #![cfg(not(target_arch = "wasm32"))] // native-only unit tests (filesystem, system fonts)
 use super::test_support::{
-    build_docx_with_title, build_test_docx, make_simple_document, make_test_docx_bytes,
+    build_docx_with_title, build_pptx_with_text_box, build_test_docx, build_test_pptx,
+    build_test_xlsx, make_simple_document, make_test_docx_bytes,
 };
 use super::*;
+use crate::error::{ConvertWarning, WarningLocation};
 use crate::ir::*;
 
 #[test]
@@ -150,6 +152,303 @@ fn test_convert_bytes_with_pdfa_option() {
     );
 }
 
+#[test]
+fn test_convert_pdf_a_1b_and_a_3b_declare_their_part() {
+    for (standard, part) in [
+        (config::PdfStandard::PdfA1b, "<pdfaid:part>1</pdfaid:part>"),
+        (config::PdfStandard::PdfA3b, "<pdfaid:part>3</pdfaid:part>"),
+    ] {
+        let options = ConvertOptions {
+            pdf_standard: Some(standard),
+            ..Default::default()
+        };
+        let result = convert_bytes(&build_test_docx(), Format::Docx, &options).unwrap();
+        let pdf_str = String::from_utf8_lossy(&result.pdf);
+        assert!(pdf_str.contains(part), "{standard:?} should declare {part}");
+    }
+}
+
+#[cfg(feature = "pdf-ops")]
+#[test]
+fn test_convert_with_pdf_security_encrypts_output() {
+    let options = ConvertOptions {
+        pdf_security: Some(config::PdfSecurity {
+            user_password: "reader".to_string(),
+            owner_password: "owner".to_string(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let result = convert_bytes(&build_test_docx(), Format::Docx, &options).unwrap();
+    assert!(String::from_utf8_lossy(&result.pdf).contains("/Encrypt"));
+    assert_eq!(
+        result.metrics.unwrap().output_size_bytes,
+        result.pdf.len() as u64
+    );
+    let pdf = lopdf::Document::load_mem_with_password(&result.pdf, "reader").unwrap();
+    assert!(!pdf.get_pages().is_empty());
+}
+
+#[cfg(feature = "pdf-ops")]
+#[test]
+fn test_convert_with_metadata_override_writes_document_information() {
+    let options = ConvertOptions {
+        metadata_override: Some(config::MetadataOverride {
+            title: Some(Some("Annual report".to_string())),
+            keywords: Some(vec!["finance".to_string(), "2024".to_string()]),
+            company: Some(Some("Acme".to_string())),
+            custom_properties: std::collections::BTreeMap::from([(
+                "Department".to_string(),
+                Some("Finance".to_string()),
+            )]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let data: Vec<u8> = build_docx_with_title("Draft");
+    let result = convert_bytes(&data, Format::Docx, &options).unwrap();
+    let pdf = lopdf::Document::load_mem(&result.pdf).unwrap();
+    let info: &lopdf::Dictionary = pdf
+        .get_dictionary(pdf.trailer.get(b"Info").unwrap().as_reference().unwrap())
+        .unwrap();
+    let text = |key: &[u8]| -> Vec<u8> { info.get(key).unwrap().as_str().unwrap().to_vec() };
+    assert_eq!(text(b"Title"), b"Annual report");
+    assert_eq!(text(b"Keywords"), b"finance, 2024");
+    assert_eq!(text(b"Company"), b"Acme");
+    assert_eq!(text(b"Department"), b"Finance");
+}
+
+#[cfg(feature = "pdf-ops")]
+#[test]
+fn test_convert_with_embed_source_attaches_input_to_pdf_a_3() {
+    let options = ConvertOptions {
+        embed_source: true,
+        pdf_standard: Some(config::PdfStandard::PdfA3b),
+        ..Default::default()
+    };
+    let data: Vec<u8> = build_test_docx();
+    let result = convert_bytes(&data, Format::Docx, &options).unwrap();
+    let pdf = lopdf::Document::load_mem(&result.pdf).unwrap();
+    let catalog = pdf.catalog().unwrap();
+    let spec_id = catalog.get(b"AF").unwrap().as_array().unwrap()[0]
+        .as_reference()
+        .unwrap();
+    let file_id = pdf
+        .get_dictionary(spec_id)
+        .unwrap()
+        .get(b"EF")
+        .and_then(lopdf::Object::as_dict)
+        .and_then(|files| files.get(b"F"))
+        .and_then(lopdf::Object::as_reference)
+        .unwrap();
+    let file = pdf.get_object(file_id).unwrap().as_stream().unwrap();
+    let attached: Vec<u8> = file
+        .decompressed_content()
+        .unwrap_or_else(|_| file.content.clone());
+    assert_eq!(attached, data);
+    assert_eq!(
+        result.metrics.unwrap().output_size_bytes,
+        result.pdf.len() as u64
+    );
+}
+
+#[cfg(feature = "pdf-ops")]
+#[test]
+fn test_convert_with_stamp_draws_over_output() {
+    let options = ConvertOptions {
+        stamp: Some(config::Stamp::text("DRAFT {page}/{pages}")),
+        ..Default::default()
+    };
+    let result = convert_bytes(&build_test_docx(), Format::Docx, &options).unwrap();
+    let pdf = lopdf::Document::load_mem(&result.pdf).unwrap();
+    let page_id: lopdf::ObjectId = pdf.get_pages()[&1];
+    let content: Vec<u8> = pdf.get_page_content(page_id).unwrap();
+    let expected: &[u8] = b"(DRAFT 1/1) Tj";
+    assert!(
+        content
+            .windows(expected.len())
+            .any(|window| window == expected)
+    );
+}
+
+#[cfg(feature = "pdf-ops")]
+#[test]
+fn test_convert_with_bleed_sets_page_boxes() {
+    let options = ConvertOptions {
+        bleed: Some(config::Bleed {
+            margin: 9.0,
+            crop_marks: true,
+        }),
+        ..Default::default()
+    };
+    let result = convert_bytes(&build_test_docx(), Format::Docx, &options).unwrap();
+    let pdf = lopdf::Document::load_mem(&result.pdf).unwrap();
+    let page: &lopdf::Dictionary = pdf.get_dictionary(pdf.get_pages()[&1]).unwrap();
+    let left = |key: &[u8]| -> f32 {
+        page.get(key).unwrap().as_array().unwrap()[0]
+            .as_float()
+            .unwrap()
+    };
+    assert_eq!(left(b"TrimBox"), 0.0);
+    assert_eq!(left(b"BleedBox"), -9.0);
+    assert_eq!(left(b"MediaBox"), -27.0);
+}
+
+#[cfg(feature = "pdf-ops")]
+#[test]
+fn test_convert_with_linearize_writes_linearized_output() {
+    let options = ConvertOptions {
+        linearize: true,
+        ..Default::default()
+    };
+    let result = convert_bytes(&build_test_docx(), Format::Docx, &options).unwrap();
+    let head: String = String::from_utf8_lossy(&result.pdf[..64]).into_owned();
+    assert!(head.contains("/Linearized 1"), "{head}");
+    let pdf = lopdf::Document::load_mem(&result.pdf).unwrap();
+    assert_eq!(pdf.get_pages().len(), 1);
+}
+
+#[cfg(feature = "pdf-ops")]
+#[test]
+fn test_convert_with_compact_writes_object_streams() {
+    let options = ConvertOptions {
+        compact: true,
+        ..Default::default()
+    };
+    let result = convert_bytes(&build_test_docx(), Format::Docx, &options).unwrap();
+    let expected: &[u8] = b"/ObjStm";
+    assert!(
+        result
+            .pdf
+            .windows(expected.len())
+            .any(|window| window == expected)
+    );
+    let pdf = lopdf::Document::load_mem(&result.pdf).unwrap();
+    assert_eq!(pdf.get_pages().len(), 1);
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn test_convert_with_signing_signs_output() {
+    let options = ConvertOptions {
+        signing: Some(config::SigningConfig {
+            key: config::SigningKey::Pkcs12 {
+                data: include_bytes!("../../../tests/fixtures/signing/test-signer.p12").to_vec(),
+                password: "test".to_string(),
+            },
+            reason: None,
+            location: None,
+            contact_info: None,
+            appearance: None,
+        }),
+        ..Default::default()
+    };
+    let result = convert_bytes(&build_test_docx(), Format::Docx, &options).unwrap();
+    assert!(String::from_utf8_lossy(&result.pdf).contains("/ETSI.CAdES.detached"));
+    assert_eq!(
+        result.metrics.unwrap().output_size_bytes,
+        result.pdf.len() as u64
+    );
+}
+
+#[cfg(feature = "pdf-ops")]
+#[test]
+fn test_convert_pdf_x_4_adds_output_intent_and_trim_boxes() {
+    let options = ConvertOptions {
+        pdf_standard: Some(config::PdfStandard::PdfX4),
+        ..Default::default()
+    };
+    let result = convert_bytes(&build_test_docx(), Format::Docx, &options).unwrap();
+    let pdf = lopdf::Document::load_mem(&result.pdf).unwrap();
+
+    let catalog = pdf.catalog().unwrap();
+    let intents = catalog.get(b"OutputIntents").unwrap().as_array().unwrap();
+    let subtypes: Vec<&[u8]> = intents
+        .iter()
+        .map(|intent| {
+            let (_, intent) = pdf.dereference(intent).unwrap();
+            intent
+                .as_dict()
+                .unwrap()
+                .get(b"S")
+                .unwrap()
+                .as_name()
+                .unwrap()
+        })
+        .collect();
+    assert!(subtypes.contains(&b"GTS_PDFA1".as_slice()), "{subtypes:?}");
+    assert!(subtypes.contains(&b"GTS_PDFX".as_slice()), "{subtypes:?}");
+
+    let info = pdf
+        .get_dictionary(pdf.trailer.get(b"Info").unwrap().as_reference().unwrap())
+        .unwrap();
+    assert_eq!(
+        info.get(b"GTS_PDFXVersion").unwrap().as_str().unwrap(),
+        b"PDF/X-4"
+    );
+    for page_id in pdf.get_pages().into_values() {
+        assert!(pdf.get_dictionary(page_id).unwrap().has(b"TrimBox"));
+    }
+
+    let metadata = pdf
+        .get_object(catalog.get(b"Metadata").unwrap().as_reference().unwrap())
+        .unwrap()
+        .as_stream()
+        .unwrap();
+    assert!(!metadata.dict.has(b"Filter"));
+    let xmp = String::from_utf8_lossy(&metadata.content);
+    assert!(xmp.contains("<pdfxid:GTS_PDFXVersion>PDF/X-4</pdfxid:GTS_PDFXVersion>"));
+    assert!(
+        xmp.contains("<pdfaid:part>2</pdfaid:part>"),
+        "still PDF/A-2b"
+    );
+}
+
+#[test]
+fn test_deterministic_output_is_byte_identical() {
+    let options = ConvertOptions {
+        pdf_standard: Some(config::PdfStandard::PdfA2b),
+        deterministic: true,
+        ..Default::default()
+    };
+    let data: Vec<u8> = build_test_docx();
+    let first = convert_bytes(&data, Format::Docx, &options).unwrap();
+    // Cross a second boundary so an unpinned timestamp would differ.
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    let second = convert_bytes(&data, Format::Docx, &options).unwrap();
+    assert!(first.pdf == second.pdf, "deterministic PDFs differ");
+
+    let other = convert_bytes(&build_docx_with_title("Other"), Format::Docx, &options).unwrap();
+    assert!(first.pdf != other.pdf);
+}
+
+#[test]
+fn test_emit_typst_source_returns_compiled_markup() {
+    let data: Vec<u8> = build_test_docx();
+    let plain = convert_bytes(&data, Format::Docx, &ConvertOptions::default()).unwrap();
+    assert!(plain.typst_sources.is_empty());
+
+    let options = ConvertOptions {
+        emit_typst_source: true,
+        ..Default::default()
+    };
+    let result = convert_bytes(&data, Format::Docx, &options).unwrap();
+    assert_eq!(result.typst_sources.len(), 1);
+    let source: &str = &result.typst_sources[0].source;
+    assert!(source.contains("Hello from DOCX"), "{source}");
+    // The markup compiles to the same PDF on its own.
+    let recompiled: Vec<u8> = render::pdf::compile_to_pdf(
+        source,
+        &result.typst_sources[0].images,
+        None,
+        &[],
+        false,
+        false,
+    )
+    .unwrap();
+    assert!(recompiled.starts_with(b"%PDF"));
+}
+
 #[test]
 fn test_render_document_default_no_pdfa() {
     let doc = make_simple_document("No PDF/A");
@@ -161,6 +460,208 @@ fn test_render_document_default_no_pdfa() {
     );
 }
 
+#[test]
+fn test_parse_bytes_then_generate_typst_reflects_ir_edits() {
+    let docx_bytes = build_test_docx();
+    let (mut doc, _warnings) =
+        parse_bytes(&docx_bytes, Format::Docx, &ConvertOptions::default()).unwrap();
+    let Some(Page::Flow(page)) = doc.pages.first_mut() else {
+        panic!("expected a flow page");
+    };
+    let Some(Block::Paragraph(paragraph)) = page.content.first_mut() else {
+        panic!("expected a paragraph");
+    };
+    assert_eq!(paragraph.runs[0].text, "Hello from DOCX");
+    paragraph.runs[0].text = "Edited greeting".to_string();
+
+    let options = ConvertOptions {
+        landscape: Some(true),
+        ..ConvertOptions::default()
+    };
+    let output: TypstOutput = generate_typst(&doc, &options).unwrap();
+    assert!(output.source.contains("Edited greeting"));
+    assert!(!output.source.contains("Hello from DOCX"));
+    assert!(output.source.contains("height: 595."), "{}", output.source);
+}
+
+#[test]
+fn test_transform_runs_between_parse_and_render() {
+    let options = ConvertOptions {
+        transform: Some(config::DocumentTransform::new(|doc: &mut Document| {
+            let cover: Page = make_simple_document("Cover").pages.remove(0);
+            doc.pages.insert(0, cover);
+            doc.metadata.title = Some("Redacted".to_string());
+        })),
+        ..ConvertOptions::default()
+    };
+    let docx_bytes = build_test_docx();
+
+    let (doc, _warnings) = parse_bytes(&docx_bytes, Format::Docx, &options).unwrap();
+    assert_eq!(doc.metadata.title.as_deref(), Some("Redacted"));
+    let plain_pages: usize = parse_bytes(&docx_bytes, Format::Docx, &ConvertOptions::default())
+        .unwrap()
+        .0
+        .pages
+        .len();
+    assert_eq!(doc.pages.len(), plain_pages + 1);
+
+    let result = convert_bytes(&docx_bytes, Format::Docx, &options).unwrap();
+    assert_eq!(
+        result.metrics.map(|metrics| metrics.page_count as usize),
+        Some(plain_pages + 1)
+    );
+    assert!(format!("{options:?}").contains("DocumentTransform(..)"));
+}
+
+#[test]
+fn test_extract_text_returns_plain_text_and_markdown() {
+    let docx_bytes = build_test_docx();
+    let extracted = extract_text(&docx_bytes, Format::Docx, &ConvertOptions::default()).unwrap();
+    assert_eq!(extracted.text, "Hello from DOCX\n\nSecond paragraph\n");
+    assert_eq!(
+        extracted.markdown,
+        "Hello from DOCX\n\n**Second paragraph**\n"
+    );
+}
+
+#[test]
+fn test_progress_reports_each_stage_in_order() {
+    use std::sync::{Arc, Mutex};
+
+    use config::{Progress, ProgressCallback, ProgressStage};
+
+    let reports: Arc<Mutex<Vec<Progress>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&reports);
+    let options = ConvertOptions {
+        progress: Some(ProgressCallback::new(move |progress: Progress| {
+            sink.lock().unwrap().push(progress);
+        })),
+        ..ConvertOptions::default()
+    };
+    convert_bytes(&build_test_docx(), Format::Docx, &options).unwrap();
+
+    let reports: Vec<Progress> = reports.lock().unwrap().clone();
+    let steps: Vec<(ProgressStage, u32, u32)> = reports
+        .iter()
+        .map(|progress| (progress.stage, progress.current, progress.total))
+        .collect();
+    assert_eq!(
+        steps,
+        vec![
+            (ProgressStage::Parse, 0, 1),
+            (ProgressStage::Parse, 1, 1),
+            (ProgressStage::Codegen, 0, 1),
+            (ProgressStage::Codegen, 1, 1),
+            (ProgressStage::Compile, 0, 1),
+            (ProgressStage::Compile, 1, 1),
+        ]
+    );
+    assert!(
+        reports
+            .windows(2)
+            .all(|pair| pair[0].elapsed <= pair[1].elapsed)
+    );
+}
+
+#[test]
+fn test_fail_on_warning_severity_rejects_at_or_above_threshold() {
+    use crate::error::WarningSeverity;
+
+    // An unknown locale is a recovered parse error.
+    let options = |threshold: Option<WarningSeverity>| ConvertOptions {
+        locale: Some("tlh".to_string()),
+        fail_on_warning_severity: threshold,
+        ..ConvertOptions::default()
+    };
+    let data: Vec<u8> = build_test_xlsx();
+
+    let result = convert_bytes(&data, Format::Xlsx, &options(None)).unwrap();
+    assert!(
+        result
+            .warnings
+            .iter()
+            .any(|warning| warning.code() == "parse_skipped")
+    );
+
+    match convert_bytes(
+        &data,
+        Format::Xlsx,
+        &options(Some(WarningSeverity::Warning)),
+    ) {
+        Err(ConvertError::WarningThreshold(warning)) => {
+            assert_eq!(warning.severity(), WarningSeverity::RecoveredError);
+        }
+        other => panic!("expected WarningThreshold, got {other:?}"),
+    }
+    assert!(
+        parse_bytes(
+            &build_test_docx(),
+            Format::Docx,
+            &options(Some(WarningSeverity::Info))
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn test_convert_bytes_auto_detects_the_format() {
+    let result = convert_bytes_auto(&build_test_xlsx(), &ConvertOptions::default()).unwrap();
+    assert!(result.pdf.starts_with(b"%PDF"));
+
+    assert!(matches!(
+        convert_bytes_auto(b"plain text", &ConvertOptions::default()),
+        Err(ConvertError::UnsupportedFormat(_))
+    ));
+}
+
+#[test]
+fn test_convert_bytes_auto_names_legacy_binary_formats() {
+    use std::io::{Cursor, Write};
+
+    let mut file = cfb::CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+    file.create_stream("/WordDocument")
+        .unwrap()
+        .write_all(b"binary")
+        .unwrap();
+    file.flush().unwrap();
+    let doc: Vec<u8> = file.into_inner().into_inner();
+    match convert_bytes_auto(&doc, &ConvertOptions::default()) {
+        Err(ConvertError::UnsupportedFormat(message)) => {
+            assert!(message.contains(".doc"), "{message}");
+        }
+        other => panic!("expected UnsupportedFormat, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_convert_trusts_contents_over_a_wrong_extension() {
+    let input = std::env::temp_dir().join("office2pdf_test_misnamed_workbook.docx");
+    std::fs::write(&input, build_test_xlsx()).unwrap();
+    let result = convert(&input);
+    let _ = std::fs::remove_file(&input);
+    assert!(result.unwrap().pdf.starts_with(b"%PDF"));
+}
+
+#[test]
+fn test_parse_bytes_rejects_encrypted_input() {
+    let mut data: Vec<u8> = vec![0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+    data.resize(512, 0);
+    assert!(matches!(
+        parse_bytes(&data, Format::Docx, &ConvertOptions::default()),
+        Err(ConvertError::UnsupportedEncryption)
+    ));
+}
+
+#[test]
+fn test_extract_assets_rejects_encrypted_input() {
+    let mut data: Vec<u8> = vec![0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+    data.resize(512, 0);
+    assert!(matches!(
+        extract_assets(&data, Format::Docx, &config::AssetKind::ALL),
+        Err(ConvertError::UnsupportedEncryption)
+    ));
+}
+
 #[test]
 fn test_convert_bytes_with_paper_size_override() {
     use std::io::Cursor;
@@ -333,3 +834,108 @@ fn test_convert_bytes_tagged_pdf_with_heading() {
         "Tagged PDF with headings should contain structure tags"
     );
 }
+
+#[test]
+fn test_convert_pptx_warns_about_overflowing_text_box() {
+    // A one-inch square box cannot hold a paragraph of 18pt text.
+    let text: String = "Quarterly results exceeded every forecast ".repeat(6);
+    let data: Vec<u8> = build_pptx_with_text_box(914400, 914400, &text);
+    let result = convert_bytes(&data, Format::Pptx, &ConvertOptions::default()).unwrap();
+    let overflows: Vec<&ConvertWarning> = result
+        .warnings
+        .iter()
+        .filter(|warning| warning.code() == "content_overflow")
+        .collect();
+    assert_eq!(overflows.len(), 1, "{:?}", result.warnings);
+    match overflows[0] {
+        ConvertWarning::ContentOverflow {
+            format,
+            slide,
+            element,
+            location,
+        } => {
+            assert_eq!(format, "PPTX");
+            assert_eq!(*slide, 1);
+            assert_eq!(*location, Some(WarningLocation::Slide(1)));
+            assert!(
+                element.starts_with("text box \"Quarterly results"),
+                "{element}"
+            );
+        }
+        other => panic!("expected ContentOverflow, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_convert_pptx_fitting_text_box_has_no_overflow_warning() {
+    let result =
+        convert_bytes(&build_test_pptx(), Format::Pptx, &ConvertOptions::default()).unwrap();
+    assert!(
+        result
+            .warnings
+            .iter()
+            .all(|warning| warning.code() != "content_overflow"),
+        "{:?}",
+        result.warnings
+    );
+}
+
+fn docx_with_text(text: &str) -> Vec<u8> {
+    let docx = docx_rs::Docx::new()
+        .add_paragraph(docx_rs::Paragraph::new().add_run(docx_rs::Run::new().add_text(text)));
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    docx.build().pack(&mut cursor).unwrap();
+    cursor.into_inner()
+}
+
+#[test]
+fn test_convert_reports_missing_glyph() {
+    // Plane 16 private use: no font has a glyph for it.
+    let data: Vec<u8> = docx_with_text("Status \u{10FFFD} done");
+    let result = convert_bytes(&data, Format::Docx, &ConvertOptions::default()).unwrap();
+    let missing: Vec<&ConvertWarning> = result
+        .warnings
+        .iter()
+        .filter(|warning| warning.code() == "missing_glyph")
+        .collect();
+    let [
+        ConvertWarning::MissingGlyph {
+            format,
+            character,
+            page,
+            replacement,
+            ..
+        },
+    ] = missing.as_slice()
+    else {
+        panic!("expected one missing glyph: {:?}", result.warnings);
+    };
+    assert_eq!(format, "DOCX");
+    assert_eq!(*character, '\u{10FFFD}');
+    assert_eq!(*page, 1);
+    assert_eq!(*replacement, None);
+}
+
+#[cfg(feature = "pdf-ops")]
+#[test]
+fn test_convert_draws_missing_glyph_replacement() {
+    let data: Vec<u8> = docx_with_text("Status \u{10FFFD} done");
+    let options = ConvertOptions {
+        missing_glyph_replacement: Some('?'),
+        ..Default::default()
+    };
+    let result = convert_bytes(&data, Format::Docx, &options).unwrap();
+    let pages: Vec<String> = crate::pdf_ops::extract_text(&result.pdf)
+        .unwrap()
+        .into_iter()
+        .map(|page| page.text)
+        .collect();
+    assert!(pages[0].contains("Status ? done"), "{pages:?}");
+    assert!(result.warnings.iter().any(|warning| matches!(
+        warning,
+        ConvertWarning::MissingGlyph {
+            replacement: Some('?'),
+            ..
+        }
+    )));
+}
@@ -373,9 +373,12 @@ fn test_render_document_fixed_textbox_ordered_list_keeps_all_numbers() {
                     auto_fit: false,
                     text_rotation_deg: None,
                 }),
+                href: None,
             }],
             background_color: None,
             background_gradient: None,
+            background_image: None,
+            source_slide: None,
         })],
         styles: StyleSheet::default(),
     };
@@ -750,7 +753,10 @@ fn test_render_pptx_style_document_size() {
                     auto_fit: false,
                     text_rotation_deg: None,
                 }),
+                href: None,
             }],
+            background_image: None,
+            source_slide: None,
         }));
     }
     let doc = Document {
@@ -811,7 +817,10 @@ fn test_render_document_with_centered_fixed_text_box() {
                     auto_fit: false,
                     text_rotation_deg: None,
                 }),
+                href: None,
             }],
+            background_image: None,
+            source_slide: None,
         })],
         styles: StyleSheet::default(),
     };
@@ -878,7 +887,10 @@ fn test_render_document_with_auto_fit_fixed_text_box() {
                     auto_fit: true,
                     text_rotation_deg: None,
                 }),
+                href: None,
             }],
+            background_image: None,
+            source_slide: None,
         })],
         styles: StyleSheet::default(),
     };
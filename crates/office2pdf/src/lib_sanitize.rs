@@ -0,0 +1,214 @@
+//! The text-sanitization pass between parsing and rendering: characters
+//! that show up in the PDF as missing-glyph boxes, or that only matter to
+//! the authoring application, are removed per [`TextSanitization`], with
+//! one warning per character saying how many went.
+
+use std::collections::BTreeMap;
+
+use crate::config::TextSanitization;
+use crate::error::ConvertWarning;
+use crate::ir::{
+    Block, Document, FixedElementKind, HFInline, HeaderFooter, Page, Paragraph, Table,
+};
+
+/// Sanitize the text of `doc` per `mode`, returning a warning for each
+/// character removed.
+pub(super) fn sanitize_document(
+    doc: &mut Document,
+    mode: TextSanitization,
+    format_label: &str,
+) -> Vec<ConvertWarning> {
+    if mode == TextSanitization::Off {
+        return Vec::new();
+    }
+    let mut sanitizer = Sanitizer {
+        mode,
+        removed: BTreeMap::new(),
+    };
+    for page in &mut doc.pages {
+        sanitizer.page(page);
+    }
+    sanitizer
+        .removed
+        .into_iter()
+        .map(|(ch, count)| ConvertWarning::ContentOmitted {
+            format: format_label.to_string(),
+            content: format!("{} (U+{:04X}) ×{count}", describe(ch), u32::from(ch)),
+            location: None,
+        })
+        .collect()
+}
+
+struct Sanitizer {
+    mode: TextSanitization,
+    /// How often each removed character occurred.
+    removed: BTreeMap<char, usize>,
+}
+
+impl Sanitizer {
+    fn removes(&self, ch: char) -> bool {
+        is_stray_control(ch) || (self.mode == TextSanitization::Strip && is_invisible_format(ch))
+    }
+
+    fn text(&mut self, text: &mut String) {
+        if !text.chars().any(|ch| self.removes(ch)) {
+            return;
+        }
+        let mut kept: String = String::with_capacity(text.len());
+        for ch in text.chars() {
+            if self.removes(ch) {
+                *self.removed.entry(ch).or_default() += 1;
+            } else {
+                kept.push(ch);
+            }
+        }
+        *text = kept;
+    }
+
+    fn page(&mut self, page: &mut Page) {
+        match page {
+            Page::Flow(flow) => {
+                self.header_footer(flow.header.as_mut());
+                self.blocks(&mut flow.content);
+                self.header_footer(flow.footer.as_mut());
+            }
+            Page::Fixed(fixed) => {
+                for element in &mut fixed.elements {
+                    match &mut element.kind {
+                        FixedElementKind::TextBox(text_box) => self.blocks(&mut text_box.content),
+                        FixedElementKind::Table(table) => self.table(table),
+                        FixedElementKind::SmartArt(smartart) => {
+                            for node in &mut smartart.items {
+                                self.text(&mut node.text);
+                            }
+                        }
+                        FixedElementKind::Chart(chart) => {
+                            if let Some(title) = &mut chart.title {
+                                self.text(title);
+                            }
+                        }
+                        FixedElementKind::Image(_) | FixedElementKind::Shape(_) => {}
+                    }
+                }
+            }
+            Page::Sheet(sheet) => {
+                self.header_footer(sheet.header.as_mut());
+                self.table(&mut sheet.table);
+                for text_box in &mut sheet.text_boxes {
+                    for paragraph in &mut text_box.paragraphs {
+                        self.paragraph(paragraph);
+                    }
+                }
+                for (_, chart) in &mut sheet.charts {
+                    if let Some(title) = &mut chart.title {
+                        self.text(title);
+                    }
+                }
+                self.header_footer(sheet.footer.as_mut());
+            }
+        }
+    }
+
+    fn blocks(&mut self, blocks: &mut [Block]) {
+        for block in blocks {
+            match block {
+                Block::Paragraph(paragraph) => self.paragraph(paragraph),
+                Block::Table(table) => self.table(table),
+                Block::List(list) => {
+                    for item in &mut list.items {
+                        for paragraph in &mut item.content {
+                            self.paragraph(paragraph);
+                        }
+                    }
+                }
+                Block::FloatingTextBox(text_box) => self.blocks(&mut text_box.content),
+                Block::Chart(chart) => {
+                    if let Some(title) = &mut chart.title {
+                        self.text(title);
+                    }
+                }
+                Block::Image(_)
+                | Block::InlineImages(_)
+                | Block::FloatingImage(_)
+                | Block::FloatingShape(_)
+                | Block::MathEquation(_)
+                | Block::PageBreak
+                | Block::ColumnBreak => {}
+            }
+        }
+    }
+
+    fn paragraph(&mut self, paragraph: &mut Paragraph) {
+        for run in &mut paragraph.runs {
+            self.text(&mut run.text);
+            if let Some(footnote) = &mut run.footnote {
+                self.text(footnote);
+            }
+        }
+    }
+
+    fn table(&mut self, table: &mut Table) {
+        for row in &mut table.rows {
+            for cell in &mut row.cells {
+                self.blocks(&mut cell.content);
+            }
+        }
+    }
+
+    fn header_footer(&mut self, header_footer: Option<&mut HeaderFooter>) {
+        let Some(header_footer) = header_footer else {
+            return;
+        };
+        for paragraph in &mut header_footer.paragraphs {
+            for element in &mut paragraph.elements {
+                if let HFInline::Run(run) = element {
+                    self.text(&mut run.text);
+                }
+            }
+        }
+    }
+}
+
+/// Control characters, noncharacters and the object replacement character,
+/// which fonts have no glyph for. Tabs and line breaks are layout, and
+/// U+000B carries PPTX soft line breaks through the IR.
+fn is_stray_control(ch: char) -> bool {
+    match ch {
+        '\t' | '\n' | '\r' | '\u{000B}' => false,
+        '\u{FFFC}' | '\u{FDD0}'..='\u{FDEF}' => true,
+        _ => ch.is_control() || u32::from(ch) & 0xFFFE == 0xFFFE,
+    }
+}
+
+/// Invisible characters Typst lays out correctly but that land in the text
+/// layer, so copied or searched text differs from what is seen. The
+/// zero-width joiners are kept: emoji sequences and Indic and Persian
+/// spelling need them.
+fn is_invisible_format(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{00AD}'
+            | '\u{200B}'
+            | '\u{200E}'
+            | '\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{FEFF}'
+    )
+}
+
+fn describe(ch: char) -> &'static str {
+    match ch {
+        '\u{00AD}' => "soft hyphen",
+        '\u{200B}' => "zero-width space",
+        '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' => {
+            "bidirectional formatting character"
+        }
+        '\u{2060}'..='\u{2064}' | '\u{FEFF}' => "invisible formatting character",
+        '\u{FFFC}' => "object replacement character",
+        '\u{001C}'..='\u{001F}' => "field separator",
+        _ if ch.is_control() => "control character",
+        _ => "noncharacter",
+    }
+}
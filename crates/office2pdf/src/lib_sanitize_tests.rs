@@ -0,0 +1,73 @@
+use super::sanitize::sanitize_document;
+use super::test_support::{build_test_docx, make_simple_document};
+use super::*;
+use crate::config::{DocumentTransform, TextSanitization};
+use crate::ir::{Block, Document, Page};
+
+fn first_run_text(doc: &Document) -> &str {
+    let Page::Flow(flow) = &doc.pages[0] else {
+        panic!("expected a flow page");
+    };
+    let Block::Paragraph(paragraph) = &flow.content[0] else {
+        panic!("expected a paragraph");
+    };
+    &paragraph.runs[0].text
+}
+
+const MIXED: &str =
+    "a\u{001F}b\u{000C}c\u{001F}\u{000B}d\u{00AD}e\u{00A0}f\u{200B}g\u{200F}h\u{200D}i\u{FFFE}";
+
+#[test]
+fn test_normalize_removes_stray_controls_only() {
+    let mut doc: Document = make_simple_document(MIXED);
+    let warnings = sanitize_document(&mut doc, TextSanitization::Normalize, "DOCX");
+    assert_eq!(
+        first_run_text(&doc),
+        "abc\u{000B}d\u{00AD}e\u{00A0}f\u{200B}g\u{200F}h\u{200D}i"
+    );
+    let contents: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        contents,
+        [
+            "[DOCX] omitted: control character (U+000C) ×1",
+            "[DOCX] omitted: field separator (U+001F) ×2",
+            "[DOCX] omitted: noncharacter (U+FFFE) ×1",
+        ]
+    );
+}
+
+#[test]
+fn test_strip_also_removes_invisible_formatting() {
+    let mut doc: Document = make_simple_document(MIXED);
+    let warnings = sanitize_document(&mut doc, TextSanitization::Strip, "DOCX");
+    assert_eq!(first_run_text(&doc), "abc\u{000B}de\u{00A0}fgh\u{200D}i");
+    assert_eq!(warnings.len(), 6);
+}
+
+#[test]
+fn test_off_leaves_text_alone() {
+    let mut doc: Document = make_simple_document(MIXED);
+    assert!(sanitize_document(&mut doc, TextSanitization::Off, "DOCX").is_empty());
+    assert_eq!(first_run_text(&doc), MIXED);
+}
+
+#[test]
+fn test_conversion_sanitizes_after_transform() {
+    let options = ConvertOptions {
+        transform: Some(DocumentTransform::new(|doc: &mut Document| {
+            doc.pages
+                .insert(0, make_simple_document("Cover\u{001E}").pages.remove(0));
+        })),
+        ..ConvertOptions::default()
+    };
+    let result = convert_bytes(&build_test_docx(), Format::Docx, &options).unwrap();
+    assert!(
+        result
+            .warnings
+            .iter()
+            .any(|warning| warning.code() == "content_omitted"
+                && warning.to_string().contains("U+001E")),
+        "{:?}",
+        result.warnings
+    );
+}
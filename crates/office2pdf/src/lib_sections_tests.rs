@@ -0,0 +1,112 @@
+use std::io::{Cursor, Write};
+
+use super::*;
+
+/// PPTX with one text slide per entry of `slide_texts`, grouped into
+/// consecutive `(name, slide_count)` sections.
+fn build_sectioned_pptx(slide_texts: &[&str], sections: &[(&str, usize)]) -> Vec<u8> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let opts = zip::write::FileOptions::default();
+
+    zip.start_file("[Content_Types].xml", opts).unwrap();
+    zip.write_all(
+        br#"<?xml version="1.0" encoding="UTF-8"?><Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/></Types>"#,
+    )
+    .unwrap();
+
+    zip.start_file("_rels/.rels", opts).unwrap();
+    zip.write_all(
+        br#"<?xml version="1.0" encoding="UTF-8"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="ppt/presentation.xml"/></Relationships>"#,
+    )
+    .unwrap();
+
+    let mut presentation = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?><p:presentation xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:sldSz cx="9144000" cy="6858000"/><p:sldIdLst>"#,
+    );
+    let mut presentation_rels = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    );
+    for index in 0..slide_texts.len() {
+        presentation.push_str(&format!(
+            r#"<p:sldId id="{}" r:id="rId{}"/>"#,
+            256 + index,
+            2 + index
+        ));
+        presentation_rels.push_str(&format!(
+            r#"<Relationship Id="rId{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide" Target="slides/slide{}.xml"/>"#,
+            2 + index,
+            1 + index
+        ));
+    }
+    presentation.push_str(r#"</p:sldIdLst><p:extLst><p:ext uri="{521415D9-36F7-43E2-AB2F-B90AF26B5E84}"><p14:sectionLst xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main">"#);
+    let mut next_slide: usize = 0;
+    for (name, slide_count) in sections {
+        presentation.push_str(&format!(r#"<p14:section name="{name}"><p14:sldIdLst>"#));
+        for index in next_slide..next_slide + slide_count {
+            presentation.push_str(&format!(r#"<p14:sldId id="{}"/>"#, 256 + index));
+        }
+        presentation.push_str("</p14:sldIdLst></p14:section>");
+        next_slide += slide_count;
+    }
+    presentation.push_str("</p14:sectionLst></p:ext></p:extLst></p:presentation>");
+    presentation_rels.push_str("</Relationships>");
+
+    zip.start_file("ppt/presentation.xml", opts).unwrap();
+    zip.write_all(presentation.as_bytes()).unwrap();
+    zip.start_file("ppt/_rels/presentation.xml.rels", opts)
+        .unwrap();
+    zip.write_all(presentation_rels.as_bytes()).unwrap();
+
+    for (index, text) in slide_texts.iter().enumerate() {
+        zip.start_file(format!("ppt/slides/slide{}.xml", index + 1), opts)
+            .unwrap();
+        zip.write_all(format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cSld><p:spTree><p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr/><p:sp><p:nvSpPr><p:cNvPr id="2" name="TextBox 1"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="457200" y="274638"/><a:ext cx="8229600" cy="1143000"/></a:xfrm></p:spPr><p:txBody><a:bodyPr/><a:p><a:r><a:t>{text}</a:t></a:r></a:p></p:txBody></p:sp></p:spTree></p:cSld></p:sld>"#
+        ).as_bytes())
+        .unwrap();
+    }
+
+    zip.finish().unwrap().into_inner()
+}
+
+#[test]
+fn test_convert_pptx_sections_splits_at_section_boundaries() {
+    let data = build_sectioned_pptx(
+        &["Welcome", "Quarterly numbers", "Outlook"],
+        &[("Opening", 1), ("Numbers", 2)],
+    );
+    let result = convert_pptx_sections(&data, &config::ConvertOptions::default()).unwrap();
+
+    let names: Vec<&str> = result
+        .sections
+        .iter()
+        .map(|section| section.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Opening", "Numbers"]);
+    assert_eq!(pdf_ops::page_count(&result.sections[0].pdf).unwrap(), 1);
+    assert_eq!(pdf_ops::page_count(&result.sections[1].pdf).unwrap(), 2);
+    let numbers_text: String = pdf_ops::extract_text(&result.sections[1].pdf)
+        .unwrap()
+        .into_iter()
+        .map(|page| page.text)
+        .collect();
+    assert!(numbers_text.contains("Outlook"), "{numbers_text}");
+    assert!(!numbers_text.contains("Welcome"), "{numbers_text}");
+}
+
+#[test]
+fn test_convert_pptx_sections_respects_section_filter() {
+    let data = build_sectioned_pptx(
+        &["Welcome", "Quarterly numbers", "Outlook"],
+        &[("Opening", 1), ("Numbers", 2)],
+    );
+    let options = config::ConvertOptions {
+        slide_sections: Some(vec!["Numbers".to_string()]),
+        ..Default::default()
+    };
+    let result = convert_pptx_sections(&data, &options).unwrap();
+
+    assert_eq!(result.sections.len(), 1);
+    assert_eq!(result.sections[0].name, "Numbers");
+    assert_eq!(pdf_ops::page_count(&result.sections[0].pdf).unwrap(), 2);
+}
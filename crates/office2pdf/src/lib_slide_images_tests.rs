@@ -0,0 +1,59 @@
+use super::config::{SlideImageFormat, SlideImageOptions, SlideRange};
+use super::test_support::{build_test_docx, build_test_pptx};
+use super::*;
+
+#[test]
+fn test_render_slides_to_png_uses_slide_size_and_dpi() {
+    let options = SlideImageOptions {
+        dpi: 72.0,
+        ..Default::default()
+    };
+    let images = render_slides_to_images(&build_test_pptx(), &options).unwrap();
+
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].format, SlideImageFormat::Png);
+    assert!(images[0].data.starts_with(b"\x89PNG"));
+    // A 720x540pt slide at 72 dpi is one pixel per point.
+    assert_eq!((images[0].width, images[0].height), (720, 540));
+}
+
+#[test]
+fn test_render_slides_to_svg() {
+    let options = SlideImageOptions {
+        format: SlideImageFormat::Svg,
+        ..Default::default()
+    };
+    let images = render_slides_to_images(&build_test_pptx(), &options).unwrap();
+
+    assert_eq!(images.len(), 1);
+    assert!(images[0].data.starts_with(b"<svg"));
+    assert_eq!((images[0].width, images[0].height), (720, 540));
+}
+
+#[test]
+fn test_render_slides_to_images_respects_slide_range() {
+    let options = SlideImageOptions {
+        convert: ConvertOptions {
+            slide_range: Some(SlideRange::new(2, 3)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let images = render_slides_to_images(&build_test_pptx(), &options).unwrap();
+    assert!(images.is_empty(), "a one-slide deck has no slides 2-3");
+}
+
+#[test]
+fn test_render_to_images_draws_document_pages() {
+    let options = SlideImageOptions {
+        dpi: 72.0,
+        ..Default::default()
+    };
+    let images = render_to_images(&build_test_docx(), Format::Docx, &options).unwrap();
+
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].page_index, 0);
+    assert!(images[0].data.starts_with(b"\x89PNG"));
+    // Portrait paper rather than a slide.
+    assert!(images[0].width < images[0].height);
+}
@@ -34,6 +34,55 @@ fn test_streaming_xlsx_produces_valid_pdf() {
     assert!(result.pdf.len() > 100, "PDF should have content");
 }
 
+#[test]
+fn test_streaming_emits_typst_source_per_chunk() {
+    let data = build_xlsx_with_rows(50, 3);
+    let options = config::ConvertOptions {
+        streaming: true,
+        streaming_chunk_size: Some(20),
+        emit_typst_source: true,
+        ..Default::default()
+    };
+    let result = convert_bytes(&data, config::Format::Xlsx, &options).unwrap();
+    assert_eq!(result.typst_sources.len(), 3);
+    assert!(result.typst_sources[0].source.contains("R1C1"));
+    assert!(result.typst_sources[2].source.contains("R50C3"));
+}
+
+#[test]
+fn test_streaming_reports_progress_per_chunk() {
+    use std::sync::{Arc, Mutex};
+
+    use config::{Progress, ProgressCallback, ProgressStage};
+
+    let reports: Arc<Mutex<Vec<(ProgressStage, u32, u32)>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&reports);
+    let options = config::ConvertOptions {
+        streaming: true,
+        streaming_chunk_size: Some(20),
+        progress: Some(ProgressCallback::new(move |progress: Progress| {
+            sink.lock()
+                .unwrap()
+                .push((progress.stage, progress.current, progress.total));
+        })),
+        ..Default::default()
+    };
+    convert_bytes(&build_xlsx_with_rows(50, 3), config::Format::Xlsx, &options).unwrap();
+    assert_eq!(
+        *reports.lock().unwrap(),
+        vec![
+            (ProgressStage::Parse, 0, 1),
+            (ProgressStage::Parse, 1, 1),
+            (ProgressStage::Compile, 0, 3),
+            (ProgressStage::Compile, 1, 3),
+            (ProgressStage::Compile, 2, 3),
+            (ProgressStage::Compile, 3, 3),
+            (ProgressStage::Merge, 0, 1),
+            (ProgressStage::Merge, 1, 1),
+        ]
+    );
+}
+
 #[test]
 fn test_streaming_xlsx_same_data_as_normal() {
     let data = build_xlsx_with_rows(10, 2);
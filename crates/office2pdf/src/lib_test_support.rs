@@ -154,6 +154,11 @@ pub(super) fn build_test_xlsx() -> Vec<u8> {
 }
 
 pub(super) fn build_test_pptx() -> Vec<u8> {
+    build_pptx_with_text_box(8229600, 1143000, "Hello from PPTX")
+}
+
+/// A one-slide deck with a text box of `cx` x `cy` EMU holding `text`.
+pub(super) fn build_pptx_with_text_box(cx: u64, cy: u64, text: &str) -> Vec<u8> {
     use std::io::{Cursor, Write};
 
     let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
@@ -182,7 +187,10 @@ pub(super) fn build_test_pptx() -> Vec<u8> {
 
     zip.start_file("ppt/slides/slide1.xml", opts).unwrap();
     zip.write_all(
-        br#"<?xml version="1.0" encoding="UTF-8"?><p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cSld><p:spTree><p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr/><p:sp><p:nvSpPr><p:cNvPr id="2" name="TextBox 1"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="457200" y="274638"/><a:ext cx="8229600" cy="1143000"/></a:xfrm></p:spPr><p:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:t>Hello from PPTX</a:t></a:r></a:p></p:txBody></p:sp></p:spTree></p:cSld></p:sld>"#,
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cSld><p:spTree><p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr/><p:sp><p:nvSpPr><p:cNvPr id="2" name="TextBox 1"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="457200" y="274638"/><a:ext cx="{cx}" cy="{cy}"/></a:xfrm></p:spPr><p:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:t>{text}</a:t></a:r></a:p></p:txBody></p:sp></p:spTree></p:cSld></p:sld>"#
+        )
+        .as_bytes(),
     ).unwrap();
 
     zip.start_file("ppt/slides/_rels/slide1.xml.rels", opts)
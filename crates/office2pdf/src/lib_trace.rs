@@ -0,0 +1,97 @@
+//! `tracing` spans for conversions, with the `tracing-spans` feature: a
+//! `convert` span per conversion, recording the format, input and output
+//! sizes, page and warning counts, stage durations and any error, around
+//! `parse`, `codegen`, `compile` and `merge` spans for its stages. Without
+//! the feature these compile to nothing.
+
+use crate::config::{Format, ProgressStage};
+use crate::error::{ConvertError, ConvertResult};
+
+/// Run one conversion inside a `convert` span and record its outcome on
+/// the span once it finishes.
+pub(super) fn conversion(
+    format: Format,
+    input_bytes: usize,
+    run: impl FnOnce() -> Result<ConvertResult, ConvertError>,
+) -> Result<ConvertResult, ConvertError> {
+    #[cfg(not(feature = "tracing-spans"))]
+    {
+        let _ = (format, input_bytes);
+        run()
+    }
+    #[cfg(feature = "tracing-spans")]
+    {
+        use tracing::field::{Empty, display};
+
+        let span = tracing::info_span!(
+            "convert",
+            format = ?format,
+            input_bytes = input_bytes as u64,
+            output_bytes = Empty,
+            page_count = Empty,
+            warning_count = Empty,
+            parse_ms = Empty,
+            codegen_ms = Empty,
+            compile_ms = Empty,
+            total_ms = Empty,
+            error = Empty,
+        );
+        let result = span.in_scope(run);
+        match &result {
+            Ok(result) => {
+                span.record("warning_count", result.warnings.len() as u64);
+                if let Some(metrics) = &result.metrics {
+                    span.record("output_bytes", metrics.output_size_bytes);
+                    span.record("page_count", metrics.page_count);
+                    span.record("parse_ms", metrics.parse_duration.as_millis() as u64);
+                    span.record("codegen_ms", metrics.codegen_duration.as_millis() as u64);
+                    span.record("compile_ms", metrics.compile_duration.as_millis() as u64);
+                    span.record("total_ms", metrics.total_duration.as_millis() as u64);
+                }
+            }
+            Err(error) => {
+                span.record("error", display(error));
+            }
+        }
+        // Subscribers that only print events, like `fmt`, show nothing for
+        // a span; one event on close carries its fields to them.
+        span.in_scope(|| match &result {
+            Ok(_) => tracing::info!("conversion finished"),
+            Err(error) => tracing::warn!(%error, "conversion failed"),
+        });
+        result
+    }
+}
+
+/// Guard of a pipeline stage's span; the span closes when it drops.
+pub(super) struct StageSpan {
+    #[cfg(feature = "tracing-spans")]
+    _entered: tracing::span::EnteredSpan,
+}
+
+/// Enter the span of `stage`, nested in the current `convert` span.
+pub(super) fn stage(stage: ProgressStage) -> StageSpan {
+    #[cfg(not(feature = "tracing-spans"))]
+    {
+        let _ = stage;
+        StageSpan {}
+    }
+    #[cfg(feature = "tracing-spans")]
+    {
+        // Span names are static metadata, so each stage needs its own
+        // macro call.
+        let span = match stage {
+            ProgressStage::Parse => tracing::info_span!("parse"),
+            ProgressStage::Codegen => tracing::info_span!("codegen"),
+            ProgressStage::Compile => tracing::info_span!("compile"),
+            ProgressStage::Merge => tracing::info_span!("merge"),
+        };
+        StageSpan {
+            _entered: span.entered(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "tracing-spans"))]
+#[path = "lib_trace_tests.rs"]
+mod tests;
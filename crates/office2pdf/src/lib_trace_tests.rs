@@ -0,0 +1,107 @@
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+use crate::config::{ConvertOptions, Format};
+use crate::test_support::build_test_docx;
+
+/// Name and recorded fields of each span, in creation order.
+type Spans = Arc<Mutex<Vec<(String, Vec<(String, String)>)>>>;
+
+struct Recorder {
+    spans: Spans,
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .push((field.name().to_string(), format!("{value:?}")));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut spans = self.spans.lock().unwrap();
+        let mut fields: Vec<(String, String)> = Vec::new();
+        span.record(&mut FieldVisitor(&mut fields));
+        spans.push((span.metadata().name().to_string(), fields));
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        let index: usize = span.into_u64() as usize - 1;
+        values.record(&mut FieldVisitor(&mut spans[index].1));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn test_conversion_records_stage_spans_and_outcome() {
+    let spans: Spans = Arc::default();
+    let recorder = Recorder {
+        spans: spans.clone(),
+    };
+    let docx: Vec<u8> = build_test_docx();
+    tracing::subscriber::with_default(recorder, || {
+        crate::convert_bytes(&docx, Format::Docx, &ConvertOptions::default()).unwrap();
+    });
+
+    let spans = spans.lock().unwrap();
+    // Dependencies may open spans of their own in between.
+    let names: Vec<&str> = spans
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .filter(|name| ["convert", "parse", "codegen", "compile", "merge"].contains(name))
+        .collect();
+    assert_eq!(names, ["convert", "parse", "codegen", "compile"]);
+    let field = |name: &str| -> Option<&str> {
+        spans[0]
+            .1
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    };
+    assert_eq!(field("format"), Some("Docx"));
+    assert_eq!(field("input_bytes"), Some(docx.len().to_string().as_str()));
+    assert_eq!(field("page_count"), Some("1"));
+    assert_eq!(field("warning_count"), Some("0"));
+    assert!(field("total_ms").is_some());
+    assert!(field("error").is_none());
+}
+
+#[test]
+fn test_failed_conversion_records_the_error() {
+    let spans: Spans = Arc::default();
+    let recorder = Recorder {
+        spans: spans.clone(),
+    };
+    tracing::subscriber::with_default(recorder, || {
+        assert!(
+            crate::convert_bytes(b"not a zip", Format::Docx, &ConvertOptions::default()).is_err()
+        );
+    });
+
+    let spans = spans.lock().unwrap();
+    assert_eq!(spans[0].0, "convert");
+    assert!(
+        spans[0].1.iter().any(|(field, _)| field == "error"),
+        "{:?}",
+        spans[0].1
+    );
+}
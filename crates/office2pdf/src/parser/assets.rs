@@ -0,0 +1,107 @@
+//! Listing the files embedded in an OOXML package: the pictures and other
+//! media its parts reference, and its embedded fonts.
+//!
+//! The pictures and media are read as stored, straight from the package's
+//! media and embeddings folders; fonts come deobfuscated from
+//! [`embedded_fonts`](super::embedded_fonts).
+
+use std::io::Read;
+
+use crate::config::{AssetKind, Format};
+use crate::error::{Asset, ConvertError};
+use crate::parser::embedded_fonts::{EmbeddedFont, read_embedded_fonts};
+
+/// Extensions of the pictures Office stores in its media folder.
+const IMAGE_EXTENSIONS: [&str; 13] = [
+    "png", "jpg", "jpeg", "jpe", "gif", "bmp", "tif", "tiff", "svg", "emf", "wmf", "webp", "ico",
+];
+
+/// The embedded files of `kinds` in the package `data`, in package order,
+/// fonts last.
+pub(crate) fn read_assets(
+    data: &[u8],
+    format: Format,
+    kinds: &[AssetKind],
+) -> Result<Vec<Asset>, ConvertError> {
+    let mut archive = super::open_zip(data)?;
+    let mut assets: Vec<Asset> = Vec::new();
+    if kinds.contains(&AssetKind::Image) || kinds.contains(&AssetKind::Media) {
+        for index in 0..archive.len() {
+            let mut entry = archive
+                .by_index(index)
+                .map_err(|error| super::parse_err(format!("Failed to read ZIP entry: {error}")))?;
+            if entry.is_dir() {
+                continue;
+            }
+            let Some(kind) = classify(entry.name(), format) else {
+                continue;
+            };
+            if !kinds.contains(&kind) {
+                continue;
+            }
+            let name: String = safe_file_name(entry.name().rsplit('/').next().unwrap_or_default());
+            let mut bytes: Vec<u8> = Vec::new();
+            entry.read_to_end(&mut bytes).map_err(|error| {
+                super::parse_err(format!("Failed to read {}: {error}", entry.name()))
+            })?;
+            assets.push(Asset {
+                kind,
+                name,
+                data: bytes,
+            });
+        }
+    }
+    if kinds.contains(&AssetKind::Font) {
+        assets.extend(
+            read_embedded_fonts(data, format)
+                .into_iter()
+                .map(|font: EmbeddedFont| Asset {
+                    kind: AssetKind::Font,
+                    name: safe_file_name(&font.file_name),
+                    data: font.data,
+                }),
+        );
+    }
+    Ok(assets)
+}
+
+/// `name` made safe to write as a file in a directory of the caller's
+/// choosing: font names come from the document's typeface attributes, so
+/// may hold path separators or be `..`.
+fn safe_file_name(name: &str) -> String {
+    let name: String = name.replace(['/', '\\'], "_");
+    if name.is_empty() || name.chars().all(|c| c == '.') {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+/// The kind of the package part at `path`, if it is an image or media file:
+/// anything under the format's `media/` or `embeddings/` folder. Fonts live
+/// elsewhere and are obfuscated, so they are read separately.
+fn classify(path: &str, format: Format) -> Option<AssetKind> {
+    let root: &str = match format {
+        Format::Docx => "word/",
+        Format::Pptx => "ppt/",
+        Format::Xlsx => "xl/",
+    };
+    let rest: &str = path.strip_prefix(root)?;
+    if rest.starts_with("embeddings/") {
+        return Some(AssetKind::Media);
+    }
+    let file_name: &str = rest.strip_prefix("media/")?;
+    let extension: String = file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase())
+        .unwrap_or_default();
+    if IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        Some(AssetKind::Image)
+    } else {
+        Some(AssetKind::Media)
+    }
+}
+
+#[cfg(test)]
+#[path = "assets_tests.rs"]
+mod tests;
@@ -0,0 +1,83 @@
+use super::*;
+
+use std::io::{Cursor, Write};
+
+use zip::ZipWriter;
+use zip::write::FileOptions;
+
+fn build_docx(parts: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default();
+    for (name, data) in parts {
+        zip.start_file(*name, options).unwrap();
+        zip.write_all(data).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+#[test]
+fn classify_sorts_media_folder_by_extension() {
+    assert_eq!(
+        classify("word/media/image1.PNG", Format::Docx),
+        Some(AssetKind::Image)
+    );
+    assert_eq!(
+        classify("ppt/media/image2.emf", Format::Pptx),
+        Some(AssetKind::Image)
+    );
+    assert_eq!(
+        classify("ppt/media/media1.mp4", Format::Pptx),
+        Some(AssetKind::Media)
+    );
+    assert_eq!(
+        classify("xl/embeddings/oleObject1.bin", Format::Xlsx),
+        Some(AssetKind::Media)
+    );
+    assert_eq!(classify("word/document.xml", Format::Docx), None);
+    // Another format's folder is not part of this package's content.
+    assert_eq!(classify("ppt/media/image1.png", Format::Docx), None);
+}
+
+#[test]
+fn read_assets_returns_only_requested_kinds() {
+    let docx: Vec<u8> = build_docx(&[
+        ("word/document.xml", b"<w:document/>"),
+        ("word/media/image1.png", b"png bytes"),
+        ("word/media/audio1.wav", b"wav bytes"),
+    ]);
+
+    let all: Vec<Asset> = read_assets(&docx, Format::Docx, &AssetKind::ALL).unwrap();
+    let names: Vec<(&str, AssetKind)> = all
+        .iter()
+        .map(|asset| (asset.name.as_str(), asset.kind))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            ("image1.png", AssetKind::Image),
+            ("audio1.wav", AssetKind::Media)
+        ]
+    );
+    assert_eq!(all[0].data, b"png bytes");
+
+    let images: Vec<Asset> = read_assets(&docx, Format::Docx, &[AssetKind::Image]).unwrap();
+    assert_eq!(images.len(), 1);
+    assert!(read_assets(&docx, Format::Docx, &[]).unwrap().is_empty());
+}
+
+#[test]
+fn safe_file_name_keeps_names_inside_the_output_directory() {
+    assert_eq!(safe_file_name("image1.png"), "image1.png");
+    assert_eq!(
+        safe_file_name("../Evil\\Font-regular.ttf"),
+        ".._Evil_Font-regular.ttf"
+    );
+    assert_eq!(safe_file_name(".."), "_..");
+    assert_eq!(safe_file_name(""), "_");
+}
+
+#[test]
+fn read_assets_rejects_non_zip_input() {
+    let result = read_assets(b"not a zip", Format::Docx, &AssetKind::ALL);
+    assert!(matches!(result, Err(ConvertError::Parse(_))));
+}
@@ -7,7 +7,7 @@ use quick_xml::Reader;
 use quick_xml::events::Event;
 
 use super::xml_util;
-use crate::ir::{Chart, ChartSeries, ChartType};
+use crate::ir::{Chart, ChartGrouping, ChartSeries, ChartType};
 
 /// Mapping from XML chart element tag names to their corresponding `ChartType`.
 /// Both 2-D and 3-D variants map to the same logical type.
@@ -18,8 +18,13 @@ const CHART_TAG_TYPES: &[(&[u8], ChartType)] = &[
     (b"line3DChart", ChartType::Line),
     (b"pieChart", ChartType::Pie),
     (b"pie3DChart", ChartType::Pie),
+    (b"ofPieChart", ChartType::Pie),
+    (b"doughnutChart", ChartType::Doughnut),
     (b"areaChart", ChartType::Area),
+    (b"area3DChart", ChartType::Area),
     (b"scatterChart", ChartType::Scatter),
+    (b"bubbleChart", ChartType::Bubble),
+    (b"radarChart", ChartType::Radar),
 ];
 
 /// Look up a tag name in [`CHART_TAG_TYPES`] and return the matching `ChartType`.
@@ -30,13 +35,21 @@ fn chart_type_for_tag(tag: &[u8]) -> Option<ChartType> {
         .map(|(_, ct)| ct.clone())
 }
 
+/// One chart-type element of a plot area (e.g. `<c:barChart>`). Combo charts
+/// have several, each bound to its own pair of axes.
+struct ChartPlot {
+    chart_type: ChartType,
+    grouping: Option<ChartGrouping>,
+    axis_ids: Vec<String>,
+    series: Vec<ChartSeries>,
+}
+
 /// Parse a chart XML file (e.g., `word/charts/chart1.xml`) into a `Chart` IR.
 pub(crate) fn parse_chart_xml(xml: &str) -> Option<Chart> {
     let mut reader = Reader::from_str(xml);
-    let mut chart_type = None;
     let mut title = None;
     let mut categories: Vec<String> = Vec::new();
-    let mut series: Vec<ChartSeries> = Vec::new();
+    let mut plots: Vec<ChartPlot> = Vec::new();
 
     loop {
         match reader.read_event() {
@@ -46,8 +59,7 @@ pub(crate) fn parse_chart_xml(xml: &str) -> Option<Chart> {
                 if tag == b"title" && title.is_none() {
                     title = parse_chart_title(&mut reader);
                 } else if let Some(ct) = chart_type_for_tag(tag) {
-                    chart_type = Some(ct);
-                    parse_chart_series(&mut reader, tag, &mut categories, &mut series);
+                    plots.push(parse_chart_plot(&mut reader, tag, ct, &mut categories));
                 }
             }
             Ok(Event::Eof) => break,
@@ -56,7 +68,24 @@ pub(crate) fn parse_chart_xml(xml: &str) -> Option<Chart> {
         }
     }
 
-    let chart_type = chart_type?;
+    // The first plot defines the chart type, grouping, and primary axes;
+    // series of later plots keep their own type and move to the secondary
+    // value axis when their plot is bound to different axes.
+    let first_plot: &ChartPlot = plots.first()?;
+    let chart_type: ChartType = first_plot.chart_type.clone();
+    let primary_axis_ids: Vec<String> = first_plot.axis_ids.clone();
+    let grouping: ChartGrouping = first_plot.grouping.unwrap_or_default();
+    let mut series: Vec<ChartSeries> = Vec::new();
+    for plot in plots {
+        let secondary_axis: bool = !plot.axis_ids.is_empty() && plot.axis_ids != primary_axis_ids;
+        let series_type: Option<ChartType> =
+            (plot.chart_type != chart_type).then_some(plot.chart_type);
+        series.extend(plot.series.into_iter().map(|mut ser| {
+            ser.chart_type = series_type.clone();
+            ser.secondary_axis = secondary_axis;
+            ser
+        }));
+    }
 
     // Charts may omit <c:cat> entirely; Excel then labels the category axis
     // 1..N (the point count of the longest series).
@@ -70,6 +99,7 @@ pub(crate) fn parse_chart_xml(xml: &str) -> Option<Chart> {
         title,
         categories,
         series,
+        grouping,
     })
 }
 
@@ -118,13 +148,22 @@ fn parse_chart_title(reader: &mut Reader<&[u8]>) -> Option<String> {
     }
 }
 
-/// Parse series data from within a chart type element (e.g., `<c:barChart>`).
-fn parse_chart_series(
+/// Parse a chart type element (e.g., `<c:barChart>`): its series, grouping,
+/// and axis bindings.
+///
+/// A `<c:barDir val="col">` child turns a bar chart into a column chart.
+fn parse_chart_plot(
     reader: &mut Reader<&[u8]>,
     end_tag: &[u8],
+    chart_type: ChartType,
     categories: &mut Vec<String>,
-    series: &mut Vec<ChartSeries>,
-) {
+) -> ChartPlot {
+    let mut plot = ChartPlot {
+        chart_type,
+        grouping: None,
+        axis_ids: Vec::new(),
+        series: Vec::new(),
+    };
     loop {
         match reader.read_event() {
             Ok(Event::Start(ref e)) => {
@@ -134,7 +173,26 @@ fn parse_chart_series(
                     if categories.is_empty() && !cats.is_empty() {
                         *categories = cats;
                     }
-                    series.push(ser);
+                    plot.series.push(ser);
+                }
+            }
+            Ok(Event::Empty(ref e)) => {
+                let val: Option<String> = xml_util::get_attr_str(e, b"val");
+                match e.local_name().as_ref() {
+                    b"barDir" => {
+                        if plot.chart_type == ChartType::Bar && val.as_deref() == Some("col") {
+                            plot.chart_type = ChartType::Column;
+                        }
+                    }
+                    b"grouping" => {
+                        plot.grouping = match val.as_deref() {
+                            Some("stacked") => Some(ChartGrouping::Stacked),
+                            Some("percentStacked") => Some(ChartGrouping::PercentStacked),
+                            _ => None,
+                        };
+                    }
+                    b"axId" => plot.axis_ids.extend(val),
+                    _ => {}
                 }
             }
             Ok(Event::End(ref e)) if e.local_name().as_ref() == end_tag => break,
@@ -142,12 +200,14 @@ fn parse_chart_series(
             _ => {}
         }
     }
+    plot
 }
 
 /// Parse a single `<c:ser>` element and return the series data + category labels.
 fn parse_single_series(reader: &mut Reader<&[u8]>) -> (ChartSeries, Vec<String>) {
     let mut name = None;
     let mut values = Vec::new();
+    let mut bubble_sizes = Vec::new();
     let mut categories = Vec::new();
 
     loop {
@@ -156,6 +216,7 @@ fn parse_single_series(reader: &mut Reader<&[u8]>) -> (ChartSeries, Vec<String>)
                 b"tx" => name = parse_series_text(reader),
                 b"cat" => categories = parse_category_data(reader),
                 b"val" | b"yVal" => values = parse_value_data(reader),
+                b"bubbleSize" => bubble_sizes = parse_value_data(reader),
                 b"xVal" => {
                     // For scatter charts, xVal contains category-like data
                     if categories.is_empty() {
@@ -172,7 +233,16 @@ fn parse_single_series(reader: &mut Reader<&[u8]>) -> (ChartSeries, Vec<String>)
         }
     }
 
-    (ChartSeries { name, values }, categories)
+    (
+        ChartSeries {
+            name,
+            values,
+            bubble_sizes,
+            chart_type: None,
+            secondary_axis: false,
+        },
+        categories,
+    )
 }
 
 /// Parse series name from `<c:tx>`.
@@ -240,7 +310,7 @@ fn parse_category_data(reader: &mut Reader<&[u8]>) -> Vec<String> {
     categories
 }
 
-/// Parse numeric values from `<c:val>` or `<c:yVal>`.
+/// Parse numeric values from `<c:val>`, `<c:yVal>`, or `<c:bubbleSize>`.
 fn parse_value_data(reader: &mut Reader<&[u8]>) -> Vec<f64> {
     let mut values = Vec::new();
     let mut in_v = false;
@@ -266,7 +336,7 @@ fn parse_value_data(reader: &mut Reader<&[u8]>) -> Vec<f64> {
                         values.push(v);
                     }
                 }
-                b"val" | b"yVal" => break,
+                b"val" | b"yVal" | b"bubbleSize" => break,
                 _ => {}
             },
             Ok(Event::Eof) | Err(_) => break,
@@ -77,6 +77,37 @@ fn test_parse_pie_chart() {
     assert_eq!(chart.series[0].values, vec![30.0, 45.0, 25.0]);
 }
 
+#[test]
+fn test_parse_bar_chart_column_direction() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart">
+            <c:chart>
+                <c:plotArea>
+                    <c:barChart>
+                        <c:barDir val="col"/>
+                        <c:grouping val="clustered"/>
+                        <c:ser>
+                            <c:idx val="0"/>
+                            <c:val>
+                                <c:numLit>
+                                    <c:pt idx="0"><c:v>4</c:v></c:pt>
+                                    <c:pt idx="1"><c:v>7</c:v></c:pt>
+                                </c:numLit>
+                            </c:val>
+                        </c:ser>
+                    </c:barChart>
+                </c:plotArea>
+            </c:chart>
+        </c:chartSpace>"#;
+
+    let chart = parse_chart_xml(xml).unwrap();
+    assert_eq!(chart.chart_type, ChartType::Column);
+    assert_eq!(chart.series[0].values, vec![4.0, 7.0]);
+
+    let horizontal = parse_chart_xml(&xml.replace(r#"val="col""#, r#"val="bar""#)).unwrap();
+    assert_eq!(horizontal.chart_type, ChartType::Bar);
+}
+
 #[test]
 fn test_parse_line_chart_multiple_series() {
     let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -197,3 +228,107 @@ fn test_scan_chart_rels() {
     assert_eq!(rels.get("rId4").unwrap(), "word/charts/chart1.xml");
     assert_eq!(rels.get("rId5").unwrap(), "word/charts/chart2.xml");
 }
+
+/// Wrap plot-area XML in a minimal chart part.
+fn chart_with_plot_area(plot_area_xml: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+        <c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart">
+            <c:chart><c:plotArea>{plot_area_xml}</c:plotArea></c:chart>
+        </c:chartSpace>"#
+    )
+}
+
+fn literal_series(name: &str, values: &[f64]) -> String {
+    let points: String = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| format!(r#"<c:pt idx="{index}"><c:v>{value}</c:v></c:pt>"#))
+        .collect();
+    format!(
+        r#"<c:ser><c:tx><c:v>{name}</c:v></c:tx><c:val><c:numLit>{points}</c:numLit></c:val></c:ser>"#
+    )
+}
+
+#[test]
+fn test_parse_doughnut_and_radar_chart_types() {
+    let doughnut = parse_chart_xml(&chart_with_plot_area(&format!(
+        "<c:doughnutChart>{}<c:holeSize val=\"50\"/></c:doughnutChart>",
+        literal_series("Share", &[1.0, 2.0])
+    )))
+    .unwrap();
+    assert_eq!(doughnut.chart_type, ChartType::Doughnut);
+
+    let radar = parse_chart_xml(&chart_with_plot_area(&format!(
+        "<c:radarChart><c:radarStyle val=\"marker\"/>{}</c:radarChart>",
+        literal_series("Skills", &[3.0, 4.0, 5.0])
+    )))
+    .unwrap();
+    assert_eq!(radar.chart_type, ChartType::Radar);
+    assert_eq!(radar.series[0].values, vec![3.0, 4.0, 5.0]);
+}
+
+#[test]
+fn test_parse_bubble_chart_sizes() {
+    let xml = chart_with_plot_area(
+        r#"<c:bubbleChart><c:ser>
+            <c:xVal><c:numLit><c:pt idx="0"><c:v>1</c:v></c:pt><c:pt idx="1"><c:v>3</c:v></c:pt></c:numLit></c:xVal>
+            <c:yVal><c:numLit><c:pt idx="0"><c:v>10</c:v></c:pt><c:pt idx="1"><c:v>20</c:v></c:pt></c:numLit></c:yVal>
+            <c:bubbleSize><c:numLit><c:pt idx="0"><c:v>4</c:v></c:pt><c:pt idx="1"><c:v>9</c:v></c:pt></c:numLit></c:bubbleSize>
+        </c:ser></c:bubbleChart>"#,
+    );
+
+    let chart = parse_chart_xml(&xml).unwrap();
+    assert_eq!(chart.chart_type, ChartType::Bubble);
+    assert_eq!(chart.categories, vec!["1", "3"]);
+    assert_eq!(chart.series[0].values, vec![10.0, 20.0]);
+    assert_eq!(chart.series[0].bubble_sizes, vec![4.0, 9.0]);
+}
+
+#[test]
+fn test_parse_stacked_grouping() {
+    let stacked = parse_chart_xml(&chart_with_plot_area(&format!(
+        r#"<c:barChart><c:barDir val="col"/><c:grouping val="stacked"/>{}{}<c:overlap val="100"/></c:barChart>"#,
+        literal_series("A", &[1.0]),
+        literal_series("B", &[2.0])
+    )))
+    .unwrap();
+    assert_eq!(stacked.chart_type, ChartType::Column);
+    assert_eq!(stacked.grouping, ChartGrouping::Stacked);
+
+    let percent = parse_chart_xml(&chart_with_plot_area(&format!(
+        r#"<c:areaChart><c:grouping val="percentStacked"/>{}</c:areaChart>"#,
+        literal_series("A", &[1.0])
+    )))
+    .unwrap();
+    assert_eq!(percent.grouping, ChartGrouping::PercentStacked);
+
+    let clustered = parse_chart_xml(&chart_with_plot_area(&format!(
+        r#"<c:lineChart><c:grouping val="standard"/>{}</c:lineChart>"#,
+        literal_series("A", &[1.0])
+    )))
+    .unwrap();
+    assert_eq!(clustered.grouping, ChartGrouping::Clustered);
+}
+
+#[test]
+fn test_parse_combo_chart_with_secondary_axis() {
+    let xml = chart_with_plot_area(&format!(
+        r#"<c:barChart><c:barDir val="col"/><c:grouping val="clustered"/>{}<c:axId val="10"/><c:axId val="20"/></c:barChart>
+           <c:lineChart><c:grouping val="standard"/>{}<c:axId val="30"/><c:axId val="40"/></c:lineChart>
+           <c:lineChart><c:grouping val="standard"/>{}<c:axId val="10"/><c:axId val="20"/></c:lineChart>"#,
+        literal_series("Revenue", &[100.0, 150.0]),
+        literal_series("Margin", &[0.2, 0.25]),
+        literal_series("Target", &[120.0, 130.0])
+    ));
+
+    let chart = parse_chart_xml(&xml).unwrap();
+    assert_eq!(chart.chart_type, ChartType::Column);
+    assert_eq!(chart.series.len(), 3);
+    assert_eq!(chart.series[0].chart_type, None);
+    assert!(!chart.series[0].secondary_axis);
+    assert_eq!(chart.series[1].chart_type, Some(ChartType::Line));
+    assert!(chart.series[1].secondary_axis);
+    assert_eq!(chart.series[2].chart_type, Some(ChartType::Line));
+    assert!(!chart.series[2].secondary_axis);
+}
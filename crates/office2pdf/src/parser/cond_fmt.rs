@@ -1,8 +1,9 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
 use crate::ir::{Color, DataBarInfo};
 use crate::parser::xlsx::cond_fmt_raw::{RawCondFmtHint, RawCondFmtHints};
-use crate::parser::xlsx::{CellPos, CellRange, parse_cell_ref};
+use crate::parser::xlsx::{CellPos, CellRange, FormulaCache, parse_cell_ref};
 use crate::parser::xml_util;
 
 /// A conditional formatting override for a specific cell.
@@ -54,32 +55,97 @@ fn cell_numeric_value(cell: &umya_spreadsheet::Cell) -> Option<f64> {
     cell.get_value().to_string().parse::<f64>().ok()
 }
 
-/// Evaluate a CellIs conditional formatting rule against a cell value.
+/// The right-hand side of a `cellIs` comparison.
+#[derive(Debug, Clone, PartialEq)]
+enum CellIsOperand {
+    Number(f64),
+    Text(String),
+}
+
+/// Resolve a `cellIs` formula (already shifted to the cell being tested) to
+/// a literal number, a quoted string, or the value of a referenced cell.
+/// Other expressions are not evaluated.
+fn resolve_cell_is_operand(
+    sheet: &umya_spreadsheet::Worksheet,
+    formula: &str,
+) -> Option<CellIsOperand> {
+    let formula: &str = formula.trim().trim_start_matches('=');
+    if let Ok(number) = formula.parse::<f64>() {
+        return Some(CellIsOperand::Number(number));
+    }
+    if let Some(text) = formula
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+    {
+        return Some(CellIsOperand::Text(text.replace("\"\"", "\"")));
+    }
+    let (col, row) = parse_cell_ref(formula)?;
+    let Some(cell) = sheet.get_cell((col, row)) else {
+        return Some(CellIsOperand::Number(0.0));
+    };
+    Some(match cell_numeric_value(cell) {
+        Some(number) => CellIsOperand::Number(number),
+        None => CellIsOperand::Text(cell.get_value().into_owned()),
+    })
+}
+
+/// Compare a cell with a `cellIs` operand. Text compares case-insensitively
+/// and a number sorts before any text, as in Excel. A text cell is not
+/// compared with a number.
+fn compare_cell_with_operand(
+    cell: &umya_spreadsheet::Cell,
+    operand: &CellIsOperand,
+) -> Option<Ordering> {
+    let number: Option<f64> = cell_numeric_value(cell);
+    match (operand, number) {
+        (CellIsOperand::Number(threshold), Some(value)) => {
+            if (value - threshold).abs() < f64::EPSILON {
+                Some(Ordering::Equal)
+            } else {
+                value.partial_cmp(threshold)
+            }
+        }
+        (CellIsOperand::Number(_), None) => None,
+        (CellIsOperand::Text(_), Some(_)) => Some(Ordering::Less),
+        (CellIsOperand::Text(text), None) => {
+            Some(cell.get_value().to_lowercase().cmp(&text.to_lowercase()))
+        }
+    }
+}
+
+/// Evaluate a CellIs conditional formatting rule against a cell. A
+/// `between` rule with a single operand acts as its lower bound.
 fn evaluate_cell_is_rule(
-    cell_val: f64,
+    cell: &umya_spreadsheet::Cell,
     operator: &umya_spreadsheet::ConditionalFormattingOperatorValues,
-    rule: &umya_spreadsheet::ConditionalFormattingRule,
+    operands: &[CellIsOperand],
 ) -> bool {
     use umya_spreadsheet::ConditionalFormattingOperatorValues::*;
 
-    let formula_val = rule.get_formula().and_then(|f| {
-        let s = f.get_address_str();
-        s.trim().parse::<f64>().ok()
-    });
-
-    let Some(threshold) = formula_val else {
+    let Some(first) = operands
+        .first()
+        .and_then(|operand| compare_cell_with_operand(cell, operand))
+    else {
         return false;
     };
+    let second: Option<Ordering> = operands
+        .get(1)
+        .and_then(|operand| compare_cell_with_operand(cell, operand));
+    // Excel accepts the bounds in either order.
+    let between = |second: Ordering| -> bool {
+        (first != Ordering::Less && second != Ordering::Greater)
+            || (first != Ordering::Greater && second != Ordering::Less)
+    };
 
     match operator {
-        GreaterThan => cell_val > threshold,
-        GreaterThanOrEqual => cell_val >= threshold,
-        LessThan => cell_val < threshold,
-        LessThanOrEqual => cell_val <= threshold,
-        Equal => (cell_val - threshold).abs() < f64::EPSILON,
-        NotEqual => (cell_val - threshold).abs() >= f64::EPSILON,
-        Between => cell_val >= threshold,
-        NotBetween => cell_val < threshold,
+        GreaterThan => first == Ordering::Greater,
+        GreaterThanOrEqual => first != Ordering::Less,
+        LessThan => first == Ordering::Less,
+        LessThanOrEqual => first != Ordering::Greater,
+        Equal => first == Ordering::Equal,
+        NotEqual => first != Ordering::Equal,
+        Between => second.map_or(first != Ordering::Less, between),
+        NotBetween => second.map_or(first == Ordering::Less, |second| !between(second)),
         _ => false,
     }
 }
@@ -115,6 +181,33 @@ fn extract_cond_fmt_style(rule: &umya_spreadsheet::ConditionalFormattingRule) ->
     result
 }
 
+/// Apply a matched rule's differential format to a cell's override.
+fn merge_style(entry: &mut CondFmtOverride, fmt: &CondFmtOverride) {
+    if fmt.background.is_some() {
+        entry.background = fmt.background;
+    }
+    if fmt.font_color.is_some() {
+        entry.font_color = fmt.font_color;
+    }
+    if fmt.bold.is_some() {
+        entry.bold = fmt.bold;
+    }
+}
+
+/// Every cell position of `ranges`, clipped to the sheet's used area so a
+/// rule over whole rows does not visit a million empty cells.
+fn range_cells(
+    sheet: &umya_spreadsheet::Worksheet,
+    ranges: &[CellRange],
+) -> impl Iterator<Item = CellPos> {
+    let (max_col, max_row) = sheet.get_highest_column_and_row();
+    ranges.iter().flat_map(move |range| {
+        (range.start_row..=range.end_row.min(max_row)).flat_map(move |row| {
+            (range.start_col..=range.end_col.min(max_col)).map(move |col| (col, row))
+        })
+    })
+}
+
 /// Parse an ARGB hex string from umya Color into an IR Color.
 fn parse_umya_color_argb(color: &umya_spreadsheet::Color) -> Option<Color> {
     let argb = color.get_argb();
@@ -196,16 +289,7 @@ fn apply_text_rule(
                     _ => false,
                 };
                 if matched {
-                    let entry = overrides.entry((col, row)).or_default();
-                    if fmt.background.is_some() {
-                        entry.background = fmt.background;
-                    }
-                    if fmt.font_color.is_some() {
-                        entry.font_color = fmt.font_color;
-                    }
-                    if fmt.bold.is_some() {
-                        entry.bold = fmt.bold;
-                    }
+                    merge_style(overrides.entry((col, row)).or_default(), &fmt);
                 }
             }
         }
@@ -217,39 +301,322 @@ fn apply_cell_is_rule(
     rule: &umya_spreadsheet::ConditionalFormattingRule,
     ranges: &[CellRange],
     overrides: &mut HashMap<CellPos, CondFmtOverride>,
+    raw_hint: Option<&RawCondFmtHint>,
 ) {
     let operator = rule.get_operator();
     let fmt = extract_cond_fmt_style(rule);
+    let formulas: Vec<String> = rule_formulas(rule, raw_hint);
+    if formulas.is_empty() {
+        return;
+    }
+    let origin: CellPos = (ranges[0].start_col, ranges[0].start_row);
 
-    for range in ranges {
-        for row in range.start_row..=range.end_row {
-            for col in range.start_col..=range.end_col {
-                if let Some(cell) = sheet.get_cell((col, row))
-                    && let Some(val) = cell_numeric_value(cell)
-                    && evaluate_cell_is_rule(val, operator, rule)
-                {
-                    let entry = overrides.entry((col, row)).or_default();
-                    if fmt.background.is_some() {
-                        entry.background = fmt.background;
-                    }
-                    if fmt.font_color.is_some() {
-                        entry.font_color = fmt.font_color;
-                    }
-                    if fmt.bold.is_some() {
-                        entry.bold = fmt.bold;
-                    }
-                }
+    for (col, row) in range_cells(sheet, ranges) {
+        let Some(cell) = sheet.get_cell((col, row)) else {
+            continue;
+        };
+        let operands: Vec<CellIsOperand> = formulas
+            .iter()
+            .filter_map(|formula| {
+                let shifted: String = shift_relative_references(formula, origin, (col, row));
+                resolve_cell_is_operand(sheet, &shifted)
+            })
+            .collect();
+        if evaluate_cell_is_rule(cell, operator, &operands) {
+            merge_style(overrides.entry((col, row)).or_default(), &fmt);
+        }
+    }
+}
+
+/// The rule's formulas: all of them from the raw XML when available,
+/// otherwise the single one umya keeps.
+fn rule_formulas(
+    rule: &umya_spreadsheet::ConditionalFormattingRule,
+    raw_hint: Option<&RawCondFmtHint>,
+) -> Vec<String> {
+    match raw_hint.filter(|hint| !hint.formulas.is_empty()) {
+        Some(hint) => hint.formulas.clone(),
+        None => rule
+            .get_formula()
+            .map(|formula| vec![formula.get_address_str()])
+            .unwrap_or_default(),
+    }
+}
+
+/// Rewrite `formula`, written for the `origin` cell, for the cell at
+/// `target`: relative references move by the offset between the two while
+/// `$`-anchored columns and rows stay put. Text in quotes is left alone.
+fn shift_relative_references(formula: &str, origin: CellPos, target: CellPos) -> String {
+    let col_offset: i64 = i64::from(target.0) - i64::from(origin.0);
+    let row_offset: i64 = i64::from(target.1) - i64::from(origin.1);
+    if col_offset == 0 && row_offset == 0 {
+        return formula.to_string();
+    }
+    let chars: Vec<char> = formula.chars().collect();
+    let mut shifted = String::with_capacity(formula.len());
+    let mut i: usize = 0;
+    while i < chars.len() {
+        let c: char = chars[i];
+        if c == '"' || c == '\'' {
+            let end: usize = chars[i + 1..]
+                .iter()
+                .position(|ch| *ch == c)
+                .map_or(chars.len(), |pos| i + pos + 2);
+            shifted.extend(&chars[i..end]);
+            i = end;
+        } else if c.is_alphabetic() || c == '$' || c == '_' {
+            let start: usize = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || matches!(chars[i], '$' | '_' | '.'))
+            {
+                i += 1;
             }
+            let word: String = chars[start..i].iter().collect();
+            // Function names and sheet prefixes are not references.
+            let is_reference_position: bool = !matches!(chars.get(i), Some('(') | Some('!'));
+            let reference: Option<String> = if is_reference_position {
+                shift_cell_reference(&word, col_offset, row_offset)
+            } else {
+                None
+            };
+            shifted.push_str(reference.as_deref().unwrap_or(&word));
+        } else {
+            shifted.push(c);
+            i += 1;
+        }
+    }
+    shifted
+}
+
+/// Move a single cell reference such as `B$2` by the given offsets. Returns
+/// None when `word` is not a cell reference and `#REF!` when the moved
+/// reference falls off the sheet.
+fn shift_cell_reference(word: &str, col_offset: i64, row_offset: i64) -> Option<String> {
+    let (col_absolute, rest): (bool, &str) = match word.strip_prefix('$') {
+        Some(rest) => (true, rest),
+        None => (false, word),
+    };
+    let letters_end: usize = rest.find(|c: char| !c.is_ascii_alphabetic())?;
+    let (letters, rest) = rest.split_at(letters_end);
+    let (row_absolute, digits): (bool, &str) = match rest.strip_prefix('$') {
+        Some(digits) => (true, digits),
+        None => (false, rest),
+    };
+    if letters.is_empty()
+        || letters.len() > 3
+        || digits.is_empty()
+        || !digits.chars().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let (col, row) = parse_cell_ref(&format!("{}{digits}", letters.to_ascii_uppercase()))?;
+    let col: i64 = i64::from(col) + if col_absolute { 0 } else { col_offset };
+    let row: i64 = i64::from(row) + if row_absolute { 0 } else { row_offset };
+    if !(1..=16_384).contains(&col) || !(1..=1_048_576).contains(&row) {
+        return Some("#REF!".to_string());
+    }
+    Some(format!(
+        "{}{}{}{row}",
+        if col_absolute { "$" } else { "" },
+        umya_spreadsheet::helper::coordinate::string_from_column_index(&(col as u32)),
+        if row_absolute { "$" } else { "" },
+    ))
+}
+
+/// Apply an `expression` rule: the formula is written for the top-left cell
+/// of the first range and evaluated for every cell with its relative
+/// references moved along. Needs the `formula-eval` feature; without it
+/// expression rules are skipped.
+#[cfg_attr(not(feature = "formula-eval"), allow(unused_variables))]
+fn apply_expression_rule(
+    sheet: &umya_spreadsheet::Worksheet,
+    rule: &umya_spreadsheet::ConditionalFormattingRule,
+    ranges: &[CellRange],
+    overrides: &mut HashMap<CellPos, CondFmtOverride>,
+    raw_hint: Option<&RawCondFmtHint>,
+    formulas: &FormulaCache,
+) {
+    #[cfg(feature = "formula-eval")]
+    {
+        let Some(formula) = rule_formulas(rule, raw_hint).into_iter().next() else {
+            return;
+        };
+        let fmt = extract_cond_fmt_style(rule);
+        let origin: CellPos = (ranges[0].start_col, ranges[0].start_row);
+        for pos in range_cells(sheet, ranges) {
+            let shifted: String = shift_relative_references(&formula, origin, pos);
+            if crate::parser::xlsx::evaluate_condition(sheet, &shifted, formulas) {
+                merge_style(overrides.entry(pos).or_default(), &fmt);
+            }
+        }
+    }
+}
+
+/// Apply a `top10` rule: the top or bottom N values, or N percent of them.
+fn apply_top10_rule(
+    sheet: &umya_spreadsheet::Worksheet,
+    rule: &umya_spreadsheet::ConditionalFormattingRule,
+    ranges: &[CellRange],
+    overrides: &mut HashMap<CellPos, CondFmtOverride>,
+    raw_hint: Option<&RawCondFmtHint>,
+) {
+    let mut values: Vec<f64> = collect_numeric_values_in_ranges(sheet, ranges);
+    if values.is_empty() {
+        return;
+    }
+    let rank: usize = raw_hint.and_then(|hint| hint.rank).unwrap_or(10) as usize;
+    let percent: bool = raw_hint.is_some_and(|hint| hint.percent);
+    let bottom: bool = raw_hint.is_some_and(|hint| hint.bottom);
+    let count: usize = if percent {
+        (values.len() * rank / 100).max(1)
+    } else {
+        rank.max(1)
+    }
+    .min(values.len());
+    if bottom {
+        values.sort_by(f64::total_cmp);
+    } else {
+        values.sort_by(|a, b| b.total_cmp(a));
+    }
+    // Ties with the last ranked value are all included, as in Excel.
+    let cutoff: f64 = values[count - 1];
+    let fmt = extract_cond_fmt_style(rule);
+
+    for (col, row) in range_cells(sheet, ranges) {
+        let matched: bool = sheet
+            .get_cell((col, row))
+            .and_then(cell_numeric_value)
+            .is_some_and(|value| {
+                if bottom {
+                    value <= cutoff
+                } else {
+                    value >= cutoff
+                }
+            });
+        if matched {
+            merge_style(overrides.entry((col, row)).or_default(), &fmt);
+        }
+    }
+}
+
+/// Apply an `aboveAverage` rule, optionally offset by standard deviations.
+fn apply_above_average_rule(
+    sheet: &umya_spreadsheet::Worksheet,
+    rule: &umya_spreadsheet::ConditionalFormattingRule,
+    ranges: &[CellRange],
+    overrides: &mut HashMap<CellPos, CondFmtOverride>,
+    raw_hint: Option<&RawCondFmtHint>,
+) {
+    let values: Vec<f64> = collect_numeric_values_in_ranges(sheet, ranges);
+    if values.is_empty() {
+        return;
+    }
+    let count: f64 = values.len() as f64;
+    let mean: f64 = values.iter().sum::<f64>() / count;
+    let above: bool = raw_hint.and_then(|hint| hint.above_average).unwrap_or(true);
+    let equal: bool = raw_hint.is_some_and(|hint| hint.equal_average);
+    let std_devs: f64 = f64::from(raw_hint.and_then(|hint| hint.std_dev).unwrap_or(0));
+    let threshold: f64 = if std_devs > 0.0 && values.len() > 1 {
+        let variance: f64 = values
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / (count - 1.0);
+        let offset: f64 = std_devs * variance.sqrt();
+        if above { mean + offset } else { mean - offset }
+    } else {
+        mean
+    };
+    let fmt = extract_cond_fmt_style(rule);
+
+    for (col, row) in range_cells(sheet, ranges) {
+        let Some(value) = sheet.get_cell((col, row)).and_then(cell_numeric_value) else {
+            continue;
+        };
+        let matched: bool = match (above, equal) {
+            (true, true) => value >= threshold,
+            (true, false) => value > threshold,
+            (false, true) => value <= threshold,
+            (false, false) => value < threshold,
+        };
+        if matched {
+            merge_style(overrides.entry((col, row)).or_default(), &fmt);
+        }
+    }
+}
+
+/// Apply a `duplicateValues` or `uniqueValues` rule. Values compare by
+/// their displayed text, ignoring case.
+fn apply_duplicate_rule(
+    sheet: &umya_spreadsheet::Worksheet,
+    rule: &umya_spreadsheet::ConditionalFormattingRule,
+    ranges: &[CellRange],
+    overrides: &mut HashMap<CellPos, CondFmtOverride>,
+    unique: bool,
+) {
+    let keyed: Vec<(CellPos, String)> = range_cells(sheet, ranges)
+        .filter_map(|pos| {
+            let value: String = sheet.get_cell(pos)?.get_formatted_value().to_lowercase();
+            (!value.trim().is_empty()).then_some((pos, value))
+        })
+        .collect();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for (_, value) in &keyed {
+        *counts.entry(value.as_str()).or_default() += 1;
+    }
+    let fmt = extract_cond_fmt_style(rule);
+
+    for (pos, value) in &keyed {
+        let is_duplicate: bool = counts.get(value.as_str()).is_some_and(|count| *count > 1);
+        if is_duplicate != unique {
+            merge_style(overrides.entry(*pos).or_default(), &fmt);
+        }
+    }
+}
+
+/// Apply a `containsBlanks` / `notContainsBlanks` / `containsErrors` /
+/// `notContainsErrors` rule.
+fn apply_blank_or_error_rule(
+    sheet: &umya_spreadsheet::Worksheet,
+    rule: &umya_spreadsheet::ConditionalFormattingRule,
+    ranges: &[CellRange],
+    overrides: &mut HashMap<CellPos, CondFmtOverride>,
+) {
+    use umya_spreadsheet::ConditionalFormatValues;
+    const ERROR_VALUES: [&str; 7] = [
+        "#NULL!", "#DIV/0!", "#VALUE!", "#REF!", "#NAME?", "#NUM!", "#N/A",
+    ];
+    let fmt = extract_cond_fmt_style(rule);
+
+    for pos in range_cells(sheet, ranges) {
+        let value: String = sheet
+            .get_cell(pos)
+            .map(|cell| cell.get_value().into_owned())
+            .unwrap_or_default();
+        let is_blank: bool = value.trim().is_empty();
+        let is_error: bool = ERROR_VALUES.contains(&value.as_str());
+        let matched: bool = match rule.get_type() {
+            ConditionalFormatValues::ContainsBlanks => is_blank,
+            ConditionalFormatValues::NotContainsBlanks => !is_blank,
+            ConditionalFormatValues::ContainsErrors => is_error,
+            ConditionalFormatValues::NotContainsErrors => !is_error,
+            _ => false,
+        };
+        if matched {
+            merge_style(overrides.entry(pos).or_default(), &fmt);
         }
     }
 }
 
 /// Apply a ColorScale conditional formatting rule to cells in the given ranges.
+/// Each color sits at its `<cfvo>` stop (min, max, number, percent or
+/// percentile) and values between two stops blend their colors.
 fn apply_color_scale_rule(
     sheet: &umya_spreadsheet::Worksheet,
     rule: &umya_spreadsheet::ConditionalFormattingRule,
     ranges: &[CellRange],
     overrides: &mut HashMap<CellPos, CondFmtOverride>,
+    raw_hint: Option<&RawCondFmtHint>,
 ) {
     let Some(cs) = rule.get_color_scale() else {
         return;
@@ -266,42 +633,95 @@ fn apply_color_scale_rule(
     }
 
     let numeric_vals: Vec<f64> = collect_numeric_values_in_ranges(sheet, ranges);
-    let Some((min_val, _max_val, val_range)) = compute_min_max(&numeric_vals) else {
+    let Some((min_val, max_val, val_range)) = compute_min_max(&numeric_vals) else {
         return;
     };
 
-    let color_min: Color = colors[0].unwrap_or(Color::white());
-    let color_max: Color = colors[colors.len() - 1].unwrap_or(Color::black());
+    // The raw XML keeps cfvos umya drops (start/end tag pairs); otherwise
+    // umya's own cfvos are used.
+    let cfvos: Vec<(String, String)> = match raw_hint
+        .map(|hint| &hint.color_scale_cfvos)
+        .filter(|cfvos| cfvos.len() == colors.len())
+    {
+        Some(cfvos) => cfvos.clone(),
+        None => cs
+            .get_cfvo_collection()
+            .iter()
+            .map(|cfvo| (cfvo_kind(cfvo).to_string(), cfvo.get_val().to_string()))
+            .collect(),
+    };
+    let last: usize = colors.len() - 1;
+    let mut stops: Vec<(f64, Color)> = colors
+        .iter()
+        .enumerate()
+        .map(|(index, color)| {
+            // Stops that cannot be resolved (formula cfvos) fall back to
+            // Excel's defaults: min, 50th percentile, max.
+            let value: f64 = cfvos
+                .get(index)
+                .and_then(|(kind, raw_val)| {
+                    icon_cfvo_threshold(kind, raw_val, min_val, max_val, val_range, &numeric_vals)
+                })
+                .unwrap_or(match index {
+                    0 => min_val,
+                    _ if index == last => max_val,
+                    _ => percentile(&numeric_vals, 50.0),
+                });
+            let default_color: Color = match index {
+                0 => Color::white(),
+                _ if index == last => Color::black(),
+                _ => Color::new(255, 255, 0),
+            };
+            (value, color.unwrap_or(default_color))
+        })
+        .collect();
+    // Keep stops ascending even when the cfvos are out of order.
+    for index in 1..stops.len() {
+        stops[index].0 = stops[index].0.max(stops[index - 1].0);
+    }
 
-    for range in ranges {
-        for row in range.start_row..=range.end_row {
-            for col in range.start_col..=range.end_col {
-                if let Some(cell) = sheet.get_cell((col, row))
-                    && let Some(val) = cell_numeric_value(cell)
-                {
-                    let ratio: f64 = if val_range.abs() < f64::EPSILON {
-                        0.5
-                    } else {
-                        (val - min_val) / val_range
-                    };
+    for (col, row) in range_cells(sheet, ranges) {
+        if let Some(cell) = sheet.get_cell((col, row))
+            && let Some(val) = cell_numeric_value(cell)
+        {
+            let entry = overrides.entry((col, row)).or_default();
+            entry.background = Some(color_scale_color(&stops, val));
+        }
+    }
+}
 
-                    let color: Color = if colors.len() == 3 {
-                        let color_mid: Color = colors[1].unwrap_or(Color::new(255, 255, 0));
-                        if ratio <= 0.5 {
-                            interpolate_color(color_min, color_mid, ratio * 2.0)
-                        } else {
-                            interpolate_color(color_mid, color_max, (ratio - 0.5) * 2.0)
-                        }
-                    } else {
-                        interpolate_color(color_min, color_max, ratio)
-                    };
+/// The cfvo type name of an umya cfvo, as written in the XML.
+fn cfvo_kind(cfvo: &umya_spreadsheet::ConditionalFormatValueObject) -> &'static str {
+    use umya_spreadsheet::ConditionalFormatValueObjectValues as CfvoType;
+    match cfvo.get_type() {
+        CfvoType::Min => "min",
+        CfvoType::Max => "max",
+        CfvoType::Number => "num",
+        CfvoType::Percent => "percent",
+        CfvoType::Percentile => "percentile",
+        CfvoType::Formula => "formula",
+    }
+}
 
-                    let entry = overrides.entry((col, row)).or_default();
-                    entry.background = Some(color);
-                }
-            }
+/// Blend the color for `value` between the ascending `(value, color)` stops.
+fn color_scale_color(stops: &[(f64, Color)], value: f64) -> Color {
+    let (first_value, first_color) = stops[0];
+    if value <= first_value {
+        return first_color;
+    }
+    for pair in stops.windows(2) {
+        let ((low, low_color), (high, high_color)) = (pair[0], pair[1]);
+        if value <= high {
+            let span: f64 = high - low;
+            let ratio: f64 = if span.abs() < f64::EPSILON {
+                1.0
+            } else {
+                (value - low) / span
+            };
+            return interpolate_color(low_color, high_color, ratio);
         }
     }
+    stops[stops.len() - 1].1
 }
 
 /// Apply a DataBar conditional formatting rule to cells in the given ranges.
@@ -584,41 +1004,70 @@ fn icon_set_glyphs(set_type: &str, band_count: usize) -> Vec<(&'static str, Opti
 pub(crate) fn build_cond_fmt_overrides(
     sheet: &umya_spreadsheet::Worksheet,
     raw_hints: Option<&RawCondFmtHints>,
+    formulas: &FormulaCache,
 ) -> HashMap<(u32, u32), CondFmtOverride> {
     let mut overrides: HashMap<CellPos, CondFmtOverride> = HashMap::new();
 
+    let mut rules: Vec<(Vec<CellRange>, &umya_spreadsheet::ConditionalFormattingRule)> = Vec::new();
     for cf in sheet.get_conditional_formatting_collection() {
         let sqref = cf.get_sequence_of_references().get_sqref();
         let ranges: Vec<CellRange> = parse_sqref(&sqref);
         if ranges.is_empty() {
             continue;
         }
-
         for rule in cf.get_conditional_collection() {
-            use umya_spreadsheet::ConditionalFormatValues;
-            let raw_hint = raw_hints.and_then(|hints| hints.get(rule.get_priority()));
+            rules.push((ranges.clone(), rule));
+        }
+    }
+    // Priority 1 takes precedence: apply the rules from the lowest
+    // precedence up so the more important rule's format lands last.
+    rules.sort_by_key(|(_, rule)| std::cmp::Reverse(*rule.get_priority()));
 
-            match rule.get_type() {
-                ConditionalFormatValues::CellIs => {
-                    apply_cell_is_rule(sheet, rule, &ranges, &mut overrides);
-                }
-                ConditionalFormatValues::ContainsText
-                | ConditionalFormatValues::NotContainsText
-                | ConditionalFormatValues::BeginsWith
-                | ConditionalFormatValues::EndsWith => {
-                    apply_text_rule(sheet, rule, &ranges, &mut overrides);
-                }
-                ConditionalFormatValues::ColorScale => {
-                    apply_color_scale_rule(sheet, rule, &ranges, &mut overrides);
-                }
-                ConditionalFormatValues::DataBar => {
-                    apply_data_bar_rule(sheet, rule, &ranges, &mut overrides, raw_hint);
-                }
-                ConditionalFormatValues::IconSet => {
-                    apply_icon_set_rule(sheet, rule, &ranges, &mut overrides, raw_hint);
-                }
-                _ => {}
+    for (ranges, rule) in rules {
+        use umya_spreadsheet::ConditionalFormatValues;
+        let raw_hint = raw_hints.and_then(|hints| hints.get(rule.get_priority()));
+
+        match rule.get_type() {
+            ConditionalFormatValues::CellIs => {
+                apply_cell_is_rule(sheet, rule, &ranges, &mut overrides, raw_hint);
+            }
+            ConditionalFormatValues::ContainsText
+            | ConditionalFormatValues::NotContainsText
+            | ConditionalFormatValues::BeginsWith
+            | ConditionalFormatValues::EndsWith => {
+                apply_text_rule(sheet, rule, &ranges, &mut overrides);
+            }
+            ConditionalFormatValues::Expression => {
+                apply_expression_rule(sheet, rule, &ranges, &mut overrides, raw_hint, formulas);
+            }
+            ConditionalFormatValues::Top10 => {
+                apply_top10_rule(sheet, rule, &ranges, &mut overrides, raw_hint);
+            }
+            ConditionalFormatValues::AboveAverage => {
+                apply_above_average_rule(sheet, rule, &ranges, &mut overrides, raw_hint);
+            }
+            ConditionalFormatValues::DuplicateValues => {
+                apply_duplicate_rule(sheet, rule, &ranges, &mut overrides, false);
+            }
+            ConditionalFormatValues::UniqueValues => {
+                apply_duplicate_rule(sheet, rule, &ranges, &mut overrides, true);
+            }
+            ConditionalFormatValues::ContainsBlanks
+            | ConditionalFormatValues::NotContainsBlanks
+            | ConditionalFormatValues::ContainsErrors
+            | ConditionalFormatValues::NotContainsErrors => {
+                apply_blank_or_error_rule(sheet, rule, &ranges, &mut overrides);
+            }
+            ConditionalFormatValues::ColorScale => {
+                apply_color_scale_rule(sheet, rule, &ranges, &mut overrides, raw_hint);
+            }
+            ConditionalFormatValues::DataBar => {
+                apply_data_bar_rule(sheet, rule, &ranges, &mut overrides, raw_hint);
+            }
+            ConditionalFormatValues::IconSet => {
+                apply_icon_set_rule(sheet, rule, &ranges, &mut overrides, raw_hint);
             }
+            _ => {}
         }
     }
 
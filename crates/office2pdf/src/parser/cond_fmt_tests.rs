@@ -48,3 +48,49 @@ fn test_interpolate_color_midpoint() {
     assert_eq!(mid.g, 128);
     assert_eq!(mid.b, 128);
 }
+
+#[test]
+fn test_shift_relative_references_keeps_anchored_parts() {
+    assert_eq!(
+        shift_relative_references("AND($B2>5,C$1<>\"A1\",SUM(D2:D3))", (1, 2), (2, 4)),
+        "AND($B4>5,D$1<>\"A1\",SUM(E4:E5))"
+    );
+    assert_eq!(
+        shift_relative_references("$A$1=1", (1, 1), (9, 9)),
+        "$A$1=1"
+    );
+    assert_eq!(shift_relative_references("A1>0", (2, 2), (1, 1)), "#REF!>0");
+}
+
+#[test]
+fn test_cell_is_between_accepts_bounds_in_either_order() {
+    use umya_spreadsheet::ConditionalFormattingOperatorValues::{Between, Equal, NotBetween};
+    let mut cell = umya_spreadsheet::Cell::default();
+    cell.set_value_number(15.0);
+    let bounds: Vec<CellIsOperand> = vec![CellIsOperand::Number(20.0), CellIsOperand::Number(10.0)];
+    assert!(evaluate_cell_is_rule(&cell, &Between, &bounds));
+    assert!(!evaluate_cell_is_rule(&cell, &NotBetween, &bounds));
+    cell.set_value_number(25.0);
+    assert!(!evaluate_cell_is_rule(&cell, &Between, &bounds));
+    assert!(evaluate_cell_is_rule(&cell, &NotBetween, &bounds));
+
+    cell.set_value("Done");
+    assert!(evaluate_cell_is_rule(
+        &cell,
+        &Equal,
+        &[CellIsOperand::Text("done".to_string())]
+    ));
+}
+
+#[test]
+fn test_color_scale_color_blends_between_stops() {
+    let red = Color::new(255, 0, 0);
+    let yellow = Color::new(255, 255, 0);
+    let green = Color::new(0, 255, 0);
+    let stops: Vec<(f64, Color)> = vec![(0.0, red), (10.0, yellow), (100.0, green)];
+    assert_eq!(color_scale_color(&stops, -5.0), red);
+    assert_eq!(color_scale_color(&stops, 5.0), Color::new(255, 128, 0));
+    assert_eq!(color_scale_color(&stops, 10.0), yellow);
+    assert_eq!(color_scale_color(&stops, 55.0), Color::new(128, 255, 0));
+    assert_eq!(color_scale_color(&stops, 500.0), green);
+}
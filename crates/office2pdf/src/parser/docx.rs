@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::io::Read;
 
 use crate::config::ConvertOptions;
-use crate::error::{ConvertError, ConvertWarning};
+use crate::error::{ConvertError, ConvertWarning, WarningLocation};
 
 /// Maximum nesting depth for tables-within-tables.  Deeper nesting is silently
 /// truncated to prevent stack overflow on pathological documents.
@@ -203,9 +203,17 @@ struct ZipPreParseAssets {
 fn build_zip_preparse_assets(data: &[u8]) -> ZipPreParseAssets {
     match crate::parser::open_zip(data) {
         Ok(mut archive) => {
-            let metadata = crate::parser::metadata::extract_metadata_from_zip(&mut archive);
+            let mut metadata = crate::parser::metadata::extract_metadata_from_zip(&mut archive);
             let doc_xml = read_zip_text(&mut archive, "word/document.xml");
             let styles_xml = read_zip_text(&mut archive, "word/styles.xml");
+            // The document defaults describe the text itself, so they win
+            // over the package's `dc:language`.
+            if let Some(language) = styles_xml
+                .as_deref()
+                .and_then(styles::scan_default_language)
+            {
+                metadata.language = Some(language);
+            }
             let default_paragraph_style_id = styles_xml
                 .as_deref()
                 .and_then(styles::scan_default_paragraph_style_id);
@@ -380,6 +388,7 @@ impl Parser for DocxParser {
                         reason: format!(
                             "upstream panic caught (docx-rs): element at index {idx}: {detail}"
                         ),
+                        location: Some(WarningLocation::Paragraph(idx as u32)),
                     });
                 }
             }
@@ -22,8 +22,8 @@ use docx_rs::FromXML;
 use quick_xml::events::{BytesStart, Event};
 
 use crate::ir::{
-    ArrowHead, BorderLineStyle, BorderSide, Color, FloatingShape, Insets, Shape, ShapeKind,
-    TextBoxVerticalAlign, WrapMode,
+    ArrowHead, BorderLineStyle, BorderSide, Color, FloatingShape, Insets, Shape, ShapeEffects,
+    ShapeKind, TextBoxVerticalAlign, WrapMode,
 };
 use crate::parser::units::emu_to_pt;
 use crate::parser::xml_util::parse_hex_color;
@@ -191,6 +191,7 @@ impl ShapeBuilder {
                 rotation_deg: None,
                 opacity: None,
                 shadow: None,
+                effects: ShapeEffects::default(),
             },
             width,
             height,
@@ -337,6 +337,7 @@ pub(super) fn build_flow_page_from_section(
                 format: "DOCX".to_string(),
                 from: format!("chart ({title})"),
                 to: "data table".to_string(),
+                location: None,
             });
         }
     }
@@ -349,6 +350,7 @@ pub(super) fn build_flow_page_from_section(
             format: "DOCX".to_string(),
             from: "continuous section break".to_string(),
             to: "page-level section split".to_string(),
+            location: None,
         });
     }
 
@@ -365,6 +367,7 @@ pub(super) fn build_flow_page_from_section(
             format: "DOCX".to_string(),
             from: "header/footer variants".to_string(),
             to: "single header/footer per section".to_string(),
+            location: None,
         });
     }
 
@@ -378,6 +381,7 @@ pub(super) fn build_flow_page_from_section(
             format: "DOCX".to_string(),
             from: "section page number restart".to_string(),
             to: "global page counter".to_string(),
+            location: None,
         });
     }
 
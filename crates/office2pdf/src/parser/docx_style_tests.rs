@@ -356,6 +356,29 @@ fn test_scan_default_paragraph_style_id_from_raw_styles_xml() {
     );
 }
 
+#[test]
+fn test_scan_default_language_reads_run_property_defaults() {
+    let xml = r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+      <w:docDefaults>
+        <w:rPrDefault><w:rPr><w:lang w:val="de-DE" w:eastAsia="ko-KR" w:bidi="ar-SA"/></w:rPr></w:rPrDefault>
+        <w:pPrDefault/>
+      </w:docDefaults>
+      <w:style w:type="paragraph" w:styleId="English"><w:rPr><w:lang w:val="en-US"/></w:rPr></w:style>
+    </w:styles>"#;
+
+    assert_eq!(styles::scan_default_language(xml).as_deref(), Some("de-DE"));
+}
+
+#[test]
+fn test_scan_default_language_ignores_style_languages() {
+    let xml = r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+      <w:docDefaults><w:rPrDefault><w:rPr><w:sz w:val="22"/></w:rPr></w:rPrDefault></w:docDefaults>
+      <w:style w:type="paragraph" w:styleId="English"><w:rPr><w:lang w:val="en-US"/></w:rPr></w:style>
+    </w:styles>"#;
+
+    assert_eq!(styles::scan_default_language(xml), None);
+}
+
 #[test]
 fn test_doc_default_theme_font_resolves_via_theme() {
     // docDefaults referencing asciiTheme="minorHAnsi" must resolve to the
@@ -64,6 +64,40 @@ pub(super) fn scan_default_paragraph_style_id(styles_xml: &str) -> Option<String
     }
 }
 
+/// `w:lang w:val` of the document defaults (`w:docDefaults/w:rPrDefault`),
+/// the language Word assumes for text without one of its own. docx-rs does
+/// not keep `w:lang`, so it is read from the raw styles part.
+pub(super) fn scan_default_language(styles_xml: &str) -> Option<String> {
+    use quick_xml::events::Event;
+
+    let mut reader = quick_xml::Reader::from_str(styles_xml);
+    reader.config_mut().trim_text(true);
+    let mut in_run_defaults: bool = false;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) if element.local_name().as_ref() == b"rPrDefault" => {
+                in_run_defaults = true;
+            }
+            Ok(Event::End(element)) if element.local_name().as_ref() == b"rPrDefault" => {
+                return None;
+            }
+            Ok(Event::Start(element) | Event::Empty(element))
+                if in_run_defaults && element.local_name().as_ref() == b"lang" =>
+            {
+                return element.attributes().flatten().find_map(|attribute| {
+                    (attribute.key.local_name().as_ref() == b"val")
+                        .then(|| attribute.decode_and_unescape_value(reader.decoder()).ok())
+                        .flatten()
+                        .map(|value| value.into_owned())
+                        .filter(|value| !value.is_empty())
+                });
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
+        }
+    }
+}
+
 use crate::defaults::HEADING_FONT_SIZES;
 
 /// Build a map from style ID → resolved formatting by extracting formatting
@@ -406,6 +406,9 @@ fn resolve_vmerge_and_build_rows(raw_rows: &[RawRow]) -> Vec<TableRow> {
                         spill_width: None,
                         vertical_align: raw_cell.vertical_align,
                         padding: raw_cell.padding,
+                        no_wrap: false,
+                        shrink_to_fit: false,
+                        text_rotation: None,
                     });
                 }
                 _ => {
@@ -421,6 +424,9 @@ fn resolve_vmerge_and_build_rows(raw_rows: &[RawRow]) -> Vec<TableRow> {
                         spill_width: None,
                         vertical_align: raw_cell.vertical_align,
                         padding: raw_cell.padding,
+                        no_wrap: false,
+                        shrink_to_fit: false,
+                        text_rotation: None,
                     });
                 }
             }
@@ -237,6 +237,9 @@ pub(super) fn extract_run_style_from_json(rp: &serde_json::Value) -> TextStyle {
             .get("characterSpacing")
             .and_then(serde_json::Value::as_i64)
             .map(|twips| twips_to_pt(twips as f64)),
+        outline: None,
+        shadow: None,
+        language: None,
     }
 }
 
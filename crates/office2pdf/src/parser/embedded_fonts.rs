@@ -14,6 +14,7 @@ use std::path::{Path, PathBuf};
 use quick_xml::Reader;
 use quick_xml::events::Event;
 
+use crate::config::Format;
 use crate::parser::xml_util::get_attr_str;
 
 #[cfg(test)]
@@ -71,6 +72,14 @@ struct DocxFontVariantRef {
     font_key: String,
 }
 
+/// An embedded font, deobfuscated, with the file name it is extracted as:
+/// `Typeface-style.ext`.
+#[derive(Debug)]
+pub(crate) struct EmbeddedFont {
+    pub(crate) file_name: String,
+    pub(crate) data: Vec<u8>,
+}
+
 /// Temporary directory containing extracted font files.
 /// Cleaned up automatically when dropped.
 #[cfg(not(target_arch = "wasm32"))]
@@ -101,35 +110,87 @@ impl Drop for EmbeddedFontDir {
 // Public API
 // =============================================================================
 
-/// Extract embedded fonts from an OOXML archive.
+/// Read the embedded fonts of an OOXML archive, deobfuscated.
 ///
-/// Returns `None` if:
-/// - The format doesn't support embedded fonts (XLSX)
-/// - No embedded fonts are declared in the document
-/// - The ZIP cannot be opened
-/// - Extraction fails silently (best-effort)
-#[cfg(not(target_arch = "wasm32"))]
-pub(crate) fn extract_embedded_fonts(
-    data: &[u8],
-    format: crate::config::Format,
-) -> Option<EmbeddedFontDir> {
-    use crate::config::Format;
-
-    let result = match format {
-        Format::Pptx => extract_pptx_fonts(data),
-        Format::Docx => extract_docx_fonts(data),
+/// Empty if the format doesn't support embedded fonts (XLSX), none are
+/// declared, or the ZIP cannot be opened; fonts that cannot be read are
+/// skipped (best-effort).
+pub(crate) fn read_embedded_fonts(data: &[u8], format: Format) -> Vec<EmbeddedFont> {
+    match format {
+        Format::Pptx => read_pptx_fonts(data),
+        Format::Docx => read_docx_fonts(data),
         Format::Xlsx => None,
+    }
+    .unwrap_or_default()
+}
+
+/// The typefaces an OOXML archive declares embedded, in declaration order,
+/// without reading or deobfuscating the fonts themselves.
+pub(crate) fn embedded_typefaces(data: &[u8], format: Format) -> Vec<String> {
+    use std::io::Read;
+
+    let (part, parse): (&str, fn(&str) -> Vec<String>) = match format {
+        Format::Pptx => ("ppt/presentation.xml", |xml| {
+            parse_pptx_embedded_font_list(xml)
+                .into_iter()
+                .map(|entry| entry.typeface)
+                .collect()
+        }),
+        Format::Docx => ("word/fontTable.xml", |xml| {
+            parse_docx_embedded_font_entries(xml)
+                .into_iter()
+                .map(|entry| entry.font_name)
+                .collect()
+        }),
+        Format::Xlsx => return Vec::new(),
+    };
+    let Ok(mut archive) = crate::parser::open_zip(data) else {
+        return Vec::new();
     };
+    let Ok(mut file) = archive.by_name(part) else {
+        return Vec::new();
+    };
+    let mut xml = String::new();
+    if file.read_to_string(&mut xml).is_err() {
+        return Vec::new();
+    }
+    parse(&xml)
+}
 
-    if let Some(ref dir) = result {
-        tracing::info!(
-            font_count = dir.font_count,
-            path = ?dir.path,
-            "extracted embedded fonts from archive"
-        );
+/// Extract embedded fonts from an OOXML archive into a temporary directory.
+///
+/// Returns `None` if there are none to extract (see [`read_embedded_fonts`])
+/// or none could be written.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn extract_embedded_fonts(data: &[u8], format: Format) -> Option<EmbeddedFontDir> {
+    let fonts: Vec<EmbeddedFont> = read_embedded_fonts(data, format);
+    if fonts.is_empty() {
+        return None;
     }
 
-    result
+    let prefix: &str = match format {
+        Format::Pptx => "office2pdf-pptx-fonts",
+        _ => "office2pdf-docx-fonts",
+    };
+    let temp_dir = create_temp_font_dir(prefix)?;
+    let font_count: usize = fonts
+        .iter()
+        .filter(|font| std::fs::write(temp_dir.join(&font.file_name), &font.data).is_ok())
+        .count();
+    if font_count == 0 {
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        return None;
+    }
+
+    tracing::info!(
+        font_count,
+        path = ?temp_dir,
+        "extracted embedded fonts from archive"
+    );
+    Some(EmbeddedFontDir {
+        path: temp_dir,
+        font_count,
+    })
 }
 
 // =============================================================================
@@ -271,8 +332,7 @@ fn extract_guid_from_font_path(path: &str) -> Option<String> {
     }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-fn extract_pptx_fonts(data: &[u8]) -> Option<EmbeddedFontDir> {
+fn read_pptx_fonts(data: &[u8]) -> Option<Vec<EmbeddedFont>> {
     use std::io::Read;
 
     let mut archive = crate::parser::open_zip(data).ok()?;
@@ -299,9 +359,7 @@ fn extract_pptx_fonts(data: &[u8]) -> Option<EmbeddedFontDir> {
     };
     let rels = crate::parser::xml_util::parse_rels_id_target(&rels_xml);
 
-    // Create temp dir
-    let temp_dir = create_temp_font_dir("office2pdf-pptx-fonts")?;
-    let mut font_count: usize = 0;
+    let mut fonts: Vec<EmbeddedFont> = Vec::new();
 
     for entry in &font_entries {
         for variant in &entry.variants {
@@ -339,28 +397,17 @@ fn extract_pptx_fonts(data: &[u8]) -> Option<EmbeddedFontDir> {
             // Deobfuscate
             deobfuscate_font_data(&mut font_data, &key);
 
-            // Detect format and write
             let ext = detect_font_format(&font_data)
                 .map(|f| f.extension())
                 .unwrap_or("ttf");
-
-            let filename = format!("{}-{}.{}", entry.typeface, variant.style, ext);
-            let out_path = temp_dir.join(&filename);
-            if std::fs::write(&out_path, &font_data).is_ok() {
-                font_count += 1;
-            }
+            fonts.push(EmbeddedFont {
+                file_name: format!("{}-{}.{}", entry.typeface, variant.style, ext),
+                data: font_data,
+            });
         }
     }
 
-    if font_count == 0 {
-        let _ = std::fs::remove_dir_all(&temp_dir);
-        return None;
-    }
-
-    Some(EmbeddedFontDir {
-        path: temp_dir,
-        font_count,
-    })
+    Some(fonts)
 }
 
 // =============================================================================
@@ -428,8 +475,7 @@ fn parse_docx_embedded_font_entries(xml: &str) -> Vec<DocxEmbeddedFontEntry> {
     entries
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-fn extract_docx_fonts(data: &[u8]) -> Option<EmbeddedFontDir> {
+fn read_docx_fonts(data: &[u8]) -> Option<Vec<EmbeddedFont>> {
     use std::io::Read;
 
     let mut archive = crate::parser::open_zip(data).ok()?;
@@ -456,8 +502,7 @@ fn extract_docx_fonts(data: &[u8]) -> Option<EmbeddedFontDir> {
     };
     let rels = crate::parser::xml_util::parse_rels_id_target(&rels_xml);
 
-    let temp_dir = create_temp_font_dir("office2pdf-docx-fonts")?;
-    let mut font_count: usize = 0;
+    let mut fonts: Vec<EmbeddedFont> = Vec::new();
 
     for entry in &font_entries {
         for variant in &entry.variants {
@@ -492,28 +537,17 @@ fn extract_docx_fonts(data: &[u8]) -> Option<EmbeddedFontDir> {
             // Deobfuscate
             deobfuscate_font_data(&mut font_data, &key);
 
-            // Detect format and write
             let ext = detect_font_format(&font_data)
                 .map(|f| f.extension())
                 .unwrap_or("ttf");
-
-            let filename = format!("{}-{}.{}", entry.font_name, variant.style, ext);
-            let out_path = temp_dir.join(&filename);
-            if std::fs::write(&out_path, &font_data).is_ok() {
-                font_count += 1;
-            }
+            fonts.push(EmbeddedFont {
+                file_name: format!("{}-{}.{}", entry.font_name, variant.style, ext),
+                data: font_data,
+            });
         }
     }
 
-    if font_count == 0 {
-        let _ = std::fs::remove_dir_all(&temp_dir);
-        return None;
-    }
-
-    Some(EmbeddedFontDir {
-        path: temp_dir,
-        font_count,
-    })
+    Some(fonts)
 }
 
 // =============================================================================
@@ -449,6 +449,30 @@ mod integration {
         assert_eq!(extracted, original_ttf);
     }
 
+    #[test]
+    fn read_embedded_fonts_names_fonts_by_typeface() {
+        let guid = "{7B19B49C-2336-4F82-AAD2-5D2BAE389560}";
+        let original_ttf = make_fake_ttf(64);
+        let zip_data = build_pptx_with_embedded_font(&original_ttf, guid);
+
+        let fonts: Vec<EmbeddedFont> = read_embedded_fonts(&zip_data, crate::config::Format::Pptx);
+        assert_eq!(fonts.len(), 1);
+        assert_eq!(fonts[0].file_name, "TestFont-regular.ttf");
+        assert_eq!(fonts[0].data, original_ttf);
+    }
+
+    #[test]
+    fn embedded_typefaces_lists_declared_fonts() {
+        let guid = "{7B19B49C-2336-4F82-AAD2-5D2BAE389560}";
+        let zip_data = build_pptx_with_embedded_font(&make_fake_ttf(64), guid);
+
+        assert_eq!(
+            embedded_typefaces(&zip_data, crate::config::Format::Pptx),
+            vec!["TestFont".to_string()]
+        );
+        assert!(embedded_typefaces(&zip_data, crate::config::Format::Docx).is_empty());
+    }
+
     #[test]
     fn extract_embedded_fonts_no_fonts_returns_none() {
         // Build a minimal PPTX with no embedded fonts
@@ -0,0 +1,462 @@
+//! Decryption of password-protected OOXML files (MS-OFFCRYPTO).
+//!
+//! Word, Excel and PowerPoint save an encrypted document as an OLE2
+//! compound file with two streams: `EncryptionInfo`, which says how the key
+//! is derived from the password, and `EncryptedPackage`, the AES-encrypted
+//! ZIP package the parsers read. Agile encryption (Office 2010 and later)
+//! and Standard encryption (Office 2007) are supported; the RC4 schemes of
+//! the legacy binary formats are not. The agile `dataIntegrity` HMAC is not
+//! checked: a tampered package fails as a corrupt ZIP instead.
+
+use std::io::{Cursor, Read};
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockDecrypt, KeyInit};
+use base64::Engine as _;
+use quick_xml::events::Event;
+use sha2::Digest;
+
+use crate::error::ConvertError;
+use crate::parser::parse_err;
+use crate::parser::xml_util::get_attr_str;
+
+/// Highest agile spin count accepted. Office writes 100,000; the file sets
+/// the count, so a crafted one could otherwise stall the conversion.
+const MAX_SPIN_COUNT: u32 = 10_000_000;
+/// Standard encryption always hashes the password this many times.
+const STANDARD_SPIN_COUNT: u32 = 50_000;
+/// Agile encryption encrypts the package in segments of this many bytes,
+/// each with its own IV.
+const AGILE_SEGMENT_LEN: usize = 4096;
+const AES_BLOCK_LEN: usize = 16;
+
+/// Block keys deriving the agile password verifier and key-encryption keys.
+const BLOCK_KEY_VERIFIER_INPUT: [u8; 8] = [0xfe, 0xa7, 0xd2, 0x76, 0x3b, 0x4b, 0x9e, 0x79];
+const BLOCK_KEY_VERIFIER_VALUE: [u8; 8] = [0xd7, 0xaa, 0x0f, 0x6d, 0x30, 0x61, 0x34, 0x4e];
+const BLOCK_KEY_ENCRYPTED_KEY: [u8; 8] = [0x14, 0x6e, 0x0b, 0xe7, 0xab, 0xac, 0xd0, 0xd6];
+
+/// Decrypt the OOXML package inside an encrypted Office file.
+///
+/// Returns [`ConvertError::IncorrectPassword`] when `password` does not
+/// open the file and [`ConvertError::UnsupportedEncryption`] for OLE2
+/// files that are not encrypted OOXML packages.
+pub(crate) fn decrypt(data: &[u8], password: &str) -> Result<Vec<u8>, ConvertError> {
+    let mut file = cfb::CompoundFile::open(Cursor::new(data))
+        .map_err(|e| parse_err(format!("invalid OLE2 container: {e}")))?;
+    // Without these streams the file is a legacy binary document.
+    let (Some(info), Some(package)) = (
+        read_stream(&mut file, "/EncryptionInfo"),
+        read_stream(&mut file, "/EncryptedPackage"),
+    ) else {
+        return Err(ConvertError::UnsupportedEncryption);
+    };
+    let mut info = ByteReader::new(&info);
+    let major: u16 = info.u16()?;
+    let minor: u16 = info.u16()?;
+    let _flags: u32 = info.u32()?;
+
+    let mut package = ByteReader::new(&package);
+    let package_len: u64 = package.u64()?;
+    let encrypted: &[u8] = package.rest();
+    let mut decrypted: Vec<u8> = match (major, minor) {
+        (4, 4) => decrypt_agile(info.rest(), encrypted, password)?,
+        (2..=4, 2) => decrypt_standard(info.rest(), encrypted, password)?,
+        _ => return Err(ConvertError::UnsupportedEncryption),
+    };
+    // The last block is padded; the declared size is where the ZIP ends.
+    decrypted.truncate(usize::try_from(package_len).unwrap_or(usize::MAX));
+    Ok(decrypted)
+}
+
+fn read_stream(file: &mut cfb::CompoundFile<Cursor<&[u8]>>, path: &str) -> Option<Vec<u8>> {
+    let mut stream = file.open_stream(path).ok()?;
+    let mut data: Vec<u8> = Vec::new();
+    stream.read_to_end(&mut data).ok()?;
+    Some(data)
+}
+
+/// Standard encryption: a SHA-1 key derivation and the package encrypted
+/// with AES in ECB mode.
+fn decrypt_standard(
+    info: &[u8],
+    encrypted: &[u8],
+    password: &str,
+) -> Result<Vec<u8>, ConvertError> {
+    let mut info = ByteReader::new(info);
+    let header_len: usize = info.u32()? as usize;
+    let mut header = ByteReader::new(info.take(header_len)?);
+    let _flags: u32 = header.u32()?;
+    let _size_extra: u32 = header.u32()?;
+    let algorithm: u32 = header.u32()?;
+    let _hash_algorithm: u32 = header.u32()?;
+    let key_bits: u32 = header.u32()?;
+    // CALG_AES_128, CALG_AES_192, CALG_AES_256; RC4 is not supported.
+    if !matches!(algorithm, 0x660E..=0x6610) {
+        return Err(ConvertError::UnsupportedEncryption);
+    }
+
+    let salt_len: usize = info.u32()? as usize;
+    let salt: &[u8] = info.take(salt_len)?;
+    let encrypted_verifier: &[u8] = info.take(AES_BLOCK_LEN)?;
+    let verifier_hash_len: usize = info.u32()? as usize;
+    // The 20-byte SHA-1 hash, padded to whole AES blocks.
+    let encrypted_verifier_hash: &[u8] = info.take(2 * AES_BLOCK_LEN)?;
+
+    let key: Vec<u8> = standard_key(salt, password, key_bits as usize / 8);
+    let cipher = Aes::new(&key)?;
+    let verifier: Vec<u8> = cipher.decrypt_ecb(encrypted_verifier)?;
+    let verifier_hash: Vec<u8> = cipher.decrypt_ecb(encrypted_verifier_hash)?;
+    let expected: Vec<u8> = HashAlgorithm::Sha1.digest(&[&verifier]);
+    let compared: usize = verifier_hash_len.min(expected.len());
+    if verifier_hash[..compared] != expected[..compared] {
+        return Err(ConvertError::IncorrectPassword);
+    }
+    cipher.decrypt_ecb(whole_blocks(encrypted))
+}
+
+/// The Standard encryption key (MS-OFFCRYPTO 2.3.4.7).
+fn standard_key(salt: &[u8], password: &str, key_len: usize) -> Vec<u8> {
+    let sha1 = HashAlgorithm::Sha1;
+    let hash: Vec<u8> = spun_password_hash(sha1, salt, password, STANDARD_SPIN_COUNT);
+    let final_hash: Vec<u8> = sha1.digest(&[&hash, &0u32.to_le_bytes()]);
+    let derive = |fill: u8| -> Vec<u8> {
+        let mut buffer: [u8; 64] = [fill; 64];
+        for (byte, hash_byte) in buffer.iter_mut().zip(&final_hash) {
+            *byte ^= hash_byte;
+        }
+        sha1.digest(&[&buffer])
+    };
+    let mut key: Vec<u8> = derive(0x36);
+    key.extend(derive(0x5C));
+    key.truncate(key_len);
+    key
+}
+
+/// Agile encryption parameters of the package or of the password key
+/// encryptor, read from the `EncryptionInfo` XML.
+#[derive(Debug, Default)]
+struct AgileParams {
+    salt: Vec<u8>,
+    hash: Option<HashAlgorithm>,
+    key_bits: usize,
+    block_size: usize,
+    hash_size: usize,
+    spin_count: u32,
+    encrypted_verifier_input: Vec<u8>,
+    encrypted_verifier_value: Vec<u8>,
+    encrypted_key: Vec<u8>,
+}
+
+/// Agile encryption: the package key is encrypted with a key derived from
+/// the password, and the package with AES-CBC in 4096-byte segments.
+fn decrypt_agile(info: &[u8], encrypted: &[u8], password: &str) -> Result<Vec<u8>, ConvertError> {
+    let xml: &str = std::str::from_utf8(info)
+        .map_err(|_| parse_err("encryption info is not UTF-8 XML".to_string()))?;
+    let (key_data, password_encryptor) = parse_agile_info(xml)?;
+    let (Some(package_hash), Some(password_hash)) = (key_data.hash, password_encryptor.hash) else {
+        return Err(ConvertError::UnsupportedEncryption);
+    };
+    if password_encryptor.spin_count > MAX_SPIN_COUNT {
+        return Err(parse_err(format!(
+            "encryption spin count {} exceeds {MAX_SPIN_COUNT}",
+            password_encryptor.spin_count
+        )));
+    }
+
+    let hash: Vec<u8> = spun_password_hash(
+        password_hash,
+        &password_encryptor.salt,
+        password,
+        password_encryptor.spin_count,
+    );
+    let key_len: usize = password_encryptor.key_bits / 8;
+    let iv: Vec<u8> = fit(
+        password_encryptor.salt.clone(),
+        password_encryptor.block_size,
+    );
+    let decrypt_with = |block_key: &[u8], data: &[u8]| -> Result<Vec<u8>, ConvertError> {
+        let key: Vec<u8> = fit(password_hash.digest(&[&hash, block_key]), key_len);
+        Aes::new(&key)?.decrypt_cbc(&iv, data)
+    };
+
+    let verifier_input: Vec<u8> = decrypt_with(
+        &BLOCK_KEY_VERIFIER_INPUT,
+        &password_encryptor.encrypted_verifier_input,
+    )?;
+    let verifier_value: Vec<u8> = decrypt_with(
+        &BLOCK_KEY_VERIFIER_VALUE,
+        &password_encryptor.encrypted_verifier_value,
+    )?;
+    let salt_len: usize = password_encryptor.salt.len().min(verifier_input.len());
+    let expected: Vec<u8> = password_hash.digest(&[&verifier_input[..salt_len]]);
+    let hash_size: usize = password_encryptor
+        .hash_size
+        .min(expected.len())
+        .min(verifier_value.len());
+    if verifier_value[..hash_size] != expected[..hash_size] {
+        return Err(ConvertError::IncorrectPassword);
+    }
+
+    let mut package_key: Vec<u8> =
+        decrypt_with(&BLOCK_KEY_ENCRYPTED_KEY, &password_encryptor.encrypted_key)?;
+    package_key.truncate(key_data.key_bits / 8);
+    let cipher = Aes::new(&package_key)?;
+    let mut decrypted: Vec<u8> = Vec::with_capacity(encrypted.len());
+    for (index, segment) in whole_blocks(encrypted)
+        .chunks(AGILE_SEGMENT_LEN)
+        .enumerate()
+    {
+        let segment_iv: Vec<u8> = fit(
+            package_hash.digest(&[&key_data.salt, &(index as u32).to_le_bytes()]),
+            key_data.block_size,
+        );
+        decrypted.extend(cipher.decrypt_cbc(&segment_iv, segment)?);
+    }
+    Ok(decrypted)
+}
+
+/// Read the `<keyData>` element and the password `<encryptedKey>` from the
+/// agile `EncryptionInfo` XML.
+fn parse_agile_info(xml: &str) -> Result<(AgileParams, AgileParams), ConvertError> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut key_data: Option<AgileParams> = None;
+    let mut password_encryptor: Option<AgileParams> = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e) | Event::Empty(ref e)) => {
+                // Certificate key encryptors are `encryptedKey` elements too,
+                // but only the password one has a spin count.
+                let params = || -> Result<AgileParams, ConvertError> {
+                    let cipher: String = get_attr_str(e, b"cipherAlgorithm").unwrap_or_default();
+                    let chaining: String = get_attr_str(e, b"cipherChaining").unwrap_or_default();
+                    if cipher != "AES" || chaining != "ChainingModeCBC" {
+                        return Err(ConvertError::UnsupportedEncryption);
+                    }
+                    let number = |name: &[u8]| -> usize {
+                        get_attr_str(e, name)
+                            .and_then(|value| value.parse().ok())
+                            .unwrap_or_default()
+                    };
+                    let bytes = |name: &[u8]| -> Result<Vec<u8>, ConvertError> {
+                        let value: String = get_attr_str(e, name).unwrap_or_default();
+                        base64::engine::general_purpose::STANDARD
+                            .decode(value.trim())
+                            .map_err(|e| parse_err(format!("invalid encryption info: {e}")))
+                    };
+                    Ok(AgileParams {
+                        salt: bytes(b"saltValue")?,
+                        hash: get_attr_str(e, b"hashAlgorithm")
+                            .as_deref()
+                            .and_then(HashAlgorithm::from_name),
+                        key_bits: number(b"keyBits"),
+                        block_size: number(b"blockSize"),
+                        hash_size: number(b"hashSize"),
+                        spin_count: number(b"spinCount") as u32,
+                        encrypted_verifier_input: bytes(b"encryptedVerifierHashInput")?,
+                        encrypted_verifier_value: bytes(b"encryptedVerifierHashValue")?,
+                        encrypted_key: bytes(b"encryptedKeyValue")?,
+                    })
+                };
+                match e.local_name().as_ref() {
+                    b"keyData" if key_data.is_none() => key_data = Some(params()?),
+                    b"encryptedKey"
+                        if password_encryptor.is_none()
+                            && get_attr_str(e, b"spinCount").is_some() =>
+                    {
+                        password_encryptor = Some(params()?);
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(parse_err(format!("invalid encryption info: {e}"))),
+            _ => {}
+        }
+    }
+    match (key_data, password_encryptor) {
+        (Some(key_data), Some(password_encryptor)) => Ok((key_data, password_encryptor)),
+        // Only certificate encryptors: the file cannot be opened by password.
+        _ => Err(ConvertError::UnsupportedEncryption),
+    }
+}
+
+/// `H(salt + password)`, rehashed `spin_count` times with the iteration
+/// number prepended (MS-OFFCRYPTO 2.3.4.7 and 2.3.4.11).
+fn spun_password_hash(
+    algorithm: HashAlgorithm,
+    salt: &[u8],
+    password: &str,
+    spin_count: u32,
+) -> Vec<u8> {
+    let password: Vec<u8> = password.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let mut hash: Vec<u8> = algorithm.digest(&[salt, &password]);
+    for iteration in 0..spin_count {
+        hash = algorithm.digest(&[&iteration.to_le_bytes(), &hash]);
+    }
+    hash
+}
+
+/// Truncate `bytes` to `len`, or pad it with 0x36 as MS-OFFCRYPTO does for
+/// keys and IVs longer than the hash.
+fn fit(mut bytes: Vec<u8>, len: usize) -> Vec<u8> {
+    bytes.resize(len, 0x36);
+    bytes
+}
+
+/// Drop a trailing partial block, which only a truncated file has.
+fn whole_blocks(data: &[u8]) -> &[u8] {
+    &data[..data.len() - data.len() % AES_BLOCK_LEN]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "SHA1" => Some(Self::Sha1),
+            "SHA256" => Some(Self::Sha256),
+            "SHA384" => Some(Self::Sha384),
+            "SHA512" => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+
+    /// The hash of `parts` concatenated.
+    fn digest(self, parts: &[&[u8]]) -> Vec<u8> {
+        fn run<D: Digest>(parts: &[&[u8]]) -> Vec<u8> {
+            let mut hasher = D::new();
+            for part in parts {
+                hasher.update(part);
+            }
+            hasher.finalize().to_vec()
+        }
+        match self {
+            Self::Sha1 => run::<sha1::Sha1>(parts),
+            Self::Sha256 => run::<sha2::Sha256>(parts),
+            Self::Sha384 => run::<sha2::Sha384>(parts),
+            Self::Sha512 => run::<sha2::Sha512>(parts),
+        }
+    }
+}
+
+/// AES with the key size the file asks for.
+enum Aes {
+    Aes128(aes::Aes128),
+    Aes192(aes::Aes192),
+    Aes256(aes::Aes256),
+}
+
+impl Aes {
+    fn new(key: &[u8]) -> Result<Self, ConvertError> {
+        match key.len() {
+            16 => Ok(Self::Aes128(aes::Aes128::new(GenericArray::from_slice(
+                key,
+            )))),
+            24 => Ok(Self::Aes192(aes::Aes192::new(GenericArray::from_slice(
+                key,
+            )))),
+            32 => Ok(Self::Aes256(aes::Aes256::new(GenericArray::from_slice(
+                key,
+            )))),
+            len => Err(parse_err(format!(
+                "unsupported AES key length: {len} bytes"
+            ))),
+        }
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        let block = GenericArray::from_mut_slice(block);
+        match self {
+            Self::Aes128(cipher) => cipher.decrypt_block(block),
+            Self::Aes192(cipher) => cipher.decrypt_block(block),
+            Self::Aes256(cipher) => cipher.decrypt_block(block),
+        }
+    }
+
+    fn decrypt_ecb(&self, data: &[u8]) -> Result<Vec<u8>, ConvertError> {
+        let mut plain: Vec<u8> = checked_blocks(data)?;
+        for block in plain.chunks_exact_mut(AES_BLOCK_LEN) {
+            self.decrypt_block(block);
+        }
+        Ok(plain)
+    }
+
+    fn decrypt_cbc(&self, iv: &[u8], data: &[u8]) -> Result<Vec<u8>, ConvertError> {
+        let mut previous: [u8; AES_BLOCK_LEN] = fit(iv.to_vec(), AES_BLOCK_LEN)
+            .try_into()
+            .expect("fit returns a whole block");
+        let mut plain: Vec<u8> = checked_blocks(data)?;
+        for block in plain.chunks_exact_mut(AES_BLOCK_LEN) {
+            let ciphertext: [u8; AES_BLOCK_LEN] =
+                block.try_into().expect("chunks are whole blocks");
+            self.decrypt_block(block);
+            for (byte, mask) in block.iter_mut().zip(previous) {
+                *byte ^= mask;
+            }
+            previous = ciphertext;
+        }
+        Ok(plain)
+    }
+}
+
+fn checked_blocks(data: &[u8]) -> Result<Vec<u8>, ConvertError> {
+    if data.len() % AES_BLOCK_LEN != 0 {
+        return Err(parse_err(format!(
+            "encrypted data of {} bytes is not whole AES blocks",
+            data.len()
+        )));
+    }
+    Ok(data.to_vec())
+}
+
+/// Little-endian reads from an `EncryptionInfo` or package stream.
+struct ByteReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ConvertError> {
+        if len > self.data.len() {
+            return Err(parse_err("truncated encryption info".to_string()));
+        }
+        let (taken, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(taken)
+    }
+
+    fn u16(&mut self) -> Result<u16, ConvertError> {
+        Ok(u16::from_le_bytes(
+            self.take(2)?.try_into().expect("took 2 bytes"),
+        ))
+    }
+
+    fn u32(&mut self) -> Result<u32, ConvertError> {
+        Ok(u32::from_le_bytes(
+            self.take(4)?.try_into().expect("took 4 bytes"),
+        ))
+    }
+
+    fn u64(&mut self) -> Result<u64, ConvertError> {
+        Ok(u64::from_le_bytes(
+            self.take(8)?.try_into().expect("took 8 bytes"),
+        ))
+    }
+
+    fn rest(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+#[cfg(test)]
+#[path = "encryption_tests.rs"]
+mod tests;
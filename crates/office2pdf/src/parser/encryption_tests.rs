@@ -0,0 +1,209 @@
+use std::io::{Cursor, Write};
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use base64::Engine as _;
+
+use super::*;
+use crate::config::{ConvertOptions, Format};
+use crate::test_support::build_docx_with_title;
+
+const PASSWORD: &str = "Pässwörd";
+
+/// AES-encrypt whole blocks, chained from `iv` when given and in ECB mode
+/// otherwise: the encrypting side of [`Aes::decrypt_cbc`] and
+/// [`Aes::decrypt_ecb`].
+fn encrypt(key: &[u8], iv: Option<&[u8]>, data: &[u8]) -> Vec<u8> {
+    fn run<C: BlockEncrypt + KeyInit>(key: &[u8], iv: Option<&[u8]>, data: &[u8]) -> Vec<u8> {
+        let cipher = C::new(GenericArray::from_slice(key));
+        let mut previous: Option<Vec<u8>> = iv.map(|iv| fit(iv.to_vec(), AES_BLOCK_LEN));
+        let mut out: Vec<u8> = data.to_vec();
+        out.resize(data.len().next_multiple_of(AES_BLOCK_LEN), 0);
+        for block in out.chunks_exact_mut(AES_BLOCK_LEN) {
+            if let Some(previous) = &previous {
+                for (byte, mask) in block.iter_mut().zip(previous) {
+                    *byte ^= mask;
+                }
+            }
+            cipher.encrypt_block(GenericArray::from_mut_slice(block));
+            if previous.is_some() {
+                previous = Some(block.to_vec());
+            }
+        }
+        out
+    }
+    match key.len() {
+        16 => run::<aes::Aes128>(key, iv, data),
+        32 => run::<aes::Aes256>(key, iv, data),
+        len => panic!("unexpected key length {len}"),
+    }
+}
+
+/// An OLE2 container holding the two streams of an encrypted package.
+fn compound_file(info: &[u8], package_len: usize, encrypted: &[u8]) -> Vec<u8> {
+    let mut file = cfb::CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+    file.create_stream("/EncryptionInfo")
+        .unwrap()
+        .write_all(info)
+        .unwrap();
+    let mut package: Vec<u8> = (package_len as u64).to_le_bytes().to_vec();
+    package.extend_from_slice(encrypted);
+    file.create_stream("/EncryptedPackage")
+        .unwrap()
+        .write_all(&package)
+        .unwrap();
+    file.flush().unwrap();
+    file.into_inner().into_inner()
+}
+
+fn encrypt_standard(package: &[u8], password: &str) -> Vec<u8> {
+    let salt: [u8; 16] = *b"standard-salt-16";
+    let verifier: [u8; 16] = *b"verifier-16bytes";
+    let key: Vec<u8> = standard_key(&salt, password, 16);
+    let mut verifier_hash: Vec<u8> = HashAlgorithm::Sha1.digest(&[&verifier]);
+    verifier_hash.resize(2 * AES_BLOCK_LEN, 0);
+
+    let mut header: Vec<u8> = Vec::new();
+    // Flags, sizeExtra, CALG_AES_128, CALG_SHA1, key bits, provider type,
+    // two reserved fields and an empty CSP name.
+    for field in [0x24u32, 0, 0x660E, 0x8004, 128, 0x18, 0, 0] {
+        header.extend(field.to_le_bytes());
+    }
+    header.extend([0, 0]);
+    let mut info: Vec<u8> = Vec::new();
+    info.extend(3u16.to_le_bytes());
+    info.extend(2u16.to_le_bytes());
+    info.extend(0x24u32.to_le_bytes());
+    info.extend((header.len() as u32).to_le_bytes());
+    info.extend(header);
+    info.extend((salt.len() as u32).to_le_bytes());
+    info.extend(salt);
+    info.extend(encrypt(&key, None, &verifier));
+    info.extend(20u32.to_le_bytes());
+    info.extend(encrypt(&key, None, &verifier_hash));
+    compound_file(&info, package.len(), &encrypt(&key, None, package))
+}
+
+fn encrypt_agile(package: &[u8], password: &str) -> Vec<u8> {
+    let sha512 = HashAlgorithm::Sha512;
+    let password_salt: [u8; 16] = *b"password-salt-16";
+    let key_data_salt: [u8; 16] = *b"keydata-salt-16b";
+    let package_key: [u8; 32] = *b"package-key-of-thirty-two-bytes!";
+    let verifier_input: [u8; 16] = *b"verifier-16bytes";
+    // Office uses 100,000; fewer keeps the test fast.
+    let spin_count: u32 = 1000;
+
+    let hash: Vec<u8> = spun_password_hash(sha512, &password_salt, password, spin_count);
+    let encrypt_with = |block_key: &[u8], data: &[u8]| -> String {
+        let key: Vec<u8> = fit(sha512.digest(&[&hash, block_key]), 32);
+        base64::engine::general_purpose::STANDARD.encode(encrypt(&key, Some(&password_salt), data))
+    };
+    let base64 = |data: &[u8]| base64::engine::general_purpose::STANDARD.encode(data);
+    let xml: String = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<encryption xmlns="http://schemas.microsoft.com/office/2006/encryption" xmlns:p="http://schemas.microsoft.com/office/2006/keyEncryptor/password">
+<keyData saltSize="16" blockSize="16" keyBits="256" hashSize="64" cipherAlgorithm="AES" cipherChaining="ChainingModeCBC" hashAlgorithm="SHA512" saltValue="{}"/>
+<keyEncryptors><keyEncryptor uri="http://schemas.microsoft.com/office/2006/keyEncryptor/password">
+<p:encryptedKey spinCount="{spin_count}" saltSize="16" blockSize="16" keyBits="256" hashSize="64" cipherAlgorithm="AES" cipherChaining="ChainingModeCBC" hashAlgorithm="SHA512" saltValue="{}" encryptedVerifierHashInput="{}" encryptedVerifierHashValue="{}" encryptedKeyValue="{}"/>
+</keyEncryptor></keyEncryptors></encryption>"#,
+        base64(&key_data_salt),
+        base64(&password_salt),
+        encrypt_with(&BLOCK_KEY_VERIFIER_INPUT, &verifier_input),
+        encrypt_with(
+            &BLOCK_KEY_VERIFIER_VALUE,
+            &sha512.digest(&[&verifier_input])
+        ),
+        encrypt_with(&BLOCK_KEY_ENCRYPTED_KEY, &package_key),
+    );
+
+    let mut encrypted: Vec<u8> = Vec::new();
+    for (index, segment) in package.chunks(AGILE_SEGMENT_LEN).enumerate() {
+        let iv: Vec<u8> = sha512.digest(&[&key_data_salt, &(index as u32).to_le_bytes()]);
+        encrypted.extend(encrypt(&package_key, Some(&iv), segment));
+    }
+    let mut info: Vec<u8> = Vec::new();
+    info.extend(4u16.to_le_bytes());
+    info.extend(4u16.to_le_bytes());
+    info.extend(0x40u32.to_le_bytes());
+    info.extend(xml.as_bytes());
+    compound_file(&info, package.len(), &encrypted)
+}
+
+#[test]
+fn test_decrypt_agile_round_trips() {
+    // Longer than one segment, so the per-segment IVs are exercised.
+    let package: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+    let encrypted: Vec<u8> = encrypt_agile(&package, PASSWORD);
+    assert_eq!(decrypt(&encrypted, PASSWORD).unwrap(), package);
+}
+
+#[test]
+fn test_decrypt_standard_round_trips() {
+    let package: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+    let encrypted: Vec<u8> = encrypt_standard(&package, PASSWORD);
+    assert_eq!(decrypt(&encrypted, PASSWORD).unwrap(), package);
+}
+
+#[test]
+fn test_decrypt_rejects_wrong_password() {
+    let package: &[u8] = b"PK\x03\x04 not really a zip";
+    for encrypted in [
+        encrypt_agile(package, PASSWORD),
+        encrypt_standard(package, PASSWORD),
+    ] {
+        assert!(matches!(
+            decrypt(&encrypted, "password"),
+            Err(ConvertError::IncorrectPassword)
+        ));
+    }
+}
+
+#[test]
+fn test_decrypt_rejects_legacy_binary_documents() {
+    let mut file = cfb::CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+    file.create_stream("/WordDocument")
+        .unwrap()
+        .write_all(b"binary")
+        .unwrap();
+    file.flush().unwrap();
+    let data: Vec<u8> = file.into_inner().into_inner();
+    assert!(matches!(
+        decrypt(&data, PASSWORD),
+        Err(ConvertError::UnsupportedEncryption)
+    ));
+}
+
+#[test]
+fn test_decrypt_rejects_excessive_spin_count() {
+    let encrypted: Vec<u8> = encrypt_agile(b"package", PASSWORD);
+    let info = read_stream(
+        &mut cfb::CompoundFile::open(Cursor::new(encrypted.as_slice())).unwrap(),
+        "/EncryptionInfo",
+    )
+    .unwrap();
+    let info: Vec<u8> = String::from_utf8(info)
+        .unwrap()
+        .replace("spinCount=\"1000\"", "spinCount=\"4000000000\"")
+        .into_bytes();
+    let crafted: Vec<u8> = compound_file(&info, 7, &[0; 16]);
+    assert!(matches!(
+        decrypt(&crafted, PASSWORD),
+        Err(ConvertError::Parse(_))
+    ));
+}
+
+#[test]
+fn test_parse_bytes_opens_encrypted_docx_with_password() {
+    let encrypted: Vec<u8> = encrypt_agile(&build_docx_with_title("Board minutes"), PASSWORD);
+    let options = ConvertOptions {
+        input_password: Some(PASSWORD.to_string()),
+        ..ConvertOptions::default()
+    };
+    let (doc, _) = crate::parse_bytes(&encrypted, Format::Docx, &options).unwrap();
+    assert_eq!(doc.metadata.title.as_deref(), Some("Board minutes"));
+
+    assert!(matches!(
+        crate::parse_bytes(&encrypted, Format::Docx, &ConvertOptions::default()),
+        Err(ConvertError::UnsupportedEncryption)
+    ));
+}
@@ -0,0 +1,477 @@
+//! InkML parser for digital pen annotations.
+//!
+//! Office stores handwriting as W3C InkML parts (`ppt/ink/inkN.xml`)
+//! referenced from a `<p:contentPart>`. Only what is needed to draw the
+//! strokes is read: the trace coordinates, the pen force channel, and the
+//! brush width, color, and transparency.
+
+use std::collections::HashMap;
+
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+use super::xml_util::{get_attr_str, parse_hex_color};
+use crate::ir::{
+    BorderLineStyle, BorderSide, Color, FixedElement, FixedElementKind, Shape, ShapeEffects,
+    ShapeKind,
+};
+
+/// Brush used when a trace references none: a thin black pen.
+const DEFAULT_BRUSH_WIDTH_PT: f64 = 1.0;
+
+/// Opacity of a highlighter (`rasterOp="maskPen"`) without an explicit
+/// transparency.
+const HIGHLIGHTER_OPACITY: f64 = 0.5;
+
+/// Pen appearance from an `<inkml:brush>`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct InkBrush {
+    /// Nominal stroke width in points.
+    pub(crate) width: f64,
+    pub(crate) color: Color,
+    /// Opacity from 0.0 (fully transparent) to 1.0 (fully opaque).
+    pub(crate) opacity: f64,
+    /// True when the brush draws at a constant width regardless of force.
+    pub(crate) ignore_pressure: bool,
+}
+
+impl Default for InkBrush {
+    fn default() -> Self {
+        Self {
+            width: DEFAULT_BRUSH_WIDTH_PT,
+            color: Color::black(),
+            opacity: 1.0,
+            ignore_pressure: false,
+        }
+    }
+}
+
+/// One pen stroke, in the drawing's own coordinate units.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct InkTrace {
+    pub(crate) points: Vec<(f64, f64)>,
+    /// Mean pen force normalized to 0.0–1.0, when the trace records force.
+    pub(crate) pressure: Option<f64>,
+    pub(crate) brush: InkBrush,
+}
+
+/// All strokes of one InkML part.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct InkDrawing {
+    pub(crate) traces: Vec<InkTrace>,
+}
+
+/// Positions of the channels we read within each trace point.
+#[derive(Debug, Clone, Copy)]
+struct TraceFormat {
+    x: usize,
+    y: usize,
+    /// Force channel index and its maximum value.
+    force: Option<(usize, f64)>,
+    channel_count: usize,
+}
+
+impl Default for TraceFormat {
+    /// InkML's default trace format: X then Y.
+    fn default() -> Self {
+        Self {
+            x: 0,
+            y: 1,
+            force: None,
+            channel_count: 2,
+        }
+    }
+}
+
+/// Parse an InkML document. Returns `None` when it contains no drawable trace.
+pub(crate) fn parse_inkml(xml: &str) -> Option<InkDrawing> {
+    let mut reader = Reader::from_str(xml);
+    let mut brushes: HashMap<String, InkBrush> = HashMap::new();
+    let mut current_brush: Option<(String, InkBrush)> = None;
+    let mut format: Option<TraceFormat> = None;
+    let mut pending_channels: Option<Vec<(String, f64)>> = None;
+    let mut in_intermittent_channels = false;
+    // Brush references of enclosing `<inkml:traceGroup>`s.
+    let mut group_brushes: Vec<Option<String>> = Vec::new();
+    let mut trace_brush_ref: Option<Option<String>> = None;
+    let mut trace_text = String::new();
+    let mut traces: Vec<InkTrace> = Vec::new();
+
+    loop {
+        let (e, is_start): (BytesStart<'_>, bool) = match reader.read_event() {
+            Ok(Event::Start(e)) => (e, true),
+            Ok(Event::Empty(e)) => (e, false),
+            Ok(Event::Text(text)) => {
+                if trace_brush_ref.is_some()
+                    && let Ok(text) = text.decode()
+                {
+                    trace_text.push_str(&text);
+                }
+                continue;
+            }
+            Ok(Event::End(e)) => {
+                match e.local_name().as_ref() {
+                    b"brush" => {
+                        if let Some((id, brush)) = current_brush.take() {
+                            brushes.insert(id, brush);
+                        }
+                    }
+                    b"traceFormat" => {
+                        if format.is_none()
+                            && let Some(channels) = pending_channels.take()
+                        {
+                            format = Some(trace_format_from_channels(&channels));
+                        }
+                    }
+                    b"intermittentChannels" => in_intermittent_channels = false,
+                    b"traceGroup" => {
+                        group_brushes.pop();
+                    }
+                    b"trace" => {
+                        if let Some(brush_ref) = trace_brush_ref.take() {
+                            let brush: InkBrush = brush_ref
+                                .or_else(|| group_brushes.iter().rev().flatten().next().cloned())
+                                .and_then(|id| brushes.get(&id).copied())
+                                .unwrap_or_default();
+                            let trace_format: TraceFormat = format.unwrap_or_default();
+                            if let Some(trace) = decode_trace(&trace_text, trace_format, brush) {
+                                traces.push(trace);
+                            }
+                            trace_text.clear();
+                        }
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => continue,
+        };
+
+        match e.local_name().as_ref() {
+            b"brush" => {
+                let id: String = get_attr_str(&e, b"id").unwrap_or_default();
+                if is_start {
+                    current_brush = Some((id, InkBrush::default()));
+                } else {
+                    brushes.insert(id, InkBrush::default());
+                }
+            }
+            b"brushProperty" => {
+                if let Some((_, brush)) = current_brush.as_mut() {
+                    apply_brush_property(&e, brush);
+                }
+            }
+            b"traceFormat" if format.is_none() => pending_channels = Some(Vec::new()),
+            b"intermittentChannels" => in_intermittent_channels = is_start,
+            // Intermittent channels may be omitted from any point, so only
+            // the regular channels give stable value positions.
+            b"channel" if !in_intermittent_channels => {
+                if let Some(channels) = pending_channels.as_mut() {
+                    let name: String = get_attr_str(&e, b"name").unwrap_or_default();
+                    let max: f64 = get_attr_str(&e, b"max")
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or(0.0);
+                    channels.push((name, max));
+                }
+            }
+            b"traceGroup" if is_start => group_brushes.push(brush_reference(&e)),
+            b"trace" if is_start => {
+                trace_brush_ref = Some(brush_reference(&e));
+                trace_text.clear();
+            }
+            _ => {}
+        }
+    }
+
+    (!traces.is_empty()).then_some(InkDrawing { traces })
+}
+
+/// `brushRef="#br0"` → `br0`.
+fn brush_reference(e: &BytesStart<'_>) -> Option<String> {
+    get_attr_str(e, b"brushRef").map(|value| value.trim_start_matches('#').to_string())
+}
+
+fn trace_format_from_channels(channels: &[(String, f64)]) -> TraceFormat {
+    let position = |name: &str| channels.iter().position(|(channel, _)| channel == name);
+    let defaults = TraceFormat::default();
+    TraceFormat {
+        x: position("X").unwrap_or(defaults.x),
+        y: position("Y").unwrap_or(defaults.y),
+        force: position("F")
+            .map(|index| (index, channels[index].1))
+            .filter(|(_, max)| *max > 0.0),
+        channel_count: channels.len().max(defaults.channel_count),
+    }
+}
+
+fn apply_brush_property(e: &BytesStart<'_>, brush: &mut InkBrush) {
+    let Some(value) = get_attr_str(e, b"value") else {
+        return;
+    };
+    match get_attr_str(e, b"name").as_deref() {
+        Some("width") => {
+            if let Ok(width) = value.parse::<f64>() {
+                brush.width = length_to_pt(width, get_attr_str(e, b"units").as_deref());
+            }
+        }
+        Some("color") => {
+            if let Some(color) = parse_hex_color(value.trim_start_matches('#')) {
+                brush.color = color;
+            }
+        }
+        // 0 = opaque, 255 = fully transparent.
+        Some("transparency") => {
+            if let Ok(transparency) = value.parse::<f64>() {
+                brush.opacity = (1.0 - transparency / 255.0).clamp(0.0, 1.0);
+            }
+        }
+        Some("rasterOp") if value == "maskPen" && brush.opacity == 1.0 => {
+            brush.opacity = HIGHLIGHTER_OPACITY;
+        }
+        Some("ignorePressure") => {
+            brush.ignore_pressure = value == "1" || value == "true";
+        }
+        _ => {}
+    }
+}
+
+/// Convert a brush length to points. Office writes centimeters.
+fn length_to_pt(value: f64, units: Option<&str>) -> f64 {
+    match units {
+        Some("mm") => value * 72.0 / 25.4,
+        Some("in") => value * 72.0,
+        Some("pt") => value,
+        Some("himetric") => value * 72.0 / 2540.0,
+        _ => value * 72.0 / 2.54,
+    }
+}
+
+/// How a channel's values are encoded (InkML §3.2.1 prefixes `!`, `'`, `"`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Difference {
+    /// Plain value.
+    Explicit,
+    /// First difference from the previous value.
+    Velocity,
+    /// Second difference: change of the previous first difference.
+    Acceleration,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ChannelState {
+    mode: Difference,
+    last: f64,
+    velocity: f64,
+}
+
+/// A single value token of a trace.
+enum TraceValue {
+    Number(f64),
+    /// `*`: the value predicted by the current encoding (difference 0).
+    Predicted,
+    /// `?`: no value recorded; the previous value is kept.
+    Missing,
+}
+
+impl ChannelState {
+    fn decode(&mut self, prefix: Option<Difference>, value: TraceValue) -> f64 {
+        if let Some(mode) = prefix {
+            self.mode = mode;
+        }
+        let decoded: f64 = match value {
+            TraceValue::Missing => return self.last,
+            TraceValue::Predicted => self.predict(0.0),
+            TraceValue::Number(number) => self.predict(number),
+        };
+        self.velocity = decoded - self.last;
+        self.last = decoded;
+        decoded
+    }
+
+    fn predict(&self, number: f64) -> f64 {
+        match self.mode {
+            Difference::Explicit => number,
+            Difference::Velocity => self.last + number,
+            Difference::Acceleration => self.last + self.velocity + number,
+        }
+    }
+}
+
+/// Decode the text of an `<inkml:trace>`: comma-separated points whose
+/// channel values may run together (`-26-13`) and carry difference prefixes.
+fn decode_trace(text: &str, format: TraceFormat, brush: InkBrush) -> Option<InkTrace> {
+    let mut channels: Vec<ChannelState> = vec![
+        ChannelState {
+            mode: Difference::Explicit,
+            last: 0.0,
+            velocity: 0.0,
+        };
+        format.channel_count
+    ];
+    let mut points: Vec<(f64, f64)> = Vec::new();
+    let mut force_total: f64 = 0.0;
+    let mut force_samples: usize = 0;
+
+    for point_text in text.split(',') {
+        let mut values: Vec<f64> = Vec::with_capacity(format.channel_count);
+        let mut prefix: Option<Difference> = None;
+        let mut chars = point_text.char_indices().peekable();
+        while let Some((start, ch)) = chars.next() {
+            let value: TraceValue = match ch {
+                '!' => {
+                    prefix = Some(Difference::Explicit);
+                    continue;
+                }
+                '\'' => {
+                    prefix = Some(Difference::Velocity);
+                    continue;
+                }
+                '"' => {
+                    prefix = Some(Difference::Acceleration);
+                    continue;
+                }
+                '*' => TraceValue::Predicted,
+                '?' => TraceValue::Missing,
+                '-' | '+' | '.' | '0'..='9' => {
+                    let mut end: usize = start + ch.len_utf8();
+                    let mut seen_dot: bool = ch == '.';
+                    while let Some(&(index, next)) = chars.peek() {
+                        let continues: bool = next.is_ascii_digit() || (next == '.' && !seen_dot);
+                        if !continues {
+                            break;
+                        }
+                        seen_dot |= next == '.';
+                        end = index + next.len_utf8();
+                        chars.next();
+                    }
+                    match point_text[start..end].parse::<f64>() {
+                        Ok(number) => TraceValue::Number(number),
+                        Err(_) => TraceValue::Missing,
+                    }
+                }
+                // Whitespace separates values; boolean `T`/`F` values and
+                // anything else carry nothing we draw.
+                _ => continue,
+            };
+            let index: usize = values.len();
+            if let Some(channel) = channels.get_mut(index) {
+                values.push(channel.decode(prefix.take(), value));
+            } else {
+                prefix = None;
+            }
+        }
+
+        if values.len() <= format.x.max(format.y) {
+            continue;
+        }
+        points.push((values[format.x], values[format.y]));
+        if let Some((index, max)) = format.force
+            && let Some(force) = values.get(index)
+        {
+            force_total += (force / max).clamp(0.0, 1.0);
+            force_samples += 1;
+        }
+    }
+
+    (!points.is_empty()).then(|| InkTrace {
+        points,
+        pressure: (force_samples > 0).then(|| force_total / force_samples as f64),
+        brush,
+    })
+}
+
+impl InkDrawing {
+    /// Bounding box `(min_x, min_y, max_x, max_y)` of every trace point.
+    fn bounds(&self) -> (f64, f64, f64, f64) {
+        self.traces
+            .iter()
+            .flat_map(|trace| trace.points.iter())
+            .fold(
+                (f64::MAX, f64::MAX, f64::MIN, f64::MIN),
+                |(min_x, min_y, max_x, max_y), &(x, y)| {
+                    (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+                },
+            )
+    }
+}
+
+/// Stroke width after the pressure approximation: the nominal width at half
+/// force, scaling linearly from 50% with no force to 150% at full force.
+fn stroke_width(trace: &InkTrace) -> f64 {
+    match trace.pressure {
+        Some(pressure) if !trace.brush.ignore_pressure => trace.brush.width * (0.5 + pressure),
+        _ => trace.brush.width,
+    }
+}
+
+/// Lay the drawing out in a frame (points): the bounding box of the strokes,
+/// inset by half the widest pen, is stretched over the frame. Each trace
+/// becomes one `InkStroke` shape covering the frame.
+pub(crate) fn ink_elements(
+    drawing: &InkDrawing,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+) -> Vec<FixedElement> {
+    let (min_x, min_y, max_x, max_y) = drawing.bounds();
+    let widest: f64 = drawing.traces.iter().map(stroke_width).fold(0.0, f64::max);
+    let inset: f64 = (widest / 2.0).min(width / 2.0).min(height / 2.0);
+    let inner_width: f64 = width - 2.0 * inset;
+    let inner_height: f64 = height - 2.0 * inset;
+    let span_x: f64 = max_x - min_x;
+    let span_y: f64 = max_y - min_y;
+    // A perfectly straight stroke has no extent on one axis; keep its aspect
+    // by borrowing the other axis' scale.
+    let (scale_x, scale_y): (f64, f64) = match (span_x > 0.0, span_y > 0.0) {
+        (true, true) => (inner_width / span_x, inner_height / span_y),
+        (true, false) => (inner_width / span_x, inner_width / span_x),
+        (false, true) => (inner_height / span_y, inner_height / span_y),
+        (false, false) => (0.0, 0.0),
+    };
+    // Center the drawing on any axis that does not fill the frame.
+    let offset_x: f64 = inset + (inner_width - span_x * scale_x) / 2.0;
+    let offset_y: f64 = inset + (inner_height - span_y * scale_y) / 2.0;
+
+    drawing
+        .traces
+        .iter()
+        .map(|trace| FixedElement {
+            x,
+            y,
+            width,
+            height,
+            kind: FixedElementKind::Shape(Shape {
+                kind: ShapeKind::InkStroke {
+                    points: trace
+                        .points
+                        .iter()
+                        .map(|&(px, py)| {
+                            (
+                                offset_x + (px - min_x) * scale_x,
+                                offset_y + (py - min_y) * scale_y,
+                            )
+                        })
+                        .collect(),
+                },
+                fill: None,
+                gradient_fill: None,
+                stroke: Some(BorderSide {
+                    width: stroke_width(trace),
+                    color: trace.brush.color,
+                    style: BorderLineStyle::Solid,
+                }),
+                rotation_deg: None,
+                opacity: (trace.brush.opacity < 1.0).then_some(trace.brush.opacity),
+                shadow: None,
+                effects: ShapeEffects::default(),
+            }),
+            href: None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "inkml_tests.rs"]
+mod tests;
@@ -0,0 +1,139 @@
+use super::*;
+
+/// Wrap trace markup in an InkML document with one brush and an X/Y/F
+/// trace format, the shape Office writes.
+fn make_inkml(brush_properties: &str, traces: &str) -> String {
+    format!(
+        r##"<inkml:ink xmlns:inkml="http://www.w3.org/2003/InkML"><inkml:definitions><inkml:context xml:id="ctx0"><inkml:inkSource xml:id="inkSrc0"><inkml:traceFormat><inkml:channel name="X" type="integer" max="32767" units="cm"/><inkml:channel name="Y" type="integer" max="32767" units="cm"/><inkml:channel name="F" type="integer" max="1000" units="dev"/><inkml:intermittentChannels><inkml:channel name="T" type="integer" units="ms"/></inkml:intermittentChannels></inkml:traceFormat></inkml:inkSource></inkml:context><inkml:brush xml:id="br0">{brush_properties}</inkml:brush></inkml:definitions>{traces}</inkml:ink>"##
+    )
+}
+
+fn parse_single_trace(trace_text: &str) -> InkTrace {
+    let xml = make_inkml(
+        "",
+        &format!(r##"<inkml:trace contextRef="#ctx0" brushRef="#br0">{trace_text}</inkml:trace>"##),
+    );
+    parse_inkml(&xml).expect("drawing").traces.remove(0)
+}
+
+#[test]
+fn test_explicit_points_and_mean_force() {
+    let trace = parse_single_trace("10 20 200, 30 40 600");
+
+    assert_eq!(trace.points, vec![(10.0, 20.0), (30.0, 40.0)]);
+    let pressure: f64 = trace.pressure.unwrap();
+    assert!((pressure - 0.4).abs() < 1e-9, "pressure {pressure}");
+}
+
+#[test]
+fn test_difference_encodings_persist_per_channel() {
+    // Second point: first differences; third and fourth: second differences
+    // of zero, so the velocity of the second point carries on.
+    let trace = parse_single_trace(r#"10 20 0, '5 '5 '0, "0 "0 "0, 0 0 0"#);
+
+    assert_eq!(
+        trace.points,
+        vec![(10.0, 20.0), (15.0, 25.0), (20.0, 30.0), (25.0, 35.0)]
+    );
+}
+
+#[test]
+fn test_values_may_run_together_on_signs() {
+    let trace = parse_single_trace("0 0 0,'-5-3 0");
+
+    assert_eq!(trace.points, vec![(0.0, 0.0), (-5.0, -3.0)]);
+}
+
+#[test]
+fn test_brush_properties() {
+    let xml = make_inkml(
+        r##"<inkml:brushProperty name="width" value="0.254" units="cm"/><inkml:brushProperty name="height" value="0.254" units="cm"/><inkml:brushProperty name="color" value="#E71224"/><inkml:brushProperty name="transparency" value="51"/><inkml:brushProperty name="ignorePressure" value="1"/>"##,
+        r##"<inkml:trace contextRef="#ctx0" brushRef="#br0">0 0 0, 10 10 1000</inkml:trace>"##,
+    );
+    let brush: InkBrush = parse_inkml(&xml).unwrap().traces[0].brush;
+
+    assert!((brush.width - 7.2).abs() < 1e-9, "width {}", brush.width);
+    assert_eq!(brush.color, Color::new(0xE7, 0x12, 0x24));
+    assert!((brush.opacity - 0.8).abs() < 1e-9);
+    assert!(brush.ignore_pressure);
+}
+
+#[test]
+fn test_highlighter_is_translucent() {
+    let xml = make_inkml(
+        r##"<inkml:brushProperty name="color" value="#FFFF00"/><inkml:brushProperty name="rasterOp" value="maskPen"/>"##,
+        r##"<inkml:trace brushRef="#br0">0 0, 10 0</inkml:trace>"##,
+    );
+
+    assert_eq!(
+        parse_inkml(&xml).unwrap().traces[0].brush.opacity,
+        HIGHLIGHTER_OPACITY
+    );
+}
+
+#[test]
+fn test_trace_group_brush_is_inherited() {
+    let xml = make_inkml(
+        r##"<inkml:brushProperty name="color" value="#0000FF"/>"##,
+        r##"<inkml:traceGroup brushRef="#br0"><inkml:trace>0 0, 5 5</inkml:trace></inkml:traceGroup><inkml:trace>1 1, 2 2</inkml:trace>"##,
+    );
+    let drawing: InkDrawing = parse_inkml(&xml).unwrap();
+
+    assert_eq!(drawing.traces[0].brush.color, Color::new(0, 0, 255));
+    assert_eq!(drawing.traces[1].brush, InkBrush::default());
+}
+
+#[test]
+fn test_document_without_traces_is_none() {
+    assert!(parse_inkml(&make_inkml("", "")).is_none());
+}
+
+#[test]
+fn test_ink_elements_fit_strokes_into_frame() {
+    let drawing = InkDrawing {
+        traces: vec![InkTrace {
+            points: vec![(100.0, 100.0), (300.0, 200.0)],
+            pressure: None,
+            brush: InkBrush {
+                width: 2.0,
+                ..InkBrush::default()
+            },
+        }],
+    };
+    let elements: Vec<FixedElement> = ink_elements(&drawing, 50.0, 60.0, 202.0, 102.0);
+
+    assert_eq!(elements.len(), 1);
+    assert_eq!((elements[0].x, elements[0].y), (50.0, 60.0));
+    let FixedElementKind::Shape(shape) = &elements[0].kind else {
+        panic!("expected a shape");
+    };
+    // Inset by half the pen width on every side.
+    let ShapeKind::InkStroke { points } = &shape.kind else {
+        panic!("expected an ink stroke, got {:?}", shape.kind);
+    };
+    assert_eq!(points, &vec![(1.0, 1.0), (201.0, 101.0)]);
+    assert_eq!(shape.stroke.as_ref().unwrap().width, 2.0);
+    assert_eq!(shape.opacity, None);
+}
+
+#[test]
+fn test_pressure_scales_stroke_width() {
+    let trace = InkTrace {
+        points: vec![(0.0, 0.0)],
+        pressure: Some(1.0),
+        brush: InkBrush {
+            width: 2.0,
+            ..InkBrush::default()
+        },
+    };
+    assert_eq!(stroke_width(&trace), 3.0);
+
+    let constant = InkTrace {
+        brush: InkBrush {
+            ignore_pressure: true,
+            ..trace.brush
+        },
+        ..trace
+    };
+    assert_eq!(stroke_width(&constant), 2.0);
+}
@@ -1,32 +1,50 @@
 use std::io::Read;
 
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesRef, Event};
 use zip::ZipArchive;
 
 use crate::ir::Metadata;
 
-/// Parse Dublin Core metadata from OOXML `docProps/core.xml` inside a ZIP archive.
+/// Parse Dublin Core metadata from OOXML `docProps/core.xml` inside a ZIP
+/// archive, with the company from `docProps/app.xml` and the user-defined
+/// properties from `docProps/custom.xml`.
 ///
-/// Returns `Metadata::default()` if the entry is missing or unparseable (no error).
+/// Missing or unparseable parts leave their fields empty (no error).
 pub fn extract_metadata_from_zip<R: Read + std::io::Seek>(archive: &mut ZipArchive<R>) -> Metadata {
-    let xml = match archive.by_name("docProps/core.xml") {
-        Ok(mut file) => {
-            let mut content = String::new();
-            if file.read_to_string(&mut content).is_err() {
-                return Metadata::default();
-            }
-            content
-        }
-        Err(_) => return Metadata::default(),
-    };
-    parse_core_xml(&xml)
+    let mut metadata: Metadata = read_part(archive, "docProps/core.xml")
+        .map(|xml| parse_core_xml(&xml))
+        .unwrap_or_default();
+    extract_package_properties(archive, &mut metadata);
+    metadata
+}
+
+/// Fill `metadata.company` and `metadata.custom_properties` from
+/// `docProps/app.xml` and `docProps/custom.xml`.
+pub fn extract_package_properties<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    metadata: &mut Metadata,
+) {
+    if let Some(xml) = read_part(archive, "docProps/app.xml") {
+        metadata.company = parse_app_company(&xml);
+    }
+    if let Some(xml) = read_part(archive, "docProps/custom.xml") {
+        metadata.custom_properties = parse_custom_xml(&xml);
+    }
+}
+
+fn read_part<R: Read + std::io::Seek>(archive: &mut ZipArchive<R>, name: &str) -> Option<String> {
+    let mut file = archive.by_name(name).ok()?;
+    let mut content = String::new();
+    file.read_to_string(&mut content).ok()?;
+    Some(content)
 }
 
 /// Parse Dublin Core metadata from `docProps/core.xml` content string.
 ///
 /// Extracts: `dc:title`, `dc:creator`, `dc:subject`, `dc:description`,
-/// `dcterms:created`, `dcterms:modified`.
+/// `dc:language`, `cp:keywords`, `dcterms:created`, `dcterms:modified`.
 pub fn parse_core_xml(xml: &str) -> Metadata {
     let mut metadata = Metadata::default();
     let mut reader = Reader::from_str(xml);
@@ -38,6 +56,8 @@ pub fn parse_core_xml(xml: &str) -> Metadata {
         Creator,
         Subject,
         Description,
+        Language,
+        Keywords,
         Created,
         Modified,
     }
@@ -53,6 +73,8 @@ pub fn parse_core_xml(xml: &str) -> Metadata {
                     b"creator" => Field::Creator,
                     b"subject" => Field::Subject,
                     b"description" => Field::Description,
+                    b"language" => Field::Language,
+                    b"keywords" => Field::Keywords,
                     b"created" => Field::Created,
                     b"modified" => Field::Modified,
                     _ => Field::None,
@@ -68,6 +90,8 @@ pub fn parse_core_xml(xml: &str) -> Metadata {
                             Field::Title => metadata.title = Some(text),
                             Field::Creator => metadata.author = Some(text),
                             Field::Subject => metadata.subject = Some(text),
+                            Field::Language => metadata.language = Some(text),
+                            Field::Keywords => metadata.keywords = split_keywords(&text),
                             Field::Description => metadata.description = Some(text),
                             Field::Created => metadata.created = Some(text),
                             Field::Modified => metadata.modified = Some(text),
@@ -88,6 +112,124 @@ pub fn parse_core_xml(xml: &str) -> Metadata {
     metadata
 }
 
+/// Office joins keywords with `;` or `,` depending on the locale.
+fn split_keywords(text: &str) -> Vec<String> {
+    text.split([',', ';'])
+        .map(str::trim)
+        .filter(|keyword| !keyword.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The `Company` of `docProps/app.xml`, if set.
+pub fn parse_app_company(xml: &str) -> Option<String> {
+    parse_app_element(xml, b"Company")
+}
+
+/// The page count Word recorded in `docProps/app.xml` when it last saved
+/// the document (`Pages`), if any.
+pub fn parse_app_pages(xml: &str) -> Option<u32> {
+    parse_app_element(xml, b"Pages")?
+        .parse::<u32>()
+        .ok()
+        .filter(|pages| *pages > 0)
+}
+
+/// Read the `docProps/app.xml` page count of a package; see
+/// [`parse_app_pages`].
+pub fn read_app_pages<R: Read + std::io::Seek>(archive: &mut ZipArchive<R>) -> Option<u32> {
+    parse_app_pages(&read_part(archive, "docProps/app.xml")?)
+}
+
+/// The trimmed text of the first `name` element of `docProps/app.xml`,
+/// unless it is empty.
+fn parse_app_element(xml: &str, name: &[u8]) -> Option<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut value: Option<String> = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == name => {
+                value = Some(String::new());
+            }
+            Ok(Event::Text(e)) => {
+                if let Some(value) = value.as_mut()
+                    && let Ok(text) = e.xml_content()
+                {
+                    value.push_str(&text);
+                }
+            }
+            Ok(Event::GeneralRef(reference)) => {
+                if let Some(value) = value.as_mut() {
+                    value.push_str(&general_ref_text(&reference));
+                }
+            }
+            Ok(Event::End(e)) if e.local_name().as_ref() == name => break,
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// The user-defined properties of `docProps/custom.xml` as `(name, value)`.
+///
+/// Each `property` holds one variant-typed value (`vt:lpwstr`, `vt:i4`,
+/// `vt:bool`, `vt:filetime`, ...), kept as its text.
+pub fn parse_custom_xml(xml: &str) -> Vec<(String, String)> {
+    let mut properties: Vec<(String, String)> = Vec::new();
+    let mut reader = Reader::from_str(xml);
+    let mut current: Option<(String, String)> = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"property" => {
+                current = e
+                    .attributes()
+                    .flatten()
+                    .find(|attr| attr.key.local_name().as_ref() == b"name")
+                    .and_then(|attr| attr.unescape_value().ok())
+                    .map(|name| (name.into_owned(), String::new()));
+            }
+            Ok(Event::Text(e)) => {
+                if let Some((_, value)) = current.as_mut()
+                    && let Ok(text) = e.xml_content()
+                {
+                    value.push_str(&text);
+                }
+            }
+            Ok(Event::GeneralRef(reference)) => {
+                if let Some((_, value)) = current.as_mut() {
+                    value.push_str(&general_ref_text(&reference));
+                }
+            }
+            Ok(Event::End(e)) if e.local_name().as_ref() == b"property" => {
+                if let Some((name, value)) = current.take()
+                    && !name.is_empty()
+                {
+                    properties.push((name, value.trim().to_string()));
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    properties
+}
+
+// quick-xml reports `&amp;` and friends as separate events.
+fn general_ref_text(reference: &BytesRef<'_>) -> String {
+    reference
+        .decode()
+        .ok()
+        .and_then(|name| {
+            unescape(&format!("&{name};"))
+                .ok()
+                .map(|value| value.into_owned())
+        })
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 #[path = "metadata_tests.rs"]
 mod tests;
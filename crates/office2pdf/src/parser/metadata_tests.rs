@@ -11,6 +11,8 @@ fn test_parse_core_xml_full_metadata() {
   <dc:creator>John Doe</dc:creator>
   <dc:subject>Testing Subject</dc:subject>
   <dc:description>A test document description</dc:description>
+  <dc:language>en-GB</dc:language>
+  <cp:keywords>budget; forecast,  2024 ;</cp:keywords>
   <dcterms:created xsi:type="dcterms:W3CDTF">2024-06-15T10:30:00Z</dcterms:created>
   <dcterms:modified xsi:type="dcterms:W3CDTF">2024-07-20T14:00:00Z</dcterms:modified>
 </cp:coreProperties>"#;
@@ -23,6 +25,8 @@ fn test_parse_core_xml_full_metadata() {
         meta.description.as_deref(),
         Some("A test document description")
     );
+    assert_eq!(meta.language.as_deref(), Some("en-GB"));
+    assert_eq!(meta.keywords, vec!["budget", "forecast", "2024"]);
     assert_eq!(meta.created.as_deref(), Some("2024-06-15T10:30:00Z"));
     assert_eq!(meta.modified.as_deref(), Some("2024-07-20T14:00:00Z"));
 }
@@ -40,6 +44,7 @@ fn test_parse_core_xml_partial_metadata() {
     assert!(meta.author.is_none());
     assert!(meta.subject.is_none());
     assert!(meta.description.is_none());
+    assert!(meta.language.is_none());
     assert!(meta.created.is_none());
     assert!(meta.modified.is_none());
 }
@@ -127,3 +132,89 @@ fn test_extract_metadata_from_zip_without_core_xml() {
     assert!(meta.title.is_none());
     assert!(meta.author.is_none());
 }
+
+#[test]
+fn test_parse_app_company_unescapes_entities() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties">
+  <Application>Microsoft Office Word</Application>
+  <Company>Smith &amp; Sons</Company>
+</Properties>"#;
+    assert_eq!(parse_app_company(xml).as_deref(), Some("Smith & Sons"));
+    assert_eq!(
+        parse_app_company("<Properties><Company></Company></Properties>"),
+        None
+    );
+    assert_eq!(parse_app_company("<Properties/>"), None);
+}
+
+#[test]
+fn test_parse_app_pages_reads_positive_counts() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties">
+  <Template>Normal.dotm</Template>
+  <Pages>12</Pages>
+  <Words>3100</Words>
+</Properties>"#;
+    assert_eq!(parse_app_pages(xml), Some(12));
+    assert_eq!(
+        parse_app_pages("<Properties><Pages>0</Pages></Properties>"),
+        None
+    );
+    assert_eq!(
+        parse_app_pages("<Properties><Pages>many</Pages></Properties>"),
+        None
+    );
+    assert_eq!(parse_app_pages("<Properties/>"), None);
+}
+
+#[test]
+fn test_parse_custom_xml_keeps_typed_values_in_order() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/custom-properties"
+    xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes">
+  <property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="2" name="Project"><vt:lpwstr>R&amp;D</vt:lpwstr></property>
+  <property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="3" name="Reviewed"><vt:bool>true</vt:bool></property>
+  <property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="4" name="Due"><vt:filetime>2024-01-31T00:00:00Z</vt:filetime></property>
+</Properties>"#;
+    assert_eq!(
+        parse_custom_xml(xml),
+        vec![
+            ("Project".to_string(), "R&D".to_string()),
+            ("Reviewed".to_string(), "true".to_string()),
+            ("Due".to_string(), "2024-01-31T00:00:00Z".to_string()),
+        ]
+    );
+    assert!(parse_custom_xml("<Properties/>").is_empty());
+}
+
+#[test]
+fn test_extract_metadata_from_zip_reads_app_and_custom_parts() {
+    use std::io::{Cursor, Write};
+
+    let mut zip_writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::FileOptions::default();
+    zip_writer.start_file("docProps/app.xml", options).unwrap();
+    zip_writer
+        .write_all(b"<Properties><Company>Acme</Company></Properties>")
+        .unwrap();
+    zip_writer
+        .start_file("docProps/custom.xml", options)
+        .unwrap();
+    zip_writer
+        .write_all(
+            br#"<Properties xmlns:vt="vt"><property name="Client"><vt:lpwstr>Globex</vt:lpwstr></property></Properties>"#,
+        )
+        .unwrap();
+    let cursor = zip_writer.finish().unwrap();
+
+    // No core.xml: the other parts are still read.
+    let mut archive = ZipArchive::new(cursor).unwrap();
+    let meta = extract_metadata_from_zip(&mut archive);
+    assert!(meta.title.is_none());
+    assert_eq!(meta.company.as_deref(), Some("Acme"));
+    assert_eq!(
+        meta.custom_properties,
+        vec![("Client".to_string(), "Globex".to_string())]
+    );
+}
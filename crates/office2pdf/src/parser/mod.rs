@@ -1,3 +1,4 @@
+pub(crate) mod assets;
 pub(crate) mod chart;
 pub(crate) mod cond_fmt;
 pub mod docx;
@@ -5,10 +6,13 @@ pub(crate) mod drawingml;
 pub(crate) mod embedded_fonts;
 #[path = "pptx_emf.rs"]
 pub(crate) mod emf;
+pub(crate) mod encryption;
+pub(crate) mod inkml;
 pub(crate) mod metadata;
 pub(crate) mod omml;
 pub mod pptx;
 pub(crate) mod smartart;
+pub(crate) mod sniff;
 pub(crate) mod units;
 pub(crate) mod wmf;
 pub mod xlsx;
@@ -48,6 +52,17 @@ pub(crate) fn parse_err(msg: impl std::fmt::Display) -> ConvertError {
     ConvertError::Parse(msg.to_string())
 }
 
+/// Best-effort message of a caught panic payload.
+pub(crate) fn extract_panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -9,43 +9,54 @@ use quick_xml::events::{BytesStart, Event};
 use zip::ZipArchive;
 
 use crate::config::ConvertOptions;
-use crate::error::{ConvertError, ConvertWarning};
+use crate::error::{ConvertError, ConvertWarning, WarningLocation};
 use crate::ir::{
-    Alignment, ArrowHead, Block, BorderLineStyle, BorderSide, CellBorder, CellVerticalAlign, Chart,
-    Color, Document, FixedElement, FixedElementKind, FixedPage, GradientFill, ImageClipShape,
-    ImageCrop, ImageData, ImageFormat, Insets, LineSpacing, List, ListItem, ListKind,
-    ListLevelStyle, Page, PageSize, Paragraph, ParagraphStyle, Run, Shadow, Shape, ShapeKind,
-    SmartArt, SmartArtNode, StyleSheet, Table, TableCell, TableRow, TextBoxData,
-    TextBoxVerticalAlign, TextDirection, TextStyle,
+    Alignment, ArrowHead, BackgroundImage, Block, BorderLineStyle, BorderSide, CellBorder,
+    CellVerticalAlign, Chart, ChartType, Color, Document, Extrusion, FixedElement,
+    FixedElementKind, FixedPage, Glow, GradientFill, ImageClipShape, ImageCrop, ImageData,
+    ImageFormat, Insets, LineSpacing, List, ListItem, ListKind, ListLevelStyle, Metadata, Page,
+    PageSize, Paragraph, ParagraphStyle, Reflection, Rotation3d, Run, Shadow, Shape, ShapeEffects,
+    ShapeKind, SmartArt, SmartArtLayout, SmartArtNode, StyleSheet, Table, TableCell, TableRow,
+    TextBoxData, TextBoxVerticalAlign, TextDirection, TextOutline, TextStyle,
 };
 use crate::parser::Parser;
+use crate::parser::inkml::{self, InkDrawing};
 use crate::parser::smartart;
 use crate::parser::units::emu_to_pt;
 
+use self::links::resolve_slide_links;
+pub(crate) use self::outline::read_slide_outline;
 use self::package::{
     load_table_styles, load_theme, parse_presentation_xml, parse_rels_xml, read_zip_entry,
 };
 #[cfg(test)]
 use self::package::{resolve_relative_path, scan_chart_refs};
 use self::shapes::{
-    parse_arrow_head, parse_group_shape, parse_src_rect, pptx_dash_to_border_style,
-    prst_to_shape_kind,
+    is_compound_line, line_border_side, parse_arrow_head, parse_group_shape, parse_src_rect,
+    pptx_dash_to_border_style, prst_to_shape_kind,
 };
 use self::slides::{SlideParseContext, parse_single_slide, parse_slide_xml};
 use self::tables::{parse_pptx_table, scale_pptx_table_geometry_to_frame};
 use self::text::*;
 use self::theme::{
-    ColorMapData, ParsedColor, PptxMasterTextStyles, ThemeData, default_color_map,
-    parse_background_color, parse_background_gradient, parse_background_image_rid,
-    parse_background_ref, parse_color_from_empty, parse_color_from_start, parse_effect_list,
-    parse_master_color_map, parse_master_text_styles, parse_shape_gradient_fill, parse_theme_xml,
+    BackgroundPictureFill, ColorMapData, ParsedColor, PptxMasterTextStyles, ThemeData,
+    default_color_map, parse_background_color, parse_background_gradient,
+    parse_background_picture_fill, parse_background_ref, parse_color_from_empty,
+    parse_color_from_start, parse_effect_list, parse_master_color_map, parse_master_text_styles,
+    parse_scene3d, parse_shape_gradient_fill, parse_sp3d, parse_text_outline, parse_theme_xml,
     resolve_effective_color_map, resolve_scheme_color, resolve_theme_font,
 };
 
+#[path = "pptx_links.rs"]
+mod links;
+#[path = "pptx_outline.rs"]
+mod outline;
 #[path = "pptx_package.rs"]
 mod package;
 #[path = "pptx_placeholders.rs"]
 mod placeholders;
+#[path = "pptx_rescale.rs"]
+mod rescale;
 #[path = "pptx_shapes.rs"]
 mod shapes;
 #[path = "pptx_slides.rs"]
@@ -66,6 +77,27 @@ struct Relationship {
     rel_type: Option<String>,
 }
 
+/// Slide list, slide size, and sections read from `ppt/presentation.xml`.
+#[derive(Debug, Clone)]
+struct PresentationInfo {
+    slide_size: PageSize,
+    /// Relationship ID of each slide, in presentation order.
+    slide_rids: Vec<String>,
+    /// Section of each slide, parallel to `slide_rids`. All `None` when the
+    /// presentation has no `<p14:sectionLst>`.
+    slide_sections: Vec<Option<String>>,
+    /// Section names in presentation order.
+    section_names: Vec<String>,
+}
+
+/// Pages produced by one presentation section, in document order.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "pdf-ops"), allow(dead_code))]
+pub(crate) struct SectionPages {
+    pub(crate) name: String,
+    pub(crate) page_count: usize,
+}
+
 /// Image asset referenced by a slide relationship.
 #[derive(Debug, Clone)]
 struct SlideImageAsset {
@@ -100,6 +132,16 @@ enum SlideImageSource {
 /// Map from relationship ID → slide image asset.
 type SlideImageMap = HashMap<String, SlideImageAsset>;
 
+/// Map from relationship ID → file name of a linked or embedded video/audio.
+type SlideMediaMap = HashMap<String, String>;
+
+/// Map from relationship ID → ink drawing of a `<p:contentPart>`.
+type SlideInkMap = HashMap<String, InkDrawing>;
+
+/// Map from relationship ID → preview table of an embedded Excel workbook
+/// (`<p:oleObj>`), at the sheet's natural size.
+type SlideWorkbookMap = HashMap<String, Table>;
+
 /// Context for which element a `<a:solidFill>` belongs to.
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum SolidFillCtx {
@@ -319,9 +361,52 @@ impl PendingPptxList {
     }
 }
 
+/// Percent-of-line `a:spcBef`/`a:spcAft` values (thousandths of a percent).
+/// They stay unresolved while list styles inherit because the line height
+/// depends on the font size of the level that finally uses them.
+#[derive(Debug, Clone, Copy, Default)]
+struct PptxPercentSpacing {
+    before: Option<i64>,
+    after: Option<i64>,
+}
+
+impl PptxPercentSpacing {
+    /// Layer `overlay` on top of `self`/`style`, where the overlay's gaps
+    /// (points or percent) replace whichever form the base used.
+    fn merge_from(
+        &mut self,
+        style: &mut ParagraphStyle,
+        overlay: &PptxPercentSpacing,
+        overlay_style: &ParagraphStyle,
+    ) {
+        if overlay.before.is_some() {
+            self.before = overlay.before;
+            style.space_before = None;
+        } else if overlay_style.space_before.is_some() {
+            self.before = None;
+        }
+        if overlay.after.is_some() {
+            self.after = overlay.after;
+            style.space_after = None;
+        } else if overlay_style.space_after.is_some() {
+            self.after = None;
+        }
+    }
+
+    fn resolve_into(&self, style: &mut ParagraphStyle, font_size: Option<f64>) {
+        if let Some(value) = self.before {
+            style.space_before = Some(resolve_pptx_space_percent(value, font_size));
+        }
+        if let Some(value) = self.after {
+            style.space_after = Some(resolve_pptx_space_percent(value, font_size));
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 struct PptxTextLevelStyle {
     paragraph: ParagraphStyle,
+    percent_spacing: PptxPercentSpacing,
     run: TextStyle,
     bullet: PptxBulletDefinition,
 }
@@ -329,6 +414,7 @@ struct PptxTextLevelStyle {
 #[derive(Debug, Clone, Default)]
 struct PptxTextBodyStyleDefaults {
     default_paragraph: ParagraphStyle,
+    default_percent_spacing: PptxPercentSpacing,
     default_run: TextStyle,
     default_bullet: PptxBulletDefinition,
     levels: BTreeMap<u32, PptxTextLevelStyle>,
@@ -337,9 +423,16 @@ struct PptxTextBodyStyleDefaults {
 impl PptxTextBodyStyleDefaults {
     fn paragraph_style_for_level(&self, level: u32) -> ParagraphStyle {
         let mut style: ParagraphStyle = self.default_paragraph.clone();
+        let mut percent_spacing: PptxPercentSpacing = self.default_percent_spacing;
         if let Some(level_style) = self.levels.get(&level) {
             style.merge_from(&level_style.paragraph);
+            percent_spacing.merge_from(
+                &mut style,
+                &level_style.percent_spacing,
+                &level_style.paragraph,
+            );
         }
+        percent_spacing.resolve_into(&mut style, self.run_style_for_level(level).font_size);
         style
     }
 
@@ -372,12 +465,22 @@ impl PptxTextBodyStyleDefaults {
     fn merge_from(&mut self, overlay: &PptxTextBodyStyleDefaults) {
         self.default_paragraph
             .merge_from(&overlay.default_paragraph);
+        self.default_percent_spacing.merge_from(
+            &mut self.default_paragraph,
+            &overlay.default_percent_spacing,
+            &overlay.default_paragraph,
+        );
         self.default_run.merge_from(&overlay.default_run);
         merge_pptx_bullet_definition(&mut self.default_bullet, &overlay.default_bullet);
 
         for (level, overlay_style) in &overlay.levels {
             let target: &mut PptxTextLevelStyle = self.levels.entry(*level).or_default();
             target.paragraph.merge_from(&overlay_style.paragraph);
+            target.percent_spacing.merge_from(
+                &mut target.paragraph,
+                &overlay_style.percent_spacing,
+                &overlay_style.paragraph,
+            );
             target.run.merge_from(&overlay_style.run);
             merge_pptx_bullet_definition(&mut target.bullet, &overlay_style.bullet);
         }
@@ -393,14 +496,31 @@ impl Parser for PptxParser {
         data: &[u8],
         options: &ConvertOptions,
     ) -> Result<(Document, Vec<ConvertWarning>), ConvertError> {
+        let (doc, warnings, _sections) = self.parse_with_sections(data, options)?;
+        Ok((doc, warnings))
+    }
+}
+
+impl PptxParser {
+    /// Parse a presentation and also report how many pages each section
+    /// produced, so the rendered PDF can be split per section.
+    ///
+    /// Sections that produced no page (all slides hidden or filtered out)
+    /// are omitted. Presentations without sections report none.
+    pub(crate) fn parse_with_sections(
+        &self,
+        data: &[u8],
+        options: &ConvertOptions,
+    ) -> Result<(Document, Vec<ConvertWarning>, Vec<SectionPages>), ConvertError> {
         let mut archive = crate::parser::open_zip(data)?;
 
         // Extract metadata from docProps/core.xml
-        let metadata = crate::parser::metadata::extract_metadata_from_zip(&mut archive);
+        let mut metadata = crate::parser::metadata::extract_metadata_from_zip(&mut archive);
 
         // Read and parse presentation.xml for slide size and slide references
         let pres_xml = read_zip_entry(&mut archive, "ppt/presentation.xml")?;
-        let (slide_size, slide_rids) = parse_presentation_xml(&pres_xml)?;
+        let presentation: PresentationInfo = parse_presentation_xml(&pres_xml)?;
+        let slide_size: PageSize = presentation.slide_size;
 
         // Read and parse presentation.xml.rels for rId → slide path mapping
         let rels_xml = read_zip_entry(&mut archive, "ppt/_rels/presentation.xml.rels")?;
@@ -415,9 +535,24 @@ impl Parser for PptxParser {
             load_table_styles(&mut archive, &theme, &master_color_map);
 
         let mut warnings = Vec::new();
+        if let Some(ref requested) = options.slide_sections {
+            for name in requested {
+                if !presentation.section_names.contains(name) {
+                    warnings.push(ConvertWarning::ParseSkipped {
+                        format: "PPTX".to_string(),
+                        reason: format!("section \"{name}\" not found in presentation"),
+                        location: None,
+                    });
+                }
+            }
+        }
+        let mut sections: Vec<SectionPages> = Vec::new();
 
         // Parse each slide in order, skipping broken slides with warnings
+        let slide_rids: &[String] = &presentation.slide_rids;
         let mut pages = Vec::with_capacity(slide_rids.len());
+        // Slide part behind each page, for resolving slide-jump hyperlinks.
+        let mut page_slide_paths: Vec<String> = Vec::with_capacity(slide_rids.len());
         for (slide_idx, rid) in slide_rids.iter().enumerate() {
             // Filter by slide range if specified (1-indexed)
             let slide_number = (slide_idx as u32) + 1;
@@ -426,6 +561,12 @@ impl Parser for PptxParser {
             {
                 continue;
             }
+            let section: Option<&String> = presentation.slide_sections[slide_idx].as_ref();
+            if let Some(ref requested) = options.slide_sections
+                && !section.is_some_and(|name| requested.contains(name))
+            {
+                continue;
+            }
 
             if let Some(target) = rel_map.get(rid) {
                 let slide_path = if let Some(stripped) = target.strip_prefix('/') {
@@ -445,13 +586,31 @@ impl Parser for PptxParser {
                 ) {
                     // Hidden slide (show="0"): PowerPoint omits it from PDF export.
                     Ok(None) => {}
-                    Ok(Some((page, slide_warnings))) => {
-                        warnings.extend(slide_warnings);
+                    Ok(Some((mut page, slide_warnings))) => {
+                        warnings.extend(
+                            slide_warnings
+                                .into_iter()
+                                .map(|w| w.or_location(WarningLocation::Slide(slide_number))),
+                        );
+                        if let Page::Fixed(ref mut fixed_page) = page {
+                            fixed_page.source_slide = Some(slide_number);
+                        }
+                        if let Some(aspect) = options.slide_scale_to
+                            && let Page::Fixed(ref mut fixed_page) = page
+                        {
+                            rescale::rescale_slide(fixed_page, aspect, options.slide_scale_mode);
+                        }
                         // Emit structured warnings for fallback-rendered elements
                         if let Page::Fixed(ref fp) = page {
                             for elem in &fp.elements {
                                 match &elem.kind {
-                                    FixedElementKind::Chart(chart) => {
+                                    // Charts are drawn as vector plots; only
+                                    // unknown types and empty charts degrade to
+                                    // the data-table rendering.
+                                    FixedElementKind::Chart(chart)
+                                        if matches!(chart.chart_type, ChartType::Other(_))
+                                            || chart.series.is_empty() =>
+                                    {
                                         let title = chart
                                             .title
                                             .as_deref()
@@ -461,13 +620,26 @@ impl Parser for PptxParser {
                                             format: "PPTX".to_string(),
                                             from: format!("chart ({title})"),
                                             to: "data table".to_string(),
+                                            location: Some(WarningLocation::Slide(slide_number)),
                                         });
                                     }
-                                    FixedElementKind::SmartArt(_) => {
+                                    FixedElementKind::SmartArt(smartart) => {
+                                        let approximation: &str = match smartart.layout {
+                                            SmartArtLayout::List => "text list",
+                                            SmartArtLayout::Cycle => "approximate cycle diagram",
+                                            SmartArtLayout::Hierarchy => {
+                                                "approximate hierarchy diagram"
+                                            }
+                                            SmartArtLayout::Pyramid => {
+                                                "approximate pyramid diagram"
+                                            }
+                                            SmartArtLayout::Matrix => "approximate matrix diagram",
+                                        };
                                         warnings.push(ConvertWarning::FallbackUsed {
                                             format: "PPTX".to_string(),
                                             from: "SmartArt diagram".to_string(),
-                                            to: "text list".to_string(),
+                                            to: approximation.to_string(),
+                                            location: Some(WarningLocation::Slide(slide_number)),
                                         });
                                     }
                                     _ => {}
@@ -475,6 +647,16 @@ impl Parser for PptxParser {
                             }
                         }
                         pages.push(page);
+                        page_slide_paths.push(slide_path);
+                        if let Some(name) = section {
+                            match sections.last_mut() {
+                                Some(last) if last.name == *name => last.page_count += 1,
+                                _ => sections.push(SectionPages {
+                                    name: name.clone(),
+                                    page_count: 1,
+                                }),
+                            }
+                        }
                     }
                     Err(e) => {
                         warnings.push(ConvertWarning::ParseSkipped {
@@ -484,11 +666,14 @@ impl Parser for PptxParser {
                                 slide_idx + 1,
                                 slide_path
                             ),
+                            location: Some(WarningLocation::Slide(slide_number)),
                         });
                     }
                 }
             }
         }
+        resolve_slide_links(&mut pages, &page_slide_paths);
+        assign_document_language(&mut pages, &mut metadata);
 
         Ok((
             Document {
@@ -497,12 +682,14 @@ impl Parser for PptxParser {
                 styles: StyleSheet::default(),
             },
             warnings,
+            sections,
         ))
     }
 }
 
-/// Map from relationship ID → list of SmartArt nodes with hierarchy depth.
-type SmartArtMap = HashMap<String, Vec<SmartArtNode>>;
+/// Map from relationship ID → SmartArt nodes (with hierarchy depth) and the
+/// layout family named by the data model.
+type SmartArtMap = HashMap<String, SmartArt>;
 
 /// Reference to a chart found in a slide's graphicFrame.
 struct ChartRef {
@@ -102,19 +102,18 @@ fn parse_first_page(data: &[u8]) -> FixedPage {
     }
 }
 
-fn assert_full_page_image(element: &FixedElement, page: &FixedPage) {
+fn assert_full_page_image(page: &FixedPage) {
+    let background = page
+        .background_image
+        .as_ref()
+        .expect("expected a picture background on the page");
+    assert_eq!(background.tile_size, None, "picture should be stretched");
     assert!(
-        matches!(element.kind, FixedElementKind::Image(_)),
-        "expected background image element, got {:?}",
-        element.kind
-    );
-    assert!((element.x - 0.0).abs() < 0.01 && (element.y - 0.0).abs() < 0.01);
-    assert!(
-        (element.width - page.size.width).abs() < 0.01
-            && (element.height - page.size.height).abs() < 0.01,
-        "background image should cover the page: {}x{} vs page {}x{}",
-        element.width,
-        element.height,
+        background.image.width == Some(page.size.width)
+            && background.image.height == Some(page.size.height),
+        "background image should cover the page: {:?}x{:?} vs page {}x{}",
+        background.image.width,
+        background.image.height,
         page.size.width,
         page.size.height
     );
@@ -132,12 +131,16 @@ fn test_master_picture_fill_background_renders_behind_content() {
     );
 
     let page = parse_first_page(&data);
-    assert!(
-        page.elements.len() >= 2,
-        "expected background image + content, got {} elements",
-        page.elements.len()
+    assert_eq!(
+        page.elements.len(),
+        1,
+        "the background must not be added as a slide element"
     );
-    assert_full_page_image(&page.elements[0], &page);
+    assert!(matches!(
+        page.elements[0].kind,
+        FixedElementKind::TextBox(_)
+    ));
+    assert_full_page_image(&page);
 }
 
 #[test]
@@ -149,8 +152,8 @@ fn test_layout_picture_fill_background_is_inherited() {
     );
 
     let page = parse_first_page(&data);
-    assert_eq!(page.elements.len(), 1);
-    assert_full_page_image(&page.elements[0], &page);
+    assert!(page.elements.is_empty());
+    assert_full_page_image(&page);
 }
 
 #[test]
@@ -162,8 +165,8 @@ fn test_slide_picture_fill_background_renders() {
     );
 
     let page = parse_first_page(&data);
-    assert_eq!(page.elements.len(), 1);
-    assert_full_page_image(&page.elements[0], &page);
+    assert!(page.elements.is_empty());
+    assert_full_page_image(&page);
 }
 
 #[test]
@@ -178,7 +181,43 @@ fn test_slide_solid_background_wins_over_master_picture_fill() {
     let page = parse_first_page(&data);
     assert_eq!(page.background_color, Some(Color::new(255, 0, 0)));
     assert!(
-        page.elements.is_empty(),
-        "no background image element expected when the slide overrides with a solid fill"
+        page.background_image.is_none(),
+        "no background image expected when the slide overrides with a solid fill"
+    );
+}
+
+#[test]
+fn test_tiled_picture_fill_background_keeps_tile_size() {
+    // 1x1 px image at 96 DPI is 0.75pt; sx/sy scale it to 200% and 50%.
+    let tiled_bg = format!(
+        r#"<p:bg><p:bgPr><a:blipFill dpi="0" rotWithShape="1"><a:blip r:embed="{BG_IMAGE_RID}"/><a:srcRect/><a:tile tx="0" ty="0" sx="200000" sy="50000" flip="none" algn="tl"/></a:blipFill><a:effectLst/></p:bgPr></p:bg>"#
+    );
+    let data = build_test_pptx_with_bg_layers(
+        &make_slide_xml("", ""),
+        &make_layout_xml(""),
+        &make_master_xml(&tiled_bg),
+    );
+
+    let page = parse_first_page(&data);
+    let background = page.background_image.expect("tiled background");
+    let (tile_width, tile_height) = background.tile_size.expect("tile size");
+    assert!((tile_width - 1.5).abs() < 1e-9, "tile width {tile_width}");
+    assert!(
+        (tile_height - 0.375).abs() < 1e-9,
+        "tile height {tile_height}"
+    );
+}
+
+#[test]
+fn test_layout_gradient_background_wins_over_master_picture_fill() {
+    let gradient_bg = r#"<p:bg><p:bgPr><a:gradFill><a:gsLst><a:gs pos="0"><a:srgbClr val="000000"/></a:gs><a:gs pos="100000"><a:srgbClr val="FFFFFF"/></a:gs></a:gsLst><a:lin ang="0"/></a:gradFill><a:effectLst/></p:bgPr></p:bg>"#;
+    let data = build_test_pptx_with_bg_layers(
+        &make_slide_xml("", ""),
+        &make_layout_xml(gradient_bg),
+        &make_master_xml(&make_picture_fill_bg()),
     );
+
+    let page = parse_first_page(&data);
+    assert!(page.background_gradient.is_some());
+    assert!(page.background_image.is_none());
 }
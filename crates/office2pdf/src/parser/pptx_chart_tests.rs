@@ -107,6 +107,23 @@ fn test_slide_with_chart_produces_chart_element() {
     assert!((chart_elements[0].y - 144.0).abs() < 0.1);
 }
 
+#[test]
+fn test_plotted_chart_emits_no_fallback_warning() {
+    let chart_frame = make_chart_graphic_frame(914_400, 1_828_800, 5_486_400, 3_086_100, "rId5");
+    let slide_xml = make_slide_xml(&[chart_frame]);
+    let chart_xml = make_bar_chart_xml("Sales Data", &["Q1", "Q2"], &[100.0, 200.0]);
+    let data = build_test_pptx_with_chart(SLIDE_CX, SLIDE_CY, &slide_xml, "rId5", &chart_xml);
+
+    let (_doc, warnings) = PptxParser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    assert!(
+        !warnings
+            .iter()
+            .any(|warning| matches!(warning, ConvertWarning::FallbackUsed { .. })),
+        "unexpected warnings: {warnings:?}"
+    );
+}
+
 #[test]
 fn test_slide_with_chart_and_text_box() {
     let text_box = make_text_box(100_000, 100_000, 500_000, 200_000, "Title");
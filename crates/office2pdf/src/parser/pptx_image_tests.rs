@@ -633,7 +633,7 @@ fn test_unsupported_img_layer_emits_partial_warning_but_keeps_base_image() {
     assert!(
         warnings.iter().any(|warning| matches!(
             warning,
-            ConvertWarning::PartialElement { format, element, detail }
+            ConvertWarning::PartialElement { format, element, detail, .. }
                 if format == "PPTX"
                     && element.contains("slide 1")
                     && detail.contains("image layer")
@@ -668,10 +668,11 @@ fn test_wdp_only_picture_emits_unsupported_warning() {
     assert!(
         warnings.iter().any(|warning| matches!(
             warning,
-            ConvertWarning::UnsupportedElement { format, element }
+            ConvertWarning::UnsupportedElement { format, element, location }
                 if format == "PPTX"
                     && element.contains("slide 1")
                     && element.contains("image1.wdp")
+                    && *location == Some(crate::error::WarningLocation::Slide(1))
         )),
         "Expected unsupported warning for WDP-only picture, got: {warnings:?}"
     );
@@ -1069,3 +1070,116 @@ fn test_picture_outer_shadow_parsed() {
     assert!((shadow.opacity - 0.22).abs() < 0.01);
     assert!((shadow.distance - 3.0).abs() < 0.1, "38100 EMU = 3pt");
 }
+
+fn make_media_pic_xml(nv_pr_xml: &str, blip_fill_xml: &str) -> String {
+    format!(
+        r#"<p:pic><p:nvPicPr><p:cNvPr id="6" name="Media"/><p:cNvPicPr/><p:nvPr>{nv_pr_xml}</p:nvPr></p:nvPicPr><p:blipFill>{blip_fill_xml}</p:blipFill><p:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="2000000" cy="1000000"/></a:xfrm></p:spPr></p:pic>"#
+    )
+}
+
+fn test_media_relationship(rid: &str, path: &str, kind: &str) -> TestSlideImage {
+    TestSlideImage {
+        rid: rid.to_string(),
+        path: path.to_string(),
+        data: vec![0x00, 0x00, 0x00, 0x18],
+        relationship_type: Some(kind.to_string()),
+    }
+}
+
+#[test]
+fn test_video_keeps_poster_frame_with_play_overlay_and_warns() {
+    let pic = make_media_pic_xml(
+        r#"<a:videoFile r:link="rId2"/><p:extLst><p:ext uri="{DAA4B4D4-6D71-4841-9C94-3DE7FCFB9230}"><p14:media xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main" r:embed="rId4"/></p:ext></p:extLst>"#,
+        r#"<a:blip r:embed="rId3"/><a:stretch><a:fillRect/></a:stretch>"#,
+    );
+    let slide_xml = make_slide_xml(&[pic]);
+    let slide_images = vec![
+        test_media_relationship(
+            "rId2",
+            "../media/media1.mp4",
+            "http://schemas.openxmlformats.org/officeDocument/2006/relationships/video",
+        ),
+        TestSlideImage {
+            rid: "rId3".to_string(),
+            path: "../media/image1.bmp".to_string(),
+            data: make_test_bmp(),
+            relationship_type: None,
+        },
+        test_media_relationship(
+            "rId4",
+            "../media/launch-demo.mp4",
+            "http://schemas.microsoft.com/office/2007/relationships/media",
+        ),
+    ];
+    let data = build_test_pptx_with_images(SLIDE_CX, SLIDE_CY, &[(slide_xml, slide_images)]);
+    let parser = PptxParser;
+    let (doc, warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let page = first_fixed_page(&doc);
+    assert_eq!(page.elements.len(), 3, "poster, badge, and play glyph");
+    assert_eq!(get_image(&page.elements[0]).data, make_test_bmp());
+    let badge = &page.elements[1];
+    assert!(matches!(
+        &badge.kind,
+        FixedElementKind::Shape(Shape {
+            kind: ShapeKind::Ellipse,
+            ..
+        })
+    ));
+    // Badge is centered on the frame, sized from its shorter side.
+    let frame_height: f64 = emu_to_pt(1_000_000);
+    assert!((badge.width - frame_height * 0.3).abs() < 1e-6);
+    assert!((badge.x + badge.width / 2.0 - emu_to_pt(2_000_000) / 2.0).abs() < 1e-6);
+    assert!((badge.y + badge.height / 2.0 - frame_height / 2.0).abs() < 1e-6);
+    assert!(matches!(
+        &page.elements[2].kind,
+        FixedElementKind::Shape(Shape {
+            kind: ShapeKind::Polygon { .. },
+            ..
+        })
+    ));
+    assert!(
+        warnings.iter().any(|warning| matches!(
+            warning,
+            ConvertWarning::PartialElement { element, detail, .. }
+                if element.contains("video")
+                    && detail.contains("launch-demo.mp4")
+                    && detail.contains("poster frame")
+        )),
+        "Expected media warning naming the embedded file, got: {warnings:?}"
+    );
+}
+
+#[test]
+fn test_audio_without_poster_renders_placeholder() {
+    let pic = make_media_pic_xml(r#"<a:audioFile r:link="rId2"/>"#, "");
+    let slide_xml = make_slide_xml(&[pic]);
+    let slide_images = vec![test_media_relationship(
+        "rId2",
+        "../media/narration.m4a",
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/audio",
+    )];
+    let data = build_test_pptx_with_images(SLIDE_CX, SLIDE_CY, &[(slide_xml, slide_images)]);
+    let parser = PptxParser;
+    let (doc, warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let page = first_fixed_page(&doc);
+    assert_eq!(page.elements.len(), 3);
+    assert!(matches!(
+        &page.elements[0].kind,
+        FixedElementKind::Shape(Shape {
+            kind: ShapeKind::Rectangle,
+            ..
+        })
+    ));
+    assert!(
+        warnings.iter().any(|warning| matches!(
+            warning,
+            ConvertWarning::PartialElement { element, detail, .. }
+                if element.contains("audio")
+                    && detail.contains("narration.m4a")
+                    && detail.contains("placeholder")
+        )),
+        "Expected media warning naming the audio file, got: {warnings:?}"
+    );
+}
@@ -0,0 +1,102 @@
+use super::image_tests::make_test_bmp;
+use super::*;
+use std::io::{Cursor, Write};
+use zip::write::FileOptions;
+
+const INK_REL: &str = r#"<Relationship Id="rId5" Type="http://schemas.microsoft.com/office/2011/relationships/ink" Target="../ink/ink1.xml"/>"#;
+const FALLBACK_IMAGE_REL: &str = r#"<Relationship Id="rId6" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/image1.bmp"/>"#;
+
+/// Two red strokes spanning a 1000×500 unit box.
+const INK_XML: &str = r##"<inkml:ink xmlns:inkml="http://www.w3.org/2003/InkML"><inkml:definitions><inkml:context xml:id="ctx0"><inkml:inkSource xml:id="inkSrc0"><inkml:traceFormat><inkml:channel name="X" type="integer" max="32767" units="cm"/><inkml:channel name="Y" type="integer" max="32767" units="cm"/></inkml:traceFormat></inkml:inkSource></inkml:context><inkml:brush xml:id="br0"><inkml:brushProperty name="width" value="0.03528" units="cm"/><inkml:brushProperty name="color" value="#FF0000"/></inkml:brush></inkml:definitions><inkml:trace contextRef="#ctx0" brushRef="#br0">0 0, '500 '250, '500 '250</inkml:trace><inkml:trace contextRef="#ctx0" brushRef="#br0">0 500, 1000 0</inkml:trace></inkml:ink>"##;
+
+/// An ink content part as PowerPoint writes it: the vector part for readers
+/// that understand `p14`, and a rendered picture for everyone else.
+fn make_ink_alternate_content(x: i64, y: i64, cx: i64, cy: i64) -> String {
+    format!(
+        r#"<mc:AlternateContent xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006"><mc:Choice xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main" Requires="p14"><p:contentPart p14:bwMode="auto" r:id="rId5"><p14:nvContentPartPr><p14:cNvPr id="4" name="Ink 3"/><p14:cNvContentPartPr/><p14:nvPr/></p14:nvContentPartPr><p14:xfrm><a:off x="{x}" y="{y}"/><a:ext cx="{cx}" cy="{cy}"/></p14:xfrm></p:contentPart></mc:Choice><mc:Fallback><p:pic><p:nvPicPr><p:cNvPr id="4" name="Ink 3"/><p:cNvPicPr/><p:nvPr/></p:nvPicPr><p:blipFill><a:blip r:embed="rId6"/><a:stretch><a:fillRect/></a:stretch></p:blipFill><p:spPr><a:xfrm><a:off x="{x}" y="{y}"/><a:ext cx="{cx}" cy="{cy}"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></p:spPr></p:pic></mc:Fallback></mc:AlternateContent>"#
+    )
+}
+
+/// Build a one-slide PPTX carrying the ink and fallback image parts.
+fn build_ink_pptx(slide_xml: String, ink_xml: Option<&str>) -> Vec<u8> {
+    let rels: String = format!("{INK_REL}{FALLBACK_IMAGE_REL}");
+    let data: Vec<u8> = build_test_pptx_with_slide_rels(SLIDE_CX, SLIDE_CY, &[slide_xml], &[rels]);
+    let mut zip = zip::ZipWriter::new_append(Cursor::new(data)).unwrap();
+    let opts = FileOptions::default();
+    zip.start_file("ppt/media/image1.bmp", opts).unwrap();
+    zip.write_all(&make_test_bmp()).unwrap();
+    if let Some(ink_xml) = ink_xml {
+        zip.start_file("ppt/ink/ink1.xml", opts).unwrap();
+        zip.write_all(ink_xml.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+fn ink_strokes(page: &FixedPage) -> Vec<&FixedElement> {
+    page.elements
+        .iter()
+        .filter(|elem| {
+            matches!(
+                &elem.kind,
+                FixedElementKind::Shape(Shape {
+                    kind: ShapeKind::InkStroke { .. },
+                    ..
+                })
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_ink_content_part_becomes_strokes() {
+    let slide = make_slide_xml(&[make_ink_alternate_content(
+        1_270_000, 635_000, 2_540_000, 1_270_000,
+    )]);
+    let data: Vec<u8> = build_ink_pptx(slide, Some(INK_XML));
+    let parser = PptxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+    let page: &FixedPage = first_fixed_page(&doc);
+
+    let strokes: Vec<&FixedElement> = ink_strokes(page);
+    assert_eq!(strokes.len(), 2, "one stroke per trace");
+    assert_eq!(
+        (
+            strokes[0].x,
+            strokes[0].y,
+            strokes[0].width,
+            strokes[0].height
+        ),
+        (100.0, 50.0, 200.0, 100.0)
+    );
+    let shape: &Shape = get_shape(strokes[0]);
+    let ShapeKind::InkStroke { points } = &shape.kind else {
+        unreachable!();
+    };
+    assert_eq!(points.len(), 3);
+    let stroke: &BorderSide = shape.stroke.as_ref().unwrap();
+    assert_eq!(stroke.color, Color::new(255, 0, 0));
+    assert!((stroke.width - 1.0).abs() < 0.01, "width {}", stroke.width);
+    assert!(
+        !page
+            .elements
+            .iter()
+            .any(|elem| matches!(elem.kind, FixedElementKind::Image(_))),
+        "the fallback picture must not be drawn over the vector ink"
+    );
+}
+
+#[test]
+fn test_unreadable_ink_keeps_fallback_picture() {
+    let slide = make_slide_xml(&[make_ink_alternate_content(0, 0, 1_270_000, 1_270_000)]);
+    let data: Vec<u8> = build_ink_pptx(slide, None);
+    let parser = PptxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+    let page: &FixedPage = first_fixed_page(&doc);
+
+    assert!(ink_strokes(page).is_empty());
+    assert!(
+        page.elements
+            .iter()
+            .any(|elem| matches!(elem.kind, FixedElementKind::Image(_)))
+    );
+}
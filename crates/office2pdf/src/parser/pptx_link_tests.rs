@@ -0,0 +1,170 @@
+use super::*;
+use crate::config::SlideRange;
+use crate::ir::page_link;
+
+const HYPERLINK_REL: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink";
+const SLIDE_REL: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide";
+
+fn external_rel(rid: &str, url: &str) -> String {
+    format!(
+        r#"<Relationship Id="{rid}" Type="{HYPERLINK_REL}" Target="{url}" TargetMode="External"/>"#
+    )
+}
+
+fn slide_rel(rid: &str, target: &str) -> String {
+    format!(r#"<Relationship Id="{rid}" Type="{SLIDE_REL}" Target="{target}"/>"#)
+}
+
+/// Rectangle shape whose `<p:cNvPr>` carries the given `<a:hlinkClick>`.
+fn make_linked_shape(hlink_click_xml: &str) -> String {
+    format!(
+        r#"<p:sp><p:nvSpPr><p:cNvPr id="3" name="Button">{hlink_click_xml}</p:cNvPr><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="1000000" cy="500000"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom><a:solidFill><a:srgbClr val="4472C4"/></a:solidFill></p:spPr></p:sp>"#
+    )
+}
+
+fn parse_pages(slides: &[String], slide_rels: &[String], options: &ConvertOptions) -> Vec<Page> {
+    let data = build_test_pptx_with_slide_rels(SLIDE_CX, SLIDE_CY, slides, slide_rels);
+    let (doc, _warnings) = PptxParser.parse(&data, options).unwrap();
+    doc.pages
+}
+
+fn fixed_elements(page: &Page) -> &[FixedElement] {
+    match page {
+        Page::Fixed(fixed) => &fixed.elements,
+        _ => panic!("Expected FixedPage"),
+    }
+}
+
+fn first_run(elem: &FixedElement) -> &Run {
+    match &text_box_blocks(elem)[0] {
+        Block::Paragraph(paragraph) => &paragraph.runs[0],
+        other => panic!("Expected Paragraph, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_run_hyperlink_resolves_external_url() {
+    let text_box = make_formatted_text_box(
+        0,
+        0,
+        2_000_000,
+        500_000,
+        r#"<a:r><a:rPr lang="en-US"><a:hlinkClick r:id="rId2"/></a:rPr><a:t>Docs</a:t></a:r><a:r><a:rPr lang="en-US"/><a:t> plain</a:t></a:r>"#,
+    );
+    let pages = parse_pages(
+        &[make_slide_xml(&[text_box])],
+        &[external_rel("rId2", "https://example.com/docs")],
+        &ConvertOptions::default(),
+    );
+
+    let blocks = text_box_blocks(&fixed_elements(&pages[0])[0]);
+    let Block::Paragraph(paragraph) = &blocks[0] else {
+        panic!("Expected Paragraph");
+    };
+    assert_eq!(paragraph.runs.len(), 2);
+    assert_eq!(
+        paragraph.runs[0].href.as_deref(),
+        Some("https://example.com/docs")
+    );
+    assert_eq!(paragraph.runs[1].href, None);
+}
+
+#[test]
+fn test_shape_hyperlink_covers_the_shape() {
+    let shape = make_linked_shape(r#"<a:hlinkClick r:id="rId2"/>"#);
+    let pages = parse_pages(
+        &[make_slide_xml(&[shape])],
+        &[external_rel("rId2", "https://example.com")],
+        &ConvertOptions::default(),
+    );
+
+    let elements = fixed_elements(&pages[0]);
+    assert_eq!(elements.len(), 1);
+    assert_eq!(elements[0].href.as_deref(), Some("https://example.com"));
+}
+
+#[test]
+fn test_slide_jump_becomes_page_link() {
+    let shape =
+        make_linked_shape(r#"<a:hlinkClick r:id="rId2" action="ppaction://hlinksldjump"/>"#);
+    let pages = parse_pages(
+        &[
+            make_slide_xml(&[shape]),
+            make_slide_xml(&[]),
+            make_slide_xml(&[]),
+        ],
+        &[slide_rel("rId2", "slide3.xml")],
+        &ConvertOptions::default(),
+    );
+
+    assert_eq!(
+        fixed_elements(&pages[0])[0].href.as_deref(),
+        Some(page_link(3).as_str())
+    );
+}
+
+#[test]
+fn test_show_jump_actions_are_relative_to_the_page() {
+    let next = make_formatted_text_box(
+        0,
+        0,
+        2_000_000,
+        500_000,
+        r#"<a:r><a:rPr lang="en-US"><a:hlinkClick r:id="" action="ppaction://hlinkshowjump?jump=nextslide"/></a:rPr><a:t>Next</a:t></a:r>"#,
+    );
+    let previous = make_formatted_text_box(
+        0,
+        0,
+        2_000_000,
+        500_000,
+        r#"<a:r><a:rPr lang="en-US"><a:hlinkClick r:id="" action="ppaction://hlinkshowjump?jump=previousslide"/></a:rPr><a:t>Back</a:t></a:r>"#,
+    );
+    let pages = parse_pages(
+        &[
+            make_slide_xml(&[next.clone()]),
+            make_slide_xml(&[previous, next]),
+        ],
+        &[],
+        &ConvertOptions::default(),
+    );
+
+    let first_page = fixed_elements(&pages[0]);
+    assert_eq!(
+        first_run(&first_page[0]).href.as_deref(),
+        Some(page_link(2).as_str())
+    );
+    let second_page = fixed_elements(&pages[1]);
+    assert_eq!(
+        first_run(&second_page[0]).href.as_deref(),
+        Some(page_link(1).as_str())
+    );
+    // "Next" on the last slide has nowhere to go.
+    assert_eq!(first_run(&second_page[1]).href, None);
+}
+
+#[test]
+fn test_jump_to_slide_outside_range_is_dropped() {
+    let shape =
+        make_linked_shape(r#"<a:hlinkClick r:id="rId2" action="ppaction://hlinksldjump"/>"#);
+    let options = ConvertOptions {
+        slide_range: Some(SlideRange::new(1, 1)),
+        ..ConvertOptions::default()
+    };
+    let pages = parse_pages(
+        &[make_slide_xml(&[shape]), make_slide_xml(&[])],
+        &[slide_rel("rId2", "slide2.xml")],
+        &options,
+    );
+
+    assert_eq!(pages.len(), 1);
+    assert_eq!(fixed_elements(&pages[0])[0].href, None);
+}
+
+#[test]
+fn test_macro_action_has_no_link() {
+    let shape = make_linked_shape(r#"<a:hlinkClick r:id="" action="ppaction://macro?name=Run"/>"#);
+    let pages = parse_pages(&[make_slide_xml(&[shape])], &[], &ConvertOptions::default());
+
+    assert_eq!(fixed_elements(&pages[0])[0].href, None);
+}
@@ -0,0 +1,144 @@
+use crate::ir::page_link;
+
+use super::package::{parse_relationships_xml, rels_path_for, resolve_relative_path};
+use super::*;
+
+/// Href marker for a jump to another slide part, resolved to a page link by
+/// [`resolve_slide_links`] once every slide has been assigned a page.
+const SLIDE_TARGET_PREFIX: &str = "pptx-slide:";
+
+/// Href marker for a relative `ppaction://hlinkshowjump` (next, previous,
+/// first, or last slide), resolved by [`resolve_slide_links`].
+const SHOW_JUMP_PREFIX: &str = "pptx-show:";
+
+const SHOW_JUMP_ACTION_PREFIX: &str = "ppaction://hlinkshowjump?jump=";
+
+/// Map from relationship ID → hyperlink target of a slide: an external URL or
+/// a slide-part marker.
+pub(super) type SlideHyperlinkMap = HashMap<String, String>;
+
+/// Collect the hyperlink and slide relationships of a slide that
+/// `<a:hlinkClick r:id>` can point at.
+pub(super) fn load_slide_hyperlinks<R: Read + std::io::Seek>(
+    slide_path: &str,
+    archive: &mut ZipArchive<R>,
+) -> SlideHyperlinkMap {
+    let Ok(rels_xml) = read_zip_entry(archive, &rels_path_for(slide_path)) else {
+        return SlideHyperlinkMap::new();
+    };
+    let slide_dir: &str = slide_path
+        .rsplit_once('/')
+        .map(|(dir, _)| dir)
+        .unwrap_or("");
+    parse_relationships_xml(&rels_xml)
+        .into_iter()
+        .filter_map(|(id, rel)| {
+            let rel_type: String = rel.rel_type?.to_ascii_lowercase();
+            if rel_type.ends_with("/hyperlink") {
+                Some((id, rel.target))
+            } else if rel_type.ends_with("/slide") {
+                let target_path: String = resolve_relative_path(slide_dir, &rel.target);
+                Some((id, format!("{SLIDE_TARGET_PREFIX}{target_path}")))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Href for an `<a:hlinkClick>` element, or `None` when the action has no
+/// PDF equivalent (macros, programs, custom shows, end of show).
+pub(super) fn resolve_hlink_click(
+    e: &BytesStart<'_>,
+    hyperlinks: &SlideHyperlinkMap,
+) -> Option<String> {
+    let action: String = get_attr_str(e, b"action").unwrap_or_default();
+    if let Some(jump) = action.strip_prefix(SHOW_JUMP_ACTION_PREFIX) {
+        return Some(format!("{SHOW_JUMP_PREFIX}{jump}"));
+    }
+    match action.as_str() {
+        "" | "ppaction://hlinksldjump" | "ppaction://hlinkfile" => {}
+        _ => return None,
+    }
+    let rid: String = get_attr_str(e, b"r:id")?;
+    hyperlinks.get(&rid).cloned()
+}
+
+/// Rewrite slide markers in `pages` into page links. `page_slide_paths[i]` is
+/// the slide part rendered as page `i`; jumps to slides that produced no page
+/// (hidden, filtered out, or failed to parse) are dropped.
+pub(super) fn resolve_slide_links(pages: &mut [Page], page_slide_paths: &[String]) {
+    let page_count: usize = pages.len();
+    for (page_index, page) in pages.iter_mut().enumerate() {
+        let Page::Fixed(fixed) = page else {
+            continue;
+        };
+        let mut resolve = |href: &mut Option<String>| {
+            if let Some(target) = href.as_deref() {
+                *href = resolve_slide_href(target, page_index, page_count, page_slide_paths);
+            }
+        };
+        for element in &mut fixed.elements {
+            resolve(&mut element.href);
+            match &mut element.kind {
+                FixedElementKind::TextBox(text_box) => {
+                    for_each_run_href(&mut text_box.content, &mut resolve);
+                }
+                FixedElementKind::Table(table) => for_each_table_run_href(table, &mut resolve),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn resolve_slide_href(
+    href: &str,
+    page_index: usize,
+    page_count: usize,
+    page_slide_paths: &[String],
+) -> Option<String> {
+    if let Some(slide_path) = href.strip_prefix(SLIDE_TARGET_PREFIX) {
+        let target_index: usize = page_slide_paths
+            .iter()
+            .position(|path| path == slide_path)?;
+        return Some(page_link(target_index + 1));
+    }
+    let Some(jump) = href.strip_prefix(SHOW_JUMP_PREFIX) else {
+        return Some(href.to_string());
+    };
+    let target_page: usize = match jump {
+        "nextslide" if page_index + 1 < page_count => page_index + 2,
+        "previousslide" if page_index > 0 => page_index,
+        "firstslide" => 1,
+        "lastslide" => page_count,
+        _ => return None,
+    };
+    Some(page_link(target_page))
+}
+
+fn for_each_run_href(blocks: &mut [Block], visit: &mut impl FnMut(&mut Option<String>)) {
+    for block in blocks {
+        match block {
+            Block::Paragraph(paragraph) => {
+                for run in &mut paragraph.runs {
+                    visit(&mut run.href);
+                }
+            }
+            Block::List(list) => {
+                for paragraph in list.items.iter_mut().flat_map(|item| &mut item.content) {
+                    for run in &mut paragraph.runs {
+                        visit(&mut run.href);
+                    }
+                }
+            }
+            Block::Table(table) => for_each_table_run_href(table, visit),
+            _ => {}
+        }
+    }
+}
+
+fn for_each_table_run_href(table: &mut Table, visit: &mut impl FnMut(&mut Option<String>)) {
+    for cell in table.rows.iter_mut().flat_map(|row| &mut row.cells) {
+        for_each_run_href(&mut cell.content, visit);
+    }
+}
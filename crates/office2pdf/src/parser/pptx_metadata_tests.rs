@@ -85,3 +85,28 @@ fn test_parse_pptx_without_metadata_no_crash() {
     assert!(doc.metadata.title.is_none());
     assert!(doc.metadata.author.is_none());
 }
+
+#[test]
+fn test_parse_pptx_takes_document_language_from_runs() {
+    let slide = make_slide_xml(&[
+        make_formatted_text_box(
+            0,
+            0,
+            4572000,
+            914400,
+            r#"<a:r><a:rPr lang="en-US"/><a:t>Quarterly revenue grew</a:t></a:r><a:r><a:rPr lang="ko-KR"/><a:t>매출</a:t></a:r>"#,
+        ),
+        make_text_box(0, 1828800, 4572000, 914400, "Outlook"),
+    ]);
+    let data = build_test_pptx(9144000, 6858000, &[slide]);
+    let (doc, _warnings) = PptxParser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    assert_eq!(doc.metadata.language.as_deref(), Some("en-US"));
+    let page = first_fixed_page(&doc);
+    let Block::Paragraph(paragraph) = &text_box_blocks(&page.elements[0])[0] else {
+        panic!("Expected paragraph");
+    };
+    // Only the run in another language keeps its tag.
+    assert_eq!(paragraph.runs[0].style.language, None);
+    assert_eq!(paragraph.runs[1].style.language.as_deref(), Some("ko-KR"));
+}
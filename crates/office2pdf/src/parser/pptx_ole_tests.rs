@@ -0,0 +1,88 @@
+use super::image_tests::make_test_bmp;
+use super::*;
+use std::io::{Cursor, Write};
+use zip::write::FileOptions;
+
+const WORKBOOK_REL: &str = r#"<Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/package" Target="../embeddings/Microsoft_Excel_Worksheet.xlsx"/>"#;
+const PREVIEW_IMAGE_REL: &str = r#"<Relationship Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/image1.bmp"/>"#;
+
+/// An embedded worksheet as PowerPoint writes it: a VML-backed choice without
+/// a picture, and a fallback `<p:oleObj>` carrying the cached preview.
+fn make_ole_graphic_frame(x: i64, y: i64, cx: i64, cy: i64) -> String {
+    format!(
+        r#"<p:graphicFrame><p:nvGraphicFramePr><p:cNvPr id="4" name="Object 3"/><p:cNvGraphicFramePr><a:graphicFrameLocks noChangeAspect="1"/></p:cNvGraphicFramePr><p:nvPr/></p:nvGraphicFramePr><p:xfrm><a:off x="{x}" y="{y}"/><a:ext cx="{cx}" cy="{cy}"/></p:xfrm><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/presentationml/2006/ole"><mc:AlternateContent xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006"><mc:Choice xmlns:v="urn:schemas-microsoft-com:vml" Requires="v"><p:oleObj spid="_x0000_s1026" name="Worksheet" r:id="rId3" imgW="1828800" imgH="914400" progId="Excel.Sheet.12"><p:embed/></p:oleObj></mc:Choice><mc:Fallback><p:oleObj name="Worksheet" r:id="rId3" imgW="1828800" imgH="914400" progId="Excel.Sheet.12"><p:embed/><p:pic><p:nvPicPr><p:cNvPr id="0" name=""/><p:cNvPicPr/><p:nvPr/></p:nvPicPr><p:blipFill><a:blip r:embed="rId4"/><a:stretch><a:fillRect/></a:stretch></p:blipFill><p:spPr><a:xfrm><a:off x="{x}" y="{y}"/><a:ext cx="{cx}" cy="{cy}"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></p:spPr></p:pic></p:oleObj></mc:Fallback></mc:AlternateContent></a:graphicData></a:graphic></p:graphicFrame>"#
+    )
+}
+
+fn make_workbook(cells: &[(&str, &str)]) -> Vec<u8> {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        for &(coord, value) in cells {
+            sheet.get_cell_mut(coord).set_value(value);
+        }
+    }
+    let mut cursor = Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    cursor.into_inner()
+}
+
+/// Build a one-slide PPTX carrying the embedded package and preview picture.
+fn build_ole_pptx(slide_xml: String, workbook: &[u8]) -> Vec<u8> {
+    let rels: String = format!("{WORKBOOK_REL}{PREVIEW_IMAGE_REL}");
+    let data: Vec<u8> = build_test_pptx_with_slide_rels(SLIDE_CX, SLIDE_CY, &[slide_xml], &[rels]);
+    let mut zip = zip::ZipWriter::new_append(Cursor::new(data)).unwrap();
+    let opts = FileOptions::default();
+    zip.start_file("ppt/media/image1.bmp", opts).unwrap();
+    zip.write_all(&make_test_bmp()).unwrap();
+    zip.start_file("ppt/embeddings/Microsoft_Excel_Worksheet.xlsx", opts)
+        .unwrap();
+    zip.write_all(workbook).unwrap();
+    zip.finish().unwrap().into_inner()
+}
+
+#[test]
+fn test_embedded_worksheet_renders_as_table_in_frame() {
+    let slide = make_slide_xml(&[make_ole_graphic_frame(
+        1_270_000, 635_000, 2_540_000, 1_270_000,
+    )]);
+    let workbook: Vec<u8> = make_workbook(&[("A1", "Q1"), ("B1", "Q2"), ("A2", "10")]);
+    let data: Vec<u8> = build_ole_pptx(slide, &workbook);
+    let parser = PptxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+    let page: &FixedPage = first_fixed_page(&doc);
+
+    assert_eq!(page.elements.len(), 1, "table only, no preview picture");
+    let elem: &FixedElement = &page.elements[0];
+    assert_eq!(
+        (elem.x, elem.y, elem.width, elem.height),
+        (100.0, 50.0, 200.0, 100.0)
+    );
+    let FixedElementKind::Table(table) = &elem.kind else {
+        panic!("expected a table, got {:?}", elem.kind);
+    };
+    assert_eq!(table.rows.len(), 2);
+    let width: f64 = table.column_widths.iter().sum();
+    assert!((width - 200.0).abs() < 1e-6, "width {width}");
+    let height: f64 = table.rows.iter().filter_map(|row| row.height).sum();
+    assert!((height - 100.0).abs() < 1e-6, "height {height}");
+}
+
+#[test]
+fn test_unreadable_embedded_worksheet_keeps_preview_picture() {
+    let slide = make_slide_xml(&[make_ole_graphic_frame(
+        1_270_000, 635_000, 2_540_000, 1_270_000,
+    )]);
+    let data: Vec<u8> = build_ole_pptx(slide, b"not a workbook");
+    let parser = PptxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+    let page: &FixedPage = first_fixed_page(&doc);
+
+    assert_eq!(page.elements.len(), 1);
+    let elem: &FixedElement = &page.elements[0];
+    assert!(matches!(elem.kind, FixedElementKind::Image(_)));
+    assert_eq!(
+        (elem.x, elem.y, elem.width, elem.height),
+        (100.0, 50.0, 200.0, 100.0)
+    );
+}
@@ -0,0 +1,109 @@
+//! The slide list of a presentation for `info`-style summaries: each
+//! slide's title, section and visibility, read without parsing the slides'
+//! layouts, themes or shapes.
+
+use crate::error::SlideInfo;
+
+use super::package::{parse_presentation_xml, parse_rels_xml, read_zip_entry};
+use super::slides::is_hidden_slide;
+use super::*;
+
+/// List the slides of a presentation in order, hidden ones included.
+///
+/// A slide that cannot be read is listed without a title.
+pub(crate) fn read_slide_outline(data: &[u8]) -> Result<Vec<SlideInfo>, ConvertError> {
+    let mut archive = crate::parser::open_zip(data)?;
+    let pres_xml = read_zip_entry(&mut archive, "ppt/presentation.xml")?;
+    let presentation: PresentationInfo = parse_presentation_xml(&pres_xml)?;
+    let rels_xml = read_zip_entry(&mut archive, "ppt/_rels/presentation.xml.rels")?;
+    let rel_map = parse_rels_xml(&rels_xml);
+
+    let mut slides: Vec<SlideInfo> = Vec::with_capacity(presentation.slide_rids.len());
+    for (slide_idx, rid) in presentation.slide_rids.iter().enumerate() {
+        let slide_xml: Option<String> = rel_map.get(rid).and_then(|target| {
+            let slide_path: String = match target.strip_prefix('/') {
+                Some(stripped) => stripped.to_string(),
+                None => format!("ppt/{target}"),
+            };
+            read_zip_entry(&mut archive, &slide_path).ok()
+        });
+        slides.push(SlideInfo {
+            number: slide_idx as u32 + 1,
+            title: slide_xml.as_deref().and_then(slide_title),
+            section: presentation.slide_sections[slide_idx].clone(),
+            hidden: slide_xml.as_deref().is_some_and(is_hidden_slide),
+        });
+    }
+    Ok(slides)
+}
+
+/// The text of the slide's title placeholder (`<p:ph type="title">` or
+/// `"ctrTitle"`), its paragraphs and line breaks joined by spaces.
+fn slide_title(slide_xml: &str) -> Option<String> {
+    let mut reader: Reader<&[u8]> = Reader::from_str(slide_xml);
+    // One entry per open `<p:sp>`: whether it is a title placeholder, and
+    // its text so far. Shapes nest inside group shapes.
+    let mut shapes: Vec<(bool, String)> = Vec::new();
+    let mut in_text: bool = false;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                b"sp" => shapes.push((false, String::new())),
+                b"ph" => mark_title_placeholder(&mut shapes, e),
+                b"t" => in_text = true,
+                _ => {}
+            },
+            Ok(Event::Empty(ref e)) => match e.local_name().as_ref() {
+                b"ph" => mark_title_placeholder(&mut shapes, e),
+                b"br" => push_word_break(&mut shapes),
+                _ => {}
+            },
+            Ok(Event::Text(ref e)) if in_text => {
+                if let Some((_, text)) = shapes.last_mut()
+                    && let Some(content) = decode_pptx_text_event(e)
+                {
+                    text.push_str(&content);
+                }
+            }
+            Ok(Event::GeneralRef(ref e)) if in_text => {
+                if let Some((_, text)) = shapes.last_mut()
+                    && let Some(content) = decode_pptx_general_ref(e)
+                {
+                    text.push_str(&content);
+                }
+            }
+            Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"p" => push_word_break(&mut shapes),
+                b"sp" => {
+                    if let Some((true, text)) = shapes.pop() {
+                        let title: String = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                        if !title.is_empty() {
+                            return Some(title);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
+        }
+    }
+}
+
+fn mark_title_placeholder(shapes: &mut [(bool, String)], e: &BytesStart) {
+    if let Some((is_title, _)) = shapes.last_mut()
+        && matches!(
+            get_attr_str(e, b"type").as_deref(),
+            Some("title" | "ctrTitle")
+        )
+    {
+        *is_title = true;
+    }
+}
+
+fn push_word_break(shapes: &mut [(bool, String)]) {
+    if let Some((_, text)) = shapes.last_mut() {
+        text.push(' ');
+    }
+}
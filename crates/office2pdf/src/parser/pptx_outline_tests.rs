@@ -0,0 +1,51 @@
+use super::*;
+
+/// Title placeholder shape whose text body holds `paragraphs_xml`.
+fn make_title_sp(ph_type: &str, paragraphs_xml: &str) -> String {
+    format!(
+        r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="Title 1"/><p:cNvSpPr/><p:nvPr><p:ph type="{ph_type}"/></p:nvPr></p:nvSpPr><p:spPr/><p:txBody><a:bodyPr/>{paragraphs_xml}</p:txBody></p:sp>"#
+    )
+}
+
+#[test]
+fn test_slide_outline_reads_titles_sections_and_hidden_slides() {
+    let hidden_slide: String = make_slide_xml(&[make_title_sp(
+        "title",
+        "<a:p><a:r><a:t>Backup</a:t></a:r></a:p>",
+    )])
+    .replacen("<p:sld ", r#"<p:sld show="0" "#, 1);
+    let slides: Vec<String> = vec![
+        make_slide_xml(&[
+            make_text_box(0, 0, 1_000_000, 500_000, "Not a title"),
+            make_title_sp(
+                "ctrTitle",
+                "<a:p><a:r><a:t>Q3 </a:t></a:r><a:r><a:t>R&amp;D</a:t></a:r><a:br/><a:r><a:t>review</a:t></a:r></a:p><a:p><a:r><a:t>2024</a:t></a:r></a:p>",
+            ),
+        ]),
+        make_slide_xml(&[make_text_box(0, 0, 1_000_000, 500_000, "Body only")]),
+        hidden_slide,
+    ];
+    let data: Vec<u8> = build_test_pptx_with_sections(
+        SLIDE_CX,
+        SLIDE_CY,
+        &slides,
+        &[("Intro", 2), ("Appendix", 1)],
+    );
+
+    let outline: Vec<crate::error::SlideInfo> = read_slide_outline(&data).unwrap();
+
+    let titles: Vec<Option<&str>> = outline.iter().map(|s| s.title.as_deref()).collect();
+    assert_eq!(
+        titles,
+        vec![Some("Q3 R&D review 2024"), None, Some("Backup")]
+    );
+    let numbers: Vec<u32> = outline.iter().map(|s| s.number).collect();
+    assert_eq!(numbers, vec![1, 2, 3]);
+    let sections: Vec<Option<&str>> = outline.iter().map(|s| s.section.as_deref()).collect();
+    assert_eq!(
+        sections,
+        vec![Some("Intro"), Some("Intro"), Some("Appendix")]
+    );
+    let hidden: Vec<bool> = outline.iter().map(|s| s.hidden).collect();
+    assert_eq!(hidden, vec![false, false, true]);
+}
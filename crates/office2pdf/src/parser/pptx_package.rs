@@ -118,6 +118,106 @@ pub(super) fn load_slide_images<R: Read + std::io::Seek>(
     images
 }
 
+/// Collect the video/audio relationships of a slide. Only the file names are
+/// kept: media cannot play in a PDF, but warnings should name what was lost.
+pub(super) fn load_slide_media<R: Read + std::io::Seek>(
+    slide_path: &str,
+    archive: &mut ZipArchive<R>,
+) -> SlideMediaMap {
+    let Ok(rels_xml) = read_zip_entry(archive, &rels_path_for(slide_path)) else {
+        return SlideMediaMap::new();
+    };
+    parse_relationships_xml(&rels_xml)
+        .into_iter()
+        .filter(|(_, rel)| is_media_relationship(rel.rel_type.as_deref()))
+        .map(|(id, rel)| {
+            let file_name: String = rel
+                .target
+                .rsplit(['/', '\\'])
+                .next()
+                .unwrap_or(rel.target.as_str())
+                .to_string();
+            (id, file_name)
+        })
+        .collect()
+}
+
+/// Pre-load the InkML drawings referenced by a slide's `<p:contentPart>`s.
+pub(super) fn load_slide_ink<R: Read + std::io::Seek>(
+    slide_path: &str,
+    archive: &mut ZipArchive<R>,
+) -> SlideInkMap {
+    let Ok(rels_xml) = read_zip_entry(archive, &rels_path_for(slide_path)) else {
+        return SlideInkMap::new();
+    };
+    let slide_dir: &str = slide_path
+        .rsplit_once('/')
+        .map(|(dir, _)| dir)
+        .unwrap_or("");
+    let mut ink = SlideInkMap::new();
+    for (id, rel) in parse_relationships_xml(&rels_xml) {
+        if !rel
+            .rel_type
+            .as_deref()
+            .is_some_and(|rel_type| rel_type.ends_with("/ink"))
+        {
+            continue;
+        }
+        let ink_path: String = match rel.target.strip_prefix('/') {
+            Some(stripped) => stripped.to_string(),
+            None => resolve_relative_path(slide_dir, &rel.target),
+        };
+        if let Ok(xml) = read_zip_entry(archive, &ink_path)
+            && let Some(drawing) = inkml::parse_inkml(&xml)
+        {
+            ink.insert(id, drawing);
+        }
+    }
+    ink
+}
+
+/// Pre-load the embedded Excel workbooks behind a slide's `<p:oleObj>`s.
+/// Only OOXML packages are read; legacy binary `oleObject*.bin` payloads keep
+/// their cached preview picture.
+pub(super) fn load_slide_workbooks<R: Read + std::io::Seek>(
+    slide_path: &str,
+    archive: &mut ZipArchive<R>,
+) -> SlideWorkbookMap {
+    let Ok(rels_xml) = read_zip_entry(archive, &rels_path_for(slide_path)) else {
+        return SlideWorkbookMap::new();
+    };
+    let slide_dir: &str = slide_path
+        .rsplit_once('/')
+        .map(|(dir, _)| dir)
+        .unwrap_or("");
+    let mut workbooks = SlideWorkbookMap::new();
+    for (id, rel) in parse_relationships_xml(&rels_xml) {
+        let target: String = rel.target.to_ascii_lowercase();
+        let is_workbook_package: bool = rel
+            .rel_type
+            .as_deref()
+            .is_some_and(|rel_type| rel_type.ends_with("/package"))
+            && (target.ends_with(".xlsx") || target.ends_with(".xlsm"));
+        if !is_workbook_package {
+            continue;
+        }
+        let package_path: String = match rel.target.strip_prefix('/') {
+            Some(stripped) => stripped.to_string(),
+            None => resolve_relative_path(slide_dir, &rel.target),
+        };
+        let Ok(mut file) = archive.by_name(&package_path) else {
+            continue;
+        };
+        let mut data: Vec<u8> = Vec::new();
+        if file.read_to_end(&mut data).is_ok()
+            && let Some(table) = crate::parser::xlsx::parse_embedded_preview(&data)
+        {
+            workbooks.insert(id, table);
+        }
+    }
+    workbooks
+}
+
 /// Pre-load SmartArt diagram data for a slide by scanning its .rels file.
 pub(super) fn load_smartart_data<R: Read + std::io::Seek>(
     slide_path: &str,
@@ -147,7 +247,16 @@ pub(super) fn load_smartart_data<R: Read + std::io::Seek>(
         if let Ok(data_xml) = read_zip_entry(archive, &data_path) {
             let texts: Vec<SmartArtNode> = smartart::parse_smartart_data_xml(&data_xml);
             if !texts.is_empty() {
-                map.insert(id.clone(), texts);
+                let layout: SmartArtLayout = smartart::parse_data_layout_type_id(&data_xml)
+                    .map(|type_id| smartart::classify_layout(&type_id, &[]))
+                    .unwrap_or_default();
+                map.insert(
+                    id.clone(),
+                    SmartArt {
+                        items: texts,
+                        layout,
+                    },
+                );
             }
         }
     }
@@ -155,6 +264,30 @@ pub(super) fn load_smartart_data<R: Read + std::io::Seek>(
     map
 }
 
+/// Layout family of the SmartArt layout definition a slide relationship
+/// points at, or `None` when the part is missing.
+pub(super) fn load_smartart_layout<R: Read + std::io::Seek>(
+    slide_path: &str,
+    archive: &mut ZipArchive<R>,
+    layout_rid: &str,
+) -> Option<SmartArtLayout> {
+    let rels_xml: String = read_zip_entry(archive, &rels_path_for(slide_path)).ok()?;
+    let rels: HashMap<String, String> = parse_rels_xml(&rels_xml);
+    let target: &str = rels.get(layout_rid)?;
+    let layout_path: String = match target.strip_prefix('/') {
+        Some(stripped) => stripped.to_string(),
+        None => {
+            let slide_dir: &str = slide_path
+                .rsplit_once('/')
+                .map(|(dir, _)| dir)
+                .unwrap_or("");
+            resolve_relative_path(slide_dir, target)
+        }
+    };
+    let layout_xml: String = read_zip_entry(archive, &layout_path).ok()?;
+    Some(smartart::parse_layout_def_xml(&layout_xml))
+}
+
 /// Scan slide XML for chart references within graphicFrame elements.
 pub(super) fn scan_chart_refs(slide_xml: &str) -> Vec<ChartRef> {
     let mut refs = Vec::new();
@@ -267,22 +400,14 @@ pub(super) fn load_chart_data<R: Read + std::io::Seek>(
 }
 
 /// Parse presentation.xml to extract slide size and ordered slide relationship IDs.
-pub(super) fn parse_presentation_xml(xml: &str) -> Result<(PageSize, Vec<String>), ConvertError> {
+pub(super) fn parse_presentation_xml(xml: &str) -> Result<PresentationInfo, ConvertError> {
     let mut reader = Reader::from_str(xml);
-    let mut slide_size = PageSize {
-        width: 720.0,
-        height: 540.0,
-    };
-    let mut slide_rids = Vec::new();
+    let mut state = PresentationXmlState::default();
 
     loop {
         match reader.read_event() {
-            Ok(Event::Empty(ref element)) => {
-                handle_presentation_element(element, &mut slide_size, &mut slide_rids);
-            }
-            Ok(Event::Start(ref element)) => {
-                handle_presentation_element(element, &mut slide_size, &mut slide_rids);
-            }
+            Ok(Event::Empty(ref element)) => state.handle_element(element),
+            Ok(Event::Start(ref element)) => state.handle_element(element),
             Ok(Event::Eof) => break,
             Err(error) => {
                 return Err(crate::parser::parse_err(format!(
@@ -293,33 +418,86 @@ pub(super) fn parse_presentation_xml(xml: &str) -> Result<(PageSize, Vec<String>
         }
     }
 
-    Ok((slide_size, slide_rids))
+    Ok(state.finish())
+}
+
+struct PresentationXmlState {
+    slide_size: PageSize,
+    slide_rids: Vec<String>,
+    /// `<p:sldId id>` of each entry in `slide_rids`.
+    slide_ids: Vec<Option<String>>,
+    section_names: Vec<String>,
+    /// Slide ID → index into `section_names`.
+    section_by_slide_id: HashMap<String, usize>,
 }
 
-fn handle_presentation_element(
-    element: &quick_xml::events::BytesStart,
-    slide_size: &mut PageSize,
-    slide_rids: &mut Vec<String>,
-) {
-    match element.local_name().as_ref() {
-        b"sldSz" => {
-            let cx: i64 = get_attr_i64(element, b"cx").unwrap_or(9_144_000);
-            let cy: i64 = get_attr_i64(element, b"cy").unwrap_or(6_858_000);
-            *slide_size = PageSize {
-                width: emu_to_pt(cx),
-                height: emu_to_pt(cy),
-            };
+impl Default for PresentationXmlState {
+    fn default() -> Self {
+        Self {
+            slide_size: PageSize {
+                width: 720.0,
+                height: 540.0,
+            },
+            slide_rids: Vec::new(),
+            slide_ids: Vec::new(),
+            section_names: Vec::new(),
+            section_by_slide_id: HashMap::new(),
         }
-        b"sldId" => {
-            if let Some(rid) = get_attr_str(element, b"r:id") {
-                slide_rids.push(rid);
+    }
+}
+
+impl PresentationXmlState {
+    fn handle_element(&mut self, element: &quick_xml::events::BytesStart) {
+        match element.local_name().as_ref() {
+            b"sldSz" => {
+                let cx: i64 = get_attr_i64(element, b"cx").unwrap_or(9_144_000);
+                let cy: i64 = get_attr_i64(element, b"cy").unwrap_or(6_858_000);
+                self.slide_size = PageSize {
+                    width: emu_to_pt(cx),
+                    height: emu_to_pt(cy),
+                };
             }
+            // `<p14:section>` inside the `<p:extLst>` that follows `<p:sldIdLst>`.
+            b"section" => {
+                self.section_names
+                    .push(get_attr_str(element, b"name").unwrap_or_default());
+            }
+            b"sldId" => {
+                if let Some(rid) = get_attr_str(element, b"r:id") {
+                    self.slide_rids.push(rid);
+                    self.slide_ids.push(get_attr_str(element, b"id"));
+                } else if !self.section_names.is_empty()
+                    && let Some(id) = get_attr_str(element, b"id")
+                {
+                    // Section membership: `<p14:sldId id>` without `r:id`.
+                    self.section_by_slide_id
+                        .insert(id, self.section_names.len() - 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(self) -> PresentationInfo {
+        let slide_sections: Vec<Option<String>> = self
+            .slide_ids
+            .iter()
+            .map(|id| {
+                id.as_ref()
+                    .and_then(|id| self.section_by_slide_id.get(id))
+                    .map(|index| self.section_names[*index].clone())
+            })
+            .collect();
+        PresentationInfo {
+            slide_size: self.slide_size,
+            slide_rids: self.slide_rids,
+            slide_sections,
+            section_names: self.section_names,
         }
-        _ => {}
     }
 }
 
-fn parse_relationships_xml(xml: &str) -> HashMap<String, Relationship> {
+pub(super) fn parse_relationships_xml(xml: &str) -> HashMap<String, Relationship> {
     crate::parser::xml_util::parse_relationships(xml)
         .into_iter()
         .map(|entry| {
@@ -417,6 +595,15 @@ fn normalize_slide_image_asset(target: &str, data: Vec<u8>) -> (Vec<u8>, SlideIm
     (data, SlideImageSource::Unsupported)
 }
 
+/// `video`/`audio` relationships (ECMA-376) and the Office 2010 `media`
+/// relationship that points at the embedded copy of the same file.
+fn is_media_relationship(rel_type: Option<&str>) -> bool {
+    rel_type.is_some_and(|rel_type| {
+        let lower: String = rel_type.to_ascii_lowercase();
+        lower.ends_with("/video") || lower.ends_with("/audio") || lower.ends_with("/media")
+    })
+}
+
 fn is_image_relationship(rel_type: Option<&str>, target: &str) -> bool {
     target.to_ascii_lowercase().ends_with(".emf")
         || image_format_from_ext(target).is_some()
@@ -17,6 +17,16 @@ fn make_placeholder_sp(
     ph_attrs: &str,
     xfrm_emu: Option<(i64, i64, i64, i64)>,
     text: &str,
+) -> String {
+    make_placeholder_sp_with_body_pr(ph_attrs, xfrm_emu, "<a:bodyPr/>", text)
+}
+
+/// Like [`make_placeholder_sp`], with a caller-supplied `<a:bodyPr>` element.
+fn make_placeholder_sp_with_body_pr(
+    ph_attrs: &str,
+    xfrm_emu: Option<(i64, i64, i64, i64)>,
+    body_pr: &str,
+    text: &str,
 ) -> String {
     let sp_pr: String = match xfrm_emu {
         Some((x, y, cx, cy)) => format!(
@@ -25,7 +35,7 @@ fn make_placeholder_sp(
         None => "<p:spPr/>".to_string(),
     };
     format!(
-        r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="Placeholder"/><p:cNvSpPr><a:spLocks noGrp="1"/></p:cNvSpPr><p:nvPr><p:ph {ph_attrs}/></p:nvPr></p:nvSpPr>{sp_pr}<p:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:rPr lang="en-US"/><a:t>{text}</a:t></a:r></a:p></p:txBody></p:sp>"#
+        r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="Placeholder"/><p:cNvSpPr><a:spLocks noGrp="1"/></p:cNvSpPr><p:nvPr><p:ph {ph_attrs}/></p:nvPr></p:nvSpPr>{sp_pr}<p:txBody>{body_pr}<a:lstStyle/><a:p><a:r><a:rPr lang="en-US"/><a:t>{text}</a:t></a:r></a:p></p:txBody></p:sp>"#
     )
 }
 
@@ -196,6 +206,76 @@ fn test_layout_placeholder_without_geometry_falls_back_to_master() {
     assert_geometry(element, 457_200, 1_600_200, 8_229_600, 4_525_963);
 }
 
+#[test]
+fn test_title_placeholder_inherits_master_anchor_and_insets() {
+    let slide = make_slide_with_shapes(&[make_placeholder_sp(r#"type="title""#, None, "Hello")]);
+    let layout = make_layout_with_shapes(&[make_placeholder_sp(r#"type="title""#, None, "")]);
+    let master = make_master_with_shapes(&[make_placeholder_sp_with_body_pr(
+        r#"type="title""#,
+        Some((457_200, 274_638, 8_229_600, 1_143_000)),
+        r#"<a:bodyPr vert="horz" lIns="0" tIns="45720" rIns="0" bIns="45720" anchor="ctr"/>"#,
+        "Master title",
+    )]);
+    let data = build_test_pptx_with_layout_master(SLIDE_CX, SLIDE_CY, &slide, &layout, &master);
+
+    let doc = parse_document(&data);
+    let page = first_fixed_page(&doc);
+    let FixedElementKind::TextBox(text_box) = &find_text_box_with_text(page, "Hello").kind else {
+        unreachable!();
+    };
+    assert_eq!(text_box.vertical_align, TextBoxVerticalAlign::Center);
+    assert_eq!(
+        text_box.padding,
+        Insets {
+            top: 3.6,
+            right: 0.0,
+            bottom: 3.6,
+            left: 0.0,
+        }
+    );
+}
+
+#[test]
+fn test_placeholder_body_pr_layers_override_attribute_by_attribute() {
+    // Layout changes only the anchor; the master's insets survive, and the
+    // slide's own bodyPr has the last word on the left inset.
+    let slide = make_slide_with_shapes(&[make_placeholder_sp_with_body_pr(
+        r#"type="body" idx="1""#,
+        None,
+        r#"<a:bodyPr lIns="12700"/>"#,
+        "Hello",
+    )]);
+    let layout = make_layout_with_shapes(&[make_placeholder_sp_with_body_pr(
+        r#"type="body" idx="1""#,
+        None,
+        r#"<a:bodyPr anchor="b"/>"#,
+        "Layout body",
+    )]);
+    let master = make_master_with_shapes(&[make_placeholder_sp_with_body_pr(
+        r#"type="body" idx="1""#,
+        Some((457_200, 1_600_200, 8_229_600, 4_525_963)),
+        r#"<a:bodyPr lIns="0" tIns="0" rIns="0" bIns="0" anchor="ctr"/>"#,
+        "Master body",
+    )]);
+    let data = build_test_pptx_with_layout_master(SLIDE_CX, SLIDE_CY, &slide, &layout, &master);
+
+    let doc = parse_document(&data);
+    let page = first_fixed_page(&doc);
+    let FixedElementKind::TextBox(text_box) = &find_text_box_with_text(page, "Hello").kind else {
+        unreachable!();
+    };
+    assert_eq!(text_box.vertical_align, TextBoxVerticalAlign::Bottom);
+    assert_eq!(
+        text_box.padding,
+        Insets {
+            top: 0.0,
+            right: 0.0,
+            bottom: 0.0,
+            left: 1.0,
+        }
+    );
+}
+
 #[test]
 fn test_subtitle_placeholder_falls_back_to_master_body() {
     // `subTitle` has no direct master counterpart; it must normalize to the
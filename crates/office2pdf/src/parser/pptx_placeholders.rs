@@ -5,13 +5,14 @@
 //! in the slide layout, which in turn may inherit from the slide master
 //! (ECMA-376 §19.3.1.36). Placeholder text likewise stacks the master's
 //! `<p:txStyles>` bucket, the master placeholder's `<a:lstStyle>`, and the
-//! layout placeholder's `<a:lstStyle>` beneath slide-local properties.
+//! layout placeholder's `<a:lstStyle>` beneath slide-local properties, and
+//! its `<a:bodyPr>` insets and anchoring inherit the same way.
 
 use quick_xml::Reader;
 use quick_xml::events::{BytesStart, Event};
 
 use super::PptxTextBodyStyleDefaults;
-use super::text::parse_pptx_list_style;
+use super::text::{PptxTextBoxSettings, extract_pptx_text_box_body_props, parse_pptx_list_style};
 use super::theme::{ColorMapData, PptxMasterTextStyles, ThemeData};
 use crate::parser::xml_util::{get_attr_i64, get_attr_str};
 
@@ -32,6 +33,9 @@ struct LayerPlaceholder {
     geometry: Option<PlaceholderGeometry>,
     /// Parsed `<a:lstStyle>` from the placeholder's own `<p:txBody>`.
     text_defaults: Option<PptxTextBodyStyleDefaults>,
+    /// The placeholder's `<a:bodyPr>`, replayed over inheriting placeholders
+    /// so only the attributes it actually sets take effect.
+    body_pr: Option<BytesStart<'static>>,
 }
 
 /// Placeholder inheritance lookup table for one slide, built from its
@@ -89,6 +93,29 @@ impl PlaceholderGeometryMap {
         defaults
     }
 
+    /// Resolve the inherited `<a:bodyPr>` settings (insets, anchoring, wrap,
+    /// vertical text) for a slide placeholder: the master placeholder's
+    /// attributes, overlaid by the layout placeholder's.
+    pub(super) fn text_box_settings(
+        &self,
+        ph_type: Option<&str>,
+        ph_idx: Option<&str>,
+    ) -> PptxTextBoxSettings {
+        let mut settings = PptxTextBoxSettings::default();
+        let layers = [
+            find_in_master(&self.master, ph_type),
+            find_in_layer(&self.layout, ph_type, ph_idx),
+        ];
+        for body_pr in layers
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.body_pr.as_ref())
+        {
+            extract_pptx_text_box_body_props(body_pr, &mut settings);
+        }
+        settings
+    }
+
     /// Resolve the effective geometry for a slide placeholder:
     /// layout match first, then master fallback.
     pub(super) fn lookup(
@@ -178,7 +205,8 @@ fn find_in_master<'a>(
 }
 
 /// Collect every placeholder `<p:sp>`/`<p:pic>` in a layout/master layer
-/// together with its explicit `<a:xfrm>` geometry and `<a:lstStyle>`, if any.
+/// together with its explicit `<a:xfrm>` geometry, `<a:lstStyle>`, and
+/// `<a:bodyPr>`, if any.
 fn scan_layer_placeholders(
     xml: &str,
     theme: &ThemeData,
@@ -198,6 +226,7 @@ fn scan_layer_placeholders(
         in_sp_pr: bool,
         in_xfrm: bool,
         text_defaults: Option<PptxTextBodyStyleDefaults>,
+        body_pr: Option<BytesStart<'static>>,
     }
 
     fn handle_simple_start(current: &mut Option<Current>, e: &BytesStart) {
@@ -224,6 +253,11 @@ fn scan_layer_placeholders(
                     state.in_xfrm = true;
                 }
             }
+            b"bodyPr" => {
+                if let Some(state) = current.as_mut() {
+                    state.body_pr = Some(e.to_owned());
+                }
+            }
             b"off" => {
                 if let Some(state) = current.as_mut()
                     && state.in_xfrm
@@ -280,6 +314,7 @@ fn scan_layer_placeholders(
                             ph_idx: state.ph_idx,
                             geometry,
                             text_defaults: state.text_defaults,
+                            body_pr: state.body_pr,
                         });
                     }
                 }
@@ -0,0 +1,195 @@
+//! Rescaling parsed slides to another slide size preset
+//! (`ConvertOptions::slide_scale_to`), the way PowerPoint's
+//! "Slide Size" dialog refits content when a deck changes aspect ratio.
+
+use crate::config::{SlideAspect, SlideScaleMode};
+use crate::ir::{
+    Block, FixedElement, FixedElementKind, FixedPage, Insets, LineSpacing, PageSize, Paragraph,
+    ShapeKind, Table,
+};
+
+/// Affine map from source slide points to target slide points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SlideTransform {
+    scale_x: f64,
+    scale_y: f64,
+    offset_x: f64,
+    offset_y: f64,
+}
+
+impl SlideTransform {
+    fn new(source: PageSize, target: (f64, f64), mode: SlideScaleMode) -> Self {
+        let (target_width, target_height) = target;
+        let fit_x: f64 = target_width / source.width;
+        let fit_y: f64 = target_height / source.height;
+        let (scale_x, scale_y): (f64, f64) = match mode {
+            SlideScaleMode::Fit => (fit_x.min(fit_y), fit_x.min(fit_y)),
+            SlideScaleMode::Maximize => (fit_x.max(fit_y), fit_x.max(fit_y)),
+            SlideScaleMode::Stretch => (fit_x, fit_y),
+        };
+        // Uniform modes center the scaled slide: letterbox bands for `Fit`,
+        // negative offsets that crop evenly for `Maximize`.
+        Self {
+            scale_x,
+            scale_y,
+            offset_x: (target_width - source.width * scale_x) / 2.0,
+            offset_y: (target_height - source.height * scale_y) / 2.0,
+        }
+    }
+
+    /// Factor for lengths without a direction (font sizes, stroke widths,
+    /// padding), so text never outgrows its stretched box.
+    fn length_scale(&self) -> f64 {
+        self.scale_x.min(self.scale_y)
+    }
+}
+
+/// Resize `page` to `target` and map every element onto it.
+pub(super) fn rescale_slide(page: &mut FixedPage, target: SlideAspect, mode: SlideScaleMode) {
+    if page.size.width <= 0.0 || page.size.height <= 0.0 {
+        return;
+    }
+    let (target_width, target_height) = target.dimensions();
+    let transform = SlideTransform::new(page.size, (target_width, target_height), mode);
+    page.size = PageSize {
+        width: target_width,
+        height: target_height,
+    };
+    if let Some(background) = page.background_image.as_mut()
+        && let Some((tile_width, tile_height)) = background.tile_size.as_mut()
+    {
+        *tile_width *= transform.scale_x;
+        *tile_height *= transform.scale_y;
+    }
+    for element in &mut page.elements {
+        rescale_element(element, &transform);
+    }
+}
+
+fn rescale_element(element: &mut FixedElement, transform: &SlideTransform) {
+    let (sx, sy): (f64, f64) = (transform.scale_x, transform.scale_y);
+    let length: f64 = transform.length_scale();
+    element.x = transform.offset_x + element.x * sx;
+    element.y = transform.offset_y + element.y * sy;
+    element.width *= sx;
+    element.height *= sy;
+
+    match &mut element.kind {
+        FixedElementKind::TextBox(text_box) => {
+            text_box.padding = scale_insets(text_box.padding, length);
+            if let Some(stroke) = text_box.stroke.as_mut() {
+                stroke.width *= length;
+            }
+            if let Some(kind) = text_box.shape_kind.as_mut() {
+                scale_shape_kind(kind, sx, sy);
+            }
+            scale_blocks(&mut text_box.content, length);
+        }
+        FixedElementKind::Image(image) => {
+            image.width = image.width.map(|width| width * sx);
+            image.height = image.height.map(|height| height * sy);
+        }
+        FixedElementKind::Shape(shape) => {
+            scale_shape_kind(&mut shape.kind, sx, sy);
+            if let Some(stroke) = shape.stroke.as_mut() {
+                stroke.width *= length;
+            }
+            if let Some(shadow) = shape.shadow.as_mut() {
+                shadow.blur_radius *= length;
+                shadow.distance *= length;
+            }
+        }
+        FixedElementKind::Table(table) => scale_table(table, sx, sy, length),
+        // Charts and SmartArt lay themselves out inside the element box.
+        FixedElementKind::SmartArt(_) | FixedElementKind::Chart(_) => {}
+    }
+}
+
+/// Scale shape coordinates that are absolute points relative to the element.
+fn scale_shape_kind(kind: &mut ShapeKind, sx: f64, sy: f64) {
+    match kind {
+        ShapeKind::Line { x1, y1, x2, y2, .. } => {
+            *x1 *= sx;
+            *y1 *= sy;
+            *x2 *= sx;
+            *y2 *= sy;
+        }
+        ShapeKind::Polyline { points, .. } | ShapeKind::InkStroke { points } => {
+            for (x, y) in points {
+                *x *= sx;
+                *y *= sy;
+            }
+        }
+        // Normalized or proportional geometry follows the box by itself.
+        ShapeKind::Rectangle
+        | ShapeKind::Ellipse
+        | ShapeKind::RoundedRectangle { .. }
+        | ShapeKind::Polygon { .. } => {}
+    }
+}
+
+fn scale_table(table: &mut Table, sx: f64, sy: f64, length: f64) {
+    for width in &mut table.column_widths {
+        *width *= sx;
+    }
+    table.default_cell_padding = table
+        .default_cell_padding
+        .map(|padding| scale_insets(padding, length));
+    for row in &mut table.rows {
+        row.height = row.height.map(|height| height * sy);
+        for cell in &mut row.cells {
+            cell.padding = cell.padding.map(|padding| scale_insets(padding, length));
+            cell.spill_width = cell.spill_width.map(|width| width * sx);
+            scale_blocks(&mut cell.content, length);
+        }
+    }
+}
+
+fn scale_blocks(blocks: &mut [Block], length: f64) {
+    for block in blocks {
+        match block {
+            Block::Paragraph(paragraph) => scale_paragraph(paragraph, length),
+            Block::List(list) => {
+                for item in &mut list.items {
+                    for paragraph in &mut item.content {
+                        scale_paragraph(paragraph, length);
+                    }
+                }
+            }
+            Block::Table(table) => scale_table(table, length, length, length),
+            _ => {}
+        }
+    }
+}
+
+fn scale_paragraph(paragraph: &mut Paragraph, length: f64) {
+    let style = &mut paragraph.style;
+    for value in [
+        &mut style.indent_left,
+        &mut style.indent_right,
+        &mut style.indent_first_line,
+        &mut style.space_before,
+        &mut style.space_after,
+    ] {
+        *value = value.map(|points| points * length);
+    }
+    if let Some(LineSpacing::Exact(points)) = style.line_spacing.as_mut() {
+        *points *= length;
+    }
+    for run in &mut paragraph.runs {
+        run.style.font_size = run.style.font_size.map(|size| size * length);
+    }
+}
+
+fn scale_insets(insets: Insets, scale: f64) -> Insets {
+    Insets {
+        top: insets.top * scale,
+        right: insets.right * scale,
+        bottom: insets.bottom * scale,
+        left: insets.left * scale,
+    }
+}
+
+#[cfg(test)]
+#[path = "pptx_rescale_tests.rs"]
+mod tests;
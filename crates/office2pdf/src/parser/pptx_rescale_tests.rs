@@ -0,0 +1,187 @@
+use super::*;
+use crate::ir::{
+    ArrowHead, BorderLineStyle, BorderSide, Color, ParagraphStyle, Run, Shape, ShapeEffects,
+    TextBoxData, TextBoxVerticalAlign, TextStyle,
+};
+
+fn make_page(width: f64, height: f64, elements: Vec<FixedElement>) -> FixedPage {
+    FixedPage {
+        size: PageSize { width, height },
+        elements,
+        background_color: None,
+        background_gradient: None,
+        background_image: None,
+        source_slide: None,
+    }
+}
+
+fn make_text_box(x: f64, y: f64, width: f64, height: f64, font_size: f64) -> FixedElement {
+    FixedElement {
+        x,
+        y,
+        width,
+        height,
+        kind: FixedElementKind::TextBox(TextBoxData {
+            content: vec![Block::Paragraph(Paragraph {
+                style: ParagraphStyle {
+                    space_after: Some(12.0),
+                    ..ParagraphStyle::default()
+                },
+                runs: vec![Run {
+                    text: "Title".to_string(),
+                    style: TextStyle {
+                        font_size: Some(font_size),
+                        ..TextStyle::default()
+                    },
+                    href: None,
+                    footnote: None,
+                }],
+            })],
+            padding: Insets {
+                top: 4.0,
+                right: 8.0,
+                bottom: 4.0,
+                left: 8.0,
+            },
+            vertical_align: TextBoxVerticalAlign::Top,
+            fill: None,
+            opacity: None,
+            stroke: None,
+            shape_kind: None,
+            no_wrap: false,
+            auto_fit: false,
+            text_rotation_deg: None,
+        }),
+        href: None,
+    }
+}
+
+fn make_polyline(points: Vec<(f64, f64)>) -> FixedElement {
+    FixedElement {
+        x: 0.0,
+        y: 0.0,
+        width: 720.0,
+        height: 540.0,
+        kind: FixedElementKind::Shape(Shape {
+            kind: ShapeKind::Polyline {
+                points,
+                head_end: ArrowHead::None,
+                tail_end: ArrowHead::None,
+            },
+            fill: None,
+            gradient_fill: None,
+            stroke: Some(BorderSide {
+                width: 2.0,
+                color: Color::black(),
+                style: BorderLineStyle::Solid,
+            }),
+            rotation_deg: None,
+            opacity: None,
+            shadow: None,
+            effects: ShapeEffects::default(),
+        }),
+        href: None,
+    }
+}
+
+fn geometry(element: &FixedElement) -> (f64, f64, f64, f64) {
+    (element.x, element.y, element.width, element.height)
+}
+
+fn first_run_font_size(element: &FixedElement) -> f64 {
+    let FixedElementKind::TextBox(text_box) = &element.kind else {
+        panic!("expected a text box");
+    };
+    let Block::Paragraph(paragraph) = &text_box.content[0] else {
+        panic!("expected a paragraph");
+    };
+    paragraph.runs[0].style.font_size.unwrap()
+}
+
+#[test]
+fn test_fit_letterboxes_widescreen_onto_standard() {
+    let mut page = make_page(
+        960.0,
+        540.0,
+        vec![make_text_box(0.0, 0.0, 960.0, 540.0, 24.0)],
+    );
+
+    rescale_slide(&mut page, SlideAspect::Standard4x3, SlideScaleMode::Fit);
+
+    assert_eq!((page.size.width, page.size.height), (720.0, 540.0));
+    // Uniform 0.75 scale, centered vertically with 67.5pt bands.
+    assert_eq!(geometry(&page.elements[0]), (0.0, 67.5, 720.0, 405.0));
+    assert_eq!(first_run_font_size(&page.elements[0]), 18.0);
+    let FixedElementKind::TextBox(text_box) = &page.elements[0].kind else {
+        unreachable!();
+    };
+    assert_eq!(text_box.padding.left, 6.0);
+    let Block::Paragraph(paragraph) = &text_box.content[0] else {
+        unreachable!();
+    };
+    assert_eq!(paragraph.style.space_after, Some(9.0));
+}
+
+#[test]
+fn test_maximize_fills_and_crops_evenly() {
+    let mut page = make_page(
+        960.0,
+        540.0,
+        vec![make_text_box(120.0, 0.0, 720.0, 540.0, 24.0)],
+    );
+
+    rescale_slide(
+        &mut page,
+        SlideAspect::Standard4x3,
+        SlideScaleMode::Maximize,
+    );
+
+    // Scale 1.0; 120pt cropped on each side, so the centered box fills the slide.
+    assert_eq!(geometry(&page.elements[0]), (0.0, 0.0, 720.0, 540.0));
+    assert_eq!(first_run_font_size(&page.elements[0]), 24.0);
+}
+
+#[test]
+fn test_stretch_scales_axes_independently() {
+    let mut page = make_page(
+        720.0,
+        540.0,
+        vec![make_polyline(vec![
+            (0.0, 0.0),
+            (360.0, 270.0),
+            (720.0, 540.0),
+        ])],
+    );
+
+    rescale_slide(
+        &mut page,
+        SlideAspect::Widescreen16x9,
+        SlideScaleMode::Stretch,
+    );
+
+    assert_eq!((page.size.width, page.size.height), (960.0, 540.0));
+    assert_eq!(geometry(&page.elements[0]), (0.0, 0.0, 960.0, 540.0));
+    let FixedElementKind::Shape(shape) = &page.elements[0].kind else {
+        unreachable!();
+    };
+    let ShapeKind::Polyline { points, .. } = &shape.kind else {
+        unreachable!();
+    };
+    assert_eq!(points, &vec![(0.0, 0.0), (480.0, 270.0), (960.0, 540.0)]);
+    // The vertical axis is unchanged, so strokes keep their width.
+    assert_eq!(shape.stroke.as_ref().unwrap().width, 2.0);
+}
+
+#[test]
+fn test_same_aspect_is_identity() {
+    let mut page = make_page(
+        960.0,
+        540.0,
+        vec![make_text_box(10.0, 20.0, 300.0, 100.0, 24.0)],
+    );
+
+    rescale_slide(&mut page, SlideAspect::Widescreen16x9, SlideScaleMode::Fit);
+
+    assert_eq!(geometry(&page.elements[0]), (10.0, 20.0, 300.0, 100.0));
+    assert_eq!(first_run_font_size(&page.elements[0]), 24.0);
+}
@@ -0,0 +1,101 @@
+use super::*;
+
+fn paragraph_text(elem: &FixedElement) -> String {
+    text_box_blocks(elem)
+        .iter()
+        .filter_map(|block| match block {
+            Block::Paragraph(paragraph) => Some(
+                paragraph
+                    .runs
+                    .iter()
+                    .map(|run| run.text.as_str())
+                    .collect::<String>(),
+            ),
+            _ => None,
+        })
+        .collect()
+}
+
+fn skipped_reasons(warnings: &[ConvertWarning]) -> Vec<&str> {
+    warnings
+        .iter()
+        .filter_map(|warning| match warning {
+            ConvertWarning::ParseSkipped { reason, .. } => Some(reason.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A text box whose run closes with the wrong end tag.
+fn make_broken_text_box(id: u32, name: &str) -> String {
+    format!(
+        r#"<p:sp><p:nvSpPr><p:cNvPr id="{id}" name="{name}"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="0" y="1270000"/><a:ext cx="2540000" cy="635000"/></a:xfrm></p:spPr><p:txBody><a:bodyPr/><a:p><a:r><a:t>Broken</a:r></a:p></p:txBody></p:sp>"#
+    )
+}
+
+#[test]
+fn test_broken_shape_is_skipped_and_rest_of_slide_renders() {
+    let slide = make_slide_xml(&[
+        make_text_box(0, 0, 2_540_000, 635_000, "Before"),
+        make_broken_text_box(7, "Callout 6"),
+        make_text_box(0, 2_540_000, 2_540_000, 635_000, "After"),
+    ]);
+    let data: Vec<u8> = build_test_pptx(SLIDE_CX, SLIDE_CY, &[slide]);
+    let (doc, warnings) = PptxParser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let page: &FixedPage = first_fixed_page(&doc);
+    let texts: Vec<String> = page.elements.iter().map(paragraph_text).collect();
+    assert_eq!(texts, vec!["Before", "After"]);
+
+    let reasons: Vec<&str> = skipped_reasons(&warnings);
+    assert_eq!(reasons.len(), 1, "warnings: {warnings:?}");
+    assert!(
+        reasons[0].contains(r#"slide 1 sp #2 (id 7 "Callout 6")"#),
+        "warning should locate the shape: {}",
+        reasons[0]
+    );
+}
+
+#[test]
+fn test_broken_group_is_skipped_as_one_object() {
+    let group: String = format!(
+        r#"<p:grpSp><p:nvGrpSpPr><p:cNvPr id="4" name="Group 3"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="2540000" cy="2540000"/><a:chOff x="0" y="0"/><a:chExt cx="2540000" cy="2540000"/></a:xfrm></p:grpSpPr>{}{}</p:grpSp>"#,
+        make_text_box(0, 0, 2_540_000, 635_000, "Inside"),
+        make_broken_text_box(5, "Inner"),
+    );
+    let slide = make_slide_xml(&[
+        group,
+        make_text_box(0, 2_540_000, 2_540_000, 635_000, "After"),
+    ]);
+    let data: Vec<u8> = build_test_pptx(SLIDE_CX, SLIDE_CY, &[slide]);
+    let (doc, warnings) = PptxParser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let page: &FixedPage = first_fixed_page(&doc);
+    let texts: Vec<String> = page.elements.iter().map(paragraph_text).collect();
+    assert_eq!(texts, vec!["After"]);
+    assert_eq!(
+        skipped_reasons(&warnings).len(),
+        1,
+        "warnings: {warnings:?}"
+    );
+}
+
+#[test]
+fn test_unterminated_shape_keeps_earlier_shapes() {
+    let mut slide: String = make_slide_xml(&[make_text_box(0, 0, 2_540_000, 635_000, "Kept")]);
+    // Truncate the part inside a second shape, as a damaged download would.
+    let tree_end: usize = slide.find("</p:spTree>").unwrap();
+    slide.truncate(tree_end);
+    slide.push_str(r#"<p:sp><p:nvSpPr><p:cNvPr id="9" name="Cut"/>"#);
+    let data: Vec<u8> = build_test_pptx(SLIDE_CX, SLIDE_CY, &[slide]);
+    let (doc, warnings) = PptxParser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let page: &FixedPage = first_fixed_page(&doc);
+    let texts: Vec<String> = page.elements.iter().map(paragraph_text).collect();
+    assert_eq!(texts, vec!["Kept"]);
+    assert_eq!(
+        skipped_reasons(&warnings).len(),
+        1,
+        "warnings: {warnings:?}"
+    );
+}
@@ -23,6 +23,25 @@ fn test_shape_outline_dash_style() {
     }
 }
 
+#[test]
+fn test_shape_outline_compound_line_is_double() {
+    let shape = r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="Shape"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="914400" cy="914400"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom><a:ln w="38100" cmpd="dbl"><a:solidFill><a:srgbClr val="000000"/></a:solidFill></a:ln></p:spPr></p:sp>"#.to_string();
+    let slide = make_slide_xml(&[shape]);
+    let data = build_test_pptx(SLIDE_CX, SLIDE_CY, &[slide]);
+
+    let parser = PptxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let page = first_fixed_page(&doc);
+    let FixedElementKind::Shape(ref s) = page.elements[0].kind else {
+        panic!("Expected Shape element");
+    };
+    let stroke: &BorderSide = s.stroke.as_ref().expect("Expected stroke");
+    assert_eq!(stroke.style, BorderLineStyle::Double);
+    // The 3pt compound width covers both strokes and the gap between them.
+    assert!((stroke.width - 1.0).abs() < 0.001, "got {}", stroke.width);
+}
+
 // ── Shape style (rotation, transparency) test helpers ────────────────
 
 #[allow(clippy::too_many_arguments)]
@@ -398,6 +417,44 @@ fn test_shape_outer_shadow_parsed() {
     );
 }
 
+#[test]
+fn test_shape_glow_reflection_soft_edge_parsed() {
+    let shape_xml = r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="Rect"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="100000" y="200000"/><a:ext cx="500000" cy="300000"/></a:xfrm><a:prstGeom prst="rect"/><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill><a:effectLst><a:glow rad="127000"><a:srgbClr val="FFC000"><a:alpha val="40000"/></a:srgbClr></a:glow><a:reflection blurRad="6350" stA="52000" endA="300" endPos="35000" dist="12700" dir="5400000" sy="-100000" algn="bl" rotWithShape="0"/><a:softEdge rad="63500"/></a:effectLst></p:spPr></p:sp>"#.to_string();
+    let slide_xml = make_slide_xml(&[shape_xml]);
+    let data = build_test_pptx(SLIDE_CX, SLIDE_CY, &[slide_xml]);
+    let parser = PptxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+    let page = first_fixed_page(&doc);
+
+    let shape = get_shape(&page.elements[0]);
+    assert!(shape.shadow.is_none());
+    let glow = shape.effects.glow.as_ref().expect("Expected glow");
+    assert!(
+        (glow.radius - 10.0).abs() < 0.01,
+        "glow radius {}",
+        glow.radius
+    );
+    assert_eq!(glow.color, Color::new(255, 192, 0));
+    assert!(
+        (glow.opacity - 0.4).abs() < 0.01,
+        "glow opacity {}",
+        glow.opacity
+    );
+
+    let reflection = shape
+        .effects
+        .reflection
+        .as_ref()
+        .expect("Expected reflection");
+    assert!((reflection.distance - 1.0).abs() < 0.01);
+    assert!((reflection.start_opacity - 0.52).abs() < 0.001);
+    assert!((reflection.end_opacity - 0.003).abs() < 0.001);
+    assert!((reflection.end_position - 0.35).abs() < 0.001);
+
+    let soft_edge = shape.effects.soft_edge_radius.expect("Expected soft edge");
+    assert!((soft_edge - 5.0).abs() < 0.01, "soft edge {soft_edge}");
+}
+
 #[test]
 fn test_shape_no_effects_no_shadow() {
     let shape_xml = make_shape(
@@ -421,6 +478,7 @@ fn test_shape_no_effects_no_shadow() {
         shape.shadow.is_none(),
         "Shape without effectLst should have no shadow"
     );
+    assert!(shape.effects.is_empty());
 }
 
 #[test]
@@ -457,6 +515,95 @@ fn test_shape_shadow_default_opacity() {
     );
 }
 
+fn parse_first_shape_with_sp_pr_tail(sp_pr_tail: &str) -> Shape {
+    let shape_xml = format!(
+        r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="Rect"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="100000" y="200000"/><a:ext cx="500000" cy="300000"/></a:xfrm><a:prstGeom prst="rect"/><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill>{sp_pr_tail}</p:spPr></p:sp>"#
+    );
+    let slide_xml = make_slide_xml(&[shape_xml]);
+    let data = build_test_pptx(SLIDE_CX, SLIDE_CY, &[slide_xml]);
+    let (doc, _warnings) = PptxParser.parse(&data, &ConvertOptions::default()).unwrap();
+    get_shape(&first_fixed_page(&doc).elements[0]).clone()
+}
+
+#[test]
+fn test_shape_outer_shadow_perspective_parsed() {
+    let shape = parse_first_shape_with_sp_pr_tail(
+        r#"<a:effectLst><a:outerShdw blurRad="76200" dist="12700" dir="2700000" sy="23000" kx="-1200000" algn="bl"><a:srgbClr val="000000"><a:alpha val="20000"/></a:srgbClr></a:outerShdw></a:effectLst>"#,
+    );
+
+    let shadow = shape.shadow.as_ref().expect("Expected shadow");
+    assert!(shadow.has_perspective());
+    assert!((shadow.scale_x - 1.0).abs() < 0.001);
+    assert!((shadow.scale_y - 0.23).abs() < 0.001);
+    assert!((shadow.skew_x + 20.0).abs() < 0.001);
+    assert!((shadow.skew_y - 0.0).abs() < 0.001);
+}
+
+#[test]
+fn test_shape_preset_shadow_parsed() {
+    let shape = parse_first_shape_with_sp_pr_tail(
+        r#"<a:effectLst><a:prstShdw prst="shdw3" dist="25400" dir="13500000"><a:srgbClr val="808080"/></a:prstShdw></a:effectLst>"#,
+    );
+
+    let shadow = shape.shadow.as_ref().expect("Expected preset shadow");
+    assert!((shadow.distance - 2.0).abs() < 0.01);
+    assert!((shadow.direction - 225.0).abs() < 0.01);
+    assert_eq!(shadow.color, Color::new(0x80, 0x80, 0x80));
+    // Back-left perspective: flattened behind the shape, leaning left.
+    assert!((shadow.scale_y - 0.5).abs() < 0.001);
+    assert!(shadow.skew_x > 0.0);
+}
+
+#[test]
+fn test_shape_preset_drop_shadow_has_no_perspective() {
+    let shape = parse_first_shape_with_sp_pr_tail(
+        r#"<a:effectLst><a:prstShdw prst="shdw6" dist="38100" dir="2700000"/></a:effectLst>"#,
+    );
+
+    let shadow = shape.shadow.as_ref().expect("Expected preset shadow");
+    assert!(!shadow.has_perspective());
+    assert_eq!(shadow.color, Color::new(0, 0, 0));
+}
+
+#[test]
+fn test_shape_scene3d_rotation_and_extrusion_parsed() {
+    let shape = parse_first_shape_with_sp_pr_tail(
+        r#"<a:scene3d><a:camera prst="perspectiveFront"><a:rot lat="1200000" lon="18000000" rev="0"/></a:camera><a:lightRig rig="threePt" dir="t"/></a:scene3d><a:sp3d extrusionH="127000"><a:extrusionClr><a:srgbClr val="002060"/></a:extrusionClr></a:sp3d>"#,
+    );
+
+    let rotation = shape.effects.rotation_3d.expect("Expected 3-D rotation");
+    assert!((rotation.latitude - 20.0).abs() < 0.001);
+    assert!((rotation.longitude - 300.0).abs() < 0.001);
+    assert_eq!(rotation.revolution, 0.0);
+    let extrusion = shape
+        .effects
+        .extrusion
+        .as_ref()
+        .expect("Expected extrusion");
+    assert!((extrusion.depth - 10.0).abs() < 0.01);
+    assert_eq!(extrusion.color, Some(Color::new(0x00, 0x20, 0x60)));
+}
+
+#[test]
+fn test_shape_camera_preset_without_rot_uses_preset_rotation() {
+    let shape = parse_first_shape_with_sp_pr_tail(
+        r#"<a:scene3d><a:camera prst="isometricLeftDown"/><a:lightRig rig="threePt" dir="t"/></a:scene3d>"#,
+    );
+
+    let rotation = shape.effects.rotation_3d.expect("Expected preset rotation");
+    assert!(rotation.longitude > 0.0);
+    assert!(rotation.latitude < 0.0);
+}
+
+#[test]
+fn test_shape_front_camera_has_no_rotation() {
+    let shape = parse_first_shape_with_sp_pr_tail(
+        r#"<a:scene3d><a:camera prst="orthographicFront"/><a:lightRig rig="threePt" dir="t"/></a:scene3d><a:sp3d/>"#,
+    );
+
+    assert!(shape.effects.is_empty());
+}
+
 // ── fillRef style fallback tests ─────────────────────────────────
 
 #[test]
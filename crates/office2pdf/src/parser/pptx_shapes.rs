@@ -12,6 +12,39 @@ pub(super) fn pptx_dash_to_border_style(val: &str) -> BorderLineStyle {
     }
 }
 
+/// Whether `<a:ln cmpd>` draws the line as two or three parallel strokes.
+pub(super) fn is_compound_line(e: &quick_xml::events::BytesStart) -> bool {
+    matches!(
+        get_attr_str(e, b"cmpd").as_deref(),
+        Some("dbl" | "thickThin" | "thinThick" | "tri")
+    )
+}
+
+/// The border side of an `<a:ln>` `width_pt` wide. A compound line is drawn
+/// as a double one: DrawingML's width spans both strokes and the gap
+/// between them, while a double [`BorderSide`] is as wide as each stroke,
+/// so the three share the width. Its dash pattern cannot be drawn with it.
+pub(super) fn line_border_side(
+    width_pt: f64,
+    color: Color,
+    dash_style: BorderLineStyle,
+    compound: bool,
+) -> BorderSide {
+    if compound {
+        BorderSide {
+            width: width_pt / 3.0,
+            color,
+            style: BorderLineStyle::Double,
+        }
+    } else {
+        BorderSide {
+            width: width_pt,
+            color,
+            style: dash_style,
+        }
+    }
+}
+
 /// Group shape coordinate transform.
 ///
 /// Maps child coordinates from the group's internal coordinate space
@@ -82,7 +115,7 @@ impl GroupTransform {
                     *y1 *= scale_y;
                     *y2 *= scale_y;
                 }
-                ShapeKind::Polyline { points, .. } => {
+                ShapeKind::Polyline { points, .. } | ShapeKind::InkStroke { points } => {
                     for (x, y) in points.iter_mut() {
                         *x *= scale_x;
                         *y *= scale_y;
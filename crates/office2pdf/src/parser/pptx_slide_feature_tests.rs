@@ -425,6 +425,16 @@ fn test_hidden_slide_is_skipped() {
     let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
 
     assert_eq!(page_texts(&doc), vec!["First", "Third"]);
+    // The pages remember their slide so later warnings can name it.
+    let source_slides: Vec<Option<u32>> = doc
+        .pages
+        .iter()
+        .map(|page| match page {
+            Page::Fixed(fixed) => fixed.source_slide,
+            _ => None,
+        })
+        .collect();
+    assert_eq!(source_slides, vec![Some(1), Some(3)]);
 }
 
 #[test]
@@ -457,3 +467,121 @@ fn test_visible_show_attribute_keeps_slide() {
 
     assert_eq!(page_texts(&doc), vec!["Visible"]);
 }
+
+// ── Section tests ────────────────────────────────────────────────────
+
+fn make_sectioned_deck(hidden_slide: Option<usize>) -> Vec<u8> {
+    let slides: Vec<String> = ["Intro", "Result A", "Result B", "Appendix"]
+        .iter()
+        .enumerate()
+        .map(|(index, text)| {
+            let shapes = [make_text_box(0, 0, 5_000_000, 500_000, text)];
+            if hidden_slide == Some(index) {
+                make_slide_xml_with_show("0", &shapes)
+            } else {
+                make_slide_xml(&shapes)
+            }
+        })
+        .collect();
+    build_test_pptx_with_sections(
+        SLIDE_CX,
+        SLIDE_CY,
+        &slides,
+        &[("Opening", 1), ("Results", 2), ("Backup", 1)],
+    )
+}
+
+#[test]
+fn test_slide_sections_filter_named_sections() {
+    let data = make_sectioned_deck(None);
+    let opts = ConvertOptions {
+        slide_sections: Some(vec!["Results".to_string(), "Backup".to_string()]),
+        ..Default::default()
+    };
+    let (doc, warnings) = PptxParser.parse(&data, &opts).unwrap();
+
+    assert_eq!(page_texts(&doc), vec!["Result A", "Result B", "Appendix"]);
+    assert!(warnings.is_empty(), "{warnings:?}");
+}
+
+#[test]
+fn test_slide_sections_combine_with_slide_range() {
+    use crate::config::SlideRange;
+
+    let data = make_sectioned_deck(None);
+    let opts = ConvertOptions {
+        slide_range: Some(SlideRange::new(1, 2)),
+        slide_sections: Some(vec!["Results".to_string()]),
+        ..Default::default()
+    };
+    let (doc, _warnings) = PptxParser.parse(&data, &opts).unwrap();
+
+    assert_eq!(page_texts(&doc), vec!["Result A"]);
+}
+
+#[test]
+fn test_slide_sections_warns_on_unknown_section() {
+    let data = make_sectioned_deck(None);
+    let opts = ConvertOptions {
+        slide_sections: Some(vec!["Missing".to_string()]),
+        ..Default::default()
+    };
+    let (doc, warnings) = PptxParser.parse(&data, &opts).unwrap();
+
+    assert!(doc.pages.is_empty());
+    assert!(
+        warnings
+            .iter()
+            .any(|warning| warning.to_string().contains("\"Missing\"")),
+        "{warnings:?}"
+    );
+}
+
+#[test]
+fn test_parse_with_sections_counts_visible_pages() {
+    let data = make_sectioned_deck(Some(1));
+    let (doc, _warnings, sections) = PptxParser
+        .parse_with_sections(&data, &ConvertOptions::default())
+        .unwrap();
+
+    assert_eq!(doc.pages.len(), 3);
+    let counts: Vec<(&str, usize)> = sections
+        .iter()
+        .map(|section| (section.name.as_str(), section.page_count))
+        .collect();
+    assert_eq!(counts, vec![("Opening", 1), ("Results", 1), ("Backup", 1)]);
+}
+
+#[test]
+fn test_parse_with_sections_without_section_list() {
+    let data = build_test_pptx(SLIDE_CX, SLIDE_CY, &[make_empty_slide_xml()]);
+    let (doc, _warnings, sections) = PptxParser
+        .parse_with_sections(&data, &ConvertOptions::default())
+        .unwrap();
+
+    assert_eq!(doc.pages.len(), 1);
+    assert!(sections.is_empty());
+}
+
+// ── Slide size rescaling ─────────────────────────────────────────────
+
+#[test]
+fn test_slide_scale_to_widescreen_pillarboxes_standard_deck() {
+    let slide = make_slide_xml(&[make_text_box(0, 0, 2_540_000, 1_270_000, "Hello")]);
+    let data = build_test_pptx(SLIDE_CX, SLIDE_CY, &[slide]);
+    let options = ConvertOptions {
+        slide_scale_to: Some(crate::config::SlideAspect::Widescreen16x9),
+        ..ConvertOptions::default()
+    };
+
+    let parser = PptxParser;
+    let (doc, _warnings) = parser.parse(&data, &options).unwrap();
+    let page = first_fixed_page(&doc);
+
+    assert_eq!((page.size.width, page.size.height), (960.0, 540.0));
+    let element = &page.elements[0];
+    assert_eq!(
+        (element.x, element.y, element.width, element.height),
+        (120.0, 0.0, 200.0, 100.0)
+    );
+}
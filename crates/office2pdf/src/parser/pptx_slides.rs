@@ -1,5 +1,7 @@
+use super::links::{SlideHyperlinkMap, load_slide_hyperlinks, resolve_hlink_click};
 use super::package::{
-    load_chart_data, load_slide_images, load_smartart_data, parse_rels_xml, rels_path_for,
+    load_chart_data, load_slide_images, load_slide_ink, load_slide_media, load_slide_workbooks,
+    load_smartart_data, load_smartart_layout, parse_rels_xml, rels_path_for,
     resolve_layout_master_paths, resolve_relative_path, scan_chart_refs,
 };
 use super::placeholders::PlaceholderGeometryMap;
@@ -76,9 +78,17 @@ fn parse_layer_elements<R: Read + std::io::Seek>(
     archive: &mut ZipArchive<R>,
 ) -> (Vec<FixedElement>, Vec<ConvertWarning>) {
     let images: SlideImageMap = load_slide_images(layer_path, archive);
+    let media: SlideMediaMap = load_slide_media(layer_path, archive);
+    let ink: SlideInkMap = load_slide_ink(layer_path, archive);
+    let workbooks: SlideWorkbookMap = load_slide_workbooks(layer_path, archive);
+    let hyperlinks: SlideHyperlinkMap = load_slide_hyperlinks(layer_path, archive);
     let empty_table_styles: table_styles::TableStyleMap = table_styles::TableStyleMap::new();
     let ctx = SlideParseContext {
         images: &images,
+        media: &media,
+        ink: &ink,
+        workbooks: &workbooks,
+        hyperlinks: &hyperlinks,
         theme,
         color_map,
         warning_context: label,
@@ -123,15 +133,25 @@ fn collect_smartart_elements<R: Read + std::io::Seek>(
                 .unwrap_or_default();
         if !drawing_elems.is_empty() {
             elements.extend(drawing_elems);
-        } else if let Some(items) = smartart_data.get(&sa_ref.data_rid) {
+        } else if let Some(smartart) = smartart_data.get(&sa_ref.data_rid) {
+            let mut smartart: SmartArt = smartart.clone();
+            // The layout definition's algorithms are authoritative; the data
+            // model's layout ID only covers built-in layouts.
+            if let Some(layout) = sa_ref
+                .layout_rid
+                .as_deref()
+                .and_then(|rid| load_smartart_layout(slide_path, archive, rid))
+                .filter(|layout| *layout != SmartArtLayout::List)
+            {
+                smartart.layout = layout;
+            }
             elements.push(FixedElement {
                 x: emu_to_pt(sa_ref.x),
                 y: emu_to_pt(sa_ref.y),
                 width: emu_to_pt(sa_ref.cx),
                 height: emu_to_pt(sa_ref.cy),
-                kind: FixedElementKind::SmartArt(SmartArt {
-                    items: items.clone(),
-                }),
+                kind: FixedElementKind::SmartArt(smartart),
+                href: None,
             });
         }
     }
@@ -380,7 +400,9 @@ fn smartart_shape_to_elements(f: SmartArtShapeFields) -> Vec<FixedElement> {
             rotation_deg: None,
             opacity: None,
             shadow: None,
+            effects: ShapeEffects::default(),
         }),
+        href: None,
     });
     if !f.texts.is_empty() {
         let runs: Vec<Run> = f
@@ -419,6 +441,7 @@ fn smartart_shape_to_elements(f: SmartArtShapeFields) -> Vec<FixedElement> {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         });
     }
     out
@@ -451,6 +474,7 @@ fn collect_chart_elements<R: Read + std::io::Seek>(
                 width: emu_to_pt(c_ref.cx),
                 height: emu_to_pt(c_ref.cy),
                 kind: FixedElementKind::Chart(chart.clone()),
+                href: None,
             })
         })
         .collect()
@@ -459,11 +483,11 @@ fn collect_chart_elements<R: Read + std::io::Seek>(
 // ── Background resolution ───────────────────────────────────────────────
 
 /// Resolved slide background: an optional solid color and gradient, plus an
-/// optional picture fill given as (owning layer part path, image rel id).
+/// optional picture fill given with the part path of the layer that owns it.
 struct ResolvedBackground {
     color: Option<Color>,
     gradient: Option<GradientFill>,
-    image: Option<(String, String)>,
+    image: Option<(String, BackgroundPictureFill)>,
 }
 
 /// Resolve the slide background by checking slide -> layout -> master in
@@ -513,11 +537,11 @@ fn resolve_slide_background(
                 image: None,
             };
         }
-        if let Some(rid) = parse_background_image_rid(xml) {
+        if let Some(fill) = parse_background_picture_fill(xml) {
             return ResolvedBackground {
                 color: None,
                 gradient: None,
-                image: Some((layer_path.to_string(), rid)),
+                image: Some((layer_path.to_string(), fill)),
             };
         }
         if let Some((color, gradient)) = parse_background_ref(xml, theme, color_map) {
@@ -536,22 +560,30 @@ fn resolve_slide_background(
     }
 }
 
-/// Build a full-page image element for a picture-fill background.
-fn build_background_image_element<R: Read + std::io::Seek>(
+/// Load the picture of a picture-fill background. Tiled pictures repeat at
+/// their natural size (96 DPI) times the `<a:tile>` scale; a picture whose
+/// pixel size cannot be read is stretched instead.
+fn build_background_image<R: Read + std::io::Seek>(
     layer_path: &str,
-    rid: &str,
+    fill: &BackgroundPictureFill,
     slide_size: PageSize,
     archive: &mut ZipArchive<R>,
-) -> Option<FixedElement> {
+) -> Option<BackgroundImage> {
     let images: SlideImageMap = load_slide_images(layer_path, archive);
-    let asset = images.get(rid)?;
+    let asset = images.get(&fill.rid)?;
     let format = asset.format()?;
-    Some(FixedElement {
-        x: 0.0,
-        y: 0.0,
-        width: slide_size.width,
-        height: slide_size.height,
-        kind: FixedElementKind::Image(ImageData {
+    let tile_size: Option<(f64, f64)> = fill.tile_scale.and_then(|(sx, sy)| {
+        let (width_px, height_px) = image::ImageReader::new(Cursor::new(&asset.data))
+            .with_guessed_format()
+            .ok()?
+            .into_dimensions()
+            .ok()?;
+        let tile_width: f64 = f64::from(width_px) * 0.75 * sx.abs();
+        let tile_height: f64 = f64::from(height_px) * 0.75 * sy.abs();
+        (tile_width > 0.0 && tile_height > 0.0).then_some((tile_width, tile_height))
+    });
+    Some(BackgroundImage {
+        image: ImageData {
             data: asset.data.clone(),
             format,
             width: Some(slide_size.width),
@@ -561,7 +593,8 @@ fn build_background_image_element<R: Read + std::io::Seek>(
             alignment: None,
             clip_shape: None,
             shadow: None,
-        }),
+        },
+        tile_size,
     })
 }
 
@@ -569,7 +602,7 @@ fn build_background_image_element<R: Read + std::io::Seek>(
 
 /// True when the slide's root `<p:sld>` element carries `show="0"` or
 /// `show="false"` — PowerPoint omits such hidden slides from PDF export.
-fn is_hidden_slide(slide_xml: &str) -> bool {
+pub(super) fn is_hidden_slide(slide_xml: &str) -> bool {
     let mut reader: Reader<&[u8]> = Reader::from_str(slide_xml);
     loop {
         match reader.read_event() {
@@ -605,6 +638,10 @@ pub(super) fn parse_single_slide<R: Read + std::io::Seek>(
     }
 
     let slide_images: SlideImageMap = load_slide_images(slide_path, archive);
+    let slide_media: SlideMediaMap = load_slide_media(slide_path, archive);
+    let slide_ink: SlideInkMap = load_slide_ink(slide_path, archive);
+    let slide_workbooks: SlideWorkbookMap = load_slide_workbooks(slide_path, archive);
+    let slide_hyperlinks: SlideHyperlinkMap = load_slide_hyperlinks(slide_path, archive);
     let mut warnings: Vec<ConvertWarning> = Vec::new();
 
     let placeholder_geometry: PlaceholderGeometryMap = PlaceholderGeometryMap::build(
@@ -621,6 +658,10 @@ pub(super) fn parse_single_slide<R: Read + std::io::Seek>(
 
     let slide_ctx = SlideParseContext {
         images: &slide_images,
+        media: &slide_media,
+        ink: &slide_ink,
+        workbooks: &slide_workbooks,
+        hyperlinks: &slide_hyperlinks,
         theme,
         color_map: &chain.slide_color_map,
         warning_context: slide_label,
@@ -688,13 +729,10 @@ pub(super) fn parse_single_slide<R: Read + std::io::Seek>(
     ));
 
     let background: ResolvedBackground = resolve_slide_background(&chain, slide_path, theme);
-    if let Some((layer_path, rid)) = &background.image
-        && let Some(element) = build_background_image_element(layer_path, rid, slide_size, archive)
-    {
-        // Picture-fill backgrounds render as a full-page image behind
-        // everything else on the slide.
-        elements.insert(0, element);
-    }
+    let background_image: Option<BackgroundImage> =
+        background.image.as_ref().and_then(|(layer_path, fill)| {
+            build_background_image(layer_path, fill, slide_size, archive)
+        });
 
     Ok(Some((
         Page::Fixed(FixedPage {
@@ -702,6 +740,8 @@ pub(super) fn parse_single_slide<R: Read + std::io::Seek>(
             elements,
             background_color: background.color,
             background_gradient: background.gradient,
+            background_image,
+            source_slide: None,
         }),
         warnings,
     )))
@@ -741,6 +781,7 @@ fn select_picture_asset(
                 "unsupported image layer omitted: {}",
                 describe_assets(unsupported_layers)
             ),
+            location: None,
         });
     }
 
@@ -770,6 +811,7 @@ fn select_picture_asset(
                 "{warning_context} image omitted: {}",
                 describe_assets(omitted_assets)
             ),
+            location: None,
         });
     }
 
@@ -810,12 +852,53 @@ struct PictureState {
     ln_width_emu: i64,
     ln_color: Option<Color>,
     ln_dash_style: BorderLineStyle,
+    /// `<a:ln cmpd>` asks for more than one parallel stroke.
+    ln_compound: bool,
+    /// Set when the picture is the poster frame of a video/audio object.
+    media_kind: Option<PptxMediaKind>,
+    /// `r:link` of `<a:videoFile>`/`<a:audioFile>`/`<a:quickTimeFile>`.
+    media_link: Option<String>,
+    /// `r:embed` of `<p14:media>`, the embedded copy of the same file.
+    media_embed: Option<String>,
+    /// Click target from `<p:cNvPr><a:hlinkClick>`.
+    href: Option<String>,
+}
+
+/// Kind of media object a picture stands in for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PptxMediaKind {
+    Video,
+    Audio,
+}
+
+impl PptxMediaKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Video => "video",
+            Self::Audio => "audio",
+        }
+    }
 }
 
 impl PictureState {
     fn reset(&mut self) {
         *self = Self::default();
     }
+
+    /// Record a media reference from the picture's `<p:nvPr>`.
+    fn record_media(&mut self, e: &BytesStart<'_>) {
+        match e.local_name().as_ref() {
+            b"videoFile" | b"quickTimeFile" => {
+                self.media_kind = Some(PptxMediaKind::Video);
+                self.media_link = get_attr_str(e, b"r:link");
+            }
+            b"audioFile" => {
+                self.media_kind = Some(PptxMediaKind::Audio);
+                self.media_link = get_attr_str(e, b"r:link");
+            }
+            _ => self.media_embed = get_attr_str(e, b"r:embed"),
+        }
+    }
 }
 
 /// Accumulated state for a `<p:graphicFrame>` element.
@@ -852,6 +935,8 @@ struct ShapeState {
     flip_v: bool,
     opacity: Option<f64>,
     shadow: Option<Shadow>,
+    /// Glow, reflection, and soft edge from the shape's `<a:effectLst>`.
+    effects: ShapeEffects,
     in_sp_pr: bool,
     prst_geom: Option<String>,
     fill: Option<Color>,
@@ -861,6 +946,8 @@ struct ShapeState {
     ln_width_emu: i64,
     ln_color: Option<Color>,
     ln_dash_style: BorderLineStyle,
+    /// `<a:ln cmpd>` asks for more than one parallel stroke.
+    ln_compound: bool,
     /// Arrowhead at line start.
     head_end: ArrowHead,
     /// Arrowhead at line end.
@@ -878,6 +965,8 @@ struct ShapeState {
     style_font_color: Option<Color>,
     /// True when `<a:noFill/>` is explicitly set in `<p:spPr>`, preventing style fallback.
     explicit_no_fill: bool,
+    /// Click target from `<p:cNvPr><a:hlinkClick>`.
+    href: Option<String>,
 }
 
 impl Default for ShapeState {
@@ -897,6 +986,7 @@ impl Default for ShapeState {
             flip_v: false,
             opacity: None,
             shadow: None,
+            effects: ShapeEffects::default(),
             in_sp_pr: false,
             prst_geom: None,
             fill: None,
@@ -906,6 +996,7 @@ impl Default for ShapeState {
             ln_width_emu: 0,
             ln_color: None,
             ln_dash_style: BorderLineStyle::Solid,
+            ln_compound: false,
             head_end: ArrowHead::None,
             tail_end: ArrowHead::None,
             adj_values: Vec::new(),
@@ -914,6 +1005,7 @@ impl Default for ShapeState {
             style_fill_color: None,
             style_font_color: None,
             explicit_no_fill: false,
+            href: None,
         }
     }
 }
@@ -964,10 +1056,13 @@ fn finalize_shape(
         let blocks: Vec<Block> = group_pptx_text_blocks(std::mem::take(paragraphs));
         // Use explicit line color, falling back to style-based color from <p:style><a:lnRef>.
         let effective_ln_color: Option<Color> = shape.ln_color.or(shape.style_ln_color);
-        let stroke: Option<BorderSide> = effective_ln_color.map(|color| BorderSide {
-            width: effective_ln_width_pt,
-            color,
-            style: shape.ln_dash_style,
+        let stroke: Option<BorderSide> = effective_ln_color.map(|color| {
+            line_border_side(
+                effective_ln_width_pt,
+                color,
+                shape.ln_dash_style,
+                shape.ln_compound,
+            )
         });
         // For non-rectangular shapes with text, emit the shape background first,
         // then overlay a transparent text box. This ensures the geometry is rendered
@@ -1006,7 +1101,9 @@ fn finalize_shape(
                     rotation_deg: shape.rotation_deg,
                     opacity: shape.opacity,
                     shadow: shape.shadow.take(),
+                    effects: std::mem::take(&mut shape.effects),
                 }),
+                href: None,
             });
             // Transparent text overlay (no fill, no stroke).
             // Preset geometries confine text to an inset text rectangle we
@@ -1035,6 +1132,7 @@ fn finalize_shape(
                     auto_fit: text_box.auto_fit,
                     text_rotation_deg: text_box.text_rotation_deg,
                 }),
+                href: None,
             });
         } else {
             // Simple rectangular text box with fill/stroke directly on the block.
@@ -1055,6 +1153,7 @@ fn finalize_shape(
                     auto_fit: text_box.auto_fit,
                     text_rotation_deg: text_box.text_rotation_deg,
                 }),
+                href: None,
             });
         }
         elements
@@ -1073,10 +1172,13 @@ fn finalize_shape(
         );
         // Use explicit line color, falling back to style-based color from <p:style><a:lnRef>.
         let effective_ln_color: Option<Color> = shape.ln_color.or(shape.style_ln_color);
-        let stroke: Option<BorderSide> = effective_ln_color.map(|color| BorderSide {
-            width: effective_ln_width_pt,
-            color,
-            style: shape.ln_dash_style,
+        let stroke: Option<BorderSide> = effective_ln_color.map(|color| {
+            line_border_side(
+                effective_ln_width_pt,
+                color,
+                shape.ln_dash_style,
+                shape.ln_compound,
+            )
         });
         vec![FixedElement {
             x: emu_to_pt(shape.x),
@@ -1091,7 +1193,9 @@ fn finalize_shape(
                 rotation_deg: shape.rotation_deg,
                 opacity: shape.opacity,
                 shadow: shape.shadow.take(),
+                effects: std::mem::take(&mut shape.effects),
             }),
+            href: None,
         }]
     } else {
         Vec::new()
@@ -1111,10 +1215,13 @@ fn finalize_picture(
         pic.svg_blip_embed.as_deref(),
         &pic.img_layer_embeds,
     );
-    let stroke: Option<BorderSide> = pic.ln_color.map(|color| BorderSide {
-        width: emu_to_pt(pic.ln_width_emu),
-        color,
-        style: pic.ln_dash_style,
+    let stroke: Option<BorderSide> = pic.ln_color.map(|color| {
+        line_border_side(
+            emu_to_pt(pic.ln_width_emu),
+            color,
+            pic.ln_dash_style,
+            pic.ln_compound,
+        )
     });
     let element = selected_asset.and_then(|asset| {
         asset.format().map(|format| {
@@ -1156,12 +1263,123 @@ fn finalize_picture(
                     clip_shape,
                     shadow: pic.shadow.clone(),
                 }),
+                href: None,
             }
         })
     });
     (element, picture_warnings)
 }
 
+/// Fill behind the play badge when a media object has no poster frame.
+const MEDIA_PLACEHOLDER_FILL: Color = Color {
+    r: 0x40,
+    g: 0x40,
+    b: 0x40,
+};
+
+/// Play badge diameter as a fraction of the media frame's shorter side.
+const MEDIA_PLAY_BADGE_FRACTION: f64 = 0.3;
+
+/// Media cannot play in a PDF: keep the poster frame (or a dark placeholder
+/// when there is none), draw a play badge over it, and name the lost file.
+fn finalize_media_picture(
+    pic: &PictureState,
+    poster: Option<FixedElement>,
+    media: &SlideMediaMap,
+    warning_context: &str,
+) -> (Vec<FixedElement>, ConvertWarning) {
+    let kind: PptxMediaKind = pic.media_kind.unwrap_or(PptxMediaKind::Video);
+    let file_name: &str = pic
+        .media_embed
+        .iter()
+        .chain(pic.media_link.iter())
+        .find_map(|rid| media.get(rid))
+        .map(String::as_str)
+        .unwrap_or("unknown file");
+    let (x, y, width, height): (f64, f64, f64, f64) = (
+        emu_to_pt(pic.x),
+        emu_to_pt(pic.y),
+        emu_to_pt(pic.cx),
+        emu_to_pt(pic.cy),
+    );
+
+    let has_poster: bool = poster.is_some();
+    let mut elements: Vec<FixedElement> = Vec::with_capacity(3);
+    elements.push(poster.unwrap_or_else(|| FixedElement {
+        x,
+        y,
+        width,
+        height,
+        kind: FixedElementKind::Shape(media_overlay_shape(
+            ShapeKind::Rectangle,
+            MEDIA_PLACEHOLDER_FILL,
+            None,
+        )),
+        href: None,
+    }));
+
+    let diameter: f64 = width.min(height) * MEDIA_PLAY_BADGE_FRACTION;
+    if diameter > 0.0 {
+        let badge_x: f64 = x + (width - diameter) / 2.0;
+        let badge_y: f64 = y + (height - diameter) / 2.0;
+        elements.push(FixedElement {
+            x: badge_x,
+            y: badge_y,
+            width: diameter,
+            height: diameter,
+            kind: FixedElementKind::Shape(media_overlay_shape(
+                ShapeKind::Ellipse,
+                Color::black(),
+                Some(0.55),
+            )),
+            href: None,
+        });
+        // Triangle nudged right of center so it reads optically centered.
+        elements.push(FixedElement {
+            x: badge_x,
+            y: badge_y,
+            width: diameter,
+            height: diameter,
+            kind: FixedElementKind::Shape(media_overlay_shape(
+                ShapeKind::Polygon {
+                    vertices: vec![(0.38, 0.28), (0.38, 0.72), (0.74, 0.5)],
+                },
+                Color::white(),
+                None,
+            )),
+            href: None,
+        });
+    }
+
+    let rendered_as: &str = if has_poster {
+        "poster frame"
+    } else {
+        "placeholder"
+    };
+    let warning = ConvertWarning::PartialElement {
+        format: "PPTX".to_string(),
+        element: format!("{warning_context} {}", kind.label()),
+        detail: format!(
+            "{file_name} cannot play in PDF; rendered {rendered_as} with a play button"
+        ),
+        location: None,
+    };
+    (elements, warning)
+}
+
+fn media_overlay_shape(kind: ShapeKind, fill: Color, opacity: Option<f64>) -> Shape {
+    Shape {
+        kind,
+        fill: Some(fill),
+        gradient_fill: None,
+        stroke: None,
+        rotation_deg: None,
+        opacity,
+        shadow: None,
+        effects: ShapeEffects::default(),
+    }
+}
+
 /// Map a picture's preset geometry to a renderable clip shape
 /// (PowerPoint "crop to shape"); unsupported geometries clip nothing.
 fn picture_clip_shape(
@@ -1251,6 +1469,10 @@ fn apply_solid_fill_color(
 #[derive(Clone, Copy)]
 pub(super) struct SlideParseContext<'a> {
     pub(super) images: &'a SlideImageMap,
+    pub(super) media: &'a SlideMediaMap,
+    pub(super) ink: &'a SlideInkMap,
+    pub(super) workbooks: &'a SlideWorkbookMap,
+    pub(super) hyperlinks: &'a SlideHyperlinkMap,
     pub(super) theme: &'a ThemeData,
     pub(super) color_map: &'a ColorMapData,
     pub(super) warning_context: &'a str,
@@ -1258,6 +1480,49 @@ pub(super) struct SlideParseContext<'a> {
     pub(super) table_styles: &'a table_styles::TableStyleMap,
 }
 
+/// Read the `<a:off>`/`<a:ext>` frame (EMU) of a `<p:contentPart>`,
+/// consuming the element.
+fn parse_content_part_frame(reader: &mut Reader<&[u8]>) -> Option<(i64, i64, i64, i64)> {
+    let mut offset: Option<(i64, i64)> = None;
+    let mut extent: Option<(i64, i64)> = None;
+    let mut depth: usize = 1;
+    loop {
+        let e: BytesStart<'_> = match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                depth += 1;
+                e
+            }
+            Ok(Event::Empty(e)) => e,
+            Ok(Event::End(_)) => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+                continue;
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => continue,
+        };
+        match e.local_name().as_ref() {
+            // `<p:ext uri>` extension entries share the `ext` name but carry
+            // no size, so only a complete pair replaces the current value.
+            b"off" => {
+                offset = get_attr_i64(&e, b"x")
+                    .zip(get_attr_i64(&e, b"y"))
+                    .or(offset)
+            }
+            b"ext" => {
+                extent = get_attr_i64(&e, b"cx")
+                    .zip(get_attr_i64(&e, b"cy"))
+                    .or(extent)
+            }
+            _ => {}
+        }
+    }
+    let ((x, y), (cx, cy)) = offset.zip(extent)?;
+    Some((x, y, cx, cy))
+}
+
 /// Bundles the 20+ mutable state variables of the slide XML event loop
 /// into a single struct, with methods for each event type.
 ///
@@ -1308,6 +1573,8 @@ struct SlideXmlParser<'a> {
     in_run: bool,
     run_style: TextStyle,
     run_text: String,
+    /// Click target from the run's `<a:rPr><a:hlinkClick>`.
+    run_href: Option<String>,
 
     // ── Inline tracking flags ───────────────────────────────────────
     in_text: bool,
@@ -1316,7 +1583,6 @@ struct SlideXmlParser<'a> {
     /// later <a:ea>/<a:cs> in the same rPr does not override <a:latin>.
     rpr_applied_typeface: bool,
     in_end_para_rpr: bool,
-    in_text_line: bool,
     solid_fill_ctx: SolidFillCtx,
     /// Inside `<a:lnRef>` within `<p:style>` — for resolving fallback line color.
     in_style_ln_ref: bool,
@@ -1332,6 +1598,17 @@ struct SlideXmlParser<'a> {
     // ── Graphic frame state (`<p:graphicFrame>`) ────────────────────
     in_graphic_frame: bool,
     gf: GraphicFrameState,
+
+    // ── Alternate content state (`<mc:AlternateContent>`) ───────────
+    /// True once the current `<mc:AlternateContent>` drew its preferred
+    /// content (ink strokes, an embedded workbook table), so the
+    /// `<mc:Fallback>` picture of the same object is skipped.
+    choice_drawn: bool,
+
+    // ── Shape recovery ──────────────────────────────────────────────
+    /// Set by a handler when the current drawing object cannot be built,
+    /// so the parse loop drops that object instead of the whole slide.
+    shape_error: Option<String>,
 }
 
 impl<'a> SlideXmlParser<'a> {
@@ -1368,12 +1645,12 @@ impl<'a> SlideXmlParser<'a> {
             in_run: false,
             run_style: TextStyle::default(),
             run_text: String::new(),
+            run_href: None,
 
             in_text: false,
             in_rpr: false,
             rpr_applied_typeface: false,
             in_end_para_rpr: false,
-            in_text_line: false,
             solid_fill_ctx: SolidFillCtx::None,
             in_style_ln_ref: false,
             in_style_fill_ref: false,
@@ -1384,13 +1661,30 @@ impl<'a> SlideXmlParser<'a> {
 
             in_graphic_frame: false,
             gf: GraphicFrameState::default(),
+
+            choice_drawn: false,
+
+            shape_error: None,
         }
     }
 
+    /// A fresh parser over the rest of the slide XML after a drawing object
+    /// failed, keeping the finished elements and warnings but none of the
+    /// failed object's half-built state.
+    fn restart_at(self, xml: &'a str) -> Self {
+        let mut parser = Self::new(xml, self.ctx);
+        parser.skip_placeholders = self.skip_placeholders;
+        parser.placeholder_geometry = self.placeholder_geometry;
+        parser.elements = self.elements;
+        parser.warnings = self.warnings;
+        parser
+    }
+
     /// Handle an `Event::Start` element by trying each domain sub-handler in
     /// the original dispatch order.
     fn handle_start(&mut self, reader: &mut Reader<&[u8]>, e: &BytesStart<'_>) {
-        let _ = self.handle_start_frames_tables_groups(reader, e)
+        let _ = self.handle_hyperlink(e)
+            || self.handle_start_frames_tables_groups(reader, e)
             || self.handle_start_shape_tree(reader, e)
             || self.handle_start_text_body(reader, e)
             || self.handle_start_fill_colors_and_style_refs(reader, e)
@@ -1413,39 +1707,57 @@ impl<'a> SlideXmlParser<'a> {
                 self.in_graphic_frame = true;
                 self.gf.reset();
             }
-            b"xfrm" if self.in_graphic_frame && !self.in_shape => {
+            // An OLE object's preview `<p:pic>` carries its own `<a:xfrm>`.
+            b"xfrm" if self.in_graphic_frame && !self.in_shape && !self.in_pic => {
                 self.gf.in_xfrm = true;
             }
             b"tbl" if self.in_graphic_frame => {
-                if let Ok(mut table) = parse_pptx_table(
+                match parse_pptx_table(
                     reader,
                     self.ctx.theme,
                     self.ctx.color_map,
                     self.ctx.table_styles,
                 ) {
-                    scale_pptx_table_geometry_to_frame(
-                        &mut table,
-                        emu_to_pt(self.gf.cx),
-                        emu_to_pt(self.gf.cy),
-                    );
-                    // Fixed-position PPT tables have explicit row geometry from the slide frame.
-                    // Keeping Typst in content-driven mode compresses side panels like slide 30.
-                    table.use_content_driven_row_heights = false;
-                    self.elements.push(FixedElement {
-                        x: emu_to_pt(self.gf.x),
-                        y: emu_to_pt(self.gf.y),
-                        width: emu_to_pt(self.gf.cx),
-                        height: emu_to_pt(self.gf.cy),
-                        kind: FixedElementKind::Table(table),
-                    });
+                    Ok(mut table) => {
+                        scale_pptx_table_geometry_to_frame(
+                            &mut table,
+                            emu_to_pt(self.gf.cx),
+                            emu_to_pt(self.gf.cy),
+                        );
+                        // Fixed-position PPT tables have explicit row geometry from the slide frame.
+                        // Keeping Typst in content-driven mode compresses side panels like slide 30.
+                        table.use_content_driven_row_heights = false;
+                        self.elements.push(FixedElement {
+                            x: emu_to_pt(self.gf.x),
+                            y: emu_to_pt(self.gf.y),
+                            width: emu_to_pt(self.gf.cx),
+                            height: emu_to_pt(self.gf.cy),
+                            kind: FixedElementKind::Table(table),
+                            href: None,
+                        });
+                    }
+                    Err(error) => self.shape_error = Some(error.to_string()),
                 }
             }
+            b"AlternateContent" => {
+                self.choice_drawn = false;
+            }
+            b"oleObj" if self.in_graphic_frame && !self.in_pic => {
+                self.handle_ole_object(reader, e);
+            }
+            b"contentPart" if !self.in_shape && !self.in_pic && !self.in_graphic_frame => {
+                self.handle_content_part(reader, e);
+            }
+            b"Fallback" if self.choice_drawn => {
+                crate::parser::xml_util::skip_element(reader, b"Fallback");
+            }
             b"grpSp" if !self.in_shape && !self.in_pic && !self.in_graphic_frame => {
-                if let Ok((group_elems, group_warnings)) =
-                    parse_group_shape(reader, self.xml, &self.ctx)
-                {
-                    self.elements.extend(group_elems);
-                    self.warnings.extend(group_warnings);
+                match parse_group_shape(reader, self.xml, &self.ctx) {
+                    Ok((group_elems, group_warnings)) => {
+                        self.elements.extend(group_elems);
+                        self.warnings.extend(group_warnings);
+                    }
+                    Err(error) => self.shape_error = Some(error.to_string()),
                 }
             }
             _ => return false,
@@ -1453,6 +1765,48 @@ impl<'a> SlideXmlParser<'a> {
         true
     }
 
+    /// Draw the ink of a `<p:contentPart>` over its `<p14:xfrm>` frame.
+    /// Render an embedded Excel workbook as a table over the object frame.
+    /// The `<p:oleObj>` (and its cached preview picture) is consumed only
+    /// when the workbook parsed; otherwise the preview is drawn as before.
+    fn handle_ole_object(&mut self, reader: &mut Reader<&[u8]>, e: &BytesStart<'_>) {
+        let Some(preview) = get_attr_str(e, b"r:id").and_then(|rid| self.ctx.workbooks.get(&rid))
+        else {
+            return;
+        };
+        let mut table: Table = preview.clone();
+        let width: f64 = emu_to_pt(self.gf.cx);
+        let height: f64 = emu_to_pt(self.gf.cy);
+        crate::parser::xlsx::scale_embedded_preview(&mut table, width, height);
+        self.elements.push(FixedElement {
+            x: emu_to_pt(self.gf.x),
+            y: emu_to_pt(self.gf.y),
+            width,
+            height,
+            kind: FixedElementKind::Table(table),
+            href: None,
+        });
+        self.choice_drawn = true;
+        crate::parser::xml_util::skip_element(reader, b"oleObj");
+    }
+
+    fn handle_content_part(&mut self, reader: &mut Reader<&[u8]>, e: &BytesStart<'_>) {
+        let rid: Option<String> = get_attr_str(e, b"r:id");
+        let frame: Option<(i64, i64, i64, i64)> = parse_content_part_frame(reader);
+        if let Some(drawing) = rid.and_then(|rid| self.ctx.ink.get(&rid))
+            && let Some((x, y, cx, cy)) = frame
+        {
+            self.elements.extend(inkml::ink_elements(
+                drawing,
+                emu_to_pt(x),
+                emu_to_pt(y),
+                emu_to_pt(cx),
+                emu_to_pt(cy),
+            ));
+            self.choice_drawn = true;
+        }
+    }
+
     /// Shape (`sp`/`cxnSp`) tree: geometry, fills, outline, placeholders (plus picture geometry arms that share this dispatch range).
     ///
     /// Returns `true` when the element was dispatched here. The sub-handlers
@@ -1491,7 +1845,8 @@ impl<'a> SlideXmlParser<'a> {
                 self.pic.in_prst_geom = true;
             }
             b"effectLst" if self.in_pic && self.pic.in_sp_pr => {
-                self.pic.shadow = parse_effect_list(reader, self.ctx.theme, self.ctx.color_map);
+                self.pic.shadow =
+                    parse_effect_list(reader, self.ctx.theme, self.ctx.color_map).shadow;
             }
             b"gd" if self.in_pic && self.pic.in_prst_geom => {
                 if self.pic.prst_adj.is_none()
@@ -1527,7 +1882,20 @@ impl<'a> SlideXmlParser<'a> {
                 }
             }
             b"effectLst" if self.shape.in_sp_pr && !self.shape.in_ln => {
-                self.shape.shadow = parse_effect_list(reader, self.ctx.theme, self.ctx.color_map);
+                let parsed_effects = parse_effect_list(reader, self.ctx.theme, self.ctx.color_map);
+                self.shape.shadow = parsed_effects.shadow;
+                self.shape.effects = ShapeEffects {
+                    rotation_3d: self.shape.effects.rotation_3d,
+                    extrusion: self.shape.effects.extrusion.take(),
+                    ..parsed_effects.effects
+                };
+            }
+            b"scene3d" if self.shape.in_sp_pr => {
+                self.shape.effects.rotation_3d = parse_scene3d(reader);
+            }
+            b"sp3d" if self.shape.in_sp_pr => {
+                self.shape.effects.extrusion =
+                    parse_sp3d(reader, e, self.ctx.theme, self.ctx.color_map);
             }
             b"extLst" if self.shape.in_sp_pr && !self.in_txbody => {
                 // Office extension payloads such as a16:hiddenLine are not visible shape
@@ -1539,6 +1907,7 @@ impl<'a> SlideXmlParser<'a> {
                 self.shape.in_ln = true;
                 self.shape.ln_width_emu = get_attr_i64(e, b"w").unwrap_or(12700);
                 self.shape.ln_dash_style = BorderLineStyle::Solid;
+                self.shape.ln_compound = is_compound_line(e);
             }
             b"prstDash" if self.shape.in_ln => {
                 self.shape.ln_dash_style = get_attr_str(e, b"val")
@@ -1570,6 +1939,15 @@ impl<'a> SlideXmlParser<'a> {
         true
     }
 
+    /// Style targeted by the open `<a:rPr>`, or else the open `<a:endParaRPr>`.
+    fn active_rpr_style_mut(&mut self) -> &mut TextStyle {
+        if self.in_rpr {
+            &mut self.run_style
+        } else {
+            &mut self.para_end_run_style
+        }
+    }
+
     /// Text body: paragraphs, spacing, bullets, runs, and run properties.
     ///
     /// Returns `true` when the element was dispatched here. The sub-handlers
@@ -1580,6 +1958,16 @@ impl<'a> SlideXmlParser<'a> {
         match local.as_ref() {
             b"txBody" if self.in_shape => {
                 self.in_txbody = true;
+                // Placeholder insets and anchoring start from the layout and
+                // master placeholders; the slide's own `<a:bodyPr>` follows.
+                if self.shape.has_placeholder
+                    && let Some(map) = self.placeholder_geometry
+                {
+                    self.text_box = map.text_box_settings(
+                        self.shape.ph_type.as_deref(),
+                        self.shape.ph_idx.as_deref(),
+                    );
+                }
                 self.text_body_style_defaults = if self.shape.has_placeholder {
                     // Placeholder text stacks the master txStyles bucket and
                     // the matching master/layout placeholder list styles.
@@ -1662,6 +2050,20 @@ impl<'a> SlideXmlParser<'a> {
             b"spcPts" if self.in_spc_aft => {
                 extract_pptx_space_points(e, &mut self.para_style.space_after);
             }
+            b"spcPct" if self.in_spc_bef => {
+                extract_pptx_space_percent(
+                    e,
+                    self.para_default_run_style.font_size,
+                    &mut self.para_style.space_before,
+                );
+            }
+            b"spcPct" if self.in_spc_aft => {
+                extract_pptx_space_percent(
+                    e,
+                    self.para_default_run_style.font_size,
+                    &mut self.para_style.space_after,
+                );
+            }
             b"buAutoNum" if self.in_para && !self.in_run => {
                 self.para_bullet_definition.kind = Some(PptxBulletKind::AutoNumber(
                     parse_pptx_auto_numbering(e, self.para_level),
@@ -1711,6 +2113,7 @@ impl<'a> SlideXmlParser<'a> {
                 self.in_run = true;
                 self.run_style = self.para_default_run_style.clone();
                 self.run_text.clear();
+                self.run_href = None;
             }
             b"rPr" if self.in_run => {
                 self.in_rpr = true;
@@ -1724,12 +2127,21 @@ impl<'a> SlideXmlParser<'a> {
                 extract_rpr_attributes(e, &mut self.para_end_run_style);
             }
             b"ln" if self.in_rpr || self.in_end_para_rpr => {
-                self.in_text_line = true;
-            }
-            b"solidFill" if self.in_rpr && !self.in_text_line => {
+                // Consumes the whole `<a:ln>`, so its fill never reaches the
+                // run-color arms below.
+                let outline: Option<TextOutline> =
+                    parse_text_outline(reader, e, self.ctx.theme, self.ctx.color_map);
+                self.active_rpr_style_mut().outline = outline;
+            }
+            b"effectLst" if self.in_rpr || self.in_end_para_rpr => {
+                let shadow: Option<Shadow> =
+                    parse_effect_list(reader, self.ctx.theme, self.ctx.color_map).shadow;
+                self.active_rpr_style_mut().shadow = shadow;
+            }
+            b"solidFill" if self.in_rpr => {
                 self.solid_fill_ctx = SolidFillCtx::RunFill;
             }
-            b"solidFill" if self.in_end_para_rpr && !self.in_text_line => {
+            b"solidFill" if self.in_end_para_rpr => {
                 self.solid_fill_ctx = SolidFillCtx::EndParaFill;
             }
             _ => return false,
@@ -1822,6 +2234,7 @@ impl<'a> SlideXmlParser<'a> {
                 self.pic.in_ln = true;
                 self.pic.ln_width_emu = get_attr_i64(e, b"w").unwrap_or(12700);
                 self.pic.ln_dash_style = BorderLineStyle::Solid;
+                self.pic.ln_compound = is_compound_line(e);
             }
             b"solidFill" if self.in_pic && self.pic.in_ln => {
                 self.solid_fill_ctx = SolidFillCtx::PicLineFill;
@@ -1852,6 +2265,9 @@ impl<'a> SlideXmlParser<'a> {
             b"srcRect" if self.in_pic => {
                 self.pic.crop = parse_src_rect(e);
             }
+            b"videoFile" | b"quickTimeFile" | b"audioFile" | b"media" if self.in_pic => {
+                self.pic.record_media(e);
+            }
             _ => return false,
         }
         true
@@ -1860,12 +2276,33 @@ impl<'a> SlideXmlParser<'a> {
     /// Handle an `Event::Empty` element by trying each domain sub-handler in
     /// the original dispatch order.
     fn handle_empty(&mut self, e: &BytesStart<'_>) {
-        let _ = self.handle_empty_geometry_and_picture(e)
+        let _ = self.handle_hyperlink(e)
+            || self.handle_empty_geometry_and_picture(e)
             || self.handle_empty_shape_props(e)
             || self.handle_empty_fill_colors_and_style_refs(e)
             || self.handle_empty_text_body(e);
     }
 
+    /// `<a:hlinkClick>` on a run, a shape, or a picture. It is usually
+    /// self-closing but may carry a `<a:snd>` child, so both event kinds land
+    /// here.
+    ///
+    /// Returns `true` when the element was dispatched here.
+    fn handle_hyperlink(&mut self, e: &BytesStart<'_>) -> bool {
+        if e.local_name().as_ref() != b"hlinkClick" {
+            return false;
+        }
+        let href: Option<String> = resolve_hlink_click(e, self.ctx.hyperlinks);
+        if self.in_rpr {
+            self.run_href = href;
+        } else if self.in_pic {
+            self.pic.href = href;
+        } else if self.in_shape && !self.in_txbody {
+            self.shape.href = href;
+        }
+        true
+    }
+
     /// Transform offsets/extents and self-closing picture attributes.
     ///
     /// Returns `true` when the element was dispatched here (same contiguous
@@ -1916,6 +2353,9 @@ impl<'a> SlideXmlParser<'a> {
             b"srcRect" if self.in_pic => {
                 self.pic.crop = parse_src_rect(e);
             }
+            b"videoFile" | b"quickTimeFile" | b"audioFile" | b"media" if self.in_pic => {
+                self.pic.record_media(e);
+            }
             b"prstDash" if self.in_pic && self.pic.in_ln => {
                 self.pic.ln_dash_style = get_attr_str(e, b"val")
                     .as_deref()
@@ -1945,6 +2385,12 @@ impl<'a> SlideXmlParser<'a> {
                 self.pic.ph_type = get_attr_str(e, b"type");
                 self.pic.ph_idx = get_attr_str(e, b"idx");
             }
+            // Handle self-closing <a:sp3d extrusionH="..."/> (default side color).
+            b"sp3d" if self.shape.in_sp_pr => {
+                let depth: f64 = emu_to_pt(get_attr_i64(e, b"extrusionH").unwrap_or(0));
+                self.shape.effects.extrusion =
+                    (depth > 0.0).then_some(Extrusion { depth, color: None });
+            }
             // Handle self-closing <a:bodyPr anchor="ctr"/> (no child elements).
             b"bodyPr" if self.in_shape && self.in_txbody => {
                 extract_pptx_text_box_body_props(e, &mut self.text_box);
@@ -1974,6 +2420,7 @@ impl<'a> SlideXmlParser<'a> {
             }
             b"ln" if self.shape.in_sp_pr => {
                 self.shape.ln_width_emu = get_attr_i64(e, b"w").unwrap_or(12700);
+                self.shape.ln_compound = is_compound_line(e);
             }
             b"prstDash" if self.shape.in_ln => {
                 self.shape.ln_dash_style = get_attr_str(e, b"val")
@@ -2056,8 +2503,15 @@ impl<'a> SlideXmlParser<'a> {
                 self.para_end_run_style = self.para_default_run_style.clone();
                 extract_rpr_attributes(e, &mut self.para_end_run_style);
             }
-            b"ln" if self.in_rpr || self.in_end_para_rpr => {
-                self.in_text_line = true;
+            b"ln" | b"effectLst" if self.in_rpr || self.in_end_para_rpr => {
+                // A fill-less `<a:ln/>` or an empty `<a:effectLst/>` turns off
+                // an inherited outline or shadow.
+                let style: &mut TextStyle = self.active_rpr_style_mut();
+                if e.local_name().as_ref() == b"ln" {
+                    style.outline = None;
+                } else {
+                    style.shadow = None;
+                }
             }
             b"pPr" if self.in_para && !self.in_run => {
                 self.para_level = extract_paragraph_level(e);
@@ -2094,6 +2548,20 @@ impl<'a> SlideXmlParser<'a> {
             b"spcPts" if self.in_spc_aft => {
                 extract_pptx_space_points(e, &mut self.para_style.space_after);
             }
+            b"spcPct" if self.in_spc_bef => {
+                extract_pptx_space_percent(
+                    e,
+                    self.para_default_run_style.font_size,
+                    &mut self.para_style.space_before,
+                );
+            }
+            b"spcPct" if self.in_spc_aft => {
+                extract_pptx_space_percent(
+                    e,
+                    self.para_default_run_style.font_size,
+                    &mut self.para_style.space_after,
+                );
+            }
             b"buAutoNum" if self.in_para && !self.in_run => {
                 self.para_bullet_definition.kind = Some(PptxBulletKind::AutoNumber(
                     parse_pptx_auto_numbering(e, self.para_level),
@@ -2202,12 +2670,19 @@ impl<'a> SlideXmlParser<'a> {
                         self.shape.cy = geometry.cy;
                     }
                     if !(self.skip_placeholders && self.shape.has_placeholder) {
-                        self.elements.extend(finalize_shape(
+                        let href: Option<String> = self.shape.href.take();
+                        let mut shape_elements: Vec<FixedElement> = finalize_shape(
                             &mut self.shape,
                             &mut self.paragraphs,
                             self.text_box,
                             &self.ctx.theme.line_style_widths,
-                        ));
+                        );
+                        // Background and text overlay share one frame; a
+                        // single link area on the topmost avoids duplicates.
+                        if let Some(top) = shape_elements.last_mut() {
+                            top.href = href;
+                        }
+                        self.elements.extend(shape_elements);
                     }
                     self.in_shape = false;
                 }
@@ -2260,7 +2735,7 @@ impl<'a> SlideXmlParser<'a> {
                         Run {
                             text: std::mem::take(&mut self.run_text),
                             style: self.run_style.clone(),
-                            href: None,
+                            href: self.run_href.clone(),
                             footnote: None,
                         },
                     );
@@ -2273,9 +2748,6 @@ impl<'a> SlideXmlParser<'a> {
             b"endParaRPr" if self.in_end_para_rpr => {
                 self.in_end_para_rpr = false;
             }
-            b"ln" if self.in_text_line => {
-                self.in_text_line = false;
-            }
             b"lnSpc" if self.in_ln_spc => {
                 self.in_ln_spc = false;
             }
@@ -2334,10 +2806,22 @@ impl<'a> SlideXmlParser<'a> {
                     self.pic.cx = geometry.cx;
                     self.pic.cy = geometry.cy;
                 }
-                let (element, picture_warnings) =
+                let (mut element, picture_warnings) =
                     finalize_picture(&self.pic, self.ctx.images, self.ctx.warning_context);
                 self.warnings.extend(picture_warnings);
-                if let Some(element) = element {
+                if let Some(ref mut element) = element {
+                    element.href = self.pic.href.clone();
+                }
+                if self.pic.media_kind.is_some() {
+                    let (media_elements, media_warning) = finalize_media_picture(
+                        &self.pic,
+                        element,
+                        self.ctx.media,
+                        self.ctx.warning_context,
+                    );
+                    self.elements.extend(media_elements);
+                    self.warnings.push(media_warning);
+                } else if let Some(element) = element {
                     self.elements.push(element);
                 }
                 self.in_pic = false;
@@ -2382,6 +2866,103 @@ pub(super) fn parse_slide_xml<'a>(
     parse_slide_xml_inner(xml, ctx, false, placeholder_geometry)
 }
 
+/// Top-level drawing objects that fail in isolation: a broken one is
+/// dropped with a warning while the rest of the slide still renders.
+const RECOVERABLE_SHAPE_TAGS: [&[u8]; 5] = [b"sp", b"cxnSp", b"pic", b"graphicFrame", b"grpSp"];
+
+/// The outermost drawing object being parsed, with what is needed to undo
+/// its partial output and to resume after its closing tag.
+struct ShapeGuard {
+    local_name: Vec<u8>,
+    /// Raw closing tag (e.g. `</p:sp>`) for resyncing on broken XML.
+    closing_tag: String,
+    /// Open elements of the same name, to recognize the guard's own end.
+    depth: usize,
+    /// Byte offset of the start tag in the slide XML.
+    offset: usize,
+    /// 1-based position among the drawing objects of this shape tree.
+    ordinal: usize,
+    elements_len: usize,
+    warnings_len: usize,
+    /// `<p:cNvPr id name>`, as shown in PowerPoint's selection pane.
+    id: Option<String>,
+    name: Option<String>,
+}
+
+impl ShapeGuard {
+    fn new(e: &BytesStart<'_>, offset: usize, ordinal: usize, parser: &SlideXmlParser<'_>) -> Self {
+        Self {
+            local_name: e.local_name().as_ref().to_vec(),
+            closing_tag: format!("</{}>", String::from_utf8_lossy(e.name().as_ref())),
+            depth: 1,
+            offset,
+            ordinal,
+            elements_len: parser.elements.len(),
+            warnings_len: parser.warnings.len(),
+            id: None,
+            name: None,
+        }
+    }
+
+    fn record_non_visual_props(&mut self, e: &BytesStart<'_>) {
+        if e.local_name().as_ref() == b"cNvPr" && self.id.is_none() {
+            self.id = get_attr_str(e, b"id");
+            self.name = get_attr_str(e, b"name").filter(|name| !name.is_empty());
+        }
+    }
+
+    fn describe(&self, warning_context: &str) -> String {
+        let tag: String = String::from_utf8_lossy(&self.local_name).into_owned();
+        let label: String = format!("{warning_context} {tag} #{}", self.ordinal);
+        match (&self.id, &self.name) {
+            (Some(id), Some(name)) => format!("{label} (id {id} \"{name}\")"),
+            (Some(id), None) => format!("{label} (id {id})"),
+            _ => label,
+        }
+    }
+
+    /// Byte offset just past the object's closing tag, if the rest of the
+    /// XML still contains it. Scans the raw text because the reader cannot be
+    /// trusted after an XML error; same-name tags nest (`<p:grpSp>`).
+    fn resume_offset(&self, xml: &str) -> Option<usize> {
+        // `</p:sp>` -> `<p:sp`
+        let open_tag: String = format!("<{}", &self.closing_tag[2..self.closing_tag.len() - 1]);
+        let mut depth: usize = 0;
+        let mut cursor: usize = self.offset;
+        loop {
+            let rest: &str = &xml[cursor..];
+            let close: usize = rest.find(&self.closing_tag)?;
+            let open: Option<usize> = rest[..close].match_indices(&open_tag).find_map(|(at, _)| {
+                let after: &str = &rest[at + open_tag.len()..];
+                let is_start_tag: bool = after.starts_with(|c: char| c == '>' || c.is_whitespace())
+                    && !after[..after.find('>')?].ends_with('/');
+                is_start_tag.then_some(at)
+            });
+            match open {
+                Some(at) => {
+                    depth += 1;
+                    cursor += at + open_tag.len();
+                }
+                None => {
+                    depth = depth.saturating_sub(1);
+                    cursor += close + self.closing_tag.len();
+                    if depth == 0 {
+                        return Some(cursor);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Reader over the slide XML from a resync point, which starts inside
+/// already-open elements whose end tags it will see unpaired.
+fn resumed_reader(xml: &str) -> Reader<&[u8]> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().check_end_names = false;
+    reader
+}
+
 fn parse_slide_xml_inner<'a>(
     xml: &'a str,
     ctx: &SlideParseContext<'a>,
@@ -2392,36 +2973,113 @@ fn parse_slide_xml_inner<'a>(
     let mut parser = SlideXmlParser::new(xml, *ctx);
     parser.skip_placeholders = skip_placeholders;
     parser.placeholder_geometry = placeholder_geometry;
+    // Offset of `reader`'s input within `xml`; nonzero after a resync.
+    let mut base: usize = 0;
+    let mut guard: Option<ShapeGuard> = None;
+    let mut shape_count: usize = 0;
 
     loop {
-        match reader.read_event() {
+        let event_offset: usize = base + reader.buffer_position() as usize;
+        let event = reader.read_event();
+
+        // Track the outermost drawing object so a failure anywhere inside
+        // it costs only that object.
+        let mut closes_guard: bool = false;
+        let mut opened: Option<ShapeGuard> = None;
+        match (&event, guard.as_mut()) {
+            (Ok(Event::Start(e)), None)
+                if RECOVERABLE_SHAPE_TAGS.contains(&e.local_name().as_ref()) =>
+            {
+                shape_count += 1;
+                opened = Some(ShapeGuard::new(e, event_offset, shape_count, &parser));
+            }
+            (Ok(Event::Start(e)), Some(open)) => {
+                if e.local_name().as_ref() == open.local_name {
+                    open.depth += 1;
+                }
+                open.record_non_visual_props(e);
+            }
+            (Ok(Event::Empty(e)), Some(open)) => open.record_non_visual_props(e),
+            (Ok(Event::End(e)), Some(open)) if e.local_name().as_ref() == open.local_name => {
+                open.depth -= 1;
+                closes_guard = open.depth == 0;
+            }
+            _ => {}
+        }
+        if let Some(opened) = opened {
+            // Group handlers consume the whole `<p:grpSp>` subtree.
+            closes_guard = opened.local_name == b"grpSp";
+            guard = Some(opened);
+        }
+
+        let step = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| match event {
             Ok(Event::Start(ref e)) => {
                 parser.handle_start(&mut reader, e);
+                Ok(true)
             }
             Ok(Event::Empty(ref e)) => {
                 parser.handle_empty(e);
+                Ok(true)
             }
             Ok(Event::Text(ref t)) => {
                 if let Some(text) = decode_pptx_text_event(t) {
                     parser.handle_text(&text);
                 }
+                Ok(true)
             }
             Ok(Event::GeneralRef(ref reference)) => {
                 if let Some(text) = decode_pptx_general_ref(reference) {
                     parser.handle_text(&text);
                 }
+                Ok(true)
             }
             Ok(Event::End(ref e)) => {
                 parser.handle_end(e.local_name().as_ref());
-            }
-            Ok(Event::Eof) => break,
-            Err(error) => {
-                return Err(crate::parser::parse_err(format!(
-                    "XML error in slide: {error}"
-                )));
-            }
-            _ => {}
-        }
+                Ok(true)
+            }
+            Ok(Event::Eof) => Ok(false),
+            Err(error) => Err(format!("XML error in slide: {error}")),
+            _ => Ok(true),
+        }));
+        let failure: Option<String> = match step {
+            Ok(Ok(true)) => parser.shape_error.take(),
+            Ok(Ok(false)) if guard.is_some() => Some("slide XML ends inside the object".into()),
+            Ok(Ok(false)) => break,
+            Ok(Err(message)) => Some(message),
+            Err(panic) if guard.is_some() => Some(format!(
+                "parser panicked: {}",
+                crate::parser::extract_panic_message(&panic)
+            )),
+            Err(panic) => std::panic::resume_unwind(panic),
+        };
+
+        let Some(detail) = failure else {
+            if closes_guard {
+                guard = None;
+            }
+            continue;
+        };
+        let Some(failed) = guard.take() else {
+            return Err(crate::parser::parse_err(detail));
+        };
+        // Drop whatever the object emitted before failing, then resync the
+        // reader just past its closing tag.
+        parser.elements.truncate(failed.elements_len);
+        parser.warnings.truncate(failed.warnings_len);
+        parser.warnings.push(ConvertWarning::ParseSkipped {
+            format: "PPTX".to_string(),
+            reason: format!(
+                "{} failed to parse and was skipped: {detail}",
+                failed.describe(parser.ctx.warning_context)
+            ),
+            location: None,
+        });
+        let Some(resume) = failed.resume_offset(xml) else {
+            break;
+        };
+        base = resume;
+        reader = resumed_reader(&xml[resume..]);
+        parser = parser.restart_at(&xml[resume..]);
     }
 
     Ok(parser.finish())
@@ -179,3 +179,29 @@ fn test_slide_without_smartart_no_smartart_elements() {
         .count();
     assert_eq!(smartart_count, 0);
 }
+
+#[test]
+fn test_smartart_layout_from_data_model_type_id() {
+    let smartart_frame =
+        make_smartart_graphic_frame(914_400, 1_828_800, 5_486_400, 3_086_100, "rId5");
+    let slide_xml = make_slide_xml(&[smartart_frame]);
+    let data_xml = make_smartart_data_xml(&["Plan", "Do", "Check", "Act"]).replacen(
+        "<dgm:prSet/>",
+        r#"<dgm:prSet loTypeId="urn:microsoft.com/office/officeart/2005/8/layout/cycle2"/>"#,
+        1,
+    );
+    let data = build_test_pptx_with_smartart(SLIDE_CX, SLIDE_CY, &slide_xml, "rId5", &data_xml);
+
+    let (doc, warnings) = PptxParser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let page = first_fixed_page(&doc);
+    assert_eq!(
+        get_smartart(&page.elements[0]).layout,
+        SmartArtLayout::Cycle
+    );
+    assert!(warnings.iter().any(|warning| matches!(
+        warning,
+        ConvertWarning::FallbackUsed { from, to, .. }
+            if from == "SmartArt diagram" && to == "approximate cycle diagram"
+    )));
+}
@@ -163,6 +163,9 @@ fn test_apply_table_style_first_row_gets_header_fill_and_text_color() {
                     spill_width: None,
                     vertical_align: None,
                     padding: None,
+                    no_wrap: false,
+                    shrink_to_fit: false,
+                    text_rotation: None,
                 }],
                 height: Some(30.0),
             },
@@ -187,6 +190,9 @@ fn test_apply_table_style_first_row_gets_header_fill_and_text_color() {
                     spill_width: None,
                     vertical_align: None,
                     padding: None,
+                    no_wrap: false,
+                    shrink_to_fit: false,
+                    text_rotation: None,
                 }],
                 height: Some(30.0),
             },
@@ -260,6 +266,9 @@ fn test_apply_table_style_banded_rows_skip_first_row() {
                 spill_width: None,
                 vertical_align: None,
                 padding: None,
+                no_wrap: false,
+                shrink_to_fit: false,
+                text_rotation: None,
             }],
             height: Some(30.0),
         }
@@ -340,6 +349,9 @@ fn test_apply_table_style_explicit_cell_fill_not_overridden() {
                 spill_width: None,
                 vertical_align: None,
                 padding: None,
+                no_wrap: false,
+                shrink_to_fit: false,
+                text_rotation: None,
             }],
             height: Some(30.0),
         }],
@@ -382,6 +394,9 @@ fn test_apply_table_style_missing_style_id_is_noop() {
                 spill_width: None,
                 vertical_align: None,
                 padding: None,
+                no_wrap: false,
+                shrink_to_fit: false,
+                text_rotation: None,
             }],
             height: Some(30.0),
         }],
@@ -750,3 +765,121 @@ fn test_parse_region_borders_from_tc_bdr() {
         "fill still parsed alongside borders"
     );
 }
+
+#[test]
+fn test_parse_table_style_column_bands_and_direct_text_color() {
+    let body = r#"<a:band1V><a:tcStyle><a:fill><a:solidFill><a:srgbClr val="CCDDEE"/></a:solidFill></a:fill></a:tcStyle></a:band1V><a:lastCol><a:tcTxStyle b="on"><a:schemeClr val="accent1"/></a:tcTxStyle><a:tcStyle/></a:lastCol>"#;
+    let xml = make_table_style_xml(&[("colstyle", body)]);
+    let styles = table_styles::parse_table_styles_xml(&xml, &test_theme(), &test_color_map());
+    let def = &styles["colstyle"];
+
+    assert_eq!(
+        def.band1_v.as_ref().unwrap().fill,
+        Some(Color::new(0xCC, 0xDD, 0xEE))
+    );
+    let last_col = def.last_col.as_ref().unwrap();
+    assert_eq!(last_col.text_color, Some(Color::new(0x44, 0x72, 0xC4)));
+    assert_eq!(last_col.text_bold, Some(true));
+}
+
+#[test]
+fn test_apply_table_style_layers_regions_in_powerpoint_order() {
+    let mut styles: TableStyleMap = HashMap::new();
+    styles.insert(
+        "layered".to_string(),
+        PptxTableStyleDef {
+            whole_table: Some(TableCellRegionStyle {
+                fill: Some(Color::new(0xEE, 0xEE, 0xEE)),
+                text_color: Some(Color::new(0x11, 0x11, 0x11)),
+                ..TableCellRegionStyle::default()
+            }),
+            band1_v: Some(TableCellRegionStyle {
+                fill: Some(Color::new(0xCC, 0xCC, 0xFF)),
+                ..TableCellRegionStyle::default()
+            }),
+            first_row: Some(TableCellRegionStyle {
+                text_bold: Some(true),
+                ..TableCellRegionStyle::default()
+            }),
+            ..PptxTableStyleDef::default()
+        },
+    );
+    let props = PptxTableProps {
+        style_id: Some("layered".to_string()),
+        first_row: true,
+        band_col: true,
+        ..PptxTableProps::default()
+    };
+    let text_cell = |text: &str| TableCell {
+        content: vec![Block::Paragraph(Paragraph {
+            style: ParagraphStyle::default(),
+            runs: vec![Run {
+                text: text.to_string(),
+                style: TextStyle::default(),
+                href: None,
+                footnote: None,
+            }],
+        })],
+        ..TableCell::default()
+    };
+    let list_cell = TableCell {
+        content: vec![Block::List(List {
+            kind: ListKind::Unordered,
+            items: vec![ListItem {
+                content: vec![Paragraph {
+                    style: ParagraphStyle::default(),
+                    runs: vec![Run {
+                        text: "Bullet".to_string(),
+                        style: TextStyle::default(),
+                        href: None,
+                        footnote: None,
+                    }],
+                }],
+                level: 0,
+                start_at: None,
+            }],
+            level_styles: Default::default(),
+        })],
+        ..TableCell::default()
+    };
+    let mut table = Table {
+        rows: vec![
+            TableRow {
+                cells: vec![text_cell("H1"), text_cell("H2")],
+                height: None,
+            },
+            TableRow {
+                cells: vec![list_cell, text_cell("D2")],
+                height: None,
+            },
+        ],
+        column_widths: vec![100.0, 100.0],
+        ..Table::default()
+    };
+
+    table_styles::apply_table_style(&mut table, &props, &styles);
+
+    let first_run = |cell: &TableCell| -> TextStyle {
+        match &cell.content[0] {
+            Block::Paragraph(paragraph) => paragraph.runs[0].style.clone(),
+            Block::List(list) => list.items[0].content[0].runs[0].style.clone(),
+            other => panic!("Expected paragraph or list, got {other:?}"),
+        }
+    };
+    // The header inherits the wholeTbl text color and the column band fill
+    // underneath its own bold emphasis.
+    let header = &table.rows[0].cells[0];
+    assert_eq!(header.background, Some(Color::new(0xCC, 0xCC, 0xFF)));
+    assert_eq!(first_run(header).bold, Some(true));
+    assert_eq!(first_run(header).color, Some(Color::new(0x11, 0x11, 0x11)));
+    // Column banding alternates band1V / wholeTbl.
+    assert_eq!(
+        table.rows[1].cells[1].background,
+        Some(Color::new(0xEE, 0xEE, 0xEE))
+    );
+    // Bulleted cell text picks up the style's text color too.
+    assert_eq!(
+        first_run(&table.rows[1].cells[0]).color,
+        Some(Color::new(0x11, 0x11, 0x11))
+    );
+}
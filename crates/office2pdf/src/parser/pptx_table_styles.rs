@@ -29,6 +29,8 @@ pub(super) struct PptxTableStyleDef {
     pub(super) whole_table: Option<TableCellRegionStyle>,
     pub(super) band1_h: Option<TableCellRegionStyle>,
     pub(super) band2_h: Option<TableCellRegionStyle>,
+    pub(super) band1_v: Option<TableCellRegionStyle>,
+    pub(super) band2_v: Option<TableCellRegionStyle>,
     pub(super) first_row: Option<TableCellRegionStyle>,
     pub(super) last_row: Option<TableCellRegionStyle>,
     pub(super) first_col: Option<TableCellRegionStyle>,
@@ -87,6 +89,14 @@ pub(super) fn parse_table_styles_xml(
                     current_def.band2_h =
                         Some(parse_region_style(&mut reader, b"band2H", theme, color_map));
                 }
+                b"band1V" if current_style_id.is_some() => {
+                    current_def.band1_v =
+                        Some(parse_region_style(&mut reader, b"band1V", theme, color_map));
+                }
+                b"band2V" if current_style_id.is_some() => {
+                    current_def.band2_v =
+                        Some(parse_region_style(&mut reader, b"band2V", theme, color_map));
+                }
                 b"firstRow" if current_style_id.is_some() => {
                     current_def.first_row = Some(parse_region_style(
                         &mut reader,
@@ -190,7 +200,7 @@ fn parse_region_style(
                     let parsed: ParsedColor = parse_color_from_start(reader, e, theme, color_map);
                     style.fill = parsed.color;
                 }
-                b"srgbClr" | b"schemeClr" | b"sysClr" if in_font_ref => {
+                b"srgbClr" | b"schemeClr" | b"sysClr" if in_font_ref || in_tc_tx_style => {
                     let parsed: ParsedColor = parse_color_from_start(reader, e, theme, color_map);
                     style.text_color = parsed.color;
                 }
@@ -209,7 +219,7 @@ fn parse_region_style(
                     let parsed: ParsedColor = parse_color_from_empty(e, theme, color_map);
                     style.fill = parsed.color;
                 }
-                b"srgbClr" | b"schemeClr" | b"sysClr" if in_font_ref => {
+                b"srgbClr" | b"schemeClr" | b"sysClr" if in_font_ref || in_tc_tx_style => {
                     let parsed: ParsedColor = parse_color_from_empty(e, theme, color_map);
                     style.text_color = parsed.color;
                 }
@@ -271,7 +281,9 @@ fn set_region_border(borders: &mut RegionBorders, side: &[u8], width: f64, color
 
 /// Apply table style colors/formatting to cells that don't have explicit overrides.
 ///
-/// Priority (highest wins): cell-level explicit → firstRow/lastRow/firstCol/lastCol → band → wholeTbl
+/// Regions layer like PowerPoint draws them, each overriding the properties
+/// it defines: wholeTbl → column bands → row bands → lastCol → firstCol →
+/// lastRow → firstRow. Explicit cell-level formatting always wins.
 pub(super) fn apply_table_style(table: &mut Table, props: &PptxTableProps, styles: &TableStyleMap) {
     let style_id: &str = match props.style_id.as_deref() {
         Some(id) => id,
@@ -284,56 +296,54 @@ pub(super) fn apply_table_style(table: &mut Table, props: &PptxTableProps, style
 
     let total_rows: usize = table.rows.len();
     let total_cols: usize = table.column_widths.len();
-    let header_rows: usize = if props.first_row { 1 } else { 0 };
-    let footer_rows: usize = if props.last_row { 1 } else { 0 };
 
     for (row_idx, row) in table.rows.iter_mut().enumerate() {
-        let is_first_row: bool = props.first_row && row_idx < header_rows;
+        let is_first_row: bool = props.first_row && row_idx == 0;
         let is_last_row: bool =
-            props.last_row && total_rows > header_rows && row_idx == total_rows - 1;
+            props.last_row && total_rows > 1 && row_idx + 1 == total_rows && !is_first_row;
 
-        // Data row index for banding (excludes first/last special rows)
-        let data_row_idx: Option<usize> = if !is_first_row && !is_last_row {
-            Some(row_idx.saturating_sub(header_rows))
-        } else {
-            None
-        };
+        // Banding counts only data rows/columns (excludes the special ones).
+        let data_row_idx: Option<usize> = (!is_first_row && !is_last_row)
+            .then(|| row_idx.saturating_sub(usize::from(props.first_row)));
 
         for (col_idx, cell) in row.cells.iter_mut().enumerate() {
             let is_first_col: bool = props.first_col && col_idx == 0;
-            let is_last_col: bool = props.last_col && total_cols > 0 && col_idx == total_cols - 1;
-
-            // Determine which region style applies (highest priority first)
-            let region_style: Option<&TableCellRegionStyle> = if is_first_row {
-                style_def.first_row.as_ref()
-            } else if is_last_row {
-                style_def.last_row.as_ref()
-            } else if is_first_col {
-                style_def.first_col.as_ref()
-            } else if is_last_col {
-                style_def.last_col.as_ref()
-            } else if props.band_row
-                && let Some(data_idx) = data_row_idx
-            {
-                if data_idx % 2 == 0 {
-                    style_def.band1_h.as_ref()
-                } else {
-                    style_def.band2_h.as_ref()
+            let is_last_col: bool =
+                props.last_col && total_cols > 1 && col_idx + 1 == total_cols && !is_first_col;
+            let data_col_idx: Option<usize> = (!is_first_col && !is_last_col)
+                .then(|| col_idx.saturating_sub(usize::from(props.first_col)));
+
+            let column_band: Option<&TableCellRegionStyle> = match data_col_idx {
+                Some(idx) if props.band_col => {
+                    band_region(style_def.band1_v.as_ref(), style_def.band2_v.as_ref(), idx)
                 }
-            } else {
-                style_def.whole_table.as_ref()
+                _ => None,
             };
-
-            match region_style {
-                Some(region) => apply_region_to_cell(cell, region),
-                None => {
-                    // Fall back to wholeTbl if the region is active but has
-                    // no definition (e.g. band2H in built-in styles).
-                    if let Some(whole) = style_def.whole_table.as_ref() {
-                        apply_region_to_cell(cell, whole);
-                    }
+            let row_band: Option<&TableCellRegionStyle> = match data_row_idx {
+                Some(idx) if props.band_row => {
+                    band_region(style_def.band1_h.as_ref(), style_def.band2_h.as_ref(), idx)
                 }
+                _ => None,
+            };
+
+            let layers: [(bool, Option<&TableCellRegionStyle>); 7] = [
+                (true, style_def.whole_table.as_ref()),
+                (true, column_band),
+                (true, row_band),
+                (is_last_col, style_def.last_col.as_ref()),
+                (is_first_col, style_def.first_col.as_ref()),
+                (is_last_row, style_def.last_row.as_ref()),
+                (is_first_row, style_def.first_row.as_ref()),
+            ];
+            let mut effective = TableCellRegionStyle::default();
+            for region in layers
+                .into_iter()
+                .filter(|(is_active, _)| *is_active)
+                .filter_map(|(_, region)| region)
+            {
+                layer_region(&mut effective, region);
             }
+            apply_region_to_cell(cell, &effective);
 
             apply_style_borders(
                 cell,
@@ -347,9 +357,28 @@ pub(super) fn apply_table_style(table: &mut Table, props: &PptxTableProps, style
             );
         }
     }
+}
 
-    // Suppress footer_rows warning
-    let _ = footer_rows;
+/// Pick band1 for even data indices and band2 for odd ones.
+fn band_region<'a>(
+    band1: Option<&'a TableCellRegionStyle>,
+    band2: Option<&'a TableCellRegionStyle>,
+    data_idx: usize,
+) -> Option<&'a TableCellRegionStyle> {
+    if data_idx % 2 == 0 { band1 } else { band2 }
+}
+
+/// Overlay the fill and text properties a higher-priority region defines.
+fn layer_region(effective: &mut TableCellRegionStyle, region: &TableCellRegionStyle) {
+    if region.fill.is_some() {
+        effective.fill = region.fill;
+    }
+    if region.text_color.is_some() {
+        effective.text_color = region.text_color;
+    }
+    if region.text_bold.is_some() {
+        effective.text_bold = region.text_bold;
+    }
 }
 
 /// Resolve the borders a cell gets from the style: wholeTbl draws the grid
@@ -431,20 +460,31 @@ fn apply_region_to_cell(cell: &mut TableCell, region: &TableCellRegionStyle) {
     }
 
     // Apply text color and bold to all runs that don't have explicit overrides
-    if region.text_color.is_some() || region.text_bold.is_some() {
-        for block in &mut cell.content {
-            if let Block::Paragraph(paragraph) = block {
-                for run in &mut paragraph.runs {
-                    if region.text_color.is_some() && run.style.color.is_none() {
-                        run.style.color = region.text_color;
-                    }
-                    if let Some(bold) = region.text_bold
-                        && run.style.bold.is_none()
-                    {
-                        run.style.bold = Some(bold);
-                    }
+    if region.text_color.is_none() && region.text_bold.is_none() {
+        return;
+    }
+    for block in &mut cell.content {
+        match block {
+            Block::Paragraph(paragraph) => apply_region_to_runs(&mut paragraph.runs, region),
+            Block::List(list) => {
+                for paragraph in list.items.iter_mut().flat_map(|item| &mut item.content) {
+                    apply_region_to_runs(&mut paragraph.runs, region);
                 }
             }
+            _ => {}
+        }
+    }
+}
+
+fn apply_region_to_runs(runs: &mut [Run], region: &TableCellRegionStyle) {
+    for run in runs {
+        if region.text_color.is_some() && run.style.color.is_none() {
+            run.style.color = region.text_color;
+        }
+        if let Some(bold) = region.text_bold
+            && run.style.bold.is_none()
+        {
+            run.style.bold = Some(bold);
         }
     }
 }
@@ -538,6 +578,11 @@ fn medium_style_2_def(accent: Color, lt1: Color, dk1: Color) -> PptxTableStyleDe
             ..TableCellRegionStyle::default()
         }),
         band2_h: None,
+        band1_v: Some(TableCellRegionStyle {
+            fill: Some(tint_color(accent, 0.2)),
+            ..TableCellRegionStyle::default()
+        }),
+        band2_v: None,
         first_row: Some(TableCellRegionStyle {
             borders: RegionBorders {
                 bottom: solid_border(lt1),
@@ -261,6 +261,36 @@ fn test_slide_table_coalesces_adjacent_runs_with_same_style() {
     assert_eq!(paragraph.runs[1].style.italic, Some(true));
 }
 
+#[test]
+fn test_slide_table_cell_paragraph_spacing() {
+    let rows_xml = concat!(
+        r#"<a:tr h="370840">"#,
+        r#"<a:tc><a:txBody><a:bodyPr/><a:p>"#,
+        r#"<a:pPr><a:spcBef><a:spcPts val="600"/></a:spcBef><a:spcAft><a:spcPct val="50000"/></a:spcAft></a:pPr>"#,
+        r#"<a:r><a:rPr lang="en-US"/><a:t>Spaced</a:t></a:r>"#,
+        r#"</a:p></a:txBody><a:tcPr/></a:tc>"#,
+        r#"</a:tr>"#,
+    );
+    let table_frame = make_table_graphic_frame(0, 0, 914_400, 370_840, &[914_400], rows_xml);
+    let slide = make_slide_xml(&[table_frame]);
+    let data = build_test_pptx(SLIDE_CX, SLIDE_CY, &[slide]);
+
+    let parser = PptxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let page = first_fixed_page(&doc);
+    let table = table_element(&page.elements[0]);
+    let paragraph = match &table.rows[0].cells[0].content[0] {
+        Block::Paragraph(paragraph) => paragraph,
+        other => panic!("Expected paragraph, got {other:?}"),
+    };
+
+    assert_eq!(paragraph.style.space_before, Some(6.0));
+    // Half of an 18pt default line (18 × 1.2).
+    let after: f64 = paragraph.style.space_after.unwrap();
+    assert!((after - 10.8).abs() < 1e-9, "got {after}");
+}
+
 #[test]
 fn test_slide_table_cell_bulleted_paragraphs_group_into_list() {
     let rows_xml = concat!(
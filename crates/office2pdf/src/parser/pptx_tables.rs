@@ -57,6 +57,8 @@ struct PptxTableParser<'a> {
     paragraph_end_run_style: TextStyle,
     paragraph_bullet_definition: PptxBulletDefinition,
     is_in_line_spacing: bool,
+    is_in_space_before: bool,
+    is_in_space_after: bool,
     runs: Vec<Run>,
 
     // ── Run-level state ─────────────────────────────────────────────
@@ -124,6 +126,8 @@ impl<'a> PptxTableParser<'a> {
             paragraph_end_run_style: TextStyle::default(),
             paragraph_bullet_definition: PptxBulletDefinition::default(),
             is_in_line_spacing: false,
+            is_in_space_before: false,
+            is_in_space_after: false,
             runs: Vec::new(),
 
             is_in_run: false,
@@ -200,6 +204,32 @@ impl<'a> PptxTableParser<'a> {
             b"spcPts" if self.is_in_line_spacing => {
                 extract_pptx_line_spacing_pts(e, &mut self.paragraph_style);
             }
+            b"spcBef" if self.is_in_paragraph && !self.is_in_run => {
+                self.is_in_space_before = true;
+            }
+            b"spcAft" if self.is_in_paragraph && !self.is_in_run => {
+                self.is_in_space_after = true;
+            }
+            b"spcPts" if self.is_in_space_before => {
+                extract_pptx_space_points(e, &mut self.paragraph_style.space_before);
+            }
+            b"spcPts" if self.is_in_space_after => {
+                extract_pptx_space_points(e, &mut self.paragraph_style.space_after);
+            }
+            b"spcPct" if self.is_in_space_before => {
+                extract_pptx_space_percent(
+                    e,
+                    self.paragraph_default_run_style.font_size,
+                    &mut self.paragraph_style.space_before,
+                );
+            }
+            b"spcPct" if self.is_in_space_after => {
+                extract_pptx_space_percent(
+                    e,
+                    self.paragraph_default_run_style.font_size,
+                    &mut self.paragraph_style.space_after,
+                );
+            }
             name if self.is_in_paragraph && !self.is_in_run => {
                 if !self.dispatch_bullet_element(name, e) {
                     self.handle_start_non_bullet(reader, name, e)?;
@@ -300,6 +330,26 @@ impl<'a> PptxTableParser<'a> {
             b"spcPts" if self.is_in_line_spacing => {
                 extract_pptx_line_spacing_pts(e, &mut self.paragraph_style);
             }
+            b"spcPts" if self.is_in_space_before => {
+                extract_pptx_space_points(e, &mut self.paragraph_style.space_before);
+            }
+            b"spcPts" if self.is_in_space_after => {
+                extract_pptx_space_points(e, &mut self.paragraph_style.space_after);
+            }
+            b"spcPct" if self.is_in_space_before => {
+                extract_pptx_space_percent(
+                    e,
+                    self.paragraph_default_run_style.font_size,
+                    &mut self.paragraph_style.space_before,
+                );
+            }
+            b"spcPct" if self.is_in_space_after => {
+                extract_pptx_space_percent(
+                    e,
+                    self.paragraph_default_run_style.font_size,
+                    &mut self.paragraph_style.space_after,
+                );
+            }
             name if self.is_in_paragraph && !self.is_in_run => {
                 if !self.dispatch_bullet_element(name, e) {
                     self.handle_empty_non_bullet(name, e);
@@ -374,6 +424,12 @@ impl<'a> PptxTableParser<'a> {
             b"lnSpc" if self.is_in_line_spacing => {
                 self.is_in_line_spacing = false;
             }
+            b"spcBef" if self.is_in_space_before => {
+                self.is_in_space_before = false;
+            }
+            b"spcAft" if self.is_in_space_after => {
+                self.is_in_space_after = false;
+            }
             b"solidFill" if self.solid_fill_context != SolidFillCtx::None => {
                 self.solid_fill_context = SolidFillCtx::None;
             }
@@ -472,6 +528,9 @@ impl<'a> PptxTableParser<'a> {
             spill_width: None,
             vertical_align: self.cell_vertical_align.take(),
             padding: self.cell_padding.take(),
+            no_wrap: false,
+            shrink_to_fit: false,
+            text_rotation: None,
         });
         self.is_in_cell = false;
         self.is_in_table_cell_properties = false;
@@ -508,6 +567,8 @@ impl<'a> PptxTableParser<'a> {
             .text_body_style_defaults
             .bullet_for_level(self.paragraph_level);
         self.is_in_line_spacing = false;
+        self.is_in_space_before = false;
+        self.is_in_space_after = false;
         self.runs.clear();
     }
 
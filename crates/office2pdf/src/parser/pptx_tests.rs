@@ -6,6 +6,55 @@ use zip::write::FileOptions;
 
 /// Build a minimal PPTX file as bytes from slide XML strings.
 fn build_test_pptx(slide_cx_emu: i64, slide_cy_emu: i64, slide_xmls: &[String]) -> Vec<u8> {
+    build_test_pptx_with_slide_rels(slide_cx_emu, slide_cy_emu, slide_xmls, &[])
+}
+
+/// Build a minimal PPTX file whose slides also carry `.rels` parts.
+/// `slide_rels[i]` holds the `<Relationship>` elements of slide `i + 1`.
+fn build_test_pptx_with_slide_rels(
+    slide_cx_emu: i64,
+    slide_cy_emu: i64,
+    slide_xmls: &[String],
+    slide_rels: &[String],
+) -> Vec<u8> {
+    build_test_pptx_package(slide_cx_emu, slide_cy_emu, slide_xmls, slide_rels, "")
+}
+
+/// Build a minimal PPTX file whose slides are grouped into sections. Each
+/// `(name, slide_count)` entry takes the next `slide_count` slides in order.
+fn build_test_pptx_with_sections(
+    slide_cx_emu: i64,
+    slide_cy_emu: i64,
+    slide_xmls: &[String],
+    sections: &[(&str, usize)],
+) -> Vec<u8> {
+    let mut section_lst = String::from(
+        r#"<p:extLst><p:ext uri="{521415D9-36F7-43E2-AB2F-B90AF26B5E84}"><p14:sectionLst xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main">"#,
+    );
+    let mut next_slide: usize = 0;
+    for (index, (name, slide_count)) in sections.iter().enumerate() {
+        section_lst.push_str(&format!(
+            r#"<p14:section name="{name}" id="{{00000000-0000-0000-0000-{index:012}}}"><p14:sldIdLst>"#
+        ));
+        for slide_index in next_slide..next_slide + slide_count {
+            section_lst.push_str(&format!(r#"<p14:sldId id="{}"/>"#, 256 + slide_index));
+        }
+        section_lst.push_str("</p14:sldIdLst></p14:section>");
+        next_slide += slide_count;
+    }
+    section_lst.push_str("</p14:sectionLst></p:ext></p:extLst>");
+    build_test_pptx_package(slide_cx_emu, slide_cy_emu, slide_xmls, &[], &section_lst)
+}
+
+/// Shared body of the PPTX test builders. `presentation_ext_xml` is
+/// appended after `<p:sldIdLst>` in `presentation.xml`.
+fn build_test_pptx_package(
+    slide_cx_emu: i64,
+    slide_cy_emu: i64,
+    slide_xmls: &[String],
+    slide_rels: &[String],
+    presentation_ext_xml: &str,
+) -> Vec<u8> {
     let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
     let opts = FileOptions::default();
 
@@ -43,7 +92,9 @@ fn build_test_pptx(slide_cx_emu: i64, slide_cy_emu: i64, slide_xmls: &[String])
             2 + i
         ));
     }
-    pres.push_str("</p:sldIdLst></p:presentation>");
+    pres.push_str("</p:sldIdLst>");
+    pres.push_str(presentation_ext_xml);
+    pres.push_str("</p:presentation>");
     zip.start_file("ppt/presentation.xml", opts).unwrap();
     zip.write_all(pres.as_bytes()).unwrap();
 
@@ -68,6 +119,17 @@ fn build_test_pptx(slide_cx_emu: i64, slide_cy_emu: i64, slide_xmls: &[String])
         zip.start_file(format!("ppt/slides/slide{}.xml", i + 1), opts)
             .unwrap();
         zip.write_all(slide_xml.as_bytes()).unwrap();
+        if let Some(rels) = slide_rels.get(i) {
+            zip.start_file(format!("ppt/slides/_rels/slide{}.xml.rels", i + 1), opts)
+                .unwrap();
+            zip.write_all(
+                format!(
+                    r#"<?xml version="1.0" encoding="UTF-8"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{rels}</Relationships>"#
+                )
+                .as_bytes(),
+            )
+            .unwrap();
+        }
     }
 
     let cursor = zip.finish().unwrap();
@@ -508,6 +570,15 @@ mod chart_tests;
 #[path = "pptx_image_tests.rs"]
 mod image_tests;
 
+#[path = "pptx_ink_tests.rs"]
+mod ink_tests;
+
+#[path = "pptx_ole_tests.rs"]
+mod ole_tests;
+
+#[path = "pptx_shape_recovery_tests.rs"]
+mod shape_recovery_tests;
+
 #[path = "pptx_shape_style_tests.rs"]
 mod shape_style_tests;
 
@@ -531,3 +602,9 @@ mod background_ref_tests;
 
 #[path = "pptx_background_image_tests.rs"]
 mod background_image_tests;
+
+#[path = "pptx_link_tests.rs"]
+mod link_tests;
+
+#[path = "pptx_outline_tests.rs"]
+mod outline_tests;
@@ -1,5 +1,7 @@
 use super::*;
 
+use crate::defaults::{PPTX_DEFAULT_FONT_SIZE_PT, SINGLE_LINE_HEIGHT_FACTOR};
+
 /// Overwrite each `Option` field in `target` with `source` when the source is `Some`.
 /// Fields that require `.clone()` must be listed after a `;` separator.
 /// Kept only for PPTX-specific types that don't live in the IR layer.
@@ -76,6 +78,8 @@ struct ListStyleParseState {
     active_paragraph_target: Option<ParagraphTarget>,
     active_run_target: Option<ParagraphTarget>,
     is_in_line_spacing: bool,
+    is_in_space_before: bool,
+    is_in_space_after: bool,
     is_in_run_fill: bool,
     is_in_bullet_fill: bool,
 }
@@ -87,6 +91,8 @@ impl ListStyleParseState {
             active_paragraph_target: None,
             active_run_target: None,
             is_in_line_spacing: false,
+            is_in_space_before: false,
+            is_in_space_after: false,
             is_in_run_fill: false,
             is_in_bullet_fill: false,
         }
@@ -101,6 +107,20 @@ impl ListStyleParseState {
         }
     }
 
+    fn percent_spacing_mut(&mut self, target: ParagraphTarget) -> &mut PptxPercentSpacing {
+        match target {
+            ParagraphTarget::Default => &mut self.defaults.default_percent_spacing,
+            ParagraphTarget::Level(level) => {
+                &mut self
+                    .defaults
+                    .levels
+                    .entry(level)
+                    .or_default()
+                    .percent_spacing
+            }
+        }
+    }
+
     fn run_style_mut(&mut self, target: ParagraphTarget) -> &mut TextStyle {
         match target {
             ParagraphTarget::Default => &mut self.defaults.default_run,
@@ -143,6 +163,32 @@ impl ListStyleParseState {
         }
     }
 
+    /// Handle `<spcPct>` / `<spcPts>` inside `<spcBef>` or `<spcAft>`.
+    /// Points and percent are alternatives, so setting one clears the other.
+    fn handle_paragraph_gap_element(&mut self, e: &quick_xml::events::BytesStart, is_pct: bool) {
+        let Some(target) = self.active_paragraph_target else {
+            return;
+        };
+        let Some(value) = get_attr_i64(e, b"val") else {
+            return;
+        };
+        let is_before: bool = self.is_in_space_before;
+        let percent: Option<i64> = is_pct.then_some(value);
+        let points: Option<f64> = (!is_pct).then_some(value as f64 / 100.0);
+        let percent_spacing: &mut PptxPercentSpacing = self.percent_spacing_mut(target);
+        if is_before {
+            percent_spacing.before = percent;
+        } else {
+            percent_spacing.after = percent;
+        }
+        let style: &mut ParagraphStyle = self.paragraph_style_mut(target);
+        if is_before {
+            style.space_before = points;
+        } else {
+            style.space_after = points;
+        }
+    }
+
     // ── Bullet element handlers ──────────────────────────────────────
 
     fn handle_bullet_auto_num(&mut self, e: &quick_xml::events::BytesStart) {
@@ -234,6 +280,32 @@ impl ListStyleParseState {
         }
     }
 
+    /// Consume a `<defRPr><a:ln>` text outline (its fill is not a run color).
+    fn handle_run_outline(
+        &mut self,
+        reader: &mut Reader<&[u8]>,
+        e: &quick_xml::events::BytesStart,
+        theme: &ThemeData,
+        color_map: &ColorMapData,
+    ) {
+        let outline: Option<TextOutline> = parse_text_outline(reader, e, theme, color_map);
+        if let Some(target) = self.active_run_target {
+            self.run_style_mut(target).outline = outline;
+        }
+    }
+
+    fn handle_run_effects(
+        &mut self,
+        reader: &mut Reader<&[u8]>,
+        theme: &ThemeData,
+        color_map: &ColorMapData,
+    ) {
+        let shadow: Option<Shadow> = parse_effect_list(reader, theme, color_map).shadow;
+        if let Some(target) = self.active_run_target {
+            self.run_style_mut(target).shadow = shadow;
+        }
+    }
+
     fn handle_run_color_start(
         &mut self,
         reader: &mut Reader<&[u8]>,
@@ -328,10 +400,14 @@ impl ListStyleParseState {
             b"defPPr" => {
                 self.active_paragraph_target = None;
                 self.is_in_line_spacing = false;
+                self.is_in_space_before = false;
+                self.is_in_space_after = false;
             }
             name if parse_pptx_list_style_level(name).is_some() => {
                 self.active_paragraph_target = None;
                 self.is_in_line_spacing = false;
+                self.is_in_space_before = false;
+                self.is_in_space_after = false;
             }
             b"defRPr" => {
                 self.active_run_target = None;
@@ -346,6 +422,12 @@ impl ListStyleParseState {
             b"lnSpc" if self.is_in_line_spacing => {
                 self.is_in_line_spacing = false;
             }
+            b"spcBef" if self.is_in_space_before => {
+                self.is_in_space_before = false;
+            }
+            b"spcAft" if self.is_in_space_after => {
+                self.is_in_space_after = false;
+            }
             _ => {}
         }
         false
@@ -375,18 +457,36 @@ pub(super) fn parse_pptx_list_style(
                     b"lnSpc" if state.active_paragraph_target.is_some() => {
                         state.is_in_line_spacing = true;
                     }
+                    b"spcBef" if state.active_paragraph_target.is_some() => {
+                        state.is_in_space_before = true;
+                    }
+                    b"spcAft" if state.active_paragraph_target.is_some() => {
+                        state.is_in_space_after = true;
+                    }
                     b"spcPct" if state.is_in_line_spacing => {
                         state.handle_line_spacing_element(e, true);
                     }
                     b"spcPts" if state.is_in_line_spacing => {
                         state.handle_line_spacing_element(e, false);
                     }
+                    b"spcPct" if state.is_in_space_before || state.is_in_space_after => {
+                        state.handle_paragraph_gap_element(e, true);
+                    }
+                    b"spcPts" if state.is_in_space_before || state.is_in_space_after => {
+                        state.handle_paragraph_gap_element(e, false);
+                    }
                     b"buClr" if state.active_paragraph_target.is_some() => {
                         state.is_in_bullet_fill = true;
                     }
                     b"defRPr" if state.active_paragraph_target.is_some() => {
                         state.enter_default_run_props(e);
                     }
+                    b"ln" if state.active_run_target.is_some() => {
+                        state.handle_run_outline(reader, e, theme, color_map);
+                    }
+                    b"effectLst" if state.active_run_target.is_some() => {
+                        state.handle_run_effects(reader, theme, color_map);
+                    }
                     b"solidFill" if state.active_run_target.is_some() => {
                         state.is_in_run_fill = true;
                     }
@@ -424,6 +524,12 @@ pub(super) fn parse_pptx_list_style(
                     b"spcPts" if state.is_in_line_spacing => {
                         state.handle_line_spacing_element(e, false);
                     }
+                    b"spcPct" if state.is_in_space_before || state.is_in_space_after => {
+                        state.handle_paragraph_gap_element(e, true);
+                    }
+                    b"spcPts" if state.is_in_space_before || state.is_in_space_after => {
+                        state.handle_paragraph_gap_element(e, false);
+                    }
                     b"buClr" if state.active_paragraph_target.is_some() => {
                         // Empty `<buClr/>` — no color data to extract.
                     }
@@ -505,8 +611,7 @@ pub(super) fn extract_pptx_line_spacing_pts(
     }
 }
 
-/// `a:spcBef`/`a:spcAft` points value: hundredths of a point. Percent-based
-/// spacing (`a:spcPct`) is rare for before/after gaps and is not yet mapped.
+/// `a:spcBef`/`a:spcAft` points value: hundredths of a point.
 pub(super) fn extract_pptx_space_points(
     e: &quick_xml::events::BytesStart,
     target: &mut Option<f64>,
@@ -516,6 +621,24 @@ pub(super) fn extract_pptx_space_points(
     }
 }
 
+/// `a:spcBef`/`a:spcAft` percent value: thousandths of a percent of one line.
+/// The IR only carries absolute gaps, so the line height is resolved from the
+/// paragraph's inherited font size (PowerPoint's 18pt when unset).
+pub(super) fn extract_pptx_space_percent(
+    e: &quick_xml::events::BytesStart,
+    font_size: Option<f64>,
+    target: &mut Option<f64>,
+) {
+    if let Some(value) = get_attr_i64(e, b"val") {
+        *target = Some(resolve_pptx_space_percent(value, font_size));
+    }
+}
+
+pub(super) fn resolve_pptx_space_percent(value: i64, font_size: Option<f64>) -> f64 {
+    let font_size_pt: f64 = font_size.unwrap_or(PPTX_DEFAULT_FONT_SIZE_PT);
+    value as f64 / 100_000.0 * font_size_pt * SINGLE_LINE_HEIGHT_FACTOR
+}
+
 /// Text-box layout settings accumulated from `<a:bodyPr>` and autofit hints.
 #[derive(Debug, Clone, Copy)]
 pub(super) struct PptxTextBoxSettings {
@@ -811,19 +934,29 @@ pub(super) fn parse_pptx_bullet_marker(
         .or_else(|| (level == 0).then(|| PptxBulletKind::Character("•".to_string())))
 }
 
+/// Map an `ST_TextAutonumberScheme` to a Typst numbering pattern. Double-byte
+/// and black/white circled variants collapse onto the nearest pattern Typst
+/// can count with.
 fn pptx_auto_numbering_pattern(numbering_type: &str) -> Option<&'static str> {
     match numbering_type {
-        "arabicPeriod" => Some("1."),
+        "arabicPeriod" | "arabicDbPeriod" => Some("1."),
         "arabicParenR" => Some("1)"),
         "arabicParenBoth" => Some("(1)"),
+        "arabicPlain" | "arabicDbPlain" => Some("1"),
+        "arabic1Minus" | "arabic2Minus" => Some("1 -"),
         "alphaLcPeriod" => Some("a."),
         "alphaUcPeriod" => Some("A."),
         "alphaLcParenR" => Some("a)"),
         "alphaUcParenR" => Some("A)"),
+        "alphaLcParenBoth" => Some("(a)"),
+        "alphaUcParenBoth" => Some("(A)"),
         "romanLcPeriod" => Some("i."),
         "romanUcPeriod" => Some("I."),
         "romanLcParenR" => Some("i)"),
         "romanUcParenR" => Some("I)"),
+        "romanLcParenBoth" => Some("(i)"),
+        "romanUcParenBoth" => Some("(I)"),
+        "circleNumDbPlain" | "circleNumWdWhitePlain" | "circleNumWdBlackPlain" => Some("①"),
         _ => None,
     }
 }
@@ -889,6 +1022,105 @@ fn pptx_paragraph_has_visible_content(paragraph: &Paragraph) -> bool {
     })
 }
 
+/// Make the language most of the slide text is written in the document
+/// language, and drop it from the runs in that language so only runs in
+/// another language carry one. PowerPoint tags every run, so without the
+/// drop each run would switch the language to the one it already has.
+pub(super) fn assign_document_language(pages: &mut [Page], metadata: &mut Metadata) {
+    // Characters per language, in first-seen order so ties go to the
+    // language the deck starts in.
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for_each_slide_run(pages, &mut |run| {
+        let Some(language) = run.style.language.as_ref() else {
+            return;
+        };
+        let characters: usize = run.text.chars().count();
+        match counts
+            .iter_mut()
+            .find(|(seen, _)| seen.eq_ignore_ascii_case(language))
+        {
+            Some((_, count)) => *count += characters,
+            None => counts.push((language.clone(), characters)),
+        }
+    });
+    let mut primary: Option<&(String, usize)> = None;
+    for entry in &counts {
+        if primary.is_none_or(|best| entry.1 > best.1) {
+            primary = Some(entry);
+        }
+    }
+    if let Some((language, _)) = primary {
+        metadata.language = Some(language.clone());
+    }
+
+    let Some(document_language) = metadata.language.clone() else {
+        return;
+    };
+    for_each_slide_run(pages, &mut |run| {
+        if run
+            .style
+            .language
+            .as_ref()
+            .is_some_and(|language| language.eq_ignore_ascii_case(&document_language))
+        {
+            run.style.language = None;
+        }
+    });
+}
+
+fn for_each_slide_run(pages: &mut [Page], visit: &mut impl FnMut(&mut Run)) {
+    for page in pages {
+        let Page::Fixed(page) = page else {
+            continue;
+        };
+        for element in &mut page.elements {
+            match &mut element.kind {
+                FixedElementKind::TextBox(text_box) => {
+                    for_each_block_run(&mut text_box.content, visit);
+                }
+                FixedElementKind::Table(table) => for_each_table_run(table, visit),
+                FixedElementKind::Image(_)
+                | FixedElementKind::Shape(_)
+                | FixedElementKind::SmartArt(_)
+                | FixedElementKind::Chart(_) => {}
+            }
+        }
+    }
+}
+
+fn for_each_block_run(blocks: &mut [Block], visit: &mut impl FnMut(&mut Run)) {
+    for block in blocks {
+        match block {
+            Block::Paragraph(paragraph) => paragraph.runs.iter_mut().for_each(&mut *visit),
+            Block::Table(table) => for_each_table_run(table, visit),
+            Block::List(list) => {
+                for item in &mut list.items {
+                    for paragraph in &mut item.content {
+                        paragraph.runs.iter_mut().for_each(&mut *visit);
+                    }
+                }
+            }
+            Block::FloatingTextBox(text_box) => for_each_block_run(&mut text_box.content, visit),
+            Block::Image(_)
+            | Block::InlineImages(_)
+            | Block::FloatingImage(_)
+            | Block::FloatingShape(_)
+            | Block::MathEquation(_)
+            | Block::Chart(_)
+            | Block::PageBreak
+            | Block::ColumnBreak => {}
+        }
+    }
+}
+
+fn for_each_table_run(table: &mut Table, visit: &mut impl FnMut(&mut Run)) {
+    for row in &mut table.rows {
+        for cell in &mut row.cells {
+            for_each_block_run(&mut cell.content, visit);
+        }
+    }
+}
+
 pub(super) fn extract_rpr_attributes(e: &quick_xml::events::BytesStart, style: &mut TextStyle) {
     if let Some(val) = get_attr_str(e, b"b") {
         style.bold = Some(val == "1" || val == "true");
@@ -906,4 +1138,7 @@ pub(super) fn extract_rpr_attributes(e: &quick_xml::events::BytesStart, style: &
         // Font size in hundredths of a point (e.g. 1200 = 12pt)
         style.font_size = Some(sz as f64 / 100.0);
     }
+    if let Some(lang) = get_attr_str(e, b"lang").filter(|lang| !lang.is_empty()) {
+        style.language = Some(lang);
+    }
 }
@@ -225,6 +225,39 @@ fn test_text_box_auto_numbered_paragraph_start_override_sets_list_start() {
     );
 }
 
+#[test]
+fn test_text_box_auto_numbered_paren_both_and_circled_schemes() {
+    for (scheme, expected_pattern) in [
+        ("alphaLcParenBoth", "(a)"),
+        ("romanUcParenBoth", "(I)"),
+        ("arabicPlain", "1"),
+        ("circleNumDbPlain", "①"),
+    ] {
+        let paragraphs_xml = format!(
+            r#"<a:p><a:pPr marL="457200" indent="-457200"><a:buAutoNum type="{scheme}"/></a:pPr><a:r><a:t>First</a:t></a:r></a:p>"#
+        );
+        let shape = make_multi_para_text_box(0, 0, 1_000_000, 500_000, &paragraphs_xml);
+        let slide = make_slide_xml(&[shape]);
+        let data = build_test_pptx(SLIDE_CX, SLIDE_CY, &[slide]);
+        let parser = PptxParser;
+        let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+
+        let page = first_fixed_page(&doc);
+        let blocks = text_box_blocks(&page.elements[0]);
+        let list = match &blocks[0] {
+            Block::List(list) => list,
+            other => panic!("Expected List block, got {other:?}"),
+        };
+        assert_eq!(
+            list.level_styles
+                .get(&0)
+                .and_then(|style| style.numbering_pattern.as_deref()),
+            Some(expected_pattern),
+            "scheme {scheme}"
+        );
+    }
+}
+
 #[test]
 fn test_text_box_auto_numbered_paragraph_extracts_hanging_indent() {
     let paragraphs_xml = concat!(
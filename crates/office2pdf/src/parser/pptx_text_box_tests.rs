@@ -345,3 +345,120 @@ fn test_text_box_paragraph_before_after_spacing() {
     assert_eq!(para.style.space_before, Some(4.0));
     assert_eq!(para.style.space_after, Some(6.0));
 }
+
+#[test]
+fn test_text_box_paragraph_percent_spacing_uses_font_size() {
+    // spcPct inside spcBef/spcAft is a fraction of one line (font size ×
+    // 1.2); 50% of a 20pt line is 12pt, and without a size PowerPoint's 18pt
+    // default applies.
+    let shape = r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="TextBox"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="4000000" cy="2000000"/></a:xfrm></p:spPr><p:txBody><a:bodyPr/><a:lstStyle><a:lvl1pPr><a:defRPr sz="2000"/></a:lvl1pPr><a:lvl2pPr/></a:lstStyle><a:p><a:pPr><a:spcBef><a:spcPct val="50000"/></a:spcBef></a:pPr><a:r><a:rPr lang="en-US"/><a:t>sized</a:t></a:r></a:p><a:p><a:pPr lvl="1"><a:spcAft><a:spcPct val="100000"/></a:spcAft></a:pPr><a:r><a:rPr lang="en-US"/><a:t>default size</a:t></a:r></a:p></p:txBody></p:sp>"#.to_string();
+    let slide = make_slide_xml(&[shape]);
+    let data = build_test_pptx(SLIDE_CX, SLIDE_CY, &[slide]);
+    let parser = PptxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let page = first_fixed_page(&doc);
+    let blocks = text_box_blocks(&page.elements[0]);
+    let paragraphs: Vec<&Paragraph> = blocks
+        .iter()
+        .filter_map(|block| match block {
+            Block::Paragraph(paragraph) => Some(paragraph),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(paragraphs.len(), 2);
+    let before: f64 = paragraphs[0].style.space_before.unwrap();
+    assert!((before - 12.0).abs() < 1e-9, "got {before}");
+    let after: f64 = paragraphs[1].style.space_after.unwrap();
+    assert!((after - 21.6).abs() < 1e-9, "got {after}");
+}
+
+#[test]
+fn test_list_style_paragraph_spacing_is_inherited() {
+    // Master body styles usually declare spcBef as a percentage on each
+    // level; paragraphs inherit it resolved against the level's font size.
+    let shape = r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="TextBox"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="4000000" cy="2000000"/></a:xfrm></p:spPr><p:txBody><a:bodyPr/><a:lstStyle><a:lvl1pPr><a:spcBef><a:spcPct val="20000"/></a:spcBef><a:spcAft><a:spcPts val="300"/></a:spcAft><a:defRPr sz="2500"/></a:lvl1pPr></a:lstStyle><a:p><a:r><a:rPr lang="en-US"/><a:t>inherited</a:t></a:r></a:p></p:txBody></p:sp>"#.to_string();
+    let slide = make_slide_xml(&[shape]);
+    let data = build_test_pptx(SLIDE_CX, SLIDE_CY, &[slide]);
+    let parser = PptxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let page = first_fixed_page(&doc);
+    let blocks = text_box_blocks(&page.elements[0]);
+    let para = match &blocks[0] {
+        Block::Paragraph(p) => p,
+        _ => panic!("Expected Paragraph"),
+    };
+    let before: f64 = para.style.space_before.unwrap();
+    assert!((before - 6.0).abs() < 1e-9, "got {before}");
+    assert_eq!(para.style.space_after, Some(3.0));
+}
+
+/// Parse a single-text-box slide and return the first run's style.
+fn first_run_style_of(shape: String) -> TextStyle {
+    let slide = make_slide_xml(&[shape]);
+    let data = build_test_pptx(SLIDE_CX, SLIDE_CY, &[slide]);
+    let (doc, _warnings) = PptxParser.parse(&data, &ConvertOptions::default()).unwrap();
+    let page = first_fixed_page(&doc);
+    match &text_box_blocks(&page.elements[0])[0] {
+        Block::Paragraph(p) => p.runs[0].style.clone(),
+        _ => panic!("Expected Paragraph"),
+    }
+}
+
+#[test]
+fn test_text_box_run_outline_and_fill() {
+    // The `<a:ln>` fill is the outline color; the following solidFill is
+    // still the text color.
+    let runs_xml = r#"<a:r><a:rPr lang="en-US"><a:ln w="25400"><a:solidFill><a:srgbClr val="0000FF"/></a:solidFill></a:ln><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill></a:rPr><a:t>Outlined</a:t></a:r>"#;
+    let style = first_run_style_of(make_formatted_text_box(0, 0, 2_000_000, 500_000, runs_xml));
+
+    assert_eq!(
+        style.outline,
+        Some(TextOutline {
+            color: Color::new(0, 0, 255),
+            width: 2.0,
+        })
+    );
+    assert_eq!(style.color, Some(Color::new(255, 0, 0)));
+}
+
+#[test]
+fn test_text_box_run_fill_after_empty_outline() {
+    let runs_xml = r#"<a:r><a:rPr lang="en-US"><a:ln/><a:solidFill><a:srgbClr val="00FF00"/></a:solidFill></a:rPr><a:t>Plain</a:t></a:r>"#;
+    let style = first_run_style_of(make_formatted_text_box(0, 0, 2_000_000, 500_000, runs_xml));
+
+    assert_eq!(style.outline, None);
+    assert_eq!(style.color, Some(Color::new(0, 255, 0)));
+}
+
+#[test]
+fn test_text_box_run_shadow() {
+    let runs_xml = r#"<a:r><a:rPr lang="en-US"><a:effectLst><a:outerShdw blurRad="38100" dist="38100" dir="2700000" algn="tl"><a:srgbClr val="000000"><a:alpha val="43137"/></a:srgbClr></a:outerShdw></a:effectLst></a:rPr><a:t>Shadowed</a:t></a:r>"#;
+    let style = first_run_style_of(make_formatted_text_box(0, 0, 2_000_000, 500_000, runs_xml));
+
+    let shadow = style.shadow.expect("run shadow");
+    assert!((shadow.distance - 3.0).abs() < 1e-9);
+    assert!((shadow.direction - 45.0).abs() < 1e-9);
+    assert_eq!(shadow.color, Color::new(0, 0, 0));
+    assert!((shadow.opacity - 0.43137).abs() < 1e-4);
+}
+
+#[test]
+fn test_list_style_run_shadow_and_outline_are_inherited() {
+    // Title styles commonly declare the shadow on the level's defRPr.
+    let shape = r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="TextBox"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="4000000" cy="2000000"/></a:xfrm></p:spPr><p:txBody><a:bodyPr/><a:lstStyle><a:lvl1pPr><a:defRPr sz="4000"><a:ln w="12700"><a:solidFill><a:srgbClr val="FFFFFF"/></a:solidFill></a:ln><a:solidFill><a:srgbClr val="333333"/></a:solidFill><a:effectLst><a:outerShdw dist="25400" dir="5400000"><a:srgbClr val="808080"/></a:outerShdw></a:effectLst></a:defRPr></a:lvl1pPr></a:lstStyle><a:p><a:r><a:rPr lang="en-US"/><a:t>Title</a:t></a:r></a:p></p:txBody></p:sp>"#.to_string();
+    let style = first_run_style_of(shape);
+
+    assert_eq!(
+        style.outline,
+        Some(TextOutline {
+            color: Color::new(255, 255, 255),
+            width: 1.0,
+        })
+    );
+    assert_eq!(style.color, Some(Color::new(0x33, 0x33, 0x33)));
+    let shadow = style.shadow.expect("inherited shadow");
+    assert!((shadow.direction - 90.0).abs() < 1e-9);
+    assert_eq!(shadow.color, Color::new(128, 128, 128));
+}
@@ -365,13 +365,25 @@ fn extract_fill_style_entries(xml: &str, list_tag: &[u8]) -> Vec<String> {
     entries
 }
 
-/// Parse the image relationship id of a `<p:bg><p:bgPr><a:blipFill>` picture
-/// background from a slide/layout/master XML.
-pub(super) fn parse_background_image_rid(xml: &str) -> Option<String> {
+/// Picture fill of a `<p:bg><p:bgPr><a:blipFill>` background: the image
+/// relationship id plus the `<a:tile>` scale when the picture repeats.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct BackgroundPictureFill {
+    pub(super) rid: String,
+    /// Horizontal and vertical tile scale (`sx`/`sy`, 1.0 = 100%), or
+    /// `None` for a stretched picture.
+    pub(super) tile_scale: Option<(f64, f64)>,
+}
+
+/// Parse the `<p:bg><p:bgPr><a:blipFill>` picture background of a
+/// slide/layout/master XML.
+pub(super) fn parse_background_picture_fill(xml: &str) -> Option<BackgroundPictureFill> {
     let mut reader = Reader::from_str(xml);
     let mut in_bg = false;
     let mut in_bg_pr = false;
     let mut in_blip_fill = false;
+    let mut rid: Option<String> = None;
+    let mut tile_scale: Option<(f64, f64)> = None;
 
     loop {
         match reader.read_event() {
@@ -379,15 +391,25 @@ pub(super) fn parse_background_image_rid(xml: &str) -> Option<String> {
                 b"bg" => in_bg = true,
                 b"bgPr" if in_bg => in_bg_pr = true,
                 b"blipFill" if in_bg_pr => in_blip_fill = true,
-                b"blip" if in_blip_fill => {
-                    return get_attr_str(e, b"r:embed");
+                b"blip" if in_blip_fill => rid = get_attr_str(e, b"r:embed"),
+                b"tile" if in_blip_fill => {
+                    // `sx`/`sy` are in 1/1000 percent; an omitted scale is 100%.
+                    let scale = |name: &[u8]| -> f64 {
+                        get_attr_i64(e, name).map_or(1.0, |v| v as f64 / 100_000.0)
+                    };
+                    tile_scale = Some((scale(b"sx"), scale(b"sy")));
                 }
                 _ => {}
             },
             Ok(Event::End(ref e)) => match e.local_name().as_ref() {
-                b"bg" => return None,
+                b"bg" => break,
                 b"bgPr" => in_bg_pr = false,
-                b"blipFill" => in_blip_fill = false,
+                b"blipFill" => {
+                    if rid.is_some() {
+                        break;
+                    }
+                    in_blip_fill = false;
+                }
                 _ => {}
             },
             Ok(Event::Eof) | Err(_) => break,
@@ -395,7 +417,7 @@ pub(super) fn parse_background_image_rid(xml: &str) -> Option<String> {
         }
     }
 
-    None
+    rid.map(|rid| BackgroundPictureFill { rid, tile_scale })
 }
 
 /// Resolve a `<p:bg><p:bgRef idx="N">` background reference against the
@@ -701,19 +723,29 @@ pub(super) fn parse_shape_gradient_fill(
     None
 }
 
-/// Parse `<a:effectLst>` and extract outer shadow if present.
+/// Effects extracted from an `<a:effectLst>`.
+#[derive(Debug, Clone, Default)]
+pub(super) struct ParsedEffectList {
+    pub(super) shadow: Option<Shadow>,
+    pub(super) effects: ShapeEffects,
+}
+
+/// Parse `<a:effectLst>` and extract the outer shadow, glow, reflection, and
+/// soft edge if present.
 pub(super) fn parse_effect_list(
     reader: &mut Reader<&[u8]>,
     theme: &ThemeData,
     color_map: &ColorMapData,
-) -> Option<Shadow> {
-    let mut shadow: Option<Shadow> = None;
+) -> ParsedEffectList {
+    let mut parsed_effects = ParsedEffectList::default();
     let mut in_outer_shdw = false;
-    let mut shdw_blur: f64 = 0.0;
-    let mut shdw_dist: f64 = 0.0;
-    let mut shdw_dir: f64 = 0.0;
+    let mut pending_shadow: Option<Shadow> = None;
     let mut shdw_color: Option<Color> = None;
     let mut shdw_opacity: f64 = 1.0;
+    let mut in_glow = false;
+    let mut glow_radius: f64 = 0.0;
+    let mut glow_color: Option<Color> = None;
+    let mut glow_opacity: f64 = 1.0;
     let mut depth: usize = 1;
 
     loop {
@@ -722,19 +754,30 @@ pub(super) fn parse_effect_list(
                 depth += 1;
                 let local = e.local_name();
                 match local.as_ref() {
-                    b"outerShdw" => {
+                    b"outerShdw" | b"prstShdw" => {
                         in_outer_shdw = true;
-                        shdw_blur = units::emu_to_pt(get_attr_i64(e, b"blurRad").unwrap_or(0));
-                        shdw_dist = units::emu_to_pt(get_attr_i64(e, b"dist").unwrap_or(0));
-                        shdw_dir = get_attr_i64(e, b"dir").unwrap_or(0) as f64 / 60_000.0;
+                        pending_shadow = Some(parse_shadow_geometry(e));
                         shdw_color = None;
                         shdw_opacity = 1.0;
                     }
-                    b"srgbClr" | b"schemeClr" | b"sysClr" if in_outer_shdw => {
+                    b"glow" => {
+                        in_glow = true;
+                        glow_radius = units::emu_to_pt(get_attr_i64(e, b"rad").unwrap_or(0));
+                        glow_color = None;
+                        glow_opacity = 1.0;
+                    }
+                    b"srgbClr" | b"schemeClr" | b"sysClr" if in_outer_shdw || in_glow => {
                         let parsed = parse_color_from_start(reader, e, theme, color_map);
-                        shdw_color = parsed.color;
-                        if let Some(alpha) = parsed.alpha {
-                            shdw_opacity = alpha;
+                        if in_outer_shdw {
+                            shdw_color = parsed.color;
+                            if let Some(alpha) = parsed.alpha {
+                                shdw_opacity = alpha;
+                            }
+                        } else {
+                            glow_color = parsed.color;
+                            if let Some(alpha) = parsed.alpha {
+                                glow_opacity = alpha;
+                            }
                         }
                         // `parse_color_from_start` consumes the matching end tag too.
                         depth = depth.saturating_sub(1);
@@ -745,23 +788,28 @@ pub(super) fn parse_effect_list(
             Ok(Event::Empty(ref e)) => {
                 let local = e.local_name();
                 match local.as_ref() {
-                    b"outerShdw" => {
-                        let blur = units::emu_to_pt(get_attr_i64(e, b"blurRad").unwrap_or(0));
-                        let dist = units::emu_to_pt(get_attr_i64(e, b"dist").unwrap_or(0));
-                        let dir = get_attr_i64(e, b"dir").unwrap_or(0) as f64 / 60_000.0;
-                        shadow = Some(Shadow {
-                            blur_radius: blur,
-                            distance: dist,
-                            direction: dir,
-                            color: Color::new(0, 0, 0),
-                            opacity: 1.0,
-                        });
+                    b"outerShdw" | b"prstShdw" => {
+                        parsed_effects.shadow = Some(parse_shadow_geometry(e));
+                    }
+                    b"reflection" => {
+                        parsed_effects.effects.reflection = Some(parse_reflection(e));
+                    }
+                    b"softEdge" => {
+                        let radius = units::emu_to_pt(get_attr_i64(e, b"rad").unwrap_or(0));
+                        parsed_effects.effects.soft_edge_radius = Some(radius).filter(|r| *r > 0.0);
                     }
-                    b"srgbClr" | b"schemeClr" | b"sysClr" if in_outer_shdw => {
+                    b"srgbClr" | b"schemeClr" | b"sysClr" if in_outer_shdw || in_glow => {
                         let parsed = parse_color_from_empty(e, theme, color_map);
-                        shdw_color = parsed.color;
-                        if let Some(alpha) = parsed.alpha {
-                            shdw_opacity = alpha;
+                        if in_outer_shdw {
+                            shdw_color = parsed.color;
+                            if let Some(alpha) = parsed.alpha {
+                                shdw_opacity = alpha;
+                            }
+                        } else {
+                            glow_color = parsed.color;
+                            if let Some(alpha) = parsed.alpha {
+                                glow_opacity = alpha;
+                            }
                         }
                     }
                     b"alpha" if in_outer_shdw => {
@@ -778,17 +826,30 @@ pub(super) fn parse_effect_list(
                     break;
                 }
                 let local = e.local_name();
-                if local.as_ref() == b"outerShdw" && in_outer_shdw {
-                    in_outer_shdw = false;
-                    if let Some(color) = shdw_color {
-                        shadow = Some(Shadow {
-                            blur_radius: shdw_blur,
-                            distance: shdw_dist,
-                            direction: shdw_dir,
-                            color,
-                            opacity: shdw_opacity,
-                        });
+                match local.as_ref() {
+                    b"outerShdw" | b"prstShdw" if in_outer_shdw => {
+                        in_outer_shdw = false;
+                        if let Some(mut shadow) = pending_shadow.take()
+                            && let Some(color) = shdw_color
+                        {
+                            shadow.color = color;
+                            shadow.opacity = shdw_opacity;
+                            parsed_effects.shadow = Some(shadow);
+                        }
                     }
+                    b"glow" if in_glow => {
+                        in_glow = false;
+                        if let Some(color) = glow_color
+                            && glow_radius > 0.0
+                        {
+                            parsed_effects.effects.glow = Some(Glow {
+                                radius: glow_radius,
+                                color,
+                                opacity: glow_opacity,
+                            });
+                        }
+                    }
+                    _ => {}
                 }
             }
             Ok(Event::Eof) => break,
@@ -797,9 +858,258 @@ pub(super) fn parse_effect_list(
         }
     }
 
+    parsed_effects
+}
+
+/// Geometry of an `<a:outerShdw>` or `<a:prstShdw>`, with an opaque black
+/// color until a color child says otherwise.
+fn parse_shadow_geometry(e: &BytesStart<'_>) -> Shadow {
+    let mut shadow = Shadow {
+        blur_radius: units::emu_to_pt(get_attr_i64(e, b"blurRad").unwrap_or(0)),
+        distance: units::emu_to_pt(get_attr_i64(e, b"dist").unwrap_or(0)),
+        direction: get_attr_i64(e, b"dir").unwrap_or(0) as f64 / 60_000.0,
+        color: Color::new(0, 0, 0),
+        opacity: 1.0,
+        scale_x: get_attr_i64(e, b"sx").unwrap_or(100_000) as f64 / 100_000.0,
+        scale_y: get_attr_i64(e, b"sy").unwrap_or(100_000) as f64 / 100_000.0,
+        skew_x: get_attr_i64(e, b"kx").unwrap_or(0) as f64 / 60_000.0,
+        skew_y: get_attr_i64(e, b"ky").unwrap_or(0) as f64 / 60_000.0,
+    };
+    if e.local_name().as_ref() == b"prstShdw"
+        && let Some(preset) = get_attr_str(e, b"prst")
+    {
+        (shadow.scale_y, shadow.skew_x) = preset_shadow_perspective(&preset);
+    }
     shadow
 }
 
+/// Vertical scale and horizontal skew of a preset shadow (`shdw1`–`shdw20`).
+/// The drop-shadow presets are plain offset copies; the perspective presets
+/// lay the silhouette flat behind the shape ("back") or fold it forward
+/// below it ("front"), leaning left or right.
+fn preset_shadow_perspective(preset: &str) -> (f64, f64) {
+    match preset {
+        // Back left / back right perspective.
+        "shdw3" => (0.5, 45.0),
+        "shdw4" => (0.5, -45.0),
+        // Front left / front right perspective.
+        "shdw7" => (-0.5, -45.0),
+        "shdw8" => (-0.5, 45.0),
+        // Long back left / long back right perspective.
+        "shdw11" => (1.0, 60.0),
+        "shdw12" => (1.0, -60.0),
+        // Long front left / long front right perspective.
+        "shdw15" => (-1.0, -60.0),
+        "shdw16" => (-1.0, 60.0),
+        // Back center perspective.
+        "shdw19" => (0.5, 0.0),
+        // Front bottom.
+        "shdw20" => (-0.25, 0.0),
+        _ => (1.0, 0.0),
+    }
+}
+
+/// Camera rotation of an `<a:scene3d>`: the explicit `<a:camera><a:rot>`
+/// when present, otherwise the approximate rotation of the camera preset.
+/// `None` for a straight-on camera.
+pub(super) fn parse_scene3d(reader: &mut Reader<&[u8]>) -> Option<Rotation3d> {
+    let mut preset: Option<String> = None;
+    let mut explicit: Option<Rotation3d> = None;
+    let mut in_camera = false;
+    let mut depth: usize = 1;
+    loop {
+        let (e, is_start): (BytesStart<'_>, bool) = match reader.read_event() {
+            Ok(Event::Start(e)) => (e, true),
+            Ok(Event::Empty(e)) => (e, false),
+            Ok(Event::End(e)) => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+                if e.local_name().as_ref() == b"camera" {
+                    in_camera = false;
+                }
+                continue;
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => continue,
+        };
+        if is_start {
+            depth += 1;
+        }
+        match e.local_name().as_ref() {
+            b"camera" => {
+                preset = get_attr_str(&e, b"prst");
+                in_camera = is_start;
+            }
+            b"rot" if in_camera => {
+                let angle =
+                    |name: &[u8]| -> f64 { get_attr_i64(&e, name).unwrap_or(0) as f64 / 60_000.0 };
+                explicit = Some(Rotation3d {
+                    latitude: angle(b"lat"),
+                    longitude: angle(b"lon"),
+                    revolution: angle(b"rev"),
+                });
+            }
+            _ => {}
+        }
+    }
+    explicit
+        .or_else(|| preset.as_deref().and_then(camera_preset_rotation))
+        .filter(|rotation| {
+            rotation.latitude != 0.0 || rotation.longitude != 0.0 || rotation.revolution != 0.0
+        })
+}
+
+/// Approximate rotation of a camera preset (`isometricLeftDown`,
+/// `perspectiveRight`, ...). Isometric presets turn the shape 45° and tilt
+/// it 35°; perspective presets turn or tilt it 20°. Front-facing and oblique
+/// presets have no rotation.
+fn camera_preset_rotation(preset: &str) -> Option<Rotation3d> {
+    let is_isometric: bool = preset.starts_with("isometric");
+    let (turn, tilt): (f64, f64) = if is_isometric {
+        (45.0, 35.0)
+    } else if preset.starts_with("perspective") {
+        (20.0, 20.0)
+    } else {
+        return None;
+    };
+    let mentions = |words: &[&str]| words.iter().any(|word| preset.contains(word));
+    let longitude: f64 = if mentions(&["Left"]) {
+        turn
+    } else if mentions(&["Right"]) {
+        -turn
+    } else {
+        0.0
+    };
+    let latitude: f64 = if mentions(&["Bottom", "Below", "Down"]) {
+        -tilt
+    } else if is_isometric || mentions(&["Top", "Above", "Up", "Relaxed"]) {
+        tilt
+    } else {
+        0.0
+    };
+    Some(Rotation3d {
+        latitude,
+        longitude,
+        revolution: 0.0,
+    })
+}
+
+/// Extrusion of an `<a:sp3d>`, or `None` when `extrusionH` is zero.
+pub(super) fn parse_sp3d(
+    reader: &mut Reader<&[u8]>,
+    e: &BytesStart<'_>,
+    theme: &ThemeData,
+    color_map: &ColorMapData,
+) -> Option<Extrusion> {
+    let extrusion_depth: f64 = units::emu_to_pt(get_attr_i64(e, b"extrusionH").unwrap_or(0));
+    let mut color: Option<Color> = None;
+    let mut in_extrusion_color = false;
+    let mut depth: usize = 1;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref child)) => match child.local_name().as_ref() {
+                b"srgbClr" | b"schemeClr" | b"sysClr" if in_extrusion_color => {
+                    // `parse_color_from_start` consumes the matching end tag too.
+                    color = parse_color_from_start(reader, child, theme, color_map).color;
+                }
+                local => {
+                    in_extrusion_color |= local == b"extrusionClr";
+                    depth += 1;
+                }
+            },
+            Ok(Event::Empty(ref child)) => {
+                if in_extrusion_color
+                    && matches!(
+                        child.local_name().as_ref(),
+                        b"srgbClr" | b"schemeClr" | b"sysClr"
+                    )
+                {
+                    color = parse_color_from_empty(child, theme, color_map).color;
+                }
+            }
+            Ok(Event::End(ref child)) => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+                if child.local_name().as_ref() == b"extrusionClr" {
+                    in_extrusion_color = false;
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    (extrusion_depth > 0.0).then_some(Extrusion {
+        depth: extrusion_depth,
+        color,
+    })
+}
+
+/// Text outline of a run-property `<a:ln>`, or `None` when the line has no
+/// solid fill (`<a:noFill/>`, or no fill at all).
+pub(super) fn parse_text_outline(
+    reader: &mut Reader<&[u8]>,
+    e: &BytesStart<'_>,
+    theme: &ThemeData,
+    color_map: &ColorMapData,
+) -> Option<TextOutline> {
+    // PowerPoint's default text outline is 0.75pt.
+    let width: f64 = units::emu_to_pt(get_attr_i64(e, b"w").unwrap_or(9525));
+    let mut color: Option<Color> = None;
+    let mut in_solid_fill = false;
+    let mut depth: usize = 1;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref child)) => match child.local_name().as_ref() {
+                b"srgbClr" | b"schemeClr" | b"sysClr" if in_solid_fill => {
+                    // `parse_color_from_start` consumes the matching end tag too.
+                    color = parse_color_from_start(reader, child, theme, color_map).color;
+                }
+                local => {
+                    in_solid_fill |= local == b"solidFill";
+                    depth += 1;
+                }
+            },
+            Ok(Event::Empty(ref child)) => {
+                if in_solid_fill
+                    && matches!(
+                        child.local_name().as_ref(),
+                        b"srgbClr" | b"schemeClr" | b"sysClr"
+                    )
+                {
+                    color = parse_color_from_empty(child, theme, color_map).color;
+                }
+            }
+            Ok(Event::End(ref child)) => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+                if child.local_name().as_ref() == b"solidFill" {
+                    in_solid_fill = false;
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    color.map(|color| TextOutline { color, width })
+}
+
+/// Parse the fade attributes of an `<a:reflection>`, applying the
+/// DrawingML defaults (fully opaque start, transparent end, full height).
+fn parse_reflection(e: &BytesStart<'_>) -> Reflection {
+    Reflection {
+        distance: units::emu_to_pt(get_attr_i64(e, b"dist").unwrap_or(0)),
+        start_opacity: get_attr_i64(e, b"stA").unwrap_or(100_000) as f64 / 100_000.0,
+        end_opacity: get_attr_i64(e, b"endA").unwrap_or(0) as f64 / 100_000.0,
+        end_position: get_attr_i64(e, b"endPos").unwrap_or(100_000) as f64 / 100_000.0,
+    }
+}
+
 /// Resolve a font typeface, substituting theme font references.
 pub(super) fn resolve_theme_font(typeface: &str, theme: &ThemeData) -> String {
     match typeface {
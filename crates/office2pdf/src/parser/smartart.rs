@@ -6,7 +6,8 @@ use quick_xml::Reader;
 /// (`http://schemas.openxmlformats.org/drawingml/2006/diagram`).
 use quick_xml::events::Event;
 
-use crate::ir::SmartArtNode;
+use super::xml_util;
+use crate::ir::{SmartArtLayout, SmartArtNode};
 use std::collections::HashMap;
 
 /// Internal representation of a parsed SmartArt point before depth resolution.
@@ -52,43 +53,129 @@ pub(crate) fn parse_smartart_data_xml(xml: &str) -> Vec<SmartArtNode> {
             .push(dest.clone());
     }
 
-    // BFS from doc root to assign depth to each node
+    // BFS from doc root to assign depth (and parent) to each node
     let mut depth_map: HashMap<String, usize> = HashMap::new();
+    let mut parent_map: HashMap<String, String> = HashMap::new();
     if let Some(root_id) = doc_id {
         let mut queue = std::collections::VecDeque::new();
         // Children of doc root are depth 0
         if let Some(children) = children_map.get(&root_id) {
             for child in children {
-                queue.push_back((child.clone(), 0usize));
+                queue.push_back((child.clone(), 0usize, None));
             }
         }
-        while let Some((id, depth)) = queue.pop_front() {
+        while let Some((id, depth, parent)) = queue.pop_front() {
             if depth_map.contains_key(&id) {
                 continue;
             }
             depth_map.insert(id.clone(), depth);
+            if let Some(parent) = parent {
+                parent_map.insert(id.clone(), parent);
+            }
             if let Some(children) = children_map.get(&id) {
                 for child in children {
                     if !depth_map.contains_key(child) {
-                        queue.push_back((child.clone(), depth + 1));
+                        queue.push_back((child.clone(), depth + 1, Some(id.clone())));
                     }
                 }
             }
         }
     }
 
-    // Build result in original document order, using depth from BFS
-    node_order
+    // Build result in original document order, using depth from BFS. Parents
+    // without text are skipped, so their children attach to the nearest
+    // ancestor that is kept.
+    let item_ids: Vec<&String> = node_order
+        .iter()
+        .filter(|id| node_texts.contains_key(*id))
+        .collect();
+    let index_of: HashMap<&String, usize> = item_ids
         .iter()
-        .filter_map(|id| {
-            node_texts.get(id).map(|text| SmartArtNode {
-                text: text.clone(),
-                depth: depth_map.get(id).copied().unwrap_or(0),
-            })
+        .enumerate()
+        .map(|(index, id)| (*id, index))
+        .collect();
+    item_ids
+        .iter()
+        .map(|id| {
+            let mut parent: Option<&String> = parent_map.get(*id);
+            while let Some(parent_id) = parent
+                && !index_of.contains_key(parent_id)
+            {
+                parent = parent_map.get(parent_id);
+            }
+            SmartArtNode {
+                text: node_texts[*id].clone(),
+                depth: depth_map.get(*id).copied().unwrap_or(0),
+                parent: parent.and_then(|parent_id| index_of.get(parent_id).copied()),
+            }
         })
         .collect()
 }
 
+/// Layout type ID (`loTypeId`) recorded on the data model's document point,
+/// e.g. `urn:microsoft.com/office/officeart/2005/8/layout/cycle2`.
+pub(crate) fn parse_data_layout_type_id(xml: &str) -> Option<String> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                if e.local_name().as_ref() == b"prSet" =>
+            {
+                if let Some(id) = xml_util::get_attr_str(e, b"loTypeId") {
+                    return Some(id);
+                }
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
+        }
+    }
+}
+
+/// Layout family of a SmartArt layout definition (`diagrams/layoutN.xml`),
+/// from its `uniqueId` and the `<dgm:alg type>` algorithms it uses.
+pub(crate) fn parse_layout_def_xml(xml: &str) -> SmartArtLayout {
+    let mut reader = Reader::from_str(xml);
+    let mut unique_id = String::new();
+    let mut algorithms: Vec<String> = Vec::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => match e.local_name().as_ref() {
+                b"layoutDef" => {
+                    unique_id = xml_util::get_attr_str(e, b"uniqueId").unwrap_or_default();
+                }
+                b"alg" => algorithms.extend(xml_util::get_attr_str(e, b"type")),
+                _ => {}
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    classify_layout(&unique_id, &algorithms)
+}
+
+/// Map a layout ID and its algorithms to a layout family. The built-in
+/// Office layout IDs name their family (`cycle2`, `orgChart1`, `pyramid1`,
+/// `matrix3`); custom layouts are recognized by their algorithms.
+pub(crate) fn classify_layout(unique_id: &str, algorithms: &[String]) -> SmartArtLayout {
+    let id: String = unique_id
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let uses = |algorithm: &str| algorithms.iter().any(|alg| alg == algorithm);
+    if id.contains("matrix") {
+        SmartArtLayout::Matrix
+    } else if id.contains("pyramid") || uses("pyra") {
+        SmartArtLayout::Pyramid
+    } else if id.contains("cycle") || id.contains("radial") || uses("cycle") {
+        SmartArtLayout::Cycle
+    } else if id.contains("orgchart") || id.contains("hierarchy") || uses("hierRoot") {
+        SmartArtLayout::Hierarchy
+    } else {
+        SmartArtLayout::List
+    }
+}
+
 /// Parse both `<dgm:ptLst>` points and `<dgm:cxnLst>` connections from SmartArt XML.
 ///
 /// Returns (points, connections) where connections are (srcId, destId) pairs
@@ -225,6 +312,8 @@ pub(crate) struct SmartArtRef {
     pub cy: i64,
     /// Relationship ID for the data model (r:dm from dgm:relIds).
     pub data_rid: String,
+    /// Relationship ID for the layout definition (r:lo from dgm:relIds).
+    pub layout_rid: Option<String>,
 }
 
 /// Scan slide XML for SmartArt references within graphicFrame elements.
@@ -301,12 +390,16 @@ pub(crate) fn scan_smartart_refs(slide_xml: &str) -> Vec<SmartArtRef> {
                     b"relIds" if in_graphic_frame => {
                         // <dgm:relIds r:dm="rIdN" .../>
                         let mut data_rid = None;
+                        let mut layout_rid = None;
                         for attr in e.attributes().flatten() {
-                            // r:dm is the data model relationship
-                            if attr.key.as_ref() == b"r:dm"
-                                && let Ok(v) = attr.unescape_value()
-                            {
-                                data_rid = Some(v.to_string());
+                            // r:dm is the data model relationship, r:lo the
+                            // layout definition
+                            if let Ok(v) = attr.unescape_value() {
+                                match attr.key.as_ref() {
+                                    b"r:dm" => data_rid = Some(v.to_string()),
+                                    b"r:lo" => layout_rid = Some(v.to_string()),
+                                    _ => {}
+                                }
                             }
                         }
                         if let Some(rid) = data_rid {
@@ -316,6 +409,7 @@ pub(crate) fn scan_smartart_refs(slide_xml: &str) -> Vec<SmartArtRef> {
                                 cx: gf_cx,
                                 cy: gf_cy,
                                 data_rid: rid,
+                                layout_rid,
                             });
                         }
                     }
@@ -167,6 +167,8 @@ fn test_parse_smartart_data_with_hierarchy() {
     let items = parse_smartart_data_xml(xml);
     assert_eq!(texts(&items), vec!["Manager A", "Manager B", "Employee C"]);
     assert_eq!(depths(&items), vec![0, 0, 1]);
+    let parents: Vec<Option<usize>> = items.iter().map(|n| n.parent).collect();
+    assert_eq!(parents, vec![None, None, Some(0)]);
 }
 
 #[test]
@@ -235,6 +237,7 @@ fn test_scan_smartart_refs_basic() {
     assert_eq!(refs[0].cx, 5486400);
     assert_eq!(refs[0].cy, 3086100);
     assert_eq!(refs[0].data_rid, "rId5");
+    assert_eq!(refs[0].layout_rid.as_deref(), Some("rId6"));
 }
 
 #[test]
@@ -287,3 +290,66 @@ fn test_scan_smartart_refs_multiple() {
     assert_eq!(refs[1].data_rid, "rId20");
     assert_eq!(refs[1].x, 500);
 }
+
+#[test]
+fn test_parse_data_layout_type_id_from_doc_point() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <dgm:dataModel xmlns:dgm="http://schemas.openxmlformats.org/drawingml/2006/diagram">
+          <dgm:ptLst>
+            <dgm:pt modelId="0" type="doc">
+              <dgm:prSet loTypeId="urn:microsoft.com/office/officeart/2005/8/layout/orgChart1"/>
+            </dgm:pt>
+          </dgm:ptLst>
+        </dgm:dataModel>"#;
+
+    assert_eq!(
+        parse_data_layout_type_id(xml).as_deref(),
+        Some("urn:microsoft.com/office/officeart/2005/8/layout/orgChart1")
+    );
+    assert_eq!(parse_data_layout_type_id("<dgm:dataModel/>"), None);
+}
+
+#[test]
+fn test_classify_layout_by_builtin_id() {
+    let layout = |id: &str| classify_layout(id, &[]);
+    assert_eq!(
+        layout("urn:microsoft.com/office/officeart/2005/8/layout/cycle2"),
+        SmartArtLayout::Cycle
+    );
+    assert_eq!(
+        layout("urn:microsoft.com/office/officeart/2005/8/layout/radial1"),
+        SmartArtLayout::Cycle
+    );
+    assert_eq!(
+        layout("urn:microsoft.com/office/officeart/2005/8/layout/orgChart1"),
+        SmartArtLayout::Hierarchy
+    );
+    assert_eq!(
+        layout("urn:microsoft.com/office/officeart/2005/8/layout/pyramid1"),
+        SmartArtLayout::Pyramid
+    );
+    assert_eq!(
+        layout("urn:microsoft.com/office/officeart/2005/8/layout/matrix3"),
+        SmartArtLayout::Matrix
+    );
+    assert_eq!(
+        layout("urn:microsoft.com/office/officeart/2005/8/layout/default"),
+        SmartArtLayout::List
+    );
+}
+
+#[test]
+fn test_parse_layout_def_recognizes_custom_layout_by_algorithm() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <dgm:layoutDef xmlns:dgm="http://schemas.openxmlformats.org/drawingml/2006/diagram"
+                       uniqueId="urn:example.com/layouts/custom">
+          <dgm:layoutNode name="diagram">
+            <dgm:alg type="cycle"/>
+            <dgm:forEach name="nodes" axis="ch" ptType="node">
+              <dgm:layoutNode name="node"><dgm:alg type="tx"/></dgm:layoutNode>
+            </dgm:forEach>
+          </dgm:layoutNode>
+        </dgm:layoutDef>"#;
+
+    assert_eq!(parse_layout_def_xml(xml), SmartArtLayout::Cycle);
+}
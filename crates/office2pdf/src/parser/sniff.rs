@@ -0,0 +1,93 @@
+//! Identification of an input's format from its bytes rather than its
+//! file name: the main part's content type in an OOXML package, and the
+//! stream names of a legacy binary (OLE2) document.
+
+use std::io::{Cursor, Read};
+
+use quick_xml::events::Event;
+
+use crate::config::Format;
+use crate::parser::open_zip;
+use crate::parser::xml_util::get_attr_str;
+
+/// The format of an OOXML package, from the content type of its main part
+/// or, when `[Content_Types].xml` does not name one, from where that part
+/// conventionally lives.
+pub(crate) fn detect_package(data: &[u8]) -> Option<Format> {
+    let mut archive = open_zip(data).ok()?;
+    let mut content_types = String::new();
+    if let Ok(mut entry) = archive.by_name("[Content_Types].xml")
+        && entry.read_to_string(&mut content_types).is_ok()
+        && let Some(format) = format_from_content_types(&content_types)
+    {
+        return Some(format);
+    }
+    let has_entry = |name: &str| archive.file_names().any(|entry| entry == name);
+    if has_entry("word/document.xml") {
+        Some(Format::Docx)
+    } else if has_entry("xl/workbook.xml") {
+        Some(Format::Xlsx)
+    } else if has_entry("ppt/presentation.xml") {
+        Some(Format::Pptx)
+    } else {
+        None
+    }
+}
+
+fn format_from_content_types(xml: &str) -> Option<Format> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e) | Event::Empty(ref e))
+                if e.local_name().as_ref() == b"Override" =>
+            {
+                if let Some(format) = get_attr_str(e, b"ContentType")
+                    .as_deref()
+                    .and_then(format_from_content_type)
+                {
+                    return Some(format);
+                }
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
+        }
+    }
+}
+
+/// Main-part content types of documents, templates and macro-enabled
+/// variants; all share the part layout the parsers read.
+fn format_from_content_type(content_type: &str) -> Option<Format> {
+    match content_type {
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"
+        | "application/vnd.openxmlformats-officedocument.wordprocessingml.template.main+xml"
+        | "application/vnd.ms-word.document.macroEnabled.main+xml"
+        | "application/vnd.ms-word.template.macroEnabledTemplate.main+xml" => Some(Format::Docx),
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"
+        | "application/vnd.openxmlformats-officedocument.spreadsheetml.template.main+xml"
+        | "application/vnd.ms-excel.sheet.macroEnabled.main+xml"
+        | "application/vnd.ms-excel.template.macroEnabled.main+xml" => Some(Format::Xlsx),
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml"
+        | "application/vnd.openxmlformats-officedocument.presentationml.slideshow.main+xml"
+        | "application/vnd.openxmlformats-officedocument.presentationml.template.main+xml"
+        | "application/vnd.ms-powerpoint.presentation.macroEnabled.main+xml"
+        | "application/vnd.ms-powerpoint.slideshow.macroEnabled.main+xml"
+        | "application/vnd.ms-powerpoint.template.macroEnabled.main+xml" => Some(Format::Pptx),
+        _ => None,
+    }
+}
+
+/// A description of the legacy binary format of an OLE2 document, for
+/// telling the caller what they passed; `None` for encrypted OOXML, whose
+/// container is OLE2 too, and for other OLE2 files.
+pub(crate) fn legacy_format(data: &[u8]) -> Option<&'static str> {
+    let file = cfb::CompoundFile::open(Cursor::new(data)).ok()?;
+    if file.exists("/WordDocument") {
+        Some("Word 97-2003 document (.doc)")
+    } else if file.exists("/Workbook") || file.exists("/Book") {
+        Some("Excel 97-2003 workbook (.xls)")
+    } else if file.exists("/PowerPoint Document") {
+        Some("PowerPoint 97-2003 presentation (.ppt)")
+    } else {
+        None
+    }
+}
@@ -1,7 +1,7 @@
 use std::io::Cursor;
 
 use crate::config::ConvertOptions;
-use crate::error::{ConvertError, ConvertWarning};
+use crate::error::{ConvertError, ConvertWarning, WarningLocation};
 use crate::ir::{
     Chart, Document, ImageData, Margins, Metadata, Page, PageSize, SheetPage, StyleSheet, Table,
     TableRow,
@@ -14,19 +14,55 @@ pub(crate) mod cond_fmt_raw;
 mod xlsx_cells;
 #[path = "xlsx_drawing.rs"]
 mod xlsx_drawing;
+#[path = "xlsx_embedded.rs"]
+mod xlsx_embedded;
+#[cfg(feature = "formula-eval")]
+#[path = "xlsx_formula.rs"]
+mod xlsx_formula;
 #[path = "xlsx_hf.rs"]
 mod xlsx_hf;
+#[path = "xlsx_hidden.rs"]
+mod xlsx_hidden;
+#[path = "xlsx_hyperlinks.rs"]
+mod xlsx_hyperlinks;
+#[path = "xlsx_numfmt.rs"]
+mod xlsx_numfmt;
+#[path = "xlsx_page_setup.rs"]
+mod xlsx_page_setup;
 #[path = "xlsx_pagination.rs"]
 mod xlsx_pagination;
+#[path = "xlsx_pivot.rs"]
+mod xlsx_pivot;
+#[path = "xlsx_print_options.rs"]
+mod xlsx_print_options;
+#[path = "xlsx_protection.rs"]
+mod xlsx_protection;
+#[path = "xlsx_ranges.rs"]
+mod xlsx_ranges;
+#[path = "xlsx_rich_text.rs"]
+mod xlsx_rich_text;
+#[path = "xlsx_shrink.rs"]
+mod xlsx_shrink;
 #[path = "xlsx_style.rs"]
 mod xlsx_style;
+#[path = "xlsx_tables.rs"]
+mod xlsx_tables;
+#[path = "xlsx_validation.rs"]
+mod xlsx_validation;
 
 use self::xlsx_cells::*;
 use self::xlsx_drawing::*;
 use self::xlsx_hf::*;
+use self::xlsx_hidden::{HiddenCells, is_hidden_sheet};
+pub(crate) use self::xlsx_numfmt::civil_from_days;
+use self::xlsx_numfmt::{ErrorDisplay, FormatSettings, NumberLocale};
+use self::xlsx_print_options::SheetHeadings;
 
 // Re-export cell address types for cond_fmt module.
-pub(crate) use self::xlsx_cells::{CellPos, CellRange, parse_cell_ref};
+pub(crate) use self::xlsx_cells::{CellPos, CellRange, FormulaCache, parse_cell_ref};
+pub(crate) use self::xlsx_embedded::{parse_embedded_preview, scale_embedded_preview};
+#[cfg(feature = "formula-eval")]
+pub(crate) use self::xlsx_formula::evaluate_condition;
 
 /// Parser for XLSX (Office Open XML Excel) spreadsheets.
 /// Print margins for a sheet: the worksheet's explicit `<pageMargins>` when
@@ -99,9 +135,69 @@ fn title_column_indices(print_titles: PrintTitles, ctx: &SheetContext) -> Option
     Some((start_idx as usize, end_idx as usize))
 }
 
-/// Convert a raw drawing anchor into a render-ready image: 1-indexed anchor
-/// row plus a size in points resolved against the sheet's column widths and
-/// row heights (twoCellAnchor) or the declared extent (oneCellAnchor).
+/// Convert manual column breaks (last column before each break, 1-indexed)
+/// to the 0-based index of the first column on each new page within the
+/// rendered column range.
+fn column_break_indices(breaks: &[u32], ctx: &SheetContext) -> Vec<usize> {
+    breaks
+        .iter()
+        .filter(|col| **col >= ctx.col_start && **col < ctx.col_end)
+        .map(|col| (col - ctx.col_start + 1) as usize)
+        .collect()
+}
+
+/// Apply the sheet's print scale to a page, then split it into column
+/// groups that fit the printable width.
+fn paginate_sheet_page(
+    mut page: SheetPage,
+    print_scale: f64,
+    title_columns: Option<(usize, usize)>,
+    column_breaks: &[usize],
+    column_labels: Option<&[String]>,
+    warnings: &mut Vec<ConvertWarning>,
+) -> Vec<SheetPage> {
+    xlsx_page_setup::scale_sheet_page(&mut page, print_scale);
+    xlsx_pagination::split_sheet_page_by_width(
+        page,
+        title_columns,
+        column_breaks,
+        column_labels,
+        warnings,
+    )
+}
+
+/// Title columns of a sheet's overflow pages: its print titles, else the
+/// `ConvertOptions::xlsx_row_label_columns` leading columns.
+fn overflow_title_columns(
+    title_columns: Option<(usize, usize)>,
+    options: &ConvertOptions,
+) -> Option<(usize, usize)> {
+    title_columns.or_else(|| {
+        let count: usize = options.xlsx_row_label_columns as usize;
+        (count > 0).then_some((0, count))
+    })
+}
+
+/// Letters of the printed columns, after the row-number column when
+/// headings are on, for `ConvertOptions::xlsx_label_column_pages`.
+fn printed_column_letters(ctx: &SheetContext, hidden: &HiddenCells, headings: bool) -> Vec<String> {
+    let mut letters: Vec<String> = Vec::new();
+    if headings {
+        letters.push(String::new());
+    }
+    letters.extend(
+        (ctx.col_start..=ctx.col_end)
+            .filter(|col| !hidden.is_column_hidden(*col))
+            .map(|col| umya_spreadsheet::helper::coordinate::string_from_column_index(&col)),
+    );
+    letters
+}
+
+/// Convert a raw drawing anchor into a render-ready image: anchored after the
+/// sheet row above its top-left corner (like charts), offset from the first
+/// printed column and into its row, and sized against the sheet's column
+/// widths and row heights (twoCellAnchor) or the declared extent
+/// (oneCellAnchor, absoluteAnchor).
 fn anchored_image(
     anchor: xlsx_drawing::RawImageAnchor,
     sheet: &umya_spreadsheet::Worksheet,
@@ -117,7 +213,7 @@ fn anchored_image(
                 .copied()
                 .unwrap_or(0.0)
         } else {
-            column_width_to_pt(DEFAULT_COLUMN_WIDTH, ctx.max_digit_width_px)
+            column_width_to_pt(sheet_default_column_width(sheet), ctx.max_digit_width_px)
         }
     };
     let row_height_at = |row_zero_based: u32| -> f64 {
@@ -132,13 +228,27 @@ fn anchored_image(
         native_excel_pdf_row_height(declared)
     };
 
-    let (width, height): (f64, f64) =
+    // absoluteAnchor gives a position on the sheet; find the cell it lands in.
+    let (from_col, from_col_off_pt, from_row, from_row_off_pt): (u32, f64, u32, f64) =
+        match anchor.pos_emu {
+            Some((x, y)) => {
+                let (col, col_off) = cell_at_offset(x as f64 / EMU_PER_PT, column_width_at);
+                let (row, row_off) = cell_at_offset(y as f64 / EMU_PER_PT, row_height_at);
+                (col, col_off, row, row_off)
+            }
+            None => (
+                anchor.from_col,
+                anchor.from_col_off_emu as f64 / EMU_PER_PT,
+                anchor.from_row,
+                anchor.from_row_off_emu as f64 / EMU_PER_PT,
+            ),
+        };
+
+    let (mut width, mut height): (f64, f64) =
         if let Some((to_col, to_col_off, to_row, to_row_off)) = anchor.to {
-            let width: f64 = (anchor.from_col..to_col).map(column_width_at).sum::<f64>()
-                - anchor.from_col_off_emu as f64 / EMU_PER_PT
+            let width: f64 = (from_col..to_col).map(column_width_at).sum::<f64>() - from_col_off_pt
                 + to_col_off as f64 / EMU_PER_PT;
-            let height: f64 = (anchor.from_row..to_row).map(row_height_at).sum::<f64>()
-                - anchor.from_row_off_emu as f64 / EMU_PER_PT
+            let height: f64 = (from_row..to_row).map(row_height_at).sum::<f64>() - from_row_off_pt
                 + to_row_off as f64 / EMU_PER_PT;
             (width.max(1.0), height.max(1.0))
         } else if let Some((cx, cy)) = anchor.ext_emu {
@@ -150,8 +260,21 @@ fn anchored_image(
             (100.0, 100.0)
         };
 
-    let x_offset_pt: f64 = (0..anchor.from_col).map(column_width_at).sum::<f64>()
-        + anchor.from_col_off_emu as f64 / EMU_PER_PT;
+    // Offsets count from the first printed column, not column A.
+    let first_col: u32 = ctx.col_start.saturating_sub(1);
+    let columns_before: f64 = if from_col >= first_col {
+        (first_col..from_col).map(column_width_at).sum::<f64>()
+    } else {
+        -(from_col..first_col).map(column_width_at).sum::<f64>()
+    };
+    let mut x_offset_pt: f64 = columns_before + from_col_off_pt;
+    let mut y_offset_pt: f64 = from_row_off_pt;
+    if let Some([left, top, part_width, part_height]) = anchor.part {
+        x_offset_pt += left * width;
+        y_offset_pt += top * height;
+        width = (width * part_width).max(1.0);
+        height = (height * part_height).max(1.0);
+    }
 
     let image = ImageData {
         data: anchor.data,
@@ -165,12 +288,95 @@ fn anchored_image(
         shadow: None,
     };
     crate::ir::SheetImage {
-        anchor_row: anchor.from_row + 1,
-        x_offset_pt,
+        anchor_row: from_row,
+        x_offset_pt: x_offset_pt.max(0.0),
+        y_offset_pt: y_offset_pt.max(0.0),
         image,
     }
 }
 
+/// The 0-indexed column or row holding a point `offset` from the sheet's
+/// edge, and the remaining offset into it.
+fn cell_at_offset(offset: f64, size_at: impl Fn(u32) -> f64) -> (u32, f64) {
+    // Excel's grid is 16384 columns by 1048576 rows.
+    const LAST_INDEX: u32 = 1_048_575;
+    let mut remaining: f64 = offset.max(0.0);
+    let mut index: u32 = 0;
+    while index < LAST_INDEX {
+        let size: f64 = size_at(index);
+        if remaining < size {
+            break;
+        }
+        remaining -= size;
+        index += 1;
+    }
+    (index, remaining)
+}
+
+/// A sheet's entry in a per-sheet map: taken for the sheet's last range,
+/// cloned while later ranges of the same sheet still need it.
+fn sheet_entry<T: Clone>(
+    map: &mut std::collections::HashMap<String, T>,
+    sheet_name: &str,
+    shared: bool,
+) -> Option<T> {
+    if shared {
+        map.get(sheet_name).cloned()
+    } else {
+        map.remove(sheet_name)
+    }
+}
+
+/// Whether a drawing anchored after sheet row `row` belongs on the page of
+/// an `xlsx_ranges` selection. Whole sheets keep all their drawings.
+fn anchored_in_area(area: Option<CellRange>, row_start: u32, row_end: u32, row: u32) -> bool {
+    area.is_none() || (row + 1 >= row_start && row < row_end)
+}
+
+/// Move a 1-indexed sheet row anchor onto the printed rows: skip hidden rows
+/// and count from the first printed row.
+fn printed_anchor_row(hidden: &HiddenCells, row_start: u32, row: u32) -> u32 {
+    hidden
+        .visible_anchor_row(row)
+        .saturating_sub(row_start.saturating_sub(1))
+}
+
+/// Where one streaming chunk sits among a sheet's printed rows, for moving
+/// row-anchored drawings into it.
+struct ChunkAnchors {
+    /// Printed rows in the sheet's earlier chunks.
+    rows_before: u32,
+    /// Printed rows of this chunk.
+    rows: u32,
+    /// Repeated title rows ahead of the chunk's own rows.
+    lead_rows: u32,
+    /// The sheet's last chunk, which takes every remaining drawing.
+    last: bool,
+}
+
+impl ChunkAnchors {
+    /// Take the drawings (sorted by anchor row) anchored within this chunk
+    /// and re-anchor them to its table. A drawing anchored after a chunk's
+    /// last row opens the next chunk instead, which prints at the same spot.
+    fn take<T>(&self, items: &mut Vec<T>, anchor_row: impl Fn(&mut T) -> &mut u32) -> Vec<T> {
+        let end: u32 = self.rows_before + self.rows;
+        let count: usize = if self.last {
+            items.len()
+        } else {
+            items
+                .iter_mut()
+                .position(|item| *anchor_row(item) >= end)
+                .unwrap_or(items.len())
+        };
+        let mut taken: Vec<T> = items.drain(..count).collect();
+        for item in &mut taken {
+            let row: &mut u32 = anchor_row(item);
+            *row = row.saturating_sub(self.rows_before) + self.lead_rows;
+        }
+        taken
+    }
+}
+
 /// Context stand-in for sheets with no used cells, so drawing anchors can
 /// still resolve against default column widths and row heights.
 fn empty_sheet_context() -> SheetContext {
@@ -183,9 +389,51 @@ fn empty_sheet_context() -> SheetContext {
         merge_tops: std::collections::HashMap::new(),
         merge_skips: std::collections::HashSet::new(),
         cond_fmt_overrides: std::collections::HashMap::new(),
+        shrink_to_fit_cells: std::collections::HashSet::new(),
+        rich_run_vertical_aligns: std::collections::HashMap::new(),
+        hyperlinks: xlsx_hyperlinks::CellHyperlinks::default(),
+        table_styles: std::collections::HashMap::new(),
+        gridlines: false,
+        number_format: FormatSettings::default(),
+        formulas: xlsx_cells::FormulaCache::default(),
+        redacted_cells: std::collections::HashSet::new(),
+        dropdowns: Vec::new(),
+    }
+}
+
+/// The workbook's date system and `ConvertOptions::locale`. A locale we
+/// have no separators for is reported and numbers keep the en-US look.
+fn format_settings(
+    data: &[u8],
+    options: &ConvertOptions,
+    warnings: &mut Vec<ConvertWarning>,
+) -> FormatSettings {
+    let locale: Option<NumberLocale> = options.locale.as_deref().and_then(|tag| {
+        let locale: Option<NumberLocale> = NumberLocale::from_tag(tag);
+        if locale.is_none() {
+            warnings.push(ConvertWarning::ParseSkipped {
+                format: "XLSX".to_string(),
+                reason: format!("unknown locale '{tag}', using en-US number formats"),
+                location: None,
+            });
+        }
+        locale
+    });
+    FormatSettings {
+        date1904: extract_date1904(data),
+        locale,
+        ..FormatSettings::default()
     }
 }
 
+/// Row numbers and column letters over the printed columns of a sheet.
+fn sheet_headings(ctx: &SheetContext, hidden: &HiddenCells, row_end: u32) -> SheetHeadings {
+    let columns: Vec<u32> = (ctx.col_start..=ctx.col_end)
+        .filter(|col| !hidden.is_column_hidden(*col))
+        .collect();
+    SheetHeadings::new(&columns, row_end)
+}
+
 /// Convert a raw text-box anchor into a render-ready box, sized like images.
 fn anchored_text_box(
     anchor: xlsx_drawing::RawTextBoxAnchor,
@@ -200,6 +448,8 @@ fn anchored_text_box(
             from_row_off_emu: anchor.geometry.from_row_off_emu,
             to: anchor.geometry.to,
             ext_emu: anchor.geometry.ext_emu,
+            pos_emu: anchor.geometry.pos_emu,
+            part: anchor.geometry.part,
             data: Vec::new(),
             format: crate::ir::ImageFormat::Png,
         },
@@ -233,12 +483,14 @@ impl XlsxParser {
         chunk_size: usize,
     ) -> Result<(Vec<Document>, Vec<ConvertWarning>), ConvertError> {
         let cursor = Cursor::new(data);
-        let book = umya_spreadsheet::reader::xlsx::read_reader(cursor, true).map_err(|e| {
+        let mut book = umya_spreadsheet::reader::xlsx::read_reader(cursor, true).map_err(|e| {
             crate::parser::parse_err(format!("Failed to parse XLSX (umya-spreadsheet): {e}"))
         })?;
+        xlsx_pivot::apply_pivot_tables(&mut book, data);
 
-        let metadata = extract_xlsx_metadata(&book);
+        let metadata = extract_xlsx_metadata(&book, data);
         let cond_fmt_hints = cond_fmt_raw::extract_cond_fmt_hints(data);
+        let sheet_scaling = xlsx_page_setup::extract_sheet_scaling(data);
         // Excel derives every column print metric from the workbook Normal
         // font; cell fonts do not participate (issue #366).
         let normal_font_mdw: Option<f64> = extract_normal_font(data)
@@ -247,27 +499,58 @@ impl XlsxParser {
         let mut chart_map = extract_charts_with_anchors(data);
         let mut image_map = extract_images_with_anchors(data);
         let mut text_box_map = extract_text_boxes_with_anchors(data);
+        let mut hf_image_map = extract_hf_images(data);
+        let mut shrink_to_fit_map = xlsx_shrink::extract_shrink_to_fit_cells(data);
+        let mut rich_run_map = xlsx_rich_text::extract_rich_run_vertical_aligns(data);
+        let mut hyperlink_map = xlsx_hyperlinks::extract_hyperlinks(data);
+        let mut table_style_map = xlsx_tables::extract_table_styles(data);
+        let print_options_map = xlsx_print_options::extract_print_options(data);
+        let mut dropdown_map = if options.xlsx_annotate_dropdowns {
+            xlsx_validation::extract_dropdowns(data)
+        } else {
+            std::collections::HashMap::new()
+        };
 
         let mut chunks = Vec::new();
         let mut warnings = Vec::new();
+        let number_format: FormatSettings = format_settings(data, options, &mut warnings);
 
-        for sheet in book.get_sheet_collection() {
+        let targets: Vec<(&umya_spreadsheet::Worksheet, Option<CellRange>)> =
+            xlsx_ranges::sheet_targets(&book, options, &mut warnings);
+        for (index, &(sheet, area)) in targets.iter().enumerate() {
+            // Later ranges of the same sheet still need its per-sheet data.
+            let shared: bool = targets[index + 1..]
+                .iter()
+                .any(|(later, _)| later.get_name() == sheet.get_name());
             // Filter by sheet name if specified
             if let Some(ref names) = options.sheet_names
                 && !names.iter().any(|n| n == sheet.get_name())
             {
                 continue;
             }
+            if !options.include_hidden && is_hidden_sheet(sheet) {
+                warnings.push(ConvertWarning::ContentOmitted {
+                    format: "XLSX".to_string(),
+                    content: format!("hidden sheet '{}'", sheet.get_name()),
+                    location: Some(WarningLocation::Sheet(sheet.get_name().to_string())),
+                });
+                continue;
+            }
 
-            let Some((ctx, row_start, row_end)) =
-                prepare_sheet_context(sheet, normal_font_mdw, cond_fmt_hints.get(sheet.get_name()))
-            else {
+            let Some((mut ctx, row_start, row_end)) = prepare_sheet_context(
+                &book,
+                sheet,
+                area,
+                normal_font_mdw,
+                cond_fmt_hints.get(sheet.get_name()),
+                number_format,
+            ) else {
                 // A sheet without used cells can still carry drawings; give
                 // its images a page instead of dropping them.
                 let sheet_name = sheet.get_name().to_string();
-                let raw_images = image_map.remove(&sheet_name);
-                let raw_text_boxes = text_box_map.remove(&sheet_name);
-                let raw_charts = chart_map.remove(&sheet_name);
+                let raw_images = sheet_entry(&mut image_map, &sheet_name, shared);
+                let raw_text_boxes = sheet_entry(&mut text_box_map, &sheet_name, shared);
+                let raw_charts = sheet_entry(&mut chart_map, &sheet_name, shared);
                 if raw_images.is_some() || raw_text_boxes.is_some() || raw_charts.is_some() {
                     let stub_ctx = empty_sheet_context();
                     let images: Vec<crate::ir::SheetImage> = raw_images
@@ -301,116 +584,365 @@ impl XlsxParser {
                 }
                 continue;
             };
+            ctx.shrink_to_fit_cells =
+                sheet_entry(&mut shrink_to_fit_map, sheet.get_name(), shared).unwrap_or_default();
+            ctx.rich_run_vertical_aligns =
+                sheet_entry(&mut rich_run_map, sheet.get_name(), shared).unwrap_or_default();
+            ctx.hyperlinks = xlsx_hyperlinks::CellHyperlinks::resolve(
+                sheet_entry(&mut hyperlink_map, sheet.get_name(), shared).unwrap_or_default(),
+                &book,
+                sheet,
+            );
+            ctx.table_styles =
+                sheet_entry(&mut table_style_map, sheet.get_name(), shared).unwrap_or_default();
+            let print_options: xlsx_print_options::PrintOptions = print_options_map
+                .get(sheet.get_name())
+                .copied()
+                .unwrap_or_default();
+            ctx.gridlines = options.xlsx_gridlines || print_options.gridlines;
+            ctx.number_format.errors = if options.xlsx_blank_errors {
+                ErrorDisplay::Blank
+            } else {
+                print_options.errors
+            };
+            ctx.dropdowns = sheet_entry(&mut dropdown_map, sheet.get_name(), shared)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|dropdown| {
+                    dropdown.overlaps(ctx.col_start, ctx.col_end, row_start, row_end)
+                })
+                .collect();
 
             let sheet_name = sheet.get_name().to_string();
+            if options.xlsx_redact_hidden {
+                ctx.redacted_cells =
+                    xlsx_protection::redacted_cells(sheet, &ctx, row_start, row_end);
+                if !ctx.redacted_cells.is_empty() {
+                    warnings.push(xlsx_protection::redaction_warning(
+                        &sheet_name,
+                        ctx.redacted_cells.len(),
+                    ));
+                }
+            }
+            let hidden: HiddenCells = if options.include_hidden {
+                HiddenCells::default()
+            } else {
+                HiddenCells::for_range(sheet, &ctx, row_start, row_end)
+            };
+            if let Some(summary) = hidden.summary() {
+                warnings.push(ConvertWarning::ContentOmitted {
+                    format: "XLSX".to_string(),
+                    content: format!("{summary} on sheet '{sheet_name}'"),
+                    location: Some(WarningLocation::Sheet(sheet_name.clone())),
+                });
+            }
+            hidden.zero_hidden_widths(&mut ctx.column_widths);
+            let column_widths: Vec<f64> = hidden.visible_widths(&ctx.column_widths);
+            let headings: Option<SheetHeadings> = (options.xlsx_headings || print_options.headings)
+                .then(|| sheet_headings(&ctx, &hidden, row_end));
 
             // Extract sheet header/footer
             let hf = sheet.get_header_footer();
-            let sheet_header = parse_hf_format_string(hf.get_odd_header().get_value());
-            let sheet_footer = parse_hf_format_string(hf.get_odd_footer().get_value());
+            let hf_context = HfContext::for_sheet(
+                &sheet_name,
+                sheet_entry(&mut hf_image_map, &sheet_name, shared).unwrap_or_default(),
+                &number_format,
+            );
+            let sheet_header = parse_hf_format_string(
+                hf.get_odd_header().get_value(),
+                &hf_context,
+                HfPart::Header,
+            );
+            let sheet_footer = parse_hf_format_string(
+                hf.get_odd_footer().get_value(),
+                &hf_context,
+                HfPart::Footer,
+            );
 
             // Pull charts for this sheet
-            let mut sheet_charts = chart_map.remove(&sheet_name).unwrap_or_default();
+            let mut sheet_charts: Vec<(u32, Chart)> =
+                sheet_entry(&mut chart_map, &sheet_name, shared).unwrap_or_default();
+            sheet_charts.retain(|(row, _)| anchored_in_area(area, row_start, row_end, *row));
             for (_, chart) in &sheet_charts {
                 let title = chart.title.as_deref().unwrap_or("untitled").to_string();
                 warnings.push(ConvertWarning::FallbackUsed {
                     format: "XLSX".to_string(),
                     from: format!("chart ({title})"),
                     to: "data table".to_string(),
+                    location: Some(WarningLocation::Sheet(sheet_name.clone())),
                 });
             }
+            for (row, _) in &mut sheet_charts {
+                *row = printed_anchor_row(&hidden, row_start, *row);
+            }
             sheet_charts.sort_by_key(|(row, _)| *row);
-            let mut sheet_images: Vec<crate::ir::SheetImage> = image_map
-                .remove(&sheet_name)
-                .unwrap_or_default()
-                .into_iter()
-                .map(|anchor| anchored_image(anchor, sheet, &ctx))
-                .collect();
+            let mut sheet_images: Vec<crate::ir::SheetImage> =
+                sheet_entry(&mut image_map, &sheet_name, shared)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|anchor| anchored_image(anchor, sheet, &ctx))
+                    .filter(|sheet_image| {
+                        anchored_in_area(area, row_start, row_end, sheet_image.anchor_row)
+                    })
+                    .map(|mut sheet_image| {
+                        sheet_image.anchor_row =
+                            printed_anchor_row(&hidden, row_start, sheet_image.anchor_row);
+                        sheet_image
+                    })
+                    .collect();
             sheet_images.sort_by_key(|sheet_image| sheet_image.anchor_row);
-            let mut sheet_text_boxes: Vec<crate::ir::SheetTextBox> = text_box_map
-                .remove(&sheet_name)
-                .unwrap_or_default()
-                .into_iter()
-                .map(|anchor| anchored_text_box(anchor, sheet, &ctx))
-                .collect();
+            let mut sheet_text_boxes: Vec<crate::ir::SheetTextBox> =
+                sheet_entry(&mut text_box_map, &sheet_name, shared)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|anchor| anchored_text_box(anchor, sheet, &ctx))
+                    .filter(|text_box| {
+                        anchored_in_area(area, row_start, row_end, text_box.anchor_row)
+                    })
+                    .map(|mut text_box| {
+                        text_box.anchor_row =
+                            printed_anchor_row(&hidden, row_start, text_box.anchor_row);
+                        text_box
+                    })
+                    .collect();
             sheet_text_boxes.sort_by_key(|text_box| text_box.anchor_row);
 
-            let print_titles = find_print_titles(&book, sheet);
-            let title_columns: Option<(usize, usize)> = title_column_indices(print_titles, &ctx);
+            let print_titles: PrintTitles = if options.xlsx_ignore_frozen_panes {
+                find_print_titles(&book, sheet)
+            } else {
+                print_options.with_frozen_titles(find_print_titles(&book, sheet))
+            };
+            let title_columns: Option<(usize, usize)> = title_column_indices(print_titles, &ctx)
+                .map(|(start, end)| {
+                    (
+                        hidden.visible_column_index(start),
+                        hidden.visible_column_index(end),
+                    )
+                })
+                .filter(|(start, end)| start < end);
+            let title_columns: Option<(usize, usize)> =
+                overflow_title_columns(title_columns, options);
+            let title_columns: Option<(usize, usize)> = match &headings {
+                Some(headings) => Some(headings.title_columns(title_columns)),
+                None => title_columns,
+            };
+            let column_labels: Option<Vec<String>> = options
+                .xlsx_label_column_pages
+                .then(|| printed_column_letters(&ctx, &hidden, headings.is_some()));
+            // Page setup as in `parse`. Fit-to-page scales the whole sheet,
+            // so every row's height counts; they are measured a chunk at a
+            // time to keep memory bounded.
+            let scaling: xlsx_page_setup::SheetScaling = sheet_scaling
+                .get(sheet.get_name())
+                .copied()
+                .unwrap_or_default();
+            let page_size: PageSize = sheet_page_size(sheet);
+            let margins: Margins = sheet_print_margins(sheet);
+            let printed_widths: Vec<f64> = headings
+                .iter()
+                .map(|headings| headings.width)
+                .chain(column_widths.iter().copied())
+                .collect();
+            let mut measured_rows: Vec<TableRow> = Vec::new();
+            if scaling.fit_to_page {
+                let mut measure_start: u32 = row_start;
+                while measure_start <= row_end {
+                    let measure_end: u32 = (measure_start + chunk_size as u32 - 1).min(row_end);
+                    measured_rows.extend(
+                        hidden
+                            .remove_from(
+                                build_rows_for_range(sheet, &ctx, measure_start, measure_end),
+                                measure_start,
+                            )
+                            .into_iter()
+                            .map(|row| TableRow {
+                                cells: Vec::new(),
+                                height: row.height,
+                            }),
+                    );
+                    measure_start = measure_end + 1;
+                }
+            }
+            let print_scale: f64 = scaling.factor(
+                &printed_widths,
+                &measured_rows,
+                page_size.width - margins.left - margins.right,
+                page_size.height - margins.top - margins.bottom,
+            );
+            drop(measured_rows);
+            let (column_breaks, row_breaks): (Vec<usize>, Vec<u32>) = if scaling.fit_to_page {
+                (Vec::new(), Vec::new())
+            } else {
+                (
+                    column_break_indices(&collect_column_breaks(sheet), &ctx)
+                        .into_iter()
+                        .map(|index| {
+                            hidden.visible_column_index(index) + usize::from(headings.is_some())
+                        })
+                        .collect(),
+                    collect_row_breaks(sheet),
+                )
+            };
 
-            // Process rows in chunks
+            // Process rows in chunks. A chunk also ends at a manual row
+            // break, so the break falls between two chunk PDFs.
             let mut chunk_start = row_start;
-            let mut first_chunk = true;
+            // Printed rows of the sheet in earlier chunks.
+            let mut rows_before: u32 = 0;
             while chunk_start <= row_end {
-                let chunk_end = (chunk_start + chunk_size as u32 - 1).min(row_end);
+                let chunk_end: u32 = row_breaks
+                    .iter()
+                    .copied()
+                    .find(|row| *row >= chunk_start)
+                    .unwrap_or(row_end)
+                    .min(chunk_start + chunk_size as u32 - 1)
+                    .min(row_end);
 
-                let mut rows = build_rows_for_range(sheet, &ctx, chunk_start, chunk_end);
+                let mut rows: Vec<TableRow> = hidden.remove_from(
+                    build_rows_for_range(sheet, &ctx, chunk_start, chunk_end),
+                    chunk_start,
+                );
+                let mut row_numbers: Vec<u32> = (chunk_start..=chunk_end)
+                    .filter(|row| !hidden.is_row_hidden(*row))
+                    .collect();
+                let chunk_rows: u32 = row_numbers.len() as u32;
                 let mut header_row_count: usize = 0;
+                // Title rows repeated ahead of this chunk's own rows.
+                let mut lead_rows: u32 = 0;
                 if let Some((title_start, title_end)) = print_titles.rows
                     && title_end < chunk_start
+                    && title_end >= row_start
                 {
                     // Later chunks don't contain the title rows — prepend them.
-                    let mut title_rows = build_rows_for_range(sheet, &ctx, title_start, title_end);
+                    let title_first_row: u32 = title_start.max(row_start);
+                    let mut title_rows: Vec<TableRow> = hidden.remove_from(
+                        build_rows_for_range(sheet, &ctx, title_first_row, title_end),
+                        title_first_row,
+                    );
                     header_row_count = title_rows.len();
+                    lead_rows = header_row_count as u32;
                     title_rows.append(&mut rows);
                     rows = title_rows;
+                    let mut title_numbers: Vec<u32> = (title_first_row..=title_end)
+                        .filter(|row| !hidden.is_row_hidden(*row))
+                        .collect();
+                    title_numbers.append(&mut row_numbers);
+                    row_numbers = title_numbers;
                 } else if let Some((_, title_end)) = print_titles.rows
                     && title_end >= chunk_start
                     && title_end <= chunk_end
                 {
-                    header_row_count = (title_end - chunk_start + 1) as usize;
+                    header_row_count = (chunk_start..=title_end)
+                        .filter(|row| !hidden.is_row_hidden(*row))
+                        .count();
                 }
 
-                let doc = Document {
+                // Drawings go to the chunk holding their anchor row.
+                let last_chunk: bool = chunk_end == row_end;
+                let anchors = ChunkAnchors {
+                    rows_before,
+                    rows: chunk_rows,
+                    lead_rows,
+                    last: last_chunk,
+                };
+                let mut page = SheetPage {
+                    name: sheet_name.clone(),
+                    size: page_size,
+                    margins,
+                    table: Table {
+                        rows,
+                        column_widths: column_widths.clone(),
+                        header_row_count,
+                        alignment: None,
+                        default_cell_padding: Some(xlsx_cells::XLSX_CELL_PADDING),
+                        use_content_driven_row_heights: false,
+                        default_vertical_align: Some(crate::ir::CellVerticalAlign::Bottom),
+                    },
+                    header: sheet_header.clone(),
+                    footer: sheet_footer.clone(),
+                    charts: anchors.take(&mut sheet_charts, |chart| &mut chart.0),
+                    images: anchors
+                        .take(&mut sheet_images, |sheet_image| &mut sheet_image.anchor_row),
+                    text_boxes: anchors
+                        .take(&mut sheet_text_boxes, |text_box| &mut text_box.anchor_row),
+                };
+                if let Some(headings) = &headings {
+                    headings.apply(&mut page, &row_numbers);
+                }
+                let mut doc = Document {
                     metadata: metadata.clone(),
-                    pages: xlsx_pagination::split_sheet_page_by_width(
-                        SheetPage {
-                            name: sheet_name.clone(),
-                            size: sheet_page_size(sheet),
-                            margins: sheet_print_margins(sheet),
-                            table: Table {
-                                rows,
-                                column_widths: ctx.column_widths.clone(),
-                                header_row_count,
-                                alignment: None,
-                                default_cell_padding: Some(xlsx_cells::XLSX_CELL_PADDING),
-                                use_content_driven_row_heights: false,
-                                default_vertical_align: Some(crate::ir::CellVerticalAlign::Bottom),
-                            },
-                            header: sheet_header.clone(),
-                            footer: sheet_footer.clone(),
-                            charts: if first_chunk {
-                                std::mem::take(&mut sheet_charts)
-                            } else {
-                                vec![]
-                            },
-                            images: if first_chunk {
-                                std::mem::take(&mut sheet_images)
-                            } else {
-                                vec![]
-                            },
-                            text_boxes: if first_chunk {
-                                first_chunk = false;
-                                std::mem::take(&mut sheet_text_boxes)
-                            } else {
-                                vec![]
-                            },
-                        },
+                    pages: paginate_sheet_page(
+                        page,
+                        print_scale,
                         title_columns,
+                        &column_breaks,
+                        column_labels.as_deref(),
+                        &mut warnings,
                     )
                     .into_iter()
                     .map(Page::Sheet)
                     .collect(),
                     styles: StyleSheet::default(),
                 };
+                // Each chunk renders on its own, so links can only reach
+                // sheets within the same chunk.
+                xlsx_hyperlinks::drop_dangling_sheet_links(&mut doc.pages);
 
                 chunks.push(doc);
+                rows_before += chunk_rows;
                 chunk_start = chunk_end + 1;
             }
+            if let Some(appendix) = xlsx_validation::dropdown_appendix(
+                &book,
+                &sheet_name,
+                &ctx.dropdowns,
+                &ctx.number_format,
+                page_size,
+                margins,
+            ) {
+                chunks.push(Document {
+                    metadata: metadata.clone(),
+                    pages: vec![Page::Sheet(appendix)],
+                    styles: StyleSheet::default(),
+                });
+            }
         }
 
         Ok((chunks, warnings))
     }
+
+    /// Parse XLSX into one `Document` per printed sheet, in workbook (or
+    /// `xlsx_ranges`) order, for callers that need to know where each sheet
+    /// starts. Each sheet renders on its own, so links between sheets are
+    /// dropped.
+    pub fn parse_by_sheet(
+        &self,
+        data: &[u8],
+        options: &ConvertOptions,
+    ) -> Result<(Vec<Document>, Vec<ConvertWarning>), ConvertError> {
+        let (doc, warnings) = self.parse(data, options)?;
+        let mut sheets: Vec<Document> = Vec::new();
+        for page in doc.pages {
+            let same_sheet: bool = match (sheets.last().and_then(|last| last.pages.last()), &page) {
+                (Some(Page::Sheet(previous)), Page::Sheet(current)) => {
+                    previous.name == current.name
+                }
+                _ => false,
+            };
+            match sheets.last_mut() {
+                Some(last) if same_sheet => last.pages.push(page),
+                _ => sheets.push(Document {
+                    metadata: doc.metadata.clone(),
+                    pages: vec![page],
+                    styles: doc.styles.clone(),
+                }),
+            }
+        }
+        for sheet in &mut sheets {
+            xlsx_hyperlinks::drop_dangling_sheet_links(&mut sheet.pages);
+        }
+        Ok((sheets, warnings))
+    }
 }
 
 impl Parser for XlsxParser {
@@ -420,13 +952,15 @@ impl Parser for XlsxParser {
         options: &ConvertOptions,
     ) -> Result<(Document, Vec<ConvertWarning>), ConvertError> {
         let cursor = Cursor::new(data);
-        let book = umya_spreadsheet::reader::xlsx::read_reader(cursor, true).map_err(|e| {
+        let mut book = umya_spreadsheet::reader::xlsx::read_reader(cursor, true).map_err(|e| {
             crate::parser::parse_err(format!("Failed to parse XLSX (umya-spreadsheet): {e}"))
         })?;
+        xlsx_pivot::apply_pivot_tables(&mut book, data);
 
         // Extract metadata from umya-spreadsheet properties
-        let metadata = extract_xlsx_metadata(&book);
+        let metadata = extract_xlsx_metadata(&book, data);
         let cond_fmt_hints = cond_fmt_raw::extract_cond_fmt_hints(data);
+        let sheet_scaling = xlsx_page_setup::extract_sheet_scaling(data);
         // Excel derives every column print metric from the workbook Normal
         // font; cell fonts do not participate (issue #366).
         let normal_font_mdw: Option<f64> = extract_normal_font(data)
@@ -436,28 +970,59 @@ impl Parser for XlsxParser {
         let mut chart_map = extract_charts_with_anchors(data);
         let mut image_map = extract_images_with_anchors(data);
         let mut text_box_map = extract_text_boxes_with_anchors(data);
+        let mut hf_image_map = extract_hf_images(data);
+        let mut shrink_to_fit_map = xlsx_shrink::extract_shrink_to_fit_cells(data);
+        let mut rich_run_map = xlsx_rich_text::extract_rich_run_vertical_aligns(data);
+        let mut hyperlink_map = xlsx_hyperlinks::extract_hyperlinks(data);
+        let mut table_style_map = xlsx_tables::extract_table_styles(data);
+        let print_options_map = xlsx_print_options::extract_print_options(data);
+        let mut dropdown_map = if options.xlsx_annotate_dropdowns {
+            xlsx_validation::extract_dropdowns(data)
+        } else {
+            std::collections::HashMap::new()
+        };
 
         let sheet_count = book.get_sheet_collection().len();
         let mut pages = Vec::with_capacity(sheet_count);
         let mut warnings = Vec::new();
+        let number_format: FormatSettings = format_settings(data, options, &mut warnings);
 
-        for sheet in book.get_sheet_collection() {
+        let targets: Vec<(&umya_spreadsheet::Worksheet, Option<CellRange>)> =
+            xlsx_ranges::sheet_targets(&book, options, &mut warnings);
+        for (index, &(sheet, area)) in targets.iter().enumerate() {
+            // Later ranges of the same sheet still need its per-sheet data.
+            let shared: bool = targets[index + 1..]
+                .iter()
+                .any(|(later, _)| later.get_name() == sheet.get_name());
             // Filter by sheet name if specified
             if let Some(ref names) = options.sheet_names
                 && !names.iter().any(|n| n == sheet.get_name())
             {
                 continue;
             }
+            if !options.include_hidden && is_hidden_sheet(sheet) {
+                warnings.push(ConvertWarning::ContentOmitted {
+                    format: "XLSX".to_string(),
+                    content: format!("hidden sheet '{}'", sheet.get_name()),
+                    location: Some(WarningLocation::Sheet(sheet.get_name().to_string())),
+                });
+                continue;
+            }
 
-            let Some((ctx, row_start, row_end)) =
-                prepare_sheet_context(sheet, normal_font_mdw, cond_fmt_hints.get(sheet.get_name()))
-            else {
+            let Some((mut ctx, row_start, row_end)) = prepare_sheet_context(
+                &book,
+                sheet,
+                area,
+                normal_font_mdw,
+                cond_fmt_hints.get(sheet.get_name()),
+                number_format,
+            ) else {
                 // A sheet without used cells can still carry drawings; give
                 // its images a page instead of dropping them.
                 let sheet_name = sheet.get_name().to_string();
-                let raw_images = image_map.remove(&sheet_name);
-                let raw_text_boxes = text_box_map.remove(&sheet_name);
-                let raw_charts = chart_map.remove(&sheet_name);
+                let raw_images = sheet_entry(&mut image_map, &sheet_name, shared);
+                let raw_text_boxes = sheet_entry(&mut text_box_map, &sheet_name, shared);
+                let raw_charts = sheet_entry(&mut chart_map, &sheet_name, shared);
                 if raw_images.is_some() || raw_text_boxes.is_some() || raw_charts.is_some() {
                     let stub_ctx = empty_sheet_context();
                     let images: Vec<crate::ir::SheetImage> = raw_images
@@ -487,11 +1052,121 @@ impl Parser for XlsxParser {
                 }
                 continue;
             };
+            ctx.shrink_to_fit_cells =
+                sheet_entry(&mut shrink_to_fit_map, sheet.get_name(), shared).unwrap_or_default();
+            ctx.rich_run_vertical_aligns =
+                sheet_entry(&mut rich_run_map, sheet.get_name(), shared).unwrap_or_default();
+            ctx.hyperlinks = xlsx_hyperlinks::CellHyperlinks::resolve(
+                sheet_entry(&mut hyperlink_map, sheet.get_name(), shared).unwrap_or_default(),
+                &book,
+                sheet,
+            );
+            ctx.table_styles =
+                sheet_entry(&mut table_style_map, sheet.get_name(), shared).unwrap_or_default();
+            let print_options: xlsx_print_options::PrintOptions = print_options_map
+                .get(sheet.get_name())
+                .copied()
+                .unwrap_or_default();
+            ctx.gridlines = options.xlsx_gridlines || print_options.gridlines;
+            ctx.number_format.errors = if options.xlsx_blank_errors {
+                ErrorDisplay::Blank
+            } else {
+                print_options.errors
+            };
+            ctx.dropdowns = sheet_entry(&mut dropdown_map, sheet.get_name(), shared)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|dropdown| {
+                    dropdown.overlaps(ctx.col_start, ctx.col_end, row_start, row_end)
+                })
+                .collect();
+            if options.xlsx_redact_hidden {
+                ctx.redacted_cells =
+                    xlsx_protection::redacted_cells(sheet, &ctx, row_start, row_end);
+                if !ctx.redacted_cells.is_empty() {
+                    warnings.push(xlsx_protection::redaction_warning(
+                        sheet.get_name(),
+                        ctx.redacted_cells.len(),
+                    ));
+                }
+            }
+
+            let hidden: HiddenCells = if options.include_hidden {
+                HiddenCells::default()
+            } else {
+                HiddenCells::for_range(sheet, &ctx, row_start, row_end)
+            };
+            if let Some(summary) = hidden.summary() {
+                warnings.push(ConvertWarning::ContentOmitted {
+                    format: "XLSX".to_string(),
+                    content: format!("{summary} on sheet '{}'", sheet.get_name()),
+                    location: Some(WarningLocation::Sheet(sheet.get_name().to_string())),
+                });
+            }
+            hidden.zero_hidden_widths(&mut ctx.column_widths);
+            let column_widths: Vec<f64> = hidden.visible_widths(&ctx.column_widths);
+            let row_numbers: Vec<u32> = (row_start..=row_end)
+                .filter(|row| !hidden.is_row_hidden(*row))
+                .collect();
+            let rows: Vec<TableRow> = hidden.remove_from(
+                build_rows_for_range(sheet, &ctx, row_start, row_end),
+                row_start,
+            );
+            let headings: Option<SheetHeadings> = (options.xlsx_headings || print_options.headings)
+                .then(|| sheet_headings(&ctx, &hidden, row_end));
 
-            let rows = build_rows_for_range(sheet, &ctx, row_start, row_end);
+            // Scale from the worksheet's page setup. Fit-to-page sizes the
+            // whole sheet onto its page grid, so manual breaks no longer apply.
+            let scaling: xlsx_page_setup::SheetScaling = sheet_scaling
+                .get(sheet.get_name())
+                .copied()
+                .unwrap_or_default();
+            let page_size: PageSize = sheet_page_size(sheet);
+            let margins: Margins = sheet_print_margins(sheet);
+            let printed_widths: Vec<f64> = headings
+                .iter()
+                .map(|headings| headings.width)
+                .chain(column_widths.iter().copied())
+                .collect();
+            let print_scale: f64 = scaling.factor(
+                &printed_widths,
+                &rows,
+                page_size.width - margins.left - margins.right,
+                page_size.height - margins.top - margins.bottom,
+            );
 
-            let print_titles = find_print_titles(&book, sheet);
-            let title_columns: Option<(usize, usize)> = title_column_indices(print_titles, &ctx);
+            let print_titles: PrintTitles = if options.xlsx_ignore_frozen_panes {
+                find_print_titles(&book, sheet)
+            } else {
+                print_options.with_frozen_titles(find_print_titles(&book, sheet))
+            };
+            let title_columns: Option<(usize, usize)> = title_column_indices(print_titles, &ctx)
+                .map(|(start, end)| {
+                    (
+                        hidden.visible_column_index(start),
+                        hidden.visible_column_index(end),
+                    )
+                })
+                .filter(|(start, end)| start < end);
+            let title_columns: Option<(usize, usize)> =
+                overflow_title_columns(title_columns, options);
+            let title_columns: Option<(usize, usize)> = match &headings {
+                Some(headings) => Some(headings.title_columns(title_columns)),
+                None => title_columns,
+            };
+            let column_labels: Option<Vec<String>> = options
+                .xlsx_label_column_pages
+                .then(|| printed_column_letters(&ctx, &hidden, headings.is_some()));
+            let column_breaks: Vec<usize> = if scaling.fit_to_page {
+                Vec::new()
+            } else {
+                column_break_indices(&collect_column_breaks(sheet), &ctx)
+                    .into_iter()
+                    .map(|index| {
+                        hidden.visible_column_index(index) + usize::from(headings.is_some())
+                    })
+                    .collect()
+            };
             // Rows from the sheet top through the end of the title range
             // repeat as the table header on every page. Excel repeats only
             // the title rows themselves; when they don't start at the top
@@ -499,70 +1174,124 @@ impl Parser for XlsxParser {
             // than not repeating at all.
             let header_row_count: usize = print_titles
                 .rows
-                .filter(|(_, title_end)| *title_end >= row_start)
-                .map(|(_, title_end)| (title_end.min(row_end) - row_start + 1) as usize)
+                .map(|(_, title_end)| row_numbers.iter().filter(|row| **row <= title_end).count())
                 .unwrap_or(0);
 
             // Collect row page breaks and split rows into page segments
-            let row_breaks = collect_row_breaks(sheet);
+            let row_breaks: Vec<u32> = if scaling.fit_to_page {
+                Vec::new()
+            } else {
+                collect_row_breaks(sheet)
+            };
             let sheet_name = sheet.get_name().to_string();
 
             // Extract sheet header/footer
             let hf = sheet.get_header_footer();
-            let sheet_header = parse_hf_format_string(hf.get_odd_header().get_value());
-            let sheet_footer = parse_hf_format_string(hf.get_odd_footer().get_value());
+            let hf_context = HfContext::for_sheet(
+                &sheet_name,
+                sheet_entry(&mut hf_image_map, &sheet_name, shared).unwrap_or_default(),
+                &number_format,
+            );
+            let sheet_header = parse_hf_format_string(
+                hf.get_odd_header().get_value(),
+                &hf_context,
+                HfPart::Header,
+            );
+            let sheet_footer = parse_hf_format_string(
+                hf.get_odd_footer().get_value(),
+                &hf_context,
+                HfPart::Footer,
+            );
 
             // Pull charts for this sheet (if any)
-            let mut sheet_charts = chart_map.remove(&sheet_name).unwrap_or_default();
+            let mut sheet_charts: Vec<(u32, Chart)> =
+                sheet_entry(&mut chart_map, &sheet_name, shared).unwrap_or_default();
+            sheet_charts.retain(|(row, _)| anchored_in_area(area, row_start, row_end, *row));
             for (_, chart) in &sheet_charts {
                 let title = chart.title.as_deref().unwrap_or("untitled").to_string();
                 warnings.push(ConvertWarning::FallbackUsed {
                     format: "XLSX".to_string(),
                     from: format!("chart ({title})"),
                     to: "data table".to_string(),
+                    location: Some(WarningLocation::Sheet(sheet_name.clone())),
                 });
             }
             // Sort by anchor row
+            for (row, _) in &mut sheet_charts {
+                *row = printed_anchor_row(&hidden, row_start, *row);
+            }
             sheet_charts.sort_by_key(|(row, _)| *row);
-            let mut sheet_images: Vec<crate::ir::SheetImage> = image_map
-                .remove(&sheet_name)
-                .unwrap_or_default()
-                .into_iter()
-                .map(|anchor| anchored_image(anchor, sheet, &ctx))
-                .collect();
+            let mut sheet_images: Vec<crate::ir::SheetImage> =
+                sheet_entry(&mut image_map, &sheet_name, shared)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|anchor| anchored_image(anchor, sheet, &ctx))
+                    .filter(|sheet_image| {
+                        anchored_in_area(area, row_start, row_end, sheet_image.anchor_row)
+                    })
+                    .map(|mut sheet_image| {
+                        sheet_image.anchor_row =
+                            printed_anchor_row(&hidden, row_start, sheet_image.anchor_row);
+                        sheet_image
+                    })
+                    .collect();
             sheet_images.sort_by_key(|sheet_image| sheet_image.anchor_row);
-            let mut sheet_text_boxes: Vec<crate::ir::SheetTextBox> = text_box_map
-                .remove(&sheet_name)
-                .unwrap_or_default()
-                .into_iter()
-                .map(|anchor| anchored_text_box(anchor, sheet, &ctx))
-                .collect();
+            let mut sheet_text_boxes: Vec<crate::ir::SheetTextBox> =
+                sheet_entry(&mut text_box_map, &sheet_name, shared)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|anchor| anchored_text_box(anchor, sheet, &ctx))
+                    .filter(|text_box| {
+                        anchored_in_area(area, row_start, row_end, text_box.anchor_row)
+                    })
+                    .map(|mut text_box| {
+                        text_box.anchor_row =
+                            printed_anchor_row(&hidden, row_start, text_box.anchor_row);
+                        text_box
+                    })
+                    .collect();
             sheet_text_boxes.sort_by_key(|text_box| text_box.anchor_row);
+            let dropdown_appendix: Option<SheetPage> = xlsx_validation::dropdown_appendix(
+                &book,
+                &sheet_name,
+                &ctx.dropdowns,
+                &ctx.number_format,
+                page_size,
+                margins,
+            );
 
             if row_breaks.is_empty() {
                 // No page breaks — single page
+                let mut page = SheetPage {
+                    name: sheet_name,
+                    size: page_size,
+                    margins,
+                    table: Table {
+                        rows,
+                        column_widths,
+                        header_row_count,
+                        alignment: None,
+                        default_cell_padding: Some(xlsx_cells::XLSX_CELL_PADDING),
+                        use_content_driven_row_heights: false,
+                        default_vertical_align: Some(crate::ir::CellVerticalAlign::Bottom),
+                    },
+                    header: sheet_header.clone(),
+                    footer: sheet_footer.clone(),
+                    charts: sheet_charts,
+                    images: sheet_images,
+                    text_boxes: sheet_text_boxes,
+                };
+                if let Some(headings) = &headings {
+                    headings.apply(&mut page, &row_numbers);
+                }
                 pages.extend(
-                    xlsx_pagination::split_sheet_page_by_width(
-                        SheetPage {
-                            name: sheet_name,
-                            size: sheet_page_size(sheet),
-                            margins: sheet_print_margins(sheet),
-                            table: Table {
-                                rows,
-                                column_widths: ctx.column_widths,
-                                header_row_count,
-                                alignment: None,
-                                default_cell_padding: Some(xlsx_cells::XLSX_CELL_PADDING),
-                                use_content_driven_row_heights: false,
-                                default_vertical_align: Some(crate::ir::CellVerticalAlign::Bottom),
-                            },
-                            header: sheet_header.clone(),
-                            footer: sheet_footer.clone(),
-                            charts: sheet_charts,
-                            images: sheet_images,
-                            text_boxes: sheet_text_boxes,
-                        },
+                    paginate_sheet_page(
+                        page,
+                        print_scale,
                         title_columns,
+                        &column_breaks,
+                        column_labels.as_deref(),
+                        &mut warnings,
                     )
                     .into_iter()
                     .map(Page::Sheet),
@@ -570,28 +1299,47 @@ impl Parser for XlsxParser {
             } else {
                 // Split rows at break points
                 // Breaks are 1-indexed row numbers; break after that row
-                let mut segments: Vec<Vec<TableRow>> = Vec::new();
+                // Each segment keeps the sheet row numbers of its rows.
+                let mut segments: Vec<(Vec<TableRow>, Vec<u32>)> = Vec::new();
                 let mut current_segment: Vec<TableRow> = Vec::new();
+                let mut current_numbers: Vec<u32> = Vec::new();
                 let mut break_idx = 0;
 
-                for (i, row) in rows.into_iter().enumerate() {
-                    let actual_row = row_start + i as u32; // 1-indexed row number
+                for (row, &actual_row) in rows.into_iter().zip(&row_numbers) {
+                    // A break after a hidden row takes effect before the
+                    // next visible one.
+                    if break_idx < row_breaks.len()
+                        && row_breaks[break_idx] < actual_row
+                        && !current_segment.is_empty()
+                    {
+                        segments.push((
+                            std::mem::take(&mut current_segment),
+                            std::mem::take(&mut current_numbers),
+                        ));
+                    }
+                    while break_idx < row_breaks.len() && row_breaks[break_idx] < actual_row {
+                        break_idx += 1;
+                    }
                     current_segment.push(row);
+                    current_numbers.push(actual_row);
 
                     // Check if this row is a break point
                     if break_idx < row_breaks.len() && actual_row == row_breaks[break_idx] {
-                        segments.push(std::mem::take(&mut current_segment));
+                        segments.push((
+                            std::mem::take(&mut current_segment),
+                            std::mem::take(&mut current_numbers),
+                        ));
                         break_idx += 1;
                     }
                 }
                 // Push remaining rows as the last segment
                 if !current_segment.is_empty() {
-                    segments.push(current_segment);
+                    segments.push((current_segment, current_numbers));
                 }
 
                 // For page-break segments, attach all charts to the first segment
                 let mut first_segment = true;
-                for mut segment in segments {
+                for (mut segment, mut segment_numbers) in segments {
                     let mut segment_header_rows: usize = 0;
                     if first_segment {
                         segment_header_rows = header_row_count.min(segment.len());
@@ -599,61 +1347,76 @@ impl Parser for XlsxParser {
                         && title_end >= row_start
                     {
                         // Later segments don't contain the title rows — prepend.
-                        let mut title_rows = build_rows_for_range(
-                            sheet,
-                            &ctx,
-                            title_start.max(row_start),
-                            title_end,
+                        let title_first_row: u32 = title_start.max(row_start);
+                        let mut title_rows = hidden.remove_from(
+                            build_rows_for_range(sheet, &ctx, title_first_row, title_end),
+                            title_first_row,
                         );
                         segment_header_rows = title_rows.len();
                         title_rows.append(&mut segment);
                         segment = title_rows;
+                        let mut title_numbers: Vec<u32> = (title_first_row..=title_end)
+                            .filter(|row| !hidden.is_row_hidden(*row))
+                            .collect();
+                        title_numbers.append(&mut segment_numbers);
+                        segment_numbers = title_numbers;
+                    }
+                    let mut page = SheetPage {
+                        name: sheet_name.clone(),
+                        size: page_size,
+                        margins,
+                        table: Table {
+                            rows: segment,
+                            column_widths: column_widths.clone(),
+                            header_row_count: segment_header_rows,
+                            alignment: None,
+                            default_cell_padding: Some(xlsx_cells::XLSX_CELL_PADDING),
+                            use_content_driven_row_heights: false,
+                            default_vertical_align: Some(crate::ir::CellVerticalAlign::Bottom),
+                        },
+                        header: sheet_header.clone(),
+                        footer: sheet_footer.clone(),
+                        charts: if first_segment {
+                            std::mem::take(&mut sheet_charts)
+                        } else {
+                            vec![]
+                        },
+                        images: if first_segment {
+                            std::mem::take(&mut sheet_images)
+                        } else {
+                            vec![]
+                        },
+                        text_boxes: if first_segment {
+                            first_segment = false;
+                            std::mem::take(&mut sheet_text_boxes)
+                        } else {
+                            vec![]
+                        },
+                    };
+                    if let Some(headings) = &headings {
+                        headings.apply(&mut page, &segment_numbers);
                     }
                     pages.extend(
-                        xlsx_pagination::split_sheet_page_by_width(
-                            SheetPage {
-                                name: sheet_name.clone(),
-                                size: sheet_page_size(sheet),
-                                margins: sheet_print_margins(sheet),
-                                table: Table {
-                                    rows: segment,
-                                    column_widths: ctx.column_widths.clone(),
-                                    header_row_count: segment_header_rows,
-                                    alignment: None,
-                                    default_cell_padding: Some(xlsx_cells::XLSX_CELL_PADDING),
-                                    use_content_driven_row_heights: false,
-                                    default_vertical_align: Some(
-                                        crate::ir::CellVerticalAlign::Bottom,
-                                    ),
-                                },
-                                header: sheet_header.clone(),
-                                footer: sheet_footer.clone(),
-                                charts: if first_segment {
-                                    std::mem::take(&mut sheet_charts)
-                                } else {
-                                    vec![]
-                                },
-                                images: if first_segment {
-                                    std::mem::take(&mut sheet_images)
-                                } else {
-                                    vec![]
-                                },
-                                text_boxes: if first_segment {
-                                    first_segment = false;
-                                    std::mem::take(&mut sheet_text_boxes)
-                                } else {
-                                    vec![]
-                                },
-                            },
+                        paginate_sheet_page(
+                            page,
+                            print_scale,
                             title_columns,
+                            &column_breaks,
+                            column_labels.as_deref(),
+                            &mut warnings,
                         )
                         .into_iter()
                         .map(Page::Sheet),
                     );
                 }
             }
+            if let Some(appendix) = dropdown_appendix {
+                pages.push(Page::Sheet(appendix));
+            }
         }
 
+        xlsx_hyperlinks::drop_dangling_sheet_links(&mut pages);
+
         Ok((
             Document {
                 metadata,
@@ -665,10 +1428,38 @@ impl Parser for XlsxParser {
     }
 }
 
-/// Extract metadata from umya-spreadsheet Properties.
+/// List the sheets of a workbook in order, hidden ones included, with the
+/// last row and column holding a cell.
+pub(crate) fn read_sheet_outline(
+    data: &[u8],
+) -> Result<Vec<crate::error::SheetInfo>, ConvertError> {
+    let book =
+        umya_spreadsheet::reader::xlsx::read_reader(Cursor::new(data), true).map_err(|e| {
+            crate::parser::parse_err(format!("Failed to parse XLSX (umya-spreadsheet): {e}"))
+        })?;
+    Ok(book
+        .get_sheet_collection()
+        .iter()
+        .map(|sheet| {
+            let (columns, rows): (u32, u32) = sheet.get_highest_column_and_row();
+            crate::error::SheetInfo {
+                name: sheet.get_name().to_string(),
+                rows,
+                columns,
+                hidden: is_hidden_sheet(sheet),
+            }
+        })
+        .collect())
+}
+
+/// Extract metadata from umya-spreadsheet Properties, with the keywords,
+/// company and custom properties read from the package itself.
 /// Empty strings are converted to None.
-fn extract_xlsx_metadata(book: &umya_spreadsheet::Spreadsheet) -> Metadata {
+fn extract_xlsx_metadata(book: &umya_spreadsheet::Spreadsheet, data: &[u8]) -> Metadata {
     let props = book.get_properties();
+    let package: Metadata = crate::parser::open_zip(data)
+        .map(|mut archive| crate::parser::metadata::extract_metadata_from_zip(&mut archive))
+        .unwrap_or_default();
     let non_empty = |s: &str| {
         if s.is_empty() {
             None
@@ -683,6 +1474,8 @@ fn extract_xlsx_metadata(book: &umya_spreadsheet::Spreadsheet) -> Metadata {
         description: non_empty(props.get_description()),
         created: non_empty(props.get_created()),
         modified: non_empty(props.get_modified()),
+        language: None,
+        ..package
     }
 }
 
@@ -592,6 +592,77 @@ fn test_number_format_custom_format_string() {
     assert_eq!(text, "3.142", "Expected 3 decimal places formatting");
 }
 
+#[test]
+fn test_number_format_negative_section_sets_color_and_text() {
+    let data = build_xlsx_formatted(|sheet| {
+        let cell = sheet.get_cell_mut("A1");
+        cell.set_value_number(-1234.5f64);
+        cell.get_style_mut()
+            .get_number_format_mut()
+            .set_format_code("#,##0.00;[Red](#,##0.00)");
+        let date = sheet.get_cell_mut("B1");
+        date.set_value_number(43567f64);
+        date.get_style_mut()
+            .get_number_format_mut()
+            .set_format_code("d mmm yyyy");
+    });
+    let parser = XlsxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let tp = get_sheet_page(&doc, 0);
+    let cell = &tp.table.rows[0].cells[0];
+    assert_eq!(cell_text(cell), "(1,234.50)");
+    let Block::Paragraph(paragraph) = &cell.content[0] else {
+        panic!("expected a paragraph");
+    };
+    assert_eq!(paragraph.runs[0].style.color, Some(Color::new(255, 0, 0)));
+    assert_eq!(cell_text(&tp.table.rows[0].cells[1]), "12 Apr 2019");
+}
+
+#[cfg(feature = "formula-eval")]
+#[test]
+fn test_formula_without_cached_value_is_evaluated() {
+    let data = build_xlsx_formatted(|sheet| {
+        sheet.get_cell_mut("A1").set_value_number(1234.5f64);
+        let cell = sheet.get_cell_mut("B1");
+        cell.set_formula("A1*2");
+        cell.get_style_mut()
+            .get_number_format_mut()
+            .set_format_code("#,##0.00");
+        sheet
+            .get_cell_mut("C1")
+            .set_formula("IF(A1>1000,\"high\",\"low\")");
+    });
+    let parser = XlsxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let tp = get_sheet_page(&doc, 0);
+    assert_eq!(cell_text(&tp.table.rows[0].cells[1]), "2,469.00");
+    assert_eq!(cell_text(&tp.table.rows[0].cells[2]), "high");
+}
+
+#[cfg(feature = "formula-eval")]
+#[test]
+fn test_formula_chain_is_evaluated_once_per_cell() {
+    // Each term references the two before it; without caching, evaluating
+    // the last term would take hundreds of millions of evaluations.
+    let data = build_xlsx_formatted(|sheet| {
+        sheet.get_cell_mut("A1").set_value_number(1f64);
+        sheet.get_cell_mut("A2").set_value_number(1f64);
+        for row in 3..=40 {
+            sheet
+                .get_cell_mut(format!("A{row}").as_str())
+                .set_formula(format!("A{}+A{}", row - 1, row - 2).as_str());
+        }
+    });
+    let parser = XlsxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let tp = get_sheet_page(&doc, 0);
+    assert_eq!(cell_text(&tp.table.rows[9].cells[0]), "55");
+    assert_eq!(cell_text(&tp.table.rows[39].cells[0]), "102334155");
+}
+
 #[test]
 fn test_cell_combined_formatting() {
     let data = build_xlsx_formatted(|sheet| {
@@ -1397,3 +1468,130 @@ fn test_spill_still_blocked_by_occupied_neighbor() {
         "an occupied neighbor still blocks the spill"
     );
 }
+
+// ----- Wrap, rotation, and vertical alignment -----
+
+#[test]
+fn test_blocked_unwrapped_text_is_clipped_not_wrapped() {
+    let data = build_xlsx_formatted(|sheet| {
+        sheet
+            .get_cell_mut("A1")
+            .set_value("text with comment stretching well past column A");
+        sheet.get_cell_mut("B1").set_value("blocker");
+        let wrapped = sheet.get_cell_mut("A2");
+        wrapped.set_value("wrapped text stretching well past column A");
+        wrapped
+            .get_style_mut()
+            .get_alignment_mut()
+            .set_wrap_text(true);
+        sheet.get_cell_mut("B2").set_value("blocker");
+    });
+    let parser = XlsxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+    let tp = get_sheet_page(&doc, 0);
+
+    assert!(tp.table.rows[0].cells[0].no_wrap);
+    assert!(!tp.table.rows[0].cells[1].no_wrap, "short text fits");
+    assert!(!tp.table.rows[1].cells[0].no_wrap, "wrapText must wrap");
+}
+
+#[test]
+fn test_text_rotation_maps_angles_and_stacked_text() {
+    let data = build_xlsx_formatted(|sheet| {
+        for (coord, rotation) in [("A1", 45), ("B1", 135), ("C1", 255), ("D1", 0)] {
+            let cell = sheet.get_cell_mut(coord);
+            cell.set_value("Turn");
+            cell.get_style_mut()
+                .get_alignment_mut()
+                .set_text_rotation(rotation);
+        }
+    });
+    let parser = XlsxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+    let tp = get_sheet_page(&doc, 0);
+
+    let rotations: Vec<Option<CellTextRotation>> = tp.table.rows[0]
+        .cells
+        .iter()
+        .map(|cell| cell.text_rotation)
+        .collect();
+    assert_eq!(
+        rotations,
+        vec![
+            Some(CellTextRotation::Angle(45.0)),
+            Some(CellTextRotation::Angle(-45.0)),
+            Some(CellTextRotation::Stacked),
+            None,
+        ]
+    );
+    assert_eq!(
+        tp.table.rows[0].height, None,
+        "rotated text grows the row like wrapped text"
+    );
+}
+
+#[test]
+fn test_vertical_justify_aligns_top() {
+    let data = build_xlsx_formatted(|sheet| {
+        let cell = sheet.get_cell_mut("A1");
+        cell.set_value("Spread");
+        cell.get_style_mut()
+            .get_alignment_mut()
+            .set_vertical(umya_spreadsheet::VerticalAlignmentValues::Justify);
+    });
+    let parser = XlsxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let tp = get_sheet_page(&doc, 0);
+    assert_eq!(
+        tp.table.rows[0].cells[0].vertical_align,
+        Some(CellVerticalAlign::Top)
+    );
+}
+
+// ----- Cell hyperlinks -----
+
+#[test]
+fn test_cell_hyperlinks_become_run_hrefs() {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.set_name("Summary");
+        let external = sheet.get_cell_mut("A1");
+        external.set_value("Website");
+        external.get_hyperlink_mut().set_url("https://example.com/");
+        let internal = sheet.get_cell_mut("A2");
+        internal.set_value("Details");
+        internal
+            .get_hyperlink_mut()
+            .set_url("Detail!B2")
+            .set_location(true);
+        let missing = sheet.get_cell_mut("A3");
+        missing.set_value("Nowhere");
+        missing
+            .get_hyperlink_mut()
+            .set_url("Missing!A1")
+            .set_location(true);
+    }
+    let mut detail = umya_spreadsheet::Worksheet::default();
+    detail.set_name("Detail");
+    detail.get_cell_mut("B2").set_value("Target");
+    book.add_sheet(detail).unwrap();
+    let mut cursor = Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+
+    let parser = XlsxParser;
+    let (doc, _warnings) = parser
+        .parse(&cursor.into_inner(), &ConvertOptions::default())
+        .unwrap();
+    let tp = get_sheet_page(&doc, 0);
+    let href = |row: usize| -> Option<String> {
+        let Block::Paragraph(paragraph) = &tp.table.rows[row].cells[0].content[0] else {
+            panic!("expected paragraph");
+        };
+        paragraph.runs[0].href.clone()
+    };
+    assert_eq!(href(0).as_deref(), Some("https://example.com/"));
+    assert_eq!(href(1), Some(sheet_link("Detail")));
+    assert_eq!(href(2), None, "links to absent sheets are dropped");
+}
@@ -1,12 +1,18 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::ir::{Block, Paragraph, ParagraphStyle, Run, TableRow};
+use crate::ir::{Block, Paragraph, ParagraphStyle, Run, TableRow, TextStyle};
 use crate::parser::cond_fmt::build_cond_fmt_overrides;
 
+use super::xlsx_hyperlinks::CellHyperlinks;
+use super::xlsx_numfmt::{FormatSettings, FormattedValue, format_number_with, format_text};
+use super::xlsx_print_options::with_gridlines;
+use super::xlsx_rich_text::RunVerticalAligns;
 use super::xlsx_style::{
     apply_rich_run_font, extract_cell_alignment, extract_cell_background, extract_cell_borders,
-    extract_cell_text_style,
+    extract_cell_text_rotation, extract_cell_text_style,
 };
+use super::xlsx_tables::TableCellStyle;
+use super::xlsx_validation::{Dropdown, dropdown_marker};
 use crate::ir::TableCell;
 
 /// A cell range within a sheet (1-indexed, inclusive).
@@ -21,6 +27,18 @@ pub(crate) struct CellRange {
 /// A (column, row) coordinate pair (1-indexed).
 pub(crate) type CellPos = (u32, u32);
 
+/// Values of formulas saved without a cached result, evaluated once per
+/// sheet. Cells referenced by many formulas are otherwise re-evaluated for
+/// each reference, which grows exponentially along a chain of formulas.
+/// `None` marks a formula still being evaluated, so a reference back to it
+/// is a circular reference. Empty without the `formula-eval` feature.
+#[derive(Default)]
+pub(crate) struct FormulaCache {
+    #[cfg(feature = "formula-eval")]
+    pub(super) values:
+        std::cell::RefCell<HashMap<CellPos, Option<super::xlsx_formula::FormulaValue>>>,
+}
+
 /// Info about a merged cell region, keyed by its top-left coordinate.
 pub(super) struct MergeInfo {
     pub(super) col_span: u32,
@@ -39,6 +57,121 @@ pub(super) fn column_width_to_pt(char_width: f64, max_digit_width_px: f64) -> f6
     char_width * max_digit_width_px * 0.75
 }
 
+/// Excel's widest column, in character units.
+const MAX_COLUMN_WIDTH: f64 = 255.0;
+
+/// A column's `<col width>` when the worksheet sets one. A `<col>` that only
+/// carries a style or the hidden flag has no usable width.
+fn explicit_column_width(sheet: &umya_spreadsheet::Worksheet, col: u32) -> Option<f64> {
+    sheet
+        .get_column_dimension_by_number(&col)
+        .map(|column| *column.get_width())
+        .filter(|width| *width > 0.0)
+}
+
+/// Width in character units of columns without their own `<col width>`:
+/// `<sheetFormatPr defaultColWidth>`, else Excel's 8.43 shifted by however
+/// many digits `baseColWidth` differs from its standard 8.
+pub(super) fn sheet_default_column_width(sheet: &umya_spreadsheet::Worksheet) -> f64 {
+    let format = sheet.get_sheet_format_properties();
+    let default_width: f64 = *format.get_default_column_width();
+    if default_width > 0.0 {
+        return default_width.min(MAX_COLUMN_WIDTH);
+    }
+    let base_width: u32 = *format.get_base_column_width();
+    if base_width > 0 {
+        return (DEFAULT_COLUMN_WIDTH + f64::from(base_width) - 8.0).clamp(1.0, MAX_COLUMN_WIDTH);
+    }
+    DEFAULT_COLUMN_WIDTH
+}
+
+/// Estimate auto-fit widths, in character units, for columns without an
+/// explicit width. Only content that cannot overflow into a neighbor counts:
+/// numbers (Excel would print `####`) and text whose right neighbor holds a
+/// value. Text with room to spill, wrapped text, and merged cells keep the
+/// default width, as in Excel. Excel widens a column on entry for numbers,
+/// so files written by other tools otherwise print truncated values.
+fn autofit_column_widths(
+    sheet: &umya_spreadsheet::Worksheet,
+    merge_tops: &HashMap<CellPos, MergeInfo>,
+    merge_skips: &HashSet<CellPos>,
+    max_digit_width_px: f64,
+    col_start: u32,
+    col_end: u32,
+    number_format: &FormatSettings,
+    formulas: &FormulaCache,
+) -> HashMap<u32, f64> {
+    let mut fitted: HashMap<u32, f64> = HashMap::new();
+    let digit_width_pt: f64 = max_digit_width_px * 0.75;
+    for cell in sheet.get_cell_collection() {
+        let col: u32 = *cell.get_coordinate().get_col_num();
+        let row: u32 = *cell.get_coordinate().get_row_num();
+        if col < col_start
+            || col > col_end
+            || explicit_column_width(sheet, col).is_some()
+            || merge_skips.contains(&(col, row))
+            || merge_tops.contains_key(&(col, row))
+        {
+            continue;
+        }
+        let wraps: bool = cell
+            .get_style()
+            .get_alignment()
+            .is_some_and(|alignment| *alignment.get_wrap_text());
+        if wraps {
+            continue;
+        }
+        let is_number: bool = matches!(
+            cell.get_cell_value().get_raw_value(),
+            umya_spreadsheet::CellRawValue::Numeric(_)
+        );
+        if !is_number {
+            let right_neighbor_filled: bool = sheet
+                .get_cell((col + 1, row))
+                .is_some_and(|neighbor| !neighbor.get_value().is_empty());
+            if !right_neighbor_filled {
+                continue;
+            }
+        }
+        let text: String = format_cell_value(sheet, cell, number_format, formulas).text;
+        let font_size: f64 = cell
+            .get_style()
+            .get_font()
+            .map(|font| *font.get_size())
+            .filter(|size| *size > 0.0)
+            .unwrap_or(11.0);
+        let widest_line_pt: f64 = text
+            .lines()
+            .map(|line| estimate_line_width_pt(line, font_size, digit_width_pt))
+            .fold(0.0, f64::max);
+        if widest_line_pt <= 0.0 {
+            continue;
+        }
+        // Leave room for the ~4pt total horizontal cell inset.
+        let chars: f64 = ((widest_line_pt + 4.0) / digit_width_pt).min(MAX_COLUMN_WIDTH);
+        let entry = fitted.entry(col).or_insert(0.0);
+        *entry = entry.max(chars);
+    }
+    fitted
+}
+
+/// Single-line width estimate for auto-fit: digits use the Normal font's
+/// max digit width scaled to the cell's font size, other glyphs the same
+/// averages as the spill estimate.
+fn estimate_line_width_pt(line: &str, font_size: f64, digit_width_pt: f64) -> f64 {
+    line.chars()
+        .map(|c| {
+            if c.is_ascii_digit() {
+                digit_width_pt * font_size / 11.0
+            } else if c.is_ascii() {
+                0.55 * font_size
+            } else {
+                1.05 * font_size
+            }
+        })
+        .sum()
+}
+
 /// Read the workbook's Normal font (the first `<font>` in `xl/styles.xml`)
 /// straight from the archive; umya does not expose the stylesheet. Excel
 /// derives all column print metrics from this font, not from cell fonts.
@@ -80,6 +213,32 @@ pub(super) fn extract_normal_font(data: &[u8]) -> Option<(String, f64)> {
     Some((name?, size.unwrap_or(11.0)))
 }
 
+/// Read `<workbookPr date1904>` from `xl/workbook.xml`. Workbooks from old
+/// Mac Excel count date serials from 1904-01-01 instead of 1900-01-01.
+pub(super) fn extract_date1904(data: &[u8]) -> bool {
+    use quick_xml::events::Event;
+
+    let Ok(mut archive) = crate::parser::open_zip(data) else {
+        return false;
+    };
+    let xml: String = super::xlsx_drawing::read_zip_entry_string(&mut archive, "xl/workbook.xml");
+    let mut reader = quick_xml::Reader::from_str(&xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                if e.local_name().as_ref() == b"workbookPr" =>
+            {
+                return crate::parser::xml_util::get_attr_str(e, b"date1904")
+                    .is_some_and(|value| value == "1" || value == "true");
+            }
+            // workbookPr precedes the sheet list.
+            Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"sheets" => return false,
+            Ok(Event::Eof) | Err(_) => return false,
+            _ => {}
+        }
+    }
+}
+
 /// Excel pixel-ceils the Normal font's max digit width at 96 DPI to derive
 /// column print metrics. Digit advances: Calibri/Carlito 0.5066 em,
 /// Arial/Helvetica/Liberation Sans 0.556 em, Malgun Gothic ≈0.529 em.
@@ -156,6 +315,9 @@ pub(crate) fn parse_cell_ref(s: &str) -> Option<(u32, u32)> {
 
 /// Parse a print area address string (e.g., "Sheet1!$A$1:$C$10") into a CellRange.
 pub(super) fn parse_print_area_range(address: &str) -> Option<CellRange> {
+    // A print area of several disjoint ranges lists them comma-separated;
+    // only the first one is printed.
+    let address: &str = address.split(',').next().unwrap_or(address);
     // Strip optional sheet prefix (everything up to and including '!')
     let range_part = if let Some(pos) = address.rfind('!') {
         &address[pos + 1..]
@@ -174,8 +336,13 @@ pub(super) fn parse_print_area_range(address: &str) -> Option<CellRange> {
     })
 }
 
-/// Look up the print area for a given sheet from its defined names.
-pub(super) fn find_print_area(sheet: &umya_spreadsheet::Worksheet) -> Option<CellRange> {
+/// Look up the print area for a given sheet from its defined names. Like
+/// print titles, a name the reader could not scope to the sheet stays at the
+/// workbook level and is matched by the sheet prefix of its address.
+pub(super) fn find_print_area(
+    book: &umya_spreadsheet::Spreadsheet,
+    sheet: &umya_spreadsheet::Worksheet,
+) -> Option<CellRange> {
     for dn in sheet.get_defined_names() {
         if dn.get_name() == "_xlnm.Print_Area" {
             let addr = dn.get_address();
@@ -184,7 +351,14 @@ pub(super) fn find_print_area(sheet: &umya_spreadsheet::Worksheet) -> Option<Cel
             }
         }
     }
-    None
+    let plain_prefix: String = format!("{}!", sheet.get_name());
+    let quoted_prefix: String = format!("'{}'!", sheet.get_name());
+    book.get_defined_names()
+        .iter()
+        .filter(|dn| dn.get_name() == "_xlnm.Print_Area")
+        .map(|dn| dn.get_address())
+        .filter(|address| address.starts_with(&plain_prefix) || address.starts_with(&quoted_prefix))
+        .find_map(|address| parse_print_area_range(&address))
 }
 
 /// Print-title ranges from `_xlnm.Print_Titles`: rows and/or columns that
@@ -249,9 +423,17 @@ fn parse_print_title_address(address: &str, titles: &mut PrintTitles) {
 
 /// Collect sorted manual row page break positions from a sheet.
 pub(super) fn collect_row_breaks(sheet: &umya_spreadsheet::Worksheet) -> Vec<u32> {
-    let mut breaks: Vec<u32> = sheet
-        .get_row_breaks()
-        .get_break_list()
+    manual_breaks(sheet.get_row_breaks().get_break_list())
+}
+
+/// Collect sorted manual column page break positions from a sheet. Like row
+/// breaks, each id is the last column (1-indexed) before the break.
+pub(super) fn collect_column_breaks(sheet: &umya_spreadsheet::Worksheet) -> Vec<u32> {
+    manual_breaks(sheet.get_column_breaks().get_break_list())
+}
+
+fn manual_breaks(break_list: &[umya_spreadsheet::Break]) -> Vec<u32> {
+    let mut breaks: Vec<u32> = break_list
         .iter()
         .filter(|b| *b.get_manual_page_break())
         .map(|b| *b.get_id())
@@ -318,6 +500,24 @@ pub(super) struct SheetContext {
     pub(super) merge_tops: HashMap<(u32, u32), MergeInfo>,
     pub(super) merge_skips: HashSet<(u32, u32)>,
     pub(super) cond_fmt_overrides: HashMap<(u32, u32), crate::parser::cond_fmt::CondFmtOverride>,
+    /// Cells formatted shrink-to-fit, as `(col, row)`.
+    pub(super) shrink_to_fit_cells: HashSet<(u32, u32)>,
+    /// Superscript/subscript rich-text runs per `(col, row)`, which umya drops.
+    pub(super) rich_run_vertical_aligns: HashMap<(u32, u32), RunVerticalAligns>,
+    /// Hyperlinked cells.
+    pub(super) hyperlinks: CellHyperlinks,
+    /// Table style formatting per `(col, row)` for cells inside Excel tables.
+    pub(super) table_styles: HashMap<(u32, u32), TableCellStyle>,
+    /// Print gridlines around cells without their own borders.
+    pub(super) gridlines: bool,
+    /// The workbook's date system and the locale numbers are shown in.
+    pub(super) number_format: FormatSettings,
+    /// Formulas without cached results evaluated so far on this sheet.
+    pub(super) formulas: FormulaCache,
+    /// Hidden cells of a protected sheet printed blank, as `(col, row)`.
+    pub(super) redacted_cells: HashSet<(u32, u32)>,
+    /// List validations whose cells print a dropdown marker.
+    pub(super) dropdowns: Vec<Dropdown>,
 }
 
 /// First strong bidi direction of a character: Some(true) for right-to-left
@@ -484,7 +684,9 @@ fn compute_spill_width(
 }
 
 /// Excel's fallback row height when the sheet declares none (Calibri 11).
-const EXCEL_DEFAULT_ROW_HEIGHT_PT: f64 = 15.0;
+pub(super) const EXCEL_DEFAULT_ROW_HEIGHT_PT: f64 = 15.0;
+/// Size of Excel's default Normal font, which the default row height fits.
+const DEFAULT_FONT_SIZE_PT: f64 = 11.0;
 
 /// Convert an OOXML row height to the whole-point track emitted by native
 /// Excel's macOS PDF path. Excel exposes the stored value in points in the
@@ -512,7 +714,8 @@ pub(super) const XLSX_CELL_PADDING: crate::ir::Insets = crate::ir::Insets {
 
 /// The height a row prints at. A recorded `ht` is the current worksheet
 /// height even when `customHeight` is false; rows without one use the sheet's
-/// defaultRowHeight. Fixed tracks are calibrated to native Excel's PDF grid.
+/// defaultRowHeight, grown as Excel auto-fits rows to their largest font.
+/// Fixed tracks are calibrated to native Excel's PDF grid.
 /// Exception: auto-sized rows (customHeight=false) that contain wrapped cells
 /// stay content-driven — our text metrics differ slightly from Excel's and a
 /// fixed height could clip a wrapped line.
@@ -520,6 +723,7 @@ fn printed_row_height(
     sheet: &umya_spreadsheet::Worksheet,
     row_idx: u32,
     row_has_wrapping_cell: &dyn Fn() -> bool,
+    row_max_font_size: &dyn Fn() -> f64,
 ) -> Option<f64> {
     let row_dimension = sheet.get_row_dimension(&row_idx);
     let is_custom_height: bool = row_dimension
@@ -534,15 +738,90 @@ fn printed_row_height(
     declared_height
         .or_else(|| {
             let sheet_default: f64 = *sheet.get_sheet_format_properties().get_default_row_height();
-            if sheet_default > 0.0 {
-                Some(sheet_default)
+            let default_height: f64 = if sheet_default > 0.0 {
+                sheet_default
             } else {
-                Some(EXCEL_DEFAULT_ROW_HEIGHT_PT)
-            }
+                EXCEL_DEFAULT_ROW_HEIGHT_PT
+            };
+            // Excel's 15pt default row fits its 11pt default font.
+            let fitted_height: f64 =
+                row_max_font_size() * EXCEL_DEFAULT_ROW_HEIGHT_PT / DEFAULT_FONT_SIZE_PT;
+            Some(default_height.max(fitted_height))
         })
         .map(native_excel_pdf_row_height)
 }
 
+/// The text Excel shows for a cell. Numbers go through the cell's number
+/// format (built-in ids resolve to their ECMA-376 codes); booleans print as
+/// `TRUE`/`FALSE` and errors as the sheet's error setting says, whatever
+/// the format. With `formula-eval`, formulas saved without a cached result
+/// are evaluated instead of printing blank.
+#[cfg_attr(not(feature = "formula-eval"), allow(unused_variables))]
+pub(super) fn format_cell_value(
+    sheet: &umya_spreadsheet::Worksheet,
+    cell: &umya_spreadsheet::Cell,
+    settings: &FormatSettings,
+    formulas: &FormulaCache,
+) -> FormattedValue {
+    let format_code: String = cell
+        .get_style()
+        .get_number_format()
+        .map(|number_format| {
+            settings.format_code(
+                *number_format.get_number_format_id(),
+                number_format.get_format_code(),
+            )
+        })
+        .unwrap_or_else(|| "General".to_string());
+    let format_code: &str = &format_code;
+    #[cfg(feature = "formula-eval")]
+    if let Some(value) = super::xlsx_formula::evaluate_uncached_formula(sheet, cell, formulas) {
+        use super::xlsx_formula::FormulaValue;
+        return match value {
+            FormulaValue::Number(number) => format_number_with(number, format_code, settings),
+            FormulaValue::Text(text) => format_text(&text, format_code),
+            FormulaValue::Error(error) => FormattedValue {
+                text: settings.errors.show(error),
+                color: None,
+            },
+            other => FormattedValue {
+                text: other.to_text(),
+                color: None,
+            },
+        };
+    }
+    match cell.get_cell_value().get_raw_value() {
+        umya_spreadsheet::CellRawValue::Numeric(number) => {
+            format_number_with(*number, format_code, settings)
+        }
+        umya_spreadsheet::CellRawValue::String(text) => format_text(text, format_code),
+        umya_spreadsheet::CellRawValue::Bool(flag) => FormattedValue {
+            text: if *flag { "TRUE" } else { "FALSE" }.to_string(),
+            color: None,
+        },
+        umya_spreadsheet::CellRawValue::Error(_) => FormattedValue {
+            text: settings.errors.show(&cell.get_value()),
+            color: None,
+        },
+        _ => FormattedValue {
+            text: cell.get_formatted_value(),
+            color: None,
+        },
+    }
+}
+
+/// Excel's "General" horizontal alignment for a cell's value: numbers to the
+/// right, booleans and errors centered, text as its direction says.
+fn general_alignment(cell: &umya_spreadsheet::Cell) -> Option<crate::ir::Alignment> {
+    match cell.get_cell_value().get_raw_value() {
+        umya_spreadsheet::CellRawValue::Numeric(_) => Some(crate::ir::Alignment::Right),
+        umya_spreadsheet::CellRawValue::Bool(_) | umya_spreadsheet::CellRawValue::Error(_) => {
+            Some(crate::ir::Alignment::Center)
+        }
+        _ => None,
+    }
+}
+
 /// Build TableRows for a range of rows in a sheet.
 pub(super) fn build_rows_for_range(
     sheet: &umya_spreadsheet::Worksheet,
@@ -562,10 +841,18 @@ pub(super) fn build_rows_for_range(
 
             // umya-spreadsheet tuple is (column, row), both 1-indexed
             let umya_cell = sheet.get_cell((col_idx, row_idx));
-            let mut value = umya_cell
-                .map(|cell| cell.get_formatted_value())
+            // A redacted cell keeps its formatting but none of its value,
+            // including what value-driven conditional formats would show.
+            let redacted: bool = ctx.redacted_cells.contains(&(col_idx, row_idx));
+            let value_cell = umya_cell.filter(|_| !redacted);
+            let formatted: Option<FormattedValue> = value_cell
+                .map(|cell| format_cell_value(sheet, cell, &ctx.number_format, &ctx.formulas));
+            let format_color: Option<crate::ir::Color> =
+                formatted.as_ref().and_then(|formatted| formatted.color);
+            let mut value: String = formatted
+                .map(|formatted| formatted.text)
                 .unwrap_or_default();
-            if let Some(cell) = umya_cell
+            if let Some(cell) = value_cell
                 && let Some(number_format) = cell.get_style().get_number_format()
                 && uses_native_arabic_digits(number_format.get_format_code())
             {
@@ -574,17 +861,33 @@ pub(super) fn build_rows_for_range(
 
             // Extract formatting from the cell
             let mut text_style = umya_cell.map(extract_cell_text_style).unwrap_or_default();
+            if format_color.is_some() {
+                text_style.color = format_color;
+            }
             let (cell_alignment, cell_vertical_align) = umya_cell
                 .map(extract_cell_alignment)
                 .unwrap_or((None, None));
             let mut background = umya_cell.and_then(extract_cell_background);
-            let border = umya_cell.and_then(extract_cell_borders);
+            let mut border = umya_cell.and_then(extract_cell_borders);
+
+            // Table styles sit beneath the cell's own formatting.
+            if let Some(table_style) = ctx.table_styles.get(&(col_idx, row_idx)) {
+                background = background.or(table_style.background);
+                text_style.color = text_style.color.or(table_style.font_color);
+                if table_style.bold {
+                    text_style.bold = Some(true);
+                }
+                border = border.or_else(|| table_style.border.clone());
+            }
+            if ctx.gridlines {
+                border = Some(with_gridlines(border));
+            }
 
             // Apply conditional formatting overrides
             let mut data_bar = None;
             let mut icon_text = None;
             let mut icon_color = None;
-            if let Some(ovr) = ctx.cond_fmt_overrides.get(&(col_idx, row_idx)) {
+            if !redacted && let Some(ovr) = ctx.cond_fmt_overrides.get(&(col_idx, row_idx)) {
                 if ovr.background.is_some() {
                     background = ovr.background;
                 }
@@ -603,20 +906,28 @@ pub(super) fn build_rows_for_range(
             // per-run fonts/colors) that the cell's single xf style loses —
             // emit one IR run per rich run instead of flattening.
             let rich_text: Option<umya_spreadsheet::RichText> =
-                umya_cell.and_then(|cell| cell.get_cell_value().get_raw_value().get_rich_text());
+                value_cell.and_then(|cell| cell.get_cell_value().get_raw_value().get_rich_text());
             let runs: Vec<Run> = if let Some(rich_text) = rich_text {
+                let vertical_aligns: Option<&RunVerticalAligns> =
+                    ctx.rich_run_vertical_aligns.get(&(col_idx, row_idx));
                 rich_text
                     .get_rich_text_elements()
                     .iter()
-                    .filter(|element| !element.get_text().is_empty())
-                    .map(|element| Run {
-                        text: element.get_text().to_string(),
-                        style: element
+                    .enumerate()
+                    .filter(|(_, element)| !element.get_text().is_empty())
+                    .map(|(index, element)| {
+                        let mut style: TextStyle = element
                             .get_run_properties()
                             .map(|font| apply_rich_run_font(&text_style, font))
-                            .unwrap_or_else(|| text_style.clone()),
-                        href: None,
-                        footnote: None,
+                            .unwrap_or_else(|| text_style.clone());
+                        style.vertical_align =
+                            vertical_aligns.and_then(|aligns| aligns.get(index).copied().flatten());
+                        Run {
+                            text: element.get_text().to_string(),
+                            style,
+                            href: None,
+                            footnote: None,
+                        }
                     })
                     .collect()
             } else if value.is_empty() {
@@ -630,6 +941,24 @@ pub(super) fn build_rows_for_range(
                 }]
             };
 
+            let mut runs: Vec<Run> = match ctx.hyperlinks.href_at(col_idx, row_idx) {
+                Some(href) => runs
+                    .into_iter()
+                    .map(|run| Run {
+                        href: Some(href.to_string()),
+                        ..run
+                    })
+                    .collect(),
+                None => runs,
+            };
+            if ctx
+                .dropdowns
+                .iter()
+                .any(|dropdown| dropdown.contains(col_idx, row_idx))
+            {
+                runs.push(dropdown_marker(!runs.is_empty()));
+            }
+
             // Excel's "general" horizontal alignment follows the text
             // direction: cells whose text starts with a right-to-left script
             // print right-aligned.
@@ -640,11 +969,8 @@ pub(super) fn build_rows_for_range(
                     .filter(|is_rtl| *is_rtl)
                     .map(|_| crate::ir::Alignment::Right)
             });
-            let paragraph_alignment = cell_alignment.or_else(|| {
-                umya_cell
-                    .and_then(|cell| cell.get_value_number())
-                    .map(|_| crate::ir::Alignment::Right)
-            });
+            let paragraph_alignment =
+                cell_alignment.or_else(|| value_cell.and_then(general_alignment));
 
             let (col_span, row_span) = if let Some(info) = ctx.merge_tops.get(&(col_idx, row_idx)) {
                 (info.col_span, info.row_span)
@@ -652,16 +978,43 @@ pub(super) fn build_rows_for_range(
                 (1, 1)
             };
 
-            let spill_width: Option<f64> = compute_spill_width(
-                sheet,
-                ctx,
-                col_idx,
-                row_idx,
-                &runs,
-                paragraph_alignment,
-                col_span,
-                umya_cell,
-            );
+            let has_text: bool = !runs.is_empty();
+            let wrap_text: bool = umya_cell
+                .and_then(|cell| cell.get_style().get_alignment().cloned())
+                .is_some_and(|alignment| *alignment.get_wrap_text());
+            let text_rotation: Option<crate::ir::CellTextRotation> = umya_cell
+                .and_then(extract_cell_text_rotation)
+                .filter(|_| has_text);
+            // Excel ignores shrink-to-fit on wrapped text.
+            let shrink_to_fit: bool =
+                has_text && !wrap_text && ctx.shrink_to_fit_cells.contains(&(col_idx, row_idx));
+            // Rotated and shrunk text stays inside its own cell.
+            let spill_width: Option<f64> = if shrink_to_fit || text_rotation.is_some() {
+                None
+            } else {
+                compute_spill_width(
+                    sheet,
+                    ctx,
+                    col_idx,
+                    row_idx,
+                    &runs,
+                    paragraph_alignment,
+                    col_span,
+                    umya_cell,
+                )
+            };
+            // Unwrapped text too wide for its cell with nowhere to spill is
+            // clipped at the cell edge rather than wrapped onto more lines.
+            let no_wrap: bool = has_text
+                && !wrap_text
+                && !shrink_to_fit
+                && text_rotation.is_none()
+                && spill_width.is_none()
+                && !runs.iter().any(|run| run.text.contains('\n'))
+                && ctx
+                    .column_widths
+                    .get((col_idx - ctx.col_start) as usize)
+                    .is_some_and(|width| estimate_text_width_pt(&runs) > width - 4.0);
 
             let content = if runs.is_empty() {
                 Vec::new()
@@ -687,19 +1040,32 @@ pub(super) fn build_rows_for_range(
                 spill_width,
                 vertical_align: cell_vertical_align,
                 padding: None,
+                no_wrap,
+                shrink_to_fit,
+                text_rotation,
             });
         }
 
+        // Wrapped and rotated text both grow the row to fit.
         let row_has_wrapping_cell = || {
             (ctx.col_start..=ctx.col_end).any(|col| {
                 sheet
                     .get_cell((col, row_idx))
                     .and_then(|cell| cell.get_style().get_alignment().cloned())
-                    .map(|alignment| *alignment.get_wrap_text())
-                    .unwrap_or(false)
+                    .is_some_and(|alignment| {
+                        *alignment.get_wrap_text() || *alignment.get_text_rotation() != 0
+                    })
             })
         };
-        let height: Option<f64> = printed_row_height(sheet, row_idx, &row_has_wrapping_cell);
+        let row_max_font_size = || -> f64 {
+            (ctx.col_start..=ctx.col_end)
+                .filter_map(|col| sheet.get_cell((col, row_idx)))
+                .filter(|cell| !cell.get_value().is_empty())
+                .filter_map(|cell| cell.get_style().get_font().map(|font| *font.get_size()))
+                .fold(0.0, f64::max)
+        };
+        let height: Option<f64> =
+            printed_row_height(sheet, row_idx, &row_has_wrapping_cell, &row_max_font_size);
 
         rows.push(TableRow { cells, height });
     }
@@ -707,11 +1073,15 @@ pub(super) fn build_rows_for_range(
 }
 
 /// Prepare the shared context for processing a sheet (dimensions, merges, styles, etc.).
+/// `area` overrides the sheet's print area.
 /// Returns (SheetContext, row_start, row_end) or None if the sheet is empty.
 pub(super) fn prepare_sheet_context(
+    book: &umya_spreadsheet::Spreadsheet,
     sheet: &umya_spreadsheet::Worksheet,
+    area: Option<CellRange>,
     normal_font_mdw: Option<f64>,
     raw_cond_fmt_hints: Option<&super::cond_fmt_raw::RawCondFmtHints>,
+    number_format: FormatSettings,
 ) -> Option<(SheetContext, u32, u32)> {
     let (mut max_col, mut max_row) = sheet.get_highest_column_and_row();
     if max_col == 0 || max_row == 0 {
@@ -728,40 +1098,81 @@ pub(super) fn prepare_sheet_context(
         }
     }
 
-    // Check for print area — limit to that range if defined
-    let print_area = find_print_area(sheet);
+    // A selected range replaces the print area; otherwise limit to the
+    // print area if one is defined.
+    let print_area: Option<CellRange> = area.or_else(|| find_print_area(book, sheet));
     let (col_start, col_end, row_start, row_end) = if let Some(pa) = print_area {
         (pa.start_col, pa.end_col, pa.start_row, pa.end_row)
     } else {
         (1, max_col, 1, max_row)
     };
 
+    let ctx = sheet_context_for_columns(
+        sheet,
+        normal_font_mdw,
+        raw_cond_fmt_hints,
+        col_start,
+        col_end,
+        number_format,
+    );
+    Some((ctx, row_start, row_end))
+}
+
+/// Build the sheet context for an explicit column span (1-indexed, inclusive).
+pub(super) fn sheet_context_for_columns(
+    sheet: &umya_spreadsheet::Worksheet,
+    normal_font_mdw: Option<f64>,
+    raw_cond_fmt_hints: Option<&super::cond_fmt_raw::RawCondFmtHints>,
+    col_start: u32,
+    col_end: u32,
+    number_format: FormatSettings,
+) -> SheetContext {
     let max_digit_width_px = normal_font_mdw.unwrap_or_else(|| sheet_max_digit_width_px(sheet));
+    let (merge_tops, merge_skips) = build_merge_maps(sheet);
+    let default_width: f64 = sheet_default_column_width(sheet);
+    let formulas: FormulaCache = FormulaCache::default();
+    let fitted_widths: HashMap<u32, f64> = autofit_column_widths(
+        sheet,
+        &merge_tops,
+        &merge_skips,
+        max_digit_width_px,
+        col_start,
+        col_end,
+        &number_format,
+        &formulas,
+    );
     let column_widths: Vec<f64> = (col_start..=col_end)
         .map(|col| {
-            sheet
-                .get_column_dimension_by_number(&col)
-                .map(|c| column_width_to_pt(*c.get_width(), max_digit_width_px))
-                .unwrap_or_else(|| column_width_to_pt(DEFAULT_COLUMN_WIDTH, max_digit_width_px))
+            let chars: f64 = explicit_column_width(sheet, col).unwrap_or_else(|| {
+                fitted_widths
+                    .get(&col)
+                    .copied()
+                    .unwrap_or(default_width)
+                    .max(default_width)
+            });
+            column_width_to_pt(chars, max_digit_width_px)
         })
         .collect();
-
-    let (merge_tops, merge_skips) = build_merge_maps(sheet);
-    let cond_fmt_overrides = build_cond_fmt_overrides(sheet, raw_cond_fmt_hints);
+    let cond_fmt_overrides = build_cond_fmt_overrides(sheet, raw_cond_fmt_hints, &formulas);
     let num_cols = (col_end - col_start + 1) as usize;
 
-    Some((
-        SheetContext {
-            col_start,
-            col_end,
-            num_cols,
-            column_widths,
-            max_digit_width_px,
-            merge_tops,
-            merge_skips,
-            cond_fmt_overrides,
-        },
-        row_start,
-        row_end,
-    ))
+    SheetContext {
+        col_start,
+        col_end,
+        num_cols,
+        column_widths,
+        max_digit_width_px,
+        merge_tops,
+        merge_skips,
+        cond_fmt_overrides,
+        shrink_to_fit_cells: HashSet::new(),
+        rich_run_vertical_aligns: HashMap::new(),
+        hyperlinks: CellHyperlinks::default(),
+        table_styles: HashMap::new(),
+        gridlines: false,
+        number_format,
+        formulas,
+        redacted_cells: HashSet::new(),
+        dropdowns: Vec::new(),
+    }
 }
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::io::Read;
 
 use quick_xml::Reader;
+use quick_xml::escape::unescape;
 use quick_xml::events::{BytesStart, Event};
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -13,6 +14,20 @@ pub(crate) struct RawCondFmtHint {
     /// order. Parsed from the raw XML because umya-spreadsheet's IconSet
     /// reader drops cfvos written as start/end tag pairs (issue #406).
     pub(crate) icon_cfvos: Vec<(String, String)>,
+    /// Color-scale `<cfvo>` stops as `(type, val)` pairs; `val` is empty for
+    /// `min`/`max`.
+    pub(crate) color_scale_cfvos: Vec<(String, String)>,
+    /// Every `<formula>` of the rule. umya keeps only the first, which loses
+    /// the upper bound of `between` and `notBetween`.
+    pub(crate) formulas: Vec<String>,
+    /// `top10` rank (count, or percent when `percent` is set).
+    pub(crate) rank: Option<u32>,
+    pub(crate) percent: bool,
+    pub(crate) bottom: bool,
+    /// `aboveAverage` attributes; an absent `aboveAverage` means above.
+    pub(crate) above_average: Option<bool>,
+    pub(crate) equal_average: bool,
+    pub(crate) std_dev: Option<u32>,
 }
 
 pub(crate) type RawCondFmtHints = HashMap<i32, RawCondFmtHint>;
@@ -31,6 +46,25 @@ fn attr_value(reader: &Reader<&[u8]>, element: &BytesStart<'_>, name: &[u8]) ->
         })
 }
 
+fn attr_flag(reader: &Reader<&[u8]>, element: &BytesStart<'_>, name: &[u8]) -> Option<bool> {
+    attr_value(reader, element, name).map(|value| value == "1" || value == "true")
+}
+
+/// Rule-level attributes of a `<cfRule>` that umya does not expose.
+fn read_rule_attributes(
+    reader: &Reader<&[u8]>,
+    element: &BytesStart<'_>,
+    hint: &mut RawCondFmtHint,
+) {
+    hint.rank = attr_value(reader, element, b"rank").and_then(|value| value.parse::<u32>().ok());
+    hint.percent = attr_flag(reader, element, b"percent").unwrap_or(false);
+    hint.bottom = attr_flag(reader, element, b"bottom").unwrap_or(false);
+    hint.above_average = attr_flag(reader, element, b"aboveAverage");
+    hint.equal_average = attr_flag(reader, element, b"equalAverage").unwrap_or(false);
+    hint.std_dev =
+        attr_value(reader, element, b"stdDev").and_then(|value| value.parse::<u32>().ok());
+}
+
 fn read_zip_text(
     archive: &mut zip::ZipArchive<std::io::Cursor<&[u8]>>,
     path: &str,
@@ -85,14 +119,83 @@ pub(crate) fn parse_worksheet_hints(xml: &str) -> RawCondFmtHints {
     // Only cfvos nested inside an <iconSet> belong to the icon-set hint;
     // dataBar/colorScale cfvos must not leak in (issue #406).
     let mut in_icon_set = false;
+    let mut in_color_scale = false;
+    let mut in_formula = false;
+    // Text is not trimmed: formulas split around entity references
+    // (`A1 &gt; 5`) must keep their spaces.
     let mut reader = Reader::from_str(xml);
-    reader.config_mut().trim_text(true);
 
     loop {
         match reader.read_event() {
             Ok(Event::Start(element)) if element.local_name().as_ref() == b"cfRule" => {
                 current_priority = attr_value(&reader, &element, b"priority")
                     .and_then(|value| value.parse::<i32>().ok());
+                if let Some(priority) = current_priority {
+                    read_rule_attributes(&reader, &element, hints.entry(priority).or_default());
+                }
+            }
+            // Rules such as duplicateValues or top10 often have no children.
+            Ok(Event::Empty(element)) if element.local_name().as_ref() == b"cfRule" => {
+                if let Some(priority) = attr_value(&reader, &element, b"priority")
+                    .and_then(|value| value.parse::<i32>().ok())
+                {
+                    read_rule_attributes(&reader, &element, hints.entry(priority).or_default());
+                }
+            }
+            Ok(Event::Start(element)) if element.local_name().as_ref() == b"formula" => {
+                if let Some(priority) = current_priority {
+                    hints
+                        .entry(priority)
+                        .or_default()
+                        .formulas
+                        .push(String::new());
+                    in_formula = true;
+                }
+            }
+            Ok(Event::Text(text)) if in_formula => {
+                if let Some(priority) = current_priority
+                    && let Ok(content) = text.xml_content()
+                    && let Some(formula) = hints.entry(priority).or_default().formulas.last_mut()
+                {
+                    formula.push_str(&content);
+                }
+            }
+            // quick-xml reports `&lt;` and friends as separate events.
+            Ok(Event::GeneralRef(reference)) if in_formula => {
+                let resolved: Option<String> = reference.decode().ok().and_then(|name| {
+                    unescape(&format!("&{name};"))
+                        .ok()
+                        .map(|value| value.into_owned())
+                });
+                if let Some(priority) = current_priority
+                    && let Some(resolved) = resolved
+                    && let Some(formula) = hints.entry(priority).or_default().formulas.last_mut()
+                {
+                    formula.push_str(&resolved);
+                }
+            }
+            Ok(Event::End(element)) if element.local_name().as_ref() == b"formula" => {
+                in_formula = false;
+            }
+            Ok(Event::Start(element)) if element.local_name().as_ref() == b"colorScale" => {
+                in_color_scale = true;
+            }
+            Ok(Event::Start(element) | Event::Empty(element))
+                if in_color_scale && element.local_name().as_ref() == b"cfvo" =>
+            {
+                if let Some(priority) = current_priority
+                    && let Some(kind) = attr_value(&reader, &element, b"type")
+                {
+                    let value: String = attr_value(&reader, &element, b"val").unwrap_or_default();
+                    hints
+                        .entry(priority)
+                        .or_default()
+                        .color_scale_cfvos
+                        .push((kind, value));
+                }
+            }
+            Ok(Event::End(element)) if element.local_name().as_ref() == b"colorScale" => {
+                in_color_scale = false;
             }
             Ok(Event::Start(element) | Event::Empty(element))
                 if element.local_name().as_ref() == b"dataBar" =>
@@ -146,6 +249,8 @@ pub(crate) fn parse_worksheet_hints(xml: &str) -> RawCondFmtHints {
             Ok(Event::End(element)) if element.local_name().as_ref() == b"cfRule" => {
                 current_priority = None;
                 in_icon_set = false;
+                in_color_scale = false;
+                in_formula = false;
             }
             Ok(Event::Eof) | Err(_) => break,
             _ => {}
@@ -243,6 +348,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn worksheet_hints_keep_rule_formulas_and_ranking_attributes() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+  <conditionalFormatting sqref="A1:A9">
+    <cfRule type="cellIs" priority="1" operator="between"><formula>10</formula><formula>20</formula></cfRule>
+    <cfRule type="expression" priority="2"><formula>AND($B1&gt;5, $C1&lt;&gt;"a &amp; b")</formula></cfRule>
+    <cfRule type="top10" priority="3" rank="20" percent="1" bottom="1"/>
+    <cfRule type="aboveAverage" priority="4" aboveAverage="0"/>
+    <cfRule type="colorScale" priority="5">
+      <colorScale><cfvo type="min"/><cfvo type="percentile" val="50"/><cfvo type="max"/></colorScale>
+    </cfRule>
+  </conditionalFormatting>
+</worksheet>"#;
+
+        let hints = parse_worksheet_hints(xml);
+        assert_eq!(hints[&1].formulas, vec!["10", "20"]);
+        assert_eq!(hints[&2].formulas, vec![r#"AND($B1>5, $C1<>"a & b")"#]);
+        assert_eq!(hints[&3].rank, Some(20));
+        assert!(hints[&3].percent && hints[&3].bottom);
+        assert_eq!(hints[&4].above_average, Some(false));
+        assert_eq!(
+            hints[&5].color_scale_cfvos,
+            vec![
+                ("min".to_string(), String::new()),
+                ("percentile".to_string(), "50".to_string()),
+                ("max".to_string(), String::new()),
+            ]
+        );
+        assert!(hints[&5].icon_cfvos.is_empty());
+    }
+
     #[test]
     fn worksheet_hints_do_not_confuse_databar_cfvo_with_icon_set() {
         // cfvos inside a dataBar rule must not leak into the icon-set hint.
@@ -763,3 +763,208 @@ fn test_cond_fmt_icon_set_num_thresholds_from_start_end_cfvos() {
         Some(Color::new(104, 164, 144))
     );
 }
+
+/// Helper: one rule over `sqref` with a solid fill, on a sheet prepared by
+/// `fill`.
+fn build_fill_rule_fixture(
+    sqref: &str,
+    fill: impl FnOnce(&mut umya_spreadsheet::Worksheet),
+    configure: impl FnOnce(&mut umya_spreadsheet::ConditionalFormattingRule),
+) -> Vec<u8> {
+    build_xlsx_with_cond_fmt(|sheet| {
+        fill(sheet);
+        let mut rule = umya_spreadsheet::ConditionalFormattingRule::default();
+        rule.set_priority(1);
+        let mut style = umya_spreadsheet::Style::default();
+        style.set_background_color("FFFF0000");
+        rule.set_style(style);
+        configure(&mut rule);
+
+        let mut seq = umya_spreadsheet::SequenceOfReferences::default();
+        seq.set_sqref(sqref);
+        let mut cf = umya_spreadsheet::ConditionalFormatting::default();
+        cf.set_sequence_of_references(seq);
+        cf.add_conditional_collection(rule);
+        sheet.set_conditional_formatting_collection(vec![cf]);
+    })
+}
+
+/// Whether each cell of column A (rows 1..=count) got the fixture's fill.
+fn filled_rows(data: &[u8], count: usize) -> Vec<bool> {
+    let parser = XlsxParser;
+    let (doc, _warnings) = parser.parse(data, &ConvertOptions::default()).unwrap();
+    let tp = get_sheet_page(&doc, 0);
+    (0..count)
+        .map(|row| tp.table.rows[row].cells[0].background == Some(Color::new(255, 0, 0)))
+        .collect()
+}
+
+#[test]
+fn test_cond_fmt_color_scale_three_color_percentile_midpoint() {
+    let data = build_xlsx_with_cond_fmt(|sheet| {
+        sheet.get_cell_mut("A1").set_value_number(0.0);
+        sheet.get_cell_mut("A2").set_value_number(10.0);
+        sheet.get_cell_mut("A3").set_value_number(100.0);
+
+        let mut rule = umya_spreadsheet::ConditionalFormattingRule::default();
+        rule.set_type(umya_spreadsheet::ConditionalFormatValues::ColorScale);
+        rule.set_priority(1);
+
+        let mut cs = umya_spreadsheet::ColorScale::default();
+        for (kind, val) in [
+            (
+                umya_spreadsheet::ConditionalFormatValueObjectValues::Min,
+                "",
+            ),
+            (
+                umya_spreadsheet::ConditionalFormatValueObjectValues::Percentile,
+                "50",
+            ),
+            (
+                umya_spreadsheet::ConditionalFormatValueObjectValues::Max,
+                "",
+            ),
+        ] {
+            let mut cfvo = umya_spreadsheet::ConditionalFormatValueObject::default();
+            cfvo.set_type(kind);
+            if !val.is_empty() {
+                cfvo.set_val(val);
+            }
+            cs.add_cfvo_collection(cfvo);
+        }
+        for argb in ["FFF8696B", "FFFFEB84", "FF63BE7B"] {
+            let mut color = umya_spreadsheet::Color::default();
+            color.set_argb(argb);
+            cs.add_color_collection(color);
+        }
+        rule.set_color_scale(cs);
+
+        let mut seq = umya_spreadsheet::SequenceOfReferences::default();
+        seq.set_sqref("A1:A3");
+        let mut cf = umya_spreadsheet::ConditionalFormatting::default();
+        cf.set_sequence_of_references(seq);
+        cf.add_conditional_collection(rule);
+        sheet.set_conditional_formatting_collection(vec![cf]);
+    });
+
+    let parser = XlsxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+    let tp = get_sheet_page(&doc, 0);
+
+    // The median (10) sits on the middle stop, not at 10% of the way from
+    // red to green.
+    assert_eq!(
+        tp.table.rows[1].cells[0].background,
+        Some(Color::new(0xFF, 0xEB, 0x84))
+    );
+    assert_eq!(
+        tp.table.rows[0].cells[0].background,
+        Some(Color::new(0xF8, 0x69, 0x6B))
+    );
+    assert_eq!(
+        tp.table.rows[2].cells[0].background,
+        Some(Color::new(0x63, 0xBE, 0x7B))
+    );
+}
+
+#[test]
+fn test_cond_fmt_top_rank() {
+    let data = build_fill_rule_fixture(
+        "A1:A4",
+        |sheet| {
+            for (cell, value) in [("A1", 5.0), ("A2", 40.0), ("A3", 20.0), ("A4", 30.0)] {
+                sheet.get_cell_mut(cell).set_value_number(value);
+            }
+        },
+        |rule| {
+            rule.set_type(umya_spreadsheet::ConditionalFormatValues::Top10);
+            rule.set_rank(2);
+        },
+    );
+    assert_eq!(filled_rows(&data, 4), vec![false, true, false, true]);
+}
+
+#[test]
+fn test_cond_fmt_above_average() {
+    let data = build_fill_rule_fixture(
+        "A1:A3",
+        |sheet| {
+            for (cell, value) in [("A1", 1.0), ("A2", 2.0), ("A3", 6.0)] {
+                sheet.get_cell_mut(cell).set_value_number(value);
+            }
+        },
+        |rule| rule.set_type(umya_spreadsheet::ConditionalFormatValues::AboveAverage),
+    );
+    assert_eq!(filled_rows(&data, 3), vec![false, false, true]);
+}
+
+#[test]
+fn test_cond_fmt_duplicate_values_ignore_case() {
+    let data = build_fill_rule_fixture(
+        "A1:A3",
+        |sheet| {
+            sheet.get_cell_mut("A1").set_value("North");
+            sheet.get_cell_mut("A2").set_value("South");
+            sheet.get_cell_mut("A3").set_value("north");
+        },
+        |rule| rule.set_type(umya_spreadsheet::ConditionalFormatValues::DuplicateValues),
+    );
+    assert_eq!(filled_rows(&data, 3), vec![true, false, true]);
+}
+
+#[test]
+fn test_cond_fmt_lower_priority_number_wins() {
+    let data = build_xlsx_with_cond_fmt(|sheet| {
+        sheet.get_cell_mut("A1").set_value_number(60.0);
+
+        let rule_for = |priority: i32, argb: &str| {
+            let mut rule = umya_spreadsheet::ConditionalFormattingRule::default();
+            rule.set_type(umya_spreadsheet::ConditionalFormatValues::CellIs);
+            rule.set_operator(umya_spreadsheet::ConditionalFormattingOperatorValues::GreaterThan);
+            rule.set_priority(priority);
+            let mut style = umya_spreadsheet::Style::default();
+            style.set_background_color(argb);
+            rule.set_style(style);
+            let mut formula = umya_spreadsheet::Formula::default();
+            formula.set_string_value("50");
+            rule.set_formula(formula);
+            rule
+        };
+
+        let mut seq = umya_spreadsheet::SequenceOfReferences::default();
+        seq.set_sqref("A1");
+        let mut cf = umya_spreadsheet::ConditionalFormatting::default();
+        cf.set_sequence_of_references(seq);
+        // Listed first but takes precedence over the rule after it.
+        cf.add_conditional_collection(rule_for(1, "FFFF0000"));
+        cf.add_conditional_collection(rule_for(2, "FF00FF00"));
+        sheet.set_conditional_formatting_collection(vec![cf]);
+    });
+
+    assert_eq!(filled_rows(&data, 1), vec![true]);
+}
+
+#[cfg(feature = "formula-eval")]
+#[test]
+fn test_cond_fmt_expression_follows_relative_rows() {
+    let data = build_fill_rule_fixture(
+        "A1:A3",
+        |sheet| {
+            for (row, value) in [(1, 1.0), (2, 10.0), (3, 3.0)] {
+                sheet
+                    .get_cell_mut(format!("A{row}").as_str())
+                    .set_value("task");
+                sheet
+                    .get_cell_mut(format!("B{row}").as_str())
+                    .set_value_number(value);
+            }
+        },
+        |rule| {
+            rule.set_type(umya_spreadsheet::ConditionalFormatValues::Expression);
+            let mut formula = umya_spreadsheet::Formula::default();
+            formula.set_string_value("$B1>5");
+            rule.set_formula(formula);
+        },
+    );
+    assert_eq!(filled_rows(&data, 3), vec![false, true, false]);
+}
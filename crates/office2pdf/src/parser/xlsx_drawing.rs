@@ -390,6 +390,7 @@ pub(super) fn parse_drawing_chart_anchors(xml: &str) -> Vec<(u32, String)> {
 
 /// A picture anchor from a worksheet drawing, in raw drawing coordinates.
 /// Rows/columns are 0-indexed as in the XML; offsets and extents are EMU.
+#[derive(Clone)]
 pub(super) struct RawImageAnchor {
     pub(super) from_row: u32,
     pub(super) from_col: u32,
@@ -399,6 +400,10 @@ pub(super) struct RawImageAnchor {
     pub(super) to: Option<(u32, i64, u32, i64)>,
     /// oneCellAnchor extent (cx, cy).
     pub(super) ext_emu: Option<(i64, i64)>,
+    /// absoluteAnchor position (x, y).
+    pub(super) pos_emu: Option<(i64, i64)>,
+    /// Share of the anchor box taken by a grouped picture.
+    pub(super) part: Option<[f64; 4]>,
     pub(super) data: Vec<u8>,
     pub(super) format: crate::ir::ImageFormat,
 }
@@ -471,6 +476,8 @@ pub(super) fn extract_images_with_anchors(data: &[u8]) -> HashMap<String, Vec<Ra
                         from_row_off_emu: geometry.from_row_off_emu,
                         to: geometry.to,
                         ext_emu: geometry.ext_emu,
+                        pos_emu: geometry.pos_emu,
+                        part: geometry.part,
                         data,
                         format,
                     });
@@ -481,7 +488,7 @@ pub(super) fn extract_images_with_anchors(data: &[u8]) -> HashMap<String, Vec<Ra
     result
 }
 
-fn read_zip_entry_bytes<R: std::io::Read + std::io::Seek>(
+pub(super) fn read_zip_entry_bytes<R: std::io::Read + std::io::Seek>(
     archive: &mut zip::ZipArchive<R>,
     path: &str,
 ) -> Option<Vec<u8>> {
@@ -494,7 +501,10 @@ fn read_zip_entry_bytes<R: std::io::Read + std::io::Seek>(
 
 /// Map media bytes to a renderable (data, format) pair; metafiles are
 /// converted to SVG.
-fn decode_media(path: &str, bytes: Vec<u8>) -> Option<(Vec<u8>, crate::ir::ImageFormat)> {
+pub(super) fn decode_media(
+    path: &str,
+    bytes: Vec<u8>,
+) -> Option<(Vec<u8>, crate::ir::ImageFormat)> {
     use crate::ir::ImageFormat;
     let extension: String = path.rsplit('.').next().unwrap_or("").to_ascii_lowercase();
     match extension.as_str() {
@@ -511,6 +521,7 @@ fn decode_media(path: &str, bytes: Vec<u8>) -> Option<(Vec<u8>, crate::ir::Image
 }
 
 /// Geometry captured from a single pic anchor before media resolution.
+#[derive(Clone)]
 pub(super) struct ImageAnchorGeometry {
     pub(super) from_row: u32,
     pub(super) from_col: u32,
@@ -518,10 +529,80 @@ pub(super) struct ImageAnchorGeometry {
     pub(super) from_row_off_emu: i64,
     pub(super) to: Option<(u32, i64, u32, i64)>,
     pub(super) ext_emu: Option<(i64, i64)>,
+    /// absoluteAnchor position (x, y) from the sheet's top-left corner.
+    pub(super) pos_emu: Option<(i64, i64)>,
+    /// For a picture inside a group shape: its share of the anchor box as
+    /// (left, top, width, height) fractions.
+    pub(super) part: Option<[f64; 4]>,
+}
+
+/// An `<a:xfrm>` rectangle, EMU.
+#[derive(Default, Clone, Copy)]
+struct Xfrm {
+    off: (i64, i64),
+    ext: (i64, i64),
+    ch_off: (i64, i64),
+    ch_ext: (i64, i64),
+}
+
+impl Xfrm {
+    /// Map a rectangle from this group's child space into its parent space.
+    fn to_parent(&self, (x, y, cx, cy): (f64, f64, f64, f64)) -> (f64, f64, f64, f64) {
+        let scale = |ext: i64, ch_ext: i64| -> f64 {
+            if ch_ext > 0 {
+                ext as f64 / ch_ext as f64
+            } else {
+                1.0
+            }
+        };
+        let (sx, sy) = (
+            scale(self.ext.0, self.ch_ext.0),
+            scale(self.ext.1, self.ch_ext.1),
+        );
+        (
+            self.off.0 as f64 + (x - self.ch_off.0 as f64) * sx,
+            self.off.1 as f64 + (y - self.ch_off.1 as f64) * sy,
+            cx * sx,
+            cy * sy,
+        )
+    }
+}
+
+/// Where a picture's rectangle sits within the box of the outermost group
+/// holding it, as fractions; `groups` runs outermost first.
+fn group_part(groups: &[Xfrm], pic: &Xfrm) -> Option<[f64; 4]> {
+    let outer: &Xfrm = groups.first()?;
+    if outer.ext.0 <= 0 || outer.ext.1 <= 0 {
+        return None;
+    }
+    let mut rect = (
+        pic.off.0 as f64,
+        pic.off.1 as f64,
+        pic.ext.0 as f64,
+        pic.ext.1 as f64,
+    );
+    for group in groups.iter().rev() {
+        rect = group.to_parent(rect);
+    }
+    let (width, height) = (outer.ext.0 as f64, outer.ext.1 as f64);
+    Some([
+        (rect.0 - outer.off.0 as f64) / width,
+        (rect.1 - outer.off.1 as f64) / height,
+        rect.2 / width,
+        rect.3 / height,
+    ])
+}
+
+fn emu_pair(e: &quick_xml::events::BytesStart, x: &[u8], y: &[u8]) -> (i64, i64) {
+    (
+        xml_util::get_attr_i64(e, x).unwrap_or(0),
+        xml_util::get_attr_i64(e, y).unwrap_or(0),
+    )
 }
 
 /// Parse `<xdr:pic>` anchors from a worksheet drawing: anchor geometry plus
-/// the blip relationship id.
+/// the blip relationship id. Pictures inside group shapes each get their
+/// own entry, placed by the group transforms.
 pub(super) fn parse_drawing_image_anchors(xml: &str) -> Vec<(ImageAnchorGeometry, String)> {
     #[derive(Default, Clone, Copy)]
     struct Corner {
@@ -535,68 +616,27 @@ pub(super) fn parse_drawing_image_anchors(xml: &str) -> Vec<(ImageAnchorGeometry
     let mut reader = quick_xml::Reader::from_str(xml);
 
     let mut in_anchor = false;
-    let mut in_pic = false;
     let mut corner_target: Option<bool> = None; // Some(true)=from, Some(false)=to
     let mut current_field: Option<&'static str> = None;
     let mut from = Corner::default();
     let mut to: Option<Corner> = None;
     let mut ext_emu: Option<(i64, i64)> = None;
+    let mut pos_emu: Option<(i64, i64)> = None;
+    // Open shapes (pic, sp, grpSp, ...) inside the anchor; the anchor's own
+    // ext/pos live outside all of them.
+    let mut shape_depth: u32 = 0;
+    let mut groups: Vec<Xfrm> = Vec::new();
+    let mut in_group_props = false;
+    let mut in_pic = false;
+    let mut pic_xfrm = Xfrm::default();
     let mut blip_rid: Option<String> = None;
+    // (rid, picture xfrm, enclosing groups) per picture of the anchor.
+    let mut pictures: Vec<(String, Xfrm, Vec<Xfrm>)> = Vec::new();
 
     loop {
-        match reader.read_event() {
-            Ok(quick_xml::events::Event::Start(ref e)) => match e.local_name().as_ref() {
-                b"twoCellAnchor" | b"oneCellAnchor" | b"absoluteAnchor" => {
-                    in_anchor = true;
-                    in_pic = false;
-                    from = Corner::default();
-                    to = None;
-                    ext_emu = None;
-                    blip_rid = None;
-                }
-                b"from" if in_anchor => corner_target = Some(true),
-                b"to" if in_anchor => {
-                    corner_target = Some(false);
-                    to = Some(Corner::default());
-                }
-                b"col" if corner_target.is_some() => current_field = Some("col"),
-                b"colOff" if corner_target.is_some() => current_field = Some("colOff"),
-                b"row" if corner_target.is_some() => current_field = Some("row"),
-                b"rowOff" if corner_target.is_some() => current_field = Some("rowOff"),
-                b"pic" if in_anchor => in_pic = true,
-                _ => {}
-            },
-            Ok(quick_xml::events::Event::Empty(ref e)) => {
-                let local = e.local_name();
-                if in_anchor && local.as_ref() == b"ext" && !in_pic && to.is_none() {
-                    // oneCellAnchor extent (the pic's own a:ext lives inside
-                    // xfrm, which we ignore by requiring !in_pic).
-                    let mut cx: i64 = 0;
-                    let mut cy: i64 = 0;
-                    for attr in e.attributes().flatten() {
-                        let value: i64 = attr
-                            .unescape_value()
-                            .ok()
-                            .and_then(|v| v.parse().ok())
-                            .unwrap_or(0);
-                        match attr.key.local_name().as_ref() {
-                            b"cx" => cx = value,
-                            b"cy" => cy = value,
-                            _ => {}
-                        }
-                    }
-                    ext_emu = Some((cx, cy));
-                }
-                if in_pic && local.as_ref() == b"blip" {
-                    for attr in e.attributes().flatten() {
-                        if attr.key.local_name().as_ref() == b"embed"
-                            && let Ok(val) = attr.unescape_value()
-                        {
-                            blip_rid = Some(val.to_string());
-                        }
-                    }
-                }
-            }
+        let (e, is_start) = match reader.read_event() {
+            Ok(quick_xml::events::Event::Start(e)) => (e, true),
+            Ok(quick_xml::events::Event::Empty(e)) => (e, false),
             Ok(quick_xml::events::Event::Text(ref t)) => {
                 if let (Some(is_from), Some(field)) = (corner_target, current_field)
                     && let Ok(text) = t.xml_content()
@@ -615,32 +655,110 @@ pub(super) fn parse_drawing_image_anchors(xml: &str) -> Vec<(ImageAnchorGeometry
                         _ => {}
                     }
                 }
+                continue;
             }
-            Ok(quick_xml::events::Event::End(ref e)) => match e.local_name().as_ref() {
-                b"twoCellAnchor" | b"oneCellAnchor" | b"absoluteAnchor" => {
-                    if in_pic && let Some(rid) = blip_rid.take() {
-                        result.push((
-                            ImageAnchorGeometry {
-                                from_row: from.row,
-                                from_col: from.col,
-                                from_col_off_emu: from.col_off,
-                                from_row_off_emu: from.row_off,
-                                to: to.map(|c| (c.col, c.col_off, c.row, c.row_off)),
-                                ext_emu,
-                            },
-                            rid,
-                        ));
+            Ok(quick_xml::events::Event::End(ref e)) => {
+                match e.local_name().as_ref() {
+                    b"twoCellAnchor" | b"oneCellAnchor" | b"absoluteAnchor" => {
+                        for (rid, xfrm, enclosing) in pictures.drain(..) {
+                            result.push((
+                                ImageAnchorGeometry {
+                                    from_row: from.row,
+                                    from_col: from.col,
+                                    from_col_off_emu: from.col_off,
+                                    from_row_off_emu: from.row_off,
+                                    to: to.map(|c| (c.col, c.col_off, c.row, c.row_off)),
+                                    ext_emu,
+                                    pos_emu,
+                                    part: group_part(&enclosing, &xfrm),
+                                },
+                                rid,
+                            ));
+                        }
+                        in_anchor = false;
+                        corner_target = None;
+                    }
+                    b"pic" if in_pic => {
+                        if let Some(rid) = blip_rid.take() {
+                            pictures.push((rid, pic_xfrm, groups.clone()));
+                        }
+                        in_pic = false;
+                        shape_depth = shape_depth.saturating_sub(1);
                     }
-                    in_anchor = false;
-                    in_pic = false;
-                    corner_target = None;
+                    b"grpSp" => {
+                        groups.pop();
+                        shape_depth = shape_depth.saturating_sub(1);
+                    }
+                    b"sp" | b"cxnSp" | b"graphicFrame" => {
+                        shape_depth = shape_depth.saturating_sub(1);
+                    }
+                    b"grpSpPr" => in_group_props = false,
+                    b"from" | b"to" => corner_target = None,
+                    b"col" | b"colOff" | b"row" | b"rowOff" => current_field = None,
+                    _ => {}
                 }
-                b"from" | b"to" => corner_target = None,
-                b"col" | b"colOff" | b"row" | b"rowOff" => current_field = None,
-                _ => {}
-            },
-            Ok(quick_xml::events::Event::Eof) => break,
-            Err(_) => break,
+                continue;
+            }
+            Ok(quick_xml::events::Event::Eof) | Err(_) => break,
+            _ => continue,
+        };
+        match e.local_name().as_ref() {
+            b"twoCellAnchor" | b"oneCellAnchor" | b"absoluteAnchor" if is_start => {
+                in_anchor = true;
+                from = Corner::default();
+                to = None;
+                ext_emu = None;
+                pos_emu = None;
+                shape_depth = 0;
+                groups.clear();
+                in_pic = false;
+                pictures.clear();
+            }
+            b"from" if in_anchor && is_start => corner_target = Some(true),
+            b"to" if in_anchor && is_start => {
+                corner_target = Some(false);
+                to = Some(Corner::default());
+            }
+            b"col" if corner_target.is_some() => current_field = Some("col"),
+            b"colOff" if corner_target.is_some() => current_field = Some("colOff"),
+            b"row" if corner_target.is_some() => current_field = Some("row"),
+            b"rowOff" if corner_target.is_some() => current_field = Some("rowOff"),
+            b"pos" if in_anchor && shape_depth == 0 => pos_emu = Some(emu_pair(&e, b"x", b"y")),
+            // oneCellAnchor/absoluteAnchor extent; shapes carry their own
+            // a:ext inside xfrm.
+            b"ext" if in_anchor && shape_depth == 0 => ext_emu = Some(emu_pair(&e, b"cx", b"cy")),
+            b"pic" if in_anchor && is_start => {
+                in_pic = true;
+                pic_xfrm = Xfrm::default();
+                blip_rid = None;
+                shape_depth += 1;
+            }
+            b"grpSp" if in_anchor && is_start => {
+                groups.push(Xfrm::default());
+                shape_depth += 1;
+            }
+            b"sp" | b"cxnSp" | b"graphicFrame" if in_anchor && is_start => shape_depth += 1,
+            b"grpSpPr" if is_start => in_group_props = true,
+            b"off" | b"ext" | b"chOff" | b"chExt" if in_pic || in_group_props => {
+                let target: &mut Xfrm = if in_pic {
+                    &mut pic_xfrm
+                } else if let Some(group) = groups.last_mut() {
+                    group
+                } else {
+                    continue;
+                };
+                match e.local_name().as_ref() {
+                    b"off" => target.off = emu_pair(&e, b"x", b"y"),
+                    b"ext" => target.ext = emu_pair(&e, b"cx", b"cy"),
+                    b"chOff" => target.ch_off = emu_pair(&e, b"x", b"y"),
+                    _ => target.ch_ext = emu_pair(&e, b"cx", b"cy"),
+                }
+            }
+            b"blip" if in_pic => {
+                if let Some(rid) = xml_util::get_attr_str(&e, b"embed") {
+                    blip_rid = Some(rid);
+                }
+            }
             _ => {}
         }
     }
@@ -651,6 +769,7 @@ pub(super) fn parse_drawing_image_anchors(xml: &str) -> Vec<(ImageAnchorGeometry
 // ── Drawing text boxes ──────────────────────────────────────────────────
 
 /// A text-box shape from a worksheet drawing, in raw drawing coordinates.
+#[derive(Clone)]
 pub(super) struct RawTextBoxAnchor {
     pub(super) geometry: ImageAnchorGeometry,
     pub(super) paragraphs: Vec<crate::ir::Paragraph>,
@@ -981,6 +1100,8 @@ pub(super) fn parse_drawing_text_boxes(xml: &str) -> Vec<RawTextBoxAnchor> {
                                     from_row_off_emu: from.row_off,
                                     to: to.map(|c| (c.col, c.col_off, c.row, c.row_off)),
                                     ext_emu,
+                                    pos_emu: None,
+                                    part: None,
                                 },
                                 paragraphs: std::mem::take(&mut paragraphs),
                                 fill,
@@ -1034,3 +1155,7 @@ pub(super) fn parse_drawing_text_boxes(xml: &str) -> Vec<RawTextBoxAnchor> {
 
     result
 }
+
+#[cfg(test)]
+#[path = "xlsx_drawing_tests.rs"]
+mod tests;
@@ -0,0 +1,151 @@
+use super::*;
+use crate::config::ConvertOptions;
+use crate::ir::Page;
+use crate::parser::Parser;
+
+const DRAWING_NS: &str = r#"xmlns:xdr="http://schemas.openxmlformats.org/drawingml/2006/spreadsheetDrawing" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships""#;
+
+fn pic(rid: &str, xfrm: &str) -> String {
+    format!(
+        r#"<xdr:pic><xdr:nvPicPr><xdr:cNvPr id="2" name="Picture"/><xdr:cNvPicPr/></xdr:nvPicPr><xdr:blipFill><a:blip r:embed="{rid}"/></xdr:blipFill><xdr:spPr>{xfrm}</xdr:spPr></xdr:pic>"#
+    )
+}
+
+#[test]
+fn test_one_cell_anchor_keeps_its_own_extent() {
+    let xml = format!(
+        r#"<xdr:wsDr {DRAWING_NS}><xdr:oneCellAnchor><xdr:from><xdr:col>1</xdr:col><xdr:colOff>12700</xdr:colOff><xdr:row>2</xdr:row><xdr:rowOff>127000</xdr:rowOff></xdr:from><xdr:ext cx="508000" cy="254000"/>{}<xdr:clientData/></xdr:oneCellAnchor></xdr:wsDr>"#,
+        pic(
+            "rId1",
+            r#"<a:xfrm><a:off x="1" y="2"/><a:ext cx="99" cy="99"/></a:xfrm>"#
+        )
+    );
+    let anchors = parse_drawing_image_anchors(&xml);
+    assert_eq!(anchors.len(), 1);
+    let (geometry, rid) = &anchors[0];
+    assert_eq!(rid, "rId1");
+    assert_eq!((geometry.from_col, geometry.from_row), (1, 2));
+    assert_eq!(
+        (geometry.from_col_off_emu, geometry.from_row_off_emu),
+        (12_700, 127_000)
+    );
+    assert_eq!(geometry.ext_emu, Some((508_000, 254_000)));
+    assert_eq!(geometry.part, None);
+}
+
+#[test]
+fn test_absolute_anchor_reads_position() {
+    let xml = format!(
+        r#"<xdr:wsDr {DRAWING_NS}><xdr:absoluteAnchor><xdr:pos x="635000" y="1270000"/><xdr:ext cx="508000" cy="254000"/>{}<xdr:clientData/></xdr:absoluteAnchor></xdr:wsDr>"#,
+        pic("rId3", "")
+    );
+    let anchors = parse_drawing_image_anchors(&xml);
+    assert_eq!(anchors.len(), 1);
+    assert_eq!(anchors[0].0.pos_emu, Some((635_000, 1_270_000)));
+    assert_eq!(anchors[0].0.ext_emu, Some((508_000, 254_000)));
+}
+
+#[test]
+fn test_grouped_pictures_each_take_their_share_of_the_anchor() {
+    // A 200x100 group whose child space is scaled 2x: the left picture fills
+    // the left half, the right one the top half of the right half.
+    let xml = format!(
+        r#"<xdr:wsDr {DRAWING_NS}><xdr:twoCellAnchor><xdr:from><xdr:col>0</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>0</xdr:row><xdr:rowOff>0</xdr:rowOff></xdr:from><xdr:to><xdr:col>4</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>6</xdr:row><xdr:rowOff>0</xdr:rowOff></xdr:to><xdr:grpSp><xdr:nvGrpSpPr><xdr:cNvPr id="1" name="Group"/><xdr:cNvGrpSpPr/></xdr:nvGrpSpPr><xdr:grpSpPr><a:xfrm><a:off x="1000" y="1000"/><a:ext cx="200" cy="100"/><a:chOff x="0" y="0"/><a:chExt cx="400" cy="200"/></a:xfrm></xdr:grpSpPr>{}{}</xdr:grpSp><xdr:clientData/></xdr:twoCellAnchor></xdr:wsDr>"#,
+        pic(
+            "rId1",
+            r#"<a:xfrm><a:off x="0" y="0"/><a:ext cx="200" cy="200"/></a:xfrm>"#
+        ),
+        pic(
+            "rId2",
+            r#"<a:xfrm><a:off x="200" y="0"/><a:ext cx="200" cy="100"/></a:xfrm>"#
+        ),
+    );
+    let anchors = parse_drawing_image_anchors(&xml);
+    let parts: Vec<(&str, Option<[f64; 4]>)> = anchors
+        .iter()
+        .map(|(geometry, rid)| (rid.as_str(), geometry.part))
+        .collect();
+    assert_eq!(
+        parts,
+        vec![
+            ("rId1", Some([0.0, 0.0, 0.5, 1.0])),
+            ("rId2", Some([0.5, 0.0, 0.5, 0.5])),
+        ]
+    );
+    // The group's xfrm must not be mistaken for the anchor extent.
+    assert_eq!(anchors[0].0.ext_emu, None);
+}
+
+/// Add a one-picture drawing to the first worksheet of `xlsx`.
+fn with_picture(xlsx: &[u8], drawing_xml: &str) -> Vec<u8> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(xlsx.to_vec())).expect("zip");
+    let mut out = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::FileOptions::default();
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).expect("entry");
+        let name: String = file.name().to_string();
+        if name == "xl/worksheets/_rels/sheet1.xml.rels" {
+            continue;
+        }
+        out.start_file(name, options).expect("start entry");
+        std::io::copy(&mut file, &mut out).expect("copy entry");
+    }
+    let parts: [(&str, &[u8]); 4] = [
+        (
+            "xl/worksheets/_rels/sheet1.xml.rels",
+            br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/drawing" Target="../drawings/drawing1.xml"/></Relationships>"#,
+        ),
+        ("xl/drawings/drawing1.xml", drawing_xml.as_bytes()),
+        (
+            "xl/drawings/_rels/drawing1.xml.rels",
+            br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/image1.png"/></Relationships>"#,
+        ),
+        ("xl/media/image1.png", &[0x89, b'P', b'N', b'G']),
+    ];
+    for (name, content) in parts {
+        out.start_file(name, options).expect("start entry");
+        std::io::Write::write_all(&mut out, content).expect("write entry");
+    }
+    out.finish().expect("finish zip").into_inner()
+}
+
+#[test]
+fn test_picture_sits_at_its_anchor_cell() {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        for row in 1..=5 {
+            sheet
+                .get_cell_mut(format!("A{row}").as_str())
+                .set_value("x");
+            sheet
+                .get_cell_mut(format!("B{row}").as_str())
+                .set_value("y");
+        }
+    }
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    // Picture at B3, 10pt into the row.
+    let drawing_xml = format!(
+        r#"<xdr:wsDr {DRAWING_NS}><xdr:oneCellAnchor><xdr:from><xdr:col>1</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>2</xdr:row><xdr:rowOff>127000</xdr:rowOff></xdr:from><xdr:ext cx="508000" cy="254000"/>{}<xdr:clientData/></xdr:oneCellAnchor></xdr:wsDr>"#,
+        pic("rId1", "")
+    );
+    let data: Vec<u8> = with_picture(&cursor.into_inner(), &drawing_xml);
+
+    let (doc, _warnings) = crate::parser::xlsx::XlsxParser
+        .parse(&data, &ConvertOptions::default())
+        .unwrap();
+    let Page::Sheet(page) = &doc.pages[0] else {
+        panic!("expected a sheet page");
+    };
+    assert_eq!(page.images.len(), 1);
+    let sheet_image = &page.images[0];
+    // Rendered after row 2, so it starts where row 3 does.
+    assert_eq!(sheet_image.anchor_row, 2);
+    assert!((sheet_image.x_offset_pt - page.table.column_widths[0]).abs() < 1e-6);
+    assert!((sheet_image.y_offset_pt - 10.0).abs() < 1e-6);
+    assert_eq!(sheet_image.image.width, Some(40.0));
+    assert_eq!(sheet_image.image.height, Some(20.0));
+}
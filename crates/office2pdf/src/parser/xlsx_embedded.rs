@@ -0,0 +1,191 @@
+//! Workbooks embedded as OLE objects in other documents (an Excel worksheet
+//! pasted onto a slide). Only the part of the sheet the object shows is
+//! rendered, as a plain table sized to the host frame.
+
+use std::io::{Cursor, Read};
+
+use quick_xml::events::Event;
+
+use super::cond_fmt_raw;
+use super::xlsx_cells::*;
+use super::xlsx_numfmt::FormatSettings;
+use crate::ir::{Block, Insets, Table};
+use crate::parser::xml_util::get_attr_str;
+
+/// Preview bounds when the workbook records no `<oleSize>`: an embedded
+/// object never shows more than a screenful of cells.
+const EMBEDDED_PREVIEW_MAX_ROWS: u32 = 50;
+const EMBEDDED_PREVIEW_MAX_COLS: u32 = 20;
+
+/// Excel's default body font size, for runs without an explicit size.
+const DEFAULT_CELL_FONT_SIZE_PT: f64 = 11.0;
+
+/// What `xl/workbook.xml` says about the object's visible area.
+#[derive(Debug, Default)]
+struct EmbeddedView {
+    /// `<oleSize ref>`: the cell range the host displays.
+    ole_size: Option<CellRange>,
+    /// `<workbookView activeTab>`: the sheet shown in the object.
+    active_tab: usize,
+}
+
+fn read_embedded_view(data: &[u8]) -> EmbeddedView {
+    let mut view = EmbeddedView::default();
+    let Ok(mut archive) = zip::ZipArchive::new(Cursor::new(data)) else {
+        return view;
+    };
+    let Ok(mut file) = archive.by_name("xl/workbook.xml") else {
+        return view;
+    };
+    let mut xml = String::new();
+    if file.read_to_string(&mut xml).is_err() {
+        return view;
+    }
+
+    let mut reader = quick_xml::Reader::from_str(&xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => match e.local_name().as_ref() {
+                b"workbookView" => {
+                    view.active_tab = get_attr_str(e, b"activeTab")
+                        .and_then(|tab| tab.parse().ok())
+                        .unwrap_or(0);
+                }
+                b"oleSize" => {
+                    view.ole_size = get_attr_str(e, b"ref").and_then(|range| {
+                        parse_print_area_range(&range).or_else(|| {
+                            // A single visible cell is written without `:`.
+                            let (col, row) = parse_cell_ref(&range)?;
+                            Some(CellRange {
+                                start_col: col,
+                                start_row: row,
+                                end_col: col,
+                                end_row: row,
+                            })
+                        })
+                    });
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    view
+}
+
+/// Parse the visible range of an embedded XLSX package into a table at the
+/// sheet's natural size. Returns `None` when the package is unreadable or the
+/// shown sheet is empty, so the host can fall back to its cached preview.
+pub(crate) fn parse_embedded_preview(data: &[u8]) -> Option<Table> {
+    let mut book = umya_spreadsheet::reader::xlsx::read_reader(Cursor::new(data), true).ok()?;
+    super::xlsx_pivot::apply_pivot_tables(&mut book, data);
+    let view: EmbeddedView = read_embedded_view(data);
+    let sheets = book.get_sheet_collection();
+    let sheet = sheets.get(view.active_tab).or_else(|| sheets.first())?;
+
+    let range: CellRange = match view.ole_size {
+        Some(range) => range,
+        None => {
+            let (max_col, max_row) = sheet.get_highest_column_and_row();
+            if max_col == 0 || max_row == 0 {
+                return None;
+            }
+            CellRange {
+                start_col: 1,
+                start_row: 1,
+                end_col: max_col.min(EMBEDDED_PREVIEW_MAX_COLS),
+                end_row: max_row.min(EMBEDDED_PREVIEW_MAX_ROWS),
+            }
+        }
+    };
+    if range.end_col < range.start_col || range.end_row < range.start_row {
+        return None;
+    }
+
+    let normal_font_mdw: Option<f64> = extract_normal_font(data)
+        .map(|(family, size)| max_digit_width_px_for_normal_font(&family, size));
+    let cond_fmt_hints = cond_fmt_raw::extract_cond_fmt_hints(data);
+    let mut ctx: SheetContext = sheet_context_for_columns(
+        sheet,
+        normal_font_mdw,
+        cond_fmt_hints.get(sheet.get_name()),
+        range.start_col,
+        range.end_col,
+        FormatSettings {
+            date1904: extract_date1904(data),
+            ..FormatSettings::default()
+        },
+    );
+    ctx.table_styles = super::xlsx_tables::extract_table_styles(data)
+        .remove(sheet.get_name())
+        .unwrap_or_default();
+    let rows = build_rows_for_range(sheet, &ctx, range.start_row, range.end_row);
+
+    Some(Table {
+        rows,
+        column_widths: ctx.column_widths,
+        header_row_count: 0,
+        alignment: None,
+        default_cell_padding: Some(XLSX_CELL_PADDING),
+        use_content_driven_row_heights: false,
+        default_vertical_align: Some(crate::ir::CellVerticalAlign::Bottom),
+    })
+}
+
+/// Stretch a preview table over its host frame. The host shows the visible
+/// range scaled to the frame on both axes, so column widths and row heights
+/// follow their own axis while text, padding, and spill widths shrink or grow
+/// with the smaller factor to stay inside their cells.
+pub(crate) fn scale_embedded_preview(table: &mut Table, frame_width: f64, frame_height: f64) {
+    let natural_width: f64 = table.column_widths.iter().sum();
+    let natural_height: f64 = table
+        .rows
+        .iter()
+        .map(|row| row.height.unwrap_or(EXCEL_DEFAULT_ROW_HEIGHT_PT))
+        .sum();
+    if natural_width <= 0.0 || natural_height <= 0.0 || frame_width <= 0.0 || frame_height <= 0.0 {
+        return;
+    }
+    let x_scale: f64 = frame_width / natural_width;
+    let y_scale: f64 = frame_height / natural_height;
+    let text_scale: f64 = x_scale.min(y_scale);
+
+    for width in &mut table.column_widths {
+        *width *= x_scale;
+    }
+    table.default_cell_padding = table
+        .default_cell_padding
+        .map(|padding| scale_insets(padding, text_scale));
+    for row in &mut table.rows {
+        row.height = Some(row.height.unwrap_or(EXCEL_DEFAULT_ROW_HEIGHT_PT) * y_scale);
+        for cell in &mut row.cells {
+            cell.spill_width = cell.spill_width.map(|width| width * x_scale);
+            cell.padding = cell
+                .padding
+                .map(|padding| scale_insets(padding, text_scale));
+            for block in &mut cell.content {
+                let Block::Paragraph(paragraph) = block else {
+                    continue;
+                };
+                for run in &mut paragraph.runs {
+                    let size: f64 = run.style.font_size.unwrap_or(DEFAULT_CELL_FONT_SIZE_PT);
+                    run.style.font_size = Some(size * text_scale);
+                }
+            }
+        }
+    }
+}
+
+fn scale_insets(insets: Insets, scale: f64) -> Insets {
+    Insets {
+        top: insets.top * scale,
+        right: insets.right * scale,
+        bottom: insets.bottom * scale,
+        left: insets.left * scale,
+    }
+}
+
+#[cfg(test)]
+#[path = "xlsx_embedded_tests.rs"]
+mod tests;
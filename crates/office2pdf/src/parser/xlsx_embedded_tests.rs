@@ -0,0 +1,123 @@
+use super::*;
+use crate::ir::{TableCell, TableRow};
+use std::io::Write;
+
+/// Build a one-sheet workbook, optionally recording an `<oleSize>` range the
+/// way Excel does for a workbook embedded in another document.
+fn build_embedded_xlsx(cells: &[(&str, &str)], ole_size: Option<&str>) -> Vec<u8> {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        for &(coord, value) in cells {
+            sheet.get_cell_mut(coord).set_value(value);
+        }
+    }
+    let mut cursor = Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    let data: Vec<u8> = cursor.into_inner();
+    let Some(ole_size) = ole_size else {
+        return data;
+    };
+
+    let mut source = zip::ZipArchive::new(Cursor::new(data)).unwrap();
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for index in 0..source.len() {
+        let mut file = source.by_index(index).unwrap();
+        let name: String = file.name().to_string();
+        let mut content: Vec<u8> = Vec::new();
+        file.read_to_end(&mut content).unwrap();
+        if name == "xl/workbook.xml" {
+            let xml: String = String::from_utf8(content).unwrap().replace(
+                "</workbook>",
+                &format!(r#"<oleSize ref="{ole_size}"/></workbook>"#),
+            );
+            content = xml.into_bytes();
+        }
+        zip.start_file(name, zip::write::FileOptions::default())
+            .unwrap();
+        zip.write_all(&content).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+fn cell_text(cell: &TableCell) -> String {
+    cell.content
+        .iter()
+        .filter_map(|block| match block {
+            Block::Paragraph(paragraph) => Some(
+                paragraph
+                    .runs
+                    .iter()
+                    .map(|run| run.text.as_str())
+                    .collect::<String>(),
+            ),
+            _ => None,
+        })
+        .collect()
+}
+
+fn row_texts(row: &TableRow) -> Vec<String> {
+    row.cells.iter().map(cell_text).collect()
+}
+
+#[test]
+fn test_preview_uses_ole_size_range() {
+    let data: Vec<u8> = build_embedded_xlsx(
+        &[
+            ("A1", "Region"),
+            ("B1", "Sales"),
+            ("C1", "Hidden"),
+            ("A2", "North"),
+            ("B2", "120"),
+            ("A3", "Outside"),
+        ],
+        Some("A1:B2"),
+    );
+    let table: Table = parse_embedded_preview(&data).expect("preview table");
+
+    assert_eq!(table.column_widths.len(), 2);
+    assert_eq!(table.rows.len(), 2);
+    assert_eq!(row_texts(&table.rows[0]), vec!["Region", "Sales"]);
+    assert_eq!(row_texts(&table.rows[1]), vec!["North", "120"]);
+}
+
+#[test]
+fn test_preview_without_ole_size_uses_bounded_used_range() {
+    let data: Vec<u8> = build_embedded_xlsx(&[("A1", "top"), ("B80", "far below")], None);
+    let table: Table = parse_embedded_preview(&data).expect("preview table");
+
+    assert_eq!(table.column_widths.len(), 2);
+    assert_eq!(table.rows.len(), EMBEDDED_PREVIEW_MAX_ROWS as usize);
+    assert_eq!(cell_text(&table.rows[0].cells[0]), "top");
+}
+
+#[test]
+fn test_preview_of_empty_or_invalid_workbook_is_none() {
+    assert!(parse_embedded_preview(&build_embedded_xlsx(&[], None)).is_none());
+    assert!(parse_embedded_preview(b"not a workbook").is_none());
+}
+
+#[test]
+fn test_scale_preview_fits_frame() {
+    let data: Vec<u8> =
+        build_embedded_xlsx(&[("A1", "a"), ("B1", "b"), ("A2", "c")], Some("A1:B2"));
+    let mut table: Table = parse_embedded_preview(&data).unwrap();
+    let natural_width: f64 = table.column_widths.iter().sum();
+    let natural_height: f64 = table.rows.iter().map(|row| row.height.unwrap()).sum();
+
+    scale_embedded_preview(&mut table, natural_width / 2.0, natural_height);
+
+    let width: f64 = table.column_widths.iter().sum();
+    let height: f64 = table.rows.iter().map(|row| row.height.unwrap()).sum();
+    assert!((width - natural_width / 2.0).abs() < 1e-9);
+    assert!((height - natural_height).abs() < 1e-9);
+    // Text follows the tighter axis so it still fits the narrower columns.
+    let Block::Paragraph(paragraph) = &table.rows[0].cells[0].content[0] else {
+        panic!("expected a paragraph");
+    };
+    let font_size: f64 = paragraph.runs[0].style.font_size.unwrap();
+    assert!(
+        (font_size - DEFAULT_CELL_FONT_SIZE_PT / 2.0).abs() < 0.01,
+        "font size {font_size}"
+    );
+}
@@ -0,0 +1,1186 @@
+//! A small spreadsheet formula evaluator for cells that carry a formula but
+//! no cached result (workbooks written by tools that never calculate).
+//! Covers arithmetic, comparison, concatenation, cell and range references,
+//! and the functions people reach for most; anything else evaluates to an
+//! Excel error value rather than guessing.
+
+use super::xlsx_cells::{FormulaCache, parse_cell_ref};
+use super::xlsx_numfmt::format_general;
+
+/// Excel's last column (`XFD`) and row.
+const MAX_COL: u32 = 16_384;
+const MAX_ROW: u32 = 1_048_576;
+
+/// The result of evaluating a formula or reading a cell.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum FormulaValue {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    /// An Excel error literal such as `#DIV/0!`.
+    Error(&'static str),
+    Empty,
+}
+
+const DIV_ZERO: &str = "#DIV/0!";
+const VALUE_ERROR: &str = "#VALUE!";
+const REF_ERROR: &str = "#REF!";
+const NAME_ERROR: &str = "#NAME?";
+const NA_ERROR: &str = "#N/A";
+const NUM_ERROR: &str = "#NUM!";
+
+impl FormulaValue {
+    /// Display text for values that have no number format to go through.
+    pub(super) fn to_text(&self) -> String {
+        match self {
+            Self::Number(number) => format_general(*number),
+            Self::Text(text) => text.clone(),
+            Self::Bool(true) => "TRUE".to_string(),
+            Self::Bool(false) => "FALSE".to_string(),
+            Self::Error(error) => (*error).to_string(),
+            Self::Empty => String::new(),
+        }
+    }
+
+    fn to_number(&self) -> Result<f64, &'static str> {
+        match self {
+            Self::Number(number) => Ok(*number),
+            Self::Bool(flag) => Ok(f64::from(u8::from(*flag))),
+            Self::Empty => Ok(0.0),
+            Self::Text(text) => text.trim().parse().map_err(|_| VALUE_ERROR),
+            Self::Error(error) => Err(error),
+        }
+    }
+
+    fn to_bool(&self) -> Result<bool, &'static str> {
+        match self {
+            Self::Bool(flag) => Ok(*flag),
+            Self::Text(text) if text.eq_ignore_ascii_case("true") => Ok(true),
+            Self::Text(text) if text.eq_ignore_ascii_case("false") => Ok(false),
+            Self::Text(_) => Err(VALUE_ERROR),
+            other => other.to_number().map(|number| number != 0.0),
+        }
+    }
+}
+
+/// Where the evaluator reads referenced cells from.
+pub(super) trait CellSource {
+    /// Value of a cell (1-indexed). `sheet` is the name from a `Sheet!A1`
+    /// prefix, `None` for the formula's own sheet.
+    fn cell_value(&self, sheet: Option<&str>, col: u32, row: u32) -> FormulaValue;
+
+    /// Last used column and row, used to clip whole-column ranges.
+    fn used_bounds(&self) -> (u32, u32);
+}
+
+/// Evaluate `formula` (with or without its leading `=`).
+pub(super) fn evaluate_formula(formula: &str, source: &dyn CellSource) -> FormulaValue {
+    let formula: &str = formula.trim().strip_prefix('=').unwrap_or(formula.trim());
+    let Some(tokens) = tokenize(formula) else {
+        return FormulaValue::Error(NAME_ERROR);
+    };
+    let mut parser = FormulaParser { tokens, pos: 0 };
+    let Some(expr) = parser.parse_expression() else {
+        return FormulaValue::Error(NAME_ERROR);
+    };
+    if parser.pos != parser.tokens.len() {
+        return FormulaValue::Error(NAME_ERROR);
+    }
+    let evaluator = Evaluator { source };
+    evaluator.eval(&expr).into_scalar()
+}
+
+/// How deep formulas without cached values may reference each other. A
+/// reference back to a formula still being evaluated is caught as circular
+/// by the cache; this only bounds the recursion of a very long chain, whose
+/// far end is then treated like a circular reference, which Excel shows as 0.
+const MAX_FORMULA_DEPTH: usize = 256;
+
+/// Evaluate the formula of a cell that was saved without a cached result.
+/// Returns `None` for plain values and for formulas whose cached value is
+/// present, which stays authoritative. `formulas` holds the values already
+/// evaluated on the sheet.
+pub(super) fn evaluate_uncached_formula(
+    sheet: &umya_spreadsheet::Worksheet,
+    cell: &umya_spreadsheet::Cell,
+    formulas: &FormulaCache,
+) -> Option<FormulaValue> {
+    let formula: &str = cell.get_formula();
+    if formula.is_empty() || !cell.get_value().is_empty() {
+        return None;
+    }
+    let cells = SheetCells {
+        sheet,
+        formulas,
+        depth: 0,
+    };
+    let coordinate = cell.get_coordinate();
+    Some(cells.formula_value(
+        *coordinate.get_col_num(),
+        *coordinate.get_row_num(),
+        formula,
+    ))
+}
+
+/// Whether a conditional-format `expression` rule holds: the formula's
+/// value, as a boolean, with errors and text counting as false.
+pub(crate) fn evaluate_condition(
+    sheet: &umya_spreadsheet::Worksheet,
+    formula: &str,
+    formulas: &FormulaCache,
+) -> bool {
+    let cells = SheetCells {
+        sheet,
+        formulas,
+        depth: 0,
+    };
+    evaluate_formula(formula, &cells).to_bool().unwrap_or(false)
+}
+
+/// Cells of the worksheet a formula lives on. Other sheets are not
+/// reachable from the row builder, so cross-sheet references give `#REF!`.
+struct SheetCells<'a> {
+    sheet: &'a umya_spreadsheet::Worksheet,
+    formulas: &'a FormulaCache,
+    depth: usize,
+}
+
+impl SheetCells<'_> {
+    /// The value of the uncached formula in `(col, row)`, evaluated at most
+    /// once per sheet.
+    fn formula_value(&self, col: u32, row: u32, formula: &str) -> FormulaValue {
+        if let Some(slot) = self.formulas.values.borrow().get(&(col, row)) {
+            // Still being evaluated: a circular reference, shown as 0.
+            return slot.clone().unwrap_or(FormulaValue::Number(0.0));
+        }
+        if self.depth >= MAX_FORMULA_DEPTH {
+            return FormulaValue::Number(0.0);
+        }
+        self.formulas.values.borrow_mut().insert((col, row), None);
+        let nested = SheetCells {
+            sheet: self.sheet,
+            formulas: self.formulas,
+            depth: self.depth + 1,
+        };
+        let value: FormulaValue = evaluate_formula(formula, &nested);
+        self.formulas
+            .values
+            .borrow_mut()
+            .insert((col, row), Some(value.clone()));
+        value
+    }
+}
+
+impl CellSource for SheetCells<'_> {
+    fn cell_value(&self, sheet: Option<&str>, col: u32, row: u32) -> FormulaValue {
+        if sheet.is_some_and(|name| !name.eq_ignore_ascii_case(self.sheet.get_name())) {
+            return FormulaValue::Error(REF_ERROR);
+        }
+        let Some(cell) = self.sheet.get_cell((col, row)) else {
+            return FormulaValue::Empty;
+        };
+        let value = cell.get_value();
+        if !cell.get_formula().is_empty() && value.is_empty() {
+            return self.formula_value(col, row, cell.get_formula());
+        }
+        match cell.get_cell_value().get_raw_value() {
+            umya_spreadsheet::CellRawValue::Numeric(number) => FormulaValue::Number(*number),
+            umya_spreadsheet::CellRawValue::Bool(flag) => FormulaValue::Bool(*flag),
+            _ if value.is_empty() => FormulaValue::Empty,
+            _ => match ERROR_LITERALS.iter().find(|literal| **literal == value) {
+                Some(error) => FormulaValue::Error(error),
+                None => FormulaValue::Text(value.into_owned()),
+            },
+        }
+    }
+
+    fn used_bounds(&self) -> (u32, u32) {
+        self.sheet.get_highest_column_and_row()
+    }
+}
+
+// ── Tokens ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum FormulaToken {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    Error(&'static str),
+    Cell(u32, u32),
+    /// Whole column like the `A` in `A:A`.
+    Column(u32),
+    /// A `Sheet!` or `'My Sheet'!` prefix.
+    Sheet(String),
+    Function(String),
+    Name(String),
+    Op(&'static str),
+    OpenParen,
+    CloseParen,
+    Comma,
+    Colon,
+}
+
+const ERROR_LITERALS: [&str; 7] = [
+    DIV_ZERO,
+    VALUE_ERROR,
+    REF_ERROR,
+    NAME_ERROR,
+    NA_ERROR,
+    NUM_ERROR,
+    "#NULL!",
+];
+
+fn tokenize(formula: &str) -> Option<Vec<FormulaToken>> {
+    let chars: Vec<char> = formula.chars().collect();
+    let mut tokens: Vec<FormulaToken> = Vec::new();
+    let mut i: usize = 0;
+    while i < chars.len() {
+        let c: char = chars[i];
+        match c {
+            _ if c.is_whitespace() => i += 1,
+            '0'..='9' | '.' => {
+                let start: usize = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                if i < chars.len()
+                    && chars[i].eq_ignore_ascii_case(&'e')
+                    && chars
+                        .get(i + 1)
+                        .is_some_and(|next| next.is_ascii_digit() || *next == '+' || *next == '-')
+                {
+                    i += 2;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+                let literal: String = chars[start..i].iter().collect();
+                tokens.push(FormulaToken::Number(literal.parse().ok()?));
+            }
+            '"' => {
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        Some('"') if chars.get(i + 1) == Some(&'"') => {
+                            text.push('"');
+                            i += 2;
+                        }
+                        Some('"') => {
+                            i += 1;
+                            break;
+                        }
+                        Some(ch) => {
+                            text.push(*ch);
+                            i += 1;
+                        }
+                        None => return None,
+                    }
+                }
+                tokens.push(FormulaToken::Text(text));
+            }
+            '\'' => {
+                let end: usize = i + 1 + chars[i + 1..].iter().position(|ch| *ch == '\'')?;
+                if chars.get(end + 1) != Some(&'!') {
+                    return None;
+                }
+                tokens.push(FormulaToken::Sheet(chars[i + 1..end].iter().collect()));
+                i = end + 2;
+            }
+            '#' => {
+                let rest: String = chars[i..].iter().collect::<String>().to_ascii_uppercase();
+                let error: &'static str = ERROR_LITERALS
+                    .iter()
+                    .find(|literal| rest.starts_with(**literal))?;
+                tokens.push(FormulaToken::Error(error));
+                i += error.len();
+            }
+            _ if c.is_alphabetic() || c == '$' || c == '_' => {
+                let start: usize = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '$' | '_' | '.'))
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                let after_colon: bool = tokens.last() == Some(&FormulaToken::Colon);
+                tokens.push(classify_word(word, chars.get(i).copied(), after_colon));
+                if matches!(tokens.last(), Some(FormulaToken::Sheet(_))) {
+                    i += 1;
+                }
+            }
+            '<' | '>' => {
+                let op: &'static str = match (c, chars.get(i + 1)) {
+                    ('<', Some('=')) => "<=",
+                    ('<', Some('>')) => "<>",
+                    ('>', Some('=')) => ">=",
+                    ('<', _) => "<",
+                    _ => ">",
+                };
+                tokens.push(FormulaToken::Op(op));
+                i += op.len();
+            }
+            '+' | '-' | '*' | '/' | '^' | '&' | '=' | '%' => {
+                let op: &'static str = match c {
+                    '+' => "+",
+                    '-' => "-",
+                    '*' => "*",
+                    '/' => "/",
+                    '^' => "^",
+                    '&' => "&",
+                    '=' => "=",
+                    _ => "%",
+                };
+                tokens.push(FormulaToken::Op(op));
+                i += 1;
+            }
+            '(' => {
+                tokens.push(FormulaToken::OpenParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(FormulaToken::CloseParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(FormulaToken::Comma);
+                i += 1;
+            }
+            ':' => {
+                tokens.push(FormulaToken::Colon);
+                i += 1;
+            }
+            _ => return None,
+        }
+    }
+    Some(tokens)
+}
+
+/// Decide what a bare word is from its shape and its neighbours.
+fn classify_word(word: String, next: Option<char>, after_colon: bool) -> FormulaToken {
+    if next == Some('!') {
+        return FormulaToken::Sheet(word);
+    }
+    let upper: String = word.to_ascii_uppercase();
+    if next == Some('(') {
+        // Newer functions are stored with an `_xlfn.` prefix.
+        let name: &str = upper.strip_prefix("_XLFN.").unwrap_or(&upper);
+        return FormulaToken::Function(name.to_string());
+    }
+    match upper.as_str() {
+        "TRUE" => return FormulaToken::Bool(true),
+        "FALSE" => return FormulaToken::Bool(false),
+        _ => {}
+    }
+    if let Some((col, row)) = parse_cell_ref(&upper)
+        && (1..=MAX_COL).contains(&col)
+        && (1..=MAX_ROW).contains(&row)
+    {
+        return FormulaToken::Cell(col, row);
+    }
+    if (next == Some(':') || after_colon)
+        && let Some(col) = column_number(upper.trim_start_matches('$'))
+    {
+        return FormulaToken::Column(col);
+    }
+    FormulaToken::Name(word)
+}
+
+fn column_number(letters: &str) -> Option<u32> {
+    if letters.is_empty() || letters.len() > 3 || !letters.chars().all(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let col: u32 = letters
+        .chars()
+        .fold(0, |acc, c| acc * 26 + (c as u32 - 'A' as u32 + 1));
+    (col <= MAX_COL).then_some(col)
+}
+
+// ── Parsing ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+struct RangeRef {
+    sheet: Option<String>,
+    start_col: u32,
+    start_row: u32,
+    end_col: u32,
+    end_row: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Value(FormulaValue),
+    Cell(Option<String>, u32, u32),
+    Range(RangeRef),
+    Negate(Box<Expr>),
+    Percent(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+struct FormulaParser {
+    tokens: Vec<FormulaToken>,
+    pos: usize,
+}
+
+/// Binary operators from loosest to tightest binding.
+const PRECEDENCE: [&[&str]; 5] = [
+    &["=", "<>", "<", ">", "<=", ">="],
+    &["&"],
+    &["+", "-"],
+    &["*", "/"],
+    &["^"],
+];
+
+impl FormulaParser {
+    fn peek(&self) -> Option<&FormulaToken> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<FormulaToken> {
+        let token: Option<FormulaToken> = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_expression(&mut self) -> Option<Expr> {
+        self.parse_binary(0)
+    }
+
+    fn parse_binary(&mut self, level: usize) -> Option<Expr> {
+        if level == PRECEDENCE.len() {
+            return self.parse_unary();
+        }
+        let mut left: Expr = self.parse_binary(level + 1)?;
+        while let Some(FormulaToken::Op(op)) = self.peek()
+            && PRECEDENCE[level].contains(op)
+        {
+            let op: &'static str = op;
+            self.pos += 1;
+            let right: Expr = self.parse_binary(level + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Some(left)
+    }
+
+    /// Prefix signs bind tighter than `^` in Excel (`-2^2` is 4).
+    fn parse_unary(&mut self) -> Option<Expr> {
+        match self.peek() {
+            Some(FormulaToken::Op("-")) => {
+                self.pos += 1;
+                Some(Expr::Negate(Box::new(self.parse_unary()?)))
+            }
+            Some(FormulaToken::Op("+")) => {
+                self.pos += 1;
+                self.parse_unary()
+            }
+            _ => {
+                let mut expr: Expr = self.parse_primary()?;
+                while self.peek() == Some(&FormulaToken::Op("%")) {
+                    self.pos += 1;
+                    expr = Expr::Percent(Box::new(expr));
+                }
+                Some(expr)
+            }
+        }
+    }
+
+    fn parse_primary(&mut self) -> Option<Expr> {
+        if matches!(
+            self.peek(),
+            Some(FormulaToken::Cell(..) | FormulaToken::Column(_))
+        ) {
+            return self.parse_reference(None);
+        }
+        match self.next()? {
+            FormulaToken::Number(number) => Some(Expr::Value(FormulaValue::Number(number))),
+            FormulaToken::Text(text) => Some(Expr::Value(FormulaValue::Text(text))),
+            FormulaToken::Bool(flag) => Some(Expr::Value(FormulaValue::Bool(flag))),
+            FormulaToken::Error(error) => Some(Expr::Value(FormulaValue::Error(error))),
+            FormulaToken::Sheet(sheet) => self.parse_reference(Some(sheet)),
+            FormulaToken::Function(name) => {
+                self.expect(FormulaToken::OpenParen)?;
+                let mut args: Vec<Expr> = Vec::new();
+                if self.peek() == Some(&FormulaToken::CloseParen) {
+                    self.pos += 1;
+                    return Some(Expr::Call(name, args));
+                }
+                loop {
+                    // An omitted argument (`IF(A1,,1)`) reads as empty.
+                    if matches!(
+                        self.peek(),
+                        Some(FormulaToken::Comma | FormulaToken::CloseParen)
+                    ) {
+                        args.push(Expr::Value(FormulaValue::Empty));
+                    } else {
+                        args.push(self.parse_expression()?);
+                    }
+                    match self.next()? {
+                        FormulaToken::Comma => {}
+                        FormulaToken::CloseParen => break,
+                        _ => return None,
+                    }
+                }
+                Some(Expr::Call(name, args))
+            }
+            FormulaToken::OpenParen => {
+                let expr: Expr = self.parse_expression()?;
+                self.expect(FormulaToken::CloseParen)?;
+                Some(expr)
+            }
+            // Defined names are not resolved.
+            FormulaToken::Name(_) => Some(Expr::Value(FormulaValue::Error(NAME_ERROR))),
+            _ => None,
+        }
+    }
+
+    fn parse_reference(&mut self, sheet: Option<String>) -> Option<Expr> {
+        let start: FormulaToken = self.next()?;
+        if self.peek() != Some(&FormulaToken::Colon) {
+            let FormulaToken::Cell(col, row) = start else {
+                return None;
+            };
+            return Some(Expr::Cell(sheet, col, row));
+        }
+        self.pos += 1;
+        // The end may repeat the sheet prefix (`Sheet1!A1:Sheet1!B2`).
+        if matches!(self.peek(), Some(FormulaToken::Sheet(_))) {
+            self.pos += 1;
+        }
+        let end: FormulaToken = self.next()?;
+        let range: RangeRef = match (start, end) {
+            (FormulaToken::Cell(c1, r1), FormulaToken::Cell(c2, r2)) => RangeRef {
+                sheet,
+                start_col: c1.min(c2),
+                start_row: r1.min(r2),
+                end_col: c1.max(c2),
+                end_row: r1.max(r2),
+            },
+            (FormulaToken::Column(c1), FormulaToken::Column(c2)) => RangeRef {
+                sheet,
+                start_col: c1.min(c2),
+                start_row: 1,
+                end_col: c1.max(c2),
+                end_row: MAX_ROW,
+            },
+            _ => return None,
+        };
+        Some(Expr::Range(range))
+    }
+
+    fn expect(&mut self, token: FormulaToken) -> Option<()> {
+        (self.next()? == token).then_some(())
+    }
+}
+
+// ── Evaluation ──────────────────────────────────────────────────────────
+
+/// A grid of values read from a range, row by row.
+#[derive(Debug, Clone)]
+struct RangeValues {
+    rows: Vec<Vec<FormulaValue>>,
+}
+
+impl RangeValues {
+    fn top_left(&self) -> FormulaValue {
+        self.rows
+            .first()
+            .and_then(|row| row.first())
+            .cloned()
+            .unwrap_or(FormulaValue::Empty)
+    }
+
+    fn cells(&self) -> impl Iterator<Item = &FormulaValue> {
+        self.rows.iter().flatten()
+    }
+
+    fn get(&self, row: usize, col: usize) -> Option<&FormulaValue> {
+        self.rows.get(row).and_then(|cells| cells.get(col))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Arg {
+    Scalar(FormulaValue),
+    Range(RangeValues),
+}
+
+impl Arg {
+    fn into_scalar(self) -> FormulaValue {
+        match self {
+            Self::Scalar(value) => value,
+            Self::Range(range) => range.top_left(),
+        }
+    }
+
+    /// All values of the argument, flattened.
+    fn values(&self) -> Vec<&FormulaValue> {
+        match self {
+            Self::Scalar(value) => vec![value],
+            Self::Range(range) => range.cells().collect(),
+        }
+    }
+
+    fn into_range(self) -> RangeValues {
+        match self {
+            Self::Scalar(value) => RangeValues {
+                rows: vec![vec![value]],
+            },
+            Self::Range(range) => range,
+        }
+    }
+}
+
+struct Evaluator<'a> {
+    source: &'a dyn CellSource,
+}
+
+/// Early-return the error inside a `Result` as the formula's value.
+macro_rules! try_value {
+    ($expr:expr) => {
+        match $expr {
+            Ok(value) => value,
+            Err(error) => return FormulaValue::Error(error),
+        }
+    };
+}
+
+impl Evaluator<'_> {
+    fn eval(&self, expr: &Expr) -> Arg {
+        match expr {
+            Expr::Value(value) => Arg::Scalar(value.clone()),
+            Expr::Cell(sheet, col, row) => {
+                Arg::Scalar(self.source.cell_value(sheet.as_deref(), *col, *row))
+            }
+            Expr::Range(range) => Arg::Range(self.read_range(range)),
+            Expr::Negate(inner) => Arg::Scalar(self.arithmetic(inner, |value| -value)),
+            Expr::Percent(inner) => Arg::Scalar(self.arithmetic(inner, |value| value / 100.0)),
+            Expr::Binary(op, left, right) => Arg::Scalar(self.binary(op, left, right)),
+            Expr::Call(name, args) => Arg::Scalar(self.call(name, args)),
+        }
+    }
+
+    fn scalar(&self, expr: &Expr) -> FormulaValue {
+        self.eval(expr).into_scalar()
+    }
+
+    fn number(&self, expr: &Expr) -> Result<f64, &'static str> {
+        self.scalar(expr).to_number()
+    }
+
+    fn read_range(&self, range: &RangeRef) -> RangeValues {
+        let (max_col, max_row) = self.source.used_bounds();
+        let end_col: u32 = range.end_col.min(max_col.max(range.start_col));
+        let end_row: u32 = range.end_row.min(max_row.max(range.start_row));
+        let rows: Vec<Vec<FormulaValue>> = (range.start_row..=end_row)
+            .map(|row| {
+                (range.start_col..=end_col)
+                    .map(|col| self.source.cell_value(range.sheet.as_deref(), col, row))
+                    .collect()
+            })
+            .collect();
+        RangeValues { rows }
+    }
+
+    fn arithmetic(&self, inner: &Expr, op: impl Fn(f64) -> f64) -> FormulaValue {
+        FormulaValue::Number(op(try_value!(self.number(inner))))
+    }
+
+    fn binary(&self, op: &str, left: &Expr, right: &Expr) -> FormulaValue {
+        let left: FormulaValue = self.scalar(left);
+        let right: FormulaValue = self.scalar(right);
+        for value in [&left, &right] {
+            if let FormulaValue::Error(error) = value {
+                return FormulaValue::Error(error);
+            }
+        }
+        if op == "&" {
+            return FormulaValue::Text(left.to_text() + &right.to_text());
+        }
+        if let Some(ordering) = comparison_result(op) {
+            return FormulaValue::Bool(ordering(compare_values(&left, &right)));
+        }
+        let a: f64 = try_value!(left.to_number());
+        let b: f64 = try_value!(right.to_number());
+        let result: f64 = match op {
+            "+" => a + b,
+            "-" => a - b,
+            "*" => a * b,
+            "/" if b == 0.0 => return FormulaValue::Error(DIV_ZERO),
+            "/" => a / b,
+            _ => a.powf(b),
+        };
+        finite(result)
+    }
+
+    fn call(&self, name: &str, args: &[Expr]) -> FormulaValue {
+        match name {
+            "IF" => {
+                let condition: bool = try_value!(self.arg_scalar(args, 0).to_bool());
+                let branch: usize = if condition { 1 } else { 2 };
+                match args.get(branch) {
+                    Some(expr) => self.scalar(expr),
+                    None => FormulaValue::Bool(false),
+                }
+            }
+            "IFERROR" => match self.arg_scalar(args, 0) {
+                FormulaValue::Error(_) => self.arg_scalar(args, 1),
+                value => value,
+            },
+            "AND" | "OR" => {
+                let mut flags: Vec<bool> = Vec::new();
+                for arg in args {
+                    for value in self.eval(arg).values() {
+                        match value {
+                            FormulaValue::Error(error) => return FormulaValue::Error(error),
+                            FormulaValue::Empty | FormulaValue::Text(_) => {}
+                            other => flags.push(try_value!(other.to_bool())),
+                        }
+                    }
+                }
+                if flags.is_empty() {
+                    return FormulaValue::Error(VALUE_ERROR);
+                }
+                FormulaValue::Bool(if name == "AND" {
+                    flags.iter().all(|flag| *flag)
+                } else {
+                    flags.iter().any(|flag| *flag)
+                })
+            }
+            "NOT" => FormulaValue::Bool(!try_value!(self.arg_scalar(args, 0).to_bool())),
+            "SUM" => self.aggregate(args, |numbers| numbers.iter().sum()),
+            "PRODUCT" => self.aggregate(args, |numbers| numbers.iter().product()),
+            "MIN" => self.aggregate(args, |numbers| {
+                numbers.iter().copied().reduce(f64::min).unwrap_or(0.0)
+            }),
+            "MAX" => self.aggregate(args, |numbers| {
+                numbers.iter().copied().reduce(f64::max).unwrap_or(0.0)
+            }),
+            "AVERAGE" => {
+                let numbers: Vec<f64> = try_value!(self.collect_numbers(args));
+                if numbers.is_empty() {
+                    return FormulaValue::Error(DIV_ZERO);
+                }
+                FormulaValue::Number(numbers.iter().sum::<f64>() / numbers.len() as f64)
+            }
+            "COUNT" => FormulaValue::Number(
+                self.count(args, |value| matches!(value, FormulaValue::Number(_))),
+            ),
+            "COUNTA" => FormulaValue::Number(
+                self.count(args, |value| !matches!(value, FormulaValue::Empty)),
+            ),
+            "ABS" => self.unary_math(args, f64::abs),
+            "INT" => self.unary_math(args, f64::floor),
+            "SQRT" => {
+                let value: f64 = try_value!(self.arg_number(args, 0));
+                if value < 0.0 {
+                    return FormulaValue::Error(NUM_ERROR);
+                }
+                FormulaValue::Number(value.sqrt())
+            }
+            "ROUND" | "ROUNDUP" | "ROUNDDOWN" => {
+                let value: f64 = try_value!(self.arg_number(args, 0));
+                let digits: f64 = try_value!(self.arg_number(args, 1)).trunc();
+                let factor: f64 = 10f64.powf(digits);
+                // Scale through the shortest decimal form so 2.675 rounds
+                // like the 2.675 Excel shows, not its binary neighbour.
+                let scaled: f64 = format!("{}", value * factor)
+                    .parse()
+                    .unwrap_or(value * factor);
+                let rounded: f64 = match name {
+                    "ROUND" => scaled.round(),
+                    "ROUNDUP" => scaled.abs().ceil().copysign(scaled),
+                    _ => scaled.trunc(),
+                };
+                finite(rounded / factor)
+            }
+            "MOD" => {
+                let value: f64 = try_value!(self.arg_number(args, 0));
+                let divisor: f64 = try_value!(self.arg_number(args, 1));
+                if divisor == 0.0 {
+                    return FormulaValue::Error(DIV_ZERO);
+                }
+                // Excel's MOD takes the divisor's sign.
+                FormulaValue::Number(value - divisor * (value / divisor).floor())
+            }
+            "POWER" => {
+                let base: f64 = try_value!(self.arg_number(args, 0));
+                let exponent: f64 = try_value!(self.arg_number(args, 1));
+                finite(base.powf(exponent))
+            }
+            "CONCATENATE" | "CONCAT" => {
+                let mut text = String::new();
+                for arg in args {
+                    for value in self.eval(arg).values() {
+                        if let FormulaValue::Error(error) = value {
+                            return FormulaValue::Error(error);
+                        }
+                        text.push_str(&value.to_text());
+                    }
+                }
+                FormulaValue::Text(text)
+            }
+            "LEN" => {
+                let text: String = try_value!(self.arg_text(args, 0));
+                FormulaValue::Number(text.chars().count() as f64)
+            }
+            "UPPER" => FormulaValue::Text(try_value!(self.arg_text(args, 0)).to_uppercase()),
+            "LOWER" => FormulaValue::Text(try_value!(self.arg_text(args, 0)).to_lowercase()),
+            "TRIM" => {
+                let text: String = try_value!(self.arg_text(args, 0));
+                FormulaValue::Text(text.split_whitespace().collect::<Vec<&str>>().join(" "))
+            }
+            "LEFT" | "RIGHT" => {
+                let text: String = try_value!(self.arg_text(args, 0));
+                let count: usize = match args.get(1) {
+                    Some(_) => try_value!(self.arg_count(args, 1)),
+                    None => 1,
+                };
+                let chars: Vec<char> = text.chars().collect();
+                let count: usize = count.min(chars.len());
+                let slice: &[char] = if name == "LEFT" {
+                    &chars[..count]
+                } else {
+                    &chars[chars.len() - count..]
+                };
+                FormulaValue::Text(slice.iter().collect())
+            }
+            "MID" => {
+                let text: String = try_value!(self.arg_text(args, 0));
+                let start: usize = try_value!(self.arg_count(args, 1));
+                let count: usize = try_value!(self.arg_count(args, 2));
+                if start == 0 {
+                    return FormulaValue::Error(VALUE_ERROR);
+                }
+                FormulaValue::Text(text.chars().skip(start - 1).take(count).collect())
+            }
+            "VLOOKUP" | "HLOOKUP" => self.lookup(name == "VLOOKUP", args),
+            "INDEX" => {
+                let Some(range) = args.first().map(|arg| self.eval(arg).into_range()) else {
+                    return FormulaValue::Error(VALUE_ERROR);
+                };
+                let row: usize = try_value!(self.arg_count(args, 1));
+                let col: usize = match args.get(2) {
+                    Some(_) => try_value!(self.arg_count(args, 2)),
+                    None => 1,
+                };
+                // A single row indexed by one number walks its columns.
+                let (row, col): (usize, usize) = if range.rows.len() == 1 && args.len() == 2 {
+                    (1, row)
+                } else {
+                    (row, col)
+                };
+                range
+                    .get(row.saturating_sub(1), col.saturating_sub(1))
+                    .cloned()
+                    .unwrap_or(FormulaValue::Error(REF_ERROR))
+            }
+            "MATCH" => {
+                let needle: FormulaValue = self.arg_scalar(args, 0);
+                let Some(range) = args.get(1).map(|arg| self.eval(arg).into_range()) else {
+                    return FormulaValue::Error(VALUE_ERROR);
+                };
+                let match_type: f64 = match args.get(2) {
+                    Some(_) => try_value!(self.arg_number(args, 2)),
+                    None => 1.0,
+                };
+                let haystack: Vec<&FormulaValue> = range.cells().collect();
+                match find_position(&needle, &haystack, match_type) {
+                    Some(index) => FormulaValue::Number((index + 1) as f64),
+                    None => FormulaValue::Error(NA_ERROR),
+                }
+            }
+            "SUMIF" | "COUNTIF" | "AVERAGEIF" => self.conditional_aggregate(name, args),
+            _ => FormulaValue::Error(NAME_ERROR),
+        }
+    }
+
+    fn arg_scalar(&self, args: &[Expr], index: usize) -> FormulaValue {
+        args.get(index)
+            .map(|expr| self.scalar(expr))
+            .unwrap_or(FormulaValue::Empty)
+    }
+
+    fn arg_number(&self, args: &[Expr], index: usize) -> Result<f64, &'static str> {
+        match args.get(index) {
+            Some(expr) => self.number(expr),
+            None => Err(VALUE_ERROR),
+        }
+    }
+
+    fn arg_count(&self, args: &[Expr], index: usize) -> Result<usize, &'static str> {
+        let value: f64 = self.arg_number(args, index)?;
+        if value < 0.0 {
+            return Err(VALUE_ERROR);
+        }
+        Ok(value.trunc() as usize)
+    }
+
+    fn arg_text(&self, args: &[Expr], index: usize) -> Result<String, &'static str> {
+        match self.arg_scalar(args, index) {
+            FormulaValue::Error(error) => Err(error),
+            value => Ok(value.to_text()),
+        }
+    }
+
+    fn unary_math(&self, args: &[Expr], op: fn(f64) -> f64) -> FormulaValue {
+        finite(op(try_value!(self.arg_number(args, 0))))
+    }
+
+    /// Numbers from all arguments: ranges contribute only their numeric
+    /// cells, direct arguments are coerced (like Excel's SUM).
+    fn collect_numbers(&self, args: &[Expr]) -> Result<Vec<f64>, &'static str> {
+        let mut numbers: Vec<f64> = Vec::new();
+        for arg in args {
+            match self.eval(arg) {
+                Arg::Scalar(FormulaValue::Empty) => {}
+                Arg::Scalar(value) => numbers.push(value.to_number()?),
+                Arg::Range(range) => {
+                    for value in range.cells() {
+                        match value {
+                            FormulaValue::Number(number) => numbers.push(*number),
+                            FormulaValue::Error(error) => return Err(error),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+        Ok(numbers)
+    }
+
+    fn aggregate(&self, args: &[Expr], reduce: impl Fn(&[f64]) -> f64) -> FormulaValue {
+        let numbers: Vec<f64> = try_value!(self.collect_numbers(args));
+        finite(reduce(&numbers))
+    }
+
+    fn count(&self, args: &[Expr], counts: impl Fn(&FormulaValue) -> bool) -> f64 {
+        args.iter()
+            .map(|arg| {
+                self.eval(arg)
+                    .values()
+                    .into_iter()
+                    .filter(|value| counts(value))
+                    .count()
+            })
+            .sum::<usize>() as f64
+    }
+
+    /// `VLOOKUP(value, table, index, [approximate])` and its row-wise twin.
+    fn lookup(&self, vertical: bool, args: &[Expr]) -> FormulaValue {
+        let needle: FormulaValue = self.arg_scalar(args, 0);
+        if let FormulaValue::Error(error) = needle {
+            return FormulaValue::Error(error);
+        }
+        let Some(table) = args.get(1).map(|arg| self.eval(arg).into_range()) else {
+            return FormulaValue::Error(VALUE_ERROR);
+        };
+        let index: usize = try_value!(self.arg_count(args, 2));
+        let approximate: bool = match args.get(3) {
+            Some(Expr::Value(FormulaValue::Empty)) | None => true,
+            Some(_) => try_value!(self.arg_scalar(args, 3).to_bool()),
+        };
+        if index == 0 {
+            return FormulaValue::Error(VALUE_ERROR);
+        }
+        let keys: Vec<&FormulaValue> = if vertical {
+            table.rows.iter().filter_map(|row| row.first()).collect()
+        } else {
+            table
+                .rows
+                .first()
+                .map(|row| row.iter().collect())
+                .unwrap_or_default()
+        };
+        let match_type: f64 = if approximate { 1.0 } else { 0.0 };
+        let Some(position) = find_position(&needle, &keys, match_type) else {
+            return FormulaValue::Error(NA_ERROR);
+        };
+        let found: Option<&FormulaValue> = if vertical {
+            table.get(position, index - 1)
+        } else {
+            table.get(index - 1, position)
+        };
+        found.cloned().unwrap_or(FormulaValue::Error(REF_ERROR))
+    }
+
+    fn conditional_aggregate(&self, name: &str, args: &[Expr]) -> FormulaValue {
+        let Some(range) = args.first().map(|arg| self.eval(arg).into_range()) else {
+            return FormulaValue::Error(VALUE_ERROR);
+        };
+        let criteria: Criteria = Criteria::parse(&self.arg_scalar(args, 1));
+        let values: RangeValues = match args.get(2) {
+            Some(arg) => self.eval(arg).into_range(),
+            None => range.clone(),
+        };
+        let mut count: usize = 0;
+        let mut sum: f64 = 0.0;
+        for (row_index, row) in range.rows.iter().enumerate() {
+            for (col_index, value) in row.iter().enumerate() {
+                if !criteria.matches(value) {
+                    continue;
+                }
+                count += 1;
+                if let Some(FormulaValue::Number(number)) = values.get(row_index, col_index) {
+                    sum += number;
+                }
+            }
+        }
+        match name {
+            "COUNTIF" => FormulaValue::Number(count as f64),
+            "SUMIF" => finite(sum),
+            _ if count == 0 => FormulaValue::Error(DIV_ZERO),
+            _ => {
+                let numeric: usize = range
+                    .rows
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(r, row)| row.iter().enumerate().map(move |(c, v)| (r, c, v)))
+                    .filter(|(r, c, value)| {
+                        criteria.matches(value)
+                            && matches!(values.get(*r, *c), Some(FormulaValue::Number(_)))
+                    })
+                    .count();
+                if numeric == 0 {
+                    FormulaValue::Error(DIV_ZERO)
+                } else {
+                    finite(sum / numeric as f64)
+                }
+            }
+        }
+    }
+}
+
+fn finite(value: f64) -> FormulaValue {
+    if value.is_finite() {
+        FormulaValue::Number(value)
+    } else {
+        FormulaValue::Error(NUM_ERROR)
+    }
+}
+
+fn comparison_result(op: &str) -> Option<fn(std::cmp::Ordering) -> bool> {
+    let test: fn(std::cmp::Ordering) -> bool = match op {
+        "=" => |ordering| ordering.is_eq(),
+        "<>" => |ordering| ordering.is_ne(),
+        "<" => |ordering| ordering.is_lt(),
+        ">" => |ordering| ordering.is_gt(),
+        "<=" => |ordering| ordering.is_le(),
+        ">=" => |ordering| ordering.is_ge(),
+        _ => return None,
+    };
+    Some(test)
+}
+
+/// Excel's comparison order: numbers < text < booleans, text compared
+/// case-insensitively, and an empty cell equal to both `0` and `""`.
+fn compare_values(left: &FormulaValue, right: &FormulaValue) -> std::cmp::Ordering {
+    use FormulaValue::{Bool, Empty, Number, Text};
+    fn rank(value: &FormulaValue) -> u8 {
+        match value {
+            Number(_) | Empty => 0,
+            Text(_) => 1,
+            _ => 2,
+        }
+    }
+    match (left, right) {
+        (Text(a), Text(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+        (Text(a), Empty) => a.as_str().cmp(""),
+        (Empty, Text(b)) => "".cmp(b.as_str()),
+        (Bool(a), Bool(b)) => a.cmp(b),
+        _ if rank(left) != rank(right) => rank(left).cmp(&rank(right)),
+        _ => {
+            let a: f64 = left.to_number().unwrap_or(0.0);
+            let b: f64 = right.to_number().unwrap_or(0.0);
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        }
+    }
+}
+
+/// `MATCH` semantics: `0` finds an exact match, `1` the largest value not
+/// above the needle in ascending data, `-1` the smallest value not below it
+/// in descending data.
+fn find_position(
+    needle: &FormulaValue,
+    haystack: &[&FormulaValue],
+    match_type: f64,
+) -> Option<usize> {
+    use std::cmp::Ordering;
+    if match_type == 0.0 {
+        return haystack
+            .iter()
+            .position(|value| compare_values(value, needle) == Ordering::Equal);
+    }
+    let mut best: Option<usize> = None;
+    for (index, value) in haystack.iter().enumerate() {
+        if matches!(value, FormulaValue::Empty) {
+            continue;
+        }
+        let ordering: Ordering = compare_values(value, needle);
+        let fits: bool = if match_type > 0.0 {
+            ordering != Ordering::Greater
+        } else {
+            ordering != Ordering::Less
+        };
+        if fits {
+            best = Some(index);
+        } else {
+            break;
+        }
+    }
+    best
+}
+
+/// A `SUMIF`/`COUNTIF` criterion such as `">5"`, `"<>done"`, or `42`.
+struct Criteria {
+    op: &'static str,
+    operand: FormulaValue,
+}
+
+impl Criteria {
+    fn parse(value: &FormulaValue) -> Self {
+        let FormulaValue::Text(text) = value else {
+            return Self {
+                op: "=",
+                operand: value.clone(),
+            };
+        };
+        let (op, rest): (&'static str, &str) = ["<=", ">=", "<>", "<", ">", "="]
+            .iter()
+            .find_map(|op| text.strip_prefix(op).map(|rest| (*op, rest)))
+            .unwrap_or(("=", text.as_str()));
+        let operand: FormulaValue = match rest.trim().parse::<f64>() {
+            Ok(number) => FormulaValue::Number(number),
+            Err(_) if rest.is_empty() => FormulaValue::Empty,
+            Err(_) => FormulaValue::Text(rest.to_string()),
+        };
+        Self { op, operand }
+    }
+
+    fn matches(&self, value: &FormulaValue) -> bool {
+        let Some(test) = comparison_result(self.op) else {
+            return false;
+        };
+        let is_blank = |value: &FormulaValue| match value {
+            FormulaValue::Empty => true,
+            FormulaValue::Text(text) => text.is_empty(),
+            _ => false,
+        };
+        match (&self.operand, value) {
+            (FormulaValue::Empty, _) if self.op == "=" => is_blank(value),
+            (FormulaValue::Empty, _) => !is_blank(value),
+            (FormulaValue::Number(_), FormulaValue::Number(_))
+            | (FormulaValue::Text(_), FormulaValue::Text(_)) => {
+                test(compare_values(value, &self.operand))
+            }
+            // A number never satisfies a text criterion and vice versa,
+            // except that they are always "not equal".
+            _ => self.op == "<>",
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "xlsx_formula_tests.rs"]
+mod tests;
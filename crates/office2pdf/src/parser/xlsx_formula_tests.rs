@@ -0,0 +1,165 @@
+use super::*;
+use std::collections::HashMap;
+
+/// A sheet held in a map, keyed by `(col, row)`.
+struct MapSource(HashMap<(u32, u32), FormulaValue>);
+
+impl CellSource for MapSource {
+    fn cell_value(&self, sheet: Option<&str>, col: u32, row: u32) -> FormulaValue {
+        if sheet.is_some_and(|name| name != "Data") {
+            return FormulaValue::Error(REF_ERROR);
+        }
+        self.0
+            .get(&(col, row))
+            .cloned()
+            .unwrap_or(FormulaValue::Empty)
+    }
+
+    fn used_bounds(&self) -> (u32, u32) {
+        self.0
+            .keys()
+            .fold((0, 0), |(max_col, max_row), (col, row)| {
+                (max_col.max(*col), max_row.max(*row))
+            })
+    }
+}
+
+/// Columns A–C, rows 1–4: fruit names, quantities, and prices.
+fn source() -> MapSource {
+    let mut cells: HashMap<(u32, u32), FormulaValue> = HashMap::new();
+    let rows: [(&str, f64, f64); 4] = [
+        ("apple", 3.0, 0.5),
+        ("banana", 12.0, 0.25),
+        ("cherry", 7.0, 4.0),
+        ("date", 0.0, 2.5),
+    ];
+    for (index, (name, quantity, price)) in rows.into_iter().enumerate() {
+        let row: u32 = index as u32 + 1;
+        cells.insert((1, row), FormulaValue::Text(name.to_string()));
+        cells.insert((2, row), FormulaValue::Number(quantity));
+        cells.insert((3, row), FormulaValue::Number(price));
+    }
+    MapSource(cells)
+}
+
+fn eval(formula: &str) -> FormulaValue {
+    evaluate_formula(formula, &source())
+}
+
+fn number(value: f64) -> FormulaValue {
+    FormulaValue::Number(value)
+}
+
+fn text(value: &str) -> FormulaValue {
+    FormulaValue::Text(value.to_string())
+}
+
+#[test]
+fn test_arithmetic_precedence_and_unary() {
+    assert_eq!(eval("=1+2*3"), number(7.0));
+    assert_eq!(eval("(1+2)*3"), number(9.0));
+    assert_eq!(eval("=-2^2"), number(4.0));
+    assert_eq!(eval("=2^3^2"), number(64.0));
+    assert_eq!(eval("=50%"), number(0.5));
+    assert_eq!(eval("=1.5E+2/3"), number(50.0));
+    assert_eq!(eval("=1/0"), FormulaValue::Error(DIV_ZERO));
+}
+
+#[test]
+fn test_references_and_ranges() {
+    assert_eq!(eval("=B2*C2"), number(3.0));
+    assert_eq!(eval("=$B$3+Data!B1"), number(10.0));
+    assert_eq!(eval("='Other Sheet'!A1"), FormulaValue::Error(REF_ERROR));
+    assert_eq!(eval("=SUM(B1:B4)"), number(22.0));
+    assert_eq!(eval("=SUM(B:B)"), number(22.0));
+    assert_eq!(eval("=D9+1"), number(1.0));
+}
+
+#[test]
+fn test_comparison_and_concatenation() {
+    assert_eq!(eval("=A1=\"APPLE\""), FormulaValue::Bool(true));
+    assert_eq!(eval("=B2>=B3"), FormulaValue::Bool(true));
+    assert_eq!(eval("=1<\"a\""), FormulaValue::Bool(true));
+    assert_eq!(eval("=A1&\" x\"&B1"), text("apple x3"));
+    assert_eq!(eval("=\"say \"\"hi\"\"\""), text("say \"hi\""));
+    assert_eq!(eval("=\"a\"+1"), FormulaValue::Error(VALUE_ERROR));
+}
+
+#[test]
+fn test_aggregates() {
+    assert_eq!(eval("=AVERAGE(B1:B4)"), number(5.5));
+    assert_eq!(eval("=MIN(C1:C4)"), number(0.25));
+    assert_eq!(eval("=MAX(B1:C4,100)"), number(100.0));
+    assert_eq!(eval("=COUNT(A1:C4)"), number(8.0));
+    assert_eq!(eval("=COUNTA(A1:C5)"), number(12.0));
+    assert_eq!(eval("=PRODUCT(B1,C3)"), number(12.0));
+    assert_eq!(eval("=AVERAGE(D1:D2)"), FormulaValue::Error(DIV_ZERO));
+}
+
+#[test]
+fn test_logic() {
+    assert_eq!(eval("=IF(B1>5,\"many\",\"few\")"), text("few"));
+    assert_eq!(eval("=IF(B2>5,\"many\")"), text("many"));
+    assert_eq!(eval("=IF(B4,1)"), FormulaValue::Bool(false));
+    assert_eq!(eval("=IFERROR(1/B4,\"n/a\")"), text("n/a"));
+    assert_eq!(eval("=AND(B1>0,B2>0)"), FormulaValue::Bool(true));
+    assert_eq!(eval("=OR(B4>0,FALSE)"), FormulaValue::Bool(false));
+    assert_eq!(eval("=NOT(TRUE)"), FormulaValue::Bool(false));
+}
+
+#[test]
+fn test_math_functions() {
+    assert_eq!(eval("=ROUND(2.675,2)"), number(2.68));
+    assert_eq!(eval("=ROUND(-2.5,0)"), number(-3.0));
+    assert_eq!(eval("=ROUNDUP(1.21,1)"), number(1.3));
+    assert_eq!(eval("=ROUNDDOWN(-1.29,1)"), number(-1.2));
+    assert_eq!(eval("=INT(-1.5)"), number(-2.0));
+    assert_eq!(eval("=MOD(-3,2)"), number(1.0));
+    assert_eq!(eval("=ABS(-4)+SQRT(9)+POWER(2,3)"), number(15.0));
+    assert_eq!(eval("=SQRT(-1)"), FormulaValue::Error(NUM_ERROR));
+}
+
+#[test]
+fn test_text_functions() {
+    assert_eq!(eval("=CONCATENATE(A1,\"-\",B1)"), text("apple-3"));
+    assert_eq!(eval("=_xlfn.CONCAT(A1:A2)"), text("applebanana"));
+    assert_eq!(eval("=LEN(A2)"), number(6.0));
+    assert_eq!(eval("=UPPER(LEFT(A3,3))&RIGHT(A4)"), text("CHEe"));
+    assert_eq!(eval("=MID(A2,2,3)"), text("ana"));
+    assert_eq!(eval("=TRIM(\"  a   b \")"), text("a b"));
+}
+
+#[test]
+fn test_lookups() {
+    assert_eq!(eval("=VLOOKUP(\"cherry\",A1:C4,3,FALSE)"), number(4.0));
+    assert_eq!(
+        eval("=VLOOKUP(\"fig\",A1:C4,2,FALSE)"),
+        FormulaValue::Error(NA_ERROR)
+    );
+    // Approximate match takes the last key not above the needle.
+    assert_eq!(eval("=VLOOKUP(\"coconut\",A1:C4,2)"), number(7.0));
+    assert_eq!(eval("=HLOOKUP(3,B1:C4,2,FALSE)"), number(12.0));
+    assert_eq!(eval("=INDEX(A1:C4,2,3)"), number(0.25));
+    assert_eq!(eval("=MATCH(\"date\",A1:A4,0)"), number(4.0));
+    assert_eq!(
+        eval("=INDEX(C1:C4,MATCH(\"banana\",A1:A4,0))"),
+        number(0.25)
+    );
+}
+
+#[test]
+fn test_conditional_aggregates() {
+    assert_eq!(eval("=SUMIF(B1:B4,\">5\")"), number(19.0));
+    assert_eq!(eval("=SUMIF(A1:A4,\"banana\",C1:C4)"), number(0.25));
+    assert_eq!(eval("=COUNTIF(B1:B4,0)"), number(1.0));
+    assert_eq!(eval("=COUNTIF(A1:A4,\"<>apple\")"), number(3.0));
+    assert_eq!(eval("=AVERAGEIF(B1:B4,\">0\")"), number(22.0 / 3.0));
+}
+
+#[test]
+fn test_unsupported_input_is_an_error_value() {
+    assert_eq!(eval("=NOSUCHFN(1)"), FormulaValue::Error(NAME_ERROR));
+    assert_eq!(eval("=MyRange*2"), FormulaValue::Error(NAME_ERROR));
+    assert_eq!(eval("=1+"), FormulaValue::Error(NAME_ERROR));
+    assert_eq!(eval("=#N/A"), FormulaValue::Error(NA_ERROR));
+}
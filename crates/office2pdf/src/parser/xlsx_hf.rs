@@ -1,119 +1,205 @@
+use std::collections::HashMap;
+
+use quick_xml::events::Event;
+
+use super::xlsx_drawing::{
+    decode_media, parse_rels_targets, parse_workbook_sheet_rids, read_zip_entry_bytes,
+    read_zip_entry_string, resolve_relative_xl_path,
+};
+use super::xlsx_numfmt::FormatSettings;
 use crate::ir::{
-    Alignment, HFInline, HeaderFooter, HeaderFooterParagraph, ParagraphStyle, Run, TextStyle,
+    Alignment, DateTimeField, HFInline, HeaderFooter, HeaderFooterParagraph, ImageData,
+    ParagraphStyle, Run, TextStyle, VerticalTextAlign,
 };
+use crate::parser::xml_util::{get_attr_str, parse_hex_color};
+
+/// Format of `&T`, which Excel shows the same in every locale.
+const TIME_FORMAT: &str = "h:mm AM/PM";
+
+/// Which half of a worksheet's `headerFooter` a format string belongs to.
+/// Picks the `&G` picture: VML shape ids end in `H` for headers, `F` for footers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum HfPart {
+    Header,
+    Footer,
+}
+
+/// Values the field codes of one sheet's header/footer expand to.
+#[derive(Debug, Clone, Default)]
+pub(super) struct HfContext {
+    /// `&A`: the worksheet tab name.
+    pub(super) sheet_name: String,
+    /// Format of `&D`: the short date of the printing locale.
+    pub(super) date_format: String,
+    /// `&G` pictures keyed by VML shape id (`LH`, `CH`, `RH`, `LF`, `CF`, `RF`).
+    pub(super) images: HashMap<String, ImageData>,
+}
+
+impl HfContext {
+    /// Context for a sheet. The date is shown as the short date of
+    /// `ConvertOptions::locale`, since the workbook does not record the
+    /// printing locale; without one, in Excel's en-US default.
+    pub(super) fn for_sheet(
+        sheet_name: &str,
+        images: HashMap<String, ImageData>,
+        number_format: &FormatSettings,
+    ) -> Self {
+        Self {
+            sheet_name: sheet_name.to_string(),
+            date_format: number_format
+                .locale
+                .map_or("m/d/yyyy", |locale| locale.short_date)
+                .to_string(),
+            images,
+        }
+    }
+}
 
 /// Parse an Excel header/footer format string into IR HeaderFooter.
 ///
-/// Excel format strings use `&L`, `&C`, `&R` to define left/center/right sections,
-/// `&P` for current page number, and `&N` for total page count.
-/// Returns `None` if the format string is empty.
-pub(super) fn parse_hf_format_string(format_str: &str) -> Option<HeaderFooter> {
+/// Excel format strings use `&L`, `&C`, `&R` to define left/center/right
+/// sections, `&P`/`&N` for the page number and page count, `&D`/`&T`/`&A`
+/// for date, time and sheet name, `&G` for a picture, and font codes
+/// (`&"Arial,Bold"`, `&12`, `&KFF0000`, `&B`, `&I`, `&U`, ...) that style the
+/// text after them. Returns `None` if the format string is empty.
+pub(super) fn parse_hf_format_string(
+    format_str: &str,
+    context: &HfContext,
+    part: HfPart,
+) -> Option<HeaderFooter> {
     let s = format_str.trim();
     if s.is_empty() {
         return None;
     }
 
-    // Split into left/center/right sections
-    let mut left = String::new();
-    let mut center = String::new();
-    let mut right = String::new();
-    let mut current = &mut center; // Default section is center if no &L/&C/&R prefix
+    // Left, center, right. Text before any &L/&C/&R belongs to the center.
+    let mut sections: [Vec<HFInline>; 3] = [Vec::new(), Vec::new(), Vec::new()];
+    let mut current: usize = 1;
+    let mut style = TextStyle::default();
+    let mut text = String::new();
 
     let chars: Vec<char> = s.chars().collect();
     let mut i = 0;
     while i < chars.len() {
-        if chars[i] == '&' && i + 1 < chars.len() {
-            match chars[i + 1] {
-                'L' => {
-                    current = &mut left;
-                    i += 2;
-                }
-                'C' => {
-                    current = &mut center;
-                    i += 2;
-                }
-                'R' => {
-                    current = &mut right;
-                    i += 2;
-                }
-                'P' => {
-                    current.push('\x01'); // Sentinel for page number
-                    i += 2;
-                }
-                'N' => {
-                    current.push('\x02'); // Sentinel for total pages
-                    i += 2;
+        if chars[i] != '&' || i + 1 >= chars.len() {
+            text.push(chars[i]);
+            i += 1;
+            continue;
+        }
+        let code: char = chars[i + 1];
+        i += 2;
+        match code {
+            'L' | 'C' | 'R' => {
+                flush_run(&mut sections[current], &mut text, &style);
+                current = match code {
+                    'L' => 0,
+                    'C' => 1,
+                    _ => 2,
+                };
+                // Font codes apply until the end of their section.
+                style = TextStyle::default();
+            }
+            'P' | 'N' => {
+                flush_run(&mut sections[current], &mut text, &style);
+                sections[current].push(if code == 'P' {
+                    HFInline::PageNumber
+                } else {
+                    HFInline::TotalPages
+                });
+            }
+            // Fields rather than text: the date is the one the PDF is
+            // rendered on, which the renderer's clock decides.
+            'D' | 'T' => {
+                flush_run(&mut sections[current], &mut text, &style);
+                let format: &str = if code == 'D' {
+                    &context.date_format
+                } else {
+                    TIME_FORMAT
+                };
+                sections[current].push(HFInline::DateTime(DateTimeField {
+                    format: format.to_string(),
+                    style: style.clone(),
+                }));
+            }
+            'A' => text.push_str(&context.sheet_name),
+            'G' => {
+                flush_run(&mut sections[current], &mut text, &style);
+                let suffix: char = match part {
+                    HfPart::Header => 'H',
+                    HfPart::Footer => 'F',
+                };
+                let shape_id: String = format!("{}{suffix}", ['L', 'C', 'R'][current]);
+                if let Some(image) = context.images.get(&shape_id) {
+                    sections[current].push(HFInline::Image(image.clone()));
                 }
-                '&' => {
-                    // Escaped ampersand: && → &
-                    current.push('&');
-                    i += 2;
+            }
+            // Escaped ampersand: && → &
+            '&' => text.push('&'),
+            '"' => {
+                // Font: &"Name,Style" where "-" keeps the current family.
+                let start = i;
+                while i < chars.len() && chars[i] != '"' {
+                    i += 1;
                 }
-                '"' => {
-                    // Font name: &"FontName" — skip to closing quote
-                    i += 2; // skip &"
-                    while i < chars.len() && chars[i] != '"' {
-                        i += 1;
-                    }
-                    if i < chars.len() {
-                        i += 1; // skip closing "
-                    }
+                let font: String = chars[start..i].iter().collect();
+                i = (i + 1).min(chars.len());
+                flush_run(&mut sections[current], &mut text, &style);
+                apply_font_code(&mut style, &font);
+            }
+            c if c.is_ascii_digit() => {
+                let start = i - 1;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
                 }
-                c if c.is_ascii_digit() => {
-                    // Font size: &NN — skip digits
-                    i += 1; // skip &
-                    while i < chars.len() && chars[i].is_ascii_digit() {
-                        i += 1;
-                    }
+                let size: String = chars[start..i].iter().collect();
+                flush_run(&mut sections[current], &mut text, &style);
+                style.font_size = size.parse().ok();
+            }
+            'K' => {
+                // Font color: &KRRGGBB, or the theme form &KTTSNN (theme
+                // index, tint sign, tint) which has no RGB to resolve here.
+                let start = i;
+                while i < chars.len() && i - start < 6 && chars[i].is_ascii_alphanumeric() {
+                    i += 1;
                 }
-                'K' => {
-                    // Font color: &KRRGGBB (or &KTTSNN theme form) — skip the
-                    // six code characters; leaving them printed literal hex
-                    // prefixes like "000000top center".
-                    i += 2;
-                    let mut consumed = 0;
-                    while i < chars.len() && consumed < 6 && chars[i].is_ascii_alphanumeric() {
+                let code: String = chars[start..i].iter().collect();
+                if let Some(color) = parse_hex_color(&code) {
+                    flush_run(&mut sections[current], &mut text, &style);
+                    style.color = Some(color);
+                } else if i < chars.len() && matches!(chars[i], '+' | '-') {
+                    i += 1;
+                    let tint_start = i;
+                    while i < chars.len() && i - tint_start < 3 && chars[i].is_ascii_digit() {
                         i += 1;
-                        consumed += 1;
                     }
                 }
-                _ => {
-                    // Unknown code — skip it
-                    i += 2;
-                }
             }
-        } else {
-            current.push(chars[i]);
-            i += 1;
+            'B' | 'I' | 'U' | 'E' | 'S' | 'X' | 'Y' => {
+                flush_run(&mut sections[current], &mut text, &style);
+                toggle_style(&mut style, code);
+            }
+            // &F/&Z (file name and path) are unknown to the parser, and
+            // &O/&H (outline, shadow) have no IR equivalent.
+            _ => {}
         }
     }
+    flush_run(&mut sections[current], &mut text, &style);
 
-    let mut paragraphs = Vec::new();
-
-    // Build paragraph for each non-empty section
-    let sections = [
-        (&left, Alignment::Left),
-        (&center, Alignment::Center),
-        (&right, Alignment::Right),
-    ];
-
-    for (text, alignment) in &sections {
-        if text.is_empty() {
-            continue;
-        }
-        let elements = build_hf_elements(text);
-        if !elements.is_empty() {
-            paragraphs.push(HeaderFooterParagraph {
-                style: ParagraphStyle {
-                    alignment: Some(*alignment),
-                    ..ParagraphStyle::default()
-                },
-                elements,
-                border: None,
-                frame: None,
-            });
-        }
-    }
+    let alignments = [Alignment::Left, Alignment::Center, Alignment::Right];
+    let paragraphs: Vec<HeaderFooterParagraph> = sections
+        .into_iter()
+        .zip(alignments)
+        .filter(|(elements, _)| !elements.is_empty())
+        .map(|(elements, alignment)| HeaderFooterParagraph {
+            style: ParagraphStyle {
+                alignment: Some(alignment),
+                ..ParagraphStyle::default()
+            },
+            elements,
+            border: None,
+            frame: None,
+        })
+        .collect();
 
     if paragraphs.is_empty() {
         None
@@ -125,51 +211,211 @@ pub(super) fn parse_hf_format_string(format_str: &str) -> Option<HeaderFooter> {
     }
 }
 
-/// Build HFInline elements from a section string, replacing sentinel chars.
-pub(super) fn build_hf_elements(section: &str) -> Vec<HFInline> {
-    let mut elements = Vec::new();
-    let mut current_text = String::new();
-
-    for ch in section.chars() {
-        match ch {
-            '\x01' => {
-                // Page number sentinel
-                if !current_text.is_empty() {
-                    elements.push(HFInline::Run(Run {
-                        text: std::mem::take(&mut current_text),
-                        style: TextStyle::default(),
-                        href: None,
-                        footnote: None,
-                    }));
-                }
-                elements.push(HFInline::PageNumber);
-            }
-            '\x02' => {
-                // Total pages sentinel
-                if !current_text.is_empty() {
-                    elements.push(HFInline::Run(Run {
-                        text: std::mem::take(&mut current_text),
-                        style: TextStyle::default(),
-                        href: None,
-                        footnote: None,
-                    }));
-                }
-                elements.push(HFInline::TotalPages);
-            }
-            _ => {
-                current_text.push(ch);
+/// Emit pending text as a run in the style in effect.
+fn flush_run(elements: &mut Vec<HFInline>, text: &mut String, style: &TextStyle) {
+    if text.is_empty() {
+        return;
+    }
+    elements.push(HFInline::Run(Run {
+        text: std::mem::take(text),
+        style: style.clone(),
+        href: None,
+        footnote: None,
+    }));
+}
+
+/// Apply `&"Name,Style"`. Style words combine ("Bold Italic"); "Regular"
+/// clears both.
+fn apply_font_code(style: &mut TextStyle, font: &str) {
+    let (family, font_style) = font.split_once(',').unwrap_or((font, ""));
+    let family: &str = family.trim();
+    if !family.is_empty() && family != "-" {
+        style.font_family = Some(family.to_string());
+    }
+    let font_style: String = font_style.to_ascii_lowercase();
+    if font_style.is_empty() {
+        return;
+    }
+    style.bold = Some(font_style.contains("bold"));
+    style.italic = Some(font_style.contains("italic") || font_style.contains("oblique"));
+}
+
+/// Toggle a two-character style code: bold, italic, single or double
+/// underline, strikethrough, superscript, subscript.
+fn toggle_style(style: &mut TextStyle, code: char) {
+    let flip = |flag: &mut Option<bool>| *flag = Some(!flag.unwrap_or(false));
+    match code {
+        'B' => flip(&mut style.bold),
+        'I' => flip(&mut style.italic),
+        'U' | 'E' => flip(&mut style.underline),
+        'S' => flip(&mut style.strikethrough),
+        'X' | 'Y' => {
+            let target = if code == 'X' {
+                VerticalTextAlign::Superscript
+            } else {
+                VerticalTextAlign::Subscript
+            };
+            style.vertical_align = if style.vertical_align == Some(target) {
+                None
+            } else {
+                Some(target)
+            };
+        }
+        _ => {}
+    }
+}
+
+/// Read the `&G` pictures of every worksheet, keyed by sheet name and then
+/// VML shape id. They live in the VML part referenced by `<legacyDrawingHF>`.
+pub(super) fn extract_hf_images(data: &[u8]) -> HashMap<String, HashMap<String, ImageData>> {
+    let Ok(mut archive) = crate::parser::open_zip(data) else {
+        return HashMap::new();
+    };
+    let workbook_xml = read_zip_entry_string(&mut archive, "xl/workbook.xml");
+    let workbook_rels_xml = read_zip_entry_string(&mut archive, "xl/_rels/workbook.xml.rels");
+    let rid_to_target = parse_rels_targets(&workbook_rels_xml);
+
+    let mut result: HashMap<String, HashMap<String, ImageData>> = HashMap::new();
+    for (sheet_name, sheet_rid) in parse_workbook_sheet_rids(&workbook_xml) {
+        let Some(sheet_target) = rid_to_target.get(&sheet_rid) else {
+            continue;
+        };
+        let sheet_path: String = format!("xl/{}", sheet_target.trim_start_matches("/xl/"));
+        let sheet_xml = read_zip_entry_string(&mut archive, &sheet_path);
+        let Some(vml_rid) = find_legacy_drawing_hf_rid(&sheet_xml) else {
+            continue;
+        };
+        let (sheet_dir, sheet_filename) = sheet_path
+            .rsplit_once('/')
+            .unwrap_or(("xl/worksheets", sheet_path.as_str()));
+        let sheet_rels_xml = read_zip_entry_string(
+            &mut archive,
+            &format!("{sheet_dir}/_rels/{sheet_filename}.rels"),
+        );
+        let Some(vml_target) = parse_rels_targets(&sheet_rels_xml).remove(&vml_rid) else {
+            continue;
+        };
+        let vml_path: String = resolve_relative_xl_path(sheet_dir, &vml_target);
+        let vml_xml = read_zip_entry_string(&mut archive, &vml_path);
+        let (vml_dir, vml_filename) = vml_path
+            .rsplit_once('/')
+            .unwrap_or(("xl/drawings", vml_path.as_str()));
+        let vml_rels_xml = read_zip_entry_string(
+            &mut archive,
+            &format!("{vml_dir}/_rels/{vml_filename}.rels"),
+        );
+        let rid_to_media = parse_rels_targets(&vml_rels_xml);
+
+        let mut images: HashMap<String, ImageData> = HashMap::new();
+        for shape in parse_vml_hf_shapes(&vml_xml) {
+            let Some(media_target) = rid_to_media.get(&shape.image_rid) else {
+                continue;
+            };
+            let media_path: String = resolve_relative_xl_path(vml_dir, media_target);
+            let Some(bytes) = read_zip_entry_bytes(&mut archive, &media_path) else {
+                continue;
+            };
+            let Some((data, format)) = decode_media(&media_path, bytes) else {
+                continue;
+            };
+            images.insert(
+                shape.id,
+                ImageData {
+                    data,
+                    format,
+                    width: shape.width_pt,
+                    height: shape.height_pt,
+                    crop: None,
+                    stroke: None,
+                    alignment: None,
+                    clip_shape: None,
+                    shadow: None,
+                },
+            );
+        }
+        if !images.is_empty() {
+            result.insert(sheet_name, images);
+        }
+    }
+    result
+}
+
+/// The relationship id of a worksheet's `<legacyDrawingHF r:id>`.
+fn find_legacy_drawing_hf_rid(sheet_xml: &str) -> Option<String> {
+    let mut reader = quick_xml::Reader::from_str(sheet_xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                if e.local_name().as_ref() == b"legacyDrawingHF" =>
+            {
+                return get_attr_str(e, b"r:id");
             }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
         }
     }
+}
 
-    if !current_text.is_empty() {
-        elements.push(HFInline::Run(Run {
-            text: current_text,
-            style: TextStyle::default(),
-            href: None,
-            footnote: None,
-        }));
+/// A picture placeholder from a header/footer VML drawing.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct VmlHfShape {
+    /// Section code such as `CH` (center header) or `LF` (left footer).
+    pub(super) id: String,
+    pub(super) image_rid: String,
+    pub(super) width_pt: Option<f64>,
+    pub(super) height_pt: Option<f64>,
+}
+
+/// Parse the `<v:shape>` pictures of a header/footer VML drawing.
+pub(super) fn parse_vml_hf_shapes(xml: &str) -> Vec<VmlHfShape> {
+    let mut shapes: Vec<VmlHfShape> = Vec::new();
+    let mut current: Option<(String, String)> = None;
+    let mut reader = quick_xml::Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"shape" => {
+                current = get_attr_str(e, b"id")
+                    .map(|id| (id, get_attr_str(e, b"style").unwrap_or_default()));
+            }
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                if e.local_name().as_ref() == b"imagedata" =>
+            {
+                if let Some((id, css)) = current.as_ref()
+                    && let Some(image_rid) = get_attr_str(e, b"o:relid")
+                {
+                    shapes.push(VmlHfShape {
+                        id: id.clone(),
+                        image_rid,
+                        width_pt: css_length_pt(css, "width"),
+                        height_pt: css_length_pt(css, "height"),
+                    });
+                }
+            }
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"shape" => current = None,
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
     }
+    shapes
+}
 
-    elements
+/// Read a length property from a VML inline style, in points.
+fn css_length_pt(css: &str, property: &str) -> Option<f64> {
+    let value: &str = css.split(';').find_map(|declaration| {
+        let (name, value) = declaration.split_once(':')?;
+        (name.trim() == property).then_some(value.trim())
+    })?;
+    let split_at: usize = value
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(value.len());
+    let number: f64 = value[..split_at].parse().ok()?;
+    let points_per_unit: f64 = match &value[split_at..] {
+        "pt" | "" => 1.0,
+        "in" => 72.0,
+        "cm" => 72.0 / 2.54,
+        "mm" => 72.0 / 25.4,
+        "px" => 0.75,
+        _ => return None,
+    };
+    Some(number * points_per_unit)
 }
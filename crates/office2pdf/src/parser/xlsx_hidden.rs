@@ -0,0 +1,207 @@
+//! Hidden rows and columns. Excel leaves them out of the printout, so unless
+//! `ConvertOptions::include_hidden` is set they are removed from the rendered
+//! table, including their share of merged cells.
+
+use std::collections::BTreeMap;
+
+use super::xlsx_cells::SheetContext;
+use crate::ir::TableRow;
+
+/// Whether a worksheet is hidden (`state="hidden"`) or only reachable
+/// through VBA (`state="veryHidden"`).
+pub(super) fn is_hidden_sheet(sheet: &umya_spreadsheet::Worksheet) -> bool {
+    let state: &str = sheet.get_sheet_state();
+    state == "hidden" || state == "veryHidden"
+}
+
+/// The hidden rows and columns of one sheet's rendered range.
+#[derive(Debug, Clone, Default)]
+pub(super) struct HiddenCells {
+    /// Hidden sheet rows (1-indexed), ascending.
+    pub(super) rows: Vec<u32>,
+    /// Hidden sheet columns (1-indexed), ascending.
+    pub(super) columns: Vec<u32>,
+    /// First column of the rendered range.
+    col_start: u32,
+}
+
+impl HiddenCells {
+    /// Collect what the worksheet marks hidden within the rendered range.
+    pub(super) fn for_range(
+        sheet: &umya_spreadsheet::Worksheet,
+        ctx: &SheetContext,
+        row_start: u32,
+        row_end: u32,
+    ) -> Self {
+        let rows: Vec<u32> = (row_start..=row_end)
+            .filter(|row| {
+                sheet
+                    .get_row_dimension(row)
+                    .is_some_and(|dimension| *dimension.get_hidden())
+            })
+            .collect();
+        let columns: Vec<u32> = (ctx.col_start..=ctx.col_end)
+            .filter(|col| {
+                sheet
+                    .get_column_dimension_by_number(col)
+                    .is_some_and(|dimension| *dimension.get_hidden())
+            })
+            .collect();
+        Self {
+            rows,
+            columns,
+            col_start: ctx.col_start,
+        }
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.rows.is_empty() && self.columns.is_empty()
+    }
+
+    /// What was hidden, for the omission warning: "rows 3, 5 and columns C".
+    pub(super) fn summary(&self) -> Option<String> {
+        let rows: String = self
+            .rows
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<String>>()
+            .join(", ");
+        let columns: String = self
+            .columns
+            .iter()
+            .map(umya_spreadsheet::helper::coordinate::string_from_column_index)
+            .collect::<Vec<String>>()
+            .join(", ");
+        match (rows.is_empty(), columns.is_empty()) {
+            (true, true) => None,
+            (false, true) => Some(format!("hidden rows {rows}")),
+            (true, false) => Some(format!("hidden columns {columns}")),
+            (false, false) => Some(format!("hidden rows {rows} and columns {columns}")),
+        }
+    }
+
+    pub(super) fn is_row_hidden(&self, row: u32) -> bool {
+        self.rows.binary_search(&row).is_ok()
+    }
+
+    pub(super) fn is_column_hidden(&self, col: u32) -> bool {
+        self.columns.binary_search(&col).is_ok()
+    }
+
+    /// Zero the width of hidden columns, so drawings anchored to the right
+    /// of them are not pushed out by space that never prints.
+    pub(super) fn zero_hidden_widths(&self, column_widths: &mut [f64]) {
+        for col in &self.columns {
+            if let Some(width) = column_widths.get_mut((col - self.col_start) as usize) {
+                *width = 0.0;
+            }
+        }
+    }
+
+    /// The widths of the visible columns.
+    pub(super) fn visible_widths(&self, column_widths: &[f64]) -> Vec<f64> {
+        column_widths
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.is_column_hidden(self.col_start + *index as u32))
+            .map(|(_, width)| *width)
+            .collect()
+    }
+
+    /// Map a 0-based column index of the full range to its index among the
+    /// visible columns. A hidden column maps to the next visible one.
+    pub(super) fn visible_column_index(&self, index: usize) -> usize {
+        let col: u32 = self.col_start + index as u32;
+        index - self.columns.iter().filter(|hidden| **hidden < col).count()
+    }
+
+    /// Shift a 1-indexed sheet row anchor up by the hidden rows above it.
+    pub(super) fn visible_anchor_row(&self, row: u32) -> u32 {
+        let hidden_above: u32 = self.rows.iter().filter(|hidden| **hidden < row).count() as u32;
+        row.saturating_sub(hidden_above)
+    }
+
+    /// Remove hidden rows and columns from `rows`, built for the sheet rows
+    /// starting at `first_row`. Merged cells shrink by their hidden share and
+    /// a merge whose top-left cell is hidden moves to its first visible cell.
+    pub(super) fn remove_from(&self, rows: Vec<TableRow>, first_row: u32) -> Vec<TableRow> {
+        if self.is_empty() {
+            return rows;
+        }
+
+        // Place every cell on the sheet grid first: a row's cells skip the
+        // columns still covered by row spans from above.
+        let mut covered_until: Vec<u32> = Vec::new();
+        let mut heights: Vec<Option<f64>> = Vec::with_capacity(rows.len());
+        let mut placed: BTreeMap<(u32, u32), crate::ir::TableCell> = BTreeMap::new();
+        for (offset, row) in rows.into_iter().enumerate() {
+            let row_number: u32 = first_row + offset as u32;
+            heights.push(row.height);
+            let mut col: u32 = self.col_start;
+            for cell in row.cells {
+                let covered = |col: u32| -> bool {
+                    covered_until
+                        .get((col - self.col_start) as usize)
+                        .is_some_and(|last_row| *last_row >= row_number)
+                };
+                while covered(col) {
+                    col += 1;
+                }
+                let span_end: u32 = col + cell.col_span.max(1);
+                let last_row: u32 = row_number + cell.row_span.max(1) - 1;
+                for spanned in col..span_end {
+                    let index: usize = (spanned - self.col_start) as usize;
+                    if covered_until.len() <= index {
+                        covered_until.resize(index + 1, 0);
+                    }
+                    covered_until[index] = last_row;
+                }
+                placed.insert((row_number, col), cell);
+                col = span_end;
+            }
+        }
+
+        let mut visible_rows: BTreeMap<u32, TableRow> = BTreeMap::new();
+        for (offset, height) in heights.into_iter().enumerate() {
+            let row_number: u32 = first_row + offset as u32;
+            if !self.is_row_hidden(row_number) {
+                visible_rows.insert(
+                    row_number,
+                    TableRow {
+                        cells: Vec::new(),
+                        height,
+                    },
+                );
+            }
+        }
+        // Key each surviving cell by its first visible grid position; the
+        // map's row-major order then lays cells out left to right.
+        let mut shown: BTreeMap<(u32, u32), crate::ir::TableCell> = BTreeMap::new();
+        for ((row_number, col), mut cell) in placed {
+            let shown_rows: Vec<u32> = (row_number..row_number + cell.row_span.max(1))
+                .filter(|row| visible_rows.contains_key(row))
+                .collect();
+            let shown_cols: Vec<u32> = (col..col + cell.col_span.max(1))
+                .filter(|col| !self.is_column_hidden(*col))
+                .collect();
+            let (Some(first_row_shown), Some(first_col_shown)) =
+                (shown_rows.first().copied(), shown_cols.first().copied())
+            else {
+                continue;
+            };
+            cell.row_span = shown_rows.len() as u32;
+            cell.col_span = shown_cols.len() as u32;
+            shown.insert((first_row_shown, first_col_shown), cell);
+        }
+        for ((row_number, _), cell) in shown {
+            if let Some(row) = visible_rows.get_mut(&row_number) {
+                row.cells.push(cell);
+            }
+        }
+        visible_rows.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "xlsx_hidden_tests.rs"]
+mod tests;
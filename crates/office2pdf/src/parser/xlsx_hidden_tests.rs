@@ -0,0 +1,137 @@
+use super::*;
+use crate::ir::{Block, Paragraph, ParagraphStyle, Run, TableCell, TextStyle};
+
+fn cell(text: &str, col_span: u32, row_span: u32) -> TableCell {
+    TableCell {
+        content: vec![Block::Paragraph(Paragraph {
+            style: ParagraphStyle::default(),
+            runs: vec![Run {
+                text: text.to_string(),
+                style: TextStyle::default(),
+                href: None,
+                footnote: None,
+            }],
+        })],
+        col_span,
+        row_span,
+        ..TableCell::default()
+    }
+}
+
+fn texts(rows: &[TableRow]) -> Vec<Vec<(String, u32, u32)>> {
+    rows.iter()
+        .map(|row| {
+            row.cells
+                .iter()
+                .map(|cell| {
+                    let text: String = match cell.content.first() {
+                        Some(Block::Paragraph(paragraph)) => paragraph.runs[0].text.clone(),
+                        _ => String::new(),
+                    };
+                    (text, cell.col_span, cell.row_span)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn hidden(rows: Vec<u32>, columns: Vec<u32>) -> HiddenCells {
+    HiddenCells {
+        rows,
+        columns,
+        col_start: 1,
+    }
+}
+
+/// Three rows by three columns of single cells named by their address.
+fn grid() -> Vec<TableRow> {
+    (1..=3)
+        .map(|row| TableRow {
+            cells: ["A", "B", "C"]
+                .iter()
+                .map(|col| cell(&format!("{col}{row}"), 1, 1))
+                .collect(),
+            height: Some(row as f64 * 10.0),
+        })
+        .collect()
+}
+
+fn plain(names: &[&str]) -> Vec<(String, u32, u32)> {
+    names.iter().map(|name| (name.to_string(), 1, 1)).collect()
+}
+
+#[test]
+fn test_remove_hidden_row_and_column() {
+    let rows: Vec<TableRow> = hidden(vec![2], vec![2]).remove_from(grid(), 1);
+    assert_eq!(
+        texts(&rows),
+        vec![plain(&["A1", "C1"]), plain(&["A3", "C3"])]
+    );
+    assert_eq!(rows[1].height, Some(30.0));
+}
+
+#[test]
+fn test_nothing_hidden_keeps_rows() {
+    let rows: Vec<TableRow> = HiddenCells::default().remove_from(grid(), 1);
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0].cells.len(), 3);
+}
+
+#[test]
+fn test_merge_shrinks_by_hidden_share() {
+    // A1:C2 merged, then a plain third row.
+    let rows: Vec<TableRow> = vec![
+        TableRow {
+            cells: vec![cell("Merged", 3, 2)],
+            height: None,
+        },
+        TableRow {
+            cells: vec![],
+            height: None,
+        },
+        TableRow {
+            cells: vec![cell("A3", 1, 1), cell("B3", 1, 1), cell("C3", 1, 1)],
+            height: None,
+        },
+    ];
+    let rows: Vec<TableRow> = hidden(vec![2], vec![3]).remove_from(rows, 1);
+    assert_eq!(
+        texts(&rows),
+        vec![vec![("Merged".to_string(), 2, 1)], plain(&["A3", "B3"])]
+    );
+}
+
+#[test]
+fn test_merge_from_hidden_row_moves_to_first_visible_row() {
+    // B1:B2 merged with row 1 hidden: the merge shows in row 2 between A2 and C2.
+    let rows: Vec<TableRow> = vec![
+        TableRow {
+            cells: vec![cell("A1", 1, 1), cell("B1", 1, 2), cell("C1", 1, 1)],
+            height: None,
+        },
+        TableRow {
+            cells: vec![cell("A2", 1, 1), cell("C2", 1, 1)],
+            height: None,
+        },
+    ];
+    let rows: Vec<TableRow> = hidden(vec![1], vec![]).remove_from(rows, 1);
+    assert_eq!(texts(&rows), vec![plain(&["A2", "B1", "C2"])]);
+}
+
+#[test]
+fn test_index_mapping_and_summary() {
+    let cells = hidden(vec![2, 5], vec![2, 4]);
+    assert_eq!(cells.visible_column_index(0), 0);
+    assert_eq!(cells.visible_column_index(2), 1);
+    assert_eq!(cells.visible_column_index(4), 2);
+    assert_eq!(cells.visible_anchor_row(6), 4);
+    assert_eq!(
+        cells.visible_widths(&[10.0, 20.0, 30.0, 40.0]),
+        vec![10.0, 30.0]
+    );
+    assert_eq!(
+        cells.summary().as_deref(),
+        Some("hidden rows 2, 5 and columns B, D")
+    );
+    assert_eq!(HiddenCells::default().summary(), None);
+}
@@ -0,0 +1,241 @@
+//! Cell hyperlinks from `<worksheet><hyperlinks>`. External targets live in
+//! the sheet's relationships; in-workbook targets are a `location` such as
+//! `'Q1 Sales'!B4` or a defined name, and become links to the target sheet's
+//! first page when that sheet is part of the output.
+
+use std::collections::{HashMap, HashSet};
+
+use quick_xml::events::Event;
+
+use super::xlsx_drawing::{parse_rels_targets, parse_workbook_sheet_rids, read_zip_entry_string};
+use crate::ir::{Block, Page, sheet_link, sheet_link_target};
+use crate::parser::xml_util::get_attr_str;
+
+/// A `<hyperlink>` as written in the worksheet.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct RawHyperlink {
+    /// Covered cells as `(first_col, first_row, last_col, last_row)`, 1-indexed.
+    pub(super) range: (u32, u32, u32, u32),
+    /// External target from the sheet relationships.
+    pub(super) url: Option<String>,
+    /// In-workbook target (`Sheet!A1` or a defined name), or the fragment
+    /// of an external URL.
+    pub(super) location: Option<String>,
+}
+
+/// Resolved hrefs of one sheet's linked cells.
+#[derive(Debug, Clone, Default)]
+pub(super) struct CellHyperlinks {
+    cells: HashMap<(u32, u32), String>,
+    /// Links covering more than one cell, checked after single cells.
+    ranges: Vec<((u32, u32, u32, u32), String)>,
+}
+
+impl CellHyperlinks {
+    /// Resolve a sheet's raw hyperlinks to run hrefs.
+    pub(super) fn resolve(
+        links: Vec<RawHyperlink>,
+        book: &umya_spreadsheet::Spreadsheet,
+        sheet: &umya_spreadsheet::Worksheet,
+    ) -> Self {
+        let mut resolved = Self::default();
+        for link in links {
+            let Some(href) = resolve_href(&link, book, sheet) else {
+                continue;
+            };
+            let (first_col, first_row, last_col, last_row) = link.range;
+            if first_col == last_col && first_row == last_row {
+                resolved.cells.insert((first_col, first_row), href);
+            } else {
+                resolved.ranges.push((link.range, href));
+            }
+        }
+        resolved
+    }
+
+    /// The href of the cell at `(col, row)`, if it is linked.
+    pub(super) fn href_at(&self, col: u32, row: u32) -> Option<&str> {
+        if let Some(href) = self.cells.get(&(col, row)) {
+            return Some(href);
+        }
+        self.ranges
+            .iter()
+            .find(|((first_col, first_row, last_col, last_row), _)| {
+                (*first_col..=*last_col).contains(&col) && (*first_row..=*last_row).contains(&row)
+            })
+            .map(|(_, href)| href.as_str())
+    }
+}
+
+fn resolve_href(
+    link: &RawHyperlink,
+    book: &umya_spreadsheet::Spreadsheet,
+    sheet: &umya_spreadsheet::Worksheet,
+) -> Option<String> {
+    match (&link.url, &link.location) {
+        (Some(url), Some(location)) => Some(format!("{url}#{location}")),
+        (Some(url), None) => Some(url.clone()),
+        (None, Some(location)) => Some(sheet_link(&location_sheet(location, book, sheet))),
+        (None, None) => None,
+    }
+}
+
+/// Name of the sheet an in-workbook location points into.
+fn location_sheet(
+    location: &str,
+    book: &umya_spreadsheet::Spreadsheet,
+    sheet: &umya_spreadsheet::Worksheet,
+) -> String {
+    let location: &str = location.trim_start_matches('#');
+    if let Some((sheet_name, _)) = location.rsplit_once('!') {
+        return unquote_sheet_name(sheet_name);
+    }
+    // A defined name jumps to wherever its range lives.
+    let defined_address: Option<String> = sheet
+        .get_defined_names()
+        .iter()
+        .chain(book.get_defined_names())
+        .find(|defined| defined.get_name() == location)
+        .map(|defined| defined.get_address());
+    if let Some(address) = defined_address
+        && let Some((sheet_name, _)) = address.rsplit_once('!')
+    {
+        return unquote_sheet_name(sheet_name);
+    }
+    // A bare cell reference stays on the same sheet.
+    sheet.get_name().to_string()
+}
+
+/// `'It''s here'` -> `It's here`.
+fn unquote_sheet_name(name: &str) -> String {
+    name.strip_prefix('\'')
+        .and_then(|name| name.strip_suffix('\''))
+        .map(|name| name.replace("''", "'"))
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Read the raw hyperlinks of every worksheet, keyed by sheet name.
+pub(super) fn extract_hyperlinks(data: &[u8]) -> HashMap<String, Vec<RawHyperlink>> {
+    let Ok(mut archive) = crate::parser::open_zip(data) else {
+        return HashMap::new();
+    };
+    let workbook_xml = read_zip_entry_string(&mut archive, "xl/workbook.xml");
+    let workbook_rels_xml = read_zip_entry_string(&mut archive, "xl/_rels/workbook.xml.rels");
+    let rid_to_target = parse_rels_targets(&workbook_rels_xml);
+
+    let mut result: HashMap<String, Vec<RawHyperlink>> = HashMap::new();
+    for (sheet_name, sheet_rid) in parse_workbook_sheet_rids(&workbook_xml) {
+        let Some(sheet_target) = rid_to_target.get(&sheet_rid) else {
+            continue;
+        };
+        let sheet_path: String = format!("xl/{}", sheet_target.trim_start_matches("/xl/"));
+        let sheet_xml = read_zip_entry_string(&mut archive, &sheet_path);
+        if !sheet_xml.contains("<hyperlink") {
+            continue;
+        }
+        let sheet_filename: &str = sheet_path.rsplit('/').next().unwrap_or(&sheet_path);
+        let sheet_rels_xml = read_zip_entry_string(
+            &mut archive,
+            &format!("xl/worksheets/_rels/{sheet_filename}.rels"),
+        );
+        let links: Vec<RawHyperlink> =
+            parse_sheet_hyperlinks(&sheet_xml, &parse_rels_targets(&sheet_rels_xml));
+        if !links.is_empty() {
+            result.insert(sheet_name, links);
+        }
+    }
+    result
+}
+
+/// Parse the `<hyperlink>` elements of one worksheet. `rels` maps the
+/// sheet's relationship ids to their targets.
+pub(super) fn parse_sheet_hyperlinks(
+    sheet_xml: &str,
+    rels: &HashMap<String, String>,
+) -> Vec<RawHyperlink> {
+    let mut links: Vec<RawHyperlink> = Vec::new();
+    let mut reader = quick_xml::Reader::from_str(sheet_xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                if e.local_name().as_ref() == b"hyperlink" =>
+            {
+                let Some(range) = get_attr_str(e, b"ref").and_then(|range| parse_range(&range))
+                else {
+                    continue;
+                };
+                let url: Option<String> = get_attr_str(e, b"id")
+                    .and_then(|rid| rels.get(&rid).cloned())
+                    .filter(|url| !url.is_empty());
+                let location: Option<String> =
+                    get_attr_str(e, b"location").filter(|location| !location.is_empty());
+                links.push(RawHyperlink {
+                    range,
+                    url,
+                    location,
+                });
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    links
+}
+
+/// `B2` or `B2:D4` as `(first_col, first_row, last_col, last_row)`.
+fn parse_range(range: &str) -> Option<(u32, u32, u32, u32)> {
+    let cell = |reference: &str| -> Option<(u32, u32)> {
+        let (col, row, _, _) =
+            umya_spreadsheet::helper::coordinate::index_from_coordinate(reference);
+        col.zip(row)
+    };
+    let (first, last) = range.split_once(':').unwrap_or((range, range));
+    let (first_col, first_row) = cell(first)?;
+    let (last_col, last_row) = cell(last)?;
+    Some((
+        first_col.min(last_col),
+        first_row.min(last_row),
+        first_col.max(last_col),
+        first_row.max(last_row),
+    ))
+}
+
+/// Unlink runs that jump to a sheet without a page in `pages`: the sheet was
+/// filtered out, hidden, empty, or rendered into another streaming chunk.
+pub(super) fn drop_dangling_sheet_links(pages: &mut [Page]) {
+    let sheet_names: HashSet<String> = pages
+        .iter()
+        .filter_map(|page| match page {
+            Page::Sheet(sheet_page) => Some(sheet_page.name.clone()),
+            _ => None,
+        })
+        .collect();
+    for page in pages {
+        let Page::Sheet(sheet_page) = page else {
+            continue;
+        };
+        for row in &mut sheet_page.table.rows {
+            for cell in &mut row.cells {
+                for block in &mut cell.content {
+                    let Block::Paragraph(paragraph) = block else {
+                        continue;
+                    };
+                    for run in &mut paragraph.runs {
+                        let is_dangling: bool = run
+                            .href
+                            .as_deref()
+                            .and_then(sheet_link_target)
+                            .is_some_and(|target| !sheet_names.contains(target));
+                        if is_dangling {
+                            run.href = None;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "xlsx_hyperlinks_tests.rs"]
+mod tests;
@@ -0,0 +1,101 @@
+use super::*;
+use crate::ir::{
+    Margins, PageSize, Paragraph, ParagraphStyle, Run, SheetPage, Table, TableCell, TableRow,
+    TextStyle,
+};
+
+#[test]
+fn test_parse_sheet_hyperlinks_reads_urls_locations_and_ranges() {
+    let sheet = r#"<worksheet xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+        <sheetData/>
+        <hyperlinks>
+            <hyperlink ref="A1" r:id="rId1"/>
+            <hyperlink ref="B2:C3" location="'Q1 Sales'!B4" display="Q1"/>
+            <hyperlink ref="D1" r:id="rId2" location="top"/>
+        </hyperlinks>
+    </worksheet>"#;
+    let rels: HashMap<String, String> = HashMap::from([
+        ("rId1".to_string(), "https://example.com/".to_string()),
+        ("rId2".to_string(), "https://example.com/doc".to_string()),
+    ]);
+    assert_eq!(
+        parse_sheet_hyperlinks(sheet, &rels),
+        vec![
+            RawHyperlink {
+                range: (1, 1, 1, 1),
+                url: Some("https://example.com/".to_string()),
+                location: None,
+            },
+            RawHyperlink {
+                range: (2, 2, 3, 3),
+                url: None,
+                location: Some("'Q1 Sales'!B4".to_string()),
+            },
+            RawHyperlink {
+                range: (4, 1, 4, 1),
+                url: Some("https://example.com/doc".to_string()),
+                location: Some("top".to_string()),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_unquote_sheet_name() {
+    assert_eq!(unquote_sheet_name("'It''s here'"), "It's here");
+    assert_eq!(unquote_sheet_name("Sheet2"), "Sheet2");
+}
+
+fn linked_page(name: &str, href: &str) -> Page {
+    Page::Sheet(SheetPage {
+        name: name.to_string(),
+        size: PageSize::default(),
+        margins: Margins::default(),
+        table: Table {
+            rows: vec![TableRow {
+                cells: vec![TableCell {
+                    content: vec![Block::Paragraph(Paragraph {
+                        style: ParagraphStyle::default(),
+                        runs: vec![Run {
+                            text: "link".to_string(),
+                            style: TextStyle::default(),
+                            href: Some(href.to_string()),
+                            footnote: None,
+                        }],
+                    })],
+                    ..TableCell::default()
+                }],
+                height: None,
+            }],
+            ..Table::default()
+        },
+        header: None,
+        footer: None,
+        charts: vec![],
+        images: vec![],
+        text_boxes: vec![],
+    })
+}
+
+fn first_href(page: &Page) -> Option<&str> {
+    let Page::Sheet(sheet_page) = page else {
+        return None;
+    };
+    let Block::Paragraph(paragraph) = &sheet_page.table.rows[0].cells[0].content[0] else {
+        return None;
+    };
+    paragraph.runs[0].href.as_deref()
+}
+
+#[test]
+fn test_drop_dangling_sheet_links_keeps_included_targets_and_urls() {
+    let mut pages: Vec<Page> = vec![
+        linked_page("Summary", &sheet_link("Detail")),
+        linked_page("Detail", &sheet_link("Hidden")),
+        linked_page("Other", "https://example.com/"),
+    ];
+    drop_dangling_sheet_links(&mut pages);
+    assert_eq!(first_href(&pages[0]), Some("#sheet=Detail"));
+    assert_eq!(first_href(&pages[1]), None);
+    assert_eq!(first_href(&pages[2]), Some("https://example.com/"));
+}
@@ -0,0 +1,1369 @@
+//! ECMA-376 number format interpreter (§18.8.31): renders a cell value
+//! through its `numFmt` code the way Excel displays it — sections and
+//! conditions, digit placeholders, grouping and scaling, percent, scientific,
+//! fractions, dates and times, literals, and `[Color]` overrides.
+
+use crate::ir::Color;
+
+/// A cell value as Excel displays it.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct FormattedValue {
+    pub(super) text: String,
+    /// Font color from a `[Red]`/`[ColorN]` section prefix.
+    pub(super) color: Option<Color>,
+}
+
+/// Shown instead of dates Excel cannot represent (negative or past 9999).
+const INVALID_DATE_TEXT: &str = "########";
+
+/// Serial of 9999-12-31, the last date Excel displays.
+const MAX_DATE_SERIAL: f64 = 2_958_466.0;
+
+/// 1900-system serial of 1904-01-01, serial 0 of the 1904 date system.
+const DATE1904_OFFSET_DAYS: i64 = 1462;
+
+/// Inputs to formatting beyond the cell's own format code: the workbook's
+/// date system, the locale the numbers are shown in, and how the sheet
+/// prints error values.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(super) struct FormatSettings {
+    /// `<workbookPr date1904="1">`: serials count days from 1904-01-01, the
+    /// default of old Mac Excel.
+    pub(super) date1904: bool,
+    /// `ConvertOptions::locale`; `None` shows numbers as en-US Excel does.
+    pub(super) locale: Option<NumberLocale>,
+    /// The sheet's "Cell errors as" print setting.
+    pub(super) errors: ErrorDisplay,
+}
+
+/// How error values such as `#DIV/0!` print: the `errors` attribute of a
+/// sheet's `<pageSetup>`, Excel's "Cell errors as" page setup choice.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(super) enum ErrorDisplay {
+    /// The error itself.
+    #[default]
+    Displayed,
+    Blank,
+    /// `--`.
+    Dash,
+    /// `#N/A`, whatever the error.
+    NotAvailable,
+}
+
+impl ErrorDisplay {
+    pub(super) fn from_attr(value: &str) -> Self {
+        match value {
+            "blank" => Self::Blank,
+            "dash" => Self::Dash,
+            "NA" => Self::NotAvailable,
+            _ => Self::Displayed,
+        }
+    }
+
+    /// The printed text of an error value.
+    pub(super) fn show(self, error: &str) -> String {
+        match self {
+            Self::Displayed => error.to_string(),
+            Self::Blank => String::new(),
+            Self::Dash => "--".to_string(),
+            Self::NotAvailable => "#N/A".to_string(),
+        }
+    }
+}
+
+impl FormatSettings {
+    /// The format code a cell is shown with. Built-in ids 14 and 22 are the
+    /// reader's short date in Excel, so a locale replaces them; an empty or
+    /// `General` code of another built-in id resolves to that id's code.
+    pub(super) fn format_code(&self, id: u32, code: &str) -> String {
+        if let Some(locale) = self.locale {
+            match id {
+                14 => return locale.short_date.to_string(),
+                22 => return format!("{} h:mm", locale.short_date),
+                _ => {}
+            }
+        }
+        if code.is_empty() || code.eq_ignore_ascii_case("general") {
+            builtin_format_code(id).unwrap_or(code).to_string()
+        } else {
+            code.to_string()
+        }
+    }
+}
+
+/// The separators and short date of a locale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct NumberLocale {
+    pub(super) decimal: char,
+    pub(super) group: char,
+    /// Format code of the short date (built-in format 14).
+    pub(super) short_date: &'static str,
+}
+
+impl NumberLocale {
+    const EN_US: Self = Self {
+        decimal: '.',
+        group: ',',
+        short_date: "m/d/yyyy",
+    };
+
+    /// The locale of a BCP 47 tag such as `de-DE`, `fr` or `en_GB`, or
+    /// `None` when the tag is not one we know.
+    pub(super) fn from_tag(tag: &str) -> Option<Self> {
+        let tag: String = tag.trim().to_ascii_lowercase().replace('_', "-");
+        let language: &str = tag.split('-').next().unwrap_or_default();
+        let (decimal, group, short_date): (char, char, &'static str) = match tag.as_str() {
+            "en-gb" | "en-ie" | "en-au" | "en-nz" | "en-in" => ('.', ',', "dd/mm/yyyy"),
+            "en-ca" => ('.', ',', "yyyy-mm-dd"),
+            "de-ch" | "fr-ch" | "it-ch" => ('.', '\'', "dd.mm.yyyy"),
+            "es-mx" | "es-us" => ('.', ',', "dd/mm/yyyy"),
+            _ => match language {
+                "en" => return Some(Self::EN_US),
+                "de" => (',', '.', "dd.mm.yyyy"),
+                // French and Russian group with a (narrow) no-break space.
+                "fr" => (',', '\u{202F}', "dd/mm/yyyy"),
+                "ru" | "pl" => (',', '\u{00A0}', "dd.mm.yyyy"),
+                "es" | "it" | "pt" => (',', '.', "dd/mm/yyyy"),
+                "nl" => (',', '.', "d-m-yyyy"),
+                "sv" => (',', '\u{00A0}', "yyyy-mm-dd"),
+                "ja" => ('.', ',', "yyyy/mm/dd"),
+                "ko" => ('.', ',', "yyyy-mm-dd"),
+                "zh" => ('.', ',', "yyyy/m/d"),
+                _ => return None,
+            },
+        };
+        Some(Self {
+            decimal,
+            group,
+            short_date,
+        })
+    }
+}
+
+/// Built-in format codes for `numFmtId`s that a workbook may reference
+/// without declaring (ECMA-376 §18.8.30).
+pub(super) fn builtin_format_code(id: u32) -> Option<&'static str> {
+    let code: &'static str = match id {
+        0 => "General",
+        1 => "0",
+        2 => "0.00",
+        3 => "#,##0",
+        4 => "#,##0.00",
+        9 => "0%",
+        10 => "0.00%",
+        11 => "0.00E+00",
+        12 => "# ?/?",
+        13 => "# ??/??",
+        14 => "mm-dd-yy",
+        15 => "d-mmm-yy",
+        16 => "d-mmm",
+        17 => "mmm-yy",
+        18 => "h:mm AM/PM",
+        19 => "h:mm:ss AM/PM",
+        20 => "h:mm",
+        21 => "h:mm:ss",
+        22 => "m/d/yy h:mm",
+        37 => "#,##0 ;(#,##0)",
+        38 => "#,##0 ;[Red](#,##0)",
+        39 => "#,##0.00;(#,##0.00)",
+        40 => "#,##0.00;[Red](#,##0.00)",
+        45 => "mm:ss",
+        46 => "[h]:mm:ss",
+        47 => "mmss.0",
+        48 => "##0.0E+0",
+        49 => "@",
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Format a numeric cell value with `format_code`.
+pub(super) fn format_number(value: f64, format_code: &str) -> FormattedValue {
+    format_number_with(value, format_code, &FormatSettings::default())
+}
+
+/// Format a numeric cell value with `format_code` in the workbook's date
+/// system and the given locale.
+pub(super) fn format_number_with(
+    value: f64,
+    format_code: &str,
+    settings: &FormatSettings,
+) -> FormattedValue {
+    if !value.is_finite() {
+        return FormattedValue {
+            text: "#NUM!".to_string(),
+            color: None,
+        };
+    }
+    let locale: NumberLocale = settings.locale.unwrap_or(NumberLocale::EN_US);
+    let sections: Vec<Section> = parse_format(format_code);
+    let Some((section, show_sign)) = select_number_section(&sections, value) else {
+        return FormattedValue {
+            text: format_general(value).replace('.', &locale.decimal.to_string()),
+            color: None,
+        };
+    };
+    let text: String = if section.is_date() {
+        format_date_section(section, value, settings.date1904, locale.decimal)
+    } else {
+        let body: String = format_number_section(section, value.abs(), &locale);
+        if show_sign && value < 0.0 {
+            format!("-{body}")
+        } else {
+            body
+        }
+    };
+    FormattedValue {
+        text,
+        color: section.color,
+    }
+}
+
+/// Format a text cell value. Only a text section (the fourth, or a lone
+/// section containing `@`) changes what is shown.
+pub(super) fn format_text(value: &str, format_code: &str) -> FormattedValue {
+    let sections: Vec<Section> = parse_format(format_code);
+    let text_section: Option<&Section> = sections
+        .get(3)
+        .or_else(|| sections.iter().find(|section| section.has(Token::is_text)));
+    let Some(section) = text_section else {
+        return FormattedValue {
+            text: value.to_string(),
+            color: None,
+        };
+    };
+    let mut text = String::new();
+    for token in &section.tokens {
+        match token {
+            Token::Literal(literal) => text.push_str(literal),
+            Token::Text => text.push_str(value),
+            _ => {}
+        }
+    }
+    FormattedValue {
+        text,
+        color: section.color,
+    }
+}
+
+// ── Parsing ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(String),
+    /// `0` (pad with zero), `#` (no padding), `?` (pad with space).
+    Digit(char),
+    DecimalPoint,
+    Thousands,
+    Percent,
+    /// `E+` (always signed) or `E-` (sign only when negative).
+    Exponent {
+        always_sign: bool,
+    },
+    FractionSlash,
+    FixedDenominator(u32),
+    Text,
+    General,
+    Date(DatePart, usize),
+    /// `AM/PM` or `A/P`; the flag keeps the casing of the format code.
+    AmPm {
+        short: bool,
+        lowercase: bool,
+    },
+    Elapsed(DatePart),
+    /// Fractional seconds after `ss`, with this many digits.
+    SubSecond(usize),
+}
+
+impl Token {
+    fn is_text(&self) -> bool {
+        matches!(self, Self::Text)
+    }
+
+    fn is_digit(&self) -> bool {
+        matches!(self, Self::Digit(_))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DatePart {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Condition {
+    comparison: Comparison,
+    threshold: f64,
+}
+
+impl Condition {
+    fn matches(self, value: f64) -> bool {
+        match self.comparison {
+            Comparison::Less => value < self.threshold,
+            Comparison::LessEqual => value <= self.threshold,
+            Comparison::Greater => value > self.threshold,
+            Comparison::GreaterEqual => value >= self.threshold,
+            Comparison::Equal => value == self.threshold,
+            Comparison::NotEqual => value != self.threshold,
+        }
+    }
+
+    /// Sections that only ever see negative numbers print them unsigned,
+    /// like the implicit negative section.
+    fn selects_negatives_only(self) -> bool {
+        match self.comparison {
+            Comparison::Less => self.threshold <= 0.0,
+            Comparison::LessEqual => self.threshold < 0.0,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Section {
+    tokens: Vec<Token>,
+    color: Option<Color>,
+    condition: Option<Condition>,
+}
+
+impl Section {
+    fn has(&self, predicate: impl Fn(&Token) -> bool) -> bool {
+        self.tokens.iter().any(predicate)
+    }
+
+    fn is_date(&self) -> bool {
+        self.has(|token| matches!(token, Token::Date(..) | Token::Elapsed(_)))
+    }
+}
+
+fn parse_format(format_code: &str) -> Vec<Section> {
+    let chars: Vec<char> = format_code.chars().collect();
+    let mut sections: Vec<Section> = Vec::new();
+    let mut section = Section::default();
+    let mut i: usize = 0;
+    while i < chars.len() {
+        let c: char = chars[i];
+        match c {
+            '"' => {
+                let end: usize = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == '"')
+                    .map_or(chars.len(), |offset| i + 1 + offset);
+                push_literal(&mut section, &chars[i + 1..end].iter().collect::<String>());
+                i = end + 1;
+                continue;
+            }
+            '\\' => {
+                if let Some(&next) = chars.get(i + 1) {
+                    push_literal(&mut section, &next.to_string());
+                }
+                i += 2;
+                continue;
+            }
+            // `_x` reserves the width of `x`; a space is the closest text.
+            '_' => {
+                push_literal(&mut section, " ");
+                i += 2;
+                continue;
+            }
+            // `*x` repeats `x` to fill the cell; there is no cell width here.
+            '*' => {
+                i += 2;
+                continue;
+            }
+            '[' => {
+                let end: usize = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == ']')
+                    .map_or(chars.len(), |offset| i + 1 + offset);
+                let content: String = chars[i + 1..end].iter().collect();
+                apply_bracket(&mut section, &content);
+                i = end + 1;
+                continue;
+            }
+            ';' => {
+                sections.push(std::mem::take(&mut section));
+            }
+            '0' | '#' | '?' => section.tokens.push(Token::Digit(c)),
+            '.' => section.tokens.push(Token::DecimalPoint),
+            ',' => section.tokens.push(Token::Thousands),
+            '%' => section.tokens.push(Token::Percent),
+            '@' => section.tokens.push(Token::Text),
+            'E' | 'e' if matches!(chars.get(i + 1), Some('+') | Some('-')) => {
+                section.tokens.push(Token::Exponent {
+                    always_sign: chars[i + 1] == '+',
+                });
+                i += 2;
+                continue;
+            }
+            '/' => {
+                let digits: String = chars[i + 1..]
+                    .iter()
+                    .take_while(|ch| ch.is_ascii_digit())
+                    .collect();
+                section.tokens.push(Token::FractionSlash);
+                if digits.starts_with(|ch: char| ch != '0')
+                    && let Ok(denominator) = digits.parse::<u32>()
+                {
+                    section.tokens.push(Token::FixedDenominator(denominator));
+                    i += 1 + digits.len();
+                    continue;
+                }
+            }
+            _ if starts_with_ignore_case(&chars[i..], "general") => {
+                section.tokens.push(Token::General);
+                i += "general".len();
+                continue;
+            }
+            _ if starts_with_ignore_case(&chars[i..], "am/pm") => {
+                section.tokens.push(Token::AmPm {
+                    short: false,
+                    lowercase: c.is_ascii_lowercase(),
+                });
+                i += "am/pm".len();
+                continue;
+            }
+            _ if starts_with_ignore_case(&chars[i..], "a/p") => {
+                section.tokens.push(Token::AmPm {
+                    short: true,
+                    lowercase: c.is_ascii_lowercase(),
+                });
+                i += "a/p".len();
+                continue;
+            }
+            _ if date_part(c).is_some() => {
+                let lower: char = c.to_ascii_lowercase();
+                let run: usize = chars[i..]
+                    .iter()
+                    .take_while(|ch| ch.to_ascii_lowercase() == lower)
+                    .count();
+                if let Some(part) = date_part(c) {
+                    section.tokens.push(Token::Date(part, run));
+                }
+                i += run;
+                continue;
+            }
+            _ => push_literal(&mut section, &c.to_string()),
+        }
+        i += 1;
+    }
+    sections.push(section);
+
+    for section in &mut sections {
+        if section.is_date() {
+            resolve_date_tokens(&mut section.tokens);
+        }
+    }
+    sections
+}
+
+fn starts_with_ignore_case(chars: &[char], word: &str) -> bool {
+    chars.len() >= word.len()
+        && chars
+            .iter()
+            .zip(word.chars())
+            .all(|(a, b)| a.eq_ignore_ascii_case(&b))
+}
+
+fn date_part(c: char) -> Option<DatePart> {
+    match c.to_ascii_lowercase() {
+        // `e` is the era year in Japanese formats; Excel shows the year.
+        'y' | 'e' => Some(DatePart::Year),
+        'm' => Some(DatePart::Month),
+        'd' => Some(DatePart::Day),
+        'h' => Some(DatePart::Hour),
+        's' => Some(DatePart::Second),
+        _ => None,
+    }
+}
+
+fn push_literal(section: &mut Section, text: &str) {
+    if let Some(Token::Literal(last)) = section.tokens.last_mut() {
+        last.push_str(text);
+    } else {
+        section.tokens.push(Token::Literal(text.to_string()));
+    }
+}
+
+fn apply_bracket(section: &mut Section, content: &str) {
+    let lower: String = content.to_ascii_lowercase();
+    // `[$€-407]`: currency symbol and locale; `[$-409]`: locale only.
+    if let Some(currency) = content.strip_prefix('$') {
+        let symbol: &str = currency.split('-').next().unwrap_or_default();
+        if !symbol.is_empty() {
+            push_literal(section, symbol);
+        }
+        return;
+    }
+    if let Some(condition) = parse_condition(content) {
+        section.condition = Some(condition);
+        return;
+    }
+    if !lower.is_empty() && lower.chars().all(|c| c == lower.as_bytes()[0] as char) {
+        let part: Option<DatePart> = match lower.as_bytes()[0] {
+            b'h' => Some(DatePart::Hour),
+            b'm' => Some(DatePart::Minute),
+            b's' => Some(DatePart::Second),
+            _ => None,
+        };
+        if let Some(part) = part {
+            section.tokens.push(Token::Elapsed(part));
+            return;
+        }
+    }
+    if let Some(color) = named_color(&lower) {
+        section.color = Some(color);
+    }
+}
+
+fn parse_condition(content: &str) -> Option<Condition> {
+    let (comparison, rest): (Comparison, &str) = if let Some(rest) = content.strip_prefix("<=") {
+        (Comparison::LessEqual, rest)
+    } else if let Some(rest) = content.strip_prefix(">=") {
+        (Comparison::GreaterEqual, rest)
+    } else if let Some(rest) = content.strip_prefix("<>") {
+        (Comparison::NotEqual, rest)
+    } else if let Some(rest) = content.strip_prefix('<') {
+        (Comparison::Less, rest)
+    } else if let Some(rest) = content.strip_prefix('>') {
+        (Comparison::Greater, rest)
+    } else if let Some(rest) = content.strip_prefix('=') {
+        (Comparison::Equal, rest)
+    } else {
+        return None;
+    };
+    Some(Condition {
+        comparison,
+        threshold: rest.trim().parse().ok()?,
+    })
+}
+
+/// Excel's default 56-color palette, addressed by `[ColorN]`.
+const PALETTE: [u32; 56] = [
+    0x000000, 0xFFFFFF, 0xFF0000, 0x00FF00, 0x0000FF, 0xFFFF00, 0xFF00FF, 0x00FFFF, 0x800000,
+    0x008000, 0x000080, 0x808000, 0x800080, 0x008080, 0xC0C0C0, 0x808080, 0x9999FF, 0x993366,
+    0xFFFFCC, 0xCCFFFF, 0x660066, 0xFF8080, 0x0066CC, 0xCCCCFF, 0x000080, 0xFF00FF, 0xFFFF00,
+    0x00FFFF, 0x800080, 0x800000, 0x008080, 0x0000FF, 0x00CCFF, 0xCCFFFF, 0xCCFFCC, 0xFFFF99,
+    0x99CCFF, 0xFF99CC, 0xCC99FF, 0xFFCC99, 0x3366FF, 0x33CCCC, 0x99CC00, 0xFFCC00, 0xFF9900,
+    0xFF6600, 0x666699, 0x969696, 0x003366, 0x339966, 0x003300, 0x333300, 0x993300, 0x993366,
+    0x333399, 0x333333,
+];
+
+fn named_color(lower: &str) -> Option<Color> {
+    let rgb: u32 = match lower {
+        "black" => 0x000000,
+        "white" => 0xFFFFFF,
+        "red" => 0xFF0000,
+        "green" => 0x00FF00,
+        "blue" => 0x0000FF,
+        "yellow" => 0xFFFF00,
+        "magenta" => 0xFF00FF,
+        "cyan" => 0x00FFFF,
+        _ => {
+            let index: usize = lower.strip_prefix("color")?.parse().ok()?;
+            *PALETTE.get(index.checked_sub(1)?)?
+        }
+    };
+    Some(Color::new((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8))
+}
+
+/// Tell minutes from months (`m` right after an hour or right before a
+/// second is a minute) and turn `.0` after seconds into fractional seconds.
+fn resolve_date_tokens(tokens: &mut Vec<Token>) {
+    let date_indices: Vec<usize> = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| matches!(token, Token::Date(..) | Token::Elapsed(_)))
+        .map(|(index, _)| index)
+        .collect();
+    for (position, &index) in date_indices.iter().enumerate() {
+        let Token::Date(DatePart::Month, run) = tokens[index] else {
+            continue;
+        };
+        if run > 2 {
+            continue;
+        }
+        let after_hour: bool = position > 0
+            && matches!(
+                tokens[date_indices[position - 1]],
+                Token::Date(DatePart::Hour, _) | Token::Elapsed(DatePart::Hour)
+            );
+        let before_second: bool = date_indices.get(position + 1).is_some_and(|&next| {
+            matches!(
+                tokens[next],
+                Token::Date(DatePart::Second, _) | Token::Elapsed(DatePart::Second)
+            )
+        });
+        if after_hour || before_second {
+            tokens[index] = Token::Date(DatePart::Minute, run);
+        }
+    }
+
+    let mut resolved: Vec<Token> = Vec::with_capacity(tokens.len());
+    let mut index: usize = 0;
+    while index < tokens.len() {
+        let follows_second: bool = matches!(
+            resolved.last(),
+            Some(Token::Date(DatePart::Second, _)) | Some(Token::Elapsed(DatePart::Second))
+        );
+        if follows_second && tokens[index] == Token::DecimalPoint {
+            let zeros: usize = tokens[index + 1..]
+                .iter()
+                .take_while(|token| **token == Token::Digit('0'))
+                .count();
+            if zeros > 0 {
+                resolved.push(Token::SubSecond(zeros.min(3)));
+                index += 1 + zeros;
+                continue;
+            }
+        }
+        // Slashes and stray placeholders are plain text in dates.
+        let token: Token = match &tokens[index] {
+            Token::FractionSlash => Token::Literal("/".to_string()),
+            Token::FixedDenominator(denominator) => Token::Literal(denominator.to_string()),
+            Token::DecimalPoint => Token::Literal(".".to_string()),
+            Token::Thousands => Token::Literal(",".to_string()),
+            Token::Digit(c) => Token::Literal(c.to_string()),
+            other => other.clone(),
+        };
+        resolved.push(token);
+        index += 1;
+    }
+    *tokens = resolved;
+}
+
+// ── Section selection ───────────────────────────────────────────────────
+
+/// Pick the section for a number and whether it prints a minus sign.
+fn select_number_section(sections: &[Section], value: f64) -> Option<(&Section, bool)> {
+    // The text-only section never formats numbers.
+    let numeric: &[Section] = &sections[..sections.len().min(3)];
+    if numeric.is_empty() || numeric.iter().all(is_general_only) && sections.len() == 1 {
+        return None;
+    }
+
+    if numeric.iter().any(|section| section.condition.is_some()) {
+        let mut fallback: Option<&Section> = None;
+        for section in numeric {
+            match section.condition {
+                Some(condition) if condition.matches(value) => {
+                    return Some((section, !condition.selects_negatives_only()));
+                }
+                Some(_) => {}
+                None if fallback.is_none() => fallback = Some(section),
+                None => {}
+            }
+        }
+        return fallback.map(|section| (section, true));
+    }
+
+    let chosen: (&Section, bool) = match numeric.len() {
+        1 => (&numeric[0], true),
+        2 if value < 0.0 => (&numeric[1], false),
+        2 => (&numeric[0], true),
+        _ if value > 0.0 => (&numeric[0], true),
+        _ if value < 0.0 => (&numeric[1], false),
+        _ => (&numeric[2], true),
+    };
+    // An empty section (`0;;0`) hides the value but not its color.
+    Some(chosen)
+}
+
+fn is_general_only(section: &Section) -> bool {
+    section.color.is_none()
+        && section.condition.is_none()
+        && section
+            .tokens
+            .iter()
+            .all(|token| matches!(token, Token::General))
+}
+
+// ── Numbers ─────────────────────────────────────────────────────────────
+
+/// Excel's `General` format: integers as-is, otherwise up to ten
+/// significant digits within an 11-character cell, switching to scientific
+/// notation for very large or very small magnitudes.
+pub(super) fn format_general(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    let sign: &str = if value < 0.0 { "-" } else { "" };
+    let magnitude: f64 = value.abs();
+    if !(1e-9..1e11).contains(&magnitude) {
+        let scientific: String = format!("{magnitude:.5e}");
+        let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+        let mantissa: &str = mantissa.trim_end_matches('0').trim_end_matches('.');
+        let exponent: i32 = exponent.parse().unwrap_or(0);
+        let exponent_sign: char = if exponent < 0 { '-' } else { '+' };
+        return format!("{sign}{mantissa}E{exponent_sign}{:02}", exponent.abs());
+    }
+    let integer_digits: i32 = if magnitude >= 1.0 {
+        magnitude.log10().floor() as i32 + 1
+    } else {
+        1
+    };
+    let decimals: usize = (10 - integer_digits).max(0) as usize;
+    let (integer, fraction): (String, String) = round_decimal(magnitude, decimals);
+    let integer: &str = if integer.is_empty() { "0" } else { &integer };
+    let fraction: &str = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{sign}{integer}")
+    } else {
+        format!("{sign}{integer}.{fraction}")
+    }
+}
+
+/// Round `value` (non-negative) half away from zero to `decimals` places and
+/// return its integer digits (empty when zero) and exactly `decimals`
+/// fraction digits. Rounds the 15-significant-digit decimal form Excel
+/// displays rather than the binary double, so `1.065` rounds to `1.07`.
+fn round_decimal(value: f64, decimals: usize) -> (String, String) {
+    let scientific: String = format!("{:.14e}", value.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let exponent: i64 = exponent.parse().unwrap_or(0);
+    let mut digits: Vec<u8> = mantissa
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|b| b - b'0')
+        .collect();
+    let mut point: i64 = exponent + 1;
+    if point < 1 {
+        let padding: usize = (1 - point) as usize;
+        digits.splice(0..0, std::iter::repeat_n(0, padding));
+        point = 1;
+    }
+    let point: usize = point as usize;
+    if digits.len() < point {
+        digits.resize(point, 0);
+    }
+
+    let mut point: usize = point;
+    let cut: usize = point + decimals;
+    if digits.len() > cut {
+        let round_up: bool = digits[cut] >= 5;
+        digits.truncate(cut);
+        if round_up {
+            let mut index: usize = cut;
+            loop {
+                if index == 0 {
+                    digits.insert(0, 1);
+                    point += 1;
+                    break;
+                }
+                index -= 1;
+                if digits[index] == 9 {
+                    digits[index] = 0;
+                } else {
+                    digits[index] += 1;
+                    break;
+                }
+            }
+        }
+    } else {
+        digits.resize(cut, 0);
+    }
+
+    let to_string = |slice: &[u8]| -> String { slice.iter().map(|d| (b'0' + d) as char).collect() };
+    let integer: String = to_string(&digits[..point]);
+    let integer: String = integer.trim_start_matches('0').to_string();
+    (integer, to_string(&digits[point..]))
+}
+
+/// Where each token of a numeric section belongs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Zone {
+    Integer,
+    Fraction,
+    Exponent,
+}
+
+fn format_number_section(section: &Section, magnitude: f64, locale: &NumberLocale) -> String {
+    let tokens: &[Token] = &section.tokens;
+    if let Some(slash) = fraction_slash_index(tokens) {
+        return format_fraction_section(tokens, slash, magnitude);
+    }
+
+    let zones: Vec<Zone> = assign_zones(tokens);
+    let percent_count: usize = tokens.iter().filter(|t| **t == Token::Percent).count();
+    let integer_placeholders: Vec<char> = placeholders_in(tokens, &zones, Zone::Integer);
+    let fraction_placeholders: Vec<char> = placeholders_in(tokens, &zones, Zone::Fraction);
+    let exponent_placeholders: Vec<char> = placeholders_in(tokens, &zones, Zone::Exponent);
+    let (grouping, scale_thousands): (bool, usize) = thousands_usage(tokens, &zones);
+
+    let mut scaled: f64 = magnitude * 100f64.powi(percent_count as i32);
+    scaled /= 1000f64.powi(scale_thousands as i32);
+
+    let has_exponent: bool = tokens.iter().any(|t| matches!(t, Token::Exponent { .. }));
+    let mut exponent: i32 = 0;
+    let (integer, fraction): (String, String) = if has_exponent {
+        let step: i32 = integer_placeholders.len().max(1) as i32;
+        if scaled != 0.0 {
+            exponent = decimal_exponent(scaled).div_euclid(step) * step;
+        }
+        let mut parts = round_decimal(scaled / 10f64.powi(exponent), fraction_placeholders.len());
+        if scaled != 0.0 && parts.0.len() > step as usize {
+            exponent += step;
+            parts = round_decimal(scaled / 10f64.powi(exponent), fraction_placeholders.len());
+        }
+        parts
+    } else {
+        round_decimal(scaled, fraction_placeholders.len())
+    };
+
+    let integer_chars: Vec<char> = render_integer(
+        &integer,
+        &integer_placeholders,
+        grouping.then_some(locale.group),
+    );
+    let fraction_chars: Vec<char> = render_fraction(&fraction, &fraction_placeholders);
+    let exponent_digits: String = exponent.abs().to_string();
+    let exponent_chars: Vec<char> = render_integer(&exponent_digits, &exponent_placeholders, None);
+
+    let mut output = String::new();
+    let (mut integer_index, mut fraction_index, mut exponent_index): (usize, usize, usize) =
+        (0, 0, 0);
+    let mut integer_emitted: bool = false;
+    let emit_integer = |output: &mut String, emitted: &mut bool| {
+        if !*emitted {
+            output.extend(integer_chars.iter());
+            *emitted = true;
+        }
+    };
+    for (token, zone) in tokens.iter().zip(&zones) {
+        match token {
+            // Literals between integer placeholders were placed by
+            // `emit_split_integer`.
+            Token::Literal(_)
+                if *zone == Zone::Integer
+                    && integer_index > 0
+                    && integer_index < integer_placeholders.len() => {}
+            Token::Literal(literal) => output.push_str(literal),
+            Token::Digit(_) => match zone {
+                Zone::Integer => {
+                    // All integer digits are rendered as one run at the
+                    // first placeholder, except literals between
+                    // placeholders (`000-0000`), which split the run.
+                    if integer_index == 0 {
+                        emit_split_integer(
+                            &mut output,
+                            tokens,
+                            &zones,
+                            &integer_chars,
+                            &integer_placeholders,
+                        );
+                        integer_emitted = true;
+                    }
+                    integer_index += 1;
+                }
+                Zone::Fraction => {
+                    emit_integer(&mut output, &mut integer_emitted);
+                    if let Some(c) = fraction_chars.get(fraction_index) {
+                        output.push(*c);
+                    }
+                    fraction_index += 1;
+                }
+                Zone::Exponent => {
+                    if exponent_index == 0 {
+                        output.extend(exponent_chars.iter());
+                    }
+                    exponent_index += 1;
+                }
+            },
+            Token::DecimalPoint => {
+                emit_integer(&mut output, &mut integer_emitted);
+                output.push(locale.decimal);
+            }
+            Token::Percent => output.push('%'),
+            Token::Exponent { always_sign } => {
+                emit_integer(&mut output, &mut integer_emitted);
+                output.push('E');
+                if exponent < 0 {
+                    output.push('-');
+                } else if *always_sign {
+                    output.push('+');
+                }
+            }
+            Token::General => output
+                .push_str(&format_general(magnitude).replace('.', &locale.decimal.to_string())),
+            _ => {}
+        }
+    }
+    output
+}
+
+/// Emit the integer digits, splitting them across literals that sit between
+/// integer placeholders so patterns like `(000) 000-0000` keep their shape.
+fn emit_split_integer(
+    output: &mut String,
+    tokens: &[Token],
+    zones: &[Zone],
+    integer_chars: &[char],
+    placeholders: &[char],
+) {
+    // Placeholders after each integer-zone literal, counted from the right.
+    let mut literal_cuts: Vec<(usize, &str)> = Vec::new();
+    let mut seen: usize = 0;
+    let mut started: bool = false;
+    for (token, zone) in tokens.iter().zip(zones) {
+        if *zone != Zone::Integer {
+            continue;
+        }
+        match token {
+            Token::Digit(_) => {
+                seen += 1;
+                started = true;
+            }
+            Token::Literal(literal) if started && seen < placeholders.len() => {
+                literal_cuts.push((placeholders.len() - seen, literal.as_str()));
+            }
+            _ => {}
+        }
+    }
+    if literal_cuts.is_empty() {
+        output.extend(integer_chars.iter());
+        return;
+    }
+    // Digits from the right: cut N means "N placeholders to the right".
+    let total: usize = integer_chars.len();
+    for (position, c) in integer_chars.iter().enumerate() {
+        let from_right: usize = total - position;
+        output.push(*c);
+        for (cut, literal) in &literal_cuts {
+            if from_right - 1 == *cut {
+                output.push_str(literal);
+            }
+        }
+    }
+}
+
+fn assign_zones(tokens: &[Token]) -> Vec<Zone> {
+    let mut zone: Zone = Zone::Integer;
+    tokens
+        .iter()
+        .map(|token| {
+            match token {
+                Token::DecimalPoint if zone == Zone::Integer => zone = Zone::Fraction,
+                Token::Exponent { .. } => zone = Zone::Exponent,
+                _ => {}
+            }
+            zone
+        })
+        .collect()
+}
+
+fn placeholders_in(tokens: &[Token], zones: &[Zone], wanted: Zone) -> Vec<char> {
+    tokens
+        .iter()
+        .zip(zones)
+        .filter_map(|(token, zone)| match token {
+            Token::Digit(c) if *zone == wanted => Some(*c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Commas between integer placeholders turn on grouping; commas after the
+/// last integer placeholder each divide the value by 1000.
+fn thousands_usage(tokens: &[Token], zones: &[Zone]) -> (bool, usize) {
+    let mut grouping: bool = false;
+    let mut scale: usize = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        if *token != Token::Thousands || zones[index] == Zone::Exponent {
+            continue;
+        }
+        let digit_before: bool = tokens[..index].iter().any(Token::is_digit);
+        let digit_after_in_integer: bool = tokens[index + 1..]
+            .iter()
+            .zip(&zones[index + 1..])
+            .any(|(token, zone)| token.is_digit() && *zone == Zone::Integer);
+        if digit_before && digit_after_in_integer && zones[index] == Zone::Integer {
+            grouping = true;
+        } else if digit_before {
+            scale += 1;
+        }
+    }
+    (grouping, scale)
+}
+
+fn decimal_exponent(value: f64) -> i32 {
+    let scientific: String = format!("{:.14e}", value.abs());
+    scientific
+        .split_once('e')
+        .and_then(|(_, exponent)| exponent.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Lay integer digits onto placeholders: missing leading digits become `0`
+/// for `0`, a space for `?`, and nothing for `#`; extra digits all show.
+/// `grouping` is the thousands separator, when the format groups.
+fn render_integer(digits: &str, placeholders: &[char], grouping: Option<char>) -> Vec<char> {
+    let digits: Vec<char> = digits.chars().collect();
+    let width: usize = digits.len().max(placeholders.len());
+    let mut rendered: Vec<char> = Vec::with_capacity(width + width / 3);
+    for position in (0..width).rev() {
+        let c: Option<char> = if position < digits.len() {
+            Some(digits[digits.len() - 1 - position])
+        } else {
+            match placeholders[placeholders.len() - 1 - position] {
+                '0' => Some('0'),
+                '?' => Some(' '),
+                _ => None,
+            }
+        };
+        let Some(c) = c else {
+            continue;
+        };
+        rendered.push(c);
+        if let Some(separator) = grouping
+            && position > 0
+            && position % 3 == 0
+            && c.is_ascii_digit()
+        {
+            rendered.push(separator);
+        }
+    }
+    rendered
+}
+
+/// Trailing zeros disappear under `#` and turn into spaces under `?`.
+fn render_fraction(digits: &str, placeholders: &[char]) -> Vec<char> {
+    let mut rendered: Vec<char> = digits.chars().collect();
+    for index in (0..rendered.len()).rev() {
+        if rendered[index] != '0' {
+            break;
+        }
+        match placeholders.get(index) {
+            Some('#') => {
+                rendered.pop();
+            }
+            Some('?') => rendered[index] = ' ',
+            _ => break,
+        }
+    }
+    rendered
+}
+
+// ── Fractions ───────────────────────────────────────────────────────────
+
+/// A `/` with digit placeholders on both sides makes a fraction format.
+fn fraction_slash_index(tokens: &[Token]) -> Option<usize> {
+    let slash: usize = tokens.iter().position(|t| *t == Token::FractionSlash)?;
+    let digit_before: bool = tokens[..slash].iter().any(Token::is_digit);
+    let denominator_after: bool = tokens[slash + 1..]
+        .iter()
+        .any(|t| t.is_digit() || matches!(t, Token::FixedDenominator(_)));
+    (digit_before && denominator_after).then_some(slash)
+}
+
+fn format_fraction_section(tokens: &[Token], slash: usize, magnitude: f64) -> String {
+    // Numerator placeholders are the digit run right before the slash;
+    // any earlier placeholders hold the whole part.
+    let numerator_start: usize = tokens[..slash]
+        .iter()
+        .rposition(|t| !t.is_digit())
+        .map_or(0, |index| index + 1);
+    let whole_placeholders: Vec<char> = tokens[..numerator_start]
+        .iter()
+        .filter_map(|t| match t {
+            Token::Digit(c) => Some(*c),
+            _ => None,
+        })
+        .collect();
+    let numerator_placeholders: Vec<char> = tokens[numerator_start..slash]
+        .iter()
+        .filter_map(|t| match t {
+            Token::Digit(c) => Some(*c),
+            _ => None,
+        })
+        .collect();
+    let fixed_denominator: Option<u32> = tokens[slash + 1..].iter().find_map(|t| match t {
+        Token::FixedDenominator(denominator) => Some(*denominator),
+        _ => None,
+    });
+    let denominator_placeholders: Vec<char> = tokens[slash + 1..]
+        .iter()
+        .take_while(|t| t.is_digit())
+        .filter_map(|t| match t {
+            Token::Digit(c) => Some(*c),
+            _ => None,
+        })
+        .collect();
+
+    let has_whole: bool = !whole_placeholders.is_empty();
+    let mut whole: f64 = if has_whole { magnitude.trunc() } else { 0.0 };
+    let remainder: f64 = magnitude - whole;
+    let (mut numerator, denominator): (u64, u64) = match fixed_denominator {
+        Some(denominator) => (
+            (remainder * denominator as f64).round() as u64,
+            denominator as u64,
+        ),
+        None => {
+            let max_denominator: u64 = 10u64
+                .pow(denominator_placeholders.len().clamp(1, 4) as u32)
+                .saturating_sub(1);
+            best_rational(remainder, max_denominator)
+        }
+    };
+    if has_whole && numerator >= denominator && denominator > 0 {
+        whole += (numerator / denominator) as f64;
+        numerator %= denominator;
+    }
+
+    let whole_text: String = if has_whole {
+        let digits: String = if whole == 0.0 {
+            String::new()
+        } else {
+            format!("{whole:.0}")
+        };
+        // A zero whole part still shows when there is no fraction to print.
+        let digits: String = if digits.is_empty() && numerator == 0 {
+            "0".to_string()
+        } else {
+            digits
+        };
+        render_integer(&digits, &whole_placeholders, None)
+            .into_iter()
+            .collect()
+    } else {
+        String::new()
+    };
+    let numerator_text: String =
+        render_integer(&numerator.to_string(), &numerator_placeholders, None)
+            .into_iter()
+            .collect();
+    let denominator_text: String = match fixed_denominator {
+        Some(_) => denominator.to_string(),
+        None => {
+            let digits: String = denominator.to_string();
+            let padding: usize = denominator_placeholders.len().saturating_sub(digits.len());
+            // Denominators pad on the right so the slashes line up.
+            format!("{digits}{}", " ".repeat(padding))
+        }
+    };
+    let fraction_text: String = if has_whole && numerator == 0 {
+        " ".repeat(numerator_text.len() + 1 + denominator_text.len())
+    } else {
+        format!("{numerator_text}/{denominator_text}")
+    };
+
+    let mut output = String::new();
+    let mut fraction_emitted: bool = false;
+    let mut whole_emitted: bool = false;
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Literal(literal) => {
+                // The separator between whole part and fraction collapses
+                // with the whole part when that part is blank.
+                let between: bool = index < numerator_start && whole_emitted;
+                if !(between && whole_text.trim().is_empty()) {
+                    output.push_str(literal);
+                }
+            }
+            // Each part prints once, at its first placeholder.
+            Token::Digit(_) if index < numerator_start && whole_emitted => {}
+            Token::Digit(_) if index < numerator_start => {
+                output.push_str(&whole_text);
+                whole_emitted = true;
+            }
+            Token::Digit(_) | Token::FractionSlash | Token::FixedDenominator(_)
+                if !fraction_emitted =>
+            {
+                output.push_str(&fraction_text);
+                fraction_emitted = true;
+            }
+            Token::Percent => output.push('%'),
+            _ => {}
+        }
+    }
+    output
+}
+
+/// Closest fraction to `value` (in 0..1) with a denominator up to `max`.
+fn best_rational(value: f64, max: u64) -> (u64, u64) {
+    let mut best: (u64, u64) = ((value.round()) as u64, 1);
+    let mut best_error: f64 = (value - value.round()).abs();
+    for denominator in 2..=max.max(1) {
+        let numerator: u64 = (value * denominator as f64).round() as u64;
+        let error: f64 = (value - numerator as f64 / denominator as f64).abs();
+        if error < best_error - 1e-12 {
+            best = (numerator, denominator);
+            best_error = error;
+        }
+    }
+    best
+}
+
+// ── Dates and times ─────────────────────────────────────────────────────
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const DAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// `date1904` moves the calendar date only; elapsed `[h]` times count from
+/// the serial itself.
+fn format_date_section(section: &Section, serial: f64, date1904: bool, decimal: char) -> String {
+    let offset_days: i64 = if date1904 { DATE1904_OFFSET_DAYS } else { 0 };
+    if !(0.0..MAX_DATE_SERIAL - offset_days as f64).contains(&serial) {
+        return INVALID_DATE_TEXT.to_string();
+    }
+    let subsecond_digits: usize = section
+        .tokens
+        .iter()
+        .find_map(|token| match token {
+            Token::SubSecond(digits) => Some(*digits),
+            _ => None,
+        })
+        .unwrap_or(0);
+    let ticks_per_second: u64 = 10u64.pow(subsecond_digits as u32);
+    let total_ticks: u64 = (serial * 86_400.0 * ticks_per_second as f64).round() as u64;
+    let total_seconds: u64 = total_ticks / ticks_per_second;
+    let subsecond: u64 = total_ticks % ticks_per_second;
+    let day_serial: i64 = (total_seconds / 86_400) as i64 + offset_days;
+    let second_of_day: u64 = total_seconds % 86_400;
+    let (year, month, day): (i64, u32, u32) = serial_to_civil(day_serial);
+    let weekday: usize = (day_serial + 6).rem_euclid(7) as usize;
+    let hour: u64 = second_of_day / 3600;
+    let minute: u64 = (second_of_day / 60) % 60;
+    let second: u64 = second_of_day % 60;
+    let twelve_hour: bool = section.has(|token| matches!(token, Token::AmPm { .. }));
+
+    let mut output = String::new();
+    for token in &section.tokens {
+        match *token {
+            Token::Literal(ref literal) => output.push_str(literal),
+            Token::Date(DatePart::Year, run) => {
+                if run <= 2 {
+                    output.push_str(&format!("{:02}", year.rem_euclid(100)));
+                } else {
+                    output.push_str(&format!("{year:04}"));
+                }
+            }
+            Token::Date(DatePart::Month, run) => match run {
+                1 => output.push_str(&month.to_string()),
+                2 => output.push_str(&format!("{month:02}")),
+                3 => output.push_str(&MONTH_NAMES[month as usize - 1][..3]),
+                5 => output.push_str(&MONTH_NAMES[month as usize - 1][..1]),
+                _ => output.push_str(MONTH_NAMES[month as usize - 1]),
+            },
+            Token::Date(DatePart::Day, run) => match run {
+                1 => output.push_str(&day.to_string()),
+                2 => output.push_str(&format!("{day:02}")),
+                3 => output.push_str(&DAY_NAMES[weekday][..3]),
+                _ => output.push_str(DAY_NAMES[weekday]),
+            },
+            Token::Date(DatePart::Hour, run) => {
+                let shown: u64 = if twelve_hour {
+                    (hour + 11) % 12 + 1
+                } else {
+                    hour
+                };
+                push_padded(&mut output, shown, run);
+            }
+            Token::Date(DatePart::Minute, run) => push_padded(&mut output, minute, run),
+            Token::Date(DatePart::Second, run) => push_padded(&mut output, second, run),
+            Token::Elapsed(DatePart::Hour) => {
+                output.push_str(&(total_seconds / 3600).to_string());
+            }
+            Token::Elapsed(DatePart::Minute) => {
+                output.push_str(&(total_seconds / 60).to_string());
+            }
+            Token::Elapsed(DatePart::Second) => output.push_str(&total_seconds.to_string()),
+            Token::SubSecond(digits) => {
+                output.push(decimal);
+                output.push_str(&format!("{subsecond:0digits$}"));
+            }
+            Token::AmPm { short, lowercase } => {
+                let marker: &str = match (hour < 12, short) {
+                    (true, false) => "AM",
+                    (false, false) => "PM",
+                    (true, true) => "A",
+                    (false, true) => "P",
+                };
+                if lowercase {
+                    output.push_str(&marker.to_ascii_lowercase());
+                } else {
+                    output.push_str(marker);
+                }
+            }
+            _ => {}
+        }
+    }
+    output
+}
+
+fn push_padded(output: &mut String, value: u64, run: usize) {
+    if run >= 2 {
+        output.push_str(&format!("{value:02}"));
+    } else {
+        output.push_str(&value.to_string());
+    }
+}
+
+/// Civil date of a 1900-system serial. Excel keeps Lotus 1-2-3's phantom
+/// 1900-02-29 (serial 60) and shows serial 0 as 1900-01-00.
+fn serial_to_civil(serial: i64) -> (i64, u32, u32) {
+    match serial {
+        0 => return (1900, 1, 0),
+        60 => return (1900, 2, 29),
+        _ => {}
+    }
+    let real_serial: i64 = if serial > 60 { serial - 1 } else { serial };
+    // Serial 1 is 1899-12-31 + 1 day; 1970-01-01 is serial 25569 (real 25568).
+    let days_since_epoch: i64 = real_serial - 25_568;
+    civil_from_days(days_since_epoch)
+}
+
+/// Howard Hinnant's `civil_from_days`
+/// (<http://howardhinnant.github.io/date_algorithms.html>).
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z: i64 = days + 719_468;
+    let era: i64 = z.div_euclid(146_097);
+    let doe: i64 = z.rem_euclid(146_097);
+    let yoe: i64 = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy: i64 = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp: i64 = (5 * doy + 2) / 153;
+    let day: u32 = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month: u32 = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year: i64 = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+#[path = "xlsx_numfmt_tests.rs"]
+mod tests;
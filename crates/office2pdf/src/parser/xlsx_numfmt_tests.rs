@@ -0,0 +1,184 @@
+use super::*;
+
+fn fmt(value: f64, code: &str) -> String {
+    format_number(value, code).text
+}
+
+#[test]
+fn test_general_matches_excel_display() {
+    assert_eq!(fmt(42.0, "General"), "42");
+    assert_eq!(fmt(-2.75, "General"), "-2.75");
+    assert_eq!(fmt(0.1 + 0.2, "General"), "0.3");
+    assert_eq!(fmt(1.0 / 3.0, "General"), "0.333333333");
+    assert_eq!(fmt(123_456_789_012.0, "General"), "1.23457E+11");
+    assert_eq!(fmt(0.000_000_000_12, "General"), "1.2E-10");
+}
+
+#[test]
+fn test_digit_placeholders_and_grouping() {
+    assert_eq!(fmt(1_234_567.0, "#,##0"), "1,234,567");
+    assert_eq!(fmt(107_310.6, "#,##0"), "107,311");
+    assert_eq!(fmt(39.1, "0.00"), "39.10");
+    assert_eq!(fmt(5.0, "000"), "005");
+    assert_eq!(fmt(0.5, "#.##"), ".5");
+    assert_eq!(fmt(2.5, "0.0#"), "2.5");
+    assert_eq!(fmt(1.5, "0.0?"), "1.5 ");
+    assert_eq!(fmt(5_551_234.0, "000-0000"), "555-1234");
+}
+
+#[test]
+fn test_rounds_displayed_decimal_half_away_from_zero() {
+    assert_eq!(fmt(1.065, "0%"), "107%");
+    assert_eq!(fmt(1.005, "0.00"), "1.01");
+    assert_eq!(fmt(-2.5, "0"), "-3");
+    assert_eq!(fmt(9.999, "0.00"), "10.00");
+}
+
+#[test]
+fn test_percent_and_thousands_scaling() {
+    assert_eq!(fmt(0.12345, "0.0%"), "12.3%");
+    assert_eq!(fmt(1_234_567.0, "#,##0,"), "1,235");
+    assert_eq!(fmt(1_234_567.0, "0.0,,\"M\""), "1.2M");
+}
+
+#[test]
+fn test_sections_choose_sign_and_zero_text() {
+    let code: &str = "#,##0.00;(#,##0.00);\"-\"";
+    assert_eq!(fmt(1234.5, code), "1,234.50");
+    assert_eq!(fmt(-1234.5, code), "(1,234.50)");
+    assert_eq!(fmt(0.0, code), "-");
+    // A single section prints its own minus sign.
+    assert_eq!(fmt(-7.0, "0.0"), "-7.0");
+}
+
+#[test]
+fn test_section_color_and_conditions() {
+    let negative: FormattedValue = format_number(-5.0, "0;[Red]0");
+    assert_eq!(negative.text, "5");
+    assert_eq!(negative.color, Some(Color::new(255, 0, 0)));
+    assert_eq!(format_number(5.0, "0;[Red]0").color, None);
+    assert_eq!(
+        format_number(1.0, "[Color10]0").color,
+        Some(Color::new(0, 128, 0))
+    );
+
+    let code: &str = "[>=100]\"big \"0;[<0]\"neg \"0;\"small \"0";
+    assert_eq!(fmt(150.0, code), "big 150");
+    assert_eq!(fmt(-3.0, code), "neg 3");
+    assert_eq!(fmt(7.0, code), "small 7");
+}
+
+#[test]
+fn test_literals_escapes_currency_and_padding() {
+    assert_eq!(fmt(1240.0, "#,##0.00\" €\""), "1,240.00 €");
+    assert_eq!(fmt(3.0, "0\\k"), "3k");
+    assert_eq!(fmt(12.0, "[$€-407]#,##0.00"), "€12.00");
+    assert_eq!(fmt(12.0, "_(0_)"), " 12 ");
+    assert_eq!(fmt(12.0, "$* #,##0"), "$12");
+}
+
+#[test]
+fn test_scientific_notation() {
+    assert_eq!(fmt(12_345.0, "0.00E+00"), "1.23E+04");
+    assert_eq!(fmt(0.000_123, "0.00E+00"), "1.23E-04");
+    assert_eq!(fmt(12_345.0, "0.0E-0"), "1.2E4");
+    assert_eq!(fmt(12_345.0, "##0.0E+0"), "12.3E+3");
+    assert_eq!(fmt(0.0, "0.00E+00"), "0.00E+00");
+}
+
+#[test]
+fn test_fractions() {
+    assert_eq!(fmt(1.5, "# ?/?"), "1 1/2");
+    assert_eq!(fmt(0.75, "?/?"), "3/4");
+    assert_eq!(fmt(2.3, "# ??/??"), "2  3/10");
+    assert_eq!(fmt(0.3, "# ?/8"), "2/8");
+    assert_eq!(fmt(2.0, "# ?/?"), "2    ");
+}
+
+#[test]
+fn test_dates_in_1900_system() {
+    assert_eq!(fmt(43567.0, "yyyy-mm-dd"), "2019-04-12");
+    assert_eq!(fmt(45306.0, "d-mmm-yy"), "15-Jan-24");
+    assert_eq!(
+        fmt(45306.0, "dddd, mmmm d, yyyy"),
+        "Monday, January 15, 2024"
+    );
+    assert_eq!(fmt(45306.0, "mmmmm"), "J");
+    assert_eq!(fmt(1.0, "m/d/yyyy"), "1/1/1900");
+    // Excel's phantom leap day and the dates after it.
+    assert_eq!(fmt(60.0, "yyyy-mm-dd"), "1900-02-29");
+    assert_eq!(fmt(61.0, "yyyy-mm-dd"), "1900-03-01");
+    assert_eq!(fmt(-1.0, "yyyy-mm-dd"), "########");
+}
+
+#[test]
+fn test_times_minutes_and_am_pm() {
+    let afternoon: f64 = 45306.0 + 14.0 / 24.0 + 5.0 / 1440.0 + 9.0 / 86_400.0;
+    assert_eq!(fmt(afternoon, "h:mm:ss"), "14:05:09");
+    assert_eq!(fmt(afternoon, "h:mm AM/PM"), "2:05 PM");
+    assert_eq!(fmt(afternoon, "hh:mm a/p"), "02:05 p");
+    assert_eq!(fmt(afternoon, "m/d/yy h:mm"), "1/15/24 14:05");
+    assert_eq!(fmt(0.5 / 86_400.0 + 1.25 / 86_400.0, "mm:ss.0"), "00:01.8");
+}
+
+#[test]
+fn test_elapsed_time() {
+    assert_eq!(fmt(1.5, "[h]:mm:ss"), "36:00:00");
+    assert_eq!(fmt(2.0 / 24.0, "[mm]"), "120");
+    assert_eq!(fmt(90.0 / 86_400.0, "[ss]"), "90");
+}
+
+#[test]
+fn test_text_section() {
+    let labeled: FormattedValue = format_text("north", "0;-0;0;\"Region: \"@");
+    assert_eq!(labeled.text, "Region: north");
+    assert_eq!(format_text("plain", "0.00").text, "plain");
+    assert_eq!(
+        format_text("x", "[Blue]@").color,
+        Some(Color::new(0, 0, 255))
+    );
+    // Numbers never use the text section.
+    assert_eq!(fmt(5.0, "0;-0;0;\"Region: \"@"), "5");
+}
+
+#[test]
+fn test_builtin_codes() {
+    assert_eq!(builtin_format_code(4), Some("#,##0.00"));
+    assert_eq!(builtin_format_code(14), Some("mm-dd-yy"));
+    assert_eq!(builtin_format_code(5), None);
+    assert_eq!(fmt(1234.5, builtin_format_code(4).unwrap()), "1,234.50");
+}
+
+#[test]
+fn test_dates_in_1904_system() {
+    let settings = FormatSettings {
+        date1904: true,
+        ..FormatSettings::default()
+    };
+    let fmt_1904 = |value: f64, code: &str| format_number_with(value, code, &settings).text;
+    assert_eq!(fmt_1904(0.0, "yyyy-mm-dd dddd"), "1904-01-01 Friday");
+    // The same date is 1462 days apart in the two systems.
+    assert_eq!(fmt_1904(45306.0 - 1462.0, "yyyy-mm-dd"), "2024-01-15");
+    // Durations do not move.
+    assert_eq!(fmt_1904(1.5, "[h]:mm"), "36:00");
+}
+
+#[test]
+fn test_locale_separators_and_short_date() {
+    let german = FormatSettings {
+        locale: NumberLocale::from_tag("de_DE"),
+        ..FormatSettings::default()
+    };
+    let fmt_de = |value: f64, code: &str| format_number_with(value, code, &german).text;
+    assert_eq!(fmt_de(1_234_567.891, "#,##0.00"), "1.234.567,89");
+    assert_eq!(fmt_de(0.125, "0.0%"), "12,5%");
+    assert_eq!(fmt_de(-2.75, "General"), "-2,75");
+    assert_eq!(german.format_code(14, "mm-dd-yy"), "dd.mm.yyyy");
+    assert_eq!(fmt_de(45306.0, &german.format_code(14, "")), "15.01.2024");
+    assert_eq!(german.format_code(4, ""), "#,##0.00");
+
+    let default = FormatSettings::default();
+    assert_eq!(default.format_code(14, "mm-dd-yy"), "mm-dd-yy");
+    assert_eq!(default.format_code(3, "General"), "#,##0");
+    assert_eq!(NumberLocale::from_tag("xx-YY"), None);
+}
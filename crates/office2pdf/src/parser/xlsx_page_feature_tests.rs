@@ -1,4 +1,5 @@
 use super::*;
+use std::collections::HashMap;
 
 // ----- US-029: Sheet selection tests -----
 
@@ -263,17 +264,197 @@ fn test_page_break_column_widths_preserved() {
     assert_eq!(tp0.table.column_widths, tp1.table.column_widths);
 }
 
+#[test]
+fn test_manual_column_breaks_split_into_pages() {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.set_name("Sheet1");
+        for (coord, value) in [("A1", "C1"), ("B1", "C2"), ("C1", "C3")] {
+            sheet.get_cell_mut(coord).set_value(value);
+        }
+        let mut brk = umya_spreadsheet::Break::default();
+        brk.set_id(2);
+        brk.set_manual_page_break(true);
+        sheet.get_column_breaks_mut().add_break_list(brk);
+    }
+    let mut cursor = Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    let parser = XlsxParser;
+    let (doc, _warnings) = parser
+        .parse(&cursor.into_inner(), &ConvertOptions::default())
+        .unwrap();
+
+    assert_eq!(doc.pages.len(), 2, "Column break should split into 2 pages");
+    let tp0 = get_sheet_page(&doc, 0);
+    let tp1 = get_sheet_page(&doc, 1);
+    assert_eq!(tp0.table.column_widths.len(), 2);
+    assert_eq!(cell_text(&tp1.table.rows[0].cells[0]), "C3");
+}
+
+#[test]
+fn test_print_area_uses_first_of_several_ranges() {
+    let data = build_xlsx_with_print_area(
+        &[("A1", "First"), ("B1", "Skip"), ("D1", "Second")],
+        "Sheet1!$A$1:$A$1,Sheet1!$D$1:$D$1",
+    );
+    let parser = XlsxParser;
+    let (doc, _warnings) = parser.parse(&data, &ConvertOptions::default()).unwrap();
+
+    let tp = get_sheet_page(&doc, 0);
+    assert_eq!(tp.table.rows[0].cells.len(), 1);
+    assert_eq!(cell_text(&tp.table.rows[0].cells[0]), "First");
+}
+
+#[test]
+fn test_page_setup_scale_shrinks_columns_and_rows() {
+    let build = |scale: Option<u32>| -> Vec<u8> {
+        let mut book = umya_spreadsheet::new_file();
+        {
+            let sheet = book.get_sheet_mut(&0).unwrap();
+            sheet.get_cell_mut("A1").set_value("Scaled");
+            sheet.get_cell_mut("B1").set_value("Sheet");
+            if let Some(scale) = scale {
+                sheet.get_page_setup_mut().set_scale(scale);
+            }
+        }
+        let mut cursor = Cursor::new(Vec::new());
+        umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+        cursor.into_inner()
+    };
+    let parser = XlsxParser;
+    let (full, _) = parser
+        .parse(&build(None), &ConvertOptions::default())
+        .unwrap();
+    let (half, _) = parser
+        .parse(&build(Some(50)), &ConvertOptions::default())
+        .unwrap();
+
+    let full_page = get_sheet_page(&full, 0);
+    let half_page = get_sheet_page(&half, 0);
+    for (full_width, half_width) in full_page
+        .table
+        .column_widths
+        .iter()
+        .zip(&half_page.table.column_widths)
+    {
+        assert!((full_width / 2.0 - half_width).abs() < 1e-6);
+    }
+    let full_height: f64 = full_page.table.rows[0].height.unwrap();
+    assert_eq!(half_page.table.rows[0].height, Some(full_height / 2.0));
+}
+
+fn build_xlsx_with_hidden_parts() -> Vec<u8> {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.set_name("Visible");
+        for (coord, value) in [
+            ("A1", "A1"),
+            ("B1", "Secret column"),
+            ("C1", "C1"),
+            ("A2", "Secret row"),
+            ("A3", "A3"),
+        ] {
+            sheet.get_cell_mut(coord).set_value(value);
+        }
+        sheet.get_row_dimension_mut(&2).set_hidden(true);
+        sheet.get_column_dimension_mut("B").set_hidden(true);
+    }
+    let secret = book.new_sheet("Secret").unwrap();
+    secret.get_cell_mut("A1").set_value("Secret sheet");
+    secret.set_sheet_state("hidden".to_string());
+    let mut cursor = Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    cursor.into_inner()
+}
+
+#[test]
+fn test_hidden_rows_columns_and_sheets_are_omitted() {
+    let parser = XlsxParser;
+    let (doc, warnings) = parser
+        .parse(&build_xlsx_with_hidden_parts(), &ConvertOptions::default())
+        .unwrap();
+
+    assert_eq!(doc.pages.len(), 1);
+    let tp = get_sheet_page(&doc, 0);
+    assert_eq!(tp.name, "Visible");
+    assert_eq!(tp.table.column_widths.len(), 2);
+    let texts: Vec<Vec<String>> = tp
+        .table
+        .rows
+        .iter()
+        .map(|row| row.cells.iter().map(cell_text).collect())
+        .collect();
+    assert_eq!(texts, vec![vec!["A1", "C1"], vec!["A3", ""]]);
+
+    let reasons: Vec<String> = warnings.iter().map(|w| w.to_string()).collect();
+    assert!(
+        reasons
+            .iter()
+            .any(|r| r.contains("hidden rows 2 and columns B on sheet 'Visible'")),
+        "{reasons:?}"
+    );
+    assert!(
+        reasons.iter().any(|r| r.contains("hidden sheet 'Secret'")),
+        "{reasons:?}"
+    );
+    assert!(
+        warnings
+            .iter()
+            .all(|w| w.severity() == crate::error::WarningSeverity::Info),
+        "{warnings:?}"
+    );
+}
+
+#[test]
+fn test_sheet_outline_lists_hidden_sheets_with_their_extent() {
+    let outline: Vec<crate::error::SheetInfo> =
+        read_sheet_outline(&build_xlsx_with_hidden_parts()).unwrap();
+
+    let summary: Vec<(&str, u32, u32, bool)> = outline
+        .iter()
+        .map(|sheet| (sheet.name.as_str(), sheet.rows, sheet.columns, sheet.hidden))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![("Visible", 3, 3, false), ("Secret", 1, 1, true)]
+    );
+}
+
+#[test]
+fn test_include_hidden_renders_everything() {
+    let opts = ConvertOptions {
+        include_hidden: true,
+        ..Default::default()
+    };
+    let parser = XlsxParser;
+    let (doc, warnings) = parser
+        .parse(&build_xlsx_with_hidden_parts(), &opts)
+        .unwrap();
+
+    assert_eq!(doc.pages.len(), 2);
+    let tp = get_sheet_page(&doc, 0);
+    assert_eq!(tp.table.rows.len(), 3);
+    assert_eq!(tp.table.column_widths.len(), 3);
+    assert!(warnings.iter().all(|w| !w.to_string().contains("hidden")));
+}
+
 // --- US-036: Sheet headers and footers ---
 
+fn parse_hf(format_str: &str) -> Option<HeaderFooter> {
+    parse_hf_format_string(format_str, &HfContext::default(), HfPart::Header)
+}
+
 #[test]
 fn test_parse_hf_format_string_empty() {
-    assert!(parse_hf_format_string("").is_none());
-    assert!(parse_hf_format_string("   ").is_none());
+    assert!(parse_hf("").is_none());
+    assert!(parse_hf("   ").is_none());
 }
 
 #[test]
 fn test_parse_hf_format_string_center_only() {
-    let hf = parse_hf_format_string("My Report").unwrap();
+    let hf = parse_hf("My Report").unwrap();
     assert_eq!(hf.paragraphs.len(), 1);
     assert_eq!(hf.paragraphs[0].style.alignment, Some(Alignment::Center));
     assert_eq!(hf.paragraphs[0].elements.len(), 1);
@@ -285,7 +466,7 @@ fn test_parse_hf_format_string_center_only() {
 
 #[test]
 fn test_parse_hf_format_string_left_center_right() {
-    let hf = parse_hf_format_string("&LLeft Text&CCenter Text&RRight Text").unwrap();
+    let hf = parse_hf("&LLeft Text&CCenter Text&RRight Text").unwrap();
     assert_eq!(hf.paragraphs.len(), 3);
 
     assert_eq!(hf.paragraphs[0].style.alignment, Some(Alignment::Left));
@@ -309,7 +490,7 @@ fn test_parse_hf_format_string_left_center_right() {
 
 #[test]
 fn test_parse_hf_format_string_page_numbers() {
-    let hf = parse_hf_format_string("&CPage &P of &N").unwrap();
+    let hf = parse_hf("&CPage &P of &N").unwrap();
     assert_eq!(hf.paragraphs.len(), 1);
     let elems = &hf.paragraphs[0].elements;
     assert_eq!(elems.len(), 4);
@@ -327,7 +508,7 @@ fn test_parse_hf_format_string_page_numbers() {
 
 #[test]
 fn test_parse_hf_format_string_escaped_ampersand() {
-    let hf = parse_hf_format_string("&CA && B").unwrap();
+    let hf = parse_hf("&CA && B").unwrap();
     assert_eq!(hf.paragraphs.len(), 1);
     match &hf.paragraphs[0].elements[0] {
         HFInline::Run(r) => assert_eq!(r.text, "A & B"),
@@ -337,7 +518,7 @@ fn test_parse_hf_format_string_escaped_ampersand() {
 
 #[test]
 fn test_parse_hf_format_string_font_codes_skipped() {
-    let hf = parse_hf_format_string(r#"&C&"Arial"&12Hello"#).unwrap();
+    let hf = parse_hf(r#"&C&"Arial"&12Hello"#).unwrap();
     assert_eq!(hf.paragraphs.len(), 1);
     match &hf.paragraphs[0].elements[0] {
         HFInline::Run(r) => assert_eq!(r.text, "Hello"),
@@ -468,7 +649,7 @@ fn test_parse_xlsx_without_metadata_no_crash() {
 fn test_hf_font_color_code_is_stripped() {
     // Excel emits &KRRGGBB for header colors; the six hex digits must not
     // leak into the text ("000000top center").
-    let hf = parse_hf_format_string(
+    let hf = parse_hf(
         r#"&L&"Calibri,Regular"&K000000top left&C&"Calibri,Regular"&K000000top center&R&"Calibri,Regular"&K000000top right"#,
     )
     .expect("header parsed");
@@ -487,3 +668,133 @@ fn test_hf_font_color_code_is_stripped() {
         .collect();
     assert_eq!(texts, vec!["top left", "top center", "top right"]);
 }
+
+#[test]
+fn test_hf_font_codes_style_following_text() {
+    let hf = parse_hf(r#"&L&"Arial,Bold Italic"&14&KFF0000Title&"-,Regular" plain&B!"#).unwrap();
+    let runs: Vec<&Run> = hf.paragraphs[0]
+        .elements
+        .iter()
+        .filter_map(|e| match e {
+            HFInline::Run(run) => Some(run),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(runs.len(), 3);
+    assert_eq!(runs[0].text, "Title");
+    assert_eq!(runs[0].style.font_family.as_deref(), Some("Arial"));
+    assert_eq!(runs[0].style.font_size, Some(14.0));
+    assert_eq!(runs[0].style.bold, Some(true));
+    assert_eq!(runs[0].style.italic, Some(true));
+    assert_eq!(runs[0].style.color, Some(Color::new(255, 0, 0)));
+    // "-" keeps the family; Regular clears bold and italic.
+    assert_eq!(runs[1].text, " plain");
+    assert_eq!(runs[1].style.font_family.as_deref(), Some("Arial"));
+    assert_eq!(runs[1].style.bold, Some(false));
+    // &B toggles bold back on.
+    assert_eq!(runs[2].style.bold, Some(true));
+}
+
+#[test]
+fn test_hf_style_resets_per_section_and_theme_color_is_skipped() {
+    let hf = parse_hf("&L&B&K01+000Bold&RPlain").unwrap();
+    let styles: Vec<(String, Option<bool>)> = hf
+        .paragraphs
+        .iter()
+        .map(|p| match &p.elements[0] {
+            HFInline::Run(run) => (run.text.clone(), run.style.bold),
+            _ => panic!("Expected Run"),
+        })
+        .collect();
+    assert_eq!(
+        styles,
+        vec![
+            ("Bold".to_string(), Some(true)),
+            ("Plain".to_string(), None)
+        ]
+    );
+}
+
+#[test]
+fn test_hf_date_time_and_sheet_name_fields() {
+    let context = HfContext {
+        sheet_name: "Budget".to_string(),
+        date_format: "m/d/yyyy".to_string(),
+        images: HashMap::new(),
+    };
+    let hf = parse_hf_format_string("&C&A printed &D &B&T", &context, HfPart::Footer).unwrap();
+    let elements: &[HFInline] = &hf.paragraphs[0].elements;
+    assert_eq!(elements.len(), 4);
+    assert!(matches!(&elements[0], HFInline::Run(r) if r.text == "Budget printed "));
+    let HFInline::DateTime(date) = &elements[1] else {
+        panic!("Expected a date field, got {:?}", elements[1]);
+    };
+    assert_eq!(date.format, "m/d/yyyy");
+    assert_eq!(date.style.bold, None);
+    let HFInline::DateTime(time) = &elements[3] else {
+        panic!("Expected a time field, got {:?}", elements[3]);
+    };
+    // Styled by the codes before it, like text.
+    assert_eq!(time.format, "h:mm AM/PM");
+    assert_eq!(time.style.bold, Some(true));
+}
+
+#[test]
+fn test_hf_context_uses_the_locale_short_date() {
+    let context = HfContext::for_sheet("Sheet1", HashMap::new(), &FormatSettings::default());
+    assert_eq!(context.date_format, "m/d/yyyy");
+
+    let german = FormatSettings {
+        locale: NumberLocale::from_tag("de-DE"),
+        ..FormatSettings::default()
+    };
+    let context = HfContext::for_sheet("Sheet1", HashMap::new(), &german);
+    assert_eq!(context.date_format, "dd.mm.yyyy");
+}
+
+#[test]
+fn test_hf_picture_code_uses_section_image() {
+    let logo = ImageData {
+        data: vec![0x89, b'P', b'N', b'G'],
+        format: ImageFormat::Png,
+        width: Some(40.0),
+        height: Some(20.0),
+        crop: None,
+        stroke: None,
+        alignment: None,
+        clip_shape: None,
+        shadow: None,
+    };
+    let context = HfContext {
+        images: HashMap::from([("RH".to_string(), logo)]),
+        ..HfContext::default()
+    };
+    let header = parse_hf_format_string("&L&G&R&G", &context, HfPart::Header).unwrap();
+    assert_eq!(header.paragraphs.len(), 1);
+    assert_eq!(header.paragraphs[0].style.alignment, Some(Alignment::Right));
+    assert!(matches!(
+        &header.paragraphs[0].elements[0],
+        HFInline::Image(image) if image.width == Some(40.0)
+    ));
+    // Footer pictures use the F-suffixed shapes, so none resolves here.
+    assert!(parse_hf_format_string("&R&G", &context, HfPart::Footer).is_none());
+}
+
+#[test]
+fn test_parse_vml_hf_shapes() {
+    let vml = r##"<xml xmlns:v="urn:schemas-microsoft-com:vml" xmlns:o="urn:schemas-microsoft-com:office:office">
+<v:shapetype id="_x0000_t75"/>
+<v:shape id="CH" o:spid="_x0000_s1025" type="#_x0000_t75" style="position:absolute;margin-left:0;margin-top:0;width:1in;height:24pt;z-index:1">
+<v:imagedata o:relid="rId1" o:title="logo"/>
+</v:shape>
+<v:shape id="LF" style="width:30px;height:15px"><v:imagedata o:relid="rId2"/></v:shape>
+</xml>"##;
+    let shapes = parse_vml_hf_shapes(vml);
+    assert_eq!(shapes.len(), 2);
+    assert_eq!(shapes[0].id, "CH");
+    assert_eq!(shapes[0].image_rid, "rId1");
+    assert_eq!(shapes[0].width_pt, Some(72.0));
+    assert_eq!(shapes[0].height_pt, Some(24.0));
+    assert_eq!(shapes[1].id, "LF");
+    assert_eq!(shapes[1].width_pt, Some(22.5));
+}
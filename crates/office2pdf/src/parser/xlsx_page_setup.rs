@@ -0,0 +1,200 @@
+//! Print scaling from a worksheet's page setup: a fixed `<pageSetup scale>`
+//! percentage, or "Fit to N pages wide by M tall" (`<pageSetUpPr fitToPage>`
+//! with `fitToWidth`/`fitToHeight`). umya does not keep the fit flag, so the
+//! settings are read from the raw worksheet XML.
+
+use std::collections::HashMap;
+
+use quick_xml::events::Event;
+
+use super::xlsx_cells::EXCEL_DEFAULT_ROW_HEIGHT_PT;
+use super::xlsx_drawing::{parse_rels_targets, parse_workbook_sheet_rids, read_zip_entry_string};
+use crate::ir::{Block, Insets, SheetPage, Table, TableRow};
+use crate::parser::xml_util::get_attr_str;
+
+/// Excel's bounds for the print scale.
+const MIN_PRINT_SCALE: f64 = 0.1;
+const MAX_PRINT_SCALE: f64 = 4.0;
+
+/// Excel's default body font size, for runs without an explicit size.
+const DEFAULT_CELL_FONT_SIZE_PT: f64 = 11.0;
+
+/// How a worksheet asks to be scaled when printed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct SheetScaling {
+    /// `<pageSetup scale>` in percent; only used when not fitting to pages.
+    pub(super) scale_percent: u32,
+    /// `<sheetPr><pageSetUpPr fitToPage="1"/>`.
+    pub(super) fit_to_page: bool,
+    /// Pages wide to fit into; 0 leaves the width unconstrained.
+    pub(super) fit_to_width: u32,
+    /// Pages tall to fit into; 0 leaves the height unconstrained.
+    pub(super) fit_to_height: u32,
+}
+
+impl Default for SheetScaling {
+    fn default() -> Self {
+        Self {
+            scale_percent: 100,
+            fit_to_page: false,
+            fit_to_width: 1,
+            fit_to_height: 1,
+        }
+    }
+}
+
+impl SheetScaling {
+    /// Uniform factor for a sheet with the given columns and rows, printed
+    /// on pages with the given printable area. Fitting never enlarges content.
+    pub(super) fn factor(
+        &self,
+        column_widths: &[f64],
+        rows: &[TableRow],
+        printable_width: f64,
+        printable_height: f64,
+    ) -> f64 {
+        if !self.fit_to_page {
+            return (f64::from(self.scale_percent) / 100.0).clamp(MIN_PRINT_SCALE, MAX_PRINT_SCALE);
+        }
+        let total_width: f64 = column_widths.iter().sum();
+        let total_height: f64 = rows
+            .iter()
+            .map(|row| row.height.unwrap_or(EXCEL_DEFAULT_ROW_HEIGHT_PT))
+            .sum();
+        let fit = |pages: u32, available: f64, total: f64| -> f64 {
+            if pages == 0 || total <= 0.0 {
+                f64::INFINITY
+            } else {
+                available * f64::from(pages) / total
+            }
+        };
+        let width_scale: f64 = fit(self.fit_to_width, printable_width, total_width);
+        let height_scale: f64 = fit(self.fit_to_height, printable_height, total_height);
+        width_scale.min(height_scale).min(1.0).max(MIN_PRINT_SCALE)
+    }
+}
+
+/// Read the print scaling of every worksheet, keyed by sheet name.
+pub(super) fn extract_sheet_scaling(data: &[u8]) -> HashMap<String, SheetScaling> {
+    let Ok(mut archive) = crate::parser::open_zip(data) else {
+        return HashMap::new();
+    };
+    let workbook_xml = read_zip_entry_string(&mut archive, "xl/workbook.xml");
+    let workbook_rels_xml = read_zip_entry_string(&mut archive, "xl/_rels/workbook.xml.rels");
+    let rid_to_target = parse_rels_targets(&workbook_rels_xml);
+
+    let mut result: HashMap<String, SheetScaling> = HashMap::new();
+    for (sheet_name, sheet_rid) in parse_workbook_sheet_rids(&workbook_xml) {
+        let Some(sheet_target) = rid_to_target.get(&sheet_rid) else {
+            continue;
+        };
+        let sheet_path: String = format!("xl/{}", sheet_target.trim_start_matches("/xl/"));
+        let sheet_xml = read_zip_entry_string(&mut archive, &sheet_path);
+        let scaling: SheetScaling = parse_sheet_scaling(&sheet_xml);
+        if scaling != SheetScaling::default() {
+            result.insert(sheet_name, scaling);
+        }
+    }
+    result
+}
+
+/// Parse the scaling settings from one worksheet's XML.
+pub(super) fn parse_sheet_scaling(xml: &str) -> SheetScaling {
+    let mut scaling = SheetScaling::default();
+    let mut reader = quick_xml::Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => match e.local_name().as_ref() {
+                b"pageSetUpPr" => {
+                    scaling.fit_to_page = get_attr_str(e, b"fitToPage")
+                        .is_some_and(|value| value == "1" || value == "true");
+                }
+                b"pageSetup" => {
+                    let number = |key: &[u8]| -> Option<u32> {
+                        get_attr_str(e, key).and_then(|value| value.parse().ok())
+                    };
+                    if let Some(scale) = number(b"scale").filter(|scale| *scale > 0) {
+                        scaling.scale_percent = scale;
+                    }
+                    if let Some(width) = number(b"fitToWidth") {
+                        scaling.fit_to_width = width;
+                    }
+                    if let Some(height) = number(b"fitToHeight") {
+                        scaling.fit_to_height = height;
+                    }
+                    // pageSetup follows sheetPr and the cell data; nothing
+                    // after it matters here.
+                    break;
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    scaling
+}
+
+/// Shrink or grow everything printed on a sheet page by `factor`: column
+/// widths, row heights, text, padding, and anchored drawings.
+pub(super) fn scale_sheet_page(page: &mut SheetPage, factor: f64) {
+    if (factor - 1.0).abs() < f64::EPSILON {
+        return;
+    }
+    scale_table(&mut page.table, factor);
+    for sheet_image in &mut page.images {
+        sheet_image.x_offset_pt *= factor;
+        sheet_image.y_offset_pt *= factor;
+        sheet_image.image.width = sheet_image.image.width.map(|width| width * factor);
+        sheet_image.image.height = sheet_image.image.height.map(|height| height * factor);
+    }
+    for text_box in &mut page.text_boxes {
+        text_box.x_offset_pt *= factor;
+        text_box.width *= factor;
+        text_box.height *= factor;
+        for paragraph in &mut text_box.paragraphs {
+            for run in &mut paragraph.runs {
+                let size: f64 = run.style.font_size.unwrap_or(DEFAULT_CELL_FONT_SIZE_PT);
+                run.style.font_size = Some(size * factor);
+            }
+        }
+    }
+}
+
+fn scale_table(table: &mut Table, factor: f64) {
+    for width in &mut table.column_widths {
+        *width *= factor;
+    }
+    table.default_cell_padding = table
+        .default_cell_padding
+        .map(|padding| scale_insets(padding, factor));
+    for row in &mut table.rows {
+        row.height = Some(row.height.unwrap_or(EXCEL_DEFAULT_ROW_HEIGHT_PT) * factor);
+        for cell in &mut row.cells {
+            cell.spill_width = cell.spill_width.map(|width| width * factor);
+            cell.padding = cell.padding.map(|padding| scale_insets(padding, factor));
+            for block in &mut cell.content {
+                let Block::Paragraph(paragraph) = block else {
+                    continue;
+                };
+                for run in &mut paragraph.runs {
+                    let size: f64 = run.style.font_size.unwrap_or(DEFAULT_CELL_FONT_SIZE_PT);
+                    run.style.font_size = Some(size * factor);
+                }
+            }
+        }
+    }
+}
+
+fn scale_insets(insets: Insets, factor: f64) -> Insets {
+    Insets {
+        top: insets.top * factor,
+        right: insets.right * factor,
+        bottom: insets.bottom * factor,
+        left: insets.left * factor,
+    }
+}
+
+#[cfg(test)]
+#[path = "xlsx_page_setup_tests.rs"]
+mod tests;
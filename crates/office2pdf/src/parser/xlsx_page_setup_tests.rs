@@ -0,0 +1,125 @@
+use super::*;
+use crate::ir::{Margins, PageSize, Paragraph, ParagraphStyle, Run, TableCell, TextStyle};
+
+fn sized_cell(font_size: Option<f64>) -> TableCell {
+    TableCell {
+        content: vec![Block::Paragraph(Paragraph {
+            style: ParagraphStyle::default(),
+            runs: vec![Run {
+                text: "x".to_string(),
+                style: TextStyle {
+                    font_size,
+                    ..TextStyle::default()
+                },
+                href: None,
+                footnote: None,
+            }],
+        })],
+        ..TableCell::default()
+    }
+}
+
+fn rows(heights: &[Option<f64>]) -> Vec<TableRow> {
+    heights
+        .iter()
+        .map(|height| TableRow {
+            cells: vec![sized_cell(None)],
+            height: *height,
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_fixed_scale() {
+    let scaling = parse_sheet_scaling(
+        r#"<worksheet><sheetData/><pageSetup paperSize="9" scale="75" orientation="landscape"/></worksheet>"#,
+    );
+    assert_eq!(scaling.scale_percent, 75);
+    assert!(!scaling.fit_to_page);
+}
+
+#[test]
+fn test_parse_fit_to_width_only() {
+    let scaling = parse_sheet_scaling(
+        r#"<worksheet><sheetPr><pageSetUpPr fitToPage="1"/></sheetPr><sheetData/><pageSetup scale="40" fitToWidth="1" fitToHeight="0"/></worksheet>"#,
+    );
+    assert!(scaling.fit_to_page);
+    assert_eq!(scaling.fit_to_width, 1);
+    assert_eq!(scaling.fit_to_height, 0);
+}
+
+#[test]
+fn test_parse_defaults_without_page_setup() {
+    assert_eq!(
+        parse_sheet_scaling("<worksheet><sheetData/></worksheet>"),
+        SheetScaling::default()
+    );
+}
+
+#[test]
+fn test_fixed_scale_is_clamped() {
+    let scaling = SheetScaling {
+        scale_percent: 5,
+        ..SheetScaling::default()
+    };
+    assert_eq!(scaling.factor(&[100.0], &[], 400.0, 700.0), 0.1);
+}
+
+#[test]
+fn test_fit_to_width_shrinks_wide_sheet() {
+    let scaling = SheetScaling {
+        fit_to_page: true,
+        fit_to_height: 0,
+        ..SheetScaling::default()
+    };
+    // 800pt of columns into 400pt of printable width; height is unconstrained.
+    let factor: f64 = scaling.factor(&[500.0, 300.0], &rows(&[Some(5000.0)]), 400.0, 700.0);
+    assert!((factor - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_fit_to_page_uses_tighter_axis_and_never_enlarges() {
+    let scaling = SheetScaling {
+        fit_to_page: true,
+        ..SheetScaling::default()
+    };
+    // Ten default-height rows are 150pt tall; the height limit of 75pt wins.
+    let factor: f64 = scaling.factor(&[200.0], &rows(&[None; 10]), 400.0, 75.0);
+    assert!((factor - 0.5).abs() < 1e-9);
+    assert_eq!(scaling.factor(&[10.0], &rows(&[None]), 400.0, 700.0), 1.0);
+}
+
+#[test]
+fn test_scale_sheet_page_scales_widths_heights_and_fonts() {
+    let mut page = SheetPage {
+        name: "Sheet1".to_string(),
+        size: PageSize::default(),
+        margins: Margins::default(),
+        table: Table {
+            rows: vec![TableRow {
+                cells: vec![sized_cell(Some(20.0)), sized_cell(None)],
+                height: None,
+            }],
+            column_widths: vec![100.0, 60.0],
+            ..Table::default()
+        },
+        header: None,
+        footer: None,
+        charts: vec![],
+        images: vec![],
+        text_boxes: vec![],
+    };
+    scale_sheet_page(&mut page, 0.5);
+
+    assert_eq!(page.table.column_widths, vec![50.0, 30.0]);
+    assert_eq!(page.table.rows[0].height, Some(7.5));
+    let font_sizes: Vec<Option<f64>> = page.table.rows[0]
+        .cells
+        .iter()
+        .map(|cell| match &cell.content[0] {
+            Block::Paragraph(paragraph) => paragraph.runs[0].style.font_size,
+            _ => None,
+        })
+        .collect();
+    assert_eq!(font_sizes, vec![Some(10.0), Some(5.5)]);
+}
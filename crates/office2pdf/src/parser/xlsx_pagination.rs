@@ -2,9 +2,15 @@
 //!
 //! Excel prints columns that overflow the page width on subsequent pages
 //! (default order: down, then over). office2pdf previously clipped them at
-//! the right page edge, silently losing content.
+//! the right page edge, silently losing content. With
+//! `ConvertOptions::xlsx_label_column_pages` each of those pages names the
+//! columns it shows ("Columns H–P") in its header.
 
-use crate::ir::{SheetPage, Table, TableCell, TableRow};
+use crate::error::{ConvertWarning, WarningLocation};
+use crate::ir::{
+    Alignment, Color, HFInline, HeaderFooter, HeaderFooterParagraph, ParagraphStyle, Run,
+    SheetPage, Table, TableCell, TableRow, TextStyle,
+};
 
 /// Upper bound on overflow pages per sheet chunk. Pathological sheets (used
 /// ranges thousands of columns wide) would otherwise explode into thousands
@@ -16,13 +22,25 @@ const MAX_COLUMN_GROUPS: usize = 12;
 /// Returns the page unchanged when everything fits. `title_columns` is the
 /// 0-based inclusive-exclusive range of print-title columns (from
 /// `_xlnm.Print_Titles`) repeated at the left of every overflow page.
+/// `column_breaks` are manual page breaks (`<colBreaks>`) as 0-based column
+/// indices that start a new page; width packing continues within each run.
+/// `column_labels` holds the column letters of the table's columns (empty
+/// for inserted columns such as row headings); when given, every page of a
+/// split sheet is labeled with the columns it shows.
 pub(super) fn split_sheet_page_by_width(
     page: SheetPage,
     title_columns: Option<(usize, usize)>,
+    column_breaks: &[usize],
+    column_labels: Option<&[String]>,
+    warnings: &mut Vec<ConvertWarning>,
 ) -> Vec<SheetPage> {
     let printable_width: f64 = page.size.width - page.margins.left - page.margins.right;
     let total_width: f64 = page.table.column_widths.iter().sum();
-    if total_width <= printable_width || page.table.column_widths.len() <= 1 {
+    let column_count: usize = page.table.column_widths.len();
+    let has_manual_breaks: bool = column_breaks
+        .iter()
+        .any(|index| *index > 0 && *index < column_count);
+    if (total_width <= printable_width && !has_manual_breaks) || column_count <= 1 {
         return vec![page];
     }
 
@@ -36,16 +54,44 @@ pub(super) fn split_sheet_page_by_width(
     let packing_width: f64 = (printable_width - title_width)
         .max(page.table.column_widths.iter().cloned().fold(0.0, f64::max));
 
-    let mut groups: Vec<(usize, usize)> = column_groups(&page.table.column_widths, packing_width);
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    let mut run_start: usize = 0;
+    let run_ends = column_breaks
+        .iter()
+        .copied()
+        .filter(|index| *index > 0 && *index < column_count)
+        .chain(std::iter::once(column_count));
+    for run_end in run_ends {
+        if run_end <= run_start {
+            continue;
+        }
+        groups.extend(
+            column_groups(&page.table.column_widths[run_start..run_end], packing_width)
+                .into_iter()
+                .map(|(start, end)| (run_start + start, run_start + end)),
+        );
+        run_start = run_end;
+    }
     if groups.len() <= 1 {
         return vec![page];
     }
     if groups.len() > MAX_COLUMN_GROUPS {
-        let column_count = page.table.column_widths.len();
         groups.truncate(MAX_COLUMN_GROUPS);
         if let Some(last) = groups.last_mut() {
             last.1 = column_count;
         }
+        let warning = ConvertWarning::PartialElement {
+            format: "XLSX".to_string(),
+            element: format!("sheet '{}'", page.name),
+            detail: format!(
+                "wider than {MAX_COLUMN_GROUPS} pages; the remaining columns are clipped on the last page"
+            ),
+            location: Some(WarningLocation::Sheet(page.name.clone())),
+        };
+        // Every row page of the sheet splits the same way.
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
     }
 
     let title_table: Option<Table> =
@@ -60,12 +106,17 @@ pub(super) fn split_sheet_page_by_width(
         {
             table = prepend_title_columns(title_table, table);
         }
+        let header: Option<HeaderFooter> =
+            match column_labels.and_then(|labels| column_span_label(labels.get(start..end)?)) {
+                Some(label) => Some(with_column_label(page.header.clone(), label)),
+                None => page.header.clone(),
+            };
         result.push(SheetPage {
             name: page.name.clone(),
             size: page.size,
             margins: page.margins,
             table,
-            header: page.header.clone(),
+            header,
             footer: page.footer.clone(),
             // Charts and images anchor to rows of the first column group only.
             charts: if index == 0 {
@@ -88,6 +139,44 @@ pub(super) fn split_sheet_page_by_width(
     result
 }
 
+/// "Columns H–P" for a page showing columns H through P, "Column H" for
+/// one column.
+fn column_span_label(labels: &[String]) -> Option<String> {
+    let mut letters = labels.iter().filter(|label| !label.is_empty());
+    let first: &String = letters.next()?;
+    Some(match letters.next_back() {
+        Some(last) => format!("Columns {first}–{last}"),
+        None => format!("Column {first}"),
+    })
+}
+
+/// Add a small right-aligned column label below the sheet's own header.
+fn with_column_label(header: Option<HeaderFooter>, label: String) -> HeaderFooter {
+    let mut header: HeaderFooter = header.unwrap_or(HeaderFooter {
+        paragraphs: Vec::new(),
+        distance_from_edge: None,
+    });
+    header.paragraphs.push(HeaderFooterParagraph {
+        style: ParagraphStyle {
+            alignment: Some(Alignment::Right),
+            ..ParagraphStyle::default()
+        },
+        elements: vec![HFInline::Run(Run {
+            text: label,
+            style: TextStyle {
+                font_size: Some(8.0),
+                color: Some(Color::new(0x59, 0x59, 0x59)),
+                ..TextStyle::default()
+            },
+            href: None,
+            footnote: None,
+        })],
+        border: None,
+        frame: None,
+    });
+    header
+}
+
 /// Concatenate the repeated title columns before a column group's table.
 fn prepend_title_columns(title_table: &Table, group_table: Table) -> Table {
     let mut column_widths: Vec<f64> = title_table.column_widths.clone();
@@ -22,6 +22,9 @@ fn cell(text: &str) -> TableCell {
         spill_width: None,
         vertical_align: None,
         padding: None,
+        no_wrap: false,
+        shrink_to_fit: false,
+        text_rotation: None,
     }
 }
 
@@ -81,7 +84,7 @@ fn test_narrow_sheet_stays_single_page() {
             height: None,
         }],
     );
-    let pages = split_sheet_page_by_width(page, None);
+    let pages = split_sheet_page_by_width(page, None, &[], None, &mut Vec::new());
     assert_eq!(pages.len(), 1);
 }
 
@@ -95,7 +98,7 @@ fn test_wide_sheet_splits_into_column_groups() {
             height: None,
         }],
     );
-    let pages = split_sheet_page_by_width(page, None);
+    let pages = split_sheet_page_by_width(page, None, &[], None, &mut Vec::new());
     assert_eq!(pages.len(), 3);
     assert_eq!(pages[0].table.column_widths.len(), 2);
     assert_eq!(pages[1].table.column_widths.len(), 2);
@@ -121,7 +124,7 @@ fn test_merge_straddling_boundary_truncates_and_blanks_continuation() {
             height: None,
         }],
     );
-    let pages = split_sheet_page_by_width(page, None);
+    let pages = split_sheet_page_by_width(page, None, &[], None, &mut Vec::new());
     assert_eq!(pages.len(), 2);
 
     let first_row = &pages[0].table.rows[0];
@@ -151,9 +154,10 @@ fn test_charts_stay_on_first_column_group() {
             title: None,
             categories: vec![],
             series: vec![],
+            grouping: crate::ir::ChartGrouping::Clustered,
         },
     )];
-    let pages = split_sheet_page_by_width(page, None);
+    let pages = split_sheet_page_by_width(page, None, &[], None, &mut Vec::new());
     assert_eq!(pages.len(), 2);
     assert_eq!(pages[0].charts.len(), 1);
     assert!(pages[1].charts.is_empty());
@@ -171,8 +175,135 @@ fn test_pathologically_wide_sheet_is_capped() {
             height: None,
         }],
     );
-    let pages = split_sheet_page_by_width(page, None);
+    let mut warnings: Vec<ConvertWarning> = Vec::new();
+    let pages = split_sheet_page_by_width(page, None, &[], None, &mut warnings);
     assert_eq!(pages.len(), 12);
     let total_columns: usize = pages.iter().map(|p| p.table.column_widths.len()).sum();
     assert_eq!(total_columns, 100);
+    // The clipping is reported rather than silent.
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn test_manual_column_breaks_start_new_pages() {
+    // Everything fits the 400pt printable width, but a manual break after
+    // column B forces C and D onto their own page.
+    let page = make_page(
+        vec![80.0; 4],
+        vec![TableRow {
+            cells: vec![cell("A"), cell("B"), cell("C"), cell("D")],
+            height: None,
+        }],
+    );
+    let pages = split_sheet_page_by_width(page, None, &[2], None, &mut Vec::new());
+    assert_eq!(pages.len(), 2);
+    assert_eq!(cell_text(&pages[0].table.rows[0].cells[1]), "B");
+    assert_eq!(cell_text(&pages[1].table.rows[0].cells[0]), "C");
+    assert_eq!(pages[1].table.column_widths.len(), 2);
+}
+
+#[test]
+fn test_width_packing_continues_after_manual_break() {
+    // Break after A; the remaining 150pt columns still pack two per page.
+    let cells: Vec<TableCell> = (0..5).map(|i| cell(&format!("c{i}"))).collect();
+    let page = make_page(
+        vec![150.0; 5],
+        vec![TableRow {
+            cells,
+            height: None,
+        }],
+    );
+    let pages = split_sheet_page_by_width(page, None, &[1], None, &mut Vec::new());
+    let widths: Vec<usize> = pages.iter().map(|p| p.table.column_widths.len()).collect();
+    assert_eq!(widths, vec![1, 2, 2]);
+}
+
+fn header_text(page: &SheetPage) -> Vec<String> {
+    page.header
+        .iter()
+        .flat_map(|header| &header.paragraphs)
+        .map(|paragraph| {
+            paragraph
+                .elements
+                .iter()
+                .map(|element| match element {
+                    HFInline::Run(run) => run.text.as_str(),
+                    _ => "",
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_split_pages_are_labeled_with_their_columns() {
+    // A row-number heading column, then A..F; the heading and A repeat as
+    // title columns, leaving 320pt of the 400pt page for the rest.
+    let labels: Vec<String> = ["", "A", "B", "C", "D", "E", "F"]
+        .iter()
+        .map(|label| label.to_string())
+        .collect();
+    let cells: Vec<TableCell> = labels.iter().map(|label| cell(label)).collect();
+    let page = make_page(
+        vec![20.0, 60.0, 100.0, 100.0, 100.0, 100.0, 250.0],
+        vec![TableRow {
+            cells,
+            height: None,
+        }],
+    );
+    let pages = split_sheet_page_by_width(page, Some((0, 2)), &[], Some(&labels), &mut Vec::new());
+    let headers: Vec<Vec<String>> = pages.iter().map(header_text).collect();
+    assert_eq!(
+        headers,
+        vec![
+            vec!["Columns A–C".to_string()],
+            vec!["Columns D–E".to_string()],
+            vec!["Column F".to_string()],
+        ]
+    );
+    // The title columns lead every later page.
+    assert_eq!(cell_text(&pages[2].table.rows[0].cells[1]), "A");
+
+    // A sheet that fits keeps its header as is.
+    let page = make_page(vec![100.0], vec![]);
+    let pages = split_sheet_page_by_width(page, None, &[], Some(&labels[1..2]), &mut Vec::new());
+    assert!(pages[0].header.is_none());
+}
+
+#[test]
+fn test_row_label_columns_repeat_on_overflow_pages() {
+    use crate::parser::Parser;
+
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut((1, 1)).set_value("Region");
+        for col in 2..=40u32 {
+            sheet.get_cell_mut((col, 1)).set_value(format!("Q{col}"));
+        }
+    }
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    let options = crate::config::ConvertOptions {
+        xlsx_row_label_columns: 1,
+        xlsx_label_column_pages: true,
+        ..crate::config::ConvertOptions::default()
+    };
+    let (doc, _warnings) = crate::parser::xlsx::XlsxParser
+        .parse(&cursor.into_inner(), &options)
+        .unwrap();
+    let pages: Vec<&SheetPage> = doc
+        .pages
+        .iter()
+        .map(|page| match page {
+            crate::ir::Page::Sheet(page) => page,
+            _ => panic!("expected sheet pages"),
+        })
+        .collect();
+    assert!(pages.len() > 1);
+    for page in &pages[1..] {
+        assert_eq!(cell_text(&page.table.rows[0].cells[0]), "Region");
+    }
+    assert!(header_text(pages[0])[0].starts_with("Columns A–"));
+    assert!(header_text(pages[pages.len() - 1])[0].ends_with("–AN"));
 }
@@ -0,0 +1,847 @@
+//! Pivot tables rebuilt from their cache. A pivot table's cells hold
+//! whatever its last refresh wrote, which is stale or empty in workbooks
+//! produced by tools that only update the cache. The grid is recomputed
+//! from `pivotCacheRecords` and the pivot definition, in Excel's default
+//! compact layout, and written over the pivot's cells before the sheet is
+//! rendered.
+
+use std::collections::{HashMap, HashSet};
+
+use quick_xml::events::{BytesStart, Event};
+
+use super::xlsx_drawing::{
+    parse_rels_by_type, parse_rels_targets, parse_workbook_sheet_rids, read_zip_entry_string,
+    resolve_relative_xl_path,
+};
+use super::xlsx_numfmt::{builtin_format_code, format_general};
+use crate::parser::xml_util::get_attr_str;
+
+/// Indentation of one nesting level in the compact row-label column. Plain
+/// spaces would collapse at render time.
+const LEVEL_INDENT: &str = "\u{00A0}\u{00A0}";
+
+/// A value of the pivot cache: a shared item or a field of a record.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum CacheValue {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    /// An ISO timestamp such as `2024-01-31T00:00:00`.
+    Date(String),
+    Error(String),
+    Missing,
+}
+
+impl CacheValue {
+    /// Read `<n v>`, `<s v>`, `<b v>`, `<d v>`, `<e v>` or `<m/>`.
+    fn from_element(element: &BytesStart) -> Option<Self> {
+        let value = || get_attr_str(element, b"v").unwrap_or_default();
+        Some(match element.local_name().as_ref() {
+            b"n" => Self::Number(value().parse().ok()?),
+            b"s" => Self::Text(value()),
+            b"b" => Self::Bool(matches!(value().as_str(), "1" | "true")),
+            b"d" => Self::Date(value()),
+            b"e" => Self::Error(value()),
+            b"m" => Self::Missing,
+            _ => return None,
+        })
+    }
+
+    /// The label Excel shows for the value as a row or column item.
+    fn label(&self) -> String {
+        match self {
+            Self::Number(number) => format_general(*number),
+            Self::Text(text) | Self::Error(text) => text.clone(),
+            Self::Bool(true) => "TRUE".to_string(),
+            Self::Bool(false) => "FALSE".to_string(),
+            Self::Date(date) => date.split('T').next().unwrap_or(date).to_string(),
+            Self::Missing => "(blank)".to_string(),
+        }
+    }
+}
+
+/// The cached source data of a pivot table.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct PivotCache {
+    /// Each cache field's shared items, which `<x v>` record values and
+    /// pivot field items index into.
+    pub(super) shared_items: Vec<Vec<CacheValue>>,
+    /// The source rows, one value per cache field.
+    pub(super) records: Vec<Vec<CacheValue>>,
+}
+
+/// How a data field summarizes its values (`dataField@subtotal`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Aggregate {
+    Sum,
+    Count,
+    Average,
+    Max,
+    Min,
+    Product,
+    CountNums,
+    StdDev,
+    StdDevP,
+    Var,
+    VarP,
+}
+
+impl Aggregate {
+    fn parse(value: &str) -> Self {
+        match value {
+            "count" => Self::Count,
+            "average" => Self::Average,
+            "max" => Self::Max,
+            "min" => Self::Min,
+            "product" => Self::Product,
+            "countNums" => Self::CountNums,
+            "stdDev" => Self::StdDev,
+            "stdDevp" => Self::StdDevP,
+            "var" => Self::Var,
+            "varp" => Self::VarP,
+            _ => Self::Sum,
+        }
+    }
+
+    /// Summarize the data field values of a group of records. None when the
+    /// group has nothing to summarize, which Excel leaves blank.
+    fn apply(self, values: &[&CacheValue]) -> Option<f64> {
+        let present: Vec<&CacheValue> = values
+            .iter()
+            .copied()
+            .filter(|value| **value != CacheValue::Missing)
+            .collect();
+        let numbers: Vec<f64> = present
+            .iter()
+            .filter_map(|value| match value {
+                CacheValue::Number(number) => Some(*number),
+                _ => None,
+            })
+            .collect();
+        let count: f64 = numbers.len() as f64;
+        let mean: f64 = numbers.iter().sum::<f64>() / count.max(1.0);
+        let squares = || numbers.iter().map(|n| (n - mean).powi(2)).sum::<f64>();
+        match self {
+            Self::Count => (!present.is_empty()).then_some(present.len() as f64),
+            Self::CountNums => (!present.is_empty()).then_some(count),
+            Self::Sum => (!present.is_empty()).then(|| numbers.iter().sum()),
+            Self::Product => (!numbers.is_empty()).then(|| numbers.iter().product()),
+            Self::Average => (!numbers.is_empty()).then_some(mean),
+            Self::Max => numbers.iter().copied().reduce(f64::max),
+            Self::Min => numbers.iter().copied().reduce(f64::min),
+            Self::StdDev => (numbers.len() > 1).then(|| (squares() / (count - 1.0)).sqrt()),
+            Self::StdDevP => (!numbers.is_empty()).then(|| (squares() / count).sqrt()),
+            Self::Var => (numbers.len() > 1).then(|| squares() / (count - 1.0)),
+            Self::VarP => (!numbers.is_empty()).then(|| squares() / count),
+        }
+    }
+}
+
+/// A field of the pivot definition, parallel to the cache fields.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct PivotField {
+    /// Display order of the field's items as shared-item indices, with
+    /// whether each is filtered out.
+    pub(super) items: Vec<(usize, bool)>,
+    /// Whether group rows of this field show subtotals.
+    pub(super) subtotals: bool,
+}
+
+impl Default for PivotField {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            subtotals: true,
+        }
+    }
+}
+
+/// A summarized field of the values area.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct DataField {
+    pub(super) name: String,
+    pub(super) field: usize,
+    pub(super) aggregate: Aggregate,
+    pub(super) num_fmt_id: Option<u32>,
+}
+
+/// What `xl/pivotTables/pivotTableN.xml` says about the layout.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct PivotDefinition {
+    /// The body range written at the last refresh, as
+    /// `(first_col, first_row, last_col, last_row)`, 1-indexed.
+    pub(super) location: (u32, u32, u32, u32),
+    pub(super) fields: Vec<PivotField>,
+    pub(super) row_fields: Vec<usize>,
+    pub(super) col_fields: Vec<usize>,
+    /// Report filters as `(field, item)`, `item` indexing the field's items.
+    pub(super) page_filters: Vec<(usize, usize)>,
+    pub(super) data_fields: Vec<DataField>,
+    /// The "Grand Total" column on the right.
+    pub(super) row_grand_totals: bool,
+    /// The "Grand Total" row at the bottom.
+    pub(super) col_grand_totals: bool,
+    pub(super) row_header_caption: String,
+    pub(super) col_header_caption: String,
+    pub(super) grand_total_caption: String,
+}
+
+/// One cell of a rebuilt pivot grid.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct GridCell {
+    pub(super) value: GridValue,
+    /// Headers, group rows and totals print bold in the default style.
+    pub(super) bold: bool,
+    pub(super) num_fmt_id: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum GridValue {
+    Text(String),
+    Number(f64),
+    Empty,
+}
+
+impl GridCell {
+    fn text(text: impl Into<String>, bold: bool) -> Self {
+        Self {
+            value: GridValue::Text(text.into()),
+            bold,
+            num_fmt_id: None,
+        }
+    }
+
+    fn empty(bold: bool) -> Self {
+        Self {
+            value: GridValue::Empty,
+            bold,
+            num_fmt_id: None,
+        }
+    }
+}
+
+/// A column of the values area: the column-field labels it is limited to
+/// (empty for a grand total) and the data field it summarizes.
+struct ValueColumn {
+    labels: Vec<String>,
+    data: usize,
+}
+
+/// Rebuild the pivot grid, header rows first, in compact form: every row
+/// field shares one label column, nested items indented under their group.
+pub(super) fn build_pivot_grid(
+    definition: &PivotDefinition,
+    cache: &PivotCache,
+) -> Vec<Vec<GridCell>> {
+    if definition.data_fields.is_empty() {
+        return Vec::new();
+    }
+    let labels = |record: &[CacheValue], field: usize| -> String {
+        record.get(field).map(CacheValue::label).unwrap_or_default()
+    };
+    let item_label = |field: usize, item: usize| -> Option<String> {
+        let (shared_index, _) = *definition.fields.get(field)?.items.get(item)?;
+        Some(cache.shared_items.get(field)?.get(shared_index)?.label())
+    };
+    let hidden_labels: Vec<HashSet<String>> = (0..definition.fields.len())
+        .map(|field| {
+            (0..definition.fields[field].items.len())
+                .filter(|item| definition.fields[field].items[*item].1)
+                .filter_map(|item| item_label(field, item))
+                .collect()
+        })
+        .collect();
+    let page_labels: Vec<(usize, String)> = definition
+        .page_filters
+        .iter()
+        .filter_map(|(field, item)| Some((*field, item_label(*field, *item)?)))
+        .collect();
+    let axis_fields: Vec<usize> = definition
+        .row_fields
+        .iter()
+        .chain(&definition.col_fields)
+        .copied()
+        .collect();
+    let records: Vec<&Vec<CacheValue>> = cache
+        .records
+        .iter()
+        .filter(|record| {
+            page_labels
+                .iter()
+                .all(|(field, label)| labels(record, *field) == *label)
+                && axis_fields.iter().all(|field| {
+                    hidden_labels
+                        .get(*field)
+                        .is_none_or(|hidden| !hidden.contains(&labels(record, *field)))
+                })
+        })
+        .collect();
+
+    // Item order of a field: its pivot items, then values the definition
+    // does not list in order of appearance.
+    let ordered_labels = |field: usize, records: &[&Vec<CacheValue>]| -> Vec<String> {
+        let present: HashSet<String> = records.iter().map(|r| labels(r, field)).collect();
+        let mut order: Vec<String> = (0..definition.fields.get(field).map_or(0, |f| f.items.len()))
+            .filter_map(|item| item_label(field, item))
+            .collect();
+        for record in records {
+            let label: String = labels(record, field);
+            if !order.contains(&label) {
+                order.push(label);
+            }
+        }
+        order.retain(|label| present.contains(label));
+        order.dedup();
+        order
+    };
+
+    let data_count: usize = definition.data_fields.len();
+    let mut columns: Vec<ValueColumn> = Vec::new();
+    let mut column_keys: Vec<Vec<String>> = Vec::new();
+    if definition.col_fields.is_empty() {
+        columns.extend((0..data_count).map(|data| ValueColumn {
+            labels: Vec::new(),
+            data,
+        }));
+    } else {
+        collect_keys(
+            &definition.col_fields,
+            &records,
+            &ordered_labels,
+            &labels,
+            Vec::new(),
+            &mut column_keys,
+        );
+        for key in &column_keys {
+            columns.extend((0..data_count).map(|data| ValueColumn {
+                labels: key.clone(),
+                data,
+            }));
+        }
+        if definition.row_grand_totals {
+            columns.extend((0..data_count).map(|data| ValueColumn {
+                labels: Vec::new(),
+                data,
+            }));
+        }
+    }
+
+    let has_labels: bool = !definition.row_fields.is_empty();
+    let summarize = |records: &[&Vec<CacheValue>], column: &ValueColumn, bold: bool| -> GridCell {
+        let data_field: &DataField = &definition.data_fields[column.data];
+        let values: Vec<&CacheValue> = records
+            .iter()
+            .filter(|record| {
+                definition
+                    .col_fields
+                    .iter()
+                    .zip(&column.labels)
+                    .all(|(field, label)| labels(record, *field) == *label)
+            })
+            .filter_map(|record| record.get(data_field.field))
+            .collect();
+        match data_field.aggregate.apply(&values) {
+            Some(number) => GridCell {
+                value: GridValue::Number(number),
+                bold,
+                num_fmt_id: data_field.num_fmt_id,
+            },
+            None => GridCell::empty(bold),
+        }
+    };
+
+    let mut grid: Vec<Vec<GridCell>> = Vec::new();
+    let label_cell = |text: &str| -> Vec<GridCell> {
+        if has_labels {
+            vec![GridCell::text(text, true)]
+        } else {
+            Vec::new()
+        }
+    };
+    let data_name = |column: &ValueColumn| definition.data_fields[column.data].name.clone();
+    if definition.col_fields.is_empty() {
+        let mut header: Vec<GridCell> = label_cell(&definition.row_header_caption);
+        header.extend(
+            columns
+                .iter()
+                .map(|column| GridCell::text(data_name(column), true)),
+        );
+        grid.push(header);
+    } else {
+        let single_data: bool = data_count == 1;
+        let corner: String = if single_data {
+            definition.data_fields[0].name.clone()
+        } else {
+            String::new()
+        };
+        let mut caption_row: Vec<GridCell> = label_cell(&corner);
+        caption_row.push(GridCell::text(definition.col_header_caption.clone(), true));
+        caption_row.extend((1..columns.len()).map(|_| GridCell::empty(true)));
+        grid.push(caption_row);
+
+        let row_caption: &str = if single_data {
+            &definition.row_header_caption
+        } else {
+            ""
+        };
+        let mut item_row: Vec<GridCell> = label_cell(row_caption);
+        // Several data fields under one item share its label; their grand
+        // totals are named per field.
+        item_row.extend(columns.iter().map(|column| {
+            if column.labels.is_empty() && single_data {
+                GridCell::text(definition.grand_total_caption.clone(), true)
+            } else if column.labels.is_empty() {
+                GridCell::text(format!("Total {}", data_name(column)), true)
+            } else if column.data > 0 {
+                GridCell::empty(true)
+            } else {
+                GridCell::text(column.labels.join(" / "), true)
+            }
+        }));
+        grid.push(item_row);
+
+        if !single_data {
+            let mut data_row: Vec<GridCell> = label_cell(&definition.row_header_caption);
+            data_row.extend(columns.iter().map(|column| {
+                if column.labels.is_empty() {
+                    GridCell::empty(true)
+                } else {
+                    GridCell::text(data_name(column), true)
+                }
+            }));
+            grid.push(data_row);
+        }
+    }
+
+    if has_labels {
+        emit_row_groups(
+            definition,
+            &records,
+            0,
+            &ordered_labels,
+            &labels,
+            &mut |depth: usize, label: &str, group: &[&Vec<CacheValue>], is_leaf: bool| {
+                let bold: bool = !is_leaf;
+                let show_values: bool = is_leaf
+                    || definition
+                        .fields
+                        .get(definition.row_fields[depth])
+                        .is_none_or(|field| field.subtotals);
+                let mut row: Vec<GridCell> = vec![GridCell::text(
+                    format!("{}{label}", LEVEL_INDENT.repeat(depth)),
+                    bold,
+                )];
+                row.extend(columns.iter().map(|column| {
+                    if show_values {
+                        summarize(group, column, bold)
+                    } else {
+                        GridCell::empty(bold)
+                    }
+                }));
+                grid.push(row);
+            },
+        );
+        if definition.col_grand_totals {
+            let mut total: Vec<GridCell> =
+                vec![GridCell::text(definition.grand_total_caption.clone(), true)];
+            total.extend(
+                columns
+                    .iter()
+                    .map(|column| summarize(&records, column, true)),
+            );
+            grid.push(total);
+        }
+    } else {
+        grid.push(
+            columns
+                .iter()
+                .map(|column| summarize(&records, column, false))
+                .collect(),
+        );
+    }
+    grid
+}
+
+/// Every combination of `fields` labels that occurs in `records`, nested in
+/// item order.
+fn collect_keys(
+    fields: &[usize],
+    records: &[&Vec<CacheValue>],
+    ordered_labels: &dyn Fn(usize, &[&Vec<CacheValue>]) -> Vec<String>,
+    labels: &dyn Fn(&[CacheValue], usize) -> String,
+    prefix: Vec<String>,
+    keys: &mut Vec<Vec<String>>,
+) {
+    let Some((&field, rest)) = fields.split_first() else {
+        keys.push(prefix);
+        return;
+    };
+    for label in ordered_labels(field, records) {
+        let group: Vec<&Vec<CacheValue>> = records
+            .iter()
+            .copied()
+            .filter(|record| labels(record, field) == label)
+            .collect();
+        let mut key: Vec<String> = prefix.clone();
+        key.push(label);
+        collect_keys(rest, &group, ordered_labels, labels, key, keys);
+    }
+}
+
+/// Walk the row fields depth first, calling `emit` for each group row
+/// before its nested rows.
+fn emit_row_groups(
+    definition: &PivotDefinition,
+    records: &[&Vec<CacheValue>],
+    depth: usize,
+    ordered_labels: &dyn Fn(usize, &[&Vec<CacheValue>]) -> Vec<String>,
+    labels: &dyn Fn(&[CacheValue], usize) -> String,
+    emit: &mut dyn FnMut(usize, &str, &[&Vec<CacheValue>], bool),
+) {
+    let Some(&field) = definition.row_fields.get(depth) else {
+        return;
+    };
+    let is_leaf: bool = depth + 1 == definition.row_fields.len();
+    for label in ordered_labels(field, records) {
+        let group: Vec<&Vec<CacheValue>> = records
+            .iter()
+            .copied()
+            .filter(|record| labels(record, field) == label)
+            .collect();
+        emit(depth, &label, &group, is_leaf);
+        emit_row_groups(definition, &group, depth + 1, ordered_labels, labels, emit);
+    }
+}
+
+/// Rebuild every pivot table of the workbook and write it into its sheet.
+pub(super) fn apply_pivot_tables(book: &mut umya_spreadsheet::Spreadsheet, data: &[u8]) {
+    for (sheet_name, tables) in extract_pivot_tables(data) {
+        let Some(sheet) = book.get_sheet_by_name_mut(&sheet_name) else {
+            continue;
+        };
+        for (definition, cache) in tables {
+            let grid: Vec<Vec<GridCell>> = build_pivot_grid(&definition, &cache);
+            if !grid.is_empty() {
+                write_pivot_grid(sheet, &definition, &grid);
+            }
+        }
+    }
+}
+
+/// Replace the pivot's cells with `grid`, clearing what the last refresh
+/// left outside the new grid.
+fn write_pivot_grid(
+    sheet: &mut umya_spreadsheet::Worksheet,
+    definition: &PivotDefinition,
+    grid: &[Vec<GridCell>],
+) {
+    let (first_col, first_row, last_col, last_row) = definition.location;
+    for row in first_row..=last_row {
+        for col in first_col..=last_col {
+            if sheet.get_cell((col, row)).is_some() {
+                sheet.get_cell_mut((col, row)).set_value_string("");
+            }
+        }
+    }
+    for (row_offset, row) in grid.iter().enumerate() {
+        for (col_offset, grid_cell) in row.iter().enumerate() {
+            let position: (u32, u32) =
+                (first_col + col_offset as u32, first_row + row_offset as u32);
+            if grid_cell.value == GridValue::Empty && sheet.get_cell(position).is_none() {
+                continue;
+            }
+            let cell = sheet.get_cell_mut(position);
+            match &grid_cell.value {
+                GridValue::Text(text) => {
+                    cell.set_value_string(text.as_str());
+                }
+                GridValue::Number(number) => {
+                    cell.set_value_number(*number);
+                }
+                GridValue::Empty => {
+                    cell.set_value_string("");
+                }
+            }
+            if grid_cell.bold {
+                cell.get_style_mut().get_font_mut().set_bold(true);
+            }
+            // A format the refresh applied to the cell stays; otherwise the
+            // data field's built-in format is used.
+            let has_format: bool = cell.get_style().get_number_format().is_some_and(|format| {
+                let code: &str = format.get_format_code();
+                !code.is_empty() && !code.eq_ignore_ascii_case("general")
+            });
+            if !has_format && let Some(code) = grid_cell.num_fmt_id.and_then(builtin_format_code) {
+                cell.get_style_mut()
+                    .get_number_format_mut()
+                    .set_format_code(code);
+            }
+        }
+    }
+}
+
+/// Read each worksheet's pivot tables with their caches, keyed by sheet name.
+pub(super) fn extract_pivot_tables(
+    data: &[u8],
+) -> HashMap<String, Vec<(PivotDefinition, PivotCache)>> {
+    let Ok(mut archive) = crate::parser::open_zip(data) else {
+        return HashMap::new();
+    };
+    let workbook_xml = read_zip_entry_string(&mut archive, "xl/workbook.xml");
+    let workbook_rels_xml = read_zip_entry_string(&mut archive, "xl/_rels/workbook.xml.rels");
+    let rid_to_target = parse_rels_targets(&workbook_rels_xml);
+
+    let mut result: HashMap<String, Vec<(PivotDefinition, PivotCache)>> = HashMap::new();
+    for (sheet_name, sheet_rid) in parse_workbook_sheet_rids(&workbook_xml) {
+        let Some(sheet_target) = rid_to_target.get(&sheet_rid) else {
+            continue;
+        };
+        let sheet_path: String = format!("xl/{}", sheet_target.trim_start_matches("/xl/"));
+        let sheet_filename: &str = sheet_path.rsplit('/').next().unwrap_or(&sheet_path);
+        let sheet_rels_xml = read_zip_entry_string(
+            &mut archive,
+            &format!("xl/worksheets/_rels/{sheet_filename}.rels"),
+        );
+        let mut tables: Vec<(PivotDefinition, PivotCache)> = Vec::new();
+        for pivot_target in parse_rels_by_type(&sheet_rels_xml, "/pivotTable") {
+            let pivot_path: String = resolve_relative_xl_path("xl/worksheets", &pivot_target);
+            let Some(definition) =
+                parse_pivot_definition(&read_zip_entry_string(&mut archive, &pivot_path))
+            else {
+                continue;
+            };
+            let (pivot_dir, pivot_file) = pivot_path
+                .rsplit_once('/')
+                .unwrap_or(("", pivot_path.as_str()));
+            let pivot_rels_xml = read_zip_entry_string(
+                &mut archive,
+                &format!("{pivot_dir}/_rels/{pivot_file}.rels"),
+            );
+            let Some(cache_target) = parse_rels_by_type(&pivot_rels_xml, "/pivotCacheDefinition")
+                .into_iter()
+                .next()
+            else {
+                continue;
+            };
+            let cache_path: String = resolve_relative_xl_path(pivot_dir, &cache_target);
+            let mut cache: PivotCache =
+                parse_cache_definition(&read_zip_entry_string(&mut archive, &cache_path));
+            let (cache_dir, cache_file) = cache_path
+                .rsplit_once('/')
+                .unwrap_or(("", cache_path.as_str()));
+            let cache_rels_xml = read_zip_entry_string(
+                &mut archive,
+                &format!("{cache_dir}/_rels/{cache_file}.rels"),
+            );
+            if let Some(records_target) = parse_rels_by_type(&cache_rels_xml, "/pivotCacheRecords")
+                .into_iter()
+                .next()
+            {
+                let records_path: String = resolve_relative_xl_path(cache_dir, &records_target);
+                cache.records = parse_cache_records(
+                    &read_zip_entry_string(&mut archive, &records_path),
+                    &cache.shared_items,
+                );
+            }
+            tables.push((definition, cache));
+        }
+        if !tables.is_empty() {
+            result.insert(sheet_name, tables);
+        }
+    }
+    result
+}
+
+/// Parse a `<pivotTableDefinition>`. None without a usable location.
+pub(super) fn parse_pivot_definition(xml: &str) -> Option<PivotDefinition> {
+    let mut definition = PivotDefinition {
+        location: (0, 0, 0, 0),
+        fields: Vec::new(),
+        row_fields: Vec::new(),
+        col_fields: Vec::new(),
+        page_filters: Vec::new(),
+        data_fields: Vec::new(),
+        row_grand_totals: true,
+        col_grand_totals: true,
+        row_header_caption: "Row Labels".to_string(),
+        col_header_caption: "Column Labels".to_string(),
+        grand_total_caption: "Grand Total".to_string(),
+    };
+    let mut has_location = false;
+    // Which `<field>` list is open: row or column fields.
+    let mut field_list: Option<bool> = None;
+    let mut reader = quick_xml::Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => match e.local_name().as_ref() {
+                b"pivotTableDefinition" => {
+                    let flag = |name: &[u8]| get_attr_str(e, name).map(|v| v == "1" || v == "true");
+                    definition.row_grand_totals = flag(b"rowGrandTotals").unwrap_or(true);
+                    definition.col_grand_totals = flag(b"colGrandTotals").unwrap_or(true);
+                    for (name, caption) in [
+                        (&b"rowHeaderCaption"[..], &mut definition.row_header_caption),
+                        (&b"colHeaderCaption"[..], &mut definition.col_header_caption),
+                        (
+                            &b"grandTotalCaption"[..],
+                            &mut definition.grand_total_caption,
+                        ),
+                    ] {
+                        if let Some(value) = get_attr_str(e, name) {
+                            *caption = value;
+                        }
+                    }
+                }
+                b"location" => {
+                    if let Some(location) = get_attr_str(e, b"ref").and_then(|r| parse_range(&r)) {
+                        definition.location = location;
+                        has_location = true;
+                    }
+                }
+                b"pivotField" => {
+                    definition.fields.push(PivotField {
+                        items: Vec::new(),
+                        subtotals: get_attr_str(e, b"defaultSubtotal")
+                            .is_none_or(|value| value != "0" && value != "false"),
+                    });
+                }
+                // Items with a type are subtotal markers, not values.
+                b"item" if get_attr_str(e, b"t").is_none() => {
+                    if let Some(field) = definition.fields.last_mut()
+                        && let Some(index) = get_attr_str(e, b"x").and_then(|x| x.parse().ok())
+                    {
+                        let hidden: bool = get_attr_str(e, b"h").is_some_and(|h| h == "1");
+                        field.items.push((index, hidden));
+                    }
+                }
+                b"rowFields" => field_list = Some(true),
+                b"colFields" => field_list = Some(false),
+                // `x="-2"` places the data fields themselves on the axis.
+                b"field" => {
+                    if let Some(is_row) = field_list
+                        && let Some(index) =
+                            get_attr_str(e, b"x").and_then(|x| x.parse::<usize>().ok())
+                    {
+                        if is_row {
+                            definition.row_fields.push(index);
+                        } else {
+                            definition.col_fields.push(index);
+                        }
+                    }
+                }
+                b"pageField" => {
+                    let field: Option<usize> = get_attr_str(e, b"fld").and_then(|f| f.parse().ok());
+                    let item: Option<usize> = get_attr_str(e, b"item").and_then(|i| i.parse().ok());
+                    if let (Some(field), Some(item)) = (field, item) {
+                        definition.page_filters.push((field, item));
+                    }
+                }
+                b"dataField" => {
+                    let Some(field) = get_attr_str(e, b"fld").and_then(|f| f.parse().ok()) else {
+                        continue;
+                    };
+                    let aggregate: Aggregate =
+                        Aggregate::parse(&get_attr_str(e, b"subtotal").unwrap_or_default());
+                    definition.data_fields.push(DataField {
+                        name: get_attr_str(e, b"name").unwrap_or_default(),
+                        field,
+                        aggregate,
+                        num_fmt_id: get_attr_str(e, b"numFmtId").and_then(|id| id.parse().ok()),
+                    });
+                }
+                _ => {}
+            },
+            Ok(Event::End(ref e))
+                if matches!(e.local_name().as_ref(), b"rowFields" | b"colFields") =>
+            {
+                field_list = None;
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    has_location.then_some(definition)
+}
+
+/// Parse the cache fields' shared items of a `<pivotCacheDefinition>`.
+pub(super) fn parse_cache_definition(xml: &str) -> PivotCache {
+    let mut cache = PivotCache::default();
+    let mut in_shared_items = false;
+    let mut reader = quick_xml::Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"sharedItems" => {
+                in_shared_items = true;
+            }
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => match e.local_name().as_ref() {
+                b"cacheField" => cache.shared_items.push(Vec::new()),
+                _ if in_shared_items => {
+                    if let (Some(items), Some(value)) =
+                        (cache.shared_items.last_mut(), CacheValue::from_element(e))
+                    {
+                        items.push(value);
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"sharedItems" => {
+                in_shared_items = false;
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    cache
+}
+
+/// Parse `<pivotCacheRecords>`, resolving `<x v>` into the shared items.
+pub(super) fn parse_cache_records(
+    xml: &str,
+    shared_items: &[Vec<CacheValue>],
+) -> Vec<Vec<CacheValue>> {
+    let mut records: Vec<Vec<CacheValue>> = Vec::new();
+    let mut current: Option<Vec<CacheValue>> = None;
+    let mut reader = quick_xml::Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"r" => {
+                current = Some(Vec::new());
+            }
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                let Some(record) = current.as_mut() else {
+                    continue;
+                };
+                let value: Option<CacheValue> = if e.local_name().as_ref() == b"x" {
+                    let index: Option<usize> = get_attr_str(e, b"v").and_then(|v| v.parse().ok());
+                    Some(
+                        index
+                            .and_then(|index| shared_items.get(record.len())?.get(index).cloned())
+                            .unwrap_or(CacheValue::Missing),
+                    )
+                } else {
+                    CacheValue::from_element(e)
+                };
+                if let Some(value) = value {
+                    record.push(value);
+                }
+            }
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"r" => {
+                records.extend(current.take());
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    records
+}
+
+/// `A3:C10` as `(first_col, first_row, last_col, last_row)`.
+fn parse_range(range: &str) -> Option<(u32, u32, u32, u32)> {
+    let (first, last) = range.split_once(':').unwrap_or((range, range));
+    let (first_col, first_row) = super::xlsx_cells::parse_cell_ref(first)?;
+    let (last_col, last_row) = super::xlsx_cells::parse_cell_ref(last)?;
+    Some((first_col, first_row, last_col, last_row))
+}
+
+#[cfg(test)]
+#[path = "xlsx_pivot_tests.rs"]
+mod tests;
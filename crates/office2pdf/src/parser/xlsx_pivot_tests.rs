@@ -0,0 +1,272 @@
+use super::*;
+use crate::config::ConvertOptions;
+use crate::ir::{Block, Page};
+use crate::parser::Parser;
+
+const CACHE_DEFINITION_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<pivotCacheDefinition xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" r:id="rId1" refreshOnLoad="1" recordCount="4">
+  <cacheSource type="worksheet"><worksheetSource ref="A1:C5" sheet="Data"/></cacheSource>
+  <cacheFields count="3">
+    <cacheField name="Region" numFmtId="0"><sharedItems count="2"><s v="East"/><s v="West"/></sharedItems></cacheField>
+    <cacheField name="Product" numFmtId="0"><sharedItems count="2"><s v="Pens"/><s v="Ink"/></sharedItems></cacheField>
+    <cacheField name="Sales" numFmtId="0"><sharedItems containsSemiMixedTypes="0" containsString="0" containsNumber="1" minValue="5" maxValue="40"/></cacheField>
+  </cacheFields>
+</pivotCacheDefinition>"#;
+
+const CACHE_RECORDS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<pivotCacheRecords xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" count="4">
+  <r><x v="0"/><x v="0"/><n v="10"/></r>
+  <r><x v="1"/><x v="0"/><n v="20"/></r>
+  <r><x v="0"/><x v="1"/><n v="5"/></r>
+  <r><x v="1"/><x v="1"/><n v="40"/></r>
+</pivotCacheRecords>"#;
+
+const PIVOT_TABLE_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<pivotTableDefinition xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" name="PivotTable1" cacheId="1" dataCaption="Values">
+  <location ref="A3:B6" firstHeaderRow="1" firstDataRow="1" firstDataCol="1"/>
+  <pivotFields count="3">
+    <pivotField axis="axisRow" showAll="0"><items count="3"><item x="1"/><item x="0"/><item t="default"/></items></pivotField>
+    <pivotField showAll="0"/>
+    <pivotField dataField="1" showAll="0"/>
+  </pivotFields>
+  <rowFields count="1"><field x="0"/></rowFields>
+  <rowItems count="3"><i><x/></i><i><x v="1"/></i><i t="grand"><x/></i></rowItems>
+  <colItems count="1"><i/></colItems>
+  <dataFields count="1"><dataField name="Sum of Sales" fld="2" baseField="0" baseItem="0" numFmtId="3"/></dataFields>
+</pivotTableDefinition>"#;
+
+fn fixture() -> (PivotDefinition, PivotCache) {
+    let definition = parse_pivot_definition(PIVOT_TABLE_XML).expect("pivot definition");
+    let mut cache: PivotCache = parse_cache_definition(CACHE_DEFINITION_XML);
+    cache.records = parse_cache_records(CACHE_RECORDS_XML, &cache.shared_items);
+    (definition, cache)
+}
+
+/// The grid as display strings; numbers print plainly, bold cells get `*`.
+fn render(grid: &[Vec<GridCell>]) -> Vec<Vec<String>> {
+    grid.iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| {
+                    let text: String = match &cell.value {
+                        GridValue::Text(text) => text.replace('\u{00A0}', "."),
+                        GridValue::Number(number) => format_general(*number),
+                        GridValue::Empty => String::new(),
+                    };
+                    if cell.bold { format!("*{text}") } else { text }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn strings(rows: &[&[&str]]) -> Vec<Vec<String>> {
+    rows.iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect()
+}
+
+#[test]
+fn test_parse_pivot_parts() {
+    let (definition, cache) = fixture();
+    assert_eq!(definition.location, (1, 3, 2, 6));
+    assert_eq!(definition.row_fields, vec![0]);
+    assert_eq!(definition.fields[0].items, vec![(1, false), (0, false)]);
+    assert_eq!(
+        definition.data_fields,
+        vec![DataField {
+            name: "Sum of Sales".to_string(),
+            field: 2,
+            aggregate: Aggregate::Sum,
+            num_fmt_id: Some(3),
+        }]
+    );
+    assert_eq!(cache.shared_items[2], Vec::<CacheValue>::new());
+    assert_eq!(
+        cache.records[1],
+        vec![
+            CacheValue::Text("West".to_string()),
+            CacheValue::Text("Pens".to_string()),
+            CacheValue::Number(20.0),
+        ]
+    );
+}
+
+#[test]
+fn test_grid_follows_item_order_with_grand_total() {
+    let (definition, cache) = fixture();
+    assert_eq!(
+        render(&build_pivot_grid(&definition, &cache)),
+        strings(&[
+            &["*Row Labels", "*Sum of Sales"],
+            &["West", "60"],
+            &["East", "15"],
+            &["*Grand Total", "*75"],
+        ])
+    );
+}
+
+#[test]
+fn test_nested_row_fields_show_group_subtotals() {
+    let (mut definition, cache) = fixture();
+    definition.row_fields = vec![0, 1];
+    assert_eq!(
+        render(&build_pivot_grid(&definition, &cache)),
+        strings(&[
+            &["*Row Labels", "*Sum of Sales"],
+            &["*West", "*60"],
+            &["..Pens", "20"],
+            &["..Ink", "40"],
+            &["*East", "*15"],
+            &["..Pens", "10"],
+            &["..Ink", "5"],
+            &["*Grand Total", "*75"],
+        ])
+    );
+}
+
+#[test]
+fn test_column_field_spreads_values_across_items() {
+    let (mut definition, cache) = fixture();
+    definition.col_fields = vec![1];
+    assert_eq!(
+        render(&build_pivot_grid(&definition, &cache)),
+        strings(&[
+            &["*Sum of Sales", "*Column Labels", "*", "*"],
+            &["*Row Labels", "*Pens", "*Ink", "*Grand Total"],
+            &["West", "20", "40", "60"],
+            &["East", "10", "5", "15"],
+            &["*Grand Total", "*30", "*45", "*75"],
+        ])
+    );
+}
+
+#[test]
+fn test_report_filter_and_hidden_items_drop_records() {
+    let (mut definition, cache) = fixture();
+    definition.fields[1].items = vec![(0, false), (1, false)];
+    definition.page_filters = vec![(1, 1)];
+    definition.fields[0].items[0].1 = true;
+    definition.data_fields[0].aggregate = Aggregate::Count;
+    assert_eq!(
+        render(&build_pivot_grid(&definition, &cache)),
+        strings(&[
+            &["*Row Labels", "*Sum of Sales"],
+            &["East", "1"],
+            &["*Grand Total", "*1"],
+        ])
+    );
+}
+
+/// Add the pivot table parts to the first worksheet of `xlsx`.
+fn with_pivot_parts(xlsx: &[u8]) -> Vec<u8> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(xlsx.to_vec())).expect("zip");
+    let mut out = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::FileOptions::default();
+    let sheet_rels_path: &str = "xl/worksheets/_rels/sheet1.xml.rels";
+    let pivot_relationship: &str = r#"<Relationship Id="rIdPivot1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotTable" Target="../pivotTables/pivotTable1.xml"/>"#;
+    let mut has_sheet_rels = false;
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).expect("entry");
+        let name: String = file.name().to_string();
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut file, &mut content).expect("text entry");
+        if name == sheet_rels_path {
+            has_sheet_rels = true;
+            content = content.replace(
+                "</Relationships>",
+                &format!("{pivot_relationship}</Relationships>"),
+            );
+        } else if name == "[Content_Types].xml" {
+            content = content.replace(
+                "</Types>",
+                r#"<Override PartName="/xl/pivotTables/pivotTable1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.pivotTable+xml"/><Override PartName="/xl/pivotCache/pivotCacheDefinition1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.pivotCacheDefinition+xml"/><Override PartName="/xl/pivotCache/pivotCacheRecords1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.pivotCacheRecords+xml"/></Types>"#,
+            );
+        }
+        out.start_file(name, options).expect("start entry");
+        std::io::Write::write_all(&mut out, content.as_bytes()).expect("write entry");
+    }
+    let mut parts: Vec<(&str, String)> = vec![
+        ("xl/pivotTables/pivotTable1.xml", PIVOT_TABLE_XML.to_string()),
+        (
+            "xl/pivotTables/_rels/pivotTable1.xml.rels",
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotCacheDefinition" Target="../pivotCache/pivotCacheDefinition1.xml"/></Relationships>"#
+                .to_string(),
+        ),
+        (
+            "xl/pivotCache/pivotCacheDefinition1.xml",
+            CACHE_DEFINITION_XML.to_string(),
+        ),
+        (
+            "xl/pivotCache/_rels/pivotCacheDefinition1.xml.rels",
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotCacheRecords" Target="pivotCacheRecords1.xml"/></Relationships>"#
+                .to_string(),
+        ),
+        (
+            "xl/pivotCache/pivotCacheRecords1.xml",
+            CACHE_RECORDS_XML.to_string(),
+        ),
+    ];
+    if !has_sheet_rels {
+        parts.push((
+            sheet_rels_path,
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{pivot_relationship}</Relationships>"#
+            ),
+        ));
+    }
+    for (name, content) in parts {
+        out.start_file(name, options).expect("start entry");
+        std::io::Write::write_all(&mut out, content.as_bytes()).expect("write entry");
+    }
+    out.finish().expect("finish zip").into_inner()
+}
+
+#[test]
+fn test_stale_pivot_cells_are_rebuilt_from_cache() {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.set_name("Summary");
+        sheet.get_cell_mut("A1").set_value("Report");
+        sheet.get_cell_mut("A3").set_value("stale");
+    }
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    let data: Vec<u8> = with_pivot_parts(&cursor.into_inner());
+
+    let (doc, _warnings) = crate::parser::xlsx::XlsxParser
+        .parse(&data, &ConvertOptions::default())
+        .unwrap();
+    let Page::Sheet(page) = &doc.pages[0] else {
+        panic!("expected a sheet page");
+    };
+    let texts: Vec<Vec<String>> = page
+        .table
+        .rows
+        .iter()
+        .map(|row| {
+            row.cells
+                .iter()
+                .map(|cell| match cell.content.first() {
+                    Some(Block::Paragraph(paragraph)) => {
+                        paragraph.runs.iter().map(|run| run.text.as_str()).collect()
+                    }
+                    _ => String::new(),
+                })
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        texts[2..].to_vec(),
+        strings(&[
+            &["Row Labels", "Sum of Sales"],
+            &["West", "60"],
+            &["East", "15"],
+            &["Grand Total", "75"],
+        ])
+    );
+}
@@ -0,0 +1,262 @@
+//! Gridlines and row/column headings on the printed sheet. Excel prints them
+//! when the sheet's page setup asks for them (`<printOptions gridLines
+//! headings>`); `ConvertOptions::xlsx_gridlines` and `xlsx_headings` turn
+//! them on for every sheet. How error values print (`<pageSetup errors>`)
+//! comes along with them. Frozen panes (`<sheetView><pane>`) are read here
+//! too: the frozen rows and columns repeat on every page like print titles.
+//! umya does not keep the flags, so they are read from the raw worksheet
+//! XML.
+
+use std::collections::HashMap;
+
+use quick_xml::events::Event;
+
+use super::xlsx_cells::{PrintTitles, parse_cell_ref};
+use super::xlsx_drawing::{parse_rels_targets, parse_workbook_sheet_rids, read_zip_entry_string};
+use super::xlsx_numfmt::ErrorDisplay;
+use crate::ir::{
+    Alignment, Block, BorderLineStyle, BorderSide, CellBorder, Color, Paragraph, ParagraphStyle,
+    Run, SheetPage, TableCell, TableRow, TextStyle,
+};
+use crate::parser::xml_util::get_attr_str;
+
+/// Excel draws printed gridlines as hairlines; a light grey keeps them
+/// behind real cell borders.
+const GRIDLINE: BorderSide = BorderSide {
+    width: 0.25,
+    color: Color {
+        r: 0xC0,
+        g: 0xC0,
+        b: 0xC0,
+    },
+    style: BorderLineStyle::Solid,
+};
+
+/// Fill of the heading row and column.
+const HEADING_FILL: Color = Color {
+    r: 0xF2,
+    g: 0xF2,
+    b: 0xF2,
+};
+
+/// Width of one row-number digit in points, at the default 11pt font.
+const HEADING_DIGIT_WIDTH_PT: f64 = 6.0;
+
+/// A worksheet's `<printOptions>`, error printing and frozen panes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(super) struct PrintOptions {
+    pub(super) gridlines: bool,
+    pub(super) headings: bool,
+    /// How error values print.
+    pub(super) errors: ErrorDisplay,
+    /// Rows frozen at the top of the first sheet view (1-indexed, inclusive).
+    pub(super) frozen_rows: Option<(u32, u32)>,
+    /// Columns frozen at the left of the first sheet view.
+    pub(super) frozen_cols: Option<(u32, u32)>,
+}
+
+impl PrintOptions {
+    /// Print titles with the frozen rows and columns filling in whichever
+    /// of them the sheet's own `Print_Titles` leaves unset.
+    pub(super) fn with_frozen_titles(&self, titles: PrintTitles) -> PrintTitles {
+        PrintTitles {
+            rows: titles.rows.or(self.frozen_rows),
+            cols: titles.cols.or(self.frozen_cols),
+        }
+    }
+}
+
+/// Read the print options of every worksheet that sets one, keyed by sheet
+/// name.
+pub(super) fn extract_print_options(data: &[u8]) -> HashMap<String, PrintOptions> {
+    let Ok(mut archive) = crate::parser::open_zip(data) else {
+        return HashMap::new();
+    };
+    let workbook_xml = read_zip_entry_string(&mut archive, "xl/workbook.xml");
+    let workbook_rels_xml = read_zip_entry_string(&mut archive, "xl/_rels/workbook.xml.rels");
+    let rid_to_target = parse_rels_targets(&workbook_rels_xml);
+
+    let mut result: HashMap<String, PrintOptions> = HashMap::new();
+    for (sheet_name, sheet_rid) in parse_workbook_sheet_rids(&workbook_xml) {
+        let Some(sheet_target) = rid_to_target.get(&sheet_rid) else {
+            continue;
+        };
+        let sheet_path: String = format!("xl/{}", sheet_target.trim_start_matches("/xl/"));
+        let sheet_xml = read_zip_entry_string(&mut archive, &sheet_path);
+        let print_options: PrintOptions = parse_print_options(&sheet_xml);
+        if print_options != PrintOptions::default() {
+            result.insert(sheet_name, print_options);
+        }
+    }
+    result
+}
+
+/// Parse `<printOptions>`, `<pageSetup errors>` and the first sheet view's
+/// frozen pane from one worksheet's XML.
+pub(super) fn parse_print_options(xml: &str) -> PrintOptions {
+    let mut print_options = PrintOptions::default();
+    let mut reader = quick_xml::Reader::from_str(xml);
+    // The sheet view's scrolled-to cell; frozen rows and columns start there.
+    let mut view_top_left: Option<(u32, u32)> = None;
+    let mut seen_view = false;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => match e.local_name().as_ref() {
+                b"sheetView" if !seen_view => {
+                    seen_view = true;
+                    view_top_left = Some(
+                        get_attr_str(e, b"topLeftCell")
+                            .and_then(|cell| parse_cell_ref(&cell))
+                            .unwrap_or((1, 1)),
+                    );
+                }
+                b"pane" => {
+                    let Some((first_col, first_row)) = view_top_left.take() else {
+                        continue;
+                    };
+                    // A plain split scrolls both sides; only frozen panes stay
+                    // put on screen.
+                    let frozen: bool = get_attr_str(e, b"state")
+                        .is_some_and(|state| state == "frozen" || state == "frozenSplit");
+                    if frozen {
+                        let split = |name: &[u8]| -> u32 {
+                            get_attr_str(e, name)
+                                .and_then(|value| value.parse::<f64>().ok())
+                                .map_or(0, |value| value.max(0.0) as u32)
+                        };
+                        let (cols, rows): (u32, u32) = (split(b"xSplit"), split(b"ySplit"));
+                        print_options.frozen_rows =
+                            (rows > 0).then(|| (first_row, first_row + rows - 1));
+                        print_options.frozen_cols =
+                            (cols > 0).then(|| (first_col, first_col + cols - 1));
+                    }
+                }
+                b"printOptions" => {
+                    let flag = |name: &[u8]| -> bool {
+                        get_attr_str(e, name).is_some_and(|value| value == "1" || value == "true")
+                    };
+                    print_options.gridlines = flag(b"gridLines");
+                    print_options.headings = flag(b"headings");
+                }
+                b"pageSetup" => {
+                    if let Some(errors) = get_attr_str(e, b"errors") {
+                        print_options.errors = ErrorDisplay::from_attr(&errors);
+                    }
+                    break;
+                }
+                // Everything read here comes before the header and footer.
+                b"headerFooter" | b"drawing" => break,
+                _ => {}
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    print_options
+}
+
+/// Give every side of a cell without its own border a gridline.
+pub(super) fn with_gridlines(border: Option<CellBorder>) -> CellBorder {
+    let border: CellBorder = border.unwrap_or_default();
+    CellBorder {
+        top: border.top.or(Some(GRIDLINE)),
+        bottom: border.bottom.or(Some(GRIDLINE)),
+        left: border.left.or(Some(GRIDLINE)),
+        right: border.right.or(Some(GRIDLINE)),
+    }
+}
+
+/// The A, B, C column letters and 1, 2, 3 row numbers Excel prints around a
+/// sheet when headings are on.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct SheetHeadings {
+    /// Letters of the printed (visible) columns, left to right.
+    column_labels: Vec<String>,
+    /// Width of the row-number column in points.
+    pub(super) width: f64,
+}
+
+impl SheetHeadings {
+    /// Headings for the given printed columns (1-indexed) of a sheet whose
+    /// last printed row is `last_row`.
+    pub(super) fn new(columns: &[u32], last_row: u32) -> Self {
+        let digits: usize = last_row.max(1).to_string().len().max(2);
+        Self {
+            column_labels: columns
+                .iter()
+                .map(umya_spreadsheet::helper::coordinate::string_from_column_index)
+                .collect(),
+            width: digits as f64 * HEADING_DIGIT_WIDTH_PT + 2.0 * HEADING_DIGIT_WIDTH_PT,
+        }
+    }
+
+    /// Frame a sheet page's table with headings: the letter row on top,
+    /// repeated on every page, and each row's number on the left.
+    /// `row_numbers` are the sheet rows of the table's rows. Anchored
+    /// drawings move with the grid.
+    pub(super) fn apply(&self, page: &mut SheetPage, row_numbers: &[u32]) {
+        let table = &mut page.table;
+        for (row, number) in table.rows.iter_mut().zip(row_numbers) {
+            row.cells.insert(0, heading_cell(number.to_string()));
+        }
+        let mut letter_row = TableRow {
+            cells: vec![heading_cell(String::new())],
+            height: None,
+        };
+        letter_row.cells.extend(
+            self.column_labels
+                .iter()
+                .map(|label| heading_cell(label.clone())),
+        );
+        table.rows.insert(0, letter_row);
+        table.header_row_count += 1;
+        table.column_widths.insert(0, self.width);
+
+        for (row, _) in &mut page.charts {
+            *row = row.saturating_add(1);
+        }
+        for sheet_image in &mut page.images {
+            sheet_image.anchor_row = sheet_image.anchor_row.saturating_add(1);
+            sheet_image.x_offset_pt += self.width;
+        }
+        for text_box in &mut page.text_boxes {
+            text_box.anchor_row = text_box.anchor_row.saturating_add(1);
+            text_box.x_offset_pt += self.width;
+        }
+    }
+
+    /// Print-title columns once the number column leads the table: it
+    /// repeats on every page along with any title columns.
+    pub(super) fn title_columns(&self, title_columns: Option<(usize, usize)>) -> (usize, usize) {
+        (0, title_columns.map_or(1, |(_, end)| end + 1))
+    }
+}
+
+fn heading_cell(text: String) -> TableCell {
+    let runs: Vec<Run> = if text.is_empty() {
+        Vec::new()
+    } else {
+        vec![Run {
+            text,
+            style: TextStyle::default(),
+            href: None,
+            footnote: None,
+        }]
+    };
+    TableCell {
+        content: vec![Block::Paragraph(Paragraph {
+            style: ParagraphStyle {
+                alignment: Some(Alignment::Center),
+                ..ParagraphStyle::default()
+            },
+            runs,
+        })],
+        border: Some(with_gridlines(None)),
+        background: Some(HEADING_FILL),
+        ..TableCell::default()
+    }
+}
+
+#[cfg(test)]
+#[path = "xlsx_print_options_tests.rs"]
+mod tests;
@@ -0,0 +1,176 @@
+use super::*;
+use crate::config::ConvertOptions;
+use crate::ir::Page;
+use crate::parser::Parser;
+
+fn is_gridline(side: BorderSide) -> bool {
+    side.width == GRIDLINE.width && side.color == GRIDLINE.color
+}
+
+fn cell_text(cell: &TableCell) -> String {
+    match cell.content.first() {
+        Some(Block::Paragraph(paragraph)) => {
+            paragraph.runs.iter().map(|run| run.text.as_str()).collect()
+        }
+        _ => String::new(),
+    }
+}
+
+#[test]
+fn test_parse_print_options_flags() {
+    let xml = r#"<worksheet><sheetData/><printOptions gridLines="1" headings="true"/><pageMargins left="0.7"/></worksheet>"#;
+    assert_eq!(
+        parse_print_options(xml),
+        PrintOptions {
+            gridlines: true,
+            headings: true,
+            ..PrintOptions::default()
+        }
+    );
+    let xml = r#"<worksheet><sheetData/><printOptions gridLines="0"/></worksheet>"#;
+    assert_eq!(parse_print_options(xml), PrintOptions::default());
+    let xml = r#"<worksheet><sheetData/><pageMargins left="0.7"/></worksheet>"#;
+    assert_eq!(parse_print_options(xml), PrintOptions::default());
+}
+
+#[test]
+fn test_parse_page_setup_error_display() {
+    let xml = r#"<worksheet><sheetData/><printOptions headings="1"/><pageMargins left="0.7"/><pageSetup orientation="landscape" errors="dash"/></worksheet>"#;
+    let print_options: PrintOptions = parse_print_options(xml);
+    assert!(print_options.headings);
+    assert_eq!(print_options.errors, ErrorDisplay::Dash);
+    assert_eq!(print_options.errors.show("#DIV/0!"), "--");
+    assert_eq!(ErrorDisplay::from_attr("NA").show("#REF!"), "#N/A");
+    assert_eq!(ErrorDisplay::from_attr("blank").show("#REF!"), "");
+    assert_eq!(ErrorDisplay::Displayed.show("#REF!"), "#REF!");
+}
+
+#[test]
+fn test_parse_frozen_pane() {
+    let xml = r#"<worksheet><sheetViews><sheetView workbookViewId="0"><pane xSplit="1" ySplit="2" topLeftCell="B3" activePane="bottomRight" state="frozen"/></sheetView></sheetViews><sheetData/></worksheet>"#;
+    let print_options: PrintOptions = parse_print_options(xml);
+    assert_eq!(print_options.frozen_rows, Some((1, 2)));
+    assert_eq!(print_options.frozen_cols, Some((1, 1)));
+
+    // Frozen rows start at the row the view is scrolled to.
+    let xml = r#"<worksheet><sheetViews><sheetView topLeftCell="A5" workbookViewId="0"><pane ySplit="1" topLeftCell="A6" state="frozen"/></sheetView></sheetViews></worksheet>"#;
+    let print_options: PrintOptions = parse_print_options(xml);
+    assert_eq!(print_options.frozen_rows, Some((5, 5)));
+    assert_eq!(print_options.frozen_cols, None);
+
+    // A split pane scrolls; it is not a title.
+    let xml = r#"<worksheet><sheetViews><sheetView workbookViewId="0"><pane xSplit="2000" ySplit="600" state="split"/></sheetView></sheetViews></worksheet>"#;
+    assert_eq!(parse_print_options(xml), PrintOptions::default());
+}
+
+#[test]
+fn test_frozen_panes_fill_in_missing_print_titles() {
+    let print_options = PrintOptions {
+        frozen_rows: Some((1, 1)),
+        frozen_cols: Some((1, 2)),
+        ..PrintOptions::default()
+    };
+    let titles: PrintTitles = print_options.with_frozen_titles(PrintTitles {
+        rows: Some((2, 3)),
+        cols: None,
+    });
+    assert_eq!(titles.rows, Some((2, 3)));
+    assert_eq!(titles.cols, Some((1, 2)));
+}
+
+#[test]
+fn test_with_gridlines_keeps_cell_borders() {
+    let own = BorderSide {
+        width: 2.0,
+        color: Color::black(),
+        style: BorderLineStyle::Solid,
+    };
+    let border: CellBorder = with_gridlines(Some(CellBorder {
+        top: Some(own),
+        ..CellBorder::default()
+    }));
+    assert_eq!(border.top.map(|side| side.width), Some(2.0));
+    for side in [border.bottom, border.left, border.right] {
+        assert!(side.is_some_and(is_gridline));
+    }
+}
+
+#[test]
+fn test_headings_width_follows_last_row_digits() {
+    assert_eq!(
+        SheetHeadings::new(&[1], 9).width,
+        4.0 * HEADING_DIGIT_WIDTH_PT
+    );
+    assert_eq!(
+        SheetHeadings::new(&[1], 12_345).width,
+        7.0 * HEADING_DIGIT_WIDTH_PT
+    );
+    assert_eq!(
+        SheetHeadings::new(&[1, 2, 28], 1).column_labels,
+        vec!["A", "B", "AB"]
+    );
+    assert_eq!(
+        SheetHeadings::new(&[1], 1).title_columns(Some((2, 3))),
+        (0, 4)
+    );
+}
+
+#[test]
+fn test_gridlines_and_headings_frame_the_sheet() {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut("A1").set_value("Name");
+        sheet.get_cell_mut("B1").set_value("Qty");
+        sheet.get_cell_mut("A2").set_value("Pens");
+        sheet.get_cell_mut("B2").set_value("3");
+    }
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    let data: Vec<u8> = cursor.into_inner();
+
+    let options = ConvertOptions {
+        xlsx_gridlines: true,
+        xlsx_headings: true,
+        ..ConvertOptions::default()
+    };
+    let (doc, _warnings) = crate::parser::xlsx::XlsxParser
+        .parse(&data, &options)
+        .unwrap();
+    let Page::Sheet(page) = &doc.pages[0] else {
+        panic!("expected a sheet page");
+    };
+    let texts: Vec<Vec<String>> = page
+        .table
+        .rows
+        .iter()
+        .map(|row| row.cells.iter().map(cell_text).collect())
+        .collect();
+    assert_eq!(
+        texts,
+        vec![
+            vec!["", "A", "B"],
+            vec!["1", "Name", "Qty"],
+            vec!["2", "Pens", "3"],
+        ]
+    );
+    assert_eq!(page.table.header_row_count, 1);
+    assert_eq!(page.table.column_widths.len(), 3);
+    assert_eq!(page.table.rows[0].cells[1].background, Some(HEADING_FILL));
+    assert!(
+        page.table.rows[2].cells[2]
+            .border
+            .clone()
+            .and_then(|border| border.bottom)
+            .is_some_and(is_gridline)
+    );
+
+    let (doc, _warnings) = crate::parser::xlsx::XlsxParser
+        .parse(&data, &ConvertOptions::default())
+        .unwrap();
+    let Page::Sheet(page) = &doc.pages[0] else {
+        panic!("expected a sheet page");
+    };
+    assert_eq!(page.table.rows.len(), 2);
+    assert!(page.table.rows[1].cells[1].border.is_none());
+}
@@ -0,0 +1,65 @@
+//! Redaction of protected sheets (`ConvertOptions::xlsx_redact_hidden`).
+//! Excel's "Hidden" cell protection only keeps a cell's formula out of the
+//! formula bar while its sheet is protected; the value still prints. When
+//! redacting, such cells print blank instead, so a converted copy exposes
+//! neither the formula nor what it computes.
+
+use std::collections::HashSet;
+
+use super::xlsx_cells::SheetContext;
+use crate::error::{ConvertWarning, WarningLocation};
+
+/// Whether the worksheet's protection is switched on.
+pub(super) fn is_protected(sheet: &umya_spreadsheet::Worksheet) -> bool {
+    sheet
+        .get_sheet_protection()
+        .is_some_and(|protection| *protection.get_sheet())
+}
+
+/// Whether a cell's format marks it hidden.
+pub(super) fn is_hidden_cell(cell: &umya_spreadsheet::Cell) -> bool {
+    cell.get_style()
+        .get_protection()
+        .is_some_and(|protection| *protection.get_hidden())
+}
+
+/// The non-empty hidden cells of a protected sheet's rendered range, as
+/// `(col, row)`. Unprotected sheets redact nothing, as Excel shows their
+/// hidden cells.
+pub(super) fn redacted_cells(
+    sheet: &umya_spreadsheet::Worksheet,
+    ctx: &SheetContext,
+    row_start: u32,
+    row_end: u32,
+) -> HashSet<(u32, u32)> {
+    if !is_protected(sheet) {
+        return HashSet::new();
+    }
+    sheet
+        .get_cell_collection()
+        .into_iter()
+        .filter(|cell| is_hidden_cell(cell) && !cell.get_value().is_empty())
+        .map(|cell| {
+            let coordinate = cell.get_coordinate();
+            (*coordinate.get_col_num(), *coordinate.get_row_num())
+        })
+        .filter(|(col, row)| {
+            (ctx.col_start..=ctx.col_end).contains(col) && (row_start..=row_end).contains(row)
+        })
+        .collect()
+}
+
+/// The warning summarizing one sheet's redactions.
+pub(super) fn redaction_warning(sheet_name: &str, count: usize) -> ConvertWarning {
+    let cells: &str = if count == 1 { "cell" } else { "cells" };
+    ConvertWarning::PartialElement {
+        format: "XLSX".to_string(),
+        element: format!("sheet '{sheet_name}'"),
+        detail: format!("{count} hidden {cells} of the protected sheet redacted"),
+        location: Some(WarningLocation::Sheet(sheet_name.to_string())),
+    }
+}
+
+#[cfg(test)]
+#[path = "xlsx_protection_tests.rs"]
+mod tests;
@@ -0,0 +1,70 @@
+use super::*;
+use crate::config::ConvertOptions;
+use crate::ir::{Block, Page};
+use crate::parser::Parser;
+
+/// A sheet with a visible "Label" in A1 and a hidden "Secret" in B1.
+fn build_workbook(protected: bool) -> Vec<u8> {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut("A1").set_value("Label");
+        let cell = sheet.get_cell_mut("B1");
+        cell.set_value("Secret");
+        let mut protection = umya_spreadsheet::Protection::default();
+        protection.set_hidden(true);
+        cell.get_style_mut().set_protection(protection);
+        if protected {
+            sheet.get_sheet_protection_mut().set_sheet(true);
+        }
+    }
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    cursor.into_inner()
+}
+
+fn first_row_texts(data: &[u8], redact: bool) -> (Vec<String>, Vec<ConvertWarning>) {
+    let options = ConvertOptions {
+        xlsx_redact_hidden: redact,
+        ..ConvertOptions::default()
+    };
+    let (doc, warnings) = crate::parser::xlsx::XlsxParser
+        .parse(data, &options)
+        .unwrap();
+    let Page::Sheet(page) = &doc.pages[0] else {
+        panic!("expected a sheet page");
+    };
+    let texts: Vec<String> = page.table.rows[0]
+        .cells
+        .iter()
+        .map(|cell| match cell.content.first() {
+            Some(Block::Paragraph(paragraph)) => {
+                paragraph.runs.iter().map(|run| run.text.as_str()).collect()
+            }
+            _ => String::new(),
+        })
+        .collect();
+    (texts, warnings)
+}
+
+#[test]
+fn test_redaction_blanks_hidden_cells_of_protected_sheets() {
+    let (texts, warnings) = first_row_texts(&build_workbook(true), true);
+    assert_eq!(texts, vec!["Label", ""]);
+    assert_eq!(
+        warnings,
+        vec![redaction_warning("Sheet1", 1)],
+        "{warnings:?}"
+    );
+}
+
+#[test]
+fn test_hidden_cells_print_without_redaction_or_protection() {
+    let (texts, warnings) = first_row_texts(&build_workbook(true), false);
+    assert_eq!(texts, vec!["Label", "Secret"]);
+    assert!(warnings.is_empty(), "{warnings:?}");
+
+    let (texts, warnings) = first_row_texts(&build_workbook(false), true);
+    assert_eq!(texts, vec!["Label", "Secret"]);
+    assert!(warnings.is_empty(), "{warnings:?}");
+}
@@ -0,0 +1,119 @@
+//! `ConvertOptions::xlsx_ranges`: convert chosen cell ranges instead of
+//! whole sheets. A range is written `Sheet1!A1:F50` (`'My Sheet'!A1:F50`
+//! when the sheet name needs quoting) or names a workbook defined name that
+//! refers to one or more ranges. Each range prints like a sheet whose print
+//! area is that range.
+
+use crate::config::ConvertOptions;
+use crate::error::ConvertWarning;
+
+use super::xlsx_cells::{CellRange, parse_cell_ref};
+
+/// The sheets to convert, each with the range that replaces its print area.
+/// Without `xlsx_ranges` every sheet is converted whole, in workbook order;
+/// with it, the selected ranges are converted in the order given.
+pub(super) fn sheet_targets<'a>(
+    book: &'a umya_spreadsheet::Spreadsheet,
+    options: &ConvertOptions,
+    warnings: &mut Vec<ConvertWarning>,
+) -> Vec<(&'a umya_spreadsheet::Worksheet, Option<CellRange>)> {
+    let sheets: &[umya_spreadsheet::Worksheet] = book.get_sheet_collection();
+    let Some(specs) = &options.xlsx_ranges else {
+        return sheets.iter().map(|sheet| (sheet, None)).collect();
+    };
+
+    let mut targets: Vec<(&umya_spreadsheet::Worksheet, Option<CellRange>)> = Vec::new();
+    for spec in specs {
+        let resolved: Vec<(String, CellRange)> = resolve_range(book, spec);
+        if resolved.is_empty() {
+            warnings.push(ConvertWarning::ParseSkipped {
+                format: "XLSX".to_string(),
+                reason: format!("range '{spec}' not found"),
+                location: None,
+            });
+        }
+        for (sheet_name, range) in resolved {
+            // Excel compares sheet names case-insensitively.
+            let sheet_name: String = sheet_name.to_lowercase();
+            match sheets
+                .iter()
+                .find(|sheet| sheet.get_name().to_lowercase() == sheet_name)
+            {
+                Some(sheet) => targets.push((sheet, Some(range))),
+                None => warnings.push(ConvertWarning::ParseSkipped {
+                    format: "XLSX".to_string(),
+                    reason: format!("range '{spec}' refers to a missing sheet"),
+                    location: None,
+                }),
+            }
+        }
+    }
+    targets
+}
+
+/// Resolve one `xlsx_ranges` entry into (sheet name, range) pairs: a direct
+/// `Sheet!A1:B2` reference, or the ranges of a defined name with that name.
+pub(super) fn resolve_range(
+    book: &umya_spreadsheet::Spreadsheet,
+    spec: &str,
+) -> Vec<(String, CellRange)> {
+    let spec: &str = spec.trim();
+    if spec.contains('!') {
+        return parse_sheet_range(spec).into_iter().collect();
+    }
+    // Defined names are case-insensitive too. Sheet-scoped names sit on
+    // their worksheet.
+    let name: String = spec.to_lowercase();
+    let addresses: Vec<String> = book
+        .get_defined_names()
+        .iter()
+        .chain(
+            book.get_sheet_collection()
+                .iter()
+                .flat_map(|sheet| sheet.get_defined_names()),
+        )
+        .filter(|defined_name| defined_name.get_name().to_lowercase() == name)
+        .map(|defined_name| defined_name.get_address())
+        .collect();
+    addresses
+        .iter()
+        .flat_map(|address| address.split(','))
+        .filter_map(parse_sheet_range)
+        .collect()
+}
+
+/// Parse `Sheet1!$A$1:$F$50`, `'My Sheet'!A1:F50`, or a single cell like
+/// `Sheet1!B2`.
+pub(super) fn parse_sheet_range(reference: &str) -> Option<(String, CellRange)> {
+    let (sheet_part, range_part) = reference.trim().rsplit_once('!')?;
+    let sheet_name: String = match sheet_part
+        .strip_prefix('\'')
+        .and_then(|quoted| quoted.strip_suffix('\''))
+    {
+        Some(quoted) => quoted.replace("''", "'"),
+        None => sheet_part.to_string(),
+    };
+    if sheet_name.is_empty() {
+        return None;
+    }
+
+    let range_part: String = range_part.trim().to_ascii_uppercase();
+    let (start, end) = range_part
+        .split_once(':')
+        .unwrap_or((range_part.as_str(), range_part.as_str()));
+    let (start_col, start_row) = parse_cell_ref(start)?;
+    let (end_col, end_row) = parse_cell_ref(end)?;
+    Some((
+        sheet_name,
+        CellRange {
+            start_col: start_col.min(end_col),
+            start_row: start_row.min(end_row),
+            end_col: start_col.max(end_col),
+            end_row: start_row.max(end_row),
+        },
+    ))
+}
+
+#[cfg(test)]
+#[path = "xlsx_ranges_tests.rs"]
+mod tests;
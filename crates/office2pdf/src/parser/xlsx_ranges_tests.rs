@@ -0,0 +1,126 @@
+use super::*;
+use crate::ir::{Block, Page, SheetPage};
+use crate::parser::Parser;
+
+fn bounds(range: CellRange) -> (u32, u32, u32, u32) {
+    (
+        range.start_col,
+        range.start_row,
+        range.end_col,
+        range.end_row,
+    )
+}
+
+#[test]
+fn test_parse_sheet_range_forms() {
+    let (sheet, range) = parse_sheet_range("Sheet1!$A$1:$F$50").unwrap();
+    assert_eq!((sheet.as_str(), bounds(range)), ("Sheet1", (1, 1, 6, 50)));
+
+    let (sheet, range) = parse_sheet_range("'Q1 ''24'!c9:b2").unwrap();
+    assert_eq!((sheet.as_str(), bounds(range)), ("Q1 '24", (2, 2, 3, 9)));
+
+    let (_, range) = parse_sheet_range("Data!D4").unwrap();
+    assert_eq!(bounds(range), (4, 4, 4, 4));
+
+    assert!(parse_sheet_range("A1:B2").is_none());
+    assert!(parse_sheet_range("Sheet1!nonsense").is_none());
+}
+
+/// A 4x6 sheet whose cells read "A1", "B1", ... plus a second sheet.
+fn build_workbook(setup: impl FnOnce(&mut umya_spreadsheet::Spreadsheet)) -> Vec<u8> {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        for row in 1..=6u32 {
+            for col in 1..=4u32 {
+                let name: String = format!(
+                    "{}{row}",
+                    umya_spreadsheet::helper::coordinate::string_from_column_index(&col)
+                );
+                sheet.get_cell_mut((col, row)).set_value(name);
+            }
+        }
+    }
+    book.new_sheet("Notes")
+        .unwrap()
+        .get_cell_mut("A1")
+        .set_value("note");
+    setup(&mut book);
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    cursor.into_inner()
+}
+
+fn page_texts(page: &SheetPage) -> Vec<Vec<String>> {
+    page.table
+        .rows
+        .iter()
+        .map(|row| {
+            row.cells
+                .iter()
+                .map(|cell| match cell.content.first() {
+                    Some(Block::Paragraph(paragraph)) => {
+                        paragraph.runs.iter().map(|run| run.text.as_str()).collect()
+                    }
+                    _ => String::new(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn convert(data: &[u8], ranges: &[&str]) -> (Vec<Page>, Vec<ConvertWarning>) {
+    let options = ConvertOptions {
+        xlsx_ranges: Some(ranges.iter().map(|range| range.to_string()).collect()),
+        ..ConvertOptions::default()
+    };
+    let (doc, warnings) = crate::parser::xlsx::XlsxParser
+        .parse(data, &options)
+        .unwrap();
+    (doc.pages, warnings)
+}
+
+#[test]
+fn test_ranges_convert_in_the_order_given() {
+    let data: Vec<u8> = build_workbook(|_| {});
+    let (pages, warnings) = convert(&data, &["notes!A1", "Sheet1!B2:C3", "Sheet1!D6"]);
+    assert!(warnings.is_empty(), "{warnings:?}");
+    let texts: Vec<Vec<Vec<String>>> = pages
+        .iter()
+        .map(|page| match page {
+            Page::Sheet(page) => page_texts(page),
+            _ => panic!("expected sheet pages"),
+        })
+        .collect();
+    assert_eq!(
+        texts,
+        vec![
+            vec![vec!["note"]],
+            vec![vec!["B2", "C2"], vec!["B3", "C3"]],
+            vec![vec!["D6"]],
+        ]
+    );
+}
+
+#[test]
+fn test_defined_name_selects_its_range() {
+    let data: Vec<u8> = build_workbook(|book| {
+        book.get_sheet_mut(&0)
+            .unwrap()
+            .add_defined_name("Dashboard", "Sheet1!$C$5:$D$6")
+            .unwrap();
+    });
+    let (pages, _warnings) = convert(&data, &["dashboard"]);
+    let [Page::Sheet(page)] = pages.as_slice() else {
+        panic!("expected one sheet page");
+    };
+    assert_eq!(page_texts(page), vec![vec!["C5", "D5"], vec!["C6", "D6"]]);
+}
+
+#[test]
+fn test_unknown_range_warns() {
+    let data: Vec<u8> = build_workbook(|_| {});
+    let (pages, warnings) = convert(&data, &["Missing", "Nowhere!A1:B2"]);
+    assert!(pages.is_empty());
+    assert_eq!(warnings.len(), 2);
+}
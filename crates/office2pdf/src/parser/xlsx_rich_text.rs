@@ -0,0 +1,155 @@
+//! Superscript and subscript runs in rich-text shared strings. umya keeps
+//! each `<si><r>` run's font but drops its `<vertAlign>`, so the runs are read
+//! from the raw `xl/sharedStrings.xml` and matched to cells through each
+//! worksheet's `<c t="s"><v>` indices.
+
+use std::collections::HashMap;
+
+use quick_xml::events::Event;
+
+use super::xlsx_drawing::{parse_rels_targets, parse_workbook_sheet_rids, read_zip_entry_string};
+use crate::ir::VerticalTextAlign;
+use crate::parser::xml_util::get_attr_str;
+
+/// Vertical alignment of every `<r>` run of one shared string, in order.
+pub(super) type RunVerticalAligns = Vec<Option<VerticalTextAlign>>;
+
+/// Collect the raised or lowered rich-text runs of every worksheet's cells,
+/// keyed by sheet name and then `(col, row)`, both 1-indexed.
+pub(super) fn extract_rich_run_vertical_aligns(
+    data: &[u8],
+) -> HashMap<String, HashMap<(u32, u32), RunVerticalAligns>> {
+    let Ok(mut archive) = crate::parser::open_zip(data) else {
+        return HashMap::new();
+    };
+    let shared_strings_xml = read_zip_entry_string(&mut archive, "xl/sharedStrings.xml");
+    let shared_aligns: HashMap<u32, RunVerticalAligns> =
+        parse_shared_string_vertical_aligns(&shared_strings_xml);
+    // Most workbooks have no super- or subscript: skip the sheet pass.
+    if shared_aligns.is_empty() {
+        return HashMap::new();
+    }
+
+    let workbook_xml = read_zip_entry_string(&mut archive, "xl/workbook.xml");
+    let workbook_rels_xml = read_zip_entry_string(&mut archive, "xl/_rels/workbook.xml.rels");
+    let rid_to_target = parse_rels_targets(&workbook_rels_xml);
+
+    let mut result: HashMap<String, HashMap<(u32, u32), RunVerticalAligns>> = HashMap::new();
+    for (sheet_name, sheet_rid) in parse_workbook_sheet_rids(&workbook_xml) {
+        let Some(sheet_target) = rid_to_target.get(&sheet_rid) else {
+            continue;
+        };
+        let sheet_path: String = format!("xl/{}", sheet_target.trim_start_matches("/xl/"));
+        let sheet_xml = read_zip_entry_string(&mut archive, &sheet_path);
+        let cells: HashMap<(u32, u32), RunVerticalAligns> =
+            parse_shared_string_cells(&sheet_xml, &shared_aligns);
+        if !cells.is_empty() {
+            result.insert(sheet_name, cells);
+        }
+    }
+    result
+}
+
+/// Per-run vertical alignment of the shared strings (by `<si>` index) that
+/// have at least one superscript or subscript run.
+pub(super) fn parse_shared_string_vertical_aligns(xml: &str) -> HashMap<u32, RunVerticalAligns> {
+    let mut result: HashMap<u32, RunVerticalAligns> = HashMap::new();
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut next_index: u32 = 0;
+    let mut runs: RunVerticalAligns = Vec::new();
+    let mut in_run = false;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                b"si" => runs.clear(),
+                b"r" => {
+                    in_run = true;
+                    runs.push(None);
+                }
+                _ => {}
+            },
+            Ok(Event::Empty(ref e)) => match e.local_name().as_ref() {
+                b"si" => next_index += 1,
+                b"vertAlign" if in_run => {
+                    let align: Option<VerticalTextAlign> = match get_attr_str(e, b"val").as_deref()
+                    {
+                        Some("superscript") => Some(VerticalTextAlign::Superscript),
+                        Some("subscript") => Some(VerticalTextAlign::Subscript),
+                        _ => None,
+                    };
+                    if let Some(last) = runs.last_mut() {
+                        *last = align;
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                b"r" => in_run = false,
+                b"si" => {
+                    if runs.iter().any(Option::is_some) {
+                        result.insert(next_index, std::mem::take(&mut runs));
+                    }
+                    next_index += 1;
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    result
+}
+
+/// Cells of one worksheet holding one of `shared_aligns`' strings.
+pub(super) fn parse_shared_string_cells(
+    sheet_xml: &str,
+    shared_aligns: &HashMap<u32, RunVerticalAligns>,
+) -> HashMap<(u32, u32), RunVerticalAligns> {
+    let mut cells: HashMap<(u32, u32), RunVerticalAligns> = HashMap::new();
+    let mut reader = quick_xml::Reader::from_str(sheet_xml);
+    // Address of the shared-string cell being read, if any.
+    let mut current_cell: Option<(u32, u32)> = None;
+    let mut in_value = false;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                b"c" => {
+                    current_cell = None;
+                    if get_attr_str(e, b"t").as_deref() == Some("s")
+                        && let Some(reference) = get_attr_str(e, b"r")
+                    {
+                        let (col, row, _, _) =
+                            umya_spreadsheet::helper::coordinate::index_from_coordinate(&reference);
+                        current_cell = col.zip(row);
+                    }
+                }
+                b"v" => in_value = current_cell.is_some(),
+                _ => {}
+            },
+            Ok(Event::Text(ref text)) if in_value => {
+                let index: Option<u32> = text
+                    .xml_content()
+                    .ok()
+                    .and_then(|value| value.trim().parse().ok());
+                if let (Some(cell), Some(aligns)) = (
+                    current_cell,
+                    index.and_then(|index| shared_aligns.get(&index)),
+                ) {
+                    cells.insert(cell, aligns.clone());
+                }
+            }
+            Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                b"v" => in_value = false,
+                b"c" => current_cell = None,
+                _ => {}
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    cells
+}
+
+#[cfg(test)]
+#[path = "xlsx_rich_text_tests.rs"]
+mod tests;
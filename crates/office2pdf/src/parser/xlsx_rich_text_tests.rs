@@ -0,0 +1,40 @@
+use super::*;
+
+#[test]
+fn test_parse_shared_string_vertical_aligns_keeps_run_order() {
+    let xml = r#"<sst count="3" uniqueCount="3">
+        <si><t>plain</t></si>
+        <si>
+            <r><t>m</t></r>
+            <r><rPr><vertAlign val="superscript"/><sz val="11"/></rPr><t>2</t></r>
+        </si>
+        <si><r><rPr><b/></rPr><t>H</t></r><r><rPr><vertAlign val="subscript"/></rPr><t>2</t></r><r><t>O</t></r></si>
+    </sst>"#;
+    let aligns: HashMap<u32, RunVerticalAligns> = parse_shared_string_vertical_aligns(xml);
+    assert_eq!(aligns.len(), 2, "strings without raised runs are skipped");
+    assert_eq!(aligns[&1], vec![None, Some(VerticalTextAlign::Superscript)]);
+    assert_eq!(
+        aligns[&2],
+        vec![None, Some(VerticalTextAlign::Subscript), None]
+    );
+}
+
+#[test]
+fn test_parse_shared_string_cells_maps_addresses() {
+    let shared_aligns: HashMap<u32, RunVerticalAligns> =
+        HashMap::from([(1, vec![None, Some(VerticalTextAlign::Superscript)])]);
+    let sheet = r#"<worksheet><sheetData>
+        <row r="2">
+            <c r="A2" t="s"><v>0</v></c>
+            <c r="B2" t="s"><v>1</v></c>
+            <c r="C2"><v>1</v></c>
+        </row>
+    </sheetData></worksheet>"#;
+    let cells: HashMap<(u32, u32), RunVerticalAligns> =
+        parse_shared_string_cells(sheet, &shared_aligns);
+    assert_eq!(cells.len(), 1, "numbers equal to an index are not strings");
+    assert_eq!(
+        cells[&(2, 2)],
+        vec![None, Some(VerticalTextAlign::Superscript)]
+    );
+}
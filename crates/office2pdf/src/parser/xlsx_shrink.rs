@@ -0,0 +1,122 @@
+//! Shrink-to-fit cells (`<alignment shrinkToFit="1"/>` on a cell format).
+//! umya does not keep the flag, so the cell formats are read from the raw
+//! `xl/styles.xml` and matched against each worksheet's `<c s>` indices.
+
+use std::collections::{HashMap, HashSet};
+
+use quick_xml::events::Event;
+
+use super::xlsx_drawing::{parse_rels_targets, parse_workbook_sheet_rids, read_zip_entry_string};
+use crate::parser::xml_util::get_attr_str;
+
+/// Collect the shrink-to-fit cells of every worksheet as `(col, row)`,
+/// both 1-indexed, keyed by sheet name.
+pub(super) fn extract_shrink_to_fit_cells(data: &[u8]) -> HashMap<String, HashSet<(u32, u32)>> {
+    let Ok(mut archive) = crate::parser::open_zip(data) else {
+        return HashMap::new();
+    };
+    let styles_xml = read_zip_entry_string(&mut archive, "xl/styles.xml");
+    let shrink_formats: HashSet<u32> = parse_shrink_to_fit_formats(&styles_xml);
+    // Most workbooks never shrink: skip the second pass over the sheets.
+    if shrink_formats.is_empty() {
+        return HashMap::new();
+    }
+
+    let workbook_xml = read_zip_entry_string(&mut archive, "xl/workbook.xml");
+    let workbook_rels_xml = read_zip_entry_string(&mut archive, "xl/_rels/workbook.xml.rels");
+    let rid_to_target = parse_rels_targets(&workbook_rels_xml);
+
+    let mut result: HashMap<String, HashSet<(u32, u32)>> = HashMap::new();
+    for (sheet_name, sheet_rid) in parse_workbook_sheet_rids(&workbook_xml) {
+        let Some(sheet_target) = rid_to_target.get(&sheet_rid) else {
+            continue;
+        };
+        let sheet_path: String = format!("xl/{}", sheet_target.trim_start_matches("/xl/"));
+        let sheet_xml = read_zip_entry_string(&mut archive, &sheet_path);
+        let cells: HashSet<(u32, u32)> = parse_shrink_to_fit_cells(&sheet_xml, &shrink_formats);
+        if !cells.is_empty() {
+            result.insert(sheet_name, cells);
+        }
+    }
+    result
+}
+
+/// Indices into `<cellXfs>` whose alignment sets `shrinkToFit`.
+pub(super) fn parse_shrink_to_fit_formats(styles_xml: &str) -> HashSet<u32> {
+    let mut formats: HashSet<u32> = HashSet::new();
+    let mut reader = quick_xml::Reader::from_str(styles_xml);
+    let mut in_cell_xfs = false;
+    // Index of the `<xf>` being read inside `<cellXfs>`.
+    let mut xf_index: Option<u32> = None;
+    let mut next_index: u32 = 0;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                b"cellXfs" => in_cell_xfs = true,
+                b"xf" if in_cell_xfs => {
+                    xf_index = Some(next_index);
+                    next_index += 1;
+                }
+                _ => {}
+            },
+            Ok(Event::Empty(ref e)) => match e.local_name().as_ref() {
+                b"xf" if in_cell_xfs => next_index += 1,
+                b"alignment" => {
+                    if let Some(index) = xf_index
+                        && get_attr_str(e, b"shrinkToFit")
+                            .is_some_and(|value| value == "1" || value == "true")
+                    {
+                        formats.insert(index);
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                b"cellXfs" => in_cell_xfs = false,
+                b"xf" => xf_index = None,
+                _ => {}
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    formats
+}
+
+/// Cells of one worksheet whose `s` attribute names a shrink-to-fit format.
+pub(super) fn parse_shrink_to_fit_cells(
+    sheet_xml: &str,
+    shrink_formats: &HashSet<u32>,
+) -> HashSet<(u32, u32)> {
+    let mut cells: HashSet<(u32, u32)> = HashSet::new();
+    let mut reader = quick_xml::Reader::from_str(sheet_xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                if e.local_name().as_ref() == b"c" =>
+            {
+                let is_shrunk: bool = get_attr_str(e, b"s")
+                    .and_then(|value| value.parse::<u32>().ok())
+                    .is_some_and(|index| shrink_formats.contains(&index));
+                if !is_shrunk {
+                    continue;
+                }
+                let Some(reference) = get_attr_str(e, b"r") else {
+                    continue;
+                };
+                let (col, row, _, _) =
+                    umya_spreadsheet::helper::coordinate::index_from_coordinate(&reference);
+                if let (Some(col), Some(row)) = (col, row) {
+                    cells.insert((col, row));
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    cells
+}
+
+#[cfg(test)]
+#[path = "xlsx_shrink_tests.rs"]
+mod tests;
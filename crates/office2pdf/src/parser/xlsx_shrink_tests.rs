@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn test_parse_shrink_to_fit_formats_counts_only_cell_xfs() {
+    let styles = r#"<styleSheet>
+        <cellStyleXfs count="1"><xf numFmtId="0"><alignment shrinkToFit="1"/></xf></cellStyleXfs>
+        <cellXfs count="3">
+            <xf numFmtId="0"/>
+            <xf numFmtId="0" applyAlignment="1"><alignment wrapText="1"/></xf>
+            <xf numFmtId="0" applyAlignment="1"><alignment shrinkToFit="1"/></xf>
+        </cellXfs>
+    </styleSheet>"#;
+    assert_eq!(parse_shrink_to_fit_formats(styles), HashSet::from([2]));
+}
+
+#[test]
+fn test_parse_shrink_to_fit_cells_matches_style_index() {
+    let sheet = r#"<worksheet><sheetData>
+        <row r="1"><c r="A1" s="2" t="s"><v>0</v></c><c r="B1" s="1"><v>5</v></c></row>
+        <row r="3"><c r="C3" s="2"/></row>
+    </sheetData></worksheet>"#;
+    assert_eq!(
+        parse_shrink_to_fit_cells(sheet, &HashSet::from([2])),
+        HashSet::from([(1, 1), (3, 3)])
+    );
+}
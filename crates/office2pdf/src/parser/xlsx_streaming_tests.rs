@@ -117,3 +117,73 @@ fn test_parse_streaming_empty_sheet_skipped() {
 
     assert_eq!(chunks.len(), 0, "Empty sheet should be skipped");
 }
+
+#[test]
+fn test_parse_streaming_chunks_end_at_row_breaks() {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        for row in 1..=6u32 {
+            sheet.get_cell_mut((1, row)).set_value(format!("R{row}"));
+        }
+        let mut brk = umya_spreadsheet::Break::default();
+        brk.set_id(2);
+        brk.set_manual_page_break(true);
+        sheet.get_row_breaks_mut().add_break_list(brk);
+    }
+    let mut cursor = Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    let (chunks, _warnings) = XlsxParser
+        .parse_streaming(&cursor.into_inner(), &ConvertOptions::default(), 10)
+        .unwrap();
+
+    let row_counts: Vec<usize> = chunks
+        .iter()
+        .map(|chunk| get_sheet_page(chunk, 0).table.rows.len())
+        .collect();
+    assert_eq!(row_counts, vec![2, 4]);
+}
+
+#[test]
+fn test_parse_streaming_applies_print_scale() {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        for row in 1..=4u32 {
+            sheet.get_cell_mut((1, row)).set_value(format!("R{row}"));
+        }
+        sheet.get_page_setup_mut().set_scale(50);
+    }
+    let mut cursor = Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    let data: Vec<u8> = cursor.into_inner();
+
+    let (doc, _warnings) = XlsxParser.parse(&data, &ConvertOptions::default()).unwrap();
+    let (chunks, _warnings) = XlsxParser
+        .parse_streaming(&data, &ConvertOptions::default(), 2)
+        .unwrap();
+    let whole = get_sheet_page(&doc, 0);
+    for chunk in &chunks {
+        let page = get_sheet_page(chunk, 0);
+        assert_eq!(page.table.column_widths, whole.table.column_widths);
+        assert_eq!(page.table.rows[0].height, whole.table.rows[0].height);
+    }
+}
+
+#[test]
+fn test_chunk_anchors_move_drawings_into_their_chunk() {
+    // Anchors after printed rows 0, 3, 4 and 9 of a sheet streamed in chunks
+    // of four rows; later chunks lead with one repeated title row.
+    let mut anchors: Vec<u32> = vec![0, 3, 4, 9];
+    let chunk = |rows_before: u32, lead_rows: u32, last: bool| ChunkAnchors {
+        rows_before,
+        rows: 4,
+        lead_rows,
+        last,
+    };
+    assert_eq!(chunk(0, 0, false).take(&mut anchors, |row| row), vec![0, 3]);
+    // After the first chunk's last row: the top of the second chunk.
+    assert_eq!(chunk(4, 1, false).take(&mut anchors, |row| row), vec![1]);
+    assert_eq!(chunk(8, 1, true).take(&mut anchors, |row| row), vec![2]);
+    assert!(anchors.is_empty());
+}
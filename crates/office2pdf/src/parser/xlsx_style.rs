@@ -73,6 +73,9 @@ pub(super) fn extract_cell_text_style(cell: &umya_spreadsheet::Cell) -> TextStyl
         all_caps: None,
         small_caps: None,
         letter_spacing: None,
+        outline: None,
+        shadow: None,
+        language: None,
     }
 }
 
@@ -190,9 +193,28 @@ pub(super) fn extract_cell_alignment(
     };
     let vertical = match alignment.get_vertical() {
         V::Center => Some(crate::ir::CellVerticalAlign::Center),
-        V::Top => Some(crate::ir::CellVerticalAlign::Top),
+        // Justified and distributed text starts at the top like Excel's
+        // single-line rendering of both.
+        V::Top | V::Justify | V::Distributed => Some(crate::ir::CellVerticalAlign::Top),
         // "bottom" is Excel's default; leave None so the renderer default applies.
         _ => None,
     };
     (horizontal, vertical)
 }
+
+/// Text orientation from a cell's `textRotation`: 1-90 turn the text
+/// counterclockwise, 91-180 turn it clockwise by `value - 90` degrees, and
+/// 255 stacks the letters vertically.
+pub(super) fn extract_cell_text_rotation(
+    cell: &umya_spreadsheet::Cell,
+) -> Option<crate::ir::CellTextRotation> {
+    let rotation: u32 = *cell.get_style().get_alignment()?.get_text_rotation();
+    match rotation {
+        1..=90 => Some(crate::ir::CellTextRotation::Angle(f64::from(rotation))),
+        91..=180 => Some(crate::ir::CellTextRotation::Angle(-f64::from(
+            rotation - 90,
+        ))),
+        255 => Some(crate::ir::CellTextRotation::Stacked),
+        _ => None,
+    }
+}
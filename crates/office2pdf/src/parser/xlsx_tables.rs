@@ -0,0 +1,490 @@
+//! Excel tables (ListObjects) from `xl/tables/tableN.xml`. Excel paints a
+//! table's header, banded rows and totals row from its table style when it
+//! draws the sheet; the cells themselves carry none of that formatting. The
+//! built-in styles are resolved here against the workbook theme and laid
+//! under each cell's own formatting.
+
+use std::collections::HashMap;
+
+use quick_xml::events::Event;
+
+use super::xlsx_drawing::{
+    parse_rels_by_type, parse_rels_targets, parse_workbook_sheet_rids, read_zip_entry_string,
+    resolve_relative_xl_path,
+};
+use crate::ir::{BorderLineStyle, BorderSide, CellBorder, Color};
+use crate::parser::xml_util::{get_attr_str, parse_hex_color};
+
+/// The `<tableStyleInfo>` of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct TableStyleInfo {
+    pub(super) name: String,
+    pub(super) row_stripes: bool,
+    pub(super) column_stripes: bool,
+    pub(super) first_column: bool,
+    pub(super) last_column: bool,
+}
+
+/// A table definition as written in `xl/tables/tableN.xml`.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct RawTable {
+    /// Covered cells as `(first_col, first_row, last_col, last_row)`, 1-indexed.
+    pub(super) range: (u32, u32, u32, u32),
+    pub(super) header_rows: u32,
+    pub(super) totals_rows: u32,
+    pub(super) style: Option<TableStyleInfo>,
+}
+
+/// What a table style paints on one cell. Cell formatting takes precedence.
+#[derive(Debug, Clone, Default)]
+pub(super) struct TableCellStyle {
+    pub(super) background: Option<Color>,
+    pub(super) font_color: Option<Color>,
+    pub(super) bold: bool,
+    pub(super) border: Option<CellBorder>,
+}
+
+/// The theme colors the built-in table styles are drawn in.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct ThemePalette {
+    pub(super) dark: Color,
+    pub(super) light: Color,
+    pub(super) accents: [Color; 6],
+}
+
+impl Default for ThemePalette {
+    /// The Office theme, for workbooks without a readable theme part.
+    fn default() -> Self {
+        Self {
+            dark: Color::black(),
+            light: Color::white(),
+            accents: [
+                Color::new(0x44, 0x72, 0xC4),
+                Color::new(0xED, 0x7D, 0x31),
+                Color::new(0xA5, 0xA5, 0xA5),
+                Color::new(0xFF, 0xC0, 0x00),
+                Color::new(0x5B, 0x9B, 0xD5),
+                Color::new(0x70, 0xAD, 0x47),
+            ],
+        }
+    }
+}
+
+/// The parts of a built-in table style this renderer draws.
+#[derive(Debug, Clone, Default)]
+struct BuiltinTableStyle {
+    header_fill: Option<Color>,
+    header_font: Option<Color>,
+    /// Rule under the header row.
+    header_rule: Option<Color>,
+    body_fill: Option<Color>,
+    /// Fill of the odd body rows or columns when striping is on.
+    stripe_fill: Option<Color>,
+    body_font: Option<Color>,
+    /// Rules between body rows.
+    row_rule: Option<Color>,
+    /// Rules around every cell.
+    grid: Option<Color>,
+    /// Rules above and below the table.
+    frame: Option<Color>,
+    totals_fill: Option<Color>,
+    totals_font: Option<Color>,
+    /// Double rule above the totals row.
+    totals_rule: Option<Color>,
+}
+
+/// Resolve every worksheet's tables to per-cell styles, keyed by sheet name
+/// and then `(col, row)`.
+pub(super) fn extract_table_styles(
+    data: &[u8],
+) -> HashMap<String, HashMap<(u32, u32), TableCellStyle>> {
+    let Ok(mut archive) = crate::parser::open_zip(data) else {
+        return HashMap::new();
+    };
+    let workbook_xml = read_zip_entry_string(&mut archive, "xl/workbook.xml");
+    let workbook_rels_xml = read_zip_entry_string(&mut archive, "xl/_rels/workbook.xml.rels");
+    let rid_to_target = parse_rels_targets(&workbook_rels_xml);
+
+    let mut palette: Option<ThemePalette> = None;
+    let mut result: HashMap<String, HashMap<(u32, u32), TableCellStyle>> = HashMap::new();
+    for (sheet_name, sheet_rid) in parse_workbook_sheet_rids(&workbook_xml) {
+        let Some(sheet_target) = rid_to_target.get(&sheet_rid) else {
+            continue;
+        };
+        let sheet_path: String = format!("xl/{}", sheet_target.trim_start_matches("/xl/"));
+        let sheet_filename: &str = sheet_path.rsplit('/').next().unwrap_or(&sheet_path);
+        let sheet_rels_xml = read_zip_entry_string(
+            &mut archive,
+            &format!("xl/worksheets/_rels/{sheet_filename}.rels"),
+        );
+        let mut cells: HashMap<(u32, u32), TableCellStyle> = HashMap::new();
+        for table_target in parse_rels_by_type(&sheet_rels_xml, "/table") {
+            let table_path: String = resolve_relative_xl_path("xl/worksheets", &table_target);
+            let Some(table) =
+                parse_table_definition(&read_zip_entry_string(&mut archive, &table_path))
+            else {
+                continue;
+            };
+            // Only workbooks with tables need the theme.
+            let palette: &ThemePalette = palette.get_or_insert_with(|| {
+                parse_rels_by_type(&workbook_rels_xml, "/theme")
+                    .first()
+                    .map(|target| {
+                        let theme_xml = read_zip_entry_string(
+                            &mut archive,
+                            &resolve_relative_xl_path("xl", target),
+                        );
+                        parse_theme_palette(&theme_xml)
+                    })
+                    .unwrap_or_default()
+            });
+            cells.extend(table_cell_styles(&table, palette));
+        }
+        if !cells.is_empty() {
+            result.insert(sheet_name, cells);
+        }
+    }
+    result
+}
+
+/// Parse a `<table>` part. None without a usable `ref`.
+pub(super) fn parse_table_definition(xml: &str) -> Option<RawTable> {
+    let mut table: Option<RawTable> = None;
+    let mut reader = quick_xml::Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => match e.local_name().as_ref() {
+                b"table" => {
+                    let count = |name: &[u8], default: u32| -> u32 {
+                        get_attr_str(e, name)
+                            .and_then(|value| value.parse().ok())
+                            .unwrap_or(default)
+                    };
+                    table = get_attr_str(e, b"ref")
+                        .and_then(|range| parse_range(&range))
+                        .map(|range| RawTable {
+                            range,
+                            header_rows: count(b"headerRowCount", 1),
+                            totals_rows: count(b"totalsRowCount", 0),
+                            style: None,
+                        });
+                }
+                b"tableStyleInfo" => {
+                    let flag = |name: &[u8]| -> bool {
+                        get_attr_str(e, name).is_some_and(|value| value == "1" || value == "true")
+                    };
+                    if let Some(table) = table.as_mut()
+                        && let Some(name) = get_attr_str(e, b"name")
+                    {
+                        table.style = Some(TableStyleInfo {
+                            name,
+                            row_stripes: flag(b"showRowStripes"),
+                            column_stripes: flag(b"showColumnStripes"),
+                            first_column: flag(b"showFirstColumn"),
+                            last_column: flag(b"showLastColumn"),
+                        });
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    table
+}
+
+/// Read `dk1`, `lt1` and the six accents from a theme's `<a:clrScheme>`.
+/// Slots the theme does not define keep the Office colors.
+pub(super) fn parse_theme_palette(xml: &str) -> ThemePalette {
+    let mut palette = ThemePalette::default();
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut in_color_scheme = false;
+    // The scheme slot (`dk1`, `accent1`, ...) being read.
+    let mut slot: Option<Vec<u8>> = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => match e.local_name().as_ref() {
+                b"clrScheme" => in_color_scheme = true,
+                b"srgbClr" | b"sysClr" => {
+                    let value: Option<String> = get_attr_str(e, b"val")
+                        .filter(|_| e.local_name().as_ref() == b"srgbClr")
+                        .or_else(|| get_attr_str(e, b"lastClr"));
+                    let Some(color) = value.as_deref().and_then(parse_hex_color) else {
+                        continue;
+                    };
+                    match slot.as_deref() {
+                        Some(b"dk1") => palette.dark = color,
+                        Some(b"lt1") => palette.light = color,
+                        Some(
+                            name @ (b"accent1" | b"accent2" | b"accent3" | b"accent4" | b"accent5"
+                            | b"accent6"),
+                        ) => {
+                            let index: usize = (name[6] - b'1') as usize;
+                            palette.accents[index] = color;
+                        }
+                        _ => {}
+                    }
+                }
+                name if in_color_scheme => slot = Some(name.to_vec()),
+                _ => {}
+            },
+            Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                b"clrScheme" => in_color_scheme = false,
+                name if slot.as_deref() == Some(name) => slot = None,
+                _ => {}
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    palette
+}
+
+/// Mix `color` toward white by `amount` (Excel's positive tint).
+fn tint(color: Color, amount: f64) -> Color {
+    let channel =
+        |value: u8| -> u8 { (value as f64 + (255.0 - value as f64) * amount).round() as u8 };
+    Color::new(channel(color.r), channel(color.g), channel(color.b))
+}
+
+/// Darken `color` toward black by `amount` (Excel's negative tint).
+fn shade(color: Color, amount: f64) -> Color {
+    let channel = |value: u8| -> u8 { (value as f64 * (1.0 - amount)).round() as u8 };
+    Color::new(channel(color.r), channel(color.g), channel(color.b))
+}
+
+/// Resolve a built-in style name such as `TableStyleMedium2`. Each family
+/// repeats its layout over dk1 and accents 1 through 6.
+fn builtin_table_style(name: &str, palette: &ThemePalette) -> Option<BuiltinTableStyle> {
+    let (family, number) = ["Light", "Medium", "Dark"].iter().find_map(|family| {
+        let number: u32 = name
+            .strip_prefix("TableStyle")?
+            .strip_prefix(family)?
+            .parse()
+            .ok()?;
+        Some((*family, number))
+    })?;
+    let theme_color = |index: u32| -> Color {
+        match index {
+            0 => palette.dark,
+            _ => palette.accents[(index as usize - 1).min(5)],
+        }
+    };
+    let color_index: u32 = (number.max(1) - 1) % 7;
+    let color: Color = theme_color(color_index);
+    let white: Color = palette.light;
+    let dark: Color = palette.dark;
+    let style = match (family, number) {
+        ("Light", 1..=7) => {
+            // Accent text is a darker shade of the accent; dk1 stays black.
+            let font: Option<Color> = (color_index > 0).then(|| shade(color, 0.25));
+            BuiltinTableStyle {
+                header_font: font,
+                header_rule: Some(color),
+                stripe_fill: Some(tint(color, 0.8)),
+                body_font: font,
+                frame: Some(color),
+                totals_font: font,
+                totals_rule: Some(color),
+                ..BuiltinTableStyle::default()
+            }
+        }
+        ("Light", 8..=14) => BuiltinTableStyle {
+            header_fill: Some(color),
+            header_font: Some(white),
+            row_rule: Some(color),
+            frame: Some(color),
+            totals_rule: Some(color),
+            ..BuiltinTableStyle::default()
+        },
+        ("Light", 15..=21) => BuiltinTableStyle {
+            header_rule: Some(color),
+            stripe_fill: Some(tint(color, 0.8)),
+            grid: Some(color),
+            totals_rule: Some(color),
+            ..BuiltinTableStyle::default()
+        },
+        ("Medium", 1..=7) => BuiltinTableStyle {
+            header_fill: Some(color),
+            header_font: Some(white),
+            stripe_fill: Some(tint(color, 0.8)),
+            row_rule: Some(tint(color, 0.4)),
+            frame: Some(color),
+            totals_rule: Some(color),
+            ..BuiltinTableStyle::default()
+        },
+        ("Medium", 8..=14) => BuiltinTableStyle {
+            header_fill: Some(color),
+            header_font: Some(white),
+            body_fill: Some(tint(color, 0.8)),
+            stripe_fill: Some(tint(color, 0.6)),
+            grid: Some(white),
+            totals_fill: Some(color),
+            totals_font: Some(white),
+            ..BuiltinTableStyle::default()
+        },
+        ("Medium", 15..=21) => BuiltinTableStyle {
+            header_fill: Some(dark),
+            header_font: Some(white),
+            stripe_fill: Some(tint(color, 0.8)),
+            grid: Some(tint(color, 0.4)),
+            frame: Some(dark),
+            totals_rule: Some(dark),
+            ..BuiltinTableStyle::default()
+        },
+        ("Medium", 22..=28) => BuiltinTableStyle {
+            body_fill: Some(tint(color, 0.8)),
+            stripe_fill: Some(tint(color, 0.6)),
+            grid: Some(tint(color, 0.4)),
+            totals_rule: Some(color),
+            ..BuiltinTableStyle::default()
+        },
+        ("Dark", 1..=7) => {
+            // Shading black leaves black: dk1 tables use greys instead.
+            let (body, stripe) = if color_index == 0 {
+                (tint(dark, 0.45), tint(dark, 0.25))
+            } else {
+                (shade(color, 0.25), shade(color, 0.5))
+            };
+            BuiltinTableStyle {
+                header_fill: Some(dark),
+                header_font: Some(white),
+                header_rule: Some(white),
+                body_fill: Some(body),
+                stripe_fill: Some(stripe),
+                body_font: Some(white),
+                totals_fill: Some(stripe),
+                totals_font: Some(white),
+                totals_rule: Some(white),
+                ..BuiltinTableStyle::default()
+            }
+        }
+        ("Dark", 8..=11) => {
+            let color: Color = theme_color([0, 1, 3, 5][(number - 8) as usize]);
+            BuiltinTableStyle {
+                header_fill: Some(dark),
+                header_font: Some(white),
+                body_fill: Some(tint(color, 0.8)),
+                stripe_fill: Some(tint(color, 0.6)),
+                totals_rule: Some(dark),
+                ..BuiltinTableStyle::default()
+            }
+        }
+        _ => return None,
+    };
+    Some(style)
+}
+
+fn thin(color: Color) -> BorderSide {
+    BorderSide {
+        width: 0.5,
+        color,
+        style: BorderLineStyle::Solid,
+    }
+}
+
+/// The style a table paints on each of its cells. Tables without a style or
+/// with a custom one paint nothing.
+pub(super) fn table_cell_styles(
+    table: &RawTable,
+    palette: &ThemePalette,
+) -> HashMap<(u32, u32), TableCellStyle> {
+    let mut cells: HashMap<(u32, u32), TableCellStyle> = HashMap::new();
+    let Some(info) = &table.style else {
+        return cells;
+    };
+    let Some(style) = builtin_table_style(&info.name, palette) else {
+        return cells;
+    };
+    let (first_col, first_row, last_col, last_row) = table.range;
+    let body_start: u32 = first_row + table.header_rows;
+    let totals_start: u32 = (last_row + 1).saturating_sub(table.totals_rows);
+    for row in first_row..=last_row {
+        for col in first_col..=last_col {
+            let is_header: bool = row < body_start;
+            let is_totals: bool = !is_header && row >= totals_start;
+            let mut border = CellBorder::default();
+            let cell: TableCellStyle = if is_header {
+                border.bottom = style.header_rule.map(thin);
+                TableCellStyle {
+                    background: style.header_fill,
+                    font_color: style.header_font,
+                    bold: true,
+                    border: None,
+                }
+            } else if is_totals {
+                if row == totals_start {
+                    border.top = style.totals_rule.map(|color| BorderSide {
+                        width: 1.0,
+                        color,
+                        style: BorderLineStyle::Double,
+                    });
+                }
+                TableCellStyle {
+                    background: style.totals_fill.or(style.body_fill),
+                    font_color: style.totals_font.or(style.body_font),
+                    bold: true,
+                    border: None,
+                }
+            } else {
+                let body_row: u32 = row - body_start;
+                let is_striped: bool = (info.row_stripes && body_row % 2 == 0)
+                    || (info.column_stripes && (col - first_col) % 2 == 0);
+                if row + 1 < totals_start {
+                    border.bottom = style.row_rule.map(thin);
+                }
+                let is_emphasized_column: bool = (info.first_column && col == first_col)
+                    || (info.last_column && col == last_col);
+                TableCellStyle {
+                    background: if is_striped {
+                        style.stripe_fill.or(style.body_fill)
+                    } else {
+                        style.body_fill
+                    },
+                    font_color: style.body_font,
+                    bold: is_emphasized_column,
+                    border: None,
+                }
+            };
+            if let Some(color) = style.grid {
+                border.top = border.top.or(Some(thin(color)));
+                border.bottom = border.bottom.or(Some(thin(color)));
+                border.left = Some(thin(color));
+                border.right = Some(thin(color));
+            }
+            if let Some(color) = style.frame {
+                if row == first_row {
+                    border.top = Some(thin(color));
+                }
+                if row == last_row {
+                    border.bottom = Some(thin(color));
+                }
+            }
+            let has_border: bool = border.top.is_some()
+                || border.bottom.is_some()
+                || border.left.is_some()
+                || border.right.is_some();
+            cells.insert(
+                (col, row),
+                TableCellStyle {
+                    border: has_border.then_some(border),
+                    ..cell
+                },
+            );
+        }
+    }
+    cells
+}
+
+/// `A1:C10` as `(first_col, first_row, last_col, last_row)`.
+fn parse_range(range: &str) -> Option<(u32, u32, u32, u32)> {
+    let (first, last) = range.split_once(':').unwrap_or((range, range));
+    let (first_col, first_row) = super::xlsx_cells::parse_cell_ref(first)?;
+    let (last_col, last_row) = super::xlsx_cells::parse_cell_ref(last)?;
+    Some((first_col, first_row, last_col, last_row))
+}
+
+#[cfg(test)]
+#[path = "xlsx_tables_tests.rs"]
+mod tests;
@@ -0,0 +1,209 @@
+use super::*;
+use crate::config::ConvertOptions;
+use crate::ir::{Page, TableCell};
+use crate::parser::Parser;
+
+const TABLE_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<table xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" id="1" name="Sales" displayName="Sales" ref="A1:B4" totalsRowCount="1">
+  <autoFilter ref="A1:B3"/>
+  <tableColumns count="2"><tableColumn id="1" name="Name"/><tableColumn id="2" name="Qty" totalsRowFunction="sum"/></tableColumns>
+  <tableStyleInfo name="TableStyleMedium2" showFirstColumn="1" showLastColumn="0" showRowStripes="1" showColumnStripes="0"/>
+</table>"#;
+
+fn medium2_table() -> RawTable {
+    parse_table_definition(TABLE_XML).expect("table definition")
+}
+
+#[test]
+fn test_parse_table_definition() {
+    assert_eq!(
+        medium2_table(),
+        RawTable {
+            range: (1, 1, 2, 4),
+            header_rows: 1,
+            totals_rows: 1,
+            style: Some(TableStyleInfo {
+                name: "TableStyleMedium2".to_string(),
+                row_stripes: true,
+                column_stripes: false,
+                first_column: true,
+                last_column: false,
+            }),
+        }
+    );
+    assert_eq!(parse_table_definition("<table/>"), None);
+}
+
+#[test]
+fn test_parse_theme_palette_reads_scheme_colors() {
+    let theme = r#"<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><a:themeElements>
+        <a:clrScheme name="Custom">
+            <a:dk1><a:sysClr val="windowText" lastClr="101010"/></a:dk1>
+            <a:lt1><a:sysClr val="window" lastClr="FFFFFF"/></a:lt1>
+            <a:dk2><a:srgbClr val="44546A"/></a:dk2>
+            <a:accent1><a:srgbClr val="112233"/></a:accent1>
+            <a:accent6><a:srgbClr val="AABBCC"/></a:accent6>
+        </a:clrScheme>
+        <a:fmtScheme name="Office"><a:fillStyleLst><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill></a:fillStyleLst></a:fmtScheme>
+    </a:themeElements></a:theme>"#;
+    let palette: ThemePalette = parse_theme_palette(theme);
+    assert_eq!(palette.dark, Color::new(0x10, 0x10, 0x10));
+    assert_eq!(palette.accents[0], Color::new(0x11, 0x22, 0x33));
+    assert_eq!(palette.accents[5], Color::new(0xAA, 0xBB, 0xCC));
+    // Slots the theme leaves out keep the Office colors.
+    assert_eq!(palette.accents[1], ThemePalette::default().accents[1]);
+}
+
+#[test]
+fn test_medium_style_paints_header_stripes_and_totals() {
+    let palette = ThemePalette::default();
+    let accent1: Color = palette.accents[0];
+    let cells = table_cell_styles(&medium2_table(), &palette);
+    assert_eq!(cells.len(), 8);
+
+    let header: &TableCellStyle = &cells[&(2, 1)];
+    assert_eq!(header.background, Some(accent1));
+    assert_eq!(header.font_color, Some(Color::white()));
+    assert!(header.bold);
+
+    assert_eq!(cells[&(2, 2)].background, Some(tint(accent1, 0.8)));
+    assert_eq!(cells[&(2, 3)].background, None);
+    // showFirstColumn emphasizes the first column only.
+    assert!(cells[&(1, 3)].bold);
+    assert!(!cells[&(2, 3)].bold);
+
+    let totals: &TableCellStyle = &cells[&(2, 4)];
+    assert!(totals.bold);
+    let rule: &BorderSide = totals
+        .border
+        .as_ref()
+        .and_then(|border| border.top.as_ref())
+        .expect("totals rule");
+    assert_eq!(rule.style, BorderLineStyle::Double);
+    assert_eq!(rule.color, accent1);
+}
+
+#[test]
+fn test_column_stripes_and_unknown_styles() {
+    let mut table: RawTable = medium2_table();
+    let info: &mut TableStyleInfo = table.style.as_mut().unwrap();
+    info.row_stripes = false;
+    info.column_stripes = true;
+    info.name = "TableStyleLight9".to_string();
+    let palette = ThemePalette::default();
+    let cells = table_cell_styles(&table, &palette);
+    // Light 8-14 rule rows instead of filling them.
+    assert_eq!(cells[&(1, 3)].background, None);
+    assert_eq!(cells[&(2, 3)].background, None);
+    assert!(
+        cells[&(1, 2)]
+            .border
+            .as_ref()
+            .is_some_and(|border| border.bottom.is_some())
+    );
+
+    table.style.as_mut().unwrap().name = "TableStyleDark2".to_string();
+    let cells = table_cell_styles(&table, &palette);
+    assert_eq!(
+        cells[&(1, 2)].background,
+        Some(shade(palette.accents[0], 0.5))
+    );
+    assert_eq!(
+        cells[&(2, 2)].background,
+        Some(shade(palette.accents[0], 0.25))
+    );
+
+    table.style.as_mut().unwrap().name = "MyCompanyTable".to_string();
+    assert!(table_cell_styles(&table, &palette).is_empty());
+}
+
+/// Add `TABLE_XML` to the first worksheet of `xlsx`.
+fn with_table_part(xlsx: &[u8]) -> Vec<u8> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(xlsx.to_vec())).expect("zip");
+    let mut out = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::FileOptions::default();
+    let sheet_rels_path: &str = "xl/worksheets/_rels/sheet1.xml.rels";
+    let relationship: &str = r#"<Relationship Id="rIdTable1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/table" Target="../tables/table1.xml"/>"#;
+    let mut has_sheet_rels = false;
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).expect("entry");
+        let name: String = file.name().to_string();
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut file, &mut content).expect("text entry");
+        if name == sheet_rels_path {
+            has_sheet_rels = true;
+            content = content.replace(
+                "</Relationships>",
+                &format!("{relationship}</Relationships>"),
+            );
+        } else if name == "[Content_Types].xml" {
+            content = content.replace(
+                "</Types>",
+                r#"<Override PartName="/xl/tables/table1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.table+xml"/></Types>"#,
+            );
+        }
+        out.start_file(name, options).expect("start entry");
+        std::io::Write::write_all(&mut out, content.as_bytes()).expect("write entry");
+    }
+    let mut parts: Vec<(&str, String)> = vec![("xl/tables/table1.xml", TABLE_XML.to_string())];
+    if !has_sheet_rels {
+        parts.push((
+            sheet_rels_path,
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{relationship}</Relationships>"#
+            ),
+        ));
+    }
+    for (name, content) in parts {
+        out.start_file(name, options).expect("start entry");
+        std::io::Write::write_all(&mut out, content.as_bytes()).expect("write entry");
+    }
+    out.finish().expect("finish zip").into_inner()
+}
+
+#[test]
+fn test_table_style_renders_under_cell_formatting() {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        for (coordinate, value) in [
+            ("A1", "Name"),
+            ("B1", "Qty"),
+            ("A2", "Pens"),
+            ("B2", "3"),
+            ("A3", "Ink"),
+            ("B3", "4"),
+            ("A4", "Total"),
+            ("B4", "7"),
+        ] {
+            sheet.get_cell_mut(coordinate).set_value(value);
+        }
+        sheet
+            .get_cell_mut("A2")
+            .get_style_mut()
+            .set_background_color("FFFF0000");
+    }
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    let data: Vec<u8> = with_table_part(&cursor.into_inner());
+
+    let (doc, _warnings) = crate::parser::xlsx::XlsxParser
+        .parse(&data, &ConvertOptions::default())
+        .unwrap();
+    let Page::Sheet(page) = &doc.pages[0] else {
+        panic!("expected a sheet page");
+    };
+    let cell = |col: usize, row: usize| -> &TableCell { &page.table.rows[row].cells[col] };
+    let accent1: Color = ThemePalette::default().accents[0];
+    assert_eq!(cell(1, 0).background, Some(accent1));
+    // The cell's own fill wins over the stripe.
+    assert_eq!(cell(0, 1).background, Some(Color::new(255, 0, 0)));
+    assert_eq!(cell(1, 1).background, Some(tint(accent1, 0.8)));
+    assert_eq!(cell(1, 2).background, None);
+    let Some(crate::ir::Block::Paragraph(header)) = cell(1, 0).content.first() else {
+        panic!("expected header text");
+    };
+    assert_eq!(header.runs[0].style.bold, Some(true));
+    assert_eq!(header.runs[0].style.color, Some(Color::white()));
+}
@@ -145,6 +145,92 @@ fn test_parse_dates_as_text() {
     assert_eq!(cell_text(&tp.table.rows[1].cells[0]), "December 25");
 }
 
+fn booleans_and_errors_xlsx(page_setup_errors: Option<&str>) -> Vec<u8> {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut("A1").set_value_bool(true);
+        sheet.get_cell_mut("B1").set_value_bool(false);
+        sheet.get_cell_mut("C1").set_error("#DIV/0!");
+        sheet.get_cell_mut("D1").set_error("#N/A");
+    }
+    let mut cursor = Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    let data: Vec<u8> = cursor.into_inner();
+    let Some(errors) = page_setup_errors else {
+        return data;
+    };
+
+    // umya does not keep the print setting, so it goes into the sheet XML.
+    let mut archive = zip::ZipArchive::new(Cursor::new(data)).expect("read zip");
+    let mut out = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).expect("zip entry");
+        let name: String = file.name().to_string();
+        let mut content: Vec<u8> = Vec::new();
+        std::io::Read::read_to_end(&mut file, &mut content).expect("read entry");
+        if name == "xl/worksheets/sheet1.xml" {
+            let xml = String::from_utf8(content).expect("sheet xml utf8");
+            let attr: String = format!(r#"errors="{errors}""#);
+            let xml: String = if xml.contains("<pageSetup ") {
+                xml.replacen("<pageSetup ", &format!("<pageSetup {attr} "), 1)
+            } else {
+                xml.replacen(
+                    "</worksheet>",
+                    &format!("<pageSetup {attr}/></worksheet>"),
+                    1,
+                )
+            };
+            content = xml.into_bytes();
+        }
+        out.start_file(name, zip::write::FileOptions::default())
+            .expect("start entry");
+        std::io::Write::write_all(&mut out, &content).expect("write entry");
+    }
+    out.finish().expect("finish zip").into_inner()
+}
+
+#[test]
+fn test_booleans_and_errors_print_as_excel_shows_them() {
+    let data = booleans_and_errors_xlsx(None);
+    let (doc, _warnings) = XlsxParser.parse(&data, &ConvertOptions::default()).unwrap();
+    let row = &get_sheet_page(&doc, 0).table.rows[0];
+    let texts: Vec<String> = row.cells.iter().map(cell_text).collect();
+    assert_eq!(texts, vec!["TRUE", "FALSE", "#DIV/0!", "#N/A"]);
+    // General alignment centers booleans and errors.
+    for cell in &row.cells {
+        let Some(Block::Paragraph(paragraph)) = cell.content.first() else {
+            panic!("expected a paragraph");
+        };
+        assert_eq!(paragraph.style.alignment, Some(Alignment::Center));
+    }
+}
+
+#[test]
+fn test_error_cells_follow_print_setting_or_blank_option() {
+    let texts = |data: &[u8], options: &ConvertOptions| -> Vec<String> {
+        let (doc, _warnings) = XlsxParser.parse(data, options).unwrap();
+        get_sheet_page(&doc, 0).table.rows[0]
+            .cells
+            .iter()
+            .map(cell_text)
+            .collect()
+    };
+    let dashed = booleans_and_errors_xlsx(Some("dash"));
+    assert_eq!(
+        texts(&dashed, &ConvertOptions::default()),
+        vec!["TRUE", "FALSE", "--", "--"]
+    );
+    let blank_errors = ConvertOptions {
+        xlsx_blank_errors: true,
+        ..ConvertOptions::default()
+    };
+    assert_eq!(
+        texts(&booleans_and_errors_xlsx(None), &blank_errors),
+        vec!["TRUE", "FALSE", "", ""]
+    );
+}
+
 // ----- Sheet name tests -----
 
 #[test]
@@ -305,6 +391,98 @@ fn test_column_overflow_splits_to_second_page_like_excel() {
     );
 }
 
+/// Helper: parse a single-sheet workbook built by `fill` and return the
+/// printed column widths.
+fn parsed_column_widths(fill: impl FnOnce(&mut umya_spreadsheet::Worksheet)) -> Vec<f64> {
+    let mut book = umya_spreadsheet::new_file();
+    fill(book.get_sheet_mut(&0).unwrap());
+    let mut cursor = Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    let parser = XlsxParser;
+    let (doc, _warnings) = parser
+        .parse(&cursor.into_inner(), &ConvertOptions::default())
+        .unwrap();
+    get_sheet_page(&doc, 0).table.column_widths.clone()
+}
+
+#[test]
+fn test_sheet_default_column_width_is_used() {
+    let widths = parsed_column_widths(|sheet| {
+        sheet.get_cell_mut("A1").set_value("a");
+        sheet
+            .get_sheet_format_properties_mut()
+            .set_default_column_width(12.0);
+    });
+    // 12 chars × 8px MDW × 0.75.
+    assert_eq!(widths, vec![72.0]);
+}
+
+#[test]
+fn test_number_column_without_width_is_auto_fit() {
+    let widths = parsed_column_widths(|sheet| {
+        let cell = sheet.get_cell_mut("A1");
+        cell.set_value_number(1_234_567_890.25);
+        cell.get_style_mut()
+            .get_number_format_mut()
+            .set_format_code("#,##0.00");
+        sheet.get_cell_mut("B1").set_value_number(7);
+    });
+    // "1,234,567,890.25" needs far more than the 50.6pt default; the short
+    // number in B keeps the default.
+    assert!(widths[0] > 90.0, "got {widths:?}");
+    assert!((widths[1] - 50.58).abs() < 0.1, "got {widths:?}");
+}
+
+#[test]
+fn test_blocked_text_is_auto_fit_but_spilling_text_is_not() {
+    let widths = parsed_column_widths(|sheet| {
+        sheet
+            .get_cell_mut("A1")
+            .set_value("a label that is much too long");
+        sheet.get_cell_mut("B1").set_value("next");
+        sheet
+            .get_cell_mut("C1")
+            .set_value("free to spill over the empty neighbor");
+    });
+    assert!(widths[0] > 100.0, "got {widths:?}");
+    assert!((widths[2] - 50.58).abs() < 0.1, "got {widths:?}");
+}
+
+#[test]
+fn test_explicit_column_width_is_not_auto_fit() {
+    let widths = parsed_column_widths(|sheet| {
+        sheet.get_cell_mut("A1").set_value_number(1_234_567_890.25);
+        sheet.get_cell_mut("B1").set_value("x");
+        sheet.get_column_dimension_mut("A").set_width(5.0);
+    });
+    assert_eq!(widths[0], 30.0);
+}
+
+#[test]
+fn test_row_without_height_grows_for_large_font() {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet
+            .get_cell_mut("A1")
+            .set_value("Title")
+            .get_style_mut()
+            .get_font_mut()
+            .set_size(22.0);
+        sheet.get_cell_mut("A2").set_value("Body");
+    }
+    let mut cursor = Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+    let parser = XlsxParser;
+    let (doc, _warnings) = parser
+        .parse(&cursor.into_inner(), &ConvertOptions::default())
+        .unwrap();
+    let tp = get_sheet_page(&doc, 0);
+    // 22pt text auto-fits to a 30pt row, printed as Excel's 28pt track.
+    assert_eq!(tp.table.rows[0].height, Some(28.0));
+    assert_eq!(tp.table.rows[1].height, Some(14.0));
+}
+
 // ----- Page size and margins defaults -----
 
 #[test]
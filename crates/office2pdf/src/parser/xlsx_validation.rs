@@ -0,0 +1,316 @@
+//! List data validation (`<dataValidation type="list">`): the cells Excel
+//! shows a dropdown for. A printout loses the dropdown, so with
+//! `ConvertOptions::xlsx_annotate_dropdowns` such cells print a small ▼
+//! after their value and each sheet is followed by an appendix page listing
+//! the values its dropdowns allow. Lists drawn from another sheet are stored
+//! in the `x14` extension instead, and are read the same way.
+
+use std::collections::HashMap;
+
+use quick_xml::escape::unescape;
+use quick_xml::events::Event;
+
+use super::xlsx_cells::{CellRange, FormulaCache, format_cell_value};
+use super::xlsx_drawing::{parse_rels_targets, parse_workbook_sheet_rids, read_zip_entry_string};
+use super::xlsx_numfmt::FormatSettings;
+use super::xlsx_ranges::{parse_sheet_range, resolve_range};
+use crate::ir::{
+    Block, Color, Margins, PageSize, Paragraph, ParagraphStyle, Run, SheetPage, Table, TableCell,
+    TableRow, TextStyle,
+};
+use crate::parser::xml_util::get_attr_str;
+
+/// Width of the appendix's "Cells" column in points.
+const CELLS_COLUMN_WIDTH_PT: f64 = 120.0;
+
+/// One list validation of a worksheet.
+#[derive(Debug, Clone)]
+pub(super) struct Dropdown {
+    /// The validated cells as written in the sheet (`B2:B10 D4`).
+    pub(super) sqref: String,
+    pub(super) ranges: Vec<CellRange>,
+    /// The list source: `"Yes,No"` for values typed in, otherwise a
+    /// reference or defined name.
+    pub(super) formula: String,
+}
+
+impl Dropdown {
+    pub(super) fn contains(&self, col: u32, row: u32) -> bool {
+        self.ranges.iter().any(|range| {
+            (range.start_col..=range.end_col).contains(&col)
+                && (range.start_row..=range.end_row).contains(&row)
+        })
+    }
+
+    /// Whether any validated cell lies in the given printed area.
+    pub(super) fn overlaps(
+        &self,
+        col_start: u32,
+        col_end: u32,
+        row_start: u32,
+        row_end: u32,
+    ) -> bool {
+        self.ranges.iter().any(|range| {
+            range.start_col <= col_end
+                && range.end_col >= col_start
+                && range.start_row <= row_end
+                && range.end_row >= row_start
+        })
+    }
+
+    /// The values the dropdown offers. A reference lists the non-empty
+    /// cells of its range as they display; `sheet_name` is the sheet a
+    /// reference without a sheet points into.
+    pub(super) fn values(
+        &self,
+        book: &umya_spreadsheet::Spreadsheet,
+        sheet_name: &str,
+        settings: &FormatSettings,
+    ) -> Vec<String> {
+        let formula: &str = self.formula.trim().trim_start_matches('=');
+        if let Some(list) = formula
+            .strip_prefix('"')
+            .and_then(|list| list.strip_suffix('"'))
+        {
+            return list
+                .split(',')
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .collect();
+        }
+
+        let ranges: Vec<(String, CellRange)> = if formula.contains('!') {
+            parse_sheet_range(formula).into_iter().collect()
+        } else {
+            let quoted: String = format!("'{}'!{formula}", sheet_name.replace('\'', "''"));
+            match parse_sheet_range(&quoted) {
+                Some(range) => vec![range],
+                None => resolve_range(book, formula),
+            }
+        };
+        let mut values: Vec<String> = Vec::new();
+        for (name, range) in ranges {
+            let Some(sheet) = book.get_sheet_by_name(&name) else {
+                continue;
+            };
+            // Whole-column sources stop at the sheet's last used cell.
+            let (last_col, last_row) = sheet.get_highest_column_and_row();
+            let formulas: FormulaCache = FormulaCache::default();
+            for row in range.start_row..=range.end_row.min(last_row) {
+                for col in range.start_col..=range.end_col.min(last_col) {
+                    if let Some(cell) = sheet.get_cell((col, row)) {
+                        let text: String = format_cell_value(sheet, cell, settings, &formulas).text;
+                        if !text.is_empty() {
+                            values.push(text);
+                        }
+                    }
+                }
+            }
+        }
+        values
+    }
+}
+
+/// Read the list validations of every worksheet that has any, keyed by
+/// sheet name.
+pub(super) fn extract_dropdowns(data: &[u8]) -> HashMap<String, Vec<Dropdown>> {
+    let Ok(mut archive) = crate::parser::open_zip(data) else {
+        return HashMap::new();
+    };
+    let workbook_xml = read_zip_entry_string(&mut archive, "xl/workbook.xml");
+    let workbook_rels_xml = read_zip_entry_string(&mut archive, "xl/_rels/workbook.xml.rels");
+    let rid_to_target = parse_rels_targets(&workbook_rels_xml);
+
+    let mut result: HashMap<String, Vec<Dropdown>> = HashMap::new();
+    for (sheet_name, sheet_rid) in parse_workbook_sheet_rids(&workbook_xml) {
+        let Some(sheet_target) = rid_to_target.get(&sheet_rid) else {
+            continue;
+        };
+        let sheet_path: String = format!("xl/{}", sheet_target.trim_start_matches("/xl/"));
+        let sheet_xml = read_zip_entry_string(&mut archive, &sheet_path);
+        let dropdowns: Vec<Dropdown> = parse_dropdowns(&sheet_xml);
+        if !dropdowns.is_empty() {
+            result.insert(sheet_name, dropdowns);
+        }
+    }
+    result
+}
+
+/// Parse the list validations of one worksheet's XML, from both
+/// `<dataValidations>` and the `x14:dataValidations` extension.
+pub(super) fn parse_dropdowns(xml: &str) -> Vec<Dropdown> {
+    let mut dropdowns: Vec<Dropdown> = Vec::new();
+    let mut reader = quick_xml::Reader::from_str(xml);
+    // The list validation being read, and the element whose text is next.
+    let mut current: Option<Dropdown> = None;
+    let mut in_formula1 = false;
+    let mut in_sqref = false;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                b"dataValidation" => {
+                    let is_list: bool = get_attr_str(e, b"type").is_some_and(|kind| kind == "list");
+                    current = is_list.then(|| Dropdown {
+                        sqref: get_attr_str(e, b"sqref").unwrap_or_default(),
+                        ranges: Vec::new(),
+                        formula: String::new(),
+                    });
+                }
+                b"formula1" => in_formula1 = true,
+                b"sqref" => in_sqref = true,
+                _ => {}
+            },
+            Ok(Event::Text(ref t)) => {
+                let Some(dropdown) = current.as_mut() else {
+                    continue;
+                };
+                let text: String = t
+                    .xml_content()
+                    .map(|text| text.into_owned())
+                    .unwrap_or_default();
+                if in_formula1 {
+                    dropdown.formula.push_str(&text);
+                } else if in_sqref {
+                    dropdown.sqref.push_str(&text);
+                }
+            }
+            // quick-xml reports `&quot;` and friends as separate events.
+            Ok(Event::GeneralRef(ref reference)) => {
+                let Some(dropdown) = current.as_mut() else {
+                    continue;
+                };
+                let text: String = reference
+                    .decode()
+                    .ok()
+                    .and_then(|name| {
+                        unescape(&format!("&{name};"))
+                            .ok()
+                            .map(|value| value.into_owned())
+                    })
+                    .unwrap_or_default();
+                if in_formula1 {
+                    dropdown.formula.push_str(&text);
+                } else if in_sqref {
+                    dropdown.sqref.push_str(&text);
+                }
+            }
+            Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                b"formula1" => in_formula1 = false,
+                b"sqref" => in_sqref = false,
+                b"dataValidation" => {
+                    if let Some(mut dropdown) = current.take()
+                        && !dropdown.formula.trim().is_empty()
+                    {
+                        dropdown.ranges = parse_sqref(&dropdown.sqref);
+                        if !dropdown.ranges.is_empty() {
+                            dropdowns.push(dropdown);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    dropdowns
+}
+
+/// Parse a space-separated list of cells and ranges (`B2:B10 D4`).
+fn parse_sqref(sqref: &str) -> Vec<CellRange> {
+    sqref
+        .split_whitespace()
+        .filter_map(|reference| parse_sheet_range(&format!("S!{reference}")))
+        .map(|(_, range)| range)
+        .collect()
+}
+
+/// The ▼ printed after a dropdown cell's value.
+pub(super) fn dropdown_marker(after_text: bool) -> Run {
+    Run {
+        text: if after_text { " ▼" } else { "▼" }.to_string(),
+        style: TextStyle {
+            font_size: Some(7.0),
+            color: Some(Color::new(0x80, 0x80, 0x80)),
+            ..TextStyle::default()
+        },
+        href: None,
+        footnote: None,
+    }
+}
+
+/// The appendix page after a sheet: one row per dropdown with its cells and
+/// the values it allows. `None` for a sheet without dropdowns.
+pub(super) fn dropdown_appendix(
+    book: &umya_spreadsheet::Spreadsheet,
+    sheet_name: &str,
+    dropdowns: &[Dropdown],
+    settings: &FormatSettings,
+    size: PageSize,
+    margins: Margins,
+) -> Option<SheetPage> {
+    if dropdowns.is_empty() {
+        return None;
+    }
+    let bold = TextStyle {
+        bold: Some(true),
+        ..TextStyle::default()
+    };
+    let mut rows: Vec<TableRow> = vec![TableRow {
+        cells: vec![
+            appendix_cell("Cells", bold.clone()),
+            appendix_cell("Allowed values", bold),
+        ],
+        height: None,
+    }];
+    rows.extend(dropdowns.iter().map(|dropdown| TableRow {
+        cells: vec![
+            appendix_cell(&dropdown.sqref, TextStyle::default()),
+            appendix_cell(
+                &dropdown.values(book, sheet_name, settings).join(", "),
+                TextStyle::default(),
+            ),
+        ],
+        height: None,
+    }));
+    let content_width: f64 = size.width - margins.left - margins.right;
+    Some(SheetPage {
+        name: format!("{sheet_name} – dropdown values"),
+        size,
+        margins,
+        table: Table {
+            rows,
+            column_widths: vec![
+                CELLS_COLUMN_WIDTH_PT,
+                (content_width - CELLS_COLUMN_WIDTH_PT).max(CELLS_COLUMN_WIDTH_PT),
+            ],
+            header_row_count: 1,
+            default_cell_padding: Some(super::xlsx_cells::XLSX_CELL_PADDING),
+            ..Table::default()
+        },
+        header: None,
+        footer: None,
+        charts: Vec::new(),
+        images: Vec::new(),
+        text_boxes: Vec::new(),
+    })
+}
+
+fn appendix_cell(text: &str, style: TextStyle) -> TableCell {
+    TableCell {
+        content: vec![Block::Paragraph(Paragraph {
+            style: ParagraphStyle::default(),
+            runs: vec![Run {
+                text: text.to_string(),
+                style,
+                href: None,
+                footnote: None,
+            }],
+        })],
+        ..TableCell::default()
+    }
+}
+
+#[cfg(test)]
+#[path = "xlsx_validation_tests.rs"]
+mod tests;
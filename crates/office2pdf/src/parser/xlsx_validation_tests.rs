@@ -0,0 +1,121 @@
+use super::*;
+use crate::config::ConvertOptions;
+use crate::ir::Page;
+use crate::parser::Parser;
+
+#[test]
+fn test_parse_list_validations() {
+    let xml = r#"<worksheet xmlns:x14="http://schemas.microsoft.com/office/spreadsheetml/2009/9/main" xmlns:xm="http://schemas.microsoft.com/office/excel/2006/main"><sheetData/><dataValidations count="2"><dataValidation type="list" allowBlank="1" sqref="B2:B10 D4"><formula1>"Yes,No,Maybe"</formula1></dataValidation><dataValidation type="whole" sqref="C2"><formula1>1</formula1></dataValidation></dataValidations><extLst><ext><x14:dataValidations count="1"><x14:dataValidation type="list"><x14:formula1><xm:f>Lists!$A$1:$A$3</xm:f></x14:formula1><xm:sqref>E1</xm:sqref></x14:dataValidation></x14:dataValidations></ext></extLst></worksheet>"#;
+    let dropdowns: Vec<Dropdown> = parse_dropdowns(xml);
+    let parsed: Vec<(&str, &str)> = dropdowns
+        .iter()
+        .map(|dropdown| (dropdown.sqref.as_str(), dropdown.formula.as_str()))
+        .collect();
+    assert_eq!(
+        parsed,
+        vec![("B2:B10 D4", "\"Yes,No,Maybe\""), ("E1", "Lists!$A$1:$A$3")]
+    );
+    assert!(dropdowns[0].contains(2, 5));
+    assert!(dropdowns[0].contains(4, 4));
+    assert!(!dropdowns[0].contains(3, 4));
+    assert!(dropdowns[0].overlaps(1, 3, 1, 2));
+    assert!(!dropdowns[0].overlaps(1, 1, 1, 20));
+}
+
+#[test]
+fn test_dropdown_values_from_list_and_ranges() {
+    let mut book = umya_spreadsheet::new_file();
+    book.get_sheet_mut(&0)
+        .unwrap()
+        .get_cell_mut("F1")
+        .set_value("Local");
+    {
+        let lists = book.new_sheet("Lists").unwrap();
+        lists.get_cell_mut("A1").set_value("Red");
+        lists.get_cell_mut("A3").set_value("Blue");
+    }
+    let dropdown = |formula: &str| Dropdown {
+        sqref: "A1".to_string(),
+        ranges: Vec::new(),
+        formula: formula.to_string(),
+    };
+    let settings = FormatSettings::default();
+    assert_eq!(
+        dropdown("\"Yes, No\"").values(&book, "Sheet1", &settings),
+        vec!["Yes", "No"]
+    );
+    assert_eq!(
+        dropdown("Lists!$A$1:$A$3").values(&book, "Sheet1", &settings),
+        vec!["Red", "Blue"]
+    );
+    assert_eq!(
+        dropdown("$F$1:$F$2").values(&book, "Sheet1", &settings),
+        vec!["Local"]
+    );
+}
+
+#[test]
+fn test_dropdown_cells_are_marked_and_listed_after_the_sheet() {
+    let mut book = umya_spreadsheet::new_file();
+    {
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut("A1").set_value("Approved?");
+        sheet.get_cell_mut("B1").set_value("Yes");
+    }
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    umya_spreadsheet::writer::xlsx::write_writer(&book, &mut cursor).unwrap();
+
+    // umya's writer has no say here; the validation goes into the XML.
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(cursor.into_inner())).unwrap();
+    let mut out = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).unwrap();
+        let name: String = file.name().to_string();
+        let mut content: Vec<u8> = Vec::new();
+        std::io::Read::read_to_end(&mut file, &mut content).unwrap();
+        if name == "xl/worksheets/sheet1.xml" {
+            content = String::from_utf8(content)
+                .unwrap()
+                .replacen(
+                    "</sheetData>",
+                    r#"</sheetData><dataValidations count="1"><dataValidation type="list" sqref="B1:B2"><formula1>"Yes,No"</formula1></dataValidation></dataValidations>"#,
+                    1,
+                )
+                .into_bytes();
+        }
+        out.start_file(name, zip::write::FileOptions::default())
+            .unwrap();
+        std::io::Write::write_all(&mut out, &content).unwrap();
+    }
+    let data: Vec<u8> = out.finish().unwrap().into_inner();
+
+    let options = ConvertOptions {
+        xlsx_annotate_dropdowns: true,
+        ..ConvertOptions::default()
+    };
+    let (doc, _warnings) = crate::parser::xlsx::XlsxParser
+        .parse(&data, &options)
+        .unwrap();
+    let [Page::Sheet(sheet), Page::Sheet(appendix)] = doc.pages.as_slice() else {
+        panic!("expected the sheet and its appendix");
+    };
+    let cell_runs = |cell: &TableCell| -> Vec<String> {
+        match cell.content.first() {
+            Some(Block::Paragraph(paragraph)) => {
+                paragraph.runs.iter().map(|run| run.text.clone()).collect()
+            }
+            _ => Vec::new(),
+        }
+    };
+    assert_eq!(cell_runs(&sheet.table.rows[0].cells[0]), vec!["Approved?"]);
+    assert_eq!(cell_runs(&sheet.table.rows[0].cells[1]), vec!["Yes", " ▼"]);
+    assert_eq!(appendix.name, "Sheet1 – dropdown values");
+    assert_eq!(cell_runs(&appendix.table.rows[1].cells[0]), vec!["B1:B2"]);
+    assert_eq!(cell_runs(&appendix.table.rows[1].cells[1]), vec!["Yes, No"]);
+
+    // Without the option the sheet prints as before.
+    let (doc, _warnings) = crate::parser::xlsx::XlsxParser
+        .parse(&data, &ConvertOptions::default())
+        .unwrap();
+    assert_eq!(doc.pages.len(), 1);
+}
@@ -1,11 +1,78 @@
-//! PDF manipulation operations: merge, split, and page counting.
+//! PDF manipulation operations: merge (with bookmarks and a table of
+//! contents), split (by page ranges, bookmarks, page count or size), page
+//! rotation, reordering and deletion, page counting, text extraction and
+//! search, comparison, stamping, page and Bates numbering, overlays, bleed
+//! and crop marks, size optimization, linearization, encryption and
+//! decryption and, with the `sign` feature, digital signatures.
 //!
 //! These operations work on existing PDF files and are independent
 //! from the document conversion pipeline.
 
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use crate::config::{Format, PdfSecurity};
 use crate::error::ConvertError;
+use crate::ir::Metadata;
+use lopdf::encryption::crypt_filters::{Aes256CryptFilter, CryptFilter};
+use lopdf::encryption::{EncryptionState, EncryptionVersion, Permissions};
 use lopdf::{Document, dictionary};
 
+#[cfg(feature = "sign")]
+#[path = "pdf_ops_sign.rs"]
+mod sign;
+#[cfg(feature = "sign")]
+pub use sign::sign;
+#[cfg(feature = "sign")]
+pub(crate) use sign::sign_at;
+
+#[path = "pdf_ops_bleed.rs"]
+mod bleed;
+pub use bleed::bleed;
+
+#[path = "pdf_ops_dedupe.rs"]
+mod dedupe;
+
+#[path = "pdf_ops_diff.rs"]
+mod diff;
+pub use diff::{DiffOptions, DiffReport, PageDiff, PixelDiff, TextChange, diff};
+
+#[path = "pdf_ops_linearize.rs"]
+mod linearize;
+pub use linearize::linearize;
+
+#[path = "pdf_ops_merge.rs"]
+mod merge;
+pub use merge::{MergeOptions, merge_with_options};
+
+#[path = "pdf_ops_numbering.rs"]
+mod numbering;
+pub use numbering::{NumberingOptions, NumberingStyle, stamp_page_numbers};
+
+#[path = "pdf_ops_overlay.rs"]
+mod overlay;
+pub use overlay::{OverlayOptions, overlay};
+
+#[path = "pdf_ops_text.rs"]
+mod text;
+pub use text::{BoundingBox, Match, PageText, Word, extract_text, find_text};
+
+#[path = "pdf_ops_split.rs"]
+mod split;
+pub use split::{SplitBy, SplitPart, split_by};
+
+#[path = "pdf_ops_stamp.rs"]
+mod stamp;
+pub use stamp::stamp;
+
+#[cfg(test)]
+#[path = "pdf_ops_test_support.rs"]
+mod test_support;
+
+/// Page attributes a page inherits from the page tree when it does not set
+/// them itself.
+const INHERITED_KEYS: [&[u8]; 4] = [b"Resources", b"MediaBox", b"CropBox", b"Rotate"];
+
 /// A range of pages to extract (1-indexed, inclusive).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PageRange {
@@ -79,6 +146,22 @@ fn save_pdf_to_bytes(doc: &mut Document, context: &str) -> Result<Vec<u8>, Conve
     Ok(output)
 }
 
+/// The pages of `doc` in `range` as a PDF of their own, without the
+/// objects only the other pages used, such as their fonts and images.
+fn extract_pages(doc: &Document, range: &PageRange) -> Result<Vec<u8>, ConvertError> {
+    let mut part: Document = doc.clone();
+    let total_pages: u32 = part.get_pages().len() as u32;
+    let others: Vec<u32> = (1..=total_pages)
+        .filter(|page| *page < range.start || *page > range.end)
+        .collect();
+    if !others.is_empty() {
+        part.delete_pages(&others);
+    }
+    let referenced: BTreeSet<lopdf::ObjectId> = referenced_objects(&part).into_iter().collect();
+    part.objects.retain(|id, _| referenced.contains(id));
+    save_pdf_to_bytes(&mut part, "split")
+}
+
 /// Count the number of pages in a PDF.
 pub fn page_count(input: &[u8]) -> Result<u32, ConvertError> {
     let doc: Document = load_pdf_document(input, "")?;
@@ -90,160 +173,862 @@ pub fn page_count(input: &[u8]) -> Result<u32, ConvertError> {
 /// Each element of `inputs` is the raw bytes of a PDF file.
 /// Returns the merged PDF bytes.
 pub fn merge(inputs: &[&[u8]]) -> Result<Vec<u8>, ConvertError> {
-    if inputs.is_empty() {
-        return Err(ConvertError::Parse("no input PDFs to merge".to_string()));
-    }
+    merge_with_options(inputs, &MergeOptions::default())
+}
 
-    if inputs.len() == 1 {
-        // Single PDF — just return a copy
-        return Ok(inputs[0].to_vec());
+/// Split a PDF into multiple PDFs based on page ranges.
+///
+/// Each `PageRange` specifies a 1-indexed inclusive range of pages to extract.
+/// Returns a vector of PDF byte arrays, one per range.
+pub fn split(input: &[u8], ranges: &[PageRange]) -> Result<Vec<Vec<u8>>, ConvertError> {
+    if ranges.is_empty() {
+        return Err(ConvertError::Parse(
+            "no page ranges specified for split".to_string(),
+        ));
     }
 
-    // Load all documents
-    let documents: Vec<Document> = inputs
+    let doc: Document = load_pdf_document(input, "")?;
+
+    let total_pages: u32 = doc.get_pages().len() as u32;
+    validate_page_ranges(ranges, total_pages)?;
+
+    ranges
         .iter()
-        .enumerate()
-        .map(|(i, data)| load_pdf_document(data, &format!(" at index {i}")))
-        .collect::<Result<_, _>>()?;
-
-    // Use lopdf's merge approach: renumber objects, collect pages
-    let mut max_id = 1;
-    let mut all_pages = Vec::new();
-    let mut all_objects = std::collections::BTreeMap::new();
-
-    for mut doc in documents {
-        doc.renumber_objects_with(max_id);
-        max_id = doc.max_id + 1;
-
-        // Collect page references in order
-        let pages = doc.get_pages();
-        let mut page_ids: Vec<_> = pages.into_iter().collect();
-        page_ids.sort_by_key(|(num, _)| *num);
-        for (_, page_id) in &page_ids {
-            all_pages.push(*page_id);
-        }
-
-        // Collect all objects except Catalog
-        for (id, object) in doc.objects {
-            if let Ok(dict) = object.as_dict()
-                && dict
-                    .get(b"Type")
-                    .ok()
-                    .and_then(|t| t.as_name().ok())
-                    .is_some_and(|name| name == b"Catalog")
-            {
-                continue;
-            }
-            all_objects.insert(id, object);
+        .map(|range| extract_pages(&doc, range))
+        .collect()
+}
+
+/// Rotate the pages of a PDF in `pages` clockwise by `degrees`, a multiple
+/// of 90 that may be negative, on top of any rotation they already have.
+pub fn rotate(input: &[u8], pages: &[PageRange], degrees: i32) -> Result<Vec<u8>, ConvertError> {
+    if pages.is_empty() {
+        return Err(ConvertError::Parse(
+            "no page ranges specified for rotate".to_string(),
+        ));
+    }
+    if degrees % 90 != 0 {
+        return Err(ConvertError::Parse(format!(
+            "pages can only be rotated by a multiple of 90 degrees, got {degrees}"
+        )));
+    }
+
+    let mut doc: Document = load_pdf_document(input, "")?;
+    let page_ids: BTreeMap<u32, lopdf::ObjectId> = doc.get_pages();
+    validate_page_ranges(pages, page_ids.len() as u32)?;
+
+    for (number, page_id) in page_ids {
+        if !pages
+            .iter()
+            .any(|range| (range.start..=range.end).contains(&number))
+        {
+            continue;
+        }
+        let current: i64 = inherited_attribute(&doc, page_id, b"Rotate")
+            .and_then(|rotation| rotation.as_i64().ok())
+            .unwrap_or(0);
+        let rotation: i64 = (current + i64::from(degrees)).rem_euclid(360);
+        doc.get_dictionary_mut(page_id)
+            .map_err(|_| ConvertError::Parse(format!("page {number} is not a dictionary")))?
+            .set("Rotate", rotation);
+    }
+
+    save_pdf_to_bytes(&mut doc, "rotated")
+}
+
+/// Put the pages of a PDF in a new order: `order` lists every page number
+/// (1-indexed) once, in the order the pages should appear.
+///
+/// The page tree is flattened on the way, so attributes pages inherited
+/// from it are copied into each page.
+pub fn reorder(input: &[u8], order: &[u32]) -> Result<Vec<u8>, ConvertError> {
+    let mut doc: Document = load_pdf_document(input, "")?;
+    let page_ids: BTreeMap<u32, lopdf::ObjectId> = doc.get_pages();
+    let total_pages: u32 = page_ids.len() as u32;
+    if order.len() != page_ids.len() {
+        return Err(ConvertError::Parse(format!(
+            "the page order must list all {total_pages} pages once, got {} pages",
+            order.len()
+        )));
+    }
+    let mut listed: BTreeSet<u32> = BTreeSet::new();
+    let mut kids: Vec<lopdf::ObjectId> = Vec::with_capacity(order.len());
+    for &number in order {
+        let page_id: lopdf::ObjectId = *page_ids.get(&number).ok_or_else(|| {
+            ConvertError::Parse(format!(
+                "page {number} exceeds document page count ({total_pages})"
+            ))
+        })?;
+        if !listed.insert(number) {
+            return Err(ConvertError::Parse(format!(
+                "page {number} is listed twice in the page order"
+            )));
         }
+        kids.push(page_id);
     }
 
-    // Build a new document with merged pages
-    let mut merged = Document::with_version("1.7");
+    let root_id: lopdf::ObjectId = doc
+        .catalog()
+        .and_then(|catalog| catalog.get(b"Pages"))
+        .and_then(lopdf::Object::as_reference)
+        .map_err(|_| ConvertError::Parse("the PDF has no page tree".to_string()))?;
+    push_inherited_attributes(&mut doc, &kids);
+    for &page_id in &kids {
+        if let Ok(page) = doc.get_dictionary_mut(page_id) {
+            page.set("Parent", root_id);
+        }
+    }
+    let root: &mut lopdf::Dictionary = doc
+        .get_dictionary_mut(root_id)
+        .map_err(|_| ConvertError::Parse("the page tree is not a dictionary".to_string()))?;
+    root.set(
+        "Kids",
+        kids.into_iter()
+            .map(lopdf::Object::Reference)
+            .collect::<Vec<lopdf::Object>>(),
+    );
+    root.set("Count", i64::from(total_pages));
+    // Drops the intermediate nodes of the old tree, which no longer hold
+    // any pages.
+    let referenced: BTreeSet<lopdf::ObjectId> = referenced_objects(&doc).into_iter().collect();
+    doc.objects.retain(|id, _| referenced.contains(id));
 
-    // Insert all collected objects
-    for (id, object) in &all_objects {
-        merged.objects.insert(*id, object.clone());
+    save_pdf_to_bytes(&mut doc, "reordered")
+}
+
+/// Delete the pages of a PDF in `pages`. At least one page must remain.
+pub fn delete(input: &[u8], pages: &[PageRange]) -> Result<Vec<u8>, ConvertError> {
+    if pages.is_empty() {
+        return Err(ConvertError::Parse(
+            "no page ranges specified for delete".to_string(),
+        ));
     }
-    merged.max_id = max_id;
 
-    // Create Pages dictionary
-    let pages_id = merged.new_object_id();
-    let page_refs: Vec<lopdf::Object> = all_pages
+    let mut doc: Document = load_pdf_document(input, "")?;
+    let total_pages: u32 = doc.get_pages().len() as u32;
+    validate_page_ranges(pages, total_pages)?;
+
+    let doomed: BTreeSet<u32> = pages
         .iter()
-        .map(|id| lopdf::Object::Reference(*id))
+        .flat_map(|range| range.start..=range.end)
         .collect();
+    if doomed.len() as u32 == total_pages {
+        return Err(ConvertError::Parse(
+            "cannot delete every page of the PDF".to_string(),
+        ));
+    }
+    doc.delete_pages(&doomed.into_iter().collect::<Vec<u32>>());
+
+    save_pdf_to_bytes(&mut doc, "trimmed")
+}
+
+/// Shrink a PDF: drop objects nothing refers to and the extra copies of
+/// fonts and images embedded more than once, compress streams stored
+/// uncompressed and pack the other objects into compressed object streams
+/// behind a cross-reference stream, which needs PDF 1.5.
+///
+/// The XMP metadata stream stays uncompressed, as PDF/A requires.
+pub fn compact(input: &[u8]) -> Result<Vec<u8>, ConvertError> {
+    let mut doc: Document = load_pdf_document(input, "")?;
+    // lopdf decrypts on load: writing the document back would silently
+    // drop the protection.
+    if doc.was_encrypted() {
+        return Err(ConvertError::InvalidOptions(
+            "cannot compact an encrypted PDF".to_string(),
+        ));
+    }
+    let referenced: BTreeSet<lopdf::ObjectId> = referenced_objects(&doc).into_iter().collect();
+    doc.objects.retain(|id, _| referenced.contains(id));
+    dedupe::deduplicate(&mut doc);
+    compress_streams(&mut doc);
+    let mut output: Vec<u8> = Vec::new();
+    doc.save_modern(&mut output)
+        .map_err(|e| ConvertError::Render(format!("failed to write compacted PDF: {e}")))?;
+    Ok(output)
+}
+
+/// Replace the outline (bookmarks) of a PDF with one top-level entry per
+/// `(title, page)` pair, `page` being 1-indexed, and have viewers open with
+/// the outline showing.
+pub(crate) fn set_outline(
+    input: &[u8],
+    entries: &[(String, u32)],
+) -> Result<Vec<u8>, ConvertError> {
+    let mut doc: Document = load_pdf_document(input, "")?;
+    let pages = doc.get_pages();
+
+    let outlines_id = doc.new_object_id();
+    let item_ids: Vec<lopdf::ObjectId> = entries.iter().map(|_| doc.new_object_id()).collect();
+    for (index, ((title, page), item_id)) in entries.iter().zip(&item_ids).enumerate() {
+        let page_id = *pages.get(page).ok_or_else(|| {
+            ConvertError::Parse(format!(
+                "bookmark page {page} exceeds document page count ({})",
+                pages.len()
+            ))
+        })?;
+        let mut item = dictionary! {
+            "Title" => pdf_text_string(title),
+            "Parent" => outlines_id,
+            "Dest" => vec![lopdf::Object::Reference(page_id), "Fit".into()],
+        };
+        if index > 0 {
+            item.set("Prev", item_ids[index - 1]);
+        }
+        if let Some(next_id) = item_ids.get(index + 1) {
+            item.set("Next", *next_id);
+        }
+        doc.objects
+            .insert(*item_id, lopdf::Object::Dictionary(item));
+    }
 
-    let pages_dict = dictionary! {
-        "Type" => "Pages",
-        "Count" => all_pages.len() as i64,
-        "Kids" => page_refs,
+    let mut outlines = dictionary! {
+        "Type" => "Outlines",
+        "Count" => item_ids.len() as i64,
     };
-    merged
-        .objects
-        .insert(pages_id, lopdf::Object::Dictionary(pages_dict));
-
-    // Update each page's Parent reference
-    for page_id in &all_pages {
-        if let Some(object) = merged.objects.get_mut(page_id)
-            && let Ok(page_dict) = object.as_dict_mut()
+    if let (Some(first), Some(last)) = (item_ids.first(), item_ids.last()) {
+        outlines.set("First", *first);
+        outlines.set("Last", *last);
+    }
+    doc.objects
+        .insert(outlines_id, lopdf::Object::Dictionary(outlines));
+    let catalog = doc
+        .catalog_mut()
+        .map_err(|e| ConvertError::Parse(format!("invalid PDF: {e}")))?;
+    catalog.set("Outlines", outlines_id);
+    catalog.set("PageMode", "UseOutlines");
+
+    save_pdf_to_bytes(&mut doc, "bookmarked")
+}
+
+/// `input` with the document information dictionary (title, author,
+/// dates) of `source`, which [`merge`] does not carry over. Left as is
+/// when `source` has none.
+pub(crate) fn copy_info(input: &[u8], source: &[u8]) -> Result<Vec<u8>, ConvertError> {
+    let source: Document = load_pdf_document(source, "")?;
+    let Some(info) = source
+        .trailer
+        .get(b"Info")
+        .and_then(|info| source.dereference(info))
+        .and_then(|(_, info)| info.as_dict())
+        .ok()
+        .cloned()
+    else {
+        return Ok(input.to_vec());
+    };
+    let mut doc: Document = load_pdf_document(input, "")?;
+    let info_id = doc.add_object(lopdf::Object::Dictionary(info));
+    doc.trailer.set("Info", info_id);
+    save_pdf_to_bytes(&mut doc, "merged")
+}
+
+/// Identify a PDF/A-2b file Typst wrote as PDF/X-4 as well: a `GTS_PDFX`
+/// output intent sharing the sRGB profile of the PDF/A one, the version
+/// and `Trapped` keys in the document information and XMP metadata, and a
+/// trim box on every page that has none, defaulting to the media box.
+pub(crate) fn add_pdf_x_4(input: &[u8]) -> Result<Vec<u8>, ConvertError> {
+    let mut doc: Document = load_pdf_document(input, "")?;
+    let invalid = |what: &str| ConvertError::Render(format!("cannot write PDF/X-4: {what}"));
+
+    let catalog = doc
+        .catalog()
+        .map_err(|e| ConvertError::Parse(format!("invalid PDF: {e}")))?;
+    let mut intents: Vec<lopdf::Object> = catalog
+        .get(b"OutputIntents")
+        .and_then(|intents| doc.dereference(intents))
+        .and_then(|(_, intents)| intents.as_array())
+        .cloned()
+        .map_err(|_| invalid("the PDF/A output intent is missing"))?;
+    let mut intent: lopdf::Dictionary = intents
+        .first()
+        .and_then(|intent| doc.dereference(intent).ok())
+        .and_then(|(_, intent)| intent.as_dict().ok())
+        .cloned()
+        .ok_or_else(|| invalid("the PDF/A output intent is missing"))?;
+    let metadata_id: lopdf::ObjectId = catalog
+        .get(b"Metadata")
+        .and_then(lopdf::Object::as_reference)
+        .map_err(|_| invalid("the XMP metadata is missing"))?;
+    intent.set("S", "GTS_PDFX");
+    intents.push(lopdf::Object::Dictionary(intent));
+    doc.catalog_mut()
+        .map_err(|e| ConvertError::Parse(format!("invalid PDF: {e}")))?
+        .set("OutputIntents", intents);
+
+    let info_id: lopdf::ObjectId = match doc
+        .trailer
+        .get(b"Info")
+        .and_then(lopdf::Object::as_reference)
+    {
+        Ok(id) => id,
+        Err(_) => {
+            let id = doc.add_object(lopdf::Object::Dictionary(lopdf::Dictionary::new()));
+            doc.trailer.set("Info", id);
+            id
+        }
+    };
+    let info = doc
+        .get_dictionary_mut(info_id)
+        .map_err(|_| invalid("the document information is not a dictionary"))?;
+    info.set("GTS_PDFXVersion", lopdf::Object::string_literal("PDF/X-4"));
+    info.set("Trapped", "False");
+
+    let metadata = doc
+        .get_object_mut(metadata_id)
+        .and_then(lopdf::Object::as_stream_mut)
+        .map_err(|_| invalid("the XMP metadata is not a stream"))?;
+    let xmp: Vec<u8> = metadata
+        .decompressed_content()
+        .unwrap_or_else(|_| metadata.content.clone());
+    let xmp: String =
+        String::from_utf8(xmp).map_err(|_| invalid("the XMP metadata is not UTF-8"))?;
+    let end: usize = xmp
+        .rfind("</rdf:RDF>")
+        .ok_or_else(|| invalid("the XMP metadata has no RDF"))?;
+    let xmp: String = format!(
+        "{}<rdf:Description rdf:about=\"\" xmlns:pdfxid=\"http://www.npes.org/pdfx/ns/id/\" \
+         xmlns:pdf=\"http://ns.adobe.com/pdf/1.3/\">\
+         <pdfxid:GTS_PDFXVersion>PDF/X-4</pdfxid:GTS_PDFXVersion>\
+         <pdf:Trapped>False</pdf:Trapped></rdf:Description>{}",
+        &xmp[..end],
+        &xmp[end..],
+    );
+    // PDF/A forbids filters on the metadata stream.
+    metadata.set_plain_content(xmp.into_bytes());
+
+    let page_ids: Vec<lopdf::ObjectId> = doc.get_pages().into_values().collect();
+    for page_id in page_ids {
+        let media_box: lopdf::Object =
+            inherited_media_box(&doc, page_id).ok_or_else(|| invalid("a page has no media box"))?;
+        let page = doc
+            .get_dictionary_mut(page_id)
+            .map_err(|_| invalid("a page is not a dictionary"))?;
+        if !page.has(b"TrimBox") && !page.has(b"ArtBox") {
+            page.set("TrimBox", media_box);
+        }
+    }
+
+    // Saved without `save_pdf_to_bytes`'s recompression, which would put a
+    // filter back on the metadata stream.
+    let mut output: Vec<u8> = Vec::new();
+    doc.save_to(&mut output)
+        .map_err(|e| ConvertError::Render(format!("failed to write PDF/X-4 PDF: {e}")))?;
+    Ok(output)
+}
+
+/// Document information keys that PDF defines; custom properties with these
+/// names are left out rather than overwrite them.
+const STANDARD_INFO_KEYS: [&str; 9] = [
+    "Title",
+    "Author",
+    "Subject",
+    "Keywords",
+    "Creator",
+    "Producer",
+    "CreationDate",
+    "ModDate",
+    "Trapped",
+];
+
+/// Write the metadata Typst has no field for, the company and custom
+/// properties of `metadata`, as document information entries and, unless
+/// the file claims PDF/A, in the `pdfx` namespace of its XMP metadata the
+/// way Acrobat mirrors custom entries. PDF/A would need an XMP extension
+/// schema for that namespace.
+pub(crate) fn set_document_properties(
+    input: &[u8],
+    metadata: &Metadata,
+) -> Result<Vec<u8>, ConvertError> {
+    let mut properties: Vec<(&str, &str)> = Vec::new();
+    if let Some(company) = &metadata.company {
+        properties.push(("Company", company));
+    }
+    for (name, value) in &metadata.custom_properties {
+        if !name.is_empty()
+            && !STANDARD_INFO_KEYS.contains(&name.as_str())
+            && !properties.iter().any(|(existing, _)| existing == name)
         {
-            page_dict.set("Parent", lopdf::Object::Reference(pages_id));
+            properties.push((name, value));
         }
     }
+    if properties.is_empty() {
+        return Ok(input.to_vec());
+    }
 
-    // Create Catalog
-    let catalog_id = merged.new_object_id();
-    let catalog_dict = dictionary! {
-        "Type" => "Catalog",
-        "Pages" => pages_id,
-    };
-    merged
-        .objects
-        .insert(catalog_id, lopdf::Object::Dictionary(catalog_dict));
-    merged
+    let mut doc: Document = load_pdf_document(input, "")?;
+    let info_id: lopdf::ObjectId = match doc
         .trailer
-        .set("Root", lopdf::Object::Reference(catalog_id));
-
-    // Remove orphaned intermediate Pages nodes from source documents
-    // (they are no longer needed since we have a new top-level Pages node)
-    let page_set: std::collections::HashSet<_> = all_pages.iter().collect();
-    let mut to_remove = Vec::new();
-    for (id, object) in &merged.objects {
-        if page_set.contains(id) || *id == pages_id || *id == catalog_id {
-            continue;
+        .get(b"Info")
+        .and_then(lopdf::Object::as_reference)
+    {
+        Ok(id) => id,
+        Err(_) => {
+            let id = doc.add_object(lopdf::Object::Dictionary(lopdf::Dictionary::new()));
+            doc.trailer.set("Info", id);
+            id
         }
-        if let Ok(dict) = object.as_dict()
-            && dict
-                .get(b"Type")
-                .ok()
-                .and_then(|t| t.as_name().ok())
-                .is_some_and(|name| name == b"Pages")
+    };
+    let info = doc
+        .get_dictionary_mut(info_id)
+        .map_err(|_| ConvertError::Render("the document information is not a dictionary".into()))?;
+    for (name, value) in &properties {
+        info.set(name.as_bytes().to_vec(), pdf_text_string(value));
+    }
+
+    let metadata_id: Option<lopdf::ObjectId> = doc
+        .catalog()
+        .and_then(|catalog| catalog.get(b"Metadata"))
+        .and_then(lopdf::Object::as_reference)
+        .ok();
+    if let Some(stream) = metadata_id
+        .and_then(|id| doc.get_object_mut(id).ok())
+        .and_then(|object| object.as_stream_mut().ok())
+    {
+        let xmp: Vec<u8> = stream
+            .decompressed_content()
+            .unwrap_or_else(|_| stream.content.clone());
+        if let Ok(xmp) = String::from_utf8(xmp)
+            && !xmp.contains("pdfaid:")
+            && let Some(end) = xmp.rfind("</rdf:RDF>")
         {
-            to_remove.push(*id);
+            let mut description: String = String::from(
+                "<rdf:Description rdf:about=\"\" xmlns:pdfx=\"http://ns.adobe.com/pdfx/1.3/\">",
+            );
+            // XMP property names are XML names; others stay in the
+            // document information only.
+            for (name, value) in properties.iter().filter(|(name, _)| is_xml_name(name)) {
+                description.push_str(&format!("<pdfx:{name}>{}</pdfx:{name}>", escape_xml(value)));
+            }
+            description.push_str("</rdf:Description>");
+            let xmp: String = format!("{}{description}{}", &xmp[..end], &xmp[end..]);
+            stream.set_plain_content(xmp.into_bytes());
         }
     }
-    for id in to_remove {
-        merged.objects.remove(&id);
+
+    // Saved without `save_pdf_to_bytes`'s recompression, so the metadata
+    // stream stays readable by tools that scan for XMP packets.
+    let mut output: Vec<u8> = Vec::new();
+    doc.save_to(&mut output)
+        .map_err(|e| ConvertError::Render(format!("failed to write PDF: {e}")))?;
+    Ok(output)
+}
+
+/// Whether `name` can be used as an XML element name without a prefix.
+fn is_xml_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Attach `sources` to `input` as embedded files with the `Source`
+/// relationship, listed both in the embedded files name tree, where viewers
+/// show attachments, and in the catalog's associated files, as PDF/A-3
+/// requires. One source is named `source.<ext>`, several
+/// `source-<n>.<ext>`.
+pub(crate) fn attach_sources(
+    input: &[u8],
+    sources: &[(&[u8], Format)],
+) -> Result<Vec<u8>, ConvertError> {
+    let mut doc: Document = load_pdf_document(input, "")?;
+    let invalid = |what: &str| ConvertError::Render(format!("cannot attach source: {what}"));
+
+    let mut file_specs: Vec<(String, lopdf::ObjectId)> = Vec::with_capacity(sources.len());
+    for (index, (data, format)) in sources.iter().enumerate() {
+        let name: String = if sources.len() == 1 {
+            format!("source.{}", format.extension())
+        } else {
+            format!("source-{}.{}", index + 1, format.extension())
+        };
+        let file_id = doc.add_object(lopdf::Stream::new(
+            dictionary! {
+                "Type" => "EmbeddedFile",
+                "Subtype" => lopdf::Object::Name(format.mime_type().as_bytes().to_vec()),
+                "Params" => dictionary! { "Size" => data.len() as i64 },
+            },
+            data.to_vec(),
+        ));
+        let spec_id = doc.add_object(dictionary! {
+            "Type" => "Filespec",
+            "F" => lopdf::Object::string_literal(name.as_str()),
+            "UF" => pdf_text_string(&name),
+            "Desc" => lopdf::Object::string_literal("Original document"),
+            "AFRelationship" => "Source",
+            "EF" => dictionary! { "F" => file_id, "UF" => file_id },
+        });
+        file_specs.push((name, spec_id));
     }
 
-    save_pdf_to_bytes(&mut merged, "merged")
+    let catalog = doc
+        .catalog()
+        .map_err(|e| ConvertError::Parse(format!("invalid PDF: {e}")))?;
+    let mut names: lopdf::Dictionary = match catalog.get(b"Names") {
+        Ok(names) => doc
+            .dereference(names)
+            .and_then(|(_, names)| names.as_dict())
+            .cloned()
+            .map_err(|_| invalid("the name dictionary is not a dictionary"))?,
+        Err(_) => lopdf::Dictionary::new(),
+    };
+    let mut associated: Vec<lopdf::Object> = match catalog.get(b"AF") {
+        Ok(files) => doc
+            .dereference(files)
+            .and_then(|(_, files)| files.as_array())
+            .cloned()
+            .map_err(|_| invalid("the associated files are not an array"))?,
+        Err(_) => Vec::new(),
+    };
+    // A name tree's leaf keeps its keys sorted; Typst writes no embedded
+    // files, so this one is built from scratch.
+    if names.has(b"EmbeddedFiles") {
+        return Err(invalid("the PDF already has embedded files"));
+    }
+    file_specs.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut entries: Vec<lopdf::Object> = Vec::with_capacity(file_specs.len() * 2);
+    for (name, spec_id) in &file_specs {
+        entries.push(lopdf::Object::string_literal(name.as_str()));
+        entries.push(lopdf::Object::Reference(*spec_id));
+        associated.push(lopdf::Object::Reference(*spec_id));
+    }
+    names.set("EmbeddedFiles", dictionary! { "Names" => entries });
+
+    let catalog = doc
+        .catalog_mut()
+        .map_err(|e| ConvertError::Parse(format!("invalid PDF: {e}")))?;
+    catalog.set("Names", names);
+    catalog.set("AF", associated);
+
+    // Saved without `save_pdf_to_bytes`'s recompression: PDF/A forbids
+    // filters on the XMP metadata stream.
+    let mut output: Vec<u8> = Vec::new();
+    doc.save_to(&mut output)
+        .map_err(|e| ConvertError::Render(format!("failed to write PDF: {e}")))?;
+    Ok(output)
 }
 
-/// Split a PDF into multiple PDFs based on page ranges.
+/// `input` encrypted with AES-256 (PDF 2.0 security handler revision 6)
+/// behind the passwords of `security`, allowing what its permissions grant.
 ///
-/// Each `PageRange` specifies a 1-indexed inclusive range of pages to extract.
-/// Returns a vector of PDF byte arrays, one per range.
-pub fn split(input: &[u8], ranges: &[PageRange]) -> Result<Vec<Vec<u8>>, ConvertError> {
-    if ranges.is_empty() {
-        return Err(ConvertError::Parse(
-            "no page ranges specified for split".to_string(),
+/// # Errors
+///
+/// Returns [`ConvertError::InvalidOptions`] for an empty owner password
+/// or a PDF that is already encrypted; [`decrypt`] it first.
+pub fn encrypt(input: &[u8], security: &PdfSecurity) -> Result<Vec<u8>, ConvertError> {
+    if security.owner_password.is_empty() {
+        return Err(ConvertError::InvalidOptions(
+            "encryption needs an owner password, or anyone could lift its restrictions".to_string(),
+        ));
+    }
+    let mut doc: Document = load_pdf_document(input, "")?;
+    if doc.was_encrypted() || doc.is_encrypted() {
+        return Err(ConvertError::InvalidOptions(
+            "the PDF is already encrypted; decrypt it first".to_string(),
         ));
     }
+    let mut file_key: [u8; 32] = [0; 32];
+    getrandom::fill(&mut file_key)
+        .map_err(|e| ConvertError::Render(format!("cannot encrypt PDF: no random source: {e}")))?;
+    let crypt_filters: BTreeMap<Vec<u8>, Arc<dyn CryptFilter>> = BTreeMap::from([(
+        b"StdCF".to_vec(),
+        Arc::new(Aes256CryptFilter) as Arc<dyn CryptFilter>,
+    )]);
+    let state = EncryptionState::try_from(EncryptionVersion::V5 {
+        encrypt_metadata: true,
+        crypt_filters,
+        file_encryption_key: &file_key,
+        stream_filter: b"StdCF".to_vec(),
+        string_filter: b"StdCF".to_vec(),
+        owner_password: &security.owner_password,
+        user_password: &security.user_password,
+        permissions: permissions(security),
+    })
+    .map_err(|e| ConvertError::Render(format!("cannot encrypt PDF: {e}")))?;
+    doc.encrypt(&state)
+        .map_err(|e| ConvertError::Render(format!("cannot encrypt PDF: {e}")))?;
 
-    let doc: Document = load_pdf_document(input, "")?;
+    // Saved without `save_pdf_to_bytes`'s compression: a filter added on
+    // top of encrypted data would be undone after decryption, not before.
+    let mut output: Vec<u8> = Vec::new();
+    doc.save_to(&mut output)
+        .map_err(|e| ConvertError::Render(format!("failed to write encrypted PDF: {e}")))?;
+    Ok(output)
+}
 
-    let total_pages: u32 = doc.get_pages().len() as u32;
-    validate_page_ranges(ranges, total_pages)?;
+/// `input` with its encryption removed, opened with `password`: the user
+/// password, or the owner password. A PDF that only restricts permissions
+/// opens with an empty password.
+///
+/// # Errors
+///
+/// Returns [`ConvertError::IncorrectPassword`] if `password` opens neither
+/// way, and [`ConvertError::InvalidOptions`] if the PDF is not encrypted.
+pub fn decrypt(input: &[u8], password: &str) -> Result<Vec<u8>, ConvertError> {
+    // lopdf decrypts on load and drops the security handler from the
+    // trailer, so saving writes the content in the clear.
+    let mut doc: Document =
+        Document::load_mem_with_password(input, password).map_err(|e| match e {
+            lopdf::Error::InvalidPassword => ConvertError::IncorrectPassword,
+            e => ConvertError::Parse(format!("invalid PDF: {e}")),
+        })?;
+    if !doc.was_encrypted() {
+        return Err(ConvertError::InvalidOptions(
+            "the PDF is not encrypted".to_string(),
+        ));
+    }
+    save_pdf_to_bytes(&mut doc, "decrypted")
+}
+
+/// The permission flags `security` grants.
+fn permissions(security: &PdfSecurity) -> Permissions {
+    // Screen readers need the text whatever else is restricted.
+    let mut permissions: Permissions = Permissions::COPYABLE_FOR_ACCESSIBILITY;
+    if security.allow_print {
+        permissions |= Permissions::PRINTABLE | Permissions::PRINTABLE_IN_HIGH_QUALITY;
+    }
+    if security.allow_copy {
+        permissions |= Permissions::COPYABLE;
+    }
+    if security.allow_modify {
+        permissions |= Permissions::MODIFIABLE | Permissions::ASSEMBLABLE;
+    }
+    if security.allow_annotate {
+        permissions |= Permissions::ANNOTABLE | Permissions::FILLABLE;
+    }
+    permissions
+}
 
-    let mut results = Vec::with_capacity(ranges.len());
+/// The objects reachable from the trailer, in the order they are first
+/// met going depth-first.
+fn referenced_objects(doc: &Document) -> Vec<lopdf::ObjectId> {
+    let mut seen: BTreeSet<lopdf::ObjectId> = BTreeSet::new();
+    let mut order: Vec<lopdf::ObjectId> = Vec::new();
+    let mut pending: Vec<&lopdf::Object> = doc.trailer.iter().rev().map(|(_, v)| v).collect();
+    while let Some(object) = pending.pop() {
+        match object {
+            lopdf::Object::Reference(id) => {
+                if seen.insert(*id)
+                    && let Ok(target) = doc.get_object(*id)
+                {
+                    order.push(*id);
+                    pending.push(target);
+                }
+            }
+            lopdf::Object::Array(items) => pending.extend(items.iter().rev()),
+            lopdf::Object::Dictionary(dict) => pending.extend(dict.iter().rev().map(|(_, v)| v)),
+            lopdf::Object::Stream(stream) => {
+                pending.extend(stream.dict.iter().rev().map(|(_, v)| v));
+            }
+            _ => {}
+        }
+    }
+    order
+}
 
-    for range in ranges {
-        let mut split_doc = doc.clone();
+/// Compress the streams stored uncompressed, but for XMP metadata, which
+/// PDF/A requires unfiltered and tools scan for in the clear.
+fn compress_streams(doc: &mut Document) {
+    for object in doc.objects.values_mut() {
+        if let lopdf::Object::Stream(stream) = object
+            && stream.allows_compression
+            && stream
+                .dict
+                .get(b"Type")
+                .and_then(lopdf::Object::as_name)
+                .ok()
+                != Some(b"Metadata".as_slice())
+        {
+            // Left as it is when compression fails or does not pay off.
+            let _ = stream.compress();
+        }
+    }
+}
 
-        // Determine which pages to delete (all pages NOT in range)
-        let pages_to_delete: Vec<u32> = (1..=total_pages)
-            .filter(|p| *p < range.start || *p > range.end)
-            .collect();
+/// Copy the attributes pages inherit into each page, and take them off
+/// the page tree.
+fn push_inherited_attributes(doc: &mut Document, page_ids: &[lopdf::ObjectId]) {
+    let mut tree_nodes: BTreeSet<lopdf::ObjectId> = BTreeSet::new();
+    for &page_id in page_ids {
+        let mut inherited: Vec<(&[u8], lopdf::Object)> = Vec::new();
+        let mut ancestors: BTreeSet<lopdf::ObjectId> = BTreeSet::new();
+        let mut parent: Option<lopdf::ObjectId> = parent_of(doc, page_id);
+        // A malformed tree may loop back on itself.
+        while let Some(node_id) = parent
+            && ancestors.insert(node_id)
+            && let Ok(node) = doc.get_dictionary(node_id)
+        {
+            for key in INHERITED_KEYS {
+                if let Ok(value) = node.get(key)
+                    && !inherited.iter().any(|(found, _)| *found == key)
+                {
+                    inherited.push((key, value.clone()));
+                }
+            }
+            parent = parent_of(doc, node_id);
+        }
+        tree_nodes.extend(ancestors);
+        if let Ok(page) = doc.get_dictionary_mut(page_id) {
+            for (key, value) in inherited {
+                if !page.has(key) {
+                    page.set(key, value);
+                }
+            }
+        }
+    }
+    for node_id in tree_nodes {
+        if let Ok(node) = doc.get_dictionary_mut(node_id) {
+            for key in INHERITED_KEYS {
+                node.remove(key);
+            }
+        }
+    }
+}
 
-        if !pages_to_delete.is_empty() {
-            split_doc.delete_pages(&pages_to_delete);
+fn parent_of(doc: &Document, id: lopdf::ObjectId) -> Option<lopdf::ObjectId> {
+    doc.get_dictionary(id)
+        .and_then(|dict| dict.get(b"Parent"))
+        .and_then(lopdf::Object::as_reference)
+        .ok()
+}
+
+/// The media box of a page, which it may inherit from the page tree.
+fn inherited_media_box(doc: &Document, page_id: lopdf::ObjectId) -> Option<lopdf::Object> {
+    inherited_attribute(doc, page_id, b"MediaBox")
+}
+
+/// The page's value for `key`, one of [`INHERITED_KEYS`], its own or the
+/// one it inherits.
+fn inherited_attribute(
+    doc: &Document,
+    page_id: lopdf::ObjectId,
+    key: &[u8],
+) -> Option<lopdf::Object> {
+    let mut node: &lopdf::Dictionary = doc.get_dictionary(page_id).ok()?;
+    loop {
+        if let Ok(value) = node.get(key) {
+            return doc.dereference(value).ok().map(|(_, value)| value.clone());
+        }
+        let parent: lopdf::ObjectId = node
+            .get(b"Parent")
+            .and_then(lopdf::Object::as_reference)
+            .ok()?;
+        node = doc.get_dictionary(parent).ok()?;
+    }
+}
+
+/// A rectangle such as a page's media box as `[left, bottom, right, top]`,
+/// whichever corners it was written with.
+fn rectangle(object: &lopdf::Object) -> Option<[f64; 4]> {
+    let numbers: Vec<f64> = object
+        .as_array()
+        .ok()?
+        .iter()
+        .map(|number| number.as_float().ok().map(f64::from))
+        .collect::<Option<_>>()?;
+    let [x0, y0, x1, y1] = numbers[..] else {
+        return None;
+    };
+    Some([x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1)])
+}
+
+/// A copy of the page's resources, its own or those it inherits.
+fn inherited_resources(doc: &Document, page_id: lopdf::ObjectId) -> lopdf::Dictionary {
+    let mut node: Option<&lopdf::Dictionary> = doc.get_dictionary(page_id).ok();
+    while let Some(dict) = node {
+        if let Ok(resources) = dict.get(b"Resources") {
+            return doc
+                .dereference(resources)
+                .ok()
+                .and_then(|(_, resources)| resources.as_dict().ok())
+                .cloned()
+                .unwrap_or_default();
         }
+        node = dict
+            .get(b"Parent")
+            .and_then(lopdf::Object::as_reference)
+            .and_then(|parent| doc.get_dictionary(parent))
+            .ok();
+    }
+    lopdf::Dictionary::new()
+}
 
-        results.push(save_pdf_to_bytes(&mut split_doc, "split")?);
+/// The page's content streams, as references.
+fn page_contents(doc: &Document, page_id: lopdf::ObjectId) -> Vec<lopdf::Object> {
+    let Some(contents) = doc
+        .get_dictionary(page_id)
+        .ok()
+        .and_then(|page| page.get(b"Contents").ok())
+    else {
+        return Vec::new();
+    };
+    match contents {
+        lopdf::Object::Reference(id) => match doc.get_object(*id) {
+            // A referenced array lists the streams itself.
+            Ok(lopdf::Object::Array(streams)) => streams.clone(),
+            _ => vec![contents.clone()],
+        },
+        lopdf::Object::Array(streams) => streams.clone(),
+        _ => Vec::new(),
     }
+}
+
+/// A PDF text string: PDFDocEncoding agrees with ASCII, anything else is
+/// written as UTF-16BE behind a byte order mark.
+fn pdf_text_string(text: &str) -> lopdf::Object {
+    if text.is_ascii() {
+        return lopdf::Object::string_literal(text);
+    }
+    let mut bytes: Vec<u8> = vec![0xFE, 0xFF];
+    bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+    lopdf::Object::String(bytes, lopdf::StringFormat::Hexadecimal)
+}
+
+/// `text` as the body of a literal string in WinAnsiEncoding, which agrees
+/// with Latin-1 outside 0x80–0x9F. Other characters become `?`.
+fn win_ansi_literal(text: &str) -> Vec<u8> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(text.len());
+    for ch in text.chars() {
+        let byte: u8 = match u32::from(ch) {
+            code @ (0x20..=0x7E | 0xA0..=0xFF) => code as u8,
+            _ => b'?',
+        };
+        if matches!(byte, b'(' | b')' | b'\\') {
+            bytes.push(b'\\');
+        }
+        bytes.push(byte);
+    }
+    bytes
+}
+
+/// Advance widths of Helvetica for the printable ASCII characters, ' '
+/// to '~', in thousandths of the font size, from the Adobe font metrics.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Width assumed for characters past ASCII: that of most Helvetica digits
+/// and lowercase letters.
+const DEFAULT_WIDTH: u16 = 556;
 
-    Ok(results)
+/// Width of `text` in Helvetica at a font size of 1.
+fn text_width(text: &str) -> f64 {
+    text.chars()
+        .map(|ch| {
+            let width: u16 = match u32::from(ch) {
+                code @ 0x20..=0x7E => HELVETICA_WIDTHS[(code - 0x20) as usize],
+                _ => DEFAULT_WIDTH,
+            };
+            f64::from(width) / 1000.0
+        })
+        .sum()
 }
 
 #[cfg(test)]
@@ -0,0 +1,158 @@
+//! Bleed and crop marks for commercial printing: pages grow past the edge
+//! the printer cuts to, and the page boxes tell prepress tools where that
+//! edge and the bleed are.
+
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream, dictionary};
+
+use super::{
+    inherited_media_box, inherited_resources, load_pdf_document, page_contents, rectangle,
+};
+use crate::config::Bleed;
+use crate::error::ConvertError;
+
+/// Resource name for the registration color, unlikely to clash with a
+/// page's own.
+const REGISTRATION: &str = "O2PRegistration";
+
+/// Gap between the trim box and a crop mark when the bleed is narrower,
+/// in points.
+const MARK_OFFSET: f64 = 6.0;
+
+/// Length of a crop mark, in points.
+const MARK_LENGTH: f64 = 18.0;
+
+/// Extend every page of `input` by the bleed margin: the page as it was
+/// becomes the trim box, the bleed box lies `margin` points outside it and
+/// the media box holds the bleed and, with crop marks, the marks too.
+/// Nothing moves on the page.
+///
+/// Pages that already have a trim box, such as those of PDF/X output, keep
+/// it. Crop marks are drawn in registration color, which prints on every
+/// separation, and marked as artifacts so screen readers skip them.
+pub fn bleed(input: &[u8], bleed: &Bleed) -> Result<Vec<u8>, ConvertError> {
+    bleed.check()?;
+    let mut doc: Document = load_pdf_document(input, "")?;
+    let page_ids: Vec<ObjectId> = doc.get_pages().into_values().collect();
+
+    // Registration color: the `All` separation, shown as black.
+    let registration: Object = Object::Array(vec![
+        "Separation".into(),
+        "All".into(),
+        "DeviceGray".into(),
+        Object::Dictionary(dictionary! {
+            "FunctionType" => 2,
+            "Domain" => vec![0.into(), 1.into()],
+            "C0" => vec![1.into()],
+            "C1" => vec![0.into()],
+            "N" => 1,
+        }),
+    ]);
+    let mark_offset: f64 = bleed.margin.max(MARK_OFFSET);
+    let slug: f64 = if bleed.crop_marks {
+        mark_offset + MARK_LENGTH
+    } else {
+        bleed.margin
+    };
+    // Whatever graphics state the page content leaves behind is restored
+    // before the marks: its content is wrapped in `q` and `Q`.
+    let save_id: Option<ObjectId> = bleed
+        .crop_marks
+        .then(|| doc.add_object(Stream::new(Dictionary::new(), b"q".to_vec())));
+
+    for page_id in page_ids {
+        let trim: [f64; 4] = trim_box(&doc, page_id)?;
+        let grown = |by: f64| -> Vec<Object> {
+            vec![
+                (trim[0] - by).into(),
+                (trim[1] - by).into(),
+                (trim[2] + by).into(),
+                (trim[3] + by).into(),
+            ]
+        };
+        let marks_id: Option<ObjectId> = bleed.crop_marks.then(|| {
+            doc.add_object(Stream::new(
+                Dictionary::new(),
+                crop_marks(trim, mark_offset),
+            ))
+        });
+        let mut resources: Dictionary = inherited_resources(&doc, page_id);
+        if bleed.crop_marks {
+            let mut color_spaces: Dictionary = resources
+                .get(b"ColorSpace")
+                .ok()
+                .and_then(|entries| doc.dereference(entries).ok())
+                .and_then(|(_, entries)| entries.as_dict().ok())
+                .cloned()
+                .unwrap_or_default();
+            color_spaces.set(REGISTRATION, registration.clone());
+            resources.set("ColorSpace", color_spaces);
+        }
+        let contents: Vec<Object> = page_contents(&doc, page_id);
+
+        let page: &mut Dictionary = doc
+            .get_dictionary_mut(page_id)
+            .map_err(|_| failed("a page is not a dictionary"))?;
+        page.set("MediaBox", grown(slug));
+        page.set("BleedBox", grown(bleed.margin));
+        page.set("TrimBox", grown(0.0));
+        // The crop box would otherwise hide the bleed.
+        page.remove(b"CropBox");
+        if let (Some(save_id), Some(marks_id)) = (save_id, marks_id) {
+            let mut marked: Vec<Object> = Vec::with_capacity(contents.len() + 2);
+            marked.push(save_id.into());
+            marked.extend(contents);
+            marked.push(marks_id.into());
+            page.set("Contents", marked);
+            page.set("Resources", resources);
+        }
+    }
+
+    let mut output: Vec<u8> = Vec::new();
+    doc.save_to(&mut output)
+        .map_err(|e| failed(&format!("cannot write PDF: {e}")))?;
+    Ok(output)
+}
+
+/// The page's own trim box, or else its media box, as `[left, bottom,
+/// right, top]`.
+fn trim_box(doc: &Document, page_id: ObjectId) -> Result<[f64; 4], ConvertError> {
+    let trim_box: Option<Object> = doc
+        .get_dictionary(page_id)
+        .ok()
+        .and_then(|page| page.get(b"TrimBox").ok())
+        .and_then(|trim_box| doc.dereference(trim_box).ok())
+        .map(|(_, trim_box)| trim_box.clone());
+    let page_box: Object = trim_box
+        .or_else(|| inherited_media_box(doc, page_id))
+        .ok_or_else(|| failed("a page has no media box"))?;
+    rectangle(&page_box).ok_or_else(|| failed("a page's box is not four numbers"))
+}
+
+/// Two hairlines at each corner of `trim`, extending its edges outward
+/// from `offset` points past it.
+fn crop_marks(trim: [f64; 4], offset: f64) -> Vec<u8> {
+    let [left, bottom, right, top] = trim;
+    let (near, far): (f64, f64) = (offset, offset + MARK_LENGTH);
+    let mut content: String = format!("Q\n/Artifact BMC q 0.25 w /{REGISTRATION} CS 1 SCN\n");
+    for (x, outward) in [(left, -1.0), (right, 1.0)] {
+        for (y, upward) in [(bottom, -1.0), (top, 1.0)] {
+            content.push_str(&format!(
+                "{:.3} {y:.3} m {:.3} {y:.3} l S {x:.3} {:.3} m {x:.3} {:.3} l S\n",
+                x + outward * near,
+                x + outward * far,
+                y + upward * near,
+                y + upward * far,
+            ));
+        }
+    }
+    content.push_str("Q EMC");
+    content.into_bytes()
+}
+
+fn failed(what: &str) -> ConvertError {
+    ConvertError::Render(format!("cannot add bleed to PDF: {what}"))
+}
+
+#[cfg(test)]
+#[path = "pdf_ops_bleed_tests.rs"]
+mod tests;
@@ -0,0 +1,129 @@
+use super::*;
+use crate::pdf_ops::test_support::{TestPdf, labelled_page, page_content};
+
+/// A PDF of `num_pages` A4 pages inheriting their media box from the page
+/// tree, each with one content stream.
+fn make_test_pdf(num_pages: u32) -> Vec<u8> {
+    let mut pdf = TestPdf::new();
+    for number in 1..=num_pages {
+        pdf.add_page(
+            &labelled_page("Page", number),
+            dictionary! { "CropBox" => vec![10.into(), 10.into(), 585.into(), 832.into()] },
+        );
+    }
+    pdf.save()
+}
+
+fn page_box(doc: &Document, number: u32, key: &[u8]) -> [f64; 4] {
+    let page: &Dictionary = doc.get_dictionary(doc.get_pages()[&number]).unwrap();
+    rectangle(page.get(key).unwrap()).unwrap()
+}
+
+#[test]
+fn test_bleed_grows_media_box_around_trim_box() {
+    let bleed_only = Bleed {
+        margin: 9.0,
+        crop_marks: false,
+    };
+    let output: Vec<u8> = bleed(&make_test_pdf(2), &bleed_only).unwrap();
+    let doc: Document = Document::load_mem(&output).unwrap();
+    for number in 1..=2 {
+        assert_eq!(page_box(&doc, number, b"TrimBox"), [0.0, 0.0, 595.0, 842.0]);
+        assert_eq!(
+            page_box(&doc, number, b"BleedBox"),
+            [-9.0, -9.0, 604.0, 851.0]
+        );
+        assert_eq!(
+            page_box(&doc, number, b"MediaBox"),
+            [-9.0, -9.0, 604.0, 851.0]
+        );
+        let page: &Dictionary = doc.get_dictionary(doc.get_pages()[&number]).unwrap();
+        assert!(!page.has(b"CropBox"), "the crop box would hide the bleed");
+    }
+    assert!(!page_content(&doc, 1).contains(REGISTRATION));
+}
+
+#[test]
+fn test_bleed_crop_marks_sit_outside_the_bleed() {
+    let marked = Bleed {
+        margin: 9.0,
+        crop_marks: true,
+    };
+    let output: Vec<u8> = bleed(&make_test_pdf(1), &marked).unwrap();
+    let doc: Document = Document::load_mem(&output).unwrap();
+    // Marks start 9 pt out, past the bleed, and are 18 pt long.
+    assert_eq!(page_box(&doc, 1, b"MediaBox"), [-27.0, -27.0, 622.0, 869.0]);
+    assert_eq!(page_box(&doc, 1, b"BleedBox"), [-9.0, -9.0, 604.0, 851.0]);
+
+    let content: String = page_content(&doc, 1);
+    let original: usize = content.find("(Page 1) Tj").unwrap();
+    let marks: usize = content.find("/O2PRegistration CS").unwrap();
+    assert!(original < marks);
+    assert!(content.starts_with('q'), "page content must be isolated");
+    assert!(content.contains("/Artifact BMC"));
+    assert!(content.contains("-9.000 0.000 m -27.000 0.000 l S"));
+    assert!(content.contains("595.000 851.000 m 595.000 869.000 l S"));
+
+    let page: &Dictionary = doc.get_dictionary(doc.get_pages()[&1]).unwrap();
+    let color_space: &Object = page
+        .get(b"Resources")
+        .and_then(Object::as_dict)
+        .and_then(|resources| resources.get(b"ColorSpace"))
+        .and_then(Object::as_dict)
+        .and_then(|spaces| spaces.get(REGISTRATION.as_bytes()))
+        .unwrap();
+    let color_space: &[Object] = color_space.as_array().unwrap();
+    assert_eq!(color_space[1].as_name().unwrap(), b"All");
+}
+
+#[test]
+fn test_bleed_crop_marks_without_bleed_keep_their_offset() {
+    let marks_only = Bleed {
+        margin: 0.0,
+        crop_marks: true,
+    };
+    let output: Vec<u8> = bleed(&make_test_pdf(1), &marks_only).unwrap();
+    let doc: Document = Document::load_mem(&output).unwrap();
+    assert_eq!(page_box(&doc, 1, b"MediaBox"), [-24.0, -24.0, 619.0, 866.0]);
+    assert_eq!(page_box(&doc, 1, b"BleedBox"), [0.0, 0.0, 595.0, 842.0]);
+}
+
+#[test]
+fn test_bleed_keeps_existing_trim_box() {
+    let mut doc: Document = Document::load_mem(&make_test_pdf(1)).unwrap();
+    let page_id: ObjectId = doc.get_pages()[&1];
+    doc.get_dictionary_mut(page_id).unwrap().set(
+        "TrimBox",
+        vec![20.into(), 20.into(), 575.into(), 822.into()],
+    );
+    let mut pdf: Vec<u8> = Vec::new();
+    doc.save_to(&mut pdf).unwrap();
+
+    let bleed_only = Bleed {
+        margin: 5.0,
+        crop_marks: false,
+    };
+    let output: Vec<u8> = bleed(&pdf, &bleed_only).unwrap();
+    let doc: Document = Document::load_mem(&output).unwrap();
+    assert_eq!(page_box(&doc, 1, b"TrimBox"), [20.0, 20.0, 575.0, 822.0]);
+    assert_eq!(page_box(&doc, 1, b"MediaBox"), [15.0, 15.0, 580.0, 827.0]);
+}
+
+#[test]
+fn test_bleed_rejects_nothing_to_do() {
+    let nothing = Bleed {
+        margin: 0.0,
+        crop_marks: false,
+    };
+    let err: ConvertError = bleed(&make_test_pdf(1), &nothing).unwrap_err();
+    assert!(matches!(err, ConvertError::InvalidOptions(_)), "{err:?}");
+}
+
+#[test]
+fn test_bleed_rejects_invalid_pdf() {
+    let bleed_only = Bleed {
+        margin: 9.0,
+        crop_marks: false,
+    };
+    assert!(bleed(b"not a pdf", &bleed_only).is_err());
+}
@@ -0,0 +1,194 @@
+//! Sharing identical fonts and images, such as the copies each chunk of a
+//! streamed spreadsheet embeds before the chunks are merged.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use lopdf::{Dictionary, Document, Object, ObjectId};
+
+/// Replace each font and image object, and each object they refer to,
+/// with the first identical one, and drop the copies.
+///
+/// Objects are compared as stored, so a font subset differently in each
+/// input stays apart. Returns the number of objects dropped.
+pub(super) fn deduplicate(doc: &mut Document) -> usize {
+    let candidates: BTreeSet<ObjectId> = font_and_image_objects(doc);
+    let mut dropped: usize = 0;
+    // Merging two font files makes the descriptors pointing at them equal,
+    // and those the fonts in turn: repeat until nothing changes.
+    loop {
+        let replacements: BTreeMap<ObjectId, ObjectId> = duplicates(doc, &candidates);
+        if replacements.is_empty() {
+            return dropped;
+        }
+        for id in replacements.keys() {
+            doc.objects.remove(id);
+        }
+        dropped += replacements.len();
+        for object in doc.objects.values_mut() {
+            redirect(object, &replacements);
+        }
+        for (_, value) in doc.trailer.iter_mut() {
+            redirect(value, &replacements);
+        }
+    }
+}
+
+/// The fonts and image XObjects in `doc`, and every object reachable from
+/// them but pages and the page tree.
+fn font_and_image_objects(doc: &Document) -> BTreeSet<ObjectId> {
+    let mut found: BTreeSet<ObjectId> = BTreeSet::new();
+    let mut pending: Vec<ObjectId> = doc
+        .objects
+        .iter()
+        .filter(|(_, object)| {
+            let dict: Option<&Dictionary> = match object {
+                Object::Dictionary(dict) => Some(dict),
+                Object::Stream(stream) => Some(&stream.dict),
+                _ => None,
+            };
+            dict.is_some_and(|dict| {
+                dict.has_type(b"Font")
+                    || dict.get(b"Subtype").and_then(Object::as_name).ok() == Some(b"Image")
+            })
+        })
+        .map(|(&id, _)| id)
+        .collect();
+    while let Some(id) = pending.pop() {
+        let Ok(object) = doc.get_object(id) else {
+            continue;
+        };
+        // A Type 3 font's resources may lead back to pages, which have to
+        // stay distinct.
+        let structural: bool = object
+            .as_dict()
+            .is_ok_and(|dict| dict.has_type(b"Page") || dict.has_type(b"Pages"));
+        if structural || !found.insert(id) {
+            continue;
+        }
+        collect_references(object, &mut pending);
+    }
+    found
+}
+
+fn collect_references(object: &Object, references: &mut Vec<ObjectId>) {
+    match object {
+        Object::Reference(id) => references.push(*id),
+        Object::Array(items) => {
+            for item in items {
+                collect_references(item, references);
+            }
+        }
+        Object::Dictionary(dict) => {
+            for (_, value) in dict.iter() {
+                collect_references(value, references);
+            }
+        }
+        Object::Stream(stream) => {
+            for (_, value) in stream.dict.iter() {
+                collect_references(value, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Each candidate equal to one with a lower number, mapped to the lowest.
+fn duplicates(doc: &Document, candidates: &BTreeSet<ObjectId>) -> BTreeMap<ObjectId, ObjectId> {
+    // Hashing first keeps the byte-for-byte comparisons to likely matches.
+    let mut by_hash: HashMap<u64, Vec<ObjectId>> = HashMap::new();
+    let mut replacements: BTreeMap<ObjectId, ObjectId> = BTreeMap::new();
+    for &id in candidates {
+        let Some(object) = doc.objects.get(&id) else {
+            continue;
+        };
+        let mut hasher = DefaultHasher::new();
+        hash_object(object, &mut hasher);
+        let kept: &mut Vec<ObjectId> = by_hash.entry(hasher.finish()).or_default();
+        match kept
+            .iter()
+            .find(|kept_id| same_object(&doc.objects[kept_id], object))
+        {
+            Some(&original) => {
+                replacements.insert(id, original);
+            }
+            None => kept.push(id),
+        }
+    }
+    replacements
+}
+
+/// Hash `object` consistently with [`same_object`]: dictionary entries in
+/// any order hash alike.
+fn hash_object(object: &Object, hasher: &mut DefaultHasher) {
+    std::mem::discriminant(object).hash(hasher);
+    match object {
+        Object::Null => {}
+        Object::Boolean(value) => value.hash(hasher),
+        Object::Integer(value) => value.hash(hasher),
+        Object::Real(value) => value.to_bits().hash(hasher),
+        Object::Name(name) => name.hash(hasher),
+        Object::String(bytes, _) => bytes.hash(hasher),
+        Object::Array(items) => {
+            items.len().hash(hasher);
+            for item in items {
+                hash_object(item, hasher);
+            }
+        }
+        Object::Dictionary(dict) => hash_dictionary(dict, hasher),
+        Object::Stream(stream) => {
+            hash_dictionary(&stream.dict, hasher);
+            stream.content.hash(hasher);
+        }
+        Object::Reference(id) => id.hash(hasher),
+    }
+}
+
+fn hash_dictionary(dict: &Dictionary, hasher: &mut DefaultHasher) {
+    let mut entries: Vec<(&Vec<u8>, &Object)> = dict.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries.len().hash(hasher);
+    for (key, value) in entries {
+        key.hash(hasher);
+        hash_object(value, hasher);
+    }
+}
+
+/// Whether two objects read the same, leaving aside where a stream was
+/// found in the file it was loaded from.
+fn same_object(a: &Object, b: &Object) -> bool {
+    match (a, b) {
+        (Object::Stream(a), Object::Stream(b)) => a.dict == b.dict && a.content == b.content,
+        _ => a == b,
+    }
+}
+
+fn redirect(object: &mut Object, replacements: &BTreeMap<ObjectId, ObjectId>) {
+    match object {
+        Object::Reference(id) => {
+            if let Some(&original) = replacements.get(id) {
+                *id = original;
+            }
+        }
+        Object::Array(items) => {
+            for item in items {
+                redirect(item, replacements);
+            }
+        }
+        Object::Dictionary(dict) => {
+            for (_, value) in dict.iter_mut() {
+                redirect(value, replacements);
+            }
+        }
+        Object::Stream(stream) => {
+            for (_, value) in stream.dict.iter_mut() {
+                redirect(value, replacements);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+#[path = "pdf_ops_dedupe_tests.rs"]
+mod tests;
@@ -0,0 +1,128 @@
+use super::*;
+use lopdf::{Stream, dictionary};
+
+/// Add an embedded TrueType font, its descriptor and font file, returning
+/// the font.
+fn add_font(doc: &mut Document, font_file: &[u8]) -> ObjectId {
+    let file_id = doc.add_object(Stream::new(dictionary! {}, font_file.to_vec()));
+    let descriptor_id = doc.add_object(dictionary! {
+        "Type" => "FontDescriptor",
+        "FontName" => "Example",
+        "FontFile2" => file_id,
+    });
+    doc.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "TrueType",
+        "BaseFont" => "Example",
+        "FontDescriptor" => descriptor_id,
+    })
+}
+
+/// Add an image with a soft mask, returning the image.
+fn add_image(doc: &mut Document, pixels: &[u8]) -> ObjectId {
+    let image = |extra: Dictionary| {
+        let mut dict: Dictionary = dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Image",
+            "Width" => 1,
+            "Height" => 1,
+            "BitsPerComponent" => 8,
+        };
+        dict.extend(&extra);
+        dict
+    };
+    let mask_id = doc.add_object(Stream::new(
+        image(dictionary! { "ColorSpace" => "DeviceGray" }),
+        vec![255],
+    ));
+    doc.add_object(Stream::new(
+        image(dictionary! { "ColorSpace" => "DeviceRGB", "SMask" => mask_id }),
+        pixels.to_vec(),
+    ))
+}
+
+/// A document with a page for each `(font, image)` pair, drawing both.
+fn make_document(resources: &[(ObjectId, ObjectId)], doc: &mut Document) {
+    let pages_id = doc.new_object_id();
+    let mut kids: Vec<Object> = Vec::new();
+    for &(font_id, image_id) in resources {
+        let content_id = doc.add_object(Stream::new(
+            dictionary! {},
+            b"q /Im1 Do Q BT /F1 12 Tf (Hi) Tj ET".to_vec(),
+        ));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+            "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            "Resources" => dictionary! {
+                "Font" => dictionary! { "F1" => font_id },
+                "XObject" => dictionary! { "Im1" => image_id },
+            },
+        });
+        kids.push(page_id.into());
+    }
+    doc.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Count" => resources.len() as i64,
+            "Kids" => kids,
+        }),
+    );
+    let catalog_id = doc.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    });
+    doc.trailer.set("Root", catalog_id);
+}
+
+fn page_resource(doc: &Document, number: u32, kind: &[u8]) -> ObjectId {
+    let page: &Dictionary = doc.get_dictionary(doc.get_pages()[&number]).unwrap();
+    let resources: &Dictionary = page.get(b"Resources").unwrap().as_dict().unwrap();
+    let entries: &Dictionary = resources.get(kind).unwrap().as_dict().unwrap();
+    entries.iter().next().unwrap().1.as_reference().unwrap()
+}
+
+#[test]
+fn test_deduplicate_shares_identical_fonts_and_images() {
+    let mut doc = Document::with_version("1.7");
+    let first_font: ObjectId = add_font(&mut doc, b"glyphs");
+    let second_font: ObjectId = add_font(&mut doc, b"glyphs");
+    let first_image: ObjectId = add_image(&mut doc, &[1, 2, 3]);
+    let second_image: ObjectId = add_image(&mut doc, &[1, 2, 3]);
+    make_document(
+        &[(first_font, first_image), (second_font, second_image)],
+        &mut doc,
+    );
+    let before: usize = doc.objects.len();
+
+    // Font file, descriptor and font; mask and image.
+    assert_eq!(deduplicate(&mut doc), 5);
+    assert_eq!(doc.objects.len(), before - 5);
+    assert_eq!(page_resource(&doc, 2, b"Font"), first_font);
+    assert_eq!(page_resource(&doc, 2, b"XObject"), first_image);
+    // The identical pages and content streams stay as they are.
+    assert_eq!(doc.get_pages().len(), 2);
+    let mut pdf: Vec<u8> = Vec::new();
+    doc.save_to(&mut pdf).unwrap();
+    assert!(Document::load_mem(&pdf).is_ok());
+}
+
+#[test]
+fn test_deduplicate_keeps_fonts_and_images_that_differ() {
+    let mut doc = Document::with_version("1.7");
+    let first_font: ObjectId = add_font(&mut doc, b"glyphs");
+    let second_font: ObjectId = add_font(&mut doc, b"other glyphs");
+    let first_image: ObjectId = add_image(&mut doc, &[1, 2, 3]);
+    let second_image: ObjectId = add_image(&mut doc, &[3, 2, 1]);
+    make_document(
+        &[(first_font, first_image), (second_font, second_image)],
+        &mut doc,
+    );
+
+    // Only the two soft masks are the same.
+    assert_eq!(deduplicate(&mut doc), 1);
+    assert_eq!(page_resource(&doc, 2, b"Font"), second_font);
+    assert_eq!(page_resource(&doc, 2, b"XObject"), second_image);
+}
@@ -0,0 +1,267 @@
+//! Comparing two PDFs page by page, by their text and, optionally, by how
+//! their pages look.
+
+use image::{Rgba, RgbaImage};
+
+use super::{extract_text, page_count};
+use crate::config::SlideImageFormat;
+use crate::error::ConvertError;
+use crate::render::pdf::{FontSources, compile_to_images};
+use crate::render::typst_gen::ImageAsset;
+
+/// How [`diff`] compares two PDFs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiffOptions {
+    /// Render the pages and compare them pixel by pixel, besides their
+    /// text.
+    pub pixels: bool,
+    /// Resolution the pages are rendered at.
+    pub dpi: f32,
+    /// How far apart, out of 255, two pixels' color channels may be and
+    /// still count as the same, absorbing anti-aliasing noise.
+    pub tolerance: u8,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            pixels: false,
+            dpi: 72.0,
+            tolerance: 16,
+        }
+    }
+}
+
+/// What differs between two PDFs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffReport {
+    /// Page count of the first PDF.
+    pub pages_a: u32,
+    /// Page count of the second PDF.
+    pub pages_b: u32,
+    /// The pages that differ, in order. A page only one PDF has is listed
+    /// with all of its text added or removed.
+    pub pages: Vec<PageDiff>,
+}
+
+impl DiffReport {
+    /// Whether the PDFs have as many pages and no page differs.
+    pub fn is_identical(&self) -> bool {
+        self.pages_a == self.pages_b && self.pages.is_empty()
+    }
+}
+
+/// How one page differs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageDiff {
+    /// The page number, from 1.
+    pub page: u32,
+    /// Lines of text, in page order, that only one of the PDFs has.
+    pub text: Vec<TextChange>,
+    /// The rendered comparison, when asked for and both PDFs have the page.
+    pub pixels: Option<PixelDiff>,
+}
+
+/// A line of text that differs, its words separated by single spaces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextChange {
+    /// Only in the first PDF.
+    Removed(String),
+    /// Only in the second PDF.
+    Added(String),
+}
+
+/// How a page's rendering differs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PixelDiff {
+    /// Pixels that differ, counting those only one rendering covers.
+    pub differing: u64,
+    /// Pixels of the larger rendering.
+    pub total: u64,
+    /// A PNG of the second PDF's page, faded, with the differing pixels in
+    /// red.
+    pub image: Vec<u8>,
+}
+
+/// Compare two PDFs page by page: the text of each page, line by line,
+/// and with [`DiffOptions::pixels`] the rendered pages.
+pub fn diff(a: &[u8], b: &[u8], options: &DiffOptions) -> Result<DiffReport, ConvertError> {
+    let text_a: Vec<Vec<String>> = page_lines(a)?;
+    let text_b: Vec<Vec<String>> = page_lines(b)?;
+    let (images_a, images_b): (Vec<RgbaImage>, Vec<RgbaImage>) = if options.pixels {
+        (render_pages(a, options.dpi)?, render_pages(b, options.dpi)?)
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let mut pages: Vec<PageDiff> = Vec::new();
+    for index in 0..text_a.len().max(text_b.len()) {
+        let text: Vec<TextChange> = diff_lines(
+            text_a.get(index).map_or(&[], Vec::as_slice),
+            text_b.get(index).map_or(&[], Vec::as_slice),
+        );
+        let pixels: Option<PixelDiff> = match (images_a.get(index), images_b.get(index)) {
+            (Some(image_a), Some(image_b)) => {
+                Some(compare_images(image_a, image_b, options.tolerance)?)
+            }
+            _ => None,
+        };
+        let only_in_one: bool = index >= text_a.len() || index >= text_b.len();
+        if only_in_one
+            || !text.is_empty()
+            || pixels.as_ref().is_some_and(|pixels| pixels.differing > 0)
+        {
+            pages.push(PageDiff {
+                page: index as u32 + 1,
+                text,
+                pixels,
+            });
+        }
+    }
+    Ok(DiffReport {
+        pages_a: text_a.len() as u32,
+        pages_b: text_b.len() as u32,
+        pages,
+    })
+}
+
+/// The lines of text of each page: words in content order, a new line
+/// starting wherever a word sits off the baseline of the one before it.
+/// Rebuilding lines from the words leaves out spacing a converter changes.
+fn page_lines(pdf: &[u8]) -> Result<Vec<Vec<String>>, ConvertError> {
+    Ok(extract_text(pdf)?
+        .into_iter()
+        .map(|page| {
+            let mut lines: Vec<String> = Vec::new();
+            let mut baseline: Option<f64> = None;
+            for word in &page.words {
+                let same_line: bool =
+                    baseline.is_some_and(|y| (word.bbox.y - y).abs() <= word.bbox.height / 2.0);
+                match lines.last_mut() {
+                    Some(line) if same_line => {
+                        line.push(' ');
+                        line.push_str(&word.text);
+                    }
+                    _ => lines.push(word.text.clone()),
+                }
+                baseline = Some(word.bbox.y);
+            }
+            lines
+        })
+        .collect())
+}
+
+/// The lines outside the longest common subsequence of `a` and `b`, in
+/// order, removals before the additions that replace them.
+fn diff_lines(a: &[String], b: &[String]) -> Vec<TextChange> {
+    // common[i][j]: the longest common subsequence of a[i..] and b[j..].
+    let mut common: Vec<Vec<u32>> = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            common[i][j] = if a[i] == b[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let mut changes: Vec<TextChange> = Vec::new();
+    let (mut i, mut j): (usize, usize) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            i += 1;
+            j += 1;
+        } else if j == b.len() || (i < a.len() && common[i + 1][j] >= common[i][j + 1]) {
+            changes.push(TextChange::Removed(a[i].clone()));
+            i += 1;
+        } else {
+            changes.push(TextChange::Added(b[j].clone()));
+            j += 1;
+        }
+    }
+    changes
+}
+
+/// Render every page of `pdf`, placed as an image in an otherwise empty
+/// Typst document of pages its size.
+fn render_pages(pdf: &[u8], dpi: f32) -> Result<Vec<RgbaImage>, ConvertError> {
+    const FILE: &str = "input.pdf";
+    let pages: u32 = page_count(pdf)?;
+    if pages == 0 {
+        return Ok(Vec::new());
+    }
+    let mut source: String = String::from("#set page(width: auto, height: auto, margin: 0pt)\n");
+    for page in 1..=pages {
+        if page > 1 {
+            source.push_str("#pagebreak()\n");
+        }
+        source.push_str(&format!("#image(\"{FILE}\", page: {page})\n"));
+    }
+    let images: [ImageAsset; 1] = [ImageAsset {
+        path: FILE.to_string(),
+        data: pdf.to_vec(),
+    }];
+    // The pages bring their own fonts.
+    let fonts = FontSources {
+        paths: &[],
+        data: &[],
+        system_fonts: false,
+    };
+    compile_to_images(&source, &images, &fonts, SlideImageFormat::Png, dpi)?
+        .into_iter()
+        .map(|output| {
+            image::load_from_memory(&output.data)
+                .map(|image| image.to_rgba8())
+                .map_err(|e| ConvertError::Render(format!("cannot read a rendered page: {e}")))
+        })
+        .collect()
+}
+
+/// Compare two renderings of a page over the larger of the two.
+fn compare_images(a: &RgbaImage, b: &RgbaImage, tolerance: u8) -> Result<PixelDiff, ConvertError> {
+    let (width, height): (u32, u32) = (a.width().max(b.width()), a.height().max(b.height()));
+    let mut marked: RgbaImage = RgbaImage::new(width, height);
+    let mut differing: u64 = 0;
+    for (x, y, pixel) in marked.enumerate_pixels_mut() {
+        let pixel_a: Option<&Rgba<u8>> = a.get_pixel_checked(x, y);
+        let pixel_b: Option<&Rgba<u8>> = b.get_pixel_checked(x, y);
+        let same: bool = match (pixel_a, pixel_b) {
+            (Some(pixel_a), Some(pixel_b)) => pixel_a
+                .0
+                .iter()
+                .zip(pixel_b.0)
+                .all(|(channel_a, channel_b)| channel_a.abs_diff(channel_b) <= tolerance),
+            _ => false,
+        };
+        *pixel = if same {
+            faded(pixel_b.or(pixel_a).copied().unwrap_or(Rgba([255; 4])))
+        } else {
+            differing += 1;
+            Rgba([255, 0, 0, 255])
+        };
+    }
+    let mut image: Vec<u8> = Vec::new();
+    marked
+        .write_to(
+            &mut std::io::Cursor::new(&mut image),
+            image::ImageFormat::Png,
+        )
+        .map_err(|e| ConvertError::Render(format!("failed to write the diff image: {e}")))?;
+    Ok(PixelDiff {
+        differing,
+        total: u64::from(width) * u64::from(height),
+        image,
+    })
+}
+
+/// `pixel` as a light gray, so that red marks stand out against it.
+fn faded(pixel: Rgba<u8>) -> Rgba<u8> {
+    let [red, green, blue, _] = pixel.0.map(u32::from);
+    let luma: u32 = (red * 299 + green * 587 + blue * 114) / 1000;
+    let light: u8 = (255 - (255 - luma) / 3) as u8;
+    Rgba([light, light, light, 255])
+}
+
+#[cfg(test)]
+#[path = "pdf_ops_diff_tests.rs"]
+mod tests;
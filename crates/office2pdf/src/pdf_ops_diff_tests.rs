@@ -0,0 +1,132 @@
+use super::*;
+use crate::pdf_ops::test_support::TestPdf;
+use lopdf::dictionary;
+
+/// A PDF with a page per entry of `pages`, each showing its lines in
+/// Helvetica.
+fn make_test_pdf(pages: &[&[&str]]) -> Vec<u8> {
+    let mut pdf = TestPdf::new();
+    for lines in pages {
+        let mut content: String = String::from("BT /F1 12 Tf 14 TL 72 770 Td");
+        for line in *lines {
+            content.push_str(&format!(" ({line}) Tj T*"));
+        }
+        content.push_str(" ET");
+        pdf.add_page(
+            &content,
+            dictionary! {
+                "Resources" => dictionary! {
+                    "Font" => dictionary! {
+                        "F1" => dictionary! {
+                            "Type" => "Font",
+                            "Subtype" => "Type1",
+                            "BaseFont" => "Helvetica",
+                            "Encoding" => "WinAnsiEncoding",
+                        },
+                    },
+                },
+            },
+        );
+    }
+    pdf.save()
+}
+
+fn lines(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|line| line.to_string()).collect()
+}
+
+#[test]
+fn test_diff_identical_pdfs() {
+    let pdf: Vec<u8> = make_test_pdf(&[&["Quarterly report", "Revenue grew"], &["Outlook"]]);
+    let report: DiffReport = diff(&pdf, &pdf, &DiffOptions::default()).unwrap();
+    assert!(report.is_identical(), "{report:?}");
+    assert_eq!((report.pages_a, report.pages_b), (2, 2));
+}
+
+#[test]
+fn test_diff_reports_changed_lines_per_page() {
+    let a: Vec<u8> = make_test_pdf(&[&["Title"], &["Revenue grew", "Costs fell", "Outlook"]]);
+    let b: Vec<u8> = make_test_pdf(&[&["Title"], &["Revenue grew", "Costs rose", "Outlook"]]);
+    let report: DiffReport = diff(&a, &b, &DiffOptions::default()).unwrap();
+    assert!(!report.is_identical());
+    assert_eq!(report.pages.len(), 1);
+    assert_eq!(report.pages[0].page, 2);
+    assert_eq!(
+        report.pages[0].text,
+        vec![
+            TextChange::Removed("Costs fell".to_string()),
+            TextChange::Added("Costs rose".to_string()),
+        ]
+    );
+    assert_eq!(report.pages[0].pixels, None);
+}
+
+#[test]
+fn test_diff_reports_pages_only_one_pdf_has() {
+    let a: Vec<u8> = make_test_pdf(&[&["One"]]);
+    let b: Vec<u8> = make_test_pdf(&[&["One"], &["Two"]]);
+    let report: DiffReport = diff(&a, &b, &DiffOptions::default()).unwrap();
+    assert_eq!((report.pages_a, report.pages_b), (1, 2));
+    assert_eq!(report.pages.len(), 1);
+    assert_eq!(report.pages[0].page, 2);
+    assert_eq!(
+        report.pages[0].text,
+        vec![TextChange::Added("Two".to_string())]
+    );
+}
+
+#[test]
+fn test_diff_lines_keeps_common_lines() {
+    let changes: Vec<TextChange> =
+        diff_lines(&lines(&["a", "b", "c", "d"]), &lines(&["a", "c", "e", "d"]));
+    assert_eq!(
+        changes,
+        vec![
+            TextChange::Removed("b".to_string()),
+            TextChange::Added("e".to_string()),
+        ]
+    );
+    assert!(diff_lines(&lines(&["x"]), &lines(&["x"])).is_empty());
+}
+
+#[test]
+fn test_compare_images_marks_differences() {
+    let a: RgbaImage = RgbaImage::from_pixel(4, 2, Rgba([255, 255, 255, 255]));
+    let mut b: RgbaImage = a.clone();
+    // Within the tolerance, then well beyond it.
+    b.put_pixel(0, 0, Rgba([250, 250, 250, 255]));
+    b.put_pixel(3, 1, Rgba([0, 0, 0, 255]));
+    let diff: PixelDiff = compare_images(&a, &b, 16).unwrap();
+    assert_eq!((diff.differing, diff.total), (1, 8));
+    let marked: RgbaImage = image::load_from_memory(&diff.image).unwrap().to_rgba8();
+    assert_eq!(marked.get_pixel(3, 1), &Rgba([255, 0, 0, 255]));
+    assert_eq!(marked.get_pixel(1, 1), &Rgba([255, 255, 255, 255]));
+
+    // What only the larger rendering covers differs too.
+    let taller: RgbaImage = RgbaImage::from_pixel(4, 3, Rgba([255, 255, 255, 255]));
+    let diff: PixelDiff = compare_images(&a, &taller, 16).unwrap();
+    assert_eq!((diff.differing, diff.total), (4, 12));
+}
+
+#[test]
+fn test_diff_pixels_renders_pages() {
+    let a: Vec<u8> = make_test_pdf(&[&["Same"], &["Before"]]);
+    let b: Vec<u8> = make_test_pdf(&[&["Same"], &["After"]]);
+    let options = DiffOptions {
+        pixels: true,
+        ..DiffOptions::default()
+    };
+    let report: DiffReport = diff(&a, &b, &options).unwrap();
+    assert_eq!(report.pages.len(), 1);
+    let pixels: &PixelDiff = report.pages[0].pixels.as_ref().unwrap();
+    assert!(pixels.differing > 0);
+    // A4 at 72 dpi.
+    assert_eq!(pixels.total, 595 * 842);
+    assert!(diff(&a, &a, &options).unwrap().is_identical());
+}
+
+#[test]
+fn test_diff_rejects_invalid_pdf() {
+    let pdf: Vec<u8> = make_test_pdf(&[&["One"]]);
+    assert!(diff(&pdf, b"not a pdf", &DiffOptions::default()).is_err());
+}
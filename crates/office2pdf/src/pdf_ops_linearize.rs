@@ -0,0 +1,636 @@
+//! Linearization, or "fast web view": the objects of the first page come
+//! first, so a viewer can show it before the rest of the file arrives, and
+//! hint tables tell it where every other page starts (ISO 32000-1,
+//! Annex F).
+//!
+//! The file is laid out as Annex F orders it: the linearization
+//! dictionary and the first-page cross-reference section, the catalog and
+//! what opening the document needs, the hint stream, the first page, then
+//! every other page with the objects only it uses, the objects pages share
+//! and everything else, and last the main cross-reference section.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use lopdf::{Dictionary, Document, Object, ObjectId, StringFormat};
+
+use super::{compress_streams, load_pdf_document, push_inherited_attributes, referenced_objects};
+use crate::error::ConvertError;
+
+/// Catalog entries a viewer reads on opening, whose objects are written
+/// with the catalog.
+const OPEN_DOCUMENT_KEYS: [&[u8]; 4] =
+    [b"ViewerPreferences", b"Threads", b"OpenAction", b"AcroForm"];
+
+/// Width of the numbers only known once the file is laid out, which are
+/// padded so that knowing them moves nothing: ten digits reach 10 GB.
+const PADDED: usize = 10;
+
+/// Which objects go where, in file order.
+struct Layout {
+    /// The catalog and what opening the document needs.
+    open_document: Vec<ObjectId>,
+    /// The first page object and the objects it uses.
+    first_page: Vec<ObjectId>,
+    /// Every other page: its page object and the objects only it uses.
+    pages: Vec<Vec<ObjectId>>,
+    /// Objects used by more than one page but the first.
+    shared: Vec<ObjectId>,
+    /// Everything else: the page tree, outlines, structure tree, metadata.
+    other: Vec<ObjectId>,
+    /// For every page, the objects it shares with others, as indexes into
+    /// `first_page` followed by `shared`.
+    shared_by_page: Vec<Vec<usize>>,
+}
+
+/// Rewrite `input` linearized. Streams stored uncompressed are compressed
+/// on the way, but for XMP metadata, and objects nothing refers to are
+/// dropped.
+///
+/// Linearization only lasts until the file is rewritten or appended to,
+/// which encrypting or signing it does; encrypted input is refused.
+pub fn linearize(input: &[u8]) -> Result<Vec<u8>, ConvertError> {
+    let mut doc: Document = load_pdf_document(input, "")?;
+    // lopdf decrypts on load: writing the document back would silently
+    // drop the protection.
+    if doc.was_encrypted() {
+        return Err(ConvertError::InvalidOptions(
+            "cannot linearize an encrypted PDF".to_string(),
+        ));
+    }
+    let page_ids: Vec<ObjectId> = doc.get_pages().into_values().collect();
+    if page_ids.is_empty() {
+        return Err(failed("the PDF has no pages"));
+    }
+    push_inherited_attributes(&mut doc, &page_ids);
+    compress_streams(&mut doc);
+    let layout: Layout = plan(&doc, &page_ids)?;
+    Ok(write(&doc, &layout))
+}
+
+/// Sort the objects into the parts of a linearized file.
+fn plan(doc: &Document, page_ids: &[ObjectId]) -> Result<Layout, ConvertError> {
+    let root_id: ObjectId = doc
+        .trailer
+        .get(b"Root")
+        .and_then(Object::as_reference)
+        .map_err(|_| failed("the trailer has no catalog"))?;
+    let catalog: &Dictionary = doc
+        .get_dictionary(root_id)
+        .map_err(|_| failed("the catalog is not a dictionary"))?;
+
+    let mut opening: Vec<&Object> = OPEN_DOCUMENT_KEYS
+        .iter()
+        .filter_map(|key| catalog.get(key).ok())
+        .collect();
+    // Viewers open such files with the outline showing.
+    if catalog.get(b"PageMode").and_then(Object::as_name).ok() == Some(b"UseOutlines".as_slice())
+        && let Ok(outlines) = catalog.get(b"Outlines")
+    {
+        opening.push(outlines);
+    }
+    let mut open_document: Vec<ObjectId> = vec![root_id];
+    open_document.extend(walk(doc, opening).into_iter().filter(|id| *id != root_id));
+    let mut placed: BTreeSet<ObjectId> = open_document.iter().copied().collect();
+
+    let used: Vec<Vec<ObjectId>> = page_ids
+        .iter()
+        .map(|&page_id| match doc.get_object(page_id) {
+            Ok(page) => walk(doc, vec![page]),
+            Err(_) => Vec::new(),
+        })
+        .collect();
+    let mut users: BTreeMap<ObjectId, usize> = BTreeMap::new();
+    for objects in &used[1..] {
+        for &id in objects {
+            *users.entry(id).or_default() += 1;
+        }
+    }
+
+    let mut first_page: Vec<ObjectId> = vec![page_ids[0]];
+    placed.insert(page_ids[0]);
+    for &id in &used[0] {
+        if placed.insert(id) {
+            first_page.push(id);
+        }
+    }
+    // Page objects go first: a later page must not be claimed as another
+    // page's own object.
+    placed.extend(page_ids[1..].iter().copied());
+    let mut pages: Vec<Vec<ObjectId>> = Vec::with_capacity(page_ids.len() - 1);
+    let mut shared: Vec<ObjectId> = Vec::new();
+    for (&page_id, objects) in page_ids[1..].iter().zip(&used[1..]) {
+        let mut own: Vec<ObjectId> = vec![page_id];
+        for &id in objects {
+            if placed.contains(&id) {
+                continue;
+            }
+            placed.insert(id);
+            if users.get(&id) == Some(&1) {
+                own.push(id);
+            } else {
+                shared.push(id);
+            }
+        }
+        pages.push(own);
+    }
+
+    let index: BTreeMap<ObjectId, usize> = first_page
+        .iter()
+        .chain(&shared)
+        .enumerate()
+        .map(|(position, id)| (*id, position))
+        .collect();
+    // Whatever a later page uses from the first page or the shared objects
+    // it shares; the first page is listed as sharing nothing.
+    let mut shared_by_page: Vec<Vec<usize>> = vec![Vec::new()];
+    for objects in &used[1..] {
+        shared_by_page.push(
+            objects
+                .iter()
+                .filter_map(|id| index.get(id).copied())
+                .collect(),
+        );
+    }
+
+    let other: Vec<ObjectId> = referenced_objects(doc)
+        .into_iter()
+        .filter(|id| !placed.contains(id))
+        .collect();
+    Ok(Layout {
+        open_document,
+        first_page,
+        pages,
+        shared,
+        other,
+        shared_by_page,
+    })
+}
+
+/// The objects `roots` lead to, depth-first, short of the page tree and of
+/// the `/Parent` and `/P` links back up to it: the objects of a page are
+/// found from the page, not from links to it.
+fn walk<'a>(doc: &'a Document, mut roots: Vec<&'a Object>) -> Vec<ObjectId> {
+    let mut seen: BTreeSet<ObjectId> = BTreeSet::new();
+    let mut order: Vec<ObjectId> = Vec::new();
+    roots.reverse();
+    let mut pending: Vec<&Object> = roots;
+    while let Some(object) = pending.pop() {
+        match object {
+            Object::Reference(id) => {
+                if seen.insert(*id)
+                    && let Ok(target) = doc.get_object(*id)
+                    && !is_page_tree_node(target)
+                {
+                    order.push(*id);
+                    pending.push(target);
+                }
+            }
+            Object::Array(items) => pending.extend(items.iter().rev()),
+            Object::Dictionary(dict) => pending.extend(walked_entries(dict)),
+            Object::Stream(stream) => pending.extend(walked_entries(&stream.dict)),
+            _ => {}
+        }
+    }
+    order
+}
+
+fn walked_entries(dict: &Dictionary) -> impl Iterator<Item = &Object> {
+    dict.iter()
+        .rev()
+        .filter(|(key, _)| !matches!(key.as_slice(), b"Parent" | b"P"))
+        .map(|(_, value)| value)
+}
+
+fn is_page_tree_node(object: &Object) -> bool {
+    object
+        .as_dict()
+        .and_then(|dict| dict.get(b"Type"))
+        .and_then(Object::as_name)
+        .is_ok_and(|name| matches!(name, b"Page" | b"Pages"))
+}
+
+/// Write the file `layout` describes.
+fn write(doc: &Document, layout: &Layout) -> Vec<u8> {
+    let file: LinearizedFile = LinearizedFile::new(doc, layout);
+    // Laid out once with placeholder offsets to learn where everything
+    // lands; the numbers that change are padded or fixed-width, so the
+    // second pass lands everything at the same place.
+    let (_, offsets) = file.assemble(&Offsets::default());
+    let (bytes, check) = file.assemble(&offsets);
+    debug_assert_eq!(check, offsets);
+    bytes
+}
+
+/// Where things land in the written file.
+#[derive(Debug, Default, PartialEq)]
+struct Offsets {
+    hint_stream: usize,
+    hint_stream_length: usize,
+    first_page_object: usize,
+    end_of_first_page: usize,
+    first_shared_object: usize,
+    main_xref: usize,
+    file_length: usize,
+}
+
+/// The objects of a [`Layout`], numbered and serialized.
+struct LinearizedFile<'a> {
+    doc: &'a Document,
+    numbers: BTreeMap<ObjectId, u32>,
+    /// The number of the linearization dictionary, the first after those
+    /// of the main section.
+    first_number: u32,
+    hint_number: u32,
+    open_document: Vec<Vec<u8>>,
+    first_page: Vec<Vec<u8>>,
+    /// The later pages, the shared objects and the others.
+    main: Vec<Vec<u8>>,
+    /// Object count and byte length of every page.
+    pages: Vec<(usize, usize)>,
+    /// Where the shared objects start in `main`.
+    shared_start: usize,
+    shared_by_page: &'a [Vec<usize>],
+    /// The byte length of every object of the first page and every shared
+    /// object, which the hint tables list as groups of one.
+    group_lengths: Vec<usize>,
+}
+
+impl<'a> LinearizedFile<'a> {
+    fn new(doc: &'a Document, layout: &'a Layout) -> Self {
+        // The main section, which the main cross-reference table covers, is
+        // numbered from 1; the first-page section follows it.
+        let main: Vec<ObjectId> = layout
+            .pages
+            .iter()
+            .flatten()
+            .chain(&layout.shared)
+            .chain(&layout.other)
+            .copied()
+            .collect();
+        let first_number: u32 = main.len() as u32 + 1;
+        let hint_number: u32 = first_number + 1 + layout.open_document.len() as u32;
+        let numbers: BTreeMap<ObjectId, u32> = main
+            .iter()
+            .zip(1..)
+            .chain(layout.open_document.iter().zip(first_number + 1..))
+            .chain(layout.first_page.iter().zip(hint_number + 1..))
+            .map(|(id, number)| (*id, number))
+            .collect();
+
+        let serialize = |id: &ObjectId| -> Vec<u8> {
+            let mut bytes: Vec<u8> = format!("{} 0 obj\n", numbers[id]).into_bytes();
+            match doc.get_object(*id) {
+                Ok(object) => write_object(&mut bytes, object, &numbers),
+                Err(_) => bytes.extend_from_slice(b"null"),
+            }
+            bytes.extend_from_slice(b"\nendobj\n");
+            bytes
+        };
+        let open_document: Vec<Vec<u8>> = layout.open_document.iter().map(serialize).collect();
+        let first_page: Vec<Vec<u8>> = layout.first_page.iter().map(serialize).collect();
+        let main: Vec<Vec<u8>> = main.iter().map(serialize).collect();
+
+        let mut pages: Vec<(usize, usize)> =
+            vec![(first_page.len(), first_page.iter().map(Vec::len).sum())];
+        let mut start: usize = 0;
+        for objects in &layout.pages {
+            let length: usize = main[start..start + objects.len()]
+                .iter()
+                .map(Vec::len)
+                .sum();
+            pages.push((objects.len(), length));
+            start += objects.len();
+        }
+        let shared_start: usize = start;
+        let group_lengths: Vec<usize> = first_page
+            .iter()
+            .chain(&main[shared_start..shared_start + layout.shared.len()])
+            .map(Vec::len)
+            .collect();
+
+        Self {
+            doc,
+            numbers,
+            first_number,
+            hint_number,
+            open_document,
+            first_page,
+            main,
+            pages,
+            shared_start,
+            shared_by_page: &layout.shared_by_page,
+            group_lengths,
+        }
+    }
+
+    fn size(&self) -> u32 {
+        self.hint_number + 1 + self.first_page.len() as u32
+    }
+
+    /// The whole file, given where `known` says things land, and where
+    /// they did.
+    fn assemble(&self, known: &Offsets) -> (Vec<u8>, Offsets) {
+        let mut found: Offsets = Offsets::default();
+        let mut out: Vec<u8> = format!("%PDF-{}\n%", self.doc.version).into_bytes();
+        // Bytes above 127 mark the file as binary.
+        out.extend_from_slice(&[0xE2, 0xE3, 0xCF, 0xD3, b'\n']);
+
+        let first_number: u32 = self.first_number;
+        let main_xref_header: String = format!("xref\n0 {first_number}");
+        let mut first_offsets: Vec<usize> = vec![out.len()];
+        out.extend(
+            format!(
+                "{first_number} 0 obj\n<</Linearized 1/L {:<PADDED$}/H [{:<PADDED$} {:<PADDED$}]\
+                 /O {}/E {:<PADDED$}/N {}/T {:<PADDED$}>>\nendobj\n",
+                known.file_length,
+                known.hint_stream,
+                known.hint_stream_length,
+                self.hint_number + 1,
+                known.end_of_first_page,
+                self.pages.len(),
+                // The white-space before the main table's first entry.
+                known.main_xref + main_xref_header.len(),
+            )
+            .into_bytes(),
+        );
+
+        let first_page_xref: usize = out.len();
+        let entries: usize = 1 + self.open_document.len() + 1 + self.first_page.len();
+        out.extend(format!("xref\n{first_number} {entries}\n").into_bytes());
+        // Filled in once the objects are placed.
+        let entries_at: usize = out.len();
+        out.resize(entries_at + 20 * entries, 0);
+        out.extend(format!("trailer\n<</Size {}", self.size()).into_bytes());
+        for key in [b"Root".as_slice(), b"Info", b"ID"] {
+            if let Ok(value) = self.doc.trailer.get(key) {
+                write_name(&mut out, key);
+                out.push(b' ');
+                write_object(&mut out, value, &self.numbers);
+            }
+        }
+        out.extend(
+            format!(
+                "/Prev {:<PADDED$}>>\nstartxref\n0\n%%EOF\n",
+                known.main_xref
+            )
+            .into_bytes(),
+        );
+
+        for bytes in &self.open_document {
+            first_offsets.push(out.len());
+            out.extend_from_slice(bytes);
+        }
+        found.hint_stream = out.len();
+        first_offsets.push(out.len());
+        out.extend(self.hint_stream(known));
+        found.hint_stream_length = out.len() - found.hint_stream;
+        found.first_page_object = out.len();
+        for bytes in &self.first_page {
+            first_offsets.push(out.len());
+            out.extend_from_slice(bytes);
+        }
+        found.end_of_first_page = out.len();
+
+        let mut main_offsets: Vec<usize> = Vec::with_capacity(self.main.len());
+        for (index, bytes) in self.main.iter().enumerate() {
+            if index == self.shared_start && self.group_lengths.len() > self.first_page.len() {
+                found.first_shared_object = out.len();
+            }
+            main_offsets.push(out.len());
+            out.extend_from_slice(bytes);
+        }
+
+        found.main_xref = out.len();
+        out.extend(format!("{main_xref_header}\n0000000000 65535 f \n").into_bytes());
+        for offset in &main_offsets {
+            out.extend(format!("{offset:010} 00000 n \n").into_bytes());
+        }
+        out.extend(
+            format!("trailer\n<</Size {first_number}>>\nstartxref\n{first_page_xref}\n%%EOF\n")
+                .into_bytes(),
+        );
+        found.file_length = out.len();
+
+        for (slot, offset) in first_offsets.iter().enumerate() {
+            let at: usize = entries_at + 20 * slot;
+            out[at..at + 20].copy_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+        }
+        (out, found)
+    }
+
+    /// The primary hint stream: the page offset hint table and the shared
+    /// object hint table (ISO 32000-1, F.4). Its length does not depend on
+    /// `known`, whose offsets are fixed-width.
+    ///
+    /// Like qpdf, content stream offsets and lengths repeat the page's,
+    /// which is what viewers use them for, and shared objects are groups
+    /// of one object.
+    fn hint_stream(&self, known: &Offsets) -> Vec<u8> {
+        let mut bits: BitWriter = BitWriter::default();
+        let (min_objects, max_objects) = min_max(self.pages.iter().map(|page| page.0));
+        let (min_length, max_length) = min_max(self.pages.iter().map(|page| page.1));
+        let max_shared: usize = self.shared_by_page.iter().map(Vec::len).max().unwrap_or(0);
+        let length_bits: u32 = bit_width(max_length - min_length);
+        let identifier_bits: u32 = bit_width(self.group_lengths.len());
+
+        bits.write(min_objects, 32);
+        bits.write(known.first_page_object, 32);
+        bits.write(bit_width(max_objects - min_objects) as usize, 16);
+        bits.write(min_length, 32);
+        bits.write(length_bits as usize, 16);
+        // Content stream offsets: from the start of the page, always 0.
+        bits.write(0, 32);
+        bits.write(0, 16);
+        bits.write(min_length, 32);
+        bits.write(length_bits as usize, 16);
+        bits.write(bit_width(max_shared) as usize, 16);
+        bits.write(identifier_bits as usize, 16);
+        // No fractional positions of shared objects within a page.
+        bits.write(0, 16);
+        bits.write(1, 16);
+
+        for &(objects, _) in &self.pages {
+            bits.write(objects - min_objects, bit_width(max_objects - min_objects));
+        }
+        bits.align();
+        for &(_, length) in &self.pages {
+            bits.write(length - min_length, length_bits);
+        }
+        bits.align();
+        for shared in self.shared_by_page {
+            bits.write(shared.len(), bit_width(max_shared));
+        }
+        bits.align();
+        for &group in self.shared_by_page.iter().flatten() {
+            bits.write(group, identifier_bits);
+        }
+        bits.align();
+        for &(_, length) in &self.pages {
+            bits.write(length - min_length, length_bits);
+        }
+        bits.align();
+
+        let shared_table: usize = bits.bytes.len();
+        let (min_group, max_group) = min_max(self.group_lengths.iter().copied());
+        let group_bits: u32 = bit_width(max_group - min_group);
+        let first_shared_number: usize = if self.group_lengths.len() > self.first_page.len() {
+            self.shared_start + 1
+        } else {
+            0
+        };
+        bits.write(first_shared_number, 32);
+        bits.write(known.first_shared_object, 32);
+        bits.write(self.first_page.len(), 32);
+        bits.write(self.group_lengths.len(), 32);
+        // Every group is one object.
+        bits.write(0, 16);
+        bits.write(min_group, 32);
+        bits.write(group_bits as usize, 16);
+        for &length in &self.group_lengths {
+            bits.write(length - min_group, group_bits);
+        }
+        bits.align();
+        // No group carries an MD5 signature.
+        for _ in &self.group_lengths {
+            bits.write(0, 1);
+        }
+        bits.align();
+
+        let mut stream: Vec<u8> = format!(
+            "{} 0 obj\n<</Length {}/S {shared_table}>>stream\n",
+            self.hint_number,
+            bits.bytes.len()
+        )
+        .into_bytes();
+        stream.extend(bits.bytes);
+        stream.extend_from_slice(b"\nendstream\nendobj\n");
+        stream
+    }
+}
+
+/// Packs the hint tables' bit fields, most significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    /// Bits used in the last byte, 0 when it is full.
+    used: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: usize, width: u32) {
+        for bit in (0..width).rev() {
+            if self.used == 0 {
+                self.bytes.push(0);
+            }
+            if (value >> bit) & 1 == 1
+                && let Some(last) = self.bytes.last_mut()
+            {
+                *last |= 0x80 >> self.used;
+            }
+            self.used = (self.used + 1) % 8;
+        }
+    }
+
+    /// Start the next field on a byte boundary.
+    fn align(&mut self) {
+        self.used = 0;
+    }
+}
+
+/// Bits needed to write `value`.
+fn bit_width(value: usize) -> u32 {
+    usize::BITS - value.leading_zeros()
+}
+
+fn min_max(values: impl Iterator<Item = usize>) -> (usize, usize) {
+    values.fold((usize::MAX, 0), |(min, max), value| {
+        (min.min(value), max.max(value))
+    })
+}
+
+/// Serialize `object` with its references renumbered; a reference to an
+/// object that does not exist becomes `null`, as readers take it.
+fn write_object(out: &mut Vec<u8>, object: &Object, numbers: &BTreeMap<ObjectId, u32>) {
+    match object {
+        Object::Null => out.extend_from_slice(b"null"),
+        Object::Boolean(value) => out.extend_from_slice(if *value { b"true" } else { b"false" }),
+        Object::Integer(value) => out.extend(value.to_string().into_bytes()),
+        Object::Real(value) if value.is_finite() => out.extend(value.to_string().into_bytes()),
+        Object::Real(_) => out.push(b'0'),
+        Object::Name(name) => write_name(out, name),
+        Object::String(text, StringFormat::Literal) => {
+            out.push(b'(');
+            for &byte in text {
+                match byte {
+                    b'(' | b')' | b'\\' => out.extend_from_slice(&[b'\\', byte]),
+                    b'\r' => out.extend_from_slice(b"\\r"),
+                    _ => out.push(byte),
+                }
+            }
+            out.push(b')');
+        }
+        Object::String(text, StringFormat::Hexadecimal) => {
+            out.push(b'<');
+            for byte in text {
+                out.extend(format!("{byte:02X}").into_bytes());
+            }
+            out.push(b'>');
+        }
+        Object::Array(items) => {
+            out.push(b'[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(b' ');
+                }
+                write_object(out, item, numbers);
+            }
+            out.push(b']');
+        }
+        Object::Dictionary(dict) => write_dictionary(out, dict, numbers),
+        Object::Stream(stream) => {
+            // The length is written direct: an indirect one may not come
+            // before the stream any more.
+            let mut dict: Dictionary = stream.dict.clone();
+            dict.set("Length", stream.content.len() as i64);
+            write_dictionary(out, &dict, numbers);
+            out.extend_from_slice(b"stream\n");
+            out.extend_from_slice(&stream.content);
+            out.extend_from_slice(b"\nendstream");
+        }
+        Object::Reference(id) => match numbers.get(id) {
+            Some(number) => out.extend(format!("{number} 0 R").into_bytes()),
+            None => out.extend_from_slice(b"null"),
+        },
+    }
+}
+
+fn write_dictionary(out: &mut Vec<u8>, dict: &Dictionary, numbers: &BTreeMap<ObjectId, u32>) {
+    out.extend_from_slice(b"<<");
+    for (key, value) in dict {
+        write_name(out, key);
+        out.push(b' ');
+        write_object(out, value, numbers);
+    }
+    out.extend_from_slice(b">>");
+}
+
+fn write_name(out: &mut Vec<u8>, name: &[u8]) {
+    out.push(b'/');
+    for &byte in name {
+        // Delimiters, white-space and bytes outside the printable range are
+        // written as `#xx`.
+        if b"()<>[]{}/%#".contains(&byte) || !(b'!'..=b'~').contains(&byte) {
+            out.extend(format!("#{byte:02X}").into_bytes());
+        } else {
+            out.push(byte);
+        }
+    }
+}
+
+fn failed(what: &str) -> ConvertError {
+    ConvertError::Render(format!("cannot linearize PDF: {what}"))
+}
+
+#[cfg(test)]
+#[path = "pdf_ops_linearize_tests.rs"]
+mod tests;
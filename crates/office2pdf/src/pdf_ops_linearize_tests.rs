@@ -0,0 +1,270 @@
+use super::*;
+use crate::pdf_ops::test_support::{TestPdf, labelled_page};
+use lopdf::{Stream, dictionary};
+
+/// A PDF of `num_pages` pages inheriting their media box and font from the
+/// page tree; every page but the first also draws a logo they share.
+fn make_test_pdf(num_pages: u32) -> Vec<u8> {
+    let mut pdf = TestPdf::new();
+    let font_id: ObjectId = pdf.add_font("Courier");
+    let logo_id: ObjectId = pdf.doc.add_object(Stream::new(
+        dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Form",
+            "BBox" => vec![0.into(), 0.into(), 10.into(), 10.into()],
+        },
+        b"0 0 10 10 re f".to_vec(),
+    ));
+    pdf.tree_entry(
+        "Resources",
+        dictionary! { "Font" => dictionary! { "F1" => font_id } },
+    );
+    for number in 1..=num_pages {
+        let mut entries: Dictionary = Dictionary::new();
+        if number > 1 {
+            entries.set(
+                "Resources",
+                dictionary! {
+                    "Font" => dictionary! { "F1" => font_id },
+                    "XObject" => dictionary! { "Logo" => logo_id },
+                },
+            );
+        }
+        pdf.add_page(&labelled_page("Page", number), entries);
+    }
+    // Left over from an earlier edit: linearization drops it.
+    pdf.doc.add_object(dictionary! { "Type" => "Orphan" });
+    pdf.save()
+}
+
+/// The byte offset of object `number` in the cross-reference table.
+fn offset_of(doc: &Document, number: u32) -> usize {
+    match doc.reference_table.get(number) {
+        Some(lopdf::xref::XrefEntry::Normal { offset, .. }) => *offset as usize,
+        other => panic!("object {number} has no offset: {other:?}"),
+    }
+}
+
+fn linearization_dictionary(doc: &Document) -> &Dictionary {
+    doc.objects
+        .values()
+        .find_map(|object| object.as_dict().ok().filter(|dict| dict.has(b"Linearized")))
+        .expect("no linearization dictionary")
+}
+
+/// The byte offset of the first `needle` at or after `from`.
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> usize {
+    haystack[from..]
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|position| from + position)
+        .unwrap()
+}
+
+fn integer(dict: &Dictionary, key: &[u8]) -> usize {
+    dict.get(key).unwrap().as_i64().unwrap() as usize
+}
+
+/// Reads the hint tables' bit fields back.
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit: usize,
+}
+
+impl BitReader<'_> {
+    fn read(&mut self, width: usize) -> usize {
+        let mut value: usize = 0;
+        for _ in 0..width {
+            let byte: u8 = self.bytes[self.bit / 8];
+            value = (value << 1) | usize::from((byte >> (7 - self.bit % 8)) & 1);
+            self.bit += 1;
+        }
+        value
+    }
+
+    fn align(&mut self) {
+        self.bit = self.bit.div_ceil(8) * 8;
+    }
+}
+
+#[test]
+fn test_linearize_keeps_pages_and_drops_orphans() {
+    let linearized: Vec<u8> = linearize(&make_test_pdf(3)).unwrap();
+    let doc: Document = Document::load_mem(&linearized).unwrap();
+    let pages = doc.get_pages();
+    assert_eq!(pages.len(), 3);
+    for (number, page_id) in pages {
+        let content: Vec<u8> = doc.get_page_content(page_id).unwrap();
+        let expected: String = format!("(Page {number}) Tj");
+        assert!(
+            String::from_utf8_lossy(&content).contains(&expected),
+            "page {number} lost its content"
+        );
+    }
+    let orphaned: bool = doc.objects.values().any(|object| {
+        object
+            .as_dict()
+            .and_then(|dict| dict.get(b"Type"))
+            .and_then(Object::as_name)
+            .is_ok_and(|name| name == b"Orphan")
+    });
+    assert!(!orphaned);
+}
+
+#[test]
+fn test_linearize_starts_with_linearization_dictionary() {
+    let linearized: Vec<u8> = linearize(&make_test_pdf(3)).unwrap();
+    let doc: Document = Document::load_mem(&linearized).unwrap();
+    let dict: &Dictionary = linearization_dictionary(&doc);
+    assert_eq!(integer(dict, b"L"), linearized.len());
+    assert_eq!(integer(dict, b"N"), 3);
+
+    let first_page: u32 = doc.get_pages()[&1].0;
+    assert_eq!(integer(dict, b"O"), first_page as usize);
+    let second_page: u32 = doc.get_pages()[&2].0;
+    let end_of_first_page: usize = integer(dict, b"E");
+    assert!(offset_of(&doc, first_page) < end_of_first_page);
+    assert!(end_of_first_page <= offset_of(&doc, second_page));
+
+    // The dictionary is the first object, right after the header.
+    let header_end: usize = linearized.iter().skip(9).position(|&b| b == b'\n').unwrap() + 10;
+    let first_object: &[u8] = &linearized[header_end..header_end + 40];
+    assert!(
+        String::from_utf8_lossy(first_object).contains("/Linearized 1"),
+        "{}",
+        String::from_utf8_lossy(first_object)
+    );
+}
+
+#[test]
+fn test_linearize_cross_reference_sections() {
+    let linearized: Vec<u8> = linearize(&make_test_pdf(2)).unwrap();
+    let doc: Document = Document::load_mem(&linearized).unwrap();
+    let dict: &Dictionary = linearization_dictionary(&doc);
+
+    // `/T` points at the white-space before the main table's first entry.
+    let main_entries: usize = integer(dict, b"T");
+    assert_eq!(
+        &linearized[main_entries..main_entries + 20],
+        b"\n0000000000 65535 f "
+    );
+
+    // The last `startxref` points at the first-page section at the top.
+    let tail: &[u8] = &linearized[linearized.len() - 32..];
+    let startxref: usize = find(tail, b"startxref\n", 0) + b"startxref\n".len();
+    let start: usize = String::from_utf8_lossy(&tail[startxref..])
+        .trim_end()
+        .trim_end_matches("%%EOF")
+        .trim()
+        .parse()
+        .unwrap();
+    assert!(start < integer(dict, b"E"));
+    assert!(linearized[start..].starts_with(b"xref\n"));
+}
+
+#[test]
+fn test_linearize_hint_tables_locate_every_page() {
+    let linearized: Vec<u8> = linearize(&make_test_pdf(4)).unwrap();
+    let doc: Document = Document::load_mem(&linearized).unwrap();
+    let dict: &Dictionary = linearization_dictionary(&doc);
+    let hint: &[Object] = dict.get(b"H").unwrap().as_array().unwrap();
+    let hint_offset: usize = hint[0].as_i64().unwrap() as usize;
+    let hint_id: ObjectId = doc
+        .objects
+        .keys()
+        .copied()
+        .find(|id| {
+            matches!(
+                doc.reference_table.get(id.0),
+                Some(lopdf::xref::XrefEntry::Normal { offset, .. }) if *offset as usize == hint_offset
+            )
+        })
+        .expect("no object at the hint stream offset");
+    let stream: &Stream = doc.get_object(hint_id).unwrap().as_stream().unwrap();
+    assert_eq!(
+        hint[1].as_i64().unwrap() as usize,
+        find(&linearized, b"endobj\n", hint_offset) + b"endobj\n".len() - hint_offset
+    );
+
+    let mut bits = BitReader {
+        bytes: &stream.content,
+        bit: 0,
+    };
+    let min_objects: usize = bits.read(32);
+    let first_page_offset: usize = bits.read(32);
+    let object_bits: usize = bits.read(16);
+    let min_length: usize = bits.read(32);
+    let length_bits: usize = bits.read(16);
+    bits.read(32 + 16 + 32 + 16 + 16 + 16 + 16 + 16);
+    let _objects: Vec<usize> = (0..4)
+        .map(|_| min_objects + bits.read(object_bits))
+        .collect();
+    bits.align();
+    let lengths: Vec<usize> = (0..4)
+        .map(|_| min_length + bits.read(length_bits))
+        .collect();
+
+    let pages = doc.get_pages();
+    let mut start: usize = first_page_offset;
+    for (number, length) in (1..=4).zip(lengths) {
+        assert_eq!(start, offset_of(&doc, pages[&number].0), "page {number}");
+        start += length;
+    }
+
+    // The shared object table points at the logo the later pages share.
+    let mut bits = BitReader {
+        bytes: &stream.content,
+        bit: 8 * integer(&stream.dict, b"S"),
+    };
+    let first_shared: u32 = bits.read(32) as u32;
+    let first_shared_offset: usize = bits.read(32);
+    assert_eq!(first_shared_offset, offset_of(&doc, first_shared));
+    let logo: &Object = doc.get_object((first_shared, 0)).unwrap();
+    assert_eq!(
+        logo.as_stream()
+            .unwrap()
+            .dict
+            .get(b"Subtype")
+            .unwrap()
+            .as_name()
+            .unwrap(),
+        b"Form"
+    );
+}
+
+#[test]
+fn test_linearize_pushes_inherited_attributes_to_pages() {
+    let linearized: Vec<u8> = linearize(&make_test_pdf(2)).unwrap();
+    let doc: Document = Document::load_mem(&linearized).unwrap();
+    for page_id in doc.get_pages().into_values() {
+        let page: &Dictionary = doc.get_dictionary(page_id).unwrap();
+        assert!(page.has(b"MediaBox"));
+        assert!(page.has(b"Resources"));
+        let parent: ObjectId = page.get(b"Parent").unwrap().as_reference().unwrap();
+        assert!(!doc.get_dictionary(parent).unwrap().has(b"MediaBox"));
+    }
+}
+
+#[test]
+fn test_linearize_single_page() {
+    let linearized: Vec<u8> = linearize(&make_test_pdf(1)).unwrap();
+    let doc: Document = Document::load_mem(&linearized).unwrap();
+    assert_eq!(doc.get_pages().len(), 1);
+    assert_eq!(integer(linearization_dictionary(&doc), b"N"), 1);
+}
+
+#[test]
+fn test_linearize_rejects_invalid_pdf() {
+    assert!(linearize(b"not a pdf").is_err());
+}
+
+#[test]
+fn test_linearize_rejects_encrypted_pdf() {
+    let security: crate::config::PdfSecurity = crate::config::PdfSecurity {
+        owner_password: "owner".to_string(),
+        ..Default::default()
+    };
+    let encrypted: Vec<u8> = crate::pdf_ops::encrypt(&make_test_pdf(1), &security).unwrap();
+    let err: ConvertError = linearize(&encrypted).unwrap_err();
+    assert!(matches!(err, ConvertError::InvalidOptions(_)), "{err:?}");
+}
@@ -0,0 +1,549 @@
+//! Merging PDFs, optionally with a bookmark for each input and a generated
+//! table of contents in front.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream, dictionary};
+
+use super::dedupe::deduplicate;
+use super::{
+    inherited_media_box, load_pdf_document, pdf_text_string, push_inherited_attributes, rectangle,
+    referenced_objects, save_pdf_to_bytes, text_width, win_ansi_literal,
+};
+use crate::error::ConvertError;
+
+/// Resource names for the table of contents fonts.
+const FONT: &str = "O2PTocFont";
+const BOLD_FONT: &str = "O2PTocBold";
+
+/// Layout of the table of contents, in points.
+const MARGIN: f64 = 72.0;
+const HEADING_SIZE: f64 = 18.0;
+const HEADING_SPACE: f64 = 36.0;
+const ENTRY_SIZE: f64 = 11.0;
+const LINE_HEIGHT: f64 = 18.0;
+
+/// Page size of the table of contents when the first input has none: A4.
+const DEFAULT_PAGE: [f64; 4] = [0.0, 0.0, 595.0, 842.0];
+
+/// How [`merge_with_options`] combines its inputs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeOptions {
+    /// Add a top-level bookmark for each input, with the input's own
+    /// bookmarks under it.
+    pub bookmarks: bool,
+    /// Names for the inputs, in order, such as their file names. An input
+    /// whose metadata has a title goes by that instead; one with neither
+    /// becomes "Document N".
+    pub names: Vec<String>,
+    /// Put a table of contents in front, listing each input with the page
+    /// it starts on.
+    pub table_of_contents: bool,
+}
+
+/// An input as it lands in the merged document.
+struct Part {
+    title: String,
+    pages: Vec<ObjectId>,
+    /// The top-level items of the input's own outline.
+    outline: Vec<ObjectId>,
+}
+
+/// Merge multiple PDFs into a single PDF, with a bookmark for each input
+/// and a table of contents as `options` ask.
+///
+/// Fonts and images the inputs share are embedded once.
+///
+/// Inputs without pages get neither a bookmark nor an entry in the table
+/// of contents, as there is no page to point them at.
+pub fn merge_with_options(
+    inputs: &[&[u8]],
+    options: &MergeOptions,
+) -> Result<Vec<u8>, ConvertError> {
+    if inputs.is_empty() {
+        return Err(ConvertError::Parse("no input PDFs to merge".to_string()));
+    }
+
+    if inputs.len() == 1 && !options.bookmarks && !options.table_of_contents {
+        // Single PDF — just return a copy
+        return Ok(inputs[0].to_vec());
+    }
+
+    // Load all documents
+    let documents: Vec<Document> = inputs
+        .iter()
+        .enumerate()
+        .map(|(i, data)| load_pdf_document(data, &format!(" at index {i}")))
+        .collect::<Result<_, _>>()?;
+
+    // Use lopdf's merge approach: renumber objects, collect pages
+    let mut max_id: u32 = 1;
+    let mut all_objects: BTreeMap<ObjectId, Object> = BTreeMap::new();
+    let mut parts: Vec<Part> = Vec::with_capacity(documents.len());
+    for (index, mut doc) in documents.into_iter().enumerate() {
+        if options.bookmarks {
+            // The input's name tree does not survive the merge.
+            resolve_named_destinations(&mut doc);
+        }
+        doc.renumber_objects_with(max_id);
+        max_id = doc.max_id + 1;
+
+        // Collect page references in order; what they inherit from the
+        // input's page tree moves onto them, as that tree is dropped.
+        let pages: Vec<ObjectId> = doc.get_pages().into_values().collect();
+        push_inherited_attributes(&mut doc, &pages);
+        let title: String = document_title(&doc)
+            .or_else(|| options.names.get(index).cloned())
+            .unwrap_or_else(|| format!("Document {}", index + 1));
+        let outline: Vec<ObjectId> = if options.bookmarks {
+            outline_items(&doc)
+        } else {
+            Vec::new()
+        };
+
+        // Collect all objects except Catalog
+        for (id, object) in doc.objects {
+            if let Ok(dict) = object.as_dict()
+                && dict
+                    .get(b"Type")
+                    .ok()
+                    .and_then(|t| t.as_name().ok())
+                    .is_some_and(|name| name == b"Catalog")
+            {
+                continue;
+            }
+            all_objects.insert(id, object);
+        }
+        parts.push(Part {
+            title,
+            pages,
+            outline,
+        });
+    }
+
+    // Build a new document with merged pages
+    let mut merged = Document::with_version("1.7");
+    merged.objects = all_objects;
+    merged.max_id = max_id;
+
+    let pages_id = merged.new_object_id();
+    let contents_pages: Vec<ObjectId> = if options.table_of_contents {
+        add_table_of_contents(&mut merged, &parts)
+    } else {
+        Vec::new()
+    };
+    let all_pages: Vec<ObjectId> = contents_pages
+        .iter()
+        .chain(parts.iter().flat_map(|part| &part.pages))
+        .copied()
+        .collect();
+
+    // Create Pages dictionary
+    let page_refs: Vec<Object> = all_pages.iter().map(|id| Object::Reference(*id)).collect();
+    let pages_dict = dictionary! {
+        "Type" => "Pages",
+        "Count" => all_pages.len() as i64,
+        "Kids" => page_refs,
+    };
+    merged
+        .objects
+        .insert(pages_id, Object::Dictionary(pages_dict));
+
+    // Update each page's Parent reference
+    for page_id in &all_pages {
+        if let Some(object) = merged.objects.get_mut(page_id)
+            && let Ok(page_dict) = object.as_dict_mut()
+        {
+            page_dict.set("Parent", Object::Reference(pages_id));
+        }
+    }
+
+    // Create Catalog
+    let mut catalog_dict = dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    };
+    if options.bookmarks {
+        let contents_page: Option<ObjectId> = contents_pages.first().copied();
+        let outlines_id: ObjectId = add_outline(&mut merged, &parts, contents_page);
+        catalog_dict.set("Outlines", outlines_id);
+        catalog_dict.set("PageMode", "UseOutlines");
+    }
+    let catalog_id = merged.add_object(catalog_dict);
+    merged.trailer.set("Root", Object::Reference(catalog_id));
+
+    // Drop what only the inputs' catalogs used: their page trees, outlines
+    // and name trees.
+    let referenced: BTreeSet<ObjectId> = referenced_objects(&merged).into_iter().collect();
+    merged.objects.retain(|id, _| referenced.contains(id));
+    // Inputs converted apart, such as the chunks of a large spreadsheet,
+    // each embed the same fonts.
+    deduplicate(&mut merged);
+
+    save_pdf_to_bytes(&mut merged, "merged")
+}
+
+/// The title in the document information dictionary, if it has one.
+fn document_title(doc: &Document) -> Option<String> {
+    let info: &Dictionary = doc
+        .trailer
+        .get(b"Info")
+        .and_then(|info| doc.dereference(info))
+        .and_then(|(_, info)| info.as_dict())
+        .ok()?;
+    let (_, title) = info
+        .get(b"Title")
+        .and_then(|title| doc.dereference(title))
+        .ok()?;
+    let title: String = lopdf::decode_text_string(title).ok()?;
+    let title: &str = title.trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// `first` and the items following it through `Next`.
+fn siblings(doc: &Document, first: Option<ObjectId>) -> Vec<ObjectId> {
+    let mut items: Vec<ObjectId> = Vec::new();
+    let mut next: Option<ObjectId> = first;
+    // A malformed outline can loop back on itself.
+    while let Some(id) = next.filter(|id| !items.contains(id)) {
+        items.push(id);
+        next = doc
+            .get_dictionary(id)
+            .and_then(|item| item.get(b"Next"))
+            .and_then(Object::as_reference)
+            .ok();
+    }
+    items
+}
+
+/// The first child of an outline node.
+fn first_child(doc: &Document, id: ObjectId) -> Option<ObjectId> {
+    doc.get_dictionary(id)
+        .and_then(|node| node.get(b"First"))
+        .and_then(Object::as_reference)
+        .ok()
+}
+
+/// The top-level items of the document's outline.
+pub(super) fn outline_items(doc: &Document) -> Vec<ObjectId> {
+    let outlines: Option<ObjectId> = doc
+        .catalog()
+        .and_then(|catalog| catalog.get(b"Outlines"))
+        .and_then(Object::as_reference)
+        .ok();
+    siblings(doc, outlines.and_then(|id| first_child(doc, id)))
+}
+
+/// Replace the named destinations of the outline items with the explicit
+/// ones the names stand for.
+fn resolve_named_destinations(doc: &mut Document) {
+    let destinations: BTreeMap<Vec<u8>, Object> = named_destinations(doc);
+    if destinations.is_empty() {
+        return;
+    }
+    let mut pending: Vec<ObjectId> = outline_items(doc);
+    let mut seen: BTreeSet<ObjectId> = BTreeSet::new();
+    while let Some(id) = pending.pop() {
+        if !seen.insert(id) {
+            continue;
+        }
+        pending.extend(siblings(doc, first_child(doc, id)));
+
+        // A `GoTo` action may sit in its own object.
+        let action_id: Option<ObjectId> = doc
+            .get_dictionary(id)
+            .and_then(|item| item.get(b"A"))
+            .and_then(Object::as_reference)
+            .ok();
+        let resolve = |dict: &mut Dictionary, key: &[u8]| {
+            let name: Option<&[u8]> = match dict.get(key) {
+                Ok(Object::Name(name)) | Ok(Object::String(name, _)) => Some(name.as_slice()),
+                _ => None,
+            };
+            if let Some(destination) = name.and_then(|name| destinations.get(name)) {
+                dict.set(key, destination.clone());
+            }
+        };
+        if let Ok(item) = doc.get_dictionary_mut(id) {
+            resolve(item, b"Dest");
+            if let Ok(Object::Dictionary(action)) = item.get_mut(b"A") {
+                resolve(action, b"D");
+            }
+        }
+        if let Some(action) = action_id.and_then(|id| doc.get_dictionary_mut(id).ok()) {
+            resolve(action, b"D");
+        }
+    }
+}
+
+/// The document's named destinations, from the catalog's `Dests`
+/// dictionary and the `Dests` name tree.
+pub(super) fn named_destinations(doc: &Document) -> BTreeMap<Vec<u8>, Object> {
+    let mut destinations: BTreeMap<Vec<u8>, Object> = BTreeMap::new();
+    let Ok(catalog) = doc.catalog() else {
+        return destinations;
+    };
+    let dictionary = |object: &Object| -> Option<Dictionary> {
+        doc.dereference(object)
+            .ok()
+            .and_then(|(_, object)| object.as_dict().ok())
+            .cloned()
+    };
+    // A destination is an array, or a dictionary holding one under `D`.
+    let explicit = |value: &Object| -> Option<Object> {
+        let (_, value) = doc.dereference(value).ok()?;
+        match value {
+            Object::Array(_) => Some(value.clone()),
+            Object::Dictionary(dict) => dict
+                .get(b"D")
+                .ok()
+                .and_then(|d| doc.dereference(d).ok())
+                .map(|(_, d)| d.clone()),
+            _ => None,
+        }
+    };
+
+    if let Some(dests) = catalog.get(b"Dests").ok().and_then(dictionary) {
+        for (name, value) in dests.iter() {
+            if let Some(destination) = explicit(value) {
+                destinations.insert(name.clone(), destination);
+            }
+        }
+    }
+    let mut pending: Vec<Dictionary> = catalog
+        .get(b"Names")
+        .ok()
+        .and_then(dictionary)
+        .and_then(|names| names.get(b"Dests").ok().and_then(dictionary))
+        .into_iter()
+        .collect();
+    // Bounded, as a malformed tree can loop back on itself.
+    let mut budget: usize = 10_000;
+    while let Some(node) = pending.pop() {
+        budget = match budget.checked_sub(1) {
+            Some(budget) => budget,
+            None => break,
+        };
+        if let Ok(Object::Array(names)) = node.get(b"Names") {
+            for pair in names.chunks_exact(2) {
+                if let (Ok(name), Some(destination)) = (pair[0].as_str(), explicit(&pair[1])) {
+                    destinations.insert(name.to_vec(), destination);
+                }
+            }
+        }
+        if let Ok(kids) = node.get(b"Kids").and_then(Object::as_array) {
+            pending.extend(kids.iter().filter_map(dictionary));
+        }
+    }
+    destinations
+}
+
+/// Add an outline with a top-level item for each part that has pages,
+/// after one for the table of contents if there is one, and return its
+/// root. Each part's own outline goes under its item.
+fn add_outline(doc: &mut Document, parts: &[Part], contents_page: Option<ObjectId>) -> ObjectId {
+    let outlines_id: ObjectId = doc.new_object_id();
+    let mut entries: Vec<(String, ObjectId, &[ObjectId])> = Vec::with_capacity(parts.len() + 1);
+    if let Some(page) = contents_page {
+        entries.push(("Contents".to_string(), page, &[]));
+    }
+    for part in parts {
+        if let Some(&first_page) = part.pages.first() {
+            entries.push((part.title.clone(), first_page, &part.outline));
+        }
+    }
+
+    let item_ids: Vec<ObjectId> = entries.iter().map(|_| doc.new_object_id()).collect();
+    // Each item is shown open: it counts its children and whatever of
+    // theirs they show.
+    let mut visible: i64 = 0;
+    for (index, ((title, page, children), item_id)) in entries.iter().zip(&item_ids).enumerate() {
+        let mut item = dictionary! {
+            "Title" => pdf_text_string(title),
+            "Parent" => outlines_id,
+            "Dest" => vec![Object::Reference(*page), "Fit".into()],
+        };
+        if index > 0 {
+            item.set("Prev", item_ids[index - 1]);
+        }
+        if let Some(next_id) = item_ids.get(index + 1) {
+            item.set("Next", *next_id);
+        }
+        if let (Some(first), Some(last)) = (children.first(), children.last()) {
+            let mut count: i64 = 0;
+            for child_id in children.iter() {
+                if let Ok(child) = doc.get_dictionary_mut(*child_id) {
+                    child.set("Parent", *item_id);
+                    count += 1 + child
+                        .get(b"Count")
+                        .and_then(Object::as_i64)
+                        .unwrap_or(0)
+                        .max(0);
+                }
+            }
+            item.set("First", *first);
+            item.set("Last", *last);
+            item.set("Count", count);
+            visible += count;
+        }
+        visible += 1;
+        doc.objects.insert(*item_id, Object::Dictionary(item));
+    }
+
+    let mut outlines = dictionary! {
+        "Type" => "Outlines",
+        "Count" => visible,
+    };
+    if let (Some(first), Some(last)) = (item_ids.first(), item_ids.last()) {
+        outlines.set("First", *first);
+        outlines.set("Last", *last);
+    }
+    doc.objects
+        .insert(outlines_id, Object::Dictionary(outlines));
+    outlines_id
+}
+
+/// Add table of contents pages listing each part that has pages with the
+/// page it starts on, each line a link to that page, and return them. The
+/// pages take the size of the first part's first page.
+fn add_table_of_contents(doc: &mut Document, parts: &[Part]) -> Vec<ObjectId> {
+    let [left, bottom, right, top]: [f64; 4] = parts
+        .iter()
+        .find_map(|part| part.pages.first())
+        .and_then(|&page_id| inherited_media_box(doc, page_id))
+        .as_ref()
+        .and_then(rectangle)
+        .unwrap_or(DEFAULT_PAGE);
+    let usable: f64 = top - bottom - 2.0 * MARGIN;
+    let lines_per_page: usize = ((usable / LINE_HEIGHT) as usize).max(1);
+    let first_page_lines: usize = (((usable - HEADING_SPACE) / LINE_HEIGHT) as usize).max(1);
+
+    let entries: Vec<(&str, ObjectId, usize)> = {
+        let mut entries = Vec::new();
+        let mut start: usize = 0;
+        for part in parts {
+            if let Some(&first_page) = part.pages.first() {
+                entries.push((part.title.as_str(), first_page, start));
+            }
+            start += part.pages.len();
+        }
+        entries
+    };
+    let contents_pages: usize = 1 + entries
+        .len()
+        .saturating_sub(first_page_lines)
+        .div_ceil(lines_per_page);
+
+    let fonts = dictionary! {
+        FONT => dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+            "Encoding" => "WinAnsiEncoding",
+        },
+        BOLD_FONT => dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica-Bold",
+            "Encoding" => "WinAnsiEncoding",
+        },
+    };
+    let font_id: ObjectId = doc.add_object(fonts);
+    let (text_left, text_right): (f64, f64) = (left + MARGIN, right - MARGIN);
+    let dot_width: f64 = text_width(".") * ENTRY_SIZE;
+
+    let mut page_ids: Vec<ObjectId> = Vec::with_capacity(contents_pages);
+    let mut remaining = entries.iter();
+    for index in 0..contents_pages {
+        let mut content: Vec<u8> = Vec::new();
+        let mut y: f64 = top - MARGIN - ENTRY_SIZE;
+        let mut lines: usize = lines_per_page;
+        if index == 0 {
+            content.extend(
+                format!(
+                    "BT /{BOLD_FONT} {HEADING_SIZE} Tf {text_left:.3} {:.3} Td (Contents) Tj ET\n",
+                    top - MARGIN - HEADING_SIZE
+                )
+                .into_bytes(),
+            );
+            y -= HEADING_SPACE;
+            lines = first_page_lines;
+        }
+
+        let mut annotations: Vec<Object> = Vec::new();
+        for &(title, target, start) in remaining.by_ref().take(lines) {
+            let number: String = (contents_pages + start + 1).to_string();
+            let number_width: f64 = text_width(&number) * ENTRY_SIZE;
+            // The title gives way to the page number and a few leader dots.
+            let room: f64 = text_right - text_left - number_width - 4.0 * dot_width;
+            let title: String = fit_text(title, room / ENTRY_SIZE);
+            let title_width: f64 = text_width(&title) * ENTRY_SIZE;
+            // Leader dots with a dot's width of space at either end.
+            let leader_right: f64 = text_right - number_width - dot_width;
+            let dots: usize = ((leader_right - text_left - title_width - dot_width) / dot_width)
+                .max(0.0) as usize;
+            let leader: String = ".".repeat(dots);
+            let leader_left: f64 = leader_right - dots as f64 * dot_width;
+
+            content
+                .extend(format!("BT /{FONT} {ENTRY_SIZE} Tf {text_left:.3} {y:.3} Td (").bytes());
+            content.extend(win_ansi_literal(&title));
+            content.extend(
+                format!(
+                    ") Tj ET\nBT /{FONT} {ENTRY_SIZE} Tf {leader_left:.3} {y:.3} Td ({leader}) Tj ET\n\
+                     BT /{FONT} {ENTRY_SIZE} Tf {:.3} {y:.3} Td ({number}) Tj ET\n",
+                    text_right - number_width
+                )
+                .bytes(),
+            );
+            annotations.push(Object::Dictionary(dictionary! {
+                "Type" => "Annot",
+                "Subtype" => "Link",
+                "Rect" => vec![
+                    text_left.into(),
+                    (y - 4.0).into(),
+                    text_right.into(),
+                    (y + ENTRY_SIZE).into(),
+                ],
+                "Border" => vec![0.into(), 0.into(), 0.into()],
+                "Dest" => vec![Object::Reference(target), "Fit".into()],
+            }));
+            y -= LINE_HEIGHT;
+        }
+
+        let content_id: ObjectId = doc.add_object(Stream::new(Dictionary::new(), content));
+        let mut page = dictionary! {
+            "Type" => "Page",
+            "MediaBox" => vec![left.into(), bottom.into(), right.into(), top.into()],
+            "Resources" => dictionary! { "Font" => font_id },
+            "Contents" => content_id,
+        };
+        if !annotations.is_empty() {
+            page.set("Annots", annotations);
+        }
+        page_ids.push(doc.add_object(page));
+    }
+    page_ids
+}
+
+/// `text`, cut short with an ellipsis if it is wider than `width` at a
+/// font size of 1.
+fn fit_text(text: &str, width: f64) -> String {
+    if text_width(text) <= width {
+        return text.to_string();
+    }
+    let room: f64 = width - text_width("...");
+    let mut fitted: String = String::new();
+    for ch in text.chars() {
+        fitted.push(ch);
+        if text_width(&fitted) > room {
+            fitted.pop();
+            break;
+        }
+    }
+    format!("{}...", fitted.trim_end())
+}
+
+#[cfg(test)]
+#[path = "pdf_ops_merge_tests.rs"]
+mod tests;
@@ -0,0 +1,337 @@
+use super::*;
+use crate::pdf_ops::test_support::{TestPdf, labelled_page, page_content};
+
+/// A PDF of `num_pages` A4 pages showing `label` and their page number,
+/// with `title` in its document information if given.
+fn make_test_pdf(num_pages: u32, label: &str, title: Option<&str>) -> Vec<u8> {
+    // The A4 media box is inherited, so the merge has to carry it onto the
+    // pages.
+    let mut pdf = TestPdf::new();
+    for number in 1..=num_pages {
+        pdf.add_page(&labelled_page(label, number), Dictionary::new());
+    }
+    if let Some(title) = title {
+        let info_id: ObjectId = pdf
+            .doc
+            .add_object(dictionary! { "Title" => Object::string_literal(title) });
+        pdf.doc.trailer.set("Info", info_id);
+    }
+    pdf.save()
+}
+
+/// `input` with an outline of one item per `(title, destination name,
+/// page)`, the names resolved through the `Dests` name tree.
+fn with_named_outline(input: &[u8], entries: &[(&str, &str, u32)]) -> Vec<u8> {
+    let mut doc: Document = Document::load_mem(input).unwrap();
+    let pages = doc.get_pages();
+    let outlines_id: ObjectId = doc.new_object_id();
+    let item_ids: Vec<ObjectId> = entries.iter().map(|_| doc.new_object_id()).collect();
+    let mut names: Vec<Object> = Vec::new();
+    for (index, ((title, name, page), item_id)) in entries.iter().zip(&item_ids).enumerate() {
+        let mut item = dictionary! {
+            "Title" => Object::string_literal(*title),
+            "Parent" => outlines_id,
+            "Dest" => Object::string_literal(*name),
+        };
+        if let Some(next_id) = item_ids.get(index + 1) {
+            item.set("Next", *next_id);
+        }
+        doc.objects.insert(*item_id, Object::Dictionary(item));
+        names.push(Object::string_literal(*name));
+        names.push(vec![Object::Reference(pages[page]), "Fit".into()].into());
+    }
+    doc.objects.insert(
+        outlines_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Outlines",
+            "First" => item_ids[0],
+            "Last" => *item_ids.last().unwrap(),
+            "Count" => entries.len() as i64,
+        }),
+    );
+    let catalog: &mut Dictionary = doc.catalog_mut().unwrap();
+    catalog.set("Outlines", outlines_id);
+    catalog.set(
+        "Names",
+        dictionary! { "Dests" => dictionary! { "Names" => names } },
+    );
+    let mut output: Vec<u8> = Vec::new();
+    doc.save_to(&mut output).unwrap();
+    output
+}
+
+/// The items under an outline node: their titles and the pages their
+/// explicit destinations point at.
+fn outline_children(doc: &Document, parent: &Dictionary) -> Vec<(String, u32, ObjectId)> {
+    let pages: BTreeMap<ObjectId, u32> = doc
+        .get_pages()
+        .into_iter()
+        .map(|(number, id)| (id, number))
+        .collect();
+    let first: Option<ObjectId> = parent.get(b"First").and_then(Object::as_reference).ok();
+    siblings(doc, first)
+        .into_iter()
+        .map(|id| {
+            let item: &Dictionary = doc.get_dictionary(id).unwrap();
+            let title: String = lopdf::decode_text_string(item.get(b"Title").unwrap()).unwrap();
+            let target: ObjectId = item.get(b"Dest").unwrap().as_array().unwrap()[0]
+                .as_reference()
+                .unwrap();
+            (title, pages[&target], id)
+        })
+        .collect()
+}
+
+fn outline_root(doc: &Document) -> &Dictionary {
+    let outlines: ObjectId = doc
+        .catalog()
+        .unwrap()
+        .get(b"Outlines")
+        .unwrap()
+        .as_reference()
+        .unwrap();
+    doc.get_dictionary(outlines).unwrap()
+}
+
+#[test]
+fn test_merge_with_default_options_adds_no_outline() {
+    let first: Vec<u8> = make_test_pdf(2, "A", None);
+    let second: Vec<u8> = make_test_pdf(1, "B", None);
+    let merged: Vec<u8> = merge_with_options(&[&first, &second], &MergeOptions::default()).unwrap();
+    let doc: Document = Document::load_mem(&merged).unwrap();
+    assert_eq!(doc.get_pages().len(), 3);
+    assert!(!doc.catalog().unwrap().has(b"Outlines"));
+    // The media box the inputs' page trees held is still there.
+    for page_id in doc.get_pages().into_values() {
+        assert!(inherited_media_box(&doc, page_id).is_some());
+    }
+}
+
+#[test]
+fn test_merge_embeds_shared_fonts_once() {
+    let with_font = |label: &str| -> Vec<u8> {
+        let mut doc: Document = Document::load_mem(&make_test_pdf(1, label, None)).unwrap();
+        let file_id = doc.add_object(Stream::new(dictionary! {}, b"glyphs".to_vec()));
+        let descriptor_id = doc.add_object(dictionary! {
+            "Type" => "FontDescriptor",
+            "FontName" => "Example",
+            "FontFile2" => file_id,
+        });
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "TrueType",
+            "BaseFont" => "Example",
+            "FontDescriptor" => descriptor_id,
+        });
+        let page_id: ObjectId = doc.get_pages()[&1];
+        doc.get_dictionary_mut(page_id).unwrap().set(
+            "Resources",
+            dictionary! { "Font" => dictionary! { "F1" => font_id } },
+        );
+        let mut pdf: Vec<u8> = Vec::new();
+        doc.save_to(&mut pdf).unwrap();
+        pdf
+    };
+    let first: Vec<u8> = with_font("A");
+    let second: Vec<u8> = with_font("B");
+    let merged: Vec<u8> = merge_with_options(&[&first, &second], &MergeOptions::default()).unwrap();
+    let doc: Document = Document::load_mem(&merged).unwrap();
+    let fonts: usize = doc
+        .objects
+        .values()
+        .filter(|object| object.as_dict().is_ok_and(|dict| dict.has_type(b"Font")))
+        .count();
+    assert_eq!(fonts, 1);
+    assert!(page_content(&doc, 2).contains("B 1"));
+}
+
+#[test]
+fn test_merge_bookmarks_each_input() {
+    let first: Vec<u8> = make_test_pdf(2, "A", None);
+    let second: Vec<u8> = make_test_pdf(1, "B", Some("Annual Report"));
+    let options = MergeOptions {
+        bookmarks: true,
+        names: vec!["cover".to_string(), "report".to_string()],
+        ..MergeOptions::default()
+    };
+    let merged: Vec<u8> = merge_with_options(&[&first, &second], &options).unwrap();
+    let doc: Document = Document::load_mem(&merged).unwrap();
+    let root: &Dictionary = outline_root(&doc);
+    let items: Vec<(String, u32)> = outline_children(&doc, root)
+        .into_iter()
+        .map(|(title, page, _)| (title, page))
+        .collect();
+    // The metadata title wins over the name given.
+    assert_eq!(
+        items,
+        vec![("cover".to_string(), 1), ("Annual Report".to_string(), 3)]
+    );
+    assert_eq!(root.get(b"Count").unwrap().as_i64().unwrap(), 2);
+    assert_eq!(
+        doc.catalog()
+            .unwrap()
+            .get(b"PageMode")
+            .unwrap()
+            .as_name()
+            .unwrap(),
+        b"UseOutlines"
+    );
+}
+
+#[test]
+fn test_merge_bookmarks_fall_back_to_document_number() {
+    let pdf: Vec<u8> = make_test_pdf(1, "A", None);
+    let options = MergeOptions {
+        bookmarks: true,
+        ..MergeOptions::default()
+    };
+    let merged: Vec<u8> = merge_with_options(&[&pdf], &options).unwrap();
+    let doc: Document = Document::load_mem(&merged).unwrap();
+    let items = outline_children(&doc, outline_root(&doc));
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].0, "Document 1");
+}
+
+#[test]
+fn test_merge_keeps_input_outlines_under_their_bookmark() {
+    let first: Vec<u8> = make_test_pdf(1, "A", None);
+    let second: Vec<u8> = with_named_outline(
+        &make_test_pdf(3, "B", None),
+        &[("Intro", "intro", 1), ("Results", "results", 3)],
+    );
+    let options = MergeOptions {
+        bookmarks: true,
+        names: vec!["a".to_string(), "b".to_string()],
+        ..MergeOptions::default()
+    };
+    let merged: Vec<u8> = merge_with_options(&[&first, &second], &options).unwrap();
+    let doc: Document = Document::load_mem(&merged).unwrap();
+    let root: &Dictionary = outline_root(&doc);
+    let items = outline_children(&doc, root);
+    assert_eq!(items.len(), 2);
+    let (_, _, second_id) = &items[1];
+    let second_item: &Dictionary = doc.get_dictionary(*second_id).unwrap();
+    // The named destinations now point straight at the merged pages.
+    let nested: Vec<(String, u32)> = outline_children(&doc, second_item)
+        .into_iter()
+        .map(|(title, page, _)| (title, page))
+        .collect();
+    assert_eq!(
+        nested,
+        vec![("Intro".to_string(), 2), ("Results".to_string(), 4)]
+    );
+    for (_, _, id) in outline_children(&doc, second_item) {
+        let parent: ObjectId = doc
+            .get_dictionary(id)
+            .unwrap()
+            .get(b"Parent")
+            .unwrap()
+            .as_reference()
+            .unwrap();
+        assert_eq!(parent, *second_id);
+    }
+    assert_eq!(second_item.get(b"Count").unwrap().as_i64().unwrap(), 2);
+    assert_eq!(root.get(b"Count").unwrap().as_i64().unwrap(), 4);
+}
+
+#[test]
+fn test_merge_table_of_contents() {
+    let first: Vec<u8> = make_test_pdf(2, "A", None);
+    let second: Vec<u8> = make_test_pdf(1, "B", None);
+    let options = MergeOptions {
+        names: vec!["Cover letter".to_string(), "Invoice".to_string()],
+        table_of_contents: true,
+        ..MergeOptions::default()
+    };
+    let merged: Vec<u8> = merge_with_options(&[&first, &second], &options).unwrap();
+    let doc: Document = Document::load_mem(&merged).unwrap();
+    assert_eq!(doc.get_pages().len(), 4);
+    assert!(page_content(&doc, 2).contains("(A 1) Tj"));
+
+    let contents: String = page_content(&doc, 1);
+    assert!(contents.contains("(Contents) Tj"));
+    assert!(contents.contains("(Cover letter) Tj"));
+    assert!(contents.contains("(2) Tj"), "{contents}");
+    assert!(contents.contains("(Invoice) Tj"));
+    assert!(contents.contains("(4) Tj"), "{contents}");
+
+    // Each line links to the page it names.
+    let page: &Dictionary = doc.get_dictionary(doc.get_pages()[&1]).unwrap();
+    let targets: Vec<ObjectId> = page
+        .get(b"Annots")
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|link| {
+            let dest: &[Object] = link
+                .as_dict()
+                .unwrap()
+                .get(b"Dest")
+                .unwrap()
+                .as_array()
+                .unwrap();
+            dest[0].as_reference().unwrap()
+        })
+        .collect();
+    let pages = doc.get_pages();
+    assert_eq!(targets, vec![pages[&2], pages[&4]]);
+}
+
+#[test]
+fn test_merge_long_table_of_contents_counts_its_own_pages() {
+    let pdf: Vec<u8> = make_test_pdf(1, "A", None);
+    let inputs: Vec<&[u8]> = vec![pdf.as_slice(); 60];
+    let options = MergeOptions {
+        bookmarks: true,
+        table_of_contents: true,
+        ..MergeOptions::default()
+    };
+    let merged: Vec<u8> = merge_with_options(&inputs, &options).unwrap();
+    let doc: Document = Document::load_mem(&merged).unwrap();
+    // 36 lines fit under the heading and 38 on the next page.
+    assert_eq!(doc.get_pages().len(), 62);
+    assert!(page_content(&doc, 2).contains("(Document 60) Tj"));
+    assert!(page_content(&doc, 2).contains("(62) Tj"));
+    assert!(page_content(&doc, 3).contains("(A 1) Tj"));
+
+    let items = outline_children(&doc, outline_root(&doc));
+    assert_eq!(items[0], ("Contents".to_string(), 1, items[0].2));
+    assert_eq!(items[1].1, 3);
+    assert_eq!(items[60].1, 62);
+}
+
+#[test]
+fn test_merge_skips_inputs_without_pages() {
+    let empty: Vec<u8> = make_test_pdf(0, "A", None);
+    let pdf: Vec<u8> = make_test_pdf(1, "B", None);
+    let options = MergeOptions {
+        bookmarks: true,
+        table_of_contents: true,
+        ..MergeOptions::default()
+    };
+    let merged: Vec<u8> = merge_with_options(&[&empty, &pdf], &options).unwrap();
+    let doc: Document = Document::load_mem(&merged).unwrap();
+    let items = outline_children(&doc, outline_root(&doc));
+    let titles: Vec<&str> = items.iter().map(|(title, _, _)| title.as_str()).collect();
+    assert_eq!(titles, vec!["Contents", "Document 2"]);
+    assert!(!page_content(&doc, 1).contains("(Document 1)"));
+}
+
+#[test]
+fn test_fit_text_shortens_long_titles() {
+    assert_eq!(fit_text("Short", 10.0), "Short");
+    let fitted: String = fit_text("A rather long document title", 5.0);
+    assert!(fitted.ends_with("..."));
+    assert!(text_width(&fitted) <= 5.0);
+}
+
+#[test]
+fn test_merge_with_options_rejects_bad_input() {
+    assert!(merge_with_options(&[], &MergeOptions::default()).is_err());
+    let options = MergeOptions {
+        bookmarks: true,
+        ..MergeOptions::default()
+    };
+    assert!(merge_with_options(&[b"not a pdf" as &[u8]], &options).is_err());
+}
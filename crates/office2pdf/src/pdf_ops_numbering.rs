@@ -0,0 +1,133 @@
+//! Page numbers stamped onto finished PDFs: plain numbers, "Page X of Y",
+//! or Bates numbers running across a set of documents.
+
+use super::stamp::stamp_with_labels;
+use crate::config::{Stamp, StampContent, StampPages, StampPosition};
+use crate::error::ConvertError;
+use crate::ir::Color;
+
+/// How [`stamp_page_numbers`] numbers pages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberingOptions {
+    /// What each number reads.
+    pub style: NumberingStyle,
+    /// The number of the first numbered page.
+    pub start: u64,
+    /// The pages to number. Only these count: with every page but the
+    /// first selected, the second page gets [`start`](Self::start).
+    pub pages: StampPages,
+    /// Where on the page the number sits.
+    pub position: StampPosition,
+    /// Distance from the page edges, in points.
+    pub margin: f64,
+    /// Font size in points; numbers are set in Helvetica.
+    pub font_size: f64,
+    pub color: Color,
+}
+
+/// What a page number reads.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumberingStyle {
+    /// The number alone: "7".
+    Number,
+    /// "Page 7 of 12", the total being the last page's number.
+    PageOfTotal,
+    /// A Bates number: `prefix` then the number padded with zeros to
+    /// `digits` digits, such as "ACME000123".
+    Bates { prefix: String, digits: usize },
+}
+
+impl Default for NumberingOptions {
+    fn default() -> Self {
+        Self {
+            style: NumberingStyle::Number,
+            start: 1,
+            pages: StampPages::All,
+            position: StampPosition::BottomCenter,
+            margin: 36.0,
+            font_size: 10.0,
+            color: Color::black(),
+        }
+    }
+}
+
+impl NumberingOptions {
+    /// Bates numbers `prefix` followed by `digits` digits, from 1, in the
+    /// bottom-right corner of every page.
+    pub fn bates(prefix: impl Into<String>, digits: usize) -> Self {
+        Self {
+            style: NumberingStyle::Bates {
+                prefix: prefix.into(),
+                digits,
+            },
+            position: StampPosition::BottomRight,
+            ..Self::default()
+        }
+    }
+
+    /// The label of the `index`th numbered page, counting from 0, of
+    /// `count`.
+    fn label(&self, index: u64, count: u64) -> String {
+        let number: u64 = self.start.saturating_add(index);
+        match &self.style {
+            NumberingStyle::Number => number.to_string(),
+            NumberingStyle::PageOfTotal => {
+                format!(
+                    "Page {number} of {}",
+                    self.start.saturating_add(count.saturating_sub(1))
+                )
+            }
+            NumberingStyle::Bates { prefix, digits } => format!("{prefix}{number:0digits$}"),
+        }
+    }
+}
+
+/// Longest Bates number padding accepted: more digits than any page count
+/// needs, and short enough to fit on a page.
+const MAX_BATES_DIGITS: usize = 20;
+
+/// Stamp a number onto the pages of `input` that `options` selects,
+/// counting from [`NumberingOptions::start`] in page order.
+///
+/// To number a set of documents as one, either merge them first or number
+/// each in turn, starting each where the one before it left off. The
+/// numbers are marked as artifacts, so screen readers skip them in tagged
+/// PDFs.
+pub fn stamp_page_numbers(
+    input: &[u8],
+    options: &NumberingOptions,
+) -> Result<Vec<u8>, ConvertError> {
+    if let NumberingStyle::Bates { digits, .. } = options.style
+        && digits > MAX_BATES_DIGITS
+    {
+        return Err(ConvertError::InvalidOptions(format!(
+            "Bates numbers take at most {MAX_BATES_DIGITS} digits, got {digits}"
+        )));
+    }
+    let stamp = Stamp {
+        content: StampContent::Text {
+            text: String::new(),
+            font_size: options.font_size,
+            color: options.color,
+        },
+        position: options.position,
+        margin: options.margin,
+        rotation: 0.0,
+        opacity: 1.0,
+        pages: options.pages.clone(),
+    };
+    let page_count: u32 = super::page_count(input)?;
+    // Each selected page's place among them.
+    let numbered: Vec<u32> = (1..=page_count)
+        .filter(|&number| options.pages.contains(number))
+        .collect();
+    let count: u64 = numbered.len() as u64;
+    stamp_with_labels(input, &stamp, |number, _| {
+        let index: usize = numbered.partition_point(|&selected| selected < number);
+        options.label(index as u64, count)
+    })
+}
+
+#[cfg(test)]
+#[path = "pdf_ops_numbering_tests.rs"]
+mod tests;
@@ -0,0 +1,90 @@
+use super::*;
+use crate::pdf_ops::test_support::{TestPdf, labelled_page, page_content};
+use lopdf::{Document, dictionary};
+
+/// A PDF of `num_pages` A4 pages, each with its own resources.
+fn make_test_pdf(num_pages: u32) -> Vec<u8> {
+    let mut pdf = TestPdf::new();
+    for number in 1..=num_pages {
+        pdf.add_page(
+            &labelled_page("Page", number),
+            dictionary! { "Resources" => dictionary! {} },
+        );
+    }
+    pdf.save()
+}
+
+/// The decoded content streams of each page, concatenated per page.
+fn page_contents(pdf: &[u8]) -> Vec<String> {
+    let doc: Document = Document::load_mem(pdf).unwrap();
+    (1..=doc.get_pages().len() as u32)
+        .map(|number| page_content(&doc, number))
+        .collect()
+}
+
+#[test]
+fn test_stamp_page_numbers_plain() {
+    let numbered: Vec<u8> =
+        stamp_page_numbers(&make_test_pdf(3), &NumberingOptions::default()).unwrap();
+    let contents: Vec<String> = page_contents(&numbered);
+    for (i, content) in contents.iter().enumerate() {
+        assert!(content.contains(&format!("({}) Tj", i + 1)), "{content}");
+        assert!(content.contains("/Artifact"), "{content}");
+    }
+}
+
+#[test]
+fn test_stamp_page_numbers_page_of_total() {
+    let options = NumberingOptions {
+        style: NumberingStyle::PageOfTotal,
+        start: 3,
+        ..NumberingOptions::default()
+    };
+    let contents: Vec<String> =
+        page_contents(&stamp_page_numbers(&make_test_pdf(2), &options).unwrap());
+    assert!(contents[0].contains("(Page 3 of 4) Tj"), "{}", contents[0]);
+    assert!(contents[1].contains("(Page 4 of 4) Tj"), "{}", contents[1]);
+}
+
+#[test]
+fn test_stamp_page_numbers_bates_continues_a_set() {
+    // The second document of a set picks up where the first left off.
+    let options = NumberingOptions {
+        start: 5,
+        ..NumberingOptions::bates("ACME", 6)
+    };
+    let contents: Vec<String> =
+        page_contents(&stamp_page_numbers(&make_test_pdf(2), &options).unwrap());
+    assert!(contents[0].contains("(ACME000005) Tj"), "{}", contents[0]);
+    assert!(contents[1].contains("(ACME000006) Tj"), "{}", contents[1]);
+}
+
+#[test]
+fn test_stamp_page_numbers_counts_only_selected_pages() {
+    let options = NumberingOptions {
+        pages: StampPages::Ranges(vec![(2, 3)]),
+        ..NumberingOptions::default()
+    };
+    let contents: Vec<String> =
+        page_contents(&stamp_page_numbers(&make_test_pdf(3), &options).unwrap());
+    assert!(!contents[0].contains("/Artifact"), "{}", contents[0]);
+    assert!(contents[1].contains("(1) Tj"), "{}", contents[1]);
+    assert!(contents[2].contains("(2) Tj"), "{}", contents[2]);
+}
+
+#[test]
+fn test_stamp_page_numbers_rejects_bad_options() {
+    let pdf: Vec<u8> = make_test_pdf(1);
+    let err: ConvertError =
+        stamp_page_numbers(&pdf, &NumberingOptions::bates("X", MAX_BATES_DIGITS + 1)).unwrap_err();
+    assert!(matches!(err, ConvertError::InvalidOptions(_)), "{err:?}");
+
+    let options = NumberingOptions {
+        font_size: 0.0,
+        ..NumberingOptions::default()
+    };
+    let err: ConvertError = stamp_page_numbers(&pdf, &options).unwrap_err();
+    assert!(matches!(err, ConvertError::InvalidOptions(_)), "{err:?}");
+
+    assert!(stamp_page_numbers(b"not a pdf", &NumberingOptions::default()).is_err());
+}
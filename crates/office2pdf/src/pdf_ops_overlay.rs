@@ -0,0 +1,192 @@
+//! Overlays: the pages of one PDF drawn over, or under, the pages of
+//! another, such as a letterhead under converted letters or an approval
+//! page over a slide deck.
+
+use std::collections::BTreeSet;
+
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream, dictionary};
+
+use super::{
+    inherited_attribute, inherited_media_box, inherited_resources, load_pdf_document,
+    page_contents, rectangle, referenced_objects,
+};
+use crate::config::{StampPages, StampPosition};
+use crate::error::ConvertError;
+
+/// How [`overlay`] places the overlay's pages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverlayOptions {
+    /// The pages of the base PDF to draw on.
+    pub pages: StampPages,
+    /// Where on the page the overlay sits; corners are flush with the page
+    /// edges.
+    pub position: StampPosition,
+    /// Size of the overlay relative to its own page size.
+    pub scale: f64,
+    /// Draw the overlay under the page content, as a letterhead, rather
+    /// than over it.
+    pub behind: bool,
+}
+
+impl Default for OverlayOptions {
+    fn default() -> Self {
+        Self {
+            pages: StampPages::All,
+            position: StampPosition::Center,
+            scale: 1.0,
+            behind: false,
+        }
+    }
+}
+
+/// Draw the pages of `overlay` onto the pages of `base`: base page `n`
+/// gets overlay page `n`, and once the overlay runs out of pages its last
+/// page repeats, so a one-page letterhead goes on every page and a
+/// two-page one puts its first page on the first page only.
+///
+/// The overlay is marked as an artifact, so screen readers skip it in
+/// tagged PDFs.
+pub fn overlay(
+    base: &[u8],
+    overlay: &[u8],
+    options: &OverlayOptions,
+) -> Result<Vec<u8>, ConvertError> {
+    if !(options.scale.is_finite() && options.scale > 0.0) {
+        return Err(ConvertError::InvalidOptions(format!(
+            "the overlay scale must be a positive number, got {}",
+            options.scale
+        )));
+    }
+    let mut doc: Document = load_pdf_document(base, "")?;
+    let mut layer: Document = load_pdf_document(overlay, " to overlay")?;
+    if layer.get_pages().is_empty() {
+        return Err(ConvertError::Parse(
+            "the overlay PDF has no pages".to_string(),
+        ));
+    }
+    layer.renumber_objects_with(doc.max_id + 1);
+    doc.max_id = layer.max_id;
+
+    // One form XObject per overlay page, with the box it draws in.
+    let mut forms: Vec<(ObjectId, [f64; 4])> = Vec::new();
+    for page_id in layer.get_pages().into_values() {
+        let form_box: [f64; 4] = inherited_attribute(&layer, page_id, b"CropBox")
+            .or_else(|| inherited_media_box(&layer, page_id))
+            .as_ref()
+            .and_then(rectangle)
+            .ok_or_else(|| failed("an overlay page has no media box"))?;
+        let content: Vec<u8> = layer
+            .get_page_content(page_id)
+            .map_err(|e| failed(&format!("cannot read an overlay page: {e}")))?;
+        let mut form = Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Form",
+                "BBox" => form_box.iter().map(|&n| n.into()).collect::<Vec<Object>>(),
+                "Resources" => inherited_resources(&layer, page_id),
+            },
+            content,
+        );
+        // Left uncompressed when compression does not pay off.
+        let _ = form.compress();
+        let form_id: ObjectId = doc.new_object_id();
+        doc.objects.insert(form_id, Object::Stream(form));
+        forms.push((form_id, form_box));
+    }
+    // What the forms use; the overlay's page tree is dropped below.
+    doc.objects.extend(layer.objects);
+
+    let pages: Vec<(u32, ObjectId)> = doc.get_pages().into_iter().collect();
+    let save_id: ObjectId = doc.add_object(Stream::new(Dictionary::new(), b"q".to_vec()));
+    for (number, page_id) in pages {
+        if !options.pages.contains(number) {
+            continue;
+        }
+        let index: usize = (number as usize - 1).min(forms.len() - 1);
+        let (form_id, [form_left, form_bottom, form_right, form_top]) = forms[index];
+        let name: String = format!("O2POverlay{}", index + 1);
+        let page_box: [f64; 4] = inherited_media_box(&doc, page_id)
+            .as_ref()
+            .and_then(rectangle)
+            .ok_or_else(|| failed("a page has no media box"))?;
+        let [left, bottom, right, top] = page_box;
+        let scale: f64 = options.scale;
+        let (width, height): (f64, f64) = (
+            (form_right - form_left) * scale,
+            (form_top - form_bottom) * scale,
+        );
+        let x: f64 = match options.position {
+            StampPosition::TopLeft | StampPosition::BottomLeft => left,
+            StampPosition::Center | StampPosition::TopCenter | StampPosition::BottomCenter => {
+                (left + right - width) / 2.0
+            }
+            StampPosition::TopRight | StampPosition::BottomRight => right - width,
+        };
+        let y: f64 = match options.position {
+            StampPosition::TopLeft | StampPosition::TopCenter | StampPosition::TopRight => {
+                top - height
+            }
+            StampPosition::Center => (bottom + top - height) / 2.0,
+            StampPosition::BottomLeft
+            | StampPosition::BottomCenter
+            | StampPosition::BottomRight => bottom,
+        };
+        let drawing: String = format!(
+            "/Artifact BMC q {scale:.5} 0 0 {scale:.5} {:.3} {:.3} cm /{name} Do Q EMC",
+            x - form_left * scale,
+            y - form_bottom * scale,
+        );
+
+        let mut resources: Dictionary = inherited_resources(&doc, page_id);
+        let mut xobjects: Dictionary = resources
+            .get(b"XObject")
+            .ok()
+            .and_then(|entries| doc.dereference(entries).ok())
+            .and_then(|(_, entries)| entries.as_dict().ok())
+            .cloned()
+            .unwrap_or_default();
+        xobjects.set(name.as_str(), form_id);
+        resources.set("XObject", xobjects);
+        let contents: Vec<Object> = page_contents(&doc, page_id);
+        let mut layered: Vec<Object> = Vec::with_capacity(contents.len() + 2);
+        if options.behind {
+            let drawing_id: ObjectId = doc.add_object(Stream::new(
+                Dictionary::new(),
+                format!("{drawing}\n").into_bytes(),
+            ));
+            layered.push(drawing_id.into());
+            layered.extend(contents);
+        } else {
+            // Whatever graphics state the page content leaves behind is
+            // restored before the overlay: its content is wrapped in `q`
+            // and `Q`.
+            let drawing_id: ObjectId = doc.add_object(Stream::new(
+                Dictionary::new(),
+                format!("Q\n{drawing}").into_bytes(),
+            ));
+            layered.push(save_id.into());
+            layered.extend(contents);
+            layered.push(drawing_id.into());
+        }
+        let page: &mut Dictionary = doc
+            .get_dictionary_mut(page_id)
+            .map_err(|_| failed("a page is not a dictionary"))?;
+        page.set("Contents", layered);
+        page.set("Resources", resources);
+    }
+
+    let referenced: BTreeSet<ObjectId> = referenced_objects(&doc).into_iter().collect();
+    doc.objects.retain(|id, _| referenced.contains(id));
+    let mut output: Vec<u8> = Vec::new();
+    doc.save_to(&mut output)
+        .map_err(|e| failed(&format!("cannot write PDF: {e}")))?;
+    Ok(output)
+}
+
+fn failed(what: &str) -> ConvertError {
+    ConvertError::Render(format!("cannot overlay PDF: {what}"))
+}
+
+#[cfg(test)]
+#[path = "pdf_ops_overlay_tests.rs"]
+mod tests;
@@ -0,0 +1,139 @@
+use super::*;
+use crate::pdf_ops::test_support::{TestPdf, page_content};
+
+/// A PDF of `num_pages` pages of `width` by `height` points, each showing
+/// `label` and its page number.
+fn make_test_pdf(num_pages: u32, label: &str, width: i64, height: i64) -> Vec<u8> {
+    let mut pdf = TestPdf::new();
+    let font_id: ObjectId = pdf.add_font("Courier");
+    pdf.tree_entry(
+        "MediaBox",
+        vec![0.into(), 0.into(), width.into(), height.into()],
+    )
+    .tree_entry(
+        "Resources",
+        dictionary! { "Font" => dictionary! { "F1" => font_id } },
+    );
+    for number in 1..=num_pages {
+        pdf.add_page(
+            &format!("BT /F1 12 Tf 10 10 Td ({label} {number}) Tj ET"),
+            Dictionary::new(),
+        );
+    }
+    pdf.save()
+}
+
+/// The form XObject page `number` draws under `name`.
+fn page_form<'a>(doc: &'a Document, number: u32, name: &str) -> &'a Stream {
+    let page: &Dictionary = doc.get_dictionary(doc.get_pages()[&number]).unwrap();
+    let form_id: ObjectId = page
+        .get(b"Resources")
+        .and_then(Object::as_dict)
+        .and_then(|resources| resources.get(b"XObject"))
+        .and_then(Object::as_dict)
+        .and_then(|xobjects| xobjects.get(name.as_bytes()))
+        .and_then(Object::as_reference)
+        .unwrap();
+    doc.get_object(form_id).unwrap().as_stream().unwrap()
+}
+
+#[test]
+fn test_overlay_draws_over_page_content() {
+    let base: Vec<u8> = make_test_pdf(1, "Body", 595, 842);
+    let approval: Vec<u8> = make_test_pdf(1, "Approved", 595, 842);
+    let output: Vec<u8> = overlay(&base, &approval, &OverlayOptions::default()).unwrap();
+    let doc: Document = Document::load_mem(&output).unwrap();
+
+    let content: String = page_content(&doc, 1);
+    let body: usize = content.find("(Body 1) Tj").unwrap();
+    let drawn: usize = content.find("/O2POverlay1 Do").unwrap();
+    assert!(body < drawn, "the overlay must be drawn over the page");
+    assert!(content.starts_with('q'), "page content must be isolated");
+    assert!(content.contains("/Artifact BMC"));
+
+    let form: &Stream = page_form(&doc, 1, "O2POverlay1");
+    let drawing: Vec<u8> = form.decompressed_content().unwrap_or(form.content.clone());
+    assert!(String::from_utf8_lossy(&drawing).contains("(Approved 1) Tj"));
+    let fonts: &Dictionary = form
+        .dict
+        .get(b"Resources")
+        .and_then(Object::as_dict)
+        .and_then(|resources| resources.get(b"Font"))
+        .and_then(Object::as_dict)
+        .unwrap();
+    assert!(fonts.has(b"F1"), "the overlay keeps its own resources");
+    assert_eq!(doc.get_pages().len(), 1, "no overlay page is added");
+}
+
+#[test]
+fn test_overlay_behind_draws_under_page_content() {
+    let base: Vec<u8> = make_test_pdf(1, "Letter", 595, 842);
+    let letterhead: Vec<u8> = make_test_pdf(1, "Letterhead", 595, 842);
+    let options = OverlayOptions {
+        behind: true,
+        ..OverlayOptions::default()
+    };
+    let output: Vec<u8> = overlay(&base, &letterhead, &options).unwrap();
+    let doc: Document = Document::load_mem(&output).unwrap();
+    let content: String = page_content(&doc, 1);
+    let drawn: usize = content.find("/O2POverlay1 Do").unwrap();
+    let letter: usize = content.find("(Letter 1) Tj").unwrap();
+    assert!(drawn < letter, "the overlay must be drawn under the page");
+}
+
+#[test]
+fn test_overlay_repeats_its_last_page() {
+    let base: Vec<u8> = make_test_pdf(3, "Body", 595, 842);
+    let letterhead: Vec<u8> = make_test_pdf(2, "Letterhead", 595, 842);
+    let output: Vec<u8> = overlay(&base, &letterhead, &OverlayOptions::default()).unwrap();
+    let doc: Document = Document::load_mem(&output).unwrap();
+    assert!(page_content(&doc, 1).contains("/O2POverlay1 Do"));
+    assert!(page_content(&doc, 2).contains("/O2POverlay2 Do"));
+    assert!(page_content(&doc, 3).contains("/O2POverlay2 Do"));
+}
+
+#[test]
+fn test_overlay_position_and_scale() {
+    let base: Vec<u8> = make_test_pdf(1, "Body", 600, 800);
+    let badge: Vec<u8> = make_test_pdf(1, "Badge", 200, 100);
+    let options = OverlayOptions {
+        position: StampPosition::TopRight,
+        scale: 0.5,
+        ..OverlayOptions::default()
+    };
+    let output: Vec<u8> = overlay(&base, &badge, &options).unwrap();
+    let doc: Document = Document::load_mem(&output).unwrap();
+    // 100 by 50 points, flush with the top-right corner.
+    assert!(
+        page_content(&doc, 1).contains("0.50000 0 0 0.50000 500.000 750.000 cm"),
+        "{}",
+        page_content(&doc, 1)
+    );
+}
+
+#[test]
+fn test_overlay_only_selected_pages() {
+    let base: Vec<u8> = make_test_pdf(3, "Body", 595, 842);
+    let stamp: Vec<u8> = make_test_pdf(1, "Stamp", 595, 842);
+    let options = OverlayOptions {
+        pages: StampPages::First,
+        ..OverlayOptions::default()
+    };
+    let output: Vec<u8> = overlay(&base, &stamp, &options).unwrap();
+    let doc: Document = Document::load_mem(&output).unwrap();
+    assert!(page_content(&doc, 1).contains("Do"));
+    assert!(!page_content(&doc, 2).contains("Do"));
+}
+
+#[test]
+fn test_overlay_rejects_bad_input() {
+    let pdf: Vec<u8> = make_test_pdf(1, "Body", 595, 842);
+    let options = OverlayOptions {
+        scale: 0.0,
+        ..OverlayOptions::default()
+    };
+    let err: ConvertError = overlay(&pdf, &pdf, &options).unwrap_err();
+    assert!(matches!(err, ConvertError::InvalidOptions(_)), "{err:?}");
+    assert!(overlay(&pdf, b"not a pdf", &OverlayOptions::default()).is_err());
+    assert!(overlay(b"not a pdf", &pdf, &OverlayOptions::default()).is_err());
+}
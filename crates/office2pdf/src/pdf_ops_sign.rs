@@ -0,0 +1,616 @@
+//! Digital signatures: [`sign`] adds a PAdES baseline B-B signature, a
+//! detached CAdES `SignedData` over every byte of the file but the
+//! signature's own `Contents`. The CMS structure is small and fixed, so it
+//! is written with a minimal DER encoder instead of an ASN.1 framework.
+
+use lopdf::{
+    Dictionary, Document, IncrementalDocument, Object, ObjectId, Stream, StringFormat, dictionary,
+};
+use rsa::pkcs1::{DecodeRsaPrivateKey, EncodeRsaPublicKey};
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::traits::PublicKeyParts;
+use rsa::{Pkcs1v15Sign, RsaPrivateKey, RsaPublicKey};
+use sha2::{Digest, Sha256};
+use typst::foundations::Datetime;
+
+use super::{load_pdf_document, pdf_text_string, win_ansi_literal};
+use crate::config::{SignatureAppearance, SigningConfig, SigningKey};
+use crate::error::ConvertError;
+
+const INTEGER: u8 = 0x02;
+const BIT_STRING: u8 = 0x03;
+const OCTET_STRING: u8 = 0x04;
+const NULL: u8 = 0x05;
+const OID: u8 = 0x06;
+const BMP_STRING: u8 = 0x1E;
+const SEQUENCE: u8 = 0x30;
+const SET: u8 = 0x31;
+const CONTEXT_0: u8 = 0xA0;
+
+/// 1.2.840.113549.1.7.1
+const OID_DATA: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x01];
+/// 1.2.840.113549.1.7.2
+const OID_SIGNED_DATA: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x02];
+/// 1.2.840.113549.1.9.3
+const OID_CONTENT_TYPE: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x03];
+/// 1.2.840.113549.1.9.4
+const OID_MESSAGE_DIGEST: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x04];
+/// 1.2.840.113549.1.9.16.2.47
+const OID_SIGNING_CERTIFICATE_V2: &[u8] = &[
+    0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x10, 0x02, 0x2F,
+];
+/// 1.2.840.113549.1.1.1
+const OID_RSA_ENCRYPTION: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x01];
+/// 2.16.840.1.101.3.4.2.1
+const OID_SHA256: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+/// 2.5.4.3
+const OID_COMMON_NAME: &[u8] = &[0x55, 0x04, 0x03];
+
+/// `ByteRange` written before the signed ranges are known: numbers wide
+/// enough for any offset below 4 GiB, overwritten in place afterwards.
+const BYTE_RANGE_PLACEHOLDER: [i64; 4] = [0, 4_294_967_295, 4_294_967_295, 4_294_967_295];
+
+/// Room in `Contents` for the CMS structure around the certificates and
+/// the signature value.
+const CMS_OVERHEAD: usize = 1024;
+
+/// Print (4) and locked (128): the signature field is printed with the
+/// page and cannot be moved or deleted.
+const SIGNATURE_FIELD_FLAGS: i64 = 132;
+
+/// Sign `input` with the key of `config`, as a PAdES baseline B-B
+/// signature: a detached CAdES signature (RSA PKCS#1 v1.5 with SHA-256)
+/// carrying the signer's certificate chain, invisible or drawn where
+/// `config.appearance` says. The signing time is the current time.
+/// `input` is kept byte for byte with the signature appended as an
+/// incremental update, so signatures already on it stay valid.
+///
+/// Requires the `sign` feature.
+///
+/// # Errors
+///
+/// Returns [`ConvertError::InvalidOptions`] if the key cannot be read, is
+/// not an RSA key, does not match its certificate, or the appearance is on
+/// a page the PDF does not have, and [`ConvertError::Parse`] if `input` is
+/// not a PDF.
+pub fn sign(input: &[u8], config: &SigningConfig) -> Result<Vec<u8>, ConvertError> {
+    sign_at(input, config, &crate::render::pdf::current_utc_datetime()?)
+}
+
+/// [`sign`] with the signing time `now`, which deterministic output pins.
+pub(crate) fn sign_at(
+    input: &[u8],
+    config: &SigningConfig,
+    now: &Datetime,
+) -> Result<Vec<u8>, ConvertError> {
+    let signer: Signer = Signer::load(&config.key)?;
+    let certificate: Certificate = parse_certificate(&signer.certificates[0])
+        .ok_or_else(|| bad_key("the signer's certificate is not valid DER"))?;
+    let original: Document = load_pdf_document(input, "")?;
+    if original.is_encrypted() {
+        return Err(failed("encrypted PDFs cannot be signed"));
+    }
+    // Changed on a copy and appended as an incremental update: rewriting
+    // the file would break any signature already on it.
+    let mut doc: Document = original.clone();
+
+    let pages = doc.get_pages();
+    let page_number: u32 = config.appearance.map_or(1, |appearance| appearance.page);
+    let page_id: ObjectId = *pages.get(&page_number).ok_or_else(|| {
+        ConvertError::InvalidOptions(format!(
+            "cannot sign PDF: the signature appearance is on page {page_number}, \
+             but the PDF has {} pages",
+            pages.len()
+        ))
+    })?;
+
+    let (year, month, day, hour, minute, second) = datetime_parts(now);
+    let signer_name: Option<String> = common_name(certificate.subject);
+    let capacity: usize =
+        CMS_OVERHEAD + signer.certificates.iter().map(Vec::len).sum::<usize>() + signer.key.size();
+    let mut signature: Dictionary = dictionary! {
+        "Type" => "Sig",
+        "Filter" => "Adobe.PPKLite",
+        "SubFilter" => "ETSI.CAdES.detached",
+        "ByteRange" => BYTE_RANGE_PLACEHOLDER.map(Object::Integer).to_vec(),
+        "Contents" => Object::String(vec![0; capacity], StringFormat::Hexadecimal),
+        "M" => Object::string_literal(format!(
+            "D:{year:04}{month:02}{day:02}{hour:02}{minute:02}{second:02}Z"
+        )),
+    };
+    let details: [(&str, &Option<String>); 4] = [
+        ("Name", &signer_name),
+        ("Reason", &config.reason),
+        ("Location", &config.location),
+        ("ContactInfo", &config.contact_info),
+    ];
+    for (key, value) in details {
+        if let Some(value) = value {
+            signature.set(key, pdf_text_string(value));
+        }
+    }
+    let signature_id: ObjectId = doc.add_object(signature);
+
+    let rect: [f64; 4] = config.appearance.map_or([0.0; 4], |appearance| {
+        [
+            appearance.x,
+            appearance.y,
+            appearance.x + appearance.width,
+            appearance.y + appearance.height,
+        ]
+    });
+    let mut field: Dictionary = dictionary! {
+        "Type" => "Annot",
+        "Subtype" => "Widget",
+        "FT" => "Sig",
+        "T" => Object::string_literal(format!("Signature{}", form_field_count(&doc) + 1)),
+        "V" => signature_id,
+        "F" => SIGNATURE_FIELD_FLAGS,
+        "Rect" => rect.map(Object::from).to_vec(),
+        "P" => page_id,
+    };
+    if let Some(appearance) = &config.appearance {
+        let mut lines: Vec<String> = vec![match &signer_name {
+            Some(name) => format!("Digitally signed by {name}"),
+            None => "Digitally signed".to_string(),
+        }];
+        lines.push(format!(
+            "Date: {year:04}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02} UTC"
+        ));
+        if let Some(reason) = &config.reason {
+            lines.push(format!("Reason: {reason}"));
+        }
+        if let Some(location) = &config.location {
+            lines.push(format!("Location: {location}"));
+        }
+        let stream_id: ObjectId = doc.add_object(appearance_stream(appearance, &lines));
+        field.set("AP", dictionary! { "N" => stream_id });
+    }
+    let field_id: ObjectId = doc.add_object(field);
+    add_annotation(&mut doc, page_id, field_id)?;
+    add_form_field(&mut doc, field_id)?;
+
+    let mut update: IncrementalDocument =
+        IncrementalDocument::create_from(input.to_vec(), original);
+    update.new_document.max_id = doc.max_id;
+    for (id, object) in doc.objects {
+        if update.get_prev_documents().objects.get(&id) != Some(&object) {
+            update.new_document.set_object(id, object);
+        }
+    }
+    // Inherited from the previous trailer but wrong for this one: the
+    // update's cross-reference stream is written unfiltered, and a hybrid
+    // file's XRefStm points at the previous section's stream.
+    update.new_document.trailer.remove(b"DecodeParms");
+    update.new_document.trailer.remove(b"XRefStm");
+    // Saved as is: the signed bytes are the bytes written here.
+    let mut output: Vec<u8> = Vec::new();
+    update
+        .save_to(&mut output)
+        .map_err(|e| ConvertError::Render(format!("failed to write signed PDF: {e}")))?;
+
+    let contents_placeholder: Vec<u8> = [b"<".as_slice(), &vec![b'0'; capacity * 2], b">"].concat();
+    let contents_start: usize = find(&output[input.len()..], &contents_placeholder)
+        .ok_or_else(|| failed("the signature contents were not written as expected"))?
+        + input.len();
+    let contents_end: usize = contents_start + contents_placeholder.len();
+    let range_placeholder: String = format!(
+        "[{} {} {} {}]",
+        BYTE_RANGE_PLACEHOLDER[0],
+        BYTE_RANGE_PLACEHOLDER[1],
+        BYTE_RANGE_PLACEHOLDER[2],
+        BYTE_RANGE_PLACEHOLDER[3]
+    );
+    let range_start: usize = find(&output[input.len()..], range_placeholder.as_bytes())
+        .ok_or_else(|| failed("the signature byte range was not written as expected"))?
+        + input.len();
+    let byte_range: String = format!(
+        "[0 {contents_start} {contents_end} {}",
+        output.len() - contents_end
+    );
+    // Padded inside the brackets so no offset after it moves.
+    let byte_range: String = format!("{byte_range:<width$}]", width = range_placeholder.len() - 1);
+    output[range_start..range_start + byte_range.len()].copy_from_slice(byte_range.as_bytes());
+
+    let mut hasher = Sha256::new();
+    hasher.update(&output[..contents_start]);
+    hasher.update(&output[contents_end..]);
+    let cms: Vec<u8> = signed_data(&signer, &certificate, &hasher.finalize())?;
+    if cms.len() > capacity {
+        return Err(failed(
+            "the signature does not fit the space reserved for it",
+        ));
+    }
+    let hex: String = cms.iter().map(|byte| format!("{byte:02X}")).collect();
+    output[contents_start + 1..contents_start + 1 + hex.len()].copy_from_slice(hex.as_bytes());
+    Ok(output)
+}
+
+/// The signer's key and certificate chain, signer first.
+struct Signer {
+    key: RsaPrivateKey,
+    certificates: Vec<Vec<u8>>,
+}
+
+impl Signer {
+    fn load(key: &SigningKey) -> Result<Self, ConvertError> {
+        let (private_key, certificates): (Vec<u8>, Vec<Vec<u8>>) = match key {
+            SigningKey::Pkcs12 { data, password } => {
+                let store = p12_keystore::KeyStore::from_pkcs12(data, password)
+                    .map_err(|e| bad_key(&format!("cannot read the PKCS#12 file: {e}")))?;
+                let (_, chain) = store.private_key_chain().ok_or_else(|| {
+                    bad_key("the PKCS#12 file holds no private key with a certificate")
+                })?;
+                (
+                    chain.key().to_vec(),
+                    chain
+                        .chain()
+                        .iter()
+                        .map(|certificate| certificate.as_der().to_vec())
+                        .collect(),
+                )
+            }
+            SigningKey::Der {
+                private_key,
+                certificates,
+            } => (private_key.clone(), certificates.clone()),
+        };
+        let key: RsaPrivateKey = RsaPrivateKey::from_pkcs8_der(&private_key)
+            .or_else(|_| RsaPrivateKey::from_pkcs1_der(&private_key))
+            .map_err(|_| bad_key("the private key is not an RSA key in PKCS#8 or PKCS#1 DER"))?;
+        let signer_certificate: &[u8] = certificates
+            .first()
+            .ok_or_else(|| bad_key("no certificate was given for the private key"))?;
+        // A signature made with another key than the certificate's would
+        // only fail once someone tries to validate it.
+        let public_key = RsaPublicKey::from(&key)
+            .to_pkcs1_der()
+            .map_err(|_| bad_key("cannot encode the public key"))?;
+        let certified_key: Option<&[u8]> =
+            parse_certificate(signer_certificate).map(|certificate| certificate.public_key);
+        if certified_key != Some(public_key.as_bytes()) {
+            return Err(bad_key(
+                "the private key does not belong to the first certificate",
+            ));
+        }
+        Ok(Self { key, certificates })
+    }
+}
+
+/// The parts of an X.509 certificate a signature refers to, each as its
+/// DER encoding except `public_key`, the `RSAPublicKey` structure.
+struct Certificate<'a> {
+    issuer: &'a [u8],
+    serial: &'a [u8],
+    subject: &'a [u8],
+    public_key: &'a [u8],
+}
+
+fn parse_certificate(der: &[u8]) -> Option<Certificate<'_>> {
+    let (certificate, _) = read_der(der)?;
+    let (tbs, _) = read_der(certificate.content)?;
+    let mut fields = DerElements(tbs.content);
+    let mut serial: DerElement = fields.next()?;
+    // The version is an optional explicit [0] before the serial number.
+    if serial.tag == CONTEXT_0 {
+        serial = fields.next()?;
+    }
+    let _signature_algorithm: DerElement = fields.next()?;
+    let issuer: DerElement = fields.next()?;
+    let _validity: DerElement = fields.next()?;
+    let subject: DerElement = fields.next()?;
+    let public_key_info: DerElement = fields.next()?;
+    let mut public_key_fields = DerElements(public_key_info.content);
+    let _algorithm: DerElement = public_key_fields.next()?;
+    let public_key: DerElement = public_key_fields.next()?;
+    if serial.tag != INTEGER || public_key.tag != BIT_STRING {
+        return None;
+    }
+    Some(Certificate {
+        issuer: issuer.encoded,
+        serial: serial.encoded,
+        subject: subject.encoded,
+        // Skip the count of unused bits, always 0 for a DER structure.
+        public_key: public_key.content.get(1..)?,
+    })
+}
+
+/// The common name (CN) in a DER-encoded X.509 name.
+fn common_name(name: &[u8]) -> Option<String> {
+    let (name, _) = read_der(name)?;
+    for relative_name in DerElements(name.content) {
+        for attribute in DerElements(relative_name.content) {
+            let mut parts = DerElements(attribute.content);
+            let (Some(oid), Some(value)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            if oid.content != OID_COMMON_NAME {
+                continue;
+            }
+            if value.tag == BMP_STRING {
+                let units: Vec<u16> = value
+                    .content
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect();
+                return Some(String::from_utf16_lossy(&units));
+            }
+            return Some(String::from_utf8_lossy(value.content).into_owned());
+        }
+    }
+    None
+}
+
+/// A detached CMS `SignedData` for a file whose signed bytes hash to
+/// `digest`, wrapped in its `ContentInfo`.
+fn signed_data(
+    signer: &Signer,
+    certificate: &Certificate,
+    digest: &[u8],
+) -> Result<Vec<u8>, ConvertError> {
+    let sha256: Vec<u8> = der_sequence(&[&der(OID, OID_SHA256)]);
+    let certificate_hash = Sha256::digest(&signer.certificates[0]);
+    let mut attributes: Vec<Vec<u8>> = vec![
+        attribute(OID_CONTENT_TYPE, &der(OID, OID_DATA)),
+        attribute(OID_MESSAGE_DIGEST, &der(OCTET_STRING, digest)),
+        // PAdES binds the signer's certificate into what is signed. The
+        // hash algorithm is left out as the default, SHA-256.
+        attribute(
+            OID_SIGNING_CERTIFICATE_V2,
+            &der_sequence(&[&der_sequence(&[&der_sequence(&[&der(
+                OCTET_STRING,
+                &certificate_hash,
+            )])])]),
+        ),
+    ];
+    // DER orders the members of a SET OF by their encoding.
+    attributes.sort();
+    let attributes: Vec<u8> = attributes.concat();
+    let signature: Vec<u8> = signer
+        .key
+        .sign(
+            Pkcs1v15Sign::new::<Sha256>(),
+            &Sha256::digest(der(SET, &attributes)),
+        )
+        .map_err(|e| failed(&format!("RSA signing failed: {e}")))?;
+
+    let signer_info: Vec<u8> = der_sequence(&[
+        &der(INTEGER, &[1]),
+        &der_sequence(&[certificate.issuer, certificate.serial]),
+        &sha256,
+        &der(CONTEXT_0, &attributes),
+        &der_sequence(&[&der(OID, OID_RSA_ENCRYPTION), &der(NULL, &[])]),
+        &der(OCTET_STRING, &signature),
+    ]);
+    let signed_data: Vec<u8> = der_sequence(&[
+        &der(INTEGER, &[1]),
+        &der(SET, &sha256),
+        &der_sequence(&[&der(OID, OID_DATA)]),
+        &der(CONTEXT_0, &signer.certificates.concat()),
+        &der(SET, &signer_info),
+    ]);
+    Ok(der_sequence(&[
+        &der(OID, OID_SIGNED_DATA),
+        &der(CONTEXT_0, &signed_data),
+    ]))
+}
+
+/// A CMS attribute with a single value.
+fn attribute(oid: &[u8], value: &[u8]) -> Vec<u8> {
+    der_sequence(&[&der(OID, oid), &der(SET, value)])
+}
+
+/// The visible signature: a thin frame around `lines` of Helvetica.
+fn appearance_stream(appearance: &SignatureAppearance, lines: &[String]) -> Stream {
+    let (width, height) = (appearance.width, appearance.height);
+    let font_size: f64 = (height / (lines.len() as f64 + 1.0)).min(10.0);
+    let leading: f64 = font_size * 1.2;
+    let mut content: Vec<u8> = format!(
+        "q 0.4 G 0.75 w 0.375 0.375 {:.3} {:.3} re S Q\nBT /Helv {font_size:.3} Tf 0 g {leading:.3} TL 4 {:.3} Td\n",
+        width - 0.75,
+        height - 0.75,
+        height - 4.0 - font_size,
+    )
+    .into_bytes();
+    for (index, line) in lines.iter().enumerate() {
+        if index > 0 {
+            content.extend_from_slice(b"T* ");
+        }
+        content.push(b'(');
+        content.extend(win_ansi_literal(line));
+        content.extend_from_slice(b") Tj\n");
+    }
+    content.extend_from_slice(b"ET");
+
+    let resources: Dictionary = dictionary! {
+        "Font" => dictionary! {
+            "Helv" => dictionary! {
+                "Type" => "Font",
+                "Subtype" => "Type1",
+                "BaseFont" => "Helvetica",
+                "Encoding" => "WinAnsiEncoding",
+            },
+        },
+    };
+    Stream::new(
+        dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Form",
+            "BBox" => vec![0.into(), 0.into(), Object::from(width), Object::from(height)],
+            "Resources" => resources,
+        },
+        content,
+    )
+}
+
+/// Add `annotation_id` to the annotations of the page `page_id`.
+fn add_annotation(
+    doc: &mut Document,
+    page_id: ObjectId,
+    annotation_id: ObjectId,
+) -> Result<(), ConvertError> {
+    let annotations: Option<ObjectId> = doc
+        .get_dictionary(page_id)
+        .ok()
+        .and_then(|page| page.get(b"Annots").ok())
+        .and_then(|annotations| annotations.as_reference().ok());
+    if let Some(annotations_id) = annotations {
+        doc.get_object_mut(annotations_id)
+            .and_then(Object::as_array_mut)
+            .map_err(|_| failed("the page annotations are not an array"))?
+            .push(annotation_id.into());
+        return Ok(());
+    }
+    let page: &mut Dictionary = doc
+        .get_dictionary_mut(page_id)
+        .map_err(|_| failed("a page is not a dictionary"))?;
+    match page.get_mut(b"Annots") {
+        Ok(annotations) => annotations
+            .as_array_mut()
+            .map_err(|_| failed("the page annotations are not an array"))?
+            .push(annotation_id.into()),
+        Err(_) => page.set("Annots", vec![Object::from(annotation_id)]),
+    }
+    Ok(())
+}
+
+/// The fields of the document's interactive form, if it has one.
+fn form_fields(doc: &Document) -> Option<&Vec<Object>> {
+    let form = doc.catalog().ok()?.get(b"AcroForm").ok()?;
+    let (_, form) = doc.dereference(form).ok()?;
+    let fields = form.as_dict().ok()?.get(b"Fields").ok()?;
+    let (_, fields) = doc.dereference(fields).ok()?;
+    fields.as_array().ok()
+}
+
+fn form_field_count(doc: &Document) -> usize {
+    form_fields(doc).map_or(0, Vec::len)
+}
+
+/// Add `field_id` to the interactive form, creating it if needed, and
+/// mark the form as signed.
+fn add_form_field(doc: &mut Document, field_id: ObjectId) -> Result<(), ConvertError> {
+    let mut fields: Vec<Object> = form_fields(doc).cloned().unwrap_or_default();
+    fields.push(field_id.into());
+    let form_id: Option<ObjectId> = doc
+        .catalog()
+        .ok()
+        .and_then(|catalog| catalog.get(b"AcroForm").ok())
+        .and_then(|form| form.as_reference().ok());
+    let form: &mut Dictionary = match form_id {
+        Some(form_id) => doc.get_dictionary_mut(form_id),
+        None => {
+            let catalog: &mut Dictionary = doc
+                .catalog_mut()
+                .map_err(|e| ConvertError::Parse(format!("invalid PDF: {e}")))?;
+            if !catalog.has(b"AcroForm") {
+                catalog.set("AcroForm", Dictionary::new());
+            }
+            catalog.get_mut(b"AcroForm").and_then(Object::as_dict_mut)
+        }
+    }
+    .map_err(|_| failed("the interactive form is not a dictionary"))?;
+    form.set("Fields", fields);
+    // SignaturesExist (1) and AppendOnly (2).
+    form.set("SigFlags", 3);
+    Ok(())
+}
+
+fn datetime_parts(datetime: &Datetime) -> (i32, u8, u8, u8, u8, u8) {
+    (
+        datetime.year().unwrap_or(1970),
+        datetime.month().unwrap_or(1),
+        datetime.day().unwrap_or(1),
+        datetime.hour().unwrap_or(0),
+        datetime.minute().unwrap_or(0),
+        datetime.second().unwrap_or(0),
+    )
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn bad_key(what: &str) -> ConvertError {
+    ConvertError::InvalidOptions(format!("cannot sign PDF: {what}"))
+}
+
+fn failed(what: &str) -> ConvertError {
+    ConvertError::Render(format!("cannot sign PDF: {what}"))
+}
+
+/// One DER element: its tag, its content and its whole encoding.
+struct DerElement<'a> {
+    tag: u8,
+    content: &'a [u8],
+    encoded: &'a [u8],
+}
+
+/// The first DER element of `data` and what follows it. Only definite
+/// lengths of up to four bytes are accepted, which covers certificates.
+fn read_der(data: &[u8]) -> Option<(DerElement<'_>, &[u8])> {
+    let tag: u8 = *data.first()?;
+    let first: u8 = *data.get(1)?;
+    let (length, header): (usize, usize) = if first < 0x80 {
+        (usize::from(first), 2)
+    } else {
+        let count: usize = usize::from(first & 0x7F);
+        if count == 0 || count > 4 {
+            return None;
+        }
+        let bytes: &[u8] = data.get(2..2 + count)?;
+        let length: usize = bytes
+            .iter()
+            .fold(0, |length, byte| (length << 8) | usize::from(*byte));
+        (length, 2 + count)
+    };
+    let end: usize = header.checked_add(length)?;
+    let encoded: &[u8] = data.get(..end)?;
+    Some((
+        DerElement {
+            tag,
+            content: &encoded[header..],
+            encoded,
+        },
+        &data[end..],
+    ))
+}
+
+/// The DER elements laid out one after another in a constructed value.
+struct DerElements<'a>(&'a [u8]);
+
+impl<'a> Iterator for DerElements<'a> {
+    type Item = DerElement<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (element, rest) = read_der(self.0)?;
+        self.0 = rest;
+        Some(element)
+    }
+}
+
+/// A DER element with definite length.
+fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut encoded: Vec<u8> = Vec::with_capacity(content.len() + 6);
+    encoded.push(tag);
+    if content.len() < 0x80 {
+        encoded.push(content.len() as u8);
+    } else {
+        let length: [u8; 8] = (content.len() as u64).to_be_bytes();
+        let skip: usize = length.iter().take_while(|byte| **byte == 0).count();
+        encoded.push(0x80 | (length.len() - skip) as u8);
+        encoded.extend_from_slice(&length[skip..]);
+    }
+    encoded.extend_from_slice(content);
+    encoded
+}
+
+fn der_sequence(parts: &[&[u8]]) -> Vec<u8> {
+    der(SEQUENCE, &parts.concat())
+}
+
+#[cfg(test)]
+#[path = "pdf_ops_sign_tests.rs"]
+mod tests;
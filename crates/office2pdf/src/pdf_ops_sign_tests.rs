@@ -0,0 +1,287 @@
+use super::*;
+use crate::pdf_ops::test_support::{TestPdf, labelled_page};
+use rsa::pkcs1::DecodeRsaPublicKey;
+
+const KEY_DER: &[u8] = include_bytes!("../../../tests/fixtures/signing/test-signer.key.der");
+const CERT_DER: &[u8] = include_bytes!("../../../tests/fixtures/signing/test-signer.cert.der");
+const P12: &[u8] = include_bytes!("../../../tests/fixtures/signing/test-signer.p12");
+
+fn der_key() -> SigningKey {
+    SigningKey::Der {
+        private_key: KEY_DER.to_vec(),
+        certificates: vec![CERT_DER.to_vec()],
+    }
+}
+
+fn config(key: SigningKey, appearance: Option<SignatureAppearance>) -> SigningConfig {
+    SigningConfig {
+        key,
+        reason: Some("Approved".to_string()),
+        location: None,
+        contact_info: None,
+        appearance,
+    }
+}
+
+fn signing_time() -> Datetime {
+    Datetime::from_ymd_hms(2026, 3, 14, 15, 9, 26).unwrap()
+}
+
+/// A one-page PDF with a text content stream.
+fn one_page_pdf() -> Vec<u8> {
+    let mut pdf = TestPdf::new();
+    pdf.add_page(&labelled_page("Page", 1), Dictionary::new());
+    pdf.save()
+}
+
+/// The signature dictionary of the document's only signature field.
+fn signature_dictionary(doc: &Document) -> &Dictionary {
+    let field_id: ObjectId = form_fields(doc).unwrap()[0].as_reference().unwrap();
+    let field: &Dictionary = doc.get_dictionary(field_id).unwrap();
+    doc.get_dictionary(field.get(b"V").unwrap().as_reference().unwrap())
+        .unwrap()
+}
+
+#[test]
+fn test_sign_covers_whole_file_but_contents_with_valid_signature() {
+    let signed: Vec<u8> =
+        sign_at(&one_page_pdf(), &config(der_key(), None), &signing_time()).unwrap();
+    let doc: Document = Document::load_mem(&signed).unwrap();
+    let signature: &Dictionary = signature_dictionary(&doc);
+    assert_eq!(
+        signature.get(b"SubFilter").unwrap().as_name().unwrap(),
+        b"ETSI.CAdES.detached"
+    );
+    assert_eq!(
+        signature.get(b"M").unwrap().as_str().unwrap(),
+        b"D:20260314150926Z"
+    );
+
+    let byte_range: Vec<usize> = signature
+        .get(b"ByteRange")
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n.as_i64().unwrap() as usize)
+        .collect();
+    assert_eq!(byte_range[0], 0);
+    assert_eq!(byte_range[2] + byte_range[3], signed.len());
+    assert_eq!(signed[byte_range[1]], b'<');
+    assert_eq!(signed[byte_range[2] - 1], b'>');
+
+    // Walk ContentInfo > SignedData > SignerInfo.
+    let contents: &[u8] = signature.get(b"Contents").unwrap().as_str().unwrap();
+    let (content_info, _) = read_der(contents).unwrap();
+    let mut content_info = DerElements(content_info.content);
+    assert_eq!(content_info.next().unwrap().content, OID_SIGNED_DATA);
+    let (signed_data, _) = read_der(content_info.next().unwrap().content).unwrap();
+    let signer_infos: DerElement = DerElements(signed_data.content).nth(4).unwrap();
+    let (signer_info, _) = read_der(signer_infos.content).unwrap();
+    let fields: Vec<DerElement> = DerElements(signer_info.content).collect();
+    let mut attributes: Vec<u8> = fields[3].encoded.to_vec();
+    attributes[0] = SET;
+    let signature_value: &[u8] = fields[5].content;
+
+    let mut hasher = Sha256::new();
+    hasher.update(&signed[..byte_range[1]]);
+    hasher.update(&signed[byte_range[2]..]);
+    let digest = hasher.finalize();
+    assert!(find(&attributes, &der(OCTET_STRING, &digest)).is_some());
+
+    let certificate: Certificate = parse_certificate(CERT_DER).unwrap();
+    let public_key = RsaPublicKey::from_pkcs1_der(certificate.public_key).unwrap();
+    public_key
+        .verify(
+            Pkcs1v15Sign::new::<Sha256>(),
+            &Sha256::digest(&attributes),
+            signature_value,
+        )
+        .expect("the signature verifies with the certificate's key");
+}
+
+#[test]
+fn test_sign_at_a_fixed_time_is_byte_identical() {
+    // What deterministic output relies on: PKCS#1 v1.5 signatures are
+    // not randomised, so the signing time is the only varying input.
+    let signing_config: SigningConfig = config(der_key(), None);
+    let first: Vec<u8> = sign_at(&one_page_pdf(), &signing_config, &signing_time()).unwrap();
+    let second: Vec<u8> = sign_at(&one_page_pdf(), &signing_config, &signing_time()).unwrap();
+    assert!(first == second, "signatures at the same time differ");
+}
+
+#[test]
+fn test_sign_appends_to_a_signed_pdf_and_keeps_its_signature() {
+    let signing_config: SigningConfig = config(der_key(), None);
+    let once: Vec<u8> = sign_at(&one_page_pdf(), &signing_config, &signing_time()).unwrap();
+    let twice: Vec<u8> = sign_at(&once, &signing_config, &signing_time()).unwrap();
+    // The first signature covers exactly the bytes it was made over.
+    assert!(twice.starts_with(&once));
+
+    let doc: Document = Document::load_mem(&twice).unwrap();
+    let names: Vec<&[u8]> = form_fields(&doc)
+        .unwrap()
+        .iter()
+        .map(|field| {
+            let field: &Dictionary = doc.get_dictionary(field.as_reference().unwrap()).unwrap();
+            field.get(b"T").unwrap().as_str().unwrap()
+        })
+        .collect();
+    assert_eq!(names, vec![b"Signature1".as_slice(), b"Signature2"]);
+    let byte_range: Vec<i64> = signature_dictionary(&doc)
+        .get(b"ByteRange")
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n.as_i64().unwrap())
+        .collect();
+    assert_eq!((byte_range[2] + byte_range[3]) as usize, once.len());
+}
+
+#[test]
+fn test_sign_invisible_adds_locked_zero_size_widget() {
+    let signed: Vec<u8> =
+        sign_at(&one_page_pdf(), &config(der_key(), None), &signing_time()).unwrap();
+    let doc: Document = Document::load_mem(&signed).unwrap();
+    let page: &Dictionary = doc.get_dictionary(doc.get_pages()[&1]).unwrap();
+    let annotations: &Vec<Object> = page.get(b"Annots").unwrap().as_array().unwrap();
+    let widget: &Dictionary = doc
+        .get_dictionary(annotations[0].as_reference().unwrap())
+        .unwrap();
+    assert_eq!(widget.get(b"FT").unwrap().as_name().unwrap(), b"Sig");
+    assert_eq!(widget.get(b"F").unwrap().as_i64().unwrap(), 132);
+    assert!(!widget.has(b"AP"), "an invisible signature draws nothing");
+    let rect: Vec<f32> = widget
+        .get(b"Rect")
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n.as_float().unwrap())
+        .collect();
+    assert_eq!(rect, vec![0.0; 4]);
+
+    let form = doc.catalog().unwrap().get(b"AcroForm").unwrap();
+    let form: &Dictionary = doc.dereference(form).unwrap().1.as_dict().unwrap();
+    assert_eq!(form.get(b"SigFlags").unwrap().as_i64().unwrap(), 3);
+}
+
+#[test]
+fn test_sign_visible_draws_signer_name_and_reason() {
+    let appearance = SignatureAppearance {
+        page: 1,
+        x: 36.0,
+        y: 36.0,
+        width: 220.0,
+        height: 60.0,
+    };
+    let signed: Vec<u8> = sign_at(
+        &one_page_pdf(),
+        &config(der_key(), Some(appearance)),
+        &signing_time(),
+    )
+    .unwrap();
+    let doc: Document = Document::load_mem(&signed).unwrap();
+    let field_id: ObjectId = form_fields(&doc).unwrap()[0].as_reference().unwrap();
+    let widget: &Dictionary = doc.get_dictionary(field_id).unwrap();
+    let normal: ObjectId = widget
+        .get(b"AP")
+        .and_then(Object::as_dict)
+        .and_then(|appearances| appearances.get(b"N"))
+        .and_then(Object::as_reference)
+        .unwrap();
+    let stream: &Stream = doc.get_object(normal).unwrap().as_stream().unwrap();
+    let content: String = String::from_utf8_lossy(&stream.content).into_owned();
+    assert!(
+        content.contains("(Digitally signed by office2pdf test signer) Tj"),
+        "{content}"
+    );
+    assert!(content.contains("(Date: 2026-03-14 15:09:26 UTC) Tj"));
+    assert!(content.contains("(Reason: Approved) Tj"));
+}
+
+#[test]
+fn test_sign_reads_pkcs12_and_rejects_wrong_password() {
+    let key = SigningKey::Pkcs12 {
+        data: P12.to_vec(),
+        password: "test".to_string(),
+    };
+    assert!(sign_at(&one_page_pdf(), &config(key, None), &signing_time()).is_ok());
+
+    let key = SigningKey::Pkcs12 {
+        data: P12.to_vec(),
+        password: "wrong".to_string(),
+    };
+    let result = sign_at(&one_page_pdf(), &config(key, None), &signing_time());
+    assert!(
+        matches!(result, Err(ConvertError::InvalidOptions(_))),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn test_sign_rejects_appearance_past_last_page() {
+    let appearance = SignatureAppearance {
+        page: 2,
+        x: 0.0,
+        y: 0.0,
+        width: 100.0,
+        height: 40.0,
+    };
+    let result = sign_at(
+        &one_page_pdf(),
+        &config(der_key(), Some(appearance)),
+        &signing_time(),
+    );
+    assert!(
+        matches!(&result, Err(ConvertError::InvalidOptions(message)) if message.contains("page 2")),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn test_sign_rejects_certificate_of_another_key() {
+    let key = SigningKey::Der {
+        private_key: KEY_DER.to_vec(),
+        certificates: Vec::new(),
+    };
+    assert!(matches!(
+        sign_at(&one_page_pdf(), &config(key, None), &signing_time()),
+        Err(ConvertError::InvalidOptions(_))
+    ));
+
+    // A certificate whose key differs in one byte of the modulus.
+    let mut other: Vec<u8> = CERT_DER.to_vec();
+    let certificate: Certificate = parse_certificate(CERT_DER).unwrap();
+    let offset: usize = find(CERT_DER, certificate.public_key).unwrap() + 20;
+    other[offset] ^= 0xFF;
+    let key = SigningKey::Der {
+        private_key: KEY_DER.to_vec(),
+        certificates: vec![other],
+    };
+    let result = sign_at(&one_page_pdf(), &config(key, None), &signing_time());
+    assert!(
+        matches!(&result, Err(ConvertError::InvalidOptions(message)) if message.contains("does not belong")),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn test_common_name_reads_certificate_subject() {
+    let certificate: Certificate = parse_certificate(CERT_DER).unwrap();
+    assert_eq!(
+        common_name(certificate.subject).as_deref(),
+        Some("office2pdf test signer")
+    );
+}
+
+#[test]
+fn test_der_uses_long_form_lengths_past_127_bytes() {
+    assert_eq!(der(OCTET_STRING, &[7; 3]), vec![0x04, 0x03, 7, 7, 7]);
+    let long: Vec<u8> = der(OCTET_STRING, &[0; 300]);
+    assert_eq!(&long[..4], &[0x04, 0x82, 0x01, 0x2C]);
+    let (element, rest) = read_der(&long).unwrap();
+    assert_eq!(element.content.len(), 300);
+    assert!(rest.is_empty());
+}
@@ -0,0 +1,253 @@
+//! Splitting a PDF at its top-level bookmarks, or into parts of at most so
+//! many pages or bytes.
+
+use std::collections::BTreeMap;
+
+use lopdf::{Dictionary, Document, Object, ObjectId};
+
+use super::merge::{named_destinations, outline_items};
+use super::{PageRange, extract_pages, load_pdf_document};
+use crate::error::ConvertError;
+
+/// Where [`split_by`] cuts a PDF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitBy {
+    /// Before each page a top-level bookmark points at, giving a part per
+    /// chapter, or per input of a PDF merged with bookmarks.
+    Bookmarks,
+    /// Every so many pages.
+    PageCount(u32),
+    /// Into parts of at most so many bytes.
+    MaxBytes(u64),
+}
+
+/// A part of a split PDF.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitPart {
+    /// The pages of the original PDF in this part.
+    pub pages: PageRange,
+    /// The title of the bookmark the part starts at, when split by
+    /// bookmarks.
+    pub title: Option<String>,
+    pub pdf: Vec<u8>,
+}
+
+impl SplitPart {
+    /// A file name for the part, `number` counting parts from 1, made from
+    /// `template` and ending in ".pdf". The template may use `{stem}` for
+    /// `stem`, `{n}` for `number`, `{start}` and `{end}` for the first and
+    /// last page, and `{title}` for the bookmark title, made safe for a
+    /// file name, or "part-N" when the part has none.
+    pub fn file_name(&self, template: &str, stem: &str, number: usize) -> String {
+        let title: String = self
+            .title
+            .as_deref()
+            .map(file_name_safe)
+            .filter(|title| !title.is_empty())
+            .unwrap_or_else(|| format!("part-{number}"));
+        let name: String = template
+            .replace("{stem}", stem)
+            .replace("{n}", &number.to_string())
+            .replace("{start}", &self.pages.start.to_string())
+            .replace("{end}", &self.pages.end.to_string())
+            .replace("{title}", &title);
+        format!("{name}.pdf")
+    }
+}
+
+/// `text` with the characters file systems reserve, and leading dots,
+/// replaced or dropped.
+fn file_name_safe(text: &str) -> String {
+    let safe: String = text
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    safe.trim().trim_start_matches('.').trim().to_string()
+}
+
+/// Split a PDF where `by` says, into parts that together hold every page
+/// in order.
+///
+/// Split by bookmarks, pages before the first bookmarked page make a part
+/// of their own, and a PDF without bookmarks is refused. Split by size, a
+/// page that is too big on its own still gets a part; finding where to cut
+/// writes each part a few times over, so this is slower than the others.
+pub fn split_by(input: &[u8], by: &SplitBy) -> Result<Vec<SplitPart>, ConvertError> {
+    let doc: Document = load_pdf_document(input, "")?;
+    let total_pages: u32 = doc.get_pages().len() as u32;
+    match *by {
+        SplitBy::Bookmarks => {
+            let starts: BTreeMap<u32, String> = bookmark_starts(&doc);
+            if starts.is_empty() {
+                return Err(ConvertError::InvalidOptions(
+                    "the PDF has no bookmarks to split at".to_string(),
+                ));
+            }
+            let mut boundaries: Vec<(u32, Option<String>)> = Vec::new();
+            if starts.first_key_value().is_some_and(|(&page, _)| page > 1) {
+                boundaries.push((1, None));
+            }
+            boundaries.extend(starts.into_iter().map(|(page, title)| (page, Some(title))));
+            let ends = boundaries
+                .iter()
+                .skip(1)
+                .map(|(start, _)| start - 1)
+                .chain([total_pages]);
+            boundaries
+                .iter()
+                .zip(ends)
+                .map(|((start, title), end)| {
+                    let pages = PageRange::new(*start, end);
+                    Ok(SplitPart {
+                        pdf: extract_pages(&doc, &pages)?,
+                        pages,
+                        title: title.clone(),
+                    })
+                })
+                .collect()
+        }
+        SplitBy::PageCount(0) => Err(ConvertError::InvalidOptions(
+            "cannot split into parts of 0 pages".to_string(),
+        )),
+        SplitBy::PageCount(count) => (1..=total_pages)
+            .step_by(count as usize)
+            .map(|start| {
+                let pages = PageRange::new(start, start.saturating_add(count - 1).min(total_pages));
+                Ok(SplitPart {
+                    pdf: extract_pages(&doc, &pages)?,
+                    pages,
+                    title: None,
+                })
+            })
+            .collect(),
+        SplitBy::MaxBytes(0) => Err(ConvertError::InvalidOptions(
+            "cannot split into parts of 0 bytes".to_string(),
+        )),
+        SplitBy::MaxBytes(max_bytes) => {
+            let mut parts: Vec<SplitPart> = Vec::new();
+            let mut start: u32 = 1;
+            while start <= total_pages {
+                let part: SplitPart = largest_part(&doc, start, total_pages, max_bytes)?;
+                start = part.pages.end + 1;
+                parts.push(part);
+            }
+            Ok(parts)
+        }
+    }
+}
+
+/// The longest run of pages from `start` that fits in `max_bytes`, or the
+/// page at `start` alone when even that does not.
+fn largest_part(
+    doc: &Document,
+    start: u32,
+    total_pages: u32,
+    max_bytes: u64,
+) -> Result<SplitPart, ConvertError> {
+    let part = |end: u32| -> Result<SplitPart, ConvertError> {
+        let pages = PageRange::new(start, end);
+        Ok(SplitPart {
+            pdf: extract_pages(doc, &pages)?,
+            pages,
+            title: None,
+        })
+    };
+    let fits = |part: &SplitPart| part.pdf.len() as u64 <= max_bytes;
+    let mut best: SplitPart = part(start)?;
+    if !fits(&best) {
+        return Ok(best);
+    }
+    // Shared resources make the size grow unevenly with the page count,
+    // but it does not shrink: double the run until it no longer fits, then
+    // narrow down on the end between the last two tries.
+    let mut step: u32 = 1;
+    let mut too_long: Option<u32> = None;
+    while too_long.is_none() && best.pages.end < total_pages {
+        let end: u32 = best.pages.end.saturating_add(step).min(total_pages);
+        let candidate: SplitPart = part(end)?;
+        if fits(&candidate) {
+            best = candidate;
+            step = step.saturating_mul(2);
+        } else {
+            too_long = Some(end);
+        }
+    }
+    if let Some(mut too_long) = too_long {
+        while too_long - best.pages.end > 1 {
+            let end: u32 = best.pages.end + (too_long - best.pages.end) / 2;
+            let candidate: SplitPart = part(end)?;
+            if fits(&candidate) {
+                best = candidate;
+            } else {
+                too_long = end;
+            }
+        }
+    }
+    Ok(best)
+}
+
+/// The page each top-level bookmark points at, by page number, with the
+/// bookmark's title. Of bookmarks pointing at the same page, the first
+/// counts.
+fn bookmark_starts(doc: &Document) -> BTreeMap<u32, String> {
+    let page_numbers: BTreeMap<ObjectId, u32> = doc
+        .get_pages()
+        .into_iter()
+        .map(|(number, id)| (id, number))
+        .collect();
+    let destinations: BTreeMap<Vec<u8>, Object> = named_destinations(doc);
+    let mut starts: BTreeMap<u32, String> = BTreeMap::new();
+    for id in outline_items(doc) {
+        let Ok(item) = doc.get_dictionary(id) else {
+            continue;
+        };
+        let Some(page) =
+            destination_page(doc, item, &destinations).and_then(|page| page_numbers.get(&page))
+        else {
+            continue;
+        };
+        let title: String = item
+            .get(b"Title")
+            .ok()
+            .and_then(|title| doc.dereference(title).ok())
+            .and_then(|(_, title)| lopdf::decode_text_string(title).ok())
+            .unwrap_or_default();
+        starts.entry(*page).or_insert(title);
+    }
+    starts
+}
+
+/// The page an outline item goes to, through its `Dest` or a `GoTo`
+/// action, explicit or named.
+fn destination_page(
+    doc: &Document,
+    item: &Dictionary,
+    destinations: &BTreeMap<Vec<u8>, Object>,
+) -> Option<ObjectId> {
+    let action: Option<&Dictionary> = item
+        .get(b"A")
+        .ok()
+        .and_then(|action| doc.dereference(action).ok())
+        .and_then(|(_, action)| action.as_dict().ok())
+        .filter(|action| action.get(b"S").and_then(Object::as_name).ok() == Some(b"GoTo"));
+    let destination: &Object = item
+        .get(b"Dest")
+        .ok()
+        .or_else(|| action.and_then(|action| action.get(b"D").ok()))?;
+    let (_, destination) = doc.dereference(destination).ok()?;
+    let destination: &Object = match destination {
+        Object::Name(name) | Object::String(name, _) => destinations.get(name)?,
+        _ => destination,
+    };
+    destination.as_array().ok()?.first()?.as_reference().ok()
+}
+
+#[cfg(test)]
+#[path = "pdf_ops_split_tests.rs"]
+mod tests;
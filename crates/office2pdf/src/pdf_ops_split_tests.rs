@@ -0,0 +1,155 @@
+use super::*;
+use crate::pdf_ops::test_support::{TestPdf, labelled_page};
+use lopdf::{Stream, dictionary};
+
+/// A PDF of `num_pages` A4 pages, each with its own `image_bytes` byte
+/// image, and an outline of one item per `(title, page)`.
+fn make_test_pdf(num_pages: u32, image_bytes: usize, bookmarks: &[(&str, u32)]) -> Vec<u8> {
+    let mut pdf = TestPdf::new();
+    for i in 0..num_pages {
+        // Varied bytes, so compression does not shrink the image away.
+        let pixels: Vec<u8> = (0..image_bytes)
+            .map(|n| ((n as u32).wrapping_mul(2_654_435_761) ^ i).to_le_bytes()[3])
+            .collect();
+        let image_id: ObjectId = pdf.doc.add_object(
+            Stream::new(
+                dictionary! {
+                    "Type" => "XObject",
+                    "Subtype" => "Image",
+                    "Width" => image_bytes as i64,
+                    "Height" => 1,
+                    "ColorSpace" => "DeviceGray",
+                    "BitsPerComponent" => 8,
+                },
+                pixels,
+            )
+            .with_compression(false),
+        );
+        pdf.add_page(
+            &format!("q /Im1 Do Q {}", labelled_page("Page", i + 1)),
+            dictionary! { "Resources" => dictionary! { "XObject" => dictionary! { "Im1" => image_id } } },
+        );
+    }
+    let mut catalog: Dictionary = Dictionary::new();
+    if !bookmarks.is_empty() {
+        let doc: &mut Document = &mut pdf.doc;
+        let outlines_id = doc.new_object_id();
+        let item_ids: Vec<ObjectId> = bookmarks.iter().map(|_| doc.new_object_id()).collect();
+        for (index, ((title, page), item_id)) in bookmarks.iter().zip(&item_ids).enumerate() {
+            let destination: Object =
+                vec![pdf.page_ids[*page as usize - 1].into(), "Fit".into()].into();
+            let mut item = dictionary! {
+                "Title" => Object::string_literal(*title),
+                "Parent" => outlines_id,
+            };
+            // Alternate between a destination and a `GoTo` action.
+            if index % 2 == 0 {
+                item.set("Dest", destination);
+            } else {
+                item.set("A", dictionary! { "S" => "GoTo", "D" => destination });
+            }
+            if let Some(next) = item_ids.get(index + 1) {
+                item.set("Next", *next);
+            }
+            doc.objects.insert(*item_id, Object::Dictionary(item));
+        }
+        doc.objects.insert(
+            outlines_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Outlines",
+                "First" => item_ids[0],
+                "Last" => *item_ids.last().unwrap(),
+                "Count" => item_ids.len() as i64,
+            }),
+        );
+        catalog.set("Outlines", outlines_id);
+    }
+    pdf.save_with_catalog(catalog)
+}
+
+fn ranges(parts: &[SplitPart]) -> Vec<(u32, u32)> {
+    parts
+        .iter()
+        .map(|part| (part.pages.start, part.pages.end))
+        .collect()
+}
+
+#[test]
+fn test_split_by_bookmarks() {
+    let pdf: Vec<u8> = make_test_pdf(6, 10, &[("Intro", 2), ("Results", 4), ("Also", 4)]);
+    let parts: Vec<SplitPart> = split_by(&pdf, &SplitBy::Bookmarks).unwrap();
+    assert_eq!(ranges(&parts), vec![(1, 1), (2, 3), (4, 6)]);
+    let titles: Vec<Option<&str>> = parts.iter().map(|part| part.title.as_deref()).collect();
+    assert_eq!(titles, vec![None, Some("Intro"), Some("Results")]);
+    for part in &parts {
+        let doc: Document = Document::load_mem(&part.pdf).unwrap();
+        assert_eq!(
+            doc.get_pages().len() as u32,
+            part.pages.end - part.pages.start + 1
+        );
+    }
+}
+
+#[test]
+fn test_split_by_bookmarks_needs_bookmarks() {
+    let err: ConvertError = split_by(&make_test_pdf(2, 10, &[]), &SplitBy::Bookmarks).unwrap_err();
+    assert!(matches!(err, ConvertError::InvalidOptions(_)), "{err:?}");
+}
+
+#[test]
+fn test_split_by_page_count() {
+    let pdf: Vec<u8> = make_test_pdf(5, 10, &[]);
+    let parts: Vec<SplitPart> = split_by(&pdf, &SplitBy::PageCount(2)).unwrap();
+    assert_eq!(ranges(&parts), vec![(1, 2), (3, 4), (5, 5)]);
+    assert!(split_by(&pdf, &SplitBy::PageCount(0)).is_err());
+}
+
+#[test]
+fn test_split_by_size_keeps_parts_under_the_limit() {
+    let pdf: Vec<u8> = make_test_pdf(7, 4_000, &[]);
+    let one_page: u64 = split_by(&pdf, &SplitBy::PageCount(1)).unwrap()[0].pdf.len() as u64;
+    // Room for about three pages a part, each part carrying only its own
+    // images.
+    let max_bytes: u64 = one_page * 3;
+    let parts: Vec<SplitPart> = split_by(&pdf, &SplitBy::MaxBytes(max_bytes)).unwrap();
+    assert!(parts.len() >= 3, "{:?}", ranges(&parts));
+    assert_eq!(parts.first().unwrap().pages.start, 1);
+    assert_eq!(parts.last().unwrap().pages.end, 7);
+    for pair in parts.windows(2) {
+        assert_eq!(pair[0].pages.end + 1, pair[1].pages.start);
+    }
+    for part in &parts {
+        assert!(part.pdf.len() as u64 <= max_bytes);
+    }
+    assert!(parts[0].pages.end > 1, "{:?}", ranges(&parts));
+}
+
+#[test]
+fn test_split_by_size_gives_oversized_pages_a_part_each() {
+    let pdf: Vec<u8> = make_test_pdf(2, 4_000, &[]);
+    let parts: Vec<SplitPart> = split_by(&pdf, &SplitBy::MaxBytes(100)).unwrap();
+    assert_eq!(ranges(&parts), vec![(1, 1), (2, 2)]);
+}
+
+#[test]
+fn test_split_part_file_name() {
+    let part = SplitPart {
+        pages: PageRange::new(4, 9),
+        title: Some("Q3: Results / Summary".to_string()),
+        pdf: Vec::new(),
+    };
+    assert_eq!(
+        part.file_name("{stem}_{n}_{title}_{start}-{end}", "report", 2),
+        "report_2_Q3_ Results _ Summary_4-9.pdf"
+    );
+    let escaping = SplitPart {
+        title: Some("../../etc".to_string()),
+        ..part.clone()
+    };
+    assert_eq!(escaping.file_name("{title}", "report", 1), "_.._etc.pdf");
+    let untitled = SplitPart {
+        title: Some(" ".to_string()),
+        ..part
+    };
+    assert_eq!(untitled.file_name("{title}", "report", 3), "part-3.pdf");
+}
@@ -0,0 +1,253 @@
+//! Stamps drawn over the pages of a PDF: a line of text or an image, such
+//! as "DRAFT", a Bates number or a confidentiality notice.
+
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream, dictionary};
+
+use super::{
+    inherited_media_box, inherited_resources, load_pdf_document, page_contents, rectangle,
+    text_width, win_ansi_literal,
+};
+use crate::config::{Stamp, StampContent, StampPosition};
+use crate::error::ConvertError;
+use crate::ir::Color;
+
+/// Resource names for the stamp, unlikely to clash with a page's own.
+const FONT: &str = "O2PStampFont";
+const IMAGE: &str = "O2PStampImage";
+const GRAPHICS_STATE: &str = "O2PStampGS";
+
+/// What the stamp draws, once its image, if any, is in the document.
+enum Mark {
+    Text { font_size: f64, color: Color },
+    Image { width: f64, height: f64 },
+}
+
+/// Draw `stamp` over the pages of `input` it selects, centered on its
+/// position and rotated around its center. The stamp is marked as an
+/// artifact, so screen readers skip it in tagged PDFs.
+///
+/// `{page}` and `{pages}` in a text stamp become each page's number and
+/// the page count.
+pub fn stamp(input: &[u8], stamp: &Stamp) -> Result<Vec<u8>, ConvertError> {
+    let template: &str = match &stamp.content {
+        StampContent::Text { text, .. } => text,
+        StampContent::Image { .. } => "",
+    };
+    stamp_with_labels(input, stamp, |number, page_count| {
+        template
+            .replace("{page}", &number.to_string())
+            .replace("{pages}", &page_count.to_string())
+    })
+}
+
+/// Draw `stamp` as [`stamp`] does, a text stamp showing `label(number,
+/// page_count)` on page `number` rather than its own text.
+pub(super) fn stamp_with_labels(
+    input: &[u8],
+    stamp: &Stamp,
+    label: impl Fn(u32, u32) -> String,
+) -> Result<Vec<u8>, ConvertError> {
+    stamp.check()?;
+    let mut doc: Document = load_pdf_document(input, "")?;
+    let pages: Vec<(u32, ObjectId)> = doc.get_pages().into_iter().collect();
+    let page_count: u32 = pages.len() as u32;
+
+    let mut resources: Vec<(&str, &str, Object)> = vec![(
+        "ExtGState",
+        GRAPHICS_STATE,
+        Object::Dictionary(dictionary! {
+            "Type" => "ExtGState",
+            "ca" => stamp.opacity,
+            "CA" => stamp.opacity,
+        }),
+    )];
+    let mark: Mark = match &stamp.content {
+        StampContent::Text {
+            font_size, color, ..
+        } => {
+            resources.push((
+                "Font",
+                FONT,
+                Object::Dictionary(dictionary! {
+                    "Type" => "Font",
+                    "Subtype" => "Type1",
+                    "BaseFont" => "Helvetica",
+                    "Encoding" => "WinAnsiEncoding",
+                }),
+            ));
+            Mark::Text {
+                font_size: *font_size,
+                color: *color,
+            }
+        }
+        StampContent::Image { data, width } => {
+            let (image_id, height) = add_image(&mut doc, data, *width)?;
+            resources.push(("XObject", IMAGE, Object::Reference(image_id)));
+            Mark::Image {
+                width: *width,
+                height,
+            }
+        }
+    };
+
+    // Whatever graphics state the page content leaves behind is restored
+    // before the stamp: its content is wrapped in `q` and `Q`.
+    let save_id: ObjectId = doc.add_object(Stream::new(Dictionary::new(), b"q".to_vec()));
+    for (number, page_id) in pages {
+        if !stamp.pages.contains(number) {
+            continue;
+        }
+        let [left, bottom, right, top] = page_box(&doc, page_id)?;
+        let (width, height, drawing): (f64, f64, Vec<u8>) = match &mark {
+            Mark::Text { font_size, color } => {
+                let text: String = label(number, page_count);
+                let width: f64 = text_width(&text) * font_size;
+                // Helvetica's cap height, so capitals center vertically.
+                let height: f64 = 0.718 * font_size;
+                let mut drawing: Vec<u8> = format!(
+                    "BT /{FONT} {font_size:.3} Tf {:.3} {:.3} {:.3} rg {:.3} {:.3} Td (",
+                    f64::from(color.r) / 255.0,
+                    f64::from(color.g) / 255.0,
+                    f64::from(color.b) / 255.0,
+                    -width / 2.0,
+                    -height / 2.0,
+                )
+                .into_bytes();
+                drawing.extend(win_ansi_literal(&text));
+                drawing.extend_from_slice(b") Tj ET");
+                (width, height, drawing)
+            }
+            Mark::Image { width, height } => (
+                *width,
+                *height,
+                format!(
+                    "{width:.3} 0 0 {height:.3} {:.3} {:.3} cm /{IMAGE} Do",
+                    -width / 2.0,
+                    -height / 2.0
+                )
+                .into_bytes(),
+            ),
+        };
+        let margin: f64 = stamp.margin;
+        let x: f64 = match stamp.position {
+            StampPosition::TopLeft | StampPosition::BottomLeft => left + margin + width / 2.0,
+            StampPosition::Center | StampPosition::TopCenter | StampPosition::BottomCenter => {
+                (left + right) / 2.0
+            }
+            StampPosition::TopRight | StampPosition::BottomRight => right - margin - width / 2.0,
+        };
+        let y: f64 = match stamp.position {
+            StampPosition::TopLeft | StampPosition::TopCenter | StampPosition::TopRight => {
+                top - margin - height / 2.0
+            }
+            StampPosition::Center => (bottom + top) / 2.0,
+            StampPosition::BottomLeft
+            | StampPosition::BottomCenter
+            | StampPosition::BottomRight => bottom + margin + height / 2.0,
+        };
+        let (sin, cos): (f64, f64) = stamp.rotation.to_radians().sin_cos();
+        let mut content: Vec<u8> = format!(
+            "Q\n/Artifact BMC q /{GRAPHICS_STATE} gs 1 0 0 1 {x:.3} {y:.3} cm \
+             {cos:.5} {sin:.5} {:.5} {cos:.5} 0 0 cm\n",
+            -sin
+        )
+        .into_bytes();
+        content.extend(drawing);
+        content.extend_from_slice(b"\nQ EMC");
+        let stamp_id: ObjectId = doc.add_object(Stream::new(Dictionary::new(), content));
+
+        let mut page_resources: Dictionary = inherited_resources(&doc, page_id);
+        for (category, name, resource) in &resources {
+            let mut entries: Dictionary = page_resources
+                .get(category.as_bytes())
+                .ok()
+                .and_then(|entries| doc.dereference(entries).ok())
+                .and_then(|(_, entries)| entries.as_dict().ok())
+                .cloned()
+                .unwrap_or_default();
+            entries.set(*name, resource.clone());
+            page_resources.set(*category, entries);
+        }
+        let contents: Vec<Object> = page_contents(&doc, page_id);
+        let page: &mut Dictionary = doc
+            .get_dictionary_mut(page_id)
+            .map_err(|_| failed("a page is not a dictionary"))?;
+        let mut stamped: Vec<Object> = Vec::with_capacity(contents.len() + 2);
+        stamped.push(save_id.into());
+        stamped.extend(contents);
+        stamped.push(stamp_id.into());
+        page.set("Contents", stamped);
+        page.set("Resources", page_resources);
+    }
+
+    let mut output: Vec<u8> = Vec::new();
+    doc.save_to(&mut output)
+        .map_err(|e| failed(&format!("cannot write PDF: {e}")))?;
+    Ok(output)
+}
+
+/// Add the PNG or JPEG `data` as an image XObject, with its alpha channel
+/// as a soft mask, and return it with its height at `width` points wide.
+fn add_image(doc: &mut Document, data: &[u8], width: f64) -> Result<(ObjectId, f64), ConvertError> {
+    let image = image::load_from_memory(data)
+        .map_err(|e| ConvertError::InvalidOptions(format!("cannot read the stamp image: {e}")))?;
+    let (pixels_wide, pixels_high) = (image.width(), image.height());
+    if pixels_wide == 0 || pixels_high == 0 {
+        return Err(ConvertError::InvalidOptions(
+            "the stamp image is empty".to_string(),
+        ));
+    }
+    let rgba = image.to_rgba8();
+    let mut samples: Vec<u8> = Vec::with_capacity(rgba.len() / 4 * 3);
+    let mut alpha: Vec<u8> = Vec::with_capacity(rgba.len() / 4);
+    for pixel in rgba.pixels() {
+        samples.extend_from_slice(&pixel.0[..3]);
+        alpha.push(pixel.0[3]);
+    }
+
+    let mut dict: Dictionary = dictionary! {
+        "Type" => "XObject",
+        "Subtype" => "Image",
+        "Width" => i64::from(pixels_wide),
+        "Height" => i64::from(pixels_high),
+        "ColorSpace" => "DeviceRGB",
+        "BitsPerComponent" => 8,
+    };
+    if alpha.iter().any(|&value| value < u8::MAX) {
+        let mut mask = Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Image",
+                "Width" => i64::from(pixels_wide),
+                "Height" => i64::from(pixels_high),
+                "ColorSpace" => "DeviceGray",
+                "BitsPerComponent" => 8,
+            },
+            alpha,
+        );
+        mask.compress()
+            .map_err(|e| failed(&format!("cannot compress the stamp image: {e}")))?;
+        dict.set("SMask", doc.add_object(mask));
+    }
+    let mut stream = Stream::new(dict, samples);
+    stream
+        .compress()
+        .map_err(|e| failed(&format!("cannot compress the stamp image: {e}")))?;
+    let height: f64 = width * f64::from(pixels_high) / f64::from(pixels_wide);
+    Ok((doc.add_object(stream), height))
+}
+
+/// The page's media box as `[left, bottom, right, top]`.
+fn page_box(doc: &Document, page_id: ObjectId) -> Result<[f64; 4], ConvertError> {
+    let media_box: Object =
+        inherited_media_box(doc, page_id).ok_or_else(|| failed("a page has no media box"))?;
+    rectangle(&media_box).ok_or_else(|| failed("a page's media box is not four numbers"))
+}
+
+fn failed(what: &str) -> ConvertError {
+    ConvertError::Render(format!("cannot stamp PDF: {what}"))
+}
+
+#[cfg(test)]
+#[path = "pdf_ops_stamp_tests.rs"]
+mod tests;
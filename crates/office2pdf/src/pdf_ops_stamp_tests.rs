@@ -0,0 +1,184 @@
+use super::*;
+use crate::config::StampPages;
+use crate::pdf_ops::test_support::{TestPdf, labelled_page, page_content};
+
+/// A PDF of `num_pages` A4 pages sharing their resources through the page
+/// tree, each with one content stream.
+fn make_test_pdf(num_pages: u32) -> Vec<u8> {
+    let mut pdf = TestPdf::new();
+    let font_id: ObjectId = pdf.add_font("Courier");
+    pdf.tree_entry(
+        "Resources",
+        dictionary! { "Font" => dictionary! { "F1" => font_id } },
+    );
+    for number in 1..=num_pages {
+        pdf.add_page(&labelled_page("Page", number), Dictionary::new());
+    }
+    pdf.save()
+}
+
+fn page_resources(doc: &Document, number: u32) -> &Dictionary {
+    let page_id: ObjectId = doc.get_pages()[&number];
+    doc.get_dictionary(page_id)
+        .unwrap()
+        .get(b"Resources")
+        .unwrap()
+        .as_dict()
+        .unwrap()
+}
+
+fn png(width: u32, height: u32, alpha: u8) -> Vec<u8> {
+    let image = image::RgbaImage::from_pixel(width, height, image::Rgba([255, 0, 0, alpha]));
+    let mut output: Vec<u8> = Vec::new();
+    image
+        .write_to(
+            &mut std::io::Cursor::new(&mut output),
+            image::ImageFormat::Png,
+        )
+        .unwrap();
+    output
+}
+
+#[test]
+fn test_stamp_text_draws_after_page_content_as_artifact() {
+    let stamped: Vec<u8> = stamp(&make_test_pdf(1), &Stamp::text("DRAFT")).unwrap();
+    let doc: Document = Document::load_mem(&stamped).unwrap();
+    let content: String = page_content(&doc, 1);
+    let original: usize = content.find("(Page 1) Tj").unwrap();
+    let drawn: usize = content.find("(DRAFT) Tj").unwrap();
+    assert!(original < drawn, "stamp must be drawn over the page");
+    assert!(content.starts_with('q'), "page content must be isolated");
+    assert!(content.contains("/Artifact BMC"));
+    assert!(content.contains("/O2PStampGS gs"));
+}
+
+#[test]
+fn test_stamp_keeps_inherited_resources() {
+    let stamped: Vec<u8> = stamp(&make_test_pdf(1), &Stamp::text("DRAFT")).unwrap();
+    let doc: Document = Document::load_mem(&stamped).unwrap();
+    let fonts: &Dictionary = page_resources(&doc, 1)
+        .get(b"Font")
+        .unwrap()
+        .as_dict()
+        .unwrap();
+    assert!(fonts.has(b"F1"), "the page's own font must survive");
+    let font: &Dictionary = fonts.get(b"O2PStampFont").unwrap().as_dict().unwrap();
+    assert_eq!(
+        font.get(b"BaseFont").unwrap().as_name().unwrap(),
+        b"Helvetica"
+    );
+}
+
+#[test]
+fn test_stamp_opacity_sets_graphics_state_alpha() {
+    let mut mark: Stamp = Stamp::text("DRAFT");
+    mark.opacity = 0.25;
+    let stamped: Vec<u8> = stamp(&make_test_pdf(1), &mark).unwrap();
+    let doc: Document = Document::load_mem(&stamped).unwrap();
+    let state: &Dictionary = page_resources(&doc, 1)
+        .get(b"ExtGState")
+        .unwrap()
+        .as_dict()
+        .unwrap()
+        .get(b"O2PStampGS")
+        .unwrap()
+        .as_dict()
+        .unwrap();
+    assert_eq!(state.get(b"ca").unwrap().as_float().unwrap(), 0.25);
+    assert_eq!(state.get(b"CA").unwrap().as_float().unwrap(), 0.25);
+}
+
+#[test]
+fn test_stamp_replaces_page_placeholders() {
+    let mark: Stamp = Stamp::text("DOC-{page} of {pages}");
+    let stamped: Vec<u8> = stamp(&make_test_pdf(3), &mark).unwrap();
+    let doc: Document = Document::load_mem(&stamped).unwrap();
+    assert!(page_content(&doc, 1).contains("(DOC-1 of 3) Tj"));
+    assert!(page_content(&doc, 3).contains("(DOC-3 of 3) Tj"));
+}
+
+#[test]
+fn test_stamp_only_selected_pages() {
+    let mut mark: Stamp = Stamp::text("DRAFT");
+    mark.pages = StampPages::Ranges(vec![(2, 3)]);
+    let stamped: Vec<u8> = stamp(&make_test_pdf(4), &mark).unwrap();
+    let doc: Document = Document::load_mem(&stamped).unwrap();
+    let stamped_pages: Vec<u32> = (1..=4)
+        .filter(|&number| page_content(&doc, number).contains("(DRAFT) Tj"))
+        .collect();
+    assert_eq!(stamped_pages, vec![2, 3]);
+}
+
+#[test]
+fn test_stamp_position_bottom_right_sits_inside_margin() {
+    let mut mark: Stamp = Stamp::text("X");
+    mark.position = StampPosition::BottomRight;
+    mark.content = StampContent::Text {
+        text: "X".to_string(),
+        font_size: 10.0,
+        color: Color::new(0, 0, 0),
+    };
+    let stamped: Vec<u8> = stamp(&make_test_pdf(1), &mark).unwrap();
+    let doc: Document = Document::load_mem(&stamped).unwrap();
+    // "X" is 6.67 pt wide and 7.18 pt tall at 10 pt: its center sits half
+    // of that in from the 36 pt margin.
+    assert!(
+        page_content(&doc, 1).contains("1 0 0 1 555.665 39.590 cm"),
+        "{}",
+        page_content(&doc, 1)
+    );
+}
+
+#[test]
+fn test_stamp_escapes_text() {
+    let stamped: Vec<u8> = stamp(&make_test_pdf(1), &Stamp::text("A (B) \\ C")).unwrap();
+    let doc: Document = Document::load_mem(&stamped).unwrap();
+    assert!(page_content(&doc, 1).contains("(A \\(B\\) \\\\ C) Tj"));
+}
+
+#[test]
+fn test_stamp_image_adds_xobject() {
+    let mark: Stamp = Stamp::image(png(4, 2, 255), 100.0);
+    let stamped: Vec<u8> = stamp(&make_test_pdf(1), &mark).unwrap();
+    let doc: Document = Document::load_mem(&stamped).unwrap();
+    assert!(
+        page_content(&doc, 1).contains("100.000 0 0 50.000 -50.000 -25.000 cm /O2PStampImage Do")
+    );
+    let image_id: ObjectId = page_resources(&doc, 1)
+        .get(b"XObject")
+        .unwrap()
+        .as_dict()
+        .unwrap()
+        .get(b"O2PStampImage")
+        .unwrap()
+        .as_reference()
+        .unwrap();
+    let image: &Stream = doc.get_object(image_id).unwrap().as_stream().unwrap();
+    assert_eq!(image.dict.get(b"Width").unwrap().as_i64().unwrap(), 4);
+    assert!(!image.dict.has(b"SMask"), "an opaque image needs no mask");
+}
+
+#[test]
+fn test_stamp_translucent_image_has_soft_mask() {
+    let mark: Stamp = Stamp::image(png(2, 2, 128), 20.0);
+    let stamped: Vec<u8> = stamp(&make_test_pdf(1), &mark).unwrap();
+    let doc: Document = Document::load_mem(&stamped).unwrap();
+    let has_mask: bool = doc.objects.values().any(|object| {
+        object
+            .as_stream()
+            .is_ok_and(|stream| stream.dict.has(b"SMask"))
+    });
+    assert!(has_mask);
+}
+
+#[test]
+fn test_stamp_rejects_unreadable_image() {
+    let mark: Stamp = Stamp::image(b"not an image".to_vec(), 20.0);
+    let err: ConvertError = stamp(&make_test_pdf(1), &mark).unwrap_err();
+    assert!(matches!(err, ConvertError::InvalidOptions(_)), "{err:?}");
+}
+
+#[test]
+fn test_stamp_rejects_invalid_pdf() {
+    assert!(stamp(b"not a pdf", &Stamp::text("DRAFT")).is_err());
+}
@@ -0,0 +1,113 @@
+//! Small PDFs for the `pdf_ops` tests. Every test builds the same page
+//! tree and catalog around the few page entries it cares about, so that
+//! part lives here once.
+
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream, dictionary};
+
+/// A PDF being built: pages are added in order under one page tree whose
+/// entries they inherit, an A4 media box unless a test replaces it.
+pub(crate) struct TestPdf {
+    pub(crate) doc: Document,
+    pub(crate) pages_id: ObjectId,
+    /// The pages added so far, in order.
+    pub(crate) page_ids: Vec<ObjectId>,
+    tree: Dictionary,
+}
+
+impl TestPdf {
+    pub(crate) fn new() -> Self {
+        let mut doc: Document = Document::with_version("1.7");
+        let pages_id: ObjectId = doc.new_object_id();
+        Self {
+            doc,
+            pages_id,
+            page_ids: Vec::new(),
+            tree: dictionary! {
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            },
+        }
+    }
+
+    /// Set an entry of the page tree for every page to inherit, replacing
+    /// the A4 media box when `key` is `MediaBox`.
+    pub(crate) fn tree_entry(&mut self, key: &str, value: impl Into<Object>) -> &mut Self {
+        self.tree.set(key, value);
+        self
+    }
+
+    /// Add a standard 14 font, returning its object.
+    pub(crate) fn add_font(&mut self, base_font: &str) -> ObjectId {
+        self.doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => base_font,
+        })
+    }
+
+    /// Add a page drawing `content`, with `entries` such as its resources or
+    /// page boxes set on its dictionary.
+    pub(crate) fn add_page(&mut self, content: &str, entries: Dictionary) -> ObjectId {
+        let content_id: ObjectId = self
+            .doc
+            .add_object(Stream::new(dictionary! {}, content.as_bytes().to_vec()));
+        let mut page: Dictionary = dictionary! {
+            "Type" => "Page",
+            "Parent" => self.pages_id,
+            "Contents" => content_id,
+        };
+        set_all(&mut page, &entries);
+        let page_id: ObjectId = self.doc.add_object(page);
+        self.page_ids.push(page_id);
+        page_id
+    }
+
+    pub(crate) fn save(self) -> Vec<u8> {
+        self.save_with_catalog(Dictionary::new())
+    }
+
+    /// Write the page tree and a catalog with `catalog` entries such as
+    /// `Outlines`, and save the PDF.
+    pub(crate) fn save_with_catalog(mut self, catalog: Dictionary) -> Vec<u8> {
+        let kids: Vec<Object> = self.page_ids.iter().map(|&id| id.into()).collect();
+        let mut tree: Dictionary = dictionary! {
+            "Type" => "Pages",
+            "Count" => kids.len() as i64,
+            "Kids" => kids,
+        };
+        set_all(&mut tree, &self.tree);
+        self.doc
+            .objects
+            .insert(self.pages_id, Object::Dictionary(tree));
+
+        let mut root: Dictionary = dictionary! {
+            "Type" => "Catalog",
+            "Pages" => self.pages_id,
+        };
+        set_all(&mut root, &catalog);
+        let catalog_id: ObjectId = self.doc.add_object(root);
+        self.doc.trailer.set("Root", catalog_id);
+
+        let mut output: Vec<u8> = Vec::new();
+        self.doc.save_to(&mut output).unwrap();
+        output
+    }
+}
+
+/// Copy `entries` into `dict`, replacing what it had under their keys;
+/// lopdf's `Dictionary::extend` would keep both values in an array.
+fn set_all(dict: &mut Dictionary, entries: &Dictionary) {
+    for (key, value) in entries.iter() {
+        dict.set(key.clone(), value.clone());
+    }
+}
+
+/// A content stream showing `label` and the page `number` in `/F1`.
+pub(crate) fn labelled_page(label: &str, number: u32) -> String {
+    format!("BT /F1 12 Tf 100 700 Td ({label} {number}) Tj ET")
+}
+
+/// The content of page `number`, counted from 1, as text.
+pub(crate) fn page_content(doc: &Document, number: u32) -> String {
+    let page_id: ObjectId = doc.get_pages()[&number];
+    String::from_utf8_lossy(&doc.get_page_content(page_id).unwrap()).into_owned()
+}
@@ -101,6 +101,37 @@ fn test_page_count_invalid_pdf() {
 
 // --- merge tests ---
 
+#[test]
+fn test_extract_text_from_rendered_document() {
+    let doc = crate::test_support::make_simple_document("Quarterly revenue");
+    let pdf = crate::render_document(&doc).unwrap();
+    let pages: Vec<PageText> = extract_text(&pdf).unwrap();
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].page, 1);
+    let text: String = pages[0].text.split_whitespace().collect();
+    assert!(
+        text.contains("Quarterlyrevenue"),
+        "got: {:?}",
+        pages[0].text
+    );
+    // Typst's fonts carry widths and a `ToUnicode` map, so the words come
+    // with their places on the page.
+    let words: Vec<&str> = pages[0]
+        .words
+        .iter()
+        .map(|word| word.text.as_str())
+        .collect();
+    assert_eq!(words, vec!["Quarterly", "revenue"]);
+    let [quarterly, revenue] = [&pages[0].words[0].bbox, &pages[0].words[1].bbox];
+    assert!(quarterly.x + quarterly.width < revenue.x);
+    assert!((quarterly.y - revenue.y).abs() < 1.0);
+}
+
+#[test]
+fn test_extract_text_invalid_pdf() {
+    assert!(extract_text(b"not a pdf").is_err());
+}
+
 #[test]
 fn test_merge_two_single_page_pdfs() {
     let pdf1 = make_test_pdf(1);
@@ -245,3 +276,495 @@ fn test_split_and_merge_round_trip() {
     let merged = merge(&[&parts[0], &parts[1]]).unwrap();
     assert_eq!(page_count(&merged).unwrap(), 4);
 }
+
+#[test]
+fn test_set_outline_adds_one_bookmark_per_entry() {
+    let pdf: Vec<u8> = make_test_pdf(3);
+    let entries: Vec<(String, u32)> = vec![("Intro".to_string(), 1), ("Résumé".to_string(), 3)];
+    let bookmarked: Vec<u8> = set_outline(&pdf, &entries).unwrap();
+
+    let doc: Document = Document::load_mem(&bookmarked).unwrap();
+    let catalog = doc.catalog().unwrap();
+    assert_eq!(
+        catalog.get(b"PageMode").unwrap().as_name().unwrap(),
+        b"UseOutlines"
+    );
+    let outlines = doc
+        .get_dictionary(catalog.get(b"Outlines").unwrap().as_reference().unwrap())
+        .unwrap();
+    assert_eq!(outlines.get(b"Count").unwrap().as_i64().unwrap(), 2);
+
+    let first = doc
+        .get_dictionary(outlines.get(b"First").unwrap().as_reference().unwrap())
+        .unwrap();
+    assert_eq!(first.get(b"Title").unwrap().as_str().unwrap(), b"Intro");
+    let last = doc
+        .get_dictionary(first.get(b"Next").unwrap().as_reference().unwrap())
+        .unwrap();
+    let title: &[u8] = last.get(b"Title").unwrap().as_str().unwrap();
+    assert_eq!(&title[..2], &[0xFE, 0xFF], "non-ASCII titles are UTF-16BE");
+    let third_page = doc.get_pages()[&3];
+    let dest = last.get(b"Dest").unwrap().as_array().unwrap();
+    assert_eq!(dest[0].as_reference().unwrap(), third_page);
+}
+
+#[test]
+fn test_set_outline_rejects_page_past_end() {
+    let pdf: Vec<u8> = make_test_pdf(1);
+    assert!(set_outline(&pdf, &[("Missing".to_string(), 2)]).is_err());
+}
+
+#[test]
+fn test_copy_info_sets_source_information_dictionary() {
+    let mut source: Document = Document::load_mem(&make_test_pdf(1)).unwrap();
+    let info_id = source.add_object(dictionary! {
+        "Title" => lopdf::Object::string_literal("Quarterly review"),
+    });
+    source.trailer.set("Info", info_id);
+    let mut source_pdf: Vec<u8> = Vec::new();
+    source.save_to(&mut source_pdf).unwrap();
+
+    let merged: Vec<u8> = merge(&[&source_pdf, &make_test_pdf(1)]).unwrap();
+    let copied: Vec<u8> = copy_info(&merged, &source_pdf).unwrap();
+    let doc: Document = Document::load_mem(&copied).unwrap();
+    let info = doc
+        .get_dictionary(doc.trailer.get(b"Info").unwrap().as_reference().unwrap())
+        .unwrap();
+    assert_eq!(
+        info.get(b"Title").unwrap().as_str().unwrap(),
+        b"Quarterly review"
+    );
+
+    // Without an information dictionary there is nothing to copy.
+    assert_eq!(copy_info(&merged, &make_test_pdf(1)).unwrap(), merged);
+}
+
+/// The XMP packet of `pdf`, if it has one.
+fn xmp(pdf: &[u8]) -> Option<String> {
+    let doc: Document = Document::load_mem(pdf).unwrap();
+    let id: lopdf::ObjectId = doc
+        .catalog()
+        .ok()?
+        .get(b"Metadata")
+        .ok()?
+        .as_reference()
+        .ok()?;
+    let stream: &lopdf::Stream = doc.get_object(id).ok()?.as_stream().ok()?;
+    Some(
+        String::from_utf8(
+            stream
+                .decompressed_content()
+                .unwrap_or(stream.content.clone()),
+        )
+        .unwrap(),
+    )
+}
+
+#[test]
+fn test_set_document_properties_writes_info_and_xmp() {
+    let mut source = crate::test_support::make_simple_document("Body");
+    source.metadata.title = Some("Quarterly review".to_string());
+    let pdf: Vec<u8> = crate::render_document(&source).unwrap();
+    let metadata = Metadata {
+        company: Some("Smith & Söhne".to_string()),
+        custom_properties: vec![
+            ("Project".to_string(), "R<D>".to_string()),
+            ("Due date".to_string(), "2024-01-31".to_string()),
+            ("Title".to_string(), "Not the title".to_string()),
+        ],
+        ..Metadata::default()
+    };
+    let output: Vec<u8> = set_document_properties(&pdf, &metadata).unwrap();
+
+    let doc: Document = Document::load_mem(&output).unwrap();
+    let info: &lopdf::Dictionary = doc
+        .get_dictionary(doc.trailer.get(b"Info").unwrap().as_reference().unwrap())
+        .unwrap();
+    let company: Vec<u8> = info.get(b"Company").unwrap().as_str().unwrap().to_vec();
+    assert_eq!(&company[..2], &[0xFE, 0xFF], "non-ASCII text is UTF-16");
+    assert_eq!(info.get(b"Project").unwrap().as_str().unwrap(), b"R<D>");
+    assert_eq!(
+        info.get(b"Due date").unwrap().as_str().unwrap(),
+        b"2024-01-31"
+    );
+    assert_eq!(
+        info.get(b"Title").unwrap().as_str().unwrap(),
+        b"Quarterly review",
+        "custom properties never replace standard entries"
+    );
+
+    let xmp: String = xmp(&output).expect("Typst writes XMP metadata");
+    assert!(
+        xmp.contains("<pdfx:Company>Smith &amp; Söhne</pdfx:Company>"),
+        "{xmp}"
+    );
+    assert!(
+        xmp.contains("<pdfx:Project>R&lt;D&gt;</pdfx:Project>"),
+        "{xmp}"
+    );
+    assert!(!xmp.contains("Due date"), "not an XML name: {xmp}");
+
+    // Nothing to add leaves the file alone.
+    assert_eq!(
+        set_document_properties(&pdf, &Metadata::default()).unwrap(),
+        pdf
+    );
+}
+
+#[test]
+fn test_set_document_properties_keeps_pdf_a_xmp_untouched() {
+    let mut source = crate::test_support::make_simple_document("Body");
+    source.metadata.title = Some("Archived".to_string());
+    let pdf: Vec<u8> = crate::render::pdf::compile_to_pdf(
+        &crate::render::typst_gen::generate_typst(&source)
+            .unwrap()
+            .source,
+        &[],
+        Some(crate::config::PdfStandard::PdfA2b),
+        &[],
+        false,
+        false,
+    )
+    .unwrap();
+    let metadata = Metadata {
+        company: Some("Acme".to_string()),
+        ..Metadata::default()
+    };
+    let output: Vec<u8> = set_document_properties(&pdf, &metadata).unwrap();
+    let xmp: String = xmp(&output).unwrap();
+    assert!(xmp.contains("pdfaid:"));
+    assert!(
+        !xmp.contains("pdfx:Company"),
+        "needs an extension schema in PDF/A"
+    );
+
+    let doc: Document = Document::load_mem(&output).unwrap();
+    let info: &lopdf::Dictionary = doc
+        .get_dictionary(doc.trailer.get(b"Info").unwrap().as_reference().unwrap())
+        .unwrap();
+    assert_eq!(info.get(b"Company").unwrap().as_str().unwrap(), b"Acme");
+}
+
+#[test]
+fn test_attach_sources_lists_files_as_source_attachments() {
+    let docx: &[u8] = b"PK docx bytes";
+    let xlsx: &[u8] = b"PK xlsx bytes";
+    let output: Vec<u8> = attach_sources(
+        &make_test_pdf(1),
+        &[(docx, Format::Docx), (xlsx, Format::Xlsx)],
+    )
+    .unwrap();
+
+    let doc: Document = Document::load_mem(&output).unwrap();
+    let catalog: &lopdf::Dictionary = doc.catalog().unwrap();
+    let associated: &Vec<lopdf::Object> = catalog.get(b"AF").unwrap().as_array().unwrap();
+    assert_eq!(associated.len(), 2);
+    let entries: &Vec<lopdf::Object> = catalog
+        .get(b"Names")
+        .and_then(lopdf::Object::as_dict)
+        .and_then(|names| names.get(b"EmbeddedFiles"))
+        .and_then(lopdf::Object::as_dict)
+        .and_then(|tree| tree.get(b"Names"))
+        .and_then(lopdf::Object::as_array)
+        .unwrap();
+    assert_eq!(entries[0].as_str().unwrap(), b"source-1.docx");
+    assert_eq!(entries[2].as_str().unwrap(), b"source-2.xlsx");
+
+    let spec: &lopdf::Dictionary = doc
+        .get_dictionary(entries[1].as_reference().unwrap())
+        .unwrap();
+    assert_eq!(
+        spec.get(b"AFRelationship").unwrap().as_name().unwrap(),
+        b"Source"
+    );
+    let file_id: lopdf::ObjectId = spec
+        .get(b"EF")
+        .and_then(lopdf::Object::as_dict)
+        .and_then(|files| files.get(b"F"))
+        .and_then(lopdf::Object::as_reference)
+        .unwrap();
+    let file: &lopdf::Stream = doc.get_object(file_id).unwrap().as_stream().unwrap();
+    assert_eq!(file.content, docx);
+    assert_eq!(
+        file.dict.get(b"Subtype").unwrap().as_name().unwrap(),
+        Format::Docx.mime_type().as_bytes()
+    );
+
+    let single: Vec<u8> = attach_sources(&make_test_pdf(1), &[(docx, Format::Docx)]).unwrap();
+    assert!(String::from_utf8_lossy(&single).contains("(source.docx)"));
+}
+
+#[test]
+fn test_encrypt_requires_password_and_limits_permissions() {
+    let security: PdfSecurity = PdfSecurity {
+        user_password: "open sesame".to_string(),
+        owner_password: "owner".to_string(),
+        allow_print: true,
+        ..Default::default()
+    };
+    let encrypted: Vec<u8> = encrypt(&make_test_pdf(2), &security).unwrap();
+    let raw: String = String::from_utf8_lossy(&encrypted).into_owned();
+    assert!(
+        raw.contains("/Encrypt"),
+        "trailer points at the security handler"
+    );
+    assert!(raw.contains("/AESV3"), "AES-256 crypt filter");
+    assert!(
+        !raw.contains("(Page 1)"),
+        "content is not readable in the clear"
+    );
+
+    assert!(Document::load_mem_with_password(&encrypted, "wrong").is_err());
+    let doc: Document = Document::load_mem_with_password(&encrypted, "open sesame").unwrap();
+    assert_eq!(doc.get_pages().len(), 2);
+
+    let granted: Permissions = permissions(&security);
+    assert!(granted.contains(Permissions::PRINTABLE));
+    assert!(granted.contains(Permissions::COPYABLE_FOR_ACCESSIBILITY));
+    assert!(!granted.contains(Permissions::COPYABLE));
+    assert!(!granted.contains(Permissions::MODIFIABLE));
+    assert!(!granted.contains(Permissions::ANNOTABLE));
+}
+
+#[test]
+fn test_decrypt_reverses_encrypt() {
+    let security: PdfSecurity = PdfSecurity {
+        user_password: "open sesame".to_string(),
+        owner_password: "owner".to_string(),
+        ..Default::default()
+    };
+    let encrypted: Vec<u8> = encrypt(&make_test_pdf(2), &security).unwrap();
+    assert!(matches!(
+        encrypt(&encrypted, &security),
+        Err(ConvertError::InvalidOptions(_))
+    ));
+    assert!(matches!(
+        decrypt(&encrypted, "wrong"),
+        Err(ConvertError::IncorrectPassword)
+    ));
+
+    for password in ["open sesame", "owner"] {
+        let decrypted: Vec<u8> = decrypt(&encrypted, password).unwrap();
+        assert!(!String::from_utf8_lossy(&decrypted).contains("/Encrypt"));
+        let doc: Document = Document::load_mem(&decrypted).unwrap();
+        assert!(!doc.was_encrypted());
+        assert_eq!(doc.get_pages().len(), 2);
+    }
+
+    assert!(matches!(
+        decrypt(&make_test_pdf(1), ""),
+        Err(ConvertError::InvalidOptions(_))
+    ));
+    let no_owner: PdfSecurity = PdfSecurity::default();
+    assert!(matches!(
+        encrypt(&make_test_pdf(1), &no_owner),
+        Err(ConvertError::InvalidOptions(_))
+    ));
+}
+
+#[test]
+fn test_compact_packs_objects_and_drops_orphans() {
+    let mut doc: Document = Document::load_mem(&make_test_pdf(2)).unwrap();
+    let metadata_id: lopdf::ObjectId = doc.add_object(lopdf::Stream::new(
+        dictionary! { "Type" => "Metadata", "Subtype" => "XML" },
+        "<x:xmpmeta>compact me not</x:xmpmeta>"
+            .repeat(8)
+            .into_bytes(),
+    ));
+    doc.catalog_mut().unwrap().set("Metadata", metadata_id);
+    doc.add_object(dictionary! { "Type" => "Orphan" });
+    let mut pdf: Vec<u8> = Vec::new();
+    doc.save_to(&mut pdf).unwrap();
+
+    let compacted: Vec<u8> = compact(&pdf).unwrap();
+    let raw: String = String::from_utf8_lossy(&compacted).into_owned();
+    assert!(raw.contains("/ObjStm"), "objects go into object streams");
+    assert!(raw.contains("/XRef"), "cross-reference stream");
+    assert!(
+        raw.contains("compact me not"),
+        "XMP metadata stays readable"
+    );
+
+    let doc: Document = Document::load_mem(&compacted).unwrap();
+    assert_eq!(doc.get_pages().len(), 2);
+    let orphaned: bool = doc.objects.values().any(|object| {
+        object
+            .as_dict()
+            .and_then(|dict| dict.get(b"Type"))
+            .and_then(lopdf::Object::as_name)
+            .is_ok_and(|name| name == b"Orphan")
+    });
+    assert!(!orphaned);
+    let content: Vec<u8> = doc.get_page_content(doc.get_pages()[&2]).unwrap();
+    assert!(String::from_utf8_lossy(&content).contains("(Page 2) Tj"));
+}
+
+#[test]
+fn test_compact_rejects_encrypted_pdf() {
+    let security: PdfSecurity = PdfSecurity {
+        owner_password: "owner".to_string(),
+        ..Default::default()
+    };
+    let encrypted: Vec<u8> = encrypt(&make_test_pdf(1), &security).unwrap();
+    let err: ConvertError = compact(&encrypted).unwrap_err();
+    assert!(matches!(err, ConvertError::InvalidOptions(_)), "{err:?}");
+}
+
+// --- Rotate, reorder and delete tests ---
+
+/// The text marker of each page of `pdf`, in page order.
+fn page_markers(pdf: &[u8]) -> Vec<String> {
+    let doc: Document = Document::load_mem(pdf).unwrap();
+    doc.get_pages()
+        .into_values()
+        .map(|page_id| {
+            let content: Vec<u8> = doc.get_page_content(page_id).unwrap();
+            let content: String = String::from_utf8_lossy(&content).into_owned();
+            let start: usize = content.find('(').unwrap() + 1;
+            let end: usize = content.find(')').unwrap();
+            content[start..end].to_string()
+        })
+        .collect()
+}
+
+fn page_rotations(pdf: &[u8]) -> Vec<i64> {
+    let doc: Document = Document::load_mem(pdf).unwrap();
+    doc.get_pages()
+        .into_values()
+        .map(|page_id| {
+            inherited_attribute(&doc, page_id, b"Rotate")
+                .and_then(|rotation| rotation.as_i64().ok())
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+/// Three pages in a two-level page tree: the first two under a node that
+/// sets their media box and rotation, the third straight under the root.
+fn make_nested_pdf() -> Vec<u8> {
+    let mut doc = Document::with_version("1.7");
+    let root_id = doc.new_object_id();
+    let node_id = doc.new_object_id();
+    let mut page = |number: u32, parent: lopdf::ObjectId| -> lopdf::ObjectId {
+        let content = format!("BT /F1 12 Tf 100 700 Td (Page {number}) Tj ET");
+        let content_id = doc.add_object(lopdf::Stream::new(dictionary! {}, content.into_bytes()));
+        doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => parent,
+            "Contents" => content_id,
+        })
+    };
+    let first = page(1, node_id);
+    let second = page(2, node_id);
+    let third = page(3, root_id);
+    doc.objects.insert(
+        node_id,
+        lopdf::Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Parent" => root_id,
+            "Count" => 2,
+            "Kids" => vec![first.into(), second.into()],
+            "MediaBox" => vec![0.into(), 0.into(), 300.into(), 400.into()],
+            "Rotate" => 90,
+        }),
+    );
+    doc.objects.insert(
+        root_id,
+        lopdf::Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Count" => 3,
+            "Kids" => vec![node_id.into(), third.into()],
+            "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+        }),
+    );
+    let catalog_id = doc.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => root_id,
+    });
+    doc.trailer.set("Root", catalog_id);
+    let mut output = Vec::new();
+    doc.save_to(&mut output).unwrap();
+    output
+}
+
+#[test]
+fn test_rotate_adds_to_current_rotation() {
+    let rotated: Vec<u8> = rotate(&make_nested_pdf(), &[PageRange::new(2, 3)], 90).unwrap();
+    assert_eq!(page_rotations(&rotated), vec![90, 180, 90]);
+
+    let rotated: Vec<u8> = rotate(&rotated, &[PageRange::new(1, 3)], -270).unwrap();
+    assert_eq!(page_rotations(&rotated), vec![180, 270, 180]);
+}
+
+#[test]
+fn test_rotate_rejects_bad_angle_and_pages() {
+    let pdf: Vec<u8> = make_test_pdf(2);
+    assert!(rotate(&pdf, &[PageRange::new(1, 1)], 45).is_err());
+    assert!(rotate(&pdf, &[PageRange::new(3, 3)], 90).is_err());
+    assert!(rotate(&pdf, &[], 90).is_err());
+    assert!(rotate(b"not a pdf", &[PageRange::new(1, 1)], 90).is_err());
+}
+
+#[test]
+fn test_reorder_puts_pages_in_order() {
+    let reordered: Vec<u8> = reorder(&make_test_pdf(3), &[3, 1, 2]).unwrap();
+    assert_eq!(page_markers(&reordered), vec!["Page 3", "Page 1", "Page 2"]);
+}
+
+#[test]
+fn test_reorder_flattens_nested_page_tree() {
+    let reordered: Vec<u8> = reorder(&make_nested_pdf(), &[3, 2, 1]).unwrap();
+    assert_eq!(page_markers(&reordered), vec!["Page 3", "Page 2", "Page 1"]);
+    assert_eq!(page_rotations(&reordered), vec![0, 90, 90]);
+
+    let doc: Document = Document::load_mem(&reordered).unwrap();
+    let media_boxes: Vec<lopdf::Object> = doc
+        .get_pages()
+        .into_values()
+        .map(|page_id| inherited_media_box(&doc, page_id).unwrap())
+        .collect();
+    assert_eq!(
+        media_boxes[1],
+        lopdf::Object::Array(vec![0.into(), 0.into(), 300.into(), 400.into()])
+    );
+    assert_eq!(
+        media_boxes[0],
+        lopdf::Object::Array(vec![0.into(), 0.into(), 595.into(), 842.into()])
+    );
+    let page_tree_nodes: usize = doc
+        .objects
+        .values()
+        .filter(|object| {
+            object
+                .as_dict()
+                .and_then(|dict| dict.get(b"Type"))
+                .and_then(lopdf::Object::as_name)
+                .is_ok_and(|name| name == b"Pages")
+        })
+        .count();
+    assert_eq!(page_tree_nodes, 1, "the old intermediate node is dropped");
+}
+
+#[test]
+fn test_reorder_rejects_incomplete_orders() {
+    let pdf: Vec<u8> = make_test_pdf(3);
+    assert!(reorder(&pdf, &[1, 2]).is_err());
+    assert!(reorder(&pdf, &[1, 2, 2]).is_err());
+    assert!(reorder(&pdf, &[1, 2, 4]).is_err());
+    assert!(reorder(&pdf, &[0, 1, 2]).is_err());
+}
+
+#[test]
+fn test_delete_removes_pages() {
+    let pdf: Vec<u8> = make_test_pdf(5);
+    let trimmed: Vec<u8> = delete(&pdf, &[PageRange::new(2, 3), PageRange::new(5, 5)]).unwrap();
+    assert_eq!(page_markers(&trimmed), vec!["Page 1", "Page 4"]);
+}
+
+#[test]
+fn test_delete_keeps_at_least_one_page() {
+    let pdf: Vec<u8> = make_test_pdf(2);
+    assert!(delete(&pdf, &[PageRange::new(1, 2)]).is_err());
+    assert!(delete(&pdf, &[PageRange::new(3, 3)]).is_err());
+    assert!(delete(&pdf, &[]).is_err());
+}
@@ -0,0 +1,683 @@
+//! Text extraction and search: the text of each page with where its words
+//! sit, and the places a phrase occurs, so converted documents can be
+//! indexed or highlighted without another PDF library.
+
+use std::collections::BTreeMap;
+
+use lopdf::content::Content;
+use lopdf::{Dictionary, Document, Encoding, Object};
+
+use super::{inherited_resources, load_pdf_document, text_width};
+use crate::error::ConvertError;
+
+/// A rectangle on a page, in points in the page's own coordinates: from the
+/// bottom-left corner of its media box in most PDFs, `y` growing upward.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    /// Left edge.
+    pub x: f64,
+    /// Bottom edge.
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// The text of one page of a PDF.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageText {
+    /// Page number, 1-indexed.
+    pub page: u32,
+    /// The page's text in content order, decoded through the fonts'
+    /// `ToUnicode` maps; layout whitespace is not preserved exactly.
+    pub text: String,
+    /// The page's words in content order, with where they are drawn.
+    pub words: Vec<Word>,
+}
+
+/// A run of text with no space or gap in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Word {
+    pub text: String,
+    pub bbox: BoundingBox,
+}
+
+/// An occurrence of the text [`find_text`] looks for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    /// Page number, 1-indexed.
+    pub page: u32,
+    /// The text found, as the page has it.
+    pub text: String,
+    /// One box per line the occurrence spans, in reading order.
+    pub boxes: Vec<BoundingBox>,
+}
+
+/// Nested form XObjects followed before giving up on a content stream, as
+/// a malformed PDF can have a form draw itself.
+const MAX_FORM_DEPTH: u8 = 8;
+
+/// Height of a glyph above and below the baseline, in thousandths of the
+/// font size, when the font does not say.
+const DEFAULT_ASCENT: f64 = 800.0;
+const DEFAULT_DESCENT: f64 = -200.0;
+
+/// Extract the text of each page of a PDF, in page order, with the words
+/// on each page and their bounding boxes.
+///
+/// Glyphs are decoded through the fonts' `ToUnicode` maps, so the result is
+/// only as faithful as the PDF's text layer. Bounding boxes come from the
+/// fonts' glyph widths and ascent and descent; for the standard 14 fonts,
+/// which carry no widths, those of Helvetica are assumed.
+pub fn extract_text(input: &[u8]) -> Result<Vec<PageText>, ConvertError> {
+    let doc: Document = load_pdf_document(input, "")?;
+    doc.get_pages()
+        .into_iter()
+        .map(|(page_number, page_id)| {
+            let text: String = doc.extract_text(&[page_number]).map_err(|e| {
+                ConvertError::Parse(format!(
+                    "failed to extract text from page {page_number}: {e}"
+                ))
+            })?;
+            let words: Vec<Word> = group_words(&page_glyphs(&doc, page_id))
+                .into_iter()
+                .map(|word| Word {
+                    text: word
+                        .glyphs
+                        .iter()
+                        .map(|glyph| glyph.text.as_str())
+                        .collect(),
+                    bbox: union(word.glyphs.iter().map(|glyph| glyph.bbox)),
+                })
+                .collect();
+            Ok(PageText {
+                page: page_number,
+                text,
+                words,
+            })
+        })
+        .collect()
+}
+
+/// Find every occurrence of `query` in a PDF, in page order.
+///
+/// The search ignores case and treats any run of whitespace, in `query`
+/// and between the words on the page, as a single space, so a phrase
+/// broken across lines is still found.
+pub fn find_text(input: &[u8], query: &str) -> Result<Vec<Match>, ConvertError> {
+    let needle: Vec<char> = fold_case(&query.split_whitespace().collect::<Vec<&str>>().join(" "));
+    if needle.is_empty() {
+        return Err(ConvertError::InvalidOptions(
+            "the text to find is empty".to_string(),
+        ));
+    }
+    let doc: Document = load_pdf_document(input, "")?;
+    let mut matches: Vec<Match> = Vec::new();
+    for (page_number, page_id) in doc.get_pages() {
+        let words: Vec<WordGlyphs> = group_words(&page_glyphs(&doc, page_id));
+        // The page as one line of text, each character knowing the glyph
+        // it came from; the spaces between words come from none.
+        let mut text: String = String::new();
+        let mut owners: Vec<Option<(usize, usize)>> = Vec::new();
+        for (word_index, word) in words.iter().enumerate() {
+            if word_index > 0 {
+                text.push(' ');
+                owners.push(None);
+            }
+            for (glyph_index, glyph) in word.glyphs.iter().enumerate() {
+                for ch in glyph.text.chars().filter(|ch| !ch.is_whitespace()) {
+                    text.push(ch);
+                    owners.push(Some((word_index, glyph_index)));
+                }
+            }
+        }
+        let original: Vec<char> = text.chars().collect();
+        let haystack: Vec<char> = fold_case(&text);
+
+        let mut start: usize = 0;
+        while start + needle.len() <= haystack.len() {
+            if haystack[start..start + needle.len()] != needle[..] {
+                start += 1;
+                continue;
+            }
+            let end: usize = start + needle.len();
+            let mut lines: Vec<(usize, Vec<[f64; 4]>)> = Vec::new();
+            for &(word_index, glyph_index) in owners[start..end].iter().flatten() {
+                let word: &WordGlyphs = &words[word_index];
+                let bbox: [f64; 4] = word.glyphs[glyph_index].bbox;
+                match lines.last_mut() {
+                    Some((line, boxes)) if *line == word.line => boxes.push(bbox),
+                    _ => lines.push((word.line, vec![bbox])),
+                }
+            }
+            matches.push(Match {
+                page: page_number,
+                text: original[start..end].iter().collect(),
+                boxes: lines
+                    .into_iter()
+                    .map(|(_, boxes)| union(boxes.into_iter()))
+                    .collect(),
+            });
+            start = end;
+        }
+    }
+    Ok(matches)
+}
+
+/// `text` in lowercase, one character for each of its own, so positions
+/// in the two agree.
+fn fold_case(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|ch| ch.to_lowercase().next().unwrap_or(ch))
+        .collect()
+}
+
+/// A glyph as drawn: its text, its box as `[left, bottom, right, top]`,
+/// where its baseline starts and ends, and its font size, all in the
+/// page's coordinates.
+#[derive(Debug, Clone)]
+struct Glyph {
+    text: String,
+    bbox: [f64; 4],
+    origin: (f64, f64),
+    end: (f64, f64),
+    size: f64,
+}
+
+/// A word's glyphs and the line it is on, counting from 0.
+struct WordGlyphs {
+    line: usize,
+    glyphs: Vec<Glyph>,
+}
+
+/// Split the glyphs of a page into words: at whitespace, at a gap wider
+/// than a fifth of the font size, and where the baseline moves to a new
+/// line.
+fn group_words(glyphs: &[Glyph]) -> Vec<WordGlyphs> {
+    let mut words: Vec<WordGlyphs> = Vec::new();
+    let mut current: Vec<Glyph> = Vec::new();
+    let mut line: usize = 0;
+    let mut previous: Option<&Glyph> = None;
+    for glyph in glyphs {
+        if let Some(previous) = previous {
+            let size: f64 = glyph.size.max(previous.size).max(1.0);
+            let new_line: bool = (glyph.origin.1 - previous.origin.1).abs() > size / 2.0
+                || glyph.origin.0 < previous.origin.0 - size / 2.0;
+            let gap: bool = glyph.origin.0 - previous.end.0 > size / 5.0;
+            if new_line || gap {
+                if !current.is_empty() {
+                    words.push(WordGlyphs {
+                        line,
+                        glyphs: std::mem::take(&mut current),
+                    });
+                }
+                if new_line {
+                    line += 1;
+                }
+            }
+        }
+        previous = Some(glyph);
+        if glyph.text.chars().all(char::is_whitespace) {
+            if !current.is_empty() {
+                words.push(WordGlyphs {
+                    line,
+                    glyphs: std::mem::take(&mut current),
+                });
+            }
+        } else {
+            current.push(glyph.clone());
+        }
+    }
+    if !current.is_empty() {
+        words.push(WordGlyphs {
+            line,
+            glyphs: current,
+        });
+    }
+    words
+}
+
+/// The smallest box holding every `[left, bottom, right, top]` of `boxes`.
+fn union(boxes: impl Iterator<Item = [f64; 4]>) -> BoundingBox {
+    let [left, bottom, right, top] = boxes
+        .reduce(|a, b| {
+            [
+                a[0].min(b[0]),
+                a[1].min(b[1]),
+                a[2].max(b[2]),
+                a[3].max(b[3]),
+            ]
+        })
+        .unwrap_or_default();
+    BoundingBox {
+        x: left,
+        y: bottom,
+        width: right - left,
+        height: top - bottom,
+    }
+}
+
+/// An affine transformation `[a b c d e f]`, as PDF writes them.
+type Matrix = [f64; 6];
+
+const IDENTITY: Matrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// `first` then `second`.
+fn multiply(first: Matrix, second: Matrix) -> Matrix {
+    let [a, b, c, d, e, f] = first;
+    let [a2, b2, c2, d2, e2, f2] = second;
+    [
+        a * a2 + b * c2,
+        a * b2 + b * d2,
+        c * a2 + d * c2,
+        c * b2 + d * d2,
+        e * a2 + f * c2 + e2,
+        e * b2 + f * d2 + f2,
+    ]
+}
+
+fn translate(x: f64, y: f64) -> Matrix {
+    [1.0, 0.0, 0.0, 1.0, x, y]
+}
+
+fn apply(matrix: Matrix, x: f64, y: f64) -> (f64, f64) {
+    let [a, b, c, d, e, f] = matrix;
+    (a * x + c * y + e, b * x + d * y + f)
+}
+
+fn number(object: &Object) -> Option<f64> {
+    match object {
+        Object::Integer(n) => Some(*n as f64),
+        Object::Real(n) => Some(f64::from(*n)),
+        _ => None,
+    }
+}
+
+/// Six numbers as a matrix; the identity if they are not.
+fn matrix(operands: &[Object]) -> Matrix {
+    let numbers: Vec<f64> = operands.iter().filter_map(number).collect();
+    numbers.as_slice().try_into().unwrap_or(IDENTITY)
+}
+
+/// The parts of the graphics state that place text.
+#[derive(Debug, Clone)]
+struct State {
+    ctm: Matrix,
+    char_spacing: f64,
+    word_spacing: f64,
+    horizontal_scale: f64,
+    leading: f64,
+    rise: f64,
+    font: Option<Vec<u8>>,
+    font_size: f64,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            ctm: IDENTITY,
+            char_spacing: 0.0,
+            word_spacing: 0.0,
+            horizontal_scale: 1.0,
+            leading: 0.0,
+            rise: 0.0,
+            font: None,
+            font_size: 0.0,
+        }
+    }
+}
+
+/// What it takes to decode and measure a font's glyphs.
+struct Font<'a> {
+    encoding: Option<Encoding<'a>>,
+    /// Type 0 fonts use two-byte codes, simple fonts one.
+    two_byte: bool,
+    /// Glyph widths by code, in thousandths of the font size.
+    widths: BTreeMap<u32, f64>,
+    default_width: Option<f64>,
+    ascent: f64,
+    descent: f64,
+}
+
+impl<'a> Font<'a> {
+    fn load(doc: &'a Document, font: &'a Dictionary) -> Self {
+        let get = |dict: &'a Dictionary, key: &[u8]| -> Option<&'a Object> {
+            dict.get(key)
+                .and_then(|value| doc.dereference(value))
+                .map(|(_, value)| value)
+                .ok()
+        };
+        let two_byte: bool =
+            get(font, b"Subtype").and_then(|s| s.as_name().ok()) == Some(b"Type0".as_slice());
+        // A Type 0 font's widths and metrics are in its descendant font.
+        let metrics: &Dictionary = if two_byte {
+            get(font, b"DescendantFonts")
+                .and_then(|fonts| fonts.as_array().ok())
+                .and_then(|fonts| fonts.first())
+                .and_then(|descendant| doc.dereference(descendant).ok())
+                .and_then(|(_, descendant)| descendant.as_dict().ok())
+                .unwrap_or(font)
+        } else {
+            font
+        };
+        let descriptor: Option<&Dictionary> =
+            get(metrics, b"FontDescriptor").and_then(|d| d.as_dict().ok());
+        let descriptor_number =
+            |key: &[u8]| -> Option<f64> { descriptor.and_then(|d| get(d, key)).and_then(number) };
+
+        let mut widths: BTreeMap<u32, f64> = BTreeMap::new();
+        let default_width: Option<f64>;
+        if two_byte {
+            // `c [w1 w2 ...]` gives widths from `c` on, `first last w` one
+            // width for a range.
+            let entries: Vec<&Object> = get(metrics, b"W")
+                .and_then(|w| w.as_array().ok())
+                .map(|w| w.iter().collect())
+                .unwrap_or_default();
+            let mut index: usize = 0;
+            while index + 1 < entries.len() {
+                let Some(first) = number(entries[index]).map(|n| n as u32) else {
+                    break;
+                };
+                match doc.dereference(entries[index + 1]).map(|(_, next)| next) {
+                    Ok(Object::Array(run)) => {
+                        for (offset, width) in run.iter().enumerate() {
+                            if let Some(width) = number(width) {
+                                widths.insert(first + offset as u32, width);
+                            }
+                        }
+                        index += 2;
+                    }
+                    Ok(last) => {
+                        let (Some(last), Some(width)) =
+                            (number(last), entries.get(index + 2).and_then(|w| number(w)))
+                        else {
+                            break;
+                        };
+                        // Bounded, as a malformed range could span billions.
+                        for code in first..=(last as u32).min(first.saturating_add(0xFFFF)) {
+                            widths.insert(code, width);
+                        }
+                        index += 3;
+                    }
+                    Err(_) => break,
+                }
+            }
+            default_width = Some(get(metrics, b"DW").and_then(number).unwrap_or(1000.0));
+        } else {
+            let first: u32 = get(font, b"FirstChar").and_then(number).unwrap_or(0.0) as u32;
+            if let Some(list) = get(font, b"Widths").and_then(|w| w.as_array().ok()) {
+                for (offset, width) in list.iter().enumerate() {
+                    if let Some(width) = doc.dereference(width).ok().and_then(|(_, w)| number(w)) {
+                        widths.insert(first + offset as u32, width);
+                    }
+                }
+                default_width = Some(descriptor_number(b"MissingWidth").unwrap_or(0.0));
+            } else {
+                // One of the standard 14 fonts, measured below.
+                default_width = None;
+            }
+        }
+
+        Font {
+            encoding: font.get_font_encoding(doc).ok(),
+            two_byte,
+            widths,
+            default_width,
+            ascent: descriptor_number(b"Ascent")
+                .filter(|&ascent| ascent > 0.0)
+                .unwrap_or(DEFAULT_ASCENT),
+            descent: descriptor_number(b"Descent")
+                .filter(|&descent| descent < 0.0)
+                .unwrap_or(DEFAULT_DESCENT),
+        }
+    }
+
+    /// The text of the glyph coded `bytes`.
+    fn decode(&self, bytes: &[u8]) -> String {
+        match self
+            .encoding
+            .as_ref()
+            .map(|encoding| encoding.bytes_to_string(bytes))
+        {
+            Some(Ok(text)) => text,
+            // Latin-1 is the best guess for a simple font, and nothing is
+            // for a Type 0 one.
+            _ if self.two_byte => char::REPLACEMENT_CHARACTER.to_string(),
+            _ => bytes.iter().map(|&byte| char::from(byte)).collect(),
+        }
+    }
+
+    /// Width of the glyph coded `code`, showing `text`, in thousandths of
+    /// the font size.
+    fn width(&self, code: u32, text: &str) -> f64 {
+        match (self.widths.get(&code), self.default_width) {
+            (Some(&width), _) => width,
+            (None, Some(width)) => width,
+            (None, None) => text_width(text) * 1000.0,
+        }
+    }
+}
+
+/// The glyphs drawn on a page, in content order.
+fn page_glyphs(doc: &Document, page_id: lopdf::ObjectId) -> Vec<Glyph> {
+    let mut glyphs: Vec<Glyph> = Vec::new();
+    if let Ok(content) = doc.get_page_content(page_id) {
+        let resources: Dictionary = inherited_resources(doc, page_id);
+        interpret(doc, &content, &resources, State::default(), 0, &mut glyphs);
+    }
+    glyphs
+}
+
+/// Follow a content stream's text operators, and the forms it draws,
+/// adding the glyphs it shows to `glyphs`.
+fn interpret(
+    doc: &Document,
+    content: &[u8],
+    resources: &Dictionary,
+    mut state: State,
+    depth: u8,
+    glyphs: &mut Vec<Glyph>,
+) {
+    let Ok(content) = Content::decode(content) else {
+        return;
+    };
+    let resource = |kind: &[u8]| -> Option<&Dictionary> {
+        resources
+            .get(kind)
+            .and_then(|entries| doc.dereference(entries))
+            .and_then(|(_, entries)| entries.as_dict())
+            .ok()
+    };
+    let fonts: BTreeMap<&[u8], Font> = resource(b"Font")
+        .into_iter()
+        .flat_map(|fonts| fonts.iter())
+        .filter_map(|(name, font)| {
+            let (_, font) = doc.dereference(font).ok()?;
+            Some((name.as_slice(), Font::load(doc, font.as_dict().ok()?)))
+        })
+        .collect();
+
+    let font_of =
+        |state: &State| -> Option<&Font> { state.font.as_deref().and_then(|name| fonts.get(name)) };
+    let mut saved: Vec<State> = Vec::new();
+    let (mut text_matrix, mut line_matrix): (Matrix, Matrix) = (IDENTITY, IDENTITY);
+    for operation in &content.operations {
+        let operands: &[Object] = &operation.operands;
+        let operand = |index: usize| -> f64 { operands.get(index).and_then(number).unwrap_or(0.0) };
+        match operation.operator.as_str() {
+            "q" => saved.push(state.clone()),
+            "Q" => state = saved.pop().unwrap_or(state),
+            "cm" => state.ctm = multiply(matrix(operands), state.ctm),
+            "BT" => (text_matrix, line_matrix) = (IDENTITY, IDENTITY),
+            "Tf" => {
+                state.font = operands
+                    .first()
+                    .and_then(|name| name.as_name().ok())
+                    .map(<[u8]>::to_vec);
+                state.font_size = operand(1);
+            }
+            "Tc" => state.char_spacing = operand(0),
+            "Tw" => state.word_spacing = operand(0),
+            "Tz" => state.horizontal_scale = operand(0) / 100.0,
+            "TL" => state.leading = operand(0),
+            "Ts" => state.rise = operand(0),
+            "Td" | "TD" => {
+                if operation.operator == "TD" {
+                    state.leading = -operand(1);
+                }
+                line_matrix = multiply(translate(operand(0), operand(1)), line_matrix);
+                text_matrix = line_matrix;
+            }
+            "Tm" => {
+                line_matrix = matrix(operands);
+                text_matrix = line_matrix;
+            }
+            "T*" | "'" | "\"" => {
+                if operation.operator == "\"" {
+                    state.word_spacing = operand(0);
+                    state.char_spacing = operand(1);
+                }
+                line_matrix = multiply(translate(0.0, -state.leading), line_matrix);
+                text_matrix = line_matrix;
+                if let (Some(Object::String(bytes, _)), Some(font)) =
+                    (operands.last(), font_of(&state))
+                    && operation.operator != "T*"
+                {
+                    show(bytes, font, &state, &mut text_matrix, glyphs);
+                }
+            }
+            "Tj" => {
+                if let (Some(Object::String(bytes, _)), Some(font)) =
+                    (operands.first(), font_of(&state))
+                {
+                    show(bytes, font, &state, &mut text_matrix, glyphs);
+                }
+            }
+            "TJ" => {
+                let (Some(Object::Array(items)), Some(font)) = (operands.first(), font_of(&state))
+                else {
+                    continue;
+                };
+                for item in items {
+                    match item {
+                        Object::String(bytes, _) => {
+                            show(bytes, font, &state, &mut text_matrix, glyphs)
+                        }
+                        // Moves the next glyph back by thousandths of the
+                        // font size.
+                        _ => {
+                            let shift: f64 = -number(item).unwrap_or(0.0) / 1000.0
+                                * state.font_size
+                                * state.horizontal_scale;
+                            text_matrix = multiply(translate(shift, 0.0), text_matrix);
+                        }
+                    }
+                }
+            }
+            "Do" if depth < MAX_FORM_DEPTH => {
+                let Some(form) = operands
+                    .first()
+                    .and_then(|name| name.as_name().ok())
+                    .and_then(|name| resource(b"XObject")?.get(name).ok())
+                    .and_then(|form| doc.dereference(form).ok())
+                    .and_then(|(_, form)| form.as_stream().ok())
+                    .filter(|form| {
+                        form.dict.get(b"Subtype").and_then(Object::as_name).ok()
+                            == Some(b"Form".as_slice())
+                    })
+                else {
+                    continue;
+                };
+                let content: Vec<u8> = form
+                    .decompressed_content()
+                    .unwrap_or_else(|_| form.content.clone());
+                // A form without resources of its own uses the page's.
+                let form_resources: &Dictionary = form
+                    .dict
+                    .get(b"Resources")
+                    .and_then(|entries| doc.dereference(entries))
+                    .and_then(|(_, entries)| entries.as_dict())
+                    .unwrap_or(resources);
+                let form_matrix: Matrix = form
+                    .dict
+                    .get(b"Matrix")
+                    .and_then(Object::as_array)
+                    .map(|numbers| matrix(numbers))
+                    .unwrap_or(IDENTITY);
+                let form_state = State {
+                    ctm: multiply(form_matrix, state.ctm),
+                    ..state.clone()
+                };
+                interpret(doc, &content, form_resources, form_state, depth + 1, glyphs);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Show the string `bytes` in `font`, adding its glyphs to `glyphs` and
+/// moving the text matrix past them.
+fn show(
+    bytes: &[u8],
+    font: &Font,
+    state: &State,
+    text_matrix: &mut Matrix,
+    glyphs: &mut Vec<Glyph>,
+) {
+    let size: f64 = state.font_size;
+    let scale: f64 = state.horizontal_scale;
+    let code_length: usize = if font.two_byte { 2 } else { 1 };
+    for code_bytes in bytes.chunks(code_length) {
+        let code: u32 = code_bytes
+            .iter()
+            .fold(0, |code, &byte| (code << 8) | u32::from(byte));
+        let text: String = font.decode(code_bytes);
+        let width: f64 = font.width(code, &text) / 1000.0;
+
+        // Glyph space, in units of the font size, to the page.
+        let to_page = multiply(*text_matrix, state.ctm);
+        let point = |x: f64, y: f64| apply(to_page, x * size * scale, y * size + state.rise);
+        if !text.is_empty() {
+            let (bottom, top): (f64, f64) = (font.descent / 1000.0, font.ascent / 1000.0);
+            let corners: [(f64, f64); 4] = [
+                point(0.0, bottom),
+                point(width, bottom),
+                point(0.0, top),
+                point(width, top),
+            ];
+            let bbox: [f64; 4] = corners.iter().fold(
+                [
+                    f64::INFINITY,
+                    f64::INFINITY,
+                    f64::NEG_INFINITY,
+                    f64::NEG_INFINITY,
+                ],
+                |[left, bottom, right, top], &(x, y)| {
+                    [left.min(x), bottom.min(y), right.max(x), top.max(y)]
+                },
+            );
+            let origin: (f64, f64) = point(0.0, 0.0);
+            let above: (f64, f64) = point(0.0, 1.0);
+            glyphs.push(Glyph {
+                text,
+                bbox,
+                origin,
+                end: point(width, 0.0),
+                size: (above.0 - origin.0).hypot(above.1 - origin.1),
+            });
+        }
+
+        // Word spacing applies to the single-byte space only.
+        let word_spacing: f64 = if code_bytes == [b' '] {
+            state.word_spacing
+        } else {
+            0.0
+        };
+        let advance: f64 = (width * size + state.char_spacing + word_spacing) * scale;
+        *text_matrix = multiply(translate(advance, 0.0), *text_matrix);
+    }
+}
+
+#[cfg(test)]
+#[path = "pdf_ops_text_tests.rs"]
+mod tests;
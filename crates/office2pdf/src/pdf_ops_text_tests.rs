@@ -0,0 +1,227 @@
+use super::*;
+use crate::pdf_ops::test_support::TestPdf;
+use lopdf::{Stream, dictionary};
+
+/// A PDF with one page per content stream in `pages`, each with `resources`.
+fn make_pdf(pages: &[&str], resources: Dictionary) -> Vec<u8> {
+    let mut pdf = TestPdf::new();
+    for content in pages {
+        pdf.add_page(content, dictionary! { "Resources" => resources.clone() });
+    }
+    pdf.save()
+}
+
+/// Helvetica, one of the standard 14 fonts, as `/F1`.
+fn helvetica() -> Dictionary {
+    dictionary! {
+        "Font" => dictionary! {
+            "F1" => dictionary! {
+                "Type" => "Font",
+                "Subtype" => "Type1",
+                "BaseFont" => "Helvetica",
+                "Encoding" => "WinAnsiEncoding",
+            },
+        },
+    }
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 0.01,
+        "expected {expected}, got {actual}"
+    );
+}
+
+fn word_texts(page: &PageText) -> Vec<&str> {
+    page.words.iter().map(|word| word.text.as_str()).collect()
+}
+
+#[test]
+fn test_extract_text_words_and_boxes() {
+    let pdf: Vec<u8> = make_pdf(
+        &["BT /F1 10 Tf 100 700 Td (Hello world) Tj ET"],
+        helvetica(),
+    );
+    let pages: Vec<PageText> = extract_text(&pdf).unwrap();
+    assert_eq!(pages.len(), 1);
+    assert!(pages[0].text.contains("Hello world"), "{}", pages[0].text);
+    assert_eq!(word_texts(&pages[0]), vec!["Hello", "world"]);
+
+    // Helvetica's widths, and the default ascent and descent.
+    let hello: &BoundingBox = &pages[0].words[0].bbox;
+    assert_close(hello.x, 100.0);
+    assert_close(hello.width, text_width("Hello") * 10.0);
+    assert_close(hello.y, 698.0);
+    assert_close(hello.height, 10.0);
+    let world: &BoundingBox = &pages[0].words[1].bbox;
+    assert_close(world.x, 100.0 + text_width("Hello ") * 10.0);
+}
+
+#[test]
+fn test_extract_text_follows_text_and_graphics_state() {
+    let resources = dictionary! {
+        "Font" => dictionary! {
+            "F1" => dictionary! {
+                "Type" => "Font",
+                "Subtype" => "TrueType",
+                "BaseFont" => "Example",
+                "Encoding" => "WinAnsiEncoding",
+                "FirstChar" => 65,
+                "Widths" => vec![500.into(), 600.into()],
+                "FontDescriptor" => dictionary! {
+                    "Type" => "FontDescriptor",
+                    "Ascent" => 700,
+                    "Descent" => -300,
+                },
+            },
+        },
+    };
+    // Scaled by 2, with a point of character spacing and 50% horizontal
+    // scaling: each glyph advances (width * 10 + 1) * 0.5 text units.
+    let pdf: Vec<u8> = make_pdf(
+        &["q 2 0 0 2 0 0 cm BT /F1 10 Tf 1 Tc 50 Tz 10 20 Td (AB) Tj ET Q"],
+        resources,
+    );
+    let pages: Vec<PageText> = extract_text(&pdf).unwrap();
+    assert_eq!(word_texts(&pages[0]), vec!["AB"]);
+    let bbox: &BoundingBox = &pages[0].words[0].bbox;
+    assert_close(bbox.x, 20.0);
+    assert_close(bbox.y, 40.0 - 2.0 * 3.0);
+    assert_close(bbox.height, 2.0 * 10.0);
+    // "A" advances (5 + 1) * 0.5 = 3 units; "B" is 6 * 0.5 = 3 wide.
+    assert_close(bbox.width, 2.0 * (3.0 + 3.0));
+}
+
+#[test]
+fn test_extract_text_type0_font_with_to_unicode_map() {
+    let cmap: &str = "/CIDInit /ProcSet findresource begin 12 dict begin begincmap \
+         /CMapName /Test def 1 begincodespacerange <0000> <FFFF> endcodespacerange \
+         2 beginbfchar <0001> <0048> <0002> <0069> endbfchar \
+         endcmap CMapName currentdict /CMap defineresource pop end end";
+    let mut doc_resources = dictionary! {};
+    let font = dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type0",
+        "BaseFont" => "Example",
+        "Encoding" => "Identity-H",
+        "DescendantFonts" => vec![Object::Dictionary(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "CIDFontType2",
+            "BaseFont" => "Example",
+            "W" => vec![1.into(), vec![700.into()].into(), 2.into(), 2.into(), 300.into()],
+        })],
+    };
+    // The map has to be an indirect stream, so build the document by hand.
+    let mut doc = Document::load_mem(&make_pdf(
+        &["BT /F1 20 Tf 50 50 Td <00010002> Tj ET"],
+        dictionary! {},
+    ))
+    .unwrap();
+    let cmap_id = doc.add_object(Stream::new(dictionary! {}, cmap.as_bytes().to_vec()));
+    let mut font = font;
+    font.set("ToUnicode", cmap_id);
+    doc_resources.set("Font", dictionary! { "F1" => font });
+    let page_id = doc.get_pages()[&1];
+    doc.get_dictionary_mut(page_id)
+        .unwrap()
+        .set("Resources", doc_resources);
+    let mut pdf: Vec<u8> = Vec::new();
+    doc.save_to(&mut pdf).unwrap();
+
+    let pages: Vec<PageText> = extract_text(&pdf).unwrap();
+    assert_eq!(word_texts(&pages[0]), vec!["Hi"]);
+    let bbox: &BoundingBox = &pages[0].words[0].bbox;
+    assert_close(bbox.x, 50.0);
+    assert_close(bbox.width, 20.0 * (0.7 + 0.3));
+}
+
+#[test]
+fn test_extract_text_splits_words_at_gaps_and_lines() {
+    let pdf: Vec<u8> = make_pdf(
+        &["BT /F1 10 Tf 14 TL 100 700 Td [(Hel) -20 (lo) -600 (there)] TJ T* (next line) Tj ET"],
+        helvetica(),
+    );
+    let pages: Vec<PageText> = extract_text(&pdf).unwrap();
+    assert_eq!(
+        word_texts(&pages[0]),
+        vec!["Hello", "there", "next", "line"]
+    );
+    assert_close(pages[0].words[2].bbox.x, 100.0);
+    assert_close(pages[0].words[2].bbox.y, 686.0 - 2.0);
+}
+
+#[test]
+fn test_extract_text_inside_form_xobjects() {
+    let form = Stream::new(
+        dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Form",
+            "BBox" => vec![0.into(), 0.into(), 200.into(), 50.into()],
+            "Matrix" => vec![1.into(), 0.into(), 0.into(), 1.into(), 300.into(), 400.into()],
+        },
+        b"BT /F1 10 Tf 5 5 Td (Approved) Tj ET".to_vec(),
+    );
+    let mut doc = Document::load_mem(&make_pdf(&["/Stamp Do"], helvetica())).unwrap();
+    let form_id = doc.add_object(form);
+    let mut resources: Dictionary = helvetica();
+    resources.set("XObject", dictionary! { "Stamp" => form_id });
+    let page_id = doc.get_pages()[&1];
+    doc.get_dictionary_mut(page_id)
+        .unwrap()
+        .set("Resources", resources);
+    let mut pdf: Vec<u8> = Vec::new();
+    doc.save_to(&mut pdf).unwrap();
+
+    let pages: Vec<PageText> = extract_text(&pdf).unwrap();
+    assert_eq!(word_texts(&pages[0]), vec!["Approved"]);
+    assert_close(pages[0].words[0].bbox.x, 305.0);
+}
+
+#[test]
+fn test_find_text_ignores_case_and_spans_lines() {
+    let pdf: Vec<u8> = make_pdf(
+        &[
+            "BT /F1 10 Tf 14 TL 100 700 Td (Net revenue grew) Tj T* (while revenue) Tj T* (fell) Tj ET",
+            "BT /F1 10 Tf 100 700 Td (No match here) Tj ET",
+            "BT /F1 10 Tf 100 700 Td (REVENUE) Tj ET",
+        ],
+        helvetica(),
+    );
+    let matches: Vec<Match> = find_text(&pdf, "revenue").unwrap();
+    let found: Vec<(u32, &str)> = matches
+        .iter()
+        .map(|found| (found.page, found.text.as_str()))
+        .collect();
+    assert_eq!(found, vec![(1, "revenue"), (1, "revenue"), (3, "REVENUE")]);
+    let first: &BoundingBox = &matches[0].boxes[0];
+    assert_close(first.x, 100.0 + text_width("Net ") * 10.0);
+    assert_close(first.width, text_width("revenue") * 10.0);
+
+    // A phrase broken across lines gets a box on each.
+    let matches: Vec<Match> = find_text(&pdf, "while  REVENUE fell").unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].text, "while revenue fell");
+    assert_eq!(matches[0].boxes.len(), 2);
+    assert_close(matches[0].boxes[1].y, 672.0 - 2.0);
+}
+
+#[test]
+fn test_find_text_part_of_a_word() {
+    let pdf: Vec<u8> = make_pdf(
+        &["BT /F1 10 Tf 100 700 Td (Reconciliation) Tj ET"],
+        helvetica(),
+    );
+    let matches: Vec<Match> = find_text(&pdf, "cil").unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_close(matches[0].boxes[0].x, 100.0 + text_width("Recon") * 10.0);
+    assert_close(matches[0].boxes[0].width, text_width("cil") * 10.0);
+}
+
+#[test]
+fn test_find_text_rejects_bad_input() {
+    let pdf: Vec<u8> = make_pdf(&["BT /F1 10 Tf 100 700 Td (Text) Tj ET"], helvetica());
+    let err: ConvertError = find_text(&pdf, "  ").unwrap_err();
+    assert!(matches!(err, ConvertError::InvalidOptions(_)), "{err:?}");
+    assert!(find_text(b"not a pdf", "text").is_err());
+    assert!(find_text(&pdf, "missing").unwrap().is_empty());
+}
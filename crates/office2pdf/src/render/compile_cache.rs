@@ -0,0 +1,144 @@
+//! State a [`Converter`](crate::Converter) keeps between conversions, so
+//! each one does not rebuild it: Typst's standard library, the fonts of
+//! [`FontConfig::data`](crate::config::FontConfig::data) parsed into faces,
+//! the font context resolved for the converter's font paths, and image
+//! files by content hash.
+//!
+//! Like the font rules in `font_subst`, the cache is made active for the
+//! duration of a conversion with [`with_compile_cache`] rather than passed
+//! through every stage.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use typst::Library;
+use typst::LibraryExt;
+use typst::foundations::Bytes;
+use typst::text::Font;
+use typst::utils::LazyHash;
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::font_context::FontSearchContext;
+
+/// Image data kept at most, so a long-running server converting distinct
+/// documents does not hold every picture it has seen.
+const MAX_CACHED_IMAGE_BYTES: usize = 256 * 1024 * 1024;
+
+thread_local! {
+    static ACTIVE_CACHE: RefCell<Option<Arc<CompileCache>>> = const { RefCell::new(None) };
+}
+
+/// Compilation state shared by the conversions of one `Converter`, all
+/// with the same options.
+pub(crate) struct CompileCache {
+    library: Arc<LazyHash<Library>>,
+    /// Faces of the options' in-memory fonts, parsed on first use.
+    memory_fonts: OnceLock<Vec<Font>>,
+    /// Font context for the options' font paths. Documents with embedded
+    /// fonts add a directory of their own and resolve theirs afresh.
+    #[cfg(not(target_arch = "wasm32"))]
+    font_context: OnceLock<FontSearchContext>,
+    images: Mutex<ImageCache>,
+}
+
+#[derive(Default)]
+struct ImageCache {
+    by_hash: HashMap<u128, Bytes>,
+    size: usize,
+}
+
+impl CompileCache {
+    pub(crate) fn new() -> Self {
+        Self {
+            library: Arc::new(LazyHash::new(Library::default())),
+            memory_fonts: OnceLock::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            font_context: OnceLock::new(),
+            images: Mutex::new(ImageCache::default()),
+        }
+    }
+
+    pub(crate) fn library(&self) -> Arc<LazyHash<Library>> {
+        Arc::clone(&self.library)
+    }
+
+    /// The faces of `font_data`, which is the same on every call.
+    pub(crate) fn memory_fonts(&self, font_data: &[Vec<u8>]) -> Vec<Font> {
+        self.memory_fonts
+            .get_or_init(|| parse_fonts(font_data))
+            .clone()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn font_context(
+        &self,
+        resolve: impl FnOnce() -> FontSearchContext,
+    ) -> FontSearchContext {
+        self.font_context.get_or_init(resolve).clone()
+    }
+
+    /// `data` as Typst bytes, shared with earlier images of the same
+    /// content. Typst memoizes image decoding by the hash each `Bytes`
+    /// computes once, so a logo on every document is decoded once.
+    pub(crate) fn image(&self, data: &[u8]) -> Bytes {
+        let hash: u128 = typst::utils::hash128(data);
+        let mut images = self
+            .images
+            .lock()
+            .expect("image cache mutex should not be poisoned");
+        if let Some(bytes) = images.by_hash.get(&hash) {
+            return bytes.clone();
+        }
+        let bytes: Bytes = Bytes::new(data.to_vec());
+        if images.size + data.len() <= MAX_CACHED_IMAGE_BYTES {
+            images.size += data.len();
+            images.by_hash.insert(hash, bytes.clone());
+        }
+        bytes
+    }
+
+    #[cfg(test)]
+    fn cached_images(&self) -> usize {
+        self.images
+            .lock()
+            .expect("image cache mutex should not be poisoned")
+            .by_hash
+            .len()
+    }
+}
+
+/// Every face of every font file in `font_data`; files Typst cannot read
+/// are skipped.
+pub(crate) fn parse_fonts(font_data: &[Vec<u8>]) -> Vec<Font> {
+    font_data
+        .iter()
+        .flat_map(|data| Font::iter(Bytes::new(data.clone())))
+        .collect()
+}
+
+/// Run `operation` with `cache` as the one compilation on this thread
+/// draws from.
+pub(crate) fn with_compile_cache<T>(
+    cache: Option<&Arc<CompileCache>>,
+    operation: impl FnOnce() -> T,
+) -> T {
+    ACTIVE_CACHE.with(|active_cache| {
+        let previous = active_cache.replace(cache.cloned());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(operation));
+        active_cache.replace(previous);
+        match result {
+            Ok(value) => value,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    })
+}
+
+/// The cache made active on this thread by [`with_compile_cache`].
+pub(crate) fn active() -> Option<Arc<CompileCache>> {
+    ACTIVE_CACHE.with(|active_cache| active_cache.borrow().clone())
+}
+
+#[cfg(test)]
+#[path = "compile_cache_tests.rs"]
+mod tests;
@@ -0,0 +1,31 @@
+use super::*;
+
+#[test]
+fn test_images_with_the_same_content_share_bytes() {
+    let cache = CompileCache::new();
+    let first: Bytes = cache.image(b"logo");
+    let second: Bytes = cache.image(b"logo");
+    assert_eq!(first, second);
+    cache.image(b"photo");
+    assert_eq!(cache.cached_images(), 2);
+}
+
+#[test]
+fn test_memory_fonts_are_parsed_once() {
+    let cache = CompileCache::new();
+    // Not a font: nothing to parse, and the result is kept all the same.
+    assert!(cache.memory_fonts(&[b"not a font".to_vec()]).is_empty());
+    assert!(cache.memory_fonts.get().is_some());
+}
+
+#[test]
+fn test_active_cache_is_scoped_to_the_operation() {
+    let cache: Arc<CompileCache> = Arc::new(CompileCache::new());
+    assert!(active().is_none());
+    with_compile_cache(Some(&cache), || {
+        assert!(active().is_some_and(|active| Arc::ptr_eq(&active, &cache)));
+        with_compile_cache(None, || assert!(active().is_none()));
+        assert!(active().is_some());
+    });
+    assert!(active().is_none());
+}
@@ -7,9 +7,13 @@ use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
 
+use typst::foundations::Bytes;
+use typst::text::Font;
 #[cfg(not(target_arch = "wasm32"))]
 use typst_kit::fonts::FontSearcher;
 
+use crate::config::FontConfig;
+
 #[cfg(not(target_arch = "wasm32"))]
 use tracing::debug;
 
@@ -74,17 +78,25 @@ fn normalize_family_name(family: &str) -> String {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-pub(crate) fn resolve_font_search_context(user_font_paths: &[PathBuf]) -> FontSearchContext {
-    let office_paths = if cfg!(target_os = "macos") {
+pub(crate) fn resolve_font_search_context(
+    user_font_paths: &[PathBuf],
+    config: &FontConfig,
+) -> FontSearchContext {
+    let system_fonts: bool = !config.disable_system_fonts;
+    // Office's bundled fonts are installed fonts too.
+    let office_paths = if cfg!(target_os = "macos") && system_fonts {
         discover_default_macos_office_font_paths()
     } else {
         Vec::new()
     };
     let user_paths = canonicalize_existing_dirs(user_font_paths.iter().cloned());
     let search_paths = merge_prioritized_paths(&office_paths, &user_paths);
-    let office_families = available_families_from_paths(&office_paths, false);
-    let user_families = available_families_from_paths(&user_paths, false);
-    let available_families = available_families_from_paths(&search_paths, true);
+    let office_families = available_families_from_paths(&office_paths, false, false);
+    let mut user_families = available_families_from_paths(&user_paths, false, false);
+    let mut available_families = available_families_from_paths(&search_paths, system_fonts, true);
+    let memory_families: HashSet<String> = memory_font_families(&config.data);
+    user_families.extend(memory_families.iter().cloned());
+    available_families.extend(memory_families);
 
     debug!(
         office_path_count = office_paths.len(),
@@ -103,15 +115,36 @@ pub(crate) fn resolve_font_search_context(user_font_paths: &[PathBuf]) -> FontSe
 }
 
 #[cfg(target_arch = "wasm32")]
-pub(crate) fn resolve_font_search_context(_user_font_paths: &[PathBuf]) -> FontSearchContext {
-    FontSearchContext::default()
+pub(crate) fn resolve_font_search_context(
+    _user_font_paths: &[PathBuf],
+    config: &FontConfig,
+) -> FontSearchContext {
+    let memory_families: HashSet<String> = memory_font_families(&config.data);
+    FontSearchContext {
+        available_families: memory_families.clone(),
+        user_families: memory_families,
+        ..FontSearchContext::default()
+    }
+}
+
+/// Families of the faces in fonts supplied in memory.
+fn memory_font_families(font_data: &[Vec<u8>]) -> HashSet<String> {
+    font_data
+        .iter()
+        .flat_map(|data| Font::iter(Bytes::new(data.clone())))
+        .map(|font| normalize_family_name(&font.info().family))
+        .collect()
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-fn available_families_from_paths(paths: &[PathBuf], include_system_fonts: bool) -> HashSet<String> {
+fn available_families_from_paths(
+    paths: &[PathBuf],
+    include_system_fonts: bool,
+    include_embedded_fonts: bool,
+) -> HashSet<String> {
     let mut searcher = FontSearcher::new();
     searcher.include_system_fonts(include_system_fonts);
-    searcher.include_embedded_fonts(include_system_fonts);
+    searcher.include_embedded_fonts(include_embedded_fonts);
     let font_data = if paths.is_empty() {
         searcher.search()
     } else {
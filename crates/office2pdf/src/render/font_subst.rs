@@ -9,12 +9,14 @@
 #![cfg_attr(target_arch = "wasm32", allow(dead_code))]
 
 use std::cell::RefCell;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 #[cfg(target_arch = "wasm32")]
 use std::path::PathBuf;
 
+use crate::config::FontConfig;
 use crate::ir::{
-    Block, Document, FixedElementKind, HFInline, HeaderFooter, Page, Paragraph, Table,
+    Block, DateTimeField, Document, FixedElementKind, HFInline, HeaderFooter, Page, Paragraph, Run,
+    Table,
 };
 
 use super::font_context::FontSearchContext;
@@ -22,6 +24,93 @@ use super::typst_gen::escape_typst_string;
 
 thread_local! {
     static ACTIVE_FONT_CONTEXT: RefCell<Option<FontSearchContext>> = const { RefCell::new(None) };
+    static ACTIVE_FONT_RULES: RefCell<FontRules> = RefCell::new(FontRules::default());
+}
+
+/// Characters the CJK fallbacks of [`FontConfig::fallbacks`] are used for,
+/// as a Typst `covers` regex.
+const CJK_COVERS: &str = r"[\p{Han}\p{Hiragana}\p{Katakana}\p{Hangul}\p{Bopomofo}]";
+const ARABIC_COVERS: &str = r"\p{Arabic}";
+
+/// The parts of a [`FontConfig`] that shape the font lists codegen writes:
+/// substitutions keyed by normalized family, and the fallbacks appended to
+/// every list.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FontRules {
+    substitutions: HashMap<String, Vec<String>>,
+    /// Fallback families with the characters each is limited to, if any.
+    fallbacks: Vec<(String, Option<&'static str>)>,
+}
+
+impl FontRules {
+    pub(crate) fn new(config: &FontConfig) -> Self {
+        let substitutions: HashMap<String, Vec<String>> = config
+            .substitutions
+            .iter()
+            .map(|(family, substitutes)| (normalized_lookup_key(family), substitutes.clone()))
+            .collect();
+        // Script-limited fallbacks go first so a Latin fallback with a few
+        // CJK or Arabic glyphs does not take those characters from them.
+        let fallbacks: Vec<(String, Option<&'static str>)> = config
+            .fallbacks
+            .cjk
+            .iter()
+            .map(|family| (family.clone(), Some(CJK_COVERS)))
+            .chain(
+                config
+                    .fallbacks
+                    .arabic
+                    .iter()
+                    .map(|family| (family.clone(), Some(ARABIC_COVERS))),
+            )
+            .chain(
+                config
+                    .fallbacks
+                    .latin
+                    .iter()
+                    .map(|family| (family.clone(), None)),
+            )
+            .collect();
+        Self {
+            substitutions,
+            fallbacks,
+        }
+    }
+
+    fn substitutes(&self, font_family: &str) -> &[String] {
+        self.substitutions
+            .get(&normalized_lookup_key(font_family))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Fallbacks that cover any character, for telling which font text
+    /// ends up in.
+    fn general_fallbacks(&self) -> impl Iterator<Item = &str> {
+        self.fallbacks
+            .iter()
+            .filter(|(_, covers)| covers.is_none())
+            .map(|(family, _)| family.as_str())
+    }
+
+    fn write_fallbacks(&self, out: &mut String) {
+        for (family, covers) in &self.fallbacks {
+            out.push_str(", ");
+            match covers {
+                Some(covers) => {
+                    out.push_str("(name: \"");
+                    out.push_str(&escape_typst_string(family));
+                    out.push_str("\", covers: regex(\"");
+                    out.push_str(&escape_typst_string(covers));
+                    out.push_str("\"))");
+                }
+                None => {
+                    out.push('"');
+                    out.push_str(&escape_typst_string(family));
+                    out.push('"');
+                }
+            }
+        }
+    }
 }
 
 fn normalized_lookup_key(font_family: &str) -> String {
@@ -312,21 +401,36 @@ pub fn is_primary_font_available(font_family: &str) -> bool {
 /// If substitutions exist, returns a Typst array literal like
 /// `("Calibri", "Carlito", "Liberation Sans")`.
 /// If no substitutions exist, returns a simple quoted name like `"Helvetica"`.
+///
+/// Substitutions from [`FontConfig::substitutions`] come before the
+/// requested family and fallbacks from [`FontConfig::fallbacks`] after
+/// everything else.
 pub fn font_with_fallbacks(font_family: &str) -> String {
     ACTIVE_FONT_CONTEXT.with(|active_context| {
-        font_with_fallbacks_for_context(font_family, active_context.borrow().as_ref())
+        ACTIVE_FONT_RULES.with(|rules| {
+            font_with_fallbacks_for_context(
+                font_family,
+                active_context.borrow().as_ref(),
+                &rules.borrow(),
+            )
+        })
     })
 }
 
 fn font_with_fallbacks_for_context(
     font_family: &str,
     context: Option<&FontSearchContext>,
+    rules: &FontRules,
 ) -> String {
-    let fallbacks = fallback_candidates(font_family, context);
-    // Family names originate from parsed OOXML (document-controlled); escape
-    // them so `"` or `\` cannot break out of the Typst string literal.
-    let family = escape_typst_string(font_family);
-    if fallbacks.is_empty() {
+    let mut families: Vec<String> = rules.substitutes(font_family).to_vec();
+    families.push(font_family.to_string());
+    families.extend(fallback_candidates(font_family, context));
+    let mut seen: BTreeSet<String> = BTreeSet::new();
+    families.retain(|family| seen.insert(family.trim().to_ascii_lowercase()));
+    if families.len() == 1 && rules.fallbacks.is_empty() {
+        // Family names originate from parsed OOXML (document-controlled);
+        // escape them so `"` or `\` cannot break out of the string literal.
+        let family = escape_typst_string(&families[0]);
         let mut result = String::with_capacity(family.len() + 2);
         result.push('"');
         result.push_str(&family);
@@ -336,18 +440,48 @@ fn font_with_fallbacks_for_context(
 
     let mut result = String::with_capacity(64);
     result.push('(');
-    result.push('"');
-    result.push_str(&family);
-    result.push('"');
-    for sub in fallbacks {
-        result.push_str(", \"");
-        result.push_str(&escape_typst_string(&sub));
+    for (index, family) in families.iter().enumerate() {
+        if index > 0 {
+            result.push_str(", ");
+        }
+        result.push('"');
+        result.push_str(&escape_typst_string(family));
         result.push('"');
     }
+    rules.write_fallbacks(&mut result);
     result.push(')');
     result
 }
 
+/// The font list for text that names no family: Typst's default font
+/// followed by the configured fallbacks, or `None` without fallbacks.
+pub(crate) fn default_font_with_fallbacks() -> Option<String> {
+    ACTIVE_FONT_RULES.with(|rules| {
+        let rules = rules.borrow();
+        if rules.fallbacks.is_empty() {
+            return None;
+        }
+        let mut result = String::from("(\"Libertinus Serif\"");
+        rules.write_fallbacks(&mut result);
+        result.push(')');
+        Some(result)
+    })
+}
+
+/// Run `operation` with the font rules of `config` applied to the font
+/// lists [`font_with_fallbacks`] builds.
+pub(crate) fn with_font_rules<T>(config: &FontConfig, operation: impl FnOnce() -> T) -> T {
+    ACTIVE_FONT_RULES.with(|active_rules| {
+        let previous = active_rules.replace(FontRules::new(config));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(operation));
+        active_rules.replace(previous);
+        match result {
+            Ok(value) => value,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    })
+}
+
 pub(crate) fn with_font_search_context<T>(
     context: Option<&FontSearchContext>,
     operation: impl FnOnce() -> T,
@@ -420,13 +554,14 @@ fn visit_header_footer_fonts(
 ) -> bool {
     header_footer.paragraphs.iter().all(|paragraph| {
         paragraph.elements.iter().all(|inline| match inline {
-            HFInline::Run(run) => run
-                .style
-                .font_family
-                .as_deref()
-                .map(str::trim)
-                .filter(|f| !f.is_empty())
-                .is_none_or(&mut *visitor),
+            HFInline::Run(Run { style, .. }) | HFInline::DateTime(DateTimeField { style, .. }) => {
+                style
+                    .font_family
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|f| !f.is_empty())
+                    .is_none_or(&mut *visitor)
+            }
             HFInline::Image(_)
             | HFInline::PageNumber
             | HFInline::TotalPages
@@ -557,13 +692,27 @@ pub(crate) fn document_requests_font_families(doc: &Document) -> bool {
     })
 }
 
-fn resolve_available_fallback(font_family: &str, context: &FontSearchContext) -> Option<String> {
+/// The family text requested in `font_family` is rendered in, when it is
+/// not that family itself.
+fn resolve_available_fallback(
+    font_family: &str,
+    context: &FontSearchContext,
+    rules: &FontRules,
+) -> Option<String> {
+    if let Some(substitute) = rules
+        .substitutes(font_family)
+        .iter()
+        .find(|substitute| context.has_family(substitute))
+    {
+        return (!substitute.eq_ignore_ascii_case(font_family)).then(|| substitute.clone());
+    }
     if context.has_family(font_family) {
         return None;
     }
 
     fallback_candidates(font_family, Some(context))
         .into_iter()
+        .chain(rules.general_fallbacks().map(str::to_string))
         .find(|candidate| context.has_family(candidate))
 }
 
@@ -571,15 +720,17 @@ fn resolve_available_fallback(font_family: &str, context: &FontSearchContext) ->
 pub(crate) fn detect_missing_font_fallbacks_with_context(
     doc: &Document,
     context: &FontSearchContext,
+    config: &FontConfig,
 ) -> Vec<(String, String)> {
     let requested_fonts = collect_document_font_families(doc);
     if requested_fonts.is_empty() {
         return Vec::new();
     }
 
+    let rules = FontRules::new(config);
     requested_fonts
         .into_iter()
-        .filter_map(|font| resolve_available_fallback(&font, context).map(|to| (font, to)))
+        .filter_map(|font| resolve_available_fallback(&font, context, &rules).map(|to| (font, to)))
         .collect()
 }
 
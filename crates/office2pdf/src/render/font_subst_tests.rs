@@ -267,7 +267,7 @@ fn test_font_with_fallbacks_pretendard_variant_includes_base_family() {
 fn test_resolve_available_fallback_prefers_alias_before_system_fallback() {
     let context =
         FontSearchContext::for_test(Vec::new(), &["Pretendard", "Apple SD Gothic Neo"], &[], &[]);
-    let fallback = resolve_available_fallback("Pretendard Medium", &context);
+    let fallback = resolve_available_fallback("Pretendard Medium", &context, &FontRules::default());
     assert_eq!(fallback.as_deref(), Some("Pretendard"));
 }
 
@@ -325,7 +325,8 @@ fn test_detect_missing_font_fallbacks_with_context_prefers_office_font() {
         styles: crate::ir::StyleSheet::default(),
     };
 
-    let fallbacks = detect_missing_font_fallbacks_with_context(&doc, &context);
+    let fallbacks =
+        detect_missing_font_fallbacks_with_context(&doc, &context, &FontConfig::default());
     assert_eq!(
         fallbacks,
         vec![("Pretendard Medium".to_string(), "Malgun Gothic".to_string())]
@@ -536,3 +537,71 @@ fn test_noto_sans_kr_short_name_substitutes() {
     let subs = substitutes("Noto Sans KR").expect("Noto Sans KR should have substitutes");
     assert!(subs.contains(&"Apple SD Gothic Neo"));
 }
+
+fn font_config(
+    substitutions: &[(&str, &[&str])],
+    fallbacks: crate::config::FontFallbacks,
+) -> FontConfig {
+    FontConfig {
+        substitutions: substitutions
+            .iter()
+            .map(|(family, substitutes)| {
+                (
+                    family.to_string(),
+                    substitutes.iter().map(|s| s.to_string()).collect(),
+                )
+            })
+            .collect(),
+        fallbacks,
+        ..FontConfig::default()
+    }
+}
+
+#[test]
+fn test_configured_substitutions_precede_the_requested_family() {
+    let config = font_config(&[("calibri", &["Carlito"])], Default::default());
+    let result = with_font_rules(&config, || font_with_fallbacks("Calibri"));
+    assert_eq!(result, "(\"Carlito\", \"Calibri\", \"Liberation Sans\")");
+}
+
+#[test]
+fn test_configured_fallbacks_are_limited_to_their_scripts() {
+    let config = font_config(
+        &[],
+        crate::config::FontFallbacks {
+            latin: vec!["DejaVu Sans".to_string()],
+            cjk: vec!["Noto Sans CJK SC".to_string()],
+            arabic: Vec::new(),
+        },
+    );
+    let (result, default) = with_font_rules(&config, || {
+        (
+            font_with_fallbacks("Helvetica"),
+            default_font_with_fallbacks(),
+        )
+    });
+    let fallbacks = concat!(
+        r#"(name: "Noto Sans CJK SC", covers: regex("[\\p{Han}\\p{Hiragana}\\p{Katakana}\\p{Hangul}\\p{Bopomofo}]")), "#,
+        r#""DejaVu Sans")"#
+    );
+    assert_eq!(result, format!("(\"Helvetica\", {fallbacks}"));
+    assert_eq!(default, Some(format!("(\"Libertinus Serif\", {fallbacks}")));
+    assert_eq!(default_font_with_fallbacks(), None);
+}
+
+#[test]
+fn test_configured_substitution_is_reported_even_when_the_family_is_installed() {
+    let context = FontSearchContext::for_test(Vec::new(), &["Calibri", "Carlito"], &[], &[]);
+    let rules = FontRules::new(&font_config(
+        &[("Calibri", &["Carlito"])],
+        Default::default(),
+    ));
+    assert_eq!(
+        resolve_available_fallback("Calibri", &context, &rules).as_deref(),
+        Some("Carlito")
+    );
+    assert_eq!(
+        resolve_available_fallback("Calibri", &context, &FontRules::default()),
+        None
+    );
+}
@@ -0,0 +1,153 @@
+//! Recompression of raster images before they are handed to Typst, with
+//! the `image-opt` feature: downsampling to a maximum density at the size
+//! they are placed, JPEG re-encoding, and JPEG for opaque PNG photos.
+//! Typst embeds image data as given, so camera photos scaled down on a
+//! slide otherwise keep every pixel in the PDF.
+
+use std::collections::HashSet;
+use std::io::Cursor;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView, ImageDecoder, ImageReader, RgbImage};
+
+use crate::config::ImageOptimization;
+use crate::ir::ImageFormat;
+
+/// JPEG quality for images re-encoded without [`ImageOptimization::jpeg_quality`].
+const DEFAULT_JPEG_QUALITY: u8 = 85;
+/// Distinct colours, among the sampled pixels, from which an image is taken
+/// to be a photograph. Screenshots, charts and line art have far fewer, and
+/// JPEG would blur their edges.
+const PHOTO_MIN_COLORS: usize = 4096;
+/// Pixels sampled when counting colours.
+const PHOTO_SAMPLE_PIXELS: usize = 65_536;
+
+/// `data` in `format` recompressed per `settings`, or unchanged when that
+/// would not make it smaller or it is not a raster format worth touching.
+/// `placed_size_pt` is the width and height the image is drawn at, where
+/// known; without it the image is not downsampled.
+pub(crate) fn optimize(
+    data: Vec<u8>,
+    format: ImageFormat,
+    placed_size_pt: (Option<f64>, Option<f64>),
+    settings: &ImageOptimization,
+) -> (Vec<u8>, ImageFormat) {
+    // GIFs may be animated and SVGs have no pixels.
+    if !matches!(
+        format,
+        ImageFormat::Png | ImageFormat::Jpeg | ImageFormat::Bmp | ImageFormat::Tiff
+    ) {
+        return (data, format);
+    }
+    match recompress(&data, format, placed_size_pt, settings) {
+        Some((optimized, optimized_format)) if optimized.len() < data.len() => {
+            (optimized, optimized_format)
+        }
+        _ => (data, format),
+    }
+}
+
+fn recompress(
+    data: &[u8],
+    format: ImageFormat,
+    placed_size_pt: (Option<f64>, Option<f64>),
+    settings: &ImageOptimization,
+) -> Option<(Vec<u8>, ImageFormat)> {
+    let mut decoder = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .ok()?
+        .into_decoder()
+        .ok()?;
+    // Typst honours the EXIF orientation, which re-encoding drops, so the
+    // pixels are turned upright first.
+    let orientation = decoder.orientation().ok()?;
+    let mut decoded: DynamicImage = DynamicImage::from_decoder(decoder).ok()?;
+    decoded.apply_orientation(orientation);
+
+    let target: Option<(u32, u32)> = settings
+        .max_dpi
+        .and_then(|max_dpi| downsampled_size(decoded.dimensions(), placed_size_pt, max_dpi));
+    let resized: bool = target.is_some();
+    if let Some((width, height)) = target {
+        decoded = decoded.resize_exact(width, height, FilterType::CatmullRom);
+    }
+
+    let opaque: bool = is_opaque(&decoded);
+    let as_jpeg: bool = match format {
+        ImageFormat::Jpeg => true,
+        _ => settings.png_to_jpeg && opaque && looks_like_photo(&decoded.to_rgb8()),
+    };
+    if !resized && !as_jpeg {
+        return None;
+    }
+    if !resized && format == ImageFormat::Jpeg && settings.jpeg_quality.is_none() {
+        return None;
+    }
+
+    let mut encoded: Vec<u8> = Vec::new();
+    if as_jpeg {
+        let quality: u8 = settings.jpeg_quality.unwrap_or(DEFAULT_JPEG_QUALITY);
+        JpegEncoder::new_with_quality(&mut encoded, quality)
+            .encode_image(&decoded.to_rgb8())
+            .ok()?;
+        Some((encoded, ImageFormat::Jpeg))
+    } else {
+        decoded
+            .write_to(&mut Cursor::new(&mut encoded), image::ImageFormat::Png)
+            .ok()?;
+        Some((encoded, ImageFormat::Png))
+    }
+}
+
+/// The pixel size that brings `pixels` down to `max_dpi` at the placed
+/// size, or `None` when the image is no denser than that already. A
+/// missing placed dimension follows the image's aspect ratio.
+fn downsampled_size(
+    (width, height): (u32, u32),
+    placed_size_pt: (Option<f64>, Option<f64>),
+    max_dpi: f32,
+) -> Option<(u32, u32)> {
+    let aspect: f64 = f64::from(height) / f64::from(width);
+    let (placed_width, placed_height): (f64, f64) = match placed_size_pt {
+        (Some(w), Some(h)) => (w, h),
+        (Some(w), None) => (w, w * aspect),
+        (None, Some(h)) => (h / aspect, h),
+        (None, None) => return None,
+    };
+    let max_pixels = |points: f64, pixels: u32| -> u32 {
+        let limit: f64 = (points / 72.0 * f64::from(max_dpi)).ceil();
+        if limit.is_finite() && limit >= 1.0 {
+            pixels.min(limit as u32)
+        } else {
+            pixels
+        }
+    };
+    let target: (u32, u32) = (
+        max_pixels(placed_width, width),
+        max_pixels(placed_height, height),
+    );
+    (target != (width, height)).then_some(target)
+}
+
+fn is_opaque(image: &DynamicImage) -> bool {
+    !image.color().has_alpha() || image.to_rgba8().pixels().all(|pixel| pixel.0[3] == u8::MAX)
+}
+
+/// Whether `image` has the many distinct colours of a photograph.
+fn looks_like_photo(image: &RgbImage) -> bool {
+    let pixel_count: usize = image.pixels().len();
+    let step: usize = (pixel_count / PHOTO_SAMPLE_PIXELS).max(1);
+    let mut colors: HashSet<[u8; 3]> = HashSet::new();
+    for pixel in image.pixels().step_by(step) {
+        colors.insert(pixel.0);
+        if colors.len() >= PHOTO_MIN_COLORS {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+#[path = "image_opt_tests.rs"]
+mod tests;
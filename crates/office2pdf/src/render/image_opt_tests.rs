@@ -0,0 +1,140 @@
+use image::{Rgb, RgbImage, Rgba, RgbaImage};
+
+use super::*;
+
+/// Pseudo-random pixels, which no codec compresses well: a stand-in for a
+/// camera photo.
+fn noise(width: u32, height: u32) -> RgbImage {
+    let mut state: u32 = 0x1234_5678;
+    RgbImage::from_fn(width, height, |_, _| {
+        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        let [r, g, b, _] = state.to_le_bytes();
+        Rgb([r, g, b])
+    })
+}
+
+fn encode_png(image: &DynamicImage) -> Vec<u8> {
+    let mut data: Vec<u8> = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut data), image::ImageFormat::Png)
+        .unwrap();
+    data
+}
+
+fn lossless() -> ImageOptimization {
+    ImageOptimization {
+        max_dpi: None,
+        jpeg_quality: None,
+        png_to_jpeg: false,
+    }
+}
+
+#[test]
+fn test_downsamples_to_max_dpi_at_placed_size() {
+    let png: Vec<u8> = encode_png(&DynamicImage::ImageRgb8(noise(400, 200)));
+    let settings = ImageOptimization {
+        max_dpi: Some(72.0),
+        ..lossless()
+    };
+    // 100 x 50 pt at 72 dpi is 100 x 50 pixels.
+    let (data, format) = optimize(png, ImageFormat::Png, (Some(100.0), None), &settings);
+    assert_eq!(format, ImageFormat::Png);
+    assert_eq!(
+        image::load_from_memory(&data).unwrap().dimensions(),
+        (100, 50)
+    );
+}
+
+#[test]
+fn test_keeps_images_no_denser_than_max_dpi() {
+    let png: Vec<u8> = encode_png(&DynamicImage::ImageRgb8(noise(100, 50)));
+    let settings = ImageOptimization {
+        max_dpi: Some(150.0),
+        ..lossless()
+    };
+    let (data, format) = optimize(
+        png.clone(),
+        ImageFormat::Png,
+        (Some(100.0), Some(50.0)),
+        &settings,
+    );
+    assert_eq!((data, format), (png.clone(), ImageFormat::Png));
+    // Without a placed size there is no density to cap.
+    let (data, _) = optimize(png.clone(), ImageFormat::Png, (None, None), &settings);
+    assert_eq!(data, png);
+}
+
+#[test]
+fn test_png_photo_becomes_jpeg() {
+    let png: Vec<u8> = encode_png(&DynamicImage::ImageRgb8(noise(256, 256)));
+    let settings = ImageOptimization {
+        png_to_jpeg: true,
+        ..lossless()
+    };
+    let (data, format) = optimize(png.clone(), ImageFormat::Png, (None, None), &settings);
+    assert_eq!(format, ImageFormat::Jpeg);
+    assert!(data.len() < png.len());
+}
+
+#[test]
+fn test_png_graphics_and_transparency_stay_png() {
+    let settings = ImageOptimization {
+        png_to_jpeg: true,
+        ..lossless()
+    };
+    // Few colours: a chart or screenshot.
+    let flat = RgbImage::from_fn(256, 256, |x, _| {
+        if x < 128 {
+            Rgb([255, 0, 0])
+        } else {
+            Rgb([0, 0, 255])
+        }
+    });
+    let png: Vec<u8> = encode_png(&DynamicImage::ImageRgb8(flat));
+    assert_eq!(
+        optimize(png.clone(), ImageFormat::Png, (None, None), &settings),
+        (png, ImageFormat::Png)
+    );
+
+    let photo: RgbImage = noise(256, 256);
+    let translucent = RgbaImage::from_fn(256, 256, |x, y| {
+        let [r, g, b] = photo.get_pixel(x, y).0;
+        Rgba([r, g, b, 128])
+    });
+    let png: Vec<u8> = encode_png(&DynamicImage::ImageRgba8(translucent));
+    assert_eq!(
+        optimize(png.clone(), ImageFormat::Png, (None, None), &settings).1,
+        ImageFormat::Png
+    );
+}
+
+#[test]
+fn test_jpeg_reencoded_at_lower_quality() {
+    let mut jpeg: Vec<u8> = Vec::new();
+    JpegEncoder::new_with_quality(&mut jpeg, 100)
+        .encode_image(&noise(256, 256))
+        .unwrap();
+    let settings = ImageOptimization {
+        jpeg_quality: Some(40),
+        ..lossless()
+    };
+    let (data, format) = optimize(jpeg.clone(), ImageFormat::Jpeg, (None, None), &settings);
+    assert_eq!(format, ImageFormat::Jpeg);
+    assert!(data.len() < jpeg.len());
+
+    // Without a quality an untouched JPEG is not re-encoded.
+    assert_eq!(
+        optimize(jpeg.clone(), ImageFormat::Jpeg, (None, None), &lossless()).0,
+        jpeg
+    );
+}
+
+#[test]
+fn test_leaves_svg_alone() {
+    let settings = ImageOptimization::default();
+    let svg: Vec<u8> = b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>".to_vec();
+    assert_eq!(
+        optimize(svg.clone(), ImageFormat::Svg, (Some(10.0), None), &settings),
+        (svg, ImageFormat::Svg)
+    );
+}
@@ -1,4 +1,8 @@
+pub mod compile_cache;
 pub mod font_context;
 pub mod font_subst;
+#[cfg(feature = "image-opt")]
+pub mod image_opt;
 pub mod pdf;
+pub mod svg_sanitize;
 pub mod typst_gen;
@@ -12,17 +12,20 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use web_time::{SystemTime, UNIX_EPOCH};
 
 use typst::diag::FileResult;
-use typst::foundations::{Bytes, Datetime};
+use typst::foundations::{Bytes, Datetime, Label, Selector, Smart, Value};
+use typst::introspection::MetadataElem;
+use typst::layout::{Frame, FrameItem, PagedDocument};
 use typst::syntax::{FileId, Source, VirtualPath};
 use typst::text::Font;
-use typst::utils::LazyHash;
+use typst::utils::{LazyHash, PicoStr};
 use typst::{Library, LibraryExt, World};
 use typst_kit::fonts::FontSearcher;
 
-use crate::config::PdfStandard;
-use crate::error::ConvertError;
+use crate::config::{ConvertOptions, FontConfig, PdfStandard, SlideImageFormat};
+use crate::error::{ConvertError, ImageOutput};
 
-use super::typst_gen::ImageAsset;
+use super::compile_cache::{self, CompileCache};
+use super::typst_gen::{ImageAsset, OVERFLOW_LABEL};
 
 /// Cached font data (book + font slots). Font discovery is expensive because
 /// it scans the filesystem; the result doesn't change during the process
@@ -37,9 +40,12 @@ struct CachedFontData {
 #[cfg(not(target_arch = "wasm32"))]
 static SYSTEM_FONTS: OnceLock<CachedFontData> = OnceLock::new();
 
-/// Cached font data for resolved extra font path sets.
+/// Cached font data for resolved extra font path sets, with and without
+/// system fonts.
 #[cfg(not(target_arch = "wasm32"))]
-static EXTRA_FONT_PATHS_CACHE: OnceLock<Mutex<HashMap<Vec<PathBuf>, Arc<CachedFontData>>>> =
+type FontPathsKey = (Vec<PathBuf>, bool);
+#[cfg(not(target_arch = "wasm32"))]
+static EXTRA_FONT_PATHS_CACHE: OnceLock<Mutex<HashMap<FontPathsKey, Arc<CachedFontData>>>> =
     OnceLock::new();
 
 /// Cached embedded-only fonts (no system font search). Used on WASM
@@ -62,19 +68,20 @@ fn get_system_fonts() -> &'static CachedFontData {
 
 /// Get or initialize cached fonts for a resolved extra font path set.
 #[cfg(not(target_arch = "wasm32"))]
-fn get_fonts_for_extra_paths(font_paths: &[PathBuf]) -> Arc<CachedFontData> {
+fn get_fonts_for_extra_paths(font_paths: &[PathBuf], system_fonts: bool) -> Arc<CachedFontData> {
     let cache = EXTRA_FONT_PATHS_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let key: FontPathsKey = (font_paths.to_vec(), system_fonts);
     {
         let cache_guard = cache
             .lock()
             .expect("font cache mutex should not be poisoned");
-        if let Some(cached) = cache_guard.get(font_paths) {
+        if let Some(cached) = cache_guard.get(&key) {
             return Arc::clone(cached);
         }
     }
 
     let mut searcher = FontSearcher::new();
-    searcher.include_system_fonts(true);
+    searcher.include_system_fonts(system_fonts);
     let font_data = searcher.search_with(font_paths.iter().map(|path| path.as_path()));
     let cached = Arc::new(CachedFontData {
         book: LazyHash::new(font_data.book),
@@ -85,7 +92,7 @@ fn get_fonts_for_extra_paths(font_paths: &[PathBuf]) -> Arc<CachedFontData> {
         .lock()
         .expect("font cache mutex should not be poisoned");
     let entry = cache_guard
-        .entry(font_paths.to_vec())
+        .entry(key)
         .or_insert_with(|| Arc::clone(&cached));
     Arc::clone(entry)
 }
@@ -103,6 +110,68 @@ fn get_embedded_fonts() -> &'static CachedFontData {
     })
 }
 
+/// Where the compiler finds fonts; see [`FontConfig`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FontSources<'a> {
+    /// Directories searched before the system fonts. Ignored on WASM.
+    pub(crate) paths: &'a [std::path::PathBuf],
+    /// Font files supplied in memory.
+    pub(crate) data: &'a [Vec<u8>],
+    /// Whether installed system fonts are discovered. Never on WASM.
+    pub(crate) system_fonts: bool,
+}
+
+impl<'a> FontSources<'a> {
+    /// `paths` and the system fonts.
+    pub(crate) fn paths(paths: &'a [std::path::PathBuf]) -> Self {
+        Self {
+            paths,
+            data: &[],
+            system_fonts: true,
+        }
+    }
+
+    /// `paths`, resolved from [`ConvertOptions::font_paths`], and the fonts
+    /// `config` adds or rules out.
+    pub(crate) fn new(paths: &'a [std::path::PathBuf], config: &'a FontConfig) -> Self {
+        Self {
+            paths,
+            data: &config.data,
+            system_fonts: !config.disable_system_fonts,
+        }
+    }
+}
+
+/// How the compiled document is written out as PDF, beyond the markup
+/// itself.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PdfExport {
+    /// PDF standard to conform to.
+    pub pdf_standard: Option<PdfStandard>,
+    /// Write structure tags.
+    pub tagged: bool,
+    /// Conform to PDF/UA-1; implies `tagged`.
+    pub pdf_ua: bool,
+    /// Pin the clock to the Unix epoch and derive the document ID from the
+    /// content; see [`ConvertOptions::deterministic`].
+    pub deterministic: bool,
+    /// Drawn for characters no font has; see
+    /// [`ConvertOptions::missing_glyph_replacement`].
+    pub missing_glyph_replacement: Option<char>,
+}
+
+impl PdfExport {
+    pub(crate) fn from_options(options: &ConvertOptions) -> Self {
+        Self {
+            pdf_standard: options.pdf_standard,
+            tagged: options.tagged,
+            pdf_ua: options.pdf_ua,
+            deterministic: options.deterministic,
+            missing_glyph_replacement: options.missing_glyph_replacement,
+        }
+    }
+}
+
 /// Compile Typst markup to PDF bytes.
 ///
 /// When `pdf_standard` is `Some`, the output PDF will conform to the
@@ -137,8 +206,32 @@ pub fn compile_to_pdf(
     tagged: bool,
     pdf_ua: bool,
 ) -> Result<Vec<u8>, ConvertError> {
-    let world = MinimalWorld::new(typst_source, images, font_paths);
-    compile_to_pdf_inner(&world, pdf_standard, tagged, pdf_ua)
+    let export = PdfExport {
+        pdf_standard,
+        tagged,
+        pdf_ua,
+        ..PdfExport::default()
+    };
+    compile_to_pdf_with(
+        typst_source,
+        images,
+        &FontSources::paths(font_paths),
+        &export,
+    )
+    .map(|compiled| compiled.pdf)
+}
+
+/// Compile Typst markup to PDF bytes with the given fonts and export
+/// settings.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn compile_to_pdf_with(
+    typst_source: &str,
+    images: &[ImageAsset],
+    fonts: &FontSources,
+    export: &PdfExport,
+) -> Result<CompiledPdf, ConvertError> {
+    let mut world = MinimalWorld::new(typst_source, images, fonts);
+    compile_to_pdf_inner(&mut world, export)
 }
 
 /// Compile Typst markup to PDF bytes (WASM target).
@@ -149,30 +242,214 @@ pub fn compile_to_pdf(
     typst_source: &str,
     images: &[ImageAsset],
     pdf_standard: Option<PdfStandard>,
-    _font_paths: &[std::path::PathBuf],
+    font_paths: &[std::path::PathBuf],
     tagged: bool,
     pdf_ua: bool,
 ) -> Result<Vec<u8>, ConvertError> {
-    let world = MinimalWorld::new_embedded_only(typst_source, images);
-    compile_to_pdf_inner(&world, pdf_standard, tagged, pdf_ua)
+    let export = PdfExport {
+        pdf_standard,
+        tagged,
+        pdf_ua,
+        ..PdfExport::default()
+    };
+    compile_to_pdf_with(
+        typst_source,
+        images,
+        &FontSources::paths(font_paths),
+        &export,
+    )
+    .map(|compiled| compiled.pdf)
 }
 
-fn compile_to_pdf_inner(
-    world: &MinimalWorld,
-    pdf_standard: Option<PdfStandard>,
-    tagged: bool,
-    pdf_ua: bool,
-) -> Result<Vec<u8>, ConvertError> {
-    let warned = typst::compile::<typst::layout::PagedDocument>(world);
-    let document = warned.output.map_err(|errors| {
+/// Compile Typst markup to PDF bytes with the given fonts and export
+/// settings (WASM target: Typst's bundled fonts and in-memory fonts only).
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn compile_to_pdf_with(
+    typst_source: &str,
+    images: &[ImageAsset],
+    fonts: &FontSources,
+    export: &PdfExport,
+) -> Result<CompiledPdf, ConvertError> {
+    let mut world = MinimalWorld::new_embedded_only(typst_source, images, fonts.data);
+    compile_to_pdf_inner(&mut world, export)
+}
+
+/// A compiled PDF with what its layout showed to be wrong.
+pub(crate) struct CompiledPdf {
+    pub(crate) pdf: Vec<u8>,
+    /// Slide text boxes whose text is taller than the box.
+    pub(crate) overflows: Vec<TextOverflow>,
+    /// Characters no font has a glyph for, each where it first occurs.
+    pub(crate) missing_glyphs: Vec<MissingGlyph>,
+    /// Pages in `pdf`.
+    pub(crate) page_count: u32,
+}
+
+/// A slide text box whose text does not fit, found after layout.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TextOverflow {
+    /// Page of the slide in the output, numbered from 1.
+    pub(crate) slide: u32,
+    /// The slide in the source presentation, when the page came from one.
+    pub(crate) source_slide: Option<u32>,
+    /// Description of the text box.
+    pub(crate) element: String,
+}
+
+/// A character that no available font has a glyph for, so it shows as
+/// the font's missing-glyph box unless it is replaced.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MissingGlyph {
+    pub(crate) character: char,
+    /// Page of its first occurrence, numbered from 1.
+    pub(crate) page: u32,
+    /// The run of text it first occurs in, shortened.
+    pub(crate) text: String,
+}
+
+/// Add the missing glyphs of a part compiled on its own to those of the
+/// parts before it, which fill `pages_before` pages. Characters already
+/// found keep their first occurrence.
+pub(crate) fn extend_missing_glyphs(
+    found: &mut Vec<MissingGlyph>,
+    part: Vec<MissingGlyph>,
+    pages_before: u32,
+) {
+    for mut glyph in part {
+        if found.iter().all(|known| known.character != glyph.character) {
+            glyph.page += pages_before;
+            found.push(glyph);
+        }
+    }
+}
+
+fn compile_document(world: &MinimalWorld) -> Result<PagedDocument, ConvertError> {
+    let warned = typst::compile::<PagedDocument>(world);
+    warned.output.map_err(|errors| {
         let messages: Vec<String> = errors.iter().map(|e| e.message.to_string()).collect();
         ConvertError::Render(format!("Typst compilation failed: {}", messages.join("; ")))
-    })?;
+    })
+}
+
+/// Compile Typst markup and encode every page as a PNG or SVG image instead
+/// of a PDF. `dpi` sets the PNG resolution and is ignored for SVG.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn compile_to_images(
+    typst_source: &str,
+    images: &[ImageAsset],
+    fonts: &FontSources,
+    format: SlideImageFormat,
+    dpi: f32,
+) -> Result<Vec<ImageOutput>, ConvertError> {
+    let world = MinimalWorld::new(typst_source, images, fonts);
+    compile_to_images_inner(&world, format, dpi)
+}
+
+/// Compile Typst markup to page images (WASM target, embedded fonts only).
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn compile_to_images(
+    typst_source: &str,
+    images: &[ImageAsset],
+    fonts: &FontSources,
+    format: SlideImageFormat,
+    dpi: f32,
+) -> Result<Vec<ImageOutput>, ConvertError> {
+    let world = MinimalWorld::new_embedded_only(typst_source, images, fonts.data);
+    compile_to_images_inner(&world, format, dpi)
+}
+
+fn compile_to_images_inner(
+    world: &MinimalWorld,
+    format: SlideImageFormat,
+    dpi: f32,
+) -> Result<Vec<ImageOutput>, ConvertError> {
+    if !(dpi.is_finite() && dpi > 0.0) {
+        return Err(ConvertError::Render(format!(
+            "image resolution must be positive, got {dpi} dpi"
+        )));
+    }
+    let document: PagedDocument = compile_document(world)?;
+    // Typst lays out in points (1/72 inch).
+    let pixel_per_pt: f32 = dpi / 72.0;
+
+    let mut outputs: Vec<ImageOutput> = Vec::with_capacity(document.pages.len());
+    for (page_index, page) in document.pages.iter().enumerate() {
+        let output: ImageOutput = match format {
+            SlideImageFormat::Png => {
+                let pixmap = typst_render::render(page, pixel_per_pt);
+                let data: Vec<u8> = pixmap.encode_png().map_err(|e| {
+                    ConvertError::Render(format!("PNG encoding failed for page {page_index}: {e}"))
+                })?;
+                ImageOutput {
+                    page_index,
+                    format,
+                    data,
+                    width: pixmap.width(),
+                    height: pixmap.height(),
+                }
+            }
+            SlideImageFormat::Svg => {
+                let size = page.frame.size();
+                ImageOutput {
+                    page_index,
+                    format,
+                    data: typst_svg::svg(page).into_bytes(),
+                    width: size.x.to_pt().round() as u32,
+                    height: size.y.to_pt().round() as u32,
+                }
+            }
+        };
+        outputs.push(output);
+    }
+    Ok(outputs)
+}
+
+fn compile_to_pdf_inner(
+    world: &mut MinimalWorld,
+    export: &PdfExport,
+) -> Result<CompiledPdf, ConvertError> {
+    let PdfExport {
+        pdf_standard,
+        tagged,
+        pdf_ua,
+        deterministic,
+        missing_glyph_replacement,
+    } = *export;
+    if deterministic {
+        world.clock = 0;
+    }
+    let mut document: PagedDocument = compile_document(world)?;
+    let missing_glyphs: Vec<MissingGlyph> = missing_glyphs(&document);
+    // Only layout shows which characters no font covers, so the markup is
+    // compiled again with them replaced. Typst memoizes layout, so the
+    // second pass redoes little besides the lines they are in.
+    if let Some(replacement) = missing_glyph_replacement
+        && !missing_glyphs.is_empty()
+    {
+        let replaced: String = replace_characters(
+            world.source.text(),
+            &missing_glyphs
+                .iter()
+                .map(|glyph| glyph.character)
+                .collect::<Vec<char>>(),
+            replacement,
+        );
+        world.source.replace(&replaced);
+        document = compile_document(world)?;
+    }
 
     // Build PDF standards list
     let mut pdf_standards = Vec::new();
-    if let Some(PdfStandard::PdfA2b) = pdf_standard {
-        pdf_standards.push(typst_pdf::PdfStandard::A_2b);
+    match pdf_standard {
+        Some(PdfStandard::PdfA1b) => pdf_standards.push(typst_pdf::PdfStandard::A_1b),
+        // PDF/X-4 is added onto a PDF/A-2b file once Typst has written it:
+        // Typst has no PDF/X support of its own, but PDF/A-2b already
+        // embeds every font and an sRGB output intent, as PDF/X-4 requires.
+        Some(PdfStandard::PdfA2b | PdfStandard::PdfX4) => {
+            pdf_standards.push(typst_pdf::PdfStandard::A_2b);
+        }
+        Some(PdfStandard::PdfA3b) => pdf_standards.push(typst_pdf::PdfStandard::A_3b),
+        None => {}
     }
     if pdf_ua {
         pdf_standards.push(typst_pdf::PdfStandard::Ua_1);
@@ -184,10 +461,12 @@ fn compile_to_pdf_inner(
             .map_err(|e| ConvertError::Render(format!("PDF standard configuration error: {e}")))?
     };
 
-    // PDF/A and PDF/UA require a document creation timestamp
+    // PDF/A and PDF/UA require a document creation timestamp. A document
+    // date set from the source's metadata takes precedence over it.
     let needs_timestamp = pdf_standard.is_some() || pdf_ua;
     let timestamp = if needs_timestamp {
-        Some(typst_pdf::Timestamp::new_utc(current_utc_datetime()))
+        let now: Datetime = pdf_datetime(world.clock)?;
+        Some(typst_pdf::Timestamp::new_utc(now))
     } else {
         None
     };
@@ -195,50 +474,183 @@ fn compile_to_pdf_inner(
     // Enable tagging when explicitly requested or when PDF/UA requires it
     let enable_tagged = tagged || pdf_ua;
 
+    // Typst otherwise derives the ID from the title and author alone, which
+    // different documents share; the markup and image bytes tell them apart.
+    let ident: Option<String> = deterministic.then(|| format!("{:032x}", world.content_hash()));
+
     let options = typst_pdf::PdfOptions {
+        ident: ident.as_deref().map_or(Smart::Auto, Smart::Custom),
         standards,
         timestamp,
         tagged: enable_tagged,
         ..Default::default()
     };
-    typst_pdf::pdf(&document, &options).map_err(|errors| {
+    let pdf: Vec<u8> = typst_pdf::pdf(&document, &options).map_err(|errors| {
         let messages: Vec<String> = errors.iter().map(|e| e.message.to_string()).collect();
         ConvertError::Render(format!("PDF export failed: {}", messages.join("; ")))
+    })?;
+    #[cfg(feature = "pdf-ops")]
+    let pdf: Vec<u8> = if pdf_standard == Some(PdfStandard::PdfX4) {
+        crate::pdf_ops::add_pdf_x_4(&pdf)?
+    } else {
+        pdf
+    };
+    Ok(CompiledPdf {
+        pdf,
+        overflows: text_overflows(&document),
+        missing_glyphs,
+        page_count: document.pages.len() as u32,
     })
 }
 
+/// Characters shaped as glyph 0, the missing-glyph box: Typst falls back
+/// through every font it has before giving up on a character, so none of
+/// them covers it.
+fn missing_glyphs(document: &PagedDocument) -> Vec<MissingGlyph> {
+    let mut found: Vec<MissingGlyph> = Vec::new();
+    for (index, page) in document.pages.iter().enumerate() {
+        collect_missing_glyphs(&page.frame, index as u32 + 1, &mut found);
+    }
+    found
+}
+
+fn collect_missing_glyphs(frame: &Frame, page: u32, found: &mut Vec<MissingGlyph>) {
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => collect_missing_glyphs(&group.frame, page, found),
+            FrameItem::Text(text) => {
+                for glyph in text.glyphs.iter().filter(|glyph| glyph.id == 0) {
+                    let Some(character) = text
+                        .text
+                        .get(glyph.range())
+                        .and_then(|cluster| cluster.chars().next())
+                    else {
+                        continue;
+                    };
+                    if character.is_whitespace()
+                        || found.iter().any(|known| known.character == character)
+                    {
+                        continue;
+                    }
+                    found.push(MissingGlyph {
+                        character,
+                        page,
+                        text: shorten(text.text.trim()),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `text` cut to the first 40 characters.
+fn shorten(text: &str) -> String {
+    const MAX_CHARS: usize = 40;
+    let mut short: String = text.chars().take(MAX_CHARS).collect();
+    if text.chars().count() > MAX_CHARS {
+        short.push('…');
+    }
+    short
+}
+
+/// `source` with each of `characters` written as a Typst escape of
+/// `replacement`, which reads the same in markup and in string literals
+/// whatever the character is.
+fn replace_characters(source: &str, characters: &[char], replacement: char) -> String {
+    let escape: String = format!("\\u{{{:x}}}", u32::from(replacement));
+    let mut replaced: String = String::with_capacity(source.len());
+    for ch in source.chars() {
+        if characters.contains(&ch) {
+            replaced.push_str(&escape);
+        } else {
+            replaced.push(ch);
+        }
+    }
+    replaced
+}
+
+/// The overflow markers codegen placed in text boxes whose measured text
+/// came out taller than the box. Only layout knows the height: it depends
+/// on the fonts found and on how lines break.
+fn text_overflows(document: &PagedDocument) -> Vec<TextOverflow> {
+    let Some(label) = Label::new(PicoStr::intern(OVERFLOW_LABEL)) else {
+        return Vec::new();
+    };
+    document
+        .introspector
+        .query(&Selector::Label(label))
+        .iter()
+        .filter_map(|content| content.to_packed::<MetadataElem>())
+        .filter_map(|metadata| match &metadata.value {
+            Value::Dict(marker) => {
+                let slide: u32 = match marker.get("slide") {
+                    Ok(Value::Int(slide)) => u32::try_from(*slide).ok()?,
+                    _ => return None,
+                };
+                let element: String = match marker.get("element") {
+                    Ok(Value::Str(element)) => element.to_string(),
+                    _ => return None,
+                };
+                let source_slide: Option<u32> = match marker.get("source") {
+                    Ok(Value::Int(source)) => u32::try_from(*source).ok(),
+                    _ => None,
+                };
+                Some(TextOverflow {
+                    slide,
+                    source_slide,
+                    element,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 /// Convert the current system time to a Typst `Datetime` in UTC.
-///
-/// Uses `std::time::SystemTime` to avoid an external chrono dependency.
-/// The civil date is computed from the Unix timestamp using Howard Hinnant's
-/// algorithm (<http://howardhinnant.github.io/date_algorithms.html>).
-fn current_utc_datetime() -> Datetime {
-    let duration = SystemTime::now()
+pub(crate) fn current_utc_datetime() -> Result<Datetime, ConvertError> {
+    pdf_datetime(unix_now())
+}
+
+/// The Unix epoch as a Typst `Datetime`: the time deterministic output
+/// records wherever it would otherwise record the current time.
+pub(crate) fn epoch_datetime() -> Result<Datetime, ConvertError> {
+    pdf_datetime(0)
+}
+
+/// [`utc_datetime`], failing for a time no PDF date can hold.
+fn pdf_datetime(secs: i64) -> Result<Datetime, ConvertError> {
+    utc_datetime(secs).ok_or_else(|| {
+        ConvertError::Render(format!(
+            "the time {secs} seconds after the Unix epoch is out of range for a PDF date"
+        ))
+    })
+}
+
+/// Seconds since the Unix epoch.
+fn unix_now() -> i64 {
+    SystemTime::now()
         .duration_since(UNIX_EPOCH)
-        .unwrap_or_default();
-    let secs = duration.as_secs() as i64;
-
-    // Split into days since epoch and time-of-day
-    let days = secs.div_euclid(86400);
-    let rem = secs.rem_euclid(86400);
-    let hours = (rem / 3600) as u8;
-    let minutes = ((rem % 3600) / 60) as u8;
-    let seconds = (rem % 60) as u8;
-
-    // Civil date from day count since Unix epoch (1970-01-01)
-    let z = days + 719_468;
-    let era = z.div_euclid(146_097);
-    let doe = z.rem_euclid(146_097) as u32; // day of era [0, 146096]
-    let yoe = (doe - doe / 1461 + doe / 36524 - doe / 146096) / 365;
-    let y = yoe as i64 + era * 400;
-    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // day of year [0, 365]
-    let mp = (5 * doy + 2) / 153; // [0, 11]
-    let d = (doy - (153 * mp + 2) / 5 + 1) as u8;
-    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
-    let y = if m <= 2 { y + 1 } else { y } as i32;
-
-    Datetime::from_ymd_hms(y, m, d, hours, minutes, seconds)
-        .expect("valid date derived from SystemTime")
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+/// The UTC `Datetime` `secs` seconds after the Unix epoch, if Typst can
+/// represent it. Uses integer arithmetic to avoid an external chrono
+/// dependency, with the civil date from the XLSX parser's
+/// `civil_from_days`.
+fn utc_datetime(secs: i64) -> Option<Datetime> {
+    let days: i64 = secs.div_euclid(86_400);
+    let rem: i64 = secs.rem_euclid(86_400);
+    let (year, month, day) = crate::parser::xlsx::civil_from_days(days);
+    Datetime::from_ymd_hms(
+        i32::try_from(year).ok()?,
+        month as u8,
+        day as u8,
+        (rem / 3600) as u8,
+        ((rem % 3600) / 60) as u8,
+        (rem % 60) as u8,
+    )
 }
 
 /// Font data source: either a static reference to cached fonts or owned
@@ -270,40 +682,37 @@ impl FontSource {
 
 /// Minimal World implementation providing Typst compiler with source, fonts, and images.
 struct MinimalWorld {
-    library: LazyHash<Library>,
+    library: Arc<LazyHash<Library>>,
     font_source: FontSource,
+    /// Fonts supplied in memory, numbered after `font_source`'s.
+    memory_fonts: Vec<Font>,
+    /// `font_source`'s book with `memory_fonts` added, when there are any.
+    memory_book: Option<LazyHash<typst::text::FontBook>>,
     source: Source,
     images: HashMap<String, Bytes>,
+    /// Seconds since the Unix epoch that `datetime.today()` and the PDF
+    /// timestamp report: when the world was made, so every page shows the
+    /// same time, or the epoch itself for deterministic output.
+    clock: i64,
 }
 
 impl MinimalWorld {
-    /// Create a new `MinimalWorld` with system fonts and optional custom font paths.
+    /// Create a new `MinimalWorld` with the fonts of `fonts`.
     ///
-    /// When `font_paths` is empty (the common case), system fonts are loaded from
+    /// When there are no font paths (the common case), system fonts are loaded from
     /// a process-wide cache, avoiding expensive filesystem scanning on repeated calls.
     /// Resolved extra font path sets are also cached by path list.
     #[cfg(not(target_arch = "wasm32"))]
-    fn new(source_text: &str, images: &[ImageAsset], font_paths: &[PathBuf]) -> Self {
-        let font_source = if font_paths.is_empty() {
-            FontSource::Cached(get_system_fonts())
-        } else {
-            FontSource::Shared(get_fonts_for_extra_paths(font_paths))
+    fn new(source_text: &str, images: &[ImageAsset], fonts: &FontSources) -> Self {
+        let font_source = match (fonts.paths.is_empty(), fonts.system_fonts) {
+            (true, true) => FontSource::Cached(get_system_fonts()),
+            (true, false) => FontSource::Cached(get_embedded_fonts()),
+            (false, system_fonts) => {
+                FontSource::Shared(get_fonts_for_extra_paths(fonts.paths, system_fonts))
+            }
         };
 
-        let main_id = FileId::new(None, VirtualPath::new("main.typ"));
-        let source = Source::new(main_id, source_text.to_string());
-
-        let image_map: HashMap<String, Bytes> = images
-            .iter()
-            .map(|a| (a.path.clone(), Bytes::new(a.data.clone())))
-            .collect();
-
-        Self {
-            library: LazyHash::new(Library::default()),
-            font_source,
-            source,
-            images: image_map,
-        }
+        Self::with_font_source(source_text, images, font_source, fonts.data)
     }
 
     /// Create a new `MinimalWorld` with embedded fonts only (no system font search).
@@ -311,21 +720,82 @@ impl MinimalWorld {
     /// Uses a process-wide cache for embedded font data. This is the constructor
     /// used on WASM targets where system font discovery is not available.
     #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
-    fn new_embedded_only(source_text: &str, images: &[ImageAsset]) -> Self {
+    fn new_embedded_only(source_text: &str, images: &[ImageAsset], font_data: &[Vec<u8>]) -> Self {
+        Self::with_font_source(
+            source_text,
+            images,
+            FontSource::Cached(get_embedded_fonts()),
+            font_data,
+        )
+    }
+
+    /// The world for `source_text` and `images`, drawing on the active
+    /// [`CompileCache`] for the library, in-memory fonts and images when a
+    /// `Converter` has one.
+    fn with_font_source(
+        source_text: &str,
+        images: &[ImageAsset],
+        font_source: FontSource,
+        font_data: &[Vec<u8>],
+    ) -> Self {
+        let cache: Option<Arc<CompileCache>> = compile_cache::active();
         let main_id = FileId::new(None, VirtualPath::new("main.typ"));
         let source = Source::new(main_id, source_text.to_string());
 
         let image_map: HashMap<String, Bytes> = images
             .iter()
-            .map(|a| (a.path.clone(), Bytes::new(a.data.clone())))
+            .map(|a| {
+                let data: Bytes = match &cache {
+                    Some(cache) => cache.image(&a.data),
+                    None => Bytes::new(a.data.clone()),
+                };
+                (a.path.clone(), data)
+            })
             .collect();
+        // Compiles outside the document's own options, such as the blank
+        // page of an empty workbook, pass no fonts of their own.
+        let memory_fonts: Vec<Font> = match &cache {
+            Some(cache) if !font_data.is_empty() => cache.memory_fonts(font_data),
+            _ => compile_cache::parse_fonts(font_data),
+        };
 
         Self {
-            library: LazyHash::new(Library::default()),
-            font_source: FontSource::Cached(get_embedded_fonts()),
+            library: cache.as_ref().map_or_else(
+                || Arc::new(LazyHash::new(Library::default())),
+                |cache| cache.library(),
+            ),
+            font_source,
+            memory_fonts,
+            memory_book: None,
             source,
             images: image_map,
+            clock: unix_now(),
         }
+        .with_memory_book()
+    }
+
+    /// Add the in-memory fonts to the book of the fonts found by search.
+    fn with_memory_book(mut self) -> Self {
+        if !self.memory_fonts.is_empty() {
+            let mut book: typst::text::FontBook = (**self.font_source.book()).clone();
+            for font in &self.memory_fonts {
+                book.push(font.info().clone());
+            }
+            self.memory_book = Some(LazyHash::new(book));
+        }
+        self
+    }
+
+    /// A hash of the markup and every image file, the same on every run and
+    /// platform; the image map is walked in path order.
+    fn content_hash(&self) -> u128 {
+        let mut images: Vec<(&str, &[u8])> = self
+            .images
+            .iter()
+            .map(|(path, data)| (path.as_str(), data.as_slice()))
+            .collect();
+        images.sort_unstable_by_key(|(path, _)| *path);
+        typst::utils::hash128(&(self.source.text(), images))
     }
 }
 
@@ -335,7 +805,9 @@ impl World for MinimalWorld {
     }
 
     fn book(&self) -> &LazyHash<typst::text::FontBook> {
-        self.font_source.book()
+        self.memory_book
+            .as_ref()
+            .unwrap_or_else(|| self.font_source.book())
     }
 
     fn main(&self) -> FileId {
@@ -369,14 +841,18 @@ impl World for MinimalWorld {
     }
 
     fn font(&self, index: usize) -> Option<Font> {
-        self.font_source
-            .fonts()
-            .get(index)
-            .and_then(|slot| slot.get())
+        let searched: &[typst_kit::fonts::FontSlot] = self.font_source.fonts();
+        match searched.get(index) {
+            Some(slot) => slot.get(),
+            None => self.memory_fonts.get(index - searched.len()).cloned(),
+        }
     }
 
-    fn today(&self, _offset: Option<i64>) -> Option<Datetime> {
-        None
+    /// The world's clock, in UTC unless `offset` gives hours east of it:
+    /// without a time zone database, local time is unknown.
+    fn today(&self, offset: Option<i64>) -> Option<Datetime> {
+        let offset: i64 = offset.unwrap_or(0).checked_mul(3600)?;
+        utc_datetime(self.clock.checked_add(offset)?)
     }
 }
 
@@ -409,8 +885,9 @@ pub(crate) fn font_line_metrics_em(family: &str) -> Option<(f64, f64, f64)> {
 
     // Use the same font set the compiler will use (system + discovered
     // Office font dirs); this also primes the compile-time cache.
-    let search_context = super::font_context::resolve_font_search_context(&[]);
-    let data = get_fonts_for_extra_paths(search_context.search_paths());
+    let search_context =
+        super::font_context::resolve_font_search_context(&[], &FontConfig::default());
+    let data = get_fonts_for_extra_paths(search_context.search_paths(), true);
     let metrics: Option<(f64, f64, f64)> = data
         .book
         .select(&key, typst::text::FontVariant::default())
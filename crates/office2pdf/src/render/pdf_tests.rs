@@ -145,7 +145,7 @@ fn make_test_png() -> Vec<u8> {
 fn test_embedded_fonts_are_available() {
     // MinimalWorld should always have embedded fallback fonts available
     // (Libertinus Serif, New Computer Modern, DejaVu Sans Mono)
-    let world = MinimalWorld::new("", &[], &[]);
+    let world = MinimalWorld::new("", &[], &FontSources::paths(&[]));
     assert!(
         !world.font_source.fonts().is_empty(),
         "MinimalWorld should have at least the embedded fallback fonts"
@@ -157,7 +157,7 @@ fn test_system_fonts_enabled() {
     // With system font discovery enabled, on typical systems we should have
     // more fonts than just the embedded set. On minimal systems, we at least
     // have the embedded fonts.
-    let world = MinimalWorld::new("", &[], &[]);
+    let world = MinimalWorld::new("", &[], &FontSources::paths(&[]));
     let embedded_only_count = {
         let mut s = FontSearcher::new();
         s.include_system_fonts(false);
@@ -288,7 +288,7 @@ fn test_compile_with_embedded_svg_image() {
 fn test_embedded_only_world_produces_valid_pdf() {
     // Simulates the WASM code path: embedded fonts only, no system fonts.
     // This verifies that the embedded-only MinimalWorld can produce valid PDFs.
-    let world = MinimalWorld::new_embedded_only("Hello from embedded-only world!", &[]);
+    let world = MinimalWorld::new_embedded_only("Hello from embedded-only world!", &[], &[]);
     assert!(
         !world.font_source.fonts().is_empty(),
         "Embedded-only world should have fonts"
@@ -305,7 +305,7 @@ fn test_embedded_only_world_produces_valid_pdf() {
 fn test_embedded_only_world_has_fonts() {
     // The embedded-only constructor (used on WASM) must have at least
     // the embedded fallback fonts (Libertinus, New Computer Modern, DejaVu).
-    let world = MinimalWorld::new_embedded_only("", &[]);
+    let world = MinimalWorld::new_embedded_only("", &[], &[]);
     let embedded_count = {
         let mut s = FontSearcher::new();
         s.include_system_fonts(false);
@@ -342,10 +342,53 @@ fn test_pdfa_timestamp_is_not_hardcoded() {
 #[test]
 fn test_current_utc_datetime_is_valid() {
     // The helper should produce a valid Datetime that can create a Timestamp.
-    let dt = current_utc_datetime();
+    let dt = current_utc_datetime().unwrap();
     let _ts = typst_pdf::Timestamp::new_utc(dt);
 }
 
+#[test]
+fn test_utc_datetime_round_trips_leap_days() {
+    // Days from the epoch to Feb 29 of a leap year, of a century leap
+    // year and of the latest leap year before this was written.
+    for (year, days) in [(1972, 789), (2000, 11_016), (2024, 19_782)] {
+        let secs: i64 = days * 86_400 + 45_296;
+        assert_eq!(
+            utc_datetime(secs),
+            Datetime::from_ymd_hms(year, 2, 29, 12, 34, 56),
+            "{year}-02-29"
+        );
+        assert_eq!(
+            utc_datetime(secs + 86_400),
+            Datetime::from_ymd_hms(year, 3, 1, 12, 34, 56)
+        );
+    }
+}
+
+#[test]
+fn test_world_today_reports_its_clock() {
+    let mut world = MinimalWorld::new("", &[], &FontSources::paths(&[]));
+    world.clock = 0;
+    assert_eq!(
+        world.today(None),
+        Datetime::from_ymd_hms(1970, 1, 1, 0, 0, 0)
+    );
+    assert_eq!(
+        world.today(Some(-5)),
+        Datetime::from_ymd_hms(1969, 12, 31, 19, 0, 0)
+    );
+}
+
+#[test]
+fn test_header_date_fields_compile_against_the_world_clock() {
+    // The patterns `&D` and `&T` produce must be ones Typst accepts.
+    let source: &str = "#datetime.today().display(\"[day].[month].[year] [hour repr:12 padding:none]:[minute] [period]\");.";
+    let export = PdfExport {
+        deterministic: true,
+        ..PdfExport::default()
+    };
+    assert!(compile_to_pdf_with(source, &[], &FontSources::paths(&[]), &export).is_ok());
+}
+
 #[test]
 fn test_pdfa_timestamp_has_recent_date() {
     // The PDF/A XMP metadata should contain a date from the current
@@ -607,3 +650,104 @@ fn test_tagged_pdf_with_pdfa_combined() {
         "Should contain structure tags"
     );
 }
+
+#[test]
+fn test_compile_to_png_images_one_per_page() {
+    let source = r#"#set page(width: 72pt, height: 36pt, margin: 0pt)
+First
+#pagebreak()
+Second"#;
+    let images = compile_to_images(
+        source,
+        &[],
+        &FontSources::paths(&[]),
+        SlideImageFormat::Png,
+        144.0,
+    )
+    .unwrap();
+    assert_eq!(images.len(), 2);
+    for (index, image) in images.iter().enumerate() {
+        assert_eq!(image.page_index, index);
+        assert!(image.data.starts_with(b"\x89PNG\r\n\x1a\n"));
+        // 144 dpi is two pixels per point.
+        assert_eq!((image.width, image.height), (144, 72));
+    }
+}
+
+#[test]
+fn test_compile_to_svg_images() {
+    let source = r#"#set page(width: 200pt, height: 100pt)
+Hello SVG"#;
+    let images = compile_to_images(
+        source,
+        &[],
+        &FontSources::paths(&[]),
+        SlideImageFormat::Svg,
+        144.0,
+    )
+    .unwrap();
+    assert_eq!(images.len(), 1);
+    let svg: &str = std::str::from_utf8(&images[0].data).unwrap();
+    assert!(svg.starts_with("<svg"), "unexpected SVG start: {svg:.40}");
+    assert_eq!((images[0].width, images[0].height), (200, 100));
+}
+
+#[test]
+fn test_compile_to_images_rejects_non_positive_dpi() {
+    let result = compile_to_images(
+        "Hi",
+        &[],
+        &FontSources::paths(&[]),
+        SlideImageFormat::Png,
+        0.0,
+    );
+    assert!(matches!(result, Err(ConvertError::Render(_))));
+}
+
+#[test]
+fn test_missing_glyphs_are_reported_once_with_their_page() {
+    // Plane 16 private use: no font has a glyph for it.
+    let source: &str = "Plain page\n#pagebreak()\nA \u{10FFFD} and \u{10FFFD} again";
+    let compiled =
+        compile_to_pdf_with(source, &[], &FontSources::paths(&[]), &PdfExport::default()).unwrap();
+    assert_eq!(compiled.page_count, 2);
+    let [glyph] = compiled.missing_glyphs.as_slice() else {
+        panic!("expected one missing glyph: {:?}", compiled.missing_glyphs);
+    };
+    assert_eq!(glyph.character, '\u{10FFFD}');
+    assert_eq!(glyph.page, 2);
+    // Typst may shape the uncovered character as a run of its own.
+    assert!(glyph.text.contains('\u{10FFFD}'), "{}", glyph.text);
+}
+
+#[test]
+fn test_missing_glyph_replacement_still_reports_the_character() {
+    let export = PdfExport {
+        missing_glyph_replacement: Some('?'),
+        ..PdfExport::default()
+    };
+    let compiled =
+        compile_to_pdf_with("A \u{10FFFD}", &[], &FontSources::paths(&[]), &export).unwrap();
+    assert!(compiled.pdf.starts_with(b"%PDF"));
+    assert_eq!(compiled.missing_glyphs.len(), 1);
+}
+
+#[test]
+fn test_replace_characters_writes_typst_escapes() {
+    assert_eq!(
+        replace_characters("#text[a\u{10FFFD}b] \"\u{10FFFD}\"", &['\u{10FFFD}'], '?'),
+        "#text[a\\u{3f}b] \"\\u{3f}\""
+    );
+}
+
+#[test]
+fn test_extend_missing_glyphs_keeps_first_occurrence() {
+    let glyph = |character: char, page: u32| MissingGlyph {
+        character,
+        page,
+        text: String::new(),
+    };
+    let mut found: Vec<MissingGlyph> = vec![glyph('a', 1)];
+    extend_missing_glyphs(&mut found, vec![glyph('a', 1), glyph('b', 2)], 3);
+    assert_eq!(found, vec![glyph('a', 1), glyph('b', 5)]);
+}
@@ -0,0 +1,182 @@
+//! Removal of external references from SVG images before they reach Typst.
+//!
+//! SVGs come straight from the input package, so an `href` or CSS `url()`
+//! could name a file on the converting machine or a remote resource. Typst
+//! resolves such references through the compiler's world, which only holds
+//! this document's images, but a converter fed untrusted uploads should not
+//! depend on that: everything but same-document fragments (`#id`) and
+//! `data:` URIs is dropped here.
+
+use std::borrow::Cow;
+
+use quick_xml::Reader;
+use quick_xml::Writer;
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::{BytesCData, BytesStart, BytesText, Event};
+
+/// What an SVG that cannot be parsed is replaced with: nothing in it could
+/// be checked, and usvg may accept markup quick-xml rejects.
+const EMPTY_SVG: &[u8] = b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>";
+
+/// `data` without references outside the SVG, or `data` itself when it
+/// has none.
+pub(crate) fn sanitize(data: Vec<u8>) -> Vec<u8> {
+    match strip_external_references(&data) {
+        Some(Some(sanitized)) => sanitized,
+        Some(None) => data,
+        None => EMPTY_SVG.to_vec(),
+    }
+}
+
+/// The sanitized SVG, `Some(None)` when nothing had to go, or `None` when
+/// it is not well-formed XML.
+fn strip_external_references(data: &[u8]) -> Option<Option<Vec<u8>>> {
+    let mut reader = Reader::from_reader(data);
+    let mut writer = Writer::new(Vec::with_capacity(data.len()));
+    let mut changed: bool = false;
+    let mut in_style: bool = false;
+    loop {
+        let event: Event = match reader.read_event().ok()? {
+            Event::Eof => break,
+            Event::Start(element) => {
+                in_style = element.local_name().as_ref() == b"style";
+                let (element, stripped) = strip_attributes(&element)?;
+                changed |= stripped;
+                Event::Start(element)
+            }
+            Event::Empty(element) => {
+                let (element, stripped) = strip_attributes(&element)?;
+                changed |= stripped;
+                Event::Empty(element)
+            }
+            Event::End(element) => {
+                in_style = false;
+                Event::End(element)
+            }
+            Event::Text(text) if in_style => {
+                match strip_external_css(&String::from_utf8_lossy(&text)) {
+                    Some(css) => {
+                        changed = true;
+                        Event::Text(BytesText::from_escaped(css))
+                    }
+                    None => Event::Text(text),
+                }
+            }
+            Event::CData(css) if in_style => {
+                match strip_external_css(&String::from_utf8_lossy(&css)) {
+                    Some(css) => {
+                        changed = true;
+                        Event::CData(BytesCData::new(css))
+                    }
+                    None => Event::CData(css),
+                }
+            }
+            // `<?xml-stylesheet href="…"?>` loads a style sheet.
+            Event::PI(_) => {
+                changed = true;
+                continue;
+            }
+            Event::DocType(doctype) if doctype_hides_references(&doctype) => {
+                changed = true;
+                continue;
+            }
+            event => event,
+        };
+        writer.write_event(event).ok()?;
+    }
+    Some(changed.then(|| writer.into_inner()))
+}
+
+/// `element` without external `href`s and with external CSS `url()`s in
+/// its attributes replaced by `none`, and whether anything was removed.
+fn strip_attributes(element: &BytesStart) -> Option<(BytesStart<'static>, bool)> {
+    let mut stripped: BytesStart<'static> = element.clone().into_owned();
+    stripped.clear_attributes();
+    let mut changed: bool = false;
+    for attribute in element.attributes() {
+        let attribute: Attribute = attribute.ok()?;
+        // The raw value is checked, so entity references cannot hide a
+        // target; `&#35;id` is dropped along with the rest.
+        let value: Cow<str> = String::from_utf8_lossy(&attribute.value);
+        if attribute.key.local_name().as_ref() == b"href" {
+            if is_internal_reference(value.trim()) {
+                stripped.push_attribute(attribute);
+            } else {
+                changed = true;
+            }
+        } else if let Some(css) = strip_external_css(&value) {
+            changed = true;
+            stripped.push_attribute(Attribute {
+                key: attribute.key,
+                value: Cow::Owned(css.into_bytes()),
+            });
+        } else {
+            stripped.push_attribute(attribute);
+        }
+    }
+    Some((stripped, changed))
+}
+
+/// Whether a reference stays inside the SVG.
+fn is_internal_reference(target: &str) -> bool {
+    target.starts_with('#')
+        || target
+            .get(..5)
+            .is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:"))
+}
+
+/// `css` with `@import` rules removed and `url()`s naming anything but an
+/// internal reference replaced by `none`, or `None` when it has neither.
+fn strip_external_css(css: &str) -> Option<String> {
+    // ASCII lowercasing keeps byte offsets, so positions carry over.
+    let lower: String = css.to_ascii_lowercase();
+    let mut out: String = String::with_capacity(css.len());
+    let mut changed: bool = false;
+    let mut pos: usize = 0;
+    loop {
+        let import: Option<usize> = lower[pos..].find("@import").map(|i| pos + i);
+        let url: Option<usize> = lower[pos..].find("url(").map(|i| pos + i);
+        let Some(start) = import.into_iter().chain(url).min() else {
+            out.push_str(&css[pos..]);
+            break;
+        };
+        out.push_str(&css[pos..start]);
+        if import == Some(start) {
+            pos = css[start..].find(';').map_or(css.len(), |i| start + i + 1);
+            changed = true;
+            continue;
+        }
+        let target_start: usize = start + "url(".len();
+        let end: usize = css[target_start..]
+            .find(')')
+            .map_or(css.len(), |i| target_start + i);
+        let target: &str = css[target_start..end].trim().trim_matches(['"', '\'']);
+        if is_internal_reference(target.trim()) {
+            out.push_str(&css[start..(end + 1).min(css.len())]);
+        } else {
+            out.push_str("none");
+            changed = true;
+        }
+        pos = (end + 1).min(css.len());
+    }
+    changed.then_some(out)
+}
+
+/// Whether the internal DTD declares entities that expand to markup or CSS
+/// references, which the checks above would only see unexpanded. Plain
+/// entities, like the namespace URIs Illustrator declares, are kept.
+fn doctype_hides_references(doctype: &[u8]) -> bool {
+    let doctype: String = String::from_utf8_lossy(doctype).to_ascii_lowercase();
+    doctype.contains("<!entity")
+        && ["<", "url(", "@import", "href"].iter().any(|needle| {
+            doctype
+                .split(['"', '\''])
+                .skip(1)
+                .step_by(2)
+                .any(|quoted| quoted.contains(needle))
+        })
+}
+
+#[cfg(test)]
+#[path = "svg_sanitize_tests.rs"]
+mod tests;
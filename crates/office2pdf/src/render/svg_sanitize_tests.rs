@@ -0,0 +1,67 @@
+use super::*;
+
+fn sanitized(svg: &str) -> String {
+    String::from_utf8(sanitize(svg.as_bytes().to_vec())).unwrap()
+}
+
+#[test]
+fn test_svg_without_references_is_untouched() {
+    let svg: &str = r##"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+  <defs><linearGradient id="g"><stop offset="0" stop-color="red"/></linearGradient></defs>
+  <rect width="10" height="10" fill="url(#g)"/>
+  <use xlink:href="#g"/>
+  <image href="data:image/png;base64,iVBORw0KGgo=" width="1" height="1"/>
+</svg>"##;
+    assert_eq!(sanitized(svg), svg);
+}
+
+#[test]
+fn test_external_hrefs_are_removed() {
+    let svg: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"><image xlink:href="file:///etc/passwd" width="1" height="1"/><use href="other.svg#shape"/><a href="https://example.com"><rect width="1" height="1"/></a></svg>"##;
+    let result: String = sanitized(svg);
+    assert!(!result.contains("passwd"), "{result}");
+    assert!(!result.contains("other.svg"), "{result}");
+    assert!(!result.contains("example.com"), "{result}");
+    assert!(
+        result.contains(r#"<image width="1" height="1"/>"#),
+        "{result}"
+    );
+}
+
+#[test]
+fn test_external_css_urls_and_imports_are_removed() {
+    let svg: &str = r##"<svg xmlns="http://www.w3.org/2000/svg"><style>@import "https://example.com/a.css"; rect { fill: URL('https://example.com/p.svg#x'); stroke: url(#g) }</style><rect style="filter: url(/tmp/f.svg#blur)" mask="url(https://example.com/m)" width="1" height="1"/></svg>"##;
+    let result: String = sanitized(svg);
+    assert!(!result.contains("example.com"), "{result}");
+    assert!(!result.contains("/tmp"), "{result}");
+    assert!(result.contains("fill: none;"), "{result}");
+    assert!(result.contains("stroke: url(#g)"), "{result}");
+    assert!(result.contains(r#"mask="none""#), "{result}");
+}
+
+#[test]
+fn test_stylesheet_instructions_and_markup_entities_are_removed() {
+    let svg: &str = r#"<?xml-stylesheet href="https://example.com/a.css"?>
+<!DOCTYPE svg [ <!ENTITY pic "<image href='file:///etc/passwd'/>"> ]>
+<svg xmlns="http://www.w3.org/2000/svg">&pic;</svg>"#;
+    let result: String = sanitized(svg);
+    assert!(!result.contains("example.com"), "{result}");
+    assert!(!result.contains("passwd"), "{result}");
+}
+
+#[test]
+fn test_plain_entities_are_kept() {
+    // Illustrator declares its namespaces as entities.
+    let svg: &str = r#"<!DOCTYPE svg [ <!ENTITY ns_svg "http://www.w3.org/2000/svg"> ]>
+<svg xmlns="&ns_svg;"><rect width="1" height="1"/></svg>"#;
+    assert_eq!(sanitized(svg), svg);
+}
+
+#[test]
+fn test_malformed_svg_is_replaced() {
+    assert_eq!(
+        sanitize(b"<svg><image href='x'></svg>".to_vec()),
+        EMPTY_SVG.to_vec()
+    );
+}
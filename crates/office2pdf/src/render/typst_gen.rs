@@ -3,19 +3,22 @@ use std::io::Cursor;
 
 use image::{GenericImageView, ImageFormat as RasterImageFormat};
 
-use crate::config::ConvertOptions;
+use crate::config::{ConvertOptions, EffectFidelity};
 use crate::error::ConvertError;
 use crate::ir::{
-    Alignment, ArrowHead, Block, BorderLineStyle, BorderSide, CellBorder, CellVerticalAlign, Chart,
-    ChartType, Color, ColumnLayout, Document, FixedElement, FixedElementKind, FixedPage,
-    FloatingImage, FloatingShape, FloatingTextBox, FlowPage, FrameAnchor, GradientFill, HFInline,
+    Alignment, ArrowHead, BackgroundImage, Block, BorderLineStyle, BorderSide, CellBorder,
+    CellTextRotation, CellVerticalAlign, Chart, ChartGrouping, ChartType, Color, ColumnLayout,
+    DateTimeField, Document, Extrusion, FixedElement, FixedElementKind, FixedPage, FloatingImage,
+    FloatingShape, FloatingTextBox, FlowPage, FrameAnchor, Glow, GradientFill, HFInline,
     HeaderFooter, HeaderFooterFrame, ImageCrop, ImageData, ImageFormat, Insets, LineBox,
     LineSpacing, List, ListKind, Margins, MathEquation, Metadata, Page, PageSize, Paragraph,
-    ParagraphStyle, PositionedTabAlignment, PositionedTabRelativeTo, Run, Shadow, Shape, ShapeKind,
-    SheetPage, SmartArt, TabAlignment, TabLeader, TabStop, Table, TableCell, TableRow, TextBoxData,
-    TextBoxVerticalAlign, TextDirection, TextStyle, VerticalTextAlign, WrapMode,
+    ParagraphStyle, PositionedTabAlignment, PositionedTabRelativeTo, Reflection, Rotation3d, Run,
+    Shadow, Shape, ShapeEffects, ShapeKind, SheetPage, SmartArt, SmartArtLayout, TabAlignment,
+    TabLeader, TabStop, Table, TableCell, TableRow, TextBoxData, TextBoxVerticalAlign,
+    TextDirection, TextStyle, VerticalTextAlign, WrapMode,
 };
 
+use self::charts::generate_fixed_chart;
 use self::diagrams::{generate_chart, generate_smartart};
 use self::fmt::*;
 use self::lists::{
@@ -23,13 +26,15 @@ use self::lists::{
     write_common_text_settings, write_fixed_text_default_par_settings,
 };
 use self::shapes::{
-    generate_shape, shadow_blur_layers, write_fill_color, write_gradient_fill, write_shape_stroke,
-    write_text_box_shape_background,
+    generate_shape_with_effects, shadow_blur_layers, write_fill_color, write_gradient_fill,
+    write_shape_stroke, write_text_box_shape_background,
 };
 use self::tables::generate_table;
 use self::text::*;
 use super::font_context::FontSearchContext;
 
+#[path = "typst_gen_charts.rs"]
+mod charts;
 #[path = "typst_gen_diagrams.rs"]
 mod diagrams;
 #[path = "typst_gen_fmt.rs"]
@@ -38,6 +43,8 @@ mod fmt;
 mod lists;
 #[path = "typst_gen_shapes.rs"]
 mod shapes;
+#[path = "typst_gen_smartart.rs"]
+mod smartart;
 #[path = "typst_gen_tables.rs"]
 mod tables;
 #[path = "typst_gen_text.rs"]
@@ -61,6 +68,14 @@ pub struct TypstOutput {
     pub images: Vec<ImageAsset>,
 }
 
+/// Label of the metadata a slide text box places when its text is taller
+/// than the box; the compiled document is queried for it.
+pub(crate) const OVERFLOW_LABEL: &str = "office2pdf-overflow";
+/// Overflow smaller than this is rounding in the measured line heights.
+const OVERFLOW_TOLERANCE_PT: f64 = 1.0;
+/// Characters of a text box's text that name it in an overflow warning.
+const OVERFLOW_PREVIEW_CHARS: usize = 40;
+
 /// Maximum nesting depth for tables-within-tables, matching the parser limit.
 const MAX_TABLE_DEPTH: usize = 64;
 /// Typst's line box leaves more top leading than Word/LibreOffice text frames.
@@ -78,6 +93,34 @@ struct GenCtx {
     document_default_tab_stop_pt: Option<f64>,
     /// Effective default tab stop interval, in points, for the active page.
     default_tab_width_pt: f64,
+    /// How glow, soft-edge, and reflection shape effects are rendered.
+    effects: EffectFidelity,
+    /// Sheets that hyperlinks jump to and whose first page has not yet
+    /// been labelled.
+    linked_sheets: std::collections::HashSet<String>,
+    /// Page numbers of a document compiled as one part of a larger PDF.
+    page_numbering: PageNumbering,
+    /// Output page being generated, numbered from 1 across all parts.
+    page_number: u32,
+    /// Source slide of the page being generated, when it is a slide.
+    source_slide: Option<u32>,
+    /// Tagged output: headers, footers, and decoration are marked as
+    /// artifacts so they stay out of the structure tree and reading order.
+    tagged: bool,
+    /// Recompression applied to images as they are added.
+    #[cfg(feature = "image-opt")]
+    image_optimization: Option<crate::config::ImageOptimization>,
+}
+
+/// Page numbering of a document rendered as one part of a merged PDF, as
+/// XLSX streaming chunks are: header and footer page numbers continue from
+/// the earlier parts and the page count covers them all.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct PageNumbering {
+    /// Pages printed before this document.
+    pub(crate) offset: u32,
+    /// Total pages of the merged PDF, once known.
+    pub(crate) total: Option<u32>,
 }
 
 impl GenCtx {
@@ -90,11 +133,31 @@ impl GenCtx {
             line_grid_pitch: None,
             document_default_tab_stop_pt: None,
             default_tab_width_pt: DEFAULT_TAB_WIDTH_PT,
+            effects: EffectFidelity::default(),
+            linked_sheets: std::collections::HashSet::new(),
+            page_numbering: PageNumbering::default(),
+            page_number: 0,
+            source_slide: None,
+            tagged: false,
+            #[cfg(feature = "image-opt")]
+            image_optimization: None,
         }
     }
 
     fn add_image(&mut self, image: &ImageData) -> String {
         let (data, format) = preprocess_image_asset(image);
+        let data: Vec<u8> = if format == ImageFormat::Svg {
+            super::svg_sanitize::sanitize(data)
+        } else {
+            data
+        };
+        #[cfg(feature = "image-opt")]
+        let (data, format) = match &self.image_optimization {
+            Some(settings) => {
+                super::image_opt::optimize(data, format, (image.width, image.height), settings)
+            }
+            None => (data, format),
+        };
         let ext = format.extension();
         let id = self.next_image_id;
         self.next_image_id += 1;
@@ -184,39 +247,46 @@ fn resolve_page_size(original: &PageSize, options: &ConvertOptions) -> PageSize
     }
 }
 
-/// Emit `#set document(title: ..., author: ..., date: ...)` if metadata is present.
+/// Emit `#set document(title: ..., author: ..., description: ..., keywords: ...,
+/// date: ...)` if metadata is present. Typst writes these to both the
+/// document information dictionary and the XMP metadata.
 fn generate_document_metadata(out: &mut String, metadata: &Metadata) {
-    let has_title = metadata.title.is_some();
-    let has_author = metadata.author.is_some();
-    let parsed_date = metadata.created.as_deref().and_then(parse_iso8601_date);
-    if !has_title && !has_author && parsed_date.is_none() {
-        return;
-    }
-
-    out.push_str("#set document(");
-    let mut first = true;
+    let mut fields: Vec<String> = Vec::new();
     if let Some(ref title) = metadata.title {
-        let _ = write!(out, "title: \"{}\"", escape_typst_string(title));
-        first = false;
+        fields.push(format!("title: \"{}\"", escape_typst_string(title)));
     }
     if let Some(ref author) = metadata.author {
-        if !first {
-            out.push_str(", ");
-        }
-        let _ = write!(out, "author: \"{}\"", escape_typst_string(author));
-        first = false;
+        fields.push(format!("author: \"{}\"", escape_typst_string(author)));
+    }
+    // PDF has one `Subject` entry; Office's subject is the closer match, and
+    // its comments stand in when there is none.
+    if let Some(description) = metadata.subject.as_ref().or(metadata.description.as_ref()) {
+        fields.push(format!(
+            "description: \"{}\"",
+            escape_typst_string(description)
+        ));
+    }
+    if !metadata.keywords.is_empty() {
+        let keywords: Vec<String> = metadata
+            .keywords
+            .iter()
+            .map(|keyword| format!("\"{}\"", escape_typst_string(keyword)))
+            .collect();
+        // The trailing comma keeps a single keyword an array.
+        fields.push(format!("keywords: ({},)", keywords.join(", ")));
     }
-    if let Some((year, month, day, hour, minute, second)) = parsed_date {
-        if !first {
-            out.push_str(", ");
-        }
-        let _ = write!(
-            out,
+    if let Some((year, month, day, hour, minute, second)) =
+        metadata.created.as_deref().and_then(parse_iso8601_date)
+    {
+        fields.push(format!(
             "date: datetime(year: {year}, month: {month}, day: {day}, \
              hour: {hour}, minute: {minute}, second: {second})"
-        );
+        ));
     }
-    out.push_str(")\n");
+    if fields.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "#set document({})", fields.join(", "));
 }
 
 /// Parse an ISO 8601 date string (e.g. `2024-06-15T10:30:00Z`) into components.
@@ -281,34 +351,98 @@ pub(crate) fn generate_typst_with_options_and_font_context(
     options: &ConvertOptions,
     font_context: Option<&FontSearchContext>,
 ) -> Result<TypstOutput, ConvertError> {
-    super::font_subst::with_font_search_context(font_context, || {
-        // Pre-allocate output string: ~2KB per page is a reasonable estimate
-        let mut out = String::with_capacity(doc.pages.len() * 2048);
+    generate_typst_part(doc, options, font_context, PageNumbering::default())
+}
 
-        // Emit document metadata (title/author) if present
-        generate_document_metadata(&mut out, &doc.metadata);
+/// Generate Typst markup for one part of a PDF assembled from several
+/// separately compiled documents.
+pub(crate) fn generate_typst_part(
+    doc: &Document,
+    options: &ConvertOptions,
+    font_context: Option<&FontSearchContext>,
+    page_numbering: PageNumbering,
+) -> Result<TypstOutput, ConvertError> {
+    super::font_subst::with_font_rules(&options.fonts, || {
+        super::font_subst::with_font_search_context(font_context, || {
+            generate_typst_pages(doc, options, page_numbering)
+        })
+    })
+}
 
-        let mut ctx = GenCtx::new();
-        ctx.document_default_tab_stop_pt = doc.styles.default_tab_stop_pt;
-        for (index, page) in doc.pages.iter().enumerate() {
-            if index > 0 {
-                out.push_str("\n#pagebreak()\n");
-            }
-            match page {
-                Page::Flow(flow) => generate_flow_page(&mut out, flow, &mut ctx, options)?,
-                Page::Fixed(fixed) => generate_fixed_page(&mut out, fixed, &mut ctx, options)?,
-                Page::Sheet(sheet_page) => {
-                    generate_table_page(&mut out, sheet_page, &mut ctx, options)?;
-                }
+fn generate_typst_pages(
+    doc: &Document,
+    options: &ConvertOptions,
+    page_numbering: PageNumbering,
+) -> Result<TypstOutput, ConvertError> {
+    // Pre-allocate output string: ~2KB per page is a reasonable estimate
+    let mut out = String::with_capacity(doc.pages.len() * 2048);
+
+    // Emit document metadata (title/author) if present
+    generate_document_metadata(&mut out, &doc.metadata);
+    // Text without a family of its own falls back like the rest.
+    if let Some(fonts) = super::font_subst::default_font_with_fallbacks() {
+        let _ = writeln!(out, "#set text(font: {fonts})");
+    }
+    // Typst writes the language most of the text is set in to the PDF
+    // catalog, so the document language becomes the text default.
+    if let Some(params) = doc.metadata.language.as_deref().and_then(language_params) {
+        let _ = writeln!(out, "#set text({params})");
+    }
+
+    let mut ctx = GenCtx::new();
+    ctx.document_default_tab_stop_pt = doc.styles.default_tab_stop_pt;
+    ctx.effects = options.effects;
+    ctx.linked_sheets = collect_sheet_link_targets(&doc.pages);
+    ctx.page_numbering = page_numbering;
+    ctx.tagged = options.tagged || options.pdf_ua;
+    #[cfg(feature = "image-opt")]
+    {
+        ctx.image_optimization = options.image_optimization;
+    }
+    for (index, page) in doc.pages.iter().enumerate() {
+        if index > 0 {
+            out.push_str("\n#pagebreak()\n");
+        }
+        ctx.page_number = page_numbering.offset + index as u32 + 1;
+        ctx.source_slide = match page {
+            Page::Fixed(fixed) => fixed.source_slide,
+            _ => None,
+        };
+        match page {
+            Page::Flow(flow) => generate_flow_page(&mut out, flow, &mut ctx, options)?,
+            Page::Fixed(fixed) => generate_fixed_page(&mut out, fixed, &mut ctx, options)?,
+            Page::Sheet(sheet_page) => {
+                generate_table_page(&mut out, sheet_page, &mut ctx, options)?;
             }
         }
-        Ok(TypstOutput {
-            source: out,
-            images: ctx.images,
-        })
+    }
+    Ok(TypstOutput {
+        source: out,
+        images: ctx.images,
     })
 }
 
+/// Names of the sheets that cell hyperlinks jump to.
+fn collect_sheet_link_targets(pages: &[Page]) -> std::collections::HashSet<String> {
+    pages
+        .iter()
+        .filter_map(|page| match page {
+            Page::Sheet(sheet_page) => Some(sheet_page),
+            _ => None,
+        })
+        .flat_map(|sheet_page| &sheet_page.table.rows)
+        .flat_map(|row| &row.cells)
+        .flat_map(|cell| &cell.content)
+        .filter_map(|block| match block {
+            Block::Paragraph(paragraph) => Some(paragraph),
+            _ => None,
+        })
+        .flat_map(|paragraph| &paragraph.runs)
+        .filter_map(|run| run.href.as_deref().and_then(crate::ir::sheet_link_target))
+        .map(str::to_string)
+        .collect()
+}
+
 fn generate_flow_page(
     out: &mut String,
     page: &FlowPage,
@@ -437,12 +571,82 @@ fn generate_fixed_page(
     }
     out.push('\n');
 
+    if let Some(ref background) = page.background_image {
+        write_artifact_markup_start(out, ctx, "page");
+        write_background_image(out, background, ctx);
+        write_artifact_markup_end(out, ctx);
+    }
     for elem in &page.elements {
-        generate_fixed_element(out, elem, ctx)?;
+        if is_decorative_element(elem) {
+            write_artifact_markup_start(out, ctx, "other");
+            generate_fixed_element(out, elem, ctx)?;
+            write_artifact_markup_end(out, ctx);
+        } else {
+            generate_fixed_element(out, elem, ctx)?;
+        }
     }
     Ok(())
 }
 
+/// Whether a slide element only decorates the page. Bare shapes carry no
+/// text, so tagging them only adds empty entries to the reading order;
+/// a linked shape stays tagged because its link must remain reachable.
+fn is_decorative_element(elem: &FixedElement) -> bool {
+    matches!(elem.kind, FixedElementKind::Shape(_)) && elem.href.is_none()
+}
+
+/// Open a `pdf.artifact` call of `kind` ahead of a code-mode expression,
+/// such as a page's `header:` value, when the output is tagged.
+fn write_artifact_start(out: &mut String, ctx: &GenCtx, kind: &str) {
+    if ctx.tagged {
+        let _ = write!(out, "pdf.artifact(kind: \"{kind}\", ");
+    }
+}
+
+/// Close a call opened by [`write_artifact_start`].
+fn write_artifact_end(out: &mut String, ctx: &GenCtx) {
+    if ctx.tagged {
+        out.push(')');
+    }
+}
+
+/// Open a `#pdf.artifact` block of `kind` in markup when the output is
+/// tagged.
+fn write_artifact_markup_start(out: &mut String, ctx: &GenCtx, kind: &str) {
+    if ctx.tagged {
+        let _ = writeln!(out, "#pdf.artifact(kind: \"{kind}\")[");
+    }
+}
+
+/// Close a block opened by [`write_artifact_markup_start`].
+fn write_artifact_markup_end(out: &mut String, ctx: &GenCtx) {
+    if ctx.tagged {
+        out.push_str("]\n");
+    }
+}
+
+/// Emit a page-covering picture background ahead of the page content. Tiled
+/// pictures fill the page through a `tiling` pattern anchored at the top-left.
+fn write_background_image(out: &mut String, background: &BackgroundImage, ctx: &mut GenCtx) {
+    let path: String = ctx.add_image(&background.image);
+    match background.tile_size {
+        Some((tile_width, tile_height)) => {
+            let _ = writeln!(
+                out,
+                "#place(top + left, rect(width: 100%, height: 100%, fill: tiling(size: ({w}pt, {h}pt))[#image(\"{path}\", width: {w}pt, height: {h}pt, fit: \"stretch\")]))",
+                w = format_f64(tile_width),
+                h = format_f64(tile_height),
+            );
+        }
+        None => {
+            let _ = writeln!(
+                out,
+                "#place(top + left, image(\"{path}\", width: 100%, height: 100%, fit: \"stretch\"))",
+            );
+        }
+    }
+}
+
 fn generate_table_page(
     out: &mut String,
     page: &SheetPage,
@@ -452,6 +656,10 @@ fn generate_table_page(
     let size = resolve_page_size(&page.size, options);
     write_table_page_setup(out, page, &size, ctx);
     out.push('\n');
+    // Hyperlinks into this sheet jump to its first page.
+    if ctx.linked_sheets.remove(&page.name) {
+        let _ = writeln!(out, "#metadata(none) <{}>", sheet_label(&page.name));
+    }
 
     if page.charts.is_empty() && page.images.is_empty() && page.text_boxes.is_empty() {
         generate_table(out, &page.table, ctx)?;
@@ -508,6 +716,13 @@ fn generate_table_with_anchors(
     let mut row_start = 0usize;
     let mut chart_idx = 0;
 
+    // Anchors at row 0 sit above the first row.
+    while chart_idx < sorted_charts.len() && sorted_charts[chart_idx].0 == 0 {
+        generate_sheet_anchor(out, &sorted_charts[chart_idx].1, ctx);
+        out.push('\n');
+        chart_idx += 1;
+    }
+
     // Walk through rows and emit table segments + charts
     for row_end in 0..total_rows {
         let row_num = (row_end + 1) as u32; // 1-indexed row number
@@ -605,14 +820,15 @@ fn generate_sheet_anchor(out: &mut String, anchor: &SheetAnchor, ctx: &mut GenCt
             out.push_str("]]]\n");
         }
         SheetAnchor::Image(sheet_image) => {
-            // Keep the anchor's horizontal position: reserve the image height
-            // in the flow and place the image at its column offset.
+            // Keep the anchor's position: reserve the image height (plus its
+            // offset into the row) in the flow and place the image there.
             let height: f64 = sheet_image.image.height.unwrap_or(100.0);
             let _ = write!(
                 out,
-                "#box(width: 100%, height: {}pt)[#place(top + left, dx: {}pt)[",
-                format_f64(height),
+                "#box(width: 100%, height: {}pt)[#place(top + left, dx: {}pt, dy: {}pt)[",
+                format_f64(height + sheet_image.y_offset_pt),
                 format_f64(sheet_image.x_offset_pt),
+                format_f64(sheet_image.y_offset_pt),
             );
             generate_image(out, &sheet_image.image, ctx);
             out.push_str("]]\n");
@@ -669,18 +885,30 @@ fn generate_fixed_element(
                     format_f64(elem.x),
                     format_f64(elem.y),
                 );
-                let _ = write!(
-                    out,
-                    "#rect(width: {}pt, height: {}pt, fill: none, stroke: ",
-                    format_f64(elem.width),
-                    format_f64(elem.height),
-                );
-                shapes::write_image_border_stroke(out, stroke);
-                out.push_str(")\n");
+                if stroke.style == BorderLineStyle::Double {
+                    shapes::write_double_outline(
+                        out,
+                        "rect",
+                        (0.0, 0.0),
+                        elem.width,
+                        elem.height,
+                        None,
+                        stroke,
+                    );
+                } else {
+                    let _ = write!(
+                        out,
+                        "#rect(width: {}pt, height: {}pt, fill: none, stroke: ",
+                        format_f64(elem.width),
+                        format_f64(elem.height),
+                    );
+                    shapes::write_image_border_stroke(out, stroke);
+                    out.push_str(")\n");
+                }
             }
         }
         FixedElementKind::Shape(shape) => {
-            generate_shape(out, shape, elem.width, elem.height);
+            generate_shape_with_effects(out, shape, elem.width, elem.height, ctx.effects);
         }
         FixedElementKind::Table(table) => {
             generate_table(out, table, ctx)?;
@@ -689,11 +917,25 @@ fn generate_fixed_element(
             generate_smartart(out, smartart, elem.width, elem.height);
         }
         FixedElementKind::Chart(chart) => {
-            generate_chart(out, chart);
+            generate_fixed_chart(out, chart, elem.width, elem.height);
         }
     }
 
     out.push_str("]\n");
+
+    // An empty box over the frame keeps the link area independent of how
+    // the element's content is laid out.
+    if let Some(ref href) = elem.href {
+        let _ = writeln!(
+            out,
+            "#place(top + left, dx: {}pt, dy: {}pt)[#link({})[#box(width: {}pt, height: {}pt)]]",
+            format_f64(elem.x),
+            format_f64(elem.y),
+            link_destination(href),
+            format_f64(elem.width.max(0.0)),
+            format_f64(elem.height.max(0.0)),
+        );
+    }
     Ok(())
 }
 
@@ -737,6 +979,7 @@ fn generate_fixed_text_box(
             width: elem.height,
             height: elem.width,
             kind: elem.kind.clone(),
+            href: None,
         };
         // The outer #place pins the top-left of a width x height region;
         // center the swapped box on that region before rotating in place.
@@ -779,10 +1022,26 @@ fn generate_fixed_text_box(
         if let Some(fill) = &text_box.fill {
             write_fill_color(out, fill, text_box.opacity);
         }
-        write_shape_stroke(out, &text_box.stroke);
+        if shapes::double_stroke(&text_box.stroke).is_none() {
+            write_shape_stroke(out, &text_box.stroke);
+        }
     }
     out.push_str(")[\n");
 
+    // A double outline is placed inside the block, offset by its inset.
+    if !has_custom_shape && let Some(side) = shapes::double_stroke(&text_box.stroke) {
+        out.push_str("  ");
+        shapes::write_double_outline(
+            out,
+            "rect",
+            (-text_box.padding.left, -text_box.padding.top),
+            outer_width_pt,
+            outer_height_pt,
+            None,
+            side,
+        );
+    }
+
     // Render non-rectangular shape background via #place overlay.
     if let Some(ref shape_kind) = text_box.shape_kind {
         write_text_box_shape_background(
@@ -796,6 +1055,7 @@ fn generate_fixed_text_box(
             &text_box.stroke,
         );
     }
+    let mut scaled: bool = true;
     if let Some(paragraph) = single_line_fit_paragraph(text_box, inner_height_pt) {
         let mut raw_paragraph: Paragraph = paragraph.clone();
         raw_paragraph.style.alignment = None;
@@ -865,6 +1125,7 @@ fn generate_fixed_text_box(
         out.push_str("    ]\n");
         out.push_str("  }\n");
     } else {
+        scaled = false;
         let _ = writeln!(
             out,
             "  #let text_box_content_{text_box_id} = block(width: {}pt)[",
@@ -904,11 +1165,65 @@ fn generate_fixed_text_box(
             out.push_str("  }\n");
         }
     }
+    // The fit branches scale their text into the box; everything else can
+    // spill past it.
+    if !scaled {
+        write_overflow_marker(out, text_box, text_box_id, inner_height_pt, ctx);
+    }
 
     out.push_str("]\n");
     Ok(())
 }
 
+/// Place a labelled marker when the text box's content measures taller
+/// than the box, for [`OVERFLOW_LABEL`] queries after layout.
+fn write_overflow_marker(
+    out: &mut String,
+    text_box: &TextBoxData,
+    text_box_id: usize,
+    inner_height_pt: f64,
+    ctx: &GenCtx,
+) {
+    if text_box.content.is_empty() {
+        return;
+    }
+    let source: String = ctx
+        .source_slide
+        .map(|slide| format!(", source: {slide}"))
+        .unwrap_or_default();
+    let _ = writeln!(
+        out,
+        "  #context if measure(text_box_content_{text_box_id}).height > {}pt [#metadata((slide: {}{source}, element: \"{}\")) <{OVERFLOW_LABEL}>]",
+        format_f64(inner_height_pt + OVERFLOW_TOLERANCE_PT),
+        ctx.page_number,
+        escape_typst_string(&text_box_description(text_box)),
+    );
+}
+
+/// A text box named by the start of its text, as a reader finds it on the
+/// slide.
+fn text_box_description(text_box: &TextBoxData) -> String {
+    let text: String = text_box
+        .content
+        .iter()
+        .filter_map(|block| match block {
+            Block::Paragraph(paragraph) => Some(paragraph),
+            _ => None,
+        })
+        .flat_map(|paragraph| &paragraph.runs)
+        .map(|run| run.text.as_str())
+        .collect::<String>();
+    let words: String = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if words.is_empty() {
+        return "text box".to_string();
+    }
+    let mut preview: String = words.chars().take(OVERFLOW_PREVIEW_CHARS).collect();
+    if words.chars().count() > OVERFLOW_PREVIEW_CHARS {
+        preview.push('…');
+    }
+    format!("text box \"{preview}\"")
+}
+
 fn write_page_setup(out: &mut String, size: &PageSize, margins: &Margins) {
     let _ = writeln!(
         out,
@@ -943,13 +1258,16 @@ fn write_flow_page_setup(out: &mut String, page: &FlowPage, size: &PageSize, ctx
     if let Some(header) = &page.header
         && hf_has_flow_content(header)
     {
+        out.push_str(", header: ");
+        write_artifact_start(out, ctx, "header");
         if hf_needs_context(header) {
-            out.push_str(", header: context [");
+            out.push_str("context [");
         } else {
-            out.push_str(", header: [");
+            out.push('[');
         }
         generate_flow_hf_content(out, header, ctx);
         out.push(']');
+        write_artifact_end(out, ctx);
     }
 
     if let Some(footer) = &page.footer
@@ -959,8 +1277,10 @@ fn write_flow_page_setup(out: &mut String, page: &FlowPage, size: &PageSize, ctx
             .distance_from_edge
             .map(|distance| (page.margins.bottom - distance).max(0.0))
             .unwrap_or(0.0);
+        out.push_str(", footer: ");
+        write_artifact_start(out, ctx, "footer");
         if hf_needs_stack_offset(footer) || edge_offset > 0.0 {
-            out.push_str(", footer: context { let footer_content = block(width: 100%)[");
+            out.push_str("context { let footer_content = block(width: 100%)[");
             generate_flow_hf_content(out, footer, ctx);
             out.push_str("]; move(dy: ");
             if hf_needs_stack_offset(footer) {
@@ -973,14 +1293,15 @@ fn write_flow_page_setup(out: &mut String, page: &FlowPage, size: &PageSize, ctx
             }
             out.push_str(")[#footer_content] }");
         } else if hf_needs_context(footer) {
-            out.push_str(", footer: context [");
+            out.push_str("context [");
             generate_flow_hf_content(out, footer, ctx);
             out.push(']');
         } else {
-            out.push_str(", footer: [");
+            out.push('[');
             generate_flow_hf_content(out, footer, ctx);
             out.push(']');
         }
+        write_artifact_end(out, ctx);
     }
 
     if page
@@ -991,10 +1312,14 @@ fn write_flow_page_setup(out: &mut String, page: &FlowPage, size: &PageSize, ctx
     {
         out.push_str(", foreground: [");
         if let Some(header) = &page.header {
+            write_artifact_markup_start(out, ctx, "header");
             generate_page_anchored_hf_frames(out, header, size.width, page.margins.right, ctx);
+            write_artifact_markup_end(out, ctx);
         }
         if let Some(footer) = &page.footer {
+            write_artifact_markup_start(out, ctx, "footer");
             generate_page_anchored_hf_frames(out, footer, size.width, page.margins.right, ctx);
+            write_artifact_markup_end(out, ctx);
         }
         out.push(']');
     }
@@ -1117,29 +1442,35 @@ fn write_table_page_setup(out: &mut String, page: &SheetPage, size: &PageSize, c
     );
 
     if let Some(header) = &page.header {
+        out.push_str(", header: ");
+        write_artifact_start(out, ctx, "header");
         if hf_needs_context(header) {
-            out.push_str(", header: context [");
+            out.push_str("context [");
         } else {
-            out.push_str(", header: [");
+            out.push('[');
         }
         generate_hf_content(out, header, ctx);
         out.push(']');
+        write_artifact_end(out, ctx);
     }
 
     if let Some(footer) = &page.footer {
+        out.push_str(", footer: ");
+        write_artifact_start(out, ctx, "footer");
         if hf_needs_stack_offset(footer) {
-            out.push_str(", footer: context { let footer_content = block(width: 100%)[");
+            out.push_str("context { let footer_content = block(width: 100%)[");
             generate_hf_content(out, footer, ctx);
             out.push_str("]; move(dy: -measure(footer_content).height / 2)[#footer_content] }");
         } else if hf_needs_context(footer) {
-            out.push_str(", footer: context [");
+            out.push_str("context [");
             generate_hf_content(out, footer, ctx);
             out.push(']');
         } else {
-            out.push_str(", footer: [");
+            out.push('[');
             generate_hf_content(out, footer, ctx);
             out.push(']');
         }
+        write_artifact_end(out, ctx);
     }
 
     out.push_str(")\n");
@@ -1308,9 +1639,20 @@ fn generate_hf_elements(out: &mut String, elements: &[HFInline], ctx: &mut GenCt
         match element {
             HFInline::Run(run) => generate_run(out, run),
             HFInline::Image(image) => generate_image(out, image, ctx),
-            HFInline::PageNumber => out.push_str("#counter(page).display()"),
-            HFInline::TotalPages => out.push_str("#counter(page).final().first()"),
+            HFInline::PageNumber => match ctx.page_numbering.offset {
+                0 => out.push_str("#counter(page).display()"),
+                offset => {
+                    let _ = write!(out, "#counter(page).display(n => str(n + {offset}))");
+                }
+            },
+            HFInline::TotalPages => match ctx.page_numbering.total {
+                Some(total) => {
+                    let _ = write!(out, "{total}");
+                }
+                None => out.push_str("#counter(page).final().first()"),
+            },
             HFInline::PositionedTab(_) => out.push_str("#h(1em)"),
+            HFInline::DateTime(field) => generate_date_time(out, field),
         }
     }
 }
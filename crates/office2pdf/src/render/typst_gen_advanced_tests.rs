@@ -336,6 +336,46 @@ fn test_generate_typst_with_metadata_title_and_author() {
     );
 }
 
+#[test]
+fn test_generate_typst_sets_document_and_run_languages() {
+    let doc = Document {
+        metadata: Metadata {
+            language: Some("en-US".to_string()),
+            ..Default::default()
+        },
+        pages: vec![make_flow_page(vec![Block::Paragraph(Paragraph {
+            runs: vec![
+                Run {
+                    text: "Revenue ".to_string(),
+                    style: TextStyle::default(),
+                    footnote: None,
+                    href: None,
+                },
+                Run {
+                    text: "Umsatz".to_string(),
+                    style: TextStyle {
+                        language: Some("de-DE".to_string()),
+                        ..TextStyle::default()
+                    },
+                    footnote: None,
+                    href: None,
+                },
+            ],
+            style: ParagraphStyle::default(),
+        })])],
+        styles: StyleSheet::default(),
+    };
+    let result = generate_typst(&doc).unwrap().source;
+    assert!(
+        result.contains("#set text(lang: \"en\", region: \"US\")"),
+        "Expected document language, got: {result}"
+    );
+    assert!(
+        result.contains("#text(lang: \"de\", region: \"DE\")[Umsatz]"),
+        "Expected run language switch, got: {result}"
+    );
+}
+
 #[test]
 fn test_generate_typst_with_metadata_title_only() {
     let doc = Document {
@@ -361,6 +401,29 @@ fn test_generate_typst_with_metadata_title_only() {
     );
 }
 
+#[test]
+fn test_generate_typst_with_metadata_subject_and_keywords() {
+    let mut doc = make_doc(vec![make_flow_page(vec![])]);
+    doc.metadata = Metadata {
+        subject: Some("Budget \"2024\"".to_string()),
+        description: Some("Comments".to_string()),
+        keywords: vec!["finance".to_string()],
+        ..Default::default()
+    };
+    let result = generate_typst(&doc).unwrap().source;
+    assert!(
+        result.contains(r#"#set document(description: "Budget \"2024\"", keywords: ("finance",))"#),
+        "Expected subject and keywords in Typst output, got: {result}"
+    );
+
+    doc.metadata.subject = None;
+    let result = generate_typst(&doc).unwrap().source;
+    assert!(
+        result.contains(r#"description: "Comments""#),
+        "Expected the description to stand in for the subject, got: {result}"
+    );
+}
+
 #[test]
 fn test_generate_typst_without_metadata() {
     let doc = make_doc(vec![make_flow_page(vec![Block::Paragraph(Paragraph {
@@ -954,6 +1017,145 @@ fn test_spill_width_codegen() {
     );
 }
 
+/// Typst source for a one-cell sheet page.
+fn single_cell_sheet_source(cell: TableCell) -> String {
+    let table = Table {
+        rows: vec![TableRow {
+            cells: vec![cell],
+            height: None,
+        }],
+        column_widths: vec![60.0],
+        ..Table::default()
+    };
+    let page = Page::Sheet(SheetPage {
+        name: "Sheet1".to_string(),
+        size: PageSize::default(),
+        margins: Margins::default(),
+        table,
+        header: None,
+        footer: None,
+        charts: vec![],
+        images: Vec::new(),
+        text_boxes: Vec::new(),
+    });
+    generate_typst(&make_doc(vec![page])).unwrap().source
+}
+
+fn text_cell(text: &str) -> TableCell {
+    TableCell {
+        content: vec![Block::Paragraph(Paragraph {
+            style: ParagraphStyle::default(),
+            runs: vec![Run {
+                text: text.to_string(),
+                style: TextStyle::default(),
+                href: None,
+                footnote: None,
+            }],
+        })],
+        ..TableCell::default()
+    }
+}
+
+#[test]
+fn test_rotated_cell_codegen() {
+    let source: String = single_cell_sheet_source(TableCell {
+        text_rotation: Some(CellTextRotation::Angle(90.0)),
+        ..text_cell("Up")
+    });
+    assert!(
+        source.contains("rotate(-90deg, reflow: true, box(width: natural, body))"),
+        "rotated text must turn counterclockwise on one line. Got: {source}"
+    );
+}
+
+#[test]
+fn test_stacked_cell_codegen() {
+    let source: String = single_cell_sheet_source(TableCell {
+        text_rotation: Some(CellTextRotation::Stacked),
+        ..text_cell("Tall")
+    });
+    assert!(
+        source.contains(r#"#show regex("\\S"): it => block("#),
+        "stacked text must put each letter on its own line. Got: {source}"
+    );
+}
+
+#[test]
+fn test_shrink_to_fit_cell_codegen() {
+    let source: String = single_cell_sheet_source(TableCell {
+        shrink_to_fit: true,
+        ..text_cell("Shrink me to fit")
+    });
+    assert!(
+        source.contains("scale(x: fit, y: fit"),
+        "shrink-to-fit must scale overflowing text into the cell. Got: {source}"
+    );
+}
+
+#[test]
+fn test_no_wrap_cell_codegen() {
+    let source: String = single_cell_sheet_source(TableCell {
+        no_wrap: true,
+        ..text_cell("Clipped at the cell edge")
+    });
+    assert!(
+        source.contains("box(width: size.width, clip: true"),
+        "unwrapped text must clip at the cell edge. Got: {source}"
+    );
+    assert!(
+        !single_cell_sheet_source(text_cell("Plain")).contains("#layout("),
+        "plain cells must not pay for measuring"
+    );
+}
+
+#[test]
+fn test_sheet_link_targets_labelled_first_sheet_page() {
+    let sheet_page = |name: &str, cell: TableCell| -> Page {
+        Page::Sheet(SheetPage {
+            name: name.to_string(),
+            size: PageSize::default(),
+            margins: Margins::default(),
+            table: Table {
+                rows: vec![TableRow {
+                    cells: vec![cell],
+                    height: None,
+                }],
+                column_widths: vec![60.0],
+                ..Table::default()
+            },
+            header: None,
+            footer: None,
+            charts: vec![],
+            images: Vec::new(),
+            text_boxes: Vec::new(),
+        })
+    };
+    let mut link_cell: TableCell = text_cell("Go");
+    if let Block::Paragraph(paragraph) = &mut link_cell.content[0] {
+        paragraph.runs[0].href = Some(crate::ir::sheet_link("Q1"));
+    }
+    let doc = make_doc(vec![
+        sheet_page("Index", link_cell),
+        sheet_page("Q1", text_cell("first")),
+        sheet_page("Q1", text_cell("continued")),
+    ]);
+    let source: String = generate_typst(&doc).unwrap().source;
+
+    assert!(
+        source.contains("#link(<sheet-5131>)"),
+        "sheet links must jump to the sheet label. Got: {source}"
+    );
+    assert_eq!(
+        source.matches("#metadata(none) <sheet-5131>").count(),
+        1,
+        "only the sheet's first page carries the label. Got: {source}"
+    );
+    assert!(
+        !source.contains("<sheet-496e646578>"),
+        "unlinked sheets get no label. Got: {source}"
+    );
+}
+
 #[test]
 fn test_table_default_vertical_align_codegen() {
     let table = Table {
@@ -1029,12 +1231,15 @@ fn test_vert_text_box_remaps_insets() {
         width: 100.0,
         height: 50.0,
         kind: FixedElementKind::TextBox(text_box),
+        href: None,
     };
     let page = Page::Fixed(FixedPage {
         size: PageSize::default(),
         elements: vec![elem],
         background_color: None,
         background_gradient: None,
+        background_image: None,
+        source_slide: None,
     });
     let doc = make_doc(vec![page]);
     let output = generate_typst(&doc).unwrap();
@@ -0,0 +1,318 @@
+use super::*;
+
+fn make_chart_element(width: f64, height: f64, chart: Chart) -> FixedElement {
+    FixedElement {
+        x: 40.0,
+        y: 30.0,
+        width,
+        height,
+        kind: FixedElementKind::Chart(chart),
+        href: None,
+    }
+}
+
+fn make_chart(chart_type: ChartType, series: Vec<(&str, Vec<f64>)>) -> Chart {
+    let point_count: usize = series
+        .iter()
+        .map(|(_, values)| values.len())
+        .max()
+        .unwrap_or(0);
+    Chart {
+        chart_type,
+        title: Some("Revenue".to_string()),
+        categories: (1..=point_count).map(|i| format!("Q{i}")).collect(),
+        series: series
+            .into_iter()
+            .map(|(name, values)| ChartSeries {
+                name: Some(name.to_string()),
+                values,
+                bubble_sizes: Vec::new(),
+                chart_type: None,
+                secondary_axis: false,
+            })
+            .collect(),
+        grouping: ChartGrouping::Clustered,
+    }
+}
+
+fn chart_source(width: f64, height: f64, chart: Chart) -> String {
+    let doc = make_doc(vec![make_fixed_page(
+        720.0,
+        540.0,
+        vec![make_chart_element(width, height, chart)],
+    )]);
+    generate_typst(&doc).unwrap().source
+}
+
+#[test]
+fn test_fixed_chart_fills_element_frame() {
+    let source = chart_source(
+        400.0,
+        250.0,
+        make_chart(ChartType::Column, vec![("North", vec![10.0, 20.0])]),
+    );
+    assert!(
+        source.contains("#box(width: 400pt, height: 250pt)["),
+        "Expected chart frame sized to the element, got:\n{source}"
+    );
+    assert!(source.contains("Revenue"), "Expected title, got:\n{source}");
+    assert!(source.contains("Q1") && source.contains("Q2"));
+    // Value tick labels from the nice 0..20 axis.
+    assert!(
+        source.contains("[20]"),
+        "Expected tick label, got:\n{source}"
+    );
+    assert!(!source.contains("Column Chart"));
+}
+
+#[test]
+fn test_fixed_chart_bars_scale_with_frame() {
+    let bar_heights = |height: f64| -> Vec<f64> {
+        let source = chart_source(
+            300.0,
+            height,
+            make_chart(ChartType::Column, vec![("North", vec![10.0, 20.0])]),
+        );
+        source
+            .lines()
+            .filter(|line| line.contains("rect(width:") && line.contains("fill: rgb(68, 114, 196)"))
+            .map(|line| {
+                let start: usize = line.find("height: ").unwrap() + "height: ".len();
+                let end: usize = start + line[start..].find("pt").unwrap();
+                line[start..end].parse::<f64>().unwrap()
+            })
+            .collect()
+    };
+
+    let short: Vec<f64> = bar_heights(200.0);
+    let tall: Vec<f64> = bar_heights(400.0);
+    assert_eq!(short.len(), 2);
+    assert_eq!(tall.len(), 2);
+    // The 20 bar spans the full axis, the 10 bar half of it.
+    assert!((short[1] - 2.0 * short[0]).abs() < 0.05, "{short:?}");
+    assert!(tall[1] > short[1] * 1.8, "{short:?} vs {tall:?}");
+}
+
+#[test]
+fn test_fixed_chart_negative_values_draw_below_baseline() {
+    let source = chart_source(
+        300.0,
+        200.0,
+        make_chart(ChartType::Column, vec![("Profit", vec![-5.0, 10.0])]),
+    );
+    // Nice axis from -8 to 12 in steps of 4 straddles the zero baseline.
+    assert!(
+        source.contains("[-4]"),
+        "Expected negative tick, got:\n{source}"
+    );
+    assert!(source.contains("[0]"), "Expected zero tick, got:\n{source}");
+}
+
+#[test]
+fn test_fixed_chart_multi_series_line_has_legend_and_polylines() {
+    let source = chart_source(
+        360.0,
+        240.0,
+        make_chart(
+            ChartType::Line,
+            vec![
+                ("North", vec![1.0, 3.0, 2.0]),
+                ("South", vec![2.0, 1.0, 4.0]),
+            ],
+        ),
+    );
+    assert_eq!(source.matches("path(stroke:").count(), 2, "{source}");
+    assert!(
+        source.contains("circle(radius:"),
+        "Expected markers:\n{source}"
+    );
+    assert!(
+        source.contains("stack(dir: ltr"),
+        "Expected legend:\n{source}"
+    );
+    assert!(source.contains("North") && source.contains("South"));
+}
+
+#[test]
+fn test_fixed_chart_area_is_filled_polygon() {
+    let source = chart_source(
+        300.0,
+        200.0,
+        make_chart(ChartType::Area, vec![("Visits", vec![3.0, 5.0, 4.0])]),
+    );
+    assert!(
+        source.contains("path(fill: rgb(68, 114, 196), stroke: none, closed: true"),
+        "Expected filled area, got:\n{source}"
+    );
+}
+
+#[test]
+fn test_fixed_chart_pie_draws_wedges_with_legend() {
+    let mut chart = make_chart(ChartType::Pie, vec![("Share", vec![30.0, 45.0, 25.0])]);
+    chart.categories = vec!["Apple".into(), "Banana".into(), "Cherry".into()];
+    let source = chart_source(300.0, 300.0, chart);
+    assert_eq!(
+        source.matches("closed: true").count(),
+        3,
+        "Expected one wedge per slice, got:\n{source}"
+    );
+    assert!(source.contains("Apple") && source.contains("Cherry"));
+    assert!(
+        !source.contains("Pie Chart"),
+        "Pie plot should not render the table"
+    );
+}
+
+#[test]
+fn test_fixed_chart_scatter_uses_numeric_x_axis() {
+    let mut chart = make_chart(ChartType::Scatter, vec![("Samples", vec![1.0, 4.0])]);
+    chart.categories = vec!["2".into(), "8".into()];
+    let source = chart_source(300.0, 200.0, chart);
+    assert_eq!(source.matches("circle(radius:").count(), 2, "{source}");
+    assert!(
+        source.contains("[10]"),
+        "Expected x tick label, got:\n{source}"
+    );
+    assert!(!source.contains("path(stroke:"));
+}
+
+#[test]
+fn test_fixed_chart_unknown_type_falls_back_to_table() {
+    let chart = make_chart(
+        ChartType::Other("Radar".to_string()),
+        vec![("Score", vec![1.0, 2.0])],
+    );
+    let source = chart_source(300.0, 200.0, chart);
+    assert!(
+        source.contains("Radar"),
+        "Expected fallback label:\n{source}"
+    );
+    assert!(!source.contains("#box(width: 300pt, height: 200pt)["));
+}
+
+/// `dx` offsets of the bar rects filled with `color`.
+fn bar_offsets(source: &str, color: &str) -> Vec<String> {
+    source
+        .lines()
+        .filter(|line| line.contains("rect(width:") && line.contains(&format!("fill: {color}")))
+        .map(|line| {
+            let start: usize = line.find("dx: ").unwrap() + "dx: ".len();
+            let end: usize = start + line[start..].find("pt").unwrap();
+            line[start..end].to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn test_fixed_chart_stacked_columns_share_a_slot() {
+    let mut chart = make_chart(
+        ChartType::Column,
+        vec![("North", vec![10.0]), ("South", vec![20.0])],
+    );
+    chart.grouping = ChartGrouping::Stacked;
+    let source = chart_source(300.0, 200.0, chart);
+
+    let north: Vec<String> = bar_offsets(&source, "rgb(68, 114, 196)");
+    let south: Vec<String> = bar_offsets(&source, "rgb(237, 125, 49)");
+    assert_eq!(north.len(), 1);
+    assert_eq!(north, south, "Stacked bars should share a slot:\n{source}");
+    // The axis covers the stack total (30), not the largest value (20).
+    assert!(
+        source.contains("[50]"),
+        "Expected stacked axis, got:\n{source}"
+    );
+}
+
+#[test]
+fn test_fixed_chart_percent_stacked_axis_is_labelled_in_percent() {
+    let mut chart = make_chart(
+        ChartType::Area,
+        vec![("North", vec![1.0, 3.0]), ("South", vec![3.0, 1.0])],
+    );
+    chart.grouping = ChartGrouping::PercentStacked;
+    let source = chart_source(300.0, 200.0, chart);
+    assert!(
+        source.contains("[100%]"),
+        "Expected percent axis:\n{source}"
+    );
+    assert_eq!(source.matches("stroke: none, closed: true").count(), 2);
+}
+
+#[test]
+fn test_fixed_chart_doughnut_draws_one_ring_per_series() {
+    let mut chart = make_chart(
+        ChartType::Doughnut,
+        vec![("2024", vec![1.0, 3.0]), ("2025", vec![2.0, 2.0])],
+    );
+    chart.categories = vec!["Online".into(), "Retail".into()];
+    let source = chart_source(300.0, 300.0, chart);
+    assert_eq!(
+        source.matches("stroke: 1pt + white, closed: true").count(),
+        4,
+        "Expected two slices per ring, got:\n{source}"
+    );
+    assert!(source.contains("Online") && source.contains("Retail"));
+}
+
+#[test]
+fn test_fixed_chart_radar_draws_grid_and_closed_series() {
+    let source = chart_source(
+        300.0,
+        300.0,
+        make_chart(ChartType::Radar, vec![("Skills", vec![3.0, 4.0, 5.0])]),
+    );
+    assert!(
+        source.contains("path(stroke: 2pt + rgb(68, 114, 196), closed: true"),
+        "Expected closed series outline, got:\n{source}"
+    );
+    assert!(source.contains("path(stroke: 0.5pt + rgb(217, 217, 217), closed: true"));
+    assert!(
+        source.contains("Q3"),
+        "Expected spoke label, got:\n{source}"
+    );
+}
+
+#[test]
+fn test_fixed_chart_bubble_radius_follows_size() {
+    let mut chart = make_chart(ChartType::Bubble, vec![("Deals", vec![10.0, 20.0])]);
+    chart.categories = vec!["1".into(), "3".into()];
+    chart.series[0].bubble_sizes = vec![1.0, 4.0];
+    let source = chart_source(300.0, 200.0, chart);
+
+    let radii: Vec<f64> = source
+        .lines()
+        .filter(|line| line.contains("transparentize(25%)"))
+        .map(|line| {
+            let start: usize = line.find("radius: ").unwrap() + "radius: ".len();
+            let end: usize = start + line[start..].find("pt").unwrap();
+            line[start..end].parse::<f64>().unwrap()
+        })
+        .collect();
+    assert_eq!(radii.len(), 2);
+    // Bubble area, not radius, is proportional to size.
+    assert!((radii[1] - 2.0 * radii[0]).abs() < 0.05, "{radii:?}");
+}
+
+#[test]
+fn test_fixed_chart_combo_line_on_secondary_axis() {
+    let mut chart = make_chart(
+        ChartType::Column,
+        vec![("Revenue", vec![100.0, 150.0]), ("Margin", vec![0.2, 0.25])],
+    );
+    chart.series[1].chart_type = Some(ChartType::Line);
+    chart.series[1].secondary_axis = true;
+    let source = chart_source(400.0, 250.0, chart);
+
+    assert_eq!(bar_offsets(&source, "rgb(68, 114, 196)").len(), 2);
+    assert!(bar_offsets(&source, "rgb(237, 125, 49)").is_empty());
+    assert!(
+        source.contains("path(stroke: ") && source.contains("+ rgb(237, 125, 49), ("),
+        "Expected margin line, got:\n{source}"
+    );
+    // Primary axis up to 200, secondary axis up to 0.5.
+    assert!(source.contains("[200]"), "{source}");
+    assert!(
+        source.contains("[0.5]"),
+        "Expected secondary ticks:\n{source}"
+    );
+}
@@ -0,0 +1,936 @@
+use super::diagrams::{CHART_SERIES_COLORS, chart_value_label, generate_chart, nice_axis};
+use super::*;
+use crate::ir::ChartSeries;
+
+/// Gridline stroke matching Office's default major gridlines.
+const GRID_STROKE: &str = "0.5pt + rgb(217, 217, 217)";
+/// Axis line stroke matching Office's default axis color.
+const AXIS_STROKE: &str = "0.75pt + rgb(191, 191, 191)";
+/// Tick label and legend text color used by Office's default chart style.
+const LABEL_FILL: &str = "rgb(89, 89, 89)";
+/// Office's default bar gap width: the gap between category groups is 150%
+/// of one bar's thickness.
+const BAR_GAP_RATIO: f64 = 1.5;
+/// Angular resolution of pie slice outlines (Typst paths have no arc segment).
+const PIE_SEGMENT_DEGREES: f64 = 3.0;
+/// Hole diameter of a doughnut chart relative to its outer diameter.
+const DOUGHNUT_HOLE_RATIO: f64 = 0.5;
+/// Diameter of the largest bubble relative to the plot's shorter side, as at
+/// Office's default 100% bubble scale.
+const BUBBLE_MAX_DIAMETER_RATIO: f64 = 0.25;
+/// Frames smaller than this (in points) cannot fit axes and labels; they use
+/// the flow chart rendering instead.
+const MIN_PLOT_FRAME: f64 = 48.0;
+/// Approximate advance of one label character relative to the font size,
+/// used to size the tick-label gutters without measuring text.
+const LABEL_CHAR_WIDTH: f64 = 0.55;
+
+/// Generate a vector plot of `chart` scaled to a `width` × `height` frame.
+///
+/// Bar, column, line, area, scatter, bubble, radar, pie, and doughnut charts
+/// (including stacked groupings and combo charts with a secondary axis) are
+/// drawn with Typst primitives inside the frame. Other chart types, charts
+/// without data, and frames too small for a plot fall back to
+/// [`generate_chart`].
+pub(super) fn generate_fixed_chart(out: &mut String, chart: &Chart, width: f64, height: f64) {
+    if !has_plot_data(chart) || width < MIN_PLOT_FRAME || height < MIN_PLOT_FRAME {
+        generate_chart(out, chart);
+        return;
+    }
+
+    let font: f64 = (width.min(height) / 22.0).clamp(6.0, 10.0);
+    let pad: f64 = font * 0.5;
+    let title: Option<&str> = chart_area_title(chart);
+    let legend: Vec<(String, &str)> = legend_entries(chart);
+    let title_h: f64 = if title.is_some() { font * 2.2 } else { 0.0 };
+    let legend_h: f64 = if legend.is_empty() { 0.0 } else { font * 2.0 };
+
+    let _ = writeln!(
+        out,
+        "#box(width: {}pt, height: {}pt)[",
+        format_f64(width),
+        format_f64(height)
+    );
+
+    if let Some(title) = title {
+        let _ = writeln!(
+            out,
+            "#place(top + left, dy: {}pt, box(width: {}pt)[#align(center)[#text(size: {}pt, weight: \"bold\", fill: {LABEL_FILL})[{}]]])",
+            format_f64(pad),
+            format_f64(width),
+            format_f64(font * 1.3),
+            escape_typst(title)
+        );
+    }
+
+    let area: PlotRect = PlotRect {
+        x: pad,
+        y: title_h + pad,
+        width: (width - 2.0 * pad).max(1.0),
+        height: (height - title_h - legend_h - 2.0 * pad).max(1.0),
+    };
+    match chart.chart_type {
+        ChartType::Pie => generate_pie_plot(out, chart, &area, 0.0),
+        ChartType::Doughnut => generate_pie_plot(out, chart, &area, DOUGHNUT_HOLE_RATIO),
+        ChartType::Radar => generate_radar_plot(out, chart, &area, font),
+        _ => generate_axis_plot(out, chart, &area, font),
+    }
+
+    if !legend.is_empty() {
+        let entries: Vec<String> = legend
+            .iter()
+            .map(|(name, color)| {
+                format!(
+                    "box[#box(width: {size}pt, height: {size}pt, fill: {color}) #text(size: {}pt, fill: {LABEL_FILL})[{}]]",
+                    format_f64(font),
+                    escape_typst(name),
+                    size = format_f64(font * 0.8),
+                )
+            })
+            .collect();
+        let _ = writeln!(
+            out,
+            "#place(bottom + center, dy: -{}pt, stack(dir: ltr, spacing: {}pt, {}))",
+            format_f64(pad),
+            format_f64(font),
+            entries.join(", ")
+        );
+    }
+
+    out.push_str("]\n");
+}
+
+/// True when [`generate_fixed_chart`] can draw `chart` as a plot rather than
+/// falling back to the flow rendering.
+fn has_plot_data(chart: &Chart) -> bool {
+    let has_positive = |series: &ChartSeries| series.values.iter().any(|value| *value > 0.0);
+    match chart.chart_type {
+        ChartType::Pie => chart.series.first().is_some_and(has_positive),
+        ChartType::Doughnut => chart.series.iter().any(has_positive),
+        ChartType::Other(_) => false,
+        _ => chart.series.iter().any(|series| !series.values.is_empty()),
+    }
+}
+
+/// Chart-area title: the explicit chart title, else the single series name
+/// (PowerPoint's automatic title for one-series charts).
+fn chart_area_title(chart: &Chart) -> Option<&str> {
+    chart
+        .title
+        .as_deref()
+        .or_else(|| match chart.series.as_slice() {
+            [only] => only.name.as_deref(),
+            _ => None,
+        })
+}
+
+/// Legend entries as (label, color). Pie and doughnut charts label their
+/// slices; other charts label their series when there is more than one.
+fn legend_entries(chart: &Chart) -> Vec<(String, &'static str)> {
+    if matches!(chart.chart_type, ChartType::Pie | ChartType::Doughnut) {
+        return chart
+            .categories
+            .iter()
+            .enumerate()
+            .map(|(index, category)| (category.clone(), series_color(index)))
+            .collect();
+    }
+    if chart.series.len() < 2 {
+        return Vec::new();
+    }
+    chart
+        .series
+        .iter()
+        .enumerate()
+        .map(|(index, series)| {
+            let name: String = series
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("Series {}", index + 1));
+            (name, series_color(index))
+        })
+        .collect()
+}
+
+/// Plot type of `series`: its own type in a combo chart, else the chart's.
+fn series_type<'a>(chart: &'a Chart, series: &'a ChartSeries) -> &'a ChartType {
+    series.chart_type.as_ref().unwrap_or(&chart.chart_type)
+}
+
+/// Chart types whose series can be stacked by `<c:grouping>`.
+fn is_stackable(chart_type: &ChartType) -> bool {
+    matches!(
+        chart_type,
+        ChartType::Bar | ChartType::Column | ChartType::Line | ChartType::Area
+    )
+}
+
+/// Value-space extent `(start, end)` of every point of every series (`None`
+/// for non-finite values). Bars and areas span start..end; lines and markers
+/// sit at end. Unstacked points start at zero.
+///
+/// Stacked series are those of the chart's own plot when its grouping is
+/// stacked. Stacked bars accumulate positive and negative values separately,
+/// as Office does; stacked lines and areas keep a running total.
+fn series_extents(chart: &Chart) -> Vec<Vec<Option<(f64, f64)>>> {
+    let grouped: bool =
+        chart.grouping != ChartGrouping::Clustered && is_stackable(&chart.chart_type);
+    let stacks = |series: &ChartSeries| grouped && series.chart_type.is_none();
+    let point_count: usize = chart
+        .series
+        .iter()
+        .map(|series| series.values.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut totals: Vec<f64> = vec![0.0; point_count];
+    if chart.grouping == ChartGrouping::PercentStacked {
+        for series in chart.series.iter().filter(|series| stacks(series)) {
+            for (total, value) in totals.iter_mut().zip(&series.values) {
+                if value.is_finite() {
+                    *total += value.abs();
+                }
+            }
+        }
+    }
+
+    let split_signs: bool = matches!(chart.chart_type, ChartType::Bar | ChartType::Column);
+    let mut positive: Vec<f64> = vec![0.0; point_count];
+    let mut negative: Vec<f64> = vec![0.0; point_count];
+    let mut extents: Vec<Vec<Option<(f64, f64)>>> = Vec::with_capacity(chart.series.len());
+    for series in &chart.series {
+        let stacked: bool = stacks(series);
+        let mut points: Vec<Option<(f64, f64)>> = Vec::with_capacity(series.values.len());
+        for (index, value) in series.values.iter().enumerate() {
+            if !value.is_finite() {
+                points.push(None);
+                continue;
+            }
+            if !stacked {
+                points.push(Some((0.0, *value)));
+                continue;
+            }
+            let value: f64 = if totals[index] > 0.0 {
+                value / totals[index] * 100.0
+            } else {
+                *value
+            };
+            let running: &mut f64 = if split_signs && value < 0.0 {
+                &mut negative[index]
+            } else {
+                &mut positive[index]
+            };
+            let start: f64 = *running;
+            *running += value;
+            points.push(Some((start, *running)));
+        }
+        extents.push(points);
+    }
+    extents
+}
+
+/// Value axis covering zero and every extent of the series `include` selects.
+fn axis_for_series(
+    chart: &Chart,
+    extents: &[Vec<Option<(f64, f64)>>],
+    include: impl Fn(&ChartSeries) -> bool,
+) -> ValueAxis {
+    let (min_value, max_value) = chart
+        .series
+        .iter()
+        .zip(extents)
+        .filter(|(series, _)| include(series))
+        .flat_map(|(_, points)| points.iter().flatten())
+        .fold((0.0_f64, 0.0_f64), |(low, high), (start, end)| {
+            (low.min(*start).min(*end), high.max(*start).max(*end))
+        });
+    ValueAxis::covering(min_value, max_value)
+}
+
+fn tick_label(value: f64, percent: bool) -> String {
+    if percent {
+        format!("{}%", chart_value_label(value))
+    } else {
+        chart_value_label(value)
+    }
+}
+
+fn longest_label_width(labels: &[String], font: f64) -> f64 {
+    let longest: usize = labels
+        .iter()
+        .map(|label| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    longest as f64 * font * LABEL_CHAR_WIDTH + font * 0.6
+}
+
+fn series_color(index: usize) -> &'static str {
+    CHART_SERIES_COLORS[index % CHART_SERIES_COLORS.len()]
+}
+
+/// A rectangle in the chart frame's coordinate space (points, y down).
+struct PlotRect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// A linear value axis spanning `[low, high]` with ticks every `step`.
+#[derive(Debug, PartialEq)]
+struct ValueAxis {
+    low: f64,
+    high: f64,
+    step: f64,
+}
+
+impl ValueAxis {
+    /// Choose "nice" bounds covering `[min_value, max_value]` and zero, so
+    /// bars always grow from a visible baseline.
+    fn covering(min_value: f64, max_value: f64) -> Self {
+        let min_value: f64 = min_value.min(0.0);
+        let max_value: f64 = max_value.max(0.0);
+        if min_value >= 0.0 {
+            let (high, step) = nice_axis(max_value);
+            return Self {
+                low: 0.0,
+                high,
+                step,
+            };
+        }
+        if max_value <= 0.0 {
+            let (depth, step) = nice_axis(-min_value);
+            return Self {
+                low: -depth,
+                high: 0.0,
+                step,
+            };
+        }
+        let (_, step) = nice_axis(max_value - min_value);
+        Self {
+            low: (min_value / step).floor() * step,
+            high: (max_value / step).ceil() * step,
+            step,
+        }
+    }
+
+    fn ticks(&self) -> Vec<f64> {
+        let count: usize = ((self.high - self.low) / self.step).round() as usize;
+        (0..=count)
+            .map(|index| self.low + index as f64 * self.step)
+            .collect()
+    }
+
+    /// Position of `value` along the axis as a fraction of its length.
+    fn fraction(&self, value: f64) -> f64 {
+        ((value - self.low) / (self.high - self.low)).clamp(0.0, 1.0)
+    }
+}
+
+/// Draw a chart with value axes: bar, column, line, area, scatter, bubble,
+/// and combinations of them.
+fn generate_axis_plot(out: &mut String, chart: &Chart, area: &PlotRect, font: f64) {
+    let horizontal: bool = matches!(chart.chart_type, ChartType::Bar);
+    let percent: bool =
+        chart.grouping == ChartGrouping::PercentStacked && is_stackable(&chart.chart_type);
+    let extents: Vec<Vec<Option<(f64, f64)>>> = series_extents(chart);
+
+    // A secondary axis only exists when both axes have series.
+    let has_secondary: bool = chart.series.iter().any(|series| series.secondary_axis)
+        && chart.series.iter().any(|series| !series.secondary_axis);
+    let on_secondary = |series: &ChartSeries| has_secondary && series.secondary_axis;
+    let primary_axis: ValueAxis = axis_for_series(chart, &extents, |series| !on_secondary(series));
+    let secondary_axis: Option<ValueAxis> =
+        has_secondary.then(|| axis_for_series(chart, &extents, on_secondary));
+    let primary_ticks: Vec<f64> = primary_axis.ticks();
+    let primary_labels: Vec<String> = primary_ticks
+        .iter()
+        .map(|tick| tick_label(*tick, percent))
+        .collect();
+    let secondary_ticks: Vec<f64> = secondary_axis
+        .as_ref()
+        .map(ValueAxis::ticks)
+        .unwrap_or_default();
+    let secondary_labels: Vec<String> = secondary_ticks
+        .iter()
+        .map(|tick| tick_label(*tick, false))
+        .collect();
+
+    // Scatter and bubble charts with numeric x values get a horizontal value
+    // axis; other charts (and text x values) use category bands.
+    let x_values: Option<Vec<f64>> =
+        if matches!(chart.chart_type, ChartType::Scatter | ChartType::Bubble) {
+            chart
+                .categories
+                .iter()
+                .map(|category| category.trim().parse::<f64>().ok())
+                .collect()
+        } else {
+            None
+        };
+    let x_axis: Option<ValueAxis> = x_values.as_ref().filter(|xs| !xs.is_empty()).map(|xs| {
+        let min_x: f64 = xs.iter().copied().fold(f64::INFINITY, f64::min);
+        let max_x: f64 = xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        ValueAxis::covering(min_x, max_x)
+    });
+    let category_count: usize = chart
+        .series
+        .iter()
+        .map(|series| series.values.len())
+        .max()
+        .unwrap_or(0)
+        .max(chart.categories.len())
+        .max(1);
+
+    // Gutters for tick labels: primary value labels sit left of a vertical
+    // value axis (bar charts put category labels there instead); secondary
+    // labels sit right of it, or above a horizontal one.
+    let label_h: f64 = font * 1.6;
+    let left_gutter: f64 = if horizontal {
+        longest_label_width(&chart.categories, font)
+    } else {
+        longest_label_width(&primary_labels, font)
+    }
+    .min(area.width * 0.35);
+    let right_gutter: f64 = if has_secondary && !horizontal {
+        longest_label_width(&secondary_labels, font).min(area.width * 0.25)
+    } else {
+        font * 0.6
+    };
+    let top_gutter: f64 = if has_secondary && horizontal {
+        label_h
+    } else {
+        0.0
+    };
+    let plot: PlotRect = PlotRect {
+        x: area.x + left_gutter,
+        y: area.y + font * 0.6 + top_gutter,
+        width: (area.width - left_gutter - right_gutter).max(1.0),
+        height: (area.height - label_h - font * 0.6 - top_gutter).max(1.0),
+    };
+    let value_pos = |axis: &ValueAxis, value: f64| -> f64 {
+        if horizontal {
+            plot.x + axis.fraction(value) * plot.width
+        } else {
+            plot.y + (1.0 - axis.fraction(value)) * plot.height
+        }
+    };
+
+    // Gridlines and primary value tick labels.
+    for (tick, label) in primary_ticks.iter().zip(&primary_labels) {
+        let pos: f64 = value_pos(&primary_axis, *tick);
+        if horizontal {
+            write_line(out, pos, plot.y, 0.0, plot.height, GRID_STROKE);
+            write_label(
+                out,
+                pos - plot.width / 2.0,
+                plot.y + plot.height + font * 0.3,
+                plot.width,
+                "center",
+                font,
+                label,
+            );
+        } else {
+            write_line(out, plot.x, pos, plot.width, 0.0, GRID_STROKE);
+            write_label(
+                out,
+                area.x,
+                pos - font * 0.6,
+                left_gutter - font * 0.4,
+                "right",
+                font,
+                label,
+            );
+        }
+    }
+    if let Some(axis) = &secondary_axis {
+        for (tick, label) in secondary_ticks.iter().zip(&secondary_labels) {
+            let pos: f64 = value_pos(axis, *tick);
+            if horizontal {
+                write_label(
+                    out,
+                    pos - plot.width / 2.0,
+                    plot.y - label_h,
+                    plot.width,
+                    "center",
+                    font,
+                    label,
+                );
+            } else {
+                write_label(
+                    out,
+                    plot.x + plot.width + font * 0.4,
+                    pos - font * 0.6,
+                    right_gutter - font * 0.4,
+                    "left",
+                    font,
+                    label,
+                );
+            }
+        }
+    }
+
+    // Category positions: band centers for bars, columns, and lines; band
+    // edges for areas, which span the full plot width like Office's.
+    let band: f64 = (if horizontal { plot.height } else { plot.width }) / category_count as f64;
+    let edge_to_edge: bool = matches!(chart.chart_type, ChartType::Area) && category_count > 1;
+    let category_pos = |index: usize| -> f64 {
+        if edge_to_edge {
+            plot.x + index as f64 / (category_count - 1) as f64 * plot.width
+        } else if horizontal {
+            // Office draws the first category at the bottom of a bar chart.
+            plot.y + plot.height - band * (index as f64 + 0.5)
+        } else {
+            plot.x + band * (index as f64 + 0.5)
+        }
+    };
+    let x_pos = |index: usize| -> f64 {
+        match (&x_axis, &x_values) {
+            (Some(axis), Some(xs)) => {
+                let x: f64 = xs.get(index).copied().unwrap_or(index as f64 + 1.0);
+                plot.x + axis.fraction(x) * plot.width
+            }
+            _ => category_pos(index),
+        }
+    };
+
+    // Category (or x value) tick labels.
+    if let Some(axis) = &x_axis {
+        let ticks: Vec<f64> = axis.ticks();
+        let slot: f64 = plot.width / ticks.len().max(1) as f64;
+        for tick in ticks {
+            let x: f64 = plot.x + axis.fraction(tick) * plot.width;
+            write_line(out, x, plot.y, 0.0, plot.height, GRID_STROKE);
+            write_label(
+                out,
+                x - slot / 2.0,
+                plot.y + plot.height + font * 0.3,
+                slot,
+                "center",
+                font,
+                &chart_value_label(tick),
+            );
+        }
+    } else {
+        for (index, category) in chart.categories.iter().enumerate() {
+            let pos: f64 = category_pos(index);
+            if horizontal {
+                write_label(
+                    out,
+                    area.x,
+                    pos - font * 0.6,
+                    left_gutter - font * 0.4,
+                    "right",
+                    font,
+                    category,
+                );
+            } else {
+                write_label(
+                    out,
+                    pos - band / 2.0,
+                    plot.y + plot.height + font * 0.3,
+                    band,
+                    "center",
+                    font,
+                    category,
+                );
+            }
+        }
+    }
+
+    // Bar slots within a category band: stacked series of the chart's own
+    // plot share one slot, every other bar series gets its own.
+    let mut bar_slots: Vec<Option<usize>> = vec![None; chart.series.len()];
+    let mut slot_count: usize = 0;
+    let mut stack_slot: Option<usize> = None;
+    let stacked_bars: bool = chart.grouping != ChartGrouping::Clustered;
+    for (s_index, series) in chart.series.iter().enumerate() {
+        if !matches!(
+            series_type(chart, series),
+            ChartType::Bar | ChartType::Column
+        ) {
+            continue;
+        }
+        let slot: usize = match stack_slot {
+            Some(slot) if stacked_bars && series.chart_type.is_none() => slot,
+            _ => {
+                slot_count += 1;
+                if stacked_bars && series.chart_type.is_none() {
+                    stack_slot = Some(slot_count - 1);
+                }
+                slot_count - 1
+            }
+        };
+        bar_slots[s_index] = Some(slot);
+    }
+    let thickness: f64 = band / (slot_count as f64 + BAR_GAP_RATIO);
+
+    // Series geometry, in series order so combo lines draw over bars.
+    let stroke_width: f64 = (font / 5.0).clamp(1.0, 2.25);
+    let max_bubble: f64 = chart
+        .series
+        .iter()
+        .flat_map(|series| series.bubble_sizes.iter().copied())
+        .filter(|size| size.is_finite())
+        .fold(0.0, f64::max);
+    let max_bubble_radius: f64 = plot.width.min(plot.height) * BUBBLE_MAX_DIAMETER_RATIO / 2.0;
+    for (s_index, (series, points)) in chart.series.iter().zip(&extents).enumerate() {
+        let color: &str = series_color(s_index);
+        let axis: &ValueAxis = match &secondary_axis {
+            Some(axis) if on_secondary(series) => axis,
+            _ => &primary_axis,
+        };
+        let coords: Vec<(usize, f64, f64)> = points
+            .iter()
+            .enumerate()
+            .filter_map(|(index, extent)| {
+                extent.map(|(start, end)| (index, value_pos(axis, start), value_pos(axis, end)))
+            })
+            .collect();
+        match series_type(chart, series) {
+            ChartType::Bar | ChartType::Column => {
+                let slot: f64 = bar_slots[s_index].unwrap_or(0) as f64;
+                let offset: f64 = thickness * (slot - slot_count as f64 / 2.0);
+                for (index, start, end) in coords {
+                    let (low, length) = (start.min(end), (end - start).abs());
+                    let center: f64 = category_pos(index);
+                    let (x, y, w, h) = if horizontal {
+                        // Series stack upward within a bar group.
+                        (low, center - offset - thickness, length, thickness)
+                    } else {
+                        (center + offset, low, thickness, length)
+                    };
+                    let _ = writeln!(
+                        out,
+                        "#place(top + left, dx: {}pt, dy: {}pt, rect(width: {}pt, height: {}pt, fill: {color}, stroke: none))",
+                        format_f64(x),
+                        format_f64(y),
+                        format_f64(w),
+                        format_f64(h)
+                    );
+                }
+            }
+            ChartType::Area => {
+                if coords.is_empty() {
+                    continue;
+                }
+                // Upper edge left to right, then the lower edge back.
+                let mut outline: Vec<(f64, f64)> = coords
+                    .iter()
+                    .map(|(index, _, end)| (x_pos(*index), *end))
+                    .collect();
+                outline.extend(
+                    coords
+                        .iter()
+                        .rev()
+                        .map(|(index, start, _)| (x_pos(*index), *start)),
+                );
+                let _ = writeln!(
+                    out,
+                    "#place(top + left, path(fill: {color}, stroke: none, closed: true, {}))",
+                    path_coords(&outline)
+                );
+            }
+            ChartType::Bubble => {
+                for (index, _, end) in coords {
+                    let size: f64 = series.bubble_sizes.get(index).copied().unwrap_or(1.0);
+                    let radius: f64 = if max_bubble > 0.0 && size > 0.0 {
+                        (size / max_bubble).sqrt() * max_bubble_radius
+                    } else {
+                        stroke_width * 1.5
+                    };
+                    let _ = writeln!(
+                        out,
+                        "#place(top + left, dx: {}pt, dy: {}pt, circle(radius: {}pt, fill: {color}.transparentize(25%), stroke: 0.75pt + {color}))",
+                        format_f64(x_pos(index) - radius),
+                        format_f64(end - radius),
+                        format_f64(radius)
+                    );
+                }
+            }
+            plot_type => {
+                let points: Vec<(f64, f64)> = coords
+                    .iter()
+                    .map(|(index, _, end)| (x_pos(*index), *end))
+                    .collect();
+                if matches!(plot_type, ChartType::Line) && points.len() >= 2 {
+                    let _ = writeln!(
+                        out,
+                        "#place(top + left, path(stroke: {}pt + {color}, {}))",
+                        format_f64(stroke_width),
+                        path_coords(&points)
+                    );
+                }
+                write_markers(out, &points, stroke_width * 1.5, color);
+            }
+        }
+    }
+
+    // Category axis line along the zero baseline.
+    let baseline: f64 = value_pos(&primary_axis, 0.0);
+    if horizontal {
+        write_line(out, baseline, plot.y, 0.0, plot.height, AXIS_STROKE);
+    } else {
+        write_line(out, plot.x, baseline, plot.width, 0.0, AXIS_STROKE);
+    }
+}
+
+/// Draw a radar chart: one spoke per category, concentric gridline polygons
+/// at the value ticks, and a closed outline per series.
+fn generate_radar_plot(out: &mut String, chart: &Chart, area: &PlotRect, font: f64) {
+    let spoke_count: usize = chart
+        .series
+        .iter()
+        .map(|series| series.values.len())
+        .max()
+        .unwrap_or(0)
+        .max(chart.categories.len())
+        .max(1);
+    let values: Vec<f64> = chart
+        .series
+        .iter()
+        .flat_map(|series| series.values.iter().copied())
+        .filter(|value| value.is_finite())
+        .collect();
+    let axis: ValueAxis = ValueAxis::covering(
+        values.iter().copied().fold(0.0, f64::min),
+        values.iter().copied().fold(0.0, f64::max),
+    );
+    let radius: f64 = (area.width.min(area.height) / 2.0 - font * 2.0).max(1.0);
+    let center: (f64, f64) = (area.x + area.width / 2.0, area.y + area.height / 2.0);
+    let spoke_point = |index: usize, distance: f64| -> (f64, f64) {
+        let radians: f64 = (index as f64 / spoke_count as f64 * 360.0).to_radians();
+        (
+            center.0 + distance * radians.sin(),
+            center.1 - distance * radians.cos(),
+        )
+    };
+
+    for tick in axis.ticks().into_iter().skip(1) {
+        let distance: f64 = axis.fraction(tick) * radius;
+        let ring: Vec<(f64, f64)> = (0..spoke_count)
+            .map(|index| spoke_point(index, distance))
+            .collect();
+        let _ = writeln!(
+            out,
+            "#place(top + left, path(stroke: {GRID_STROKE}, closed: true, {}))",
+            path_coords(&ring)
+        );
+        write_label(
+            out,
+            center.0 - font * 4.3,
+            center.1 - distance - font * 0.6,
+            font * 4.0,
+            "right",
+            font,
+            &chart_value_label(tick),
+        );
+    }
+    for index in 0..spoke_count {
+        let (x, y) = spoke_point(index, radius);
+        write_line(
+            out,
+            center.0,
+            center.1,
+            x - center.0,
+            y - center.1,
+            AXIS_STROKE,
+        );
+    }
+    let label_w: f64 = font * 8.0;
+    for (index, category) in chart.categories.iter().enumerate() {
+        let (x, y) = spoke_point(index, radius + font * 1.2);
+        write_label(
+            out,
+            x - label_w / 2.0,
+            y - font * 0.6,
+            label_w,
+            "center",
+            font,
+            category,
+        );
+    }
+
+    let stroke_width: f64 = (font / 5.0).clamp(1.0, 2.25);
+    for (s_index, series) in chart.series.iter().enumerate() {
+        let color: &str = series_color(s_index);
+        let points: Vec<(f64, f64)> = series
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| value.is_finite())
+            .map(|(index, value)| spoke_point(index, axis.fraction(*value) * radius))
+            .collect();
+        if points.len() >= 2 {
+            let _ = writeln!(
+                out,
+                "#place(top + left, path(stroke: {}pt + {color}, closed: true, {}))",
+                format_f64(stroke_width),
+                path_coords(&points)
+            );
+        }
+        write_markers(out, &points, stroke_width * 1.5, color);
+    }
+}
+
+fn write_markers(out: &mut String, points: &[(f64, f64)], radius: f64, color: &str) {
+    for (x, y) in points {
+        let _ = writeln!(
+            out,
+            "#place(top + left, dx: {}pt, dy: {}pt, circle(radius: {}pt, fill: {color}, stroke: none))",
+            format_f64(x - radius),
+            format_f64(y - radius),
+            format_f64(radius)
+        );
+    }
+}
+
+/// Draw a pie (`hole_ratio` 0) from the first series, or a doughnut with one
+/// ring per series, innermost first. Slices run clockwise from 12 o'clock.
+fn generate_pie_plot(out: &mut String, chart: &Chart, area: &PlotRect, hole_ratio: f64) {
+    let rings: &[ChartSeries] = if hole_ratio > 0.0 {
+        &chart.series
+    } else {
+        &chart.series[..chart.series.len().min(1)]
+    };
+    if rings.is_empty() {
+        return;
+    }
+    let outer_radius: f64 = area.width.min(area.height) / 2.0 * 0.9;
+    let hole_radius: f64 = outer_radius * hole_ratio;
+    let ring_width: f64 = (outer_radius - hole_radius) / rings.len() as f64;
+    let center: (f64, f64) = (area.x + area.width / 2.0, area.y + area.height / 2.0);
+    for (ring_index, series) in rings.iter().enumerate() {
+        let inner: f64 = hole_radius + ring_width * ring_index as f64;
+        write_ring_slices(out, series, center, inner, inner + ring_width);
+    }
+}
+
+/// Slices of one pie or doughnut ring between `inner` and `outer` radii.
+fn write_ring_slices(
+    out: &mut String,
+    series: &ChartSeries,
+    center: (f64, f64),
+    inner: f64,
+    outer: f64,
+) {
+    let total: f64 = series.values.iter().filter(|value| **value > 0.0).sum();
+    if total <= 0.0 {
+        return;
+    }
+    let point_at = |degrees: f64, radius: f64| -> (f64, f64) {
+        let radians: f64 = degrees.to_radians();
+        (
+            center.0 + radius * radians.sin(),
+            center.1 - radius * radians.cos(),
+        )
+    };
+
+    let mut start: f64 = 0.0;
+    for (index, value) in series.values.iter().enumerate() {
+        if *value <= 0.0 {
+            continue;
+        }
+        let sweep: f64 = value / total * 360.0;
+        let color: &str = series_color(index);
+        if inner <= 0.0 && sweep >= 360.0 - 1e-9 {
+            let _ = writeln!(
+                out,
+                "#place(top + left, dx: {}pt, dy: {}pt, circle(radius: {}pt, fill: {color}, stroke: 1pt + white))",
+                format_f64(center.0 - outer),
+                format_f64(center.1 - outer),
+                format_f64(outer)
+            );
+            return;
+        }
+        let segments: usize = (sweep / PIE_SEGMENT_DEGREES).ceil().max(1.0) as usize;
+        let arc_angle = |step: usize| -> f64 { start + sweep * step as f64 / segments as f64 };
+        let mut outline: Vec<(f64, f64)> = (0..=segments)
+            .map(|step| point_at(arc_angle(step), outer))
+            .collect();
+        if inner > 0.0 {
+            outline.extend(
+                (0..=segments)
+                    .rev()
+                    .map(|step| point_at(arc_angle(step), inner)),
+            );
+        } else {
+            outline.insert(0, center);
+        }
+        let _ = writeln!(
+            out,
+            "#place(top + left, path(fill: {color}, stroke: 1pt + white, closed: true, {}))",
+            path_coords(&outline)
+        );
+        start += sweep;
+    }
+}
+
+pub(super) fn path_coords(points: &[(f64, f64)]) -> String {
+    points
+        .iter()
+        .map(|(x, y)| format!("({}pt, {}pt)", format_f64(*x), format_f64(*y)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub(super) fn write_line(out: &mut String, x: f64, y: f64, dx: f64, dy: f64, stroke: &str) {
+    let _ = writeln!(
+        out,
+        "#place(top + left, dx: {}pt, dy: {}pt, line(end: ({}pt, {}pt), stroke: {stroke}))",
+        format_f64(x),
+        format_f64(y),
+        format_f64(dx),
+        format_f64(dy)
+    );
+}
+
+/// Place a one-line tick label in a `width`-wide slot starting at (x, y).
+fn write_label(out: &mut String, x: f64, y: f64, width: f64, align: &str, font: f64, text: &str) {
+    let _ = writeln!(
+        out,
+        "#place(top + left, dx: {}pt, dy: {}pt, box(width: {}pt, height: {}pt)[#align({align} + horizon)[#text(size: {}pt, fill: {LABEL_FILL})[{}]]])",
+        format_f64(x),
+        format_f64(y),
+        format_f64(width.max(0.0)),
+        format_f64(font * 1.2),
+        format_f64(font),
+        escape_typst(text)
+    );
+}
+
+#[cfg(test)]
+mod value_axis_tests {
+    use super::ValueAxis;
+
+    #[test]
+    fn positive_values_start_at_zero() {
+        let axis = ValueAxis::covering(3.0, 8.2);
+        assert_eq!(
+            axis,
+            ValueAxis {
+                low: 0.0,
+                high: 10.0,
+                step: 2.0
+            }
+        );
+        assert_eq!(axis.ticks(), vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+    }
+
+    #[test]
+    fn mixed_sign_values_straddle_zero() {
+        let axis = ValueAxis::covering(-15.0, 40.0);
+        assert!(axis.low <= -15.0 && axis.low < 0.0);
+        assert!(axis.high >= 40.0);
+        assert!(axis.ticks().contains(&0.0));
+        assert_eq!(axis.fraction(axis.low), 0.0);
+        assert_eq!(axis.fraction(axis.high), 1.0);
+    }
+
+    #[test]
+    fn negative_values_end_at_zero() {
+        let axis = ValueAxis::covering(-45.0, -5.0);
+        assert_eq!(axis.low, -50.0);
+        assert_eq!(axis.high, 0.0);
+    }
+}
@@ -9,7 +9,11 @@ fn test_codegen_chart_bar_visual_bars() {
         series: vec![ChartSeries {
             name: Some("Revenue".to_string()),
             values: vec![100.0, 250.0],
+            bubble_sizes: Vec::new(),
+            chart_type: None,
+            secondary_axis: false,
         }],
+        grouping: ChartGrouping::Clustered,
     })])]);
 
     let output = generate_typst(&doc).unwrap();
@@ -51,7 +55,11 @@ fn test_codegen_chart_axis_ticks_and_no_raw_floats() {
         series: vec![ChartSeries {
             name: Some("Sales".to_string()),
             values: vec![8.200000000000001, 3.2],
+            bubble_sizes: Vec::new(),
+            chart_type: None,
+            secondary_axis: false,
         }],
+        grouping: ChartGrouping::Clustered,
     })])]);
 
     let output = generate_typst(&doc).unwrap();
@@ -81,7 +89,11 @@ fn test_codegen_chart_pie_percentages() {
         series: vec![ChartSeries {
             name: None,
             values: vec![60.0, 40.0],
+            bubble_sizes: Vec::new(),
+            chart_type: None,
+            secondary_axis: false,
         }],
+        grouping: ChartGrouping::Clustered,
     })])]);
 
     let output = generate_typst(&doc).unwrap();
@@ -111,7 +123,11 @@ fn test_codegen_chart_line_trend_indicators() {
         series: vec![ChartSeries {
             name: Some("Sales".to_string()),
             values: vec![10.0, 20.0, 15.0],
+            bubble_sizes: Vec::new(),
+            chart_type: None,
+            secondary_axis: false,
         }],
+        grouping: ChartGrouping::Clustered,
     })])]);
 
     let output = generate_typst(&doc).unwrap();
@@ -141,6 +157,7 @@ fn test_codegen_chart_empty_series() {
         title: Some("Empty".to_string()),
         categories: vec![],
         series: vec![],
+        grouping: ChartGrouping::Clustered,
     })])]);
 
     let output = generate_typst(&doc).unwrap();
@@ -155,6 +172,7 @@ fn sa_node(text: &str, depth: usize) -> SmartArtNode {
     SmartArtNode {
         text: text.to_string(),
         depth,
+        parent: None,
     }
 }
 
@@ -174,7 +192,9 @@ fn test_smartart_codegen_flat_numbered_steps() {
                     sa_node("Step 2", 0),
                     sa_node("Step 3", 0),
                 ],
+                layout: SmartArtLayout::List,
             }),
+            href: None,
         }],
     )]);
 
@@ -223,7 +243,9 @@ fn test_smartart_codegen_hierarchy_indented_tree() {
                     sa_node("VP Sales", 1),
                     sa_node("Dev Lead", 2),
                 ],
+                layout: SmartArtLayout::List,
             }),
+            href: None,
         }],
     )]);
 
@@ -260,7 +282,11 @@ fn test_smartart_codegen_empty_items() {
             y: 0.0,
             width: 200.0,
             height: 100.0,
-            kind: FixedElementKind::SmartArt(SmartArt { items: vec![] }),
+            kind: FixedElementKind::SmartArt(SmartArt {
+                items: vec![],
+                layout: SmartArtLayout::List,
+            }),
+            href: None,
         }],
     )]);
 
@@ -283,7 +309,9 @@ fn test_smartart_codegen_special_chars() {
             height: 100.0,
             kind: FixedElementKind::SmartArt(SmartArt {
                 items: vec![sa_node("Item #1", 0), sa_node("Price $10", 0)],
+                layout: SmartArtLayout::List,
             }),
+            href: None,
         }],
     )]);
 
@@ -310,12 +338,19 @@ fn test_codegen_chart_line_plot() {
             ChartSeries {
                 name: Some("A".to_string()),
                 values: vec![1.0, 2.0, 3.0],
+                bubble_sizes: Vec::new(),
+                chart_type: None,
+                secondary_axis: false,
             },
             ChartSeries {
                 name: Some("B".to_string()),
                 values: vec![10.0, 9.0, 14.0],
+                bubble_sizes: Vec::new(),
+                chart_type: None,
+                secondary_axis: false,
             },
         ],
+        grouping: ChartGrouping::Clustered,
     })])]);
 
     let output = generate_typst(&doc).unwrap();
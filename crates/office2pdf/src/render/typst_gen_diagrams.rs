@@ -1,3 +1,4 @@
+use super::smartart::generate_smartart_layout;
 use super::*;
 
 /// Generate Typst markup for a chart with improved visual representation.
@@ -5,7 +6,7 @@ use super::*;
 /// Renders charts in a bordered box with title header and type-specific
 /// visual representation:
 /// - Bar/Column: proportional visual bars
-/// - Pie: percentage legend table
+/// - Pie/Doughnut: percentage legend table
 /// - Line: data table with trend indicators (↑↓→)
 /// - Others: standard data table
 pub(super) fn generate_chart(out: &mut String, chart: &Chart) {
@@ -37,8 +38,11 @@ pub(super) fn generate_chart(out: &mut String, chart: &Chart) {
         ChartType::Column => "Column Chart",
         ChartType::Line => "Line Chart",
         ChartType::Pie => "Pie Chart",
+        ChartType::Doughnut => "Doughnut Chart",
         ChartType::Area => "Area Chart",
         ChartType::Scatter => "Scatter Chart",
+        ChartType::Bubble => "Bubble Chart",
+        ChartType::Radar => "Radar Chart",
         ChartType::Other(label) => label.as_str(),
     };
 
@@ -61,7 +65,7 @@ pub(super) fn generate_chart(out: &mut String, chart: &Chart) {
 
     match &chart.chart_type {
         ChartType::Bar | ChartType::Column => generate_chart_bar(out, chart),
-        ChartType::Pie => generate_chart_pie(out, chart),
+        ChartType::Pie | ChartType::Doughnut => generate_chart_pie(out, chart),
         ChartType::Line => generate_chart_line(out, chart),
         _ => generate_chart_table(out, chart),
     }
@@ -70,7 +74,7 @@ pub(super) fn generate_chart(out: &mut String, chart: &Chart) {
 }
 
 /// Series palette matching Office's default accent colors.
-const CHART_SERIES_COLORS: [&str; 6] = [
+pub(super) const CHART_SERIES_COLORS: [&str; 6] = [
     "rgb(68, 114, 196)",
     "rgb(237, 125, 49)",
     "rgb(165, 165, 165)",
@@ -113,7 +117,7 @@ pub(super) fn chart_value_label(value: f64) -> String {
 
 /// Choose a "nice" axis maximum and tick step covering `[0, max]`
 /// (e.g. max 8.2 → (10, 2), giving ticks 0,2,4,6,8,10).
-fn nice_axis(max_value: f64) -> (f64, f64) {
+pub(super) fn nice_axis(max_value: f64) -> (f64, f64) {
     if max_value <= 0.0 {
         return (1.0, 1.0);
     }
@@ -640,10 +644,17 @@ fn generate_chart_table(out: &mut String, chart: &Chart) {
 
 /// Generate Typst markup for a SmartArt diagram.
 ///
-/// Renders SmartArt as a visually distinct bordered box with:
+/// Cycle, hierarchy, pyramid, and matrix layouts are drawn as approximate
+/// geometry within the frame by [`generate_smartart_layout`]. Lists and
+/// unrecognized layouts render as a visually distinct bordered box with:
 /// - Hierarchy items (varying depths): indented tree with depth-based padding
 /// - Flat items (all same depth): numbered steps with arrows
 pub(super) fn generate_smartart(out: &mut String, smartart: &SmartArt, width: f64, height: f64) {
+    if smartart.layout != SmartArtLayout::List && !smartart.items.is_empty() {
+        generate_smartart_layout(out, smartart, width, height);
+        return;
+    }
+
     let _ = writeln!(
         out,
         "#block(width: {}pt, height: {}pt, stroke: 1pt + rgb(70, 130, 180), radius: 4pt, inset: 10pt, fill: rgb(245, 248, 255))[",
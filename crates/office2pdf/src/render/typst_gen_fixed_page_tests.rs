@@ -121,6 +121,144 @@ fn test_fixed_page_shape_with_stroke() {
     assert!(output.source.contains("1.5pt"));
 }
 
+#[test]
+fn test_fixed_page_shape_with_double_stroke_draws_two_outlines() {
+    let doc = make_doc(vec![make_fixed_page(
+        960.0,
+        540.0,
+        vec![make_shape_element(
+            10.0,
+            10.0,
+            100.0,
+            60.0,
+            ShapeKind::Rectangle,
+            Some(Color::new(255, 255, 255)),
+            Some(BorderSide {
+                width: 1.5,
+                color: Color::new(0, 0, 255),
+                style: BorderLineStyle::Double,
+            }),
+        )],
+    )]);
+    let output = generate_typst(&doc).unwrap();
+    assert!(
+        output
+            .source
+            .contains("#rect(width: 100pt, height: 60pt, fill: rgb(255, 255, 255))"),
+        "The filled shape itself carries no stroke, got:\n{}",
+        output.source,
+    );
+    assert!(
+        output.source.contains(
+            "#place(top + left, dx: -1.5pt, dy: -1.5pt, rect(width: 103pt, height: 63pt, stroke: 1.5pt + rgb(0, 0, 255)))"
+        ),
+        "Expected outer outline, got:\n{}",
+        output.source,
+    );
+    assert!(
+        output.source.contains(
+            "#place(top + left, dx: 1.5pt, dy: 1.5pt, rect(width: 97pt, height: 57pt, stroke: 1.5pt + rgb(0, 0, 255)))"
+        ),
+        "Expected inner outline, got:\n{}",
+        output.source,
+    );
+}
+
+#[test]
+fn test_fixed_page_line_with_double_stroke_draws_two_lines() {
+    let doc = make_doc(vec![make_fixed_page(
+        960.0,
+        540.0,
+        vec![make_shape_element(
+            0.0,
+            0.0,
+            300.0,
+            0.0,
+            ShapeKind::Line {
+                x1: 0.0,
+                y1: 0.0,
+                x2: 300.0,
+                y2: 0.0,
+                head_end: ArrowHead::None,
+                tail_end: ArrowHead::None,
+            },
+            None,
+            Some(BorderSide {
+                width: 1.0,
+                color: Color::black(),
+                style: BorderLineStyle::Double,
+            }),
+        )],
+    )]);
+    let output = generate_typst(&doc).unwrap();
+    assert!(
+        output.source.contains(
+            "#place(top + left, line(start: (0pt, 1pt), end: (300pt, 1pt), stroke: 1pt + rgb(0, 0, 0)))"
+        ),
+        "Expected line below the path, got:\n{}",
+        output.source,
+    );
+    assert!(
+        output.source.contains(
+            "#place(top + left, line(start: (0pt, -1pt), end: (300pt, -1pt), stroke: 1pt + rgb(0, 0, 0)))"
+        ),
+        "Expected line above the path, got:\n{}",
+        output.source,
+    );
+}
+
+#[test]
+fn test_tagged_fixed_page_marks_bare_shapes_as_artifacts() {
+    let mut linked = make_shape_element(
+        0.0,
+        200.0,
+        50.0,
+        50.0,
+        ShapeKind::Rectangle,
+        Some(Color::new(0, 128, 0)),
+        None,
+    );
+    linked.href = Some("https://example.com".to_string());
+    let doc = make_doc(vec![make_fixed_page(
+        960.0,
+        540.0,
+        vec![
+            make_shape_element(
+                0.0,
+                0.0,
+                960.0,
+                20.0,
+                ShapeKind::Rectangle,
+                Some(Color::new(200, 0, 0)),
+                None,
+            ),
+            make_text_box(10.0, 40.0, 300.0, 50.0, "Quarterly results"),
+            linked,
+        ],
+    )]);
+    let options = ConvertOptions {
+        pdf_ua: true,
+        ..Default::default()
+    };
+    let output = generate_typst_with_options(&doc, &options).unwrap();
+    // Only the unlinked decorative bar is kept out of the reading order.
+    assert_eq!(
+        output
+            .source
+            .matches("#pdf.artifact(kind: \"other\")[")
+            .count(),
+        1,
+        "got: {}",
+        output.source
+    );
+    let artifact_pos = output.source.find("#pdf.artifact").unwrap();
+    let text_pos = output.source.find("Quarterly results").unwrap();
+    assert!(artifact_pos < text_pos);
+
+    let output = generate_typst(&doc).unwrap();
+    assert!(!output.source.contains("pdf.artifact"));
+}
+
 #[test]
 fn test_shape_rotation_codegen() {
     let doc = make_doc(vec![make_fixed_page(
@@ -139,7 +277,9 @@ fn test_shape_rotation_codegen() {
                 rotation_deg: Some(90.0),
                 opacity: None,
                 shadow: None,
+                effects: ShapeEffects::default(),
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -165,7 +305,9 @@ fn test_shape_opacity_codegen() {
                 rotation_deg: None,
                 opacity: Some(0.5),
                 shadow: None,
+                effects: ShapeEffects::default(),
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -190,7 +332,9 @@ fn test_shape_rotation_and_opacity_codegen() {
                 rotation_deg: Some(45.0),
                 opacity: Some(0.75),
                 shadow: None,
+                effects: ShapeEffects::default(),
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -274,7 +418,9 @@ fn test_line_arrowhead_uses_place_overlay() {
                 rotation_deg: None,
                 opacity: None,
                 shadow: None,
+                effects: ShapeEffects::default(),
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -313,7 +459,9 @@ fn test_polyline_segments_use_place_overlay() {
                 rotation_deg: None,
                 opacity: None,
                 shadow: None,
+                effects: ShapeEffects::default(),
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -333,6 +481,58 @@ fn test_polyline_segments_use_place_overlay() {
     );
 }
 
+#[test]
+fn test_ink_stroke_renders_as_one_round_capped_curve() {
+    let doc = make_doc(vec![make_fixed_page(
+        960.0,
+        540.0,
+        vec![FixedElement {
+            x: 10.0,
+            y: 20.0,
+            width: 200.0,
+            height: 100.0,
+            kind: FixedElementKind::Shape(Shape {
+                kind: ShapeKind::InkStroke {
+                    points: vec![(0.0, 0.0), (100.0, 50.0), (200.0, 100.0)],
+                },
+                fill: None,
+                gradient_fill: None,
+                stroke: Some(BorderSide {
+                    width: 2.5,
+                    color: Color::new(255, 0, 0),
+                    style: BorderLineStyle::Solid,
+                }),
+                rotation_deg: None,
+                opacity: Some(0.5),
+                shadow: None,
+                effects: ShapeEffects::default(),
+            }),
+            href: None,
+        }],
+    )]);
+    let output = generate_typst(&doc).unwrap();
+
+    assert_eq!(
+        output.source.matches("#curve(").count(),
+        1,
+        "{}",
+        output.source
+    );
+    assert!(
+        output.source.contains(
+            "curve.move((0pt, 0pt)), curve.line((100pt, 50pt)), curve.line((200pt, 100pt))"
+        ),
+        "Expected the stroke path, got: {}",
+        output.source,
+    );
+    assert!(
+        output
+            .source
+            .contains("thickness: 2.5pt, cap: \"round\", join: \"round\"")
+    );
+    assert!(!output.source.contains("#line("), "{}", output.source);
+}
+
 #[test]
 fn test_rotated_polyline_pre_rotates_points_without_typst_rotate_wrapper() {
     let doc = make_doc(vec![make_fixed_page(
@@ -359,7 +559,9 @@ fn test_rotated_polyline_pre_rotates_points_without_typst_rotate_wrapper() {
                 rotation_deg: Some(270.0),
                 opacity: None,
                 shadow: None,
+                effects: ShapeEffects::default(),
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -403,3 +605,27 @@ fn test_fixed_page_multiple_text_boxes() {
     assert!(output.source.contains("Second"));
     assert!(output.source.contains("Third"));
 }
+
+#[test]
+fn test_fixed_element_href_places_link_area_over_frame() {
+    let mut shape = make_shape_element(
+        10.0,
+        20.0,
+        200.0,
+        150.0,
+        ShapeKind::Rectangle,
+        Some(Color::new(255, 0, 0)),
+        None,
+    );
+    shape.href = Some("https://example.com".to_string());
+    let doc = make_doc(vec![make_fixed_page(960.0, 540.0, vec![shape])]);
+
+    let output = generate_typst(&doc).unwrap();
+    assert!(
+        output.source.contains(
+            "#place(top + left, dx: 10pt, dy: 20pt)[#link(\"https://example.com\")[#box(width: 200pt, height: 150pt)]]"
+        ),
+        "{}",
+        output.source
+    );
+}
@@ -96,6 +96,7 @@ fn test_fixed_page_text_box_multiple_paragraphs_preserve_breaks() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -180,6 +181,7 @@ fn test_fixed_page_text_box_ordered_list_preserves_textbox_styling() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -269,6 +271,7 @@ fn test_fixed_page_text_box_compact_list_items_use_full_width_blocks() {
                 auto_fit: false,
             text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -332,6 +335,7 @@ fn test_fixed_page_text_box_compact_list_preserves_hanging_indent() {
                 auto_fit: false,
             text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -404,6 +408,7 @@ fn test_fixed_page_text_box_compact_list_preserves_marker_origin_offset() {
                 auto_fit: false,
             text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -481,6 +486,7 @@ fn test_fixed_page_text_box_compact_bulleted_list_uses_custom_marker_style() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -580,6 +586,7 @@ fn test_fixed_page_text_box_dash_bullets_use_generic_list_path() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -641,6 +648,7 @@ fn test_fixed_page_text_box_compact_list_preserves_soft_line_breaks() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -696,6 +704,7 @@ fn test_fixed_page_text_box_with_solid_fill() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -744,6 +753,7 @@ fn test_fixed_page_text_box_with_fill_and_stroke() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -759,6 +769,71 @@ fn test_fixed_page_text_box_with_fill_and_stroke() {
     );
 }
 
+#[test]
+fn test_fixed_page_text_box_double_stroke_draws_two_outlines() {
+    let doc = make_doc(vec![make_fixed_page(
+        960.0,
+        540.0,
+        vec![FixedElement {
+            x: 50.0,
+            y: 80.0,
+            width: 200.0,
+            height: 40.0,
+            kind: FixedElementKind::TextBox(crate::ir::TextBoxData {
+                content: vec![Block::Paragraph(Paragraph {
+                    style: ParagraphStyle::default(),
+                    runs: vec![Run {
+                        text: "Total".to_string(),
+                        style: TextStyle::default(),
+                        href: None,
+                        footnote: None,
+                    }],
+                })],
+                padding: Insets {
+                    top: 4.0,
+                    right: 6.0,
+                    bottom: 4.0,
+                    left: 6.0,
+                },
+                vertical_align: crate::ir::TextBoxVerticalAlign::Top,
+                fill: None,
+                opacity: None,
+                stroke: Some(BorderSide {
+                    width: 1.0,
+                    color: Color { r: 0, g: 0, b: 0 },
+                    style: BorderLineStyle::Double,
+                }),
+                shape_kind: None,
+                no_wrap: false,
+                auto_fit: false,
+                text_rotation_deg: None,
+            }),
+            href: None,
+        }],
+    )]);
+    let output = generate_typst(&doc).unwrap();
+    assert!(
+        !output.source.contains("dash: \"solid\""),
+        "Double stroke must not degrade to one stroke, got:\n{}",
+        output.source,
+    );
+    // Both outlines are offset by the block inset to line up with its edge.
+    assert!(
+        output.source.contains(
+            "#place(top + left, dx: -7pt, dy: -5pt, rect(width: 202pt, height: 42pt, stroke: 1pt + rgb(0, 0, 0)))"
+        ),
+        "Expected outer outline, got:\n{}",
+        output.source,
+    );
+    assert!(
+        output.source.contains(
+            "#place(top + left, dx: -5pt, dy: -3pt, rect(width: 198pt, height: 38pt, stroke: 1pt + rgb(0, 0, 0)))"
+        ),
+        "Expected inner outline, got:\n{}",
+        output.source,
+    );
+}
+
 #[test]
 fn test_fixed_page_text_box_with_fill_and_opacity() {
     let doc = make_doc(vec![make_fixed_page(
@@ -793,6 +868,7 @@ fn test_fixed_page_text_box_with_fill_and_opacity() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -844,6 +920,7 @@ fn test_fixed_page_text_box_with_polygon_shape_kind() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -938,6 +1015,7 @@ fn test_fixed_page_text_box_no_wrap_centered_text_uses_inline_box() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -994,6 +1072,7 @@ fn test_fixed_page_text_box_no_wrap_inserts_word_joiners_for_cjk_titles() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -1040,6 +1119,7 @@ fn test_fixed_page_text_box_no_wrap_keeps_latin_text_extractable() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -1091,6 +1171,7 @@ fn test_fixed_page_text_box_no_wrap_keeps_mixed_script_titles_unbroken() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -1151,6 +1232,7 @@ fn test_fixed_page_text_box_no_wrap_preserves_mixed_script_titles_across_runs()
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -1200,6 +1282,7 @@ fn test_fixed_page_text_box_auto_fit_short_text_uses_scale_to_fit() {
                 auto_fit: true,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -1287,6 +1370,7 @@ fn test_fixed_page_text_box_no_wrap_auto_fit_uses_scale_to_fit() {
                 auto_fit: true,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -1358,6 +1442,7 @@ fn test_fixed_page_text_box_mixed_font_header_uses_scale_to_fit() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -1427,6 +1512,7 @@ fn test_fixed_page_text_box_mixed_font_header_with_tight_leading_uses_scale_to_f
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -1499,6 +1585,7 @@ fn test_fixed_page_text_box_wrapped_centered_paragraph_scales_to_fit_height() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -1594,6 +1681,7 @@ fn test_fixed_page_text_box_ordered_grid_normalizes_marker_spacing() {
                 auto_fit: false,
                 text_rotation_deg: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -1601,3 +1689,62 @@ fn test_fixed_page_text_box_ordered_grid_normalizes_marker_spacing() {
     assert!(output.source.contains("#text(size: 20pt)[2\\. ]"));
     assert!(!output.source.contains("#text(size: 20pt)[ Alpha]"));
 }
+
+#[test]
+fn test_fixed_page_text_box_places_overflow_marker_with_slide_number() {
+    let doc = make_doc(vec![
+        make_fixed_page(960.0, 540.0, vec![]),
+        make_fixed_page(
+            960.0,
+            540.0,
+            vec![make_text_box(
+                100.0,
+                200.0,
+                300.0,
+                50.0,
+                "Say \"hi\"\nagain",
+            )],
+        ),
+    ]);
+    let output = generate_typst(&doc).unwrap();
+    assert!(
+        output.source.contains(
+            r#"#context if measure(text_box_content_0).height > 51pt [#metadata((slide: 2, element: "text box \"Say \"hi\" again\"")) <office2pdf-overflow>]"#
+        ),
+        "{}",
+        output.source
+    );
+}
+
+#[test]
+fn test_fixed_page_overflow_marker_names_the_source_slide() {
+    let mut page: Page = make_fixed_page(
+        960.0,
+        540.0,
+        vec![make_text_box(100.0, 200.0, 300.0, 50.0, "Agenda")],
+    );
+    if let Page::Fixed(ref mut fixed) = page {
+        fixed.source_slide = Some(5);
+    }
+    let output = generate_typst(&make_doc(vec![page])).unwrap();
+    assert!(
+        output
+            .source
+            .contains(r#"[#metadata((slide: 1, source: 5, element: "text box \"Agenda\"")) <office2pdf-overflow>]"#),
+        "{}",
+        output.source
+    );
+}
+
+#[test]
+fn test_fixed_page_scaled_text_box_has_no_overflow_marker() {
+    // A single centered paragraph is scaled down into the box.
+    let mut text_box = make_text_box(100.0, 200.0, 300.0, 50.0, "Centered");
+    if let FixedElementKind::TextBox(data) = &mut text_box.kind {
+        data.vertical_align = crate::ir::TextBoxVerticalAlign::Center;
+    }
+    let doc = make_doc(vec![make_fixed_page(960.0, 540.0, vec![text_box])]);
+    let output = generate_typst(&doc).unwrap();
+    assert!(output.source.contains("text_box_scale_0"));
+    assert!(!output.source.contains(OVERFLOW_LABEL));
+}
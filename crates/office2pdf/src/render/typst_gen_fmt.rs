@@ -4,6 +4,8 @@
 //! built with these helpers so the output stays uniform and golden tests
 //! don't drift on formatting details.
 
+use std::fmt::Write;
+
 use crate::ir::{BorderLineStyle, BorderSide, Color};
 
 /// Format a Typst `rgb(r, g, b)` color literal.
@@ -21,8 +23,9 @@ pub(super) fn rgb_with_alpha(color: &Color, alpha: u8) -> String {
 ///
 /// `double_is_plain` preserves an existing divergence: table borders render
 /// `Double` as a plain stroke, while shape strokes send it through the dash
-/// dict (where it maps to `dash: "solid"`). Unifying that is a visible-output
-/// change and belongs in its own visually-verified fix.
+/// dict (where it maps to `dash: "solid"`). Both only matter as a fallback:
+/// table cells, rect-like shapes, lines, and text boxes draw a true double
+/// line as two overlaid strokes, leaving polygons and paths with one.
 pub(super) fn stroke_value(side: &BorderSide, double_is_plain: bool) -> String {
     let is_plain = match side.style {
         BorderLineStyle::Solid | BorderLineStyle::None => true,
@@ -41,6 +44,24 @@ pub(super) fn stroke_value(side: &BorderSide, double_is_plain: bool) -> String {
     }
 }
 
+/// Typst `lang:` and `region:` arguments for a BCP 47 tag such as `en-US`
+/// or `zh-Hant-TW`. `None` when the tag does not start with an ISO 639
+/// language code, as Office's `x-none` placeholder does not.
+pub(super) fn language_params(tag: &str) -> Option<String> {
+    let mut subtags = tag.trim().split(['-', '_']);
+    let language: &str = subtags.next()?;
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut params: String = format!("lang: \"{}\"", language.to_ascii_lowercase());
+    if let Some(region) =
+        subtags.find(|subtag| subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic()))
+    {
+        let _ = write!(params, ", region: \"{}\"", region.to_ascii_uppercase());
+    }
+    Some(params)
+}
+
 /// Format a float without a trailing `.0` on integral values.
 pub(super) fn format_f64(v: f64) -> String {
     if v.fract() == 0.0 {
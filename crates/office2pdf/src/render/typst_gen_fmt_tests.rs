@@ -102,3 +102,22 @@ fn test_format_f64_fractional_keeps_value() {
     assert_eq!(format_f64(1.5), "1.5");
     assert_eq!(format_f64(0.75), "0.75");
 }
+
+#[test]
+fn test_language_params_splits_language_and_region() {
+    assert_eq!(
+        language_params("en-US").as_deref(),
+        Some("lang: \"en\", region: \"US\"")
+    );
+    assert_eq!(
+        language_params("zh-Hant-tw").as_deref(),
+        Some("lang: \"zh\", region: \"TW\"")
+    );
+    assert_eq!(language_params("DE").as_deref(), Some("lang: \"de\""));
+}
+
+#[test]
+fn test_language_params_rejects_placeholder_tags() {
+    assert_eq!(language_params("x-none"), None);
+    assert_eq!(language_params(""), None);
+}
@@ -288,6 +288,7 @@ fn test_fixed_image_with_border_uses_rect_overlay() {
                 clip_shape: None,
                 shadow: None,
             }),
+            href: None,
         }],
     )]);
     let output = generate_typst(&doc).unwrap();
@@ -325,3 +326,45 @@ fn test_image_without_border_no_box() {
         output.source
     );
 }
+
+#[cfg(feature = "image-opt")]
+#[test]
+fn test_image_optimization_downsamples_asset() {
+    let mut encoded = Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgb8(image::RgbImage::from_fn(300, 300, |x, y| {
+        image::Rgb([(x % 256) as u8, (y % 256) as u8, 0])
+    }))
+    .write_to(&mut encoded, RasterImageFormat::Png)
+    .unwrap();
+    let mut image = make_image(ImageFormat::Png, Some(72.0), Some(72.0));
+    if let Block::Image(data) = &mut image {
+        data.data = encoded.into_inner();
+    }
+    let doc = make_doc(vec![make_flow_page(vec![image])]);
+    let options = ConvertOptions {
+        image_optimization: Some(crate::config::ImageOptimization {
+            max_dpi: Some(100.0),
+            jpeg_quality: None,
+            png_to_jpeg: false,
+        }),
+        ..ConvertOptions::default()
+    };
+    let output = generate_typst_with_options(&doc, &options).unwrap();
+    let downsampled = image::load_from_memory(&output.images[0].data).unwrap();
+    // One inch at 100 dpi.
+    assert_eq!(downsampled.dimensions(), (100, 100));
+    assert!(output.source.contains("#image(\"img-0.png\", width: 72pt"));
+}
+
+#[test]
+fn test_svg_asset_external_references_removed() {
+    let mut image = make_image(ImageFormat::Svg, Some(10.0), Some(10.0));
+    if let Block::Image(data) = &mut image {
+        data.data = br#"<svg xmlns="http://www.w3.org/2000/svg"><image href="file:///etc/passwd" width="1" height="1"/></svg>"#.to_vec();
+    }
+    let doc = make_doc(vec![make_flow_page(vec![image])]);
+    let output = generate_typst(&doc).unwrap();
+    assert_eq!(output.images[0].path, "img-0.svg");
+    let svg: String = String::from_utf8(output.images[0].data.clone()).unwrap();
+    assert!(!svg.contains("passwd"), "{svg}");
+}
@@ -787,6 +787,52 @@ fn test_generate_bulleted_list_with_symbol_font_marker_uses_unicode_fallback() {
     assert!(output.source.contains("fill: rgb(0, 0, 0)"));
 }
 
+#[test]
+fn test_generate_list_maps_wingdings_square_bullets() {
+    use crate::ir::List;
+
+    for (wingdings_char, expected_marker) in [("n", "■"), ("§", "▪"), ("q", "❑")] {
+        let list = List {
+            kind: ListKind::Unordered,
+            items: vec![ListItem {
+                content: vec![Paragraph {
+                    style: ParagraphStyle::default(),
+                    runs: vec![Run {
+                        text: "Square bullet".to_string(),
+                        style: TextStyle::default(),
+                        href: None,
+                        footnote: None,
+                    }],
+                }],
+                level: 0,
+                start_at: None,
+            }],
+            level_styles: BTreeMap::from([(
+                0,
+                ListLevelStyle {
+                    kind: ListKind::Unordered,
+                    numbering_pattern: None,
+                    full_numbering: false,
+                    marker_text: Some(wingdings_char.to_string()),
+                    marker_style: Some(TextStyle {
+                        font_family: Some("Wingdings".to_string()),
+                        ..TextStyle::default()
+                    }),
+                },
+            )]),
+        };
+        let doc = make_doc(vec![make_flow_page(vec![Block::List(list)])]);
+        let output = generate_typst(&doc).unwrap();
+
+        assert!(
+            output.source.contains(expected_marker),
+            "Wingdings {wingdings_char:?} should map to {expected_marker}: {}",
+            output.source
+        );
+        assert!(!output.source.contains("Wingdings"));
+    }
+}
+
 #[test]
 fn test_generate_list_uses_first_item_level_marker_when_list_starts_nested() {
     use crate::ir::List;
@@ -1015,6 +1015,9 @@ fn first_visible_char_is_whitespace(runs: &[Run]) -> bool {
 }
 
 fn ordered_marker(pattern: &str, number: u32) -> String {
+    if pattern.contains('①') {
+        return pattern.replacen('①', &circled_marker(number), 1);
+    }
     if pattern.contains('1') {
         return pattern.replacen('1', &number.to_string(), 1);
     }
@@ -1088,7 +1091,14 @@ fn map_symbol_font_marker(font_family: &str, marker_text: &str) -> Option<&'stat
         .collect();
 
     match (normalized_family.as_str(), marker_char) {
-        ("symbol", '\u{F0B7}') => Some("•"),
+        ("symbol", '\u{F0B7}' | '\u{00B7}') => Some("•"),
+        ("wingdings", '\u{006C}') => Some("●"),
+        ("wingdings", '\u{006E}') => Some("■"),
+        ("wingdings", '\u{0071}') => Some("❑"),
+        ("wingdings", '\u{0076}') => Some("❖"),
+        ("wingdings", '\u{0077}') => Some("⬥"),
+        ("wingdings", '\u{00A7}') => Some("▪"),
+        ("wingdings", '\u{00A8}') => Some("◻"),
         ("wingdings", '\u{00D8}') => Some("➢"),
         ("wingdings", '\u{00E8}') => Some("➔"),
         ("wingdings", '\u{00FB}') => Some("✖"),
@@ -1099,6 +1109,16 @@ fn map_symbol_font_marker(font_family: &str, marker_text: &str) -> Option<&'stat
     }
 }
 
+/// Circled digits exist as single code points only for 1 through 20.
+fn circled_marker(number: u32) -> String {
+    match number {
+        1..=20 => char::from_u32(0x2460 + number - 1)
+            .map(String::from)
+            .unwrap_or_else(|| number.to_string()),
+        _ => format!("({number})"),
+    }
+}
+
 fn alpha_marker(mut number: u32, uppercase: bool) -> String {
     let mut chars: Vec<char> = Vec::new();
     while number > 0 {
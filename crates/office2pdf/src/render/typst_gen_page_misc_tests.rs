@@ -255,6 +255,8 @@ fn test_fixed_page_with_background_color() {
         elements: vec![],
         background_color: Some(Color::new(255, 0, 0)),
         background_gradient: None,
+        background_image: None,
+        source_slide: None,
     });
     let doc = make_doc(vec![page]);
     let output = generate_typst(&doc).unwrap();
@@ -271,6 +273,8 @@ fn test_fixed_page_without_background_color() {
         elements: vec![],
         background_color: None,
         background_gradient: None,
+        background_image: None,
+        source_slide: None,
     });
     let doc = make_doc(vec![page]);
     let output = generate_typst(&doc).unwrap();
@@ -281,6 +285,53 @@ fn test_fixed_page_without_background_color() {
     );
 }
 
+fn make_background_image_page(tile_size: Option<(f64, f64)>) -> Page {
+    let FixedElementKind::Image(image) =
+        make_fixed_image(0.0, 0.0, 720.0, 540.0, ImageFormat::Png).kind
+    else {
+        unreachable!("make_fixed_image builds an image element");
+    };
+    Page::Fixed(FixedPage {
+        size: PageSize {
+            width: 720.0,
+            height: 540.0,
+        },
+        elements: vec![make_text_box(10.0, 10.0, 200.0, 40.0, "Over the picture")],
+        background_color: Some(Color::new(0, 0, 255)),
+        background_gradient: None,
+        background_image: Some(BackgroundImage { image, tile_size }),
+        source_slide: None,
+    })
+}
+
+#[test]
+fn test_fixed_page_stretched_background_image_precedes_content() {
+    let output = generate_typst(&make_doc(vec![make_background_image_page(None)])).unwrap();
+    assert_eq!(output.images.len(), 1);
+    let background = output
+        .source
+        .find("image(\"img-0.png\", width: 100%, height: 100%, fit: \"stretch\")")
+        .expect("stretched background image");
+    let content = output.source.find("Over the picture").unwrap();
+    assert!(background < content, "background must be drawn first");
+    assert!(output.source.contains("fill: rgb(0, 0, 255)"));
+}
+
+#[test]
+fn test_fixed_page_tiled_background_image_uses_tiling() {
+    let output = generate_typst(&make_doc(vec![make_background_image_page(Some((
+        1.5, 0.375,
+    )))]))
+    .unwrap();
+    assert!(
+        output
+            .source
+            .contains("fill: tiling(size: (1.5pt, 0.375pt))[#image(\"img-0.png\", width: 1.5pt, height: 0.375pt"),
+        "expected tiling fill, got:\n{}",
+        output.source
+    );
+}
+
 #[test]
 fn test_fixed_page_table_element() {
     let table = Table {
@@ -328,9 +379,12 @@ fn test_fixed_page_table_element() {
             width: 200.0,
             height: 50.0,
             kind: FixedElementKind::Table(table),
+            href: None,
         }],
         background_color: None,
         background_gradient: None,
+        background_image: None,
+        source_slide: None,
     });
 
     let doc = make_doc(vec![page]);
@@ -566,6 +620,130 @@ fn test_table_page_with_page_number_footer() {
     assert!(output.source.contains("footer: context ["));
     assert!(output.source.contains("#counter(page).display()"));
     assert!(output.source.contains("#counter(page).final().first()"));
+
+    // As the third part of a 12-page PDF, after four pages.
+    let numbering = PageNumbering {
+        offset: 4,
+        total: Some(12),
+    };
+    let output = generate_typst_part(&doc, &ConvertOptions::default(), None, numbering).unwrap();
+    assert!(
+        output
+            .source
+            .contains("#counter(page).display(n => str(n + 4))")
+    );
+    assert!(!output.source.contains("final()"));
+}
+
+#[test]
+fn test_date_time_field_renders_from_the_world_clock() {
+    let mut out: String = String::new();
+    generate_hf_elements(
+        &mut out,
+        &[
+            HFInline::DateTime(DateTimeField {
+                format: "m/d/yyyy".to_string(),
+                style: TextStyle::default(),
+            }),
+            HFInline::Run(Run {
+                text: ".".to_string(),
+                style: TextStyle::default(),
+                href: None,
+                footnote: None,
+            }),
+        ],
+        &mut GenCtx::new(),
+    );
+    assert_eq!(
+        out,
+        "#datetime.today().display(\"[month padding:none]/[day padding:none]/[year]\");."
+    );
+
+    let mut out: String = String::new();
+    let bold = TextStyle {
+        bold: Some(true),
+        ..TextStyle::default()
+    };
+    let field = DateTimeField {
+        format: "h:mm AM/PM".to_string(),
+        style: bold,
+    };
+    generate_hf_elements(&mut out, &[HFInline::DateTime(field)], &mut GenCtx::new());
+    assert!(out.starts_with("#text(weight: \"bold\")["), "{out}");
+    assert!(
+        out.contains("[hour repr:12 padding:none]:[minute] [period]"),
+        "{out}"
+    );
+}
+
+#[test]
+fn test_datetime_pattern_translates_excel_codes() {
+    assert_eq!(datetime_pattern("dd.mm.yyyy"), "[day].[month].[year]");
+    // `m` is minutes after an hour or before seconds, a month otherwise.
+    assert_eq!(
+        datetime_pattern("yy-m-d hh:mm:ss"),
+        "[year repr:last_two]-[month padding:none]-[day padding:none] [hour]:[minute]:[second]"
+    );
+    assert_eq!(datetime_pattern("mm:ss"), "[minute]:[second]");
+    assert_eq!(
+        datetime_pattern("dddd, mmmm d \"[x]\""),
+        "[weekday], [month repr:long] [day padding:none] [[x]"
+    );
+}
+
+#[test]
+fn test_tagged_table_page_marks_header_and_footer_as_artifacts() {
+    let hf = |text: &str| HeaderFooter {
+        distance_from_edge: None,
+        paragraphs: vec![HeaderFooterParagraph {
+            style: ParagraphStyle::default(),
+            elements: vec![HFInline::Run(Run {
+                text: text.to_string(),
+                style: TextStyle::default(),
+                href: None,
+                footnote: None,
+            })],
+            border: None,
+            frame: None,
+        }],
+    };
+    let page = Page::Sheet(SheetPage {
+        name: "Sheet1".to_string(),
+        size: PageSize::default(),
+        margins: Margins::default(),
+        table: make_simple_table(vec![vec!["A"]]),
+        header: Some(hf("My Header")),
+        footer: Some(hf("My Footer")),
+        charts: vec![],
+        images: Vec::new(),
+        text_boxes: Vec::new(),
+    });
+    let doc = make_doc(vec![page]);
+
+    let tagged = ConvertOptions {
+        tagged: true,
+        ..Default::default()
+    };
+    let output = generate_typst_with_options(&doc, &tagged).unwrap();
+    assert!(
+        output
+            .source
+            .contains("header: pdf.artifact(kind: \"header\", ["),
+        "got: {}",
+        output.source
+    );
+    assert!(
+        output
+            .source
+            .contains("footer: pdf.artifact(kind: \"footer\", ["),
+        "got: {}",
+        output.source
+    );
+
+    // Untagged output is left as it was.
+    let output = generate_typst(&doc).unwrap();
+    assert!(!output.source.contains("pdf.artifact"));
+    assert!(output.source.contains("header: ["));
 }
 
 #[test]
@@ -589,7 +767,7 @@ fn test_table_page_no_header_footer() {
 
 #[test]
 fn test_table_page_with_chart_at_row() {
-    use crate::ir::{Chart, ChartSeries, ChartType};
+    use crate::ir::{Chart, ChartGrouping, ChartSeries, ChartType};
 
     let chart = Chart {
         chart_type: ChartType::Bar,
@@ -598,7 +776,11 @@ fn test_table_page_with_chart_at_row() {
         series: vec![ChartSeries {
             name: Some("Revenue".to_string()),
             values: vec![100.0, 200.0],
+            bubble_sizes: Vec::new(),
+            chart_type: None,
+            secondary_axis: false,
         }],
+        grouping: ChartGrouping::Clustered,
     };
 
     let page = Page::Sheet(SheetPage {
@@ -629,7 +811,7 @@ fn test_table_page_with_chart_at_row() {
 
 #[test]
 fn test_table_page_with_chart_at_end() {
-    use crate::ir::{Chart, ChartSeries, ChartType};
+    use crate::ir::{Chart, ChartGrouping, ChartSeries, ChartType};
 
     let chart = Chart {
         chart_type: ChartType::Pie,
@@ -638,7 +820,11 @@ fn test_table_page_with_chart_at_end() {
         series: vec![ChartSeries {
             name: None,
             values: vec![100.0],
+            bubble_sizes: Vec::new(),
+            chart_type: None,
+            secondary_axis: false,
         }],
+        grouping: ChartGrouping::Clustered,
     };
 
     let page = Page::Sheet(SheetPage {
@@ -662,6 +848,49 @@ fn test_table_page_with_chart_at_end() {
     assert!(table_pos < chart_pos);
 }
 
+#[test]
+fn test_table_page_with_image_above_first_row() {
+    use crate::ir::{ImageData, ImageFormat, SheetImage};
+
+    let page = Page::Sheet(SheetPage {
+        name: "Sheet1".to_string(),
+        size: PageSize::default(),
+        margins: Margins::default(),
+        table: make_simple_table(vec![vec!["Row 1"], vec!["Row 2"]]),
+        header: None,
+        footer: None,
+        charts: Vec::new(),
+        images: vec![SheetImage {
+            anchor_row: 0,
+            x_offset_pt: 12.0,
+            y_offset_pt: 4.0,
+            image: ImageData {
+                data: vec![0x89, b'P', b'N', b'G'],
+                format: ImageFormat::Png,
+                width: Some(40.0),
+                height: Some(20.0),
+                crop: None,
+                stroke: None,
+                alignment: None,
+                clip_shape: None,
+                shadow: None,
+            },
+        }],
+        text_boxes: Vec::new(),
+    });
+
+    let doc = make_doc(vec![page]);
+    let output = generate_typst(&doc).unwrap();
+    let src = &output.source;
+
+    let image_pos = src
+        .find("#box(width: 100%, height: 24pt)[#place(top + left, dx: 12pt, dy: 4pt)[")
+        .expect("image reserves its height plus the row offset");
+    let table_pos = src.find("#table(").unwrap();
+    assert!(image_pos < table_pos);
+    assert_eq!(src.matches("#table(").count(), 1);
+}
+
 #[test]
 fn test_paper_size_override_letter() {
     use crate::config::PaperSize;
@@ -722,3 +951,25 @@ fn test_no_override_uses_original_size() {
     let output = generate_typst_with_options(&doc, &options).unwrap();
     assert!(output.source.contains("width: 595.28pt"));
 }
+
+#[test]
+fn test_page_link_run_targets_page_location() {
+    let doc = make_doc(vec![make_flow_page(vec![Block::Paragraph(Paragraph {
+        style: ParagraphStyle::default(),
+        runs: vec![Run {
+            text: "Agenda".to_string(),
+            style: TextStyle::default(),
+            href: Some(crate::ir::page_link(2)),
+            footnote: None,
+        }],
+    })])]);
+
+    let output = generate_typst(&doc).unwrap();
+    assert!(
+        output
+            .source
+            .contains("#link((page: 2, x: 0pt, y: 0pt))[Agenda]"),
+        "{}",
+        output.source
+    );
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::ir::TextOutline;
 
 #[test]
 fn test_generate_plain_paragraph() {
@@ -340,6 +341,71 @@ fn test_generate_letter_spacing() {
     );
 }
 
+fn make_styled_run_doc(text: &str, style: TextStyle) -> Document {
+    make_doc(vec![make_flow_page(vec![Block::Paragraph(Paragraph {
+        style: ParagraphStyle::default(),
+        runs: vec![Run {
+            text: text.to_string(),
+            style,
+            href: None,
+            footnote: None,
+        }],
+    })])])
+}
+
+#[test]
+fn test_generate_text_outline_uses_stroke() {
+    let doc = make_styled_run_doc(
+        "Outlined",
+        TextStyle {
+            outline: Some(TextOutline {
+                color: Color::new(0, 0, 255),
+                width: 1.5,
+            }),
+            ..TextStyle::default()
+        },
+    );
+    let result = generate_typst(&doc).unwrap().source;
+    assert!(
+        result.contains("#text(stroke: 1.5pt + rgb(0, 0, 255))[Outlined]"),
+        "Expected stroke param in: {result}"
+    );
+}
+
+#[test]
+fn test_generate_text_shadow_duplicates_each_word() {
+    let doc = make_styled_run_doc(
+        "Big title",
+        TextStyle {
+            font_size: Some(40.0),
+            color: Some(Color::new(255, 255, 255)),
+            shadow: Some(Shadow {
+                blur_radius: 3.0,
+                distance: 2.0,
+                direction: 90.0,
+                color: Color::new(0, 0, 0),
+                opacity: 0.5,
+                scale_x: 1.0,
+                scale_y: 1.0,
+                skew_x: 0.0,
+                skew_y: 0.0,
+            }),
+            ..TextStyle::default()
+        },
+    );
+    let result = generate_typst(&doc).unwrap().source;
+    // Direction 90° points straight down: dx rounds to ~0, dy = distance.
+    let shadow_copy: &str = "pt, dy: 2pt)[#text(size: 40pt, fill: rgb(0, 0, 0, 128))[Big]]#text(size: 40pt, fill: rgb(255, 255, 255))[Big]]";
+    assert!(
+        result.contains(shadow_copy),
+        "Expected shadowed word in: {result}"
+    );
+    assert!(
+        result.contains("[Big]] #box["),
+        "words should be boxed separately so the run can wrap: {result}"
+    );
+}
+
 #[test]
 fn test_generate_letter_spacing_negative() {
     let doc = make_doc(vec![make_flow_page(vec![Block::Paragraph(Paragraph {
@@ -11,7 +11,7 @@ pub(super) fn generate_shape(out: &mut String, shape: &Shape, width: f64, height
     let use_typst_rotation = shape.rotation_deg.is_some()
         && !matches!(
             shape.kind,
-            ShapeKind::Line { .. } | ShapeKind::Polyline { .. }
+            ShapeKind::Line { .. } | ShapeKind::Polyline { .. } | ShapeKind::InkStroke { .. }
         );
     if let Some(deg) = shape.rotation_deg.filter(|_| use_typst_rotation) {
         let _ = write!(out, "#rotate({}deg)[", format_f64(deg));
@@ -22,11 +22,17 @@ pub(super) fn generate_shape(out: &mut String, shape: &Shape, width: f64, height
             out.push_str("#rect(");
             write_shape_params(out, shape, width, height);
             out.push_str(")\n");
+            if let Some(side) = double_stroke(&shape.stroke) {
+                write_double_outline(out, "rect", (0.0, 0.0), width, height, None, side);
+            }
         }
         ShapeKind::Ellipse => {
             out.push_str("#ellipse(");
             write_shape_params(out, shape, width, height);
             out.push_str(")\n");
+            if let Some(side) = double_stroke(&shape.stroke) {
+                write_double_outline(out, "ellipse", (0.0, 0.0), width, height, None, side);
+            }
         }
         ShapeKind::Line {
             x1,
@@ -45,17 +51,21 @@ pub(super) fn generate_shape(out: &mut String, shape: &Shape, width: f64, height
             if has_arrowheads {
                 out.push_str("#place(top + left)[");
             }
-            out.push_str("#line(");
-            let _ = write!(
-                out,
-                "start: ({}pt, {}pt), end: ({}pt, {}pt)",
-                format_f64(start_x),
-                format_f64(start_y),
-                format_f64(end_x),
-                format_f64(end_y),
-            );
-            write_shape_stroke(out, &shape.stroke);
-            out.push_str(")\n");
+            if let Some(side) = double_stroke(&shape.stroke) {
+                write_double_line(out, (start_x, start_y), (end_x, end_y), side);
+            } else {
+                out.push_str("#line(");
+                let _ = write!(
+                    out,
+                    "start: ({}pt, {}pt), end: ({}pt, {}pt)",
+                    format_f64(start_x),
+                    format_f64(start_y),
+                    format_f64(end_x),
+                    format_f64(end_y),
+                );
+                write_shape_stroke(out, &shape.stroke);
+                out.push_str(")\n");
+            }
             if has_arrowheads {
                 out.push_str("]\n");
             }
@@ -87,12 +97,20 @@ pub(super) fn generate_shape(out: &mut String, shape: &Shape, width: f64, height
                 }
             }
         }
+        ShapeKind::InkStroke { points } => {
+            let rotated_points: Vec<(f64, f64)> =
+                rotate_points(points, width, height, shape.rotation_deg);
+            write_ink_stroke(out, shape, &rotated_points);
+        }
         ShapeKind::RoundedRectangle { radius_fraction } => {
             let radius = radius_fraction * width.min(height);
             out.push_str("#rect(");
             write_shape_params(out, shape, width, height);
             let _ = write!(out, ", radius: {}pt", format_f64(radius));
             out.push_str(")\n");
+            if let Some(side) = double_stroke(&shape.stroke) {
+                write_double_outline(out, "rect", (0.0, 0.0), width, height, Some(radius), side);
+            }
         }
         ShapeKind::Polygon { vertices } => {
             write_polygon(out, shape, width, height, vertices);
@@ -104,6 +122,323 @@ pub(super) fn generate_shape(out: &mut String, shape: &Shape, width: f64, height
     }
 }
 
+/// Number of bands a reflection is sliced into. Each band is a clipped strip
+/// of the mirrored shape at a constant opacity, so together they step from
+/// the start to the end alpha of the fade.
+const REFLECTION_BANDS: usize = 4;
+
+/// Render a shape together with its glow, soft-edge, and reflection effects.
+///
+/// Typst has neither blur nor mask primitives, so every effect is built from
+/// stacked translucent copies of the shape outline. `EffectFidelity::Off`
+/// drops the effects and renders the bare shape.
+pub(super) fn generate_shape_with_effects(
+    out: &mut String,
+    shape: &Shape,
+    width: f64,
+    height: f64,
+    fidelity: EffectFidelity,
+) {
+    let is_outline_shape: bool = !matches!(
+        shape.kind,
+        ShapeKind::Line { .. } | ShapeKind::Polyline { .. } | ShapeKind::InkStroke { .. }
+    );
+    if fidelity == EffectFidelity::Off || shape.effects.is_empty() || !is_outline_shape {
+        generate_shape(out, shape, width, height);
+        return;
+    }
+
+    let projection: Option<Projection> = shape.effects.rotation_3d.as_ref().map(project_rotation);
+    if let Some(projection) = &projection {
+        if let Some(extrusion) = &shape.effects.extrusion {
+            write_extrusion_layers(out, shape, width, height, projection, extrusion);
+        }
+        write_affine_start(out, (0.0, 0.0), (width, height), &projection.affine);
+    }
+
+    if let Some(glow) = &shape.effects.glow {
+        write_glow_layers(out, shape, width, height, glow);
+    }
+
+    let soft_edge: Option<(f64, &Color)> = shape
+        .effects
+        .soft_edge_radius
+        .filter(|radius| *radius > 0.0 && shape.gradient_fill.is_none())
+        .zip(shape.fill.as_ref());
+    match soft_edge {
+        Some((radius, fill)) => {
+            if let Some(shadow) = &shape.shadow {
+                write_shadow_shape(out, shape, width, height, shadow);
+            }
+            write_soft_edge_layers(out, shape, width, height, radius, fill);
+        }
+        None => generate_shape(out, shape, width, height),
+    }
+
+    if let Some(reflection) = &shape.effects.reflection {
+        write_reflection_bands(out, shape, width, height, reflection);
+    }
+
+    if let Some(projection) = &projection {
+        write_affine_end(out, &projection.affine);
+    }
+}
+
+/// Scale, skew, and spin applied to a `width` × `height` box around an
+/// origin, approximating a perspective shadow or a 3-D rotation.
+struct Affine {
+    scale_x: f64,
+    scale_y: f64,
+    skew_x: f64,
+    skew_y: f64,
+    rotation: f64,
+    /// Typst alignment the transforms pivot around.
+    origin: &'static str,
+}
+
+/// Flat rendering of a shape rotated in a 3-D scene.
+struct Projection {
+    affine: Affine,
+    /// On-page offset of one point of extrusion depth (into the scene).
+    depth_step: (f64, f64),
+}
+
+/// Smallest projected scale: a shape turned edge-on keeps a sliver rather
+/// than collapsing to an infinitely skewed line.
+const MIN_PROJECTED_SCALE: f64 = 0.05;
+
+/// Most extrusion side-face layers drawn; deeper extrusions space them out.
+const MAX_EXTRUSION_LAYERS: usize = 12;
+
+/// Orthographic projection of the shape plane after turning it by the
+/// longitude (about the vertical axis) and tilting it by the latitude
+/// (about the horizontal axis): a point (x, y) lands on
+/// (x·cos lon, y·cos lat + x·sin lon·sin lat), i.e. a vertical skew followed
+/// by a scale.
+fn project_rotation(rotation: &Rotation3d) -> Projection {
+    let latitude: f64 = rotation.latitude.to_radians();
+    let longitude: f64 = rotation.longitude.to_radians();
+    let keep_sliver = |scale: f64| -> f64 {
+        if scale.abs() < MIN_PROJECTED_SCALE {
+            MIN_PROJECTED_SCALE.copysign(scale)
+        } else {
+            scale
+        }
+    };
+    let scale_x: f64 = keep_sliver(longitude.cos());
+    let scale_y: f64 = keep_sliver(latitude.cos());
+    let skew_y: f64 = (longitude.sin() * latitude.sin() / scale_y)
+        .atan()
+        .to_degrees();
+    Projection {
+        affine: Affine {
+            scale_x,
+            scale_y,
+            skew_x: 0.0,
+            skew_y,
+            rotation: rotation.revolution,
+            origin: "center + horizon",
+        },
+        depth_step: (-longitude.sin(), longitude.cos() * latitude.sin()),
+    }
+}
+
+/// Open `#place` + `#rotate` + `#scale` + `#skew` around a `width` × `height`
+/// box at `(dx, dy)`; close with [`write_affine_end`].
+fn write_affine_start(
+    out: &mut String,
+    (dx, dy): (f64, f64),
+    (width, height): (f64, f64),
+    affine: &Affine,
+) {
+    let origin: &str = affine.origin;
+    let _ = write!(
+        out,
+        "#place(top + left, dx: {}pt, dy: {}pt)[",
+        format_f64(dx),
+        format_f64(dy)
+    );
+    if affine.rotation != 0.0 {
+        let _ = write!(
+            out,
+            "#rotate({}deg, origin: {origin})[",
+            format_f64(affine.rotation)
+        );
+    }
+    let _ = write!(
+        out,
+        "#scale(x: {}%, y: {}%, origin: {origin})[#skew(ax: {}deg, ay: {}deg, origin: {origin})[#box(width: {}pt, height: {}pt)[\n",
+        format_f64(affine.scale_x * 100.0),
+        format_f64(affine.scale_y * 100.0),
+        format_f64(affine.skew_x),
+        format_f64(affine.skew_y),
+        format_f64(width.max(0.0)),
+        format_f64(height.max(0.0)),
+    );
+}
+
+fn write_affine_end(out: &mut String, affine: &Affine) {
+    out.push_str("]]]");
+    if affine.rotation != 0.0 {
+        out.push(']');
+    }
+    out.push_str("]\n");
+}
+
+/// Extrusion side faces: projected copies of the silhouette stepping from
+/// the back face toward the front, drawn behind the shape.
+fn write_extrusion_layers(
+    out: &mut String,
+    shape: &Shape,
+    width: f64,
+    height: f64,
+    projection: &Projection,
+    extrusion: &Extrusion,
+) {
+    let (step_x, step_y): (f64, f64) = projection.depth_step;
+    if extrusion.depth <= 0.0 || step_x.hypot(step_y) < 1e-6 {
+        return;
+    }
+    let color: Color = extrusion.color.unwrap_or_else(|| {
+        let base: Color = shape.fill.unwrap_or(Color::new(128, 128, 128));
+        let darken = |channel: u8| -> u8 { (channel as f64 * 0.6).round() as u8 };
+        Color::new(darken(base.r), darken(base.g), darken(base.b))
+    });
+    let alpha: u8 = (shape.opacity.unwrap_or(1.0).clamp(0.0, 1.0) * 255.0).round() as u8;
+    let fill: String = rgb_with_alpha(&color, alpha);
+    let layer_count: usize = (extrusion.depth.ceil() as usize).clamp(1, MAX_EXTRUSION_LAYERS);
+    for layer in (1..=layer_count).rev() {
+        let depth: f64 = extrusion.depth * layer as f64 / layer_count as f64;
+        write_affine_start(
+            out,
+            (step_x * depth, step_y * depth),
+            (width, height),
+            &projection.affine,
+        );
+        write_silhouette_layer(
+            out,
+            shape,
+            (width, height),
+            (0.0, 0.0),
+            0.0,
+            &fill,
+            shape.rotation_deg,
+        );
+        write_affine_end(out, &projection.affine);
+    }
+}
+
+/// Glow: rings growing outward from the outline. Every ring covers the ones
+/// inside it, so the alpha peaks at the shape edge and fades to nothing at
+/// the glow radius.
+fn write_glow_layers(out: &mut String, shape: &Shape, width: f64, height: f64, glow: &Glow) {
+    if glow.radius <= 0.0 {
+        return;
+    }
+    let layer_count = SHADOW_BLUR_LAYERS;
+    let alpha: u8 = compound_layer_alpha(glow.opacity, layer_count);
+    let fill: String = rgb_with_alpha(&glow.color, alpha);
+    for layer in (0..layer_count).rev() {
+        let expansion = glow.radius * (layer as f64 + 1.0) / layer_count as f64;
+        write_silhouette_layer(
+            out,
+            shape,
+            (width, height),
+            (0.0, 0.0),
+            expansion,
+            &fill,
+            shape.rotation_deg,
+        );
+    }
+}
+
+/// Soft edge: the fill is redrawn as insets shrinking toward the centre, so
+/// the full opacity is only reached `radius` inside the outline. The stroke
+/// is dropped because PowerPoint feathers it away as well.
+fn write_soft_edge_layers(
+    out: &mut String,
+    shape: &Shape,
+    width: f64,
+    height: f64,
+    radius: f64,
+    fill: &Color,
+) {
+    let radius = radius.min(width.min(height) / 2.0);
+    let layer_count = SHADOW_BLUR_LAYERS;
+    let alpha: u8 = compound_layer_alpha(shape.opacity.unwrap_or(1.0), layer_count);
+    let fill: String = rgb_with_alpha(fill, alpha);
+    for layer in 0..layer_count {
+        let expansion = -radius * layer as f64 / layer_count as f64;
+        write_silhouette_layer(
+            out,
+            shape,
+            (width, height),
+            (0.0, 0.0),
+            expansion,
+            &fill,
+            shape.rotation_deg,
+        );
+    }
+}
+
+/// Reflection: a vertically mirrored copy below the shape, sliced into bands
+/// whose opacity steps from `start_opacity` to `end_opacity`. Gradient and
+/// unfilled shapes are skipped since their copies cannot be faded.
+fn write_reflection_bands(
+    out: &mut String,
+    shape: &Shape,
+    width: f64,
+    height: f64,
+    reflection: &Reflection,
+) {
+    if shape.fill.is_none() || shape.gradient_fill.is_some() {
+        return;
+    }
+    let visible_height = height * reflection.end_position.clamp(0.0, 1.0);
+    if visible_height <= 0.0 {
+        return;
+    }
+    let base_opacity = shape.opacity.unwrap_or(1.0);
+    let band_height = visible_height / REFLECTION_BANDS as f64;
+    for band in 0..REFLECTION_BANDS {
+        let progress = (band as f64 + 0.5) / REFLECTION_BANDS as f64;
+        let band_opacity = reflection.start_opacity
+            + (reflection.end_opacity - reflection.start_opacity) * progress;
+        let band_opacity = (band_opacity * base_opacity).clamp(0.0, 1.0);
+        if band_opacity <= 0.0 {
+            continue;
+        }
+        let mirrored = Shape {
+            stroke: None,
+            opacity: Some(band_opacity),
+            shadow: None,
+            effects: ShapeEffects::default(),
+            ..shape.clone()
+        };
+        let band_top = band_height * band as f64;
+        let _ = write!(
+            out,
+            "#place(top + left, dy: {}pt)[#box(width: {}pt, height: {}pt, clip: true)[#move(dy: {}pt)[#scale(y: -100%)[#box(width: {}pt, height: {}pt)[",
+            format_f64(height + reflection.distance + band_top),
+            format_f64(width),
+            format_f64(band_height),
+            format_f64(-band_top),
+            format_f64(width),
+            format_f64(height),
+        );
+        generate_shape(out, &mirrored, width, height);
+        out.push_str("]]]]]\n");
+    }
+}
+
+/// Alpha for each of `layer_count` stacked layers so that their overlap
+/// compounds to `opacity`.
+fn compound_layer_alpha(opacity: f64, layer_count: usize) -> u8 {
+    let opacity = opacity.clamp(0.0, 1.0);
+    let layer_opacity = 1.0 - (1.0 - opacity).powf(1.0 / layer_count as f64);
+    (layer_opacity * 255.0).round() as u8
+}
+
 fn rotated_line_points(
     x1: f64,
     y1: f64,
@@ -175,8 +510,7 @@ pub(super) fn shadow_blur_layers(shadow: &Shadow) -> Vec<(f64, u8)> {
         return vec![(0.0, (opacity * 255.0).round() as u8)];
     }
     let layer_count = SHADOW_BLUR_LAYERS;
-    let layer_opacity = 1.0 - (1.0 - opacity).powf(1.0 / layer_count as f64);
-    let alpha = (layer_opacity * 255.0).round() as u8;
+    let alpha: u8 = compound_layer_alpha(opacity, layer_count);
     (0..layer_count)
         .map(|layer| {
             let expansion = shadow.blur_radius * ((layer as f64 + 0.5) / layer_count as f64 - 0.5);
@@ -195,68 +529,110 @@ fn write_shadow_shape(out: &mut String, shape: &Shape, width: f64, height: f64,
     let dir_rad = shadow.direction.to_radians();
     let dx = shadow.distance * dir_rad.cos();
     let dy = shadow.distance * dir_rad.sin();
+    // Perspective shadows pivot on the shape's bottom edge, DrawingML's
+    // default shadow alignment.
+    let perspective: Option<Affine> = shadow.has_perspective().then_some(Affine {
+        scale_x: shadow.scale_x,
+        scale_y: shadow.scale_y,
+        skew_x: shadow.skew_x,
+        skew_y: shadow.skew_y,
+        rotation: 0.0,
+        origin: "bottom + center",
+    });
 
     for (expansion, alpha) in shadow_blur_layers(shadow) {
-        let layer_width = (width + 2.0 * expansion).max(0.0);
-        let layer_height = (height + 2.0 * expansion).max(0.0);
-        let _ = write!(
-            out,
-            "#place(top + left, dx: {}pt, dy: {}pt)[",
-            format_f64(dx - expansion),
-            format_f64(dy - expansion),
-        );
-        match &shape.kind {
-            ShapeKind::Polygon { vertices } => {
-                out.push_str("#polygon(");
-                write_polygon_vertices(out, layer_width, layer_height, vertices);
-                let _ = write!(
-                    out,
-                    ", fill: rgb({}, {}, {}, {})",
-                    shadow.color.r, shadow.color.g, shadow.color.b, alpha,
-                );
-                out.push(')');
-            }
-            ShapeKind::RoundedRectangle { radius_fraction } => {
-                let radius = (radius_fraction * width.min(height) + expansion).max(0.0);
-                let _ = write!(
+        let fill: String = rgb_with_alpha(&shadow.color, alpha);
+        match &perspective {
+            Some(affine) => {
+                write_affine_start(out, (dx, dy), (width, height), affine);
+                write_silhouette_layer(
                     out,
-                    "#rect(width: {}pt, height: {}pt, radius: {}pt, fill: rgb({}, {}, {}, {}))",
-                    format_f64(layer_width),
-                    format_f64(layer_height),
-                    format_f64(radius),
-                    shadow.color.r,
-                    shadow.color.g,
-                    shadow.color.b,
-                    alpha,
+                    shape,
+                    (width, height),
+                    (0.0, 0.0),
+                    expansion,
+                    &fill,
+                    None,
                 );
+                write_affine_end(out, affine);
             }
-            ShapeKind::Rectangle | ShapeKind::Ellipse => {
-                let func = if matches!(shape.kind, ShapeKind::Rectangle) {
-                    "#rect("
-                } else {
-                    "#ellipse("
-                };
-                out.push_str(func);
-                let _ = write!(
-                    out,
-                    "width: {}pt, height: {}pt, fill: rgb({}, {}, {}, {})",
-                    format_f64(layer_width),
-                    format_f64(layer_height),
-                    shadow.color.r,
-                    shadow.color.g,
-                    shadow.color.b,
-                    alpha,
-                );
-                out.push(')');
-            }
-            // Line is handled above; any future variants gracefully skip
-            // the shadow rather than panicking.
-            _ => {}
+            None => write_silhouette_layer(
+                out,
+                shape,
+                (width, height),
+                (dx, dy),
+                expansion,
+                &fill,
+                None,
+            ),
         }
-        out.push_str("]\n");
     }
 }
 
+/// Write one `#place`d, solid-filled copy of the shape outline grown by
+/// `expansion` on every side (negative values shrink it). Used by the shadow,
+/// glow, and soft-edge approximations, which are all built from stacked
+/// translucent silhouettes.
+fn write_silhouette_layer(
+    out: &mut String,
+    shape: &Shape,
+    (width, height): (f64, f64),
+    (dx, dy): (f64, f64),
+    expansion: f64,
+    fill: &str,
+    rotation_deg: Option<f64>,
+) {
+    let layer_width = (width + 2.0 * expansion).max(0.0);
+    let layer_height = (height + 2.0 * expansion).max(0.0);
+    let _ = write!(
+        out,
+        "#place(top + left, dx: {}pt, dy: {}pt)[",
+        format_f64(dx - expansion),
+        format_f64(dy - expansion),
+    );
+    if let Some(deg) = rotation_deg {
+        let _ = write!(out, "#rotate({}deg)[", format_f64(deg));
+    }
+    match &shape.kind {
+        ShapeKind::Polygon { vertices } => {
+            out.push_str("#polygon(");
+            write_polygon_vertices(out, layer_width, layer_height, vertices);
+            let _ = write!(out, ", fill: {fill})");
+        }
+        ShapeKind::RoundedRectangle { radius_fraction } => {
+            let radius = (radius_fraction * width.min(height) + expansion).max(0.0);
+            let _ = write!(
+                out,
+                "#rect(width: {}pt, height: {}pt, radius: {}pt, fill: {fill})",
+                format_f64(layer_width),
+                format_f64(layer_height),
+                format_f64(radius),
+            );
+        }
+        ShapeKind::Rectangle | ShapeKind::Ellipse => {
+            let func = if matches!(shape.kind, ShapeKind::Rectangle) {
+                "#rect("
+            } else {
+                "#ellipse("
+            };
+            out.push_str(func);
+            let _ = write!(
+                out,
+                "width: {}pt, height: {}pt, fill: {fill})",
+                format_f64(layer_width),
+                format_f64(layer_height),
+            );
+        }
+        // Lines have no fillable outline; any future variants gracefully
+        // skip the silhouette rather than panicking.
+        _ => {}
+    }
+    if rotation_deg.is_some() {
+        out.push(']');
+    }
+    out.push_str("]\n");
+}
+
 /// Write fill color, using rgb with 4 args when opacity is set, rgb with 3 args otherwise.
 pub(super) fn write_fill_color(out: &mut String, fill: &Color, opacity: Option<f64>) {
     if let Some(op) = opacity {
@@ -280,7 +656,10 @@ fn write_shape_params(out: &mut String, shape: &Shape, width: f64, height: f64)
     } else if let Some(fill) = &shape.fill {
         write_fill_color(out, fill, shape.opacity);
     }
-    write_shape_stroke(out, &shape.stroke);
+    // A double outline is drawn after the shape by `write_double_outline`.
+    if double_stroke(&shape.stroke).is_none() {
+        write_shape_stroke(out, &shape.stroke);
+    }
 }
 
 /// Write stroke parameter for shapes, handling dash patterns.
@@ -290,6 +669,74 @@ pub(super) fn write_shape_stroke(out: &mut String, stroke: &Option<BorderSide>)
     }
 }
 
+/// The stroke, if it is a double line.
+///
+/// Typst strokes are single lines, so a double outline is drawn as two
+/// solid overlays instead of through the shape's own `stroke:` parameter.
+pub(super) fn double_stroke(stroke: &Option<BorderSide>) -> Option<&BorderSide> {
+    stroke
+        .as_ref()
+        .filter(|side| side.style == BorderLineStyle::Double)
+}
+
+/// Draw a double outline around a `width` x `height` rect or ellipse whose
+/// top-left corner sits at `origin` within the enclosing container.
+///
+/// Like table double borders, each stroke is `side.width` thick and the gap
+/// between them is as wide again, so the strokes are centred `side.width`
+/// outside and inside the outline.
+pub(super) fn write_double_outline(
+    out: &mut String,
+    kind: &str,
+    origin: (f64, f64),
+    width: f64,
+    height: f64,
+    radius: Option<f64>,
+    side: &BorderSide,
+) {
+    for offset in [side.width, -side.width] {
+        let _ = write!(
+            out,
+            "#place(top + left, dx: {}pt, dy: {}pt, {kind}(width: {}pt, height: {}pt",
+            format_f64(origin.0 - offset),
+            format_f64(origin.1 - offset),
+            format_f64((width + 2.0 * offset).max(0.0)),
+            format_f64((height + 2.0 * offset).max(0.0)),
+        );
+        if let Some(radius) = radius {
+            let _ = write!(
+                out,
+                ", radius: {}pt",
+                format_f64((radius + offset).max(0.0))
+            );
+        }
+        let _ = writeln!(out, ", stroke: {}))", stroke_value(side, true));
+    }
+}
+
+/// Draw a double line as two strokes offset to either side of the segment.
+fn write_double_line(out: &mut String, start: (f64, f64), end: (f64, f64), side: &BorderSide) {
+    let (dx, dy): (f64, f64) = (end.0 - start.0, end.1 - start.1);
+    let length: f64 = dx.hypot(dy);
+    // A zero-length line has no direction to offset along.
+    let (normal_x, normal_y): (f64, f64) = if length > 0.0 {
+        (-dy / length * side.width, dx / length * side.width)
+    } else {
+        (0.0, 0.0)
+    };
+    for sign in [1.0, -1.0] {
+        let _ = writeln!(
+            out,
+            "#place(top + left, line(start: ({}pt, {}pt), end: ({}pt, {}pt), stroke: {}))",
+            format_f64(start.0 + sign * normal_x),
+            format_f64(start.1 + sign * normal_y),
+            format_f64(end.0 + sign * normal_x),
+            format_f64(end.1 + sign * normal_y),
+            stroke_value(side, true),
+        );
+    }
+}
+
 /// Write a border stroke value for image box wrapping (no leading comma).
 pub(super) fn write_image_border_stroke(out: &mut String, stroke: &BorderSide) {
     out.push_str(&stroke_value(stroke, false));
@@ -393,6 +840,41 @@ fn write_polyline(out: &mut String, stroke: &Option<BorderSide>, points: &[(f64,
     }
 }
 
+/// Draw a pen stroke as a single curve so the round joins hide the seams
+/// between its many short samples. A one-point stroke is drawn as a dot.
+fn write_ink_stroke(out: &mut String, shape: &Shape, points: &[(f64, f64)]) {
+    let (Some(stroke), Some(&first)) = (&shape.stroke, points.first()) else {
+        return;
+    };
+    let alpha: u8 = (shape.opacity.unwrap_or(1.0).clamp(0.0, 1.0) * 255.0).round() as u8;
+    let _ = write!(
+        out,
+        "#place(top + left)[#curve(stroke: (paint: {}, thickness: {}pt, cap: \"round\", join: \"round\"), curve.move(({}pt, {}pt))",
+        rgb_with_alpha(&stroke.color, alpha),
+        format_f64(stroke.width),
+        format_f64(first.0),
+        format_f64(first.1),
+    );
+    if points.len() == 1 {
+        // A zero-length segment draws nothing; nudge it so the round cap shows.
+        let _ = write!(
+            out,
+            ", curve.line(({}pt, {}pt))",
+            format_f64(first.0 + 0.01),
+            format_f64(first.1),
+        );
+    }
+    for (x, y) in &points[1..] {
+        let _ = write!(
+            out,
+            ", curve.line(({}pt, {}pt))",
+            format_f64(*x),
+            format_f64(*y)
+        );
+    }
+    out.push_str(")]\n");
+}
+
 /// Draw a triangle arrowhead at `tip`, pointing in the direction from `from` → `tip`.
 fn write_arrowhead_at(
     out: &mut String,
@@ -0,0 +1,482 @@
+use super::charts::{path_coords, write_line};
+use super::diagrams::CHART_SERIES_COLORS;
+use super::*;
+use crate::ir::SmartArtNode;
+
+/// Node fill of Office's default SmartArt style (accent 1).
+const NODE_FILL: &str = "rgb(68, 114, 196)";
+/// Connector stroke of Office's default SmartArt style (a lighter accent 1).
+const CONNECTOR_STROKE: &str = "1.25pt + rgb(143, 170, 220)";
+const CONNECTOR_FILL: &str = "rgb(143, 170, 220)";
+/// Blank margin kept between the diagram and the frame edge.
+const FRAME_PAD: f64 = 4.0;
+const MIN_FONT: f64 = 5.0;
+const MAX_FONT: f64 = 14.0;
+/// Approximate advance of one character relative to the font size, used to
+/// size node text without measuring it.
+const CHAR_WIDTH: f64 = 0.55;
+/// Words longer than this many characters are expected to wrap rather than
+/// shrink the font further.
+const MAX_FIT_CHARS: usize = 18;
+/// Angular resolution of cycle connector arcs (Typst paths have no arc
+/// segment).
+const ARC_SEGMENT_DEGREES: f64 = 4.0;
+
+/// A top-level node with the text of all of its descendants.
+struct NodeGroup<'a> {
+    title: &'a str,
+    details: Vec<&'a str>,
+}
+
+/// Generate an approximate geometric rendering of `smartart` scaled to a
+/// `width` × `height` frame, picked by its layout family.
+///
+/// Cycles place the top-level nodes around an ellipse joined by arrows,
+/// hierarchies draw a tree of boxes with elbow connectors, pyramids stack
+/// trapezoid bands, and matrices tile the nodes in a grid. Child nodes of
+/// cycles, pyramids, and matrices become detail lines of their top-level
+/// node, as Office does for the default styles of those layouts.
+pub(super) fn generate_smartart_layout(
+    out: &mut String,
+    smartart: &SmartArt,
+    width: f64,
+    height: f64,
+) {
+    let _ = writeln!(
+        out,
+        "#box(width: {}pt, height: {}pt)[",
+        format_f64(width),
+        format_f64(height)
+    );
+    let frame: Rect = Rect {
+        x: FRAME_PAD,
+        y: FRAME_PAD,
+        width: (width - 2.0 * FRAME_PAD).max(0.0),
+        height: (height - 2.0 * FRAME_PAD).max(0.0),
+    };
+    match smartart.layout {
+        SmartArtLayout::Cycle => generate_cycle(out, &node_groups(&smartart.items), frame),
+        SmartArtLayout::Hierarchy => generate_hierarchy(out, &smartart.items, frame),
+        SmartArtLayout::Pyramid => generate_pyramid(out, &node_groups(&smartart.items), frame),
+        SmartArtLayout::Matrix => generate_matrix(out, &smartart.items, frame),
+        SmartArtLayout::List => {}
+    }
+    out.push_str("]\n");
+}
+
+#[derive(Clone, Copy)]
+struct Rect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// Parent of `items[index]`: the recorded parent, or for nodes without one
+/// the nearest preceding node of smaller depth.
+fn parent_of(items: &[SmartArtNode], index: usize) -> Option<usize> {
+    let node: &SmartArtNode = &items[index];
+    if node.parent.is_some() || node.depth == 0 {
+        return node.parent.filter(|parent| *parent < items.len());
+    }
+    (0..index).rev().find(|i| items[*i].depth < node.depth)
+}
+
+fn root_of(items: &[SmartArtNode], mut index: usize) -> usize {
+    // Bounded by the item count so a malformed parent cycle cannot loop.
+    for _ in 0..items.len() {
+        match parent_of(items, index) {
+            Some(parent) => index = parent,
+            None => break,
+        }
+    }
+    index
+}
+
+fn node_groups(items: &[SmartArtNode]) -> Vec<NodeGroup<'_>> {
+    let roots: Vec<usize> = (0..items.len())
+        .filter(|index| parent_of(items, *index).is_none())
+        .collect();
+    roots
+        .iter()
+        .map(|root| NodeGroup {
+            title: &items[*root].text,
+            details: (0..items.len())
+                .filter(|index| index != root && root_of(items, *index) == *root)
+                .map(|index| items[index].text.as_str())
+                .collect(),
+        })
+        .collect()
+}
+
+/// Largest font at which the node text plausibly fits a `width` × `height`
+/// box.
+fn fit_font(width: f64, height: f64, group: &NodeGroup<'_>) -> f64 {
+    let lines: usize = 1 + group.details.len();
+    let longest: usize = std::iter::once(group.title)
+        .chain(group.details.iter().copied())
+        .map(|text| text.chars().count().min(MAX_FIT_CHARS))
+        .max()
+        .unwrap_or(1)
+        .max(1);
+    let by_height: f64 = height / (lines as f64 * 1.35 + 0.6);
+    let by_width: f64 = width / (longest as f64 * CHAR_WIDTH + 1.0);
+    by_height.min(by_width).clamp(MIN_FONT, MAX_FONT)
+}
+
+/// Place a filled node box at `rect` with its text centered in white.
+fn write_node(out: &mut String, rect: Rect, fill: &str, radius: f64, group: &NodeGroup<'_>) {
+    let font: f64 = fit_font(rect.width, rect.height, group);
+    let mut text: String = format!(
+        "#text(size: {}pt, fill: white, weight: \"bold\")[{}]",
+        format_f64(font),
+        escape_typst(group.title)
+    );
+    for detail in &group.details {
+        let _ = write!(
+            text,
+            " \\ #text(size: {}pt, fill: white)[{}]",
+            format_f64(font * 0.85),
+            escape_typst(detail)
+        );
+    }
+    let _ = writeln!(
+        out,
+        "#place(top + left, dx: {}pt, dy: {}pt, box(width: {}pt, height: {}pt, fill: {fill}, radius: {}pt, inset: {}pt, clip: true)[#align(center + horizon)[{text}]])",
+        format_f64(rect.x),
+        format_f64(rect.y),
+        format_f64(rect.width.max(0.0)),
+        format_f64(rect.height.max(0.0)),
+        format_f64(radius),
+        format_f64((font * 0.4).min(rect.height / 4.0)),
+    );
+}
+
+fn generate_cycle(out: &mut String, groups: &[NodeGroup<'_>], frame: Rect) {
+    let count: usize = groups.len();
+    if count == 0 {
+        return;
+    }
+    let side: f64 = frame.width.min(frame.height);
+    let mut node_h: f64 = side * 0.26;
+    if count > 1 {
+        // Keep neighbouring nodes from overlapping on small rings.
+        let chord: f64 = side * 0.75 * (std::f64::consts::PI / count as f64).sin();
+        node_h = node_h.min(chord * 0.6);
+    }
+    let node_w: f64 = (node_h * 1.6).min(frame.width * 0.35);
+    let center: (f64, f64) = (frame.x + frame.width / 2.0, frame.y + frame.height / 2.0);
+    let radius_x: f64 = (frame.width / 2.0 - node_w / 2.0).max(0.0);
+    let radius_y: f64 = (frame.height / 2.0 - node_h / 2.0).max(0.0);
+    // Clockwise from 12 o'clock, as Office lays out the basic cycle.
+    let point_at = |degrees: f64| -> (f64, f64) {
+        let radians: f64 = degrees.to_radians();
+        (
+            center.0 + radius_x * radians.sin(),
+            center.1 - radius_y * radians.cos(),
+        )
+    };
+    let step: f64 = 360.0 / count as f64;
+
+    if count > 1 {
+        // Arcs start and end outside the node boxes they connect.
+        let node_extent: f64 = node_w.hypot(node_h) / 2.0;
+        let ring: f64 = radius_x.max(radius_y).max(1.0);
+        let gap: f64 = (node_extent / ring).to_degrees().min(step * 0.4);
+        for index in 0..count {
+            let start: f64 = index as f64 * step + gap;
+            let sweep: f64 = step - 2.0 * gap;
+            if sweep <= 0.0 {
+                continue;
+            }
+            let segments: usize = (sweep / ARC_SEGMENT_DEGREES).ceil().max(1.0) as usize;
+            let arc: Vec<(f64, f64)> = (0..=segments)
+                .map(|segment| point_at(start + sweep * segment as f64 / segments as f64))
+                .collect();
+            let _ = writeln!(
+                out,
+                "#place(top + left, path(stroke: {CONNECTOR_STROKE}, {}))",
+                path_coords(&arc)
+            );
+            write_arrowhead(out, arc[arc.len() - 2], arc[arc.len() - 1], node_h * 0.12);
+        }
+    }
+
+    for (index, group) in groups.iter().enumerate() {
+        let (x, y): (f64, f64) = point_at(index as f64 * step);
+        let rect: Rect = Rect {
+            x: x - node_w / 2.0,
+            y: y - node_h / 2.0,
+            width: node_w,
+            height: node_h,
+        };
+        write_node(out, rect, NODE_FILL, node_h / 2.0, group);
+    }
+}
+
+/// Filled triangle at `tip` pointing away from `from`.
+fn write_arrowhead(out: &mut String, from: (f64, f64), tip: (f64, f64), size: f64) {
+    let (dx, dy): (f64, f64) = (tip.0 - from.0, tip.1 - from.1);
+    let length: f64 = dx.hypot(dy);
+    if length <= 0.0 || size <= 0.0 {
+        return;
+    }
+    let (ux, uy): (f64, f64) = (dx / length, dy / length);
+    let back: (f64, f64) = (tip.0 - ux * size * 1.5, tip.1 - uy * size * 1.5);
+    let points: [(f64, f64); 3] = [
+        tip,
+        (back.0 - uy * size, back.1 + ux * size),
+        (back.0 + uy * size, back.1 - ux * size),
+    ];
+    let _ = writeln!(
+        out,
+        "#place(top + left, path(fill: {CONNECTOR_FILL}, closed: true, {}))",
+        path_coords(&points)
+    );
+}
+
+fn generate_hierarchy(out: &mut String, items: &[SmartArtNode], frame: Rect) {
+    let count: usize = items.len();
+    let parents: Vec<Option<usize>> = (0..count).map(|index| parent_of(items, index)).collect();
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); count];
+    for (index, parent) in parents.iter().enumerate() {
+        if let Some(parent) = parent {
+            children[*parent].push(index);
+        }
+    }
+    let levels: Vec<usize> = (0..count)
+        .map(|index| {
+            let mut level: usize = 0;
+            let mut current: usize = index;
+            while let Some(parent) = parents[current]
+                && level < count
+            {
+                level += 1;
+                current = parent;
+            }
+            level
+        })
+        .collect();
+    let roots: Vec<usize> = (0..count)
+        .filter(|index| parents[*index].is_none())
+        .collect();
+    if roots.is_empty() {
+        return;
+    }
+
+    let mut leaf_counts: Vec<usize> = vec![0; count];
+    // Deepest nodes first so every child is counted before its parent.
+    let mut by_depth: Vec<usize> = (0..count).collect();
+    by_depth.sort_by_key(|index| std::cmp::Reverse(levels[*index]));
+    for index in &by_depth {
+        let from_children: usize = children[*index]
+            .iter()
+            .map(|child| leaf_counts[*child])
+            .sum();
+        leaf_counts[*index] = from_children.max(1);
+    }
+
+    let total_leaves: usize = roots.iter().map(|root| leaf_counts[*root]).sum();
+    let rows: usize = levels.iter().max().copied().unwrap_or(0) + 1;
+    let slot_w: f64 = frame.width / total_leaves as f64;
+    let row_h: f64 = frame.height / rows as f64;
+    let node_w: f64 = (slot_w * 0.85).min(frame.width * 0.3);
+    let node_h: f64 = row_h * 0.6;
+
+    // Center of each node over the leaves it spans, assigned top-down.
+    let mut centers: Vec<f64> = vec![0.0; count];
+    let mut pending: Vec<(usize, f64)> = Vec::new();
+    let mut leaf_start: f64 = 0.0;
+    for root in &roots {
+        pending.push((*root, leaf_start));
+        leaf_start += leaf_counts[*root] as f64;
+    }
+    while let Some((index, start)) = pending.pop() {
+        centers[index] = frame.x + (start + leaf_counts[index] as f64 / 2.0) * slot_w;
+        let mut child_start: f64 = start;
+        for child in &children[index] {
+            pending.push((*child, child_start));
+            child_start += leaf_counts[*child] as f64;
+        }
+    }
+    let node_top =
+        |index: usize| -> f64 { frame.y + levels[index] as f64 * row_h + (row_h - node_h) / 2.0 };
+
+    for (index, node_children) in children.iter().enumerate() {
+        if node_children.is_empty() {
+            continue;
+        }
+        let bottom: f64 = node_top(index) + node_h;
+        let elbow_y: f64 = bottom + (row_h - node_h) / 2.0;
+        write_line(
+            out,
+            centers[index],
+            bottom,
+            0.0,
+            elbow_y - bottom,
+            CONNECTOR_STROKE,
+        );
+        let first: f64 = node_children
+            .iter()
+            .map(|child| centers[*child])
+            .fold(centers[index], f64::min);
+        let last: f64 = node_children
+            .iter()
+            .map(|child| centers[*child])
+            .fold(centers[index], f64::max);
+        write_line(out, first, elbow_y, last - first, 0.0, CONNECTOR_STROKE);
+        for child in node_children {
+            write_line(
+                out,
+                centers[*child],
+                elbow_y,
+                0.0,
+                node_top(*child) - elbow_y,
+                CONNECTOR_STROKE,
+            );
+        }
+    }
+
+    for (index, node) in items.iter().enumerate() {
+        let rect: Rect = Rect {
+            x: centers[index] - node_w / 2.0,
+            y: node_top(index),
+            width: node_w,
+            height: node_h,
+        };
+        let group: NodeGroup<'_> = NodeGroup {
+            title: &node.text,
+            details: Vec::new(),
+        };
+        write_node(out, rect, NODE_FILL, node_h * 0.1, &group);
+    }
+}
+
+fn generate_pyramid(out: &mut String, groups: &[NodeGroup<'_>], frame: Rect) {
+    let count: usize = groups.len();
+    if count == 0 {
+        return;
+    }
+    let center_x: f64 = frame.x + frame.width / 2.0;
+    let band_h: f64 = frame.height / count as f64;
+    let gap: f64 = band_h * 0.06;
+    // Half-width of the triangle at a given y: zero at the apex, the full
+    // frame at the base.
+    let half_width_at =
+        |y: f64| -> f64 { frame.width / 2.0 * ((y - frame.y) / frame.height).clamp(0.0, 1.0) };
+
+    for (index, group) in groups.iter().enumerate() {
+        let top: f64 = frame.y + index as f64 * band_h + if index == 0 { 0.0 } else { gap / 2.0 };
+        let bottom: f64 = frame.y + (index + 1) as f64 * band_h
+            - if index + 1 == count { 0.0 } else { gap / 2.0 };
+        let (top_half, bottom_half): (f64, f64) = (half_width_at(top), half_width_at(bottom));
+        let color: &str = CHART_SERIES_COLORS[index % CHART_SERIES_COLORS.len()];
+        let outline: [(f64, f64); 4] = [
+            (center_x - top_half, top),
+            (center_x + top_half, top),
+            (center_x + bottom_half, bottom),
+            (center_x - bottom_half, bottom),
+        ];
+        let _ = writeln!(
+            out,
+            "#place(top + left, path(fill: {color}, closed: true, {}))",
+            path_coords(&outline)
+        );
+        // Text sits in the band's average width so it stays inside the
+        // slanted edges.
+        let text_half: f64 = (top_half + bottom_half) / 2.0;
+        let rect: Rect = Rect {
+            x: center_x - text_half,
+            y: top,
+            width: text_half * 2.0,
+            height: bottom - top,
+        };
+        write_node(out, rect, "none", 0.0, group);
+    }
+}
+
+fn generate_matrix(out: &mut String, items: &[SmartArtNode], frame: Rect) {
+    let groups: Vec<NodeGroup<'_>> = node_groups(items);
+    // A single root with children is the titled matrix: the children form
+    // the quadrants and the root labels the center.
+    let (center_label, cells): (Option<&str>, Vec<NodeGroup<'_>>) = match groups.as_slice() {
+        [root] if !root.details.is_empty() => {
+            let root_index: usize = (0..items.len())
+                .find(|index| parent_of(items, *index).is_none())
+                .unwrap_or(0);
+            let cells: Vec<NodeGroup<'_>> = (0..items.len())
+                .filter(|index| parent_of(items, *index) == Some(root_index))
+                .map(|child| NodeGroup {
+                    title: &items[child].text,
+                    details: (0..items.len())
+                        .filter(|index| {
+                            *index != child
+                                && parent_of(items, *index).is_some()
+                                && ancestor_at_level_one(items, *index) == Some(child)
+                        })
+                        .map(|index| items[index].text.as_str())
+                        .collect(),
+                })
+                .collect();
+            (Some(root.title), cells)
+        }
+        _ => (None, groups),
+    };
+    let count: usize = cells.len();
+    if count == 0 {
+        return;
+    }
+
+    let columns: usize = (count as f64).sqrt().ceil() as usize;
+    let rows: usize = count.div_ceil(columns);
+    let gap: f64 = frame.width.min(frame.height) * 0.03;
+    let cell_w: f64 = (frame.width - gap * (columns - 1) as f64) / columns as f64;
+    let cell_h: f64 = (frame.height - gap * (rows - 1) as f64) / rows as f64;
+    for (index, cell) in cells.iter().enumerate() {
+        let (row, column): (usize, usize) = (index / columns, index % columns);
+        let rect: Rect = Rect {
+            x: frame.x + column as f64 * (cell_w + gap),
+            y: frame.y + row as f64 * (cell_h + gap),
+            width: cell_w,
+            height: cell_h,
+        };
+        let color: &str = CHART_SERIES_COLORS[index % CHART_SERIES_COLORS.len()];
+        write_node(out, rect, color, cell_h.min(cell_w) * 0.05, cell);
+    }
+
+    if let Some(label) = center_label {
+        let size: f64 = frame.width.min(frame.height) * 0.3;
+        let font: f64 = fit_font(
+            size,
+            size,
+            &NodeGroup {
+                title: label,
+                details: Vec::new(),
+            },
+        );
+        let _ = writeln!(
+            out,
+            "#place(top + left, dx: {}pt, dy: {}pt, box(width: {}pt, height: {}pt, fill: white, stroke: 1.5pt + {NODE_FILL}, radius: {}pt, inset: {}pt)[#align(center + horizon)[#text(size: {}pt, fill: {NODE_FILL}, weight: \"bold\")[{}]]])",
+            format_f64(frame.x + (frame.width - size) / 2.0),
+            format_f64(frame.y + (frame.height - size) / 2.0),
+            format_f64(size),
+            format_f64(size),
+            format_f64(size / 2.0),
+            format_f64(font * 0.4),
+            format_f64(font),
+            escape_typst(label)
+        );
+    }
+}
+
+/// The depth-1 ancestor of `items[index]` (itself when it is at depth 1).
+fn ancestor_at_level_one(items: &[SmartArtNode], mut index: usize) -> Option<usize> {
+    for _ in 0..items.len() {
+        let parent: usize = parent_of(items, index)?;
+        if parent_of(items, parent).is_none() {
+            return Some(index);
+        }
+        index = parent;
+    }
+    None
+}
@@ -0,0 +1,148 @@
+use super::*;
+
+fn node(text: &str, depth: usize, parent: Option<usize>) -> SmartArtNode {
+    SmartArtNode {
+        text: text.to_string(),
+        depth,
+        parent,
+    }
+}
+
+fn smartart_source(items: Vec<SmartArtNode>, layout: SmartArtLayout) -> String {
+    let doc = make_doc(vec![make_fixed_page(
+        720.0,
+        540.0,
+        vec![FixedElement {
+            x: 72.0,
+            y: 72.0,
+            width: 400.0,
+            height: 300.0,
+            kind: FixedElementKind::SmartArt(SmartArt { items, layout }),
+            href: None,
+        }],
+    )]);
+    generate_typst(&doc).unwrap().source
+}
+
+/// `dy` of the `#place` line that contains `text`.
+fn placed_dy(source: &str, text: &str) -> f64 {
+    let line: &str = source
+        .lines()
+        .find(|line| line.contains(text))
+        .unwrap_or_else(|| panic!("{text} not placed in:\n{source}"));
+    let dy: &str = line
+        .split("dy: ")
+        .nth(1)
+        .unwrap()
+        .split("pt")
+        .next()
+        .unwrap();
+    dy.parse().unwrap()
+}
+
+#[test]
+fn test_cycle_layout_places_nodes_around_ring_with_arrows() {
+    let source: String = smartart_source(
+        vec![
+            node("Plan", 0, None),
+            node("Do", 0, None),
+            node("Check", 0, None),
+            node("Act", 0, None),
+        ],
+        SmartArtLayout::Cycle,
+    );
+
+    assert!(!source.contains("SmartArt Diagram"), "{source}");
+    // One connector arc and one arrowhead per node.
+    assert_eq!(source.matches("path(stroke:").count(), 4, "{source}");
+    assert_eq!(source.matches("closed: true").count(), 4, "{source}");
+    // The first node sits at 12 o'clock, the third at 6 o'clock.
+    assert!(placed_dy(&source, "[Plan]") < placed_dy(&source, "[Do]"));
+    assert!(placed_dy(&source, "[Do]") < placed_dy(&source, "[Check]"));
+}
+
+#[test]
+fn test_hierarchy_layout_draws_tree_rows_and_elbow_connectors() {
+    let source: String = smartart_source(
+        vec![
+            node("CEO", 0, None),
+            node("VP Engineering", 1, Some(0)),
+            node("VP Sales", 1, Some(0)),
+            node("Dev Lead", 2, Some(1)),
+        ],
+        SmartArtLayout::Hierarchy,
+    );
+
+    // A drop, a bus, and one line per child for each parent: (2 + 2) + (2 + 1).
+    assert_eq!(source.matches("line(end:").count(), 7, "{source}");
+    let ceo: f64 = placed_dy(&source, "[CEO]");
+    let vp: f64 = placed_dy(&source, "[VP Engineering]");
+    assert!(ceo < vp);
+    assert!((vp - placed_dy(&source, "[VP Sales]")).abs() < 0.01);
+    assert!(vp < placed_dy(&source, "[Dev Lead]"));
+}
+
+#[test]
+fn test_hierarchy_layout_infers_missing_parents_from_depth() {
+    let source: String = smartart_source(
+        vec![node("Root", 0, None), node("Child", 1, None)],
+        SmartArtLayout::Hierarchy,
+    );
+
+    assert_eq!(source.matches("line(end:").count(), 3, "{source}");
+}
+
+#[test]
+fn test_pyramid_layout_stacks_one_band_per_top_level_node() {
+    let source: String = smartart_source(
+        vec![
+            node("Vision", 0, None),
+            node("Strategy", 0, None),
+            node("Goals", 1, Some(1)),
+            node("Tasks", 0, None),
+        ],
+        SmartArtLayout::Pyramid,
+    );
+
+    assert_eq!(source.matches("closed: true").count(), 3, "{source}");
+    assert!(placed_dy(&source, "[Vision]") < placed_dy(&source, "[Strategy]"));
+    // Child text is a detail line of its band.
+    let strategy_line: &str = source
+        .lines()
+        .find(|line| line.contains("[Strategy]"))
+        .unwrap();
+    assert!(strategy_line.contains("[Goals]"), "{strategy_line}");
+}
+
+#[test]
+fn test_matrix_layout_tiles_children_around_center_label() {
+    let source: String = smartart_source(
+        vec![
+            node("Priorities", 0, None),
+            node("Urgent", 1, Some(0)),
+            node("Important", 1, Some(0)),
+            node("Delegate", 1, Some(0)),
+            node("Drop", 1, Some(0)),
+        ],
+        SmartArtLayout::Matrix,
+    );
+
+    let urgent: f64 = placed_dy(&source, "[Urgent]");
+    assert!((urgent - placed_dy(&source, "[Important]")).abs() < 0.01);
+    assert!(urgent < placed_dy(&source, "[Delegate]"));
+    let center: &str = source
+        .lines()
+        .find(|line| line.contains("[Priorities]"))
+        .unwrap();
+    assert!(center.contains("fill: white"), "{center}");
+}
+
+#[test]
+fn test_list_layout_keeps_bordered_text_rendering() {
+    let source: String = smartart_source(
+        vec![node("Step 1", 0, None), node("Step 2", 0, None)],
+        SmartArtLayout::List,
+    );
+
+    assert!(source.contains("SmartArt Diagram"), "{source}");
+}
@@ -286,6 +286,8 @@ fn generate_table_cell(
         );
         generate_cell_content(out, &cell.content, ctx)?;
         out.push_str("])#box(width: 0pt, height: 1.3em)");
+    } else if cell.no_wrap || cell.shrink_to_fit || cell.text_rotation.is_some() {
+        generate_fitted_cell_content(out, cell, ctx)?;
     } else {
         generate_cell_content(out, &cell.content, ctx)?;
     }
@@ -293,6 +295,51 @@ fn generate_table_cell(
     Ok(())
 }
 
+/// Emit content that must not wrap at the column width: rotated text,
+/// shrink-to-fit text, and unwrapped text clipped at the cell edge. The
+/// content is measured on one line and fitted to the cell from there.
+fn generate_fitted_cell_content(
+    out: &mut String,
+    cell: &TableCell,
+    ctx: &mut GenCtx,
+) -> Result<(), ConvertError> {
+    if cell.text_rotation == Some(CellTextRotation::Stacked) {
+        // Every letter becomes its own block, stacking them upright.
+        out.push_str("#[#show regex(\"\\\\S\"): it => block(spacing: 0.2em, it)\n");
+        generate_cell_content(out, &cell.content, ctx)?;
+        out.push(']');
+        return Ok(());
+    }
+
+    out.push_str("#layout(size => { let body = [");
+    generate_cell_content(out, &cell.content, ctx)?;
+    out.push_str("]; let natural = calc.max(measure(body).width, 1pt); ");
+    match cell.text_rotation {
+        Some(CellTextRotation::Angle(angle)) => {
+            // Excel angles turn counterclockwise, Typst's clockwise.
+            let _ = write!(
+                out,
+                "rotate({}deg, reflow: true, box(width: natural, body))",
+                format_f64(-angle),
+            );
+        }
+        _ if cell.shrink_to_fit => {
+            out.push_str(
+                "if natural <= size.width { body } else { let fit = size.width / natural * 100%; \
+                 scale(x: fit, y: fit, origin: left + horizon, reflow: true, box(width: natural, body)) }",
+            );
+        }
+        _ => {
+            out.push_str(
+                "if natural <= size.width { body } else { \
+                 box(width: size.width, clip: true, box(width: natural, body)) }",
+            );
+        }
+    }
+    out.push_str(" })");
+    Ok(())
+}
+
 fn write_double_border_overlays(out: &mut String, border: &CellBorder, padding: Insets) {
     if let Some(side) = border
         .top
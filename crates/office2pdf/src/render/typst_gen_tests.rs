@@ -59,6 +59,8 @@ fn make_fixed_page(width: f64, height: f64, elements: Vec<FixedElement>) -> Page
         elements,
         background_color: None,
         background_gradient: None,
+        background_image: None,
+        source_slide: None,
     })
 }
 
@@ -89,6 +91,7 @@ fn make_text_box(x: f64, y: f64, w: f64, h: f64, text: &str) -> FixedElement {
             auto_fit: false,
             text_rotation_deg: None,
         }),
+        href: None,
     }
 }
 
@@ -115,7 +118,9 @@ fn make_shape_element(
             rotation_deg: None,
             opacity: None,
             shadow: None,
+            effects: ShapeEffects::default(),
         }),
+        href: None,
     }
 }
 
@@ -145,6 +150,7 @@ fn make_fixed_text_box(
             auto_fit: false,
             text_rotation_deg: None,
         }),
+        href: None,
     }
 }
 
@@ -166,6 +172,7 @@ fn make_fixed_image(x: f64, y: f64, w: f64, h: f64, format: ImageFormat) -> Fixe
             clip_shape: None,
             shadow: None,
         }),
+        href: None,
     }
 }
 
@@ -238,6 +245,12 @@ mod visual_tests;
 #[path = "typst_gen_diagram_visual_tests.rs"]
 mod diagram_visual_tests;
 
+#[path = "typst_gen_chart_plot_tests.rs"]
+mod chart_plot_tests;
+
+#[path = "typst_gen_smartart_layout_tests.rs"]
+mod smartart_layout_tests;
+
 #[path = "typst_gen_advanced_tests.rs"]
 mod advanced_tests;
 
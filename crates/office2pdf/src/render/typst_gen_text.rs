@@ -2,6 +2,7 @@ use std::fmt::Write;
 
 use unicode_normalization::UnicodeNormalization;
 
+use crate::ir::{page_link_target, sheet_link_target};
 use crate::render::font_subst;
 
 use super::*;
@@ -863,10 +864,10 @@ fn write_run_segment(out: &mut String, run: &Run, text: &str) {
     let style = &run.style;
 
     let needs_all_caps: bool = matches!(style.all_caps, Some(true));
-    let escaped: String = if needs_all_caps {
-        escape_typst(&text.to_uppercase())
+    let display_text: String = if needs_all_caps {
+        text.to_uppercase()
     } else {
-        escape_typst(text)
+        text.to_string()
     };
 
     let wrappers: Vec<String> = collect_formatting_wrappers(run);
@@ -875,13 +876,160 @@ fn write_run_segment(out: &mut String, run: &Run, text: &str) {
         out.push_str(wrapper);
     }
 
-    write_run_content(out, &escaped, style);
+    match style.shadow {
+        Some(ref shadow) => write_shadowed_run_content(out, &display_text, style, shadow),
+        None => write_run_content(out, &escape_typst(&display_text), style),
+    }
 
     for _ in &wrappers {
         out.push(']');
     }
 }
 
+/// Write a date or time field, styled like a run, as the time Typst's
+/// `datetime.today()` reports: the World's clock, which deterministic
+/// output pins.
+pub(super) fn generate_date_time(out: &mut String, field: &DateTimeField) {
+    let run = Run {
+        text: String::new(),
+        style: field.style.clone(),
+        href: None,
+        footnote: None,
+    };
+    let wrappers: Vec<String> = collect_formatting_wrappers(&run);
+    for wrapper in &wrappers {
+        out.push_str(wrapper);
+    }
+    // The semicolon ends the expression, so text after the field such as
+    // "." is not read as a method call on it.
+    let markup: String = format!(
+        "#datetime.today().display(\"{}\");",
+        escape_typst_string(&datetime_pattern(&field.format))
+    );
+    write_run_content(out, &markup, &field.style);
+    for _ in &wrappers {
+        out.push(']');
+    }
+}
+
+/// The Typst `display` pattern of an Excel date and time format, such as
+/// `[month padding:none]/[day padding:none]/[year]` for `m/d/yyyy`.
+pub(super) fn datetime_pattern(format: &str) -> String {
+    let chars: Vec<char> = format.chars().collect();
+    let upper: String = format.to_ascii_uppercase();
+    let twelve_hour: bool = upper.contains("AM/PM") || upper.contains("A/P");
+    let mut pattern: String = String::new();
+    // Excel reads `m` as minutes right after an hour or before seconds.
+    let mut after_hour: bool = false;
+    let mut i: usize = 0;
+    while i < chars.len() {
+        let rest: String = chars[i..].iter().collect::<String>().to_ascii_uppercase();
+        if let Some(period) = ["AM/PM", "A/P"].iter().find(|p| rest.starts_with(**p)) {
+            pattern.push_str("[period]");
+            i += period.len();
+            continue;
+        }
+        let code: char = chars[i].to_ascii_lowercase();
+        if matches!(code, 'y' | 'm' | 'd' | 'h' | 's') {
+            let count: usize = chars[i..]
+                .iter()
+                .take_while(|c| c.to_ascii_lowercase() == code)
+                .count();
+            i += count;
+            let component: &str = match (code, count) {
+                ('y', 1..=2) => "[year repr:last_two]",
+                ('y', _) => "[year]",
+                ('m', _) if after_hour || next_code(&chars[i..]) == Some('s') => {
+                    if count == 1 {
+                        "[minute padding:none]"
+                    } else {
+                        "[minute]"
+                    }
+                }
+                ('m', 1) => "[month padding:none]",
+                ('m', 2) => "[month]",
+                ('m', 3) => "[month repr:short]",
+                ('m', _) => "[month repr:long]",
+                ('d', 1) => "[day padding:none]",
+                ('d', 2) => "[day]",
+                ('d', 3) => "[weekday repr:short]",
+                ('d', _) => "[weekday]",
+                ('h', 1) if twelve_hour => "[hour repr:12 padding:none]",
+                ('h', _) if twelve_hour => "[hour repr:12]",
+                ('h', 1) => "[hour padding:none]",
+                ('h', _) => "[hour]",
+                ('s', 1) => "[second padding:none]",
+                _ => "[second]",
+            };
+            pattern.push_str(component);
+            after_hour = code == 'h';
+            continue;
+        }
+        match chars[i] {
+            // Quoted and escaped characters are literal.
+            '"' => {
+                let end: usize = chars[i + 1..]
+                    .iter()
+                    .position(|&c| c == '"')
+                    .map_or(chars.len(), |offset| i + 1 + offset);
+                push_pattern_literal(&mut pattern, &chars[i + 1..end]);
+                i = end + 1;
+            }
+            '\\' => {
+                push_pattern_literal(&mut pattern, chars.get(i + 1..i + 2).unwrap_or_default());
+                i += 2;
+            }
+            _ => {
+                push_pattern_literal(&mut pattern, &chars[i..=i]);
+                i += 1;
+            }
+        }
+    }
+    pattern
+}
+
+/// The next date or time code letter in `chars`.
+fn next_code(chars: &[char]) -> Option<char> {
+    chars
+        .iter()
+        .map(|c| c.to_ascii_lowercase())
+        .find(|c| matches!(c, 'y' | 'm' | 'd' | 'h' | 's'))
+}
+
+/// Append literal text to a `display` pattern, where `[` opens a component
+/// unless doubled.
+fn push_pattern_literal(pattern: &mut String, text: &[char]) {
+    for &c in text {
+        if c == '[' {
+            pattern.push_str("[[");
+        } else {
+            pattern.push(c);
+        }
+    }
+}
+
+/// Typst `link` destination for an IR href: page links jump to the top of
+/// that page, anything else is passed through as a URL.
+pub(super) fn link_destination(href: &str) -> String {
+    if let Some(sheet_name) = sheet_link_target(href) {
+        return format!("<{}>", sheet_label(sheet_name));
+    }
+    match page_link_target(href) {
+        Some(page) => format!("(page: {page}, x: 0pt, y: 0pt)"),
+        None => format!("\"{}\"", escape_typst_string(href)),
+    }
+}
+
+/// Typst label marking the first page of a worksheet. Sheet names may hold
+/// characters labels cannot, so the name is spelled out in hex.
+pub(super) fn sheet_label(sheet_name: &str) -> String {
+    let mut label: String = String::from("sheet-");
+    for byte in sheet_name.bytes() {
+        let _ = write!(label, "{byte:02x}");
+    }
+    label
+}
+
 /// Builds the ordered list of `#command[` openers that wrap a run's content.
 /// The order matches the original nesting: link > highlight > strike >
 /// underline > super/sub > smallcaps.
@@ -890,7 +1038,7 @@ fn collect_formatting_wrappers(run: &Run) -> Vec<String> {
     let mut wrappers: Vec<String> = Vec::new();
 
     if let Some(ref href) = run.href {
-        wrappers.push(format!("#link(\"{href}\")["));
+        wrappers.push(format!("#link({})[", link_destination(href)));
     }
     if let Some(ref highlight) = style.highlight {
         wrappers.push(format!("#highlight(fill: {})[", rgb(highlight)));
@@ -941,6 +1089,52 @@ fn write_run_content(out: &mut String, escaped: &str, style: &TextStyle) {
     }
 }
 
+/// Writes a run with a text shadow. Typst has no text shadow, so each word
+/// becomes a box holding a translucent copy of the word placed at the shadow
+/// offset, under the word itself. Boxing word by word (rather than the whole
+/// run) keeps the run able to wrap at spaces. The blur radius is not drawn.
+fn write_shadowed_run_content(out: &mut String, text: &str, style: &TextStyle, shadow: &Shadow) {
+    let direction: f64 = shadow.direction.to_radians();
+    let dx: f64 = shadow.distance * direction.cos();
+    let dy: f64 = shadow.distance * direction.sin();
+    let alpha: u8 = (shadow.opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    // The copy keeps the run's font and size but takes the shadow color and
+    // drops the outline, as PowerPoint shadows the glyph silhouette.
+    let shadow_style = TextStyle {
+        color: None,
+        outline: None,
+        shadow: None,
+        ..style.clone()
+    };
+    let mut shadow_params = String::new();
+    write_text_params(&mut shadow_params, &shadow_style);
+    let mut first: bool = shadow_params.is_empty();
+    write_param(
+        &mut shadow_params,
+        &mut first,
+        &format!("fill: {}", rgb_with_alpha(&shadow.color, alpha)),
+    );
+
+    for (index, word) in text.split(' ').enumerate() {
+        if index > 0 {
+            out.push(' ');
+        }
+        if word.is_empty() {
+            continue;
+        }
+        let escaped: String = escape_typst(word);
+        let _ = write!(
+            out,
+            "#box[#place(top + left, dx: {}pt, dy: {}pt)[#text({shadow_params})[{escaped}]]",
+            format_f64(dx),
+            format_f64(dy),
+        );
+        write_run_content(out, &escaped, style);
+        out.push(']');
+    }
+}
+
 pub(super) fn has_text_properties(style: &TextStyle) -> bool {
     matches!(style.bold, Some(true))
         || matches!(style.italic, Some(true))
@@ -948,6 +1142,12 @@ pub(super) fn has_text_properties(style: &TextStyle) -> bool {
         || style.color.is_some()
         || style.font_family.is_some()
         || style.letter_spacing.is_some()
+        || style.outline.is_some()
+        || style
+            .language
+            .as_deref()
+            .and_then(language_params)
+            .is_some()
 }
 
 fn inferred_font_weight(font_family: &str) -> Option<&'static str> {
@@ -1030,6 +1230,20 @@ pub(super) fn write_text_params(out: &mut String, style: &TextStyle) {
             &format!("tracking: {}pt", format_f64(spacing)),
         );
     }
+    if let Some(ref outline) = style.outline {
+        write_param(
+            out,
+            &mut first,
+            &format!(
+                "stroke: {}pt + {}",
+                format_f64(outline.width),
+                rgb(&outline.color)
+            ),
+        );
+    }
+    if let Some(params) = style.language.as_deref().and_then(language_params) {
+        write_param(out, &mut first, &params);
+    }
 }
 
 pub(super) fn write_param(out: &mut String, first: &mut bool, param: &str) {
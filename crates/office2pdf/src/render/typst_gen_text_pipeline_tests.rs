@@ -268,6 +268,8 @@ fn test_generate_fixed_page_empty_elements() {
         elements: vec![],
         background_color: None,
         background_gradient: None,
+        background_image: None,
+        source_slide: None,
     })]);
     let output = generate_typst(&doc).unwrap();
     assert!(!output.source.is_empty());
@@ -331,6 +333,10 @@ fn test_generate_shape_shadow_all_kinds() {
         opacity: 0.5,
         direction: 45.0,
         distance: 3.0,
+        scale_x: 1.0,
+        scale_y: 1.0,
+        skew_x: 0.0,
+        skew_y: 0.0,
     };
 
     let shape_kinds = vec![
@@ -370,11 +376,15 @@ fn test_generate_shape_shadow_all_kinds() {
                     stroke: None,
                     opacity: None,
                     shadow: Some(shadow.clone()),
+                    effects: ShapeEffects::default(),
                     rotation_deg: None,
                 }),
+                href: None,
             }],
             background_color: None,
             background_gradient: None,
+            background_image: None,
+            source_slide: None,
         })]);
         let output = generate_typst(&doc);
         assert!(
@@ -467,6 +477,8 @@ fn test_gradient_single_stop_fallback_to_solid() {
             }],
             angle: 0.0,
         }),
+        background_image: None,
+        source_slide: None,
     });
     let doc = make_doc(vec![page]);
     let output = generate_typst(&doc).unwrap();
@@ -504,6 +516,8 @@ fn test_gradient_two_stops_still_works() {
             ],
             angle: 90.0,
         }),
+        background_image: None,
+        source_slide: None,
     });
     let doc = make_doc(vec![page]);
     let output = generate_typst(&doc).unwrap();
@@ -299,6 +299,7 @@ fn test_consecutive_floating_shapes_share_one_anchor_line() {
         rotation_deg: None,
         opacity: None,
         shadow: None,
+        effects: ShapeEffects::default(),
     };
     let doc = make_doc(vec![make_flow_page(vec![
         Block::FloatingShape(FloatingShape {
@@ -413,6 +414,8 @@ fn test_gradient_background_codegen() {
             ],
             angle: 90.0,
         }),
+        background_image: None,
+        source_slide: None,
     });
     let doc = make_doc(vec![page]);
     let output = generate_typst(&doc).unwrap();
@@ -460,6 +463,8 @@ fn test_gradient_background_no_angle_codegen() {
             ],
             angle: 0.0,
         }),
+        background_image: None,
+        source_slide: None,
     });
     let doc = make_doc(vec![page]);
     let output = generate_typst(&doc).unwrap();
@@ -502,7 +507,9 @@ fn test_gradient_shape_fill_codegen() {
             rotation_deg: None,
             opacity: None,
             shadow: None,
+            effects: ShapeEffects::default(),
         }),
+        href: None,
     };
     let doc = make_doc(vec![make_fixed_page(720.0, 540.0, vec![elem])]);
     let output = generate_typst(&doc).unwrap();
@@ -547,8 +554,14 @@ fn test_shape_shadow_codegen() {
                 direction: 45.0,
                 color: Color::new(0, 0, 0),
                 opacity: 0.5,
+                scale_x: 1.0,
+                scale_y: 1.0,
+                skew_x: 0.0,
+                skew_y: 0.0,
             }),
+            effects: ShapeEffects::default(),
         }),
+        href: None,
     };
     let doc = make_doc(vec![make_fixed_page(720.0, 540.0, vec![elem])]);
     let output = generate_typst(&doc).unwrap();
@@ -582,7 +595,9 @@ fn test_shape_no_shadow_no_extra_output() {
             rotation_deg: None,
             opacity: None,
             shadow: None,
+            effects: ShapeEffects::default(),
         }),
+        href: None,
     };
     let doc = make_doc(vec![make_fixed_page(720.0, 540.0, vec![elem])]);
     let output = generate_typst(&doc).unwrap();
@@ -615,6 +630,8 @@ fn test_gradient_prefers_over_solid_fill() {
             ],
             angle: 180.0,
         }),
+        background_image: None,
+        source_slide: None,
     });
     let doc = make_doc(vec![page]);
     let output = generate_typst(&doc).unwrap();
@@ -656,6 +673,8 @@ fn test_gradient_unsorted_stops_rendered_in_sorted_order() {
             ],
             angle: 90.0,
         }),
+        background_image: None,
+        source_slide: None,
     });
     let doc = make_doc(vec![page]);
     let output = generate_typst(&doc).unwrap();
@@ -700,8 +719,14 @@ fn test_shape_shadow_blur_renders_layered_rings() {
                 direction: 45.0,
                 color: Color::new(0, 0, 0),
                 opacity: 0.5,
+                scale_x: 1.0,
+                scale_y: 1.0,
+                skew_x: 0.0,
+                skew_y: 0.0,
             }),
+            effects: ShapeEffects::default(),
         }),
+        href: None,
     };
     let doc = make_doc(vec![make_fixed_page(720.0, 540.0, vec![elem])]);
     let source = generate_typst(&doc).unwrap().source;
@@ -743,8 +768,14 @@ fn test_shape_shadow_without_blur_keeps_single_duplicate() {
                 direction: 45.0,
                 color: Color::new(0, 0, 0),
                 opacity: 0.5,
+                scale_x: 1.0,
+                scale_y: 1.0,
+                skew_x: 0.0,
+                skew_y: 0.0,
             }),
+            effects: ShapeEffects::default(),
         }),
+        href: None,
     };
     let doc = make_doc(vec![make_fixed_page(720.0, 540.0, vec![elem])]);
     let source = generate_typst(&doc).unwrap().source;
@@ -755,3 +786,255 @@ fn test_shape_shadow_without_blur_keeps_single_duplicate() {
         "zero blur keeps the single offset duplicate: {source}"
     );
 }
+
+fn make_effects_shape_doc(effects: ShapeEffects) -> Document {
+    let elem = FixedElement {
+        x: 10.0,
+        y: 20.0,
+        width: 200.0,
+        height: 150.0,
+        kind: FixedElementKind::Shape(Shape {
+            kind: ShapeKind::Rectangle,
+            fill: Some(Color::new(255, 0, 0)),
+            gradient_fill: None,
+            stroke: None,
+            rotation_deg: None,
+            opacity: None,
+            shadow: None,
+            effects,
+        }),
+        href: None,
+    };
+    make_doc(vec![make_fixed_page(720.0, 540.0, vec![elem])])
+}
+
+#[test]
+fn test_shape_glow_renders_outward_rings_before_shape() {
+    use crate::ir::Glow;
+
+    let doc = make_effects_shape_doc(ShapeEffects {
+        glow: Some(Glow {
+            radius: 8.0,
+            color: Color::new(255, 192, 0),
+            opacity: 0.5,
+        }),
+        ..ShapeEffects::default()
+    });
+    let source = generate_typst(&doc).unwrap().source;
+
+    // 4 rings, each with alpha 1-(1-0.5)^(1/4) = 0.159 -> 41.
+    assert_eq!(
+        source.matches("rgb(255, 192, 0, 41)").count(),
+        4,
+        "expected 4 glow rings in: {source}"
+    );
+    assert!(
+        source.contains("width: 216pt, height: 166pt"),
+        "outermost ring must reach the glow radius: {source}"
+    );
+    let glow_pos = source.find("rgb(255, 192, 0, 41)").unwrap();
+    let main_pos = source.find("rgb(255, 0, 0))").unwrap();
+    assert!(glow_pos < main_pos, "glow must sit behind the shape");
+}
+
+#[test]
+fn test_shape_soft_edge_replaces_fill_with_inset_layers() {
+    let doc = make_effects_shape_doc(ShapeEffects {
+        soft_edge_radius: Some(8.0),
+        ..ShapeEffects::default()
+    });
+    let source = generate_typst(&doc).unwrap().source;
+
+    assert_eq!(
+        source.matches("rgb(255, 0, 0, 255)").count(),
+        4,
+        "expected 4 soft-edge layers in: {source}"
+    );
+    assert!(
+        source.contains("width: 188pt, height: 138pt"),
+        "innermost layer must be inset toward the centre: {source}"
+    );
+    assert!(
+        !source.contains("fill: rgb(255, 0, 0))"),
+        "the crisp fill must be replaced by the layers: {source}"
+    );
+}
+
+#[test]
+fn test_shape_reflection_renders_faded_mirrored_bands() {
+    use crate::ir::Reflection;
+
+    let doc = make_effects_shape_doc(ShapeEffects {
+        reflection: Some(Reflection {
+            distance: 2.0,
+            start_opacity: 0.5,
+            end_opacity: 0.0,
+            end_position: 0.5,
+        }),
+        ..ShapeEffects::default()
+    });
+    let source = generate_typst(&doc).unwrap().source;
+
+    assert_eq!(
+        source.matches("#scale(y: -100%)").count(),
+        4,
+        "expected 4 mirrored bands in: {source}"
+    );
+    // First band starts right below the shape plus the reflection distance.
+    assert!(source.contains("dy: 152pt)[#box(width: 200pt, height: 18.75pt, clip: true)"));
+    // Band midpoint 1/8 of the fade: 0.5 - 0.5 * 0.125 = 0.4375 -> 112.
+    assert!(source.contains("fill: rgb(255, 0, 0, 112)"), "{source}");
+}
+
+#[test]
+fn test_shape_effects_off_renders_plain_shape() {
+    use crate::config::EffectFidelity;
+    use crate::ir::{Glow, Reflection, Rotation3d};
+
+    let doc = make_effects_shape_doc(ShapeEffects {
+        glow: Some(Glow {
+            radius: 8.0,
+            color: Color::new(255, 192, 0),
+            opacity: 0.5,
+        }),
+        reflection: Some(Reflection {
+            distance: 0.0,
+            start_opacity: 0.5,
+            end_opacity: 0.0,
+            end_position: 1.0,
+        }),
+        soft_edge_radius: Some(4.0),
+        rotation_3d: Some(Rotation3d {
+            latitude: 30.0,
+            longitude: 45.0,
+            revolution: 0.0,
+        }),
+        extrusion: None,
+    });
+    let options = ConvertOptions {
+        effects: EffectFidelity::Off,
+        ..Default::default()
+    };
+    let source = generate_typst_with_options(&doc, &options).unwrap().source;
+
+    assert!(!source.contains("rgb(255, 192, 0"), "{source}");
+    assert!(!source.contains("#scale("), "{source}");
+    assert!(source.contains("fill: rgb(255, 0, 0))"), "{source}");
+}
+
+#[test]
+fn test_shape_rotation_3d_projects_shape_with_scale_and_skew() {
+    use crate::ir::Rotation3d;
+
+    let doc = make_effects_shape_doc(ShapeEffects {
+        rotation_3d: Some(Rotation3d {
+            latitude: 0.0,
+            longitude: 180.0,
+            revolution: 0.0,
+        }),
+        ..ShapeEffects::default()
+    });
+    let source = generate_typst(&doc).unwrap().source;
+
+    // Turned half-way round: mirrored horizontally, no tilt.
+    assert!(
+        source.contains("#scale(x: -100%, y: 100%, origin: center + horizon)[#skew(ax: 0deg, ay: 0deg, origin: center + horizon)[#box(width: 200pt, height: 150pt)["),
+        "{source}"
+    );
+    let projection_pos = source.find("#scale(x: -100%").unwrap();
+    let main_pos = source.find("fill: rgb(255, 0, 0))").unwrap();
+    assert!(
+        projection_pos < main_pos,
+        "the shape must sit inside the projection"
+    );
+}
+
+#[test]
+fn test_shape_extrusion_draws_projected_side_faces_behind_shape() {
+    use crate::ir::{Extrusion, Rotation3d};
+
+    let doc = make_effects_shape_doc(ShapeEffects {
+        rotation_3d: Some(Rotation3d {
+            latitude: 30.0,
+            longitude: 45.0,
+            revolution: 0.0,
+        }),
+        extrusion: Some(Extrusion {
+            depth: 3.0,
+            color: Some(Color::new(0, 32, 96)),
+        }),
+        ..ShapeEffects::default()
+    });
+    let source = generate_typst(&doc).unwrap().source;
+
+    assert_eq!(
+        source.matches("fill: rgb(0, 32, 96, 255)").count(),
+        3,
+        "expected one side-face layer per point of depth: {source}"
+    );
+    // Three side faces plus the shape itself.
+    assert_eq!(source.matches("#skew(").count(), 4, "{source}");
+    let side_pos = source.find("rgb(0, 32, 96, 255)").unwrap();
+    let main_pos = source.find("fill: rgb(255, 0, 0))").unwrap();
+    assert!(side_pos < main_pos, "side faces must sit behind the shape");
+}
+
+#[test]
+fn test_shape_extrusion_without_rotation_is_invisible() {
+    use crate::ir::Extrusion;
+
+    let doc = make_effects_shape_doc(ShapeEffects {
+        extrusion: Some(Extrusion {
+            depth: 3.0,
+            color: None,
+        }),
+        ..ShapeEffects::default()
+    });
+    let source = generate_typst(&doc).unwrap().source;
+
+    assert!(!source.contains("#skew("), "{source}");
+    assert!(source.contains("fill: rgb(255, 0, 0))"), "{source}");
+}
+
+#[test]
+fn test_shape_perspective_shadow_pivots_on_bottom_edge() {
+    use crate::ir::Shadow;
+
+    let elem = FixedElement {
+        x: 10.0,
+        y: 20.0,
+        width: 200.0,
+        height: 150.0,
+        kind: FixedElementKind::Shape(Shape {
+            kind: ShapeKind::Rectangle,
+            fill: Some(Color::new(255, 0, 0)),
+            gradient_fill: None,
+            stroke: None,
+            rotation_deg: None,
+            opacity: None,
+            shadow: Some(Shadow {
+                blur_radius: 0.0,
+                distance: 0.0,
+                direction: 0.0,
+                color: Color::new(0, 0, 0),
+                opacity: 0.5,
+                scale_x: 1.0,
+                scale_y: 0.5,
+                skew_x: 45.0,
+                skew_y: 0.0,
+            }),
+            effects: ShapeEffects::default(),
+        }),
+        href: None,
+    };
+    let doc = make_doc(vec![make_fixed_page(720.0, 540.0, vec![elem])]);
+    let source = generate_typst(&doc).unwrap().source;
+
+    assert!(
+        source.contains("#scale(x: 100%, y: 50%, origin: bottom + center)[#skew(ax: 45deg, ay: 0deg, origin: bottom + center)"),
+        "{source}"
+    );
+    let shadow_pos = source.find("rgb(0, 0, 0, 128)").unwrap();
+    let main_pos = source.find("fill: rgb(255, 0, 0))").unwrap();
+    assert!(shadow_pos < main_pos);
+}